@@ -0,0 +1,11 @@
+-- Per-frame linkage metadata: frames sharing a correlation_id belong
+-- together (reassembly segments and their synthetic message, UDS
+-- request/response, transmit echoes). parent_rowid points at the first
+-- frame of the group within the same capture.
+ALTER TABLE frames ADD COLUMN correlation_id INTEGER;
+ALTER TABLE frames ADD COLUMN link_role TEXT;
+ALTER TABLE frames ADD COLUMN parent_rowid INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_frames_capture_corr
+    ON frames (capture_id, correlation_id)
+    WHERE correlation_id IS NOT NULL;
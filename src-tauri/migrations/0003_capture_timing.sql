@@ -0,0 +1,4 @@
+-- Timing provenance per capture: the session's timestamp source and the
+-- host clock sync status (JSON, see timebase::CaptureTiming) at the time
+-- recording began. NULL for imports and older captures.
+ALTER TABLE capture_metadata ADD COLUMN timing TEXT;
@@ -0,0 +1,5 @@
+-- Content integrity per capture: SHA-256 over the capture's frames/bytes in
+-- capture order, plus an optional Ed25519 signature (JSON, see
+-- capture_integrity::CaptureIntegrity). Computed when a session's capture is
+-- finalised or on request. NULL until then.
+ALTER TABLE capture_metadata ADD COLUMN integrity TEXT;
@@ -0,0 +1,11 @@
+-- Cached sparkline preview series per capture (JSON, see
+-- capture_preview::CapturePreview). `item_count` / `end_time_us` record the
+-- capture state the preview was computed from; a mismatch means it's stale.
+-- Rows are dropped with their capture.
+CREATE TABLE capture_previews (
+    capture_id TEXT PRIMARY KEY,
+    points INTEGER NOT NULL,
+    item_count INTEGER NOT NULL,
+    end_time_us INTEGER,
+    preview TEXT NOT NULL
+);
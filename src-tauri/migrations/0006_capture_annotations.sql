@@ -0,0 +1,15 @@
+-- Notes and markers pinned inside a capture ("door opened here"). Anchored
+-- by timestamp; `frame_rowid` additionally pins the exact frame when the
+-- annotation was made on one (NULL after the capture is copied, since
+-- copies renumber rows). Rows are dropped with their capture.
+CREATE TABLE capture_annotations (
+    id INTEGER PRIMARY KEY,
+    capture_id TEXT NOT NULL,
+    timestamp_us INTEGER NOT NULL,
+    frame_rowid INTEGER,
+    text TEXT NOT NULL,
+    color TEXT,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_annotations_capture_ts ON capture_annotations (capture_id, timestamp_us);
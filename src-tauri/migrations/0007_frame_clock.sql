@@ -0,0 +1,4 @@
+-- Clock each frame's timestamp came from: 'hardware' (the device's own
+-- timestamp, aligned to the epoch) or 'host' (receive time). NULL when the
+-- driver doesn't record it.
+ALTER TABLE frames ADD COLUMN clock TEXT;
@@ -0,0 +1,4 @@
+-- Main class of a CAN error frame ('bus_off', 'ack', 'stuff', ...). Set only
+-- on error frames, whose frame_id keeps the SocketCAN CAN_ERR_FLAG and error
+-- class bits; NULL for every other frame.
+ALTER TABLE frames ADD COLUMN error_class TEXT;
@@ -0,0 +1,4 @@
+-- CAN FD bit rate switch: 1 when the frame's data phase ran at the data
+-- bitrate, 0 when it didn't; NULL for classic frames and for drivers that
+-- don't report it.
+ALTER TABLE frames ADD COLUMN brs INTEGER;
@@ -0,0 +1,27 @@
+-- zstd-compressed cold chunks of frame payloads (capture_chunks.rs). A
+-- chunked frame keeps every column but `payload`, which is emptied; its
+-- bytes live in the chunk named by `chunk_id`. AUTOINCREMENT so a deleted
+-- chunk's ID is never reused (decompressed chunks are cached by ID).
+CREATE TABLE frame_chunks (
+    chunk_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    capture_id TEXT NOT NULL,
+    first_rowid INTEGER NOT NULL,
+    last_rowid INTEGER NOT NULL,
+    data BLOB NOT NULL
+);
+
+CREATE INDEX idx_frame_chunks_capture ON frame_chunks (capture_id);
+
+ALTER TABLE frames ADD COLUMN chunk_id INTEGER;
+
+-- The hot (inline) frames of each capture, for compaction
+CREATE INDEX idx_frames_hot ON frames (capture_id, rowid) WHERE chunk_id IS NULL;
+-- Frames per chunk, for pruning chunks emptied by trims
+CREATE INDEX idx_frames_chunk ON frames (chunk_id) WHERE chunk_id IS NOT NULL;
+
+-- Payload bytes each frame capture holds (compressed chunks plus inline
+-- payloads), kept up to date on append
+ALTER TABLE capture_metadata ADD COLUMN stored_bytes INTEGER NOT NULL DEFAULT 0;
+UPDATE capture_metadata SET stored_bytes = (
+    SELECT COALESCE(SUM(length(payload)), 0) FROM frames WHERE frames.capture_id = capture_metadata.capture_id
+) WHERE capture_kind = 'frames';
@@ -12,7 +12,8 @@
 
 use std::collections::{HashMap, HashSet};
 
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
 use wiretap_catalog::model::{Confidence, Signal};
 
@@ -42,6 +43,24 @@ pub fn resolve(
     }
 }
 
+/// Like [`resolve`], but also accepts a live `session_id`, which resolves to
+/// the capture the session is recording into.
+pub fn resolve_with_session(
+    capture_id: Option<String>,
+    session_id: Option<String>,
+    profile_id: Option<String>,
+) -> Result<QuerySource, String> {
+    let capture_id = match (capture_id, session_id) {
+        (Some(_), Some(_)) => return Err("Provide capture_id or session_id, not both".into()),
+        (None, Some(sid)) => Some(
+            crate::capture_store::get_session_frame_capture_id(&sid)
+                .ok_or_else(|| format!("Session '{}' has no frame capture", sid))?,
+        ),
+        (c, None) => c,
+    };
+    resolve(capture_id, profile_id)
+}
+
 // ── Result types ─────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize)]
@@ -168,8 +187,10 @@ fn capture_payloads(
     is_extended: Option<bool>,
     sample_limit: u32,
 ) -> Result<Vec<Vec<u8>>, String> {
-    let mut sql =
-        String::from("SELECT payload FROM frames WHERE capture_id = ?1 AND frame_id = ?2");
+    let mut sql = format!(
+        "SELECT {} FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
+        crate::capture_db::FRAME_PAYLOAD
+    );
     let mut bind: Vec<Box<dyn rusqlite::types::ToSql>> =
         vec![Box::new(capture_id.to_string()), Box::new(frame_id as i64)];
     let mut idx = 3;
@@ -438,3 +459,287 @@ pub async fn catalog_coverage(
         confidence,
     })
 }
+
+// ── Request → response latency ───────────────────────────────────────────────
+
+/// One request/response pairing to time, e.g. OBD `0x7DF` → `0x7E8`.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct LatencyPair {
+    /// Frame id (decimal) the request is sent on.
+    pub request_id: u32,
+    /// Frame id(s) that answer it — several for functional requests.
+    pub response_ids: Vec<u32>,
+    #[serde(default)]
+    pub is_extended: bool,
+    /// A request unanswered after this long counts as a timeout (default 1000 ms).
+    #[serde(default = "default_latency_timeout_ms")]
+    pub timeout_ms: f64,
+    /// Treat payloads as ISO-TP: only single/first frames count as requests and
+    /// responses, and responsePending (`7F xx 78`) doesn't close a request.
+    #[serde(default)]
+    pub isotp: bool,
+}
+
+fn default_latency_timeout_ms() -> f64 {
+    1000.0
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyOutlier {
+    pub request_us: i64,
+    pub response_id_hex: String,
+    pub latency_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyStats {
+    pub request_id_hex: String,
+    pub response_ids_hex: Vec<String>,
+    pub requests: usize,
+    pub responses: usize,
+    pub timeouts: usize,
+    pub min_ms: Option<f64>,
+    pub mean_ms: Option<f64>,
+    pub stddev_ms: Option<f64>,
+    pub p50_ms: Option<f64>,
+    pub p90_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    /// Samples above Q3 + 1.5·IQR, slowest first (capped).
+    pub outliers: Vec<LatencyOutlier>,
+}
+
+/// Outliers reported per pair.
+const MAX_LATENCY_OUTLIERS: usize = 50;
+
+/// True if an ISO-TP payload starts a message (single or first frame).
+fn isotp_starts_message(payload: &[u8]) -> bool {
+    matches!(payload.first().map(|b| b >> 4), Some(0x0) | Some(0x1))
+}
+
+/// True for a UDS negative response "requestCorrectlyReceived-ResponsePending".
+fn is_response_pending(payload: &[u8]) -> bool {
+    payload.first().map(|b| b >> 4) == Some(0x0) && payload.get(1) == Some(&0x7F) && payload.get(3) == Some(&0x78)
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Time request → first response for each pair over frames in timestamp order
+/// (`(timestamp_us, frame_id, is_extended, payload)`). Pure and headless.
+pub fn compute_latency(pairs: &[LatencyPair], frames: &[(i64, i64, bool, Vec<u8>)]) -> Vec<LatencyStats> {
+    pairs
+        .iter()
+        .map(|pair| {
+            let timeout_us = (pair.timeout_ms * 1000.0) as i64;
+            let mut requests = 0usize;
+            let mut timeouts = 0usize;
+            let mut pending: Option<i64> = None;
+            // (latency_ms, request_us, response_id)
+            let mut samples: Vec<(f64, i64, u32)> = Vec::new();
+
+            for (ts, id, ext, payload) in frames {
+                let id = *id as u32;
+                if *ext != pair.is_extended {
+                    continue;
+                }
+                if let Some(req_us) = pending {
+                    if ts - req_us > timeout_us {
+                        timeouts += 1;
+                        pending = None;
+                    }
+                }
+                if id == pair.request_id {
+                    if pair.isotp && !isotp_starts_message(payload) {
+                        continue;
+                    }
+                    if pending.is_some() {
+                        // Superseded before any response arrived
+                        timeouts += 1;
+                    }
+                    requests += 1;
+                    pending = Some(*ts);
+                } else if pair.response_ids.contains(&id) {
+                    let Some(req_us) = pending else { continue };
+                    if pair.isotp && (!isotp_starts_message(payload) || is_response_pending(payload)) {
+                        continue;
+                    }
+                    samples.push(((ts - req_us) as f64 / 1000.0, req_us, id));
+                    pending = None;
+                }
+            }
+            if pending.is_some() {
+                timeouts += 1;
+            }
+
+            let mut sorted: Vec<f64> = samples.iter().map(|s| s.0).collect();
+            sorted.sort_by(|a, b| a.total_cmp(b));
+            let n = sorted.len();
+            let mean = (n > 0).then(|| sorted.iter().sum::<f64>() / n as f64);
+            let stddev = mean.map(|m| (sorted.iter().map(|v| (v - m).powi(2)).sum::<f64>() / n as f64).sqrt());
+
+            let mut outliers = Vec::new();
+            if let (Some(q1), Some(q3)) = (percentile(&sorted, 25.0), percentile(&sorted, 75.0)) {
+                let fence = q3 + 1.5 * (q3 - q1);
+                samples.sort_by(|a, b| b.0.total_cmp(&a.0));
+                outliers = samples
+                    .iter()
+                    .take_while(|s| s.0 > fence)
+                    .take(MAX_LATENCY_OUTLIERS)
+                    .map(|s| LatencyOutlier {
+                        request_us: s.1,
+                        response_id_hex: hex_id(s.2, pair.is_extended),
+                        latency_ms: s.0,
+                    })
+                    .collect();
+            }
+
+            LatencyStats {
+                request_id_hex: hex_id(pair.request_id, pair.is_extended),
+                response_ids_hex: pair.response_ids.iter().map(|&r| hex_id(r, pair.is_extended)).collect(),
+                requests,
+                responses: n,
+                timeouts,
+                min_ms: sorted.first().copied(),
+                mean_ms: mean,
+                stddev_ms: stddev,
+                p50_ms: percentile(&sorted, 50.0),
+                p90_ms: percentile(&sorted, 90.0),
+                p95_ms: percentile(&sorted, 95.0),
+                p99_ms: percentile(&sorted, 99.0),
+                max_ms: sorted.last().copied(),
+                outliers,
+            }
+        })
+        .collect()
+}
+
+/// Fetch the request/response frames of all pairs from a capture, in order.
+fn capture_pair_frames(
+    capture_id: &str,
+    ids: &[u32],
+    start_us: Option<i64>,
+    end_us: Option<i64>,
+) -> Result<Vec<(i64, i64, bool, Vec<u8>)>, String> {
+    let id_list = ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",");
+    let mut sql = format!(
+        "SELECT timestamp_us, frame_id, is_extended, {payload} FROM frames \
+         WHERE capture_id = ?1 AND frame_id IN ({id_list})",
+        payload = crate::capture_db::FRAME_PAYLOAD
+    );
+    let mut bind: Vec<Box<dyn rusqlite::types::ToSql>> = vec![Box::new(capture_id.to_string())];
+    if let Some(s) = start_us {
+        sql.push_str(&format!(" AND timestamp_us >= ?{}", bind.len() + 1));
+        bind.push(Box::new(s));
+    }
+    if let Some(e) = end_us {
+        sql.push_str(&format!(" AND timestamp_us < ?{}", bind.len() + 1));
+        bind.push(Box::new(e));
+    }
+    sql.push_str(" ORDER BY rowid");
+    let refs: Vec<&dyn rusqlite::types::ToSql> = bind.iter().map(|b| b.as_ref()).collect();
+    crate::capture_db::query_raw_four_col(&sql, &refs)
+}
+
+pub async fn response_latency(
+    app: &AppHandle,
+    src: &QuerySource,
+    pairs: &[LatencyPair],
+    start_time: Option<String>,
+    end_time: Option<String>,
+) -> Result<Vec<LatencyStats>, String> {
+    if pairs.is_empty() {
+        return Err("Provide at least one request/response pair".into());
+    }
+    if let Some(p) = pairs.iter().find(|p| p.response_ids.is_empty()) {
+        return Err(format!("Pair {} has no response_ids", hex_id(p.request_id, p.is_extended)));
+    }
+    let mut ids: Vec<u32> = pairs
+        .iter()
+        .flat_map(|p| std::iter::once(p.request_id).chain(p.response_ids.iter().copied()))
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let frames = match src {
+        QuerySource::Postgres(pid) => {
+            crate::dbquery::db_fetch_id_sequence(app, pid, &ids, start_time, end_time).await?
+        }
+        QuerySource::Capture(cid) => capture_pair_frames(
+            cid,
+            &ids,
+            start_time.as_deref().and_then(iso_to_micros),
+            end_time.as_deref().and_then(iso_to_micros),
+        )?,
+    };
+    Ok(compute_latency(pairs, &frames))
+}
+
+/// Response-latency percentiles for request/response pairs over a capture, a
+/// live session's capture, or a PostgreSQL profile (exactly one source).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn analyze_response_latency(
+    app: AppHandle,
+    capture_id: Option<String>,
+    session_id: Option<String>,
+    profile_id: Option<String>,
+    pairs: Vec<LatencyPair>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+) -> Result<Vec<LatencyStats>, String> {
+    let src = resolve_with_session(capture_id, session_id, profile_id)?;
+    response_latency(&app, &src, &pairs, start_time, end_time).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(isotp: bool) -> LatencyPair {
+        LatencyPair {
+            request_id: 0x7DF,
+            response_ids: vec![0x7E8],
+            is_extended: false,
+            timeout_ms: 100.0,
+            isotp,
+        }
+    }
+
+    #[test]
+    fn latency_percentiles_and_timeouts() {
+        let frames = vec![
+            (0, 0x7DF, false, vec![0x02, 0x01, 0x0C]),
+            (10_000, 0x7E8, false, vec![0x04, 0x41, 0x0C, 0, 0]),
+            (1_000_000, 0x7DF, false, vec![0x02, 0x01, 0x0C]),
+            // No response within 100 ms
+            (2_000_000, 0x7DF, false, vec![0x02, 0x01, 0x0C]),
+            (2_020_000, 0x7E8, false, vec![0x04, 0x41, 0x0C, 0, 0]),
+        ];
+        let stats = &compute_latency(&[pair(false)], &frames)[0];
+        assert_eq!(stats.requests, 3);
+        assert_eq!(stats.responses, 2);
+        assert_eq!(stats.timeouts, 1);
+        assert_eq!(stats.min_ms, Some(10.0));
+        assert_eq!(stats.max_ms, Some(20.0));
+        assert_eq!(stats.p50_ms, Some(10.0));
+    }
+
+    #[test]
+    fn isotp_skips_response_pending() {
+        let frames = vec![
+            (0, 0x7DF, false, vec![0x02, 0x10, 0x03]),
+            (5_000, 0x7E8, false, vec![0x03, 0x7F, 0x10, 0x78]),
+            (40_000, 0x7E8, false, vec![0x06, 0x50, 0x03, 0, 0x32, 0x01, 0xF4]),
+        ];
+        let stats = &compute_latency(&[pair(true)], &frames)[0];
+        assert_eq!(stats.responses, 1);
+        assert_eq!(stats.min_ms, Some(40.0));
+    }
+}
@@ -0,0 +1,493 @@
+// ui/src-tauri/src/black_box.rs
+//
+// "Black box" mode: the last N seconds of every active session are kept in a
+// small on-disk ring, so the moments before a hard crash or power loss can be
+// recovered on the next launch (the capture database may have been cleared on
+// start, or never committed its last batch).
+//
+// Each session writes two segment files, <session>.0.wtbb and <session>.1.wtbb,
+// appending to one until it spans `window_secs`, then truncating and switching
+// to the other. The pair always holds at least the last `window_secs` and at
+// most twice that. Writes are flushed every batch and fsynced at most once per
+// SYNC_INTERVAL. A torn final record is ignored on recovery.
+//
+// Segment layout (little-endian):
+//   header: "WTBB", version u8, window_secs u32, session id (u16 len + UTF-8)
+//   record: timestamp_us u64, frame_id u32, flags u8 (ext/fd/tx), bus u8,
+//           protocol (u8 len + ASCII), payload (u16 len + bytes)
+//
+// Rings are deleted when their session ends cleanly and at app exit. Any left
+// at launch belong to a session that didn't end cleanly; `initialise` turns
+// each into a persistent capture and removes it. The config is persisted in
+// black-box.json in the app data dir.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::capture_store::{self, CaptureKind};
+use crate::io::FrameMessage;
+
+const MAGIC: &[u8; 4] = b"WTBB";
+const VERSION: u8 = 1;
+const EXTENSION: &str = "wtbb";
+
+const FLAG_EXTENDED: u8 = 1 << 0;
+const FLAG_FD: u8 = 1 << 1;
+const FLAG_TX: u8 = 1 << 2;
+
+/// Most often a segment is fsynced (flushes still happen every batch).
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+const MAX_WINDOW_SECS: u32 = 600;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlackBoxConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds of recent traffic to keep per session (1–600)
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u32,
+}
+
+fn default_window_secs() -> u32 {
+    30
+}
+
+impl Default for BlackBoxConfig {
+    fn default() -> Self {
+        Self { enabled: false, window_secs: default_window_secs() }
+    }
+}
+
+/// One session's ring: two segments, one being appended to.
+struct SessionRing {
+    paths: [PathBuf; 2],
+    current: usize,
+    writer: BufWriter<File>,
+    header: Vec<u8>,
+    window_us: u64,
+    /// Timestamp of the first record in the current segment
+    segment_start_us: Option<u64>,
+    last_sync: Instant,
+}
+
+#[derive(Default)]
+struct BlackBox {
+    dir: Option<PathBuf>,
+    config: BlackBoxConfig,
+    rings: HashMap<String, SessionRing>,
+}
+
+static STATE: Lazy<Mutex<BlackBox>> = Lazy::new(|| Mutex::new(BlackBox::default()));
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+fn encode_header(session_id: &str, window_secs: u32) -> Vec<u8> {
+    let id = &session_id.as_bytes()[..session_id.len().min(u16::MAX as usize)];
+    let mut out = Vec::with_capacity(11 + id.len());
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&window_secs.to_le_bytes());
+    out.extend_from_slice(&(id.len() as u16).to_le_bytes());
+    out.extend_from_slice(id);
+    out
+}
+
+/// Append one frame record. Also the record layout of capture_journal.
+pub(crate) fn encode_record(out: &mut Vec<u8>, frame: &FrameMessage) {
+    let mut flags = 0;
+    if frame.is_extended {
+        flags |= FLAG_EXTENDED;
+    }
+    if frame.is_fd {
+        flags |= FLAG_FD;
+    }
+    if frame.direction.as_deref() == Some("tx") {
+        flags |= FLAG_TX;
+    }
+    let protocol = &frame.protocol.as_bytes()[..frame.protocol.len().min(u8::MAX as usize)];
+    let payload = &frame.bytes[..frame.bytes.len().min(u16::MAX as usize)];
+    out.extend_from_slice(&frame.timestamp_us.to_le_bytes());
+    out.extend_from_slice(&frame.frame_id.to_le_bytes());
+    out.push(flags);
+    out.push(frame.bus);
+    out.push(protocol.len() as u8);
+    out.extend_from_slice(protocol);
+    out.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+    out.extend_from_slice(payload);
+}
+
+/// Bounds-checked little-endian reader; `None` once the data runs out.
+pub(crate) struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+    pub(crate) fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(n)?)?;
+        self.pos += n;
+        Some(bytes)
+    }
+    pub(crate) fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+    pub(crate) fn u16(&mut self) -> Option<u16> {
+        self.take(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+    pub(crate) fn u32(&mut self) -> Option<u32> {
+        self.take(4).map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+    }
+    pub(crate) fn u64(&mut self) -> Option<u64> {
+        self.take(8).map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+    }
+}
+
+pub(crate) fn decode_record(c: &mut Cursor) -> Option<FrameMessage> {
+    let timestamp_us = c.u64()?;
+    let frame_id = c.u32()?;
+    let flags = c.u8()?;
+    let bus = c.u8()?;
+    let protocol_len = c.u8()? as usize;
+    let protocol = String::from_utf8_lossy(c.take(protocol_len)?).into_owned();
+    let payload_len = c.u16()? as usize;
+    let bytes = c.take(payload_len)?.to_vec();
+    Some(FrameMessage {
+        protocol,
+        timestamp_us,
+        frame_id,
+        bus,
+        dlc: bytes.len().min(u8::MAX as usize) as u8,
+        bytes,
+        is_extended: flags & FLAG_EXTENDED != 0,
+        is_fd: flags & FLAG_FD != 0,
+        source_address: None,
+        incomplete: None,
+        direction: Some(if flags & FLAG_TX != 0 { "tx" } else { "rx" }.to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    })
+}
+
+/// A decoded segment: (session id, window_secs, frames). Records after a torn
+/// write are dropped; `None` when the header itself is unreadable.
+fn read_segment(data: &[u8]) -> Option<(String, u32, Vec<FrameMessage>)> {
+    let mut c = Cursor::new(data);
+    if c.take(4)? != MAGIC || c.u8()? != VERSION {
+        return None;
+    }
+    let window_secs = c.u32()?;
+    let id_len = c.u16()? as usize;
+    let session_id = String::from_utf8_lossy(c.take(id_len)?).into_owned();
+    let mut frames = Vec::new();
+    while let Some(frame) = decode_record(&mut c) {
+        frames.push(frame);
+    }
+    Some((session_id, window_secs, frames))
+}
+
+// ============================================================================
+// Ring
+// ============================================================================
+
+fn file_stem(session_id: &str) -> String {
+    session_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+fn start_segment(path: &Path, header: &[u8]) -> std::io::Result<BufWriter<File>> {
+    let file = OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+    let mut writer = BufWriter::new(file);
+    writer.write_all(header)?;
+    Ok(writer)
+}
+
+impl SessionRing {
+    fn open(dir: &Path, session_id: &str, window_secs: u32) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let stem = file_stem(session_id);
+        let paths = [0, 1].map(|i| dir.join(format!("{}.{}.{}", stem, i, EXTENSION)));
+        let _ = std::fs::remove_file(&paths[1]);
+        let header = encode_header(session_id, window_secs);
+        let writer = start_segment(&paths[0], &header)?;
+        Ok(SessionRing {
+            paths,
+            current: 0,
+            writer,
+            header,
+            window_us: window_secs as u64 * 1_000_000,
+            segment_start_us: None,
+            last_sync: Instant::now(),
+        })
+    }
+
+    fn write(&mut self, frames: &[FrameMessage]) -> std::io::Result<()> {
+        let mut buf = Vec::with_capacity(frames.len() * 32);
+        for frame in frames {
+            let start = *self.segment_start_us.get_or_insert(frame.timestamp_us);
+            if frame.timestamp_us.saturating_sub(start) >= self.window_us {
+                // The other segment is now entirely older than the window
+                self.writer.write_all(&buf)?;
+                buf.clear();
+                self.writer.flush()?;
+                self.current ^= 1;
+                self.writer = start_segment(&self.paths[self.current], &self.header)?;
+                self.segment_start_us = Some(frame.timestamp_us);
+            }
+            encode_record(&mut buf, frame);
+        }
+        self.writer.write_all(&buf)?;
+        self.writer.flush()?;
+        if self.last_sync.elapsed() >= SYNC_INTERVAL {
+            self.writer.get_ref().sync_data()?;
+            self.last_sync = Instant::now();
+        }
+        Ok(())
+    }
+
+    fn discard(self) {
+        drop(self.writer);
+        for path in &self.paths {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Frames in both segments of a ring, oldest first, trimmed to the window
+/// before the newest frame. Returns (session id, frames).
+fn recover_frames(paths: &[PathBuf]) -> Option<(String, Vec<FrameMessage>)> {
+    let mut session = None;
+    let mut window_us = 0;
+    let mut frames = Vec::new();
+    for path in paths {
+        let mut data = Vec::new();
+        if File::open(path).and_then(|mut f| f.read_to_end(&mut data)).is_err() {
+            continue;
+        }
+        if let Some((id, window_secs, mut segment)) = read_segment(&data) {
+            session.get_or_insert(id);
+            window_us = window_us.max(window_secs as u64 * 1_000_000);
+            frames.append(&mut segment);
+        }
+    }
+    let session = session?;
+    frames.sort_by_key(|f| f.timestamp_us);
+    let newest = frames.last().map(|f| f.timestamp_us).unwrap_or(0);
+    let cutoff = newest.saturating_sub(window_us);
+    frames.retain(|f| f.timestamp_us >= cutoff);
+    Some((session, frames))
+}
+
+// ============================================================================
+// Lifecycle
+// ============================================================================
+
+fn config_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    Ok(dir.join("black-box.json"))
+}
+
+/// Load the config and recover rings left by sessions that didn't end
+/// cleanly. Call once at startup, after the capture store is hydrated.
+pub fn initialise(app: &AppHandle) {
+    let Ok(config_file) = config_path(app) else { return };
+    let config: BlackBoxConfig = std::fs::read_to_string(&config_file)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    let dir = config_file.with_file_name("blackbox");
+    {
+        let mut state = STATE.lock().unwrap();
+        state.config = config;
+        state.dir = Some(dir.clone());
+    }
+
+    let Ok(entries) = std::fs::read_dir(&dir) else { return };
+    let mut rings: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+            continue;
+        }
+        // "<stem>.<segment>.wtbb" → group by stem
+        if let Some(stem) = path.file_stem().and_then(|s| Path::new(s).file_stem()).and_then(|s| s.to_str()) {
+            rings.entry(stem.to_string()).or_default().push(path.clone());
+        }
+    }
+    for paths in rings.into_values() {
+        if let Some((session_id, frames)) = recover_frames(&paths).filter(|(_, f)| !f.is_empty()) {
+            let name = format!("Black box – {}", session_id);
+            let id = capture_store::create_capture_inactive(CaptureKind::Frames, name);
+            let count = frames.len();
+            capture_store::append_frames_to_capture(&id, frames);
+            let _ = capture_store::set_capture_persistent(&id, true);
+            tlog!("[black_box] Recovered {} frames of session '{}' into capture '{}'", count, session_id, id);
+        }
+        for path in &paths {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Append a session's frames to its ring (no-op unless enabled).
+pub fn tap_frames(session_id: &str, frames: &[FrameMessage]) {
+    if frames.is_empty() {
+        return;
+    }
+    let mut state = STATE.lock().unwrap();
+    if !state.config.enabled {
+        return;
+    }
+    let Some(dir) = state.dir.clone() else { return };
+    let window_secs = state.config.window_secs;
+    if !state.rings.contains_key(session_id) {
+        match SessionRing::open(&dir, session_id, window_secs) {
+            Ok(ring) => {
+                state.rings.insert(session_id.to_string(), ring);
+            }
+            Err(e) => {
+                tlog!("[black_box] Failed to open ring for '{}': {}", session_id, e);
+                return;
+            }
+        }
+    }
+    if let Some(ring) = state.rings.get_mut(session_id) {
+        if let Err(e) = ring.write(frames) {
+            tlog!("[black_box] Failed to write ring for '{}': {}", session_id, e);
+        }
+    }
+}
+
+/// Remove a session's ring (the session ended cleanly).
+pub fn clear_session(session_id: &str) {
+    if let Some(ring) = STATE.lock().unwrap().rings.remove(session_id) {
+        ring.discard();
+    }
+}
+
+/// Remove every ring (app exit, or the mode was switched off).
+pub fn discard_all() {
+    let rings: Vec<SessionRing> = STATE.lock().unwrap().rings.drain().map(|(_, r)| r).collect();
+    for ring in rings {
+        ring.discard();
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+#[tauri::command]
+pub fn get_black_box_config() -> BlackBoxConfig {
+    STATE.lock().unwrap().config.clone()
+}
+
+/// Save the config. Open rings are closed so the new window applies to the
+/// next batch (and nothing is kept when the mode is switched off).
+#[tauri::command]
+pub fn set_black_box_config(app: AppHandle, config: BlackBoxConfig) -> Result<BlackBoxConfig, String> {
+    if config.window_secs == 0 || config.window_secs > MAX_WINDOW_SECS {
+        return Err(format!("Black box window must be 1–{} seconds", MAX_WINDOW_SECS));
+    }
+    let path = config_path(&app)?;
+    let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write black box config: {}", e))?;
+    STATE.lock().unwrap().config = config.clone();
+    discard_all();
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(ts: u64, id: u32, data: &[u8]) -> FrameMessage {
+        FrameMessage {
+            bus: 1,
+            is_extended: id > 0x7FF,
+            direction: Some("rx".to_string()),
+            ..test_frame(id, ts, data)
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("wiretap-blackbox-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn segments_round_trip_and_ignore_torn_tail() {
+        let mut data = encode_header("sess-1", 30);
+        encode_record(&mut data, &frame(1_000, 0x123, &[1, 2, 3]));
+        encode_record(&mut data, &frame(2_000, 0x18FEF100, &[]));
+        let mut torn = Vec::new();
+        encode_record(&mut torn, &frame(3_000, 0x456, &[9; 8]));
+        data.extend_from_slice(&torn[..torn.len() - 3]);
+
+        let (session, window, frames) = read_segment(&data).unwrap();
+        assert_eq!((session.as_str(), window), ("sess-1", 30));
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].bytes, vec![1, 2, 3]);
+        assert!(frames[1].is_extended && frames[1].bytes.is_empty());
+        assert!(read_segment(b"nope").is_none());
+    }
+
+    #[test]
+    fn ring_rotates_and_recovery_keeps_the_window() {
+        let dir = temp_dir("rotate");
+        let mut ring = SessionRing::open(&dir, "a/b", 1).unwrap();
+        // 3.5 s of traffic at 100 ms intervals with a 1 s window
+        let frames: Vec<_> = (0..36u64).map(|i| frame(i * 100_000, 0x100, &[i as u8])).collect();
+        for chunk in frames.chunks(5) {
+            ring.write(chunk).unwrap();
+        }
+        let paths = ring.paths.clone();
+        assert!(paths.iter().all(|p| p.exists()));
+        assert!(paths[0].file_name().unwrap().to_str().unwrap().starts_with("a_b."));
+
+        let (session, recovered) = recover_frames(&paths).unwrap();
+        ring.discard();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(session, "a/b");
+        // Newest is 3.5 s; everything from 2.5 s on is kept, in order
+        assert_eq!(recovered.first().unwrap().timestamp_us, 2_500_000);
+        assert_eq!(recovered.last().unwrap().timestamp_us, 3_500_000);
+        assert_eq!(recovered.len(), 11);
+    }
+
+    #[test]
+    fn discard_removes_both_segments() {
+        let dir = temp_dir("discard");
+        let mut ring = SessionRing::open(&dir, "s", 1).unwrap();
+        ring.write(&[frame(0, 1, &[1]), frame(1_500_000, 1, &[2])]).unwrap();
+        let paths = ring.paths.clone();
+        assert!(paths.iter().all(|p| p.exists()));
+        ring.discard();
+        assert!(paths.iter().all(|p| !p.exists()));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,787 @@
+// ui/src-tauri/src/canopen.rs
+//
+// CANopen (CiA 301) layer: frame decoding, an SDO client and EDS import.
+//
+// - decode() classifies an 11-bit frame by its predefined-connection-set
+//   COB-ID (NMT, SYNC, EMCY, TIME, PDO 1-4, SDO, heartbeat / boot-up) and
+//   decodes its contents.
+// - The SDO client reads and writes object dictionary entries through a
+//   transmit-capable session: requests go through io::session_transmit and
+//   the node's replies reach the waiting transfer through tap_frames, fed by
+//   capture_store::append_frames_to_session. Expedited and segmented
+//   transfers are supported (block transfer is not).
+// - eds_to_catalog_toml() turns an EDS/DCF's default PDO mappings into
+//   catalogue frames (plus heartbeat and EMCY), like catalog.import_dbc.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::io::{self, CanTransmitFrame, FrameMessage, TransmitPayload};
+
+const COB_SDO_TX: u32 = 0x580; // server → client
+const COB_SDO_RX: u32 = 0x600; // client → server
+const COB_HEARTBEAT: u32 = 0x700;
+const COB_EMCY: u32 = 0x080;
+const DEFAULT_SDO_TIMEOUT_MS: u64 = 1000;
+
+// ============================================================================
+// Frame decoding
+// ============================================================================
+
+/// A decoded CANopen frame.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CanopenMessage {
+    Nmt { command: &'static str, target_node: u8 },
+    Sync { counter: Option<u8> },
+    Time { bytes: Vec<u8> },
+    Emcy {
+        node_id: u8,
+        error_code: u16,
+        /// Error class from the code's high byte
+        error_class: &'static str,
+        error_register: u8,
+        manufacturer: Vec<u8>,
+    },
+    Pdo { node_id: u8, number: u8, transmit: bool, bytes: Vec<u8> },
+    Sdo {
+        node_id: u8,
+        /// true = client request (0x600), false = server response (0x580)
+        request: bool,
+        command: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        index: Option<u16>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        subindex: Option<u8>,
+        bytes: Vec<u8>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        abort_code: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        abort_reason: Option<&'static str>,
+    },
+    Heartbeat { node_id: u8, state: &'static str },
+}
+
+fn nmt_command(cs: u8) -> &'static str {
+    match cs {
+        0x01 => "start",
+        0x02 => "stop",
+        0x80 => "enter_pre_operational",
+        0x81 => "reset_node",
+        0x82 => "reset_communication",
+        _ => "unknown",
+    }
+}
+
+fn nmt_state(state: u8) -> &'static str {
+    match state & 0x7F {
+        0x00 => "boot_up",
+        0x04 => "stopped",
+        0x05 => "operational",
+        0x7F => "pre_operational",
+        _ => "unknown",
+    }
+}
+
+fn emcy_class(code: u16) -> &'static str {
+    match code >> 8 {
+        0x00 => "no_error",
+        0x10 => "generic",
+        0x20..=0x2F => "current",
+        0x30..=0x3F => "voltage",
+        0x40..=0x4F => "temperature",
+        0x50 => "device_hardware",
+        0x60..=0x6F => "device_software",
+        0x70 => "additional_modules",
+        0x80..=0x8F => "monitoring",
+        0x90 => "external",
+        0xF0 => "additional_functions",
+        0xFF => "device_specific",
+        _ => "unknown",
+    }
+}
+
+/// Description of an SDO abort code (CiA 301 table 22).
+pub fn sdo_abort_reason(code: u32) -> &'static str {
+    match code {
+        0x0503_0000 => "Toggle bit not alternated",
+        0x0504_0000 => "SDO protocol timed out",
+        0x0504_0001 => "Command specifier not valid or unknown",
+        0x0504_0005 => "Out of memory",
+        0x0601_0000 => "Unsupported access to an object",
+        0x0601_0001 => "Attempt to read a write-only object",
+        0x0601_0002 => "Attempt to write a read-only object",
+        0x0602_0000 => "Object does not exist in the object dictionary",
+        0x0604_0041 => "Object cannot be mapped to the PDO",
+        0x0604_0042 => "Mapped objects would exceed the PDO length",
+        0x0604_0043 => "General parameter incompatibility",
+        0x0606_0000 => "Access failed due to a hardware error",
+        0x0607_0010 => "Data type does not match, length does not match",
+        0x0607_0012 => "Data type does not match, length too high",
+        0x0607_0013 => "Data type does not match, length too low",
+        0x0609_0011 => "Sub-index does not exist",
+        0x0609_0030 => "Invalid value for parameter",
+        0x0609_0031 => "Value of parameter too high",
+        0x0609_0032 => "Value of parameter too low",
+        0x0800_0000 => "General error",
+        0x0800_0020 => "Data cannot be transferred or stored",
+        0x0800_0021 => "Data cannot be transferred or stored (local control)",
+        0x0800_0022 => "Data cannot be transferred or stored (device state)",
+        0x0800_0024 => "No data available",
+        _ => "Unknown abort code",
+    }
+}
+
+fn sdo_command(ccs: u8, request: bool) -> &'static str {
+    match (ccs >> 5, request) {
+        (4, _) => "abort",
+        (1, true) => "initiate_download",
+        (3, false) => "initiate_download_response",
+        (0, true) => "download_segment",
+        (1, false) => "download_segment_response",
+        (2, true) => "initiate_upload",
+        (2, false) => "initiate_upload_response",
+        (3, true) => "upload_segment",
+        (0, false) => "upload_segment_response",
+        (5 | 6, _) => "block_transfer",
+        _ => "unknown",
+    }
+}
+
+/// Decode an 11-bit frame under the predefined connection set. Returns None
+/// for extended IDs and COB-IDs CANopen does not assign.
+pub fn decode(frame_id: u32, is_extended: bool, data: &[u8]) -> Option<CanopenMessage> {
+    if is_extended || frame_id > 0x7FF {
+        return None;
+    }
+    let function = frame_id & 0x780;
+    let node_id = (frame_id & 0x7F) as u8;
+    let u16_at = |i: usize| data.get(i..i + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    match (function, node_id) {
+        (0x000, 0) => Some(CanopenMessage::Nmt {
+            command: nmt_command(*data.first()?),
+            target_node: *data.get(1)?,
+        }),
+        (0x080, 0) => Some(CanopenMessage::Sync { counter: data.first().copied() }),
+        (0x080, _) => Some(CanopenMessage::Emcy {
+            node_id,
+            error_code: u16_at(0)?,
+            error_class: emcy_class(u16_at(0)?),
+            error_register: *data.get(2)?,
+            manufacturer: data.get(3..).unwrap_or_default().to_vec(),
+        }),
+        (0x100, 0) => Some(CanopenMessage::Time { bytes: data.to_vec() }),
+        (0x180 | 0x280 | 0x380 | 0x480, 1..=127) => Some(CanopenMessage::Pdo {
+            node_id,
+            number: ((function - 0x080) >> 8) as u8,
+            transmit: true,
+            bytes: data.to_vec(),
+        }),
+        (0x200 | 0x300 | 0x400 | 0x500, 1..=127) => Some(CanopenMessage::Pdo {
+            node_id,
+            number: ((function - 0x100) >> 8) as u8,
+            transmit: false,
+            bytes: data.to_vec(),
+        }),
+        (0x580 | 0x600, 1..=127) => {
+            let request = function == 0x600;
+            let cs = *data.first()?;
+            let command = sdo_command(cs, request);
+            // Initiate and abort frames carry the multiplexer
+            let muxed = matches!(cs >> 5, 4) || (request && matches!(cs >> 5, 1 | 2)) || (!request && matches!(cs >> 5, 2 | 3));
+            let abort_code = (cs >> 5 == 4)
+                .then(|| data.get(4..8).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])))
+                .flatten();
+            Some(CanopenMessage::Sdo {
+                node_id,
+                request,
+                command,
+                index: muxed.then(|| u16_at(1)).flatten(),
+                subindex: muxed.then(|| data.get(3).copied()).flatten(),
+                bytes: data.to_vec(),
+                abort_code,
+                abort_reason: abort_code.map(sdo_abort_reason),
+            })
+        }
+        (0x700, 1..=127) => Some(CanopenMessage::Heartbeat { node_id, state: nmt_state(*data.first()?) }),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// SDO client
+// ============================================================================
+
+/// Where an SDO transfer goes.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SdoTarget {
+    pub node_id: u8,
+    pub index: u16,
+    #[serde(default)]
+    pub subindex: u8,
+    #[serde(default)]
+    pub bus: u8,
+    /// Per-response timeout (default 1000 ms)
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SdoReadResult {
+    pub bytes: Vec<u8>,
+    /// Little-endian unsigned value for entries up to 8 bytes
+    pub value: Option<u64>,
+    /// The bytes as text, when they are printable (VISIBLE_STRING entries)
+    pub text: Option<String>,
+    pub segmented: bool,
+}
+
+/// Frame-tap senders for running transfers: session_id → (bus, node, sender).
+#[allow(clippy::type_complexity)]
+static SDO_TAPS: Lazy<StdMutex<HashMap<String, Vec<(u8, u8, mpsc::UnboundedSender<[u8; 8]>)>>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// Called from capture_store::append_frames_to_session to route SDO
+/// responses to a transfer waiting on that node.
+pub fn tap_frames(session_id: &str, frames: &[FrameMessage]) {
+    let Ok(taps) = SDO_TAPS.lock() else {
+        return;
+    };
+    let Some(waiting) = taps.get(session_id) else {
+        return;
+    };
+    for frame in frames.iter().filter(|f| {
+        !f.is_extended && f.direction.as_deref() != Some("tx") && f.frame_id & 0x780 == COB_SDO_TX
+    }) {
+        let node = (frame.frame_id & 0x7F) as u8;
+        let Some((_, _, sender)) = waiting.iter().find(|(bus, n, _)| *bus == frame.bus && *n == node) else {
+            continue;
+        };
+        // SDO frames are always 8 bytes; pad short ones from sloppy devices
+        let mut data = [0u8; 8];
+        let len = frame.bytes.len().min(8);
+        data[..len].copy_from_slice(&frame.bytes[..len]);
+        let _ = sender.send(data);
+    }
+}
+
+/// Drop a session's pending transfers (they fail with "session closed").
+/// Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut taps) = SDO_TAPS.lock() {
+        taps.remove(session_id);
+    }
+}
+
+/// A registered transfer; unregisters itself when dropped.
+struct Transfer {
+    session_id: String,
+    target: SdoTarget,
+    rx: mpsc::UnboundedReceiver<[u8; 8]>,
+}
+
+impl Transfer {
+    fn start(session_id: &str, target: SdoTarget) -> Result<Self, String> {
+        let mut taps = SDO_TAPS.lock().map_err(|e| format!("SDO lock poisoned: {e}"))?;
+        let waiting = taps.entry(session_id.to_string()).or_default();
+        if waiting.iter().any(|(bus, n, _)| *bus == target.bus && *n == target.node_id) {
+            return Err(format!("An SDO transfer to node {} is already in progress", target.node_id));
+        }
+        let (tx, rx) = mpsc::unbounded_channel();
+        waiting.push((target.bus, target.node_id, tx));
+        Ok(Self { session_id: session_id.to_string(), target, rx })
+    }
+
+    async fn request(&mut self, data: [u8; 8]) -> Result<[u8; 8], String> {
+        // Discard replies that arrived after the previous step
+        while self.rx.try_recv().is_ok() {}
+        let frame = CanTransmitFrame {
+            frame_id: COB_SDO_RX + u32::from(self.target.node_id),
+            data: data.to_vec(),
+            bus: self.target.bus,
+            is_extended: false,
+            is_fd: false,
+            is_brs: false,
+            is_rtr: false,
+        };
+        let result = io::session_transmit(&self.session_id, &TransmitPayload::CanFrame(frame)).await?;
+        if !result.success {
+            return Err(result.error.unwrap_or_else(|| "Transmit failed".to_string()));
+        }
+        let timeout = Duration::from_millis(self.target.timeout_ms.unwrap_or(DEFAULT_SDO_TIMEOUT_MS));
+        let reply = match tokio::time::timeout(timeout, self.rx.recv()).await {
+            Ok(Some(reply)) => reply,
+            Ok(None) => return Err("Session closed during SDO transfer".to_string()),
+            Err(_) => {
+                // Tell the server we gave up so it doesn't wait for segments
+                let abort = abort_frame(&self.target, 0x0504_0000);
+                let frame = CanTransmitFrame {
+                    frame_id: COB_SDO_RX + u32::from(self.target.node_id),
+                    data: abort.to_vec(),
+                    bus: self.target.bus,
+                    is_extended: false,
+                    is_fd: false,
+                    is_brs: false,
+                    is_rtr: false,
+                };
+                let _ = io::session_transmit(&self.session_id, &TransmitPayload::CanFrame(frame)).await;
+                return Err(format!("No SDO response from node {} within {} ms", self.target.node_id, timeout.as_millis()));
+            }
+        };
+        if reply[0] >> 5 == 4 {
+            let code = u32::from_le_bytes([reply[4], reply[5], reply[6], reply[7]]);
+            return Err(format!("SDO abort 0x{:08X}: {}", code, sdo_abort_reason(code)));
+        }
+        Ok(reply)
+    }
+}
+
+impl Drop for Transfer {
+    fn drop(&mut self) {
+        if let Ok(mut taps) = SDO_TAPS.lock() {
+            if let Some(waiting) = taps.get_mut(&self.session_id) {
+                waiting.retain(|(bus, n, _)| !(*bus == self.target.bus && *n == self.target.node_id));
+                if waiting.is_empty() {
+                    taps.remove(&self.session_id);
+                }
+            }
+        }
+    }
+}
+
+fn with_mux(cs: u8, target: &SdoTarget, payload: [u8; 4]) -> [u8; 8] {
+    let [lo, hi] = target.index.to_le_bytes();
+    [cs, lo, hi, target.subindex, payload[0], payload[1], payload[2], payload[3]]
+}
+
+fn abort_frame(target: &SdoTarget, code: u32) -> [u8; 8] {
+    with_mux(0x80, target, code.to_le_bytes())
+}
+
+/// Data of an initiate-upload response: Ok(Some(bytes)) for expedited,
+/// Ok(None) when segments follow.
+fn parse_upload_response(target: &SdoTarget, reply: &[u8; 8]) -> Result<Option<Vec<u8>>, String> {
+    if reply[0] >> 5 != 2 {
+        return Err(format!("Unexpected SDO response 0x{:02X}", reply[0]));
+    }
+    if u16::from_le_bytes([reply[1], reply[2]]) != target.index || reply[3] != target.subindex {
+        return Err("SDO response is for a different object".to_string());
+    }
+    let expedited = reply[0] & 0x02 != 0;
+    let size_indicated = reply[0] & 0x01 != 0;
+    if !expedited {
+        return Ok(None);
+    }
+    let unused = if size_indicated { usize::from((reply[0] >> 2) & 0x03) } else { 0 };
+    Ok(Some(reply[4..8 - unused].to_vec()))
+}
+
+/// Segment data and the "last segment" flag of an upload-segment response.
+fn parse_upload_segment(reply: &[u8; 8], toggle: bool) -> Result<(&[u8], bool), String> {
+    if reply[0] >> 5 != 0 {
+        return Err(format!("Unexpected SDO response 0x{:02X}", reply[0]));
+    }
+    if (reply[0] & 0x10 != 0) != toggle {
+        return Err("SDO toggle bit not alternated".to_string());
+    }
+    let unused = usize::from((reply[0] >> 1) & 0x07);
+    Ok((&reply[1..8 - unused], reply[0] & 0x01 != 0))
+}
+
+/// Initiate-download request: expedited for up to 4 bytes, otherwise
+/// segmented with the size indicated.
+fn download_request(target: &SdoTarget, data: &[u8]) -> [u8; 8] {
+    if data.len() <= 4 {
+        let mut payload = [0u8; 4];
+        payload[..data.len()].copy_from_slice(data);
+        let unused = (4 - data.len()) as u8;
+        with_mux(0x23 | (unused << 2), target, payload)
+    } else {
+        with_mux(0x21, target, (data.len() as u32).to_le_bytes())
+    }
+}
+
+/// One download segment (up to 7 bytes).
+fn download_segment(chunk: &[u8], toggle: bool, last: bool) -> [u8; 8] {
+    let mut frame = [0u8; 8];
+    frame[0] = (u8::from(toggle) << 4) | (((7 - chunk.len()) as u8) << 1) | u8::from(last);
+    frame[1..1 + chunk.len()].copy_from_slice(chunk);
+    frame
+}
+
+async fn sdo_upload(session_id: &str, target: SdoTarget) -> Result<SdoReadResult, String> {
+    let mut transfer = Transfer::start(session_id, target.clone())?;
+    let reply = transfer.request(with_mux(0x40, &target, [0; 4])).await?;
+    let (bytes, segmented) = match parse_upload_response(&target, &reply)? {
+        Some(bytes) => (bytes, false),
+        None => {
+            let mut bytes = Vec::new();
+            let mut toggle = false;
+            loop {
+                let reply = transfer.request([0x60 | (u8::from(toggle) << 4), 0, 0, 0, 0, 0, 0, 0]).await?;
+                let (chunk, last) = parse_upload_segment(&reply, toggle)?;
+                bytes.extend_from_slice(chunk);
+                if last {
+                    break;
+                }
+                toggle = !toggle;
+            }
+            (bytes, true)
+        }
+    };
+    let value = (bytes.len() <= 8).then(|| {
+        bytes.iter().rev().fold(0u64, |acc, &b| (acc << 8) | u64::from(b))
+    });
+    let text = std::str::from_utf8(&bytes)
+        .ok()
+        .map(|s| s.trim_end_matches('\0'))
+        .filter(|s| !s.is_empty() && s.chars().all(|c| !c.is_control()))
+        .map(str::to_string);
+    Ok(SdoReadResult { bytes, value, text, segmented })
+}
+
+async fn sdo_download(session_id: &str, target: SdoTarget, data: &[u8]) -> Result<(), String> {
+    let mut transfer = Transfer::start(session_id, target.clone())?;
+    let reply = transfer.request(download_request(&target, data)).await?;
+    if reply[0] >> 5 != 3 {
+        return Err(format!("Unexpected SDO response 0x{:02X}", reply[0]));
+    }
+    if data.len() <= 4 {
+        return Ok(());
+    }
+    let mut toggle = false;
+    let chunks: Vec<&[u8]> = data.chunks(7).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let reply = transfer.request(download_segment(chunk, toggle, i + 1 == chunks.len())).await?;
+        if reply[0] >> 5 != 1 || (reply[0] & 0x10 != 0) != toggle {
+            return Err(format!("Unexpected SDO segment response 0x{:02X}", reply[0]));
+        }
+        toggle = !toggle;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// EDS import
+// ============================================================================
+
+/// Sections of an EDS/DCF (INI) file, keyed by lower-cased name.
+fn parse_ini(content: &str) -> HashMap<String, HashMap<String, String>> {
+    let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut current = None;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = name.trim().to_ascii_lowercase();
+            sections.entry(name.clone()).or_default();
+            current = Some(name);
+        } else if let (Some(section), Some((key, value))) = (&current, line.split_once('=')) {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+    sections
+}
+
+/// EDS integer: decimal, 0x hex, or an expression with $NODEID
+/// ("$NODEID+0x180").
+fn eds_int(value: &str, node_id: u8) -> Option<u64> {
+    value.split('+').try_fold(0u64, |acc, part| {
+        let part = part.trim();
+        let n = if part.eq_ignore_ascii_case("$nodeid") {
+            u64::from(node_id)
+        } else if let Some(hex) = part.strip_prefix("0x").or_else(|| part.strip_prefix("0X")) {
+            u64::from_str_radix(hex, 16).ok()?
+        } else {
+            part.parse().ok()?
+        };
+        Some(acc + n)
+    })
+}
+
+/// Signed CANopen data types (INTEGER8 … INTEGER64).
+fn is_signed_type(data_type: u64) -> bool {
+    matches!(data_type, 0x02 | 0x03 | 0x04 | 0x10 | 0x12 | 0x13 | 0x14 | 0x15)
+}
+
+fn toml_str(s: &str) -> String {
+    toml::Value::String(s.to_string()).to_string()
+}
+
+/// Convert an EDS/DCF into catalogue TOML: one frame per PDO with a default
+/// mapping, plus the node's heartbeat and EMCY frames. `node_id` defaults to
+/// the DCF's [DeviceComissioning] NodeID, else 1.
+pub fn eds_to_catalog_toml(content: &str, node_id: Option<u8>) -> Result<String, String> {
+    let sections = parse_ini(content);
+    if !sections.contains_key("1000") {
+        return Err("Not an EDS file: object 0x1000 (device type) is missing".to_string());
+    }
+    let node_id = node_id
+        .or_else(|| {
+            sections
+                .get("devicecomissioning")
+                .and_then(|s| s.get("nodeid"))
+                .and_then(|v| eds_int(v, 0))
+                .and_then(|n| u8::try_from(n).ok())
+        })
+        .unwrap_or(1);
+    if !(1..=127).contains(&node_id) {
+        return Err(format!("Node ID {} is outside 1-127", node_id));
+    }
+    let get = |section: &str, key: &str| sections.get(section).and_then(|s| s.get(key));
+    let value = |section: &str| {
+        get(section, "defaultvalue")
+            .or_else(|| get(section, "parametervalue"))
+            .and_then(|v| eds_int(v, node_id))
+    };
+    let product = get("deviceinfo", "productname").cloned().unwrap_or_else(|| format!("CANopen node {}", node_id));
+
+    let mut out = String::new();
+    let _ = writeln!(out, "[meta]\nname = {}\nversion = 4\n", toml_str(&product));
+    let _ = writeln!(out, "[meta.can]\ndefault_byte_order = \"little\"\n");
+
+    // (comm base, mapping base, default COB-ID base, label, transmitted by node)
+    for (comm, mapping, cob_base, label, transmit) in [
+        (0x1800u32, 0x1A00u32, 0x180u32, "TPDO", true),
+        (0x1400, 0x1600, 0x200, "RPDO", false),
+    ] {
+        for n in 0..4u32 {
+            let map_section = format!("{:x}", mapping + n);
+            let Some(count) = value(&format!("{}sub0", map_section)) else {
+                continue;
+            };
+            let cob_id = value(&format!("{:x}sub1", comm + n))
+                .map(|v| (v & 0x7FF) as u32)
+                .unwrap_or(cob_base + 0x100 * n + u32::from(node_id));
+            let mut signals = String::new();
+            let mut bit = 0u64;
+            for sub in 1..=count {
+                let Some(entry) = value(&format!("{}sub{:x}", map_section, sub)) else {
+                    continue;
+                };
+                let (index, subindex, length) = (entry >> 16, (entry >> 8) & 0xFF, entry & 0xFF);
+                // Dummy entries (data type indices) only reserve space
+                if index >= 0x1000 && length > 0 {
+                    let object = if subindex == 0 && !sections.contains_key(&format!("{:x}sub0", index)) {
+                        format!("{:x}", index)
+                    } else {
+                        format!("{:x}sub{:x}", index, subindex)
+                    };
+                    let name = get(&object, "parametername")
+                        .cloned()
+                        .unwrap_or_else(|| format!("0x{:04X}.{}", index, subindex));
+                    let signed = get(&object, "datatype").and_then(|v| eds_int(v, node_id)).is_some_and(is_signed_type);
+                    let _ = writeln!(
+                        signals,
+                        "[[frame.can.\"0x{:03X}\".signals]]\nname = {}\nstart_bit = {}\nbit_length = {}\nsigned = {}\nnotes = \"0x{:04X}sub{}\"\n",
+                        cob_id, toml_str(&name), bit, length, signed, index, subindex
+                    );
+                }
+                bit += length;
+            }
+            if bit == 0 || bit > 64 {
+                continue;
+            }
+            let _ = writeln!(out, "[frame.can.\"0x{:03X}\"]\nlength = {}", cob_id, bit.div_ceil(8));
+            if transmit {
+                let _ = writeln!(out, "transmitter = {}", toml_str(&product));
+            }
+            let _ = writeln!(out, "notes = \"{}{} (0x{:04X})\"\n", label, n + 1, mapping + n);
+            out.push_str(&signals);
+        }
+    }
+
+    let _ = writeln!(
+        out,
+        "[frame.can.\"0x{:03X}\"]\nlength = 1\ntransmitter = {}\nnotes = \"Heartbeat: 0 boot-up, 4 stopped, 5 operational, 127 pre-operational\"\n",
+        COB_HEARTBEAT + u32::from(node_id),
+        toml_str(&product)
+    );
+    let _ = writeln!(
+        out,
+        "[[frame.can.\"0x{:03X}\".signals]]\nname = \"NMT State\"\nstart_bit = 0\nbit_length = 7\n",
+        COB_HEARTBEAT + u32::from(node_id)
+    );
+    let emcy = value("1014").map_or(COB_EMCY + u32::from(node_id), |v| (v & 0x7FF) as u32);
+    let _ = writeln!(
+        out,
+        "[frame.can.\"0x{:03X}\"]\nlength = 8\ntransmitter = {}\nnotes = \"EMCY\"\n",
+        emcy,
+        toml_str(&product)
+    );
+    let _ = writeln!(
+        out,
+        "[[frame.can.\"0x{emcy:03X}\".signals]]\nname = \"Error Code\"\nstart_bit = 0\nbit_length = 16\nformat = \"hex\"\n\n\
+         [[frame.can.\"0x{emcy:03X}\".signals]]\nname = \"Error Register\"\nstart_bit = 16\nbit_length = 8\nformat = \"hex\""
+    );
+    Ok(out)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn decode_canopen_frame(frame_id: u32, is_extended: bool, bytes: Vec<u8>) -> Option<CanopenMessage> {
+    decode(frame_id, is_extended, &bytes)
+}
+
+async fn check_transmit(session_id: &str) -> Result<(), String> {
+    let caps = io::get_session_capabilities(session_id)
+        .await
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
+    if !caps.traits.tx_frames {
+        return Err("This session does not support CAN transmission".to_string());
+    }
+    Ok(())
+}
+
+/// Read an object dictionary entry (SDO upload).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn canopen_sdo_read(session_id: String, target: SdoTarget) -> Result<SdoReadResult, String> {
+    check_transmit(&session_id).await?;
+    let (node, index, subindex) = (target.node_id, target.index, target.subindex);
+    let result = sdo_upload(&session_id, target).await;
+    match &result {
+        Ok(r) => tlog!("[canopen:{}] SDO read {}:{:04X}sub{} = {} bytes", session_id, node, index, subindex, r.bytes.len()),
+        Err(e) => tlog!("[canopen:{}] SDO read {}:{:04X}sub{} failed: {}", session_id, node, index, subindex, e),
+    }
+    result
+}
+
+/// Write an object dictionary entry (SDO download).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn canopen_sdo_write(session_id: String, target: SdoTarget, bytes: Vec<u8>) -> Result<(), String> {
+    check_transmit(&session_id).await?;
+    if bytes.is_empty() {
+        return Err("Nothing to write".to_string());
+    }
+    let (node, index, subindex) = (target.node_id, target.index, target.subindex);
+    let result = sdo_download(&session_id, target, &bytes).await;
+    match &result {
+        Ok(()) => tlog!("[canopen:{}] SDO write {}:{:04X}sub{} ({} bytes)", session_id, node, index, subindex, bytes.len()),
+        Err(e) => tlog!("[canopen:{}] SDO write {}:{:04X}sub{} failed: {}", session_id, node, index, subindex, e),
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_predefined_connection_set() {
+        assert_eq!(
+            decode(0x000, false, &[0x01, 0x05]),
+            Some(CanopenMessage::Nmt { command: "start", target_node: 5 })
+        );
+        assert_eq!(decode(0x705, false, &[0x05]), Some(CanopenMessage::Heartbeat { node_id: 5, state: "operational" }));
+        assert_eq!(decode(0x705, false, &[0x00]), Some(CanopenMessage::Heartbeat { node_id: 5, state: "boot_up" }));
+        match decode(0x085, false, &[0x10, 0x42, 0x01, 0, 0, 0, 0, 0]) {
+            Some(CanopenMessage::Emcy { error_code, error_class, error_register, .. }) => {
+                assert_eq!((error_code, error_class, error_register), (0x4210, "temperature", 0x01));
+            }
+            other => panic!("expected EMCY, got {:?}", other),
+        }
+        assert!(matches!(
+            decode(0x285, false, &[1, 2]),
+            Some(CanopenMessage::Pdo { node_id: 5, number: 2, transmit: true, .. })
+        ));
+        assert!(matches!(
+            decode(0x505, false, &[1]),
+            Some(CanopenMessage::Pdo { node_id: 5, number: 4, transmit: false, .. })
+        ));
+        match decode(0x585, false, &[0x80, 0x00, 0x10, 0x00, 0x00, 0x00, 0x02, 0x06]) {
+            Some(CanopenMessage::Sdo { command, index, abort_code, abort_reason, .. }) => {
+                assert_eq!((command, index, abort_code), ("abort", Some(0x1000), Some(0x0602_0000)));
+                assert_eq!(abort_reason, Some("Object does not exist in the object dictionary"));
+            }
+            other => panic!("expected SDO abort, got {:?}", other),
+        }
+        assert_eq!(decode(0x18FF_0000, true, &[0; 8]), None);
+    }
+
+    #[test]
+    fn builds_and_parses_sdo_frames() {
+        let target = SdoTarget { node_id: 5, index: 0x1017, subindex: 0, bus: 0, timeout_ms: None };
+        // Expedited upload response with 2 bytes (n = 2, e = 1, s = 1)
+        let reply = [0x4B, 0x17, 0x10, 0x00, 0xE8, 0x03, 0x00, 0x00];
+        assert_eq!(parse_upload_response(&target, &reply), Ok(Some(vec![0xE8, 0x03])));
+        // Segmented upload announcing 11 bytes
+        let reply = [0x41, 0x17, 0x10, 0x00, 11, 0, 0, 0];
+        assert_eq!(parse_upload_response(&target, &reply), Ok(None));
+        let other = SdoTarget { index: 0x1018, ..target.clone() };
+        assert!(parse_upload_response(&other, &[0x4B, 0x17, 0x10, 0x00, 0, 0, 0, 0]).is_err());
+        // Last segment with 4 bytes (n = 3, c = 1), toggle set
+        let segment = [0x17, b'a', b'b', b'c', b'd', 0, 0, 0];
+        assert_eq!(parse_upload_segment(&segment, true), Ok((&b"abcd"[..], true)));
+        assert!(parse_upload_segment(&segment, false).is_err());
+
+        // Expedited download of 2 bytes: 0x2B, size in the command byte
+        assert_eq!(download_request(&target, &[0xE8, 0x03]), [0x2B, 0x17, 0x10, 0x00, 0xE8, 0x03, 0x00, 0x00]);
+        // Segmented download announces the size
+        assert_eq!(download_request(&target, &[0; 10]), [0x21, 0x17, 0x10, 0x00, 10, 0, 0, 0]);
+        assert_eq!(download_segment(&[1, 2, 3], true, true), [0x19, 1, 2, 3, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn imports_eds_pdo_mappings() {
+        let eds = "\
+[DeviceInfo]
+ProductName=Test Drive
+
+[1000]
+ParameterName=Device type
+DataType=0x0007
+DefaultValue=0x00020192
+
+[1800sub1]
+ParameterName=COB-ID used by TPDO
+DefaultValue=$NODEID+0x180
+
+[1A00sub0]
+DefaultValue=3
+
+[1A00sub1]
+DefaultValue=0x60410010
+
+[1A00sub2]
+DefaultValue=0x00050008
+
+[1A00sub3]
+DefaultValue=0x606C0020
+
+[6041]
+ParameterName=Statusword
+DataType=0x0006
+
+[606C]
+ParameterName=Velocity actual value
+DataType=0x0004
+";
+        let toml = eds_to_catalog_toml(eds, Some(3)).unwrap();
+        let table: toml::Table = toml.parse().unwrap();
+        let frames = table["frame"]["can"].as_table().unwrap();
+        let tpdo1 = &frames["0x183"];
+        assert_eq!(tpdo1["length"].as_integer(), Some(7));
+        let signals = tpdo1["signals"].as_array().unwrap();
+        assert_eq!(signals.len(), 2);
+        assert_eq!(signals[0]["name"].as_str(), Some("Statusword"));
+        // The 8-bit dummy entry pushes the velocity to bit 24
+        assert_eq!(signals[1]["start_bit"].as_integer(), Some(24));
+        assert_eq!(signals[1]["signed"].as_bool(), Some(true));
+        assert!(frames.contains_key("0x703") && frames.contains_key("0x083"));
+        assert_eq!(table["meta"]["name"].as_str(), Some("Test Drive"));
+
+        assert!(eds_to_catalog_toml("[DeviceInfo]\nProductName=x\n", None).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+// ui/src-tauri/src/capture_chunks.rs
+//
+// zstd-compressed cold chunks of frame payloads in the capture database.
+//
+// A capture's newest frames keep their payload inline in `frames.payload`
+// (the hot tail). Once more than HOT_TAIL_FRAMES + CHUNK_FRAMES frames are
+// hot, the oldest CHUNK_FRAMES payloads are packed into one `frame_chunks`
+// row and emptied in place, with `frames.chunk_id` pointing at the chunk.
+// Every other column stays in `frames`, so indexes and filters are unchanged.
+//
+// Reads resolve payloads through the `chunk_payload()` SQL function (see
+// `FRAME_PAYLOAD` in capture_db.rs), which decompresses a chunk once and
+// keeps the last few in a per-connection cache, so pagination, searches,
+// exports and copies all see the original bytes.
+
+use rusqlite::functions::{Context, FunctionFlags};
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Frames packed into one compressed chunk.
+pub const CHUNK_FRAMES: usize = 4096;
+/// Newest frames of a capture that always keep their payload inline.
+pub const HOT_TAIL_FRAMES: usize = 16384;
+/// zstd level: cheap enough to run on the append path.
+const ZSTD_LEVEL: i32 = 3;
+/// Decompressed chunks kept per connection.
+const CACHED_CHUNKS: usize = 8;
+/// Chunk entry header: rowid (i64 LE) and payload length (u16 LE).
+const ENTRY_HEADER: usize = 10;
+
+/// A decompressed chunk: payloads in rowid order.
+struct DecodedChunk {
+    rowids: Vec<i64>,
+    /// (start, end) of each payload in `bytes`
+    spans: Vec<(usize, usize)>,
+    bytes: Vec<u8>,
+}
+
+impl DecodedChunk {
+    fn payload(&self, rowid: i64) -> Option<&[u8]> {
+        let i = self.rowids.binary_search(&rowid).ok()?;
+        let (start, end) = self.spans[i];
+        Some(&self.bytes[start..end])
+    }
+}
+
+/// Pack (rowid, payload) pairs into a compressed chunk.
+pub fn encode_chunk(rows: &[(i64, Vec<u8>)]) -> Result<Vec<u8>, String> {
+    let mut raw = Vec::with_capacity(rows.iter().map(|(_, p)| ENTRY_HEADER + p.len()).sum());
+    for (rowid, payload) in rows {
+        let len = u16::try_from(payload.len()).map_err(|_| format!("Payload of frame {} is too long to chunk", rowid))?;
+        raw.extend_from_slice(&rowid.to_le_bytes());
+        raw.extend_from_slice(&len.to_le_bytes());
+        raw.extend_from_slice(payload);
+    }
+    zstd::bulk::compress(&raw, ZSTD_LEVEL).map_err(|e| format!("Failed to compress chunk: {}", e))
+}
+
+fn decode_chunk(data: &[u8]) -> Result<DecodedChunk, String> {
+    let bytes = zstd::stream::decode_all(data).map_err(|e| format!("Failed to decompress chunk: {}", e))?;
+    let (mut rowids, mut spans) = (Vec::new(), Vec::new());
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let header = bytes.get(pos..pos + ENTRY_HEADER).ok_or("Truncated chunk entry")?;
+        let rowid = i64::from_le_bytes(header[..8].try_into().unwrap());
+        let len = u16::from_le_bytes(header[8..].try_into().unwrap()) as usize;
+        let start = pos + ENTRY_HEADER;
+        if start + len > bytes.len() {
+            return Err("Truncated chunk payload".to_string());
+        }
+        rowids.push(rowid);
+        spans.push((start, start + len));
+        pos = start + len;
+    }
+    Ok(DecodedChunk { rowids, spans, bytes })
+}
+
+/// Most recently used decompressed chunks, keyed by `chunk_id`. Chunk IDs
+/// are AUTOINCREMENT, so a deleted chunk's ID is never reused.
+#[derive(Default)]
+struct ChunkCache {
+    entries: VecDeque<(i64, Arc<DecodedChunk>)>,
+}
+
+impl ChunkCache {
+    fn get(&mut self, chunk_id: i64) -> Option<Arc<DecodedChunk>> {
+        let i = self.entries.iter().position(|(id, _)| *id == chunk_id)?;
+        let entry = self.entries.remove(i)?;
+        let chunk = entry.1.clone();
+        self.entries.push_front(entry);
+        Some(chunk)
+    }
+
+    fn insert(&mut self, chunk_id: i64, chunk: Arc<DecodedChunk>) {
+        self.entries.push_front((chunk_id, chunk));
+        self.entries.truncate(CACHED_CHUNKS);
+    }
+}
+
+fn user_error(message: String) -> rusqlite::Error {
+    rusqlite::Error::UserFunctionError(message.into())
+}
+
+/// `chunk_payload(chunk_id, rowid, data)`: the payload of frame
+/// `rowid` in a chunk. `data` may be NULL when `chunk_cached(chunk_id)` said
+/// the chunk is already decompressed.
+fn chunk_payload(cache: &Mutex<ChunkCache>, ctx: &Context) -> rusqlite::Result<Vec<u8>> {
+    let chunk_id: i64 = ctx.get(0)?;
+    let rowid: i64 = ctx.get(1)?;
+    let mut cache = cache.lock().unwrap();
+    let chunk = match (cache.get(chunk_id), ctx.get_raw(2)) {
+        (Some(chunk), _) => chunk,
+        (None, ValueRef::Blob(data)) => {
+            let chunk = Arc::new(decode_chunk(data).map_err(user_error)?);
+            cache.insert(chunk_id, chunk.clone());
+            chunk
+        }
+        (None, _) => return Err(user_error(format!("Chunk {} is missing", chunk_id))),
+    };
+    chunk
+        .payload(rowid)
+        .map(<[u8]>::to_vec)
+        .ok_or_else(|| user_error(format!("Frame {} is not in chunk {}", rowid, chunk_id)))
+}
+
+/// Register `chunk_cached()` and `chunk_payload()` on a connection. Each
+/// connection gets its own cache.
+pub fn register_functions(conn: &Connection) -> Result<(), String> {
+    let cache = Arc::new(Mutex::new(ChunkCache::default()));
+    let probe = cache.clone();
+    conn.create_scalar_function("chunk_cached", 1, FunctionFlags::SQLITE_UTF8, move |ctx| {
+        let chunk_id: i64 = ctx.get(0)?;
+        Ok(probe.lock().unwrap().entries.iter().any(|(id, _)| *id == chunk_id))
+    })
+    .map_err(|e| format!("Failed to register chunk_cached: {}", e))?;
+    conn.create_scalar_function("chunk_payload", 3, FunctionFlags::SQLITE_UTF8, move |ctx| {
+        chunk_payload(&cache, ctx)
+    })
+    .map_err(|e| format!("Failed to register chunk_payload: {}", e))
+}
+
+/// True once a capture has enough hot frames to compress a chunk and still
+/// keep HOT_TAIL_FRAMES inline. The rowid span is an upper bound on the hot
+/// count (two MIN/MAX index seeks); only when it is large enough do we count,
+/// and then at most the threshold's worth of index entries.
+fn has_cold_chunk(conn: &Connection, capture_id: &str) -> Result<bool, String> {
+    let threshold = (HOT_TAIL_FRAMES + CHUNK_FRAMES) as i64;
+    let bound = |sql: &str| -> Result<Option<i64>, String> {
+        conn.query_row(sql, params![capture_id], |row| row.get(0))
+            .map_err(|e| format!("Failed to read hot frames: {}", e))
+    };
+    let first = bound("SELECT MIN(rowid) FROM frames WHERE capture_id = ?1 AND chunk_id IS NULL")?;
+    let last = bound("SELECT MAX(rowid) FROM frames WHERE capture_id = ?1 AND chunk_id IS NULL")?;
+    let (Some(first), Some(last)) = (first, last) else { return Ok(false) };
+    if last - first + 1 < threshold {
+        return Ok(false);
+    }
+    let hot: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM (SELECT 1 FROM frames WHERE capture_id = ?1 AND chunk_id IS NULL LIMIT ?2)",
+            params![capture_id, threshold],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to count hot frames: {}", e))?;
+    Ok(hot >= threshold)
+}
+
+/// Compress the capture's oldest CHUNK_FRAMES hot payloads into one chunk.
+/// Returns the change in stored payload bytes (negative when it shrank).
+fn compress_oldest(conn: &Connection, capture_id: &str) -> Result<i64, String> {
+    let rows: Vec<(i64, Vec<u8>)> = {
+        let mut stmt = conn
+            .prepare_cached(
+                "SELECT rowid, payload FROM frames WHERE capture_id = ?1 AND chunk_id IS NULL ORDER BY rowid LIMIT ?2",
+            )
+            .map_err(|e| format!("Failed to prepare: {}", e))?;
+        let rows = stmt
+            .query_map(params![capture_id, CHUNK_FRAMES as i64], |row| Ok((row.get(0)?, row.get(1)?)))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to read hot frames: {}", e))?;
+        rows
+    };
+    let (Some(first), Some(last)) = (rows.first().map(|r| r.0), rows.last().map(|r| r.0)) else {
+        return Ok(0);
+    };
+    let raw_len: usize = rows.iter().map(|(_, p)| p.len()).sum();
+    let data = encode_chunk(&rows)?;
+
+    conn.execute(
+        "INSERT INTO frame_chunks (capture_id, first_rowid, last_rowid, data) VALUES (?1, ?2, ?3, ?4)",
+        params![capture_id, first, last, &data],
+    )
+    .map_err(|e| format!("Failed to write chunk: {}", e))?;
+    let chunk_id = conn.last_insert_rowid();
+    conn.execute(
+        "UPDATE frames SET payload = X'', chunk_id = ?1
+         WHERE capture_id = ?2 AND chunk_id IS NULL AND rowid BETWEEN ?3 AND ?4",
+        params![chunk_id, capture_id, first, last],
+    )
+    .map_err(|e| format!("Failed to chunk frames: {}", e))?;
+
+    Ok(data.len() as i64 - raw_len as i64)
+}
+
+/// Compress every full chunk behind the capture's hot tail. Call inside the
+/// transaction that wrote the frames. Returns the change in stored bytes.
+pub fn compact_capture(conn: &Connection, capture_id: &str) -> Result<i64, String> {
+    let mut delta = 0;
+    while has_cold_chunk(conn, capture_id)? {
+        delta += compress_oldest(conn, capture_id)?;
+    }
+    Ok(delta)
+}
+
+/// Drop a capture's chunks that no frame points at any more (after trims).
+pub fn prune_chunks(conn: &Connection, capture_id: &str) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM frame_chunks WHERE capture_id = ?1
+           AND NOT EXISTS (SELECT 1 FROM frames WHERE frames.chunk_id = frame_chunks.chunk_id)",
+        params![capture_id],
+    )
+    .map(|_| ())
+    .map_err(|e| format!("Failed to prune chunks: {}", e))
+}
+
+/// Drop all of a capture's chunks (with its frames).
+pub fn delete_chunks(conn: &Connection, capture_id: &str) -> Result<(), String> {
+    conn.execute("DELETE FROM frame_chunks WHERE capture_id = ?1", params![capture_id])
+        .map(|_| ())
+        .map_err(|e| format!("Failed to delete chunks: {}", e))
+}
+
+/// Payload bytes a frame capture holds: its compressed chunks plus its hot
+/// payloads. Reads the chunk rows and the (bounded) hot tail only; used to
+/// reset the metadata counter after bulk changes, not per metadata call.
+pub fn stored_bytes(conn: &Connection, capture_id: &str) -> Result<u64, String> {
+    let chunked: i64 = conn
+        .query_row(
+            "SELECT COALESCE(SUM(length(data)), 0) FROM frame_chunks WHERE capture_id = ?1",
+            params![capture_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to measure chunks: {}", e))?;
+    let hot: i64 = conn
+        .query_row(
+            "SELECT COALESCE(SUM(length(payload)), 0) FROM frames WHERE capture_id = ?1 AND chunk_id IS NULL",
+            params![capture_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to measure hot frames: {}", e))?;
+    Ok((chunked + hot) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Same expression as capture_db's FRAME_PAYLOAD.
+    const PAYLOAD: &str = "CASE WHEN frames.chunk_id IS NULL THEN frames.payload ELSE chunk_payload(frames.chunk_id, frames.rowid, \
+         CASE WHEN chunk_cached(frames.chunk_id) THEN NULL ELSE (SELECT data FROM frame_chunks WHERE chunk_id = frames.chunk_id) END) END";
+
+    /// The chunk a frame's payload lives in, if it has been compressed.
+    fn chunk_of(conn: &Connection, rowid: i64) -> Option<i64> {
+        conn.query_row("SELECT chunk_id FROM frames WHERE rowid = ?1", params![rowid], |row| row.get(0))
+            .unwrap()
+    }
+
+    fn db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE frames (rowid INTEGER PRIMARY KEY, capture_id TEXT NOT NULL, payload BLOB NOT NULL, chunk_id INTEGER);
+             CREATE TABLE frame_chunks (chunk_id INTEGER PRIMARY KEY AUTOINCREMENT, capture_id TEXT NOT NULL,
+                 first_rowid INTEGER NOT NULL, last_rowid INTEGER NOT NULL, data BLOB NOT NULL);
+             CREATE INDEX idx_frames_hot ON frames (capture_id, rowid) WHERE chunk_id IS NULL;
+             CREATE INDEX idx_frames_chunk ON frames (chunk_id) WHERE chunk_id IS NOT NULL;",
+        )
+        .unwrap();
+        register_functions(&conn).unwrap();
+        conn
+    }
+
+    fn fd_payload(i: usize) -> Vec<u8> {
+        (0..64).map(|b| (i as u8).wrapping_add(b / 8)).collect()
+    }
+
+    fn fill(conn: &Connection, capture_id: &str, n: usize) {
+        let mut stmt = conn.prepare("INSERT INTO frames (capture_id, payload) VALUES (?1, ?2)").unwrap();
+        for i in 0..n {
+            stmt.execute(params![capture_id, fd_payload(i)]).unwrap();
+        }
+    }
+
+    fn payloads(conn: &Connection, capture_id: &str) -> Vec<Vec<u8>> {
+        let mut stmt = conn
+            .prepare(&format!("SELECT {PAYLOAD} FROM frames WHERE capture_id = ?1 ORDER BY rowid"))
+            .unwrap();
+        let rows = stmt.query_map(params![capture_id], |row| row.get(0)).unwrap();
+        rows.map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn cold_chunks_round_trip_through_sql() {
+        let conn = db();
+        let total = HOT_TAIL_FRAMES + 2 * CHUNK_FRAMES + 100;
+        fill(&conn, "fd", total);
+        let before = stored_bytes(&conn, "fd").unwrap();
+        assert_eq!(before, (total * 64) as u64);
+
+        let delta = compact_capture(&conn, "fd").unwrap();
+        // Two full chunks go cold; the hot tail (and the remainder) stays inline
+        assert_eq!(chunk_of(&conn, 1), Some(1));
+        assert_eq!(chunk_of(&conn, (2 * CHUNK_FRAMES) as i64), Some(2));
+        assert_eq!(chunk_of(&conn, (2 * CHUNK_FRAMES + 1) as i64), None);
+        assert!(delta < 0);
+        assert_eq!(stored_bytes(&conn, "fd").unwrap() as i64, before as i64 + delta);
+
+        let expected: Vec<Vec<u8>> = (0..total).map(fd_payload).collect();
+        assert_eq!(payloads(&conn, "fd"), expected);
+        // Payload filters in SQL see the decompressed bytes
+        let hits: i64 = conn
+            .query_row(
+                &format!("SELECT COUNT(*) FROM frames WHERE capture_id = 'fd' AND {PAYLOAD} = ?1"),
+                params![fd_payload(3)],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(hits as usize, (0..total).filter(|i| (*i as u8) == 3).count());
+    }
+
+    #[test]
+    fn short_captures_stay_inline() {
+        let conn = db();
+        fill(&conn, "short", HOT_TAIL_FRAMES + CHUNK_FRAMES - 1);
+        assert_eq!(compact_capture(&conn, "short").unwrap(), 0);
+        assert_eq!(chunk_of(&conn, 1), None);
+    }
+
+    #[test]
+    fn trimmed_chunks_are_pruned() {
+        let conn = db();
+        fill(&conn, "ring", HOT_TAIL_FRAMES + CHUNK_FRAMES);
+        compact_capture(&conn, "ring").unwrap();
+        conn.execute("DELETE FROM frames WHERE rowid <= ?1", params![CHUNK_FRAMES as i64 - 1]).unwrap();
+        prune_chunks(&conn, "ring").unwrap();
+        let chunks: i64 = conn.query_row("SELECT COUNT(*) FROM frame_chunks", [], |row| row.get(0)).unwrap();
+        assert_eq!(chunks, 1, "a chunk with a surviving frame is kept");
+
+        conn.execute("DELETE FROM frames WHERE rowid = ?1", params![CHUNK_FRAMES as i64]).unwrap();
+        prune_chunks(&conn, "ring").unwrap();
+        let chunks: i64 = conn.query_row("SELECT COUNT(*) FROM frame_chunks", [], |row| row.get(0)).unwrap();
+        assert_eq!(chunks, 0);
+        assert_eq!(payloads(&conn, "ring").len(), HOT_TAIL_FRAMES);
+    }
+}
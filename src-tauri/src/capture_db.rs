@@ -12,11 +12,35 @@
 
 use once_cell::sync::Lazy;
 use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Mutex;
 
-use crate::capture_store::{CaptureFrameInfo, CaptureMetadata, CaptureKind, TimestampedByte};
-use crate::io::FrameMessage;
+use crate::capture_chunks;
+use crate::capture_store::{CaptureAnnotation, CaptureFrameInfo, CaptureMetadata, CaptureKind, FramePattern, TimestampedByte};
+use crate::io::{CanErrorClass, FrameClock, FrameLink, FrameMessage, LinkRole, Protocol};
+use crate::row_model::{RowModelRow, RowModelSpec, RowSortKey};
+
+/// Bounds for the configurable database-wide page cache / mmap size (MB).
+const MIN_CACHE_MB: u32 = 16;
+const MAX_CACHE_MB: u32 = 4096;
+
+/// A frame's payload in SQL, for the frames table or alias `$t`: the inline
+/// blob for hot rows, else its slice of the row's compressed chunk
+/// (capture_chunks.rs). `chunk_cached` skips re-reading a chunk blob that is
+/// already decompressed. Every read of `payload` must go through this.
+macro_rules! payload_sql {
+    ($t:literal) => {
+        concat!(
+            "CASE WHEN ", $t, ".chunk_id IS NULL THEN ", $t, ".payload ELSE chunk_payload(", $t, ".chunk_id, ", $t, ".rowid, ",
+            "CASE WHEN chunk_cached(", $t, ".chunk_id) THEN NULL ELSE ",
+            "(SELECT c.data FROM frame_chunks c WHERE c.chunk_id = ", $t, ".chunk_id) END) END"
+        )
+    };
+}
+
+/// [`payload_sql!`] for the unaliased `frames` table.
+pub const FRAME_PAYLOAD: &str = payload_sql!("frames");
 
 /// Global database connection, protected by a Mutex.
 /// rusqlite::Connection is !Sync, so we use Mutex (not RwLock).
@@ -97,10 +121,10 @@ fn has_column(conn: &Connection, table: &str, col: &str) -> Result<bool, String>
 // ============================================================================
 
 enum MigrationStep {
-    /// Conditional logic SQL can't express. Avoid for new migrations —
-    /// plain SQL steps use `Sql(include_str!(...))`, added with the first
-    /// SQL-file migration.
+    /// Conditional logic SQL can't express. Avoid for new migrations.
     Rust(fn(&rusqlite::Transaction) -> Result<(), String>),
+    /// Plain SQL from `src-tauri/migrations/`, applied verbatim.
+    Sql(&'static str),
 }
 
 struct Migration {
@@ -111,11 +135,58 @@ struct Migration {
 
 /// All migrations, ascending and contiguous from version 1.
 /// `user_version` 0 = unstamped (any pre-versioning shape).
-const MIGRATIONS: &[Migration] = &[Migration {
-    version: 1,
-    name: "baseline_capture_schema",
-    step: MigrationStep::Rust(baseline_capture_schema),
-}];
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "baseline_capture_schema",
+        step: MigrationStep::Rust(baseline_capture_schema),
+    },
+    Migration {
+        version: 2,
+        name: "frame_linkage",
+        step: MigrationStep::Sql(include_str!("../migrations/0002_frame_linkage.sql")),
+    },
+    Migration {
+        version: 3,
+        name: "capture_timing",
+        step: MigrationStep::Sql(include_str!("../migrations/0003_capture_timing.sql")),
+    },
+    Migration {
+        version: 4,
+        name: "capture_integrity",
+        step: MigrationStep::Sql(include_str!("../migrations/0004_capture_integrity.sql")),
+    },
+    Migration {
+        version: 5,
+        name: "capture_previews",
+        step: MigrationStep::Sql(include_str!("../migrations/0005_capture_previews.sql")),
+    },
+    Migration {
+        version: 6,
+        name: "capture_annotations",
+        step: MigrationStep::Sql(include_str!("../migrations/0006_capture_annotations.sql")),
+    },
+    Migration {
+        version: 7,
+        name: "frame_clock",
+        step: MigrationStep::Sql(include_str!("../migrations/0007_frame_clock.sql")),
+    },
+    Migration {
+        version: 8,
+        name: "frame_error",
+        step: MigrationStep::Sql(include_str!("../migrations/0008_frame_error.sql")),
+    },
+    Migration {
+        version: 9,
+        name: "frame_brs",
+        step: MigrationStep::Sql(include_str!("../migrations/0009_frame_brs.sql")),
+    },
+    Migration {
+        version: 10,
+        name: "frame_chunks",
+        step: MigrationStep::Sql(include_str!("../migrations/0010_frame_chunks.sql")),
+    },
+];
 
 fn schema_version(conn: &Connection) -> Result<i64, String> {
     conn.query_row("PRAGMA user_version", [], |row| row.get(0))
@@ -145,6 +216,9 @@ fn run_migrations(conn: &mut Connection) -> Result<(), String> {
         match m.step {
             MigrationStep::Rust(f) => f(&tx)
                 .map_err(|e| format!("Migration {} ({}) failed: {}", m.version, m.name, e))?,
+            MigrationStep::Sql(sql) => tx
+                .execute_batch(sql)
+                .map_err(|e| format!("Migration {} ({}) failed: {}", m.version, m.name, e))?,
         }
 
         tx.execute(
@@ -251,7 +325,9 @@ fn baseline_capture_schema(tx: &rusqlite::Transaction) -> Result<(), String> {
 
 /// Initialise the capture database. Must be called once at app startup.
 /// When `clear_on_start` is true, leftover data from previous sessions is deleted.
-pub fn initialise(app_data_dir: &Path, clear_on_start: bool) -> Result<(), String> {
+/// `db_cache_mb` sizes SQLite's page cache and memory-mapped region for the
+/// database as a whole; it doesn't bound how much any capture can hold.
+pub fn initialise(app_data_dir: &Path, clear_on_start: bool, db_cache_mb: u32) -> Result<(), String> {
     std::fs::create_dir_all(app_data_dir)
         .map_err(|e| format!("Failed to create app data dir: {}", e))?;
 
@@ -260,6 +336,7 @@ pub fn initialise(app_data_dir: &Path, clear_on_start: bool) -> Result<(), Strin
         .map_err(|e| format!("Failed to open capture database: {}", e))?;
 
     run_migrations(&mut conn)?;
+    capture_chunks::register_functions(&conn)?;
 
     // Conditionally clear leftover data and reclaim disk space
     // Persistent (pinned) captures survive the clear.
@@ -287,11 +364,26 @@ pub fn initialise(app_data_dir: &Path, clear_on_start: bool) -> Result<(), Strin
             [],
         )
         .map_err(|e| format!("Failed to clear orphaned frames: {}", e))?;
+        conn.execute(
+            "DELETE FROM frame_chunks WHERE capture_id NOT IN (SELECT capture_id FROM capture_metadata)",
+            [],
+        )
+        .map_err(|e| format!("Failed to clear orphaned chunks: {}", e))?;
         conn.execute(
             "DELETE FROM bytes WHERE capture_id NOT IN (SELECT capture_id FROM capture_metadata)",
             [],
         )
         .map_err(|e| format!("Failed to clear orphaned bytes: {}", e))?;
+        conn.execute(
+            "DELETE FROM capture_previews WHERE capture_id NOT IN (SELECT capture_id FROM capture_metadata)",
+            [],
+        )
+        .map_err(|e| format!("Failed to clear orphaned previews: {}", e))?;
+        conn.execute(
+            "DELETE FROM capture_annotations WHERE capture_id NOT IN (SELECT capture_id FROM capture_metadata)",
+            [],
+        )
+        .map_err(|e| format!("Failed to clear orphaned annotations: {}", e))?;
         conn.execute_batch("VACUUM;")
             .map_err(|e| format!("Failed to vacuum database: {}", e))?;
         tlog!("[capture_db] Initialised at {:?} (cleared non-persistent and vacuumed)", db_path);
@@ -304,8 +396,11 @@ pub fn initialise(app_data_dir: &Path, clear_on_start: bool) -> Result<(), Strin
         .map_err(|e| format!("Failed to set WAL mode: {}", e))?;
     conn.execute_batch("PRAGMA synchronous=NORMAL;")
         .map_err(|e| format!("Failed to set synchronous mode: {}", e))?;
-    conn.execute_batch("PRAGMA cache_size=-65536;")
+    let cache_mb = db_cache_mb.clamp(MIN_CACHE_MB, MAX_CACHE_MB) as i64;
+    conn.execute_batch(&format!("PRAGMA cache_size=-{};", cache_mb * 1024))
         .map_err(|e| format!("Failed to set cache size: {}", e))?;
+    conn.execute_batch(&format!("PRAGMA mmap_size={};", cache_mb * 1024 * 1024))
+        .map_err(|e| format!("Failed to set mmap size: {}", e))?;
     conn.execute_batch("PRAGMA temp_store=MEMORY;")
         .map_err(|e| format!("Failed to set temp store: {}", e))?;
 
@@ -324,6 +419,14 @@ fn row_to_frame(row: &rusqlite::Row) -> rusqlite::Result<FrameMessage> {
     let is_fd: i32 = row.get("is_fd")?;
     let source_address: Option<i64> = row.get("source_address")?;
     let incomplete: Option<i32> = row.get("incomplete")?;
+    let correlation_id: Option<i64> = row.get("correlation_id")?;
+    let link_role: Option<String> = row.get("link_role")?;
+    let clock: Option<String> = row.get("clock")?;
+    let brs: Option<i32> = row.get("brs")?;
+    let error_class: Option<CanErrorClass> = row
+        .get::<_, Option<String>>("error_class")?
+        .as_deref()
+        .and_then(CanErrorClass::parse);
 
     Ok(FrameMessage {
         protocol: row.get("protocol")?,
@@ -337,6 +440,18 @@ fn row_to_frame(row: &rusqlite::Row) -> rusqlite::Result<FrameMessage> {
         source_address: source_address.map(|v| v as u16),
         incomplete: incomplete.map(|v| v != 0),
         direction: row.get("direction")?,
+        link: match (correlation_id, link_role.as_deref().and_then(LinkRole::parse)) {
+            (Some(id), Some(role)) => Some(FrameLink {
+                correlation_id: id as u64,
+                role,
+                parent_rowid: row.get("parent_rowid")?,
+            }),
+            _ => None,
+        },
+        clock: clock.as_deref().and_then(FrameClock::parse),
+        is_error: error_class.map(|_| true),
+        error_class,
+        brs: brs.map(|v| v != 0),
     })
 }
 
@@ -350,10 +465,12 @@ fn row_to_frame_with_rowid(row: &rusqlite::Row) -> rusqlite::Result<(i64, FrameM
 // Hot-Path Writes (called per batch during streaming)
 // ============================================================================
 
-/// Insert a batch of frames for a capture. Uses a single transaction.
-pub fn insert_frames(capture_id: &str, frames: &[FrameMessage]) -> Result<(), String> {
+/// Insert a batch of frames for a capture and compress any payloads that
+/// fell out of the hot tail, in a single transaction. Returns the change in
+/// the capture's stored payload bytes.
+pub fn insert_frames(capture_id: &str, frames: &[FrameMessage]) -> Result<i64, String> {
     if frames.is_empty() {
-        return Ok(());
+        return Ok(0);
     }
 
     let mut guard = DB.lock().unwrap();
@@ -363,36 +480,65 @@ pub fn insert_frames(capture_id: &str, frames: &[FrameMessage]) -> Result<(), St
         .transaction()
         .map_err(|e| format!("Failed to begin transaction: {}", e))?;
 
-    {
-        let mut stmt = tx
-            .prepare_cached(
-                "INSERT INTO frames (capture_id, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-            )
-            .map_err(|e| format!("Failed to prepare statement: {}", e))?;
-
-        for frame in frames {
-            stmt.execute(params![
-                capture_id,
-                &frame.protocol,
-                frame.timestamp_us as i64,
-                frame.frame_id as i64,
-                frame.bus as i64,
-                frame.dlc as i64,
-                &frame.bytes,
-                frame.is_extended as i32,
-                frame.is_fd as i32,
-                frame.source_address.map(|v| v as i64),
-                frame.incomplete.map(|v| v as i32),
-                &frame.direction,
-            ])
-            .map_err(|e| format!("Failed to insert frame: {}", e))?;
-        }
-    }
+    insert_frame_rows(&tx, capture_id, frames)?;
+    let added: usize = frames.iter().map(|f| f.bytes.len()).sum();
+    let compacted = capture_chunks::compact_capture(&tx, capture_id)?;
 
     tx.commit()
         .map_err(|e| format!("Failed to commit transaction: {}", e))?;
 
+    Ok(added as i64 + compacted)
+}
+
+/// Insert frame rows inside an open transaction. Linked frames get their
+/// `parent_rowid` resolved to the first frame in the capture sharing the
+/// correlation ID (NULL when the frame is itself the first).
+fn insert_frame_rows(
+    tx: &rusqlite::Transaction,
+    capture_id: &str,
+    frames: &[FrameMessage],
+) -> Result<(), String> {
+    let mut stmt = tx
+        .prepare_cached(
+            "INSERT INTO frames (capture_id, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+        )
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+    let mut parent_stmt = tx
+        .prepare_cached(
+            "SELECT MIN(rowid) FROM frames WHERE capture_id = ?1 AND correlation_id = ?2",
+        )
+        .map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+    for frame in frames {
+        let parent_rowid: Option<i64> = match &frame.link {
+            Some(link) => parent_stmt
+                .query_row(params![capture_id, link.correlation_id as i64], |row| row.get(0))
+                .map_err(|e| format!("Failed to resolve frame link: {}", e))?,
+            None => None,
+        };
+        stmt.execute(params![
+            capture_id,
+            &frame.protocol,
+            frame.timestamp_us as i64,
+            frame.frame_id as i64,
+            frame.bus as i64,
+            frame.dlc as i64,
+            &frame.bytes,
+            frame.is_extended as i32,
+            frame.is_fd as i32,
+            frame.source_address.map(|v| v as i64),
+            frame.incomplete.map(|v| v as i32),
+            &frame.direction,
+            frame.link.as_ref().map(|l| l.correlation_id as i64),
+            frame.link.as_ref().map(|l| l.role.as_str()),
+            parent_rowid,
+            frame.clock.map(|c| c.as_str()),
+            frame.error_class.map(|c| c.as_str()),
+            frame.brs.map(|v| v as i32),
+        ])
+        .map_err(|e| format!("Failed to insert frame: {}", e))?;
+    }
     Ok(())
 }
 
@@ -449,8 +595,10 @@ pub fn get_frames_paginated(
 
     let mut stmt = conn
         .prepare_cached(
-            "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction
-             FROM frames WHERE capture_id = ?1 ORDER BY rowid LIMIT ?2 OFFSET ?3",
+            &format!(
+                "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD} AS payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
+                 FROM frames WHERE capture_id = ?1 ORDER BY rowid LIMIT ?2 OFFSET ?3"
+            ),
         )
         .map_err(|e| format!("Failed to prepare: {}", e))?;
 
@@ -506,7 +654,7 @@ pub fn get_frames_paginated_filtered(
 
     // Get page
     let sql = format!(
-        "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction
+        "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD} AS payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
          FROM frames WHERE capture_id = ?1 AND frame_id IN ({}) ORDER BY rowid LIMIT ?2 OFFSET ?3",
         placeholders
     );
@@ -532,6 +680,119 @@ pub fn get_frames_paginated_filtered(
     Ok((frames, rowids, total))
 }
 
+/// SQL condition selecting one protocol family's frames (mirrors
+/// `Protocol::from_tag`).
+fn protocol_condition(protocol: &Protocol) -> &'static str {
+    match protocol {
+        Protocol::Can => "(protocol = 'can' AND is_fd = 0)",
+        Protocol::CanFd => "(protocol = 'canfd' OR (protocol = 'can' AND is_fd != 0))",
+        Protocol::Modbus => "protocol = 'modbus'",
+        Protocol::Serial => "protocol NOT IN ('can', 'canfd', 'modbus')",
+    }
+}
+
+/// Get paginated frames of the given protocol families — one protocol's
+/// sub-stream of a mixed session capture. Returns (frames, rowids,
+/// total_filtered_count); an empty `protocols` selects everything.
+pub fn get_frames_paginated_by_protocol(
+    capture_id: &str,
+    protocols: &[Protocol],
+    offset: usize,
+    limit: usize,
+) -> Result<(Vec<FrameMessage>, Vec<i64>, usize), String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    get_frames_paginated_by_protocol_in(conn, capture_id, protocols, offset, limit)
+}
+
+fn get_frames_paginated_by_protocol_in(
+    conn: &Connection,
+    capture_id: &str,
+    protocols: &[Protocol],
+    offset: usize,
+    limit: usize,
+) -> Result<(Vec<FrameMessage>, Vec<i64>, usize), String> {
+    // Conditions are fixed strings from protocol_condition, not user input
+    let condition = if protocols.is_empty() {
+        "1".to_string()
+    } else {
+        protocols.iter().map(protocol_condition).collect::<Vec<_>>().join(" OR ")
+    };
+
+    let total: usize = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM frames WHERE capture_id = ?1 AND ({})", condition),
+            params![capture_id],
+            |row| row.get::<_, i64>(0),
+        )
+        .map_err(|e| format!("Failed to count: {}", e))? as usize;
+
+    let sql = format!(
+        "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD} AS payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
+         FROM frames WHERE capture_id = ?1 AND ({}) ORDER BY rowid LIMIT ?2 OFFSET ?3",
+        condition
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Failed to prepare: {}", e))?;
+    let rows = stmt
+        .query_map(params![capture_id, limit as i64, offset as i64], |row| {
+            row_to_frame_with_rowid(row)
+        })
+        .map_err(|e| format!("Failed to query: {}", e))?;
+
+    let mut frames = Vec::with_capacity(limit);
+    let mut rowids = Vec::with_capacity(limit);
+    for row in rows {
+        let (rowid, frame) = row.map_err(|e| format!("Failed to read row: {}", e))?;
+        rowids.push(rowid);
+        frames.push(frame);
+    }
+    Ok((frames, rowids, total))
+}
+
+/// Frame count per protocol family in a capture (families with no frames
+/// are omitted), in `Protocol` declaration order.
+pub fn protocol_counts(capture_id: &str) -> Result<Vec<(Protocol, usize)>, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    protocol_counts_in(conn, capture_id)
+}
+
+fn protocol_counts_in(conn: &Connection, capture_id: &str) -> Result<Vec<(Protocol, usize)>, String> {
+    let mut stmt = conn
+        .prepare_cached(
+            "SELECT protocol, is_fd, COUNT(*) FROM frames WHERE capture_id = ?1 GROUP BY protocol, is_fd",
+        )
+        .map_err(|e| format!("Failed to prepare: {}", e))?;
+    let rows = stmt
+        .query_map(params![capture_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?, row.get::<_, i64>(2)?))
+        })
+        .map_err(|e| format!("Failed to query: {}", e))?;
+
+    let order = [Protocol::Can, Protocol::CanFd, Protocol::Modbus, Protocol::Serial];
+    let mut counts = [0usize; 4];
+    for row in rows {
+        let (tag, is_fd, count) = row.map_err(|e| format!("Failed to read row: {}", e))?;
+        let protocol = Protocol::from_tag(&tag, is_fd);
+        if let Some(i) = order.iter().position(|p| *p == protocol) {
+            counts[i] += count as usize;
+        }
+    }
+    Ok(order.into_iter().zip(counts).filter(|(_, n)| *n > 0).collect())
+}
+
+/// Payload bytes a frame capture holds (compressed chunks plus the inline
+/// hot tail). Bounded by the chunk rows and the hot tail, not the whole
+/// capture; used to re-measure after bulk changes, while appends keep
+/// `CaptureMetadata::stored_bytes` current incrementally.
+pub fn stored_bytes(capture_id: &str) -> Result<u64, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    capture_chunks::stored_bytes(conn, capture_id)
+}
+
 /// Get the last N frames for a capture, optionally filtered. Returns (frames, rowids, total_filtered_count, end_time).
 /// Frames are returned in chronological order (oldest first).
 pub fn get_frames_tail(
@@ -544,9 +805,10 @@ pub fn get_frames_tail(
 
     let (sql_data, sql_count, sql_end_time) = if frame_ids.is_empty() {
         (
-            "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction
-             FROM frames WHERE capture_id = ?1 ORDER BY rowid DESC LIMIT ?2"
-                .to_string(),
+            format!(
+                "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD} AS payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
+                 FROM frames WHERE capture_id = ?1 ORDER BY rowid DESC LIMIT ?2"
+            ),
             "SELECT COUNT(*) FROM frames WHERE capture_id = ?1".to_string(),
             "SELECT MAX(timestamp_us) FROM frames WHERE capture_id = ?1".to_string(),
         )
@@ -558,7 +820,7 @@ pub fn get_frames_tail(
             .join(",");
         (
             format!(
-                "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction
+                "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD} AS payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
                  FROM frames WHERE capture_id = ?1 AND frame_id IN ({}) ORDER BY rowid DESC LIMIT ?2",
                 placeholders
             ),
@@ -808,7 +1070,7 @@ pub fn search_frames(
 
     let sql = format!(
         "WITH numbered AS (
-            SELECT frame_id, payload,
+            SELECT frame_id, {FRAME_PAYLOAD} AS payload,
                    CAST(ROW_NUMBER() OVER (ORDER BY rowid) AS INTEGER) - 1 AS offset
             FROM frames
             WHERE capture_id = ?1{}
@@ -833,152 +1095,868 @@ pub fn search_frames(
     Ok(offsets)
 }
 
-/// Copy all frame and byte data from one capture to another using INSERT SELECT.
-pub fn copy_capture_data(source_id: &str, dest_id: &str) -> Result<usize, String> {
-    let mut guard = DB.lock().unwrap();
-    let conn = guard.as_mut().ok_or("Database not initialised")?;
-
-    let tx = conn
-        .transaction()
-        .map_err(|e| format!("Failed to begin transaction: {}", e))?;
-
-    let frame_count = tx
-        .execute(
-            "INSERT INTO frames (capture_id, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction)
-             SELECT ?2, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction
-             FROM frames WHERE capture_id = ?1 ORDER BY rowid",
-            params![source_id, dest_id],
-        )
-        .map_err(|e| format!("Failed to copy frames: {}", e))?;
-
-    let byte_count = tx
-        .execute(
-            "INSERT INTO bytes (capture_id, byte_val, timestamp_us, bus)
-             SELECT ?2, byte_val, timestamp_us, bus
-             FROM bytes WHERE capture_id = ?1 ORDER BY rowid",
-            params![source_id, dest_id],
-        )
-        .map_err(|e| format!("Failed to copy bytes: {}", e))?;
-
-    tx.commit()
-        .map_err(|e| format!("Failed to commit: {}", e))?;
-
-    Ok(frame_count + byte_count)
+/// Integer value of payload byte `index` (0-based), or NULL past the end.
+/// SQLite can't read a byte of a BLOB as a number directly, so it goes
+/// through its hex digits.
+fn sql_payload_byte(index: usize) -> String {
+    let hex = format!("hex(substr(payload, {}, 1))", index + 1);
+    format!(
+        "CASE WHEN length(payload) > {index} THEN \
+         (instr('0123456789ABCDEF', substr({hex}, 1, 1)) - 1) * 16 + \
+         instr('0123456789ABCDEF', substr({hex}, 2, 1)) - 1 END"
+    )
 }
 
-/// Delete all data for a specific capture.
-pub fn delete_capture_data(capture_id: &str) -> Result<(), String> {
+/// Search a frame capture with a structured pattern, returning 0-based
+/// offsets in the selected-ID-filtered result set (like `search_frames`).
+/// All parts of the pattern must match; at most `limit` offsets are returned.
+pub fn search_frames_by_pattern(
+    capture_id: &str,
+    pattern: &FramePattern,
+    frame_ids: &[u32],
+    limit: usize,
+) -> Result<Vec<usize>, String> {
     let guard = DB.lock().unwrap();
     let conn = guard.as_ref().ok_or("Database not initialised")?;
-
-    conn.execute("DELETE FROM frames WHERE capture_id = ?1", params![capture_id])
-        .map_err(|e| format!("Failed to delete frames: {}", e))?;
-    conn.execute("DELETE FROM bytes WHERE capture_id = ?1", params![capture_id])
-        .map_err(|e| format!("Failed to delete bytes: {}", e))?;
-
-    Ok(())
+    search_frames_by_pattern_in(conn, capture_id, pattern, frame_ids, limit)
 }
 
-/// Clear and refill a capture with new frames (used by framing to reuse capture IDs).
-pub fn clear_and_refill(capture_id: &str, frames: &[FrameMessage]) -> Result<(), String> {
-    let mut guard = DB.lock().unwrap();
-    let conn = guard.as_mut().ok_or("Database not initialised")?;
-
-    let tx = conn
-        .transaction()
-        .map_err(|e| format!("Failed to begin transaction: {}", e))?;
-
-    tx.execute("DELETE FROM frames WHERE capture_id = ?1", params![capture_id])
-        .map_err(|e| format!("Failed to clear frames: {}", e))?;
-
-    {
-        let mut stmt = tx
-            .prepare_cached(
-                "INSERT INTO frames (capture_id, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
-            )
-            .map_err(|e| format!("Failed to prepare: {}", e))?;
+fn search_frames_by_pattern_in(
+    conn: &Connection,
+    capture_id: &str,
+    pattern: &FramePattern,
+    frame_ids: &[u32],
+    limit: usize,
+) -> Result<Vec<usize>, String> {
+    let payload = match pattern.payload_hex.as_deref().map(str::trim).filter(|h| !h.is_empty()) {
+        Some(h) => Some(hex::decode(h.replace(' ', "")).map_err(|e| format!("Invalid payload hex: {}", e))?),
+        None => None,
+    };
 
-        for frame in frames {
-            stmt.execute(params![
-                capture_id,
-                &frame.protocol,
-                frame.timestamp_us as i64,
-                frame.frame_id as i64,
-                frame.bus as i64,
-                frame.dlc as i64,
-                &frame.bytes,
-                frame.is_extended as i32,
-                frame.is_fd as i32,
-                frame.source_address.map(|v| v as i64),
-                frame.incomplete.map(|v| v as i32),
-                &frame.direction,
-            ])
-            .map_err(|e| format!("Failed to insert frame: {}", e))?;
-        }
+    // Numeric values are inlined (as in search_frames); the payload needle is bound
+    let id_filter = if frame_ids.is_empty() {
+        String::new()
+    } else {
+        let ids = frame_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",");
+        format!(" AND frame_id IN ({})", ids)
+    };
+    let mut clauses = vec!["1".to_string()];
+    if !pattern.id_ranges.is_empty() {
+        let ranges: Vec<String> = pattern
+            .id_ranges
+            .iter()
+            .map(|r| format!("frame_id BETWEEN {} AND {}", r.from.min(r.to), r.from.max(r.to)))
+            .collect();
+        clauses.push(format!("({})", ranges.join(" OR ")));
+    }
+    for m in &pattern.byte_masks {
+        clauses.push(format!("({} & {}) = {}", sql_payload_byte(m.index), m.mask, m.value & m.mask));
+    }
+    if payload.is_some() {
+        clauses.push("instr(payload, ?2) > 0".to_string());
     }
 
-    tx.commit()
-        .map_err(|e| format!("Failed to commit: {}", e))?;
-
-    Ok(())
-}
-
-/// Get all frames for a capture (loads everything — use sparingly).
-pub fn get_all_frames(capture_id: &str) -> Result<Vec<FrameMessage>, String> {
-    let guard = DB.lock().unwrap();
-    let conn = guard.as_ref().ok_or("Database not initialised")?;
-
-    let mut stmt = conn
-        .prepare_cached(
-            "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction
-             FROM frames WHERE capture_id = ?1 ORDER BY rowid",
+    let sql = format!(
+        "WITH numbered AS (
+            SELECT frame_id, {FRAME_PAYLOAD} AS payload,
+                   CAST(ROW_NUMBER() OVER (ORDER BY rowid) AS INTEGER) - 1 AS offset
+            FROM frames
+            WHERE capture_id = ?1{}
         )
-        .map_err(|e| format!("Failed to prepare: {}", e))?;
+        SELECT offset FROM numbered WHERE {} ORDER BY offset LIMIT {}",
+        id_filter,
+        clauses.join(" AND "),
+        limit
+    );
 
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Failed to prepare search: {}", e))?;
+    let mut values: Vec<&dyn rusqlite::ToSql> = vec![&capture_id];
+    if let Some(needle) = &payload {
+        values.push(needle);
+    }
     let rows = stmt
-        .query_map(params![capture_id], |row| row_to_frame(row))
-        .map_err(|e| format!("Failed to query: {}", e))?;
+        .query_map(values.as_slice(), |row| row.get::<_, i64>(0))
+        .map_err(|e| format!("Failed to execute search: {}", e))?;
 
-    let mut frames = Vec::new();
+    let mut offsets = Vec::new();
     for row in rows {
-        frames.push(row.map_err(|e| format!("Failed to read row: {}", e))?);
+        offsets.push(row.map_err(|e| format!("Failed to read row: {}", e))? as usize);
     }
-    Ok(frames)
+    Ok(offsets)
 }
 
 // ============================================================================
-// Capture Reader Streaming (chunked reads for playback)
+// Row Models (row_model.rs)
 // ============================================================================
 
-/// Read a chunk of frames starting after the given rowid (forward).
-/// Returns Vec of (rowid, FrameMessage) for position tracking.
-pub fn read_frame_chunk(
+/// Build a `WITH r(rid, k, cnt, first_us)` CTE holding a row model's rows:
+/// `rid` is the frame shown, `k` its sort key. Filter values are numeric
+/// literals or validated enums, so they're inlined like search_frames does.
+fn row_model_cte(spec: &RowModelSpec) -> String {
+    let mut filter = String::from("capture_id = ?1");
+    if !spec.frame_ids.is_empty() {
+        let ids = spec.frame_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",");
+        filter.push_str(&format!(" AND frame_id IN ({})", ids));
+    }
+    if !spec.buses.is_empty() {
+        let buses = spec.buses.iter().map(|b| b.to_string()).collect::<Vec<_>>().join(",");
+        filter.push_str(&format!(" AND bus IN ({})", buses));
+    }
+    match spec.direction.as_deref() {
+        Some("tx") => filter.push_str(" AND direction = 'tx'"),
+        // Frames without a recorded direction are received frames
+        Some("rx") => filter.push_str(" AND (direction IS NULL OR direction = 'rx')"),
+        _ => {}
+    }
+    if let Some(start) = spec.start_us {
+        filter.push_str(&format!(" AND timestamp_us >= {}", start));
+    }
+    if let Some(end) = spec.end_us {
+        filter.push_str(&format!(" AND timestamp_us <= {}", end));
+    }
+
+    if spec.group_by_id {
+        let key = match spec.sort {
+            RowSortKey::Time => "f.rowid",
+            RowSortKey::FrameId => "f.frame_id",
+            RowSortKey::Bus => "f.bus",
+            RowSortKey::Dlc => "f.dlc",
+            RowSortKey::Count => "g.cnt",
+        };
+        format!(
+            "WITH g AS (SELECT MAX(rowid) AS last_rowid, COUNT(*) AS cnt, MIN(timestamp_us) AS first_us
+                        FROM frames WHERE {} GROUP BY frame_id, bus, is_extended),
+                  r AS (SELECT f.rowid AS rid, {} AS k, g.cnt AS cnt, g.first_us AS first_us
+                        FROM g JOIN frames f ON f.rowid = g.last_rowid)",
+            filter, key
+        )
+    } else {
+        let key = match spec.sort {
+            RowSortKey::Time | RowSortKey::Count => "rowid",
+            RowSortKey::FrameId => "frame_id",
+            RowSortKey::Bus => "bus",
+            RowSortKey::Dlc => "dlc",
+        };
+        format!(
+            "WITH r AS (SELECT rowid AS rid, {} AS k, NULL AS cnt, NULL AS first_us FROM frames WHERE {})",
+            key, filter
+        )
+    }
+}
+
+/// Number of rows in a row model.
+pub fn row_model_count(capture_id: &str, spec: &RowModelSpec) -> Result<usize, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    row_model_count_in(conn, capture_id, spec)
+}
+
+fn row_model_count_in(conn: &Connection, capture_id: &str, spec: &RowModelSpec) -> Result<usize, String> {
+    let sql = format!("{} SELECT COUNT(*) FROM r", row_model_cte(spec));
+    let count: i64 = conn
+        .query_row(&sql, params![capture_id], |row| row.get(0))
+        .map_err(|e| format!("Failed to count rows: {}", e))?;
+    Ok(count as usize)
+}
+
+/// A range of a row model's rows in view order.
+pub fn row_model_rows(
     capture_id: &str,
-    after_rowid: i64,
+    spec: &RowModelSpec,
+    offset: usize,
     limit: usize,
-) -> Result<Vec<(i64, FrameMessage)>, String> {
+) -> Result<Vec<RowModelRow>, String> {
     let guard = DB.lock().unwrap();
     let conn = guard.as_ref().ok_or("Database not initialised")?;
+    row_model_rows_in(conn, capture_id, spec, offset, limit)
+}
 
+fn row_model_rows_in(
+    conn: &Connection,
+    capture_id: &str,
+    spec: &RowModelSpec,
+    offset: usize,
+    limit: usize,
+) -> Result<Vec<RowModelRow>, String> {
+    let dir = if spec.descending { "DESC" } else { "ASC" };
+    let sql = format!(
+        "{} SELECT f.rowid, f.protocol, f.timestamp_us, f.frame_id, f.bus, f.dlc, {payload} AS payload, f.is_extended, f.is_fd, f.source_address, f.incomplete, f.direction, f.correlation_id, f.link_role, f.parent_rowid, f.clock, f.error_class, f.brs, r.cnt, r.first_us
+         FROM r JOIN frames f ON f.rowid = r.rid ORDER BY r.k {dir}, r.rid {dir} LIMIT ?2 OFFSET ?3",
+        row_model_cte(spec),
+        dir = dir,
+        payload = payload_sql!("f")
+    );
     let mut stmt = conn
-        .prepare_cached(
-            "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction
-             FROM frames WHERE capture_id = ?1 AND rowid > ?2 ORDER BY rowid ASC LIMIT ?3",
-        )
+        .prepare(&sql)
         .map_err(|e| format!("Failed to prepare: {}", e))?;
 
     let rows = stmt
-        .query_map(params![capture_id, after_rowid, limit as i64], |row| {
-            row_to_frame_with_rowid(row)
+        .query_map(params![capture_id, limit as i64, offset as i64], |row| {
+            let (capture_index, frame) = row_to_frame_with_rowid(row)?;
+            Ok(RowModelRow {
+                capture_index,
+                frame,
+                count: row.get("cnt")?,
+                first_us: row.get::<_, Option<i64>>("first_us")?.map(|v| v as u64),
+            })
         })
         .map_err(|e| format!("Failed to query: {}", e))?;
 
-    let mut result = Vec::with_capacity(limit);
+    let mut out = Vec::with_capacity(limit);
     for row in rows {
-        result.push(row.map_err(|e| format!("Failed to read row: {}", e))?);
+        out.push(row.map_err(|e| format!("Failed to read row: {}", e))?);
     }
-    Ok(result)
+    Ok(out)
+}
+
+/// Position of a capture rowid within a row model. In grouped views the
+/// frame's group row is located. None when the frame is filtered out.
+pub fn row_model_position(
+    capture_id: &str,
+    spec: &RowModelSpec,
+    rowid: i64,
+) -> Result<Option<usize>, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    row_model_position_in(conn, capture_id, spec, rowid)
+}
+
+fn row_model_position_in(
+    conn: &Connection,
+    capture_id: &str,
+    spec: &RowModelSpec,
+    rowid: i64,
+) -> Result<Option<usize>, String> {
+    let target = if spec.group_by_id {
+        // The group row shows the group's latest frame
+        "(SELECT r.rid FROM r JOIN frames f ON f.rowid = r.rid
+          JOIN frames t ON t.rowid = ?2
+          WHERE f.frame_id = t.frame_id AND f.bus = t.bus AND f.is_extended = t.is_extended)"
+    } else {
+        "?2"
+    };
+    let cmp = if spec.descending { ">" } else { "<" };
+    let sql = format!(
+        "{cte}, tgt AS (SELECT rid, k FROM r WHERE rid = {target})
+         SELECT (SELECT COUNT(*) FROM tgt),
+                (SELECT COUNT(*) FROM r, tgt WHERE r.k {cmp} tgt.k OR (r.k = tgt.k AND r.rid {cmp} tgt.rid))",
+        cte = row_model_cte(spec),
+        target = target,
+        cmp = cmp
+    );
+    let (found, position): (i64, i64) = conn
+        .query_row(&sql, params![capture_id, rowid], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to locate row: {}", e))?;
+    Ok((found > 0).then_some(position as usize))
+}
+
+// ============================================================================
+// Preview Series (capture_preview.rs)
+// ============================================================================
+
+/// Items on one bus in one preview bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewCell {
+    pub bucket: usize,
+    pub bus: u8,
+    pub count: u64,
+    /// Distinct frame IDs (0 for byte captures)
+    pub unique_ids: u64,
+}
+
+/// Bucketed counts for a capture preview. Returns (first_us, last_us, cells);
+/// the span is None for an empty capture.
+pub fn preview_cells(
+    capture_id: &str,
+    bytes: bool,
+    buckets: usize,
+) -> Result<(Option<(u64, u64)>, Vec<PreviewCell>), String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    preview_cells_in(conn, capture_id, bytes, buckets)
+}
+
+fn preview_cells_in(
+    conn: &Connection,
+    capture_id: &str,
+    bytes: bool,
+    buckets: usize,
+) -> Result<(Option<(u64, u64)>, Vec<PreviewCell>), String> {
+    let (table, unique) = if bytes {
+        ("bytes", "0")
+    } else {
+        ("frames", "COUNT(DISTINCT frame_id)")
+    };
+    // Items aren't strictly time-ordered across merged sources, so take the
+    // true span rather than first/last row.
+    let span: (Option<i64>, Option<i64>) = conn
+        .query_row(
+            &format!("SELECT MIN(timestamp_us), MAX(timestamp_us) FROM {} WHERE capture_id = ?1", table),
+            params![capture_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| format!("Failed to read capture span: {}", e))?;
+    let (Some(first), Some(last)) = span else {
+        return Ok((None, Vec::new()));
+    };
+    let buckets = buckets.max(1) as i64;
+    let bucket_us = ((last - first) / buckets + 1).max(1);
+
+    let sql = format!(
+        "SELECT MIN((timestamp_us - ?2) / ?3, ?4 - 1) AS b, bus, COUNT(*), {unique}
+         FROM {table} WHERE capture_id = ?1
+         GROUP BY b, bus ORDER BY b, bus",
+        unique = unique,
+        table = table
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Failed to prepare preview query: {}", e))?;
+    let rows = stmt
+        .query_map(params![capture_id, first, bucket_us, buckets], |row| {
+            Ok(PreviewCell {
+                bucket: row.get::<_, i64>(0)? as usize,
+                bus: row.get::<_, i64>(1)? as u8,
+                count: row.get::<_, i64>(2)? as u64,
+                unique_ids: row.get::<_, i64>(3)? as u64,
+            })
+        })
+        .map_err(|e| format!("Failed to query preview: {}", e))?;
+    let mut cells = Vec::new();
+    for row in rows {
+        cells.push(row.map_err(|e| format!("Failed to read preview row: {}", e))?);
+    }
+    Ok((Some((first as u64, last as u64)), cells))
+}
+
+/// Cached preview JSON, if it was computed for this points/count/end state.
+pub fn load_capture_preview(
+    capture_id: &str,
+    points: usize,
+    item_count: usize,
+    end_time_us: Option<u64>,
+) -> Result<Option<String>, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    conn.query_row(
+        "SELECT preview FROM capture_previews
+         WHERE capture_id = ?1 AND points = ?2 AND item_count = ?3 AND end_time_us IS ?4",
+        params![capture_id, points as i64, item_count as i64, end_time_us.map(|v| v as i64)],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| format!("Failed to load capture preview: {}", e))
+}
+
+pub fn save_capture_preview(
+    capture_id: &str,
+    points: usize,
+    item_count: usize,
+    end_time_us: Option<u64>,
+    preview_json: &str,
+) -> Result<(), String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    conn.execute(
+        "INSERT OR REPLACE INTO capture_previews (capture_id, points, item_count, end_time_us, preview)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![capture_id, points as i64, item_count as i64, end_time_us.map(|v| v as i64), preview_json],
+    )
+    .map_err(|e| format!("Failed to save capture preview: {}", e))?;
+    Ok(())
+}
+
+/// Copy all frame and byte data from one capture to another using INSERT SELECT.
+pub fn copy_capture_data(source_id: &str, dest_id: &str) -> Result<usize, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    copy_rows(conn, "main", "main", source_id, dest_id)
+}
+
+/// Copy a capture's frames and bytes from schema `from` to schema `to`
+/// (`main` or an attached capture file) in one transaction. Copied rows get
+/// new rowids, so links are re-pointed within the copy.
+fn copy_rows(conn: &Connection, from: &str, to: &str, source_id: &str, dest_id: &str) -> Result<usize, String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+    let frame_count = tx
+        .execute(
+            &format!(
+                "INSERT INTO {to}.frames (capture_id, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, clock, error_class, brs)
+                 SELECT ?2, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD}, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, clock, error_class, brs
+                 FROM {from}.frames WHERE capture_id = ?1 ORDER BY rowid"
+            ),
+            params![source_id, dest_id],
+        )
+        .map_err(|e| format!("Failed to copy frames: {}", e))?;
+
+    relink_rows(&tx, to, dest_id)?;
+    if to == "main" {
+        capture_chunks::compact_capture(&tx, dest_id)?;
+    }
+
+    let byte_count = tx
+        .execute(
+            &format!(
+                "INSERT INTO {to}.bytes (capture_id, byte_val, timestamp_us, bus)
+                 SELECT ?2, byte_val, timestamp_us, bus
+                 FROM {from}.bytes WHERE capture_id = ?1 ORDER BY rowid"
+            ),
+            params![source_id, dest_id],
+        )
+        .map_err(|e| format!("Failed to copy bytes: {}", e))?;
+
+    tx.execute(
+        &format!(
+            "INSERT INTO {to}.capture_annotations (capture_id, timestamp_us, frame_rowid, text, color, created_at)
+             SELECT ?2, timestamp_us, NULL, text, color, created_at
+             FROM {from}.capture_annotations WHERE capture_id = ?1 ORDER BY id"
+        ),
+        params![source_id, dest_id],
+    )
+    .map_err(|e| format!("Failed to copy annotations: {}", e))?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit: {}", e))?;
+
+    Ok(frame_count + byte_count)
+}
+
+/// Point each linked frame of a freshly written capture at the first frame
+/// of its correlation group (rowids change whenever rows are copied).
+fn relink_rows(tx: &rusqlite::Transaction, schema: &str, capture_id: &str) -> Result<(), String> {
+    tx.execute(
+        &format!(
+            "UPDATE {schema}.frames SET parent_rowid = NULLIF(
+                 (SELECT MIN(p.rowid) FROM {schema}.frames p
+                  WHERE p.capture_id = ?1 AND p.correlation_id = frames.correlation_id),
+                 rowid)
+             WHERE capture_id = ?1 AND correlation_id IS NOT NULL"
+        ),
+        params![capture_id],
+    )
+    .map_err(|e| format!("Failed to relink copied frames: {}", e))?;
+    Ok(())
+}
+
+/// Write the frames of several captures into `dest_id`, interleaved by
+/// timestamp (ties keep source order, then original order). Each source
+/// carries a bus renumbering map; unmapped buses keep their number.
+pub fn merge_capture_data(sources: &[(&str, &HashMap<u8, u8>)], dest_id: &str) -> Result<usize, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    merge_rows(conn, sources, dest_id)
+}
+
+fn merge_rows(conn: &Connection, sources: &[(&str, &HashMap<u8, u8>)], dest_id: &str) -> Result<usize, String> {
+    // Bus numbers are u8s, so the CASE arms are safe to inline; capture
+    // IDs are bound (?2.. in source order, ?1 is the destination).
+    let selects: Vec<String> = sources
+        .iter()
+        .enumerate()
+        .map(|(i, (_, bus_map))| {
+            let mut arms: Vec<(&u8, &u8)> = bus_map.iter().collect();
+            arms.sort();
+            let bus = if arms.is_empty() {
+                "bus".to_string()
+            } else {
+                let whens: String = arms.iter().map(|(from, to)| format!(" WHEN {from} THEN {to}")).collect();
+                format!("CASE bus{whens} ELSE bus END")
+            };
+            format!(
+                "SELECT {i} AS src, rowid AS src_row, protocol, timestamp_us, frame_id, {bus} AS bus, dlc, {FRAME_PAYLOAD} AS payload,
+                        is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, clock, error_class, brs
+                 FROM frames WHERE capture_id = ?{}",
+                i + 2
+            )
+        })
+        .collect();
+    let sql = format!(
+        "INSERT INTO frames (capture_id, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, clock, error_class, brs)
+         SELECT ?1, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, clock, error_class, brs
+         FROM ({}) ORDER BY timestamp_us, src, src_row",
+        selects.join(" UNION ALL ")
+    );
+
+    let mut values: Vec<&dyn rusqlite::ToSql> = vec![&dest_id];
+    values.extend(sources.iter().map(|(id, _)| id as &dyn rusqlite::ToSql));
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+    let count = tx
+        .execute(&sql, values.as_slice())
+        .map_err(|e| format!("Failed to merge frames: {}", e))?;
+    relink_rows(&tx, "main", dest_id)?;
+    capture_chunks::compact_capture(&tx, dest_id)?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit: {}", e))?;
+    Ok(count)
+}
+
+/// Delete all data for a specific capture.
+pub fn delete_capture_data(capture_id: &str) -> Result<(), String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+
+    conn.execute("DELETE FROM frames WHERE capture_id = ?1", params![capture_id])
+        .map_err(|e| format!("Failed to delete frames: {}", e))?;
+    capture_chunks::delete_chunks(conn, capture_id)?;
+    conn.execute("DELETE FROM bytes WHERE capture_id = ?1", params![capture_id])
+        .map_err(|e| format!("Failed to delete bytes: {}", e))?;
+
+    Ok(())
+}
+
+/// What a capture holds after `trim_capture_data`.
+#[derive(Debug, Default)]
+pub struct TrimSummary {
+    /// Rows deleted
+    pub removed: usize,
+    /// Rows remaining
+    pub count: usize,
+    pub start_time_us: Option<u64>,
+    pub end_time_us: Option<u64>,
+    pub buses: Vec<u8>,
+    /// Distinct (bus, frame_id) pairs remaining (empty for byte captures)
+    pub frame_keys: Vec<(u8, u32)>,
+    /// Payload bytes stored (0 for byte captures)
+    pub stored_bytes: u64,
+}
+
+/// Delete a capture's rows outside `[start_us, end_us]` (`keep_inside`) or
+/// inside it, in one transaction. An open bound extends to the capture's
+/// start or end. Frame links whose group lost its first frame are re-pointed
+/// at the earliest remaining member.
+pub fn trim_capture_data(
+    capture_id: &str,
+    bytes: bool,
+    start_us: Option<u64>,
+    end_us: Option<u64>,
+    keep_inside: bool,
+) -> Result<TrimSummary, String> {
+    let mut guard = DB.lock().unwrap();
+    let conn = guard.as_mut().ok_or("Database not initialised")?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+    let summary = trim_rows(&tx, capture_id, bytes, start_us, end_us, keep_inside)?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit: {}", e))?;
+    Ok(summary)
+}
+
+fn trim_rows(
+    tx: &rusqlite::Transaction,
+    capture_id: &str,
+    bytes: bool,
+    start_us: Option<u64>,
+    end_us: Option<u64>,
+    keep_inside: bool,
+) -> Result<TrimSummary, String> {
+    let table = if bytes { "bytes" } else { "frames" };
+    let start = start_us.map_or(i64::MIN, |t| t as i64);
+    let end = end_us.map_or(i64::MAX, |t| t as i64);
+    let condition = if keep_inside {
+        "(timestamp_us < ?2 OR timestamp_us > ?3)"
+    } else {
+        "(timestamp_us >= ?2 AND timestamp_us <= ?3)"
+    };
+
+    // table is an internal constant ("frames" or "bytes"), not user input
+    let removed = tx
+        .execute(
+            &format!("DELETE FROM {} WHERE capture_id = ?1 AND {}", table, condition),
+            params![capture_id, start, end],
+        )
+        .map_err(|e| format!("Failed to trim {}: {}", table, e))?;
+
+    if !bytes && removed > 0 {
+        tx.execute(
+            "UPDATE frames SET parent_rowid = NULLIF(
+                 (SELECT MIN(p.rowid) FROM frames p
+                  WHERE p.capture_id = ?1 AND p.correlation_id = frames.correlation_id),
+                 rowid)
+             WHERE capture_id = ?1 AND correlation_id IS NOT NULL",
+            params![capture_id],
+        )
+        .map_err(|e| format!("Failed to relink trimmed frames: {}", e))?;
+        capture_chunks::prune_chunks(tx, capture_id)?;
+    }
+
+    capture_summary(tx, capture_id, bytes, removed)
+}
+
+/// Count, span, buses and frame keys of a capture after its rows changed.
+fn capture_summary(
+    tx: &rusqlite::Transaction,
+    capture_id: &str,
+    bytes: bool,
+    removed: usize,
+) -> Result<TrimSummary, String> {
+    let table = if bytes { "bytes" } else { "frames" };
+    let (count, first, last): (i64, Option<i64>, Option<i64>) = tx
+        .query_row(
+            &format!(
+                "SELECT COUNT(*), MIN(timestamp_us), MAX(timestamp_us) FROM {} WHERE capture_id = ?1",
+                table
+            ),
+            params![capture_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .map_err(|e| format!("Failed to read trimmed span: {}", e))?;
+
+    let mut stmt = tx
+        .prepare(&format!(
+            "SELECT DISTINCT bus FROM {} WHERE capture_id = ?1 ORDER BY bus",
+            table
+        ))
+        .map_err(|e| format!("Failed to prepare: {}", e))?;
+    let buses = stmt
+        .query_map(params![capture_id], |row| row.get::<_, u8>(0))
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("Failed to read buses: {}", e))?;
+
+    let stored_bytes = if bytes { 0 } else { capture_chunks::stored_bytes(tx, capture_id)? };
+    let frame_keys = if bytes {
+        Vec::new()
+    } else {
+        let mut stmt = tx
+            .prepare("SELECT DISTINCT bus, frame_id FROM frames WHERE capture_id = ?1")
+            .map_err(|e| format!("Failed to prepare: {}", e))?;
+        let keys = stmt
+            .query_map(params![capture_id], |row| Ok((row.get::<_, u8>(0)?, row.get::<_, u32>(1)?)))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to read frame IDs: {}", e))?;
+        keys
+    };
+
+    Ok(TrimSummary {
+        removed,
+        count: count as usize,
+        start_time_us: first.map(|t| t as u64),
+        end_time_us: last.map(|t| t as u64),
+        buses,
+        frame_keys,
+        stored_bytes,
+    })
+}
+
+/// Copy the rows of `source_id` within `[start_us, end_us]` (open bounds
+/// extend to the capture's start or end) into `dest_id`, in one transaction.
+/// Returns the new capture's summary (`removed` is 0).
+pub fn copy_capture_range(
+    source_id: &str,
+    dest_id: &str,
+    bytes: bool,
+    start_us: Option<u64>,
+    end_us: Option<u64>,
+) -> Result<TrimSummary, String> {
+    let mut guard = DB.lock().unwrap();
+    let conn = guard.as_mut().ok_or("Database not initialised")?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+    let summary = copy_range_rows(&tx, source_id, dest_id, bytes, start_us, end_us)?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit: {}", e))?;
+    Ok(summary)
+}
+
+fn copy_range_rows(
+    tx: &rusqlite::Transaction,
+    source_id: &str,
+    dest_id: &str,
+    bytes: bool,
+    start_us: Option<u64>,
+    end_us: Option<u64>,
+) -> Result<TrimSummary, String> {
+    let start = start_us.map_or(i64::MIN, |t| t as i64);
+    let end = end_us.map_or(i64::MAX, |t| t as i64);
+    let sql = if bytes {
+        "INSERT INTO bytes (capture_id, byte_val, timestamp_us, bus)
+         SELECT ?2, byte_val, timestamp_us, bus
+         FROM bytes WHERE capture_id = ?1 AND timestamp_us >= ?3 AND timestamp_us <= ?4 ORDER BY rowid"
+            .to_string()
+    } else {
+        format!(
+            "INSERT INTO frames (capture_id, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, clock, error_class, brs)
+             SELECT ?2, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD}, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, clock, error_class, brs
+             FROM frames WHERE capture_id = ?1 AND timestamp_us >= ?3 AND timestamp_us <= ?4 ORDER BY rowid"
+        )
+    };
+    tx.execute(&sql, params![source_id, dest_id, start, end])
+        .map_err(|e| format!("Failed to copy capture range: {}", e))?;
+    if !bytes {
+        relink_rows(tx, "main", dest_id)?;
+        capture_chunks::compact_capture(tx, dest_id)?;
+    }
+    capture_summary(tx, dest_id, bytes, 0)
+}
+
+/// Drop a frame capture's oldest rows so at most `keep_frames` remain and
+/// none is older than `before_us` — the ring-buffer trim run during streaming.
+/// Cheaper than [`trim_capture_data`]: it only relinks frames whose parent was
+/// deleted and skips the bus/frame-key rescan. Returns (rows removed, new
+/// earliest timestamp).
+pub fn trim_head(
+    capture_id: &str,
+    keep_frames: Option<usize>,
+    before_us: Option<u64>,
+) -> Result<(usize, Option<u64>), String> {
+    let mut guard = DB.lock().unwrap();
+    let conn = guard.as_mut().ok_or("Database not initialised")?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+    let result = trim_head_rows(&tx, capture_id, keep_frames, before_us)?;
+    tx.commit()
+        .map_err(|e| format!("Failed to commit: {}", e))?;
+    Ok(result)
+}
+
+fn trim_head_rows(
+    tx: &rusqlite::Transaction,
+    capture_id: &str,
+    keep_frames: Option<usize>,
+    before_us: Option<u64>,
+) -> Result<(usize, Option<u64>), String> {
+    let mut removed = 0;
+
+    if let Some(keep) = keep_frames {
+        // The keep-th newest rowid is the new head; a short capture yields NULL
+        // and deletes nothing
+        removed += tx
+            .execute(
+                "DELETE FROM frames WHERE capture_id = ?1 AND rowid < (
+                     SELECT rowid FROM frames WHERE capture_id = ?1
+                     ORDER BY rowid DESC LIMIT 1 OFFSET ?2)",
+                params![capture_id, keep.saturating_sub(1) as i64],
+            )
+            .map_err(|e| format!("Failed to trim frames: {}", e))?;
+    }
+
+    if let Some(before) = before_us {
+        removed += tx
+            .execute(
+                "DELETE FROM frames WHERE capture_id = ?1 AND timestamp_us < ?2",
+                params![capture_id, before as i64],
+            )
+            .map_err(|e| format!("Failed to trim frames: {}", e))?;
+    }
+
+    if removed > 0 {
+        tx.execute(
+            "UPDATE frames SET parent_rowid = NULLIF(
+                 (SELECT MIN(p.rowid) FROM frames p
+                  WHERE p.capture_id = ?1 AND p.correlation_id = frames.correlation_id),
+                 rowid)
+             WHERE capture_id = ?1 AND parent_rowid IS NOT NULL
+               AND NOT EXISTS (SELECT 1 FROM frames p WHERE p.rowid = frames.parent_rowid)",
+            params![capture_id],
+        )
+        .map_err(|e| format!("Failed to relink trimmed frames: {}", e))?;
+        capture_chunks::prune_chunks(tx, capture_id)?;
+    }
+
+    let first: Option<i64> = tx
+        .query_row(
+            "SELECT MIN(timestamp_us) FROM frames WHERE capture_id = ?1",
+            params![capture_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to read trimmed span: {}", e))?;
+
+    Ok((removed, first.map(|t| t as u64)))
+}
+
+/// Clear and refill a capture with new frames (used by framing to reuse capture IDs).
+/// Returns the payload bytes the capture now stores.
+pub fn clear_and_refill(capture_id: &str, frames: &[FrameMessage]) -> Result<u64, String> {
+    let mut guard = DB.lock().unwrap();
+    let conn = guard.as_mut().ok_or("Database not initialised")?;
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+    tx.execute("DELETE FROM frames WHERE capture_id = ?1", params![capture_id])
+        .map_err(|e| format!("Failed to clear frames: {}", e))?;
+    capture_chunks::delete_chunks(&tx, capture_id)?;
+
+    insert_frame_rows(&tx, capture_id, frames)?;
+    let added: usize = frames.iter().map(|f| f.bytes.len()).sum();
+    let compacted = capture_chunks::compact_capture(&tx, capture_id)?;
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit: {}", e))?;
+
+    Ok((added as i64 + compacted).max(0) as u64)
+}
+
+/// Get all frames for a capture (loads everything — use sparingly).
+pub fn get_all_frames(capture_id: &str) -> Result<Vec<FrameMessage>, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+
+    let mut stmt = conn
+        .prepare_cached(
+            &format!(
+                "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD} AS payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
+                 FROM frames WHERE capture_id = ?1 ORDER BY rowid"
+            ),
+        )
+        .map_err(|e| format!("Failed to prepare: {}", e))?;
+
+    let rows = stmt
+        .query_map(params![capture_id], |row| row_to_frame(row))
+        .map_err(|e| format!("Failed to query: {}", e))?;
+
+    let mut frames = Vec::new();
+    for row in rows {
+        frames.push(row.map_err(|e| format!("Failed to read row: {}", e))?);
+    }
+    Ok(frames)
+}
+
+// ============================================================================
+// Capture Reader Streaming (chunked reads for playback)
+// ============================================================================
+
+/// Read a chunk of frames starting after the given rowid (forward).
+/// Returns Vec of (rowid, FrameMessage) for position tracking.
+pub fn read_frame_chunk(
+    capture_id: &str,
+    after_rowid: i64,
+    limit: usize,
+) -> Result<Vec<(i64, FrameMessage)>, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+
+    let mut stmt = conn
+        .prepare_cached(
+            &format!(
+                "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD} AS payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
+                 FROM frames WHERE capture_id = ?1 AND rowid > ?2 ORDER BY rowid ASC LIMIT ?3"
+            ),
+        )
+        .map_err(|e| format!("Failed to prepare: {}", e))?;
+
+    let rows = stmt
+        .query_map(params![capture_id, after_rowid, limit as i64], |row| {
+            row_to_frame_with_rowid(row)
+        })
+        .map_err(|e| format!("Failed to query: {}", e))?;
+
+    let mut result = Vec::with_capacity(limit);
+    for row in rows {
+        result.push(row.map_err(|e| format!("Failed to read row: {}", e))?);
+    }
+    Ok(result)
 }
 
 /// Read a chunk of frames before the given rowid (reverse).
@@ -993,8 +1971,10 @@ pub fn read_frame_chunk_reverse(
 
     let mut stmt = conn
         .prepare_cached(
-            "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction
-             FROM frames WHERE capture_id = ?1 AND rowid < ?2 ORDER BY rowid DESC LIMIT ?3",
+            &format!(
+                "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD} AS payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
+                 FROM frames WHERE capture_id = ?1 AND rowid < ?2 ORDER BY rowid DESC LIMIT ?3"
+            ),
         )
         .map_err(|e| format!("Failed to prepare: {}", e))?;
 
@@ -1063,8 +2043,10 @@ pub fn get_frame_at_index(
 
     let result = conn
         .query_row(
-            "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction
-             FROM frames WHERE capture_id = ?1 ORDER BY rowid LIMIT 1 OFFSET ?2",
+            &format!(
+                "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD} AS payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
+                 FROM frames WHERE capture_id = ?1 ORDER BY rowid LIMIT 1 OFFSET ?2"
+            ),
             params![capture_id, index as i64],
             |row| row_to_frame_with_rowid(row),
         )
@@ -1093,7 +2075,7 @@ pub fn get_next_filtered_frame(
 
     let sql = if frame_ids.is_empty() {
         format!(
-            "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction
+            "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD} AS payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
              FROM frames WHERE capture_id = ?1 AND rowid {} ?2 ORDER BY rowid {} LIMIT 1",
             op, order
         )
@@ -1104,7 +2086,7 @@ pub fn get_next_filtered_frame(
             .collect::<Vec<_>>()
             .join(",");
         format!(
-            "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction
+            "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, {FRAME_PAYLOAD} AS payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
              FROM frames WHERE capture_id = ?1 AND rowid {} ?2 AND frame_id IN ({}) ORDER BY rowid {} LIMIT 1",
             op, placeholders, order
         )
@@ -1371,17 +2353,22 @@ pub fn find_bytes_offset_for_timestamp(
 pub fn save_capture_metadata(meta: &CaptureMetadata) -> Result<(), String> {
     let guard = DB.lock().unwrap();
     let conn = guard.as_ref().ok_or("Database not initialised")?;
+    save_capture_metadata_in(conn, meta)
+}
 
+fn save_capture_metadata_in(conn: &Connection, meta: &CaptureMetadata) -> Result<(), String> {
     let kind_str = match &meta.kind {
         CaptureKind::Frames => "frames",
         CaptureKind::Bytes => "bytes",
     };
 
     let buses_json = serde_json::to_string(&meta.buses).unwrap_or_else(|_| "[]".to_string());
+    let timing_json = meta.timing.as_ref().and_then(|t| serde_json::to_string(t).ok());
+    let integrity_json = meta.integrity.as_ref().and_then(|i| serde_json::to_string(i).ok());
 
     conn.execute(
-        "INSERT OR REPLACE INTO capture_metadata (capture_id, capture_kind, name, count, start_time_us, end_time_us, created_at, owning_session_id, persistent, buses)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        "INSERT OR REPLACE INTO capture_metadata (capture_id, capture_kind, name, count, start_time_us, end_time_us, created_at, owning_session_id, persistent, buses, timing, integrity, stored_bytes)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
         params![
             &meta.id,
             kind_str,
@@ -1393,6 +2380,9 @@ pub fn save_capture_metadata(meta: &CaptureMetadata) -> Result<(), String> {
             &meta.owning_session_id,
             meta.persistent as i64,
             buses_json,
+            timing_json,
+            integrity_json,
+            meta.stored_bytes as i64,
         ],
     )
     .map_err(|e| format!("Failed to save capture metadata: {}", e))?;
@@ -1404,9 +2394,12 @@ pub fn save_capture_metadata(meta: &CaptureMetadata) -> Result<(), String> {
 pub fn load_all_capture_metadata() -> Result<Vec<CaptureMetadata>, String> {
     let guard = DB.lock().unwrap();
     let conn = guard.as_ref().ok_or("Database not initialised")?;
+    load_capture_metadata_in(conn)
+}
 
+fn load_capture_metadata_in(conn: &Connection) -> Result<Vec<CaptureMetadata>, String> {
     let mut stmt = conn
-        .prepare("SELECT capture_id, capture_kind, name, count, start_time_us, end_time_us, created_at, owning_session_id, persistent, buses FROM capture_metadata")
+        .prepare("SELECT capture_id, capture_kind, name, count, start_time_us, end_time_us, created_at, owning_session_id, persistent, buses, timing, integrity, stored_bytes FROM capture_metadata")
         .map_err(|e| format!("Failed to prepare: {}", e))?;
 
     let rows = stmt
@@ -1420,6 +2413,12 @@ pub fn load_all_capture_metadata() -> Result<Vec<CaptureMetadata>, String> {
 
             let buses_json: String = row.get::<_, String>("buses").unwrap_or_else(|_| "[]".to_string());
             let buses: Vec<u8> = serde_json::from_str(&buses_json).unwrap_or_default();
+            let timing = row
+                .get::<_, Option<String>>("timing")?
+                .and_then(|json| serde_json::from_str(&json).ok());
+            let integrity = row
+                .get::<_, Option<String>>("integrity")?
+                .and_then(|json| serde_json::from_str(&json).ok());
 
             Ok(CaptureMetadata {
                 id: row.get("capture_id")?,
@@ -1433,6 +2432,9 @@ pub fn load_all_capture_metadata() -> Result<Vec<CaptureMetadata>, String> {
                 owning_session_id: row.get("owning_session_id")?,
                 persistent: row.get::<_, i64>("persistent").unwrap_or(0) != 0,
                 buses,
+                timing,
+                integrity,
+                stored_bytes: row.get::<_, i64>("stored_bytes")?.max(0) as u64,
             })
         })
         .map_err(|e| format!("Failed to query: {}", e))?;
@@ -1509,13 +2511,239 @@ pub fn delete_capture_metadata(capture_id: &str) -> Result<(), String> {
         params![capture_id],
     )
     .map_err(|e| format!("Failed to delete capture metadata: {}", e))?;
+    conn.execute(
+        "DELETE FROM capture_previews WHERE capture_id = ?1",
+        params![capture_id],
+    )
+    .map_err(|e| format!("Failed to delete capture preview: {}", e))?;
+    conn.execute(
+        "DELETE FROM capture_annotations WHERE capture_id = ?1",
+        params![capture_id],
+    )
+    .map_err(|e| format!("Failed to delete capture annotations: {}", e))?;
 
     Ok(())
 }
 
+// ============================================================================
+// Annotations
+// ============================================================================
+
+/// Where an annotation is pinned: a frame (0-based capture order) or a time.
+pub enum AnnotationAnchor {
+    Frame(usize),
+    Time(u64),
+}
+
+fn row_to_annotation(row: &rusqlite::Row) -> rusqlite::Result<CaptureAnnotation> {
+    Ok(CaptureAnnotation {
+        id: row.get("id")?,
+        capture_id: row.get("capture_id")?,
+        timestamp_us: row.get::<_, i64>("timestamp_us")? as u64,
+        frame_index: row.get::<_, Option<i64>>("frame_index")?.map(|i| i as usize),
+        text: row.get("text")?,
+        color: row.get("color")?,
+        created_at: row.get::<_, i64>("created_at")? as u64,
+    })
+}
+
+/// Annotation columns plus the pinned frame's current 0-based index (NULL
+/// when unpinned or the frame has since been trimmed away).
+const ANNOTATION_SELECT: &str = "
+    SELECT a.id, a.capture_id, a.timestamp_us, a.text, a.color, a.created_at,
+           CASE WHEN EXISTS (SELECT 1 FROM frames f WHERE f.rowid = a.frame_rowid AND f.capture_id = a.capture_id)
+                THEN (SELECT COUNT(*) FROM frames f WHERE f.capture_id = a.capture_id AND f.rowid < a.frame_rowid)
+           END AS frame_index
+    FROM capture_annotations a";
+
+/// Pin a note to a frame or timestamp of a capture.
+pub fn add_annotation(
+    capture_id: &str,
+    anchor: AnnotationAnchor,
+    text: &str,
+    color: Option<&str>,
+) -> Result<CaptureAnnotation, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    add_annotation_in(conn, capture_id, anchor, text, color)
+}
+
+fn add_annotation_in(
+    conn: &Connection,
+    capture_id: &str,
+    anchor: AnnotationAnchor,
+    text: &str,
+    color: Option<&str>,
+) -> Result<CaptureAnnotation, String> {
+    let (frame_rowid, timestamp_us): (Option<i64>, i64) = match anchor {
+        AnnotationAnchor::Frame(index) => conn
+            .query_row(
+                "SELECT rowid, timestamp_us FROM frames WHERE capture_id = ?1 ORDER BY rowid LIMIT 1 OFFSET ?2",
+                params![capture_id, index as i64],
+                |row| Ok((Some(row.get(0)?), row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to find frame: {}", e))?
+            .ok_or_else(|| format!("Capture '{}' has no frame {}", capture_id, index))?,
+        AnnotationAnchor::Time(us) => (None, us as i64),
+    };
+    conn.execute(
+        "INSERT INTO capture_annotations (capture_id, timestamp_us, frame_rowid, text, color, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, strftime('%s','now'))",
+        params![capture_id, timestamp_us, frame_rowid, text, color],
+    )
+    .map_err(|e| format!("Failed to add annotation: {}", e))?;
+    let id = conn.last_insert_rowid();
+    conn.query_row(&format!("{ANNOTATION_SELECT} WHERE a.id = ?1"), params![id], row_to_annotation)
+        .map_err(|e| format!("Failed to read annotation: {}", e))
+}
+
+/// A capture's annotations in time order.
+pub fn list_annotations(capture_id: &str) -> Result<Vec<CaptureAnnotation>, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    list_annotations_in(conn, capture_id)
+}
+
+fn list_annotations_in(conn: &Connection, capture_id: &str) -> Result<Vec<CaptureAnnotation>, String> {
+    let mut stmt = conn
+        .prepare(&format!("{ANNOTATION_SELECT} WHERE a.capture_id = ?1 ORDER BY a.timestamp_us, a.id"))
+        .map_err(|e| format!("Failed to prepare: {}", e))?;
+    let rows = stmt
+        .query_map(params![capture_id], row_to_annotation)
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("Failed to list annotations: {}", e))?;
+    Ok(rows)
+}
+
+/// Change an annotation's text and color. Returns false if it doesn't exist.
+pub fn update_annotation(id: i64, text: &str, color: Option<&str>) -> Result<bool, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    conn.execute(
+        "UPDATE capture_annotations SET text = ?2, color = ?3 WHERE id = ?1",
+        params![id, text, color],
+    )
+    .map(|n| n > 0)
+    .map_err(|e| format!("Failed to update annotation: {}", e))
+}
+
+/// Delete an annotation. Returns false if it didn't exist.
+pub fn delete_annotation(id: i64) -> Result<bool, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    conn.execute("DELETE FROM capture_annotations WHERE id = ?1", params![id])
+        .map(|n| n > 0)
+        .map_err(|e| format!("Failed to delete annotation: {}", e))
+}
+
+// ============================================================================
+// Capture Files (persist_capture / open_persisted_capture)
+//
+// A capture file is a standalone SQLite database with the capture schema and
+// exactly one capture, so a capture can live outside buffers.db (surviving
+// "clear captures on start" and app reinstalls) and be opened again later.
+// Data moves with INSERT ... SELECT across an ATTACH, never through memory.
+// ============================================================================
+
+/// Schema name a capture file is attached under.
+const CAPTURE_FILE_SCHEMA: &str = "capture_file";
+
+fn attach_capture_file(conn: &Connection, path: &Path) -> Result<(), String> {
+    conn.execute(
+        &format!("ATTACH DATABASE ?1 AS {CAPTURE_FILE_SCHEMA}"),
+        params![path.to_string_lossy()],
+    )
+    .map(|_| ())
+    .map_err(|e| format!("Failed to attach '{}': {}", path.display(), e))
+}
+
+fn detach_capture_file(conn: &Connection) {
+    if let Err(e) = conn.execute(&format!("DETACH DATABASE {CAPTURE_FILE_SCHEMA}"), []) {
+        tlog!("[capture_db] Failed to detach capture file: {}", e);
+    }
+}
+
+/// Write one capture to a capture file at `path`, replacing any existing
+/// file. Returns the number of rows written.
+pub fn write_capture_file(meta: &CaptureMetadata, path: &Path) -> Result<usize, String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    write_capture_file_in(conn, meta, path)
+}
+
+fn write_capture_file_in(conn: &Connection, meta: &CaptureMetadata, path: &Path) -> Result<usize, String> {
+    if path.exists() {
+        std::fs::remove_file(path)
+            .map_err(|e| format!("Failed to replace '{}': {}", path.display(), e))?;
+    }
+    let mut file = Connection::open(path)
+        .map_err(|e| format!("Failed to create capture file '{}': {}", path.display(), e))?;
+    run_migrations(&mut file)?;
+    save_capture_metadata_in(
+        &file,
+        &CaptureMetadata { is_streaming: false, owning_session_id: None, ..meta.clone() },
+    )?;
+    drop(file);
+
+    attach_capture_file(conn, path)?;
+    let result = copy_rows(conn, "main", CAPTURE_FILE_SCHEMA, &meta.id, &meta.id);
+    detach_capture_file(conn);
+    result
+}
+
+/// Copy the capture held in the capture file at `path` into the database as
+/// `new_id`. Files written by older versions are migrated in place; files
+/// from newer versions are refused. Returns the file's metadata (still
+/// carrying the id it was saved under) and the number of rows copied.
+pub fn read_capture_file(path: &Path, new_id: &str) -> Result<(CaptureMetadata, usize), String> {
+    let guard = DB.lock().unwrap();
+    let conn = guard.as_ref().ok_or("Database not initialised")?;
+    read_capture_file_in(conn, path, new_id)
+}
+
+fn read_capture_file_in(conn: &Connection, path: &Path, new_id: &str) -> Result<(CaptureMetadata, usize), String> {
+    if !path.is_file() {
+        return Err(format!("Capture file '{}' not found", path.display()));
+    }
+    let mut file = Connection::open(path)
+        .map_err(|e| format!("Failed to open capture file '{}': {}", path.display(), e))?;
+    let version = schema_version(&file)
+        .map_err(|_| format!("'{}' is not a WireTAP capture file", path.display()))?;
+    if version == 0 {
+        return Err(format!("'{}' is not a WireTAP capture file", path.display()));
+    }
+    if version > MIGRATIONS.len() as i64 {
+        return Err(format!(
+            "'{}' was saved by a newer version of WireTAP (schema {}, this build supports {})",
+            path.display(),
+            version,
+            MIGRATIONS.len()
+        ));
+    }
+    run_migrations(&mut file)?;
+    let mut metas = load_capture_metadata_in(&file)?;
+    if metas.len() != 1 {
+        return Err(format!(
+            "'{}' holds {} captures; a capture file holds exactly one",
+            path.display(),
+            metas.len()
+        ));
+    }
+    let meta = metas.remove(0);
+    drop(file);
+
+    attach_capture_file(conn, path)?;
+    let result = copy_rows(conn, CAPTURE_FILE_SCHEMA, "main", &meta.id, new_id);
+    detach_capture_file(conn);
+    Ok((meta, result?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::io::test_frame;
+    use crate::capture_store::{ByteMask, IdRange};
 
     /// The pre-rename schema as shipped before April 2026 — used to build
     /// legacy databases the baseline must normalise.
@@ -1573,11 +2801,32 @@ mod tests {
         let mut conn = Connection::open_in_memory().unwrap();
         run_migrations(&mut conn).unwrap();
 
-        assert_eq!(version_of(&conn), 1);
-        assert_eq!(audit_rows(&conn), vec![(1, "baseline_capture_schema".to_string())]);
+        assert_eq!(version_of(&conn), 10);
+        assert_eq!(
+            audit_rows(&conn),
+            vec![
+                (1, "baseline_capture_schema".to_string()),
+                (2, "frame_linkage".to_string()),
+                (3, "capture_timing".to_string()),
+                (4, "capture_integrity".to_string()),
+                (5, "capture_previews".to_string()),
+                (6, "capture_annotations".to_string()),
+                (7, "frame_clock".to_string()),
+                (8, "frame_error".to_string()),
+                (9, "frame_brs".to_string()),
+                (10, "frame_chunks".to_string()),
+            ]
+        );
         assert!(has_column(&conn, "frames", "capture_id").unwrap());
         assert!(has_column(&conn, "capture_metadata", "persistent").unwrap());
         assert!(has_column(&conn, "capture_metadata", "buses").unwrap());
+        assert!(has_column(&conn, "capture_metadata", "timing").unwrap());
+        assert!(has_column(&conn, "capture_metadata", "integrity").unwrap());
+        assert!(has_column(&conn, "frames", "clock").unwrap());
+        assert!(has_column(&conn, "frames", "error_class").unwrap());
+        assert!(has_column(&conn, "frames", "brs").unwrap());
+        assert!(has_column(&conn, "frames", "chunk_id").unwrap());
+        assert!(has_column(&conn, "capture_metadata", "stored_bytes").unwrap());
     }
 
     #[test]
@@ -1595,17 +2844,19 @@ mod tests {
 
         run_migrations(&mut conn).unwrap();
 
-        assert_eq!(version_of(&conn), 1);
+        assert_eq!(version_of(&conn), 10);
         assert!(!has_column(&conn, "frames", "buffer_id").unwrap());
-        let (name, count): (String, i64) = conn
+        let (name, count, stored): (String, i64, i64) = conn
             .query_row(
-                "SELECT name, count FROM capture_metadata WHERE capture_id = 'b1'",
+                "SELECT name, count, stored_bytes FROM capture_metadata WHERE capture_id = 'b1'",
                 [],
-                |r| Ok((r.get(0)?, r.get(1)?)),
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
             )
             .unwrap();
         assert_eq!(name, "old capture");
         assert_eq!(count, 2);
+        // Backfilled from the inline payloads
+        assert_eq!(stored, 8);
         let frames: i64 = conn
             .query_row("SELECT COUNT(*) FROM frames WHERE capture_id = 'b1'", [], |r| r.get(0))
             .unwrap();
@@ -1639,7 +2890,7 @@ mod tests {
 
         run_migrations(&mut conn).unwrap();
 
-        assert_eq!(version_of(&conn), 1);
+        assert_eq!(version_of(&conn), 10);
         // Legacy husk gone, migrated (pinned) data untouched.
         let legacy_tables: i64 = conn
             .query_row(
@@ -1665,8 +2916,180 @@ mod tests {
         run_migrations(&mut conn).unwrap();
         run_migrations(&mut conn).unwrap();
 
-        assert_eq!(version_of(&conn), 1);
-        assert_eq!(audit_rows(&conn).len(), 1);
+        assert_eq!(version_of(&conn), 10);
+        assert_eq!(audit_rows(&conn).len(), 10);
+    }
+
+    /// Migrate a test database and register the chunk SQL functions, as
+    /// `initialise` does.
+    fn migrate(conn: &mut Connection) {
+        run_migrations(conn).unwrap();
+        capture_chunks::register_functions(conn).unwrap();
+    }
+
+    fn linked_frame(ts: u64, link: Option<FrameLink>) -> FrameMessage {
+        FrameMessage { link, ..test_frame(0x7E8, ts, &[0; 8]) }
+    }
+
+    #[test]
+    fn frame_links_resolve_parent_rowid() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+        assert!(has_column(&conn, "frames", "correlation_id").unwrap());
+
+        let link = |role| Some(FrameLink { correlation_id: 42, role, parent_rowid: None });
+        let tx = conn.transaction().unwrap();
+        insert_frame_rows(
+            &tx,
+            "c1",
+            &[
+                linked_frame(1, None),
+                linked_frame(2, link(LinkRole::Request)),
+                linked_frame(3, link(LinkRole::Response)),
+            ],
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
+                 FROM frames WHERE capture_id = 'c1' ORDER BY rowid",
+            )
+            .unwrap();
+        let rows: Vec<(i64, FrameMessage)> = stmt
+            .query_map([], |row| row_to_frame_with_rowid(row))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+
+        assert!(rows[0].1.link.is_none());
+        let request = rows[1].1.link.clone().unwrap();
+        assert_eq!(request.role, LinkRole::Request);
+        assert_eq!(request.parent_rowid, None);
+        let response = rows[2].1.link.clone().unwrap();
+        assert_eq!(response.correlation_id, 42);
+        assert_eq!(response.parent_rowid, Some(rows[1].0));
+    }
+
+    #[test]
+    fn frame_clock_round_trips() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+
+        let mut hardware = linked_frame(1, None);
+        hardware.clock = Some(FrameClock::Hardware);
+        let tx = conn.transaction().unwrap();
+        insert_frame_rows(&tx, "c1", &[hardware, linked_frame(2, None)]).unwrap();
+        tx.commit().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
+                 FROM frames WHERE capture_id = 'c1' ORDER BY rowid",
+            )
+            .unwrap();
+        let clocks: Vec<Option<FrameClock>> = stmt
+            .query_map([], |row| row_to_frame(row).map(|f| f.clock))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(clocks, vec![Some(FrameClock::Hardware), None]);
+    }
+
+    #[test]
+    fn error_frames_round_trip() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+
+        let bus_off = crate::io::can_error::error_frame(0x2000_0040, &[0; 8], 0, 1);
+        let tx = conn.transaction().unwrap();
+        insert_frame_rows(&tx, "c1", &[bus_off, linked_frame(2, None)]).unwrap();
+        tx.commit().unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT rowid, protocol, timestamp_us, frame_id, bus, dlc, payload, is_extended, is_fd, source_address, incomplete, direction, correlation_id, link_role, parent_rowid, clock, error_class, brs
+                 FROM frames WHERE capture_id = 'c1' ORDER BY rowid",
+            )
+            .unwrap();
+        let errors: Vec<(Option<bool>, Option<CanErrorClass>)> = stmt
+            .query_map([], |row| row_to_frame(row).map(|f| (f.is_error, f.error_class)))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(errors, vec![(Some(true), Some(CanErrorClass::BusOff)), (None, None)]);
+    }
+
+    #[test]
+    fn row_model_groups_sorts_and_locates_rows() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+
+        let frame = |ts: u64, id: u32, bus: u8| FrameMessage { frame_id: id, bus, ..linked_frame(ts, None) };
+        let tx = conn.transaction().unwrap();
+        insert_frame_rows(
+            &tx,
+            "c1",
+            &[frame(1, 0x200, 0), frame(2, 0x100, 0), frame(3, 0x200, 0), frame(4, 0x100, 1), frame(5, 0x200, 0)],
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        // Ungrouped, bus 0 only, by ID: 0x100@2, then 0x200 @1,3,5 in capture order
+        let spec = RowModelSpec { buses: vec![0], sort: RowSortKey::FrameId, ..Default::default() };
+        assert_eq!(row_model_count_in(&conn, "c1", &spec).unwrap(), 4);
+        let rows = row_model_rows_in(&conn, "c1", &spec, 1, 2).unwrap();
+        let ts: Vec<u64> = rows.iter().map(|r| r.frame.timestamp_us).collect();
+        assert_eq!(ts, vec![1, 3]);
+        assert_eq!(row_model_position_in(&conn, "c1", &spec, rows[1].capture_index).unwrap(), Some(2));
+        // Frame on bus 1 is filtered out
+        assert_eq!(row_model_position_in(&conn, "c1", &spec, 4).unwrap(), None);
+
+        // Grouped by ID, most frequent first; rows show the latest frame
+        let spec = RowModelSpec {
+            sort: RowSortKey::Count,
+            descending: true,
+            group_by_id: true,
+            ..Default::default()
+        };
+        assert_eq!(row_model_count_in(&conn, "c1", &spec).unwrap(), 3);
+        let rows = row_model_rows_in(&conn, "c1", &spec, 0, 10).unwrap();
+        assert_eq!(rows[0].frame.frame_id, 0x200);
+        assert_eq!(rows[0].count, Some(3));
+        assert_eq!(rows[0].first_us, Some(1));
+        assert_eq!(rows[0].frame.timestamp_us, 5);
+        // Any frame of a group locates the group's row
+        assert_eq!(row_model_position_in(&conn, "c1", &spec, 1).unwrap(), Some(0));
+    }
+
+    #[test]
+    fn preview_cells_bucket_counts_per_bus() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+
+        let frame = |ts: u64, id: u32, bus: u8| FrameMessage { frame_id: id, bus, ..linked_frame(ts, None) };
+        let tx = conn.transaction().unwrap();
+        insert_frame_rows(
+            &tx,
+            "c1",
+            &[frame(100, 0x1, 0), frame(150, 0x1, 0), frame(160, 0x2, 0), frame(120, 0x1, 1), frame(399, 0x3, 0)],
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        // Span 100..=399 over 3 buckets -> 100 us each
+        let (span, cells) = preview_cells_in(&conn, "c1", false, 3).unwrap();
+        assert_eq!(span, Some((100, 399)));
+        assert_eq!(
+            cells,
+            vec![
+                PreviewCell { bucket: 0, bus: 0, count: 3, unique_ids: 2 },
+                PreviewCell { bucket: 0, bus: 1, count: 1, unique_ids: 1 },
+                PreviewCell { bucket: 2, bus: 0, count: 1, unique_ids: 1 },
+            ]
+        );
+        assert_eq!(preview_cells_in(&conn, "empty", false, 3).unwrap(), (None, Vec::new()));
     }
 
     #[test]
@@ -1675,4 +3098,322 @@ mod tests {
             assert_eq!(m.version, i as i64 + 1, "MIGRATIONS must be contiguous from 1");
         }
     }
+
+    #[test]
+    fn trim_keeps_or_removes_a_time_range() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+        let frame = |ts: u64, bus: u8, id: u32| FrameMessage { bus, ..test_frame(id, ts, &[0]) };
+        let tx = conn.transaction().unwrap();
+        let frames: Vec<_> = (0..10).map(|i| frame(i * 1000, (i % 2) as u8, 0x100 + i as u32)).collect();
+        insert_frame_rows(&tx, "cap", &frames).unwrap();
+        insert_frame_rows(&tx, "other", &frames).unwrap();
+
+        // Keep 2000..=5000
+        let summary = trim_rows(&tx, "cap", false, Some(2000), Some(5000), true).unwrap();
+        assert_eq!((summary.removed, summary.count), (6, 4));
+        assert_eq!((summary.start_time_us, summary.end_time_us), (Some(2000), Some(5000)));
+        assert_eq!(summary.buses, vec![0, 1]);
+        assert_eq!(summary.frame_keys.len(), 4);
+
+        // Remove everything up to 3000 (open start)
+        let summary = trim_rows(&tx, "cap", false, None, Some(3000), false).unwrap();
+        assert_eq!((summary.removed, summary.count), (2, 2));
+        assert_eq!(summary.start_time_us, Some(4000));
+
+        // Other captures are untouched
+        let other: i64 = tx
+            .query_row("SELECT COUNT(*) FROM frames WHERE capture_id = 'other'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(other, 10);
+    }
+
+    #[test]
+    fn trim_head_keeps_the_newest_frames() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+        let segment = || Some(FrameLink { correlation_id: 7, role: LinkRole::Segment, parent_rowid: None });
+        let tx = conn.transaction().unwrap();
+        let frames: Vec<_> = (0..10)
+            .map(|i| linked_frame(i * 1000, if (3..6).contains(&i) { segment() } else { None }))
+            .collect();
+        insert_frame_rows(&tx, "cap", &frames).unwrap();
+
+        // Count limit: the group's first frame (ts 3000) goes, the rest relink
+        assert_eq!(trim_head_rows(&tx, "cap", Some(6), None).unwrap(), (4, Some(4000)));
+        let parents: Vec<Option<i64>> = tx
+            .prepare("SELECT parent_rowid FROM frames WHERE correlation_id = 7 ORDER BY rowid")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let head: i64 = tx
+            .query_row("SELECT MIN(rowid) FROM frames WHERE correlation_id = 7", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(parents, vec![None, Some(head)]);
+
+        // Duration limit, then a no-op once within both limits
+        assert_eq!(trim_head_rows(&tx, "cap", Some(6), Some(7000)).unwrap(), (3, Some(7000)));
+        assert_eq!(trim_head_rows(&tx, "cap", Some(100), None).unwrap(), (0, Some(7000)));
+    }
+
+    #[test]
+    fn frames_split_into_protocol_sub_streams() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+        let tagged = |ts: u64, protocol: &str, is_fd: bool| FrameMessage {
+            protocol: protocol.to_string(),
+            is_fd,
+            ..linked_frame(ts, None)
+        };
+        let tx = conn.transaction().unwrap();
+        let frames = vec![
+            tagged(0, "can", false),
+            tagged(1, "serial", false),
+            tagged(2, "can", true),
+            tagged(3, "modbus", false),
+            tagged(4, "can", false),
+            tagged(5, "serial", false),
+        ];
+        insert_frame_rows(&tx, "mixed", &frames).unwrap();
+
+        assert_eq!(
+            protocol_counts_in(&tx, "mixed").unwrap(),
+            vec![(Protocol::Can, 2), (Protocol::CanFd, 1), (Protocol::Modbus, 1), (Protocol::Serial, 2)]
+        );
+
+        let (page, _, total) =
+            get_frames_paginated_by_protocol_in(&tx, "mixed", &[Protocol::Serial], 1, 10).unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(page.iter().map(|f| f.timestamp_us).collect::<Vec<_>>(), vec![5]);
+
+        let (page, _, total) =
+            get_frames_paginated_by_protocol_in(&tx, "mixed", &[Protocol::Can, Protocol::CanFd], 0, 10).unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(page.iter().map(|f| f.timestamp_us).collect::<Vec<_>>(), vec![0, 2, 4]);
+
+        let (_, _, total) = get_frames_paginated_by_protocol_in(&tx, "mixed", &[], 0, 10).unwrap();
+        assert_eq!(total, 6);
+    }
+
+    #[test]
+    fn merged_captures_interleave_by_timestamp() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+        let on_bus = |ts: u64, bus: u8| FrameMessage { bus, ..linked_frame(ts, None) };
+        let tx = conn.transaction().unwrap();
+        insert_frame_rows(&tx, "a", &[on_bus(10, 0), on_bus(30, 0), on_bus(50, 1)]).unwrap();
+        insert_frame_rows(&tx, "b", &[on_bus(20, 0), on_bus(30, 0)]).unwrap();
+        tx.commit().unwrap();
+
+        let keep = HashMap::new();
+        let renumber = HashMap::from([(0u8, 2u8)]);
+        assert_eq!(merge_rows(&conn, &[("a", &keep), ("b", &renumber)], "ab").unwrap(), 5);
+
+        let (frames, _, total) = get_frames_paginated_by_protocol_in(&conn, "ab", &[], 0, 10).unwrap();
+        assert_eq!(total, 5);
+        let order: Vec<(u64, u8)> = frames.iter().map(|f| (f.timestamp_us, f.bus)).collect();
+        assert_eq!(order, vec![(10, 0), (20, 2), (30, 0), (30, 2), (50, 1)]);
+    }
+
+    #[test]
+    fn range_copy_leaves_the_source_untouched() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+        let tx = conn.transaction().unwrap();
+        let frames: Vec<FrameMessage> = (0..10).map(|ts| linked_frame(ts * 100, None)).collect();
+        insert_frame_rows(&tx, "long", &frames).unwrap();
+
+        let summary = copy_range_rows(&tx, "long", "window", false, Some(250), Some(600)).unwrap();
+        assert_eq!(summary.count, 4);
+        assert_eq!((summary.start_time_us, summary.end_time_us), (Some(300), Some(600)));
+        assert_eq!(summary.frame_keys, vec![(0, 0x7E8)]);
+
+        let head = copy_range_rows(&tx, "long", "head", false, None, Some(100)).unwrap();
+        assert_eq!(head.count, 2);
+        let (_, _, total) = get_frames_paginated_by_protocol_in(&tx, "long", &[], 0, 100).unwrap();
+        assert_eq!(total, 10);
+    }
+
+    #[test]
+    fn pattern_search_combines_ranges_masks_and_payload() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+        let with = |frame_id: u32, bytes: &[u8]| FrameMessage {
+            frame_id,
+            dlc: bytes.len() as u8,
+            bytes: bytes.to_vec(),
+            ..linked_frame(0, None)
+        };
+        let tx = conn.transaction().unwrap();
+        let frames = vec![
+            with(0x100, &[0x00, 0x00, 0x00, 0x2A]),
+            with(0x200, &[0x00, 0x00, 0x00, 0x25, 0xDE, 0xAD]),
+            with(0x300, &[0x00, 0x00, 0x00, 0x2F]),
+            with(0x200, &[0x00, 0x00]),
+            with(0x500, &[0x0D, 0xEA, 0xD0]),
+        ];
+        insert_frame_rows(&tx, "hunt", &frames).unwrap();
+
+        let search = |pattern: FramePattern, ids: &[u32]| search_frames_by_pattern_in(&tx, "hunt", &pattern, ids, 100).unwrap();
+        let masked = FramePattern {
+            byte_masks: vec![ByteMask { index: 3, mask: 0xF0, value: 0x20 }],
+            ..Default::default()
+        };
+        assert_eq!(search(masked.clone(), &[]), vec![0, 1, 2]);
+        assert_eq!(search(masked.clone(), &[0x200]), vec![0]);
+
+        let ranged = FramePattern { id_ranges: vec![IdRange { from: 0x1FF, to: 0x300 }], ..masked };
+        assert_eq!(search(ranged, &[]), vec![1, 2]);
+
+        // Byte-aligned: "DEAD" doesn't match the nibble-shifted 0D EA D0
+        let needle = FramePattern { payload_hex: Some("de ad".to_string()), ..Default::default() };
+        assert_eq!(search(needle, &[]), vec![1]);
+    }
+
+    #[test]
+    fn cold_payloads_are_chunked_and_read_back() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+        let tx = conn.transaction().unwrap();
+        let total = capture_chunks::HOT_TAIL_FRAMES + capture_chunks::CHUNK_FRAMES + 10;
+        let frames: Vec<FrameMessage> = (0..total as u64)
+            .map(|i| FrameMessage {
+                bytes: [(i as u32).to_be_bytes(), [0xA5; 4]].concat(),
+                ..linked_frame(i, None)
+            })
+            .collect();
+        insert_frame_rows(&tx, "cold", &frames).unwrap();
+        let delta = capture_chunks::compact_capture(&tx, "cold").unwrap();
+        assert!(delta < 0);
+
+        let chunked: i64 = tx
+            .query_row("SELECT COUNT(*) FROM frames WHERE capture_id = 'cold' AND chunk_id IS NOT NULL", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(chunked as usize, capture_chunks::CHUNK_FRAMES);
+        let raw = (total * 8) as i64;
+        assert_eq!(capture_chunks::stored_bytes(&tx, "cold").unwrap() as i64, raw + delta);
+
+        // Pages spanning the chunk boundary decode in order
+        let boundary = capture_chunks::CHUNK_FRAMES - 2;
+        let (page, _, count) = get_frames_paginated_by_protocol_in(&tx, "cold", &[], boundary, 4).unwrap();
+        assert_eq!(count, total);
+        let expected: Vec<&[u8]> = frames[boundary..boundary + 4].iter().map(|f| f.bytes.as_slice()).collect();
+        assert_eq!(page.iter().map(|f| f.bytes.as_slice()).collect::<Vec<_>>(), expected);
+
+        // Payload filters see through the chunk
+        let needle = FramePattern { payload_hex: Some("0000002A".to_string()), ..Default::default() };
+        assert_eq!(search_frames_by_pattern_in(&tx, "cold", &needle, &[], 10).unwrap(), vec![42]);
+
+        // Copies carry the payloads, and trims drop emptied chunks
+        tx.commit().unwrap();
+        copy_rows(&conn, "main", "main", "cold", "copy").unwrap();
+        let (copied, _, _) = get_frames_paginated_by_protocol_in(&conn, "copy", &[], 7, 1).unwrap();
+        assert_eq!(copied[0].bytes, frames[7].bytes);
+        let tx = conn.transaction().unwrap();
+        trim_head_rows(&tx, "cold", Some(capture_chunks::HOT_TAIL_FRAMES), None).unwrap();
+        let chunks: i64 = tx
+            .query_row("SELECT COUNT(*) FROM frame_chunks WHERE capture_id = 'cold'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(chunks, 0);
+    }
+
+    #[test]
+    fn annotations_follow_their_frame_and_copies() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+        let tx = conn.transaction().unwrap();
+        let frames: Vec<FrameMessage> = (0..5).map(|ts| linked_frame(ts * 10, None)).collect();
+        insert_frame_rows(&tx, "door", &frames).unwrap();
+        tx.commit().unwrap();
+
+        let opened = add_annotation_in(&conn, "door", AnnotationAnchor::Frame(3), "door opened", Some("#f00")).unwrap();
+        assert_eq!((opened.timestamp_us, opened.frame_index), (30, Some(3)));
+        add_annotation_in(&conn, "door", AnnotationAnchor::Time(5), "ignition", None).unwrap();
+        assert!(add_annotation_in(&conn, "door", AnnotationAnchor::Frame(9), "past the end", None).is_err());
+
+        // Trimming the head shifts the pinned frame's index
+        conn.execute("DELETE FROM frames WHERE capture_id = 'door' AND timestamp_us < 20", []).unwrap();
+        let listed = list_annotations_in(&conn, "door").unwrap();
+        assert_eq!(listed.iter().map(|a| a.text.as_str()).collect::<Vec<_>>(), vec!["ignition", "door opened"]);
+        assert_eq!(listed[1].frame_index, Some(1));
+
+        // Copies keep the notes, anchored by time only
+        copy_rows(&conn, "main", "main", "door", "door2").unwrap();
+        let copied = list_annotations_in(&conn, "door2").unwrap();
+        assert_eq!(copied.len(), 2);
+        assert_eq!((copied[1].timestamp_us, copied[1].frame_index), (30, None));
+    }
+
+    fn capture_meta(id: &str, count: usize) -> CaptureMetadata {
+        CaptureMetadata {
+            id: id.to_string(),
+            kind: CaptureKind::Frames,
+            name: "Bench run".to_string(),
+            count,
+            start_time_us: Some(0),
+            end_time_us: Some(4000),
+            created_at: 1_700_000_000,
+            is_streaming: true,
+            owning_session_id: Some("session".to_string()),
+            persistent: false,
+            buses: vec![0],
+            timing: None,
+            integrity: None,
+            stored_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn capture_files_round_trip_with_links() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn);
+        let frames: Vec<_> = (0..5u32)
+            .map(|i| FrameMessage {
+                link: (i < 2).then(|| FrameLink { correlation_id: 7, role: LinkRole::Request, parent_rowid: None }),
+                ..test_frame(0x7E0 + (i % 2) * 8, i as u64 * 1000, &[i as u8])
+            })
+            .collect();
+        let tx = conn.transaction().unwrap();
+        insert_frame_rows(&tx, "other", &frames[..1]).unwrap();
+        insert_frame_rows(&tx, "cap", &frames).unwrap();
+        tx.commit().unwrap();
+
+        let path = std::env::temp_dir().join(format!("wiretap-capfile-{}.db", std::process::id()));
+        assert_eq!(write_capture_file_in(&conn, &capture_meta("cap", 5), &path).unwrap(), 5);
+        let (meta, copied) = read_capture_file_in(&conn, &path, "opened").unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!((meta.id.as_str(), meta.name.as_str(), copied), ("cap", "Bench run", 5));
+        assert!(meta.owning_session_id.is_none() && !meta.is_streaming);
+        let payloads: Vec<Vec<u8>> = conn
+            .prepare("SELECT payload FROM frames WHERE capture_id = 'opened' ORDER BY rowid")
+            .unwrap()
+            .query_map([], |r| r.get(0))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(payloads, (0..5u8).map(|i| vec![i]).collect::<Vec<_>>());
+        // The second linked frame points at the first frame of the opened copy
+        let (first, parent): (i64, i64) = conn
+            .query_row(
+                "SELECT MIN(rowid), MAX(parent_rowid) FROM frames WHERE capture_id = 'opened'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(parent, first);
+    }
+
+    #[test]
+    fn refuses_files_that_are_not_capture_files() {
+        let conn = Connection::open_in_memory().unwrap();
+        let path = std::env::temp_dir().join(format!("wiretap-notcap-{}.db", std::process::id()));
+        Connection::open(&path).unwrap().execute_batch("CREATE TABLE t (x INTEGER);").unwrap();
+        let err = read_capture_file_in(&conn, &path, "x").unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        assert!(err.contains("not a WireTAP capture file"), "{err}");
+        assert!(read_capture_file_in(&conn, &path, "x").unwrap_err().contains("not found"));
+    }
 }
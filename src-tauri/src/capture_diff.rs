@@ -0,0 +1,285 @@
+// ui/src-tauri/src/capture_diff.rs
+//
+// Per-ID comparison of two frame captures — the "feature off" vs "feature on"
+// workflow of reverse engineering. Each capture is streamed once in rowid
+// chunks into a rollup per (frame ID, extended): count, time span and, for
+// every byte position, the set of values seen. The diff then reports IDs
+// present in only one capture, byte positions whose value sets differ, and
+// rates that moved by more than RATE_TOLERANCE.
+//
+// IDs are matched across buses: captures of the same car taken on different
+// days rarely agree on bus numbering.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::capture_db;
+use crate::capture_store::{self, CaptureKind};
+use crate::io::FrameMessage;
+
+/// Frames read per chunk while building a rollup.
+const DIFF_CHUNK: usize = 10_000;
+
+/// Relative rate change below which an ID's rate counts as unchanged.
+const RATE_TOLERANCE: f64 = 0.1;
+
+/// Values listed per side of a byte difference (the distinct counts are exact).
+const MAX_LISTED_VALUES: usize = 16;
+
+type IdKey = (u32, bool);
+
+/// 256-bit set of the values one byte position has taken.
+#[derive(Clone, Default)]
+struct ValueSet([u64; 4]);
+
+impl ValueSet {
+    fn insert(&mut self, v: u8) {
+        self.0[(v >> 6) as usize] |= 1 << (v & 63);
+    }
+
+    fn contains(&self, v: u8) -> bool {
+        self.0[(v >> 6) as usize] & (1 << (v & 63)) != 0
+    }
+
+    fn len(&self) -> u16 {
+        self.0.iter().map(|w| w.count_ones() as u16).sum()
+    }
+
+    /// Values in `self` but not in `other`, ascending, at most `limit`.
+    fn minus(&self, other: &ValueSet, limit: usize) -> Vec<u8> {
+        (0..=255u8).filter(|&v| self.contains(v) && !other.contains(v)).take(limit).collect()
+    }
+}
+
+/// Rollup of one frame ID in one capture.
+#[derive(Default)]
+struct IdStats {
+    count: u64,
+    first_us: u64,
+    last_us: u64,
+    bytes: Vec<ValueSet>,
+}
+
+impl IdStats {
+    fn add(&mut self, frame: &FrameMessage) {
+        if self.count == 0 {
+            self.first_us = frame.timestamp_us;
+        }
+        self.count += 1;
+        self.first_us = self.first_us.min(frame.timestamp_us);
+        self.last_us = self.last_us.max(frame.timestamp_us);
+        if self.bytes.len() < frame.bytes.len() {
+            self.bytes.resize(frame.bytes.len(), ValueSet::default());
+        }
+        for (set, &b) in self.bytes.iter_mut().zip(&frame.bytes) {
+            set.insert(b);
+        }
+    }
+
+    /// Mean frames per second over the ID's span (None below two frames).
+    fn rate_hz(&self) -> Option<f64> {
+        let span_us = self.last_us.saturating_sub(self.first_us);
+        (self.count >= 2 && span_us > 0).then(|| (self.count - 1) as f64 * 1_000_000.0 / span_us as f64)
+    }
+}
+
+/// An ID seen in only one of the captures.
+#[derive(Clone, Debug, Serialize)]
+pub struct IdSummary {
+    pub frame_id: u32,
+    pub is_extended: bool,
+    pub count: u64,
+    pub rate_hz: Option<f64>,
+}
+
+/// A byte position whose observed values differ between the captures.
+/// A position beyond one capture's payload length has no values on that side.
+#[derive(Clone, Debug, Serialize)]
+pub struct ByteDiff {
+    pub index: usize,
+    pub distinct_a: u16,
+    pub distinct_b: u16,
+    /// Values seen only in capture A (at most MAX_LISTED_VALUES)
+    pub only_in_a: Vec<u8>,
+    /// Values seen only in capture B (at most MAX_LISTED_VALUES)
+    pub only_in_b: Vec<u8>,
+}
+
+/// An ID present in both captures whose bytes or rate differ.
+#[derive(Clone, Debug, Serialize)]
+pub struct IdDiff {
+    pub frame_id: u32,
+    pub is_extended: bool,
+    pub count_a: u64,
+    pub count_b: u64,
+    pub rate_a_hz: Option<f64>,
+    pub rate_b_hz: Option<f64>,
+    /// Rates differ by more than RATE_TOLERANCE of the larger
+    pub rate_changed: bool,
+    pub bytes: Vec<ByteDiff>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CaptureDiff {
+    pub capture_a: String,
+    pub capture_b: String,
+    pub only_in_a: Vec<IdSummary>,
+    pub only_in_b: Vec<IdSummary>,
+    pub changed: Vec<IdDiff>,
+    /// IDs present in both captures with identical value sets and rate
+    pub unchanged: usize,
+}
+
+fn rollup(capture_id: &str) -> Result<HashMap<IdKey, IdStats>, String> {
+    match capture_store::get_capture_kind(capture_id) {
+        Some(CaptureKind::Frames) => {}
+        _ => return Err(msg!(CAPTURE_NOT_FRAMES, capture_id = capture_id).into()),
+    }
+    let mut stats: HashMap<IdKey, IdStats> = HashMap::new();
+    let mut cursor = 0i64;
+    loop {
+        let rows = capture_db::read_frame_chunk(capture_id, cursor, DIFF_CHUNK)?;
+        for (_, frame) in &rows {
+            stats.entry((frame.frame_id, frame.is_extended)).or_default().add(frame);
+        }
+        match rows.last() {
+            Some((rowid, _)) if rows.len() == DIFF_CHUNK => cursor = *rowid,
+            _ => break,
+        }
+    }
+    Ok(stats)
+}
+
+fn summary(key: &IdKey, stats: &IdStats) -> IdSummary {
+    IdSummary { frame_id: key.0, is_extended: key.1, count: stats.count, rate_hz: stats.rate_hz() }
+}
+
+fn rate_changed(a: Option<f64>, b: Option<f64>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => (a - b).abs() > RATE_TOLERANCE * a.max(b),
+        (None, None) => false,
+        _ => true,
+    }
+}
+
+fn diff_rollups(a: &HashMap<IdKey, IdStats>, b: &HashMap<IdKey, IdStats>) -> (Vec<IdSummary>, Vec<IdSummary>, Vec<IdDiff>, usize) {
+    let mut only_in_a: Vec<IdSummary> = a.iter().filter(|(k, _)| !b.contains_key(k)).map(|(k, s)| summary(k, s)).collect();
+    let mut only_in_b: Vec<IdSummary> = b.iter().filter(|(k, _)| !a.contains_key(k)).map(|(k, s)| summary(k, s)).collect();
+    let mut changed = Vec::new();
+    let mut unchanged = 0;
+
+    let empty = ValueSet::default();
+    for (key, sa) in a {
+        let Some(sb) = b.get(key) else { continue };
+        let bytes: Vec<ByteDiff> = (0..sa.bytes.len().max(sb.bytes.len()))
+            .filter_map(|i| {
+                let va = sa.bytes.get(i).unwrap_or(&empty);
+                let vb = sb.bytes.get(i).unwrap_or(&empty);
+                (va.0 != vb.0).then(|| ByteDiff {
+                    index: i,
+                    distinct_a: va.len(),
+                    distinct_b: vb.len(),
+                    only_in_a: va.minus(vb, MAX_LISTED_VALUES),
+                    only_in_b: vb.minus(va, MAX_LISTED_VALUES),
+                })
+            })
+            .collect();
+        let (rate_a_hz, rate_b_hz) = (sa.rate_hz(), sb.rate_hz());
+        let rate_changed = rate_changed(rate_a_hz, rate_b_hz);
+        if bytes.is_empty() && !rate_changed {
+            unchanged += 1;
+            continue;
+        }
+        changed.push(IdDiff {
+            frame_id: key.0,
+            is_extended: key.1,
+            count_a: sa.count,
+            count_b: sb.count,
+            rate_a_hz,
+            rate_b_hz,
+            rate_changed,
+            bytes,
+        });
+    }
+
+    only_in_a.sort_by_key(|s| (s.frame_id, s.is_extended));
+    only_in_b.sort_by_key(|s| (s.frame_id, s.is_extended));
+    changed.sort_by_key(|d| (d.frame_id, d.is_extended));
+    (only_in_a, only_in_b, changed, unchanged)
+}
+
+/// Compare two frame captures ID by ID.
+pub fn diff_capture_ids(capture_a: &str, capture_b: &str) -> Result<CaptureDiff, String> {
+    let a = rollup(capture_a)?;
+    let b = rollup(capture_b)?;
+    let (only_in_a, only_in_b, changed, unchanged) = diff_rollups(&a, &b);
+    Ok(CaptureDiff {
+        capture_a: capture_a.to_string(),
+        capture_b: capture_b.to_string(),
+        only_in_a,
+        only_in_b,
+        changed,
+        unchanged,
+    })
+}
+
+/// Compare two frame captures (e.g. "feature off" vs "feature on"): IDs
+/// present in only one, byte positions whose values differ, and rate changes.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn diff_captures(capture_a: String, capture_b: String) -> Result<CaptureDiff, String> {
+    tauri::async_runtime::spawn_blocking(move || diff_capture_ids(&capture_a, &capture_b))
+        .await
+        .map_err(|e| format!("Diff task failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame as frame;
+
+    fn rollup_of(frames: &[FrameMessage]) -> HashMap<IdKey, IdStats> {
+        let mut stats: HashMap<IdKey, IdStats> = HashMap::new();
+        for f in frames {
+            stats.entry((f.frame_id, f.is_extended)).or_default().add(f);
+        }
+        stats
+    }
+
+    #[test]
+    fn ids_present_on_one_side_are_listed() {
+        let a = rollup_of(&[frame(0x100, 0, &[1]), frame(0x200, 0, &[1])]);
+        let b = rollup_of(&[frame(0x100, 0, &[1]), frame(0x300, 0, &[1])]);
+        let (only_a, only_b, changed, unchanged) = diff_rollups(&a, &b);
+        assert_eq!(only_a.iter().map(|s| s.frame_id).collect::<Vec<_>>(), vec![0x200]);
+        assert_eq!(only_b.iter().map(|s| s.frame_id).collect::<Vec<_>>(), vec![0x300]);
+        assert!(changed.is_empty());
+        assert_eq!(unchanged, 1);
+    }
+
+    #[test]
+    fn differing_byte_values_are_reported_per_position() {
+        let a = rollup_of(&[frame(0x100, 0, &[0x10, 0x00]), frame(0x100, 100, &[0x10, 0x01])]);
+        let b = rollup_of(&[frame(0x100, 0, &[0x10, 0x00]), frame(0x100, 100, &[0x10, 0x80, 0xFF])]);
+        let (_, _, changed, _) = diff_rollups(&a, &b);
+        assert_eq!(changed.len(), 1);
+        let bytes = &changed[0].bytes;
+        assert_eq!(bytes.iter().map(|d| d.index).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!((bytes[0].only_in_a.clone(), bytes[0].only_in_b.clone()), (vec![0x01], vec![0x80]));
+        assert_eq!((bytes[1].distinct_a, bytes[1].distinct_b), (0, 1));
+        assert!(!changed[0].rate_changed);
+    }
+
+    #[test]
+    fn rate_changes_beyond_tolerance_are_flagged() {
+        // 10 Hz vs 20 Hz with identical payloads
+        let a = rollup_of(&(0..11).map(|i| frame(0x100, i * 100_000, &[0])).collect::<Vec<_>>());
+        let b = rollup_of(&(0..21).map(|i| frame(0x100, i * 50_000, &[0])).collect::<Vec<_>>());
+        let (_, _, changed, _) = diff_rollups(&a, &b);
+        assert_eq!(changed.len(), 1);
+        assert!(changed[0].rate_changed);
+        assert!((changed[0].rate_a_hz.unwrap() - 10.0).abs() < 1e-9);
+        assert!((changed[0].rate_b_hz.unwrap() - 20.0).abs() < 1e-9);
+        assert!(!rate_changed(Some(10.0), Some(10.5)));
+    }
+}
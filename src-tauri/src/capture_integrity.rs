@@ -0,0 +1,420 @@
+// ui/src-tauri/src/capture_integrity.rs
+//
+// Capture integrity — so a capture used as evidence can be shown to be
+// unmodified since it was recorded.
+//
+// When a session's capture is finalised its contents are hashed (SHA-256,
+// rolled over a canonical encoding of every frame or byte in capture order)
+// and the seal is stored on the capture's metadata. If the user has created a
+// signing key (Ed25519, seed held in the OS keyring) the seal is also signed.
+// Exports carry a manifest with the seal plus the SHA-256 of the exported file,
+// signed the same way, so the file and the capture it came from can both be
+// checked. Any change to a capture's contents clears its seal.
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::capture_db;
+use crate::capture_store::{self, CaptureKind};
+use crate::credentials;
+use crate::io::FrameMessage;
+
+/// Rows hashed per database read.
+const HASH_CHUNK: usize = 10_000;
+
+/// Keyring slot for the signing key seed (hex).
+const KEY_PROFILE: &str = "capture-signing";
+const KEY_FIELD: &str = "ed25519_seed";
+
+/// Domain-separation prefixes for hashed/signed content.
+const CAPTURE_DOMAIN: &[u8] = b"wiretap-capture-v1\0";
+const EXPORT_DOMAIN: &[u8] = b"wiretap-export-v1\0";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Ed25519 signature over a seal or manifest.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+pub struct IntegritySignature {
+    /// "ed25519"
+    pub algorithm: String,
+    /// Hex public key of the signer
+    pub public_key: String,
+    /// Hex signature
+    pub signature: String,
+}
+
+/// Content seal stored on a capture's metadata.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+pub struct CaptureIntegrity {
+    /// "sha256"
+    pub algorithm: String,
+    /// Hex digest of the capture contents
+    pub digest: String,
+    /// Frames/bytes covered by the digest
+    pub count: usize,
+    /// When the seal was computed (Unix seconds)
+    pub sealed_at: u64,
+    #[serde(default)]
+    pub signature: Option<IntegritySignature>,
+}
+
+/// Manifest written beside an export (`<file>.integrity.json`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportManifest {
+    pub capture_id: String,
+    pub capture_name: String,
+    /// Export format ("csv", "json", "candump", ...)
+    pub format: String,
+    /// Hex SHA-256 of the exported file contents
+    pub export_sha256: String,
+    /// Seal of the capture the export was taken from
+    pub capture: Option<CaptureIntegrity>,
+    pub created_at: u64,
+    #[serde(default)]
+    pub signature: Option<IntegritySignature>,
+}
+
+/// Result of re-hashing a capture against its stored seal.
+#[derive(Clone, Debug, Serialize)]
+pub struct IntegrityVerification {
+    pub capture_id: String,
+    /// None when the capture was never sealed
+    pub sealed_digest: Option<String>,
+    pub current_digest: String,
+    pub current_count: usize,
+    pub digest_matches: bool,
+    /// None when the seal is unsigned
+    pub signature_valid: Option<bool>,
+}
+
+// ============================================================================
+// Hashing
+// ============================================================================
+
+fn unix_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Canonical encoding of one frame. Everything that describes what was on
+/// the wire is covered; display-only linkage is not.
+fn hash_frame(hasher: &mut Sha256, frame: &FrameMessage) {
+    hasher.update(frame.timestamp_us.to_le_bytes());
+    hasher.update(frame.frame_id.to_le_bytes());
+    hasher.update([frame.bus, frame.dlc]);
+    let flags = frame.is_extended as u8
+        | (frame.is_fd as u8) << 1
+        | ((frame.direction.as_deref() == Some("tx")) as u8) << 2
+        | (frame.incomplete.unwrap_or(false) as u8) << 3
+        | (frame.source_address.is_some() as u8) << 4;
+    hasher.update([flags]);
+    hasher.update(frame.source_address.unwrap_or(0).to_le_bytes());
+    hasher.update((frame.protocol.len() as u8).to_le_bytes());
+    hasher.update(frame.protocol.as_bytes());
+    hasher.update((frame.bytes.len() as u32).to_le_bytes());
+    hasher.update(&frame.bytes);
+}
+
+/// Hash a capture's contents. Returns (hex digest, item count).
+pub fn hash_capture(capture_id: &str) -> Result<(String, usize), String> {
+    let kind = capture_store::get_capture_kind(capture_id)
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = capture_id))?;
+    let mut hasher = Sha256::new();
+    hasher.update(CAPTURE_DOMAIN);
+    let mut count = 0usize;
+    match kind {
+        CaptureKind::Frames => {
+            hasher.update([0u8]);
+            let mut cursor = 0i64;
+            loop {
+                let rows = capture_db::read_frame_chunk(capture_id, cursor, HASH_CHUNK)?;
+                for (_, frame) in &rows {
+                    hash_frame(&mut hasher, frame);
+                }
+                count += rows.len();
+                match rows.last() {
+                    Some((rowid, _)) if rows.len() == HASH_CHUNK => cursor = *rowid,
+                    _ => break,
+                }
+            }
+        }
+        CaptureKind::Bytes => {
+            hasher.update([1u8]);
+            loop {
+                let (bytes, total) = capture_db::get_bytes_paginated(capture_id, count, HASH_CHUNK)?;
+                for b in &bytes {
+                    hasher.update(b.timestamp_us.to_le_bytes());
+                    hasher.update([b.bus, b.byte]);
+                }
+                count += bytes.len();
+                if bytes.is_empty() || count >= total {
+                    break;
+                }
+            }
+        }
+    }
+    Ok((hex::encode(hasher.finalize()), count))
+}
+
+fn seal_message(digest: &str, count: usize) -> Vec<u8> {
+    let mut msg = CAPTURE_DOMAIN.to_vec();
+    msg.extend_from_slice(digest.as_bytes());
+    msg.extend_from_slice(&(count as u64).to_le_bytes());
+    msg
+}
+
+fn manifest_message(manifest: &ExportManifest) -> Vec<u8> {
+    let mut msg = EXPORT_DOMAIN.to_vec();
+    msg.extend_from_slice(manifest.capture_id.as_bytes());
+    msg.push(0);
+    msg.extend_from_slice(manifest.format.as_bytes());
+    msg.push(0);
+    msg.extend_from_slice(manifest.export_sha256.as_bytes());
+    msg.push(0);
+    if let Some(seal) = &manifest.capture {
+        msg.extend_from_slice(&seal_message(&seal.digest, seal.count));
+    }
+    msg.extend_from_slice(&manifest.created_at.to_le_bytes());
+    msg
+}
+
+// ============================================================================
+// Signing key
+// ============================================================================
+
+fn load_signing_key() -> Result<Option<SigningKey>, String> {
+    let Some(seed_hex) = credentials::get_credential(KEY_PROFILE, KEY_FIELD)? else {
+        return Ok(None);
+    };
+    let seed: [u8; 32] = hex::decode(seed_hex.trim())
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or("Stored capture signing key is corrupt")?;
+    Ok(Some(SigningKey::from_bytes(&seed)))
+}
+
+fn sign(key: &SigningKey, message: &[u8]) -> IntegritySignature {
+    IntegritySignature {
+        algorithm: "ed25519".to_string(),
+        public_key: hex::encode(key.verifying_key().to_bytes()),
+        signature: hex::encode(key.sign(message).to_bytes()),
+    }
+}
+
+fn verify_signature(sig: &IntegritySignature, message: &[u8]) -> bool {
+    if sig.algorithm != "ed25519" {
+        return false;
+    }
+    let key = hex::decode(&sig.public_key)
+        .ok()
+        .and_then(|b| <[u8; 32]>::try_from(b).ok())
+        .and_then(|b| VerifyingKey::from_bytes(&b).ok());
+    let signature = hex::decode(&sig.signature)
+        .ok()
+        .and_then(|b| <[u8; 64]>::try_from(b).ok())
+        .map(|b| Signature::from_bytes(&b));
+    match (key, signature) {
+        (Some(key), Some(signature)) => key.verify(message, &signature).is_ok(),
+        _ => false,
+    }
+}
+
+// ============================================================================
+// Sealing
+// ============================================================================
+
+/// Hash (and sign, when a key exists) a capture and store the seal.
+pub fn seal_capture(capture_id: &str) -> Result<CaptureIntegrity, String> {
+    let (digest, count) = hash_capture(capture_id)?;
+    // A missing keyring (headless Linux) must not block the unsigned seal
+    let signature = match load_signing_key() {
+        Ok(key) => key.map(|k| sign(&k, &seal_message(&digest, count))),
+        Err(e) => {
+            tlog!("[capture_integrity] Signing key unavailable: {}", e);
+            None
+        }
+    };
+    let integrity = CaptureIntegrity {
+        algorithm: "sha256".to_string(),
+        digest,
+        count,
+        sealed_at: unix_secs(),
+        signature,
+    };
+    capture_store::set_capture_integrity(capture_id, integrity.clone());
+    Ok(integrity)
+}
+
+/// Seal finalised captures off the caller's thread (large captures take a
+/// while to hash).
+pub fn seal_in_background(capture_ids: Vec<String>) {
+    if capture_ids.is_empty() {
+        return;
+    }
+    std::thread::spawn(move || {
+        for id in capture_ids {
+            match seal_capture(&id) {
+                Ok(seal) => tlog!(
+                    "[capture_integrity] Sealed '{}' ({} items, {}signed)",
+                    id,
+                    seal.count,
+                    if seal.signature.is_some() { "" } else { "un" }
+                ),
+                Err(e) => tlog!("[capture_integrity] Failed to seal '{}': {}", id, e),
+            }
+        }
+    });
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Seal a capture now (e.g. an import, or one changed since finalisation).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn seal_capture_integrity(capture_id: String) -> Result<CaptureIntegrity, String> {
+    tauri::async_runtime::spawn_blocking(move || seal_capture(&capture_id))
+        .await
+        .map_err(|e| format!("Seal task failed: {}", e))?
+}
+
+/// Re-hash a capture and compare it (and its signature) against the seal.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn verify_capture_integrity(capture_id: String) -> Result<IntegrityVerification, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let seal = capture_store::get_capture_metadata(&capture_id)
+            .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = capture_id))?
+            .integrity;
+        let (current_digest, current_count) = hash_capture(&capture_id)?;
+        let digest_matches = seal
+            .as_ref()
+            .is_some_and(|s| s.digest == current_digest && s.count == current_count);
+        let signature_valid = seal.as_ref().and_then(|s| {
+            s.signature
+                .as_ref()
+                .map(|sig| verify_signature(sig, &seal_message(&s.digest, s.count)))
+        });
+        Ok(IntegrityVerification {
+            capture_id,
+            sealed_digest: seal.map(|s| s.digest),
+            current_digest,
+            current_count,
+            digest_matches,
+            signature_valid,
+        })
+    })
+    .await
+    .map_err(|e| format!("Verify task failed: {}", e))?
+}
+
+/// Build (and sign, when a key exists) the manifest for an export whose
+/// contents hash to `export_sha256`.
+#[tauri::command(rename_all = "snake_case")]
+pub fn build_export_manifest(
+    capture_id: String,
+    format: String,
+    export_sha256: String,
+) -> Result<ExportManifest, String> {
+    let meta = capture_store::get_capture_metadata(&capture_id)
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = capture_id))?;
+    if export_sha256.len() != 64 || hex::decode(&export_sha256).is_err() {
+        return Err("Export hash must be a hex SHA-256 digest".to_string());
+    }
+    let mut manifest = ExportManifest {
+        capture_id,
+        capture_name: meta.name,
+        format,
+        export_sha256: export_sha256.to_lowercase(),
+        capture: meta.integrity,
+        created_at: unix_secs(),
+        signature: None,
+    };
+    if let Some(key) = load_signing_key()? {
+        manifest.signature = Some(sign(&key, &manifest_message(&manifest)));
+    }
+    Ok(manifest)
+}
+
+/// Check an export manifest's signature (the file hash is compared by the
+/// caller, which has the file).
+#[tauri::command(rename_all = "snake_case")]
+pub fn verify_export_manifest(manifest: ExportManifest) -> Option<bool> {
+    manifest
+        .signature
+        .as_ref()
+        .map(|sig| verify_signature(sig, &manifest_message(&manifest)))
+}
+
+/// Public key (hex) of the capture signing key, if one has been created.
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_capture_signing_key() -> Result<Option<String>, String> {
+    Ok(load_signing_key()?.map(|k| hex::encode(k.verifying_key().to_bytes())))
+}
+
+/// Create (or replace) the capture signing key. Returns the public key.
+#[tauri::command(rename_all = "snake_case")]
+pub fn generate_capture_signing_key() -> Result<String, String> {
+    let key = SigningKey::generate(&mut rand_core::OsRng);
+    credentials::store_credential(KEY_PROFILE, KEY_FIELD, &hex::encode(key.to_bytes()))?;
+    let public = hex::encode(key.verifying_key().to_bytes());
+    tlog!("[capture_integrity] Created signing key {}", public);
+    Ok(public)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn delete_capture_signing_key() -> Result<(), String> {
+    credentials::delete_credential(KEY_PROFILE, KEY_FIELD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(bytes: Vec<u8>) -> FrameMessage {
+        test_frame(0x123, 1_000, &bytes)
+    }
+
+    fn digest(frames: &[FrameMessage]) -> String {
+        let mut hasher = Sha256::new();
+        for f in frames {
+            hash_frame(&mut hasher, f);
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    #[test]
+    fn frame_hash_covers_payload_and_direction() {
+        let rx = digest(&[frame(vec![1, 2, 3])]);
+        let mut tx = frame(vec![1, 2, 3]);
+        tx.direction = Some("tx".to_string());
+        assert_eq!(rx, digest(&[frame(vec![1, 2, 3])]));
+        assert_ne!(rx, digest(&[frame(vec![1, 2, 4])]));
+        assert_ne!(rx, digest(&[tx]));
+    }
+
+    #[test]
+    fn signatures_verify_and_detect_tampering() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut manifest = ExportManifest {
+            capture_id: "cap1".to_string(),
+            capture_name: "drive".to_string(),
+            format: "csv".to_string(),
+            export_sha256: "ab".repeat(32),
+            capture: None,
+            created_at: 1_700_000_000,
+            signature: None,
+        };
+        let sig = sign(&key, &manifest_message(&manifest));
+        assert!(verify_signature(&sig, &manifest_message(&manifest)));
+        manifest.export_sha256 = "cd".repeat(32);
+        assert!(!verify_signature(&sig, &manifest_message(&manifest)));
+    }
+}
@@ -0,0 +1,406 @@
+// ui/src-tauri/src/capture_journal.rs
+//
+// Crash-safe journaling of live captures. When enabled, every capture a
+// session is recording also gets an append-only journal file, written in the
+// same append call as the capture database. A capture that is finalised,
+// cleared or deleted drops its journal, and so does a normal quit, so any
+// journal found at launch belongs to a capture that was still recording when
+// the app died.
+//
+// The database alone doesn't cover that case: with "clear captures on start"
+// on, the crashed capture is wiped at the next launch before anyone can look
+// at it. `initialise` turns each journal back into an orphaned, persistent
+// capture ("<name> (recovered)"). A capture that survived in the database is
+// kept as it is and pinned instead.
+//
+// File layout (little-endian), one <capture id>.wtcj per capture:
+//   header: "WTCJ", version u8, kind u8 (0 frames, 1 bytes),
+//           capture id and name (u16 len + UTF-8 each)
+//   record: frames use black_box's record layout; bytes are
+//           timestamp_us u64, byte u8, bus u8
+// Writes are flushed every batch and fsynced at most once per SYNC_INTERVAL.
+// A torn final record is ignored on recovery. Ring trims aren't mirrored, so a
+// recovered ring capture holds everything recorded since it started.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::black_box::{decode_record, encode_record, Cursor};
+use crate::capture_store::{self, CaptureKind, TimestampedByte};
+use crate::io::FrameMessage;
+
+const MAGIC: &[u8; 4] = b"WTCJ";
+const VERSION: u8 = 1;
+const EXTENSION: &str = "wtcj";
+
+const KIND_FRAMES: u8 = 0;
+const KIND_BYTES: u8 = 1;
+
+/// Most often a journal is fsynced (flushes still happen every batch).
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CaptureJournalConfig {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// One capture's open journal.
+struct JournalWriter {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    last_sync: Instant,
+}
+
+#[derive(Default)]
+struct Journal {
+    dir: Option<PathBuf>,
+    config: CaptureJournalConfig,
+    writers: HashMap<String, JournalWriter>,
+}
+
+static STATE: Lazy<Mutex<Journal>> = Lazy::new(|| Mutex::new(Journal::default()));
+
+/// What a journal held.
+struct Recovered {
+    capture_id: String,
+    name: String,
+    kind: CaptureKind,
+    frames: Vec<FrameMessage>,
+    bytes: Vec<TimestampedByte>,
+}
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+fn push_str(out: &mut Vec<u8>, s: &str) {
+    let bytes = &s.as_bytes()[..s.len().min(u16::MAX as usize)];
+    out.extend_from_slice(&(bytes.len() as u16).to_le_bytes());
+    out.extend_from_slice(bytes);
+}
+
+fn encode_header(capture_id: &str, name: &str, kind: &CaptureKind) -> Vec<u8> {
+    let mut out = Vec::with_capacity(10 + capture_id.len() + name.len());
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.push(match kind {
+        CaptureKind::Frames => KIND_FRAMES,
+        CaptureKind::Bytes => KIND_BYTES,
+    });
+    push_str(&mut out, capture_id);
+    push_str(&mut out, name);
+    out
+}
+
+fn encode_byte(out: &mut Vec<u8>, byte: &TimestampedByte) {
+    out.extend_from_slice(&byte.timestamp_us.to_le_bytes());
+    out.push(byte.byte);
+    out.push(byte.bus);
+}
+
+fn decode_byte(c: &mut Cursor) -> Option<TimestampedByte> {
+    let timestamp_us = c.u64()?;
+    let byte = c.u8()?;
+    let bus = c.u8()?;
+    Some(TimestampedByte { byte, timestamp_us, bus })
+}
+
+fn read_str(c: &mut Cursor) -> Option<String> {
+    let len = c.u16()? as usize;
+    Some(String::from_utf8_lossy(c.take(len)?).into_owned())
+}
+
+/// A decoded journal. Records after a torn write are dropped; `None` when the
+/// header itself is unreadable.
+fn read_journal(data: &[u8]) -> Option<Recovered> {
+    let mut c = Cursor::new(data);
+    if c.take(4)? != MAGIC || c.u8()? != VERSION {
+        return None;
+    }
+    let kind = match c.u8()? {
+        KIND_FRAMES => CaptureKind::Frames,
+        KIND_BYTES => CaptureKind::Bytes,
+        _ => return None,
+    };
+    let capture_id = read_str(&mut c)?;
+    let name = read_str(&mut c)?;
+    let mut recovered = Recovered { capture_id, name, kind, frames: Vec::new(), bytes: Vec::new() };
+    match recovered.kind {
+        CaptureKind::Frames => {
+            while let Some(frame) = decode_record(&mut c) {
+                recovered.frames.push(frame);
+            }
+        }
+        CaptureKind::Bytes => {
+            while let Some(byte) = decode_byte(&mut c) {
+                recovered.bytes.push(byte);
+            }
+        }
+    }
+    Some(recovered)
+}
+
+// ============================================================================
+// Writing
+// ============================================================================
+
+fn journal_path(dir: &Path, capture_id: &str) -> PathBuf {
+    let stem: String = capture_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    dir.join(format!("{}.{}", stem, EXTENSION))
+}
+
+impl JournalWriter {
+    fn open(dir: &Path, capture_id: &str, name: &str, kind: &CaptureKind) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let path = journal_path(dir, capture_id);
+        let file = OpenOptions::new().create(true).write(true).truncate(true).open(&path)?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(&encode_header(capture_id, name, kind))?;
+        Ok(JournalWriter { path, writer, last_sync: Instant::now() })
+    }
+
+    fn write(&mut self, records: &[u8]) -> std::io::Result<()> {
+        self.writer.write_all(records)?;
+        self.writer.flush()?;
+        if self.last_sync.elapsed() >= SYNC_INTERVAL {
+            self.writer.get_ref().sync_data()?;
+            self.last_sync = Instant::now();
+        }
+        Ok(())
+    }
+
+    fn discard(self) {
+        drop(self.writer);
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Append encoded records to a capture's journal, opening it on first use.
+fn append(capture_id: &str, kind: CaptureKind, records: Vec<u8>) {
+    let mut state = STATE.lock().unwrap();
+    if !state.config.enabled {
+        return;
+    }
+    let Some(dir) = state.dir.clone() else { return };
+    if !state.writers.contains_key(capture_id) {
+        let name = capture_store::get_capture_metadata(capture_id).map(|m| m.name).unwrap_or_default();
+        match JournalWriter::open(&dir, capture_id, &name, &kind) {
+            Ok(writer) => {
+                state.writers.insert(capture_id.to_string(), writer);
+            }
+            Err(e) => {
+                tlog!("[capture_journal] Failed to open journal for '{}': {}", capture_id, e);
+                return;
+            }
+        }
+    }
+    if let Some(writer) = state.writers.get_mut(capture_id) {
+        if let Err(e) = writer.write(&records) {
+            tlog!("[capture_journal] Failed to write journal for '{}': {}", capture_id, e);
+        }
+    }
+}
+
+/// Journal frames being written to a session's capture (no-op unless enabled).
+pub fn tap_frames(capture_id: &str, frames: &[FrameMessage]) {
+    if frames.is_empty() || !is_enabled() {
+        return;
+    }
+    let mut records = Vec::with_capacity(frames.len() * 32);
+    for frame in frames {
+        encode_record(&mut records, frame);
+    }
+    append(capture_id, CaptureKind::Frames, records);
+}
+
+/// Journal raw bytes being written to a session's byte capture.
+pub fn tap_bytes(capture_id: &str, bytes: &[TimestampedByte]) {
+    if bytes.is_empty() || !is_enabled() {
+        return;
+    }
+    let mut records = Vec::with_capacity(bytes.len() * 10);
+    for byte in bytes {
+        encode_byte(&mut records, byte);
+    }
+    append(capture_id, CaptureKind::Bytes, records);
+}
+
+fn is_enabled() -> bool {
+    STATE.lock().map(|s| s.config.enabled).unwrap_or(false)
+}
+
+/// Drop a capture's journal: it was finalised, cleared or deleted.
+pub fn close(capture_id: &str) {
+    if let Some(writer) = STATE.lock().unwrap().writers.remove(capture_id) {
+        writer.discard();
+    }
+}
+
+/// Drop every journal (app exit, or journaling was switched off).
+pub fn discard_all() {
+    let writers: Vec<JournalWriter> = STATE.lock().unwrap().writers.drain().map(|(_, w)| w).collect();
+    for writer in writers {
+        writer.discard();
+    }
+}
+
+// ============================================================================
+// Recovery
+// ============================================================================
+
+fn config_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    Ok(dir.join("capture-journal.json"))
+}
+
+/// Bring back a capture from its journal. Returns the recovered capture ID.
+fn restore(recovered: Recovered) -> Option<String> {
+    if capture_store::is_known_capture(&recovered.capture_id) {
+        // The database kept it: pin it so the next clear on start leaves it alone
+        let _ = capture_store::set_capture_persistent(&recovered.capture_id, true);
+        return Some(recovered.capture_id);
+    }
+    if recovered.frames.is_empty() && recovered.bytes.is_empty() {
+        return None;
+    }
+    let name = format!("{} (recovered)", recovered.name);
+    let id = capture_store::create_capture_inactive(recovered.kind, name);
+    capture_store::append_frames_to_capture(&id, recovered.frames);
+    capture_store::append_raw_bytes_to_capture(&id, recovered.bytes);
+    let _ = capture_store::set_capture_persistent(&id, true);
+    Some(id)
+}
+
+/// Load the config and recover captures that were still recording when the
+/// app last stopped. Call once at startup, after the capture store is hydrated.
+pub fn initialise(app: &AppHandle) {
+    let Ok(config_file) = config_path(app) else { return };
+    let config: CaptureJournalConfig = std::fs::read_to_string(&config_file)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    let dir = config_file.with_file_name("journal");
+    {
+        let mut state = STATE.lock().unwrap();
+        state.config = config;
+        state.dir = Some(dir.clone());
+    }
+
+    let Ok(entries) = std::fs::read_dir(&dir) else { return };
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+            continue;
+        }
+        let mut data = Vec::new();
+        if File::open(&path).and_then(|mut f| f.read_to_end(&mut data)).is_ok() {
+            if let Some(recovered) = read_journal(&data) {
+                let original = recovered.capture_id.clone();
+                let items = recovered.frames.len() + recovered.bytes.len();
+                if let Some(id) = restore(recovered) {
+                    tlog!("[capture_journal] Recovered capture '{}' as '{}' ({} journaled items)", original, id, items);
+                }
+            }
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+#[tauri::command]
+pub fn get_capture_journal_config() -> CaptureJournalConfig {
+    STATE.lock().unwrap().config.clone()
+}
+
+/// Save the config. Switching journaling off drops the open journals; a
+/// capture already recording starts journaling from its next batch when it
+/// is switched on.
+#[tauri::command]
+pub fn set_capture_journal_config(app: AppHandle, config: CaptureJournalConfig) -> Result<CaptureJournalConfig, String> {
+    let path = config_path(&app)?;
+    let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write capture journal config: {}", e))?;
+    STATE.lock().unwrap().config = config.clone();
+    if !config.enabled {
+        discard_all();
+    }
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(ts: u64, id: u32, data: &[u8]) -> FrameMessage {
+        FrameMessage { direction: Some("rx".to_string()), ..test_frame(id, ts, data) }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("wiretap-journal-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn journals_round_trip_and_ignore_torn_tail() {
+        let dir = temp_dir("frames");
+        let mut writer = JournalWriter::open(&dir, "abc123", "GVRET 10:30am", &CaptureKind::Frames).unwrap();
+        let mut records = Vec::new();
+        encode_record(&mut records, &frame(1_000, 0x123, &[1, 2, 3]));
+        encode_record(&mut records, &frame(2_000, 0x456, &[4]));
+        // Crash mid-record
+        writer.write(&records[..records.len() - 2]).unwrap();
+        let path = writer.path.clone();
+        drop(writer);
+
+        let data = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        let recovered = read_journal(&data).unwrap();
+        assert_eq!((recovered.capture_id.as_str(), recovered.name.as_str()), ("abc123", "GVRET 10:30am"));
+        assert_eq!(recovered.kind, CaptureKind::Frames);
+        assert_eq!(recovered.frames.len(), 1);
+        assert_eq!(recovered.frames[0].bytes, vec![1, 2, 3]);
+        assert!(read_journal(b"WTBB").is_none());
+    }
+
+    #[test]
+    fn byte_journals_round_trip_and_discard_removes_the_file() {
+        let dir = temp_dir("bytes");
+        let mut writer = JournalWriter::open(&dir, "ser001", "Serial dump", &CaptureKind::Bytes).unwrap();
+        let mut records = Vec::new();
+        for (i, b) in [0x7Eu8, 0x01, 0x7E].iter().enumerate() {
+            encode_byte(&mut records, &TimestampedByte { byte: *b, timestamp_us: i as u64 * 100, bus: 2 });
+        }
+        writer.write(&records).unwrap();
+        let path = writer.path.clone();
+
+        let recovered = read_journal(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(recovered.kind, CaptureKind::Bytes);
+        assert_eq!(recovered.bytes.iter().map(|b| b.byte).collect::<Vec<_>>(), vec![0x7E, 0x01, 0x7E]);
+        assert_eq!(recovered.bytes[2].timestamp_us, 200);
+
+        writer.discard();
+        assert!(!path.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
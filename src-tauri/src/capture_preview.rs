@@ -0,0 +1,126 @@
+// ui/src-tauri/src/capture_preview.rs
+//
+// Compact per-capture preview series for sparkline thumbnails in capture
+// pickers: item rate over time, per-bus activity and unique frame IDs per
+// bucket. Computed with one grouped query (capture_db::preview_cells) and
+// cached in the capture database beside the capture, keyed by the capture's
+// item count and end time so a capture that's still growing is recomputed
+// and a finished one is served straight from the cache.
+
+use serde::{Deserialize, Serialize};
+
+use crate::capture_db;
+use crate::capture_store::{self, CaptureKind};
+
+/// Buckets per series when the caller doesn't ask.
+const DEFAULT_POINTS: usize = 64;
+
+/// Upper bound on buckets per series.
+const MAX_POINTS: usize = 512;
+
+/// Activity on one bus.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BusPreview {
+    pub bus: u8,
+    /// Items per bucket
+    pub counts: Vec<u32>,
+}
+
+/// Sparkline series for one capture. All series have `points` entries.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CapturePreview {
+    pub capture_id: String,
+    pub points: usize,
+    /// Item count the preview was computed from
+    pub count: usize,
+    /// Timestamp of the first bucket (None for an empty capture)
+    pub start_us: Option<u64>,
+    pub bucket_us: u64,
+    /// Frames (or bytes) per second in each bucket
+    pub rate: Vec<f32>,
+    pub buses: Vec<BusPreview>,
+    /// Distinct (bus, frame ID) pairs per bucket; empty for byte captures
+    pub unique_ids: Vec<u32>,
+}
+
+fn build_preview(capture_id: &str, kind: &CaptureKind, count: usize, points: usize) -> Result<CapturePreview, String> {
+    let bytes = *kind == CaptureKind::Bytes;
+    let (span, cells) = capture_db::preview_cells(capture_id, bytes, points)?;
+    let mut preview = CapturePreview {
+        capture_id: capture_id.to_string(),
+        points,
+        count,
+        start_us: span.map(|(first, _)| first),
+        bucket_us: 0,
+        rate: vec![0.0; points],
+        buses: Vec::new(),
+        unique_ids: if bytes { Vec::new() } else { vec![0; points] },
+    };
+    let Some((first, last)) = span else {
+        return Ok(preview);
+    };
+    // Same bucket width capture_db used
+    preview.bucket_us = ((last - first) / points as u64 + 1).max(1);
+
+    let mut totals = vec![0u64; points];
+    for cell in cells {
+        totals[cell.bucket] += cell.count;
+        if !bytes {
+            preview.unique_ids[cell.bucket] += cell.unique_ids as u32;
+        }
+        let bus = match preview.buses.iter().position(|b| b.bus == cell.bus) {
+            Some(i) => &mut preview.buses[i],
+            None => {
+                preview.buses.push(BusPreview { bus: cell.bus, counts: vec![0; points] });
+                preview.buses.last_mut().unwrap()
+            }
+        };
+        bus.counts[cell.bucket] = cell.count as u32;
+    }
+    let bucket_secs = preview.bucket_us as f32 / 1_000_000.0;
+    preview.rate = totals.iter().map(|&n| n as f32 / bucket_secs).collect();
+    preview.buses.sort_by_key(|b| b.bus);
+    Ok(preview)
+}
+
+/// Cached preview for a capture, recomputed when the capture has changed.
+pub fn capture_preview(capture_id: &str, points: usize) -> Result<CapturePreview, String> {
+    let meta = capture_store::get_capture_metadata(capture_id)
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = capture_id))?;
+    if let Some(json) = capture_db::load_capture_preview(capture_id, points, meta.count, meta.end_time_us)? {
+        if let Ok(preview) = serde_json::from_str(&json) {
+            return Ok(preview);
+        }
+    }
+    let preview = build_preview(capture_id, &meta.kind, meta.count, points)?;
+    // Don't cache a capture that's still being written — it changes every batch
+    if !meta.is_streaming {
+        let json = serde_json::to_string(&preview).map_err(|e| format!("Failed to encode preview: {}", e))?;
+        capture_db::save_capture_preview(capture_id, points, meta.count, meta.end_time_us, &json)?;
+    }
+    Ok(preview)
+}
+
+/// Preview series for several captures (a picker's list). Captures that
+/// fail (e.g. deleted meanwhile) are skipped.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_capture_previews(
+    capture_ids: Vec<String>,
+    points: Option<usize>,
+) -> Result<Vec<CapturePreview>, String> {
+    let points = points.unwrap_or(DEFAULT_POINTS).clamp(1, MAX_POINTS);
+    tauri::async_runtime::spawn_blocking(move || {
+        capture_ids
+            .iter()
+            .filter_map(|id| match capture_preview(id, points) {
+                Ok(p) => Some(p),
+                Err(e) => {
+                    tlog!("[capture_preview] Skipping '{}': {}", id, e);
+                    None
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Preview task failed: {}", e))
+}
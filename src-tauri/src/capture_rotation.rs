@@ -0,0 +1,369 @@
+// ui/src-tauri/src/capture_rotation.rs
+//
+// Auto-split of a session's recording into segments. With a rotation policy
+// set, a session's capture is closed once it passes a frame count, a payload
+// size or a time span: it is finalised and orphaned, so it shows up as a
+// standalone capture, and the session carries on into a fresh one. Each split
+// raises `buffer-orphaned` for the full segment and `buffer-created` for its
+// successor so the UI can keep a rolling list of segments.
+//
+// The check runs in capture_store's append path after a batch is written, so
+// a segment may overshoot its limit by one batch. Sizes count payload bytes
+// (frame data, or raw bytes for byte captures) and spans are measured on item
+// timestamps, like capture_trigger, so a replayed capture splits the same way.
+// Counters follow the capture they measure: when the session moves to a new
+// capture some other way (restart, scheduled recording) the segment starts
+// again from zero. Frame and byte captures of one session split together.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::capture_store::{self, CaptureKind, TimestampedByte};
+use crate::io::FrameMessage;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// When a session's capture is split. Any limit reached starts a new segment.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotationPolicy {
+    /// Split after this many frames (frame captures only)
+    #[serde(default)]
+    pub max_frames: Option<u64>,
+    /// Split after this many payload bytes
+    #[serde(default)]
+    pub max_bytes: Option<u64>,
+    /// Split once a segment spans this long
+    #[serde(default)]
+    pub max_duration_ms: Option<u64>,
+}
+
+/// Which limit closed a segment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationReason {
+    Frames,
+    Bytes,
+    Duration,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RotationState {
+    pub session_id: String,
+    pub policy: RotationPolicy,
+    /// Segments closed so far
+    pub segments: u32,
+    /// Frames in the current frame segment
+    pub frames: u64,
+    /// Payload bytes in the current segment (frame and byte captures)
+    pub bytes: u64,
+    /// Time spanned by the current segment
+    pub span_ms: u64,
+}
+
+/// Payload of the `buffer-orphaned` event: a full segment, now standalone.
+#[derive(Clone, Debug, Serialize)]
+pub struct BufferOrphanedPayload {
+    pub session_id: String,
+    pub capture_id: String,
+    pub name: String,
+    pub kind: CaptureKind,
+    pub count: usize,
+    pub reason: RotationReason,
+}
+
+/// Payload of the `buffer-created` event: the segment the session moved on to.
+#[derive(Clone, Debug, Serialize)]
+pub struct BufferCreatedPayload {
+    pub session_id: String,
+    pub capture_id: String,
+    pub name: String,
+    pub kind: CaptureKind,
+}
+
+// ============================================================================
+// Segments
+// ============================================================================
+
+/// What has been written to one capture since the segment began.
+#[derive(Clone, Debug, Default)]
+struct Segment {
+    capture_id: String,
+    items: u64,
+    bytes: u64,
+    first_us: Option<u64>,
+    last_us: Option<u64>,
+}
+
+impl Segment {
+    fn span_us(&self) -> u64 {
+        match (self.first_us, self.last_us) {
+            (Some(first), Some(last)) => last.saturating_sub(first),
+            _ => 0,
+        }
+    }
+}
+
+impl RotationPolicy {
+    fn validate(&self) -> Result<(), String> {
+        let limits = [self.max_frames, self.max_bytes, self.max_duration_ms];
+        if limits.iter().all(Option::is_none) {
+            return Err("A rotation policy needs at least one limit".to_string());
+        }
+        if limits.contains(&Some(0)) {
+            return Err("Rotation limits must be greater than zero".to_string());
+        }
+        Ok(())
+    }
+
+    fn limit_reached(&self, kind: &CaptureKind, segment: &Segment) -> Option<RotationReason> {
+        if *kind == CaptureKind::Frames && self.max_frames.is_some_and(|max| segment.items >= max) {
+            return Some(RotationReason::Frames);
+        }
+        if self.max_bytes.is_some_and(|max| segment.bytes >= max) {
+            return Some(RotationReason::Bytes);
+        }
+        if self.max_duration_ms.is_some_and(|ms| segment.span_us() >= ms.saturating_mul(1000)) {
+            return Some(RotationReason::Duration);
+        }
+        None
+    }
+}
+
+struct Rotation {
+    policy: RotationPolicy,
+    frames: Segment,
+    bytes: Segment,
+    segments: u32,
+}
+
+impl Rotation {
+    fn new(policy: RotationPolicy) -> Self {
+        Self { policy, frames: Segment::default(), bytes: Segment::default(), segments: 0 }
+    }
+
+    /// Count a batch written to `capture_id`. Returns the limit it reached.
+    fn record(
+        &mut self,
+        capture_id: &str,
+        kind: CaptureKind,
+        items: u64,
+        bytes: u64,
+        first_us: u64,
+        last_us: u64,
+    ) -> Option<RotationReason> {
+        let segment = match kind {
+            CaptureKind::Frames => &mut self.frames,
+            CaptureKind::Bytes => &mut self.bytes,
+        };
+        if segment.capture_id != capture_id {
+            *segment = Segment { capture_id: capture_id.to_string(), ..Segment::default() };
+        }
+        segment.items += items;
+        segment.bytes += bytes;
+        segment.first_us.get_or_insert(first_us);
+        segment.last_us = Some(last_us);
+        self.policy.limit_reached(&kind, segment)
+    }
+
+    fn snapshot(&self, session_id: &str) -> RotationState {
+        RotationState {
+            session_id: session_id.to_string(),
+            policy: self.policy.clone(),
+            segments: self.segments,
+            frames: self.frames.items,
+            bytes: self.frames.bytes + self.bytes.bytes,
+            span_ms: self.frames.span_us().max(self.bytes.span_us()) / 1000,
+        }
+    }
+}
+
+/// Map of session_id -> rotation. Empty for almost every session, so the
+/// append-path check is a single read lock.
+static ROTATIONS: Lazy<RwLock<HashMap<String, Rotation>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+fn record(
+    session_id: &str,
+    capture_id: &str,
+    kind: CaptureKind,
+    items: u64,
+    bytes: u64,
+    first_us: u64,
+    last_us: u64,
+) -> Option<RotationReason> {
+    if ROTATIONS.read().map(|r| !r.contains_key(session_id)).unwrap_or(true) {
+        return None;
+    }
+    let mut rotations = ROTATIONS.write().ok()?;
+    let rotation = rotations.get_mut(session_id)?;
+    rotation.record(capture_id, kind, items, bytes, first_us, last_us)
+}
+
+/// Count frames about to be written to a session's capture. Called from
+/// capture_store::append_frames_to_session; a returned reason is passed to
+/// `rotate` once the batch has been written.
+pub fn tap_frames(session_id: &str, capture_id: &str, frames: &[FrameMessage]) -> Option<RotationReason> {
+    let (first, last) = (frames.first()?, frames.last()?);
+    let bytes = frames.iter().map(|f| f.bytes.len() as u64).sum();
+    record(
+        session_id,
+        capture_id,
+        CaptureKind::Frames,
+        frames.len() as u64,
+        bytes,
+        first.timestamp_us,
+        last.timestamp_us,
+    )
+}
+
+/// Count raw bytes about to be written to a session's byte capture.
+pub fn tap_bytes(session_id: &str, capture_id: &str, bytes: &[TimestampedByte]) -> Option<RotationReason> {
+    let (first, last) = (bytes.first()?, bytes.last()?);
+    let count = bytes.len() as u64;
+    record(session_id, capture_id, CaptureKind::Bytes, count, count, first.timestamp_us, last.timestamp_us)
+}
+
+/// Close the session's current segment and start a new one: finalise and
+/// orphan its captures, then give it fresh captures of the same kinds.
+pub fn rotate(session_id: &str, reason: RotationReason) {
+    capture_store::finalize_session_captures(session_id);
+    let orphaned = capture_store::orphan_captures_for_session(session_id);
+    if orphaned.is_empty() {
+        return;
+    }
+
+    for segment in &orphaned {
+        crate::io::emit_buffer_orphaned(&BufferOrphanedPayload {
+            session_id: session_id.to_string(),
+            capture_id: segment.capture_id.clone(),
+            name: segment.name.clone(),
+            kind: segment.kind.clone(),
+            count: segment.count,
+            reason,
+        });
+    }
+    // Frame and byte captures of one segment share a name
+    let name = capture_store::next_indexed_name(session_id);
+    for segment in &orphaned {
+        let capture_id = capture_store::create_capture(segment.kind.clone(), name.clone());
+        let _ = capture_store::set_capture_owner(&capture_id, session_id);
+        if let Some(limit) = capture_store::get_ring_limit(&segment.capture_id) {
+            let _ = capture_store::set_ring_limit(&capture_id, Some(limit));
+        }
+        crate::io::emit_buffer_created(&BufferCreatedPayload {
+            session_id: session_id.to_string(),
+            capture_id,
+            name,
+            kind: segment.kind.clone(),
+        });
+    }
+
+    let segments = ROTATIONS.write().ok().and_then(|mut r| {
+        r.get_mut(session_id).map(|rotation| {
+            rotation.segments += 1;
+            rotation.segments
+        })
+    });
+    tlog!(
+        "[CaptureRotation:{}] Segment {} closed ({:?}): {:?}",
+        session_id,
+        segments.unwrap_or(0),
+        reason,
+        orphaned.iter().map(|o| &o.capture_id).collect::<Vec<_>>()
+    );
+    crate::io::emit_capture_changed(session_id);
+}
+
+/// Remove a session's rotation policy. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut rotations) = ROTATIONS.write() {
+        rotations.remove(session_id);
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Split a session's recording whenever a limit is reached, replacing any
+/// existing policy. The frames and span of the capture being written count
+/// from when it started; its payload bytes from now.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_capture_rotation(session_id: String, policy: RotationPolicy) -> Result<RotationState, String> {
+    policy.validate()?;
+    let mut rotation = Rotation::new(policy);
+    // Pick up the capture already being written so its frames count
+    if let Some(id) = capture_store::get_session_frame_capture_id(&session_id) {
+        if let Some(meta) = capture_store::get_capture_metadata(&id) {
+            rotation.frames = Segment {
+                capture_id: id,
+                items: meta.count as u64,
+                bytes: 0,
+                first_us: meta.start_time_us,
+                last_us: meta.end_time_us,
+            };
+        }
+    }
+    let state = rotation.snapshot(&session_id);
+    ROTATIONS
+        .write()
+        .map_err(|e| format!("Rotation lock poisoned: {e}"))?
+        .insert(session_id.clone(), rotation);
+    tlog!("[CaptureRotation:{}] Policy set: {:?}", session_id, state.policy);
+    Ok(state)
+}
+
+/// Stop splitting a session's recording. The current capture keeps growing.
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_capture_rotation(session_id: String) {
+    clear_session(&session_id);
+}
+
+/// A session's rotation policy and current segment (None = no policy).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_capture_rotation(session_id: String) -> Option<RotationState> {
+    ROTATIONS.read().ok().and_then(|r| r.get(&session_id).map(|rotation| rotation.snapshot(&session_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_frames: Option<u64>, max_bytes: Option<u64>, max_duration_ms: Option<u64>) -> RotationPolicy {
+        RotationPolicy { max_frames, max_bytes, max_duration_ms }
+    }
+
+    #[test]
+    fn a_policy_needs_a_positive_limit() {
+        assert!(policy(None, None, None).validate().is_err());
+        assert!(policy(Some(0), None, None).validate().is_err());
+        assert!(policy(None, Some(1 << 20), None).validate().is_ok());
+    }
+
+    #[test]
+    fn segments_fill_up_by_frames_bytes_or_span() {
+        let mut rotation = Rotation::new(policy(Some(100), Some(600), Some(1_000)));
+        assert_eq!(rotation.record("a", CaptureKind::Frames, 60, 480, 0, 500_000), None);
+        assert_eq!(rotation.record("a", CaptureKind::Frames, 40, 80, 500_100, 600_000), Some(RotationReason::Frames));
+
+        let mut by_bytes = Rotation::new(policy(None, Some(600), None));
+        assert_eq!(by_bytes.record("a", CaptureKind::Bytes, 600, 600, 0, 10), Some(RotationReason::Bytes));
+
+        let mut by_span = Rotation::new(policy(None, None, Some(1_000)));
+        assert_eq!(by_span.record("a", CaptureKind::Frames, 1, 8, 2_000_000, 2_000_000), None);
+        assert_eq!(by_span.record("a", CaptureKind::Frames, 1, 8, 3_000_000, 3_000_000), Some(RotationReason::Duration));
+    }
+
+    #[test]
+    fn a_new_capture_starts_a_new_segment() {
+        let mut rotation = Rotation::new(policy(Some(100), None, None));
+        rotation.record("a", CaptureKind::Frames, 90, 0, 0, 0);
+        // Session restarted into capture "b": "a"'s frames no longer count
+        assert_eq!(rotation.record("b", CaptureKind::Frames, 20, 0, 0, 0), None);
+        let state = rotation.snapshot("s1");
+        assert_eq!((state.frames, state.segments), (20, 0));
+    }
+}
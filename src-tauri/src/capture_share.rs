@@ -0,0 +1,606 @@
+// ui/src-tauri/src/capture_share.rs
+//
+// WireTAP-to-WireTAP capture streaming. One instance shares its captures over
+// the LAN; another pulls a capture straight into its own capture database, so
+// large captures don't need an export → USB stick → import round trip.
+//
+// Sharing side: an opt-in HTTP listener (0.0.0.0:port) gated by a bearer
+// token that is generated when sharing starts. Endpoints:
+//
+//   GET /captures                         capture metadata list
+//   GET /captures/{id}                    one capture's metadata
+//   GET /captures/{id}/chunk?cursor&limit next chunk of frames or bytes
+//
+// Importing side: the import pulls chunks into a new local capture and records
+// its cursor (remote rowid for frames, byte offset for bytes) in
+// capture-imports.json after every chunk, so an interrupted import — dropped
+// Wi-Fi, the other end closing, an app restart — resumes where it stopped.
+// Tokens are not persisted; resuming takes the (possibly new) token.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header::AUTHORIZATION, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio_util::sync::CancellationToken;
+
+use crate::capture_db;
+use crate::capture_store::{self, CaptureKind, CaptureMetadata, TimestampedByte};
+use crate::io::FrameMessage;
+
+/// Default port for capture sharing (MCP uses 8787).
+const DEFAULT_SHARE_PORT: u16 = 8788;
+
+/// Frames/bytes per chunk when the client doesn't ask for a size.
+const DEFAULT_CHUNK: usize = 5000;
+
+/// Upper bound on a requested chunk.
+const MAX_CHUNK: usize = 50_000;
+
+/// Per-request timeout for the importing side.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+// ============================================================================
+// Wire types
+// ============================================================================
+
+/// One chunk of a shared capture. Exactly one of `frames` / `bytes` is
+/// populated, according to `kind`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CaptureChunk {
+    pub kind: CaptureKind,
+    /// Pass back as `cursor` to fetch the following chunk
+    pub next_cursor: i64,
+    #[serde(default)]
+    pub frames: Vec<FrameMessage>,
+    #[serde(default)]
+    pub bytes: Vec<TimestampedByte>,
+    /// True when this chunk reaches the end of the capture
+    pub done: bool,
+}
+
+#[derive(Deserialize)]
+struct ChunkQuery {
+    #[serde(default)]
+    cursor: i64,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+// ============================================================================
+// Sharing side (server)
+// ============================================================================
+
+struct ShareHandle {
+    cancel: CancellationToken,
+    port: u16,
+    token: String,
+}
+
+static SHARE: Lazy<Mutex<Option<ShareHandle>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CaptureShareStatus {
+    pub running: bool,
+    pub port: Option<u16>,
+    /// Token importing instances must present (only while running)
+    pub token: Option<String>,
+}
+
+fn share_status() -> CaptureShareStatus {
+    let guard = SHARE.lock().ok();
+    let handle = guard.as_ref().and_then(|g| g.as_ref());
+    CaptureShareStatus {
+        running: handle.is_some(),
+        port: handle.map(|h| h.port),
+        token: handle.map(|h| h.token.clone()),
+    }
+}
+
+/// 128-bit hex token from the std hasher's OS-seeded keys. The listener is
+/// reachable from the LAN, so this must not be guessable from the clock.
+fn generate_token() -> String {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    (0..2)
+        .map(|i| {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u128(nanos ^ i);
+            format!("{:016x}", hasher.finish())
+        })
+        .collect()
+}
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(serde_json::json!({ "error": message.into() }))).into_response()
+}
+
+async fn share_auth(State(token): State<Arc<String>>, req: Request, next: Next) -> Response {
+    let ok = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v == format!("Bearer {}", token.as_str()))
+        .unwrap_or(false);
+    if !ok {
+        return error_response(StatusCode::UNAUTHORIZED, "Invalid or missing share token");
+    }
+    next.run(req).await
+}
+
+async fn list_handler() -> Json<Vec<CaptureMetadata>> {
+    let mut captures = capture_store::list_captures();
+    captures.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+    Json(captures)
+}
+
+async fn metadata_handler(Path(id): Path<String>) -> Response {
+    match capture_store::get_capture_metadata(&id) {
+        Some(meta) => Json(meta).into_response(),
+        None => error_response(StatusCode::NOT_FOUND, msg!(CAPTURE_NOT_FOUND, capture_id = id)),
+    }
+}
+
+/// Read the chunk after `cursor`. Frames use keyset paging on rowid so deep
+/// chunks of a large capture stay cheap; byte captures page by offset.
+fn read_chunk(id: &str, cursor: i64, limit: usize) -> Result<CaptureChunk, String> {
+    let kind = capture_store::get_capture_kind(id).ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = id))?;
+    match kind {
+        CaptureKind::Frames => {
+            let rows = capture_db::read_frame_chunk(id, cursor, limit)?;
+            let done = rows.len() < limit;
+            let next_cursor = rows.last().map(|(rowid, _)| *rowid).unwrap_or(cursor);
+            let frames = rows
+                .into_iter()
+                .map(|(_, mut frame)| {
+                    // Parent rowids point into the sharing database; the
+                    // importing side re-resolves them from correlation IDs.
+                    if let Some(link) = frame.link.as_mut() {
+                        link.parent_rowid = None;
+                    }
+                    frame
+                })
+                .collect();
+            Ok(CaptureChunk { kind, next_cursor, frames, bytes: Vec::new(), done })
+        }
+        CaptureKind::Bytes => {
+            let offset = cursor.max(0) as usize;
+            let (bytes, total) = capture_db::get_bytes_paginated(id, offset, limit)?;
+            let next = offset + bytes.len();
+            Ok(CaptureChunk {
+                kind,
+                next_cursor: next as i64,
+                frames: Vec::new(),
+                bytes,
+                done: next >= total,
+            })
+        }
+    }
+}
+
+async fn chunk_handler(Path(id): Path<String>, Query(q): Query<ChunkQuery>) -> Response {
+    let limit = q.limit.unwrap_or(DEFAULT_CHUNK).clamp(1, MAX_CHUNK);
+    match tokio::task::spawn_blocking(move || read_chunk(&id, q.cursor, limit)).await {
+        Ok(Ok(chunk)) => Json(chunk).into_response(),
+        Ok(Err(e)) => error_response(StatusCode::NOT_FOUND, e),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+/// Start sharing captures on `0.0.0.0:port`. Binds synchronously so a port
+/// conflict is returned as an error.
+fn start_share(port: u16, token: String) -> Result<(), String> {
+    if token.is_empty() {
+        return Err("A share token is required".to_string());
+    }
+    let mut guard = SHARE.lock().map_err(|e| e.to_string())?;
+    if guard.is_some() {
+        return Err("Capture sharing is already running".to_string());
+    }
+
+    let std_listener = std::net::TcpListener::bind(("0.0.0.0", port))
+        .map_err(|e| format!("Failed to bind capture share on port {port}: {e}"))?;
+    std_listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to set capture share listener non-blocking: {e}"))?;
+
+    let router = axum::Router::new()
+        .route("/captures", axum::routing::get(list_handler))
+        .route("/captures/{id}", axum::routing::get(metadata_handler))
+        .route("/captures/{id}/chunk", axum::routing::get(chunk_handler))
+        .layer(axum::middleware::from_fn_with_state(Arc::new(token.clone()), share_auth));
+
+    let cancel = CancellationToken::new();
+    let cancel_for_shutdown = cancel.clone();
+    tauri::async_runtime::spawn(async move {
+        let listener = match tokio::net::TcpListener::from_std(std_listener) {
+            Ok(l) => l,
+            Err(e) => {
+                tlog!("[capture_share] Failed to adopt listener: {e}");
+                return;
+            }
+        };
+        let _ = axum::serve(listener, router)
+            .with_graceful_shutdown(async move {
+                cancel_for_shutdown.cancelled().await;
+            })
+            .await;
+        tlog!("[capture_share] Server task exited");
+    });
+
+    *guard = Some(ShareHandle { cancel, port, token });
+    tlog!("[capture_share] Sharing captures on 0.0.0.0:{port}");
+    Ok(())
+}
+
+/// Start sharing this instance's captures with other WireTAP instances on the
+/// LAN. A token is generated unless one is given; share it with the importer.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn start_capture_share(port: Option<u16>, token: Option<String>) -> Result<CaptureShareStatus, String> {
+    let token = token.filter(|t| !t.is_empty()).unwrap_or_else(generate_token);
+    start_share(port.unwrap_or(DEFAULT_SHARE_PORT), token)?;
+    Ok(share_status())
+}
+
+/// Stop sharing captures (graceful shutdown; in-flight chunks complete).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn stop_capture_share() -> Result<CaptureShareStatus, String> {
+    if let Ok(mut guard) = SHARE.lock() {
+        if let Some(handle) = guard.take() {
+            handle.cancel.cancel();
+            tlog!("[capture_share] Sharing stopped on port {}", handle.port);
+        }
+    }
+    Ok(share_status())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_capture_share_status() -> Result<CaptureShareStatus, String> {
+    Ok(share_status())
+}
+
+// ============================================================================
+// Importing side (client)
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    Running,
+    /// Stopped by an error or app exit; can be resumed
+    Interrupted,
+    Cancelled,
+    Completed,
+}
+
+/// A (possibly finished) pull of a remote capture. Persisted so imports can be
+/// resumed after a restart.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemoteImport {
+    pub import_id: String,
+    /// e.g. "http://192.168.1.20:8788"
+    pub base_url: String,
+    pub remote_capture_id: String,
+    pub local_capture_id: String,
+    pub kind: CaptureKind,
+    /// Position in the remote capture (rowid for frames, offset for bytes)
+    pub cursor: i64,
+    pub imported: usize,
+    /// Remote item count when the import started (grows if it is still recording)
+    pub total: usize,
+    pub status: ImportStatus,
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Unix seconds when the import was started
+    pub started_at: u64,
+}
+
+static HTTP: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+});
+
+/// Loaded lazily from capture-imports.json on first use.
+static IMPORTS: Lazy<Mutex<Option<HashMap<String, RemoteImport>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Cancel flags of imports currently running.
+static RUNNING_IMPORTS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn imports_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    Ok(dir.join("capture-imports.json"))
+}
+
+/// Run `f` over the import records, loading them on first use and writing
+/// them back afterwards.
+fn with_imports<R>(app: &AppHandle, f: impl FnOnce(&mut HashMap<String, RemoteImport>) -> R) -> Result<R, String> {
+    let path = imports_path(app)?;
+    let mut guard = IMPORTS.lock().map_err(|e| e.to_string())?;
+    let imports = guard.get_or_insert_with(|| {
+        let mut loaded: HashMap<String, RemoteImport> = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        // Anything still "running" was cut off by the app exiting
+        for record in loaded.values_mut() {
+            if record.status == ImportStatus::Running {
+                record.status = ImportStatus::Interrupted;
+            }
+        }
+        loaded
+    });
+    let result = f(imports);
+    let content = serde_json::to_string_pretty(&*imports).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write capture imports: {}", e))?;
+    Ok(result)
+}
+
+fn base_url(host: &str, port: Option<u16>) -> String {
+    let host = host.trim().trim_end_matches('/');
+    let host = host.strip_prefix("http://").unwrap_or(host);
+    format!("http://{}:{}", host, port.unwrap_or(DEFAULT_SHARE_PORT))
+}
+
+async fn fetch<T: serde::de::DeserializeOwned>(url: &str, token: &str) -> Result<T, String> {
+    let resp = HTTP
+        .get(url)
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|e| format!("Request to {} failed: {}", url, e))?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let msg = resp
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(String::from))
+            .unwrap_or_else(|| format!("HTTP {status}"));
+        return Err(msg);
+    }
+    resp.json::<T>().await.map_err(|e| format!("Invalid response from {}: {}", url, e))
+}
+
+fn emit_progress(app: &AppHandle, record: &RemoteImport) {
+    let _ = app.emit("capture-import-progress", record);
+}
+
+/// Record a status change and emit it.
+fn finish_import(app: &AppHandle, import_id: &str, status: ImportStatus, error: Option<String>) {
+    if let Ok(Some(record)) = with_imports(app, |imports| {
+        imports.get_mut(import_id).map(|r| {
+            r.status = status;
+            r.error = error;
+            r.clone()
+        })
+    }) {
+        tlog!(
+            "[capture_share] Import '{}' {:?} after {} item(s)",
+            import_id, status, record.imported
+        );
+        emit_progress(app, &record);
+    }
+    if let Ok(mut running) = RUNNING_IMPORTS.lock() {
+        running.remove(import_id);
+    }
+}
+
+/// Pull chunks until the remote capture is exhausted, cancelled or an error
+/// interrupts the import.
+async fn run_import(app: AppHandle, import_id: String, token: String, cancel: Arc<AtomicBool>) {
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            finish_import(&app, &import_id, ImportStatus::Cancelled, None);
+            return;
+        }
+        let Ok(Some(record)) = with_imports(&app, |imports| imports.get(&import_id).cloned()) else {
+            return;
+        };
+        let url = format!(
+            "{}/captures/{}/chunk?cursor={}&limit={}",
+            record.base_url, record.remote_capture_id, record.cursor, DEFAULT_CHUNK
+        );
+        let chunk: CaptureChunk = match fetch(&url, &token).await {
+            Ok(c) => c,
+            Err(e) => {
+                finish_import(&app, &import_id, ImportStatus::Interrupted, Some(e));
+                return;
+            }
+        };
+        if capture_store::get_capture_kind(&record.local_capture_id).is_none() {
+            finish_import(
+                &app,
+                &import_id,
+                ImportStatus::Interrupted,
+                Some("Local capture no longer exists".to_string()),
+            );
+            return;
+        }
+
+        let received = chunk.frames.len() + chunk.bytes.len();
+        match chunk.kind {
+            CaptureKind::Frames => capture_store::append_frames_to_capture(&record.local_capture_id, chunk.frames),
+            CaptureKind::Bytes => capture_store::append_raw_bytes_to_capture(&record.local_capture_id, chunk.bytes),
+        }
+        // Keep the persisted count in step with the cursor for restarts
+        if let Some(meta) = capture_store::get_capture_metadata(&record.local_capture_id) {
+            if let Err(e) = capture_db::save_capture_metadata(&meta) {
+                tlog!("[capture_share] Failed to persist import metadata: {}", e);
+            }
+        }
+
+        let updated = with_imports(&app, |imports| {
+            imports.get_mut(&import_id).map(|r| {
+                r.cursor = chunk.next_cursor;
+                r.imported += received;
+                r.total = r.total.max(r.imported);
+                r.clone()
+            })
+        });
+        if let Ok(Some(record)) = updated {
+            emit_progress(&app, &record);
+        }
+        if chunk.done {
+            finish_import(&app, &import_id, ImportStatus::Completed, None);
+            return;
+        }
+    }
+}
+
+fn spawn_import(app: AppHandle, import_id: String, token: String) -> Result<(), String> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut running = RUNNING_IMPORTS.lock().map_err(|e| e.to_string())?;
+        if running.contains_key(&import_id) {
+            return Err(format!("Import '{}' is already running", import_id));
+        }
+        running.insert(import_id.clone(), cancel.clone());
+    }
+    tauri::async_runtime::spawn(run_import(app, import_id, token, cancel));
+    Ok(())
+}
+
+/// List the captures another WireTAP instance is sharing.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_remote_captures(
+    host: String,
+    port: Option<u16>,
+    token: String,
+) -> Result<Vec<CaptureMetadata>, String> {
+    fetch(&format!("{}/captures", base_url(&host, port)), &token).await
+}
+
+/// Start pulling a capture from another WireTAP instance into a new local
+/// capture. Progress is emitted as `capture-import-progress`.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_remote_capture(
+    app: AppHandle,
+    host: String,
+    port: Option<u16>,
+    token: String,
+    capture_id: String,
+    name: Option<String>,
+) -> Result<RemoteImport, String> {
+    let base_url = base_url(&host, port);
+    let meta: CaptureMetadata = fetch(&format!("{}/captures/{}", base_url, capture_id), &token).await?;
+
+    let local_name = name.unwrap_or_else(|| format!("{} ({})", meta.name, host.trim()));
+    let local_capture_id = capture_store::create_capture_inactive(meta.kind.clone(), local_name);
+    let record = RemoteImport {
+        import_id: format!("import_{}", local_capture_id),
+        base_url,
+        remote_capture_id: capture_id,
+        local_capture_id,
+        kind: meta.kind,
+        cursor: 0,
+        imported: 0,
+        total: meta.count,
+        status: ImportStatus::Running,
+        error: None,
+        started_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+    with_imports(&app, |imports| imports.insert(record.import_id.clone(), record.clone()))?;
+    tlog!(
+        "[capture_share] Importing '{}' from {} into '{}'",
+        record.remote_capture_id, record.base_url, record.local_capture_id
+    );
+    spawn_import(app.clone(), record.import_id.clone(), token)?;
+    emit_progress(&app, &record);
+    Ok(record)
+}
+
+/// Resume an interrupted or cancelled import from its last chunk. The token
+/// is required again (sharing generates a new one each time it starts).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn resume_remote_import(app: AppHandle, import_id: String, token: String) -> Result<RemoteImport, String> {
+    let record = with_imports(&app, |imports| {
+        let r = imports
+            .get_mut(&import_id)
+            .ok_or_else(|| format!("Import '{}' not found", import_id))?;
+        match r.status {
+            ImportStatus::Completed => return Err("Import already completed".to_string()),
+            ImportStatus::Running => return Err("Import is already running".to_string()),
+            _ => {}
+        }
+        if capture_store::get_capture_kind(&r.local_capture_id).is_none() {
+            return Err("The local capture was deleted or cleared; start a new import".to_string());
+        }
+        r.status = ImportStatus::Running;
+        r.error = None;
+        Ok(r.clone())
+    })??;
+    spawn_import(app.clone(), import_id, token)?;
+    emit_progress(&app, &record);
+    Ok(record)
+}
+
+/// Stop a running import after its current chunk. It can be resumed later.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cancel_remote_import(import_id: String) -> Result<(), String> {
+    if let Ok(running) = RUNNING_IMPORTS.lock() {
+        if let Some(cancel) = running.get(&import_id) {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    }
+    Ok(())
+}
+
+/// All known imports (running, resumable and finished), newest first.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_remote_imports(app: AppHandle) -> Result<Vec<RemoteImport>, String> {
+    let mut list = with_imports(&app, |imports| imports.values().cloned().collect::<Vec<_>>())?;
+    list.sort_by_key(|r| std::cmp::Reverse(r.started_at));
+    Ok(list)
+}
+
+/// Forget a finished or interrupted import (the local capture is kept).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn forget_remote_import(app: AppHandle, import_id: String) -> Result<(), String> {
+    if RUNNING_IMPORTS.lock().map(|r| r.contains_key(&import_id)).unwrap_or(false) {
+        return Err("Cancel the import before removing it".to_string());
+    }
+    with_imports(&app, |imports| {
+        imports.remove(&import_id);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_url_normalises_host() {
+        assert_eq!(base_url("192.168.1.20", None), "http://192.168.1.20:8788");
+        assert_eq!(base_url(" http://bench.local/ ", Some(9000)), "http://bench.local:9000");
+    }
+
+    #[test]
+    fn tokens_are_unique() {
+        let a = generate_token();
+        assert_eq!(a.len(), 32);
+        assert_ne!(a, generate_token());
+    }
+}
@@ -23,6 +23,7 @@ use crate::io::FrameMessage;
 
 /// Capture kind - determines what kind of data the capture contains
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 #[serde(rename_all = "snake_case")]
 pub enum CaptureKind {
     /// CAN frames, framed serial messages
@@ -45,6 +46,7 @@ pub struct TimestampedByte {
 
 /// Metadata about a capture
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 pub struct CaptureMetadata {
     /// Unique capture ID (e.g., "xk9m2p", "r7f3kw")
     pub id: String,
@@ -75,6 +77,99 @@ pub struct CaptureMetadata {
     /// Enables bus mapping/wiring when a capture is used as a source.
     #[serde(default)]
     pub buses: Vec<u8>,
+    /// Timestamp source and host clock sync when the capture was recorded
+    /// (None for imports and captures recorded before this was tracked).
+    #[serde(default)]
+    pub timing: Option<crate::timebase::CaptureTiming>,
+    /// Content hash (and optional signature) sealed at finalisation. Cleared
+    /// when the capture's contents change.
+    #[serde(default)]
+    pub integrity: Option<crate::capture_integrity::CaptureIntegrity>,
+    /// Payload bytes the capture holds in the capture database (compressed
+    /// cold chunks plus inline payloads); kept current on append. 0 for byte
+    /// captures.
+    #[serde(default)]
+    pub stored_bytes: u64,
+}
+
+/// Ring-buffer retention for a streaming capture: once either limit is
+/// exceeded the oldest frames are dropped, so long monitoring sessions keep
+/// only their most recent window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RingLimit {
+    /// Keep at most this many frames
+    #[serde(default)]
+    pub max_frames: Option<usize>,
+    /// Keep only frames within this many milliseconds of the newest
+    #[serde(default)]
+    pub max_duration_ms: Option<u64>,
+}
+
+/// Payload of the `capture-trimmed` event, emitted after a ring trim so views
+/// can shift pagination offsets down by `removed`.
+#[derive(Clone, Debug, Serialize)]
+pub struct CaptureTrimmedPayload {
+    pub capture_id: String,
+    pub removed: usize,
+    pub count: usize,
+    pub start_time_us: Option<u64>,
+}
+
+/// Structured frame search: every non-empty part must match.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FramePattern {
+    /// Frame ID ranges (inclusive); a frame matches any one of them
+    #[serde(default)]
+    pub id_ranges: Vec<IdRange>,
+    /// Per-byte conditions, e.g. byte 3 & 0xF0 == 0x20
+    #[serde(default)]
+    pub byte_masks: Vec<ByteMask>,
+    /// Byte sequence to find anywhere in the payload, as hex ("DE AD" or "dead")
+    #[serde(default)]
+    pub payload_hex: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct IdRange {
+    pub from: u32,
+    pub to: u32,
+}
+
+/// `payload[index] & mask == value & mask`; frames shorter than `index + 1`
+/// bytes never match.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ByteMask {
+    pub index: usize,
+    pub mask: u8,
+    pub value: u8,
+}
+
+/// One input to `merge_captures`: a frame capture plus an optional bus
+/// renumbering (source bus → merged bus), so captures taken separately on
+/// devices that all call their port "bus 0" stay distinguishable.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MergeSource {
+    pub capture_id: String,
+    #[serde(default)]
+    pub bus_map: HashMap<u8, u8>,
+}
+
+/// A note or marker pinned inside a capture ("door opened here").
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+pub struct CaptureAnnotation {
+    pub id: i64,
+    pub capture_id: String,
+    /// Time the annotation marks (the pinned frame's timestamp, if any)
+    pub timestamp_us: u64,
+    /// Current 0-based index of the pinned frame (None when pinned to a
+    /// time, or the frame has been trimmed away or the capture copied)
+    pub frame_index: Option<usize>,
+    pub text: String,
+    /// CSS colour for the marker (None = default)
+    pub color: Option<String>,
+    /// When the annotation was made (Unix timestamp in seconds)
+    pub created_at: u64,
 }
 
 // ============================================================================
@@ -90,8 +185,15 @@ struct NamedCapture {
     /// unique-frame count. Populated on append only — empty for DB-hydrated
     /// captures (their live "unique" display uses a different path).
     unique_frame_ids: HashSet<u64>,
+    /// Ring-buffer limit enforced on append (None = keep everything). The
+    /// bus and unique-frame sets are not pruned when frames are dropped.
+    ring: Option<RingLimit>,
 }
 
+/// Ring captures trim once they overshoot a limit by 1/RING_SLACK_DIVISOR, so
+/// the DELETE runs every few batches rather than on every one.
+const RING_SLACK_DIVISOR: u64 = 100;
+
 /// Pack (bus, frame_id) into one key for the unique-frame set.
 #[inline]
 fn unique_frame_key(bus: u8, frame_id: u32) -> u64 {
@@ -219,9 +321,12 @@ fn create_capture_internal(kind: CaptureKind, name: String, set_streaming: bool)
         owning_session_id: None,
         persistent: false,
         buses: Vec::new(),
+        timing: None,
+        integrity: None,
+        stored_bytes: 0,
     };
 
-    let capture = NamedCapture { metadata: metadata.clone(), seen_buses: HashSet::new(), unique_frame_ids: HashSet::new() };
+    let capture = NamedCapture { metadata: metadata.clone(), seen_buses: HashSet::new(), unique_frame_ids: HashSet::new(), ring: None };
     registry.captures.insert(id.clone(), capture);
 
     if set_streaming {
@@ -311,10 +416,12 @@ pub fn delete_capture(id: &str) -> Result<(), String> {
         if let Err(e) = capture_db::delete_capture_metadata(id) {
             tlog!("[CaptureStore] Failed to delete capture metadata from SQLite: {}", e);
         }
+        crate::row_model::clear_capture(id);
+        crate::capture_journal::close(id);
         tlog!("[CaptureStore] Deleted capture '{}'", id);
         Ok(())
     } else {
-        Err(format!("Capture '{}' not found", id))
+        Err(msg!(CAPTURE_NOT_FOUND, capture_id = id).into())
     }
 }
 
@@ -329,19 +436,23 @@ pub fn clear_capture(id: &str) -> Result<(), String> {
         if let Some(cap) = registry.captures.get_mut(id) {
             owning_session = cap.metadata.owning_session_id.clone();
             cap.metadata.count = 0;
+            cap.metadata.stored_bytes = 0;
+            cap.metadata.integrity = None;
             cap.metadata.start_time_us = None;
             cap.metadata.end_time_us = None;
             cap.metadata.buses = Vec::new();
             cap.seen_buses.clear();
             cap.unique_frame_ids.clear();
         } else {
-            return Err(format!("Capture '{}' not found", id));
+            return Err(msg!(CAPTURE_NOT_FOUND, capture_id = id).into());
         }
     }
 
     if let Err(e) = capture_db::delete_capture_data(id) {
         tlog!("[CaptureStore] Failed to clear capture data from SQLite: {}", e);
     }
+    crate::row_model::mark_capture_changed(id);
+    crate::capture_journal::close(id);
 
     // Reset the WS frame delivery offset so new frames arriving into
     // this capture are delivered to subscribers from the beginning.
@@ -353,12 +464,139 @@ pub fn clear_capture(id: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Delete a capture's items outside (`keep_inside`) or inside a time range,
+/// in place. The count, span, buses and unique-frame tracking are rebuilt
+/// from what remains, and the integrity seal is cleared. Refused while the
+/// capture is streaming, since live delivery tracks positions in it.
+pub fn trim_capture(
+    id: &str,
+    start_us: Option<u64>,
+    end_us: Option<u64>,
+    keep_inside: bool,
+) -> Result<(usize, CaptureMetadata), String> {
+    if let (Some(start), Some(end)) = (start_us, end_us) {
+        if start > end {
+            return Err("Trim range start is after its end".to_string());
+        }
+    }
+    let kind = {
+        let registry = CAPTURE_REGISTRY.read().unwrap();
+        let cap = registry.captures.get(id)
+            .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = id))?;
+        if registry.streaming_ids.contains(id) {
+            return Err("Stop the session before trimming its capture".to_string());
+        }
+        cap.metadata.kind.clone()
+    };
+
+    let summary = capture_db::trim_capture_data(id, kind == CaptureKind::Bytes, start_us, end_us, keep_inside)?;
+
+    let meta = {
+        let mut registry = CAPTURE_REGISTRY.write().unwrap();
+        let cap = registry.captures.get_mut(id)
+            .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = id))?;
+        cap.metadata.count = summary.count;
+        cap.metadata.start_time_us = summary.start_time_us;
+        cap.metadata.end_time_us = summary.end_time_us;
+        cap.metadata.buses = summary.buses.clone();
+        cap.metadata.stored_bytes = summary.stored_bytes;
+        if summary.removed > 0 {
+            cap.metadata.integrity = None;
+        }
+        cap.seen_buses = summary.buses.iter().copied().collect();
+        cap.unique_frame_ids = summary
+            .frame_keys
+            .iter()
+            .map(|&(bus, frame_id)| unique_frame_key(bus, frame_id))
+            .collect();
+        cap.metadata.clone()
+    };
+
+    if let Err(e) = capture_db::save_capture_metadata(&meta) {
+        tlog!("[CaptureStore] Failed to persist trimmed capture metadata: {}", e);
+    }
+    crate::row_model::mark_capture_changed(id);
+
+    tlog!(
+        "[CaptureStore] Trimmed capture '{}': removed {}, {} remain",
+        id, summary.removed, summary.count
+    );
+    Ok((summary.removed, meta))
+}
+
+/// Copy the part of a capture within `[start_us, end_us]` into a new orphaned
+/// capture, leaving the source as it is. An open bound extends to the
+/// capture's start or end. Returns the new capture's metadata.
+pub fn split_capture(
+    id: &str,
+    start_us: Option<u64>,
+    end_us: Option<u64>,
+    new_name: String,
+) -> Result<CaptureMetadata, String> {
+    if let (Some(start), Some(end)) = (start_us, end_us) {
+        if start > end {
+            return Err("Split range start is after its end".to_string());
+        }
+    }
+    let source = {
+        let registry = CAPTURE_REGISTRY.read().unwrap();
+        registry.captures.get(id)
+            .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = id))?
+            .metadata
+            .clone()
+    };
+
+    let new_id = generate_capture_id(&CAPTURE_REGISTRY.read().unwrap());
+    let summary = capture_db::copy_capture_range(id, &new_id, source.kind == CaptureKind::Bytes, start_us, end_us)?;
+
+    let created_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let metadata = CaptureMetadata {
+        id: new_id.clone(),
+        name: new_name,
+        count: summary.count,
+        start_time_us: summary.start_time_us,
+        end_time_us: summary.end_time_us,
+        created_at,
+        is_streaming: false,
+        owning_session_id: None,
+        persistent: false,
+        buses: summary.buses.clone(),
+        integrity: None,
+        stored_bytes: summary.stored_bytes,
+        ..source
+    };
+    let entry = NamedCapture {
+        metadata: metadata.clone(),
+        seen_buses: summary.buses.iter().copied().collect(),
+        unique_frame_ids: summary
+            .frame_keys
+            .iter()
+            .map(|&(bus, frame_id)| unique_frame_key(bus, frame_id))
+            .collect(),
+        ring: None,
+    };
+    CAPTURE_REGISTRY.write().unwrap().captures.insert(new_id.clone(), entry);
+
+    if let Err(e) = capture_db::save_capture_metadata(&metadata) {
+        tlog!("[CaptureStore] Failed to persist split capture metadata: {}", e);
+    }
+
+    tlog!(
+        "[CaptureStore] Split capture '{}' -> '{}' ({} items)",
+        id, new_id, summary.count
+    );
+    Ok(metadata)
+}
+
 /// Rename a capture.
 /// Updates both the in-memory registry and SQLite metadata.
 pub fn rename_capture(id: &str, new_name: &str) -> Result<CaptureMetadata, String> {
     let mut registry = CAPTURE_REGISTRY.write().unwrap();
     let cap = registry.captures.get_mut(id)
-        .ok_or_else(|| format!("Capture '{}' not found", id))?;
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = id))?;
 
     cap.metadata.name = new_name.to_string();
     let meta = cap.metadata.clone();
@@ -379,7 +617,7 @@ pub fn rename_capture(id: &str, new_name: &str) -> Result<CaptureMetadata, Strin
 pub fn set_capture_persistent(id: &str, persistent: bool) -> Result<CaptureMetadata, String> {
     let mut registry = CAPTURE_REGISTRY.write().unwrap();
     let cap = registry.captures.get_mut(id)
-        .ok_or_else(|| format!("Capture '{}' not found", id))?;
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = id))?;
 
     cap.metadata.persistent = persistent;
     let meta = cap.metadata.clone();
@@ -395,6 +633,27 @@ pub fn set_capture_persistent(id: &str, persistent: bool) -> Result<CaptureMetad
     Ok(meta)
 }
 
+/// Set or clear a frame capture's ring-buffer limit. Takes effect on the next
+/// append; limits are not persisted.
+pub fn set_ring_limit(id: &str, limit: Option<RingLimit>) -> Result<(), String> {
+    let mut registry = CAPTURE_REGISTRY.write().unwrap();
+    let cap = registry.captures.get_mut(id)
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = id))?;
+    if cap.metadata.kind != CaptureKind::Frames {
+        return Err("Ring limits apply to frame captures only".to_string());
+    }
+    cap.ring = limit.filter(|l| l.max_frames.is_some() || l.max_duration_ms.is_some());
+
+    tlog!("[CaptureStore] Set capture '{}' ring limit {:?}", id, cap.ring);
+    Ok(())
+}
+
+/// Get a capture's ring-buffer limit, if any.
+pub fn get_ring_limit(id: &str) -> Option<RingLimit> {
+    let registry = CAPTURE_REGISTRY.read().unwrap();
+    registry.captures.get(id).and_then(|c| c.ring)
+}
+
 /// Hydrate the in-memory capture registry from persisted SQLite metadata.
 /// Called on startup when `clear_captures_on_start` is false.
 /// Verifies that data actually exists in SQLite for each metadata entry.
@@ -476,6 +735,7 @@ pub fn hydrate_from_db() {
             },
             seen_buses,
             unique_frame_ids: HashSet::new(),
+            ring: None,
         };
 
         // Persist if we changed anything (backfilled buses or orphaned)
@@ -501,6 +761,10 @@ pub fn set_capture_owner(capture_id: &str, session_id: &str) -> Result<(), Strin
         let mut registry = CAPTURE_REGISTRY.write().unwrap();
         if let Some(cap) = registry.captures.get_mut(capture_id) {
             cap.metadata.owning_session_id = Some(session_id.to_string());
+            // A fresh capture is about to be filled by this session: record its timing
+            if cap.metadata.count == 0 && cap.metadata.timing.is_none() {
+                cap.metadata.timing = Some(crate::timebase::capture_timing(session_id));
+            }
             tlog!(
                 "[CaptureStore] Assigned capture '{}' to session '{}'",
                 capture_id, session_id
@@ -518,7 +782,39 @@ pub fn set_capture_owner(capture_id: &str, session_id: &str) -> Result<(), Strin
             }
             Ok(())
         }
-        None => Err(format!("Capture '{}' not found", capture_id)),
+        None => Err(msg!(CAPTURE_NOT_FOUND, capture_id = capture_id).into()),
+    }
+}
+
+/// Record timing provenance on a capture (timebase.rs).
+pub fn set_capture_timing(capture_id: &str, timing: crate::timebase::CaptureTiming) {
+    let meta = {
+        let mut registry = CAPTURE_REGISTRY.write().unwrap();
+        registry.captures.get_mut(capture_id).map(|cap| {
+            cap.metadata.timing = Some(timing);
+            cap.metadata.clone()
+        })
+    };
+    if let Some(m) = meta {
+        if let Err(e) = capture_db::save_capture_metadata(&m) {
+            tlog!("[CaptureStore] Failed to persist capture timing: {}", e);
+        }
+    }
+}
+
+/// Record a capture's integrity seal (capture_integrity.rs).
+pub fn set_capture_integrity(capture_id: &str, integrity: crate::capture_integrity::CaptureIntegrity) {
+    let meta = {
+        let mut registry = CAPTURE_REGISTRY.write().unwrap();
+        registry.captures.get_mut(capture_id).map(|cap| {
+            cap.metadata.integrity = Some(integrity);
+            cap.metadata.clone()
+        })
+    };
+    if let Some(m) = meta {
+        if let Err(e) = capture_db::save_capture_metadata(&m) {
+            tlog!("[CaptureStore] Failed to persist capture integrity: {}", e);
+        }
     }
 }
 
@@ -586,6 +882,28 @@ pub fn get_session_capture_ids(session_id: &str) -> Vec<String> {
         .collect()
 }
 
+/// Rough per-item footprint of a captured frame / byte, for quota accounting.
+const FRAME_FOOTPRINT_BYTES: u64 = 96;
+const BYTE_FOOTPRINT_BYTES: u64 = 24;
+
+/// Estimated size of all captures owned by a session. Data lives in SQLite,
+/// so this is an item-count estimate rather than a measured heap size.
+pub fn session_buffer_bytes() -> u64 {
+    let registry = CAPTURE_REGISTRY.read().unwrap();
+    registry
+        .captures
+        .values()
+        .filter(|b| b.metadata.owning_session_id.is_some())
+        .map(|b| {
+            let per_item = match b.metadata.kind {
+                CaptureKind::Frames => FRAME_FOOTPRINT_BYTES,
+                CaptureKind::Bytes => BYTE_FOOTPRINT_BYTES,
+            };
+            b.metadata.count as u64 * per_item
+        })
+        .sum()
+}
+
 /// Next unique "{base}_{n}" capture name — n is the highest existing such suffix + 1.
 /// Gives each per-app "Leave session" snapshot a distinct, sortable name.
 pub fn next_indexed_name(base: &str) -> String {
@@ -618,13 +936,63 @@ pub fn get_session_frame_capture_id(session_id: &str) -> Option<String> {
         .map(|b| b.metadata.id.clone())
 }
 
+/// Per-session frame observer; each checks its own state and returns quickly
+/// when the session has nothing registered.
+type FrameTap = fn(&str, &[FrameMessage]);
+
+/// Observers that see every received frame, before the capture trigger drops
+/// any — they answer or correlate traffic rather than record it.
+const INCOMING_TAPS: &[FrameTap] = &[
+    // Test pattern frames for active io_test runners
+    crate::io_test::tap_test_frames,
+    // The session's simulated-ECU responder (if running)
+    crate::responder::tap_frames,
+    // ECU replies to the session's OBD-II poller (if running)
+    crate::obd_poll::tap_frames,
+    // SDO responses for pending CANopen SDO transfers
+    crate::canopen::tap_frames,
+];
+
+/// Observers of the frames being recorded, after linkage and reassembly.
+const RECORDED_TAPS: &[FrameTap] = &[
+    // Latest decoded value of every signal (attached catalogue only)
+    crate::latest_values::tap_frames,
+    // J1939 address claims and SPNs (built-in table, catalogue [j1939] or set_j1939_config)
+    crate::j1939::tap_frames,
+    // LIN signals decoded from the session's LDF-lite description
+    crate::io::lin::tap_frames,
+    // Push pinned IDs ahead of the frames-ready cadence
+    crate::frame_priority::tap_frames,
+    // Sliding-window statistics for Discovery's live analytics
+    crate::live_analysis::tap_frames,
+    // Crash-persistent ring of the last N seconds (black box mode)
+    crate::black_box::tap_frames,
+    // Mirror to an MQTT broker (if publishing)
+    crate::io::mqtt::publisher::tap_frames,
+];
+
 /// Append frames to this session's frame capture.
 /// Resolves the capture by finding the capture owned by session_id with
 /// capture kind == Frames. No-op if session has no frame capture.
-pub fn append_frames_to_session(session_id: &str, new_frames: Vec<FrameMessage>) {
+pub fn append_frames_to_session(session_id: &str, mut new_frames: Vec<FrameMessage>) {
+    if new_frames.is_empty() { return; }
+    // Session timestamp source (host / monotonic rebase; device = untouched)
+    crate::timebase::stamp_frames(session_id, &mut new_frames);
+    // J1939 source addresses on extended frames (if enabled)
+    crate::j1939::tag_frames(session_id, &mut new_frames);
+    for tap in INCOMING_TAPS {
+        tap(session_id, &new_frames);
+    }
+    // Conditional capture: drop frames outside the session's trigger window
+    let mut new_frames = crate::capture_trigger::filter_frames(session_id, new_frames);
     if new_frames.is_empty() { return; }
-    // Tap test pattern frames for active io_test runners
-    crate::io_test::tap_test_frames(session_id, &new_frames);
+    // Linkage: tag transmit echoes and UDS request/response pairs
+    crate::frame_link::tag_frames(session_id, &mut new_frames);
+    // Multi-frame reassembly: insert synthetic frames after their final segment
+    let new_frames = crate::reassembly::process_frames(session_id, new_frames);
+    for tap in RECORDED_TAPS {
+        tap(session_id, &new_frames);
+    }
     let capture_id = {
         let registry = CAPTURE_REGISTRY.read().unwrap();
         registry.captures.values()
@@ -633,7 +1001,17 @@ pub fn append_frames_to_session(session_id: &str, new_frames: Vec<FrameMessage>)
             .map(|b| b.metadata.id.clone())
     };
     if let Some(id) = capture_id {
+        // Snapshot triggers copy their window once the frames completing it are written
+        let snapshots = crate::trigger_snapshot::tap_frames(session_id, &id, &new_frames);
+        // Auto-split: the batch completes this segment, the next goes to a fresh capture
+        let rotation = crate::capture_rotation::tap_frames(session_id, &id, &new_frames);
+        // Crash-safe journal of the live capture (if enabled)
+        crate::capture_journal::tap_frames(&id, &new_frames);
         append_frames_to_capture(&id, new_frames);
+        crate::trigger_snapshot::take_snapshots(snapshots);
+        if let Some(reason) = rotation {
+            crate::capture_rotation::rotate(session_id, reason);
+        }
     } else {
         tlog!("[CaptureStore] WARN: append_frames_to_session('{}') — no frame capture found for session (dropped {} frames)", session_id, new_frames.len());
     }
@@ -652,7 +1030,12 @@ pub fn append_raw_bytes_to_session(session_id: &str, new_bytes: Vec<TimestampedB
             .map(|b| b.metadata.id.clone())
     };
     if let Some(id) = capture_id {
+        let rotation = crate::capture_rotation::tap_bytes(session_id, &id, &new_bytes);
+        crate::capture_journal::tap_bytes(&id, &new_bytes);
         append_raw_bytes_to_capture(&id, new_bytes);
+        if let Some(reason) = rotation {
+            crate::capture_rotation::rotate(session_id, reason);
+        }
     }
 }
 
@@ -686,7 +1069,11 @@ pub fn finalize_session_captures(session_id: &str) -> Vec<CaptureMetadata> {
         if let Err(e) = capture_db::save_capture_metadata(meta) {
             tlog!("[CaptureStore] Failed to persist finalized capture metadata: {}", e);
         }
+        crate::capture_journal::close(&meta.id);
     }
+    crate::capture_integrity::seal_in_background(
+        finalized.iter().filter(|m| m.count > 0).map(|m| m.id.clone()).collect(),
+    );
 
     finalized
 }
@@ -699,7 +1086,7 @@ pub fn mark_capture_active(capture_id: &str) -> Result<(), String> {
         tlog!("[CaptureStore] Marked capture active: {}", capture_id);
         Ok(())
     } else {
-        Err(format!("Capture '{}' not found", capture_id))
+        Err(msg!(CAPTURE_NOT_FOUND, capture_id = capture_id).into())
     }
 }
 
@@ -729,7 +1116,7 @@ pub fn copy_capture(source_capture_id: &str, new_name: String) -> Result<String,
         let source = registry
             .captures
             .get(source_capture_id)
-            .ok_or_else(|| format!("Capture '{}' not found", source_capture_id))?;
+            .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = source_capture_id))?;
         source.metadata.clone()
     };
 
@@ -756,16 +1143,27 @@ pub fn copy_capture(source_capture_id: &str, new_name: String) -> Result<String,
             owning_session_id: None,
             persistent: false,
             buses: source_metadata.buses.clone(),
+            timing: source_metadata.timing.clone(),
+            integrity: source_metadata.integrity.clone(),
+            stored_bytes: 0,
         };
 
         let seen_buses: HashSet<u8> = source_metadata.buses.iter().copied().collect();
-        let entry = NamedCapture { metadata: metadata.clone(), seen_buses, unique_frame_ids: HashSet::new() };
+        let entry = NamedCapture { metadata: metadata.clone(), seen_buses, unique_frame_ids: HashSet::new(), ring: None };
         registry.captures.insert(id.clone(), entry);
         (id, metadata)
     };
 
     // Copy data in SQLite (INSERT INTO ... SELECT — no memory spike)
     let count = capture_db::copy_capture_data(source_capture_id, &id)?;
+    let stored_bytes = capture_db::stored_bytes(&id).unwrap_or_else(|e| {
+        tlog!("[CaptureStore] Failed to measure copied capture: {}", e);
+        0
+    });
+    let metadata = CaptureMetadata { stored_bytes, ..metadata };
+    if let Some(entry) = CAPTURE_REGISTRY.write().unwrap().captures.get_mut(&id) {
+        entry.metadata.stored_bytes = stored_bytes;
+    }
 
     // Persist metadata for the new capture
     if let Err(e) = capture_db::save_capture_metadata(&metadata) {
@@ -780,19 +1178,152 @@ pub fn copy_capture(source_capture_id: &str, new_name: String) -> Result<String,
     Ok(id)
 }
 
+/// Merge several frame captures into a new orphaned capture, frames
+/// interleaved by timestamp and buses renumbered per source.
+/// Returns the new capture's metadata.
+pub fn merge_captures(sources: &[MergeSource], new_name: String) -> Result<CaptureMetadata, String> {
+    if sources.len() < 2 {
+        return Err("Select at least two captures to merge".to_string());
+    }
+
+    let source_metadata: Vec<CaptureMetadata> = {
+        let registry = CAPTURE_REGISTRY.read().unwrap();
+        sources
+            .iter()
+            .map(|src| match registry.captures.get(&src.capture_id) {
+                Some(c) if c.metadata.kind == CaptureKind::Frames => Ok(c.metadata.clone()),
+                _ => Err(msg!(CAPTURE_NOT_FRAMES, capture_id = src.capture_id.as_str())),
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    let buses: HashSet<u8> = sources
+        .iter()
+        .zip(&source_metadata)
+        .flat_map(|(src, meta)| meta.buses.iter().map(|b| *src.bus_map.get(b).unwrap_or(b)))
+        .collect();
+    let mut bus_list: Vec<u8> = buses.iter().copied().collect();
+    bus_list.sort_unstable();
+
+    let (id, metadata) = {
+        let mut registry = CAPTURE_REGISTRY.write().unwrap();
+        let id = generate_capture_id(&registry);
+        let created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let metadata = CaptureMetadata {
+            id: id.clone(),
+            kind: CaptureKind::Frames,
+            name: new_name.clone(),
+            count: source_metadata.iter().map(|m| m.count).sum(),
+            start_time_us: source_metadata.iter().filter_map(|m| m.start_time_us).min(),
+            end_time_us: source_metadata.iter().filter_map(|m| m.end_time_us).max(),
+            created_at,
+            is_streaming: false,
+            owning_session_id: None,
+            persistent: false,
+            buses: bus_list,
+            // Sources were timed by different clocks; no single timing applies
+            timing: None,
+            integrity: None,
+            stored_bytes: 0,
+        };
+        let entry = NamedCapture { metadata: metadata.clone(), seen_buses: buses, unique_frame_ids: HashSet::new(), ring: None };
+        registry.captures.insert(id.clone(), entry);
+        (id, metadata)
+    };
+
+    let inputs: Vec<(&str, &HashMap<u8, u8>)> =
+        sources.iter().map(|src| (src.capture_id.as_str(), &src.bus_map)).collect();
+    let count = match capture_db::merge_capture_data(&inputs, &id) {
+        Ok(count) => count,
+        Err(e) => {
+            CAPTURE_REGISTRY.write().unwrap().captures.remove(&id);
+            let _ = capture_db::delete_capture_data(&id);
+            return Err(e);
+        }
+    };
+    let stored_bytes = capture_db::stored_bytes(&id).unwrap_or_else(|e| {
+        tlog!("[CaptureStore] Failed to measure merged capture: {}", e);
+        0
+    });
+    let metadata = CaptureMetadata { count, stored_bytes, ..metadata };
+    if let Some(entry) = CAPTURE_REGISTRY.write().unwrap().captures.get_mut(&id) {
+        entry.metadata.count = count;
+        entry.metadata.stored_bytes = stored_bytes;
+    }
+
+    if let Err(e) = capture_db::save_capture_metadata(&metadata) {
+        tlog!("[CaptureStore] Failed to persist merged capture metadata: {}", e);
+    }
+
+    tlog!(
+        "[CaptureStore] Merged {} captures into '{}' ('{}', {} frames)",
+        sources.len(), id, new_name, count
+    );
+
+    Ok(metadata)
+}
+
+/// Save a capture to a standalone capture file (see capture_db "Capture
+/// Files"). Returns the number of frames/bytes written.
+pub fn persist_capture(capture_id: &str, path: &str) -> Result<usize, String> {
+    let metadata = get_capture_metadata(capture_id)
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = capture_id))?;
+    let count = capture_db::write_capture_file(&metadata, std::path::Path::new(path))?;
+    tlog!("[CaptureStore] Persisted capture '{}' to '{}' ({} items)", capture_id, path, count);
+    Ok(count)
+}
+
+/// Open a capture file as a new, persistent capture. Its data is copied
+/// into the capture database, so the paginated APIs page it from disk like
+/// any other capture. Returns the new capture's metadata.
+pub fn open_persisted_capture(path: &str) -> Result<CaptureMetadata, String> {
+    let id = generate_capture_id(&CAPTURE_REGISTRY.read().unwrap());
+    let (file_meta, count) = capture_db::read_capture_file(std::path::Path::new(path), &id)?;
+    // Re-measured: the file holds payloads inline and they were chunked anew
+    let stored_bytes = capture_db::stored_bytes(&id).unwrap_or_else(|e| {
+        tlog!("[CaptureStore] Failed to measure opened capture: {}", e);
+        0
+    });
+
+    let metadata = CaptureMetadata {
+        id: id.clone(),
+        count,
+        stored_bytes,
+        is_streaming: false,
+        owning_session_id: None,
+        persistent: true,
+        ..file_meta
+    };
+    if let Err(e) = capture_db::save_capture_metadata(&metadata) {
+        tlog!("[CaptureStore] Failed to persist opened capture metadata: {}", e);
+    }
+    let seen_buses: HashSet<u8> = metadata.buses.iter().copied().collect();
+    CAPTURE_REGISTRY.write().unwrap().captures.insert(
+        id.clone(),
+        NamedCapture { metadata: metadata.clone(), seen_buses, unique_frame_ids: HashSet::new(), ring: None },
+    );
+
+    tlog!("[CaptureStore] Opened capture file '{}' as '{}' ('{}', {} items)", path, id, metadata.name, count);
+    Ok(metadata)
+}
+
 // ============================================================================
 // Public API - Data Access (Frame Captures)
 // ============================================================================
 
 /// Append frames to a specific capture by ID.
 /// Silently returns if capture doesn't exist or is not a frame capture.
-/// Only used by framing.rs which is desktop-only.
-#[cfg(not(target_os = "ios"))]
+/// Used by framing.rs and remote capture import (capture_share.rs).
 pub fn append_frames_to_capture(capture_id: &str, new_frames: Vec<FrameMessage>) {
     if new_frames.is_empty() {
         return;
     }
 
+    let ring;
     {
         let mut registry = CAPTURE_REGISTRY.write().unwrap();
 
@@ -805,6 +1336,8 @@ pub fn append_frames_to_capture(capture_id: &str, new_frames: Vec<FrameMessage>)
             }
             cap.metadata.end_time_us = new_frames.last().map(|f| f.timestamp_us);
             cap.metadata.count += new_frames.len();
+            cap.metadata.integrity = None;
+            ring = cap.ring.and_then(|limit| ring_trim_bounds(limit, &cap.metadata));
 
             // Track distinct buses and distinct (bus, frame_id) keys
             let prev_len = cap.seen_buses.len();
@@ -823,9 +1356,75 @@ pub fn append_frames_to_capture(capture_id: &str, new_frames: Vec<FrameMessage>)
         // Registry lock dropped here
     }
 
-    if let Err(e) = capture_db::insert_frames(capture_id, &new_frames) {
-        tlog!("[CaptureStore] Failed to insert frames to capture '{}': {}", capture_id, e);
+    match capture_db::insert_frames(capture_id, &new_frames) {
+        Ok(delta) => {
+            if let Some(cap) = CAPTURE_REGISTRY.write().unwrap().captures.get_mut(capture_id) {
+                cap.metadata.stored_bytes = (cap.metadata.stored_bytes as i64 + delta).max(0) as u64;
+            }
+        }
+        Err(e) => tlog!("[CaptureStore] Failed to insert frames to capture '{}': {}", capture_id, e),
+    }
+    if let Some((keep_frames, before_us)) = ring {
+        trim_ring(capture_id, keep_frames, before_us);
     }
+    crate::row_model::mark_capture_changed(capture_id);
+}
+
+/// Decide whether a ring capture needs trimming. Returns the (frame count,
+/// earliest timestamp) to trim back to once a limit is overshot by more than
+/// the slack; None while within limits.
+fn ring_trim_bounds(limit: RingLimit, meta: &CaptureMetadata) -> Option<(Option<usize>, Option<u64>)> {
+    let window_us = limit.max_duration_ms.map(|ms| ms.saturating_mul(1000));
+    let cutoff = window_us.zip(meta.end_time_us).map(|(window, end)| end.saturating_sub(window));
+
+    let over_frames = limit
+        .max_frames
+        .is_some_and(|max| meta.count as u64 > max as u64 + max as u64 / RING_SLACK_DIVISOR);
+    let over_window = match (window_us, cutoff, meta.start_time_us) {
+        (Some(window), Some(cutoff), Some(start)) => start.saturating_add(window / RING_SLACK_DIVISOR) < cutoff,
+        _ => false,
+    };
+
+    // Once trimming, bring both limits back in line
+    (over_frames || over_window).then_some((limit.max_frames, cutoff))
+}
+
+/// Drop a ring capture's oldest frames, then fix up its metadata, shift the
+/// owning session's WebSocket delivery offset and emit `capture-trimmed`.
+fn trim_ring(capture_id: &str, keep_frames: Option<usize>, before_us: Option<u64>) {
+    let (removed, start_time_us) = match capture_db::trim_head(capture_id, keep_frames, before_us) {
+        Ok(result) => result,
+        Err(e) => {
+            tlog!("[CaptureStore] Failed to trim ring capture '{}': {}", capture_id, e);
+            return;
+        }
+    };
+    if removed == 0 {
+        return;
+    }
+    let stored_bytes = capture_db::stored_bytes(capture_id);
+
+    let (count, owner) = {
+        let mut registry = CAPTURE_REGISTRY.write().unwrap();
+        let Some(cap) = registry.captures.get_mut(capture_id) else { return };
+        cap.metadata.count = cap.metadata.count.saturating_sub(removed);
+        cap.metadata.start_time_us = start_time_us;
+        match stored_bytes {
+            Ok(bytes) => cap.metadata.stored_bytes = bytes,
+            Err(e) => tlog!("[CaptureStore] Failed to measure ring capture '{}': {}", capture_id, e),
+        }
+        (cap.metadata.count, cap.metadata.owning_session_id.clone())
+    };
+
+    if let Some(session_id) = owner {
+        crate::ws::dispatch::shift_frame_offset(&session_id, removed);
+    }
+    crate::io::emit_capture_trimmed(&CaptureTrimmedPayload {
+        capture_id: capture_id.to_string(),
+        removed,
+        count,
+        start_time_us,
+    });
 }
 
 /// Clear a frame capture and refill it with new frames.
@@ -844,6 +1443,7 @@ pub fn clear_and_refill_capture(capture_id: &str, new_frames: Vec<FrameMessage>)
             cap.metadata.start_time_us = new_frames.first().map(|f| f.timestamp_us);
             cap.metadata.end_time_us = new_frames.last().map(|f| f.timestamp_us);
             cap.metadata.count = new_frames.len();
+            cap.metadata.integrity = None;
 
             // Reset and rebuild bus + unique-frame tracking
             cap.seen_buses.clear();
@@ -860,14 +1460,19 @@ pub fn clear_and_refill_capture(capture_id: &str, new_frames: Vec<FrameMessage>)
         }
     }
 
-    if let Err(e) = capture_db::clear_and_refill(capture_id, &new_frames) {
-        tlog!("[CaptureStore] Failed to clear and refill capture '{}': {}", capture_id, e);
-    } else {
-        tlog!(
-            "[CaptureStore] Refilled capture '{}' with {} frames",
-            capture_id, new_frames.len()
-        );
+    match capture_db::clear_and_refill(capture_id, &new_frames) {
+        Ok(stored_bytes) => {
+            if let Some(cap) = CAPTURE_REGISTRY.write().unwrap().captures.get_mut(capture_id) {
+                cap.metadata.stored_bytes = stored_bytes;
+            }
+            tlog!(
+                "[CaptureStore] Refilled capture '{}' with {} frames",
+                capture_id, new_frames.len()
+            );
+        }
+        Err(e) => tlog!("[CaptureStore] Failed to clear and refill capture '{}': {}", capture_id, e),
     }
+    crate::row_model::mark_capture_changed(capture_id);
 }
 
 /// Get frames from a specific capture.
@@ -943,6 +1548,34 @@ pub fn get_capture_frames_paginated_filtered(
     }
 }
 
+/// Get a page of one or more protocols' frames from a mixed-protocol capture.
+/// Returns (frames, buffer_indices, total_filtered_count).
+pub fn get_capture_frames_by_protocol(
+    id: &str,
+    protocols: &[crate::io::Protocol],
+    offset: usize,
+    limit: usize,
+) -> (Vec<FrameMessage>, Vec<usize>, usize) {
+    {
+        let registry = CAPTURE_REGISTRY.read().unwrap();
+        match registry.captures.get(id) {
+            Some(b) if b.metadata.kind == CaptureKind::Frames => {},
+            _ => return (Vec::new(), Vec::new(), 0),
+        }
+    }
+
+    match capture_db::get_frames_paginated_by_protocol(id, protocols, offset, limit) {
+        Ok((frames, rowids, total)) => {
+            let indices = rowids.into_iter().map(|r| r as usize).collect();
+            (frames, indices, total)
+        }
+        Err(e) => {
+            tlog!("[CaptureStore] Failed to get frames by protocol: {}", e);
+            (Vec::new(), Vec::new(), 0)
+        }
+    }
+}
+
 /// Response from tail fetch operation
 #[derive(Clone, Debug, serde::Serialize)]
 pub struct TailResponse {
@@ -1072,6 +1705,7 @@ pub fn append_raw_bytes_to_capture(capture_id: &str, new_bytes: Vec<TimestampedB
             }
             cap.metadata.end_time_us = new_bytes.last().map(|b| b.timestamp_us);
             cap.metadata.count += new_bytes.len();
+            cap.metadata.integrity = None;
 
             // Track distinct buses
             let prev_len = cap.seen_buses.len();
@@ -0,0 +1,541 @@
+// ui/src-tauri/src/capture_trigger.rs
+//
+// Conditional capture — gates what a session writes into its frame capture.
+//
+// A session can be given a start condition (a frame pattern or a decoded
+// signal comparison) and a stop condition (another condition, a duration, or
+// both). Frames are dropped until the start condition matches; from then on
+// they are captured until the stop condition fires. With `rearm` set, the
+// trigger goes back to waiting for the next start match, so a week-long
+// monitoring session only keeps the windows around the rare event.
+//
+// Gating happens in capture_store::append_frames_to_session, so every reader
+// is covered without driver changes. Timing uses frame timestamps (not wall
+// clock) so the same conditions behave identically on replayed captures.
+// State is fetched by the frontend via get_capture_trigger_state after a
+// CaptureChanged signal.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::io::FrameMessage;
+use crate::signal_bits::SignalLayout;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Byte-level frame pattern. `data` is compared against the payload under
+/// `data_mask` (missing mask bytes default to 0xFF); a payload shorter than
+/// `data` never matches.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FramePattern {
+    pub frame_id: u32,
+    /// Mask applied to both the pattern and frame IDs (default: exact match)
+    #[serde(default)]
+    pub id_mask: Option<u32>,
+    /// Restrict to one bus (None = any bus)
+    #[serde(default)]
+    pub bus: Option<u8>,
+    #[serde(default)]
+    pub data: Vec<u8>,
+    #[serde(default)]
+    pub data_mask: Vec<u8>,
+}
+
+/// Comparison operator for signal conditions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl CompareOp {
+    pub fn apply(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            CompareOp::Eq => (lhs - rhs).abs() < f64::EPSILON,
+            CompareOp::Ne => (lhs - rhs).abs() >= f64::EPSILON,
+            CompareOp::Gt => lhs > rhs,
+            CompareOp::Ge => lhs >= rhs,
+            CompareOp::Lt => lhs < rhs,
+            CompareOp::Le => lhs <= rhs,
+        }
+    }
+}
+
+/// A raw bit-field signal compared against a threshold.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignalCondition {
+    #[serde(flatten)]
+    pub layout: SignalLayout,
+    pub op: CompareOp,
+    pub value: f64,
+}
+
+/// A single trigger condition.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TriggerCondition {
+    Frame(FramePattern),
+    Signal(SignalCondition),
+}
+
+/// Capture conditions attached to a session.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CaptureConditions {
+    /// Start capturing when this matches (None = capture immediately)
+    #[serde(default)]
+    pub start: Option<TriggerCondition>,
+    /// Stop capturing when this matches
+    #[serde(default)]
+    pub stop: Option<TriggerCondition>,
+    /// Stop capturing this many seconds after the window opens (the start
+    /// trigger, or the first frame when there is no start condition)
+    #[serde(default)]
+    pub stop_after_secs: Option<f64>,
+    /// After stopping, wait for the next start match instead of staying stopped
+    #[serde(default)]
+    pub rearm: bool,
+}
+
+/// Trigger state machine phase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerPhase {
+    /// Waiting for the start condition — frames are dropped
+    Armed,
+    /// Start condition matched — frames are captured
+    Capturing,
+    /// Stop condition fired and `rearm` is off — frames are dropped
+    Stopped,
+}
+
+/// Snapshot of a session's trigger, fetched by the frontend.
+#[derive(Clone, Debug, Serialize)]
+pub struct CaptureTriggerState {
+    pub session_id: String,
+    pub conditions: CaptureConditions,
+    pub phase: TriggerPhase,
+    /// Number of capture windows opened (start matches, or the first frame
+    /// when there is no start condition)
+    pub trigger_count: u32,
+    /// Timestamp of the most recent start trigger (microseconds)
+    pub last_start_us: Option<u64>,
+    /// Timestamp of the most recent stop (microseconds)
+    pub last_stop_us: Option<u64>,
+    pub frames_captured: u64,
+    pub frames_dropped: u64,
+}
+
+// ============================================================================
+// Matching
+// ============================================================================
+
+impl FramePattern {
+    pub fn matches(&self, frame: &FrameMessage) -> bool {
+        if let Some(bus) = self.bus {
+            if frame.bus != bus {
+                return false;
+            }
+        }
+        let mask = self.id_mask.unwrap_or(u32::MAX);
+        if frame.frame_id & mask != self.frame_id & mask {
+            return false;
+        }
+        if frame.bytes.len() < self.data.len() {
+            return false;
+        }
+        self.data.iter().enumerate().all(|(i, &want)| {
+            let m = self.data_mask.get(i).copied().unwrap_or(0xFF);
+            frame.bytes[i] & m == want & m
+        })
+    }
+}
+
+impl SignalCondition {
+    pub fn matches(&self, frame: &FrameMessage) -> bool {
+        self.layout
+            .physical_value(frame)
+            .map(|v| self.op.apply(v, self.value))
+            .unwrap_or(false)
+    }
+}
+
+impl TriggerCondition {
+    pub fn matches(&self, frame: &FrameMessage) -> bool {
+        match self {
+            TriggerCondition::Frame(p) => p.matches(frame),
+            TriggerCondition::Signal(s) => s.matches(frame),
+        }
+    }
+}
+
+// ============================================================================
+// Trigger state machine
+// ============================================================================
+
+struct CaptureTrigger {
+    conditions: CaptureConditions,
+    phase: TriggerPhase,
+    trigger_count: u32,
+    last_start_us: Option<u64>,
+    last_stop_us: Option<u64>,
+    frames_captured: u64,
+    frames_dropped: u64,
+}
+
+impl CaptureTrigger {
+    fn new(conditions: CaptureConditions) -> Self {
+        let phase = if conditions.start.is_some() {
+            TriggerPhase::Armed
+        } else {
+            TriggerPhase::Capturing
+        };
+        Self {
+            conditions,
+            phase,
+            trigger_count: 0,
+            last_start_us: None,
+            last_stop_us: None,
+            frames_captured: 0,
+            frames_dropped: 0,
+        }
+    }
+
+    fn stop(&mut self, timestamp_us: u64) {
+        self.last_stop_us = Some(timestamp_us);
+        self.phase = if self.conditions.rearm && self.conditions.start.is_some() {
+            TriggerPhase::Armed
+        } else {
+            TriggerPhase::Stopped
+        };
+    }
+
+    /// Run one frame through the state machine. Returns whether the frame is
+    /// captured. The start frame and the stop frame are both captured, so each
+    /// window is bracketed by the frames that opened and closed it.
+    fn process(&mut self, frame: &FrameMessage) -> bool {
+        let mut just_started = false;
+        if self.phase == TriggerPhase::Armed {
+            let started = self.conditions.start.as_ref().is_none_or(|c| c.matches(frame));
+            if !started {
+                self.frames_dropped += 1;
+                return false;
+            }
+            self.phase = TriggerPhase::Capturing;
+            self.trigger_count += 1;
+            self.last_start_us = Some(frame.timestamp_us);
+            just_started = true;
+        } else if self.phase == TriggerPhase::Capturing && self.last_start_us.is_none() {
+            // No start condition: the window opens on the first frame seen
+            self.trigger_count += 1;
+            self.last_start_us = Some(frame.timestamp_us);
+            just_started = true;
+        }
+
+        if self.phase == TriggerPhase::Stopped {
+            self.frames_dropped += 1;
+            return false;
+        }
+
+        // Capturing — a duration limit is checked before the frame so a frame
+        // past the window isn't captured.
+        if !just_started {
+            if let (Some(secs), Some(start)) = (self.conditions.stop_after_secs, self.last_start_us) {
+                let limit_us = (secs.max(0.0) * 1_000_000.0) as u64;
+                if frame.timestamp_us.saturating_sub(start) > limit_us {
+                    self.stop(frame.timestamp_us);
+                    // A re-armed trigger may start again on this very frame.
+                    return self.process(frame);
+                }
+            }
+        }
+
+        self.frames_captured += 1;
+        let stop_hit = self.conditions.stop.as_ref().is_some_and(|c| c.matches(frame));
+        if stop_hit && !just_started {
+            self.stop(frame.timestamp_us);
+        }
+        true
+    }
+
+    fn snapshot(&self, session_id: &str) -> CaptureTriggerState {
+        CaptureTriggerState {
+            session_id: session_id.to_string(),
+            conditions: self.conditions.clone(),
+            phase: self.phase,
+            trigger_count: self.trigger_count,
+            last_start_us: self.last_start_us,
+            last_stop_us: self.last_stop_us,
+            frames_captured: self.frames_captured,
+            frames_dropped: self.frames_dropped,
+        }
+    }
+}
+
+/// Map of session_id -> trigger. Empty for almost every session, so the
+/// append-path check is a single read lock.
+static CAPTURE_TRIGGERS: Lazy<RwLock<HashMap<String, CaptureTrigger>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Filter a batch of frames through the session's trigger (if any).
+/// Called from capture_store::append_frames_to_session before the capture is
+/// written. Returns the frames to keep; signals CaptureChanged on a phase
+/// transition so the frontend can refresh the trigger indicator.
+pub fn filter_frames(session_id: &str, frames: Vec<FrameMessage>) -> Vec<FrameMessage> {
+    if CAPTURE_TRIGGERS.read().map(|t| !t.contains_key(session_id)).unwrap_or(true) {
+        return frames;
+    }
+
+    let (kept, transitioned, fired) = {
+        let mut triggers = match CAPTURE_TRIGGERS.write() {
+            Ok(t) => t,
+            Err(_) => return frames,
+        };
+        let Some(trigger) = triggers.get_mut(session_id) else {
+            return frames;
+        };
+        let before = (trigger.phase, trigger.trigger_count);
+        let kept: Vec<FrameMessage> = frames.into_iter().filter(|f| trigger.process(f)).collect();
+        let transitioned = before != (trigger.phase, trigger.trigger_count);
+        let fired = (trigger.trigger_count > before.1)
+            .then_some((trigger.trigger_count, trigger.last_start_us));
+        if transitioned {
+            tlog!(
+                "[CaptureTrigger:{}] Phase {:?} (triggers: {}, captured: {}, dropped: {})",
+                session_id, trigger.phase, trigger.trigger_count,
+                trigger.frames_captured, trigger.frames_dropped
+            );
+        }
+        (kept, transitioned, fired)
+    };
+
+    if transitioned {
+        crate::io::emit_capture_changed(session_id);
+    }
+    if let Some((trigger_count, start_us)) = fired {
+        crate::webhooks::fire(
+            crate::webhooks::WebhookEvent::AlertTriggered,
+            Some(session_id),
+            serde_json::json!({
+                "source": "capture_trigger",
+                "trigger_count": trigger_count,
+                "timestamp_us": start_us,
+            }),
+        );
+    }
+    kept
+}
+
+/// Remove a session's trigger. Called when the session is destroyed.
+pub fn clear_conditions(session_id: &str) {
+    if let Ok(mut triggers) = CAPTURE_TRIGGERS.write() {
+        triggers.remove(session_id);
+    }
+}
+
+fn validate(conditions: &CaptureConditions) -> Result<(), String> {
+    if let Some(secs) = conditions.stop_after_secs {
+        if !secs.is_finite() || secs <= 0.0 {
+            return Err("stop_after_secs must be a positive number".to_string());
+        }
+    }
+    for cond in [&conditions.start, &conditions.stop].into_iter().flatten() {
+        match cond {
+            TriggerCondition::Frame(p) => {
+                if p.data_mask.len() > p.data.len() {
+                    return Err("data_mask is longer than data".to_string());
+                }
+            }
+            TriggerCondition::Signal(s) => s.layout.validate()?,
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Attach capture conditions to a session, replacing any existing ones.
+/// The trigger starts Armed (or Capturing when there is no start condition).
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_capture_conditions(
+    session_id: String,
+    conditions: CaptureConditions,
+) -> Result<CaptureTriggerState, String> {
+    validate(&conditions)?;
+    let trigger = CaptureTrigger::new(conditions);
+    let state = trigger.snapshot(&session_id);
+    CAPTURE_TRIGGERS
+        .write()
+        .map_err(|e| format!("Trigger lock poisoned: {e}"))?
+        .insert(session_id.clone(), trigger);
+    tlog!("[CaptureTrigger:{}] Conditions set, phase {:?}", session_id, state.phase);
+    Ok(state)
+}
+
+/// Remove a session's capture conditions — every frame is captured again.
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_capture_conditions(session_id: String) {
+    clear_conditions(&session_id);
+}
+
+/// Re-arm a session's trigger (back to waiting for the start condition),
+/// keeping its counters. Without a start condition the next frame opens a
+/// fresh window.
+#[tauri::command(rename_all = "snake_case")]
+pub fn rearm_capture_trigger(session_id: String) -> Result<CaptureTriggerState, String> {
+    let mut triggers = CAPTURE_TRIGGERS
+        .write()
+        .map_err(|e| format!("Trigger lock poisoned: {e}"))?;
+    let trigger = triggers
+        .get_mut(&session_id)
+        .ok_or_else(|| format!("No capture conditions on session '{}'", session_id))?;
+    trigger.phase = if trigger.conditions.start.is_some() {
+        TriggerPhase::Armed
+    } else {
+        TriggerPhase::Capturing
+    };
+    trigger.last_start_us = None;
+    Ok(trigger.snapshot(&session_id))
+}
+
+/// Current trigger state for a session (None = no conditions attached).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_capture_trigger_state(session_id: String) -> Option<CaptureTriggerState> {
+    CAPTURE_TRIGGERS
+        .read()
+        .ok()
+        .and_then(|t| t.get(&session_id).map(|tr| tr.snapshot(&session_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame as frame;
+
+    fn pattern(id: u32, data: &[u8]) -> TriggerCondition {
+        TriggerCondition::Frame(FramePattern {
+            frame_id: id,
+            id_mask: None,
+            bus: None,
+            data: data.to_vec(),
+            data_mask: vec![],
+        })
+    }
+
+    #[test]
+    fn pattern_respects_data_mask() {
+        let p = FramePattern {
+            frame_id: 0x100,
+            id_mask: None,
+            bus: None,
+            data: vec![0x10, 0xAB],
+            data_mask: vec![0xF0],
+        };
+        assert!(p.matches(&frame(0x100, 0, &[0x1F, 0xAB])));
+        assert!(!p.matches(&frame(0x100, 0, &[0x2F, 0xAB])));
+        assert!(!p.matches(&frame(0x100, 0, &[0x10])));
+        assert!(!p.matches(&frame(0x101, 0, &[0x10, 0xAB])));
+    }
+
+    #[test]
+    fn signed_signal_condition() {
+        let cond = SignalCondition {
+            layout: SignalLayout {
+                frame_id: 0x200,
+                bus: None,
+                start_bit: 0,
+                bit_length: 8,
+                big_endian: false,
+                signed: true,
+                factor: 0.5,
+                offset: 0.0,
+            },
+            op: CompareOp::Lt,
+            value: 0.0,
+        };
+        assert_eq!(cond.layout.physical_value(&frame(0x200, 0, &[0xFE])), Some(-1.0));
+        assert!(cond.matches(&frame(0x200, 0, &[0xFE])));
+        assert!(!cond.matches(&frame(0x200, 0, &[0x02])));
+    }
+
+    #[test]
+    fn start_and_stop_bracket_window() {
+        let mut t = CaptureTrigger::new(CaptureConditions {
+            start: Some(pattern(0x1, &[])),
+            stop: Some(pattern(0x2, &[])),
+            stop_after_secs: None,
+            rearm: false,
+        });
+        let kept: Vec<bool> = [0x5, 0x1, 0x5, 0x2, 0x5, 0x1]
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| t.process(&frame(id, i as u64, &[])))
+            .collect();
+        assert_eq!(kept, vec![false, true, true, true, false, false]);
+        assert_eq!(t.phase, TriggerPhase::Stopped);
+        assert_eq!(t.trigger_count, 1);
+    }
+
+    #[test]
+    fn duration_stop_with_rearm() {
+        let mut t = CaptureTrigger::new(CaptureConditions {
+            start: Some(pattern(0x1, &[])),
+            stop: None,
+            stop_after_secs: Some(1.0),
+            rearm: true,
+        });
+        assert!(t.process(&frame(0x1, 0, &[])));
+        assert!(t.process(&frame(0x5, 500_000, &[])));
+        assert!(!t.process(&frame(0x5, 1_500_000, &[])));
+        assert_eq!(t.phase, TriggerPhase::Armed);
+        assert!(t.process(&frame(0x1, 2_000_000, &[])));
+        assert_eq!(t.trigger_count, 2);
+    }
+
+    #[test]
+    fn duration_stop_without_start_condition() {
+        let mut t = CaptureTrigger::new(CaptureConditions {
+            start: None,
+            stop: None,
+            stop_after_secs: Some(1.0),
+            rearm: false,
+        });
+        assert_eq!(t.phase, TriggerPhase::Capturing);
+        assert!(t.process(&frame(0x5, 10_000_000, &[])));
+        assert_eq!((t.trigger_count, t.last_start_us), (1, Some(10_000_000)));
+        assert!(t.process(&frame(0x5, 10_900_000, &[])));
+        assert!(!t.process(&frame(0x5, 11_100_000, &[])));
+        assert_eq!(t.phase, TriggerPhase::Stopped);
+        assert!(!t.process(&frame(0x5, 12_000_000, &[])));
+    }
+
+    #[test]
+    fn rearm_without_start_condition_opens_a_fresh_window() {
+        let session = "trigger-rearm-no-start";
+        set_capture_conditions(
+            session.to_string(),
+            CaptureConditions { start: None, stop: None, stop_after_secs: Some(1.0), rearm: false },
+        )
+        .unwrap();
+        let frames = |ts: &[u64]| ts.iter().map(|&t| frame(0x5, t, &[])).collect::<Vec<_>>();
+        assert_eq!(filter_frames(session, frames(&[0, 500_000, 1_500_000])).len(), 2);
+
+        let state = rearm_capture_trigger(session.to_string()).unwrap();
+        assert_eq!((state.phase, state.last_start_us), (TriggerPhase::Capturing, None));
+        let kept = filter_frames(session, frames(&[5_000_000, 5_900_000, 6_100_000]));
+        assert_eq!(kept.len(), 2);
+        let state = get_capture_trigger_state(session.to_string()).unwrap();
+        assert_eq!((state.trigger_count, state.last_start_us), (2, Some(5_000_000)));
+        clear_conditions(session);
+    }
+}
@@ -9,7 +9,7 @@
 
 use std::collections::{BTreeMap, HashMap};
 
-use crate::capture_db;
+use crate::capture_db::{self, FRAME_PAYLOAD};
 use crate::dbquery::{
     ByteChangeQueryResult, ByteChangeResult, DistributionQueryResult, DistributionResult,
     FirstLastQueryResult, FirstLastResult, FrameChangeQueryResult, FrameChangeResult,
@@ -41,10 +41,10 @@ pub fn capture_query_byte_changes(
     );
 
     // Build the SQL dynamically based on optional filters
-    let mut sql = String::from(
+    let mut sql = format!(
         "WITH ordered AS (
-            SELECT timestamp_us, payload,
-                   LAG(payload) OVER (ORDER BY rowid) as prev_payload
+            SELECT timestamp_us, {FRAME_PAYLOAD} AS payload,
+                   LAG({FRAME_PAYLOAD}) OVER (ORDER BY rowid) as prev_payload
             FROM frames
             WHERE capture_id = ?1 AND frame_id = ?2",
     );
@@ -153,10 +153,10 @@ pub fn capture_query_frame_changes(
         capture_id, frame_id, is_extended, result_limit
     );
 
-    let mut sql = String::from(
+    let mut sql = format!(
         "WITH ordered AS (
-            SELECT timestamp_us, payload,
-                   LAG(payload) OVER (ORDER BY rowid) as prev_payload
+            SELECT timestamp_us, {FRAME_PAYLOAD} AS payload,
+                   LAG({FRAME_PAYLOAD}) OVER (ORDER BY rowid) as prev_payload
             FROM frames
             WHERE capture_id = ?1 AND frame_id = ?2",
     );
@@ -270,11 +270,11 @@ pub fn capture_query_mirror_validation(
 
     // Strategy: load mirror frames, then for each mirror frame find the closest
     // source frame within tolerance. This avoids a potentially expensive cross-join.
-    let mut mirror_sql = String::from(
-        "SELECT timestamp_us, payload FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
+    let mut mirror_sql = format!(
+        "SELECT timestamp_us, {FRAME_PAYLOAD} FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
     );
-    let mut source_sql = String::from(
-        "SELECT timestamp_us, payload FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
+    let mut source_sql = format!(
+        "SELECT timestamp_us, {FRAME_PAYLOAD} FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
     );
 
     let mut mirror_params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
@@ -425,8 +425,8 @@ pub fn capture_query_mux_statistics(
     );
 
     // Build SQL to fetch payloads
-    let mut sql = String::from(
-        "SELECT payload FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
+    let mut sql = format!(
+        "SELECT {FRAME_PAYLOAD} FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
     );
 
     let mut param_idx = 3;
@@ -536,14 +536,14 @@ pub fn capture_query_first_last(
 
     // Query 1: first frame (ASC)
     let first_sql = format!(
-        "SELECT timestamp_us, payload FROM frames {} ORDER BY rowid ASC LIMIT 1",
+        "SELECT timestamp_us, {FRAME_PAYLOAD} FROM frames {} ORDER BY rowid ASC LIMIT 1",
         where_clause
     );
     let first_rows = capture_db::query_raw_two_col(&first_sql, &param_refs)?;
 
     // Query 2: last frame (DESC)
     let last_sql = format!(
-        "SELECT timestamp_us, payload FROM frames {} ORDER BY rowid DESC LIMIT 1",
+        "SELECT timestamp_us, {FRAME_PAYLOAD} FROM frames {} ORDER BY rowid DESC LIMIT 1",
         where_clause
     );
     let last_rows = capture_db::query_raw_two_col(&last_sql, &param_refs)?;
@@ -610,9 +610,10 @@ pub fn capture_query_frequency(
         capture_id, frame_id, bucket_size_ms, is_extended, result_limit
     );
 
-    // Build SQL to fetch timestamps (use query_raw_two_col, ignore payload)
+    // Build SQL to fetch timestamps (use query_raw_two_col; an empty blob
+    // stands in for the payload so chunked rows aren't decompressed)
     let mut sql = String::from(
-        "SELECT timestamp_us, payload FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
+        "SELECT timestamp_us, x'' FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
     );
 
     let mut param_idx = 3;
@@ -714,8 +715,8 @@ pub fn capture_query_distribution(
     );
 
     // Build SQL to fetch payloads
-    let mut sql = String::from(
-        "SELECT payload FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
+    let mut sql = format!(
+        "SELECT {FRAME_PAYLOAD} FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
     );
 
     let mut param_idx = 3;
@@ -815,9 +816,10 @@ pub fn capture_query_gap_analysis(
         capture_id, frame_id, gap_threshold_ms, is_extended, result_limit
     );
 
-    // Build SQL to fetch timestamps (use query_raw_two_col, ignore payload)
+    // Build SQL to fetch timestamps (use query_raw_two_col; an empty blob
+    // stands in for the payload so chunked rows aren't decompressed)
     let mut sql = String::from(
-        "SELECT timestamp_us, payload FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
+        "SELECT timestamp_us, x'' FROM frames WHERE capture_id = ?1 AND frame_id = ?2",
     );
 
     let mut param_idx = 3;
@@ -922,8 +924,8 @@ pub fn capture_query_pattern_search(
     }
 
     // Build SQL — no frame_id filter, search across all frames
-    let mut sql = String::from(
-        "SELECT timestamp_us, frame_id, is_extended, payload FROM frames WHERE capture_id = ?1",
+    let mut sql = format!(
+        "SELECT timestamp_us, frame_id, is_extended, {FRAME_PAYLOAD} FROM frames WHERE capture_id = ?1",
     );
 
     let mut param_idx = 2;
@@ -13,10 +13,27 @@
 use tauri::{AppHandle, Emitter};
 
 use crate::{
-    capture_store::{self, CaptureMetadata, CaptureFrameInfo, TimestampedByte, TailResponse},
+    capture_db::AnnotationAnchor,
+    capture_store::{self, CaptureAnnotation, CaptureMetadata, CaptureFrameInfo, TimestampedByte, TailResponse, RingLimit, MergeSource, FramePattern},
     io::{self, FrameMessage},
+    ipc_compression,
 };
 
+/// Frames read per chunk when exporting a capture to BLF
+const BLF_EXPORT_CHUNK: usize = 5000;
+
+/// Frames read per chunk when exporting a capture to ASC
+const ASC_EXPORT_CHUNK: usize = 5000;
+
+/// Frames read per chunk when exporting a capture to Parquet
+const PARQUET_EXPORT_CHUNK: usize = 10_000;
+
+/// Hits returned by a pattern search when the caller doesn't ask
+const PATTERN_SEARCH_LIMIT: usize = 100_000;
+
+/// Frames appended per batch when importing an MDF4 log
+const MDF4_IMPORT_BATCH: usize = 10_000;
+
 /// Result of a CSV import, including capture metadata and any sequence gap diagnostics.
 #[derive(Clone, serde::Serialize)]
 pub struct CsvImportResult {
@@ -62,7 +79,11 @@ pub async fn import_csv_to_capture(session_id: String, file_path: String) -> Res
         .unwrap_or("unknown.csv")
         .to_string();
 
-    let frames = io::parse_csv_file(&file_path)?;
+    // Parses on all cores; keep it off the async runtime
+    let path = file_path.clone();
+    let frames = tauri::async_runtime::spawn_blocking(move || io::parse_csv_file(&path))
+        .await
+        .map_err(|e| format!("CSV import task failed: {}", e))??;
 
     if frames.is_empty() {
         return Err("CSV file contains no valid frames".to_string());
@@ -76,6 +97,307 @@ pub async fn import_csv_to_capture(session_id: String, file_path: String) -> Res
         .ok_or_else(|| "Failed to store frames in capture".to_string())
 }
 
+/// Import a candump log (`candump -l` output) into a session-owned capture
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_candump_to_capture(session_id: String, file_path: String) -> Result<CaptureMetadata, String> {
+    let filename = extract_filename(&file_path);
+
+    let frames = io::parse_candump_file(&file_path)?;
+
+    if frames.is_empty() {
+        return Err("candump log contains no valid frames".to_string());
+    }
+
+    let capture_id = capture_store::create_capture(capture_store::CaptureKind::Frames, filename);
+    let _ = capture_store::set_capture_owner(&capture_id, &session_id);
+    capture_store::append_frames_to_session(&session_id, frames);
+    let finalized = capture_store::finalize_session_captures(&session_id);
+    finalized.into_iter().next()
+        .ok_or_else(|| "Failed to store frames in capture".to_string())
+}
+
+/// Import a Vector BLF log into a session-owned capture
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_blf_to_capture(session_id: String, file_path: String) -> Result<CaptureMetadata, String> {
+    let filename = extract_filename(&file_path);
+
+    let path = file_path.clone();
+    let frames = tauri::async_runtime::spawn_blocking(move || io::parse_blf_file(&path))
+        .await
+        .map_err(|e| format!("BLF import task failed: {}", e))??;
+
+    if frames.is_empty() {
+        return Err("BLF log contains no CAN frames".to_string());
+    }
+
+    let capture_id = capture_store::create_capture(capture_store::CaptureKind::Frames, filename);
+    let _ = capture_store::set_capture_owner(&capture_id, &session_id);
+    capture_store::append_frames_to_session(&session_id, frames);
+    let finalized = capture_store::finalize_session_captures(&session_id);
+    finalized.into_iter().next()
+        .ok_or_else(|| "Failed to store frames in capture".to_string())
+}
+
+/// Import a pcap/pcapng SocketCAN capture (Wireshark, tcpdump) into a
+/// session-owned capture
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_pcap_to_capture(session_id: String, file_path: String) -> Result<CaptureMetadata, String> {
+    let filename = extract_filename(&file_path);
+
+    let path = file_path.clone();
+    let frames = tauri::async_runtime::spawn_blocking(move || io::parse_pcap_file(&path))
+        .await
+        .map_err(|e| format!("pcap import task failed: {}", e))??;
+
+    if frames.is_empty() {
+        return Err("Capture contains no SocketCAN frames".to_string());
+    }
+
+    let capture_id = capture_store::create_capture(capture_store::CaptureKind::Frames, filename);
+    let _ = capture_store::set_capture_owner(&capture_id, &session_id);
+    capture_store::append_frames_to_session(&session_id, frames);
+    let finalized = capture_store::finalize_session_captures(&session_id);
+    finalized.into_iter().next()
+        .ok_or_else(|| "Failed to store frames in capture".to_string())
+}
+
+/// Import a PEAK PCAN-View trace (.trc, versions 1.1 to 2.1) into a
+/// session-owned capture
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_trc_to_capture(session_id: String, file_path: String) -> Result<CaptureMetadata, String> {
+    let filename = extract_filename(&file_path);
+
+    let path = file_path.clone();
+    let frames = tauri::async_runtime::spawn_blocking(move || io::parse_trc_file(&path))
+        .await
+        .map_err(|e| format!("TRC import task failed: {}", e))??;
+
+    if frames.is_empty() {
+        return Err("TRC log contains no CAN frames".to_string());
+    }
+
+    let capture_id = capture_store::create_capture(capture_store::CaptureKind::Frames, filename);
+    let _ = capture_store::set_capture_owner(&capture_id, &session_id);
+    capture_store::append_frames_to_session(&session_id, frames);
+    let finalized = capture_store::finalize_session_captures(&session_id);
+    finalized.into_iter().next()
+        .ok_or_else(|| "Failed to store frames in capture".to_string())
+}
+
+/// Import an ASAM MDF4 log (CANedge CAN_DataFrame layout) into a
+/// session-owned capture. The file is streamed block by block and appended in
+/// batches, so multi-GB logs never sit in memory; `start_us` / `end_us`
+/// (µs since the Unix epoch) import only that part of the log.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_mdf4_to_capture(
+    session_id: String,
+    file_path: String,
+    start_us: Option<u64>,
+    end_us: Option<u64>,
+) -> Result<CaptureMetadata, String> {
+    let filename = extract_filename(&file_path);
+    let mut reader = io::Mdf4Reader::open(&file_path)?;
+
+    let capture_id = capture_store::create_capture(capture_store::CaptureKind::Frames, filename);
+    let _ = capture_store::set_capture_owner(&capture_id, &session_id);
+
+    let owner = session_id.clone();
+    let imported = tauri::async_runtime::spawn_blocking(move || {
+        reader.read_frames(start_us, end_us, MDF4_IMPORT_BATCH, |batch| {
+            capture_store::append_frames_to_session(&owner, batch);
+            Ok(())
+        })
+    })
+    .await
+    .map_err(|e| format!("MDF4 import task failed: {}", e))?;
+
+    let finalized = capture_store::finalize_session_captures(&session_id);
+    let count = imported.and_then(|count| match count {
+        0 => Err("MDF4 log contains no CAN frames in the selected range".to_string()),
+        n => Ok(n),
+    });
+    let count = match count {
+        Ok(count) => count,
+        Err(e) => {
+            let _ = capture_store::delete_capture(&capture_id);
+            return Err(e);
+        }
+    };
+    tlog!("[Captures] Imported {} MDF4 frames from {}", count, file_path);
+    finalized.into_iter().next()
+        .ok_or_else(|| "Failed to store frames in capture".to_string())
+}
+
+/// Export a frame capture as a Vector BLF log. Returns the number of frames
+/// written (non-CAN frames are skipped). With `write_sidecar` a
+/// `<file>.metadata.json` companion is written beside it.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_capture_blf(
+    capture_id: String,
+    file_path: String,
+    write_sidecar: Option<bool>,
+) -> Result<u32, String> {
+    let meta = capture_store::get_capture_metadata(&capture_id)
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = capture_id.as_str()))?;
+    if meta.kind != capture_store::CaptureKind::Frames {
+        return Err("Only frame captures can be exported as BLF".to_string());
+    }
+
+    let sidecar = write_sidecar.unwrap_or(false).then(|| (capture_id.clone(), file_path.clone()));
+    let written = tauri::async_runtime::spawn_blocking(move || {
+        let mut writer = io::BlfWriter::create(&file_path)?;
+        let mut cursor = 0i64;
+        loop {
+            let rows = crate::capture_db::read_frame_chunk(&capture_id, cursor, BLF_EXPORT_CHUNK)?;
+            let Some((last_rowid, _)) = rows.last() else { break };
+            cursor = *last_rowid;
+            for (_, frame) in &rows {
+                writer.write_frame(frame)?;
+            }
+        }
+        let written = writer.finish()?;
+        tlog!("[Captures] Exported {} frames from '{}' to BLF {}", written, capture_id, file_path);
+        crate::webhooks::fire(
+            crate::webhooks::WebhookEvent::ExportCompleted,
+            None,
+            serde_json::json!({
+                "capture_id": capture_id,
+                "format": "blf",
+                "file_path": file_path,
+                "frames": written,
+            }),
+        );
+        Ok::<_, String>(written)
+    })
+    .await
+    .map_err(|e| format!("BLF export task failed: {}", e))??;
+
+    if let Some((capture_id, file_path)) = sidecar {
+        let request = crate::export_sidecar::SidecarRequest {
+            capture_id,
+            format: "blf".to_string(),
+            ..Default::default()
+        };
+        crate::export_sidecar::write_sidecar(&file_path, request).await?;
+    }
+    Ok(written)
+}
+
+/// Export a frame capture as an Apache Parquet file (one row per frame,
+/// columnar) for pandas/polars. Returns the number of frames written; every
+/// protocol is kept. With `write_sidecar` a `<file>.metadata.json` companion
+/// is written beside it.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_capture_parquet(
+    capture_id: String,
+    file_path: String,
+    write_sidecar: Option<bool>,
+) -> Result<u32, String> {
+    let meta = capture_store::get_capture_metadata(&capture_id)
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = capture_id.as_str()))?;
+    if meta.kind != capture_store::CaptureKind::Frames {
+        return Err("Only frame captures can be exported as Parquet".to_string());
+    }
+
+    let sidecar = write_sidecar.unwrap_or(false).then(|| (capture_id.clone(), file_path.clone()));
+    let written = tauri::async_runtime::spawn_blocking(move || {
+        let mut writer = io::ParquetWriter::create(&file_path)?;
+        let mut cursor = 0i64;
+        loop {
+            let rows = crate::capture_db::read_frame_chunk(&capture_id, cursor, PARQUET_EXPORT_CHUNK)?;
+            let Some((last_rowid, _)) = rows.last() else { break };
+            cursor = *last_rowid;
+            for (_, frame) in &rows {
+                writer.write_frame(frame)?;
+            }
+        }
+        let written = writer.finish()?;
+        tlog!("[Captures] Exported {} frames from '{}' to Parquet {}", written, capture_id, file_path);
+        crate::webhooks::fire(
+            crate::webhooks::WebhookEvent::ExportCompleted,
+            None,
+            serde_json::json!({
+                "capture_id": capture_id,
+                "format": "parquet",
+                "file_path": file_path,
+                "frames": written,
+            }),
+        );
+        Ok::<_, String>(written)
+    })
+    .await
+    .map_err(|e| format!("Parquet export task failed: {}", e))??;
+
+    if let Some((capture_id, file_path)) = sidecar {
+        let request = crate::export_sidecar::SidecarRequest {
+            capture_id,
+            format: "parquet".to_string(),
+            ..Default::default()
+        };
+        crate::export_sidecar::write_sidecar(&file_path, request).await?;
+    }
+    Ok(written)
+}
+
+/// Export a frame capture as a Vector ASC log for CANalyzer. `time_base`
+/// chooses absolute (since the capture start, the default) or relative
+/// (since the previous frame) event times. Returns the number of frames
+/// written (non-CAN frames are skipped). With `write_sidecar` a
+/// `<file>.metadata.json` companion is written beside it.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_capture_asc(
+    capture_id: String,
+    file_path: String,
+    time_base: Option<io::AscTimeBase>,
+    write_sidecar: Option<bool>,
+) -> Result<u32, String> {
+    let meta = capture_store::get_capture_metadata(&capture_id)
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = capture_id.as_str()))?;
+    if meta.kind != capture_store::CaptureKind::Frames {
+        return Err("Only frame captures can be exported as ASC".to_string());
+    }
+
+    let start_us = meta.start_time_us.unwrap_or(0);
+    let sidecar = write_sidecar.unwrap_or(false).then(|| (capture_id.clone(), file_path.clone()));
+    let written = tauri::async_runtime::spawn_blocking(move || {
+        let mut writer = io::AscWriter::create(&file_path, start_us, time_base.unwrap_or_default())?;
+        let mut cursor = 0i64;
+        loop {
+            let rows = crate::capture_db::read_frame_chunk(&capture_id, cursor, ASC_EXPORT_CHUNK)?;
+            let Some((last_rowid, _)) = rows.last() else { break };
+            cursor = *last_rowid;
+            for (_, frame) in &rows {
+                writer.write_frame(frame)?;
+            }
+        }
+        let written = writer.finish()?;
+        tlog!("[Captures] Exported {} frames from '{}' to ASC {}", written, capture_id, file_path);
+        crate::webhooks::fire(
+            crate::webhooks::WebhookEvent::ExportCompleted,
+            None,
+            serde_json::json!({
+                "capture_id": capture_id,
+                "format": "asc",
+                "file_path": file_path,
+                "frames": written,
+            }),
+        );
+        Ok::<_, String>(written)
+    })
+    .await
+    .map_err(|e| format!("ASC export task failed: {}", e))??;
+
+    if let Some((capture_id, file_path)) = sidecar {
+        let request = crate::export_sidecar::SidecarRequest {
+            capture_id,
+            format: "asc".to_string(),
+            ..Default::default()
+        };
+        crate::export_sidecar::write_sidecar(&file_path, request).await?;
+    }
+    Ok(written)
+}
+
 /// Preview a data file: read first N rows, detect delimiter/headers, suggest column mappings
 #[tauri::command(rename_all = "snake_case")]
 pub async fn preview_csv(
@@ -312,7 +634,7 @@ pub async fn get_capture_metadata(capture_id: String) -> Result<Option<CaptureMe
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_capture_frames(capture_id: String) -> Result<Vec<FrameMessage>, String> {
     capture_store::get_capture_frames(&capture_id)
-        .ok_or_else(|| format!("Capture '{}' not found or is not a frame capture", capture_id))
+        .ok_or_else(|| msg!(CAPTURE_NOT_FRAMES, capture_id = capture_id).into())
 }
 
 /// Get a page of frames from a capture (for large datasets)
@@ -351,6 +673,75 @@ pub async fn get_capture_frames_paginated_filtered(
     })
 }
 
+/// Get a page of a mixed-protocol capture's frames restricted to `protocols`
+/// (e.g. only the RS-485 frames of a CAN + serial session). Empty
+/// `protocols` returns every frame.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_capture_frames_by_protocol(
+    capture_id: String,
+    protocols: Vec<io::Protocol>,
+    offset: usize,
+    limit: usize,
+) -> Result<PaginatedFramesResponse, String> {
+    let (frames, capture_indices, total_count) =
+        capture_store::get_capture_frames_by_protocol(&capture_id, &protocols, offset, limit);
+    Ok(PaginatedFramesResponse {
+        frames,
+        total_count,
+        offset,
+        limit,
+        capture_indices,
+    })
+}
+
+/// Frame count per protocol in a capture
+#[derive(Clone, serde::Serialize)]
+pub struct ProtocolCount {
+    pub protocol: io::Protocol,
+    pub count: usize,
+}
+
+/// Count a capture's frames per protocol, so a view can offer one tab per
+/// protocol sub-stream. Protocols with no frames are omitted.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_capture_protocol_counts(capture_id: String) -> Result<Vec<ProtocolCount>, String> {
+    let counts = tauri::async_runtime::spawn_blocking(move || crate::capture_db::protocol_counts(&capture_id))
+        .await
+        .map_err(|e| format!("Protocol count task failed: {}", e))??;
+    Ok(counts
+        .into_iter()
+        .map(|(protocol, count)| ProtocolCount { protocol, count })
+        .collect())
+}
+
+/// Get a page of frames (filtered when `selected_ids` is non-empty) as a
+/// packed binary body: the same JSON as `get_capture_frames_paginated_filtered`,
+/// LZ4-compressed when large, or with `encoding: "binary"` the fixed
+/// CODEC_FRAMES layout (see ipc_compression.rs).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_capture_frames_paginated_packed(
+    capture_id: String,
+    offset: usize,
+    limit: usize,
+    selected_ids: Vec<u32>,
+    encoding: Option<ipc_compression::FrameEncoding>,
+) -> Result<tauri::ipc::Response, String> {
+    let selected_set: std::collections::HashSet<u32> = selected_ids.into_iter().collect();
+    let (frames, capture_indices, total_count) = capture_store::get_capture_frames_paginated_filtered(&capture_id, offset, limit, &selected_set);
+    if encoding.unwrap_or_default() == ipc_compression::FrameEncoding::Binary {
+        let body = ipc_compression::pack_frame_page(&frames, &capture_indices, total_count, offset, limit);
+        return Ok(tauri::ipc::Response::new(body));
+    }
+    let body = ipc_compression::pack_json(&PaginatedFramesResponse {
+        frames,
+        total_count,
+        offset,
+        limit,
+        capture_indices,
+    })?;
+    Ok(tauri::ipc::Response::new(body))
+}
+
 /// Get the most recent N frames from a capture, optionally filtered by frame IDs.
 /// Used for "tail mode" during streaming.
 #[tauri::command(rename_all = "snake_case")]
@@ -409,6 +800,57 @@ pub async fn clear_capture(capture_id: String) -> Result<(), String> {
     capture_store::clear_capture(&capture_id)
 }
 
+/// Which side of a trim range to keep.
+#[derive(Clone, Copy, Debug, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrimMode {
+    /// Keep the range, delete everything outside it
+    KeepRange,
+    /// Delete the range, keep everything outside it
+    RemoveRange,
+}
+
+/// Result of a capture trim.
+#[derive(Clone, serde::Serialize)]
+pub struct TrimResult {
+    /// Number of frames (or bytes) deleted
+    pub removed: usize,
+    pub metadata: CaptureMetadata,
+}
+
+/// Delete a capture's frames outside (or inside) a time range in place.
+/// A missing bound extends to the start or end of the capture.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn trim_capture(
+    capture_id: String,
+    start_us: Option<u64>,
+    end_us: Option<u64>,
+    mode: TrimMode,
+) -> Result<TrimResult, String> {
+    let keep_inside = matches!(mode, TrimMode::KeepRange);
+    let (removed, metadata) = tauri::async_runtime::spawn_blocking(move || {
+        capture_store::trim_capture(&capture_id, start_us, end_us, keep_inside)
+    })
+    .await
+    .map_err(|e| format!("Trim task failed: {}", e))??;
+    Ok(TrimResult { removed, metadata })
+}
+
+/// Copy the part of a capture within `[start_us, end_us]` into a new capture
+/// (the source is left as it is), so the interesting window of a long
+/// capture can be kept without exporting and re-importing it.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn split_capture(
+    capture_id: String,
+    start_us: Option<u64>,
+    end_us: Option<u64>,
+    new_name: String,
+) -> Result<CaptureMetadata, String> {
+    tauri::async_runtime::spawn_blocking(move || capture_store::split_capture(&capture_id, start_us, end_us, new_name))
+        .await
+        .map_err(|e| format!("Split task failed: {}", e))?
+}
+
 /// Get metadata for a specific capture by ID
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_capture_metadata_by_id(capture_id: String) -> Result<Option<CaptureMetadata>, String> {
@@ -419,14 +861,14 @@ pub async fn get_capture_metadata_by_id(capture_id: String) -> Result<Option<Cap
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_capture_frames_by_id(capture_id: String) -> Result<Vec<FrameMessage>, String> {
     capture_store::get_capture_frames(&capture_id)
-        .ok_or_else(|| format!("Capture '{}' not found or is not a frame capture", capture_id))
+        .ok_or_else(|| msg!(CAPTURE_NOT_FRAMES, capture_id = capture_id).into())
 }
 
 /// Get raw bytes from a specific capture by ID
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_capture_bytes_by_id(capture_id: String) -> Result<Vec<TimestampedByte>, String> {
     capture_store::get_capture_bytes(&capture_id)
-        .ok_or_else(|| format!("Capture '{}' not found or is not a byte capture", capture_id))
+        .ok_or_else(|| msg!(CAPTURE_NOT_BYTES, capture_id = capture_id).into())
 }
 
 /// Mark a capture as active (being rendered by a UI panel)
@@ -559,12 +1001,72 @@ pub fn get_capture_bytes_tail(capture_id: String, tail_size: usize) -> BytesTail
 // Session-Aware Capture Commands
 // ============================================================================
 
+/// Search a frame capture with a structured pattern (frame ID ranges, byte
+/// masks, payload byte sequence), returning 0-based offsets of the hits in
+/// the selected-ID-filtered result set so the view can jump between them.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn search_capture_pattern(
+    capture_id: String,
+    pattern: FramePattern,
+    selected_ids: Vec<u32>,
+    limit: Option<usize>,
+) -> Result<Vec<usize>, String> {
+    let limit = limit.unwrap_or(PATTERN_SEARCH_LIMIT);
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::capture_db::search_frames_by_pattern(&capture_id, &pattern, &selected_ids, limit)
+    })
+    .await
+    .map_err(|e| format!("Search task failed: {}", e))?
+}
+
 /// Rename a capture.
 #[tauri::command(rename_all = "snake_case")]
 pub async fn rename_capture(capture_id: String, new_name: String) -> Result<CaptureMetadata, String> {
     capture_store::rename_capture(&capture_id, &new_name)
 }
 
+/// Pin a note or marker to a capture, either on a frame (0-based index in
+/// capture order) or at a timestamp. Exactly one anchor must be given.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn add_capture_annotation(
+    capture_id: String,
+    frame_index: Option<usize>,
+    timestamp_us: Option<u64>,
+    text: String,
+    color: Option<String>,
+) -> Result<CaptureAnnotation, String> {
+    if capture_store::get_capture_kind(&capture_id).is_none() {
+        return Err(msg!(CAPTURE_NOT_FOUND, capture_id = capture_id).into());
+    }
+    let anchor = match (frame_index, timestamp_us) {
+        (Some(index), None) => AnnotationAnchor::Frame(index),
+        (None, Some(us)) => AnnotationAnchor::Time(us),
+        _ => return Err("Give either a frame index or a timestamp".to_string()),
+    };
+    crate::capture_db::add_annotation(&capture_id, anchor, &text, color.as_deref())
+}
+
+/// List a capture's annotations in time order.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_capture_annotations(capture_id: String) -> Result<Vec<CaptureAnnotation>, String> {
+    crate::capture_db::list_annotations(&capture_id)
+}
+
+/// Change an annotation's text and colour.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn update_capture_annotation(id: i64, text: String, color: Option<String>) -> Result<(), String> {
+    match crate::capture_db::update_annotation(id, &text, color.as_deref())? {
+        true => Ok(()),
+        false => Err(format!("Annotation {} not found", id)),
+    }
+}
+
+/// Delete an annotation.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_capture_annotation(id: i64) -> Result<(), String> {
+    crate::capture_db::delete_annotation(id).map(|_| ())
+}
+
 /// Set a capture's persistent flag.
 /// Persistent captures survive app restart when 'clear captures on start' is enabled.
 #[tauri::command(rename_all = "snake_case")]
@@ -572,6 +1074,49 @@ pub async fn set_capture_persistent(capture_id: String, persistent: bool) -> Res
     capture_store::set_capture_persistent(&capture_id, persistent)
 }
 
+/// Set or clear a frame capture's ring-buffer limit (max frames and/or max
+/// duration). Frames past the limit are dropped from the head as they stream
+/// in, and a `capture-trimmed` event reports each trim.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_capture_ring_limit(capture_id: String, limit: Option<RingLimit>) -> Result<(), String> {
+    capture_store::set_ring_limit(&capture_id, limit)
+}
+
+/// Get a capture's ring-buffer limit (None = unbounded).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_capture_ring_limit(capture_id: String) -> Option<RingLimit> {
+    capture_store::get_ring_limit(&capture_id)
+}
+
+/// Save a capture to a standalone SQLite capture file at `path`, so it can
+/// be kept outside the app database and opened again later.
+/// Returns the number of frames/bytes written.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn persist_capture(capture_id: String, path: String) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || capture_store::persist_capture(&capture_id, &path))
+        .await
+        .map_err(|e| format!("Persist task failed: {}", e))?
+}
+
+/// Open a capture file saved by `persist_capture` as a new persistent
+/// capture. Pages are then served from the capture database like any other.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn open_persisted_capture(path: String) -> Result<CaptureMetadata, String> {
+    tauri::async_runtime::spawn_blocking(move || capture_store::open_persisted_capture(&path))
+        .await
+        .map_err(|e| format!("Open task failed: {}", e))?
+}
+
+/// Merge frame captures taken separately (e.g. one per device) into a new
+/// capture, frames interleaved by timestamp. Each source may renumber its
+/// buses so identically numbered ports stay apart in the merged capture.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn merge_captures(sources: Vec<MergeSource>, new_name: String) -> Result<CaptureMetadata, String> {
+    tauri::async_runtime::spawn_blocking(move || capture_store::merge_captures(&sources, new_name))
+        .await
+        .map_err(|e| format!("Merge task failed: {}", e))?
+}
+
 /// List only orphaned captures (no owning session).
 /// These are captures available for standalone selection in the IO picker.
 /// Includes CSV imports and captures from destroyed sessions.
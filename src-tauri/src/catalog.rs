@@ -89,6 +89,17 @@ pub async fn dispatch_catalog_command(
             let toml = wiretap_catalog::dbc::convert_dbc_to_toml(&content()?)?;
             Ok(serde_json::Value::String(toml))
         }
+        // EDS/DCF text → catalogue TOML (default PDO mappings, heartbeat, EMCY).
+        // Params: { content, node_id? }.
+        "catalog.import_eds" => {
+            let node_id = params
+                .get("node_id")
+                .and_then(|v| v.as_u64())
+                .map(|n| u8::try_from(n).map_err(|_| format!("Node ID {} is outside 1-127", n)))
+                .transpose()?;
+            let toml = crate::canopen::eds_to_catalog_toml(&content()?, node_id)?;
+            Ok(serde_json::Value::String(toml))
+        }
         // Attach a catalogue to a session so its frames are decoded in Rust and
         // streamed as DecodedSignals. Params: { session_id, content, path? }. The
         // optional `path` is recorded as the session's authoritative decoder path and
@@ -96,7 +107,12 @@ pub async fn dispatch_catalog_command(
         "catalog.attach" => {
             let session_id = req("session_id")?;
             let path = params.get("path").and_then(|v| v.as_str()).map(str::to_string);
-            let cat = wiretap_catalog::Catalog::parse(&content()?).map_err(|e| e.to_string())?;
+            let content = content()?;
+            let cat = wiretap_catalog::Catalog::parse(&content).map_err(|e| e.to_string())?;
+            // J1939 SPN definitions live in a [j1939] table the catalogue crate ignores
+            crate::j1939::attach_catalog(&session_id, &content)?;
+            // Likewise the LDF-lite [lin] description
+            crate::io::lin::attach_catalog(&session_id, &content)?;
             let frame_count = cat.frames.len();
             // Return the resolved Catalog so the caller can feed its UI model from
             // this one parse instead of a separate catalog.parse round-trip.
@@ -109,7 +125,10 @@ pub async fn dispatch_catalog_command(
         }
         // Detach a session's catalogue (decoded stream stops). Params: { session_id }.
         "catalog.detach" => {
-            crate::ws::dispatch::detach_catalog(&req("session_id")?);
+            let session_id = req("session_id")?;
+            crate::ws::dispatch::detach_catalog(&session_id);
+            crate::j1939::detach_catalog(&session_id);
+            crate::io::lin::detach_catalog(&session_id);
             Ok(serde_json::json!({ "attached": false }))
         }
         // Catalogue TOML → DBC text (extended | flattened mux).
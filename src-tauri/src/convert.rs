@@ -0,0 +1,352 @@
+// ui/src-tauri/src/convert.rs
+//
+// Command-line conversion mode, so historic logs can be batch-converted
+// without opening each one in the GUI. Runs in place of the app when the
+// first argument is `convert`:
+//
+//   WireTAP convert <input> <output>                 one file
+//   WireTAP convert --to <ext> [--out-dir <dir>] <input>...
+//                                                    many files, output named
+//                                                    after each input
+//
+// Formats come from file extensions and reuse the import parsers and export
+// writers in io::recorded:
+//
+//   input   .csv  .log/.candump  .blf  .pcap/.pcapng  .trc  .mf4/.mdf
+//   output  .csv  .log/.candump  .blf  .asc  .pcap/.pcapng  .parquet
+//
+// MDF4 input is streamed in batches; the other readers load the whole file.
+// Nothing touches the capture database or app settings.
+
+use std::fs::File;
+use std::io::{BufWriter, Cursor, Write};
+use std::path::{Path, PathBuf};
+
+use crate::io::{self, FrameMessage};
+
+/// Frames per batch when streaming an MDF4 input
+const MDF4_BATCH: usize = 50_000;
+
+const USAGE: &str = "\
+Usage:
+  WireTAP convert <input> <output>
+  WireTAP convert --to <ext> [--out-dir <dir>] <input>...
+
+Input formats:  csv, log/candump, blf, pcap/pcapng, trc, mf4/mdf
+Output formats: csv, log/candump, blf, asc, pcap/pcapng, parquet";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum InputFormat {
+    Csv,
+    Candump,
+    Blf,
+    Pcap,
+    Trc,
+    Mdf4,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum OutputFormat {
+    Csv,
+    Candump,
+    Blf,
+    Asc,
+    Pcap,
+    Parquet,
+}
+
+pub(crate) fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+fn input_format(path: &Path) -> Result<InputFormat, String> {
+    match extension(path).as_str() {
+        "csv" => Ok(InputFormat::Csv),
+        "log" | "candump" => Ok(InputFormat::Candump),
+        "blf" => Ok(InputFormat::Blf),
+        "pcap" | "pcapng" => Ok(InputFormat::Pcap),
+        "trc" => Ok(InputFormat::Trc),
+        "mf4" | "mdf" => Ok(InputFormat::Mdf4),
+        other => Err(format!("{}: unsupported input format '.{}'", path.display(), other)),
+    }
+}
+
+pub(crate) fn output_format(ext: &str) -> Result<OutputFormat, String> {
+    match ext.trim_start_matches('.').to_ascii_lowercase().as_str() {
+        "csv" => Ok(OutputFormat::Csv),
+        "log" | "candump" => Ok(OutputFormat::Candump),
+        "blf" => Ok(OutputFormat::Blf),
+        "asc" => Ok(OutputFormat::Asc),
+        "pcap" | "pcapng" => Ok(OutputFormat::Pcap),
+        "parquet" => Ok(OutputFormat::Parquet),
+        other => Err(format!("unsupported output format '.{}'", other)),
+    }
+}
+
+/// An open export writer, to a file or any other byte stream. Frames a
+/// CAN-only writer skips show up as the gap between frames read and frames
+/// written.
+pub(crate) enum Sink<W: Write + Send = BufWriter<File>> {
+    Csv(io::CsvWriter<W>),
+    Candump(io::CandumpWriter<W>),
+    Blf(io::BlfWriter),
+    /// BLF to a stream: the header is only known at the end and sits at the
+    /// start, so the file is assembled in memory and copied out by `finish`.
+    BlfBuffered(io::BlfWriter<Cursor<Vec<u8>>>, W),
+    Asc(io::AscWriter<W>),
+    Pcap(io::PcapWriter<W>),
+    Parquet(io::ParquetWriter<W>),
+}
+
+impl Sink {
+    /// Open the output file. `start_us` (the first frame's timestamp) is the
+    /// ASC measurement start.
+    pub(crate) fn create(format: OutputFormat, path: &str, start_us: u64) -> Result<Self, String> {
+        Ok(match format {
+            OutputFormat::Csv => Sink::Csv(io::CsvWriter::create(path)?),
+            OutputFormat::Candump => Sink::Candump(io::CandumpWriter::create(path)?),
+            OutputFormat::Blf => Sink::Blf(io::BlfWriter::create(path)?),
+            OutputFormat::Asc => Sink::Asc(io::AscWriter::create(path, start_us, io::AscTimeBase::default())?),
+            OutputFormat::Pcap => Sink::Pcap(io::PcapWriter::create(path)?),
+            OutputFormat::Parquet => Sink::Parquet(io::ParquetWriter::create(path)?),
+        })
+    }
+}
+
+impl<W: Write + Send> Sink<W> {
+    /// Write to `out` as frames arrive (BLF excepted, see `BlfBuffered`).
+    pub(crate) fn new(format: OutputFormat, out: W, start_us: u64) -> Result<Self, String> {
+        Ok(match format {
+            OutputFormat::Csv => Sink::Csv(io::CsvWriter::new(out)?),
+            OutputFormat::Candump => Sink::Candump(io::CandumpWriter::new(out)),
+            OutputFormat::Blf => Sink::BlfBuffered(io::BlfWriter::new(Cursor::new(Vec::new()))?, out),
+            OutputFormat::Asc => Sink::Asc(io::AscWriter::new(out, start_us, io::AscTimeBase::default())?),
+            OutputFormat::Pcap => Sink::Pcap(io::PcapWriter::new(out)?),
+            OutputFormat::Parquet => Sink::Parquet(io::ParquetWriter::new(out)?),
+        })
+    }
+
+    pub(crate) fn write_frame(&mut self, frame: &FrameMessage) -> Result<(), String> {
+        match self {
+            Sink::Csv(w) => w.write_frame(frame),
+            Sink::Candump(w) => w.write_frame(frame).map(|_| ()),
+            Sink::Blf(w) => w.write_frame(frame).map(|_| ()),
+            Sink::BlfBuffered(w, _) => w.write_frame(frame).map(|_| ()),
+            Sink::Asc(w) => w.write_frame(frame).map(|_| ()),
+            Sink::Pcap(w) => w.write_frame(frame).map(|_| ()),
+            Sink::Parquet(w) => w.write_frame(frame),
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<u32, String> {
+        match self {
+            Sink::Csv(w) => w.finish(),
+            Sink::Candump(w) => w.finish(),
+            Sink::Blf(w) => w.finish(),
+            Sink::BlfBuffered(w, mut out) => {
+                let (count, buffer) = w.finish_into_inner()?;
+                out.write_all(buffer.get_ref())
+                    .and_then(|_| out.flush())
+                    .map_err(|e| format!("Failed to write BLF output: {}", e))?;
+                Ok(count)
+            }
+            Sink::Asc(w) => w.finish(),
+            Sink::Pcap(w) => w.finish(),
+            Sink::Parquet(w) => w.finish(),
+        }
+    }
+}
+
+/// Convert one file. Returns (frames read, frames written); the difference is
+/// frames the output format can't hold (e.g. non-CAN frames in BLF/ASC).
+fn convert_file(input: &Path, output: &Path) -> Result<(usize, u32), String> {
+    let in_format = input_format(input)?;
+    let out_format = output_format(&extension(output))?;
+    let input_str = input.to_string_lossy();
+    let output_str = output.to_string_lossy().to_string();
+
+    // Created on the first batch, once the ASC start time is known
+    let mut sink: Option<Sink> = None;
+    let mut read = 0usize;
+    let mut write_batch = |frames: Vec<FrameMessage>| -> Result<(), String> {
+        let Some(first) = frames.first() else { return Ok(()) };
+        if sink.is_none() {
+            sink = Some(Sink::create(out_format, &output_str, first.timestamp_us)?);
+        }
+        let sink = sink.as_mut().expect("sink created above");
+        for frame in &frames {
+            sink.write_frame(frame)?;
+        }
+        read += frames.len();
+        Ok(())
+    };
+
+    match in_format {
+        InputFormat::Csv => write_batch(io::parse_csv_file(&input_str)?)?,
+        InputFormat::Candump => write_batch(io::parse_candump_file(&input_str)?)?,
+        InputFormat::Blf => write_batch(io::parse_blf_file(&input_str)?)?,
+        InputFormat::Pcap => write_batch(io::parse_pcap_file(&input_str)?)?,
+        InputFormat::Trc => write_batch(io::parse_trc_file(&input_str)?)?,
+        InputFormat::Mdf4 => {
+            let mut reader = io::Mdf4Reader::open(&input_str)?;
+            reader.read_frames(None, None, MDF4_BATCH, &mut write_batch)?;
+        }
+    }
+
+    match sink {
+        Some(sink) => Ok((read, sink.finish()?)),
+        None => Err(format!("{}: no frames to convert", input.display())),
+    }
+}
+
+/// Output path for a batch conversion: the input's file stem with the target
+/// extension, in `out_dir` or beside the input.
+fn batch_output(input: &Path, ext: &str, out_dir: Option<&Path>) -> PathBuf {
+    let name = Path::new(input.file_name().unwrap_or_default()).with_extension(ext.trim_start_matches('.'));
+    match out_dir {
+        Some(dir) => dir.join(name),
+        None => input.with_file_name(name),
+    }
+}
+
+/// Parsed `convert` arguments: (input, output) pairs to convert.
+fn parse_args(args: &[String]) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+    let mut to: Option<String> = None;
+    let mut out_dir: Option<PathBuf> = None;
+    let mut paths: Vec<PathBuf> = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--to" => to = Some(iter.next().ok_or("--to needs a format")?.clone()),
+            "--out-dir" => out_dir = Some(iter.next().ok_or("--out-dir needs a directory")?.into()),
+            flag if flag.starts_with("--") => return Err(format!("unknown option '{}'", flag)),
+            path => paths.push(path.into()),
+        }
+    }
+
+    match to {
+        Some(ext) => {
+            output_format(&ext)?;
+            if paths.is_empty() {
+                return Err("no input files".to_string());
+            }
+            Ok(paths
+                .into_iter()
+                .map(|input| {
+                    let output = batch_output(&input, &ext, out_dir.as_deref());
+                    (input, output)
+                })
+                .collect())
+        }
+        None if out_dir.is_some() => Err("--out-dir needs --to".to_string()),
+        None => match <[PathBuf; 2]>::try_from(paths) {
+            Ok([input, output]) => Ok(vec![(input, output)]),
+            Err(_) => Err("expected an input and an output file".to_string()),
+        },
+    }
+}
+
+/// Attach to the launching terminal so output is visible from a GUI-subsystem
+/// release build on Windows.
+#[cfg(target_os = "windows")]
+pub(crate) fn attach_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn attach_console() {}
+
+/// Run conversion mode when the command line asks for it. Returns the process
+/// exit code, or None to start the app normally.
+pub fn run_from_args() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) != Some("convert") {
+        return None;
+    }
+    attach_console();
+
+    let jobs = match parse_args(&args[1..]) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            eprintln!("convert: {}\n\n{}", e, USAGE);
+            return Some(2);
+        }
+    };
+
+    let mut failed = 0;
+    for (input, output) in &jobs {
+        match convert_file(input, output) {
+            Ok((read, written)) => {
+                let skipped = read.saturating_sub(written as usize);
+                println!(
+                    "{} -> {}: {} frames{}",
+                    input.display(),
+                    output.display(),
+                    written,
+                    if skipped > 0 { format!(" ({} not representable, skipped)", skipped) } else { String::new() }
+                );
+            }
+            Err(e) => {
+                eprintln!("convert: {}", e);
+                failed += 1;
+            }
+        }
+    }
+    Some(if failed == 0 { 0 } else { 1 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_single_and_batch_invocations() {
+        let jobs = parse_args(&args(&["in.trc", "out.csv"])).unwrap();
+        assert_eq!(jobs, vec![(PathBuf::from("in.trc"), PathBuf::from("out.csv"))]);
+
+        let jobs = parse_args(&args(&["--to", "pcapng", "logs/a.log", "logs/b.LOG"])).unwrap();
+        assert_eq!(jobs[0].1, PathBuf::from("logs/a.pcapng"));
+        assert_eq!(jobs[1].1, PathBuf::from("logs/b.pcapng"));
+
+        let jobs = parse_args(&args(&["--out-dir", "out", "--to", ".csv", "logs/a.blf"])).unwrap();
+        assert_eq!(jobs[0].1, Path::new("out").join("a.csv"));
+    }
+
+    #[test]
+    fn rejects_bad_invocations() {
+        assert!(parse_args(&args(&["only.log"])).is_err());
+        assert!(parse_args(&args(&["--to", "xyz", "a.log"])).is_err());
+        assert!(parse_args(&args(&["--out-dir", "out", "a.log", "b.csv"])).is_err());
+        assert!(parse_args(&args(&["--to", "csv"])).is_err());
+        assert!(parse_args(&args(&["--verbose", "a.log", "b.csv"])).is_err());
+    }
+
+    #[test]
+    fn converts_candump_to_csv() {
+        let dir = std::env::temp_dir().join(format!("wiretap-convert-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("bench.log");
+        std::fs::write(&input, "(1.000000) can0 123#0102 R\n(1.500000) can1 18DAF110#AA T\n").unwrap();
+
+        let output = dir.join("bench.csv");
+        assert_eq!(convert_file(&input, &output).unwrap(), (2, 2));
+        let frames = io::parse_csv_file(&output.to_string_lossy()).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!((frames[1].frame_id, frames[1].bus, frames[1].timestamp_us), (0x18DA_F110, 1, 1_500_000));
+        assert_eq!(frames[1].direction.as_deref(), Some("tx"));
+
+        assert!(convert_file(&input, &dir.join("bench.xyz")).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
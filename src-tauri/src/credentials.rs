@@ -58,7 +58,7 @@ pub fn delete_credential(profile_id: &str, field: &str) -> Result<(), String> {
 #[tauri::command(rename_all = "camelCase")]
 pub fn delete_all_credentials(profile_id: &str) -> Result<(), String> {
     // Common credential field names
-    let fields = ["password", "token", "api_key", "secret"];
+    let fields = ["password", "token", "api_key", "secret", "tls_client_cert", "tls_client_key"];
     for field in fields {
         delete_credential_internal(profile_id, field)?;
     }
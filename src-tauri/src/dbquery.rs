@@ -460,6 +460,48 @@ pub struct DatabaseActivityResult {
     pub queries: Vec<DatabaseActivity>,
     /// Active sessions connected to the database
     pub sessions: Vec<DatabaseActivity>,
+    /// Frame traffic breakdown (only when requested)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traffic: Option<TrafficActivity>,
+}
+
+/// Frame activity of one bus or source over the traffic window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityGroup {
+    /// Bus number or source value
+    pub key: String,
+    pub frame_count: i64,
+    pub first_us: Option<i64>,
+    pub last_us: Option<i64>,
+    pub frames_per_sec: f64,
+    /// Buckets in the window with no frames — a bus that went quiet shows up here
+    pub empty_buckets: usize,
+}
+
+/// One heatmap row: frame count per bucket, parallel to `bucket_starts_us`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivitySeries {
+    pub key: String,
+    pub counts: Vec<i64>,
+}
+
+/// Time-bucketed frame traffic, broken down by bus and (when the table has
+/// one) by a source/device column
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrafficActivity {
+    pub start_us: i64,
+    pub end_us: i64,
+    /// Effective bucket width (widened so the window has at most MAX_ACTIVITY_BUCKETS)
+    pub bucket_secs: f64,
+    pub bucket_starts_us: Vec<i64>,
+    pub by_bus: Vec<ActivityGroup>,
+    pub bus_series: Vec<ActivitySeries>,
+    /// Column the source breakdown groups by (None when the table has none)
+    pub source_column: Option<String>,
+    pub by_source: Vec<ActivityGroup>,
+    pub source_series: Vec<ActivitySeries>,
+    /// True when read from the hourly rollup (bucket edges are hour-aligned)
+    pub from_rollup: bool,
 }
 
 /// Build PostgreSQL connection string from profile
@@ -561,9 +603,9 @@ async fn connect_profile(
 ) -> Result<tokio_postgres::Client, String> {
     let settings = load_settings(app.clone())
         .await
-        .map_err(|e| format!("Failed to load settings: {}", e))?;
+        .map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
     let profile = find_profile(&settings, profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
     if profile.kind != "postgres" {
         return Err("Profile is not a PostgreSQL profile".to_string());
     }
@@ -700,6 +742,53 @@ pub async fn db_fetch_frame_payloads(
     Ok(rows.iter().map(|r| r.get::<_, Vec<u8>>("data_bytes")).collect())
 }
 
+/// Fetch every frame on the given ids from a PostgreSQL source in timestamp
+/// order, as (timestamp_us, frame_id, is_extended, payload). Used for headless
+/// request/response latency analysis.
+pub async fn db_fetch_id_sequence(
+    app: &AppHandle,
+    profile_id: &str,
+    frame_ids: &[u32],
+    start_time: Option<String>,
+    end_time: Option<String>,
+) -> Result<Vec<(i64, i64, bool, Vec<u8>)>, String> {
+    if profile_if_wiretap(app, profile_id).await.is_some() {
+        return Err("Latency analysis is not available for WireTAP API profiles".to_string());
+    }
+    let client = connect_profile(app, profile_id).await?;
+    let ids: Vec<i32> = frame_ids.iter().map(|&id| id as i32).collect();
+
+    let mut sql = String::from(
+        "SELECT (EXTRACT(EPOCH FROM ts) * 1000000)::float8 AS ts_us, id, extended, data_bytes \
+         FROM public.can_frame WHERE id = ANY($1::int4[])",
+    );
+    let mut params: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> = vec![&ids];
+    let mut idx = 2;
+    if let Some(ref s) = start_time {
+        sql.push_str(&format!(" AND ts >= (${}::text)::timestamptz", idx));
+        idx += 1;
+        params.push(s);
+    }
+    if let Some(ref e) = end_time {
+        sql.push_str(&format!(" AND ts < (${}::text)::timestamptz", idx));
+        params.push(e);
+    }
+    sql.push_str(" ORDER BY ts");
+
+    let rows = client
+        .query(sql.as_str(), &params)
+        .await
+        .map_err(|e| format!("Frame sequence query failed: {}", e))?;
+    Ok(rows
+        .iter()
+        .map(|r| {
+            let ts_us: f64 = r.get("ts_us");
+            let id: i32 = r.get("id");
+            (ts_us as i64, id as u32 as i64, r.get("extended"), r.get("data_bytes"))
+        })
+        .collect())
+}
+
 /// Query for byte changes in a specific frame
 ///
 /// Returns a list of timestamps where the specified byte changed value.
@@ -724,12 +813,12 @@ pub async fn db_query_byte_changes(
         profile_id, frame_id, byte_index, is_extended, result_limit);
 
     // Load settings to get profile
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
     tlog!("[dbquery] Loaded settings, found {} IO profiles", settings.io_profiles.len());
 
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     tlog!("[dbquery] Found profile: id='{}', kind='{}', name='{}'",
         profile.id, profile.kind, profile.name);
@@ -904,10 +993,10 @@ pub async fn db_query_frame_changes(
         profile_id, frame_id, is_extended, result_limit);
 
     // Load settings to get profile
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     tlog!("[dbquery] Found profile: id='{}', kind='{}', name='{}'",
         profile.id, profile.kind, profile.name);
@@ -1093,10 +1182,10 @@ pub async fn db_query_mirror_validation(
         profile_id, mirror_frame_id, source_frame_id, is_extended, tolerance_ms, result_limit);
 
     // Load settings to get profile
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     tlog!("[dbquery] Found profile: id='{}', kind='{}', name='{}'",
         profile.id, profile.kind, profile.name);
@@ -1289,16 +1378,23 @@ pub async fn db_query_mirror_validation(
 pub async fn db_query_activity(
     app: AppHandle,
     profile_id: String,
+    include_traffic: Option<bool>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    bucket_secs: Option<f64>,
 ) -> Result<DatabaseActivityResult, String> {
     tlog!("[dbquery] db_query_activity called for profile '{}'", profile_id);
 
     // Load settings to get profile
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     if profile.kind == "wiretap" {
+        if include_traffic.unwrap_or(false) {
+            tlog!("[dbquery] Traffic breakdown is not available for wiretap API profiles");
+        }
         return crate::apiclient::activity(&profile).await;
     }
     if profile.kind != "postgres" {
@@ -1382,7 +1478,192 @@ pub async fn db_query_activity(
     tlog!("[dbquery] Found {} active queries, {} idle sessions for database '{}'",
         queries.len(), sessions.len(), database_name);
 
-    Ok(DatabaseActivityResult { queries, sessions })
+    let traffic = if include_traffic.unwrap_or(false) {
+        Some(query_traffic_activity(&client, start_time, end_time, bucket_secs).await?)
+    } else {
+        None
+    };
+
+    Ok(DatabaseActivityResult { queries, sessions, traffic })
+}
+
+/// Heatmap resolution cap; wider windows get wider buckets.
+const MAX_ACTIVITY_BUCKETS: usize = 1000;
+
+/// Columns recognised as a per-source/device discriminator on can_frame, in
+/// preference order. Deployments that ingest several devices into one table
+/// add one of these; the stock schema has none.
+const ACTIVITY_SOURCE_COLUMNS: &[&str] = &["source", "device", "interface", "source_table"];
+
+/// (key, bucket start seconds, count, first_us, last_us)
+type ActivityRow = (String, f64, i64, f64, f64);
+
+/// Fold bucketed rows into per-key totals and zero-filled heatmap rows.
+fn build_activity(
+    rows: &[ActivityRow],
+    grid_start_s: f64,
+    bucket_secs: f64,
+    buckets: usize,
+    window_secs: f64,
+) -> (Vec<ActivityGroup>, Vec<ActivitySeries>) {
+    let mut by_key: std::collections::BTreeMap<&str, (Vec<i64>, i64, Option<f64>, Option<f64>)> =
+        std::collections::BTreeMap::new();
+    for (key, bucket_s, count, first, last) in rows {
+        let entry = by_key
+            .entry(key.as_str())
+            .or_insert_with(|| (vec![0; buckets], 0, None, None));
+        let idx = ((bucket_s - grid_start_s) / bucket_secs).round();
+        if idx >= 0.0 && (idx as usize) < buckets {
+            entry.0[idx as usize] += count;
+        }
+        entry.1 += count;
+        entry.2 = Some(entry.2.map_or(*first, |f: f64| f.min(*first)));
+        entry.3 = Some(entry.3.map_or(*last, |l: f64| l.max(*last)));
+    }
+    let mut groups = Vec::with_capacity(by_key.len());
+    let mut series = Vec::with_capacity(by_key.len());
+    for (key, (counts, total, first, last)) in by_key {
+        groups.push(ActivityGroup {
+            key: key.to_string(),
+            frame_count: total,
+            first_us: first.map(|v| v as i64),
+            last_us: last.map(|v| v as i64),
+            frames_per_sec: if window_secs > 0.0 { total as f64 / window_secs } else { 0.0 },
+            empty_buckets: counts.iter().filter(|&&c| c == 0).count(),
+        });
+        series.push(ActivitySeries { key: key.to_string(), counts });
+    }
+    (groups, series)
+}
+
+/// Bucketed frame traffic for the activity view. Defaults to the last hour in
+/// 60 s buckets. Whole-hour buckets read the hourly rollup when present.
+async fn query_traffic_activity(
+    client: &tokio_postgres::Client,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    bucket_secs: Option<f64>,
+) -> Result<TrafficActivity, String> {
+    let end_us = match end_time.as_deref() {
+        Some(t) => crate::analysis::iso_to_micros(t).ok_or_else(|| format!("Invalid end time: {}", t))?,
+        None => chrono::Utc::now().timestamp_micros(),
+    };
+    let start_us = match start_time.as_deref() {
+        Some(t) => crate::analysis::iso_to_micros(t).ok_or_else(|| format!("Invalid start time: {}", t))?,
+        None => end_us - 3_600_000_000,
+    };
+    if end_us <= start_us {
+        return Err("Traffic window end must be after its start".to_string());
+    }
+    let window_secs = (end_us - start_us) as f64 / 1_000_000.0;
+    let bucket_secs = bucket_secs
+        .filter(|b| *b > 0.0)
+        .unwrap_or(60.0)
+        .max((window_secs / MAX_ACTIVITY_BUCKETS as f64).ceil());
+    let grid_start_s = ((start_us as f64 / 1_000_000.0) / bucket_secs).floor() * bucket_secs;
+    let buckets = (((end_us as f64 / 1_000_000.0) - grid_start_s) / bucket_secs).ceil().max(1.0) as usize;
+    let start_s = start_us as f64 / 1_000_000.0;
+    let end_s = end_us as f64 / 1_000_000.0;
+
+    let source_column: Option<String> = client
+        .query(
+            "SELECT column_name::text FROM information_schema.columns \
+             WHERE table_schema = 'public' AND table_name = 'can_frame' AND column_name = ANY($1)",
+            &[&ACTIVITY_SOURCE_COLUMNS],
+        )
+        .await
+        .map_err(|e| format!("Column lookup failed: {}", e))?
+        .iter()
+        .map(|r| r.get::<_, String>(0))
+        .min_by_key(|c| ACTIVITY_SOURCE_COLUMNS.iter().position(|k| k == c));
+
+    let read_rows = |rows: Vec<tokio_postgres::Row>| -> Vec<ActivityRow> {
+        rows.iter()
+            .map(|r| {
+                let key: Option<String> = r.get("k");
+                (
+                    key.unwrap_or_else(|| "(none)".to_string()),
+                    r.get::<_, f64>("b"),
+                    r.get::<_, i64>("cnt"),
+                    r.get::<_, f64>("first_us"),
+                    r.get::<_, f64>("last_us"),
+                )
+            })
+            .collect()
+    };
+
+    // Whole-hour buckets without a source breakdown can come from the rollup
+    let from_rollup = source_column.is_none()
+        && bucket_secs % 3600.0 == 0.0
+        && rollup_available(client).await;
+    let bus_rows = if from_rollup {
+        client
+            .query(
+                "SELECT bus::text AS k, \
+                 floor(EXTRACT(EPOCH FROM bucket) / $3) * $3 AS b, \
+                 sum(frame_count)::int8 AS cnt, \
+                 (EXTRACT(EPOCH FROM min(first_ts)) * 1000000)::float8 AS first_us, \
+                 (EXTRACT(EPOCH FROM max(last_ts)) * 1000000)::float8 AS last_us \
+                 FROM public.can_frame_hourly \
+                 WHERE bucket >= to_timestamp($1) - INTERVAL '1 hour' AND bucket < to_timestamp($2) \
+                 GROUP BY 1, 2",
+                &[&start_s, &end_s, &bucket_secs],
+            )
+            .await
+    } else {
+        client
+            .query(
+                "SELECT bus::text AS k, \
+                 floor(EXTRACT(EPOCH FROM ts) / $3) * $3 AS b, \
+                 count(*)::int8 AS cnt, \
+                 (EXTRACT(EPOCH FROM min(ts)) * 1000000)::float8 AS first_us, \
+                 (EXTRACT(EPOCH FROM max(ts)) * 1000000)::float8 AS last_us \
+                 FROM public.can_frame \
+                 WHERE ts >= to_timestamp($1) AND ts < to_timestamp($2) \
+                 GROUP BY 1, 2",
+                &[&start_s, &end_s, &bucket_secs],
+            )
+            .await
+    }
+    .map_err(|e| format!("Bus activity query failed: {}", e))?;
+    let (by_bus, bus_series) = build_activity(&read_rows(bus_rows), grid_start_s, bucket_secs, buckets, window_secs);
+
+    let (by_source, source_series) = match &source_column {
+        Some(col) => {
+            // `col` comes from ACTIVITY_SOURCE_COLUMNS, never from the caller
+            let sql = format!(
+                "SELECT \"{col}\"::text AS k, \
+                 floor(EXTRACT(EPOCH FROM ts) / $3) * $3 AS b, \
+                 count(*)::int8 AS cnt, \
+                 (EXTRACT(EPOCH FROM min(ts)) * 1000000)::float8 AS first_us, \
+                 (EXTRACT(EPOCH FROM max(ts)) * 1000000)::float8 AS last_us \
+                 FROM public.can_frame \
+                 WHERE ts >= to_timestamp($1) AND ts < to_timestamp($2) \
+                 GROUP BY 1, 2"
+            );
+            let rows = client
+                .query(sql.as_str(), &[&start_s, &end_s, &bucket_secs])
+                .await
+                .map_err(|e| format!("Source activity query failed: {}", e))?;
+            build_activity(&read_rows(rows), grid_start_s, bucket_secs, buckets, window_secs)
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+
+    Ok(TrafficActivity {
+        start_us,
+        end_us,
+        bucket_secs,
+        bucket_starts_us: (0..buckets)
+            .map(|i| ((grid_start_s + i as f64 * bucket_secs) * 1_000_000.0) as i64)
+            .collect(),
+        by_bus,
+        bus_series,
+        source_column,
+        by_source,
+        source_series,
+        from_rollup,
+    })
 }
 
 /// Cancel a running query by backend PID using pg_cancel_backend
@@ -1398,10 +1679,10 @@ pub async fn db_cancel_backend(
     tlog!("[dbquery] db_cancel_backend called for pid {} on profile '{}'", pid, profile_id);
 
     // Load settings to get profile
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     if profile.kind == "wiretap" {
         return crate::apiclient::signal_backend(&profile, pid, false).await;
@@ -1450,10 +1731,10 @@ pub async fn db_terminate_backend(
     tlog!("[dbquery] db_terminate_backend called for pid {} on profile '{}'", pid, profile_id);
 
     // Load settings to get profile
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     if profile.kind == "wiretap" {
         return crate::apiclient::signal_backend(&profile, pid, true).await;
@@ -1513,9 +1794,9 @@ pub async fn db_query_mux_statistics(
     tlog!("[dbquery] db_query_mux_statistics: profile='{}', frame_id={}, mux_byte={}, limit={}",
         profile_id, frame_id, mux_selector_byte, result_limit);
 
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     if profile.kind == "wiretap" {
         return crate::apiclient::mux_statistics(&profile, frame_id, mux_selector_byte, is_extended, include_16bit, payload_length, start_time, end_time, limit, query_id).await;
@@ -1745,9 +2026,9 @@ pub async fn db_query_first_last(
         profile_id, frame_id, is_extended);
 
     // Load settings to get profile
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     if profile.kind == "wiretap" {
         return crate::apiclient::first_last(&profile, frame_id, is_extended, start_time, end_time, query_id).await;
@@ -1902,9 +2183,9 @@ pub async fn db_query_frequency(
         profile_id, frame_id, bucket_size_ms, is_extended, result_limit);
 
     // Load settings to get profile
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     if profile.kind == "wiretap" {
         return crate::apiclient::frequency(&profile, frame_id, is_extended, bucket_size_ms, start_time, end_time, limit, query_id).await;
@@ -2041,9 +2322,9 @@ pub async fn db_query_distribution(
         profile_id, frame_id, byte_index, is_extended);
 
     // Load settings to get profile
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     if profile.kind == "wiretap" {
         return crate::apiclient::distribution(&profile, frame_id, byte_index, is_extended, start_time, end_time, query_id).await;
@@ -2187,9 +2468,9 @@ pub async fn db_query_gap_analysis(
         profile_id, frame_id, gap_threshold_ms, is_extended);
 
     // Load settings to get profile
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     if profile.kind == "wiretap" {
         return crate::apiclient::gap_analysis(&profile, frame_id, is_extended, gap_threshold_ms, start_time, end_time, limit, query_id).await;
@@ -2324,9 +2605,9 @@ pub async fn db_query_pattern_search(
         profile_id, pattern.len(), result_limit);
 
     // Load settings to get profile
-    let settings = load_settings(app).await.map_err(|e| format!("Failed to load settings: {}", e))?;
+    let settings = load_settings(app).await.map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
     let profile = find_profile(&settings, &profile_id)
-        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+        .ok_or_else(|| msg!(PROFILE_NOT_FOUND, profile_id = profile_id))?;
 
     if profile.kind == "wiretap" {
         return crate::apiclient::pattern_search(&profile, pattern, pattern_mask, start_time, end_time, limit, query_id).await;
@@ -0,0 +1,310 @@
+// ui/src-tauri/src/export_sidecar.rs
+//
+// Companion metadata for exports. CSV, candump, BLF and pcap files carry frames
+// but not the context around them, so an export can optionally get a
+// `<file>.metadata.json` sidecar with:
+//
+//   - the capture: name, kind, count, time range (µs and RFC 3339), timing
+//   - sources: the owning session's source profiles and display names
+//   - buses: every bus in the capture, with a name where the caller has one
+//   - catalogs: the decoder attached to the session (path, [meta] name and
+//     version, SHA-256 of the file)
+//   - annotations: the capture's stored notes and markers, plus any the
+//     caller supplies (bookmarks)
+//   - integrity: the export file's SHA-256 and the capture's seal
+//
+// The frontend writes the sidecar beside files it exports (like the integrity
+// manifest); backend exports write it themselves via `write_sidecar`.
+
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+use crate::capture_integrity::CaptureIntegrity;
+use crate::capture_store::{self, CaptureAnnotation, CaptureKind, CaptureMetadata};
+use crate::timebase::CaptureTiming;
+
+/// Schema identifier written into every sidecar.
+const SIDECAR_SCHEMA: &str = "wiretap-export-metadata/1";
+
+/// Sidecar file suffix (appended to the export's full file name).
+pub const SIDECAR_SUFFIX: &str = ".metadata.json";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A note pinned to a point or span of the capture.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SidecarAnnotation {
+    pub label: String,
+    #[serde(default)]
+    pub start_time_us: Option<u64>,
+    #[serde(default)]
+    pub end_time_us: Option<u64>,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// A stored capture annotation as a point marker.
+fn sidecar_annotation(a: CaptureAnnotation) -> SidecarAnnotation {
+    SidecarAnnotation {
+        label: a.text,
+        start_time_us: Some(a.timestamp_us),
+        end_time_us: Some(a.timestamp_us),
+        note: None,
+        color: a.color,
+    }
+}
+
+/// What the caller knows that the backend doesn't.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct SidecarRequest {
+    pub capture_id: String,
+    /// Export format ("csv", "candump", "blf", ...)
+    pub format: String,
+    /// Session the capture came from (defaults to its owning session)
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Hex SHA-256 of the exported file
+    #[serde(default)]
+    pub export_sha256: Option<String>,
+    /// Display names for bus numbers
+    #[serde(default)]
+    pub bus_names: HashMap<u8, String>,
+    #[serde(default)]
+    pub annotations: Vec<SidecarAnnotation>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SidecarCapture {
+    pub id: String,
+    pub name: String,
+    pub kind: CaptureKind,
+    pub count: usize,
+    pub start_time_us: Option<u64>,
+    pub end_time_us: Option<u64>,
+    /// RFC 3339 forms of the time range
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub timing: Option<CaptureTiming>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SidecarSource {
+    pub profile_id: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SidecarBus {
+    pub bus: u8,
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SidecarCatalog {
+    pub path: String,
+    pub name: Option<String>,
+    pub version: Option<toml::Value>,
+    /// Hex SHA-256 of the catalogue file (None when it can't be read)
+    pub sha256: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SidecarIntegrity {
+    pub export_sha256: Option<String>,
+    pub capture: Option<CaptureIntegrity>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExportSidecar {
+    pub schema: &'static str,
+    /// RFC 3339
+    pub generated_at: String,
+    pub format: String,
+    pub capture: SidecarCapture,
+    pub session_id: Option<String>,
+    pub sources: Vec<SidecarSource>,
+    /// Source display names as shown in the app (e.g. "GVRET + PCAN")
+    pub source_names: Vec<String>,
+    pub buses: Vec<SidecarBus>,
+    pub catalogs: Vec<SidecarCatalog>,
+    pub annotations: Vec<SidecarAnnotation>,
+    pub integrity: SidecarIntegrity,
+}
+
+// ============================================================================
+// Building
+// ============================================================================
+
+fn rfc3339(us: Option<u64>) -> Option<String> {
+    let us = i64::try_from(us?).ok()?;
+    Utc.timestamp_opt(us.div_euclid(1_000_000), (us.rem_euclid(1_000_000) * 1000) as u32)
+        .single()
+        .map(|t| t.to_rfc3339())
+}
+
+/// Name, version and hash of a catalogue file.
+fn describe_catalog(path: &str) -> SidecarCatalog {
+    let content = std::fs::read(path).ok();
+    let meta = content
+        .as_deref()
+        .and_then(|c| std::str::from_utf8(c).ok())
+        .and_then(|s| s.parse::<toml::Table>().ok())
+        .and_then(|t| t.get("meta").and_then(|m| m.as_table()).cloned());
+    SidecarCatalog {
+        path: path.to_string(),
+        name: meta.as_ref().and_then(|m| m.get("name")).and_then(|n| n.as_str()).map(String::from),
+        version: meta.as_ref().and_then(|m| m.get("version")).cloned(),
+        sha256: content.map(|c| hex::encode(Sha256::digest(&c))),
+    }
+}
+
+fn capture_section(meta: &CaptureMetadata) -> SidecarCapture {
+    SidecarCapture {
+        id: meta.id.clone(),
+        name: meta.name.clone(),
+        kind: meta.kind.clone(),
+        count: meta.count,
+        start_time_us: meta.start_time_us,
+        end_time_us: meta.end_time_us,
+        start_time: rfc3339(meta.start_time_us),
+        end_time: rfc3339(meta.end_time_us),
+        timing: meta.timing.clone(),
+    }
+}
+
+fn bus_section(meta: &CaptureMetadata, names: &HashMap<u8, String>) -> Vec<SidecarBus> {
+    let mut buses = meta.buses.clone();
+    buses.extend(names.keys().copied().filter(|b| !meta.buses.contains(b)));
+    buses.sort_unstable();
+    buses
+        .into_iter()
+        .map(|bus| SidecarBus { bus, name: names.get(&bus).cloned() })
+        .collect()
+}
+
+/// Assemble the sidecar for an export of `request.capture_id`.
+pub async fn build_sidecar(request: SidecarRequest) -> Result<ExportSidecar, String> {
+    let meta = capture_store::get_capture_metadata(&request.capture_id)
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = request.capture_id.as_str()))?;
+    let session_id = request.session_id.clone().or_else(|| meta.owning_session_id.clone());
+
+    let (sources, source_names, catalogs) = match &session_id {
+        Some(sid) => (
+            crate::sessions::get_session_profile_ids(sid)
+                .into_iter()
+                .map(|profile_id| SidecarSource { profile_id })
+                .collect(),
+            crate::io::get_session_source_names(sid).await,
+            crate::ws::dispatch::attached_catalog_path(sid)
+                .map(|p| vec![describe_catalog(&p)])
+                .unwrap_or_default(),
+        ),
+        None => (Vec::new(), Vec::new(), Vec::new()),
+    };
+
+    let mut annotations: Vec<SidecarAnnotation> = crate::capture_db::list_annotations(&meta.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(sidecar_annotation)
+        .collect();
+    annotations.extend(request.annotations);
+
+    Ok(ExportSidecar {
+        schema: SIDECAR_SCHEMA,
+        generated_at: Utc::now().to_rfc3339(),
+        format: request.format,
+        capture: capture_section(&meta),
+        session_id,
+        sources,
+        source_names,
+        buses: bus_section(&meta, &request.bus_names),
+        catalogs,
+        annotations,
+        integrity: SidecarIntegrity {
+            export_sha256: request.export_sha256.map(|h| h.to_lowercase()),
+            capture: meta.integrity,
+        },
+    })
+}
+
+/// Hash an exported file and write its sidecar beside it. Used by exports the
+/// backend writes itself.
+pub async fn write_sidecar(file_path: &str, mut request: SidecarRequest) -> Result<(), String> {
+    let content = std::fs::read(file_path)
+        .map_err(|e| format!("Failed to read export for sidecar: {}", e))?;
+    request.export_sha256 = Some(hex::encode(Sha256::digest(&content)));
+    let sidecar = build_sidecar(request).await?;
+    let json = serde_json::to_string_pretty(&sidecar).map_err(|e| e.to_string())?;
+    let path = format!("{}{}", file_path, SIDECAR_SUFFIX);
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write '{}': {}", path, e))
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Build the metadata sidecar for an export; the caller writes it beside the
+/// exported file as `<file>.metadata.json`.
+#[tauri::command]
+pub async fn build_export_sidecar(request: SidecarRequest) -> Result<ExportSidecar, String> {
+    build_sidecar(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(buses: Vec<u8>) -> CaptureMetadata {
+        CaptureMetadata {
+            id: "abc123".into(),
+            kind: CaptureKind::Frames,
+            name: "GVRET 10:30am".into(),
+            count: 42,
+            start_time_us: Some(1_700_000_000_000_000),
+            end_time_us: Some(1_700_000_060_500_000),
+            created_at: 0,
+            is_streaming: false,
+            owning_session_id: None,
+            persistent: false,
+            buses,
+            timing: None,
+            integrity: None,
+            stored_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn capture_section_has_rfc3339_range() {
+        let c = capture_section(&meta(vec![0]));
+        assert_eq!(c.start_time.as_deref(), Some("2023-11-14T22:13:20+00:00"));
+        assert_eq!(c.end_time.as_deref(), Some("2023-11-14T22:14:20.500+00:00"));
+        assert_eq!(rfc3339(None), None);
+    }
+
+    #[test]
+    fn buses_merge_capture_buses_with_caller_names() {
+        let names = HashMap::from([(1, "Powertrain".to_string()), (5, "Body".to_string())]);
+        let buses = bus_section(&meta(vec![0, 1]), &names);
+        let listed: Vec<(u8, Option<&str>)> = buses.iter().map(|b| (b.bus, b.name.as_deref())).collect();
+        assert_eq!(listed, vec![(0, None), (1, Some("Powertrain")), (5, Some("Body"))]);
+    }
+
+    #[test]
+    fn describes_catalog_meta_and_hash() {
+        let path = std::env::temp_dir().join(format!("wiretap-sidecar-{}.toml", std::process::id()));
+        std::fs::write(&path, "[meta]\nname = \"Inverter\"\nversion = 3\n").unwrap();
+        let c = describe_catalog(path.to_str().unwrap());
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(c.name.as_deref(), Some("Inverter"));
+        assert_eq!(c.version.and_then(|v| v.as_integer()), Some(3));
+        assert_eq!(c.sha256.map(|h| h.len()), Some(64));
+
+        let missing = describe_catalog("/nonexistent/catalog.toml");
+        assert!(missing.sha256.is_none() && missing.name.is_none());
+    }
+}
@@ -0,0 +1,382 @@
+// ui/src-tauri/src/frame_link.rs
+//
+// Per-frame linkage tagging. Frames that belong together share a
+// correlation ID in their `FrameLink`, which the capture database stores
+// beside the frame and resolves to a parent rowid on read:
+//
+//   - Transmit echoes: a frame sent through session_transmit is remembered
+//     briefly; when the device echoes it back (direction "tx") the echo is
+//     tagged with the transmit's correlation ID.
+//   - UDS request/response: diagnostic requests on the standard physical and
+//     functional IDs (0x7DF/0x7E0–0x7E7, 29-bit 0x18DA/0x18DB) start a
+//     correlation; frames on the matching response ID(s) join it until the
+//     final (non-responsePending) response or a timeout. DoIP frames
+//     (protocol "doip", `frame_id = source << 16 | target`) are paired the
+//     same way by logical address: a request comes from a tester address and
+//     the response swaps source and target.
+//   - Reassembly (reassembly.rs) tags segments and the synthetic message.
+//
+// Tagging runs in capture_store::append_frames_to_session before
+// reassembly, so a reassembled UDS response joins its request's correlation.
+
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::io::{CanTransmitFrame, FrameLink, FrameMessage, LinkRole};
+
+/// Transmitted frames awaiting their echo, per session.
+const MAX_PENDING_ECHOES: usize = 256;
+
+/// How long a transmit waits for its echo (host clock).
+const ECHO_TIMEOUT_US: u64 = 2_000_000;
+
+/// How long a UDS request stays open for responses (frame clock). Covers
+/// P2* (5 s) for responsePending sequences.
+const UDS_RESPONSE_TIMEOUT_US: u64 = 5_000_000;
+
+/// Correlation IDs are seeded from the wall clock so IDs from different runs
+/// don't collide inside one persistent capture.
+static NEXT_CORRELATION_ID: Lazy<AtomicU64> = Lazy::new(|| {
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_micros() as u64)
+        .unwrap_or(1);
+    AtomicU64::new(seed)
+});
+
+/// Allocate a new correlation ID.
+pub fn next_correlation_id() -> u64 {
+    NEXT_CORRELATION_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+fn now_us() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_micros() as u64)
+        .unwrap_or(0)
+}
+
+fn link(correlation_id: u64, role: LinkRole) -> Option<FrameLink> {
+    Some(FrameLink { correlation_id, role, parent_rowid: None })
+}
+
+// ============================================================================
+// Transmit echoes
+// ============================================================================
+
+struct PendingEcho {
+    frame_id: u32,
+    bus: u8,
+    data: Vec<u8>,
+    correlation_id: u64,
+    sent_at_us: u64,
+}
+
+static PENDING_ECHOES: Lazy<Mutex<HashMap<String, VecDeque<PendingEcho>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Remember a transmitted frame so its echo can be tagged. Returns the
+/// correlation ID assigned to the transmit.
+pub fn record_transmit(session_id: &str, frame: &CanTransmitFrame) -> u64 {
+    let correlation_id = next_correlation_id();
+    if let Ok(mut pending) = PENDING_ECHOES.lock() {
+        let queue = pending.entry(session_id.to_string()).or_default();
+        if queue.len() >= MAX_PENDING_ECHOES {
+            queue.pop_front();
+        }
+        queue.push_back(PendingEcho {
+            frame_id: frame.frame_id,
+            bus: frame.bus,
+            data: frame.data.clone(),
+            correlation_id,
+            sent_at_us: now_us(),
+        });
+    }
+    correlation_id
+}
+
+fn tag_echoes(session_id: &str, frames: &mut [FrameMessage]) {
+    let Ok(mut pending) = PENDING_ECHOES.lock() else {
+        return;
+    };
+    let Some(queue) = pending.get_mut(session_id) else {
+        return;
+    };
+    let cutoff = now_us().saturating_sub(ECHO_TIMEOUT_US);
+    queue.retain(|p| p.sent_at_us >= cutoff);
+
+    for frame in frames.iter_mut() {
+        if frame.link.is_some() || frame.direction.as_deref() != Some("tx") {
+            continue;
+        }
+        let hit = queue
+            .iter()
+            .position(|p| p.frame_id == frame.frame_id && p.bus == frame.bus && p.data == frame.bytes);
+        if let Some(i) = hit {
+            if let Some(p) = queue.remove(i) {
+                frame.link = link(p.correlation_id, LinkRole::Echo);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// UDS request/response correlation
+// ============================================================================
+
+/// Source addresses reserved for off-board test equipment (ISO 15765-4).
+const TESTER_ADDRESSES: std::ops::RangeInclusive<u32> = 0xF0..=0xFD;
+
+/// True if `frame_id` is a diagnostic request ID (physical or functional).
+/// On 29-bit IDs requests and responses share a prefix, so a request is one
+/// sent from a tester address.
+fn is_uds_request_id(frame_id: u32, is_extended: bool) -> bool {
+    if is_extended {
+        let prefix = frame_id & 0x1FFF_0000;
+        (prefix == 0x18DA_0000 || prefix == 0x18DB_0000) && TESTER_ADDRESSES.contains(&(frame_id & 0xFF))
+    } else {
+        frame_id == 0x7DF || (0x7E0..=0x7E7).contains(&frame_id)
+    }
+}
+
+/// True if `response_id` answers a request sent on `request_id`.
+fn is_uds_response_to(request_id: u32, response_id: u32, is_extended: bool) -> bool {
+    if is_extended {
+        if response_id & 0x1FFF_0000 != 0x18DA_0000 {
+            return false;
+        }
+        let req_ta = (request_id >> 8) & 0xFF;
+        let req_sa = request_id & 0xFF;
+        let resp_ta = (response_id >> 8) & 0xFF;
+        let resp_sa = response_id & 0xFF;
+        let functional = request_id & 0x1FFF_0000 == 0x18DB_0000;
+        resp_ta == req_sa && (functional || resp_sa == req_ta)
+    } else if request_id == 0x7DF {
+        (0x7E8..=0x7EF).contains(&response_id)
+    } else {
+        response_id == request_id + 8
+    }
+}
+
+/// True if an ISO-TP frame starts a new message (single or first frame).
+fn starts_message(bytes: &[u8]) -> bool {
+    matches!(bytes.first().map(|b| b >> 4), Some(0x0) | Some(0x1))
+}
+
+/// A single-frame response that ends the exchange: anything except a
+/// negative response with NRC 0x78 (requestCorrectlyReceived-ResponsePending).
+fn is_final_single_frame(bytes: &[u8]) -> bool {
+    if bytes.first().map(|b| b >> 4) != Some(0x0) {
+        return false;
+    }
+    !(bytes.get(1) == Some(&0x7F) && bytes.get(3) == Some(&0x78))
+}
+
+/// Logical addresses of external test equipment (ISO 13400-2).
+const DOIP_TESTER_ADDRESSES: std::ops::RangeInclusive<u32> = 0x0E00..=0x0FFF;
+
+/// Functional (broadcast) logical addresses (ISO 13400-2).
+const DOIP_FUNCTIONAL_ADDRESSES: std::ops::RangeInclusive<u32> = 0xE400..=0xEFFF;
+
+/// True if a DoIP UDS payload ends the exchange (not a responsePending NRC).
+/// DoIP carries whole UDS messages, so there is no ISO-TP PCI byte.
+fn is_final_doip_response(bytes: &[u8]) -> bool {
+    !(bytes.first() == Some(&0x7F) && bytes.get(2) == Some(&0x78))
+}
+
+struct OpenRequest {
+    request_id: u32,
+    is_extended: bool,
+    correlation_id: u64,
+    last_us: u64,
+}
+
+#[derive(Default)]
+struct UdsCorrelator {
+    // (bus, request_id) -> open request
+    open: HashMap<(u8, u32), OpenRequest>,
+    // (bus, source << 16 | target) -> open DoIP request
+    doip_open: HashMap<(u8, u32), OpenRequest>,
+}
+
+impl UdsCorrelator {
+    fn tag(&mut self, frame: &mut FrameMessage) {
+        if frame.link.is_some() {
+            return;
+        }
+        if frame.protocol == "doip" {
+            self.tag_doip(frame);
+            return;
+        }
+        let ts = frame.timestamp_us;
+        self.open.retain(|_, r| ts.saturating_sub(r.last_us) <= UDS_RESPONSE_TIMEOUT_US);
+
+        if is_uds_request_id(frame.frame_id, frame.is_extended) {
+            let key = (frame.bus, frame.frame_id);
+            if starts_message(&frame.bytes) {
+                let correlation_id = next_correlation_id();
+                self.open.insert(
+                    key,
+                    OpenRequest {
+                        request_id: frame.frame_id,
+                        is_extended: frame.is_extended,
+                        correlation_id,
+                        last_us: ts,
+                    },
+                );
+                frame.link = link(correlation_id, LinkRole::Request);
+            } else if let Some(open) = self.open.get_mut(&key) {
+                // Tester's consecutive frames / flow control for the exchange
+                open.last_us = ts;
+                frame.link = link(open.correlation_id, LinkRole::Request);
+            }
+            return;
+        }
+
+        let hit = self.open.iter_mut().find(|(&(bus, _), r)| {
+            bus == frame.bus
+                && r.is_extended == frame.is_extended
+                && is_uds_response_to(r.request_id, frame.frame_id, frame.is_extended)
+        });
+        let Some((&key, open)) = hit else {
+            return;
+        };
+        open.last_us = ts;
+        frame.link = link(open.correlation_id, LinkRole::Response);
+
+        // A multi-frame response stays open until its consecutive frames are
+        // in; functional requests may be answered by several ECUs, so they
+        // stay open until they time out.
+        let functional = open.request_id == 0x7DF || open.request_id & 0x1FFF_0000 == 0x18DB_0000;
+        if !functional && is_final_single_frame(&frame.bytes) {
+            self.open.remove(&key);
+        }
+    }
+
+    fn tag_doip(&mut self, frame: &mut FrameMessage) {
+        let ts = frame.timestamp_us;
+        self.doip_open.retain(|_, r| ts.saturating_sub(r.last_us) <= UDS_RESPONSE_TIMEOUT_US);
+
+        let source = frame.frame_id >> 16;
+        let target = frame.frame_id & 0xFFFF;
+        if DOIP_TESTER_ADDRESSES.contains(&source) {
+            let correlation_id = next_correlation_id();
+            self.doip_open.insert(
+                (frame.bus, frame.frame_id),
+                OpenRequest {
+                    request_id: frame.frame_id,
+                    is_extended: false,
+                    correlation_id,
+                    last_us: ts,
+                },
+            );
+            frame.link = link(correlation_id, LinkRole::Request);
+            return;
+        }
+
+        let hit = self.doip_open.iter_mut().find(|(&(bus, _), r)| {
+            let req_target = r.request_id & 0xFFFF;
+            bus == frame.bus
+                && r.request_id >> 16 == target
+                && (req_target == source || DOIP_FUNCTIONAL_ADDRESSES.contains(&req_target))
+        });
+        let Some((&key, open)) = hit else {
+            return;
+        };
+        open.last_us = ts;
+        frame.link = link(open.correlation_id, LinkRole::Response);
+
+        let functional = DOIP_FUNCTIONAL_ADDRESSES.contains(&(open.request_id & 0xFFFF));
+        if !functional && is_final_doip_response(&frame.bytes) {
+            self.doip_open.remove(&key);
+        }
+    }
+}
+
+static UDS_CORRELATORS: Lazy<Mutex<HashMap<String, UdsCorrelator>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Pipeline hook
+// ============================================================================
+
+/// Tag a batch with echo and UDS linkage. Frames already linked are left as is.
+pub fn tag_frames(session_id: &str, frames: &mut [FrameMessage]) {
+    tag_echoes(session_id, frames);
+    if let Ok(mut correlators) = UDS_CORRELATORS.lock() {
+        let correlator = correlators.entry(session_id.to_string()).or_default();
+        for frame in frames.iter_mut() {
+            correlator.tag(frame);
+        }
+    }
+}
+
+/// Drop a session's tagging state. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut pending) = PENDING_ECHOES.lock() {
+        pending.remove(session_id);
+    }
+    if let Ok(mut correlators) = UDS_CORRELATORS.lock() {
+        correlators.remove(session_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame as frame;
+
+    #[test]
+    fn uds_request_and_response_share_correlation() {
+        let mut c = UdsCorrelator::default();
+        let mut req = frame(0x7E0, 1, &[0x03, 0x22, 0xF1, 0x90, 0, 0, 0, 0]);
+        let mut pending = frame(0x7E8, 2, &[0x03, 0x7F, 0x22, 0x78, 0, 0, 0, 0]);
+        let mut resp = frame(0x7E8, 3, &[0x04, 0x62, 0xF1, 0x90, 0x01, 0, 0, 0]);
+        let mut late = frame(0x7E8, 4, &[0x04, 0x62, 0xF1, 0x90, 0x01, 0, 0, 0]);
+        c.tag(&mut req);
+        c.tag(&mut pending);
+        c.tag(&mut resp);
+        c.tag(&mut late);
+
+        let id = req.link.as_ref().unwrap().correlation_id;
+        assert_eq!(req.link.as_ref().unwrap().role, LinkRole::Request);
+        assert_eq!(pending.link.as_ref().map(|l| l.correlation_id), Some(id));
+        assert_eq!(resp.link.as_ref().map(|l| (l.correlation_id, l.role)), Some((id, LinkRole::Response)));
+        // Exchange closed by the final response
+        assert!(late.link.is_none());
+    }
+
+    #[test]
+    fn doip_request_and_response_share_correlation() {
+        let mut c = UdsCorrelator::default();
+        let doip = |id: u32, ts: u64, bytes: &[u8]| FrameMessage { protocol: "doip".to_string(), ..frame(id, ts, bytes) };
+        let mut req = doip(0x0E00_1010, 1, &[0x22, 0xF1, 0x90]);
+        let mut pending = doip(0x1010_0E00, 2, &[0x7F, 0x22, 0x78]);
+        let mut other = doip(0x1020_0E00, 3, &[0x62, 0xF1, 0x90]);
+        let mut resp = doip(0x1010_0E00, 4, &[0x62, 0xF1, 0x90, 0x57]);
+        let mut late = doip(0x1010_0E00, 5, &[0x62, 0xF1, 0x90, 0x57]);
+        for f in [&mut req, &mut pending, &mut other, &mut resp, &mut late] {
+            c.tag(f);
+        }
+
+        let id = req.link.as_ref().unwrap().correlation_id;
+        assert_eq!(pending.link.as_ref().map(|l| l.correlation_id), Some(id));
+        assert!(other.link.is_none());
+        assert_eq!(resp.link.as_ref().map(|l| (l.correlation_id, l.role)), Some((id, LinkRole::Response)));
+        assert!(late.link.is_none());
+    }
+
+    #[test]
+    fn extended_response_matching() {
+        assert!(is_uds_response_to(0x18DA_10F1, 0x18DA_F110, true));
+        assert!(!is_uds_response_to(0x18DA_10F1, 0x18DA_F120, true));
+        assert!(is_uds_response_to(0x18DB_33F1, 0x18DA_F120, true));
+        assert!(is_uds_request_id(0x18DA_10F1, true));
+        assert!(!is_uds_request_id(0x18DA_F110, true));
+        assert!(is_uds_response_to(0x7DF, 0x7EA, false));
+        assert!(!is_uds_response_to(0x7E0, 0x7E9, false));
+    }
+}
@@ -0,0 +1,270 @@
+// ui/src-tauri/src/frame_priority.rs
+//
+// Priority frame emission — a per-session list of pinned frame IDs whose
+// frames skip the 2 Hz frames-ready cadence. Live sessions normally deliver
+// frames in one coalesced FrameData batch per throttle tick, so a signal the
+// user is watching can lag by up to half a second when the bus is busy.
+//
+// Frames for pinned IDs are picked out in capture_store::append_frames_to_session
+// (after gating and reassembly, alongside the latest-value tap) and pushed on
+// the session's WS channel in small batches, at most once per `interval_ms`.
+// Frames still buffered when the background batch goes out are flushed first.
+// The background batch (ws::dispatch::send_new_frames) then leaves pinned
+// frames out so no frame is delivered twice. Priority frames can therefore
+// arrive ahead of older background frames; consumers already key on
+// (bus, frame_id), and timestamps are unchanged.
+//
+// Changing the list first flushes the background batch under the old list,
+// so frames appended before the change are neither lost nor duplicated.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::io::FrameMessage;
+
+/// Shortest gap between priority batches when the config doesn't set one.
+const DEFAULT_INTERVAL_MS: u64 = 20;
+
+/// Longest accepted gap — beyond the frames-ready cadence there's no gain.
+const MAX_INTERVAL_MS: u64 = 500;
+
+/// Most IDs a session can pin.
+const MAX_PINNED_IDS: usize = 256;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A pinned frame ID, on one bus or on every bus.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PinnedId {
+    pub frame_id: u32,
+    /// Bus to match (None = any bus)
+    #[serde(default)]
+    pub bus: Option<u8>,
+}
+
+impl PinnedId {
+    fn matches(&self, frame: &FrameMessage) -> bool {
+        self.frame_id == frame.frame_id && self.bus.is_none_or(|b| b == frame.bus)
+    }
+}
+
+/// Priority list for a session.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PriorityConfig {
+    pub ids: Vec<PinnedId>,
+    /// Shortest gap between priority batches (ms)
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+}
+
+fn default_interval_ms() -> u64 {
+    DEFAULT_INTERVAL_MS
+}
+
+impl PriorityConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.ids.is_empty() {
+            return Err("Priority list needs at least one frame ID".to_string());
+        }
+        if self.ids.len() > MAX_PINNED_IDS {
+            return Err(format!("At most {} frame IDs can be pinned", MAX_PINNED_IDS));
+        }
+        if self.interval_ms == 0 || self.interval_ms > MAX_INTERVAL_MS {
+            return Err(format!("Priority interval must be 1-{} ms", MAX_INTERVAL_MS));
+        }
+        Ok(())
+    }
+
+    fn is_pinned(&self, frame: &FrameMessage) -> bool {
+        self.ids.iter().any(|id| id.matches(frame))
+    }
+}
+
+/// Priority list and delivery counts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PriorityStats {
+    pub config: PriorityConfig,
+    /// Pinned frames pushed ahead of the background batch
+    pub priority_frames: u64,
+    /// Priority batches pushed
+    pub priority_batches: u64,
+    /// Pinned frames waiting for the next batch
+    pub pending: usize,
+}
+
+struct SessionPriority {
+    config: PriorityConfig,
+    pending: Vec<FrameMessage>,
+    last_flush: Option<Instant>,
+    priority_frames: u64,
+    priority_batches: u64,
+}
+
+impl SessionPriority {
+    fn new(config: PriorityConfig) -> Self {
+        SessionPriority {
+            config,
+            pending: Vec::new(),
+            last_flush: None,
+            priority_frames: 0,
+            priority_batches: 0,
+        }
+    }
+
+    /// Buffer the pinned frames of a batch; returns a batch to push once the
+    /// interval since the last push has elapsed.
+    fn accept(&mut self, frames: &[FrameMessage], now: Instant) -> Option<Vec<FrameMessage>> {
+        self.pending
+            .extend(frames.iter().filter(|f| self.config.is_pinned(f)).cloned());
+        let interval = Duration::from_millis(self.config.interval_ms);
+        let due = self.last_flush.is_none_or(|t| now.duration_since(t) >= interval);
+        if due { self.take(now) } else { None }
+    }
+
+    fn take(&mut self, now: Instant) -> Option<Vec<FrameMessage>> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        self.last_flush = Some(now);
+        self.priority_frames += self.pending.len() as u64;
+        self.priority_batches += 1;
+        Some(std::mem::take(&mut self.pending))
+    }
+}
+
+static FRAME_PRIORITY: Lazy<Mutex<HashMap<String, SessionPriority>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Frame path
+// ============================================================================
+
+/// Called from capture_store::append_frames_to_session. Pushes pinned frames
+/// when a priority batch is due. Sessions without a list cost one lock.
+pub fn tap_frames(session_id: &str, frames: &[FrameMessage]) {
+    let batch = {
+        let Ok(mut sessions) = FRAME_PRIORITY.lock() else { return };
+        let Some(priority) = sessions.get_mut(session_id) else { return };
+        priority.accept(frames, Instant::now())
+    };
+    if let Some(batch) = batch {
+        crate::ws::dispatch::send_priority_frames(session_id, &batch);
+    }
+}
+
+/// Take the pinned frames still waiting for a priority batch. Called by the
+/// background send so they go out before it.
+pub fn take_pending(session_id: &str) -> Option<Vec<FrameMessage>> {
+    let mut sessions = FRAME_PRIORITY.lock().ok()?;
+    sessions.get_mut(session_id)?.take(Instant::now())
+}
+
+/// Drop pinned frames from a background batch (they were pushed already).
+pub fn without_pinned(session_id: &str, frames: Vec<FrameMessage>) -> Vec<FrameMessage> {
+    let Ok(sessions) = FRAME_PRIORITY.lock() else { return frames };
+    match sessions.get(session_id) {
+        Some(priority) => frames.into_iter().filter(|f| !priority.config.is_pinned(f)).collect(),
+        None => frames,
+    }
+}
+
+/// Remove a session's priority list. Called on session teardown.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut sessions) = FRAME_PRIORITY.lock() {
+        sessions.remove(session_id);
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Pin frame IDs on a session (replaces any existing list).
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_frame_priority(session_id: String, config: PriorityConfig) -> Result<(), String> {
+    config.validate()?;
+    // Deliver what's already captured under the old list before switching
+    crate::ws::dispatch::send_new_frames(&session_id);
+    let mut sessions = FRAME_PRIORITY.lock().map_err(|e| e.to_string())?;
+    sessions.insert(session_id, SessionPriority::new(config));
+    Ok(())
+}
+
+/// Unpin every frame ID on a session.
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_frame_priority(session_id: String) {
+    crate::ws::dispatch::send_new_frames(&session_id);
+    clear_session(&session_id);
+}
+
+/// Priority list and delivery counts for a session (None = no list).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_frame_priority(session_id: String) -> Option<PriorityStats> {
+    let sessions = FRAME_PRIORITY.lock().ok()?;
+    let priority = sessions.get(&session_id)?;
+    Some(PriorityStats {
+        config: priority.config.clone(),
+        priority_frames: priority.priority_frames,
+        priority_batches: priority.priority_batches,
+        pending: priority.pending.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(frame_id: u32, bus: u8) -> FrameMessage {
+        FrameMessage { bus, ..test_frame(frame_id, 0, &[]) }
+    }
+
+    fn config(ids: Vec<PinnedId>) -> PriorityConfig {
+        PriorityConfig { ids, interval_ms: 20 }
+    }
+
+    #[test]
+    fn pinned_id_matches_bus_or_any_bus() {
+        let cfg = config(vec![
+            PinnedId { frame_id: 0x100, bus: None },
+            PinnedId { frame_id: 0x200, bus: Some(1) },
+        ]);
+        assert!(cfg.is_pinned(&frame(0x100, 3)));
+        assert!(cfg.is_pinned(&frame(0x200, 1)));
+        assert!(!cfg.is_pinned(&frame(0x200, 0)));
+        assert!(!cfg.is_pinned(&frame(0x300, 1)));
+    }
+
+    #[test]
+    fn batches_pinned_frames_no_faster_than_interval() {
+        let mut p = SessionPriority::new(config(vec![PinnedId { frame_id: 0x100, bus: None }]));
+        let t0 = Instant::now();
+        let batch = p.accept(&[frame(0x100, 0), frame(0x7DF, 0)], t0).unwrap();
+        assert_eq!(batch.len(), 1);
+
+        // Within the interval: buffered
+        assert!(p.accept(&[frame(0x100, 0)], t0 + Duration::from_millis(5)).is_none());
+        assert_eq!(p.pending.len(), 1);
+
+        // Interval elapsed: buffered and new frames go together
+        let batch = p.accept(&[frame(0x100, 0)], t0 + Duration::from_millis(25)).unwrap();
+        assert_eq!(batch.len(), 2);
+        assert_eq!((p.priority_frames, p.priority_batches), (3, 2));
+    }
+
+    #[test]
+    fn rejects_empty_list_and_bad_interval() {
+        assert!(config(vec![]).validate().is_err());
+        let mut cfg = config(vec![PinnedId { frame_id: 1, bus: None }]);
+        assert!(cfg.validate().is_ok());
+        cfg.interval_ms = 0;
+        assert!(cfg.validate().is_err());
+        cfg.interval_ms = MAX_INTERVAL_MS + 1;
+        assert!(cfg.validate().is_err());
+    }
+}
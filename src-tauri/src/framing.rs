@@ -177,7 +177,7 @@ mod desktop {
             .ok_or_else(|| "No byte capture found for session".to_string())?;
 
         let bytes = capture_store::get_capture_bytes(&capture_id)
-            .ok_or_else(|| format!("Capture '{}' not found or is not a byte capture", capture_id))?;
+            .ok_or_else(|| msg!(CAPTURE_NOT_BYTES, capture_id = capture_id))?;
 
         if bytes.is_empty() {
             return Err("No bytes in capture".to_string());
@@ -287,6 +287,11 @@ mod desktop {
                     source_address,
                     incomplete: if *incomplete { Some(true) } else { None },
                     direction: None,
+                    link: None,
+                    clock: None,
+                    is_error: None,
+                    error_class: None,
+                    brs: None,
                 }
             })
             .collect();
@@ -327,6 +332,11 @@ mod desktop {
                     source_address,
                     incomplete: if *incomplete { Some(true) } else { None },
                     direction: None,
+                    link: None,
+                    clock: None,
+                    is_error: None,
+                    error_class: None,
+                    brs: None,
                 }
             })
             .collect();
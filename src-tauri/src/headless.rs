@@ -0,0 +1,266 @@
+// ui/src-tauri/src/headless.rs
+//
+// Headless capture mode, so a Raspberry Pi (or any machine without a display)
+// can log a bus unattended and the result be opened later in the GUI. Runs in
+// place of the app when the first argument is `capture`:
+//
+//   WireTAP capture --profile <id|name> --out <file>
+//                   [--duration <time>] [--frames <n>] [--settings <path>]
+//
+// The profile comes from the app's settings.json (or --settings), and its
+// reader is the same driver a session would run, without the webview or a
+// session around it. Frames go straight to the output file in any export
+// format (see convert.rs), which the GUI imports like any other log. Durations
+// take an s/m/h/ms suffix (bare numbers are seconds). Without --duration or
+// --frames the capture runs until Ctrl-C; either way the file is finished
+// cleanly.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::convert::{self, Sink};
+use crate::io::types::SourceMessage;
+use crate::settings::{AppSettings, IOProfile};
+
+const USAGE: &str = "\
+Usage:
+  WireTAP capture --profile <id|name> --out <file>
+                  [--duration <time>] [--frames <n>] [--settings <path>]
+
+  --duration   stop after this long: 90, 30s, 10m, 2h, 500ms
+  --frames     stop after this many frames
+  --settings   settings.json holding the profile (default: the app's own)
+
+Output formats: csv, log/candump, blf, asc, pcap/pcapng, parquet";
+
+/// Matches `identifier` in tauri.conf.json, which names the app config dir.
+const APP_IDENTIFIER: &str = "com.wiredsquare.wiretap";
+
+#[derive(Debug, PartialEq)]
+struct CaptureArgs {
+    profile: String,
+    out: PathBuf,
+    duration: Option<Duration>,
+    max_frames: Option<u64>,
+    settings: Option<PathBuf>,
+}
+
+/// Parse `90`, `30s`, `10m`, `2h` or `500ms`.
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let n: u64 = number.parse().map_err(|_| format!("invalid duration '{}'", text))?;
+    match unit {
+        "" | "s" => Ok(Duration::from_secs(n)),
+        "ms" => Ok(Duration::from_millis(n)),
+        "m" => Ok(Duration::from_secs(n * 60)),
+        "h" => Ok(Duration::from_secs(n * 3600)),
+        _ => Err(format!("invalid duration '{}'", text)),
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<CaptureArgs, String> {
+    let mut profile = None;
+    let mut out = None;
+    let mut duration = None;
+    let mut max_frames = None;
+    let mut settings = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| iter.next().cloned().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "--profile" => profile = Some(value("--profile")?),
+            "--out" => out = Some(PathBuf::from(value("--out")?)),
+            "--duration" => duration = Some(parse_duration(&value("--duration")?)?),
+            "--frames" => {
+                let n = value("--frames")?;
+                max_frames = Some(n.parse().map_err(|_| format!("invalid frame count '{}'", n))?);
+            }
+            "--settings" => settings = Some(PathBuf::from(value("--settings")?)),
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+
+    let out = out.ok_or("--out is required")?;
+    convert::output_format(&convert::extension(&out))?;
+    Ok(CaptureArgs { profile: profile.ok_or("--profile is required")?, out, duration, max_frames, settings })
+}
+
+fn load_profile(args: &CaptureArgs) -> Result<IOProfile, String> {
+    let path = match &args.settings {
+        Some(path) => path.clone(),
+        None => dirs::config_dir()
+            .ok_or("no config directory on this system; pass --settings")?
+            .join(APP_IDENTIFIER)
+            .join("settings.json"),
+    };
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let settings: AppSettings =
+        serde_json::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    settings
+        .io_profiles
+        .into_iter()
+        .find(|p| p.id == args.profile || p.name == args.profile)
+        .ok_or_else(|| format!("no IO profile '{}' in {}", args.profile, path.display()))
+}
+
+/// Run the capture. Returns the number of frames written.
+async fn capture(args: CaptureArgs) -> Result<u32, String> {
+    let profile = load_profile(&args)?;
+    let format = convert::output_format(&convert::extension(&args.out))?;
+    let out = args.out.to_string_lossy().to_string();
+    eprintln!("capture: {} ({}) -> {}", profile.name, profile.kind, out);
+
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let (tx, mut rx) = mpsc::channel(1024);
+    let reader = tokio::spawn(crate::io::run_headless_source(profile, stop_flag.clone(), tx));
+
+    let deadline = args.duration.map(|d| tokio::time::Instant::now() + d);
+    let until_deadline = async {
+        match deadline {
+            Some(deadline) => tokio::time::sleep_until(deadline).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(until_deadline);
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    // Created on the first frame, once the ASC start time is known
+    let mut sink: Option<Sink> = None;
+    let mut frames = 0u64;
+    let mut error = None;
+    loop {
+        tokio::select! {
+            msg = rx.recv() => match msg {
+                Some(SourceMessage::Frames(_, batch)) => {
+                    let Some(first) = batch.first() else { continue };
+                    if sink.is_none() {
+                        sink = Some(Sink::create(format, &out, first.timestamp_us)?);
+                    }
+                    let sink = sink.as_mut().expect("sink created above");
+                    let room = args.max_frames.map_or(usize::MAX, |max| max.saturating_sub(frames) as usize);
+                    for frame in batch.iter().take(room) {
+                        sink.write_frame(frame)?;
+                        frames += 1;
+                    }
+                    if args.max_frames.is_some_and(|max| frames >= max) {
+                        break;
+                    }
+                }
+                Some(SourceMessage::Connected(_, device_type, address, _)) => {
+                    eprintln!("capture: connected to {} at {}", device_type, address);
+                }
+                Some(SourceMessage::Error(_, e)) => {
+                    error = Some(e);
+                    break;
+                }
+                Some(SourceMessage::Ended(_, reason)) => {
+                    eprintln!("capture: source ended ({})", reason);
+                    break;
+                }
+                Some(_) => {}
+                None => break,
+            },
+            _ = &mut until_deadline => break,
+            _ = &mut ctrl_c => {
+                eprintln!("capture: interrupted");
+                break;
+            }
+        }
+    }
+
+    stop_flag.store(true, Ordering::SeqCst);
+    drop(rx);
+    let _ = tokio::time::timeout(Duration::from_secs(5), reader).await;
+
+    let written = match sink {
+        Some(sink) => sink.finish()?,
+        None => 0,
+    };
+    match error {
+        Some(e) if written == 0 => Err(e),
+        Some(e) => {
+            eprintln!("capture: source error: {}", e);
+            Ok(written)
+        }
+        None => Ok(written),
+    }
+}
+
+/// Run headless capture when the command line asks for it. Returns the
+/// process exit code, or None to start the app normally.
+pub fn run_from_args() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) != Some("capture") {
+        return None;
+    }
+    convert::attach_console();
+
+    let capture_args = match parse_args(&args[1..]) {
+        Ok(capture_args) => capture_args,
+        Err(e) => {
+            eprintln!("capture: {}\n\n{}", e, USAGE);
+            return Some(2);
+        }
+    };
+    let out = capture_args.out.clone();
+    match tauri::async_runtime::block_on(capture(capture_args)) {
+        Ok(written) => {
+            println!("{}: {} frames", out.display(), written);
+            Some(0)
+        }
+        Err(e) => {
+            eprintln!("capture: {}", e);
+            Some(1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_durations_with_units() {
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("10m").unwrap(), Duration::from_secs(600));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert!(parse_duration("10 minutes").is_err());
+        assert!(parse_duration("m").is_err());
+    }
+
+    #[test]
+    fn parses_a_capture_invocation() {
+        let parsed = parse_args(&args(&["--profile", "bench", "--duration", "10m", "--out", "run.csv"])).unwrap();
+        assert_eq!(
+            parsed,
+            CaptureArgs {
+                profile: "bench".to_string(),
+                out: PathBuf::from("run.csv"),
+                duration: Some(Duration::from_secs(600)),
+                max_frames: None,
+                settings: None,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_bad_invocations() {
+        assert!(parse_args(&args(&["--out", "run.csv"])).is_err());
+        assert!(parse_args(&args(&["--profile", "bench"])).is_err());
+        assert!(parse_args(&args(&["--profile", "bench", "--out", "run.xyz"])).is_err());
+        assert!(parse_args(&args(&["--profile", "bench", "--out", "run.csv", "--frames", "many"])).is_err());
+        assert!(parse_args(&args(&["--profile"])).is_err());
+    }
+}
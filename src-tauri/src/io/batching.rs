@@ -0,0 +1,127 @@
+// Copyright 2026 Wired Square Pty Ltd
+//
+// src-tauri/src/io/batching.rs
+//
+// Merge-task batching parameters. The multi-source merge task holds frames
+// until `batch_max_frames` are pending or `batch_max_latency_ms` has passed
+// since the last flush, then writes them to the capture and signals delivery.
+// Small values favour latency (bench work), large ones throughput (long
+// logging runs). Defaults come from settings; a session can override either
+// value with `set_session_batching`, picked up by the running merge task at
+// its next flush.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::settings::AppSettings;
+
+/// Per-session overrides of the batching defaults. None = settings default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchingOptions {
+    #[serde(default)]
+    pub batch_max_frames: Option<u32>,
+    #[serde(default)]
+    pub batch_max_latency_ms: Option<u32>,
+}
+
+/// Resolved batching parameters for one merge task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BatchConfig {
+    pub max_frames: usize,
+    pub max_latency: Duration,
+}
+
+impl BatchConfig {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self::default().with(BatchingOptions {
+            batch_max_frames: Some(settings.batch_max_frames),
+            batch_max_latency_ms: Some(settings.batch_max_latency_ms),
+        })
+    }
+
+    /// Apply overrides. Zero is clamped to 1 so a batch always flushes.
+    fn with(self, options: BatchingOptions) -> Self {
+        Self {
+            max_frames: options.batch_max_frames.map_or(self.max_frames, |n| n.max(1) as usize),
+            max_latency: options
+                .batch_max_latency_ms
+                .map_or(self.max_latency, |ms| Duration::from_millis(u64::from(ms.max(1)))),
+        }
+    }
+
+    /// Whether pending frames should be flushed.
+    pub fn should_flush(&self, pending_frames: usize, since_last_flush: Duration) -> bool {
+        pending_frames >= self.max_frames || since_last_flush >= self.max_latency
+    }
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self { max_frames: 100, max_latency: Duration::from_millis(50) }
+    }
+}
+
+static SESSION_OPTIONS: Lazy<Mutex<HashMap<String, BatchingOptions>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Set a session's batching overrides (both None = back to the defaults).
+pub fn set_session_options(session_id: &str, options: BatchingOptions) {
+    if let Ok(mut map) = SESSION_OPTIONS.lock() {
+        if options == BatchingOptions::default() {
+            map.remove(session_id);
+        } else {
+            map.insert(session_id.to_string(), options);
+        }
+    }
+}
+
+/// A session's batching overrides.
+pub fn session_options(session_id: &str) -> BatchingOptions {
+    SESSION_OPTIONS
+        .lock()
+        .ok()
+        .and_then(|map| map.get(session_id).copied())
+        .unwrap_or_default()
+}
+
+/// The batching parameters for a session: `defaults` with its overrides applied.
+pub fn session_config(session_id: &str, defaults: BatchConfig) -> BatchConfig {
+    defaults.with(session_options(session_id))
+}
+
+/// Forget a session's overrides. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut map) = SESSION_OPTIONS.lock() {
+        map.remove(session_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_replace_only_the_values_they_set() {
+        let defaults = BatchConfig { max_frames: 500, max_latency: Duration::from_millis(200) };
+        let config = defaults.with(BatchingOptions { batch_max_frames: Some(10), batch_max_latency_ms: None });
+        assert_eq!(config, BatchConfig { max_frames: 10, max_latency: Duration::from_millis(200) });
+    }
+
+    #[test]
+    fn zero_values_still_flush() {
+        let config = BatchConfig::default()
+            .with(BatchingOptions { batch_max_frames: Some(0), batch_max_latency_ms: Some(0) });
+        assert!(config.should_flush(1, Duration::ZERO));
+        assert!(config.should_flush(0, Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn flushes_on_size_or_latency() {
+        let config = BatchConfig::default();
+        assert!(!config.should_flush(99, Duration::from_millis(49)));
+        assert!(config.should_flush(100, Duration::ZERO));
+        assert!(config.should_flush(1, Duration::from_millis(50)));
+    }
+}
@@ -9,20 +9,20 @@ use tokio::sync::mpsc;
 
 use std::collections::HashMap;
 use std::sync::Mutex;
+use super::metrics::{bus_timings, MetricsWindow};
+use super::reconnect::run_with_reconnect;
+use super::silence::{BusChange, SilenceWatchdog};
 use super::spawner::run_source_reader;
 use super::types::{ControlChannels, SourceConfig, TransmitChannels};
 use super::{MergeCommand, VirtualBusCommand, VirtualBusControls, VirtualCmdTx};
 use crate::settings;
 use crate::capture_store::{self, TimestampedByte};
+use crate::io::batching::{self, BatchConfig};
 use crate::io::types::SourceMessage;
-use crate::io::{emit_device_connected, emit_session_error, emit_stream_ended, signal_bytes_ready, signal_frames_ready, FrameMessage, SignalThrottle};
+use crate::io::{emit_device_connected, emit_device_disconnected, DeviceDisconnectedPayload, emit_session_error, emit_session_metrics, emit_stream_ended, signal_bytes_ready, signal_frames_ready, FrameMessage, SignalThrottle};
 
-/// Minimum pending frames before emission.
-const FRAME_BATCH_THRESHOLD: usize = 100;
 /// Minimum pending bytes before emission.
 const BYTE_BATCH_THRESHOLD: usize = 256;
-/// Maximum time (ms) between forced emissions.
-const MERGE_EMIT_INTERVAL_MS: u64 = 50;
 /// Interval (s) between per-bus frame count log messages.
 const BUS_LOG_INTERVAL_SECS: u64 = 5;
 
@@ -60,6 +60,12 @@ pub(super) async fn run_merge_task(
     let mut source_stop_flags: HashMap<String, Arc<AtomicBool>> = HashMap::new();
     // Per-source pause flags for pause/resume polling
     let mut source_pause_flags: HashMap<String, Arc<AtomicBool>> = HashMap::new();
+    // Profile ID by source index, for per-source dedup counts
+    let mut source_profiles: HashMap<usize, String> = HashMap::new();
+    // Throughput and bus load, emitted as `session-metrics`
+    let mut metrics = MetricsWindow::new();
+    // Enabled buses that stop carrying frames, emitted as `bus-silent` / `bus-active`
+    let mut silence = SilenceWatchdog::new(settings.bus_silence_timeout_ms);
     for (index, source_config) in sources.iter().enumerate() {
         let profile = match settings.io_profiles.iter().find(|p| p.id == source_config.profile_id) {
             Some(p) => p.clone(),
@@ -76,6 +82,9 @@ pub(super) async fn run_merge_task(
         source_stop_flags.insert(source_config.profile_id.clone(), source_stop.clone());
         let source_pause = Arc::new(AtomicBool::new(false));
         source_pause_flags.insert(source_config.profile_id.clone(), source_pause.clone());
+        source_profiles.insert(index, source_config.profile_id.clone());
+        metrics.set_timings(bus_timings(source_config, &profile));
+        silence.watch_source(index, enabled_output_buses(source_config), std::time::Instant::now());
 
         let handle = spawn_source(
             index,
@@ -83,7 +92,6 @@ pub(super) async fn run_merge_task(
             &profile,
             source_stop,
             source_pause,
-            &app,
             &session_id,
             &stop_flag,
             &tx,
@@ -105,8 +113,12 @@ pub(super) async fn run_merge_task(
     let mut frames_per_bus: std::collections::HashMap<u8, usize> = std::collections::HashMap::new();
     let mut last_bus_log = std::time::Instant::now();
 
+    // Batch size and flush interval: settings defaults, overridable per
+    // session (set_session_batching) and re-read after each flush
+    let batch_defaults = BatchConfig::from_settings(&settings);
+    let mut batch = batching::session_config(&session_id, batch_defaults);
+
     // Main merge loop — uses select! to handle both source messages and commands
-    let emit_interval = std::time::Duration::from_millis(MERGE_EMIT_INTERVAL_MS);
     loop {
         if stop_flag.load(Ordering::SeqCst) {
             break;
@@ -119,10 +131,25 @@ pub(super) async fn run_merge_task(
         tokio::select! {
             msg = rx.recv() => {
                 match msg {
-                    Some(SourceMessage::Frames(_source_idx, frames)) => {
+                    Some(SourceMessage::Frames(source_idx, mut frames)) => {
+                        // Shift the source's clock onto the session's common timeline
+                        if let Some(profile_id) = source_profiles.get(&source_idx) {
+                            crate::source_clock::correct_frames(&session_id, source_idx, profile_id, &mut frames);
+                        }
+                        // Redundant adapters on one bus: keep the first copy of each frame
+                        let frames = match source_profiles.get(&source_idx) {
+                            Some(profile_id) => crate::source_dedup::filter_frames(&session_id, source_idx, profile_id, frames),
+                            None => frames,
+                        };
                         for frame in &frames {
                             *frames_per_bus.entry(frame.bus).or_insert(0) += 1;
                         }
+                        metrics.record(&frames);
+                        let recovered = silence.record(&frames, std::time::Instant::now());
+                        emit_bus_changes(&app, &session_id, &recovered);
+                        // Reactive transmit rules fire here, ahead of emit batching
+                        crate::reactive_transmit::react(&session_id, &frames);
+                        crate::io::netbridge::publish(&session_id, &frames);
                         pending_frames.extend(frames);
                     }
                     Some(SourceMessage::Bytes(_source_idx, raw_entries)) => {
@@ -136,6 +163,9 @@ pub(super) async fn run_merge_task(
                     }
                     Some(SourceMessage::Ended(source_idx, reason)) => {
                         tlog!("[IOBroker] Source {} ended: {}", source_idx, reason);
+                        crate::source_dedup::source_ended(&session_id, source_idx);
+                        crate::source_clock::source_ended(&session_id, source_idx);
+                        silence.remove_source(source_idx);
                         if let Ok(mut channels) = transmit_channels.lock() {
                             channels.remove(&source_idx);
                         }
@@ -143,10 +173,14 @@ pub(super) async fn run_merge_task(
                     }
                     Some(SourceMessage::Error(source_idx, error)) => {
                         tlog!("[IOBroker] Source {} error: {}", source_idx, error);
+                        crate::source_dedup::source_ended(&session_id, source_idx);
+                        crate::source_clock::source_ended(&session_id, source_idx);
+                        silence.remove_source(source_idx);
                         if let Ok(mut channels) = transmit_channels.lock() {
                             channels.remove(&source_idx);
                         }
                         emit_session_error(&session_id, error);
+                        metrics.record_source_error();
                         active_sources = active_sources.saturating_sub(1);
                     }
                     Some(SourceMessage::TransmitReady(source_idx, tx_sender)) => {
@@ -165,6 +199,20 @@ pub(super) async fn run_merge_task(
                         tlog!("[IOBroker] Source {} connected: {} at {}", source_idx, device_type, address);
                         emit_device_connected(&session_id, &device_type, &address, bus_number);
                     }
+                    Some(SourceMessage::Disconnected(source_idx, reason, attempt, retry_in_ms)) => {
+                        // Still an active source: its reconnect policy restarts it
+                        if let Ok(mut channels) = transmit_channels.lock() {
+                            channels.remove(&source_idx);
+                        }
+                        metrics.record_source_error();
+                        emit_device_disconnected(&DeviceDisconnectedPayload {
+                            session_id: session_id.clone(),
+                            profile_id: source_profiles.get(&source_idx).cloned().unwrap_or_default(),
+                            reason,
+                            attempt,
+                            retry_in_ms,
+                        });
+                    }
                     None => {
                         // Channel closed
                         break;
@@ -187,13 +235,15 @@ pub(super) async fn run_merge_task(
                         source_stop_flags.insert(source_config.profile_id.clone(), source_stop.clone());
                         let source_pause = Arc::new(AtomicBool::new(false));
                         source_pause_flags.insert(source_config.profile_id.clone(), source_pause.clone());
+                        source_profiles.insert(idx, source_config.profile_id.clone());
+                        metrics.set_timings(bus_timings(&source_config, &profile));
+                        silence.watch_source(idx, enabled_output_buses(&source_config), std::time::Instant::now());
                         let handle = spawn_source(
                             idx,
                             &source_config,
                             &profile,
                             source_stop,
                             source_pause,
-                            &app,
                             &session_id,
                             &stop_flag,
                             &tx,
@@ -231,7 +281,7 @@ pub(super) async fn run_merge_task(
                     }
                 }
             }
-            _ = tokio::time::sleep(emit_interval) => {
+            _ = tokio::time::sleep(batch.max_latency) => {
                 // Periodic wakeup for batch emission
             }
         }
@@ -251,11 +301,20 @@ pub(super) async fn run_merge_task(
             last_bus_log = std::time::Instant::now();
         }
 
+        let silenced = silence.check(std::time::Instant::now());
+        emit_bus_changes(&app, &session_id, &silenced);
+
+        if metrics.is_due() {
+            let mut payload = metrics.take(&session_id);
+            payload.rate_limited = crate::io::downsample::take_dropped(&session_id);
+            (payload.coalesced, payload.dropped) = crate::ws::backpressure::take_counts(&session_id);
+            emit_session_metrics(&payload);
+        }
+
         // Emit data if we have any and either:
-        // - We have a decent batch (>= 100 items)
-        // - It's been more than 50ms since last emit
-        let should_emit = last_emit.elapsed().as_millis() >= MERGE_EMIT_INTERVAL_MS as u128
-            || pending_frames.len() >= FRAME_BATCH_THRESHOLD
+        // - We have a full batch (batch_max_frames frames, or enough bytes)
+        // - It's been batch_max_latency_ms since last emit
+        let should_emit = batch.should_flush(pending_frames.len(), last_emit.elapsed())
             || pending_bytes.len() >= BYTE_BATCH_THRESHOLD;
 
         if should_emit {
@@ -278,6 +337,7 @@ pub(super) async fn run_merge_task(
             }
 
             last_emit = std::time::Instant::now();
+            batch = batching::session_config(&session_id, batch_defaults);
         }
     }
 
@@ -311,6 +371,24 @@ pub(super) async fn run_merge_task(
     emit_stream_ended(&session_id, reason, "IOBroker");
 }
 
+/// Output buses a source's enabled mappings feed.
+fn enabled_output_buses(source_config: &SourceConfig) -> Vec<u8> {
+    source_config
+        .bus_mappings
+        .iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.output_bus)
+        .collect()
+}
+
+fn emit_bus_changes(app: &AppHandle, session_id: &str, changes: &[BusChange]) {
+    for change in changes {
+        let (event, payload) = change.event(session_id);
+        tlog!("[IOBroker] Session '{}' bus {}: {} ({} ms)", session_id, payload.bus, event, payload.silent_ms);
+        crate::io::emit_to_session(app, session_id, event, payload);
+    }
+}
+
 /// Spawn a single source reader task. Creates a virtual command channel for virtual sources.
 #[allow(clippy::too_many_arguments)]
 fn spawn_source(
@@ -319,14 +397,12 @@ fn spawn_source(
     profile: &crate::settings::IOProfile,
     source_stop: Arc<AtomicBool>,
     source_pause: Arc<AtomicBool>,
-    app: &AppHandle,
     session_id: &str,
     stop_flag: &Arc<AtomicBool>,
     tx: &mpsc::Sender<SourceMessage>,
     virtual_bus_controls: &VirtualBusControls,
     virtual_cmd_txs: &Arc<Mutex<HashMap<usize, VirtualCmdTx>>>,
 ) -> tokio::task::JoinHandle<()> {
-    let app_clone = app.clone();
     let session_id_clone = session_id.to_string();
     let stop_flag_clone = stop_flag.clone();
     let source_stop_clone = source_stop;
@@ -349,7 +425,11 @@ fn spawn_source(
     let modbus_role = source_config.modbus_role.clone();
     let max_register_errors = source_config.max_register_errors;
     let virtual_bus_controls_clone = virtual_bus_controls.clone();
-    let profile = profile.clone();
+    let mut profile = profile.clone();
+    if let Some(listen_only) = source_config.listen_only {
+        crate::io::listen_only::apply(&mut profile, listen_only);
+    }
+    let reconnect = source_config.reconnect.clone();
 
     // Create virtual command channel for virtual sources
     let virtual_cmd_rx = if profile.kind == "virtual" {
@@ -379,34 +459,39 @@ fn spawn_source(
             }
         });
 
-        run_source_reader(
-            app_clone,
-            session_id_clone,
-            index,
-            profile,
-            bus_mappings,
-            display_name,
-            framing_encoding,
-            delimiter,
-            max_frame_length,
-            min_frame_length,
-            emit_raw_bytes,
-            frame_id_start_byte,
-            frame_id_bytes,
-            frame_id_big_endian,
-            source_address_start_byte,
-            source_address_bytes,
-            source_address_big_endian,
-            modbus_polls,
-            modbus_role,
-            max_register_errors,
-            combined_stop,
-            source_pause_clone,
-            tx_clone,
-            virtual_bus_controls_clone,
-            virtual_cmd_rx,
-        )
-        .await;
+        let mut virtual_cmd_rx = virtual_cmd_rx;
+        let reader = |tx: mpsc::Sender<SourceMessage>| {
+            run_source_reader(
+                session_id_clone.clone(),
+                index,
+                profile.clone(),
+                bus_mappings.clone(),
+                display_name.clone(),
+                framing_encoding.clone(),
+                delimiter.clone(),
+                max_frame_length,
+                min_frame_length,
+                emit_raw_bytes,
+                frame_id_start_byte,
+                frame_id_bytes,
+                frame_id_big_endian,
+                source_address_start_byte,
+                source_address_bytes,
+                source_address_big_endian,
+                modbus_polls.clone(),
+                modbus_role.clone(),
+                max_register_errors,
+                combined_stop.clone(),
+                source_pause_clone.clone(),
+                tx,
+                virtual_bus_controls_clone.clone(),
+                virtual_cmd_rx.take(),
+            )
+        };
+        match reconnect {
+            Some(policy) => run_with_reconnect(index, policy, combined_stop.clone(), tx_clone, reader).await,
+            None => reader(tx_clone).await,
+        }
 
         monitor.abort();
     })
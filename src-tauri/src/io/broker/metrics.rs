@@ -0,0 +1,312 @@
+// io/broker/metrics.rs
+//
+// Live bus health for multi-source sessions. The merge task counts every
+// frame it forwards into a MetricsWindow and, once per METRICS_INTERVAL_MS,
+// turns the window into a `session-metrics` event: frames/s, bytes/s and an
+// estimated bus load per output bus, plus error counts. CAN error frames
+// count as errors on their bus rather than as traffic, and are also tallied
+// by class for the whole session.
+//
+// Bus load is the share of the window the bus spent transmitting, from each
+// frame's length at the profile's nominal (and, for CAN FD with bit-rate
+// switching, data) bitrate. Stuff bits aren't known after the fact and are
+// left out, so the figure slightly understates a busy bus.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::types::SourceConfig;
+use crate::io::{CanErrorClass, FrameMessage};
+use crate::settings::IOProfile;
+
+/// How often a `session-metrics` event is emitted.
+pub(super) const METRICS_INTERVAL_MS: u64 = 1000;
+
+/// Classical CAN frame bits outside the data field, including the
+/// 3-bit interframe space: 47 for 11-bit IDs, 67 for 29-bit IDs.
+const CAN_OVERHEAD_BITS: u32 = 47;
+const CAN_EXT_OVERHEAD_BITS: u32 = 67;
+
+/// CAN FD bits sent at the nominal rate (SOF to BRS, then CRC delimiter to
+/// the end of the interframe space), for 11- and 29-bit IDs.
+const FD_ARBITRATION_BITS: u32 = 28;
+const FD_EXT_ARBITRATION_BITS: u32 = 47;
+
+/// CAN FD bits sent at the data rate besides the payload: ESI, DLC and the
+/// stuff count plus CRC (17-bit CRC up to 16 bytes, 21-bit beyond).
+const FD_DATA_OVERHEAD_BITS: u32 = 5;
+const FD_CRC17_BITS: u32 = 22;
+const FD_CRC21_BITS: u32 = 26;
+
+/// Nominal and data bitrate of an output bus.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct BusTiming {
+    pub bitrate: u32,
+    /// Data-phase bitrate for CAN FD frames (None = same as nominal)
+    pub data_bitrate: Option<u32>,
+}
+
+fn connection_u32(profile: &IOProfile, key: &str) -> Option<u32> {
+    profile
+        .connection
+        .get(key)
+        .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .and_then(|v| u32::try_from(v).ok())
+        .filter(|&v| v > 0)
+}
+
+/// Timing of each enabled output bus of a source, from its profile's
+/// `bitrate` / `data_bitrate`. Profiles without a bitrate (e.g. SocketCAN,
+/// whose bitrate is set outside the app) contribute nothing, and their
+/// buses report no load.
+pub(super) fn bus_timings(source: &SourceConfig, profile: &IOProfile) -> Vec<(u8, BusTiming)> {
+    let Some(bitrate) = connection_u32(profile, "bitrate") else {
+        return Vec::new();
+    };
+    let timing = BusTiming { bitrate, data_bitrate: connection_u32(profile, "data_bitrate") };
+    source
+        .bus_mappings
+        .iter()
+        .filter(|m| m.enabled)
+        .map(|m| (m.output_bus, timing))
+        .collect()
+}
+
+/// Microseconds `frame` occupied its bus (stuff bits excluded).
+fn frame_time_us(frame: &FrameMessage, timing: &BusTiming) -> f64 {
+    let payload = frame.bytes.len() as u32;
+    let nominal = timing.bitrate as f64;
+    if frame.is_fd {
+        let arbitration = if frame.is_extended { FD_EXT_ARBITRATION_BITS } else { FD_ARBITRATION_BITS };
+        let crc = if payload > 16 { FD_CRC21_BITS } else { FD_CRC17_BITS };
+        let data_bits = FD_DATA_OVERHEAD_BITS + crc + 8 * payload;
+        let data_rate = timing.data_bitrate.unwrap_or(timing.bitrate) as f64;
+        (arbitration as f64 / nominal + data_bits as f64 / data_rate) * 1_000_000.0
+    } else {
+        let overhead = if frame.is_extended { CAN_EXT_OVERHEAD_BITS } else { CAN_OVERHEAD_BITS };
+        (overhead + 8 * payload) as f64 / nominal * 1_000_000.0
+    }
+}
+
+#[derive(Default)]
+struct BusCounters {
+    frames: u64,
+    bytes: u64,
+    busy_us: f64,
+    /// Frames flagged incomplete (truncated or failed reassembly) and CAN
+    /// error frames
+    errors: u64,
+}
+
+/// CAN error frames by class since the session started.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct CanErrorCounts {
+    pub bus_off: u64,
+    pub ack: u64,
+    pub stuff: u64,
+    /// Every other class (form, bit, CRC, controller state, ...)
+    pub other: u64,
+}
+
+impl CanErrorCounts {
+    fn count(&mut self, class: Option<CanErrorClass>) {
+        match class {
+            Some(CanErrorClass::BusOff) => self.bus_off += 1,
+            Some(CanErrorClass::Ack) => self.ack += 1,
+            Some(CanErrorClass::Stuff) => self.stuff += 1,
+            _ => self.other += 1,
+        }
+    }
+}
+
+/// Live metrics for one output bus over the last interval.
+#[derive(Clone, Debug, Serialize)]
+pub struct BusMetrics {
+    pub bus: u8,
+    pub frames_per_sec: f64,
+    pub bytes_per_sec: f64,
+    /// Estimated share of the interval the bus was busy, 0-100 (None when
+    /// the bus bitrate isn't known)
+    pub load_percent: Option<f64>,
+    pub errors: u64,
+}
+
+/// Payload of the `session-metrics` event.
+#[derive(Clone, Debug, Serialize)]
+pub struct SessionMetricsPayload {
+    pub session_id: String,
+    /// Length of the window the rates cover
+    pub interval_ms: u64,
+    pub frames_per_sec: f64,
+    pub bytes_per_sec: f64,
+    pub buses: Vec<BusMetrics>,
+    /// Source errors, incomplete frames and CAN error frames in the window
+    pub errors: u64,
+    /// CAN error frames by class, cumulative for the session
+    pub can_errors: CanErrorCounts,
+    /// Frames left out of delivery by listener rate limits in the window
+    pub rate_limited: u64,
+    /// Delivery sends held back while the frontend was behind, their frames
+    /// merged into a later batch
+    pub coalesced: u64,
+    /// Frames skipped for delivery because the held-back backlog overflowed
+    pub dropped: u64,
+}
+
+/// Counters for the current metrics interval.
+pub(super) struct MetricsWindow {
+    started: Instant,
+    timings: HashMap<u8, BusTiming>,
+    buses: HashMap<u8, BusCounters>,
+    source_errors: u64,
+    /// Not reset between windows
+    can_errors: CanErrorCounts,
+}
+
+impl MetricsWindow {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            timings: HashMap::new(),
+            buses: HashMap::new(),
+            source_errors: 0,
+            can_errors: CanErrorCounts::default(),
+        }
+    }
+
+    pub fn set_timings(&mut self, timings: impl IntoIterator<Item = (u8, BusTiming)>) {
+        self.timings.extend(timings);
+    }
+
+    pub fn record(&mut self, frames: &[FrameMessage]) {
+        for frame in frames {
+            let counters = self.buses.entry(frame.bus).or_default();
+            if frame.is_error == Some(true) {
+                counters.errors += 1;
+                self.can_errors.count(frame.error_class);
+                continue;
+            }
+            counters.frames += 1;
+            counters.bytes += frame.bytes.len() as u64;
+            if frame.incomplete == Some(true) {
+                counters.errors += 1;
+            }
+            if let Some(timing) = self.timings.get(&frame.bus) {
+                counters.busy_us += frame_time_us(frame, timing);
+            }
+        }
+    }
+
+    pub fn record_source_error(&mut self) {
+        self.source_errors += 1;
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.started.elapsed() >= Duration::from_millis(METRICS_INTERVAL_MS)
+    }
+
+    /// Summarise the window and start a new one. Buses with timings are
+    /// reported even when idle, so a dashboard sees them drop to zero.
+    pub fn take(&mut self, session_id: &str) -> SessionMetricsPayload {
+        let elapsed = self.started.elapsed();
+        self.started = Instant::now();
+        let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+        let window_us = secs * 1_000_000.0;
+
+        for bus in self.timings.keys() {
+            self.buses.entry(*bus).or_default();
+        }
+        let mut buses: Vec<BusMetrics> = self
+            .buses
+            .drain()
+            .map(|(bus, c)| BusMetrics {
+                bus,
+                frames_per_sec: c.frames as f64 / secs,
+                bytes_per_sec: c.bytes as f64 / secs,
+                load_percent: self.timings.contains_key(&bus).then(|| (c.busy_us / window_us * 100.0).min(100.0)),
+                errors: c.errors,
+            })
+            .collect();
+        buses.sort_by_key(|b| b.bus);
+
+        let errors = std::mem::take(&mut self.source_errors) + buses.iter().map(|b| b.errors).sum::<u64>();
+        SessionMetricsPayload {
+            session_id: session_id.to_string(),
+            interval_ms: elapsed.as_millis() as u64,
+            frames_per_sec: buses.iter().map(|b| b.frames_per_sec).sum(),
+            bytes_per_sec: buses.iter().map(|b| b.bytes_per_sec).sum(),
+            buses,
+            errors,
+            can_errors: self.can_errors,
+            rate_limited: 0,
+            coalesced: 0,
+            dropped: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn can(bus: u8, len: usize) -> FrameMessage {
+        FrameMessage { bus, ..test_frame(0x123, 0, &vec![0; len]) }
+    }
+
+    #[test]
+    fn classical_frame_time_follows_bitrate() {
+        let timing = BusTiming { bitrate: 500_000, data_bitrate: None };
+        // 47 + 64 bits at 500 kbit/s
+        assert!((frame_time_us(&can(0, 8), &timing) - 222.0).abs() < 1e-9);
+        let ext = FrameMessage { is_extended: true, ..can(0, 8) };
+        assert!((frame_time_us(&ext, &timing) - 262.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fd_data_phase_runs_at_the_data_bitrate() {
+        let timing = BusTiming { bitrate: 500_000, data_bitrate: Some(2_000_000) };
+        let fd = FrameMessage { is_fd: true, ..can(0, 64) };
+        // 28 bits at 500k = 56 us; 5 + 26 + 512 bits at 2M = 271.5 us
+        assert!((frame_time_us(&fd, &timing) - 327.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn window_reports_idle_buses_and_errors() {
+        let mut window = MetricsWindow::new();
+        window.set_timings([(0, BusTiming { bitrate: 500_000, data_bitrate: None }), (1, BusTiming { bitrate: 250_000, data_bitrate: None })]);
+        window.record(&[can(0, 8), FrameMessage { incomplete: Some(true), ..can(0, 2) }, can(7, 8)]);
+        window.record_source_error();
+
+        let metrics = window.take("s1");
+        assert_eq!(metrics.buses.iter().map(|b| b.bus).collect::<Vec<_>>(), vec![0, 1, 7]);
+        assert!(metrics.buses[0].load_percent.unwrap() > 0.0);
+        assert_eq!(metrics.buses[1].load_percent, Some(0.0));
+        assert_eq!(metrics.buses[2].load_percent, None);
+        assert_eq!(metrics.errors, 2);
+
+        // The next window starts empty
+        assert_eq!(window.take("s1").errors, 0);
+    }
+
+    #[test]
+    fn error_frames_are_counted_by_class_not_as_traffic() {
+        let mut window = MetricsWindow::new();
+        let bus_off = crate::io::can_error::error_frame(0x2000_0040, &[0; 8], 0, 0);
+        let ack = crate::io::can_error::error_frame(0x2000_0020, &[0; 8], 0, 0);
+        window.record(&[can(0, 8), bus_off, ack.clone()]);
+        assert_eq!((window.buses[&0].frames, window.buses[&0].bytes), (1, 8));
+
+        let metrics = window.take("s1");
+        assert_eq!(metrics.buses[0].errors, 2);
+        assert_eq!(metrics.can_errors, CanErrorCounts { bus_off: 1, ack: 1, stuff: 0, other: 0 });
+
+        // Class counts carry over into later windows
+        window.record(&[ack]);
+        let next = window.take("s1");
+        assert_eq!(next.can_errors.ack, 2);
+        assert_eq!(next.errors, 1);
+    }
+}
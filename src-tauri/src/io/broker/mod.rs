@@ -4,6 +4,9 @@
 // Used for multi-bus capture where frames from diverse sources are merged.
 
 mod merge;
+mod metrics;
+mod reconnect;
+mod silence;
 mod spawner;
 mod types;
 
@@ -19,11 +22,16 @@ const SOURCE_CHANNEL_CAPACITY: usize = 1024;
 
 use super::framelink::{encode_framelink_can_tx, encode_framelink_serial_tx};
 use super::gvret::{encode_gvret_frame, validate_gvret_frame, BusMapping};
+use super::doip::DiagnosticRequest;
+use super::lin::encode_transmit_frame as encode_lin_frame;
+use super::modbus_tcp::ModbusWrite;
 #[cfg(not(target_os = "ios"))]
 use super::slcan::encode_transmit_frame as encode_slcan_frame;
+#[cfg(not(target_os = "ios"))]
+use super::custom_serial::encode_transmit_frame as encode_custom_serial_frame;
 #[cfg(target_os = "linux")]
 use super::socketcan::{encode_frame as encode_socketcan_frame, EncodedFrame};
-use super::traits::{get_traits_for_profile_kind, validate_session_traits};
+use super::traits::{get_traits_for_profile_kind, protocol_streams, validate_session_traits};
 use super::types::{SetFramingRequest, SourceMessage, TransmitRequest};
 use super::{
     CanTransmitFrame, IOCapabilities, IOSource, IOState, InterfaceTraits, SessionDataStreams,
@@ -31,10 +39,20 @@ use super::{
 };
 use crate::capture_store::{self, CaptureKind};
 
-#[cfg(any(target_os = "windows", target_os = "macos"))]
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
 use super::gs_usb::encode_frame as encode_gs_usb_frame;
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use super::pcan::encode_frame as encode_pcan_frame;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use super::kvaser::encode_frame as encode_kvaser_frame;
+#[cfg(target_os = "windows")]
+use super::vector_xl::encode_frame as encode_vector_xl_frame;
+#[cfg(target_os = "windows")]
+use super::j2534::encode_frame as encode_j2534_frame;
 
 use merge::run_merge_task;
+pub use metrics::SessionMetricsPayload;
+pub use reconnect::ReconnectPolicy;
 pub use types::{ModbusRole, SourceConfig};
 use types::{ControlChannels, TransmitChannels, TransmitRoute};
 
@@ -55,6 +73,44 @@ pub struct VirtualBusControl {
 /// Shared map of bus -> control, populated by the virtual source spawner
 pub type VirtualBusControls = Arc<Mutex<HashMap<u8, VirtualBusControl>>>;
 
+/// Run one profile's reader outside any session (headless capture). Its
+/// messages arrive on `tx` exactly as they would at the merge task, with all
+/// buses passed through unmapped.
+pub(crate) async fn run_headless_source(
+    profile: crate::settings::IOProfile,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let display_name = profile.name.clone();
+    spawner::run_source_reader(
+        "headless".to_string(),
+        0,
+        profile,
+        Vec::new(),
+        display_name,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        stop_flag,
+        Arc::new(AtomicBool::new(false)),
+        tx,
+        Arc::new(Mutex::new(HashMap::new())),
+        None,
+    )
+    .await;
+}
+
 // ============================================================================
 // Command Channels (for hot add/remove of sources and virtual buses)
 // ============================================================================
@@ -241,9 +297,13 @@ impl IOBroker {
 
         let (tx, rx) = mpsc::channel(SOURCE_CHANNEL_CAPACITY);
 
-        // Build transmit routing table: output_bus -> (source_idx, device_bus, kind)
+        // Build transmit routing table: output_bus -> (source_idx, device_bus, kind).
+        // Listen-only sources never transmit, so they get no routes.
         let mut transmit_routes = HashMap::new();
         for (source_idx, source) in sources.iter().enumerate() {
+            if source.listen_only == Some(true) {
+                continue;
+            }
             for mapping in &source.bus_mappings {
                 if mapping.enabled {
                     transmit_routes.insert(
@@ -318,7 +378,7 @@ impl IOBroker {
         let has_can_transmit_routes = self.transmit_routes.values().any(|route| {
             matches!(
                 route.profile_kind.as_str(),
-                "gvret_tcp" | "gvret_usb" | "slcan" | "gs_usb" | "socketcan" | "virtual" | "framelink"
+                "gvret_tcp" | "gvret_usb" | "slcan" | "custom_serial" | "gs_usb" | "pcan" | "kvaser" | "vector_xl" | "j2534" | "socketcan" | "virtual" | "framelink"
             )
         });
 
@@ -340,7 +400,7 @@ impl IOBroker {
         // Emits frames if any source is non-serial, or if any serial source has
         // framing — honouring a live `set_framing` override over the static config.
         let overrides = self.framing_overrides.lock().ok();
-        let rx_frames = self.sources.iter().enumerate().any(|(idx, s)| {
+        let emits_frames = |idx: usize, s: &SourceConfig| {
             let framing = overrides
                 .as_ref()
                 .and_then(|o| o.get(&idx))
@@ -348,7 +408,27 @@ impl IOBroker {
                 .or(s.framing_encoding.as_deref())
                 .unwrap_or("raw");
             s.profile_kind != "serial" || framing != "raw"
-        });
+        };
+        let rx_frames = self.sources.iter().enumerate().any(|(idx, s)| emits_frames(idx, s));
+
+        // Which output buses carry which protocol: a bus mapping's own traits
+        // win over its source kind's (a GVRET can expose CAN and serial buses)
+        let sub_streams = protocol_streams(
+            self.sources
+                .iter()
+                .enumerate()
+                .filter(|(idx, s)| emits_frames(*idx, s))
+                .flat_map(|(_, s)| {
+                    let source_protocols = get_traits_for_profile_kind(&s.profile_kind).protocols;
+                    s.bus_mappings.iter().filter(|m| m.enabled).map(move |m| {
+                        let protocols = m
+                            .traits
+                            .as_ref()
+                            .map_or_else(|| source_protocols.clone(), |t| t.protocols.clone());
+                        (protocols, m.output_bus)
+                    })
+                }),
+        );
 
         IOCapabilities {
             can_pause: false,
@@ -367,6 +447,7 @@ impl IOBroker {
             data_streams: SessionDataStreams {
                 rx_frames,
                 rx_bytes: self.emits_raw_bytes,
+                sub_streams,
             },
         }
     }
@@ -410,10 +491,52 @@ impl IOBroker {
                 }
                 encode_gvret_frame(&routed_frame)
             }
+            #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+            "gs_usb" => encode_gs_usb_frame(&routed_frame, routed_frame.bus).to_vec(),
             #[cfg(any(target_os = "windows", target_os = "macos"))]
-            "gs_usb" => encode_gs_usb_frame(&routed_frame, 0).to_vec(),
+            "pcan" => {
+                if routed_frame.is_fd {
+                    return Ok(TransmitResult::error(
+                        "PCAN sources run in classic CAN mode and cannot transmit FD frames".to_string(),
+                    ));
+                }
+                encode_pcan_frame(&routed_frame)
+            }
+            #[cfg(any(target_os = "windows", target_os = "linux"))]
+            "kvaser" => {
+                if routed_frame.is_fd {
+                    return Ok(TransmitResult::error(
+                        "Kvaser sources run in classic CAN mode and cannot transmit FD frames".to_string(),
+                    ));
+                }
+                encode_kvaser_frame(&routed_frame)
+            }
+            #[cfg(target_os = "windows")]
+            "vector_xl" => encode_vector_xl_frame(&routed_frame),
+            #[cfg(target_os = "windows")]
+            "j2534" => {
+                if routed_frame.is_fd || routed_frame.is_rtr {
+                    return Ok(TransmitResult::error(
+                        "J2534 sources transmit classic CAN data frames only".to_string(),
+                    ));
+                }
+                encode_j2534_frame(&routed_frame)
+            }
             #[cfg(not(target_os = "ios"))]
             "slcan" => encode_slcan_frame(&routed_frame),
+            #[cfg(not(target_os = "ios"))]
+            "custom_serial" => {
+                if routed_frame.is_fd || routed_frame.is_rtr {
+                    return Ok(TransmitResult::error(
+                        "Custom serial sources transmit classic data frames only".to_string(),
+                    ));
+                }
+                encode_custom_serial_frame(&routed_frame)
+            }
+            "lin" => match encode_lin_frame(&routed_frame) {
+                Ok(data) => data,
+                Err(e) => return Ok(TransmitResult::error(e)),
+            },
             #[cfg(target_os = "linux")]
             "socketcan" => {
                 match encode_socketcan_frame(&routed_frame) {
@@ -497,6 +620,82 @@ impl IOBroker {
         .map_err(|e| format!("Serial transmit buffer full ({})", e))?;
         Ok(TransmitResult::queued())
     }
+
+    /// Route a Modbus write to the first Modbus TCP client source
+    fn transmit_modbus_write(&self, write: &ModbusWrite) -> Result<TransmitResult, String> {
+        if let Err(e) = write.validate() {
+            return Ok(TransmitResult::error(e));
+        }
+
+        let modbus_route = self
+            .transmit_routes
+            .values()
+            .find(|route| route.profile_kind == "modbus_tcp")
+            .ok_or_else(|| "No Modbus TCP source configured in this session".to_string())?;
+
+        let channels = self
+            .transmit_channels
+            .lock()
+            .map_err(|e| format!("Failed to lock transmit channels: {}", e))?;
+
+        // Client and simulator sources open a transmit channel; a server (MITM) source has none
+        let tx = channels
+            .get(&modbus_route.source_idx)
+            .ok_or_else(|| {
+                format!(
+                    "No transmit channel for Modbus source {} (profile '{}') - source may be a listen-only server",
+                    modbus_route.source_idx, modbus_route.profile_id
+                )
+            })?
+            .clone();
+        drop(channels); // Release lock before blocking
+
+        let (result_tx, _result_rx) = std_mpsc::sync_channel(1);
+        tx.try_send(TransmitRequest {
+            data: write.encode(),
+            result_tx,
+        })
+        .map_err(|e| format!("Modbus write queue full ({})", e))?;
+        Ok(TransmitResult::queued())
+    }
+
+    /// Route a UDS request to the first DoIP source in the session
+    fn transmit_diagnostic(&self, request: &DiagnosticRequest) -> Result<TransmitResult, String> {
+        if request.data.is_empty() {
+            return Ok(TransmitResult::error("No UDS payload to send".to_string()));
+        }
+
+        let doip_route = self
+            .transmit_routes
+            .values()
+            .find(|route| route.profile_kind == "doip")
+            .ok_or_else(|| "No DoIP source configured in this session".to_string())?;
+
+        let channels = self
+            .transmit_channels
+            .lock()
+            .map_err(|e| format!("Failed to lock transmit channels: {}", e))?;
+
+        // The channel only exists once routing activation has succeeded
+        let tx = channels
+            .get(&doip_route.source_idx)
+            .ok_or_else(|| {
+                format!(
+                    "No transmit channel for DoIP source {} (profile '{}') - routing may not be active yet",
+                    doip_route.source_idx, doip_route.profile_id
+                )
+            })?
+            .clone();
+        drop(channels); // Release lock before blocking
+
+        let (result_tx, _result_rx) = std_mpsc::sync_channel(1);
+        tx.try_send(TransmitRequest {
+            data: request.encode(),
+            result_tx,
+        })
+        .map_err(|e| format!("DoIP transmit queue full ({})", e))?;
+        Ok(TransmitResult::queued())
+    }
 }
 
 #[async_trait]
@@ -687,6 +886,8 @@ impl IOSource for IOBroker {
         match payload {
             TransmitPayload::CanFrame(frame) => self.transmit_can_frame(frame),
             TransmitPayload::RawBytes(bytes) => self.transmit_raw_bytes(bytes),
+            TransmitPayload::ModbusWrite(write) => self.transmit_modbus_write(write),
+            TransmitPayload::Diagnostic(request) => self.transmit_diagnostic(request),
         }
     }
 
@@ -694,6 +895,20 @@ impl IOSource for IOBroker {
         IOBroker::set_framing(self, req)
     }
 
+    fn socketcan_transmit_interface(&self, bus: u8) -> Option<String> {
+        let route = self.transmit_routes.get(&bus)?;
+        if route.profile_kind != "socketcan" {
+            return None;
+        }
+        // Only once the source is up and offering transmit
+        if !self.transmit_channels.lock().ok()?.contains_key(&route.source_idx) {
+            return None;
+        }
+        let settings = crate::settings::load_settings_sync(&self.app).ok()?;
+        let profile = settings.io_profiles.iter().find(|p| p.id == route.profile_id)?;
+        profile.connection.get("interface")?.as_str().map(str::to_string)
+    }
+
     fn state(&self) -> IOState {
         self.state.clone()
     }
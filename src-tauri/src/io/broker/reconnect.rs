@@ -0,0 +1,209 @@
+// io/broker/reconnect.rs
+//
+// Auto-reconnect for realtime sources. A source with a reconnect policy runs
+// behind a relay: its messages pass through to the merge task, except for a
+// terminal Error/Ended while the session is still running. That is reported
+// as SourceMessage::Disconnected (emitted as `device-disconnected`) and the
+// reader is restarted after an exponential backoff. The restarted reader sends
+// Connected again, which the merge task emits as `device-connected`. Once
+// retries run out the terminal message goes through and the source ends as
+// before. A source that reconnects and delivers frames gets its full retry
+// budget back. A USB device attaching during the backoff (usb_hotplug) cuts
+// the wait short, so a re-enumerated adapter is picked up straight away.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::io::types::SourceMessage;
+
+/// Per-source reconnect policy (`SourceConfig.reconnect`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReconnectPolicy {
+    /// Attempts before giving up (0 = keep trying)
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Wait before the first attempt; doubles each attempt
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// Cap on the wait between attempts
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+fn default_max_retries() -> u32 {
+    10
+}
+fn default_initial_backoff_ms() -> u64 {
+    500
+}
+fn default_max_backoff_ms() -> u64 {
+    30_000
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: default_max_retries(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Wait before attempt `attempt` (1-based).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(20);
+        Duration::from_millis(self.initial_backoff_ms.saturating_mul(factor).min(self.max_backoff_ms))
+    }
+
+    /// Whether attempt `attempt` (1-based) is allowed.
+    pub fn allows(&self, attempt: u32) -> bool {
+        self.max_retries == 0 || attempt <= self.max_retries
+    }
+}
+
+#[cfg(not(target_os = "ios"))]
+fn attach_generation() -> u64 {
+    crate::usb_hotplug::attach_generation()
+}
+
+#[cfg(target_os = "ios")]
+fn attach_generation() -> u64 {
+    0
+}
+
+/// Run a source reader under `policy`, restarting it when it drops.
+/// `run` starts one reader attempt sending to the given channel.
+pub(super) async fn run_with_reconnect<F, Fut>(
+    source_idx: usize,
+    policy: ReconnectPolicy,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+    mut run: F,
+) where
+    F: FnMut(mpsc::Sender<SourceMessage>) -> Fut + Send,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut attempt = 0u32;
+    loop {
+        let (inner_tx, mut inner_rx) = mpsc::channel(super::SOURCE_CHANNEL_CAPACITY);
+        let reader = tokio::spawn(run(inner_tx));
+
+        let mut terminal: Option<SourceMessage> = None;
+        while let Some(msg) = inner_rx.recv().await {
+            match msg {
+                SourceMessage::Ended(..) | SourceMessage::Error(..) => terminal = Some(msg),
+                SourceMessage::Frames(..) => {
+                    attempt = 0;
+                    if tx.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+                other => {
+                    if tx.send(other).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+        let _ = reader.await;
+
+        let Some(terminal) = terminal else { return };
+        let reason = match &terminal {
+            SourceMessage::Error(_, e) => e.clone(),
+            SourceMessage::Ended(_, r) => r.clone(),
+            _ => String::new(),
+        };
+        attempt += 1;
+        if stop_flag.load(Ordering::Relaxed) || !policy.allows(attempt) {
+            let _ = tx.send(terminal).await;
+            return;
+        }
+
+        let wait = policy.backoff(attempt);
+        tlog!(
+            "[IOBroker] Source {} dropped ({}), reconnect attempt {} in {}ms",
+            source_idx, reason, attempt, wait.as_millis()
+        );
+        let _ = tx.send(SourceMessage::Disconnected(source_idx, reason, attempt, wait.as_millis() as u64)).await;
+
+        // Sleep in slices so a session stop isn't held up by the backoff
+        let attached = attach_generation();
+        let deadline = tokio::time::Instant::now() + wait;
+        while tokio::time::Instant::now() < deadline {
+            if stop_flag.load(Ordering::Relaxed) {
+                let _ = tx.send(SourceMessage::Ended(source_idx, "stopped".to_string())).await;
+                return;
+            }
+            if attach_generation() != attached {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50).min(deadline - tokio::time::Instant::now())).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = ReconnectPolicy { max_retries: 0, initial_backoff_ms: 500, max_backoff_ms: 3_000 };
+        let waits: Vec<u64> = (1..=5).map(|a| policy.backoff(a).as_millis() as u64).collect();
+        assert_eq!(waits, vec![500, 1_000, 2_000, 3_000, 3_000]);
+        assert!(policy.allows(1_000));
+    }
+
+    #[tokio::test]
+    async fn a_dropped_source_is_restarted_until_retries_run_out() {
+        let policy = ReconnectPolicy { max_retries: 2, initial_backoff_ms: 1, max_backoff_ms: 1 };
+        let (tx, mut rx) = mpsc::channel(16);
+        run_with_reconnect(0, policy, Arc::new(AtomicBool::new(false)), tx, |inner| async move {
+            let _ = inner.send(SourceMessage::Error(0, "link down".to_string())).await;
+        })
+        .await;
+
+        let mut seen = Vec::new();
+        while let Ok(msg) = rx.try_recv() {
+            seen.push(match msg {
+                SourceMessage::Disconnected(_, _, attempt, _) => format!("disconnected {attempt}"),
+                SourceMessage::Error(_, e) => format!("error {e}"),
+                _ => "other".to_string(),
+            });
+        }
+        assert_eq!(seen, vec!["disconnected 1", "disconnected 2", "error link down"]);
+    }
+
+    #[tokio::test]
+    async fn frames_reset_the_retry_budget() {
+        let policy = ReconnectPolicy { max_retries: 1, initial_backoff_ms: 1, max_backoff_ms: 1 };
+        let runs = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let (tx, mut rx) = mpsc::channel(16);
+        let counter = runs.clone();
+        run_with_reconnect(0, policy, Arc::new(AtomicBool::new(false)), tx, move |inner| {
+            let run = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                // First two runs deliver frames before dropping; the third just drops
+                if run < 2 {
+                    let _ = inner.send(SourceMessage::Frames(0, Vec::new())).await;
+                }
+                let _ = inner.send(SourceMessage::Ended(0, "eof".to_string())).await;
+            }
+        })
+        .await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        let mut last = None;
+        while let Ok(msg) = rx.try_recv() {
+            last = Some(msg);
+        }
+        assert!(matches!(last, Some(SourceMessage::Ended(_, ref r)) if r == "eof"));
+    }
+}
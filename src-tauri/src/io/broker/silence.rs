@@ -0,0 +1,189 @@
+// io/broker/silence.rs
+//
+// Per-bus silence watchdog for multi-source sessions. The merge task notes
+// the output bus of every frame it forwards; an enabled bus that carries
+// nothing for `bus_silence_timeout_ms` raises `bus-silent`, and its next
+// frame raises `bus-active` with how long it was quiet. Broken wiring or a
+// sleeping ECU shows up straight away instead of as a view that stops moving.
+//
+// A bus is watched while any running source maps onto it. Buses that never
+// carry a frame are reported too, timed from when their source started.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::io::FrameMessage;
+
+/// Payload of the `bus-silent` and `bus-active` events.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BusSilencePayload {
+    pub session_id: String,
+    pub bus: u8,
+    /// `bus-silent`: time since the bus's last frame. `bus-active`: how long
+    /// the bus was silent in all.
+    pub silent_ms: u64,
+}
+
+/// A bus going quiet or coming back.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum BusChange {
+    Silent { bus: u8, silent_for: Duration },
+    Active { bus: u8, silent_for: Duration },
+}
+
+struct BusActivity {
+    last_frame: Instant,
+    silent: bool,
+}
+
+pub(super) struct SilenceWatchdog {
+    /// None = watchdog off
+    limit: Option<Duration>,
+    /// Enabled output buses of each running source
+    source_buses: HashMap<usize, Vec<u8>>,
+    buses: HashMap<u8, BusActivity>,
+}
+
+impl SilenceWatchdog {
+    /// A watchdog reporting buses silent for `timeout_ms` (0 = off).
+    pub fn new(timeout_ms: u32) -> Self {
+        Self {
+            limit: (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms as u64)),
+            source_buses: HashMap::new(),
+            buses: HashMap::new(),
+        }
+    }
+
+    /// Start watching a source's output buses.
+    pub fn watch_source(&mut self, source_idx: usize, buses: Vec<u8>, now: Instant) {
+        if self.limit.is_none() {
+            return;
+        }
+        for &bus in &buses {
+            self.buses.entry(bus).or_insert(BusActivity { last_frame: now, silent: false });
+        }
+        self.source_buses.insert(source_idx, buses);
+    }
+
+    /// Stop watching the buses only this source mapped onto.
+    pub fn remove_source(&mut self, source_idx: usize) {
+        let Some(buses) = self.source_buses.remove(&source_idx) else { return };
+        let still_mapped: HashSet<u8> = self.source_buses.values().flatten().copied().collect();
+        for bus in buses {
+            if !still_mapped.contains(&bus) {
+                self.buses.remove(&bus);
+            }
+        }
+    }
+
+    /// Note a batch of forwarded frames. Returns the buses that came back.
+    pub fn record(&mut self, frames: &[FrameMessage], now: Instant) -> Vec<BusChange> {
+        let mut changes = Vec::new();
+        if self.limit.is_none() {
+            return changes;
+        }
+        for frame in frames {
+            if let Some(activity) = self.buses.get_mut(&frame.bus) {
+                if activity.silent {
+                    activity.silent = false;
+                    changes.push(BusChange::Active {
+                        bus: frame.bus,
+                        silent_for: now.saturating_duration_since(activity.last_frame),
+                    });
+                }
+                activity.last_frame = now;
+            }
+        }
+        changes
+    }
+
+    /// Buses that have just gone past the silence limit.
+    pub fn check(&mut self, now: Instant) -> Vec<BusChange> {
+        let Some(limit) = self.limit else { return Vec::new() };
+        let mut changes: Vec<BusChange> = self
+            .buses
+            .iter_mut()
+            .filter_map(|(&bus, activity)| {
+                let silent_for = now.saturating_duration_since(activity.last_frame);
+                if activity.silent || silent_for < limit {
+                    return None;
+                }
+                activity.silent = true;
+                Some(BusChange::Silent { bus, silent_for })
+            })
+            .collect();
+        changes.sort_by_key(|c| match c {
+            BusChange::Silent { bus, .. } | BusChange::Active { bus, .. } => *bus,
+        });
+        changes
+    }
+}
+
+impl BusChange {
+    /// Event name and payload for this change.
+    pub fn event(&self, session_id: &str) -> (&'static str, BusSilencePayload) {
+        let (name, bus, silent_for) = match *self {
+            BusChange::Silent { bus, silent_for } => ("bus-silent", bus, silent_for),
+            BusChange::Active { bus, silent_for } => ("bus-active", bus, silent_for),
+        };
+        let payload = BusSilencePayload {
+            session_id: session_id.to_string(),
+            bus,
+            silent_ms: silent_for.as_millis() as u64,
+        };
+        (name, payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame_on(bus: u8) -> FrameMessage {
+        FrameMessage { bus, ..test_frame(0x100, 0, &[]) }
+    }
+
+    #[test]
+    fn test_silence_is_reported_once_and_recovery_carries_the_duration() {
+        let start = Instant::now();
+        let mut watchdog = SilenceWatchdog::new(500);
+        watchdog.watch_source(0, vec![0, 1], start);
+
+        let t1 = start + Duration::from_millis(400);
+        assert!(watchdog.record(&[frame_on(0)], t1).is_empty());
+        assert!(watchdog.check(t1).is_empty());
+
+        let t2 = start + Duration::from_millis(600);
+        assert_eq!(watchdog.check(t2), vec![BusChange::Silent { bus: 1, silent_for: Duration::from_millis(600) }]);
+        assert!(watchdog.check(t2 + Duration::from_millis(100)).is_empty());
+
+        let t3 = start + Duration::from_millis(1500);
+        let changes = watchdog.record(&[frame_on(1), frame_on(1)], t3);
+        assert_eq!(changes, vec![BusChange::Active { bus: 1, silent_for: Duration::from_millis(1500) }]);
+        let (name, payload) = changes[0].event("s1");
+        assert_eq!((name, payload.bus, payload.silent_ms), ("bus-active", 1, 1500));
+    }
+
+    #[test]
+    fn test_shared_buses_stay_watched_until_their_last_source_goes() {
+        let start = Instant::now();
+        let mut watchdog = SilenceWatchdog::new(100);
+        watchdog.watch_source(0, vec![0], start);
+        watchdog.watch_source(1, vec![0, 2], start);
+        watchdog.remove_source(1);
+
+        let later = start + Duration::from_millis(200);
+        assert_eq!(watchdog.check(later), vec![BusChange::Silent { bus: 0, silent_for: Duration::from_millis(200) }]);
+    }
+
+    #[test]
+    fn test_zero_timeout_turns_the_watchdog_off() {
+        let start = Instant::now();
+        let mut watchdog = SilenceWatchdog::new(0);
+        watchdog.watch_source(0, vec![0], start);
+        assert!(watchdog.check(start + Duration::from_secs(60)).is_empty());
+    }
+}
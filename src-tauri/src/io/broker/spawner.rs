@@ -6,7 +6,6 @@ use std::net::SocketAddr;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::mpsc as std_mpsc;
 use std::sync::Arc;
-use tauri::AppHandle;
 use tokio::sync::mpsc;
 use tokio::sync::Mutex;
 use tokio::time::{Duration, interval};
@@ -17,13 +16,24 @@ use super::types::ModbusRole;
 use crate::io::gvret::{run_gvret_tcp_source, BusMapping};
 #[cfg(not(target_os = "ios"))]
 use crate::io::gvret::run_gvret_usb_source;
-use crate::io::modbus_tcp::{PollGroup, RegisterType};
+use crate::io::modbus_tcp::{MbapFrame, ModbusWrite, PollGroup, RegisterMap, RegisterType};
 use crate::io::periodic::Cadence;
 use crate::io::{now_us, FrameMessage};
 #[cfg(not(target_os = "ios"))]
 use crate::io::serial::{parse_profile_for_source, run_source as run_serial_source};
 #[cfg(not(target_os = "ios"))]
 use crate::io::slcan::run_slcan_source;
+#[cfg(not(target_os = "ios"))]
+use crate::io::custom_serial::{run_custom_serial_source, CustomFrameFormat, CustomSerialPortConfig};
+#[cfg(not(target_os = "ios"))]
+use crate::io::elm327::{run_elm327_source, Elm327Config};
+#[cfg(not(target_os = "ios"))]
+use crate::io::lin::{run_lin_source, LinConfig};
+use crate::io::canserver::{run_canserver_source, CanServerConfig};
+use crate::io::websocket::{run_websocket_source, WebSocketConfig};
+use crate::io::udp_can::{run_udp_can_source, UdpCanConfig};
+use crate::io::doip::{run_doip_source, DoipConfig};
+use crate::io::netbridge::{run_netbridge_source, NetBridgeConfig};
 use crate::io::framelink::reader::run_source as run_framelink_source;
 use crate::io::types::{SourceMessage, TransmitRequest};
 use crate::settings::IOProfile;
@@ -32,13 +42,24 @@ use super::{VirtualBusCommand, VirtualBusControl, VirtualBusControls};
 #[cfg(target_os = "linux")]
 use crate::io::socketcan::run_source as run_socketcan_source;
 
-#[cfg(any(target_os = "windows", target_os = "macos"))]
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
 use crate::io::gs_usb::run_source as run_gs_usb_source;
 
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+use crate::io::pcan::run_source as run_pcan_source;
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use crate::io::kvaser::run_source as run_kvaser_source;
+
+#[cfg(target_os = "windows")]
+use crate::io::vector_xl::{run_source as run_vector_xl_source, VectorBusConfig};
+
+#[cfg(target_os = "windows")]
+use crate::io::j2534::{run_source as run_j2534_source, J2534Config};
+
 /// Run a single source reader and send frames to the merge task
 #[allow(clippy::too_many_arguments)]
 pub(super) async fn run_source_reader(
-    _app: AppHandle,
     _session_id: String,
     source_idx: usize,
     profile: IOProfile,
@@ -80,10 +101,103 @@ pub(super) async fn run_source_reader(
         "slcan" => {
             run_slcan_reader(source_idx, &profile, bus_mappings, stop_flag, tx).await;
         }
-        #[cfg(any(target_os = "windows", target_os = "macos"))]
+        #[cfg(not(target_os = "ios"))]
+        "custom_serial" => {
+            run_custom_serial_reader(source_idx, &profile, bus_mappings, stop_flag, tx).await;
+        }
+        #[cfg(not(target_os = "ios"))]
+        "elm327" => {
+            let config = match Elm327Config::from_connection(&profile.connection) {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+                    return;
+                }
+            };
+            run_elm327_source(source_idx, config, bus_mappings, stop_flag, tx).await;
+        }
+        #[cfg(not(target_os = "ios"))]
+        "lin" => {
+            let config = match LinConfig::from_connection(&profile.connection) {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+                    return;
+                }
+            };
+            // The session id locates the LDF-lite description of its catalogue
+            run_lin_source(_session_id, source_idx, config, bus_mappings, stop_flag, tx).await;
+        }
+        "canserver" => {
+            let config = match CanServerConfig::from_connection(&profile.connection) {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+                    return;
+                }
+            };
+            run_canserver_source(source_idx, config, bus_mappings, stop_flag, tx).await;
+        }
+        "websocket" => {
+            let config = match WebSocketConfig::from_connection(&profile.connection) {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+                    return;
+                }
+            };
+            run_websocket_source(source_idx, config, bus_mappings, stop_flag, tx).await;
+        }
+        "udp_can" => {
+            let config = match UdpCanConfig::from_connection(&profile.connection) {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+                    return;
+                }
+            };
+            run_udp_can_source(source_idx, config, bus_mappings, stop_flag, tx).await;
+        }
+        "doip" => {
+            let config = match DoipConfig::from_connection(&profile.connection) {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+                    return;
+                }
+            };
+            run_doip_source(source_idx, config, bus_mappings, stop_flag, tx).await;
+        }
+        "wiretap_net" => {
+            let config = match NetBridgeConfig::from_connection(&profile.connection) {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+                    return;
+                }
+            };
+            run_netbridge_source(source_idx, config, bus_mappings, stop_flag, tx).await;
+        }
+        #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
         "gs_usb" => {
             run_gs_usb_reader(source_idx, &profile, bus_mappings, stop_flag, tx).await;
         }
+        #[cfg(any(target_os = "windows", target_os = "macos"))]
+        "pcan" => {
+            run_pcan_reader(source_idx, &profile, bus_mappings, stop_flag, tx).await;
+        }
+        #[cfg(any(target_os = "windows", target_os = "linux"))]
+        "kvaser" => {
+            run_kvaser_reader(source_idx, &profile, bus_mappings, stop_flag, tx).await;
+        }
+        #[cfg(target_os = "windows")]
+        "vector_xl" => {
+            run_vector_xl_reader(source_idx, &profile, bus_mappings, stop_flag, tx).await;
+        }
+        #[cfg(target_os = "windows")]
+        "j2534" => {
+            run_j2534_reader(source_idx, &profile, bus_mappings, stop_flag, tx).await;
+        }
         #[cfg(target_os = "linux")]
         "socketcan" => {
             run_socketcan_reader(source_idx, &profile, bus_mappings, stop_flag, tx).await;
@@ -117,10 +231,13 @@ pub(super) async fn run_source_reader(
             run_virtual_reader(source_idx, &profile, bus_mappings, stop_flag, tx, virtual_bus_controls, virtual_cmd_rx).await;
         }
         "modbus_tcp" => {
-            let role = _modbus_role.unwrap_or(ModbusRole::Client);
+            let role = _modbus_role
+                .or_else(|| ModbusRole::from_profile(&profile))
+                .unwrap_or(ModbusRole::Client);
             match role {
                 ModbusRole::Client => {
                     run_modbus_tcp_client(
+                        _session_id,
                         source_idx,
                         &profile,
                         bus_mappings,
@@ -134,9 +251,24 @@ pub(super) async fn run_source_reader(
                 }
                 ModbusRole::Server => {
                     run_modbus_tcp_server(
+                        _session_id,
                         source_idx,
                         &profile,
                         bus_mappings,
+                        None,
+                        stop_flag,
+                        tx,
+                    )
+                    .await;
+                }
+                ModbusRole::Simulator => {
+                    let register_map = RegisterMap::from_polls(&_modbus_polls.unwrap_or_default());
+                    run_modbus_tcp_server(
+                        _session_id,
+                        source_idx,
+                        &profile,
+                        bus_mappings,
+                        Some(register_map),
                         stop_flag,
                         tx,
                     )
@@ -182,8 +314,13 @@ async fn run_gvret_tcp_reader(
         .get("timeout")
         .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
         .unwrap_or(5.0);
+    let listen_only = profile
+        .connection
+        .get("listen_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
 
-    run_gvret_tcp_source(source_idx, host, port, timeout_sec, bus_mappings, stop_flag, tx).await;
+    run_gvret_tcp_source(source_idx, host, port, timeout_sec, listen_only, bus_mappings, stop_flag, tx).await;
 }
 
 #[cfg(not(target_os = "ios"))]
@@ -211,8 +348,13 @@ async fn run_gvret_usb_reader(
         .get("baud_rate")
         .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
         .unwrap_or(115200) as u32;
+    let listen_only = profile
+        .connection
+        .get("listen_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
 
-    run_gvret_usb_source(source_idx, port, baud_rate, bus_mappings, stop_flag, tx).await;
+    run_gvret_usb_source(source_idx, port, baud_rate, listen_only, bus_mappings, stop_flag, tx).await;
 }
 
 #[cfg(not(target_os = "ios"))]
@@ -249,7 +391,7 @@ async fn run_slcan_reader(
         .connection
         .get("silent_mode")
         .and_then(|v| v.as_bool())
-        .unwrap_or(false);
+        .unwrap_or(true);
     let enable_fd = profile
         .connection
         .get("enable_fd")
@@ -276,7 +418,78 @@ async fn run_slcan_reader(
     .await;
 }
 
-#[cfg(any(target_os = "windows", target_os = "macos"))]
+#[cfg(not(target_os = "ios"))]
+async fn run_custom_serial_reader(
+    source_idx: usize,
+    profile: &IOProfile,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let port = match profile.connection.get("port").and_then(|v| v.as_str()) {
+        Some(p) => p.to_string(),
+        None => {
+            let _ = tx
+                .send(SourceMessage::Error(
+                    source_idx,
+                    "Serial port is required".to_string(),
+                ))
+                .await;
+            return;
+        }
+    };
+    let format = match CustomFrameFormat::from_connection(&profile.connection) {
+        Ok(f) => f,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+            return;
+        }
+    };
+    let baud_rate = profile
+        .connection
+        .get("baud_rate")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(115200) as u32;
+    let data_bits = profile
+        .connection
+        .get("data_bits")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(8) as u8;
+    let stop_bits = profile
+        .connection
+        .get("stop_bits")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(1) as u8;
+    let parity = match profile.connection.get("parity").and_then(|v| v.as_str()) {
+        Some("odd") => crate::io::serial::Parity::Odd,
+        Some("even") => crate::io::serial::Parity::Even,
+        _ => crate::io::serial::Parity::None,
+    };
+    let listen_only = profile
+        .connection
+        .get("listen_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    run_custom_serial_source(
+        source_idx,
+        CustomSerialPortConfig {
+            port,
+            baud_rate,
+            data_bits,
+            stop_bits,
+            parity,
+            listen_only,
+        },
+        format,
+        bus_mappings,
+        stop_flag,
+        tx,
+    )
+    .await;
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
 async fn run_gs_usb_reader(
     source_idx: usize,
     profile: &IOProfile,
@@ -334,6 +547,16 @@ async fn run_gs_usb_reader(
         .get("data_sample_point")
         .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
         .unwrap_or(75.0) as f32;
+    let hardware_timestamps = profile
+        .connection
+        .get("hardware_timestamps")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let error_frames = profile
+        .connection
+        .get("error_frames")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
 
     run_gs_usb_source(
         source_idx,
@@ -347,6 +570,198 @@ async fn run_gs_usb_reader(
         enable_fd,
         data_bitrate,
         data_sample_point,
+        hardware_timestamps,
+        error_frames,
+        bus_mappings,
+        stop_flag,
+        tx,
+    )
+    .await;
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+async fn run_pcan_reader(
+    source_idx: usize,
+    profile: &IOProfile,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let channel = profile
+        .connection
+        .get("channel")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(1) as u8;
+    // Device ID (set with PCAN-View) pins the profile to one adapter across replugging
+    let device_id = profile
+        .connection
+        .get("device_id")
+        .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .map(|v| v as u32);
+    let bitrate = profile
+        .connection
+        .get("bitrate")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(500_000) as u32;
+    let listen_only = profile
+        .connection
+        .get("listen_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+
+    run_pcan_source(
+        source_idx,
+        channel,
+        device_id,
+        bitrate,
+        listen_only,
+        bus_mappings,
+        stop_flag,
+        tx,
+    )
+    .await;
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+async fn run_kvaser_reader(
+    source_idx: usize,
+    profile: &IOProfile,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    // Card serial pins the profile to one device across replugging
+    let serial = profile
+        .connection
+        .get("serial")
+        .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())));
+    // Otherwise the device owning this CANlib channel is used
+    let channel = profile
+        .connection
+        .get("channel")
+        .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(0) as u32;
+    let bitrate = profile
+        .connection
+        .get("bitrate")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(500_000) as u32;
+    let listen_only = profile
+        .connection
+        .get("listen_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+
+    run_kvaser_source(
+        source_idx,
+        serial,
+        channel,
+        bitrate,
+        listen_only,
+        bus_mappings,
+        stop_flag,
+        tx,
+    )
+    .await;
+}
+
+#[cfg(target_os = "windows")]
+async fn run_vector_xl_reader(
+    source_idx: usize,
+    profile: &IOProfile,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    // Interface serial pins the profile to one device across replugging
+    let serial = profile
+        .connection
+        .get("serial")
+        .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .map(|s| s as u32);
+    // Otherwise the interface owning this global XL channel index is used
+    let channel = profile
+        .connection
+        .get("channel")
+        .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(0) as u8;
+    let bitrate = profile
+        .connection
+        .get("bitrate")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(500_000) as u32;
+    let enable_fd = profile
+        .connection
+        .get("enable_fd")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let data_bitrate = profile
+        .connection
+        .get("data_bitrate")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(2_000_000) as u32;
+    let listen_only = profile
+        .connection
+        .get("listen_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+
+    run_vector_xl_source(
+        source_idx,
+        serial,
+        channel,
+        VectorBusConfig {
+            bitrate,
+            enable_fd,
+            data_bitrate,
+            listen_only,
+        },
+        bus_mappings,
+        stop_flag,
+        tx,
+    )
+    .await;
+}
+
+#[cfg(target_os = "windows")]
+async fn run_j2534_reader(
+    source_idx: usize,
+    profile: &IOProfile,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    // Registered device name; the DLL path is looked up unless given
+    let device = profile
+        .connection
+        .get("device")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let library_path = profile
+        .connection
+        .get("library_path")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    let bitrate = profile
+        .connection
+        .get("bitrate")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(500_000) as u32;
+    let listen_only = profile
+        .connection
+        .get("listen_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    run_j2534_source(
+        source_idx,
+        J2534Config {
+            device,
+            library_path,
+            bitrate,
+            listen_only,
+        },
         bus_mappings,
         stop_flag,
         tx,
@@ -391,6 +806,21 @@ async fn run_socketcan_reader(
         .get("data_bitrate")
         .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
         .map(|v| v as u32);
+    let hardware_timestamps = profile
+        .connection
+        .get("hardware_timestamps")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let error_frames = profile
+        .connection
+        .get("error_frames")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let listen_only = profile
+        .connection
+        .get("listen_only")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
 
     run_socketcan_source(
         source_idx,
@@ -398,6 +828,9 @@ async fn run_socketcan_reader(
         bitrate,
         enable_fd,
         data_bitrate,
+        hardware_timestamps,
+        error_frames,
+        listen_only,
         bus_mappings,
         stop_flag,
         tx,
@@ -657,6 +1090,11 @@ async fn run_virtual_reader(
                             source_address: None,
                             incomplete: None,
                             direction: Some("rx".to_string()),
+                            link: None,
+                            clock: None,
+                            is_error: None,
+                            error_class: None,
+                            brs: None,
                         };
                         let _ = tx_loopback
                             .send(SourceMessage::Frames(source_idx, vec![frame]))
@@ -877,6 +1315,11 @@ fn spawn_bus_generator(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     }
                 }
                 "modbus" => {
@@ -896,6 +1339,11 @@ fn spawn_bus_generator(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     }
                 }
                 _ => {
@@ -921,6 +1369,11 @@ fn spawn_bus_generator(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     }
                 }
             };
@@ -938,9 +1391,12 @@ fn spawn_bus_generator(
 // Modbus TCP Source Functions
 // ============================================================================
 
-/// Modbus TCP client source: connects to a Modbus TCP server and polls registers.
+/// Modbus TCP client source: connects to a Modbus TCP server, polls registers
+/// and runs writes queued from the Transmit app.
 /// Extracted from ModbusTcpSource to work within the multi-source framework.
+#[allow(clippy::too_many_arguments)]
 async fn run_modbus_tcp_client(
+    session_id: String,
     source_idx: usize,
     profile: &IOProfile,
     bus_mappings: Vec<BusMapping>,
@@ -980,18 +1436,13 @@ async fn run_modbus_tcp_client(
         .map(|m| m.output_bus)
         .unwrap_or(0);
 
+    // Without poll groups the connection still serves writes until a catalog
+    // reinitialises the session
     if polls.is_empty() {
         tlog!(
-            "[ModbusTCP] Source {} has no poll groups — waiting for catalog reinitialise",
+            "[ModbusTCP] Source {} has no poll groups — connecting for writes only",
             source_idx
         );
-        let _ = tx
-            .send(SourceMessage::Ended(
-                source_idx,
-                "no_polls".to_string(),
-            ))
-            .await;
-        return;
     }
 
     // Resolve server address
@@ -1041,6 +1492,22 @@ async fn run_modbus_tcp_client(
         source_idx, address, unit_id, polls.len(), output_bus
     );
 
+    // Writes share the connection with the poll tasks
+    let (transmit_tx, transmit_rx) = std_mpsc::sync_channel::<TransmitRequest>(32);
+    let _ = tx
+        .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
+        .await;
+    let polls_done = Arc::new(AtomicBool::new(false));
+    let write_handle = tokio::spawn(run_modbus_write_task(
+        session_id,
+        source_idx,
+        unit_id,
+        ctx.clone(),
+        transmit_rx,
+        stop_flag.clone(),
+        polls_done.clone(),
+    ));
+
     // Spawn one poll task per group
     let mut poll_handles = Vec::new();
     for poll in &polls {
@@ -1066,16 +1533,83 @@ async fn run_modbus_tcp_client(
         poll_handles.push(handle);
     }
 
-    // Wait for all poll tasks to finish
+    // Wait for all poll tasks to finish; if they all gave up, so does the writer
     for handle in poll_handles {
         let _ = handle.await;
     }
+    if !polls.is_empty() {
+        polls_done.store(true, Ordering::Relaxed);
+    }
+    let _ = write_handle.await;
 
     let _ = tx
         .send(SourceMessage::Ended(source_idx, "stopped".to_string()))
         .await;
 }
 
+/// Run Modbus writes queued by the session's transmit path on the shared
+/// client connection. Each write goes into the transmit history with the
+/// device's answer.
+async fn run_modbus_write_task(
+    session_id: String,
+    source_idx: usize,
+    default_unit: u8,
+    ctx: Arc<Mutex<client::Context>>,
+    transmit_rx: std_mpsc::Receiver<TransmitRequest>,
+    stop_flag: Arc<AtomicBool>,
+    polls_done: Arc<AtomicBool>,
+) {
+    while !stop_flag.load(Ordering::Relaxed) && !polls_done.load(Ordering::Relaxed) {
+        let req = match transmit_rx.try_recv() {
+            Ok(req) => req,
+            Err(std_mpsc::TryRecvError::Empty) => {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                continue;
+            }
+            Err(std_mpsc::TryRecvError::Disconnected) => break,
+        };
+        let write = match ModbusWrite::decode(&req.data) {
+            Ok(w) => w,
+            Err(e) => {
+                let _ = req.result_tx.try_send(Err(e));
+                continue;
+            }
+        };
+
+        let result = {
+            let mut ctx = ctx.lock().await;
+            ctx.set_slave(Slave(write.unit_id));
+            let result = write.execute(&mut ctx).await;
+            // The poll tasks read from the profile's unit
+            ctx.set_slave(Slave(default_unit));
+            result
+        };
+
+        record_modbus_write(&session_id, source_idx, &write, &result);
+        let _ = req.result_tx.try_send(result);
+    }
+}
+
+/// Log a Modbus write from the transmit path and add it to the transmit
+/// history, the unit ID in the bus column.
+fn record_modbus_write(session_id: &str, source_idx: usize, write: &ModbusWrite, result: &Result<(), String>) {
+    match result {
+        Ok(()) => tlog!("[multi_source] Modbus source {} wrote {}: {:?}", source_idx, write.describe(), write.values),
+        Err(e) => tlog!("[multi_source] Modbus source {} write {} failed: {}", source_idx, write.describe(), e),
+    }
+    crate::transmit_history::write_entry(
+        session_id, "modbus",
+        Some(write.address as i64),
+        Some(write.values.len() as i64),
+        &write.data_bytes(),
+        write.unit_id as i64,
+        false, false,
+        result.is_ok(),
+        result.as_ref().err().map(String::as_str),
+    );
+    crate::ws::dispatch::send_transmit_updated(crate::transmit_history::count());
+}
+
 /// Run a single Modbus poll task (one register read operation on a timer)
 async fn run_modbus_poll_task(
     source_idx: usize,
@@ -1177,6 +1711,11 @@ async fn run_modbus_poll_task(
                     source_address: None,
                     incomplete: None,
                     direction: Some("rx".to_string()),
+                    link: None,
+                    clock: None,
+                    is_error: None,
+                    error_class: None,
+                    brs: None,
                 };
 
                 let _ = tx
@@ -1206,15 +1745,23 @@ async fn run_modbus_poll_task(
 }
 
 // ============================================================================
-// Modbus TCP Server Source (MITM)
+// Modbus TCP Server Source (MITM / Simulator)
 // ============================================================================
 
 /// Modbus TCP server source: listens for incoming Modbus TCP connections and logs requests.
 /// This enables MITM scenarios where WireTAP sits between a Modbus master and slave.
+///
+/// With a register map (simulator role) it also answers every request from the
+/// map, logging the responses as tx frames, and applies writes from the
+/// session's transmit path to the map so the Transmit app can set the values
+/// clients read.
+#[allow(clippy::too_many_arguments)]
 async fn run_modbus_tcp_server(
+    session_id: String,
     source_idx: usize,
     profile: &IOProfile,
     bus_mappings: Vec<BusMapping>,
+    register_map: Option<RegisterMap>,
     stop_flag: Arc<AtomicBool>,
     tx: mpsc::Sender<SourceMessage>,
 ) {
@@ -1239,6 +1786,16 @@ async fn run_modbus_tcp_server(
         .map(|m| m.output_bus)
         .unwrap_or(0);
 
+    if register_map.as_ref().is_some_and(RegisterMap::is_empty) {
+        let _ = tx
+            .send(SourceMessage::Error(
+                source_idx,
+                "Modbus simulator needs a catalog with Modbus registers to serve".to_string(),
+            ))
+            .await;
+        return;
+    }
+
     let bind_addr = format!("{}:{}", host, port);
 
     let listener = match tokio::net::TcpListener::bind(&bind_addr).await {
@@ -1263,11 +1820,37 @@ async fn run_modbus_tcp_server(
         ))
         .await;
 
+    let register_map = register_map.map(|map| {
+        tlog!(
+            "[multi_source] Modbus TCP server source {} simulating {} register(s) on unit(s) {:?}",
+            source_idx, map.len(), map.units()
+        );
+        Arc::new(Mutex::new(map))
+    });
+
     tlog!(
         "[multi_source] Modbus TCP server source {} listening on {}, output_bus={}",
         source_idx, bind_addr, output_bus
     );
 
+    // The Transmit app sets simulated values through the write path
+    let write_handle = match &register_map {
+        Some(map) => {
+            let (transmit_tx, transmit_rx) = std_mpsc::sync_channel::<TransmitRequest>(32);
+            let _ = tx
+                .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
+                .await;
+            Some(tokio::spawn(run_modbus_simulator_write_task(
+                session_id,
+                source_idx,
+                map.clone(),
+                transmit_rx,
+                stop_flag.clone(),
+            )))
+        }
+        None => None,
+    };
+
     // Accept connections until stopped
     loop {
         if stop_flag.load(Ordering::Relaxed) {
@@ -1284,6 +1867,7 @@ async fn run_modbus_tcp_server(
 
                 let tx_clone = tx.clone();
                 let stop_clone = stop_flag.clone();
+                let map_clone = register_map.clone();
 
                 // Handle connection in a separate task
                 tokio::spawn(async move {
@@ -1292,6 +1876,7 @@ async fn run_modbus_tcp_server(
                         output_bus,
                         stream,
                         peer_addr,
+                        map_clone,
                         stop_clone,
                         tx_clone,
                     )
@@ -1310,23 +1895,56 @@ async fn run_modbus_tcp_server(
         }
     }
 
+    if let Some(handle) = write_handle {
+        let _ = handle.await;
+    }
+
     let _ = tx
         .send(SourceMessage::Ended(source_idx, "stopped".to_string()))
         .await;
 }
 
+/// Build a log frame for one Modbus TCP message: frame_id is the function
+/// code, bytes are the unit ID followed by the PDU.
+fn modbus_server_frame(output_bus: u8, unit_id: u8, pdu: &[u8], direction: &str) -> FrameMessage {
+    let mut bytes = Vec::with_capacity(pdu.len() + 1);
+    bytes.push(unit_id);
+    bytes.extend_from_slice(pdu);
+    FrameMessage {
+        protocol: "modbus".to_string(),
+        timestamp_us: now_us(),
+        frame_id: pdu.first().copied().unwrap_or(0) as u32,
+        bus: output_bus,
+        dlc: bytes.len() as u8,
+        bytes,
+        is_extended: false,
+        is_fd: false,
+        source_address: None,
+        incomplete: None,
+        direction: Some(direction.to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    }
+}
+
 /// Handle a single Modbus TCP server connection, parsing MBAP frames and logging requests.
+/// With a register map, each request is answered and the response logged too.
 async fn handle_modbus_server_connection(
     source_idx: usize,
     output_bus: u8,
     mut stream: tokio::net::TcpStream,
     peer_addr: SocketAddr,
+    register_map: Option<Arc<Mutex<RegisterMap>>>,
     stop_flag: Arc<AtomicBool>,
     tx: mpsc::Sender<SourceMessage>,
 ) {
-    use tokio::io::AsyncReadExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
     let mut buf = [0u8; 512];
+    let mut pending: Vec<u8> = Vec::new();
 
     loop {
         if stop_flag.load(Ordering::Relaxed) {
@@ -1342,36 +1960,31 @@ async fn handle_modbus_server_connection(
                 break;
             }
             Ok(Ok(n)) => {
-                // Parse MBAP header (7 bytes): transaction_id(2) + protocol_id(2) + length(2) + unit_id(1)
-                if n >= 8 {
-                    let function_code = buf[7];
-                    // Use function code as frame_id for logging
-                    let frame_id = function_code as u32;
-
-                    // Extract the PDU (everything after MBAP header)
-                    let pdu_bytes = buf[6..n].to_vec(); // unit_id + function_code + data
-
-                    let frame = FrameMessage {
-                        protocol: "modbus".to_string(),
-                        timestamp_us: now_us(),
-                        frame_id,
-                        bus: output_bus,
-                        dlc: pdu_bytes.len() as u8,
-                        bytes: pdu_bytes,
-                        is_extended: false,
-                        is_fd: false,
-                        source_address: None,
-                        incomplete: None,
-                        direction: Some("rx".to_string()),
+                pending.extend_from_slice(&buf[..n]);
+                while let Some(request) = MbapFrame::take(&mut pending) {
+                    let mut frames = vec![modbus_server_frame(output_bus, request.unit_id, &request.pdu, "rx")];
+
+                    let response = match &register_map {
+                        Some(map) => map.lock().await.respond(request.unit_id, &request.pdu),
+                        // Logging only; the master gets no answer
+                        None => None,
                     };
+                    if let Some(pdu) = response {
+                        let reply = MbapFrame::encode(request.transaction_id, request.unit_id, &pdu);
+                        if let Err(e) = stream.write_all(&reply).await {
+                            tlog!(
+                                "[multi_source] Modbus TCP server source {} write error to {}: {}",
+                                source_idx, peer_addr, e
+                            );
+                            return;
+                        }
+                        frames.push(modbus_server_frame(output_bus, request.unit_id, &pdu, "tx"));
+                    }
 
                     let _ = tx
-                        .send(SourceMessage::Frames(source_idx, vec![frame]))
+                        .send(SourceMessage::Frames(source_idx, frames))
                         .await;
                 }
-
-                // For now, don't send any response (logging only).
-                // Future: relay to paired client interface for full MITM.
             }
             Ok(Err(e)) => {
                 tlog!(
@@ -1387,6 +2000,36 @@ async fn handle_modbus_server_connection(
     }
 }
 
+/// Apply Modbus writes queued by the session's transmit path to a simulator's
+/// register map, recording each in the transmit history.
+async fn run_modbus_simulator_write_task(
+    session_id: String,
+    source_idx: usize,
+    register_map: Arc<Mutex<RegisterMap>>,
+    transmit_rx: std_mpsc::Receiver<TransmitRequest>,
+    stop_flag: Arc<AtomicBool>,
+) {
+    while !stop_flag.load(Ordering::Relaxed) {
+        let req = match transmit_rx.try_recv() {
+            Ok(req) => req,
+            Err(std_mpsc::TryRecvError::Empty) => {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                continue;
+            }
+            Err(std_mpsc::TryRecvError::Disconnected) => break,
+        };
+        let result = match ModbusWrite::decode(&req.data) {
+            Ok(write) => {
+                let result = register_map.lock().await.apply_write(&write);
+                record_modbus_write(&session_id, source_idx, &write, &result);
+                result
+            }
+            Err(e) => Err(e),
+        };
+        let _ = req.result_tx.try_send(result);
+    }
+}
+
 // ============================================================================
 // Data Conversion Helpers (shared with modbus_tcp/reader.rs)
 // ============================================================================
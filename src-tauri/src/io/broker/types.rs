@@ -8,6 +8,9 @@ use std::sync::{Arc, Mutex};
 use crate::io::gvret::BusMapping;
 use crate::io::modbus_tcp::PollGroup;
 use crate::io::types::{ControlSender, TransmitSender};
+use crate::settings::IOProfile;
+
+use super::reconnect::ReconnectPolicy;
 
 /// Modbus interface role in a multi-source session
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -17,6 +20,19 @@ pub enum ModbusRole {
     Client,
     /// Server role: accept incoming Modbus TCP connections (for MITM)
     Server,
+    /// Server role that answers requests from the catalog's register map,
+    /// simulating a device for testing SCADA clients
+    Simulator,
+}
+
+impl ModbusRole {
+    /// The role set on a modbus_tcp profile (`connection.role`), if any
+    pub fn from_profile(profile: &IOProfile) -> Option<Self> {
+        profile
+            .connection
+            .get("role")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
 }
 
 /// Configuration for a single source in a multi-source session
@@ -66,12 +82,19 @@ pub struct SourceConfig {
     /// Modbus poll groups (shared across all Modbus interfaces in a session)
     #[serde(default)]
     pub modbus_polls: Option<Vec<PollGroup>>,
-    /// Modbus interface role (client or server)
+    /// Modbus interface role (None = the profile's role, else client)
     #[serde(default)]
     pub modbus_role: Option<ModbusRole>,
     /// Modbus max consecutive register errors before stopping (0 = never stop)
     #[serde(default)]
     pub max_register_errors: Option<u32>,
+    /// Restart the source with backoff when it drops (None = the source ends)
+    #[serde(default)]
+    pub reconnect: Option<ReconnectPolicy>,
+    /// Keep the adapter off the bus: no transmit, no ACK (None = the profile's
+    /// setting; always resolved at session creation for kinds that have one)
+    #[serde(default)]
+    pub listen_only: Option<bool>,
 }
 
 /// Transmit routing info: maps output bus to source and device bus
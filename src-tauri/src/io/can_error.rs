@@ -0,0 +1,169 @@
+// ui/src-tauri/src/io/can_error.rs
+//
+// CAN error frames in the SocketCAN layout (linux/can/error.h), which the
+// kernel, candleLight gs_usb firmware and error-forwarding GVRET firmware all
+// use: CAN_ERR_FLAG in the ID word, the error classes in its low bits, and
+// details in the 8 data bytes (controller state in byte 1, protocol
+// violation type in byte 2, its location in byte 3, transceiver state in
+// byte 4, TX/RX error counters in bytes 6 and 7).
+//
+// Error frames keep CAN_ERR_FLAG in `frame_id` and are marked extended, so
+// the ID can never collide with a real 29-bit identifier in views and
+// decoders that don't look at `is_error`.
+
+use crate::io::{CanErrorClass, FrameMessage};
+
+/// Set in the ID word of an error frame
+pub const CAN_ERR_FLAG: u32 = 0x2000_0000;
+/// Error class bits of the ID word
+pub const CAN_ERR_MASK: u32 = 0x1FFF_FFFF;
+
+// Error classes (ID word)
+const CAN_ERR_TX_TIMEOUT: u32 = 0x001;
+const CAN_ERR_LOSTARB: u32 = 0x002;
+const CAN_ERR_CRTL: u32 = 0x004;
+const CAN_ERR_PROT: u32 = 0x008;
+const CAN_ERR_TRX: u32 = 0x010;
+const CAN_ERR_ACK: u32 = 0x020;
+const CAN_ERR_BUSOFF: u32 = 0x040;
+const CAN_ERR_RESTARTED: u32 = 0x100;
+
+// Controller state (data[1])
+const CAN_ERR_CRTL_OVERFLOW: u8 = 0x01 | 0x02;
+const CAN_ERR_CRTL_WARNING: u8 = 0x04 | 0x08;
+const CAN_ERR_CRTL_PASSIVE: u8 = 0x10 | 0x20;
+
+// Protocol violation type (data[2]) and location (data[3])
+const CAN_ERR_PROT_BIT: u8 = 0x01 | 0x08 | 0x10;
+const CAN_ERR_PROT_BIT_ERROR: u8 = 0x01;
+const CAN_ERR_PROT_FORM: u8 = 0x02;
+const CAN_ERR_PROT_STUFF: u8 = 0x04;
+const CAN_ERR_PROT_LOC_CRC_SEQ: u8 = 0x08;
+const CAN_ERR_PROT_LOC_ACK: u8 = 0x19;
+
+/// Whether a raw ID word marks an error frame.
+pub fn is_error_id(can_id: u32) -> bool {
+    can_id & CAN_ERR_FLAG != 0
+}
+
+/// Most severe class an error frame reports.
+pub fn classify(can_id: u32, data: &[u8]) -> CanErrorClass {
+    let class = can_id & CAN_ERR_MASK;
+    let byte = |i: usize| data.get(i).copied().unwrap_or(0);
+    let (ctrl, prot_type, prot_loc) = (byte(1), byte(2), byte(3));
+
+    if class & CAN_ERR_BUSOFF != 0 {
+        CanErrorClass::BusOff
+    } else if class & CAN_ERR_ACK != 0 || (class & CAN_ERR_PROT != 0 && prot_loc == CAN_ERR_PROT_LOC_ACK) {
+        CanErrorClass::Ack
+    } else if class & CAN_ERR_PROT != 0 && prot_type & CAN_ERR_PROT_STUFF != 0 {
+        CanErrorClass::Stuff
+    } else if class & CAN_ERR_PROT != 0 && prot_type & CAN_ERR_PROT_FORM != 0 {
+        CanErrorClass::Form
+    } else if class & CAN_ERR_PROT != 0 && prot_type & CAN_ERR_PROT_BIT != 0 {
+        CanErrorClass::Bit
+    } else if class & CAN_ERR_PROT != 0 && prot_loc == CAN_ERR_PROT_LOC_CRC_SEQ {
+        CanErrorClass::Crc
+    } else if class & CAN_ERR_CRTL != 0 && ctrl & CAN_ERR_CRTL_PASSIVE != 0 {
+        CanErrorClass::ErrorPassive
+    } else if class & CAN_ERR_CRTL != 0 && ctrl & CAN_ERR_CRTL_WARNING != 0 {
+        CanErrorClass::ErrorWarning
+    } else if class & CAN_ERR_CRTL != 0 && ctrl & CAN_ERR_CRTL_OVERFLOW != 0 {
+        CanErrorClass::Overflow
+    } else if class & CAN_ERR_LOSTARB != 0 {
+        CanErrorClass::ArbitrationLost
+    } else if class & CAN_ERR_TX_TIMEOUT != 0 {
+        CanErrorClass::TxTimeout
+    } else if class & CAN_ERR_TRX != 0 {
+        CanErrorClass::Transceiver
+    } else if class & CAN_ERR_RESTARTED != 0 {
+        CanErrorClass::Restarted
+    } else {
+        CanErrorClass::Other
+    }
+}
+
+/// Error kind as logged by tools that don't record the SocketCAN layout
+/// (PCAN-View traces, ASAM MDF4 CAN_ErrorFrame groups).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Violation {
+    Bit,
+    Form,
+    Stuff,
+    Crc,
+    Ack,
+    Other,
+}
+
+/// An error frame for a logged violation, in the SocketCAN layout.
+pub fn violation_frame(violation: Violation, bus: u8, timestamp_us: u64) -> FrameMessage {
+    let (class, prot_type, prot_loc) = match violation {
+        Violation::Bit => (CAN_ERR_PROT, CAN_ERR_PROT_BIT_ERROR, 0),
+        Violation::Form => (CAN_ERR_PROT, CAN_ERR_PROT_FORM, 0),
+        Violation::Stuff => (CAN_ERR_PROT, CAN_ERR_PROT_STUFF, 0),
+        Violation::Crc => (CAN_ERR_PROT, 0, CAN_ERR_PROT_LOC_CRC_SEQ),
+        Violation::Ack => (CAN_ERR_ACK, 0, 0),
+        Violation::Other => (0, 0, 0),
+    };
+    error_frame(CAN_ERR_FLAG | class, &[0, 0, prot_type, prot_loc, 0, 0, 0, 0], bus, timestamp_us)
+}
+
+/// An error frame from its raw ID word and data bytes.
+pub fn error_frame(can_id: u32, data: &[u8], bus: u8, timestamp_us: u64) -> FrameMessage {
+    let data = &data[..data.len().min(8)];
+    FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us,
+        frame_id: CAN_ERR_FLAG | (can_id & CAN_ERR_MASK),
+        bus,
+        dlc: data.len() as u8,
+        bytes: data.to_vec(),
+        is_extended: true,
+        is_fd: false,
+        source_address: None,
+        incomplete: None,
+        direction: Some("rx".to_string()),
+        link: None,
+        clock: None,
+        is_error: Some(true),
+        error_class: Some(classify(can_id, data)),
+        brs: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classes_follow_severity() {
+        let stuff = [0, 0, CAN_ERR_PROT_STUFF, 0, 0, 0, 0, 0];
+        assert_eq!(classify(CAN_ERR_FLAG | CAN_ERR_PROT, &stuff), CanErrorClass::Stuff);
+        // Bus-off outranks whatever else the frame reports
+        assert_eq!(classify(CAN_ERR_FLAG | CAN_ERR_PROT | CAN_ERR_BUSOFF, &stuff), CanErrorClass::BusOff);
+        assert_eq!(classify(CAN_ERR_FLAG | CAN_ERR_ACK, &[0; 8]), CanErrorClass::Ack);
+        assert_eq!(classify(CAN_ERR_FLAG | CAN_ERR_PROT, &[0, 0, 0, CAN_ERR_PROT_LOC_ACK]), CanErrorClass::Ack);
+        assert_eq!(classify(CAN_ERR_FLAG | CAN_ERR_CRTL, &[0, 0x20]), CanErrorClass::ErrorPassive);
+        assert_eq!(classify(CAN_ERR_FLAG, &[]), CanErrorClass::Other);
+    }
+
+    #[test]
+    fn test_error_frames_stay_clear_of_real_ids() {
+        let frame = error_frame(CAN_ERR_FLAG | CAN_ERR_BUSOFF, &[0; 8], 1, 42);
+        assert!(frame.frame_id > CAN_ERR_MASK);
+        assert!(is_error_id(frame.frame_id));
+        assert_eq!((frame.is_error, frame.error_class), (Some(true), Some(CanErrorClass::BusOff)));
+        assert!(!is_error_id(0x1FFF_FFFF));
+    }
+
+    #[test]
+    fn test_violations_classify_as_themselves() {
+        let class = |v| violation_frame(v, 0, 0).error_class.unwrap();
+        assert_eq!(class(Violation::Bit), CanErrorClass::Bit);
+        assert_eq!(class(Violation::Form), CanErrorClass::Form);
+        assert_eq!(class(Violation::Stuff), CanErrorClass::Stuff);
+        assert_eq!(class(Violation::Crc), CanErrorClass::Crc);
+        assert_eq!(class(Violation::Ack), CanErrorClass::Ack);
+        assert_eq!(class(Violation::Other), CanErrorClass::Other);
+    }
+}
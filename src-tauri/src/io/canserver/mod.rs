@@ -0,0 +1,184 @@
+// ui/src-tauri/src/io/canserver/mod.rs
+//
+// CANserver / panda UDP streaming source. A CANserver (and other devices
+// speaking comma.ai's panda UDP protocol) streams the CAN traffic it sees to
+// whichever client says "hello" to its UDP port (1338). The client has to
+// repeat the hello as a heartbeat, or the device stops sending after a few
+// seconds; "bye" stops it straight away.
+//
+// Each datagram holds one or more 16-byte panda records:
+//
+//   word 0 (u32 LE): 11-bit ID << 21, or 29-bit ID << 3 with bit 2 set
+//   word 1 (u32 LE): DLC in bits 0-3, bus in bits 4-11, bus time in 16-31
+//   8 data bytes (only DLC of them are valid)
+//
+// Bus numbers are the device's (0-2 on a panda) and are mapped onto session
+// buses through the profile's interfaces, like a multi-bus GVRET. Bus values
+// from 128 up are a panda's receipts for its own transmits and are skipped.
+// Receive only.
+
+pub mod reader;
+
+use serde_json::Value;
+
+use crate::io::{now_us, FrameMessage};
+
+// Internal items used by multi_source
+pub(crate) use reader::run_source as run_canserver_source;
+pub use reader::probe_canserver;
+
+/// UDP port CANserver listens on in panda mode
+pub const DEFAULT_PORT: u16 = 1338;
+
+/// Size of one panda record
+pub const RECORD_LEN: usize = 16;
+
+/// Word 0 flag: 29-bit identifier
+const EXTENDED_FLAG: u32 = 0x04;
+
+/// Bus values at or above this are transmit receipts, not bus traffic
+const RETURNED_BUS: u8 = 0x80;
+
+/// Client → device messages
+pub const HELLO: &[u8] = b"hello";
+pub const BYE: &[u8] = b"bye";
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// CANserver source settings, parsed from a profile's connection
+#[derive(Clone, Debug, PartialEq)]
+pub struct CanServerConfig {
+    pub host: String,
+    pub port: u16,
+    /// Time between heartbeat hellos
+    pub heartbeat_ms: u64,
+    /// Silence after which the source reports that no data is arriving
+    pub timeout_sec: f64,
+}
+
+fn number(connection: &Value, key: &str) -> Option<f64> {
+    connection
+        .get(key)
+        .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+}
+
+impl CanServerConfig {
+    /// Read the settings from a profile's connection map.
+    pub fn from_connection(connection: &Value) -> Result<Self, String> {
+        let host = connection
+            .get("host")
+            .and_then(|v| v.as_str())
+            .map(|h| h.trim())
+            .filter(|h| !h.is_empty())
+            .ok_or("Host is required")?
+            .to_string();
+        let port = number(connection, "port").unwrap_or(DEFAULT_PORT as f64);
+        if !(1.0..=65535.0).contains(&port) {
+            return Err(format!("Invalid UDP port {}", port));
+        }
+        Ok(CanServerConfig {
+            host,
+            port: port as u16,
+            heartbeat_ms: number(connection, "heartbeat_ms").map_or(1000, |v| v as u64).clamp(100, 4000),
+            timeout_sec: number(connection, "timeout").unwrap_or(5.0).max(0.5),
+        })
+    }
+}
+
+// ============================================================================
+// Record Parsing
+// ============================================================================
+
+/// Parse one panda record. Returns None for transmit receipts.
+pub fn parse_record(record: &[u8; RECORD_LEN]) -> Option<FrameMessage> {
+    let word0 = u32::from_le_bytes([record[0], record[1], record[2], record[3]]);
+    let word1 = u32::from_le_bytes([record[4], record[5], record[6], record[7]]);
+    let bus = ((word1 >> 4) & 0xFF) as u8;
+    if bus >= RETURNED_BUS {
+        return None;
+    }
+    let is_extended = word0 & EXTENDED_FLAG != 0;
+    let frame_id = if is_extended { word0 >> 3 } else { word0 >> 21 };
+    let dlc = ((word1 & 0x0F) as usize).min(8);
+    Some(FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us: now_us(),
+        frame_id,
+        bus,
+        dlc: dlc as u8,
+        bytes: record[8..8 + dlc].to_vec(),
+        is_extended,
+        is_fd: false,
+        source_address: None,
+        incomplete: None,
+        direction: Some("rx".to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    })
+}
+
+/// Parse every whole record in a datagram (a trailing partial record is
+/// ignored).
+pub fn parse_datagram(datagram: &[u8]) -> Vec<FrameMessage> {
+    datagram
+        .chunks_exact(RECORD_LEN)
+        .filter_map(|chunk| parse_record(chunk.try_into().ok()?))
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(word0: u32, word1: u32, data: &[u8]) -> Vec<u8> {
+        let mut r = Vec::with_capacity(RECORD_LEN);
+        r.extend_from_slice(&word0.to_le_bytes());
+        r.extend_from_slice(&word1.to_le_bytes());
+        r.extend_from_slice(data);
+        r.resize(RECORD_LEN, 0);
+        r
+    }
+
+    #[test]
+    fn test_parse_datagram() {
+        let mut datagram = record(0x3E9 << 21, 0x1234_0018, &[1, 2, 3, 4, 5, 6, 7, 8]);
+        datagram.extend(record((0x18DA_F110 << 3) | EXTENDED_FLAG, 0x0003, &[0xAA, 0xBB, 0xCC]));
+        datagram.extend(record(0x100 << 21, 0x0802, &[9, 9])); // transmit receipt (bus 0x80)
+        datagram.extend([0u8; 5]); // partial record
+
+        let frames = parse_datagram(&datagram);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].frame_id, 0x3E9);
+        assert_eq!(frames[0].bus, 1);
+        assert_eq!(frames[0].bytes, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(!frames[0].is_extended);
+        assert_eq!(frames[1].frame_id, 0x18DA_F110);
+        assert!(frames[1].is_extended);
+        assert_eq!(frames[1].bus, 0);
+        assert_eq!(frames[1].bytes, vec![0xAA, 0xBB, 0xCC]);
+    }
+
+    #[test]
+    fn test_config_from_connection() {
+        let config = CanServerConfig::from_connection(&json!({"host": " 192.168.4.1 "})).unwrap();
+        assert_eq!(config.host, "192.168.4.1");
+        assert_eq!(config.port, DEFAULT_PORT);
+        assert_eq!(config.heartbeat_ms, 1000);
+
+        let config = CanServerConfig::from_connection(&json!({"host": "canserver.local", "port": "1400"})).unwrap();
+        assert_eq!(config.port, 1400);
+
+        assert!(CanServerConfig::from_connection(&json!({})).is_err());
+        assert!(CanServerConfig::from_connection(&json!({"host": "x", "port": 70000})).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+// ui/src-tauri/src/io/canserver/reader.rs
+//
+// UDP reader for CANserver / panda streaming. The socket is connected to the
+// device so only its datagrams are received; the hello heartbeat is sent
+// from the read loop.
+
+use std::collections::BTreeSet;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+
+use super::{parse_datagram, CanServerConfig, BYE, HELLO};
+use crate::io::error::IoError;
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::types::SourceMessage;
+use crate::io::FrameMessage;
+
+/// Largest datagram read (a panda batches at most 512 bytes of records)
+const MAX_DATAGRAM: usize = 2048;
+
+/// How long a probe listens once the first datagram has arrived
+const PROBE_LISTEN: Duration = Duration::from_millis(1000);
+
+async fn connect(host: &str, port: u16) -> Result<UdpSocket, IoError> {
+    let device = format!("canserver({}:{})", host, port);
+    let socket = UdpSocket::bind(("0.0.0.0", 0))
+        .await
+        .map_err(|e| IoError::connection(&device, e.to_string()))?;
+    socket
+        .connect((host, port))
+        .await
+        .map_err(|e| IoError::connection(&device, e.to_string()))?;
+    socket
+        .send(HELLO)
+        .await
+        .map_err(|e| IoError::connection(&device, e.to_string()))?;
+    Ok(socket)
+}
+
+/// A datagram refused by the host (ICMP port unreachable) shows up as an error
+/// on the next receive; it means the device isn't listening yet, not that the
+/// socket is broken.
+fn is_transient(e: &std::io::Error) -> bool {
+    e.kind() == std::io::ErrorKind::ConnectionRefused
+}
+
+// ============================================================================
+// Device Probing
+// ============================================================================
+
+/// Say hello and listen briefly; returns the device buses seen, in order.
+pub async fn probe_canserver(host: &str, port: u16, timeout_sec: f64) -> Result<Vec<u8>, IoError> {
+    let device = format!("canserver({}:{})", host, port);
+    let socket = connect(host, port).await?;
+
+    let mut buf = [0u8; MAX_DATAGRAM];
+    let mut buses = BTreeSet::new();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs_f64(timeout_sec);
+    let mut listen_until: Option<tokio::time::Instant> = None;
+
+    loop {
+        let until = listen_until.unwrap_or(deadline);
+        let remaining = until.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        match tokio::time::timeout(remaining, socket.recv(&mut buf)).await {
+            Ok(Ok(n)) => {
+                buses.extend(parse_datagram(&buf[..n]).iter().map(|f| f.bus));
+                listen_until.get_or_insert_with(|| tokio::time::Instant::now() + PROBE_LISTEN);
+            }
+            Ok(Err(e)) if is_transient(&e) => {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                let _ = socket.send(HELLO).await;
+            }
+            Ok(Err(e)) => return Err(IoError::read(&device, e.to_string())),
+            Err(_) => break,
+        }
+    }
+    let _ = socket.send(BYE).await;
+
+    if listen_until.is_none() {
+        return Err(IoError::timeout(&device, "stream data"));
+    }
+    tlog!("[canserver] Probe of {}:{} saw buses {:?}", host, port, buses);
+    Ok(buses.into_iter().collect())
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Run a CANserver source and send frames to the merge task
+pub async fn run_source(
+    source_idx: usize,
+    config: CanServerConfig,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let socket = match connect(&config.host, config.port).await {
+        Ok(s) => s,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e.to_string())).await;
+            return;
+        }
+    };
+
+    let address = format!("{}:{}", config.host, config.port);
+    tlog!("[canserver] Source {} streaming from {}", source_idx, address);
+    let _ = tx
+        .send(SourceMessage::Connected(source_idx, "canserver".to_string(), address.clone(), None))
+        .await;
+
+    let heartbeat = Duration::from_millis(config.heartbeat_ms);
+    let silence_limit = Duration::from_secs_f64(config.timeout_sec);
+    let mut last_hello = Instant::now();
+    let mut last_data = Instant::now();
+    let mut silent = false;
+    let mut rx_frames: u64 = 0;
+    let mut bus_filtered: u64 = 0;
+    let mut buf = [0u8; MAX_DATAGRAM];
+
+    while !stop_flag.load(Ordering::SeqCst) {
+        if last_hello.elapsed() >= heartbeat {
+            let _ = socket.send(HELLO).await;
+            last_hello = Instant::now();
+        }
+
+        match tokio::time::timeout(Duration::from_millis(50), socket.recv(&mut buf)).await {
+            Ok(Ok(n)) => {
+                last_data = Instant::now();
+                if silent {
+                    tlog!("[canserver] Source {}: data resumed from {}", source_idx, address);
+                    silent = false;
+                }
+                let frames = parse_datagram(&buf[..n]);
+                let total = frames.len();
+                rx_frames += total as u64;
+                let mapped: Vec<FrameMessage> = frames
+                    .into_iter()
+                    .filter_map(|mut frame| apply_bus_mapping(&mut frame, &bus_mappings).then_some(frame))
+                    .collect();
+                bus_filtered += (total - mapped.len()) as u64;
+                if !mapped.is_empty() {
+                    let _ = tx.send(SourceMessage::Frames(source_idx, mapped)).await;
+                }
+            }
+            Ok(Err(e)) if is_transient(&e) => {}
+            Ok(Err(e)) => {
+                let _ = tx
+                    .send(SourceMessage::Error(source_idx, format!("Read error: {}", e)))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                // Timeout - continue
+            }
+        }
+
+        // UDP has no disconnect; keep saying hello and note the silence once
+        if !silent && last_data.elapsed() >= silence_limit {
+            tlog!(
+                "[canserver] Source {}: no data from {} for {:.1}s (device offline or not in panda mode?)",
+                source_idx, address, silence_limit.as_secs_f64()
+            );
+            silent = true;
+        }
+    }
+
+    let _ = socket.send(BYE).await;
+
+    tlog!(
+        "[canserver] Source {} ended: rx={}, bus_filtered={}",
+        source_idx, rx_frames, bus_filtered
+    );
+
+    let _ = tx
+        .send(SourceMessage::Ended(source_idx, "stopped".to_string()))
+        .await;
+}
@@ -10,6 +10,7 @@
 // - slcan/codec.rs - slcan ASCII protocol
 // - gs_usb/codec.rs - gs_usb/candleLight protocol
 // - socketcan/codec.rs - Linux SocketCAN protocol
+// - custom_serial/codec.rs - user-described binary CAN-over-serial formats
 //
 // The FrameCodec trait is designed for single-frame operations. Protocols that
 // require buffer-based streaming (like GVRET receive) provide additional
@@ -28,7 +29,9 @@ use crate::io::{CanTransmitFrame, FrameMessage};
 /// Trait for CAN frame codecs.
 ///
 /// Each protocol implements this trait to provide unified encode/decode operations.
-/// The associated types define the protocol-specific raw frame formats.
+/// The associated types define the protocol-specific raw frame formats. Fixed
+/// protocols are unit structs; a codec built from a runtime description
+/// (`CustomSerialCodec`) carries that description in `self`.
 pub trait FrameCodec {
     /// The raw frame type for decoding (e.g., byte slice, ASCII string)
     type RawFrame: ?Sized;
@@ -40,13 +43,13 @@ pub trait FrameCodec {
     ///
     /// Returns `Ok(FrameMessage)` on success, or `Err(IoError)` if the frame
     /// is malformed or cannot be parsed.
-    fn decode(raw: &Self::RawFrame) -> Result<FrameMessage, IoError>;
+    fn decode(&self, raw: &Self::RawFrame) -> Result<FrameMessage, IoError>;
 
     /// Encode a transmit frame for the protocol.
     ///
     /// Returns `Ok(EncodedFrame)` on success, or `Err(IoError)` if the frame
     /// cannot be encoded (e.g., invalid parameters).
-    fn encode(frame: &CanTransmitFrame) -> Result<Self::EncodedFrame, IoError>;
+    fn encode(&self, frame: &CanTransmitFrame) -> Result<Self::EncodedFrame, IoError>;
 }
 
 // ============================================================================
@@ -68,6 +71,10 @@ pub use super::gs_usb::codec::GsUsbCodec;
 #[cfg(target_os = "linux")]
 pub use super::socketcan::codec::{SocketCanCodec, SocketCanEncodedFrame};
 
+// Custom CAN-over-serial codec (desktop only - requires serial port)
+#[cfg(not(target_os = "ios"))]
+pub use super::custom_serial::codec::{CustomFrameFormat, CustomSerialCodec};
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -92,7 +99,7 @@ mod tests {
     #[test]
     fn test_gvret_roundtrip() {
         let frame = make_test_frame();
-        let encoded = GvretCodec::encode(&frame).expect("encode failed");
+        let encoded = GvretCodec.encode(&frame).expect("encode failed");
         // GVRET transmit format starts with 0xF1 0x00
         assert_eq!(encoded[0], 0xF1);
         assert_eq!(encoded[1], 0x00);
@@ -102,7 +109,7 @@ mod tests {
     #[cfg(not(target_os = "ios"))]
     fn test_slcan_roundtrip() {
         let frame = make_test_frame();
-        let encoded = SlcanCodec::encode(&frame).expect("encode failed");
+        let encoded = SlcanCodec.encode(&frame).expect("encode failed");
         // slcan starts with 't' or 'T' for standard/extended
         assert!(encoded.starts_with(b"t") || encoded.starts_with(b"T"));
         // Should end with carriage return
@@ -113,7 +120,7 @@ mod tests {
     #[cfg(any(target_os = "windows", target_os = "macos"))]
     fn test_gs_usb_encode() {
         let frame = make_test_frame();
-        let encoded = GsUsbCodec::encode(&frame).expect("encode failed");
+        let encoded = GsUsbCodec.encode(&frame).expect("encode failed");
         // gs_usb is fixed 20 bytes
         assert_eq!(encoded.len(), 20);
         // echo_id is at bytes 0-3 (should be TX_ECHO_ID = 0)
@@ -124,7 +131,7 @@ mod tests {
     #[cfg(target_os = "linux")]
     fn test_socketcan_encode_classic() {
         let frame = make_test_frame();
-        let encoded = SocketCanCodec::encode(&frame).expect("encode failed");
+        let encoded = SocketCanCodec.encode(&frame).expect("encode failed");
         match encoded {
             SocketCanEncodedFrame::Classic(buf) => {
                 // Classic frame is 16 bytes
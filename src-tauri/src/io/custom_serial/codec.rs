@@ -0,0 +1,527 @@
+// ui/src-tauri/src/io/custom_serial/codec.rs
+//
+// Generic binary CAN-over-serial codec built from a user-supplied description.
+//
+// Many hobbyist adapters stream CAN frames as a small fixed binary record
+// rather than slcan or GVRET. Instead of a driver per adapter, the profile
+// describes the record and this codec implements FrameCodec from it:
+//
+//   [Header-N bytes][FrameID-1..4 bytes][Length-1byte?][Data-0..8][Checksum-0/1][Footer-N bytes]
+//
+// - header:   sync bytes that start every frame (at least one; used to resync)
+// - id:       1-4 bytes, big- or little-endian; IDs above 0x7FF are extended
+// - length:   optional length byte; without one, every frame carries
+//             `fixed_length` data bytes
+// - checksum: none, 8-bit sum, 8-bit XOR or CRC-8 (poly 0x07), over the
+//             id/length/data bytes (optionally including the header)
+// - footer:   optional trailing bytes (e.g. `\r\n`), checked on receive
+//
+// Transmit uses the same record, so anything the adapter sends can be sent back.
+
+use serde::{Deserialize, Serialize};
+
+use crate::io::codec::FrameCodec;
+use crate::io::error::IoError;
+use crate::io::{now_us, CanTransmitFrame, FrameMessage};
+
+/// Device name used in codec errors
+const DEVICE: &str = "custom_serial";
+
+/// Largest classic CAN payload
+const MAX_DATA_LEN: usize = 8;
+
+/// Highest standard (11-bit) CAN ID
+const CAN_SFF_MAX: u32 = 0x7FF;
+
+/// Mask for extended (29-bit) CAN ID
+const CAN_EFF_MASK: u32 = 0x1FFF_FFFF;
+
+// ============================================================================
+// Format Description
+// ============================================================================
+
+/// Checksum appended after the data bytes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Checksum {
+    #[default]
+    None,
+    /// Sum of covered bytes, modulo 256
+    Sum8,
+    /// XOR of covered bytes
+    Xor8,
+    /// CRC-8, polynomial 0x07, initial value 0x00
+    Crc8,
+}
+
+impl Checksum {
+    fn len(self) -> usize {
+        match self {
+            Checksum::None => 0,
+            _ => 1,
+        }
+    }
+
+    fn compute(self, bytes: &[u8]) -> u8 {
+        match self {
+            Checksum::None => 0,
+            Checksum::Sum8 => bytes.iter().fold(0u8, |acc, &b| acc.wrapping_add(b)),
+            Checksum::Xor8 => bytes.iter().fold(0u8, |acc, &b| acc ^ b),
+            Checksum::Crc8 => bytes.iter().fold(0u8, |mut crc, &b| {
+                crc ^= b;
+                for _ in 0..8 {
+                    crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+                }
+                crc
+            }),
+        }
+    }
+}
+
+/// Description of a binary CAN-over-serial frame record
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomFrameFormat {
+    /// Sync bytes at the start of every frame
+    pub header: Vec<u8>,
+    /// Frame ID width in bytes (1-4)
+    pub id_bytes: u8,
+    /// Frame ID byte order
+    pub id_big_endian: bool,
+    /// A length byte follows the frame ID
+    pub length_byte: bool,
+    /// Data length when there is no length byte
+    pub fixed_length: u8,
+    pub checksum: Checksum,
+    /// Checksum also covers the header bytes
+    pub checksum_includes_header: bool,
+    /// Trailing bytes after the checksum
+    pub footer: Vec<u8>,
+}
+
+impl Default for CustomFrameFormat {
+    fn default() -> Self {
+        Self {
+            header: vec![0xAA],
+            id_bytes: 2,
+            id_big_endian: true,
+            length_byte: true,
+            fixed_length: 8,
+            checksum: Checksum::None,
+            checksum_includes_header: false,
+            footer: Vec::new(),
+        }
+    }
+}
+
+/// Read a byte array from a connection field (`[170, 85]`), as serial
+/// delimiters are stored.
+fn byte_array(value: Option<&serde_json::Value>) -> Option<Vec<u8>> {
+    value.and_then(|v| v.as_array()).map(|arr| {
+        arr.iter()
+            .filter_map(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+            .map(|n| n as u8)
+            .collect()
+    })
+}
+
+impl CustomFrameFormat {
+    /// Build a format from a profile's connection object, filling defaults
+    /// for missing fields, and validate it.
+    pub fn from_connection(connection: &serde_json::Value) -> Result<Self, String> {
+        let defaults = Self::default();
+        let int = |key: &str| {
+            connection
+                .get(key)
+                .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        };
+        let flag = |key: &str| connection.get(key).and_then(|v| v.as_bool());
+
+        let checksum = match connection.get("checksum").and_then(|v| v.as_str()) {
+            None | Some("") => defaults.checksum,
+            Some(name) => serde_json::from_value(serde_json::Value::String(name.to_string()))
+                .map_err(|_| format!("Unknown checksum '{}' (expected none, sum8, xor8 or crc8)", name))?,
+        };
+
+        let format = Self {
+            header: byte_array(connection.get("header")).unwrap_or(defaults.header),
+            id_bytes: int("id_bytes").map(|n| n as u8).unwrap_or(defaults.id_bytes),
+            id_big_endian: flag("id_big_endian").unwrap_or(defaults.id_big_endian),
+            length_byte: flag("length_byte").unwrap_or(defaults.length_byte),
+            fixed_length: int("fixed_length").map(|n| n as u8).unwrap_or(defaults.fixed_length),
+            checksum,
+            checksum_includes_header: flag("checksum_includes_header")
+                .unwrap_or(defaults.checksum_includes_header),
+            footer: byte_array(connection.get("footer")).unwrap_or(defaults.footer),
+        };
+        format.validate()?;
+        Ok(format)
+    }
+
+    /// Check the description can be framed unambiguously.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.header.is_empty() {
+            return Err("Frame format needs at least one header byte to find frame boundaries".to_string());
+        }
+        if !(1..=4).contains(&self.id_bytes) {
+            return Err(format!("Frame ID width must be 1-4 bytes, got {}", self.id_bytes));
+        }
+        if !self.length_byte && (self.fixed_length as usize) > MAX_DATA_LEN {
+            return Err(format!(
+                "Fixed data length must be 0-{} bytes, got {}",
+                MAX_DATA_LEN, self.fixed_length
+            ));
+        }
+        Ok(())
+    }
+
+    /// Bytes before the data: header, ID and optional length byte.
+    fn prefix_len(&self) -> usize {
+        self.header.len() + self.id_bytes as usize + self.length_byte as usize
+    }
+
+    /// Total frame length for a data length.
+    fn frame_len(&self, data_len: usize) -> usize {
+        self.prefix_len() + data_len + self.checksum.len() + self.footer.len()
+    }
+
+    /// Bytes covered by the checksum, given a frame's body (everything up to the checksum).
+    fn checksum_range<'a>(&self, body: &'a [u8]) -> &'a [u8] {
+        if self.checksum_includes_header {
+            body
+        } else {
+            &body[self.header.len()..]
+        }
+    }
+}
+
+// ============================================================================
+// Codec
+// ============================================================================
+
+/// FrameCodec for a user-described binary CAN-over-serial format.
+#[derive(Clone, Debug)]
+pub struct CustomSerialCodec {
+    pub format: CustomFrameFormat,
+}
+
+impl CustomSerialCodec {
+    pub fn new(format: CustomFrameFormat) -> Self {
+        Self { format }
+    }
+
+    /// Data length of the frame at the start of `buf`, once enough bytes are
+    /// present to know it. `Err` means the length byte is out of range.
+    fn data_len(&self, buf: &[u8]) -> Option<Result<usize, IoError>> {
+        let f = &self.format;
+        if !f.length_byte {
+            return Some(Ok(f.fixed_length as usize));
+        }
+        let len = *buf.get(f.prefix_len() - 1)? as usize;
+        if len > MAX_DATA_LEN {
+            return Some(Err(IoError::protocol(
+                DEVICE,
+                format!("length byte {} exceeds {}", len, MAX_DATA_LEN),
+            )));
+        }
+        Some(Ok(len))
+    }
+
+    /// Extract all complete frames from a receive buffer, draining the bytes
+    /// they used. Bytes that don't start a valid frame are skipped one at a
+    /// time until the header is found again. Returns the frames and the
+    /// number of bytes discarded while resyncing.
+    pub fn extract_frames(&self, buf: &mut Vec<u8>) -> (Vec<FrameMessage>, usize) {
+        let header = &self.format.header;
+        let mut frames = Vec::new();
+        let mut dropped = 0;
+
+        loop {
+            // Align the buffer on the next header
+            match buf.windows(header.len()).position(|w| w == header.as_slice()) {
+                Some(0) => {}
+                Some(pos) => {
+                    buf.drain(..pos);
+                    dropped += pos;
+                }
+                None => {
+                    // Keep a possible partial header at the end
+                    let keep = (header.len() - 1).min(buf.len());
+                    let discard = buf.len() - keep;
+                    buf.drain(..discard);
+                    dropped += discard;
+                    break;
+                }
+            }
+
+            let data_len = match self.data_len(buf) {
+                None => break,
+                Some(Ok(len)) => len,
+                Some(Err(_)) => {
+                    buf.drain(..1);
+                    dropped += 1;
+                    continue;
+                }
+            };
+            let frame_len = self.format.frame_len(data_len);
+            if buf.len() < frame_len {
+                break;
+            }
+
+            match self.decode(&buf[..frame_len]) {
+                Ok(frame) => {
+                    frames.push(frame);
+                    buf.drain(..frame_len);
+                }
+                Err(_) => {
+                    // False header match or corrupted frame
+                    buf.drain(..1);
+                    dropped += 1;
+                }
+            }
+        }
+
+        (frames, dropped)
+    }
+}
+
+impl FrameCodec for CustomSerialCodec {
+    /// Raw frame is one complete record, header included
+    type RawFrame = [u8];
+    /// Encoded frame is the record bytes
+    type EncodedFrame = Vec<u8>;
+
+    /// Decode one complete record.
+    fn decode(&self, raw: &[u8]) -> Result<FrameMessage, IoError> {
+        let f = &self.format;
+
+        if !raw.starts_with(&f.header) {
+            return Err(IoError::protocol(DEVICE, "frame does not start with the header"));
+        }
+        let data_len = self
+            .data_len(raw)
+            .ok_or_else(|| IoError::protocol(DEVICE, format!("frame too short: {} bytes", raw.len())))??;
+        let expected = f.frame_len(data_len);
+        if raw.len() != expected {
+            return Err(IoError::protocol(
+                DEVICE,
+                format!("frame length {} bytes, expected {}", raw.len(), expected),
+            ));
+        }
+
+        let id_start = f.header.len();
+        let id_bytes = &raw[id_start..id_start + f.id_bytes as usize];
+        let frame_id = if f.id_big_endian {
+            id_bytes.iter().fold(0u32, |acc, &b| (acc << 8) | b as u32)
+        } else {
+            id_bytes.iter().rev().fold(0u32, |acc, &b| (acc << 8) | b as u32)
+        } & CAN_EFF_MASK;
+
+        let data_start = f.prefix_len();
+        let body_end = data_start + data_len;
+        if f.checksum != Checksum::None {
+            let expected = f.checksum.compute(f.checksum_range(&raw[..body_end]));
+            if raw[body_end] != expected {
+                return Err(IoError::protocol(
+                    DEVICE,
+                    format!("checksum mismatch: got 0x{:02X}, expected 0x{:02X}", raw[body_end], expected),
+                ));
+            }
+        }
+        if !raw.ends_with(&f.footer) {
+            return Err(IoError::protocol(DEVICE, "frame does not end with the footer"));
+        }
+
+        Ok(FrameMessage {
+            protocol: "can".to_string(),
+            timestamp_us: now_us(),
+            frame_id,
+            bus: 0,
+            dlc: data_len as u8,
+            bytes: raw[data_start..body_end].to_vec(),
+            is_extended: frame_id > CAN_SFF_MAX,
+            is_fd: false,
+            source_address: None,
+            incomplete: None,
+            direction: None,
+            link: None,
+            clock: None,
+            is_error: None,
+            error_class: None,
+            brs: None,
+        })
+    }
+
+    /// Encode a frame as a record.
+    fn encode(&self, frame: &CanTransmitFrame) -> Result<Vec<u8>, IoError> {
+        let f = &self.format;
+
+        if frame.is_fd {
+            return Err(IoError::protocol(DEVICE, "CAN FD frames are not supported"));
+        }
+        if frame.is_rtr {
+            return Err(IoError::protocol(DEVICE, "remote frames are not supported"));
+        }
+        let id_bits = f.id_bytes as u32 * 8;
+        if id_bits < 32 && frame.frame_id >> id_bits != 0 {
+            return Err(IoError::protocol(
+                DEVICE,
+                format!("frame ID 0x{:X} does not fit in {} byte(s)", frame.frame_id, f.id_bytes),
+            ));
+        }
+        if frame.data.len() > MAX_DATA_LEN {
+            return Err(IoError::protocol(
+                DEVICE,
+                format!("data length {} exceeds {}", frame.data.len(), MAX_DATA_LEN),
+            ));
+        }
+
+        let mut data = frame.data.clone();
+        if !f.length_byte {
+            // Fixed-length records are zero-padded
+            if data.len() > f.fixed_length as usize {
+                return Err(IoError::protocol(
+                    DEVICE,
+                    format!("data length {} exceeds the fixed length {}", data.len(), f.fixed_length),
+                ));
+            }
+            data.resize(f.fixed_length as usize, 0);
+        }
+
+        let mut buf = Vec::with_capacity(f.frame_len(data.len()));
+        buf.extend_from_slice(&f.header);
+        let id = frame.frame_id.to_be_bytes();
+        let id = &id[4 - f.id_bytes as usize..];
+        if f.id_big_endian {
+            buf.extend_from_slice(id);
+        } else {
+            buf.extend(id.iter().rev());
+        }
+        if f.length_byte {
+            buf.push(data.len() as u8);
+        }
+        buf.extend_from_slice(&data);
+        if f.checksum != Checksum::None {
+            let checksum = f.checksum.compute(f.checksum_range(&buf));
+            buf.push(checksum);
+        }
+        buf.extend_from_slice(&f.footer);
+        Ok(buf)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(id: u32, data: &[u8]) -> CanTransmitFrame {
+        CanTransmitFrame {
+            frame_id: id,
+            data: data.to_vec(),
+            bus: 0,
+            is_extended: id > CAN_SFF_MAX,
+            is_fd: false,
+            is_brs: false,
+            is_rtr: false,
+        }
+    }
+
+    #[test]
+    fn test_default_format_roundtrip() {
+        let codec = CustomSerialCodec::new(CustomFrameFormat::default());
+        let encoded = codec.encode(&frame(0x123, &[0x11, 0x22, 0x33])).unwrap();
+        assert_eq!(encoded, vec![0xAA, 0x01, 0x23, 0x03, 0x11, 0x22, 0x33]);
+        let decoded = codec.decode(&encoded).unwrap();
+        assert_eq!(decoded.frame_id, 0x123);
+        assert_eq!(decoded.bytes, vec![0x11, 0x22, 0x33]);
+        assert!(!decoded.is_extended);
+    }
+
+    #[test]
+    fn test_little_endian_checksum_footer() {
+        let codec = CustomSerialCodec::new(CustomFrameFormat {
+            header: vec![0xAA, 0x55],
+            id_bytes: 4,
+            id_big_endian: false,
+            checksum: Checksum::Xor8,
+            footer: vec![b'\r', b'\n'],
+            ..Default::default()
+        });
+        let encoded = codec.encode(&frame(0x18DA_F110, &[0x02, 0x10])).unwrap();
+        assert_eq!(&encoded[2..6], &[0x10, 0xF1, 0xDA, 0x18]);
+        let xor = encoded[2..9].iter().fold(0u8, |a, &b| a ^ b);
+        assert_eq!(encoded[9], xor);
+        let decoded = codec.decode(&encoded).unwrap();
+        assert_eq!(decoded.frame_id, 0x18DA_F110);
+        assert!(decoded.is_extended);
+
+        let mut corrupted = encoded.clone();
+        corrupted[7] ^= 0xFF;
+        assert!(codec.decode(&corrupted).is_err());
+    }
+
+    #[test]
+    fn test_fixed_length_pads() {
+        let codec = CustomSerialCodec::new(CustomFrameFormat {
+            length_byte: false,
+            fixed_length: 8,
+            checksum: Checksum::Crc8,
+            ..Default::default()
+        });
+        let encoded = codec.encode(&frame(0x7FF, &[1, 2])).unwrap();
+        assert_eq!(encoded.len(), 1 + 2 + 8 + 1);
+        assert_eq!(codec.decode(&encoded).unwrap().bytes, vec![1, 2, 0, 0, 0, 0, 0, 0]);
+        assert!(codec.encode(&frame(0x1_0000, &[])).is_err());
+    }
+
+    #[test]
+    fn test_extract_frames_resyncs() {
+        let codec = CustomSerialCodec::new(CustomFrameFormat {
+            checksum: Checksum::Sum8,
+            ..Default::default()
+        });
+        let a = codec.encode(&frame(0x100, &[1, 2, 3, 4])).unwrap();
+        let b = codec.encode(&frame(0x200, &[])).unwrap();
+
+        // Noise, a false header with a bad length, two frames, then half a frame
+        let mut buf = vec![0x00, 0x13, 0xAA, 0x01, 0x00, 0x20];
+        buf.extend_from_slice(&a);
+        buf.extend_from_slice(&b);
+        buf.extend_from_slice(&a[..3]);
+
+        let (frames, dropped) = codec.extract_frames(&mut buf);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].frame_id, 0x100);
+        assert_eq!(frames[1].frame_id, 0x200);
+        assert_eq!(dropped, 6);
+        assert_eq!(buf, a[..3].to_vec());
+
+        buf.extend_from_slice(&a[3..]);
+        let (frames, _) = codec.extract_frames(&mut buf);
+        assert_eq!(frames.len(), 1);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_from_connection() {
+        let conn = serde_json::json!({
+            "port": "/dev/ttyUSB0",
+            "header": [170, 85],
+            "id_bytes": "4",
+            "id_big_endian": false,
+            "checksum": "crc8",
+        });
+        let format = CustomFrameFormat::from_connection(&conn).unwrap();
+        assert_eq!(format.header, vec![0xAA, 0x55]);
+        assert_eq!(format.id_bytes, 4);
+        assert_eq!(format.checksum, Checksum::Crc8);
+        assert!(format.length_byte);
+
+        assert!(CustomFrameFormat::from_connection(&serde_json::json!({ "header": [] })).is_err());
+        assert!(CustomFrameFormat::from_connection(&serde_json::json!({ "id_bytes": 5 })).is_err());
+        assert!(CustomFrameFormat::from_connection(&serde_json::json!({ "checksum": "md5" })).is_err());
+    }
+}
@@ -0,0 +1,17 @@
+// ui/src-tauri/src/io/custom_serial/mod.rs
+//
+// Generic CAN-over-serial driver for adapters that stream frames as a simple
+// binary record. The record layout is described in the profile (header bytes,
+// ID width and byte order, length byte, checksum, footer) and implemented by
+// CustomSerialCodec, so new hobbyist adapters need a profile, not a driver.
+
+pub mod codec;
+pub mod reader;
+
+// Re-export public items
+// Note: CustomSerialCodec is also available via io::codec::CustomSerialCodec
+pub use codec::CustomFrameFormat;
+pub use reader::{encode_transmit_frame, CustomSerialPortConfig};
+
+// Internal items used by multi_source
+pub(crate) use reader::run_source as run_custom_serial_source;
@@ -0,0 +1,251 @@
+// ui/src-tauri/src/io/custom_serial/reader.rs
+//
+// Serial reader for user-described binary CAN-over-serial formats.
+//
+// The serial side mirrors the slcan reader (cloned port handle for a dedicated
+// write thread, blocking read loop), but there is no adapter setup: bytes are
+// fed straight into a CustomSerialCodec built from the profile.
+//
+// The broker doesn't know the profile's frame format, so transmit requests
+// arrive as a neutral envelope (see `encode_transmit_frame`) and are encoded
+// with the codec here, where the format lives.
+
+use std::io::{Read, Write};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use super::codec::{CustomFrameFormat, CustomSerialCodec};
+use crate::io::codec::FrameCodec;
+use crate::io::error::IoError;
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::serial::utils::{self as serial_utils, Parity};
+use crate::io::types::{SourceMessage, TransmitRequest};
+use crate::io::{CanTransmitFrame, FrameMessage};
+
+/// Receive buffer cap; a format that never matches shouldn't grow it unbounded.
+const MAX_BUFFER: usize = 4096;
+
+// ============================================================================
+// Transmit Envelope
+// ============================================================================
+
+/// Envelope flag: extended ID
+const TX_FLAG_EXTENDED: u8 = 0x01;
+
+/// Wrap a CAN frame for the source's transmit channel:
+/// id(4 LE) + flags(1) + len(1) + data.
+pub fn encode_transmit_frame(frame: &CanTransmitFrame) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(6 + frame.data.len());
+    buf.extend_from_slice(&frame.frame_id.to_le_bytes());
+    buf.push(if frame.is_extended { TX_FLAG_EXTENDED } else { 0 });
+    buf.push(frame.data.len() as u8);
+    buf.extend_from_slice(&frame.data);
+    buf
+}
+
+/// Unwrap a transmit envelope.
+fn decode_transmit_frame(bytes: &[u8]) -> Option<CanTransmitFrame> {
+    if bytes.len() < 6 {
+        return None;
+    }
+    let len = bytes[5] as usize;
+    Some(CanTransmitFrame {
+        frame_id: u32::from_le_bytes(bytes[0..4].try_into().ok()?),
+        data: bytes.get(6..6 + len)?.to_vec(),
+        bus: 0,
+        is_extended: bytes[4] & TX_FLAG_EXTENDED != 0,
+        is_fd: false,
+        is_brs: false,
+        is_rtr: false,
+    })
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Serial port settings for a custom CAN-over-serial source
+pub struct CustomSerialPortConfig {
+    pub port: String,
+    pub baud_rate: u32,
+    pub data_bits: u8,
+    pub stop_bits: u8,
+    pub parity: Parity,
+    /// Don't offer transmit (adapter is receive-only)
+    pub listen_only: bool,
+}
+
+/// Run a custom CAN-over-serial source and send frames to the merge task
+pub async fn run_source(
+    source_idx: usize,
+    config: CustomSerialPortConfig,
+    format: CustomFrameFormat,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let device = format!("custom_serial({})", config.port);
+
+    let serial_port = match serialport::new(&config.port, config.baud_rate)
+        .data_bits(serial_utils::to_serialport_data_bits(config.data_bits))
+        .stop_bits(serial_utils::to_serialport_stop_bits(config.stop_bits))
+        .parity(serial_utils::to_serialport_parity(&config.parity))
+        .timeout(Duration::from_millis(2))
+        .open()
+    {
+        Ok(p) => p,
+        Err(e) => {
+            let _ = tx
+                .send(SourceMessage::Error(
+                    source_idx,
+                    IoError::connection(&device, e.to_string()).to_string(),
+                ))
+                .await;
+            return;
+        }
+    };
+    let _ = serial_port.clear(serialport::ClearBuffer::All);
+
+    let codec = CustomSerialCodec::new(format);
+
+    // Dedicated write thread on a cloned handle, as slcan does
+    if !config.listen_only {
+        match serial_port.try_clone() {
+            Ok(mut w_port) => {
+                let (transmit_tx, transmit_rx) = std::sync::mpsc::sync_channel::<TransmitRequest>(32);
+                let _ = tx
+                    .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
+                    .await;
+
+                let stop_flag_write = stop_flag.clone();
+                let tx_codec = codec.clone();
+                std::thread::Builder::new()
+                    .name(format!("custom-serial-tx-{}", source_idx))
+                    .spawn(move || {
+                        while !stop_flag_write.load(Ordering::SeqCst) {
+                            match transmit_rx.recv_timeout(Duration::from_millis(50)) {
+                                Ok(req) => {
+                                    let result = decode_transmit_frame(&req.data)
+                                        .ok_or_else(|| "Malformed transmit request".to_string())
+                                        .and_then(|frame| tx_codec.encode(&frame).map_err(String::from))
+                                        .and_then(|bytes| {
+                                            w_port
+                                                .write_all(&bytes)
+                                                .and_then(|_| w_port.flush())
+                                                .map_err(|e| format!("Write error: {}", e))
+                                        });
+                                    let _ = req.result_tx.send(result);
+                                }
+                                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+                                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+                            }
+                        }
+                    })
+                    .ok();
+            }
+            Err(e) => {
+                tlog!("[custom_serial] Failed to clone serial port for write thread: {} — transmit unavailable", e);
+            }
+        }
+    }
+
+    tlog!(
+        "[custom_serial] Source {} connected to {} (baud: {}, header: {:02X?}, id_bytes: {}, checksum: {:?})",
+        source_idx, config.port, config.baud_rate, codec.format.header, codec.format.id_bytes, codec.format.checksum
+    );
+
+    let _ = tx
+        .send(SourceMessage::Connected(source_idx, "custom_serial".to_string(), config.port.clone(), None))
+        .await;
+
+    // Read loop (blocking) — owns the original serial port handle
+    let tx_clone = tx.clone();
+    let stop_flag_clone = stop_flag.clone();
+    let mut serial_port = serial_port;
+
+    let blocking_handle = tokio::task::spawn_blocking(move || {
+        let mut buffer: Vec<u8> = Vec::with_capacity(1024);
+        let mut read_buf = [0u8; 256];
+        let mut rx_frames: u64 = 0;
+        let mut dropped_bytes: u64 = 0;
+
+        while !stop_flag_clone.load(Ordering::SeqCst) {
+            match serial_port.read(&mut read_buf) {
+                Ok(n) if n > 0 => {
+                    buffer.extend_from_slice(&read_buf[..n]);
+                    let (frames, dropped) = codec.extract_frames(&mut buffer);
+                    dropped_bytes += dropped as u64;
+                    if buffer.len() > MAX_BUFFER {
+                        dropped_bytes += buffer.len() as u64;
+                        buffer.clear();
+                    }
+
+                    let pending: Vec<FrameMessage> = frames
+                        .into_iter()
+                        .filter_map(|mut frame| {
+                            apply_bus_mapping(&mut frame, &bus_mappings).then_some(frame)
+                        })
+                        .collect();
+                    if !pending.is_empty() {
+                        rx_frames += pending.len() as u64;
+                        let _ = tx_clone.blocking_send(SourceMessage::Frames(source_idx, pending));
+                    }
+                }
+                Ok(_) => {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                    // Timeout — continue
+                }
+                Err(e) => {
+                    let _ = tx_clone.blocking_send(SourceMessage::Error(
+                        source_idx,
+                        format!("Read error: {}", e),
+                    ));
+                    return;
+                }
+            }
+        }
+
+        tlog!(
+            "[custom_serial] Source {} ended: rx={}, dropped_bytes={}",
+            source_idx, rx_frames, dropped_bytes
+        );
+
+        let _ = tx_clone.blocking_send(SourceMessage::Ended(source_idx, "stopped".to_string()));
+    });
+
+    let _ = blocking_handle.await;
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transmit_envelope_roundtrip() {
+        let frame = CanTransmitFrame {
+            frame_id: 0x18FF_0001,
+            data: vec![1, 2, 3],
+            bus: 2,
+            is_extended: true,
+            is_fd: false,
+            is_brs: false,
+            is_rtr: false,
+        };
+        let decoded = decode_transmit_frame(&encode_transmit_frame(&frame)).unwrap();
+        assert_eq!(decoded.frame_id, frame.frame_id);
+        assert_eq!(decoded.data, frame.data);
+        assert!(decoded.is_extended);
+        assert!(decode_transmit_frame(&[0; 5]).is_none());
+    }
+}
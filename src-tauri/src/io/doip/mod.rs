@@ -0,0 +1,359 @@
+// ui/src-tauri/src/io/doip/mod.rs
+//
+// DoIP (ISO 13400-2) client source for vehicles whose diagnostic gateway sits
+// on Ethernet. WireTAP connects to the gateway's TCP port (13400), activates
+// routing for its tester logical address, and then exchanges UDS payloads in
+// diagnostic messages (payload type 0x8001).
+//
+// Each diagnostic message becomes one frame with protocol "doip": the UDS
+// payload as bytes, `frame_id = source << 16 | target` (logical addresses)
+// and `source_address` set. That is the shape reassembly gives ISO-TP
+// messages, so the same diagnostics path applies — frame_link pairs requests
+// with responses, the analysis latency pairs work on the IDs, and the UDS
+// payload is complete in one frame. Requests go out as
+// TransmitPayload::Diagnostic; the gateway's acknowledgement is logged and a
+// negative one reported as an error. Alive-check requests are answered.
+
+pub mod reader;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::io::FrameMessage;
+
+// Internal items used by multi_source
+pub(crate) use reader::run_source as run_doip_source;
+pub use reader::probe_doip;
+
+pub const DEFAULT_PORT: u16 = 13400;
+/// First logical address of the external test equipment range (0x0E00-0x0FFF)
+pub const DEFAULT_TESTER_ADDRESS: u16 = 0x0E00;
+
+const HEADER_LEN: usize = 8;
+/// Largest payload accepted from a gateway (a UDS transfer block plus headroom)
+const MAX_PAYLOAD: usize = 1 << 20;
+
+pub const PT_GENERIC_NACK: u16 = 0x0000;
+pub const PT_ROUTING_ACTIVATION_REQUEST: u16 = 0x0005;
+pub const PT_ROUTING_ACTIVATION_RESPONSE: u16 = 0x0006;
+pub const PT_ALIVE_CHECK_REQUEST: u16 = 0x0007;
+pub const PT_ALIVE_CHECK_RESPONSE: u16 = 0x0008;
+pub const PT_DIAGNOSTIC_MESSAGE: u16 = 0x8001;
+pub const PT_DIAGNOSTIC_ACK: u16 = 0x8002;
+pub const PT_DIAGNOSTIC_NACK: u16 = 0x8003;
+
+/// Routing activation response code for "routing successfully activated"
+const ROUTING_ACTIVATED: u8 = 0x10;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// DoIP client settings, parsed from a profile's connection
+#[derive(Clone, Debug, PartialEq)]
+pub struct DoipConfig {
+    pub host: String,
+    pub port: u16,
+    /// Our logical address
+    pub tester_address: u16,
+    /// Target of requests that don't name one (usually the gateway or an ECU)
+    pub target_address: Option<u16>,
+    /// Routing activation type (0x00 default, 0x01 WWH-OBD, 0xE0 OEM)
+    pub activation_type: u8,
+    /// Protocol version byte (0x02 = ISO 13400-2:2012, 0x03 = 2019)
+    pub protocol_version: u8,
+    /// Connect and routing-activation timeout
+    pub timeout_sec: f64,
+}
+
+fn number(connection: &Value, key: &str) -> Option<f64> {
+    connection
+        .get(key)
+        .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| parse_address(s).map(f64::from))))
+}
+
+/// Decimal or 0x-prefixed hex, as typed in the profile dialog.
+fn parse_address(s: &str) -> Option<u32> {
+    let s = s.trim();
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+fn address(connection: &Value, key: &str) -> Result<Option<u16>, String> {
+    match number(connection, key) {
+        Some(v) if !(0.0..=65535.0).contains(&v) => Err(format!("Invalid {} {}", key, v)),
+        Some(v) => Ok(Some(v as u16)),
+        None => Ok(None),
+    }
+}
+
+impl DoipConfig {
+    /// Read the settings from a profile's connection map.
+    pub fn from_connection(connection: &Value) -> Result<Self, String> {
+        let host = connection
+            .get("host")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or("DoIP gateway host is required")?
+            .to_string();
+        let port = match number(connection, "port") {
+            Some(p) if !(1.0..=65535.0).contains(&p) => return Err(format!("Invalid port {}", p)),
+            Some(p) => p as u16,
+            None => DEFAULT_PORT,
+        };
+        let activation_type = match number(connection, "activation_type") {
+            Some(v) if !(0.0..=255.0).contains(&v) => return Err(format!("Invalid activation type {}", v)),
+            Some(v) => v as u8,
+            None => 0x00,
+        };
+        let protocol_version = match number(connection, "protocol_version") {
+            Some(v) if !(1.0..=254.0).contains(&v) => return Err(format!("Invalid protocol version {}", v)),
+            Some(v) => v as u8,
+            None => 0x02,
+        };
+        Ok(Self {
+            host,
+            port,
+            tester_address: address(connection, "tester_address")?.unwrap_or(DEFAULT_TESTER_ADDRESS),
+            target_address: address(connection, "target_address")?,
+            activation_type,
+            protocol_version,
+            timeout_sec: number(connection, "timeout").filter(|t| *t > 0.0).unwrap_or(5.0),
+        })
+    }
+}
+
+// ============================================================================
+// Message framing
+// ============================================================================
+
+/// One DoIP message (generic header stripped).
+#[derive(Clone, Debug, PartialEq)]
+pub struct DoipMessage {
+    pub payload_type: u16,
+    pub payload: Vec<u8>,
+}
+
+/// Generic header + payload.
+pub fn encode_message(version: u8, payload_type: u16, payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(HEADER_LEN + payload.len());
+    out.push(version);
+    out.push(!version);
+    out.extend_from_slice(&payload_type.to_be_bytes());
+    out.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Take the next complete message off the front of a TCP receive buffer.
+/// Ok(None) until a whole message is in; Err for a corrupt header (the
+/// connection can't be resynchronised).
+pub fn take_message(buf: &mut Vec<u8>) -> Result<Option<DoipMessage>, String> {
+    if buf.len() < HEADER_LEN {
+        return Ok(None);
+    }
+    if buf[1] != !buf[0] {
+        return Err(format!("Invalid DoIP header (version 0x{:02X}/0x{:02X})", buf[0], buf[1]));
+    }
+    let length = u32::from_be_bytes([buf[4], buf[5], buf[6], buf[7]]) as usize;
+    if length > MAX_PAYLOAD {
+        return Err(format!("DoIP payload of {} bytes exceeds the {} byte limit", length, MAX_PAYLOAD));
+    }
+    if buf.len() < HEADER_LEN + length {
+        return Ok(None);
+    }
+    let payload_type = u16::from_be_bytes([buf[2], buf[3]]);
+    let payload = buf[HEADER_LEN..HEADER_LEN + length].to_vec();
+    buf.drain(..HEADER_LEN + length);
+    Ok(Some(DoipMessage { payload_type, payload }))
+}
+
+pub fn routing_activation_request(config: &DoipConfig) -> Vec<u8> {
+    let mut payload = config.tester_address.to_be_bytes().to_vec();
+    payload.push(config.activation_type);
+    payload.extend_from_slice(&[0; 4]);
+    encode_message(config.protocol_version, PT_ROUTING_ACTIVATION_REQUEST, &payload)
+}
+
+fn routing_activation_reason(code: u8) -> &'static str {
+    match code {
+        0x00 => "unknown source address",
+        0x01 => "all TCP sockets are registered and active",
+        0x02 => "source address already registered on another socket",
+        0x03 => "source address already active on this socket",
+        0x04 => "missing authentication",
+        0x05 => "rejected confirmation",
+        0x06 => "unsupported routing activation type",
+        0x07 => "TLS connection required",
+        0x11 => "confirmation required",
+        _ => "reserved or OEM-specific code",
+    }
+}
+
+/// The gateway's logical address from a routing activation response, or why
+/// activation was refused.
+pub fn parse_routing_activation_response(payload: &[u8]) -> Result<u16, String> {
+    if payload.len() < 5 {
+        return Err("Routing activation response too short".to_string());
+    }
+    let entity = u16::from_be_bytes([payload[2], payload[3]]);
+    match payload[4] {
+        ROUTING_ACTIVATED => Ok(entity),
+        code => Err(format!(
+            "Routing activation refused (0x{:02X}: {})",
+            code,
+            routing_activation_reason(code)
+        )),
+    }
+}
+
+pub fn diagnostic_nack_reason(code: u8) -> &'static str {
+    match code {
+        0x02 => "invalid source address",
+        0x03 => "unknown target address",
+        0x04 => "diagnostic message too large",
+        0x05 => "out of memory",
+        0x06 => "target unreachable",
+        0x07 => "unknown network",
+        0x08 => "transport protocol error",
+        _ => "reserved code",
+    }
+}
+
+/// (source, target, UDS payload) of a diagnostic message.
+pub fn parse_diagnostic_message(payload: &[u8]) -> Option<(u16, u16, &[u8])> {
+    if payload.len() < 4 {
+        return None;
+    }
+    let source = u16::from_be_bytes([payload[0], payload[1]]);
+    let target = u16::from_be_bytes([payload[2], payload[3]]);
+    Some((source, target, &payload[4..]))
+}
+
+pub fn diagnostic_message(version: u8, source: u16, target: u16, uds: &[u8]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(4 + uds.len());
+    payload.extend_from_slice(&source.to_be_bytes());
+    payload.extend_from_slice(&target.to_be_bytes());
+    payload.extend_from_slice(uds);
+    encode_message(version, PT_DIAGNOSTIC_MESSAGE, &payload)
+}
+
+/// A diagnostic message as a frame: `frame_id = source << 16 | target`.
+pub fn diagnostic_frame(source: u16, target: u16, uds: &[u8], timestamp_us: u64, tx: bool) -> FrameMessage {
+    FrameMessage {
+        protocol: "doip".to_string(),
+        timestamp_us,
+        frame_id: (u32::from(source) << 16) | u32::from(target),
+        bus: 0,
+        dlc: uds.len().min(u8::MAX as usize) as u8,
+        bytes: uds.to_vec(),
+        is_extended: false,
+        is_fd: false,
+        source_address: Some(source),
+        incomplete: None,
+        direction: tx.then(|| "tx".to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    }
+}
+
+// ============================================================================
+// Transmit payload
+// ============================================================================
+
+/// A UDS request to send over DoIP (TransmitPayload::Diagnostic).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticRequest {
+    /// Logical address of the ECU (None = the profile's target address)
+    #[serde(default)]
+    pub target_address: Option<u16>,
+    /// UDS payload (service ID first)
+    pub data: Vec<u8>,
+}
+
+impl DiagnosticRequest {
+    /// Transmit-channel encoding: has-target flag, target (BE), payload.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(3 + self.data.len());
+        out.push(u8::from(self.target_address.is_some()));
+        out.extend_from_slice(&self.target_address.unwrap_or(0).to_be_bytes());
+        out.extend_from_slice(&self.data);
+        out
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < 4 {
+            return Err("Diagnostic request is empty".to_string());
+        }
+        Ok(Self {
+            target_address: (bytes[0] != 0).then(|| u16::from_be_bytes([bytes[1], bytes[2]])),
+            data: bytes[3..].to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_message_framing() {
+        let config = DoipConfig::from_connection(&json!({ "host": "192.168.0.10" })).unwrap();
+        let request = routing_activation_request(&config);
+        assert_eq!(request, vec![0x02, 0xFD, 0x00, 0x05, 0, 0, 0, 7, 0x0E, 0x00, 0x00, 0, 0, 0, 0]);
+
+        // Two messages split across reads
+        let mut buf = diagnostic_message(0x02, 0x1001, 0x0E00, &[0x62, 0xF1, 0x90, b'W']);
+        buf.extend_from_slice(&encode_message(0x02, PT_ALIVE_CHECK_REQUEST, &[]));
+        let tail = buf.split_off(10);
+        assert_eq!(take_message(&mut buf), Ok(None));
+        buf.extend_from_slice(&tail);
+        let msg = take_message(&mut buf).unwrap().unwrap();
+        assert_eq!(msg.payload_type, PT_DIAGNOSTIC_MESSAGE);
+        assert_eq!(parse_diagnostic_message(&msg.payload), Some((0x1001, 0x0E00, &[0x62, 0xF1, 0x90, b'W'][..])));
+        assert_eq!(take_message(&mut buf).unwrap().unwrap().payload_type, PT_ALIVE_CHECK_REQUEST);
+        assert!(buf.is_empty());
+
+        assert!(take_message(&mut vec![0x02, 0x02, 0, 0, 0, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_routing_activation_and_frames() {
+        let ok = [0x0E, 0x00, 0x10, 0x10, 0x10, 0, 0, 0, 0];
+        assert_eq!(parse_routing_activation_response(&ok), Ok(0x1010));
+        let refused = [0x0E, 0x00, 0x10, 0x10, 0x00, 0, 0, 0, 0];
+        assert!(parse_routing_activation_response(&refused).unwrap_err().contains("unknown source address"));
+
+        let frame = diagnostic_frame(0x0E00, 0x1001, &[0x22, 0xF1, 0x90], 5, true);
+        assert_eq!((frame.frame_id, frame.source_address, frame.direction.as_deref()), (0x0E00_1001, Some(0x0E00), Some("tx")));
+
+        let request = DiagnosticRequest { target_address: Some(0x1001), data: vec![0x10, 0x03] };
+        assert_eq!(DiagnosticRequest::decode(&request.encode()), Ok(request));
+        let default_target = DiagnosticRequest { target_address: None, data: vec![0x3E, 0x00] };
+        assert_eq!(DiagnosticRequest::decode(&default_target.encode()), Ok(default_target));
+    }
+
+    #[test]
+    fn test_config_from_connection() {
+        let config = DoipConfig::from_connection(&json!({
+            "host": " gateway.local ",
+            "port": "13401",
+            "tester_address": "0x0E80",
+            "target_address": 4097,
+            "activation_type": "0xE0",
+        }))
+        .unwrap();
+        assert_eq!(config.host, "gateway.local");
+        assert_eq!((config.port, config.tester_address, config.target_address), (13401, 0x0E80, Some(0x1001)));
+        assert_eq!((config.activation_type, config.protocol_version), (0xE0, 0x02));
+        assert!(DoipConfig::from_connection(&json!({})).is_err());
+        assert!(DoipConfig::from_connection(&json!({ "host": "x", "tester_address": "0x10000" })).is_err());
+    }
+}
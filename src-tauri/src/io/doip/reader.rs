@@ -0,0 +1,242 @@
+// ui/src-tauri/src/io/doip/reader.rs
+//
+// TCP client for the DoIP source. After connecting, the tester's routing
+// activation must succeed before anything else is sent; the gateway closes
+// the socket otherwise. Transmit requests are written from the same task
+// that reads, so a diagnostic message and its echo frame keep their order.
+
+use std::sync::mpsc as std_mpsc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+
+use super::*;
+use crate::io::FrameMessage;
+use crate::io::error::IoError;
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::now_us;
+use crate::io::types::{SourceMessage, TransmitRequest};
+
+fn device(config: &DoipConfig) -> String {
+    format!("doip({}:{})", config.host, config.port)
+}
+
+/// Connect and activate routing; returns the stream, any bytes read past the
+/// activation response, and the gateway's logical address.
+async fn connect(config: &DoipConfig) -> Result<(TcpStream, Vec<u8>, u16), IoError> {
+    let device = device(config);
+    let timeout = Duration::from_secs_f64(config.timeout_sec);
+    let mut stream = tokio::time::timeout(timeout, TcpStream::connect((config.host.as_str(), config.port)))
+        .await
+        .map_err(|_| IoError::timeout(&device, "TCP connect"))?
+        .map_err(|e| IoError::connection(&device, e.to_string()))?;
+    let _ = stream.set_nodelay(true);
+
+    stream
+        .write_all(&routing_activation_request(config))
+        .await
+        .map_err(|e| IoError::connection(&device, e.to_string()))?;
+
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        while let Some(msg) = take_message(&mut buf).map_err(|e| IoError::protocol(&device, e))? {
+            match msg.payload_type {
+                PT_ROUTING_ACTIVATION_RESPONSE => {
+                    let entity = parse_routing_activation_response(&msg.payload)
+                        .map_err(|e| IoError::connection(&device, e))?;
+                    return Ok((stream, buf, entity));
+                }
+                PT_GENERIC_NACK => {
+                    let code = msg.payload.first().copied().unwrap_or(0xFF);
+                    return Err(IoError::protocol(&device, format!("Gateway rejected routing activation (NACK 0x{:02X})", code)));
+                }
+                _ => {}
+            }
+        }
+        let n = tokio::time::timeout_at(deadline, stream.read(&mut chunk))
+            .await
+            .map_err(|_| IoError::timeout(&device, "routing activation response"))?
+            .map_err(|e| IoError::read(&device, e.to_string()))?;
+        if n == 0 {
+            return Err(IoError::connection(&device, "gateway closed the connection"));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+// ============================================================================
+// Gateway Probing
+// ============================================================================
+
+/// Connect and activate routing; returns the gateway's logical address.
+pub async fn probe_doip(config: &DoipConfig) -> Result<u16, IoError> {
+    let (_stream, _, entity) = connect(config).await?;
+    tlog!("[doip] Probe of {}:{} activated routing on gateway 0x{:04X}", config.host, config.port, entity);
+    Ok(entity)
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Run a DoIP client source and send diagnostic messages to the merge task
+pub async fn run_source(
+    source_idx: usize,
+    config: DoipConfig,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let (mut stream, mut buf, entity) = match connect(&config).await {
+        Ok(c) => c,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e.to_string())).await;
+            return;
+        }
+    };
+    let default_target = config.target_address.unwrap_or(entity);
+    let address = format!("{}:{}", config.host, config.port);
+    tlog!(
+        "[doip] Source {} connected to {} (gateway 0x{:04X}, tester 0x{:04X}, default target 0x{:04X})",
+        source_idx, address, entity, config.tester_address, default_target
+    );
+    let _ = tx
+        .send(SourceMessage::Connected(source_idx, "doip".to_string(), address.clone(), None))
+        .await;
+
+    let (transmit_tx, transmit_rx) = std_mpsc::sync_channel::<TransmitRequest>(32);
+    let _ = tx.send(SourceMessage::TransmitReady(source_idx, transmit_tx)).await;
+
+    let version = config.protocol_version;
+    let mut chunk = vec![0u8; 65536];
+    let mut rx_messages: u64 = 0;
+    let mut tx_messages: u64 = 0;
+    let mut nacks: u64 = 0;
+    let mut ended = "stopped".to_string();
+
+    'run: while !stop_flag.load(Ordering::SeqCst) {
+        // Queued requests first, so they go out within one poll interval
+        let mut outgoing = Vec::new();
+        loop {
+            match transmit_rx.try_recv() {
+                Ok(req) => outgoing.push(req),
+                Err(std_mpsc::TryRecvError::Empty) => break,
+                Err(std_mpsc::TryRecvError::Disconnected) => break 'run,
+            }
+        }
+        for req in outgoing {
+            let request = match DiagnosticRequest::decode(&req.data) {
+                Ok(r) => r,
+                Err(e) => {
+                    let _ = req.result_tx.try_send(Err(e));
+                    continue;
+                }
+            };
+            let target = request.target_address.unwrap_or(default_target);
+            let msg = diagnostic_message(version, config.tester_address, target, &request.data);
+            match stream.write_all(&msg).await {
+                Ok(()) => {
+                    tx_messages += 1;
+                    let frame = diagnostic_frame(config.tester_address, target, &request.data, now_us(), true);
+                    let _ = req.result_tx.try_send(Ok(()));
+                    forward(source_idx, vec![frame], &bus_mappings, &tx).await;
+                }
+                Err(e) => {
+                    let _ = req.result_tx.try_send(Err(e.to_string()));
+                    ended = format!("Write error: {}", e);
+                    break 'run;
+                }
+            }
+        }
+
+        let n = match tokio::time::timeout(Duration::from_millis(10), stream.read(&mut chunk)).await {
+            Ok(Ok(0)) => {
+                ended = "Gateway closed the connection".to_string();
+                break;
+            }
+            Ok(Ok(n)) => n,
+            Ok(Err(e)) => {
+                ended = format!("Read error: {}", e);
+                break;
+            }
+            Err(_) => 0,
+        };
+        buf.extend_from_slice(&chunk[..n]);
+
+        let mut frames = Vec::new();
+        loop {
+            let msg = match take_message(&mut buf) {
+                Ok(Some(msg)) => msg,
+                Ok(None) => break,
+                Err(e) => {
+                    ended = e;
+                    break 'run;
+                }
+            };
+            match msg.payload_type {
+                PT_DIAGNOSTIC_MESSAGE => {
+                    if let Some((source, target, uds)) = parse_diagnostic_message(&msg.payload) {
+                        rx_messages += 1;
+                        frames.push(diagnostic_frame(source, target, uds, now_us(), false));
+                    }
+                }
+                PT_DIAGNOSTIC_NACK => {
+                    nacks += 1;
+                    let code = msg.payload.get(4).copied().unwrap_or(0xFF);
+                    tlog!(
+                        "[doip] Source {}: gateway refused diagnostic message (0x{:02X}: {})",
+                        source_idx, code, diagnostic_nack_reason(code)
+                    );
+                }
+                PT_ALIVE_CHECK_REQUEST => {
+                    let reply = encode_message(version, PT_ALIVE_CHECK_RESPONSE, &config.tester_address.to_be_bytes());
+                    if let Err(e) = stream.write_all(&reply).await {
+                        ended = format!("Write error: {}", e);
+                        break 'run;
+                    }
+                }
+                PT_GENERIC_NACK => {
+                    tlog!(
+                        "[doip] Source {}: generic NACK 0x{:02X}",
+                        source_idx, msg.payload.first().copied().unwrap_or(0xFF)
+                    );
+                }
+                // Positive acks carry no information beyond "delivered"
+                PT_DIAGNOSTIC_ACK => {}
+                other => tlog!("[doip] Source {}: ignoring payload type 0x{:04X}", source_idx, other),
+            }
+        }
+        if !frames.is_empty() {
+            forward(source_idx, frames, &bus_mappings, &tx).await;
+        }
+    }
+
+    tlog!(
+        "[doip] Source {} ended ({}): rx={}, tx={}, nacks={}",
+        source_idx, ended, rx_messages, tx_messages, nacks
+    );
+    let message = if ended == "stopped" {
+        SourceMessage::Ended(source_idx, ended)
+    } else {
+        SourceMessage::Error(source_idx, ended)
+    };
+    let _ = tx.send(message).await;
+}
+
+async fn forward(source_idx: usize, frames: Vec<FrameMessage>, bus_mappings: &[BusMapping], tx: &mpsc::Sender<SourceMessage>) {
+    let mapped: Vec<FrameMessage> = frames
+        .into_iter()
+        .filter_map(|mut frame| apply_bus_mapping(&mut frame, bus_mappings).then_some(frame))
+        .collect();
+    if !mapped.is_empty() {
+        let _ = tx.send(SourceMessage::Frames(source_idx, mapped)).await;
+    }
+}
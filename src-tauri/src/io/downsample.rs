@@ -0,0 +1,146 @@
+// Copyright 2026 Wired Square Pty Ltd
+//
+// src-tauri/src/io/downsample.rs
+//
+// Per-listener delivery rate limits. A listener (app instance) can ask for at
+// most `max_frames_per_second` frames per frame ID, so a 4 Mbit/s bus can be
+// watched from an iPad or a low-power laptop. Delivery is downsampled in
+// ws::dispatch::send_new_frames: frame time is cut into 1/N-second intervals
+// and only the newest frame of each ID in each interval is sent. The capture
+// itself is untouched. Listeners on one session share its frame stream, so
+// the stream runs at the least restrictive listener's limit (unlimited if any
+// listener has none). Dropped frames are counted per session and reported in
+// `session-metrics`.
+
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use super::FrameMessage;
+
+/// (bus, frame ID, extended)
+type FrameKey = (u8, u32, bool);
+
+/// Keeps the newest frame per ID in each interval of frame time.
+struct Downsampler {
+    max_fps: u32,
+    interval_us: u64,
+    /// Interval last delivered per ID, so an interval spanning two batches
+    /// isn't delivered twice
+    last_interval: HashMap<FrameKey, u64>,
+    dropped: u64,
+}
+
+impl Downsampler {
+    fn new(max_fps: u32) -> Self {
+        Self {
+            max_fps,
+            interval_us: 1_000_000 / u64::from(max_fps.max(1)),
+            last_interval: HashMap::new(),
+            dropped: 0,
+        }
+    }
+
+    fn apply(&mut self, frames: Vec<FrameMessage>) -> Vec<FrameMessage> {
+        let total = frames.len();
+        let mut seen: HashSet<(FrameKey, u64)> = HashSet::new();
+        let mut delivered: Vec<(FrameKey, u64)> = Vec::new();
+        // Walk newest-first so the first frame met in an interval is its newest
+        let mut kept: Vec<FrameMessage> = frames
+            .into_iter()
+            .rev()
+            .filter(|f| {
+                let key = (f.bus, f.frame_id, f.is_extended);
+                let interval = f.timestamp_us / self.interval_us;
+                if !seen.insert((key, interval)) || self.last_interval.get(&key) == Some(&interval) {
+                    return false;
+                }
+                delivered.push((key, interval));
+                true
+            })
+            .collect();
+        kept.reverse();
+
+        for (key, interval) in delivered {
+            let last = self.last_interval.entry(key).or_insert(interval);
+            *last = (*last).max(interval);
+        }
+        self.dropped += (total - kept.len()) as u64;
+        kept
+    }
+}
+
+/// Per-session downsamplers, created when a limit applies.
+static DOWNSAMPLERS: Lazy<Mutex<HashMap<String, Downsampler>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Downsample a batch about to be delivered to a session's listeners.
+/// Passes the batch through untouched when no limit applies.
+pub fn for_delivery(session_id: &str, frames: Vec<FrameMessage>) -> Vec<FrameMessage> {
+    let limit = super::session_rate_limit(session_id);
+    let Ok(mut samplers) = DOWNSAMPLERS.lock() else { return frames };
+    let Some(max_fps) = limit else { return frames };
+    let sampler = samplers.entry(session_id.to_string()).or_insert_with(|| Downsampler::new(max_fps));
+    if sampler.max_fps != max_fps {
+        let dropped = sampler.dropped;
+        *sampler = Downsampler { dropped, ..Downsampler::new(max_fps) };
+    }
+    sampler.apply(frames)
+}
+
+/// Frames dropped by rate limiting since the last call.
+pub fn take_dropped(session_id: &str) -> u64 {
+    let Ok(mut samplers) = DOWNSAMPLERS.lock() else { return 0 };
+    match samplers.get_mut(session_id) {
+        Some(s) => std::mem::take(&mut s.dropped),
+        None => 0,
+    }
+}
+
+/// Forget a session's downsampler. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut samplers) = DOWNSAMPLERS.lock() {
+        samplers.remove(session_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(frame_id: u32, ts: u64, value: u8) -> FrameMessage {
+        test_frame(frame_id, ts, &[value])
+    }
+
+    #[test]
+    fn keeps_the_newest_frame_per_id_and_interval() {
+        // 10 fps = 100 ms intervals
+        let mut d = Downsampler::new(10);
+        let kept = d.apply(vec![
+            frame(0x100, 0, 1),
+            frame(0x200, 10_000, 1),
+            frame(0x100, 50_000, 2),
+            frame(0x100, 120_000, 3),
+            frame(0x200, 90_000, 2),
+        ]);
+        let got: Vec<(u32, u8)> = kept.iter().map(|f| (f.frame_id, f.bytes[0])).collect();
+        assert_eq!(got, vec![(0x100, 2), (0x100, 3), (0x200, 2)]);
+        assert_eq!(d.dropped, 2);
+    }
+
+    #[test]
+    fn an_interval_spanning_batches_is_delivered_once() {
+        let mut d = Downsampler::new(10);
+        assert_eq!(d.apply(vec![frame(0x100, 10_000, 1)]).len(), 1);
+        assert!(d.apply(vec![frame(0x100, 60_000, 2)]).is_empty());
+        assert_eq!(d.apply(vec![frame(0x100, 100_000, 3)]).len(), 1);
+        assert_eq!(d.dropped, 1);
+    }
+
+    #[test]
+    fn ids_on_different_buses_are_sampled_separately() {
+        let mut d = Downsampler::new(1);
+        let kept = d.apply(vec![frame(0x100, 0, 1), FrameMessage { bus: 1, ..frame(0x100, 0, 1) }]);
+        assert_eq!(kept.len(), 2);
+    }
+}
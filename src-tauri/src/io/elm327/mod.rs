@@ -0,0 +1,299 @@
+// ui/src-tauri/src/io/elm327/mod.rs
+//
+// ELM327 / OBD-II serial adapter driver. Cheap OBD dongles (and the STN11xx
+// clones) speak the ELM AT command set over a serial port; this driver sets
+// them up for CAN and reads frames in one of two modes:
+//
+// - Monitor: `ATMA` (monitor all) prints every frame seen on the bus, so the
+//   dongle works as a passive sniffer. CAN auto-formatting is turned off so the
+//   data bytes are exactly what was on the bus.
+// - Poll: OBD requests from the profile (e.g. "010D") are sent in turn at a
+//   fixed interval, and every response frame is captured.
+//
+// Either way headers are on and spaces off, so a line is the CAN ID (3 hex
+// digits for 11-bit, 8 for 29-bit) followed by the data bytes.
+//
+// Command reference: ELM327 datasheet (ELM Electronics, v1.4b and later).
+
+pub mod reader;
+
+use serde_json::Value;
+
+use crate::io::{now_us, FrameMessage};
+
+// Internal items used by multi_source
+pub(crate) use reader::run_source as run_elm327_source;
+pub use reader::probe_elm327;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// How the adapter is driven
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Elm327Mode {
+    /// Passive: `ATMA`, every frame on the bus
+    Monitor,
+    /// Active: send the profile's OBD requests and capture the responses
+    Poll,
+}
+
+/// CAN protocols offered for `ATSP` (the adapter's own numbering)
+pub const PROTOCOLS: &[(&str, &str)] = &[
+    ("0", "Automatic"),
+    ("6", "ISO 15765-4 CAN (11-bit, 500 kbit/s)"),
+    ("7", "ISO 15765-4 CAN (29-bit, 500 kbit/s)"),
+    ("8", "ISO 15765-4 CAN (11-bit, 250 kbit/s)"),
+    ("9", "ISO 15765-4 CAN (29-bit, 250 kbit/s)"),
+];
+
+/// ELM327 source settings, parsed from a profile's connection
+#[derive(Clone, Debug, PartialEq)]
+pub struct Elm327Config {
+    pub port: String,
+    pub baud_rate: u32,
+    pub mode: Elm327Mode,
+    /// `ATSP` protocol number
+    pub protocol: String,
+    /// OBD requests for poll mode, as hex (e.g. "010C")
+    pub pids: Vec<String>,
+    /// Time between poll rounds
+    pub poll_interval_ms: u64,
+    /// `ATSH` request header for poll mode (default: the adapter's 7DF)
+    pub request_header: Option<String>,
+}
+
+fn number(connection: &Value, key: &str) -> Option<u64> {
+    connection
+        .get(key)
+        .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+}
+
+fn is_hex(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl Elm327Config {
+    /// Read the settings from a profile's connection map.
+    pub fn from_connection(connection: &Value) -> Result<Self, String> {
+        let port = connection
+            .get("port")
+            .and_then(|v| v.as_str())
+            .filter(|p| !p.is_empty())
+            .ok_or("Serial port is required")?
+            .to_string();
+        let mode = match connection.get("mode").and_then(|v| v.as_str()).unwrap_or("monitor") {
+            "monitor" => Elm327Mode::Monitor,
+            "poll" => Elm327Mode::Poll,
+            other => return Err(format!("Unknown ELM327 mode '{}'", other)),
+        };
+        let protocol = connection
+            .get("protocol")
+            .and_then(|v| v.as_str().map(String::from).or_else(|| v.as_u64().map(|n| n.to_string())))
+            .unwrap_or_else(|| "6".to_string());
+        if !PROTOCOLS.iter().any(|(p, _)| *p == protocol) {
+            return Err(format!("Unsupported ELM327 protocol '{}' (CAN protocols are 0 and 6-9)", protocol));
+        }
+
+        let pids: Vec<String> = connection
+            .get("pids")
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|p| p.as_str())
+                    .map(|p| p.trim().replace(' ', "").to_ascii_uppercase())
+                    .filter(|p| !p.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(bad) = pids.iter().find(|p| !is_hex(p) || p.len() % 2 != 0 || p.len() > 14) {
+            return Err(format!("Invalid OBD request '{}': expected 1-7 hex bytes, e.g. 010C", bad));
+        }
+        if mode == Elm327Mode::Poll && pids.is_empty() {
+            return Err("Poll mode needs at least one OBD request".to_string());
+        }
+
+        let request_header = connection
+            .get("request_header")
+            .and_then(|v| v.as_str())
+            .map(|h| h.trim().to_ascii_uppercase())
+            .filter(|h| !h.is_empty());
+        if let Some(h) = &request_header {
+            if !is_hex(h) || !(h.len() == 3 || h.len() == 6 || h.len() == 8) {
+                return Err(format!("Invalid request header '{}': expected 3, 6 or 8 hex digits", h));
+            }
+        }
+
+        Ok(Elm327Config {
+            port,
+            baud_rate: number(connection, "baud_rate").unwrap_or(38400) as u32,
+            mode,
+            protocol,
+            pids,
+            poll_interval_ms: number(connection, "poll_interval_ms").unwrap_or(250).max(10),
+            request_header,
+        })
+    }
+
+    /// AT commands sent after reset, in order.
+    pub fn init_commands(&self) -> Vec<String> {
+        let mut commands: Vec<String> = ["ATE0", "ATL0", "ATS0", "ATH1", "ATD0"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        commands.push(format!("ATSP{}", self.protocol));
+        match self.mode {
+            // Raw bytes as on the bus, PCI and padding included
+            Elm327Mode::Monitor => commands.push("ATCAF0".to_string()),
+            Elm327Mode::Poll => {
+                commands.push("ATCAF1".to_string());
+                if let Some(header) = &self.request_header {
+                    commands.push(format!("ATSH{}", header));
+                }
+            }
+        }
+        commands
+    }
+}
+
+// ============================================================================
+// Response Parsing
+// ============================================================================
+
+/// Adapter messages that aren't frames. A monitor session ends on
+/// "BUFFER FULL" or "STOPPED" and has to be restarted.
+pub fn is_status_line(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty()
+        || line == "?"
+        || line == "OK"
+        || line.starts_with("SEARCHING")
+        || line.starts_with("NO DATA")
+        || line.starts_with("BUFFER FULL")
+        || line.starts_with("STOPPED")
+        || line.starts_with("CAN ERROR")
+        || line.starts_with("BUS INIT")
+        || line.starts_with("BUS ERROR")
+        || line.starts_with("UNABLE TO CONNECT")
+        || line.starts_with("ELM327")
+        || line.starts_with('<') // "<RX ERROR" / "<DATA ERROR"
+}
+
+/// Whether a monitor session has stopped and needs `ATMA` again.
+pub fn ends_monitor(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with("BUFFER FULL") || line.starts_with("STOPPED") || line.starts_with("CAN ERROR")
+}
+
+/// Parse one response line (headers on) into a frame.
+///
+/// With spaces off, an 11-bit ID is 3 hex digits and a 29-bit ID is 8, and
+/// the data is whole bytes, so the line's length parity tells them apart.
+/// Lines with spaces ("7E8 03 41 0D 00", or "18 DA F1 10 ...") are accepted too.
+pub fn parse_frame_line(line: &str) -> Option<FrameMessage> {
+    let line = line.trim();
+    if is_status_line(line) {
+        return None;
+    }
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    if !is_hex(&compact) {
+        return None;
+    }
+    let id_len = if line.contains(' ') {
+        // "7E8 03 41 ..." vs "18 DA F1 10 03 ..." (29-bit headers are printed as bytes)
+        let first = line.split_whitespace().next()?;
+        if first.len() == 3 { 3 } else { 8 }
+    } else if compact.len() % 2 == 1 {
+        3
+    } else {
+        8
+    };
+    if compact.len() < id_len || (compact.len() - id_len) % 2 != 0 {
+        return None;
+    }
+    let frame_id = u32::from_str_radix(&compact[..id_len], 16).ok()?;
+    let bytes = hex::decode(&compact[id_len..]).ok()?;
+    if bytes.len() > 8 {
+        return None;
+    }
+    Some(FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us: now_us(),
+        frame_id: frame_id & 0x1FFF_FFFF,
+        bus: 0,
+        dlc: bytes.len() as u8,
+        bytes,
+        is_extended: id_len == 8,
+        is_fd: false,
+        source_address: None,
+        incomplete: None,
+        direction: Some("rx".to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_frame_line() {
+        let f = parse_frame_line("7E803410D32AAAAAAAA").unwrap();
+        assert_eq!(f.frame_id, 0x7E8);
+        assert!(!f.is_extended);
+        assert_eq!(f.bytes, vec![0x03, 0x41, 0x0D, 0x32, 0xAA, 0xAA, 0xAA, 0xAA]);
+
+        let f = parse_frame_line("18DAF1100641000000").unwrap();
+        assert_eq!(f.frame_id, 0x18DA_F110);
+        assert!(f.is_extended);
+        assert_eq!(f.dlc, 5);
+
+        let f = parse_frame_line("7E8 03 41 0D 32").unwrap();
+        assert_eq!(f.bytes, vec![0x03, 0x41, 0x0D, 0x32]);
+        let f = parse_frame_line("18 DA F1 10 03 41 0D 32").unwrap();
+        assert_eq!(f.frame_id, 0x18DA_F110);
+
+        assert!(parse_frame_line("NO DATA").is_none());
+        assert!(parse_frame_line("BUFFER FULL").is_none());
+        assert!(parse_frame_line(">").is_none());
+        assert!(parse_frame_line("7E8").unwrap().bytes.is_empty());
+    }
+
+    #[test]
+    fn test_config_from_connection() {
+        let config = Elm327Config::from_connection(&json!({
+            "port": "COM4",
+            "mode": "poll",
+            "pids": ["01 0c", "010D"],
+            "poll_interval_ms": "100",
+            "request_header": "7e0",
+        }))
+        .unwrap();
+        assert_eq!(config.pids, vec!["010C", "010D"]);
+        assert_eq!(config.baud_rate, 38400);
+        assert_eq!(config.protocol, "6");
+        assert_eq!(config.poll_interval_ms, 100);
+        assert!(config.init_commands().contains(&"ATSH7E0".to_string()));
+
+        let monitor = Elm327Config::from_connection(&json!({"port": "/dev/ttyUSB0"})).unwrap();
+        assert_eq!(monitor.mode, Elm327Mode::Monitor);
+        assert!(monitor.init_commands().contains(&"ATCAF0".to_string()));
+    }
+
+    #[test]
+    fn test_config_rejects_bad_input() {
+        assert!(Elm327Config::from_connection(&json!({})).is_err());
+        assert!(Elm327Config::from_connection(&json!({"port": "COM4", "mode": "poll"})).is_err());
+        assert!(Elm327Config::from_connection(&json!({"port": "COM4", "pids": ["01G"]})).is_err());
+        assert!(Elm327Config::from_connection(&json!({"port": "COM4", "protocol": "3"})).is_err());
+    }
+}
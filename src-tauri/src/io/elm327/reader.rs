@@ -0,0 +1,314 @@
+// ui/src-tauri/src/io/elm327/reader.rs
+//
+// Serial reader for ELM327 adapters. The adapter is reset and configured with
+// AT commands (each answered up to the '>' prompt), then driven in monitor or
+// poll mode. Everything runs on one blocking thread that owns the port, as
+// the adapter handles a single command at a time.
+
+use std::io::{Read, Write};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+
+use super::{ends_monitor, parse_frame_line, Elm327Config, Elm327Mode};
+use crate::io::error::IoError;
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::types::SourceMessage;
+use crate::io::FrameMessage;
+
+/// Serial read timeout; bounds how long the loops take to notice a stop.
+const READ_TIMEOUT: Duration = Duration::from_millis(20);
+
+/// ATZ reboots the adapter, which takes up to a second on most clones
+const RESET_TIMEOUT: Duration = Duration::from_millis(3000);
+
+/// Timeout for setup commands and OBD requests
+const COMMAND_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Longest line kept while waiting for CR (a full 29-bit frame is 24 chars)
+const MAX_LINE: usize = 64;
+
+// ============================================================================
+// Command Helpers
+// ============================================================================
+
+fn open_port(port: &str, baud_rate: u32) -> Result<Box<dyn serialport::SerialPort>, String> {
+    let device = format!("elm327({})", port);
+    let serial_port = serialport::new(port, baud_rate)
+        .timeout(READ_TIMEOUT)
+        .open()
+        .map_err(|e| IoError::connection(&device, e.to_string()).to_string())?;
+    let _ = serial_port.clear(serialport::ClearBuffer::All);
+    Ok(serial_port)
+}
+
+/// Send a command and collect the response lines up to the '>' prompt.
+fn command(port: &mut Box<dyn serialport::SerialPort>, cmd: &str, timeout: Duration) -> Result<Vec<String>, String> {
+    port.write_all(format!("{}\r", cmd).as_bytes())
+        .and_then(|_| port.flush())
+        .map_err(|e| format!("Write error: {}", e))?;
+
+    let deadline = Instant::now() + timeout;
+    let mut response = String::new();
+    let mut buf = [0u8; 128];
+    while Instant::now() < deadline {
+        match port.read(&mut buf) {
+            Ok(n) if n > 0 => {
+                // Some clones send NULs around the prompt
+                response.extend(buf[..n].iter().filter(|&&b| b != 0).map(|&b| b as char));
+                if response.contains('>') {
+                    let lines = response
+                        .split(['\r', '\n', '>'])
+                        .map(|l| l.trim().to_string())
+                        // Echo is on until ATE0 has been processed
+                        .filter(|l| !l.is_empty() && !l.eq_ignore_ascii_case(cmd))
+                        .collect();
+                    return Ok(lines);
+                }
+            }
+            Ok(_) => {}
+            Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(e) => return Err(format!("Read error: {}", e)),
+        }
+    }
+    Err(format!("No response to {}", cmd))
+}
+
+/// Reset the adapter and return its identification (e.g. "ELM327 v1.5").
+fn reset(port: &mut Box<dyn serialport::SerialPort>) -> Result<String, String> {
+    // A previous session may have left the adapter monitoring; any byte stops it
+    let _ = port.write_all(b"\r");
+    std::thread::sleep(Duration::from_millis(100));
+    let _ = port.clear(serialport::ClearBuffer::Input);
+
+    let lines = command(port, "ATZ", RESET_TIMEOUT)?;
+    lines
+        .into_iter()
+        .find(|l| l.contains("ELM") || l.contains("STN"))
+        .ok_or_else(|| "Device did not identify as an ELM327".to_string())
+}
+
+/// Reset the adapter and apply the profile's settings.
+fn initialise(port: &mut Box<dyn serialport::SerialPort>, config: &Elm327Config) -> Result<String, String> {
+    let version = reset(port)?;
+    for cmd in config.init_commands() {
+        let lines = command(port, &cmd, COMMAND_TIMEOUT)?;
+        if lines.iter().any(|l| l == "?") {
+            return Err(format!("Adapter rejected {}", cmd));
+        }
+    }
+    Ok(version)
+}
+
+/// Open the port, reset the adapter and return its identification.
+pub fn probe_elm327(port: &str, baud_rate: u32) -> Result<String, String> {
+    let mut serial_port = open_port(port, baud_rate)?;
+    reset(&mut serial_port)
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Map and forward a batch of frames; returns how many were bus-filtered.
+fn forward(
+    source_idx: usize,
+    frames: Vec<FrameMessage>,
+    bus_mappings: &[BusMapping],
+    tx: &mpsc::Sender<SourceMessage>,
+) -> u64 {
+    let total = frames.len();
+    let pending: Vec<FrameMessage> = frames
+        .into_iter()
+        .filter_map(|mut frame| apply_bus_mapping(&mut frame, bus_mappings).then_some(frame))
+        .collect();
+    let filtered = (total - pending.len()) as u64;
+    if !pending.is_empty() {
+        let _ = tx.blocking_send(SourceMessage::Frames(source_idx, pending));
+    }
+    filtered
+}
+
+/// Run an ELM327 source and send frames to the merge task
+pub async fn run_source(
+    source_idx: usize,
+    config: Elm327Config,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let blocking_handle = tokio::task::spawn_blocking(move || {
+        let mut port = match open_port(&config.port, config.baud_rate) {
+            Ok(p) => p,
+            Err(e) => {
+                let _ = tx.blocking_send(SourceMessage::Error(source_idx, e));
+                return;
+            }
+        };
+        let version = match initialise(&mut port, &config) {
+            Ok(v) => v,
+            Err(e) => {
+                let _ = tx.blocking_send(SourceMessage::Error(
+                    source_idx,
+                    format!("ELM327 setup failed on {}: {}", config.port, e),
+                ));
+                return;
+            }
+        };
+
+        tlog!(
+            "[elm327] Source {} connected to {} ({}, baud: {}, mode: {:?}, protocol: {})",
+            source_idx, config.port, version, config.baud_rate, config.mode, config.protocol
+        );
+
+        let _ = tx.blocking_send(SourceMessage::Connected(
+            source_idx,
+            "elm327".to_string(),
+            config.port.clone(),
+            None,
+        ));
+
+        let mut rx_frames: u64 = 0;
+        let mut bus_filtered: u64 = 0;
+
+        let result = match config.mode {
+            Elm327Mode::Monitor => {
+                monitor(source_idx, &mut port, &bus_mappings, &stop_flag, &tx, &mut rx_frames, &mut bus_filtered)
+            }
+            Elm327Mode::Poll => poll(
+                source_idx, &mut port, &config, &bus_mappings, &stop_flag, &tx, &mut rx_frames, &mut bus_filtered,
+            ),
+        };
+        if let Err(e) = result {
+            let _ = tx.blocking_send(SourceMessage::Error(source_idx, e));
+            return;
+        }
+
+        tlog!(
+            "[elm327] Source {} ended: rx={}, bus_filtered={}",
+            source_idx, rx_frames, bus_filtered
+        );
+
+        let _ = tx.blocking_send(SourceMessage::Ended(source_idx, "stopped".to_string()));
+    });
+
+    let _ = blocking_handle.await;
+}
+
+/// `ATMA` until stopped, restarting it whenever the adapter gives up
+/// (BUFFER FULL on a busy bus, or a CAN error).
+fn monitor(
+    source_idx: usize,
+    port: &mut Box<dyn serialport::SerialPort>,
+    bus_mappings: &[BusMapping],
+    stop_flag: &AtomicBool,
+    tx: &mpsc::Sender<SourceMessage>,
+    rx_frames: &mut u64,
+    bus_filtered: &mut u64,
+) -> Result<(), String> {
+    let start = |port: &mut Box<dyn serialport::SerialPort>| {
+        port.write_all(b"ATMA\r")
+            .and_then(|_| port.flush())
+            .map_err(|e| format!("Write error: {}", e))
+    };
+    start(port)?;
+
+    let mut line = String::new();
+    let mut buf = [0u8; 512];
+    let mut restarts: u64 = 0;
+
+    while !stop_flag.load(Ordering::SeqCst) {
+        let n = match port.read(&mut buf) {
+            Ok(n) => n,
+            Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => continue,
+            Err(e) => return Err(format!("Read error: {}", e)),
+        };
+
+        let mut frames = Vec::new();
+        let mut restart = false;
+        for &b in &buf[..n] {
+            match b {
+                b'\r' | b'\n' => {
+                    if ends_monitor(&line) {
+                        restart = true;
+                    } else if let Some(frame) = parse_frame_line(&line) {
+                        frames.push(frame);
+                    }
+                    line.clear();
+                }
+                // The prompt means monitoring has stopped
+                b'>' => {
+                    restart = true;
+                    line.clear();
+                }
+                0 => {}
+                _ if line.len() < MAX_LINE => line.push(b as char),
+                _ => {}
+            }
+        }
+
+        *rx_frames += frames.len() as u64;
+        *bus_filtered += forward(source_idx, frames, bus_mappings, tx);
+
+        if restart && !stop_flag.load(Ordering::SeqCst) {
+            restarts += 1;
+            if restarts % 100 == 1 {
+                tlog!("[elm327] Source {} restarting monitor (restart {})", source_idx, restarts);
+            }
+            start(port)?;
+        }
+    }
+
+    // Any byte stops monitoring; wait for the prompt so the adapter is idle
+    let _ = command(port, "", Duration::from_millis(500));
+    Ok(())
+}
+
+/// Send each OBD request in turn, once per poll interval.
+#[allow(clippy::too_many_arguments)]
+fn poll(
+    source_idx: usize,
+    port: &mut Box<dyn serialport::SerialPort>,
+    config: &Elm327Config,
+    bus_mappings: &[BusMapping],
+    stop_flag: &AtomicBool,
+    tx: &mpsc::Sender<SourceMessage>,
+    rx_frames: &mut u64,
+    bus_filtered: &mut u64,
+) -> Result<(), String> {
+    let interval = Duration::from_millis(config.poll_interval_ms);
+    let mut timeouts: u64 = 0;
+
+    while !stop_flag.load(Ordering::SeqCst) {
+        let round_start = Instant::now();
+        for pid in &config.pids {
+            if stop_flag.load(Ordering::SeqCst) {
+                break;
+            }
+            match command(port, pid, COMMAND_TIMEOUT) {
+                Ok(lines) => {
+                    let frames: Vec<FrameMessage> = lines.iter().filter_map(|l| parse_frame_line(l)).collect();
+                    *rx_frames += frames.len() as u64;
+                    *bus_filtered += forward(source_idx, frames, bus_mappings, tx);
+                }
+                // A request nobody answers shouldn't end the session
+                Err(e) if e.starts_with("No response") => {
+                    timeouts += 1;
+                    if timeouts % 100 == 1 {
+                        tlog!("[elm327] Source {}: {} (timeouts: {})", source_idx, e, timeouts);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        while !stop_flag.load(Ordering::SeqCst) && round_start.elapsed() < interval {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+    Ok(())
+}
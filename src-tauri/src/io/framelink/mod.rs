@@ -103,6 +103,11 @@ pub fn convert_stream_frame(
                 source_address: None,
                 incomplete: None,
                 direction: Some("rx".to_string()),
+                link: None,
+                clock: None,
+                is_error: None,
+                error_class: None,
+                brs: None,
             })
         }
         FrameMetadata::Rs485 { .. } => {
@@ -123,6 +128,11 @@ pub fn convert_stream_frame(
                 source_address: None,
                 incomplete: None,
                 direction: Some("rx".to_string()),
+                link: None,
+                clock: None,
+                is_error: None,
+                error_class: None,
+                brs: None,
             })
         }
         FrameMetadata::Unknown { .. } => {
@@ -42,7 +42,7 @@ impl FrameCodec for GsUsbCodec {
     /// Decode a gs_usb host frame (20 bytes).
     ///
     /// Layout: echo_id(4) + can_id(4) + can_dlc(1) + channel(1) + flags(1) + reserved(1) + data(8)
-    fn decode(raw: &[u8]) -> Result<FrameMessage, IoError> {
+    fn decode(&self, raw: &[u8]) -> Result<FrameMessage, IoError> {
         if raw.len() < consts::HOST_FRAME_SIZE {
             return Err(IoError::protocol(
                 "gs_usb",
@@ -96,13 +96,18 @@ impl FrameCodec for GsUsbCodec {
             source_address: None,
             incomplete: None,
             direction: None,
+            link: None,
+            clock: None,
+            is_error: None,
+            error_class: None,
+            brs: None,
         })
     }
 
     /// Encode a CAN frame to gs_usb format (20 bytes).
     ///
     /// Layout: echo_id(4) + can_id(4) + can_dlc(1) + channel(1) + flags(1) + reserved(1) + data(8)
-    fn encode(frame: &CanTransmitFrame) -> Result<[u8; consts::HOST_FRAME_SIZE], IoError> {
+    fn encode(&self, frame: &CanTransmitFrame) -> Result<[u8; consts::HOST_FRAME_SIZE], IoError> {
         // Validate data length (gs_usb classic only supports 8 bytes)
         if frame.data.len() > 8 {
             return Err(IoError::protocol(
@@ -171,7 +176,7 @@ mod tests {
         // data
         raw[12..16].copy_from_slice(&[0xAA, 0xBB, 0xCC, 0xDD]);
 
-        let frame = GsUsbCodec::decode(&raw).unwrap();
+        let frame = GsUsbCodec.decode(&raw).unwrap();
         assert_eq!(frame.frame_id, 0x123);
         assert_eq!(frame.dlc, 4);
         assert_eq!(frame.bytes, vec![0xAA, 0xBB, 0xCC, 0xDD]);
@@ -184,7 +189,7 @@ mod tests {
         raw[0..4].copy_from_slice(&0u32.to_le_bytes());
         raw[4..8].copy_from_slice(&0x123u32.to_le_bytes());
 
-        assert!(GsUsbCodec::decode(&raw).is_err());
+        assert!(GsUsbCodec.decode(&raw).is_err());
     }
 
     #[test]
@@ -199,7 +204,7 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = GsUsbCodec::encode(&frame).unwrap();
+        let encoded = GsUsbCodec.encode(&frame).unwrap();
 
         // echo_id = 0
         assert_eq!(u32::from_le_bytes(encoded[0..4].try_into().unwrap()), 0);
@@ -228,7 +233,7 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = GsUsbCodec::encode(&frame).unwrap();
+        let encoded = GsUsbCodec.encode(&frame).unwrap();
 
         // can_id should have extended flag set
         let can_id = u32::from_le_bytes(encoded[4..8].try_into().unwrap());
@@ -249,6 +254,6 @@ mod tests {
             is_rtr: false,
         };
 
-        assert!(GsUsbCodec::encode(&frame).is_err());
+        assert!(GsUsbCodec.encode(&frame).is_err());
     }
 }
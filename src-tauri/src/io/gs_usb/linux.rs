@@ -116,6 +116,11 @@ fn get_gs_usb_info_for_interface(iface_name: &str) -> Option<GsUsbDeviceInfo> {
                     serial,
                     interface_name: Some(iface_name.to_string()),
                     interface_up,
+                    // The kernel driver numbers each device's interfaces by channel
+                    channel: fs::read_to_string(format!("/sys/class/net/{}/dev_port", iface_name))
+                        .ok()
+                        .and_then(|s| s.trim().parse::<u8>().ok()),
+                    channel_count: None,
                 });
             }
         }
@@ -202,6 +207,8 @@ fn scan_unbound_gs_usb_devices() -> Result<Vec<GsUsbDeviceInfo>, String> {
                 serial,
                 interface_name: None, // No CAN interface bound yet
                 interface_up: None,
+                channel: None,
+                channel_count: None,
             });
         }
     }
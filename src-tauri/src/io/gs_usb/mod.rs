@@ -9,6 +9,8 @@
 // Platform strategy:
 // - Linux: Devices appear as SocketCAN interfaces via kernel gs_usb driver.
 //          We enumerate devices and help users configure the interface.
+//          Profiles with `direct_usb` set instead detach the kernel driver
+//          and use the nusb path, so nothing needs `ip link` or sudo.
 // - Windows/macOS: Direct USB access via nusb crate (no kernel driver available).
 //
 // Supported devices:
@@ -22,16 +24,19 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::io::gvret::BusMapping;
+use crate::settings::IOProfile;
+
 pub mod codec;
 
 #[cfg(target_os = "linux")]
 pub mod linux;
 
-#[cfg(any(target_os = "windows", target_os = "macos"))]
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
 pub mod nusb_driver;
 
 // Re-export multi-source streaming functions
-#[cfg(any(target_os = "windows", target_os = "macos"))]
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
 pub use nusb_driver::{encode_frame, run_source};
 
 // ============================================================================
@@ -82,6 +87,7 @@ pub mod can_mode {
     pub const HW_TIMESTAMP: u32 = 1 << 4;
     pub const PAD_PKTS_TO_MAX_PKT_SIZE: u32 = 1 << 7;
     pub const FD: u32 = 1 << 8;
+    pub const BERR_REPORTING: u32 = 1 << 12;
 }
 
 /// CAN device feature flags (from BT_CONST.feature field)
@@ -106,6 +112,11 @@ pub mod can_feature {
 /// DLC codes 0-8 map directly; 9-15 map to 12, 16, 20, 24, 32, 48, 64 bytes.
 pub const DLC_LEN: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
 
+/// Smallest DLC code whose payload holds `len` bytes (15 beyond 64).
+pub fn len_to_dlc(len: usize) -> u8 {
+    DLC_LEN.iter().position(|&l| l >= len).unwrap_or(15) as u8
+}
+
 /// CAN FD frame flags (in GsHostFrame.flags field)
 pub mod can_fd_flags {
     pub const FD: u8 = 0x01;
@@ -268,6 +279,18 @@ impl GsHostFrameFd {
     }
 }
 
+/// Bytes the device appends to each host frame in HW_TIMESTAMP mode
+pub const HW_TIMESTAMP_SIZE: usize = 4;
+
+/// Device timestamp (µs, free-running 32-bit counter) that follows a host
+/// frame received in HW_TIMESTAMP mode. Classic frames carry it after the
+/// 8 data bytes, FD frames after 64.
+pub fn host_frame_timestamp(data: &[u8], is_fd: bool) -> Option<u32> {
+    let at = if is_fd { GsHostFrameFd::SIZE } else { GsHostFrame::SIZE };
+    let bytes = data.get(at..at + HW_TIMESTAMP_SIZE)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
 /// Device configuration response
 #[repr(C, packed)]
 #[derive(Debug, Clone, Copy)]
@@ -519,6 +542,14 @@ pub struct GsUsbConfig {
     /// the actual CAN peripheral clock is 170 MHz).
     #[serde(default)]
     pub can_clock_override: Option<u32>,
+    /// Stamp frames with the device's hardware clock when it supports
+    /// HW_TIMESTAMP; otherwise frames keep host receive time.
+    #[serde(default)]
+    pub hardware_timestamps: bool,
+    /// Ask the device to report bus errors (BERR_REPORTING) as error frames.
+    /// Bus-off and controller state changes are reported regardless.
+    #[serde(default)]
+    pub error_frames: bool,
 }
 
 fn default_sample_point() -> f32 {
@@ -550,6 +581,8 @@ impl Default for GsUsbConfig {
             data_bitrate: 2_000_000,
             data_sample_point: 75.0,
             can_clock_override: None,
+            hardware_timestamps: false,
+            error_frames: false,
         }
     }
 }
@@ -569,6 +602,13 @@ pub struct GsUsbDeviceInfo {
     pub interface_name: Option<String>,
     /// Whether the interface is currently up (Linux only)
     pub interface_up: Option<bool>,
+    /// CAN channel this entry stands for (None when it couldn't be read)
+    #[serde(default)]
+    pub channel: Option<u8>,
+    /// Number of CAN channels on the device (Windows/macOS, when the
+    /// device could be queried)
+    #[serde(default)]
+    pub channel_count: Option<u8>,
 }
 
 /// Result of probing a gs_usb device
@@ -822,11 +862,30 @@ pub fn calculate_bittiming_constrained(
     None
 }
 
+/// Whether a gs_usb profile is driven over USB by WireTAP itself. Always on
+/// Windows and macOS; on Linux only with `direct_usb`, as the kernel driver
+/// otherwise owns the device.
+pub fn uses_direct_usb(profile: &IOProfile) -> bool {
+    cfg!(not(target_os = "linux")) || profile.connection.get("direct_usb").and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// Channels a source opens: every device bus an enabled mapping reads, or
+/// `default` (the profile's channel) when there are none.
+pub fn source_channels(bus_mappings: &[BusMapping], default: u8) -> Vec<u8> {
+    let mut channels: Vec<u8> = bus_mappings.iter().filter(|m| m.enabled).map(|m| m.device_bus).collect();
+    channels.sort_unstable();
+    channels.dedup();
+    if channels.is_empty() {
+        channels.push(default);
+    }
+    channels
+}
+
 // ============================================================================
 // Tauri Commands
 // ============================================================================
 
-/// List all gs_usb devices connected to the system.
+/// List all gs_usb devices connected to the system, one entry per CAN channel.
 /// On Linux, includes the SocketCAN interface name if the device is bound.
 #[tauri::command]
 pub fn list_gs_usb_devices() -> Result<Vec<GsUsbDeviceInfo>, String> {
@@ -856,20 +915,67 @@ pub fn get_can_setup_command(interface: String, bitrate: u32) -> String {
     )
 }
 
-/// Probe a gs_usb device to get its capabilities.
-/// Implemented for Windows and macOS (Linux uses SocketCAN).
+/// Configure a CAN interface on Linux (bitrate, FD, listen-only) and bring
+/// it up. Asks for privileges through polkit.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn configure_can_interface(
+    interface: String,
+    bitrate: u32,
+    enable_fd: bool,
+    data_bitrate: Option<u32>,
+    listen_only: bool,
+) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        super::socketcan::setup::configure_interface(&interface, bitrate, enable_fd, data_bitrate, listen_only)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Bring a CAN interface up or down on Linux. Asks for privileges through polkit.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_can_interface_up(interface: String, up: bool) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || super::socketcan::setup::set_interface_up(&interface, up))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Current setup of a CAN interface on Linux.
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_can_interface_status(interface: String) -> Result<super::socketcan::setup::CanInterfaceStatus, String> {
+    super::socketcan::setup::interface_status(&interface)
+}
+
+/// Probe a gs_usb device over USB (nusb) to get its capabilities.
+/// Always available on Windows and macOS. On Linux only for profiles in
+/// direct USB mode (`direct_usb`, see `uses_direct_usb`): claiming the
+/// interface detaches the kernel driver, which would take a SocketCAN
+/// interface down for the duration of the probe.
 /// Uses serial number for stable device matching across USB re-enumeration.
-#[tauri::command]
-pub fn probe_gs_usb_device(bus: u8, address: u8, serial: Option<String>) -> Result<GsUsbProbeResult, String> {
-    #[cfg(any(target_os = "windows", target_os = "macos"))]
+#[tauri::command(rename_all = "snake_case")]
+pub fn probe_gs_usb_device(
+    bus: u8,
+    address: u8,
+    serial: Option<String>,
+    direct_usb: Option<bool>,
+) -> Result<GsUsbProbeResult, String> {
+    #[cfg(target_os = "linux")]
     {
+        if direct_usb != Some(true) {
+            return Err("On Linux, gs_usb devices are probed in direct USB mode only. Otherwise the kernel driver owns the device; use ip link show to check the interface.".to_string());
+        }
+    }
+
+    #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+    {
+        let _ = direct_usb;
         nusb_driver::probe_device(bus, address, serial.as_deref()).map_err(String::from)
     }
 
-    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
     {
-        let _ = (bus, address, serial);
-        Err("Device probing is only available on Windows/macOS. On Linux, use ip link show to check interface status.".to_string())
+        let _ = (bus, address, serial, direct_usb);
+        Err("Device probing is not available on this platform.".to_string())
     }
 }
 
@@ -913,6 +1019,25 @@ mod tests {
         assert_eq!(frame.get_data(), &[0x01, 0x02, 0x03, 0x04]);
     }
 
+    #[test]
+    fn test_source_opens_each_mapped_channel_once() {
+        let mapping = |device_bus: u8, enabled: bool| BusMapping { device_bus, enabled, ..BusMapping::default() };
+        assert_eq!(source_channels(&[], 1), vec![1]);
+        assert_eq!(
+            source_channels(&[mapping(1, true), mapping(0, true), mapping(1, true), mapping(2, false)], 0),
+            vec![0, 1]
+        );
+    }
+
+    #[test]
+    fn test_fd_lengths_round_up_to_a_dlc_code() {
+        assert_eq!(len_to_dlc(8), 8);
+        assert_eq!(len_to_dlc(9), 9);
+        assert_eq!(len_to_dlc(33), 14);
+        assert_eq!(len_to_dlc(64), 15);
+        assert_eq!(DLC_LEN[len_to_dlc(20) as usize], 20);
+    }
+
     #[test]
     fn test_common_bitrates() {
         // All supported bitrates should have timing
@@ -960,6 +1085,20 @@ mod tests {
         assert!(GsHostFrame::from_bytes(&[]).is_none());
     }
 
+    #[test]
+    fn test_host_frame_timestamp() {
+        let mut classic = vec![0u8; GsHostFrame::SIZE];
+        classic.extend_from_slice(&0x0012_D687u32.to_le_bytes());
+        assert_eq!(host_frame_timestamp(&classic, false), Some(1_234_567));
+
+        let mut fd = vec![0u8; GsHostFrameFd::SIZE];
+        fd.extend_from_slice(&42u32.to_le_bytes());
+        assert_eq!(host_frame_timestamp(&fd, true), Some(42));
+
+        // No timestamp without HW_TIMESTAMP mode
+        assert_eq!(host_frame_timestamp(&[0u8; GsHostFrame::SIZE], false), None);
+    }
+
     #[test]
     fn test_gs_device_config_from_bytes() {
         // Valid 12-byte config
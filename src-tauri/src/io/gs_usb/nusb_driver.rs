@@ -20,26 +20,30 @@ use tauri::AppHandle;
 
 use super::{
     can_fd_flags, can_feature, can_id_flags, can_mode, get_bittiming_for_bitrate,
-    GsDeviceBittiming, GsDeviceBtConst, GsDeviceBtConstExtended, GsDeviceConfig, GsDeviceMode,
-    GsHostFrame, GsHostFrameFd,
+    host_frame_timestamp, GsDeviceBittiming, GsDeviceBtConst, GsDeviceBtConstExtended, GsDeviceConfig, GsDeviceMode,
+    GsHostFrame, GsHostFrameFd, len_to_dlc,
     GsUsbBreq, GsUsbConfig, GsUsbDeviceInfo, GsUsbProbeResult, DLC_LEN, GS_USB_HOST_FORMAT,
-    GS_USB_PIDS, GS_USB_VID,
+    GS_USB_PIDS, GS_USB_VID, HW_TIMESTAMP_SIZE,
 };
 use tokio::sync::mpsc;
 
 use crate::capture_store::{self, CaptureKind};
+use crate::io::can_error;
 use crate::io::error::IoError;
 use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::hw_clock::HardwareClock;
 use crate::io::types::{SourceMessage, TransmitRequest, TransmitSender};
 use crate::io::{
     emit_session_error, emit_stream_ended, now_us, signal_frames_ready, CanTransmitFrame,
-    FrameMessage, IOCapabilities, IOSource, IOState, SignalThrottle, TransmitPayload,
+    FrameClock, FrameMessage, IOCapabilities, IOSource, IOState, SignalThrottle, TransmitPayload,
     TransmitResult,
 };
 
 /// Encode a CAN frame into gs_usb format.
 /// Classic CAN: 20 bytes (GsHostFrame)
 /// CAN FD: 76 bytes (GsHostFrameFd)
+/// `channel` is the channel on the device; multi-source sessions pass the
+/// frame's device bus once the broker has reversed the bus mapping.
 pub fn encode_frame(frame: &CanTransmitFrame, channel: u8) -> Vec<u8> {
     if frame.is_fd {
         encode_fd_frame(frame, channel)
@@ -98,8 +102,8 @@ fn encode_fd_frame(frame: &CanTransmitFrame, channel: u8) -> Vec<u8> {
     }
     buf[4..8].copy_from_slice(&can_id.to_le_bytes());
 
-    // can_dlc (actual byte count for FD, up to 64)
-    buf[8] = frame.data.len().min(64) as u8;
+    // can_dlc: DLC code (9-15 for 12-64 bytes); short payloads are zero-padded
+    buf[8] = len_to_dlc(frame.data.len().min(64));
 
     // channel
     buf[9] = channel;
@@ -186,6 +190,20 @@ fn discover_bulk_endpoints(device: &nusb::Device) -> BulkEndpoints {
 // Device Matching
 // ============================================================================
 
+/// Claim the device's gs_usb interface. On Linux the kernel gs_usb driver
+/// normally holds it, so it is detached first; nusb reattaches the driver
+/// (and the SocketCAN interfaces come back) when the interface is released.
+fn claim_gs_usb_interface(device: &nusb::Device) -> impl MaybeFuture<Output = Result<Interface, nusb::Error>> {
+    #[cfg(target_os = "linux")]
+    {
+        device.detach_and_claim_interface(0)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        device.claim_interface(0)
+    }
+}
+
 /// Check if a USB device matches by serial number (preferred) or bus:address (fallback).
 /// Returns true if:
 /// - serial is Some and matches the device's serial number, OR
@@ -212,7 +230,9 @@ pub fn device_matches(dev: &nusb::DeviceInfo, serial: Option<&str>, bus: u8, add
 // Device Enumeration
 // ============================================================================
 
-/// List all gs_usb devices on the system
+/// List all gs_usb devices on the system, one entry per CAN channel.
+/// A device that can't be queried (e.g. already open) is listed once,
+/// without a channel.
 pub fn list_devices() -> Result<Vec<GsUsbDeviceInfo>, String> {
     // nusb 0.2 list_devices() returns MaybeFuture - use .wait() for sync blocking
     let devices: Vec<GsUsbDeviceInfo> = nusb::list_devices()
@@ -221,17 +241,29 @@ pub fn list_devices() -> Result<Vec<GsUsbDeviceInfo>, String> {
         .filter(|dev| {
             dev.vendor_id() == GS_USB_VID && GS_USB_PIDS.contains(&dev.product_id())
         })
-        .map(|dev| {
+        .flat_map(|dev| {
             // bus_id() returns &str, but for our purposes we use device_address as primary identifier
             // Parse bus_id as u8 if possible (works on Linux), otherwise use 0
             let bus = dev.bus_id().parse::<u8>().unwrap_or(0);
-            GsUsbDeviceInfo {
+            let info = GsUsbDeviceInfo {
                 bus,
                 address: dev.device_address(),
                 product: dev.product_string().unwrap_or_default().to_string(),
                 serial: dev.serial_number().map(|s| s.to_string()),
                 interface_name: None, // Windows/macOS don't have SocketCAN
                 interface_up: None,
+                channel: None,
+                channel_count: None,
+            };
+            match query_channel_count(&dev) {
+                Some(count) => (0..count)
+                    .map(|channel| GsUsbDeviceInfo {
+                        channel: Some(channel),
+                        channel_count: Some(count),
+                        ..info.clone()
+                    })
+                    .collect(),
+                None => vec![info],
             }
         })
         .collect();
@@ -239,6 +271,14 @@ pub fn list_devices() -> Result<Vec<GsUsbDeviceInfo>, String> {
     Ok(devices)
 }
 
+/// Number of CAN channels a device reports, if it can be opened.
+fn query_channel_count(dev: &nusb::DeviceInfo) -> Option<u8> {
+    let handle = dev.open().wait().ok()?;
+    let interface = handle.claim_interface(0).wait().ok()?;
+    // icount is 0-indexed (number of interfaces - 1)
+    get_device_config_sync(&interface).ok().map(|c| c.icount.saturating_add(1))
+}
+
 /// Probe a specific gs_usb device to get its capabilities
 pub fn probe_device(bus: u8, address: u8, serial: Option<&str>) -> Result<GsUsbProbeResult, IoError> {
     let device = format!("gs_usb({}:{})", bus, address);
@@ -258,8 +298,7 @@ pub fn probe_device(bus: u8, address: u8, serial: Option<&str>) -> Result<GsUsbP
         .map_err(|e| IoError::connection(&device, e.to_string()))?;
 
     // Claim interface 0 (also returns MaybeFuture)
-    let interface = dev_handle
-        .claim_interface(0)
+    let interface = claim_gs_usb_interface(&dev_handle)
         .wait()
         .map_err(|_| IoError::busy(&device))?;
 
@@ -313,6 +352,22 @@ fn get_bt_const_sync(interface: &Interface) -> Result<(u32, u32), String> {
     }
 }
 
+/// Number of CAN channels the device reports (async version)
+async fn read_channel_count(interface: &Interface) -> Option<u8> {
+    let data = interface
+        .control_in(ControlIn {
+            control_type: ControlType::Vendor,
+            recipient: Recipient::Interface,
+            request: GsUsbBreq::DeviceConfig as u8,
+            value: 1,
+            index: 0,
+            length: GsDeviceConfig::SIZE as u16,
+        }, CONTROL_TIMEOUT)
+        .await
+        .ok()?;
+    GsDeviceConfig::from_bytes(&data).map(|c| c.icount.saturating_add(1))
+}
+
 /// Get device configuration via USB control transfer (sync version)
 fn get_device_config_sync(interface: &Interface) -> Result<GsDeviceConfig, String> {
     let data = interface
@@ -457,8 +512,8 @@ impl IOSource for GsUsbSource {
     fn transmit(&self, payload: &TransmitPayload) -> Result<TransmitResult, String> {
         let frame = match payload {
             TransmitPayload::CanFrame(f) => f,
-            TransmitPayload::RawBytes(_) => {
-                return Err("gs_usb devices do not support raw byte transmission".to_string());
+            _ => {
+                return Err("gs_usb devices only transmit CAN frames".to_string());
             }
         };
 
@@ -568,7 +623,7 @@ async fn run_gs_usb_stream(
         }
     };
 
-    let interface = match usb_device.claim_interface(0).await {
+    let interface = match claim_gs_usb_interface(&usb_device).await {
         Ok(i) => i,
         Err(_) => {
             emit_session_error(&session_id, IoError::busy(&device_name).to_string());
@@ -587,8 +642,8 @@ async fn run_gs_usb_stream(
     );
 
     // Initialize device
-    let pad_enabled = match initialize_device(&interface, &config).await {
-        Ok(pad) => pad,
+    let started = match initialize_device(&interface, &config).await {
+        Ok(started) => started,
         Err(e) => {
             emit_session_error(&session_id, IoError::protocol(&device_name, format!("initialize: {}", e)).to_string());
             emit_stream_ended(&session_id, "error", "gs_usb");
@@ -596,7 +651,10 @@ async fn run_gs_usb_stream(
         }
     };
 
-    tlog!("[gs_usb:{}] Device initialized (pad_enabled: {}), starting stream", session_id, pad_enabled);
+    tlog!(
+        "[gs_usb:{}] Device initialized (pad_enabled: {}, hw_timestamps: {}), starting stream",
+        session_id, started.pad_enabled, started.hw_timestamps
+    );
 
     // Bulk IN endpoint — use discovered address
     let mut bulk_in = match interface.endpoint::<nusb::transfer::Bulk, nusb::transfer::In>(endpoints.in_addr) {
@@ -668,8 +726,9 @@ async fn run_gs_usb_stream(
     }
 
     // Determine frame stride for multi-frame transfer parsing
-    let frame_size = if config.enable_fd { GsHostFrameFd::SIZE } else { GsHostFrame::SIZE };
-    let frame_stride = if pad_enabled { buf_size } else { frame_size };
+    let frame_size = started.frame_size(config.enable_fd);
+    let frame_stride = if started.pad_enabled { buf_size } else { frame_size };
+    let mut hw_clock = started.hw_timestamps.then(|| HardwareClock::new(32));
 
     // Diagnostic counters
     let mut usb_completions: u64 = 0;
@@ -733,6 +792,9 @@ async fn run_gs_usb_stream(
                             let frame_data = &data[offset..end];
 
                             if let Some(mut frame) = parse_host_frame(frame_data) {
+                                if let Some(clock) = hw_clock.as_mut() {
+                                    stamp_hardware(&mut frame, frame_data, clock);
+                                }
                                 let is_tx = frame.direction.as_deref() == Some("tx");
                                 if is_tx {
                                     tx_echoes += 1;
@@ -813,7 +875,24 @@ async fn run_gs_usb_stream(
 
 /// Initialize the gs_usb device
 /// Initialize a gs_usb device. Returns whether PAD_PKTS_TO_MAX_PKT_SIZE is enabled.
-pub async fn initialize_device(interface: &Interface, config: &GsUsbConfig) -> Result<bool, String> {
+/// How the device was started.
+#[derive(Clone, Copy, Debug)]
+pub struct StartedMode {
+    /// Bulk transfers are padded to the max packet size
+    pub pad_enabled: bool,
+    /// Each host frame is followed by a 32-bit µs device timestamp
+    pub hw_timestamps: bool,
+}
+
+impl StartedMode {
+    /// Size of one host frame as the device sends it.
+    pub fn frame_size(&self, enable_fd: bool) -> usize {
+        let frame = if enable_fd { GsHostFrameFd::SIZE } else { GsHostFrame::SIZE };
+        if self.hw_timestamps { frame + HW_TIMESTAMP_SIZE } else { frame }
+    }
+}
+
+pub async fn initialize_device(interface: &Interface, config: &GsUsbConfig) -> Result<StartedMode, String> {
     // 1. Send HOST_FORMAT (byte order negotiation)
     let host_format = GS_USB_HOST_FORMAT.to_le_bytes();
     interface
@@ -1070,6 +1149,24 @@ pub async fn initialize_device(interface: &Interface, config: &GsUsbConfig) -> R
         mode_flags |= can_mode::PAD_PKTS_TO_MAX_PKT_SIZE;
     }
 
+    // Hardware timestamps when asked for and supported; host time otherwise
+    let hw_timestamps = config.hardware_timestamps
+        && bt_const.map(|c| c.feature & can_feature::HW_TIMESTAMP != 0).unwrap_or(false);
+    if hw_timestamps {
+        mode_flags |= can_mode::HW_TIMESTAMP;
+    } else if config.hardware_timestamps {
+        tlog!("[gs_usb] Device does not support hardware timestamps; using host receive time");
+    }
+
+    // Bus error reporting when asked for and supported
+    if config.error_frames {
+        if bt_const.map(|c| c.feature & can_feature::BERR_REPORTING != 0).unwrap_or(false) {
+            mode_flags |= can_mode::BERR_REPORTING;
+        } else {
+            tlog!("[gs_usb] Device does not support bus error reporting; only state changes are reported");
+        }
+    }
+
     let mode = GsDeviceMode {
         mode: 1, // Start
         flags: mode_flags,
@@ -1094,7 +1191,7 @@ pub async fn initialize_device(interface: &Interface, config: &GsUsbConfig) -> R
         .await
         .map_err(|e| format!("MODE failed: {:?}", e))?;
 
-    Ok(pad_enabled)
+    Ok(StartedMode { pad_enabled, hw_timestamps })
 }
 
 /// Stop the gs_usb device
@@ -1127,6 +1224,15 @@ pub async fn stop_device(interface: &Interface, config: &GsUsbConfig) -> Result<
     Ok(())
 }
 
+/// Replace a frame's host receive time with the device timestamp following
+/// it, aligned to the epoch. A frame without one keeps host time.
+fn stamp_hardware(frame: &mut FrameMessage, data: &[u8], clock: &mut HardwareClock) {
+    if let Some(raw) = host_frame_timestamp(data, frame.is_fd) {
+        frame.timestamp_us = clock.epoch_us(raw as u64, frame.timestamp_us);
+        frame.clock = Some(FrameClock::Hardware);
+    }
+}
+
 /// Parse a gs_usb host frame from raw bytes (classic CAN or FD).
 /// Returns the frame with direction set: "rx" for received, "tx" for echo responses.
 pub fn parse_host_frame(data: &[u8]) -> Option<FrameMessage> {
@@ -1140,6 +1246,15 @@ pub fn parse_host_frame(data: &[u8]) -> Option<FrameMessage> {
     let has_fd_flag = data.len() >= 12 && (data[10] & can_fd_flags::FD) != 0;
     let is_fd_frame = has_fd_flag || data[8] > 8;
 
+    // Error frames: CAN_ERR_FLAG in the ID word, details in the classic data
+    let raw_id = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+    if raw_id & can_id_flags::ERR != 0 {
+        let gs_frame = GsHostFrame::from_bytes(data)?;
+        let mut frame = can_error::error_frame(raw_id, gs_frame.get_data(), gs_frame.channel, now_us());
+        frame.clock = Some(FrameClock::Host);
+        return Some(frame);
+    }
+
     if is_fd_frame && data.len() >= GsHostFrameFd::SIZE {
         let gs_frame = GsHostFrameFd::from_bytes(data)?;
         let direction = if gs_frame.is_rx() { "rx" } else { "tx" };
@@ -1156,6 +1271,11 @@ pub fn parse_host_frame(data: &[u8]) -> Option<FrameMessage> {
             source_address: None,
             incomplete: None,
             direction: Some(direction.to_string()),
+            link: None,
+            clock: Some(FrameClock::Host),
+            is_error: None,
+            error_class: None,
+            brs: Some(gs_frame.is_brs()),
         })
     } else {
         let gs_frame = GsHostFrame::from_bytes(data)?;
@@ -1172,6 +1292,11 @@ pub fn parse_host_frame(data: &[u8]) -> Option<FrameMessage> {
             source_address: None,
             incomplete: None,
             direction: Some(direction.to_string()),
+            link: None,
+            clock: Some(FrameClock::Host),
+            is_error: None,
+            error_class: None,
+            brs: None,
         })
     }
 }
@@ -1193,6 +1318,8 @@ pub async fn run_source(
     enable_fd: bool,
     data_bitrate: u32,
     data_sample_point: f32,
+    hardware_timestamps: bool,
+    error_frames: bool,
     bus_mappings: Vec<BusMapping>,
     stop_flag: Arc<AtomicBool>,
     tx: mpsc::Sender<SourceMessage>,
@@ -1228,7 +1355,7 @@ pub async fn run_source(
 
     let endpoints = discover_bulk_endpoints(&device);
 
-    let interface = match device.claim_interface(0).await {
+    let interface = match claim_gs_usb_interface(&device).await {
         Ok(i) => i,
         Err(e) => {
             let _ = tx
@@ -1241,6 +1368,20 @@ pub async fn run_source(
         }
     };
 
+    // Open every channel the session maps; the profile's channel otherwise
+    let channels = super::source_channels(&bus_mappings, channel);
+    if let Some(count) = read_channel_count(&interface).await {
+        if let Some(&missing) = channels.iter().find(|&&ch| ch >= count) {
+            let _ = tx
+                .send(SourceMessage::Error(
+                    source_idx,
+                    format!("Device has {} channel(s); channel {} does not exist", count, missing),
+                ))
+                .await;
+            return;
+        }
+    }
+
     // Build config for initialization
     let config = GsUsbConfig {
         bus,
@@ -1257,32 +1398,46 @@ pub async fn run_source(
         data_bitrate,
         data_sample_point,
         can_clock_override: None,
+        hardware_timestamps,
+        error_frames,
     };
 
-    // Initialize device
-    let pad_enabled = match initialize_device(&interface, &config).await {
-        Ok(pad) => pad,
-        Err(e) => {
-            let _ = tx
-                .send(SourceMessage::Error(
-                    source_idx,
-                    format!("Failed to initialize device: {}", e),
-                ))
-                .await;
-            return;
+    // Initialize each channel; packet format and padding are the same on all
+    let channel_configs: Vec<GsUsbConfig> =
+        channels.iter().map(|&ch| GsUsbConfig { channel: ch, ..config.clone() }).collect();
+    let mut started = None;
+    for (i, channel_config) in channel_configs.iter().enumerate() {
+        match initialize_device(&interface, channel_config).await {
+            Ok(mode) => {
+                started.get_or_insert(mode);
+            }
+            Err(e) => {
+                for opened in &channel_configs[..i] {
+                    let _ = stop_device(&interface, opened).await;
+                }
+                let _ = tx
+                    .send(SourceMessage::Error(
+                        source_idx,
+                        format!("Failed to initialize channel {}: {}", channel_config.channel, e),
+                    ))
+                    .await;
+                return;
+            }
         }
-    };
+    }
+    let Some(started) = started else { return };
 
     tlog!(
-        "[gs_usb] Source {} connected to {}:{} (bitrate: {}, listen_only: {}, EP_IN: 0x{:02X}, EP_OUT: 0x{:02X}, max_pkt: {}, pad: {})",
-        source_idx, bus, address, bitrate, listen_only,
-        endpoints.in_addr, endpoints.out_addr, endpoints.max_packet_size, pad_enabled
+        "[gs_usb] Source {} connected to {}:{} (channels: {:?}, bitrate: {}, listen_only: {}, EP_IN: 0x{:02X}, EP_OUT: 0x{:02X}, max_pkt: {}, pad: {}, hw_timestamps: {})",
+        source_idx, bus, address, channels, bitrate, listen_only,
+        endpoints.in_addr, endpoints.out_addr, endpoints.max_packet_size, started.pad_enabled, started.hw_timestamps
     );
 
-    // Emit device-connected event
+    // Emit device-connected event (bus number only when a single channel is open)
     let addr_str = format!("{}:{}", bus, address);
+    let bus_number = (channels.len() == 1).then(|| channels[0]);
     let _ = tx
-        .send(SourceMessage::Connected(source_idx, "gs_usb".to_string(), addr_str, Some(channel)))
+        .send(SourceMessage::Connected(source_idx, "gs_usb".to_string(), addr_str, bus_number))
         .await;
 
     // Bulk IN endpoint — use discovered address
@@ -1347,8 +1502,9 @@ pub async fn run_source(
     let buf_size = endpoints.max_packet_size;
 
     // Determine frame stride for multi-frame transfer parsing
-    let frame_size = if enable_fd { GsHostFrameFd::SIZE } else { GsHostFrame::SIZE };
-    let frame_stride = if pad_enabled { buf_size } else { frame_size };
+    let frame_size = started.frame_size(enable_fd);
+    let frame_stride = if started.pad_enabled { buf_size } else { frame_size };
+    let mut hw_clock = started.hw_timestamps.then(|| HardwareClock::new(32));
 
     // Pre-submit read requests
     for _ in 0..4 {
@@ -1396,6 +1552,9 @@ pub async fn run_source(
                         let frame_data = &data[offset..end];
 
                         if let Some(mut frame_msg) = parse_host_frame(frame_data) {
+                            if let Some(clock) = hw_clock.as_mut() {
+                                stamp_hardware(&mut frame_msg, frame_data, clock);
+                            }
                             let is_tx = frame_msg.direction.as_deref() == Some("tx");
                             if is_tx {
                                 tx_echoes += 1;
@@ -1457,7 +1616,9 @@ pub async fn run_source(
         task.abort();
     }
 
-    let _ = stop_device(&interface, &config).await;
+    for channel_config in &channel_configs {
+        let _ = stop_device(&interface, channel_config).await;
+    }
 
     let _ = tx
         .send(SourceMessage::Ended(source_idx, "stopped".to_string()))
@@ -12,6 +12,7 @@
 
 #![allow(dead_code)]
 
+use crate::io::can_error;
 use crate::io::error::IoError;
 use crate::io::{now_us, CanTransmitFrame, FrameMessage};
 use crate::io::codec::FrameCodec;
@@ -49,7 +50,7 @@ impl FrameCodec for GvretCodec {
     ///
     /// Expects a complete frame starting with `[0xF1][0x00]`.
     /// The frame must include the full header (11 bytes) plus data.
-    fn decode(raw: &[u8]) -> Result<FrameMessage, IoError> {
+    fn decode(&self, raw: &[u8]) -> Result<FrameMessage, IoError> {
         use constants::*;
 
         if raw.len() < RX_HEADER_LEN {
@@ -103,6 +104,11 @@ impl FrameCodec for GvretCodec {
             Vec::new()
         };
 
+        let bus = (bus_dlc >> 4) & 0x0F;
+        if can_error::is_error_id(can_id) {
+            return Ok(can_error::error_frame(can_id, &data, bus, now_us()));
+        }
+
         let is_ext = (can_id & CAN_EFF_FLAG) != 0;
         let arb_id = can_id
             & if is_ext {
@@ -111,7 +117,6 @@ impl FrameCodec for GvretCodec {
                 CAN_SFF_MASK
             };
         let is_fd = payload_len > 8;
-        let bus = (bus_dlc >> 4) & 0x0F;
 
         Ok(FrameMessage {
             protocol: "can".to_string(),
@@ -125,13 +130,18 @@ impl FrameCodec for GvretCodec {
             source_address: None,
             incomplete: None,
             direction: None,
+            link: None,
+            clock: None,
+            is_error: None,
+            error_class: None,
+            brs: None,
         })
     }
 
     /// Encode a CAN frame to GVRET binary format for transmission.
     ///
     /// Format: `[0xF1][0x00][FrameID-4bytes-LE][Bus-1byte][Length-1byte][Data...]`
-    fn encode(frame: &CanTransmitFrame) -> Result<Vec<u8>, IoError> {
+    fn encode(&self, frame: &CanTransmitFrame) -> Result<Vec<u8>, IoError> {
         use constants::*;
 
         // Validate data length
@@ -207,7 +217,7 @@ mod tests {
             0xAA, 0xBB, 0xCC, 0xDD, // Data
         ];
 
-        let frame = GvretCodec::decode(&raw).unwrap();
+        let frame = GvretCodec.decode(&raw).unwrap();
         assert_eq!(frame.frame_id, 0x123);
         assert_eq!(frame.dlc, 4);
         assert_eq!(frame.bytes, vec![0xAA, 0xBB, 0xCC, 0xDD]);
@@ -226,12 +236,29 @@ mod tests {
             0x11, 0x22, // Data
         ];
 
-        let frame = GvretCodec::decode(&raw).unwrap();
+        let frame = GvretCodec.decode(&raw).unwrap();
         assert_eq!(frame.frame_id, 0x12345678);
         assert!(frame.is_extended);
         assert_eq!(frame.bytes, vec![0x11, 0x22]);
     }
 
+    #[test]
+    fn test_gvret_decode_error_frame() {
+        // Bus-off error frame on bus 1
+        let raw = [
+            0xF1, 0x00, // Sync + command
+            0x00, 0x00, 0x00, 0x00, // Timestamp
+            0x40, 0x00, 0x00, 0x20, // CAN_ERR_FLAG | CAN_ERR_BUSOFF LE
+            0x18, // Bus 1, DLC 8
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // Data
+        ];
+
+        let frame = GvretCodec.decode(&raw).unwrap();
+        assert_eq!(frame.is_error, Some(true));
+        assert_eq!(frame.error_class, Some(crate::io::CanErrorClass::BusOff));
+        assert_eq!(frame.bus, 1);
+    }
+
     #[test]
     fn test_gvret_encode_standard_frame() {
         let frame = CanTransmitFrame {
@@ -244,7 +271,7 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = GvretCodec::encode(&frame).unwrap();
+        let encoded = GvretCodec.encode(&frame).unwrap();
 
         assert_eq!(encoded[0], 0xF1); // Sync
         assert_eq!(encoded[1], 0x00); // Command
@@ -269,7 +296,7 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = GvretCodec::encode(&frame).unwrap();
+        let encoded = GvretCodec.encode(&frame).unwrap();
 
         // ID with extended flag: 0x12345678 | 0x80000000 = 0x92345678
         // Little-endian: [0x78, 0x56, 0x34, 0x92]
@@ -292,7 +319,7 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = GvretCodec::encode(&original).unwrap();
+        let encoded = GvretCodec.encode(&original).unwrap();
 
         // Build a receive frame from the encoded data
         // TX format: [sync][cmd][id:4][bus][len][data...]
@@ -302,7 +329,7 @@ mod tests {
         rx_frame.push((original.bus << 4) | (original.data.len() as u8)); // bus_dlc
         rx_frame.extend_from_slice(&original.data);
 
-        let decoded = GvretCodec::decode(&rx_frame).unwrap();
+        let decoded = GvretCodec.decode(&rx_frame).unwrap();
         assert_eq!(decoded.frame_id, original.frame_id);
         assert_eq!(decoded.bytes, original.data);
         assert_eq!(decoded.bus, original.bus);
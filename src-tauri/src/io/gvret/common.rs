@@ -13,9 +13,12 @@
 // Frame ID encoding:
 //   - Standard (11-bit): Lower 11 bits, bit 31 = 0
 //   - Extended (29-bit): Lower 29 bits, bit 31 = 1 (0x80000000)
+//   - Error frame: bit 29 = 1 (CAN_ERR_FLAG), as error-forwarding firmware
+//     sends them; the rest follows the SocketCAN error frame layout
 
 use hex::ToHex;
 
+use crate::io::can_error;
 use crate::io::{now_us, CanTransmitFrame, FrameMessage, InterfaceTraits, Protocol, TemporalMode, TransmitResult};
 
 // ============================================================================
@@ -39,6 +42,21 @@ pub const BINARY_MODE_ENABLE: [u8; 2] = [0xE7, 0xE7];
 pub const DEVICE_INFO_PROBE: [u8; 2] = [0xF1, 0x07];
 /// Number of buses query command
 pub const GVRET_CMD_NUMBUSES: [u8; 2] = [0xF1, 0x0C];
+/// CAN bus parameters query command (buses 0 and 1)
+pub const GVRET_CMD_CANPARAMS: [u8; 2] = [0xF1, 0x06];
+/// CAN bus setup command; followed by one 32-bit word per bus
+pub const GVRET_CMD_SETUP_CANBUS: [u8; 2] = [0xF1, 0x05];
+
+/// SETUP_CANBUS word flags: the word carries enable and listen-only state
+/// (otherwise it is a bare bitrate and the bus is simply enabled)
+const SETUP_STATE_PRESENT: u32 = 0x8000_0000;
+const SETUP_ENABLED: u32 = 0x4000_0000;
+const SETUP_LISTEN_ONLY: u32 = 0x2000_0000;
+const SETUP_SPEED_MASK: u32 = 0x000F_FFFF;
+
+/// Bitrate range accepted when configuring a bus
+pub const GVRET_MIN_BITRATE: u32 = 10_000;
+pub const GVRET_MAX_BITRATE: u32 = 1_000_000;
 
 /// DLC to payload length mapping (CAN FD DLC codes)
 pub const DLC_LEN: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
@@ -69,6 +87,90 @@ pub fn parse_numbuses_response(buffer: &[u8]) -> Option<u8> {
     None
 }
 
+// ============================================================================
+// Bus Setup
+// ============================================================================
+
+/// State of one CAN bus as reported by CANPARAMS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct GvretBusParams {
+    pub enabled: bool,
+    pub listen_only: bool,
+    pub bitrate: u32,
+}
+
+/// Parse a CANPARAMS response from a buffer.
+///
+/// Searches for `[0xF1][0x06]` followed by, for buses 0 and 1, a flags byte
+/// (bit 0 = enabled, bit 4 = listen-only) and a little-endian bitrate.
+pub fn parse_canparams_response(buffer: &[u8]) -> Option<[GvretBusParams; 2]> {
+    let start = buffer.windows(2).position(|w| w == GVRET_CMD_CANPARAMS)?;
+    let reply = buffer.get(start + 2..start + 12)?;
+    let bus = |at: usize| GvretBusParams {
+        enabled: reply[at] & 0x01 != 0,
+        listen_only: reply[at] & 0x10 != 0,
+        bitrate: u32::from_le_bytes([reply[at + 1], reply[at + 2], reply[at + 3], reply[at + 4]]),
+    };
+    Some([bus(0), bus(5)])
+}
+
+/// SETUP_CANBUS command writing each bus's parameters as given.
+pub fn setup_command(params: &[GvretBusParams; 2]) -> Vec<u8> {
+    let mut cmd = GVRET_CMD_SETUP_CANBUS.to_vec();
+    for bus in params {
+        let mut word = SETUP_STATE_PRESENT | (bus.bitrate & SETUP_SPEED_MASK);
+        if bus.enabled {
+            word |= SETUP_ENABLED;
+        }
+        if bus.listen_only {
+            word |= SETUP_LISTEN_ONLY;
+        }
+        cmd.extend_from_slice(&word.to_le_bytes());
+    }
+    cmd
+}
+
+/// SETUP_CANBUS command keeping each bus's enable state and bitrate and
+/// setting its listen-only flag.
+pub fn setup_canbus_command(params: &[GvretBusParams; 2], listen_only: bool) -> Vec<u8> {
+    setup_command(&params.map(|bus| GvretBusParams { listen_only, ..bus }))
+}
+
+/// Change to one bus's parameters; unset fields keep the device's value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GvretBusChange {
+    pub bitrate: Option<u32>,
+    pub enabled: Option<bool>,
+    pub listen_only: Option<bool>,
+}
+
+impl GvretBusChange {
+    pub fn is_empty(&self) -> bool {
+        self.bitrate.is_none() && self.enabled.is_none() && self.listen_only.is_none()
+    }
+
+    /// The device's parameters with this change made to `bus`.
+    pub fn apply(&self, params: &[GvretBusParams; 2], bus: usize) -> [GvretBusParams; 2] {
+        let mut out = *params;
+        let target = &mut out[bus];
+        target.bitrate = self.bitrate.unwrap_or(target.bitrate);
+        target.enabled = self.enabled.unwrap_or(target.enabled);
+        target.listen_only = self.listen_only.unwrap_or(target.listen_only);
+        out
+    }
+}
+
+/// Whether the device reports a bus as configured. A disabled bus only has to
+/// be disabled; its bitrate and mode don't matter.
+pub fn bus_matches(expected: &GvretBusParams, reported: &GvretBusParams) -> bool {
+    expected.enabled == reported.enabled && (!expected.enabled || expected == reported)
+}
+
+/// Whether every enabled bus reports the requested listen-only state.
+pub fn buses_in_mode(params: &[GvretBusParams; 2], listen_only: bool) -> bool {
+    params.iter().filter(|b| b.enabled).all(|b| b.listen_only == listen_only)
+}
+
 // ============================================================================
 // Device Info Types
 // ============================================================================
@@ -305,6 +407,12 @@ pub fn parse_gvret_frames(buffer: &mut Vec<u8>) -> Vec<(FrameMessage, String)> {
         // Use host UNIX time in microseconds
         let ts_us = now_us();
 
+        if can_error::is_error_id(can_id) {
+            out.push((can_error::error_frame(can_id, &data, bus, ts_us), frame_bytes));
+            buffer.drain(0..total_len);
+            continue;
+        }
+
         out.push((
             FrameMessage {
                 protocol: "can".to_string(),
@@ -318,6 +426,11 @@ pub fn parse_gvret_frames(buffer: &mut Vec<u8>) -> Vec<(FrameMessage, String)> {
                 source_address: None,
                 incomplete: None,
                 direction: None, // Received frames don't have direction set
+                link: None,
+                clock: None,
+                is_error: None,
+                error_class: None,
+                brs: None,
             },
             frame_bytes,
         ));
@@ -665,4 +778,46 @@ mod tests {
         let buffer = vec![0xF1, 0x0C];
         assert_eq!(parse_numbuses_response(&buffer), None);
     }
+
+    #[test]
+    fn test_parse_canparams_response() {
+        // Bus 0 enabled at 500k, bus 1 enabled and listen-only at 250k
+        let buffer = vec![0x00, 0xF1, 0x06, 0x01, 0x20, 0xA1, 0x07, 0x00, 0x11, 0x90, 0xD0, 0x03, 0x00];
+        let params = parse_canparams_response(&buffer).unwrap();
+        assert_eq!(params[0], GvretBusParams { enabled: true, listen_only: false, bitrate: 500_000 });
+        assert_eq!(params[1], GvretBusParams { enabled: true, listen_only: true, bitrate: 250_000 });
+        assert!(!buses_in_mode(&params, true));
+        assert_eq!(parse_canparams_response(&buffer[..8]), None);
+    }
+
+    #[test]
+    fn test_setup_canbus_listen_only() {
+        let params = [
+            GvretBusParams { enabled: true, listen_only: false, bitrate: 500_000 },
+            GvretBusParams { enabled: false, listen_only: false, bitrate: 0 },
+        ];
+        let cmd = setup_canbus_command(&params, true);
+        assert_eq!(&cmd[..2], &[0xF1, 0x05]);
+        assert_eq!(u32::from_le_bytes(cmd[2..6].try_into().unwrap()), 0xE000_0000 | 500_000);
+        assert_eq!(u32::from_le_bytes(cmd[6..10].try_into().unwrap()), 0xA000_0000);
+    }
+
+    #[test]
+    fn test_bus_change_touches_one_bus() {
+        let params = [
+            GvretBusParams { enabled: true, listen_only: false, bitrate: 500_000 },
+            GvretBusParams { enabled: true, listen_only: true, bitrate: 250_000 },
+        ];
+        let change = GvretBusChange { bitrate: Some(125_000), listen_only: Some(true), ..Default::default() };
+        let next = change.apply(&params, 0);
+        assert_eq!(next[0], GvretBusParams { enabled: true, listen_only: true, bitrate: 125_000 });
+        assert_eq!(next[1], params[1]);
+        let cmd = setup_command(&next);
+        assert_eq!(u32::from_le_bytes(cmd[2..6].try_into().unwrap()), 0xE000_0000 | 125_000);
+        assert_eq!(u32::from_le_bytes(cmd[6..10].try_into().unwrap()), 0xE000_0000 | 250_000);
+
+        let disabled = GvretBusParams { enabled: false, listen_only: false, bitrate: 0 };
+        assert!(bus_matches(&disabled, &GvretBusParams { bitrate: 500_000, ..disabled }));
+        assert!(!bus_matches(&next[0], &params[0]));
+    }
 }
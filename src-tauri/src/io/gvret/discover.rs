@@ -0,0 +1,207 @@
+// ui/src-tauri/src/io/gvret/discover.rs
+//
+// LAN discovery of GVRET TCP devices (ESP32RET, WiCAN and friends) so a new
+// profile can be prefilled without knowing the device's DHCP address.
+//
+// Two sources run in parallel for the scan window:
+//   - UDP beacon: ESP32RET-style firmware broadcasts a datagram to port
+//     17222 about once a second (the port SavvyCAN listens on). The sender
+//     address is the device; a printable payload is taken as its name.
+//   - mDNS: browse the service types these devices register.
+//
+// Every candidate is then probed with a short GVRET handshake; those that
+// answer carry `bus_count`, the rest are still returned (a beacon from a
+// busy device may not accept a second connection).
+
+use futures::future::join_all;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+
+use super::tcp::probe_gvret_tcp;
+
+/// Port ESP32RET-style firmware broadcasts its beacon to.
+const BEACON_PORT: u16 = 17222;
+
+/// GVRET TCP port when a beacon doesn't say otherwise.
+const DEFAULT_GVRET_PORT: u16 = 23;
+
+/// mDNS service types GVRET devices register. ESP32RET advertises its
+/// GVRET listener as telnet; the probe weeds out real telnet servers.
+const MDNS_SERVICE_TYPES: &[&str] = &["_gvret._tcp.local.", "_telnet._tcp.local."];
+
+/// Default scan window.
+const DEFAULT_SCAN_MS: u64 = 3000;
+
+/// Per-candidate probe timeout.
+const PROBE_TIMEOUT_SEC: f64 = 1.5;
+
+/// A device found on the LAN.
+#[derive(Debug, Clone, Serialize)]
+pub struct GvretCandidate {
+    pub host: String,
+    pub port: u16,
+    /// Beacon payload or mDNS instance name, when available
+    pub name: Option<String>,
+    /// "beacon" | "mdns" (first source that saw the host)
+    pub source: String,
+    /// Set when the device answered a GVRET probe
+    pub bus_count: Option<u8>,
+}
+
+/// Result of a discovery scan.
+#[derive(Debug, Clone, Serialize)]
+pub struct GvretDiscoveryResult {
+    pub candidates: Vec<GvretCandidate>,
+    /// Non-fatal problems (e.g. beacon port already in use)
+    pub errors: Vec<String>,
+}
+
+/// Take a beacon payload as a device name when it is printable text.
+fn beacon_name(payload: &[u8]) -> Option<String> {
+    let text = std::str::from_utf8(payload).ok()?.trim_matches(|c: char| c.is_whitespace() || c == '\0');
+    if text.is_empty() || text.len() > 64 || !text.chars().all(|c| c.is_ascii_graphic() || c == ' ') {
+        return None;
+    }
+    Some(text.to_string())
+}
+
+/// Listen for beacons until `deadline`. Returns host -> name.
+async fn scan_beacons(deadline: Instant) -> Result<Vec<(IpAddr, Option<String>)>, String> {
+    let socket = UdpSocket::bind(("0.0.0.0", BEACON_PORT))
+        .await
+        .map_err(|e| format!("Beacon listener on port {} unavailable: {}", BEACON_PORT, e))?;
+    let mut found = Vec::new();
+    let mut buf = [0u8; 512];
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        match tokio::time::timeout(remaining, socket.recv_from(&mut buf)).await {
+            Ok(Ok((len, from))) => found.push((from.ip(), beacon_name(&buf[..len]))),
+            Ok(Err(e)) => return Err(format!("Beacon receive failed: {}", e)),
+            Err(_) => break,
+        }
+    }
+    Ok(found)
+}
+
+/// Browse mDNS until `deadline`. Returns (host, port, instance name).
+fn scan_mdns(deadline: Instant) -> Result<Vec<(IpAddr, u16, String)>, String> {
+    let daemon = mdns_sd::ServiceDaemon::new().map_err(|e| format!("mDNS unavailable: {}", e))?;
+    let receivers = MDNS_SERVICE_TYPES
+        .iter()
+        .filter_map(|ty| daemon.browse(ty).ok())
+        .collect::<Vec<_>>();
+    let mut found = Vec::new();
+    'scan: loop {
+        for rx in &receivers {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break 'scan;
+            }
+            // Poll each browse in short slices so one quiet type doesn't starve the other
+            let slice = remaining.min(Duration::from_millis(100));
+            if let Ok(mdns_sd::ServiceEvent::ServiceResolved(info)) = rx.recv_timeout(slice) {
+                let name = info
+                    .get_fullname()
+                    .split('.')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                for addr in info.get_addresses_v4() {
+                    found.push((IpAddr::V4(*addr), info.get_port(), name.clone()));
+                }
+            }
+        }
+    }
+    let _ = daemon.shutdown();
+    Ok(found)
+}
+
+/// Scan the LAN for GVRET TCP devices for `scan_ms` (default 3 s), then
+/// probe each candidate.
+pub async fn discover_gvret_tcp(scan_ms: Option<u64>) -> GvretDiscoveryResult {
+    let deadline = Instant::now() + Duration::from_millis(scan_ms.unwrap_or(DEFAULT_SCAN_MS));
+    tlog!("[gvret_discover] Scanning for GVRET devices");
+
+    let mdns_task = tokio::task::spawn_blocking(move || scan_mdns(deadline));
+    let beacons = scan_beacons(deadline).await;
+    let mdns = mdns_task
+        .await
+        .unwrap_or_else(|e| Err(format!("mDNS scan failed: {}", e)));
+
+    let mut errors = Vec::new();
+    // Keyed by host so a device seen by both sources is listed once
+    let mut candidates: BTreeMap<String, GvretCandidate> = BTreeMap::new();
+    match beacons {
+        Ok(found) => {
+            for (ip, name) in found {
+                let entry = candidates.entry(ip.to_string()).or_insert_with(|| GvretCandidate {
+                    host: ip.to_string(),
+                    port: DEFAULT_GVRET_PORT,
+                    name: None,
+                    source: "beacon".to_string(),
+                    bus_count: None,
+                });
+                if entry.name.is_none() {
+                    entry.name = name;
+                }
+            }
+        }
+        Err(e) => errors.push(e),
+    }
+    match mdns {
+        Ok(found) => {
+            for (ip, port, name) in found {
+                let entry = candidates.entry(ip.to_string()).or_insert_with(|| GvretCandidate {
+                    host: ip.to_string(),
+                    port,
+                    name: None,
+                    source: "mdns".to_string(),
+                    bus_count: None,
+                });
+                if entry.name.is_none() && !name.is_empty() {
+                    entry.name = Some(name);
+                }
+            }
+        }
+        Err(e) => errors.push(e),
+    }
+
+    let mut candidates: Vec<GvretCandidate> = candidates.into_values().collect();
+    let probes = join_all(
+        candidates
+            .iter()
+            .map(|c| probe_gvret_tcp(&c.host, c.port, PROBE_TIMEOUT_SEC)),
+    )
+    .await;
+    for (candidate, probe) in candidates.iter_mut().zip(probes) {
+        candidate.bus_count = probe.ok().map(|info| info.bus_count);
+    }
+    // Devices that answered first
+    candidates.sort_by_key(|c| c.bus_count.is_none());
+
+    tlog!(
+        "[gvret_discover] Found {} candidate(s), {} responding",
+        candidates.len(),
+        candidates.iter().filter(|c| c.bus_count.is_some()).count()
+    );
+    GvretDiscoveryResult { candidates, errors }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beacon_name_accepts_printable_text_only() {
+        assert_eq!(beacon_name(b"ESP32RET\0"), Some("ESP32RET".to_string()));
+        assert_eq!(beacon_name(b"  wican 1.2\r\n"), Some("wican 1.2".to_string()));
+        assert_eq!(beacon_name(&[0x01, 0xff, 0x02]), None);
+        assert_eq!(beacon_name(b""), None);
+    }
+}
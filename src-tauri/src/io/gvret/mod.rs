@@ -6,16 +6,18 @@
 
 pub mod codec;
 mod common;
+mod discover;
 mod tcp;
 #[cfg(not(target_os = "ios"))]
 mod usb;
 
 // Re-export public items
 pub use codec::GvretCodec;
-pub use common::{BusMapping, GvretDeviceInfo};
-pub use tcp::probe_gvret_tcp;
+pub use common::{BusMapping, GvretBusChange, GvretBusParams, GvretDeviceInfo, GVRET_MAX_BITRATE, GVRET_MIN_BITRATE};
+pub use discover::{discover_gvret_tcp, GvretCandidate, GvretDiscoveryResult};
+pub use tcp::{configure_gvret_tcp, probe_gvret_tcp};
 #[cfg(not(target_os = "ios"))]
-pub use usb::probe_gvret_usb;
+pub use usb::{configure_gvret_usb, probe_gvret_usb};
 
 // Internal items used by multi_source and other drivers
 #[cfg(not(target_os = "ios"))]
@@ -5,15 +5,17 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc as std_mpsc, Arc};
 use std::time::Duration;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
 
 use crate::io::error::IoError;
 use crate::io::types::{SourceMessage, TransmitRequest};
 use super::common::{
-    apply_bus_mappings_gvret, parse_gvret_frames, parse_numbuses_response, BusMapping,
-    BINARY_MODE_ENABLE, DEVICE_INFO_PROBE, GVRET_CMD_NUMBUSES, GvretDeviceInfo,
+    apply_bus_mappings_gvret, bus_matches, buses_in_mode, parse_canparams_response, parse_gvret_frames,
+    parse_numbuses_response, setup_canbus_command, setup_command, BusMapping, GvretBusChange,
+    GvretBusParams, BINARY_MODE_ENABLE, DEVICE_INFO_PROBE, GVRET_CMD_CANPARAMS, GVRET_CMD_NUMBUSES,
+    GvretDeviceInfo,
 };
 
 // ============================================================================
@@ -127,16 +129,123 @@ pub async fn probe_gvret_tcp(
     Ok(GvretDeviceInfo { bus_count: 1 })
 }
 
+// ============================================================================
+// Bus Setup
+// ============================================================================
+
+/// Query CANPARAMS and wait for the reply.
+async fn query_canparams<R, W>(reader: &mut R, writer: &mut W) -> Result<[GvretBusParams; 2], String>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    writer
+        .write_all(&GVRET_CMD_CANPARAMS)
+        .await
+        .map_err(|e| format!("Failed to query bus parameters: {}", e))?;
+    let _ = writer.flush().await;
+
+    let mut buf = Vec::with_capacity(512);
+    let mut read_buf = [0u8; 256];
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(1);
+    loop {
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            return Err("No bus parameters reply from device".to_string());
+        }
+        match tokio::time::timeout(remaining, reader.read(&mut read_buf)).await {
+            Ok(Ok(0)) => return Err("Connection closed during bus setup".to_string()),
+            Ok(Ok(n)) => {
+                buf.extend_from_slice(&read_buf[..n]);
+                if let Some(params) = parse_canparams_response(&buf) {
+                    return Ok(params);
+                }
+            }
+            Ok(Err(e)) => return Err(format!("Read error during bus setup: {}", e)),
+            Err(_) => {}
+        }
+    }
+}
+
+/// Put buses 0 and 1 into listen-only mode at their current bitrates and
+/// confirm the device took it. Returns the parameters found beforehand.
+async fn enter_listen_only<R, W>(reader: &mut R, writer: &mut W) -> Result<[GvretBusParams; 2], String>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let before = query_canparams(reader, writer).await?;
+    if buses_in_mode(&before, true) {
+        return Ok(before);
+    }
+    writer
+        .write_all(&setup_canbus_command(&before, true))
+        .await
+        .map_err(|e| format!("Failed to set listen-only mode: {}", e))?;
+    let _ = writer.flush().await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let after = query_canparams(reader, writer).await?;
+    if !buses_in_mode(&after, true) {
+        return Err("Device did not enter listen-only mode".to_string());
+    }
+    Ok(before)
+}
+
+/// Connect to a GVRET TCP device, change one bus and confirm the device took
+/// it. Returns the parameters of both buses afterwards.
+pub async fn configure_gvret_tcp(
+    host: &str,
+    port: u16,
+    timeout_sec: f64,
+    bus: u8,
+    change: GvretBusChange,
+) -> Result<[GvretBusParams; 2], String> {
+    let mut stream = tokio::time::timeout(
+        Duration::from_secs_f64(timeout_sec),
+        TcpStream::connect((host, port)),
+    )
+    .await
+    .map_err(|_| format!("Timed out connecting to {}:{}", host, port))?
+    .map_err(|e| format!("Failed to connect to {}:{}: {}", host, port, e))?;
+    let (mut reader, mut writer) = stream.split();
+
+    writer
+        .write_all(&BINARY_MODE_ENABLE)
+        .await
+        .map_err(|e| format!("Failed to enable binary mode: {}", e))?;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let before = query_canparams(&mut reader, &mut writer).await?;
+    let wanted = change.apply(&before, bus as usize);
+    writer
+        .write_all(&setup_command(&wanted))
+        .await
+        .map_err(|e| format!("Failed to send bus setup: {}", e))?;
+    let _ = writer.flush().await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let after = query_canparams(&mut reader, &mut writer).await?;
+    if !bus_matches(&wanted[bus as usize], &after[bus as usize]) {
+        return Err(format!("Device did not apply the setup to bus {}", bus));
+    }
+    tlog!("[configure_gvret_tcp] {}:{} bus {} now {:?}", host, port, bus, after[bus as usize]);
+    Ok(after)
+}
+
 // ============================================================================
 // Multi-Source Streaming
 // ============================================================================
 
-/// Run GVRET TCP source and send frames to merge task
+/// Run GVRET TCP source and send frames to merge task. With `listen_only`,
+/// buses 0 and 1 are switched to listen-only mode (and back when the source
+/// stops) and no transmit channel is offered.
 pub async fn run_source(
     source_idx: usize,
     host: String,
     port: u16,
     timeout_sec: f64,
+    listen_only: bool,
     bus_mappings: Vec<BusMapping>,
     stop_flag: Arc<AtomicBool>,
     tx: mpsc::Sender<SourceMessage>,
@@ -187,19 +296,34 @@ pub async fn run_source(
 
     tokio::time::sleep(Duration::from_millis(100)).await;
 
+    // Listen-only before any frame can be sent
+    let mut restore_params = None;
+    if listen_only {
+        match enter_listen_only(&mut read_half, &mut write_half).await {
+            Ok(before) => restore_params = (!buses_in_mode(&before, true)).then_some(before),
+            Err(e) => {
+                let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+                return;
+            }
+        }
+    }
+
     // Send device info probe
     let _ = write_half.write_all(&DEVICE_INFO_PROBE).await;
     let _ = write_half.flush().await;
 
-    // Create transmit channel and send it to the merge task
+    // Create transmit channel and send it to the merge task (not in listen-only mode)
     let (transmit_tx, transmit_rx) = std_mpsc::sync_channel::<TransmitRequest>(32);
-    let _ = tx
-        .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
-        .await;
+    if !listen_only {
+        let _ = tx
+            .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
+            .await;
+    }
 
     tlog!(
-        "[gvret_tcp] Source {} connected to {}:{}, transmit channel ready",
-        source_idx, host, port
+        "[gvret_tcp] Source {} connected to {}:{}, {}",
+        source_idx, host, port,
+        if listen_only { "listen-only" } else { "transmit channel ready" }
     );
 
     // Emit device-connected event
@@ -284,6 +408,13 @@ pub async fn run_source(
     // Abort the transmit task when the read loop exits
     transmit_task.abort();
 
+    // Leave the buses as they were found
+    if let Some(params) = restore_params {
+        let mut writer = write_half.lock().await;
+        let _ = writer.write_all(&setup_canbus_command(&params, false)).await;
+        let _ = writer.flush().await;
+    }
+
     let _ = tx
         .send(SourceMessage::Ended(source_idx, "stopped".to_string()))
         .await;
@@ -15,8 +15,10 @@ use tokio::sync::mpsc;
 use crate::io::error::IoError;
 use crate::io::types::{SourceMessage, TransmitRequest};
 use super::common::{
-    apply_bus_mappings_gvret, parse_gvret_frames, parse_numbuses_response, BusMapping,
-    BINARY_MODE_ENABLE, DEVICE_INFO_PROBE, GVRET_CMD_NUMBUSES, GvretDeviceInfo,
+    apply_bus_mappings_gvret, bus_matches, buses_in_mode, parse_canparams_response, parse_gvret_frames,
+    parse_numbuses_response, setup_canbus_command, setup_command, BusMapping, GvretBusChange,
+    GvretBusParams, BINARY_MODE_ENABLE, DEVICE_INFO_PROBE, GVRET_CMD_CANPARAMS, GVRET_CMD_NUMBUSES,
+    GvretDeviceInfo,
 };
 
 // ============================================================================
@@ -134,11 +136,93 @@ pub fn probe_gvret_usb(port: &str, baud_rate: u32) -> Result<GvretDeviceInfo, Io
 // Multi-Source Streaming
 // ============================================================================
 
-/// Run GVRET USB source and send frames to merge task
+/// Query CANPARAMS and wait for the reply.
+fn query_canparams<P: Read + Write + ?Sized>(port: &mut P) -> Result<[GvretBusParams; 2], String> {
+    port.write_all(&GVRET_CMD_CANPARAMS)
+        .map_err(|e| format!("Failed to query bus parameters: {}", e))?;
+    let _ = port.flush();
+
+    let mut buf = Vec::with_capacity(512);
+    let mut read_buf = [0u8; 256];
+    let deadline = std::time::Instant::now() + Duration::from_secs(1);
+    while std::time::Instant::now() < deadline {
+        match port.read(&mut read_buf) {
+            Ok(0) => std::thread::sleep(Duration::from_millis(10)),
+            Ok(n) => {
+                buf.extend_from_slice(&read_buf[..n]);
+                if let Some(params) = parse_canparams_response(&buf) {
+                    return Ok(params);
+                }
+            }
+            Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(e) => return Err(format!("Read error during bus setup: {}", e)),
+        }
+    }
+    Err("No bus parameters reply from device".to_string())
+}
+
+/// Put buses 0 and 1 into listen-only mode at their current bitrates and
+/// confirm the device took it. Returns the parameters found beforehand.
+fn enter_listen_only<P: Read + Write + ?Sized>(port: &mut P) -> Result<[GvretBusParams; 2], String> {
+    let before = query_canparams(port)?;
+    if buses_in_mode(&before, true) {
+        return Ok(before);
+    }
+    port.write_all(&setup_canbus_command(&before, true))
+        .map_err(|e| format!("Failed to set listen-only mode: {}", e))?;
+    let _ = port.flush();
+    std::thread::sleep(Duration::from_millis(100));
+
+    let after = query_canparams(port)?;
+    if !buses_in_mode(&after, true) {
+        return Err("Device did not enter listen-only mode".to_string());
+    }
+    Ok(before)
+}
+
+/// Open a GVRET USB device, change one bus and confirm the device took it.
+/// Returns the parameters of both buses afterwards. Blocking.
+pub fn configure_gvret_usb(
+    port: &str,
+    baud_rate: u32,
+    bus: u8,
+    change: GvretBusChange,
+) -> Result<[GvretBusParams; 2], String> {
+    let mut serial_port = serialport::new(port, baud_rate)
+        .timeout(Duration::from_millis(100))
+        .open()
+        .map_err(|e| format!("Failed to open port: {}", e))?;
+    let _ = serial_port.clear(serialport::ClearBuffer::All);
+    serial_port
+        .write_all(&BINARY_MODE_ENABLE)
+        .map_err(|e| format!("Failed to enable binary mode: {}", e))?;
+    let _ = serial_port.flush();
+    std::thread::sleep(Duration::from_millis(100));
+
+    let before = query_canparams(&mut *serial_port)?;
+    let wanted = change.apply(&before, bus as usize);
+    serial_port
+        .write_all(&setup_command(&wanted))
+        .map_err(|e| format!("Failed to send bus setup: {}", e))?;
+    let _ = serial_port.flush();
+    std::thread::sleep(Duration::from_millis(100));
+
+    let after = query_canparams(&mut *serial_port)?;
+    if !bus_matches(&wanted[bus as usize], &after[bus as usize]) {
+        return Err(format!("Device did not apply the setup to bus {}", bus));
+    }
+    tlog!("[configure_gvret_usb] {} bus {} now {:?}", port, bus, after[bus as usize]);
+    Ok(after)
+}
+
+/// Run GVRET USB source and send frames to merge task. With `listen_only`,
+/// buses 0 and 1 are switched to listen-only mode (and back when the source
+/// stops) and no transmit channel is offered.
 pub async fn run_source(
     source_idx: usize,
     port: String,
     baud_rate: u32,
+    listen_only: bool,
     bus_mappings: Vec<BusMapping>,
     stop_flag: Arc<AtomicBool>,
     tx: mpsc::Sender<SourceMessage>,
@@ -183,6 +267,22 @@ pub async fn run_source(
 
     std::thread::sleep(Duration::from_millis(100));
 
+    // Listen-only before any frame can be sent
+    let mut restore_params = None;
+    if listen_only {
+        let setup = match serial_port.lock() {
+            Ok(mut port) => enter_listen_only(&mut **port),
+            Err(e) => Err(format!("Port lock poisoned: {}", e)),
+        };
+        match setup {
+            Ok(before) => restore_params = (!buses_in_mode(&before, true)).then_some(before),
+            Err(e) => {
+                let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+                return;
+            }
+        }
+    }
+
     // Send device info probe
     let probe_err = match serial_port.lock() {
         Ok(mut port) => {
@@ -198,15 +298,18 @@ pub async fn run_source(
         return;
     }
 
-    // Create transmit channel and send it to the merge task
+    // Create transmit channel and send it to the merge task (not in listen-only mode)
     let (transmit_tx, transmit_rx) = std_mpsc::sync_channel::<TransmitRequest>(32);
-    let _ = tx
-        .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
-        .await;
+    if !listen_only {
+        let _ = tx
+            .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
+            .await;
+    }
 
     tlog!(
-        "[gvret_usb] Source {} connected to {}, transmit channel ready",
-        source_idx, port
+        "[gvret_usb] Source {} connected to {}, {}",
+        source_idx, port,
+        if listen_only { "listen-only" } else { "transmit channel ready" }
     );
 
     // Emit device-connected event
@@ -278,6 +381,12 @@ pub async fn run_source(
             }
         }
 
+        // Leave the buses as they were found
+        if let (Some(params), Ok(mut port)) = (restore_params, serial_port_clone.lock()) {
+            let _ = port.write_all(&setup_canbus_command(&params, false));
+            let _ = port.flush();
+        }
+
         let _ = tx_clone.blocking_send(SourceMessage::Ended(source_idx, "stopped".to_string()));
     });
 
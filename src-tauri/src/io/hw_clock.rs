@@ -0,0 +1,119 @@
+// ui/src-tauri/src/io/hw_clock.rs
+//
+// Device timestamps onto the UNIX epoch. Adapters that stamp frames in
+// hardware (gs_usb's HW_TIMESTAMP mode, SocketCAN's raw hardware timestamp)
+// count from their own zero, and narrow counters wrap: gs_usb's 32-bit µs
+// counter rolls over every ~71.6 minutes. `HardwareClock` unwraps the counter
+// and anchors it to host time on the first frame; after that the spacing
+// between frames is the device's. A counter that is already epoch-based
+// (the kernel seeds most CAN timecounters from the wall clock) is used as is.
+//
+// The anchor is taken again when device and host disagree by more than
+// RESYNC_US — the adapter was reset, or drift has built up over a long
+// capture — so frames can't end up minutes away from the host's view of now.
+
+/// Device time within this of host time is taken to be epoch-based already.
+const EPOCH_TOLERANCE_US: i64 = 1_000_000;
+
+/// Re-anchor when aligned device time strays this far from host time.
+const RESYNC_US: i64 = 2_000_000;
+
+/// Unwraps and aligns one device's timestamps.
+pub struct HardwareClock {
+    /// Width of the device counter; 64 for counters that never wrap
+    counter_bits: u32,
+    last_raw: Option<u64>,
+    /// Accumulated wraps, in counter units
+    wrap_base: u64,
+    /// Added to unwrapped device time to reach epoch time
+    offset_us: Option<i64>,
+    resyncs: u64,
+}
+
+impl HardwareClock {
+    /// A clock for a device counter `counter_bits` wide, counting µs.
+    pub fn new(counter_bits: u32) -> Self {
+        Self {
+            counter_bits: counter_bits.clamp(1, 64),
+            last_raw: None,
+            wrap_base: 0,
+            offset_us: None,
+            resyncs: 0,
+        }
+    }
+
+    /// Epoch time of a frame the device stamped `raw_us`, received at host
+    /// time `host_us`.
+    pub fn epoch_us(&mut self, raw_us: u64, host_us: u64) -> u64 {
+        let device_us = self.unwrap(raw_us) as i64;
+        let host = host_us as i64;
+        let offset = match self.offset_us {
+            Some(offset) if (device_us + offset - host).abs() <= RESYNC_US => offset,
+            previous => {
+                if previous.is_some() {
+                    self.resyncs += 1;
+                }
+                let offset = if (host - device_us).abs() <= EPOCH_TOLERANCE_US { 0 } else { host - device_us };
+                self.offset_us = Some(offset);
+                offset
+            }
+        };
+        (device_us + offset).max(0) as u64
+    }
+
+    /// Times the anchor was taken again after the first frame.
+    pub fn resyncs(&self) -> u64 {
+        self.resyncs
+    }
+
+    fn unwrap(&mut self, raw_us: u64) -> u64 {
+        if self.counter_bits >= 64 {
+            return raw_us;
+        }
+        let span = 1u64 << self.counter_bits;
+        let raw = raw_us & (span - 1);
+        if let Some(last) = self.last_raw {
+            // A step back of more than half the range is a wrap, not reordering
+            if raw < last && last - raw > span / 2 {
+                self.wrap_base += span;
+            }
+        }
+        self.last_raw = Some(raw);
+        self.wrap_base + raw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST: u64 = 1_760_000_000_000_000;
+
+    #[test]
+    fn test_device_time_is_anchored_to_the_first_frame() {
+        let mut clock = HardwareClock::new(32);
+        assert_eq!(clock.epoch_us(5_000, HOST), HOST);
+        // Device spacing is kept even when the host saw the frames bunched up
+        assert_eq!(clock.epoch_us(5_250, HOST + 900), HOST + 250);
+
+        let mut seeded = HardwareClock::new(64);
+        assert_eq!(seeded.epoch_us(HOST - 300, HOST), HOST - 300);
+    }
+
+    #[test]
+    fn test_narrow_counters_unwrap() {
+        let mut clock = HardwareClock::new(32);
+        let near_end = u32::MAX as u64 - 99;
+        let start = clock.epoch_us(near_end, HOST);
+        assert_eq!(clock.epoch_us(50, HOST + 150), start + 150);
+        assert_eq!(clock.resyncs(), 0);
+    }
+
+    #[test]
+    fn test_a_device_reset_re_anchors() {
+        let mut clock = HardwareClock::new(32);
+        clock.epoch_us(900_000_000, HOST);
+        assert_eq!(clock.epoch_us(10, HOST + 5_000_000), HOST + 5_000_000);
+        assert_eq!(clock.resyncs(), 1);
+    }
+}
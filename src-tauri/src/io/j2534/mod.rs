@@ -0,0 +1,265 @@
+// src-tauri/src/io/j2534/mod.rs
+//
+// SAE J2534 (PassThru) support for WireTAP, so dealer-grade vehicle
+// interfaces (Mongoose, CarDAQ, VCX, Tactrix OpenPort, ...) can be used as CAN
+// sources. Windows only: adapters register their vendor DLL under
+// HKLM\SOFTWARE\PassThruSupport.04.04, and the DLL is loaded at runtime when a
+// source starts.
+//
+// J2534 opens one raw CAN channel per device. A PassThru CAN message carries
+// the frame ID as the first four data bytes (big-endian), followed by the
+// payload; RxStatus flags mark 29-bit IDs and loopback echoes of our own
+// transmits. J2534 has no silent mode, so the adapter always acknowledges
+// frames on the bus; `listen_only` only withholds transmit.
+
+// Allow dead_code for constants and helpers that are only used on Windows
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+
+use crate::io::{now_us, CanTransmitFrame, FrameMessage};
+
+#[cfg(target_os = "windows")]
+pub mod passthru;
+
+#[cfg(target_os = "windows")]
+pub use passthru::{probe, run_source, J2534Config};
+
+// ============================================================================
+// J2534 API Constants (v04.04)
+// ============================================================================
+
+/// Protocol IDs
+pub mod protocol {
+    pub const CAN: u32 = 0x05;
+    pub const ISO15765: u32 = 0x06;
+}
+
+/// Return codes
+pub mod status {
+    pub const NOERROR: i32 = 0x00;
+    pub const ERR_NOT_SUPPORTED: i32 = 0x01;
+    pub const ERR_INVALID_CHANNEL_ID: i32 = 0x02;
+    pub const ERR_INVALID_PROTOCOL_ID: i32 = 0x03;
+    pub const ERR_NULL_PARAMETER: i32 = 0x04;
+    pub const ERR_INVALID_IOCTL_VALUE: i32 = 0x05;
+    pub const ERR_INVALID_FLAGS: i32 = 0x06;
+    pub const ERR_FAILED: i32 = 0x07;
+    pub const ERR_DEVICE_NOT_CONNECTED: i32 = 0x08;
+    pub const ERR_TIMEOUT: i32 = 0x09;
+    pub const ERR_INVALID_MSG: i32 = 0x0A;
+    pub const ERR_EXCEEDED_LIMIT: i32 = 0x0C;
+    pub const ERR_DEVICE_IN_USE: i32 = 0x0E;
+    pub const ERR_BUFFER_EMPTY: i32 = 0x10;
+    pub const ERR_BUFFER_FULL: i32 = 0x11;
+    pub const ERR_BUFFER_OVERFLOW: i32 = 0x12;
+    pub const ERR_CHANNEL_IN_USE: i32 = 0x14;
+    pub const ERR_INVALID_BAUDRATE: i32 = 0x19;
+    pub const ERR_INVALID_DEVICE_ID: i32 = 0x1A;
+}
+
+/// PassThruConnect flags / message TxFlags
+pub const CAN_29BIT_ID: u32 = 0x0100;
+pub const CAN_ID_BOTH: u32 = 0x0800;
+
+/// RxStatus bits
+pub const RX_TX_MSG_TYPE: u32 = 0x0001;
+
+/// PassThruStartMsgFilter filter type
+pub const PASS_FILTER: u32 = 0x01;
+
+/// PassThruIoctl IDs
+pub mod ioctl {
+    pub const SET_CONFIG: u32 = 0x02;
+    pub const CLEAR_TX_BUFFER: u32 = 0x07;
+    pub const CLEAR_RX_BUFFER: u32 = 0x08;
+}
+
+/// SET_CONFIG parameter: echo transmitted messages back as received
+pub const CONFIG_LOOPBACK: u32 = 0x03;
+
+/// Bitrates offered in the profile editor (J2534-1 requires 125k and 500k;
+/// the others depend on the adapter)
+pub const BITRATES: &[u32] = &[125_000, 250_000, 500_000, 1_000_000];
+
+/// Short name for a J2534 return code, for DLLs whose PassThruGetLastError
+/// returns nothing useful.
+pub fn status_name(code: i32) -> &'static str {
+    match code {
+        status::NOERROR => "STATUS_NOERROR",
+        status::ERR_NOT_SUPPORTED => "ERR_NOT_SUPPORTED",
+        status::ERR_INVALID_CHANNEL_ID => "ERR_INVALID_CHANNEL_ID",
+        status::ERR_INVALID_PROTOCOL_ID => "ERR_INVALID_PROTOCOL_ID",
+        status::ERR_NULL_PARAMETER => "ERR_NULL_PARAMETER",
+        status::ERR_INVALID_IOCTL_VALUE => "ERR_INVALID_IOCTL_VALUE",
+        status::ERR_INVALID_FLAGS => "ERR_INVALID_FLAGS",
+        status::ERR_FAILED => "ERR_FAILED",
+        status::ERR_DEVICE_NOT_CONNECTED => "ERR_DEVICE_NOT_CONNECTED",
+        status::ERR_TIMEOUT => "ERR_TIMEOUT",
+        status::ERR_INVALID_MSG => "ERR_INVALID_MSG",
+        status::ERR_EXCEEDED_LIMIT => "ERR_EXCEEDED_LIMIT",
+        status::ERR_DEVICE_IN_USE => "ERR_DEVICE_IN_USE",
+        status::ERR_BUFFER_EMPTY => "ERR_BUFFER_EMPTY",
+        status::ERR_BUFFER_FULL => "ERR_BUFFER_FULL",
+        status::ERR_BUFFER_OVERFLOW => "ERR_BUFFER_OVERFLOW",
+        status::ERR_CHANNEL_IN_USE => "ERR_CHANNEL_IN_USE",
+        status::ERR_INVALID_BAUDRATE => "ERR_INVALID_BAUDRATE",
+        status::ERR_INVALID_DEVICE_ID => "ERR_INVALID_DEVICE_ID",
+        _ => "unknown J2534 error",
+    }
+}
+
+// ============================================================================
+// Message Encoding
+// ============================================================================
+
+/// Transmit request flag: 29-bit ID
+const TX_FLAG_EXTENDED: u8 = 0x01;
+
+/// Encode a classic CAN frame for transmit routing: flags(1) followed by the
+/// PassThru message data, i.e. id(4 BE) + payload.
+pub fn encode_frame(frame: &CanTransmitFrame) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(5 + frame.data.len());
+    buf.push(if frame.is_extended { TX_FLAG_EXTENDED } else { 0 });
+    buf.extend_from_slice(&frame.frame_id.to_be_bytes());
+    buf.extend(frame.data.iter().take(8));
+    buf
+}
+
+/// Split a transmit request into PassThru TxFlags and message data.
+pub fn decode_transmit(bytes: &[u8]) -> Option<(u32, &[u8])> {
+    if bytes.len() < 5 {
+        return None;
+    }
+    let tx_flags = if bytes[0] & TX_FLAG_EXTENDED != 0 { CAN_29BIT_ID } else { 0 };
+    Some((tx_flags, &bytes[1..]))
+}
+
+/// Build a frame message from a received PassThru CAN message. Returns None
+/// for messages too short to hold a frame ID (e.g. bare TX indications).
+pub fn frame_message(rx_status: u32, data: &[u8]) -> Option<FrameMessage> {
+    if data.len() < 4 {
+        return None;
+    }
+    let id = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+    let payload = &data[4..data.len().min(12)];
+    Some(FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us: now_us(),
+        frame_id: id & 0x1FFF_FFFF,
+        bus: 0,
+        dlc: payload.len() as u8,
+        bytes: payload.to_vec(),
+        is_extended: rx_status & CAN_29BIT_ID != 0,
+        is_fd: false,
+        source_address: None,
+        incomplete: None,
+        direction: Some(if rx_status & RX_TX_MSG_TYPE != 0 { "tx" } else { "rx" }.to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    })
+}
+
+// ============================================================================
+// Device Info
+// ============================================================================
+
+/// A PassThru device registered with Windows
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct J2534DeviceInfo {
+    /// Device name from the registry (e.g. "Mongoose Pro GM II")
+    pub name: String,
+    pub vendor: String,
+    /// Vendor DLL (FunctionLibrary)
+    pub library_path: String,
+    /// Raw CAN supported
+    pub supports_can: bool,
+    /// ISO 15765 supported (informational; WireTAP uses raw CAN)
+    pub supports_iso15765: bool,
+}
+
+/// Firmware, DLL and API versions reported by an opened device
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct J2534Version {
+    pub firmware: String,
+    pub dll: String,
+    pub api: String,
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// List registered PassThru devices that support raw CAN.
+#[tauri::command]
+pub fn list_j2534_devices() -> Result<Vec<J2534DeviceInfo>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        passthru::list_devices()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(vec![])
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(is_extended: bool, len: usize) -> CanTransmitFrame {
+        CanTransmitFrame {
+            frame_id: if is_extended { 0x18DA_F110 } else { 0x7E0 },
+            data: (0..len as u8).collect(),
+            bus: 0,
+            is_extended,
+            is_fd: false,
+            is_brs: false,
+            is_rtr: false,
+        }
+    }
+
+    #[test]
+    fn test_encode_transmit_roundtrip() {
+        let bytes = encode_frame(&frame(true, 12));
+        let (tx_flags, data) = decode_transmit(&bytes).unwrap();
+        assert_eq!(tx_flags, CAN_29BIT_ID);
+        assert_eq!(&data[..4], &[0x18, 0xDA, 0xF1, 0x10]);
+        assert_eq!(data.len(), 12);
+
+        let (tx_flags, data) = decode_transmit(&encode_frame(&frame(false, 3))).unwrap();
+        assert_eq!(tx_flags, 0);
+        assert_eq!(data, &[0x00, 0x00, 0x07, 0xE0, 0, 1, 2]);
+        assert!(decode_transmit(&[0; 4]).is_none());
+    }
+
+    #[test]
+    fn test_frame_message() {
+        let f = frame_message(CAN_29BIT_ID, &[0x18, 0xDA, 0xF1, 0x10, 0x02, 0x10, 0x03]).unwrap();
+        assert_eq!(f.frame_id, 0x18DA_F110);
+        assert!(f.is_extended);
+        assert_eq!(f.bytes, vec![0x02, 0x10, 0x03]);
+        assert_eq!(f.direction.as_deref(), Some("rx"));
+
+        let echo = frame_message(RX_TX_MSG_TYPE, &[0, 0, 0x07, 0xE0]).unwrap();
+        assert_eq!(echo.frame_id, 0x7E0);
+        assert_eq!(echo.dlc, 0);
+        assert_eq!(echo.direction.as_deref(), Some("tx"));
+
+        assert!(frame_message(RX_TX_MSG_TYPE, &[]).is_none());
+    }
+
+    #[test]
+    fn test_status_name() {
+        assert_eq!(status_name(status::ERR_DEVICE_NOT_CONNECTED), "ERR_DEVICE_NOT_CONNECTED");
+        assert_eq!(status_name(0x7F), "unknown J2534 error");
+    }
+}
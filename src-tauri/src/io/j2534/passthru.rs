@@ -0,0 +1,563 @@
+// src-tauri/src/io/j2534/passthru.rs
+//
+// J2534 reader using a vendor PassThru DLL (Windows).
+//
+// Installed adapters are listed from HKLM\SOFTWARE\PassThruSupport.04.04: each
+// subkey names the vendor DLL (FunctionLibrary) and the protocols it supports.
+// Only the registry view matching this build is read, so a 64-bit WireTAP
+// lists adapters with a 64-bit driver. The DLL is loaded with libloading when
+// a source starts, so a broken vendor install only fails that source.
+//
+// Many PassThru DLLs aren't safe to call from several threads, so one blocking
+// thread owns the device: it sends queued transmit requests, then reads with a
+// short timeout. Loopback is enabled so our own frames come back as tx echoes.
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use windows_sys::Win32::Foundation::{ERROR_NO_MORE_ITEMS, ERROR_SUCCESS};
+use windows_sys::Win32::System::Registry::{
+    RegCloseKey, RegEnumKeyExW, RegOpenKeyExW, RegQueryValueExW, HKEY, HKEY_LOCAL_MACHINE, KEY_READ,
+    REG_DWORD, REG_EXPAND_SZ, REG_SZ,
+};
+
+use super::{
+    decode_transmit, frame_message, ioctl, protocol, status, status_name, J2534DeviceInfo, J2534Version,
+    CAN_ID_BOTH, CAN_29BIT_ID, CONFIG_LOOPBACK, PASS_FILTER,
+};
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::types::{SourceMessage, TransmitRequest};
+
+/// Registry key listing installed PassThru interfaces (J2534 v04.04)
+const REGISTRY_ROOT: &str = r"SOFTWARE\PassThruSupport.04.04";
+
+/// Size of the PASSTHRU_MSG data buffer
+const MAX_MSG_DATA: usize = 4128;
+
+/// Messages fetched per PassThruReadMsgs call
+const READ_CHUNK: usize = 64;
+
+/// Read timeout; bounds how long a queued transmit waits behind a read.
+const READ_TIMEOUT_MS: u32 = 10;
+
+/// Write timeout per transmit request
+const WRITE_TIMEOUT_MS: u32 = 100;
+
+// ============================================================================
+// PassThru API
+// ============================================================================
+
+/// `PASSTHRU_MSG`
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct PassThruMsg {
+    protocol_id: u32,
+    rx_status: u32,
+    tx_flags: u32,
+    timestamp: u32,
+    data_size: u32,
+    extra_data_index: u32,
+    data: [u8; MAX_MSG_DATA],
+}
+
+impl PassThruMsg {
+    fn can(tx_flags: u32, data: &[u8]) -> Self {
+        let mut msg = Self::zeroed();
+        let len = data.len().min(MAX_MSG_DATA);
+        msg.protocol_id = protocol::CAN;
+        msg.tx_flags = tx_flags;
+        msg.data_size = len as u32;
+        msg.extra_data_index = len as u32;
+        msg.data[..len].copy_from_slice(&data[..len]);
+        msg
+    }
+
+    fn zeroed() -> Self {
+        // Plain integers and bytes; all-zero is a valid value
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+/// `SCONFIG`
+#[repr(C)]
+struct SConfig {
+    parameter: u32,
+    value: u32,
+}
+
+/// `SCONFIG_LIST`
+#[repr(C)]
+struct SConfigList {
+    num_of_params: u32,
+    config_ptr: *mut SConfig,
+}
+
+type OpenFn = unsafe extern "system" fn(*const c_void, *mut u32) -> i32;
+type CloseFn = unsafe extern "system" fn(u32) -> i32;
+type ConnectFn = unsafe extern "system" fn(u32, u32, u32, u32, *mut u32) -> i32;
+type DisconnectFn = unsafe extern "system" fn(u32) -> i32;
+type ReadMsgsFn = unsafe extern "system" fn(u32, *mut PassThruMsg, *mut u32, u32) -> i32;
+type WriteMsgsFn = unsafe extern "system" fn(u32, *mut PassThruMsg, *mut u32, u32) -> i32;
+type StartMsgFilterFn =
+    unsafe extern "system" fn(u32, u32, *const PassThruMsg, *const PassThruMsg, *const PassThruMsg, *mut u32) -> i32;
+type IoctlFn = unsafe extern "system" fn(u32, u32, *mut c_void, *mut c_void) -> i32;
+type GetLastErrorFn = unsafe extern "system" fn(*mut c_char) -> i32;
+type ReadVersionFn = unsafe extern "system" fn(u32, *mut c_char, *mut c_char, *mut c_char) -> i32;
+
+/// Entry points resolved from a vendor PassThru DLL.
+struct PassThruApi {
+    // Keeps the library mapped for as long as the function pointers live
+    _lib: libloading::Library,
+    open: OpenFn,
+    close: CloseFn,
+    connect: ConnectFn,
+    disconnect: DisconnectFn,
+    read_msgs: ReadMsgsFn,
+    write_msgs: WriteMsgsFn,
+    start_msg_filter: StartMsgFilterFn,
+    ioctl: IoctlFn,
+    get_last_error: GetLastErrorFn,
+    read_version: ReadVersionFn,
+}
+
+impl PassThruApi {
+    unsafe fn load(path: &str) -> Result<PassThruApi, String> {
+        let lib = libloading::Library::new(path)
+            .map_err(|e| format!("Failed to load PassThru DLL {}: {}", path, e))?;
+        macro_rules! sym {
+            ($name:literal) => {
+                *lib.get($name).map_err(|e| format!("{} is missing {}: {}", path, String::from_utf8_lossy($name), e))?
+            };
+        }
+        Ok(PassThruApi {
+            open: sym!(b"PassThruOpen"),
+            close: sym!(b"PassThruClose"),
+            connect: sym!(b"PassThruConnect"),
+            disconnect: sym!(b"PassThruDisconnect"),
+            read_msgs: sym!(b"PassThruReadMsgs"),
+            write_msgs: sym!(b"PassThruWriteMsgs"),
+            start_msg_filter: sym!(b"PassThruStartMsgFilter"),
+            ioctl: sym!(b"PassThruIoctl"),
+            get_last_error: sym!(b"PassThruGetLastError"),
+            read_version: sym!(b"PassThruReadVersion"),
+            _lib: lib,
+        })
+    }
+
+    fn error_text(&self, code: i32) -> String {
+        let mut buf = [0 as c_char; 80];
+        let rc = unsafe { (self.get_last_error)(buf.as_mut_ptr()) };
+        let text = if rc == status::NOERROR {
+            unsafe { CStr::from_ptr(buf.as_ptr()) }.to_string_lossy().trim().to_string()
+        } else {
+            String::new()
+        };
+        if text.is_empty() {
+            status_name(code).to_string()
+        } else {
+            format!("{} ({})", text, status_name(code))
+        }
+    }
+
+    fn check(&self, code: i32) -> Result<(), String> {
+        if code == status::NOERROR {
+            Ok(())
+        } else {
+            Err(self.error_text(code))
+        }
+    }
+
+    fn open_device(&self) -> Result<u32, String> {
+        let mut device_id: u32 = 0;
+        self.check(unsafe { (self.open)(std::ptr::null(), &mut device_id) })?;
+        Ok(device_id)
+    }
+
+    fn version(&self, device_id: u32) -> Result<J2534Version, String> {
+        let mut firmware = [0 as c_char; 80];
+        let mut dll = [0 as c_char; 80];
+        let mut api = [0 as c_char; 80];
+        self.check(unsafe {
+            (self.read_version)(device_id, firmware.as_mut_ptr(), dll.as_mut_ptr(), api.as_mut_ptr())
+        })?;
+        let text = |buf: &[c_char; 80]| unsafe { CStr::from_ptr(buf.as_ptr()) }.to_string_lossy().into_owned();
+        Ok(J2534Version {
+            firmware: text(&firmware),
+            dll: text(&dll),
+            api: text(&api),
+        })
+    }
+
+    /// Connect a raw CAN channel, receiving 11- and 29-bit IDs where the
+    /// adapter allows both at once.
+    fn connect_can(&self, device_id: u32, bitrate: u32) -> Result<u32, String> {
+        let mut channel_id: u32 = 0;
+        let rc = unsafe { (self.connect)(device_id, protocol::CAN, CAN_ID_BOTH, bitrate, &mut channel_id) };
+        if rc == status::ERR_INVALID_FLAGS || rc == status::ERR_NOT_SUPPORTED {
+            tlog!("[j2534] Adapter rejected CAN_ID_BOTH; connecting with default ID mode");
+            self.check(unsafe { (self.connect)(device_id, protocol::CAN, 0, bitrate, &mut channel_id) })?;
+        } else {
+            self.check(rc)?;
+        }
+        Ok(channel_id)
+    }
+
+    /// Configure an open CAN channel: loopback on, pass-all filters, empty buffers.
+    fn setup_channel(&self, channel_id: u32) -> Result<(), String> {
+        let mut config = SConfig {
+            parameter: CONFIG_LOOPBACK,
+            value: 1,
+        };
+        let mut list = SConfigList {
+            num_of_params: 1,
+            config_ptr: &mut config,
+        };
+        if let Err(e) = self.check(unsafe {
+            (self.ioctl)(channel_id, ioctl::SET_CONFIG, &mut list as *mut SConfigList as *mut c_void, std::ptr::null_mut())
+        }) {
+            tlog!("[j2534] Could not enable loopback, transmitted frames won't be echoed: {}", e);
+        }
+
+        // J2534 delivers nothing until a filter passes it; a zero mask passes every ID
+        let mut filter_id: u32 = 0;
+        let zero = [0u8; 4];
+        let mask = PassThruMsg::can(0, &zero);
+        let pattern = PassThruMsg::can(0, &zero);
+        self.check(unsafe {
+            (self.start_msg_filter)(channel_id, PASS_FILTER, &mask, &pattern, std::ptr::null(), &mut filter_id)
+        })
+        .map_err(|e| format!("Failed to set pass filter: {}", e))?;
+        // Some adapters filter 29-bit IDs separately; not all accept this
+        let mask = PassThruMsg::can(CAN_29BIT_ID, &zero);
+        let pattern = PassThruMsg::can(CAN_29BIT_ID, &zero);
+        let _ = unsafe {
+            (self.start_msg_filter)(channel_id, PASS_FILTER, &mask, &pattern, std::ptr::null(), &mut filter_id)
+        };
+
+        unsafe {
+            (self.ioctl)(channel_id, ioctl::CLEAR_RX_BUFFER, std::ptr::null_mut(), std::ptr::null_mut());
+            (self.ioctl)(channel_id, ioctl::CLEAR_TX_BUFFER, std::ptr::null_mut(), std::ptr::null_mut());
+        }
+        Ok(())
+    }
+
+    fn write(&self, channel_id: u32, tx_flags: u32, data: &[u8]) -> Result<(), String> {
+        let mut msg = PassThruMsg::can(tx_flags, data);
+        let mut count: u32 = 1;
+        self.check(unsafe { (self.write_msgs)(channel_id, &mut msg, &mut count, WRITE_TIMEOUT_MS) })?;
+        if count == 0 {
+            return Err("Adapter did not accept the frame".to_string());
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Device Enumeration
+// ============================================================================
+
+fn wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// An open registry key, closed on drop.
+struct RegKey(HKEY);
+
+impl RegKey {
+    fn open(parent: HKEY, path: &str) -> Option<RegKey> {
+        let mut key: HKEY = std::ptr::null_mut();
+        let rc = unsafe { RegOpenKeyExW(parent, wide(path).as_ptr(), 0, KEY_READ, &mut key) };
+        (rc == ERROR_SUCCESS).then_some(RegKey(key))
+    }
+
+    fn subkeys(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        for index in 0.. {
+            let mut buf = [0u16; 256];
+            let mut len = buf.len() as u32;
+            let rc = unsafe {
+                RegEnumKeyExW(
+                    self.0,
+                    index,
+                    buf.as_mut_ptr(),
+                    &mut len,
+                    std::ptr::null(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                )
+            };
+            if rc == ERROR_NO_MORE_ITEMS {
+                break;
+            }
+            if rc == ERROR_SUCCESS {
+                names.push(String::from_utf16_lossy(&buf[..len as usize]));
+            }
+        }
+        names
+    }
+
+    fn value(&self, name: &str) -> Option<(u32, Vec<u8>)> {
+        let name = wide(name);
+        let mut ty = 0u32;
+        let mut size = 0u32;
+        let rc = unsafe {
+            RegQueryValueExW(self.0, name.as_ptr(), std::ptr::null(), &mut ty, std::ptr::null_mut(), &mut size)
+        };
+        if rc != ERROR_SUCCESS {
+            return None;
+        }
+        let mut data = vec![0u8; size as usize];
+        let rc = unsafe {
+            RegQueryValueExW(self.0, name.as_ptr(), std::ptr::null(), &mut ty, data.as_mut_ptr(), &mut size)
+        };
+        data.truncate(size as usize);
+        (rc == ERROR_SUCCESS).then_some((ty, data))
+    }
+
+    fn string(&self, name: &str) -> Option<String> {
+        match self.value(name)? {
+            (REG_SZ | REG_EXPAND_SZ, data) => {
+                let units: Vec<u16> = data.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+                let end = units.iter().position(|&u| u == 0).unwrap_or(units.len());
+                Some(String::from_utf16_lossy(&units[..end]))
+            }
+            _ => None,
+        }
+    }
+
+    fn dword(&self, name: &str) -> Option<u32> {
+        match self.value(name)? {
+            (REG_DWORD, data) if data.len() >= 4 => Some(u32::from_le_bytes([data[0], data[1], data[2], data[3]])),
+            _ => None,
+        }
+    }
+}
+
+impl Drop for RegKey {
+    fn drop(&mut self) {
+        unsafe { RegCloseKey(self.0) };
+    }
+}
+
+/// List registered PassThru devices that support raw CAN.
+pub fn list_devices() -> Result<Vec<J2534DeviceInfo>, String> {
+    let Some(root) = RegKey::open(HKEY_LOCAL_MACHINE, REGISTRY_ROOT) else {
+        return Ok(vec![]);
+    };
+    let mut devices: Vec<J2534DeviceInfo> = root
+        .subkeys()
+        .into_iter()
+        .filter_map(|subkey| {
+            let key = RegKey::open(root.0, &subkey)?;
+            Some(J2534DeviceInfo {
+                library_path: key.string("FunctionLibrary")?,
+                name: key.string("Name").unwrap_or_else(|| subkey.clone()),
+                vendor: key.string("Vendor").unwrap_or_default(),
+                supports_can: key.dword("CAN") == Some(1),
+                supports_iso15765: key.dword("ISO15765") == Some(1),
+            })
+        })
+        .filter(|d| d.supports_can)
+        .collect();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(devices)
+}
+
+/// The DLL for a profile: an explicit path wins, otherwise the registered
+/// device with this name.
+fn resolve_library(device: &str, library_path: Option<&str>) -> Result<String, String> {
+    if let Some(path) = library_path.filter(|p| !p.is_empty()) {
+        if !Path::new(path).exists() {
+            return Err(format!("PassThru DLL not found: {}", path));
+        }
+        return Ok(path.to_string());
+    }
+    list_devices()?
+        .into_iter()
+        .find(|d| d.name == device)
+        .map(|d| d.library_path)
+        .ok_or_else(|| format!("J2534 device '{}' is not registered on this PC", device))
+}
+
+/// Open the device briefly and read its versions (confirms it's connected).
+pub fn probe(device: &str, library_path: Option<&str>) -> Result<J2534Version, String> {
+    let library = resolve_library(device, library_path)?;
+    let api = unsafe { PassThruApi::load(&library) }?;
+    let device_id = api.open_device()?;
+    let version = api.version(device_id);
+    unsafe { (api.close)(device_id) };
+    version
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Settings for a J2534 source
+pub struct J2534Config {
+    /// Registered device name
+    pub device: String,
+    /// Vendor DLL path (overrides the registry lookup)
+    pub library_path: Option<String>,
+    pub bitrate: u32,
+    /// Don't offer transmit (the adapter still acknowledges frames)
+    pub listen_only: bool,
+}
+
+/// Run a J2534 source and feed frames to the merge task.
+pub async fn run_source(
+    source_idx: usize,
+    config: J2534Config,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let library = match resolve_library(&config.device, config.library_path.as_deref()) {
+        Ok(l) => l,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+            return;
+        }
+    };
+
+    let blocking_handle = tokio::task::spawn_blocking(move || {
+        let api = match unsafe { PassThruApi::load(&library) } {
+            Ok(api) => api,
+            Err(e) => {
+                let _ = tx.blocking_send(SourceMessage::Error(source_idx, e));
+                return;
+            }
+        };
+
+        let device_id = match api.open_device() {
+            Ok(id) => id,
+            Err(e) => {
+                let _ = tx.blocking_send(SourceMessage::Error(
+                    source_idx,
+                    format!("Failed to open {}: {}", config.device, e),
+                ));
+                return;
+            }
+        };
+
+        let channel_id = match api.connect_can(device_id, config.bitrate) {
+            Ok(ch) => ch,
+            Err(e) => {
+                unsafe { (api.close)(device_id) };
+                let _ = tx.blocking_send(SourceMessage::Error(
+                    source_idx,
+                    format!("Failed to connect CAN on {}: {}", config.device, e),
+                ));
+                return;
+            }
+        };
+        if let Err(e) = api.setup_channel(channel_id) {
+            unsafe {
+                (api.disconnect)(channel_id);
+                (api.close)(device_id);
+            }
+            let _ = tx.blocking_send(SourceMessage::Error(source_idx, e));
+            return;
+        }
+
+        let version = api.version(device_id).ok();
+        tlog!(
+            "[j2534] Source {} connected to {} ({}, bitrate: {}, firmware: {}, listen_only: {})",
+            source_idx,
+            config.device,
+            library,
+            config.bitrate,
+            version.as_ref().map(|v| v.firmware.as_str()).unwrap_or("?"),
+            config.listen_only
+        );
+
+        let _ = tx.blocking_send(SourceMessage::Connected(
+            source_idx,
+            "j2534".to_string(),
+            config.device.clone(),
+            None,
+        ));
+
+        // Transmit requests are served from this thread, between reads
+        let transmit_rx = if !config.listen_only {
+            let (transmit_tx, transmit_rx) = std_mpsc::sync_channel::<TransmitRequest>(32);
+            let _ = tx.blocking_send(SourceMessage::TransmitReady(source_idx, transmit_tx));
+            Some(transmit_rx)
+        } else {
+            None
+        };
+
+        let mut msgs = vec![PassThruMsg::zeroed(); READ_CHUNK];
+        let mut rx_frames: u64 = 0;
+        let mut bus_filtered: u64 = 0;
+        let mut overflows: u64 = 0;
+
+        while !stop_flag.load(Ordering::Relaxed) {
+            if let Some(transmit_rx) = &transmit_rx {
+                while let Ok(req) = transmit_rx.try_recv() {
+                    let result = match decode_transmit(&req.data) {
+                        Some((tx_flags, data)) => api
+                            .write(channel_id, tx_flags, data)
+                            .map_err(|e| format!("Write failed: {}", e)),
+                        None => Err("Malformed J2534 transmit request".to_string()),
+                    };
+                    let _ = req.result_tx.try_send(result);
+                }
+            }
+
+            let mut count = READ_CHUNK as u32;
+            let rc = unsafe { (api.read_msgs)(channel_id, msgs.as_mut_ptr(), &mut count, READ_TIMEOUT_MS) };
+            match rc {
+                status::NOERROR | status::ERR_TIMEOUT | status::ERR_BUFFER_EMPTY => {}
+                status::ERR_BUFFER_OVERFLOW => overflows += 1,
+                _ => {
+                    let _ = tx.blocking_send(SourceMessage::Error(
+                        source_idx,
+                        format!("J2534 read failed: {}", api.error_text(rc)),
+                    ));
+                    break;
+                }
+            }
+
+            let mut batch = Vec::new();
+            for msg in msgs.iter().take((count as usize).min(READ_CHUNK)) {
+                if msg.protocol_id != protocol::CAN {
+                    continue;
+                }
+                let len = (msg.data_size as usize).min(MAX_MSG_DATA);
+                let Some(mut frame) = frame_message(msg.rx_status, &msg.data[..len]) else {
+                    continue;
+                };
+                rx_frames += 1;
+                if apply_bus_mapping(&mut frame, &bus_mappings) {
+                    batch.push(frame);
+                } else {
+                    bus_filtered += 1;
+                }
+            }
+            if !batch.is_empty() {
+                let _ = tx.blocking_send(SourceMessage::Frames(source_idx, batch));
+            }
+        }
+
+        tlog!(
+            "[j2534] Source {} ended: rx={}, bus_filtered={}, overflows={}",
+            source_idx, rx_frames, bus_filtered, overflows
+        );
+
+        unsafe {
+            (api.disconnect)(channel_id);
+            (api.close)(device_id);
+        }
+
+        let _ = tx.blocking_send(SourceMessage::Ended(source_idx, "stopped".to_string()));
+    });
+
+    let _ = blocking_handle.await;
+}
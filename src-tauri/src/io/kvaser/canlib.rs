@@ -0,0 +1,435 @@
+// src-tauri/src/io/kvaser/canlib.rs
+//
+// Kvaser reader using CANlib (Windows and Linux).
+//
+// The library is loaded at runtime with libloading so a missing install only
+// fails the Kvaser source, not the app. One handle is opened per enabled
+// channel of the device; the read loop polls each handle with canRead (which
+// returns canERR_NOMSG when the queue is drained) and tags frames with the
+// channel number on the card before bus mapping.
+
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+use std::os::raw::{c_char, c_int, c_long, c_uint, c_ulong, c_void};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::{
+    bitrate_constant, channel_data, decode_msg, driver, format_ean, open_flags, status,
+    KvaserDeviceInfo, KvaserTx, HWTYPE_VIRTUAL,
+};
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::types::{SourceMessage, TransmitRequest};
+
+/// Library file name per platform
+#[cfg(target_os = "windows")]
+const LIBRARY_NAME: &str = "canlib32.dll";
+#[cfg(target_os = "linux")]
+const LIBRARY_NAME: &str = "libcanlib.so.1";
+
+/// Sleep between polls when every receive queue is empty.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Upper bound on frames drained per batch, so a flooded queue still yields.
+const MAX_BATCH: usize = 512;
+
+type InitializeLibraryFn = unsafe extern "system" fn();
+type GetNumberOfChannelsFn = unsafe extern "system" fn(*mut c_int) -> c_int;
+type GetChannelDataFn = unsafe extern "system" fn(c_int, c_int, *mut c_void, usize) -> c_int;
+type OpenChannelFn = unsafe extern "system" fn(c_int, c_int) -> c_int;
+type SetBusParamsFn = unsafe extern "system" fn(c_int, c_long, c_uint, c_uint, c_uint, c_uint, c_uint) -> c_int;
+type SetBusOutputControlFn = unsafe extern "system" fn(c_int, c_uint) -> c_int;
+type HandleFn = unsafe extern "system" fn(c_int) -> c_int;
+type ReadFn = unsafe extern "system" fn(c_int, *mut c_long, *mut c_void, *mut c_uint, *mut c_uint, *mut c_ulong) -> c_int;
+type WriteFn = unsafe extern "system" fn(c_int, c_long, *mut c_void, c_uint, c_uint) -> c_int;
+type GetErrorTextFn = unsafe extern "system" fn(c_int, *mut c_char, c_uint) -> c_int;
+
+/// Entry points resolved from CANlib.
+struct Canlib {
+    // Keeps the library mapped for as long as the function pointers live
+    _lib: libloading::Library,
+    get_number_of_channels: GetNumberOfChannelsFn,
+    get_channel_data: GetChannelDataFn,
+    open_channel: OpenChannelFn,
+    set_bus_params: SetBusParamsFn,
+    set_bus_output_control: SetBusOutputControlFn,
+    bus_on: HandleFn,
+    bus_off: HandleFn,
+    close: HandleFn,
+    read: ReadFn,
+    write: WriteFn,
+    get_error_text: GetErrorTextFn,
+}
+
+static CANLIB: OnceCell<Result<Canlib, String>> = OnceCell::new();
+
+impl Canlib {
+    /// Load and initialise CANlib once per process.
+    fn get() -> Result<&'static Canlib, String> {
+        CANLIB
+            .get_or_init(|| unsafe { Self::load() })
+            .as_ref()
+            .map_err(|e| e.clone())
+    }
+
+    unsafe fn load() -> Result<Canlib, String> {
+        let lib = libloading::Library::new(LIBRARY_NAME).map_err(|e| {
+            format!(
+                "Kvaser CANlib ({}) not found — install the Kvaser drivers (linuxcan on Linux): {}",
+                LIBRARY_NAME, e
+            )
+        })?;
+        macro_rules! sym {
+            ($name:literal) => {
+                *lib.get($name).map_err(|e| format!("CANlib is missing {}: {}", String::from_utf8_lossy($name), e))?
+            };
+        }
+        let initialize: InitializeLibraryFn = sym!(b"canInitializeLibrary");
+        initialize();
+        Ok(Canlib {
+            get_number_of_channels: sym!(b"canGetNumberOfChannels"),
+            get_channel_data: sym!(b"canGetChannelData"),
+            open_channel: sym!(b"canOpenChannel"),
+            set_bus_params: sym!(b"canSetBusParams"),
+            set_bus_output_control: sym!(b"canSetBusOutputControl"),
+            bus_on: sym!(b"canBusOn"),
+            bus_off: sym!(b"canBusOff"),
+            close: sym!(b"canClose"),
+            read: sym!(b"canRead"),
+            write: sym!(b"canWrite"),
+            get_error_text: sym!(b"canGetErrorText"),
+            _lib: lib,
+        })
+    }
+
+    fn error_text(&self, code: i32) -> String {
+        let mut buf = [0 as c_char; 256];
+        let rc = unsafe { (self.get_error_text)(code, buf.as_mut_ptr(), buf.len() as c_uint) };
+        if rc != status::OK {
+            return format!("CANlib error {}", code);
+        }
+        let bytes: Vec<u8> = buf.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    fn check(&self, code: i32) -> Result<(), String> {
+        if code >= status::OK {
+            Ok(())
+        } else {
+            Err(self.error_text(code))
+        }
+    }
+
+    fn channel_count(&self) -> Result<i32, String> {
+        let mut count: c_int = 0;
+        let rc = unsafe { (self.get_number_of_channels)(&mut count) };
+        self.check(rc).map(|_| count)
+    }
+
+    fn channel_bytes<const N: usize>(&self, channel: i32, item: i32) -> Result<[u8; N], String> {
+        let mut buf = [0u8; N];
+        let rc = unsafe { (self.get_channel_data)(channel, item, buf.as_mut_ptr() as *mut c_void, N) };
+        self.check(rc).map(|_| buf)
+    }
+
+    fn channel_u32(&self, channel: i32, item: i32) -> Result<u32, String> {
+        self.channel_bytes::<4>(channel, item).map(u32::from_le_bytes)
+    }
+
+    fn channel_u64(&self, channel: i32, item: i32) -> Result<u64, String> {
+        self.channel_bytes::<8>(channel, item).map(u64::from_le_bytes)
+    }
+
+    fn channel_string(&self, channel: i32, item: i32) -> Result<String, String> {
+        let buf = self.channel_bytes::<256>(channel, item)?;
+        let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+        Ok(String::from_utf8_lossy(&buf[..end]).into_owned())
+    }
+}
+
+// ============================================================================
+// Device Enumeration
+// ============================================================================
+
+/// List Kvaser devices, grouping CANlib channels by card.
+pub fn list_devices() -> Result<Vec<KvaserDeviceInfo>, String> {
+    let api = Canlib::get()?;
+    let mut devices: Vec<KvaserDeviceInfo> = Vec::new();
+    for channel in 0..api.channel_count()? {
+        if api.channel_u32(channel, channel_data::CARD_TYPE).ok() == Some(HWTYPE_VIRTUAL) {
+            continue;
+        }
+        let serial = api.channel_u64(channel, channel_data::CARD_SERIAL_NO).unwrap_or(0);
+        let ean = api
+            .channel_bytes::<8>(channel, channel_data::CARD_UPC_NO)
+            .map(format_ean)
+            .unwrap_or_default();
+        // Channels of one card are numbered consecutively by CANlib
+        match devices.iter_mut().find(|d| d.serial == serial && d.ean == ean) {
+            Some(device) => device.channels.push(channel as u32),
+            None => devices.push(KvaserDeviceInfo {
+                name: api
+                    .channel_string(channel, channel_data::DEVDESCR_ASCII)
+                    .unwrap_or_else(|_| "Kvaser device".to_string()),
+                serial,
+                ean,
+                channels: vec![channel as u32],
+            }),
+        }
+    }
+    Ok(devices)
+}
+
+/// Resolve the device for a profile: by card serial when one is set (stable
+/// across replugging), otherwise the card owning the given CANlib channel.
+fn resolve_device(serial: Option<u64>, channel: u32) -> Result<KvaserDeviceInfo, String> {
+    let devices = list_devices()?;
+    match serial {
+        Some(serial) => devices
+            .into_iter()
+            .find(|d| d.serial == serial)
+            .ok_or_else(|| format!("No Kvaser device with serial {} is connected", serial)),
+        None => devices
+            .into_iter()
+            .find(|d| d.channels.contains(&channel))
+            .ok_or_else(|| format!("Kvaser CANlib channel {} is not connected", channel)),
+    }
+}
+
+/// Open one channel and go bus-on.
+fn open_channel(api: &Canlib, channel: u32, bitrate: i32, listen_only: bool) -> Result<i32, String> {
+    let handle = unsafe { (api.open_channel)(channel as c_int, open_flags::EXCLUSIVE) };
+    api.check(handle)?;
+    let setup = || -> Result<(), String> {
+        api.check(unsafe { (api.set_bus_params)(handle, bitrate as c_long, 0, 0, 0, 0, 0) })?;
+        let mode = if listen_only { driver::SILENT } else { driver::NORMAL };
+        api.check(unsafe { (api.set_bus_output_control)(handle, mode) })?;
+        api.check(unsafe { (api.bus_on)(handle) })
+    };
+    if let Err(e) = setup() {
+        unsafe { (api.close)(handle) };
+        return Err(e);
+    }
+    Ok(handle)
+}
+
+fn close_channels(api: &Canlib, handles: &HashMap<u8, i32>) {
+    for &handle in handles.values() {
+        unsafe {
+            (api.bus_off)(handle);
+            (api.close)(handle);
+        }
+    }
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Run a Kvaser source and feed frames to the merge task.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_source(
+    source_idx: usize,
+    serial: Option<u64>,
+    channel: u32,
+    bitrate: u32,
+    listen_only: bool,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let api = match Canlib::get() {
+        Ok(api) => api,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+            return;
+        }
+    };
+
+    let device = match resolve_device(serial, channel) {
+        Ok(d) => d,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+            return;
+        }
+    };
+
+    let Some(bitrate_code) = bitrate_constant(bitrate) else {
+        let _ = tx
+            .send(SourceMessage::Error(
+                source_idx,
+                format!("Unsupported Kvaser bitrate {} bit/s", bitrate),
+            ))
+            .await;
+        return;
+    };
+
+    // Open every enabled channel on the card (device bus = channel on card)
+    let mut handles: HashMap<u8, i32> = HashMap::new();
+    for mapping in bus_mappings.iter().filter(|m| m.enabled) {
+        let Some(&global) = device.channels.get(mapping.device_bus as usize) else {
+            tlog!(
+                "[kvaser] Source {} skipping bus {}: {} has {} channel(s)",
+                source_idx, mapping.device_bus, device.name, device.channels.len()
+            );
+            continue;
+        };
+        match open_channel(api, global, bitrate_code, listen_only) {
+            Ok(handle) => {
+                handles.insert(mapping.device_bus, handle);
+            }
+            Err(e) => {
+                close_channels(api, &handles);
+                let _ = tx
+                    .send(SourceMessage::Error(
+                        source_idx,
+                        format!("Failed to open Kvaser channel {}: {}", mapping.device_bus, e),
+                    ))
+                    .await;
+                return;
+            }
+        }
+    }
+
+    if handles.is_empty() {
+        let _ = tx
+            .send(SourceMessage::Error(
+                source_idx,
+                format!("No enabled channels to open on {}", device.name),
+            ))
+            .await;
+        return;
+    }
+
+    tlog!(
+        "[kvaser] Source {} connected to {} (serial: {}, channels: {}, bitrate: {}, listen_only: {})",
+        source_idx, device.name, device.serial, handles.len(), bitrate, listen_only
+    );
+
+    let _ = tx
+        .send(SourceMessage::Connected(
+            source_idx,
+            "kvaser".to_string(),
+            format!("{} #{}", device.name, device.serial),
+            None,
+        ))
+        .await;
+
+    // Setup transmit channel if not in listen-only mode
+    let transmit_task = if !listen_only {
+        let (transmit_tx, transmit_rx) = std_mpsc::sync_channel::<TransmitRequest>(32);
+        let _ = tx
+            .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
+            .await;
+
+        let stop_flag_for_transmit = stop_flag.clone();
+        let tx_handles = handles.clone();
+        Some(tokio::task::spawn_blocking(move || {
+            while !stop_flag_for_transmit.load(Ordering::Relaxed) {
+                match transmit_rx.recv_timeout(Duration::from_millis(10)) {
+                    Ok(req) => {
+                        let result = match KvaserTx::from_bytes(&req.data) {
+                            Some(mut msg) => match tx_handles.get(&msg.channel) {
+                                Some(&handle) => {
+                                    let rc = unsafe {
+                                        (api.write)(
+                                            handle,
+                                            msg.id as c_long,
+                                            msg.data.as_mut_ptr() as *mut c_void,
+                                            msg.data.len() as c_uint,
+                                            msg.flags,
+                                        )
+                                    };
+                                    api.check(rc).map_err(|e| format!("Write failed: {}", e))
+                                }
+                                None => Err(format!("Kvaser channel {} is not open", msg.channel)),
+                            },
+                            None => Err("Malformed Kvaser transmit request".to_string()),
+                        };
+                        let _ = req.result_tx.try_send(result);
+                    }
+                    Err(std_mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(std_mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        }))
+    } else {
+        None
+    };
+
+    // Read loop
+    let mut rx_frames: u64 = 0;
+    let mut bus_filtered: u64 = 0;
+    let mut channels: Vec<(u8, i32)> = handles.iter().map(|(&bus, &h)| (bus, h)).collect();
+    channels.sort_unstable();
+
+    'read: while !stop_flag.load(Ordering::Relaxed) {
+        let mut batch = Vec::new();
+        for &(bus, handle) in &channels {
+            while batch.len() < MAX_BATCH {
+                let mut id: c_long = 0;
+                let mut data = [0u8; 8];
+                let mut dlc: c_uint = 0;
+                let mut flags: c_uint = 0;
+                let mut time: c_ulong = 0;
+                let rc = unsafe {
+                    (api.read)(
+                        handle,
+                        &mut id,
+                        data.as_mut_ptr() as *mut c_void,
+                        &mut dlc,
+                        &mut flags,
+                        &mut time,
+                    )
+                };
+
+                if rc == status::ERR_NOMSG {
+                    break;
+                }
+                if rc != status::OK {
+                    let _ = tx
+                        .send(SourceMessage::Error(
+                            source_idx,
+                            format!("Kvaser read failed on channel {}: {}", bus, api.error_text(rc)),
+                        ))
+                        .await;
+                    break 'read;
+                }
+
+                if let Some(mut frame) = decode_msg(bus, id as u32, &data, dlc, flags) {
+                    rx_frames += 1;
+                    if apply_bus_mapping(&mut frame, &bus_mappings) {
+                        batch.push(frame);
+                    } else {
+                        bus_filtered += 1;
+                    }
+                }
+            }
+        }
+
+        if batch.is_empty() {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        } else {
+            let _ = tx.send(SourceMessage::Frames(source_idx, batch)).await;
+        }
+    }
+
+    tlog!(
+        "[kvaser] Source {} ended: rx={}, bus_filtered={}",
+        source_idx, rx_frames, bus_filtered
+    );
+
+    // Cleanup
+    if let Some(task) = transmit_task {
+        task.abort();
+    }
+
+    close_channels(api, &handles);
+
+    let _ = tx
+        .send(SourceMessage::Ended(source_idx, "stopped".to_string()))
+        .await;
+}
@@ -0,0 +1,290 @@
+// src-tauri/src/io/kvaser/mod.rs
+//
+// Kvaser support for WireTAP (Leaf, USBcan, Memorator and other CANlib devices).
+//
+// Platform strategy:
+// - Windows: Kvaser's CANlib (canlib32.dll, from the Kvaser drivers package).
+// - Linux:   CANlib from Kvaser's linuxcan package (libcanlib.so.1). Devices
+//            bound to the mainline kvaser_usb driver appear as SocketCAN
+//            interfaces instead — use a socketcan profile for those.
+// - macOS:   Kvaser ships no CANlib, so the profile kind is unavailable.
+//
+// The library is loaded at runtime so WireTAP still starts without it. A
+// profile selects one device (by card serial number, or by the CANlib channel
+// of its first channel) and opens every channel enabled in its interface
+// list, so a dual-channel USBcan maps onto buses the same way a multi-bus
+// GVRET does: the device bus is the channel number on the card. Classic CAN
+// only for now.
+
+// Allow dead_code for constants and helpers that are only used on specific platforms
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+
+use crate::io::{now_us, CanTransmitFrame, FrameMessage};
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub mod canlib;
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub use canlib::run_source;
+
+// ============================================================================
+// CANlib Constants
+// ============================================================================
+
+/// Status codes (`canStatus`)
+pub mod status {
+    pub const OK: i32 = 0;
+    pub const ERR_PARAM: i32 = -1;
+    pub const ERR_NOMSG: i32 = -2;
+    pub const ERR_NOTFOUND: i32 = -3;
+    pub const ERR_NOCHANNELS: i32 = -5;
+    pub const ERR_TXBUFOFL: i32 = -13;
+    pub const ERR_NOTINITIALIZED: i32 = -8;
+}
+
+/// canOpenChannel flags
+pub mod open_flags {
+    pub const EXCLUSIVE: i32 = 0x0008;
+    pub const ACCEPT_VIRTUAL: i32 = 0x0020;
+}
+
+/// canGetChannelData items (`canCHANNELDATA_*`)
+pub mod channel_data {
+    pub const CARD_TYPE: i32 = 4;
+    pub const CHAN_NO_ON_CARD: i32 = 6;
+    pub const CARD_SERIAL_NO: i32 = 7;
+    pub const CARD_UPC_NO: i32 = 11;
+    pub const DEVDESCR_ASCII: i32 = 26;
+}
+
+/// canHWTYPE_VIRTUAL — CANlib's software-only channels
+pub const HWTYPE_VIRTUAL: u32 = 1;
+
+/// canSetBusOutputControl driver types
+pub mod driver {
+    pub const SILENT: u32 = 1;
+    pub const NORMAL: u32 = 4;
+}
+
+/// Message flags (`canMSG_*`)
+pub mod msg_flags {
+    pub const RTR: u32 = 0x0001;
+    pub const STD: u32 = 0x0002;
+    pub const EXT: u32 = 0x0004;
+    pub const ERROR_FRAME: u32 = 0x0020;
+    pub const TXACK: u32 = 0x0040;
+    pub const TXRQ: u32 = 0x0080;
+    pub const FDF: u32 = 0x01_0000;
+}
+
+/// Classic CAN bitrates and their CANlib predefined constants (`canBITRATE_*`)
+pub const BITRATES: &[(u32, i32)] = &[
+    (1_000_000, -1),
+    (500_000, -2),
+    (250_000, -3),
+    (125_000, -4),
+    (100_000, -5),
+    (83_000, -8),
+    (62_000, -6),
+    (50_000, -7),
+    (10_000, -9),
+];
+
+/// CANlib bitrate constant for a bitrate, or None if CANlib has no preset for it.
+pub fn bitrate_constant(bitrate: u32) -> Option<i32> {
+    BITRATES.iter().find(|(b, _)| *b == bitrate).map(|(_, c)| *c)
+}
+
+// ============================================================================
+// Transmit Encoding
+// ============================================================================
+
+/// A frame queued for one channel of a Kvaser device.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KvaserTx {
+    /// Channel number on the card (the device bus)
+    pub channel: u8,
+    pub id: u32,
+    pub flags: u32,
+    pub data: Vec<u8>,
+}
+
+impl KvaserTx {
+    /// channel(1) + id(4 LE) + flags(4 LE) + len(1) + data
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(10 + self.data.len());
+        buf.push(self.channel);
+        buf.extend_from_slice(&self.id.to_le_bytes());
+        buf.extend_from_slice(&self.flags.to_le_bytes());
+        buf.push(self.data.len() as u8);
+        buf.extend_from_slice(&self.data);
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 10 {
+            return None;
+        }
+        let len = bytes[9] as usize;
+        let data = bytes.get(10..10 + len)?.to_vec();
+        Some(KvaserTx {
+            channel: bytes[0],
+            id: u32::from_le_bytes(bytes[1..5].try_into().ok()?),
+            flags: u32::from_le_bytes(bytes[5..9].try_into().ok()?),
+            data,
+        })
+    }
+}
+
+/// Encode a CAN frame for transmit routing. `frame.bus` is the device bus
+/// (channel on the card) after the broker reverses the bus mapping.
+pub fn encode_frame(frame: &CanTransmitFrame) -> Vec<u8> {
+    let mut flags = if frame.is_extended { msg_flags::EXT } else { msg_flags::STD };
+    if frame.is_rtr {
+        flags |= msg_flags::RTR;
+    }
+    KvaserTx {
+        channel: frame.bus,
+        id: frame.frame_id,
+        flags,
+        data: frame.data.iter().take(8).copied().collect(),
+    }
+    .to_bytes()
+}
+
+/// Convert a frame read from a channel into a frame message. Error frames
+/// return None.
+pub fn decode_msg(channel: u8, id: u32, data: &[u8], dlc: u32, flags: u32) -> Option<FrameMessage> {
+    if flags & msg_flags::ERROR_FRAME != 0 {
+        return None;
+    }
+    let len = (dlc as usize).min(8).min(data.len());
+    let is_echo = flags & (msg_flags::TXACK | msg_flags::TXRQ) != 0;
+    Some(FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us: now_us(),
+        frame_id: id & 0x1FFF_FFFF,
+        bus: channel,
+        dlc: len as u8,
+        bytes: data[..len].to_vec(),
+        is_extended: flags & msg_flags::EXT != 0,
+        is_fd: false,
+        source_address: None,
+        incomplete: None,
+        direction: Some(if is_echo { "tx" } else { "rx" }.to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    })
+}
+
+// ============================================================================
+// Device Info
+// ============================================================================
+
+/// A Kvaser device (one card) with its CAN channels
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KvaserDeviceInfo {
+    /// Product name (e.g. "Kvaser Leaf Light v2")
+    pub name: String,
+    /// Card serial number (stable across replugging)
+    pub serial: u64,
+    /// Product EAN, formatted as Kvaser prints it (e.g. "73-30130-00685-0")
+    pub ean: String,
+    /// CANlib channel number of each channel on the card, in card order
+    pub channels: Vec<u32>,
+}
+
+/// Format CANlib's packed-BCD EAN (canCHANNELDATA_CARD_UPC_NO) as Kvaser prints it.
+pub fn format_ean(raw: [u8; 8]) -> String {
+    let digits: String = raw
+        .iter()
+        .rev()
+        .flat_map(|b| [b >> 4, b & 0x0F])
+        .map(|d| char::from(b'0' + d.min(9)))
+        .collect();
+    let d = digits.trim_start_matches('0');
+    let d = format!("{:0>13}", d);
+    format!("{}-{}-{}-{}", &d[0..2], &d[2..7], &d[7..12], &d[12..])
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// List Kvaser devices known to CANlib (virtual channels excluded).
+#[tauri::command]
+pub fn list_kvaser_devices() -> Result<Vec<KvaserDeviceInfo>, String> {
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    {
+        canlib::list_devices()
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// Classic CAN bitrates CANlib has presets for, for the profile editor.
+#[tauri::command]
+pub fn get_kvaser_bitrates() -> Vec<u32> {
+    BITRATES.iter().map(|(b, _)| *b).collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitrate_constants() {
+        assert_eq!(bitrate_constant(500_000), Some(-2));
+        assert_eq!(bitrate_constant(1_000_000), Some(-1));
+        assert_eq!(bitrate_constant(800_000), None);
+    }
+
+    #[test]
+    fn test_encode_roundtrip() {
+        let frame = CanTransmitFrame {
+            frame_id: 0x7DF,
+            data: vec![0x02, 0x01, 0x0D],
+            bus: 1,
+            is_extended: false,
+            is_fd: false,
+            is_brs: false,
+            is_rtr: false,
+        };
+        let msg = KvaserTx::from_bytes(&encode_frame(&frame)).unwrap();
+        assert_eq!(msg.channel, 1);
+        assert_eq!(msg.id, 0x7DF);
+        assert_eq!(msg.flags, msg_flags::STD);
+        assert_eq!(msg.data, vec![0x02, 0x01, 0x0D]);
+        assert!(KvaserTx::from_bytes(&[0; 4]).is_none());
+    }
+
+    #[test]
+    fn test_decode() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+        let frame = decode_msg(1, 0x18DAF110, &data, 4, msg_flags::EXT).unwrap();
+        assert_eq!(frame.bus, 1);
+        assert!(frame.is_extended);
+        assert_eq!(frame.bytes, vec![1, 2, 3, 4]);
+        assert_eq!(frame.direction.as_deref(), Some("rx"));
+        assert!(decode_msg(0, 0, &data, 0, msg_flags::ERROR_FRAME).is_none());
+    }
+
+    #[test]
+    fn test_format_ean() {
+        // 73-30130-00685-0 stored as packed BCD, least significant byte first
+        let raw = [0x50, 0x68, 0x00, 0x30, 0x01, 0x33, 0x07, 0x00];
+        assert_eq!(format_ean(raw), "73-30130-00685-0");
+    }
+}
@@ -0,0 +1,436 @@
+// ui/src-tauri/src/io/lin/ldf.rs
+//
+// LDF-lite: the parts of a LIN Description File WireTAP needs, kept in a
+// `[lin]` table of a catalogue (the catalogue crate ignores it, as it does
+// `[j1939]`). Frames give each ID's length, checksum model, publisher and
+// signals; schedule tables give the slots a master runs:
+//
+//   [lin]
+//   master = "BCM"
+//   checksum = "enhanced"
+//
+//   [lin.frame."0x10"]
+//   name = "MotorStatus"
+//   length = 4
+//   publisher = "Motor1"
+//
+//   [[lin.frame."0x10".signals]]
+//   name = "Speed"
+//   start_bit = 0
+//   bit_length = 16
+//   factor = 0.1
+//   unit = "rpm"
+//
+//   [[lin.schedule.normal]]
+//   frame = "MotorStatus"   # frame name or ID
+//   delay_ms = 10
+//
+// The description is registered per session when the catalogue is attached.
+// LIN readers look it up for frame lengths and the schedule; tap_frames
+// decodes the signals of "lin" frames into the latest-values cache. Signals
+// are little-endian, as on the bus.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+use super::{ChecksumModel, MAX_ID};
+use crate::io::FrameMessage;
+use crate::latest_values::LatestValue;
+use crate::signal_bits::{extract_bits, SignalLayout};
+
+// ============================================================================
+// Description
+// ============================================================================
+
+/// A signal within a LIN frame.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LinSignal {
+    pub name: String,
+    pub start_bit: u16,
+    pub bit_length: u8,
+    #[serde(default)]
+    pub signed: bool,
+    #[serde(default = "default_factor")]
+    pub factor: f64,
+    #[serde(default)]
+    pub offset: f64,
+    #[serde(default)]
+    pub unit: Option<String>,
+}
+
+fn default_factor() -> f64 {
+    1.0
+}
+
+impl LinSignal {
+    /// Raw and physical value of this signal in a frame's data.
+    pub fn decode(&self, id: u8, data: &[u8]) -> Option<(u64, f64)> {
+        let raw = extract_bits(data, self.start_bit, self.bit_length, false)?;
+        let layout = SignalLayout {
+            frame_id: id as u32,
+            bus: None,
+            start_bit: self.start_bit,
+            bit_length: self.bit_length,
+            big_endian: false,
+            signed: self.signed,
+            factor: self.factor,
+            offset: self.offset,
+        };
+        Some((raw, layout.scale(raw)))
+    }
+}
+
+/// One frame of the description.
+#[derive(Clone, Debug, Serialize)]
+pub struct LinFrameDef {
+    pub id: u8,
+    pub name: Option<String>,
+    /// Data bytes (1-8)
+    pub length: usize,
+    /// Checksum model override (None = the description's default)
+    pub checksum: Option<ChecksumModel>,
+    pub publisher: Option<String>,
+    /// Whether the master node sends the response (otherwise a slave does)
+    pub master_publishes: bool,
+    pub signals: Vec<LinSignal>,
+}
+
+/// One slot of a schedule table.
+#[derive(Clone, Debug, Serialize)]
+pub struct ScheduleSlot {
+    pub id: u8,
+    /// Slot length before the next header
+    pub delay_ms: u64,
+    /// Data the master publishes in this slot (master frames only)
+    pub data: Option<Vec<u8>>,
+}
+
+/// A session's LDF-lite description.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct LinDescription {
+    pub baud_rate: Option<u32>,
+    /// Default checksum model (None = detect per frame)
+    pub checksum: Option<ChecksumModel>,
+    pub master: Option<String>,
+    pub frames: BTreeMap<u8, LinFrameDef>,
+    pub schedules: BTreeMap<String, Vec<ScheduleSlot>>,
+}
+
+#[derive(Deserialize)]
+struct RawDescription {
+    #[serde(default)]
+    baud_rate: Option<u32>,
+    #[serde(default)]
+    checksum: Option<ChecksumModel>,
+    #[serde(default)]
+    master: Option<String>,
+    #[serde(default)]
+    frame: BTreeMap<String, RawFrame>,
+    #[serde(default)]
+    schedule: BTreeMap<String, Vec<RawSlot>>,
+}
+
+#[derive(Deserialize)]
+struct RawFrame {
+    #[serde(default)]
+    name: Option<String>,
+    length: usize,
+    #[serde(default)]
+    checksum: Option<ChecksumModel>,
+    #[serde(default)]
+    publisher: Option<String>,
+    #[serde(default)]
+    signals: Vec<LinSignal>,
+}
+
+#[derive(Deserialize)]
+struct RawSlot {
+    frame: String,
+    delay_ms: u64,
+    #[serde(default)]
+    data: Option<Vec<u8>>,
+}
+
+fn parse_id(s: &str) -> Result<u8, String> {
+    let s = s.trim();
+    let id = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => s.parse(),
+    }
+    .map_err(|_| format!("Invalid LIN frame ID '{}'", s))?;
+    if id > MAX_ID {
+        return Err(format!("LIN frame ID 0x{:X} is above 0x3F", id));
+    }
+    Ok(id)
+}
+
+impl LinDescription {
+    /// Read the `[lin]` table of a catalogue, if it has one.
+    pub fn from_catalog(content: &str) -> Result<Option<Self>, String> {
+        let Some(value) = content
+            .parse::<toml::Table>()
+            .ok()
+            .and_then(|mut t| t.remove("lin"))
+        else {
+            return Ok(None);
+        };
+        let raw: RawDescription = value
+            .try_into()
+            .map_err(|e| format!("Invalid [lin] table: {}", e))?;
+        Self::resolve(raw).map(Some)
+    }
+
+    fn resolve(raw: RawDescription) -> Result<Self, String> {
+        let is_master = |publisher: &Option<String>| match publisher {
+            Some(p) => p.eq_ignore_ascii_case("master") || raw.master.as_deref() == Some(p.as_str()),
+            None => false,
+        };
+
+        let mut frames = BTreeMap::new();
+        for (key, frame) in &raw.frame {
+            let id = parse_id(key)?;
+            if !(1..=8).contains(&frame.length) {
+                return Err(format!("LIN frame 0x{:02X}: length must be 1-8, not {}", id, frame.length));
+            }
+            if let Some(s) = frame
+                .signals
+                .iter()
+                .find(|s| s.bit_length == 0 || s.start_bit as usize + s.bit_length as usize > frame.length * 8)
+            {
+                return Err(format!("LIN frame 0x{:02X}: signal '{}' doesn't fit in {} bytes", id, s.name, frame.length));
+            }
+            frames.insert(
+                id,
+                LinFrameDef {
+                    id,
+                    name: frame.name.clone(),
+                    length: frame.length,
+                    checksum: frame.checksum,
+                    publisher: frame.publisher.clone(),
+                    master_publishes: is_master(&frame.publisher),
+                    signals: frame.signals.clone(),
+                },
+            );
+        }
+
+        let mut schedules = BTreeMap::new();
+        for (name, slots) in raw.schedule {
+            let mut resolved = Vec::with_capacity(slots.len());
+            for slot in slots {
+                let id = match frames.values().find(|f| f.name.as_deref() == Some(slot.frame.as_str())) {
+                    Some(f) => f.id,
+                    None => parse_id(&slot.frame)
+                        .map_err(|_| format!("Schedule '{}': unknown frame '{}'", name, slot.frame))?,
+                };
+                if slot.delay_ms == 0 {
+                    return Err(format!("Schedule '{}': slot for 0x{:02X} has no delay", name, id));
+                }
+                if let (Some(data), Some(frame)) = (&slot.data, frames.get(&id)) {
+                    if data.len() != frame.length {
+                        return Err(format!(
+                            "Schedule '{}': 0x{:02X} data is {} bytes, the frame is {}",
+                            name, id, data.len(), frame.length
+                        ));
+                    }
+                }
+                resolved.push(ScheduleSlot { id, delay_ms: slot.delay_ms, data: slot.data });
+            }
+            if !resolved.is_empty() {
+                schedules.insert(name, resolved);
+            }
+        }
+
+        Ok(LinDescription {
+            baud_rate: raw.baud_rate,
+            checksum: raw.checksum,
+            master: raw.master,
+            frames,
+            schedules,
+        })
+    }
+
+    /// Data length and checksum model of a frame the description covers.
+    pub fn layout(&self, id: u8) -> Option<(usize, Option<ChecksumModel>)> {
+        self.frames.get(&id).map(|f| (f.length, f.checksum.or(self.checksum)))
+    }
+
+    /// A schedule table by name, or the first one (by name) when unnamed.
+    pub fn schedule(&self, name: Option<&str>) -> Option<(&str, &[ScheduleSlot])> {
+        match name {
+            Some(name) => self.schedules.get_key_value(name),
+            None => self.schedules.iter().next(),
+        }
+        .map(|(name, slots)| (name.as_str(), slots.as_slice()))
+    }
+}
+
+// ============================================================================
+// Per-session registry
+// ============================================================================
+
+static SESSION_LIN: Lazy<RwLock<HashMap<String, Arc<LinDescription>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// The session's description, if its catalogue has a `[lin]` table.
+pub fn description(session_id: &str) -> Option<Arc<LinDescription>> {
+    SESSION_LIN.read().ok()?.get(session_id).cloned()
+}
+
+/// Pick up the `[lin]` table of a catalogue being attached to a session. A
+/// catalogue without one drops the description an earlier catalogue set.
+pub fn attach_catalog(session_id: &str, content: &str) -> Result<(), String> {
+    let description = LinDescription::from_catalog(content)?;
+    let Ok(mut sessions) = SESSION_LIN.write() else {
+        return Ok(());
+    };
+    match description {
+        Some(description) => {
+            tlog!(
+                "[lin:{}] Catalogue description: {} frames, {} schedule tables",
+                session_id, description.frames.len(), description.schedules.len()
+            );
+            sessions.insert(session_id.to_string(), Arc::new(description));
+        }
+        None => {
+            sessions.remove(session_id);
+        }
+    }
+    Ok(())
+}
+
+/// Drop the description when the session's catalogue is detached.
+pub fn detach_catalog(session_id: &str) {
+    clear_session(session_id);
+}
+
+/// Remove a session's LIN state. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut sessions) = SESSION_LIN.write() {
+        sessions.remove(session_id);
+    }
+}
+
+/// Decode the signals of "lin" frames into the latest-values cache.
+pub fn tap_frames(session_id: &str, frames: &[FrameMessage]) {
+    if !frames.iter().any(|f| f.protocol == "lin") {
+        return;
+    }
+    let Some(description) = description(session_id) else {
+        return;
+    };
+    let mut samples = Vec::new();
+    for frame in frames.iter().filter(|f| f.protocol == "lin" && f.is_error.is_none()) {
+        let Some(def) = description.frames.get(&(frame.frame_id as u8)) else {
+            continue;
+        };
+        for signal in &def.signals {
+            let Some((raw, value)) = signal.decode(def.id, &frame.bytes) else {
+                continue;
+            };
+            let rounded = (value * 1000.0).round() / 1000.0;
+            samples.push(LatestValue {
+                frame_id: frame.frame_id,
+                bus: frame.bus,
+                name: signal.name.clone(),
+                mux_value: None,
+                raw: raw as f64,
+                value,
+                display: match &signal.unit {
+                    Some(unit) => format!("{} {}", rounded, unit),
+                    None => rounded.to_string(),
+                },
+                unit: signal.unit.clone(),
+                timestamp_us: frame.timestamp_us,
+                updates: 0,
+            });
+        }
+    }
+    crate::latest_values::record_values(session_id, samples);
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// The LDF-lite description attached to a session, if any.
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_lin_description(session_id: String) -> Option<LinDescription> {
+    description(&session_id).map(|d| (*d).clone())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"
+[meta]
+name = "Seat module"
+version = 4
+
+[lin]
+master = "BCM"
+checksum = "enhanced"
+
+[lin.frame."0x10"]
+name = "MotorStatus"
+length = 2
+publisher = "Motor1"
+
+[[lin.frame."0x10".signals]]
+name = "Speed"
+start_bit = 0
+bit_length = 12
+factor = 0.5
+unit = "rpm"
+
+[lin.frame."0x20"]
+name = "MotorCommand"
+length = 1
+publisher = "BCM"
+
+[[lin.schedule.normal]]
+frame = "MotorCommand"
+delay_ms = 10
+data = [3]
+
+[[lin.schedule.normal]]
+frame = "0x10"
+delay_ms = 10
+"#;
+
+    #[test]
+    fn parses_frames_and_schedules() {
+        let d = LinDescription::from_catalog(CATALOG).unwrap().unwrap();
+        assert_eq!(d.layout(0x10), Some((2, Some(ChecksumModel::Enhanced))));
+        assert!(d.frames[&0x20].master_publishes);
+        assert!(!d.frames[&0x10].master_publishes);
+        let (name, slots) = d.schedule(None).unwrap();
+        assert_eq!(name, "normal");
+        assert_eq!(slots.iter().map(|s| s.id).collect::<Vec<_>>(), vec![0x20, 0x10]);
+        assert_eq!(slots[0].data, Some(vec![3]));
+
+        let (raw, value) = d.frames[&0x10].signals[0].decode(0x10, &[0x34, 0xF2]).unwrap();
+        assert_eq!(raw, 0x234);
+        assert_eq!(value, 282.0);
+
+        assert!(LinDescription::from_catalog("[meta]\nname = \"x\"").unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_bad_descriptions() {
+        let bad_length = "[lin.frame.\"0x10\"]\nlength = 9";
+        assert!(LinDescription::from_catalog(bad_length).is_err());
+        let bad_id = "[lin.frame.\"0x40\"]\nlength = 2";
+        assert!(LinDescription::from_catalog(bad_id).is_err());
+        let unknown = "[[lin.schedule.s]]\nframe = \"Nope\"\ndelay_ms = 10";
+        assert!(LinDescription::from_catalog(unknown).is_err());
+        let wide = "[lin.frame.\"0x10\"]\nlength = 1\n[[lin.frame.\"0x10\".signals]]\nname = \"A\"\nstart_bit = 4\nbit_length = 8";
+        assert!(LinDescription::from_catalog(wide).is_err());
+    }
+}
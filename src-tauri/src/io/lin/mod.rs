@@ -0,0 +1,445 @@
+// ui/src-tauri/src/io/lin/mod.rs
+//
+// LIN (ISO 17987 / LIN 2.x) over UART-based adapters: a serial port wired to
+// a LIN transceiver, so the bytes read are the bus itself. A frame is a break
+// (read as a 0x00 byte, usually with a framing error), the sync byte 0x55,
+// the protected identifier (6-bit ID plus two parity bits), then the response:
+// 1-8 data bytes and a checksum. Responses carry no length, so lengths come
+// from the session's LDF-lite description (ldf.rs) when one is attached, and
+// are otherwise found by looking for the checksum that closes the frame
+// before the next break.
+//
+// Two modes:
+// - Monitor: passive, frames are cut from the byte stream at each break.
+// - Master: the reader runs a schedule table from the LDF-lite description,
+//   sending each slot's header (and the data for frames the master
+//   publishes), and reads the slot's response. Frames sent through the
+//   session's transmit path are published in the next slot.
+//
+// Frames are emitted with protocol "lin", `frame_id` = the 6-bit ID and the
+// data bytes (checksum stripped). A frame whose checksum doesn't match is
+// still emitted, flagged with `is_error`.
+
+pub mod ldf;
+#[cfg(not(target_os = "ios"))]
+pub mod reader;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::io::{CanTransmitFrame, FrameMessage};
+
+// Internal items used by multi_source
+#[cfg(not(target_os = "ios"))]
+pub(crate) use reader::run_source as run_lin_source;
+#[cfg(not(target_os = "ios"))]
+pub use reader::probe_lin;
+pub use ldf::{attach_catalog, clear_session, detach_catalog, tap_frames, LinDescription};
+
+pub const BREAK: u8 = 0x00;
+pub const SYNC: u8 = 0x55;
+/// Highest LIN frame ID (0x3C/0x3D are the diagnostic frames)
+pub const MAX_ID: u8 = 0x3F;
+/// Longest response: 8 data bytes plus the checksum
+const MAX_RESPONSE: usize = 9;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// How the adapter is driven
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinMode {
+    /// Passive: frames cut from the bus traffic
+    Monitor,
+    /// Active: run a schedule table as the bus master
+    Master,
+}
+
+/// Checksum over the data only (LIN 1.x, and the diagnostic frames), or over
+/// the protected ID and the data (LIN 2.x).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumModel {
+    Classic,
+    Enhanced,
+}
+
+/// LIN source settings, parsed from a profile's connection
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinConfig {
+    pub port: String,
+    pub baud_rate: u32,
+    pub mode: LinMode,
+    /// Schedule table to run in master mode (default: the first by name)
+    pub schedule: Option<String>,
+    /// Checksum model for frames the description doesn't cover (None = detect)
+    pub checksum: Option<ChecksumModel>,
+}
+
+fn number(connection: &Value, key: &str) -> Option<u64> {
+    connection
+        .get(key)
+        .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.trim().parse().ok())))
+}
+
+impl LinConfig {
+    /// Read the settings from a profile's connection map.
+    pub fn from_connection(connection: &Value) -> Result<Self, String> {
+        let port = connection
+            .get("port")
+            .and_then(|v| v.as_str())
+            .filter(|p| !p.is_empty())
+            .ok_or("Serial port is required")?
+            .to_string();
+        let baud_rate = number(connection, "baud_rate").unwrap_or(19200);
+        if !(1000..=20000).contains(&baud_rate) {
+            return Err(format!("Invalid LIN baud rate {} (1000-20000)", baud_rate));
+        }
+        let mode = match connection.get("mode").and_then(|v| v.as_str()).unwrap_or("monitor") {
+            "monitor" => LinMode::Monitor,
+            "master" => LinMode::Master,
+            other => return Err(format!("Unknown LIN mode '{}'", other)),
+        };
+        let checksum = match connection.get("checksum").and_then(|v| v.as_str()).unwrap_or("auto") {
+            "auto" | "" => None,
+            "classic" => Some(ChecksumModel::Classic),
+            "enhanced" => Some(ChecksumModel::Enhanced),
+            other => return Err(format!("Unknown LIN checksum model '{}'", other)),
+        };
+        let schedule = connection
+            .get("schedule")
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        Ok(LinConfig { port, baud_rate: baud_rate as u32, mode, schedule, checksum })
+    }
+}
+
+// ============================================================================
+// Identifier and checksum
+// ============================================================================
+
+/// Protected identifier: the 6-bit ID with parity bits P0 (bit 6) and P1 (bit 7).
+pub fn protected_id(id: u8) -> u8 {
+    let id = id & MAX_ID;
+    let bit = |n: u8| (id >> n) & 1;
+    let p0 = bit(0) ^ bit(1) ^ bit(2) ^ bit(4);
+    let p1 = !(bit(1) ^ bit(3) ^ bit(4) ^ bit(5)) & 1;
+    id | (p0 << 6) | (p1 << 7)
+}
+
+/// The frame ID of a protected identifier, if its parity is right.
+pub fn id_from_pid(pid: u8) -> Option<u8> {
+    let id = pid & MAX_ID;
+    (protected_id(id) == pid).then_some(id)
+}
+
+/// Inverted eight-bit sum with carry, over the data (classic) or the
+/// protected ID and the data (enhanced).
+pub fn checksum(model: ChecksumModel, pid: u8, data: &[u8]) -> u8 {
+    let mut sum: u16 = match model {
+        ChecksumModel::Classic => 0,
+        ChecksumModel::Enhanced => pid as u16,
+    };
+    for &b in data {
+        sum += b as u16;
+        if sum > 0xFF {
+            sum -= 0xFF;
+        }
+    }
+    !(sum as u8)
+}
+
+/// Checksum model a frame must use: diagnostic frames are always classic,
+/// otherwise the configured model, or None to accept either.
+fn model_for(id: u8, configured: Option<ChecksumModel>) -> Option<ChecksumModel> {
+    if id >= 0x3C {
+        Some(ChecksumModel::Classic)
+    } else {
+        configured
+    }
+}
+
+// ============================================================================
+// Frames
+// ============================================================================
+
+/// One LIN frame read from the bus.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinFrame {
+    pub id: u8,
+    pub data: Vec<u8>,
+    pub checksum: u8,
+    /// Model the checksum matched (or was expected to match)
+    pub model: ChecksumModel,
+    pub checksum_ok: bool,
+}
+
+/// Build a frame from a response (data followed by the checksum byte).
+/// Returns None if there's no data.
+pub fn frame_from_response(id: u8, response: &[u8], configured: Option<ChecksumModel>) -> Option<LinFrame> {
+    let (&received, data) = response.split_last()?;
+    if data.is_empty() || data.len() > 8 {
+        return None;
+    }
+    let pid = protected_id(id);
+    let matches = |model| checksum(model, pid, data) == received;
+    let (model, checksum_ok) = match model_for(id, configured) {
+        Some(model) => (model, matches(model)),
+        None if matches(ChecksumModel::Enhanced) => (ChecksumModel::Enhanced, true),
+        None if matches(ChecksumModel::Classic) => (ChecksumModel::Classic, true),
+        None => (ChecksumModel::Enhanced, false),
+    };
+    Some(LinFrame { id, data: data.to_vec(), checksum: received, model, checksum_ok })
+}
+
+/// Response bytes for a frame the master publishes: data then checksum.
+pub fn encode_response(id: u8, data: &[u8], configured: Option<ChecksumModel>) -> Vec<u8> {
+    let model = model_for(id, configured).unwrap_or(ChecksumModel::Enhanced);
+    let mut out = Vec::with_capacity(data.len() + 1);
+    out.extend_from_slice(data);
+    out.push(checksum(model, protected_id(id), data));
+    out
+}
+
+/// Convert a LIN frame for the frame pipeline.
+pub fn frame_message(frame: &LinFrame, timestamp_us: u64, tx: bool) -> FrameMessage {
+    FrameMessage {
+        protocol: "lin".to_string(),
+        timestamp_us,
+        frame_id: frame.id as u32,
+        bus: 0,
+        dlc: frame.data.len() as u8,
+        bytes: frame.data.clone(),
+        is_extended: false,
+        is_fd: false,
+        source_address: None,
+        incomplete: None,
+        direction: Some(if tx { "tx" } else { "rx" }.to_string()),
+        link: None,
+        clock: None,
+        is_error: (!frame.checksum_ok).then_some(true),
+        error_class: None,
+        brs: None,
+    }
+}
+
+// ============================================================================
+// Stream decoding (monitor mode)
+// ============================================================================
+
+fn find_header(buf: &[u8], from: usize) -> Option<usize> {
+    (from..buf.len().saturating_sub(1)).find(|&i| buf[i] == BREAK && buf[i + 1] == SYNC)
+}
+
+/// Cuts frames out of the raw byte stream at each break + sync.
+#[derive(Default)]
+pub struct LinDecoder {
+    buf: Vec<u8>,
+    /// Headers whose protected ID failed the parity check
+    pub parity_errors: u64,
+    /// Headers no node answered
+    pub no_response: u64,
+}
+
+impl LinDecoder {
+    /// Feed received bytes. `length` gives the data length and checksum model
+    /// of IDs the description covers.
+    pub fn push(
+        &mut self,
+        bytes: &[u8],
+        length: &dyn Fn(u8) -> Option<(usize, Option<ChecksumModel>)>,
+        default_model: Option<ChecksumModel>,
+    ) -> Vec<LinFrame> {
+        self.buf.extend_from_slice(bytes);
+        let mut frames = Vec::new();
+        loop {
+            let Some(start) = find_header(&self.buf, 0) else {
+                // Keep a trailing break that may be followed by sync
+                let keep = usize::from(self.buf.last() == Some(&BREAK));
+                self.buf.drain(..self.buf.len() - keep);
+                break;
+            };
+            self.buf.drain(..start);
+            if self.buf.len() < 3 {
+                break;
+            }
+            let Some(id) = id_from_pid(self.buf[2]) else {
+                self.parity_errors += 1;
+                self.buf.drain(..1);
+                continue;
+            };
+
+            if let Some((len, model)) = length(id) {
+                let next = find_header(&self.buf, 3);
+                if next == Some(3) {
+                    self.no_response += 1;
+                    self.buf.drain(..3);
+                    continue;
+                }
+                if self.buf.len() < 3 + len + 1 {
+                    break;
+                }
+                if let Some(frame) = frame_from_response(id, &self.buf[3..3 + len + 1], model.or(default_model)) {
+                    frames.push(frame);
+                }
+                self.buf.drain(..3 + len + 1);
+                continue;
+            }
+
+            // Unknown length: the response ends at the next break whose
+            // preceding bytes close with a valid checksum
+            let mut from = 3;
+            let mut end = None;
+            while let Some(next) = find_header(&self.buf, from) {
+                let body = &self.buf[3..next];
+                if body.is_empty() {
+                    self.no_response += 1;
+                    end = Some((next, None));
+                    break;
+                }
+                if body.len() > MAX_RESPONSE {
+                    break;
+                }
+                if let Some(frame) = frame_from_response(id, body, default_model).filter(|f| f.checksum_ok) {
+                    end = Some((next, Some(frame)));
+                    break;
+                }
+                from = next + 1;
+            }
+            match end {
+                Some((next, frame)) => {
+                    frames.extend(frame);
+                    self.buf.drain(..next);
+                }
+                // Nothing closes it within a full response: drop this header
+                None if self.buf.len() > 3 + MAX_RESPONSE + 2 => {
+                    self.buf.drain(..1);
+                }
+                None => break,
+            }
+        }
+        frames
+    }
+
+    /// The line went idle: whatever follows the last header is its response.
+    pub fn flush(&mut self, default_model: Option<ChecksumModel>) -> Option<LinFrame> {
+        let buf = std::mem::take(&mut self.buf);
+        if buf.len() < 3 || buf[0] != BREAK || buf[1] != SYNC {
+            return None;
+        }
+        let id = id_from_pid(buf[2])?;
+        if buf.len() == 3 {
+            self.no_response += 1;
+            return None;
+        }
+        frame_from_response(id, &buf[3..], default_model)
+    }
+}
+
+/// Parse what was read during one master slot: the echo of our own header
+/// (the break may or may not show up as 0x00) followed by the response.
+pub fn parse_slot(bytes: &[u8], id: u8, len: Option<usize>, model: Option<ChecksumModel>) -> Option<LinFrame> {
+    let pid = protected_id(id);
+    let sync = bytes.windows(2).position(|w| w == [SYNC, pid])?;
+    let response = &bytes[sync + 2..];
+    match len {
+        Some(len) if response.len() > len => frame_from_response(id, &response[..len + 1], model),
+        Some(_) => None,
+        None => frame_from_response(id, &response[..response.len().min(MAX_RESPONSE)], model),
+    }
+}
+
+// ============================================================================
+// Transmit
+// ============================================================================
+
+/// Encode a frame for the LIN master's transmit channel: the ID then the data.
+/// The reader adds the header and checksum when the slot comes up.
+pub fn encode_transmit_frame(frame: &CanTransmitFrame) -> Result<Vec<u8>, String> {
+    if frame.frame_id > MAX_ID as u32 || frame.is_extended || frame.is_fd {
+        return Err(format!("0x{:X} is not a LIN frame ID (0x00-0x3F)", frame.frame_id));
+    }
+    if frame.data.is_empty() || frame.data.len() > 8 {
+        return Err(format!("LIN frames carry 1-8 data bytes, not {}", frame.data.len()));
+    }
+    let mut out = Vec::with_capacity(1 + frame.data.len());
+    out.push(frame.frame_id as u8);
+    out.extend_from_slice(&frame.data);
+    Ok(out)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn protected_id_and_checksums() {
+        assert_eq!(protected_id(0x3C), 0x3C);
+        assert_eq!(protected_id(0x3D), 0x7D);
+        assert_eq!(protected_id(0x10), 0x50);
+        assert_eq!(id_from_pid(0x50), Some(0x10));
+        assert_eq!(id_from_pid(0x10), None);
+
+        let data = [0x4A, 0x55, 0x93, 0xE5];
+        assert_eq!(checksum(ChecksumModel::Classic, 0, &data), 0xE6);
+        assert_eq!(checksum(ChecksumModel::Enhanced, 0x50, &data), 0x96);
+        let frame = frame_from_response(0x10, &[0x4A, 0x55, 0x93, 0xE5, 0x96], None).unwrap();
+        assert_eq!(frame.model, ChecksumModel::Enhanced);
+        assert!(frame.checksum_ok);
+        // Diagnostic frames are always classic
+        assert_eq!(encode_response(0x3C, &data, Some(ChecksumModel::Enhanced)).last(), Some(&0xE6));
+    }
+
+    #[test]
+    fn decoder_splits_stream_at_breaks() {
+        let mut d = LinDecoder::default();
+        let none = |_: u8| -> Option<(usize, Option<ChecksumModel>)> { None };
+        let mut stream = vec![0xFF, BREAK, SYNC, 0x50, 0x4A, 0x55, 0x93, 0xE5, 0x96];
+        // A header nobody answers, then a frame with a bad checksum
+        stream.extend_from_slice(&[BREAK, SYNC, protected_id(0x20)]);
+        stream.extend_from_slice(&[BREAK, SYNC, protected_id(0x11), 0x01, 0x02, 0x00]);
+        let frames = d.push(&stream, &none, None);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].data, vec![0x4A, 0x55, 0x93, 0xE5]);
+        assert_eq!(d.no_response, 1);
+        let last = d.flush(None).unwrap();
+        assert_eq!((last.id, last.checksum_ok), (0x11, false));
+
+        // A known length lets a response end without waiting for the next break
+        let known = |id: u8| (id == 0x10).then_some((4usize, Some(ChecksumModel::Enhanced)));
+        let frames = d.push(&[BREAK, SYNC, 0x50, 0x4A, 0x55, 0x93, 0xE5, 0x96], &known, None);
+        assert_eq!(frames.len(), 1);
+        assert!(frames[0].checksum_ok);
+
+        let slot = parse_slot(&[BREAK, SYNC, 0x50, 0x4A, 0x55, 0x93, 0xE5, 0x96], 0x10, Some(4), None).unwrap();
+        assert!(slot.checksum_ok);
+        assert!(parse_slot(&[SYNC, 0x50], 0x10, None, None).is_none());
+    }
+
+    #[test]
+    fn test_config_from_connection() {
+        let config = LinConfig::from_connection(&json!({
+            "port": "/dev/ttyUSB0",
+            "mode": "master",
+            "baud_rate": "10400",
+            "checksum": "classic",
+            "schedule": "normal",
+        }))
+        .unwrap();
+        assert_eq!(config.mode, LinMode::Master);
+        assert_eq!(config.baud_rate, 10400);
+        assert_eq!(config.checksum, Some(ChecksumModel::Classic));
+        assert_eq!(config.schedule.as_deref(), Some("normal"));
+
+        assert!(LinConfig::from_connection(&json!({})).is_err());
+        assert!(LinConfig::from_connection(&json!({"port": "COM3", "baud_rate": 115200})).is_err());
+        assert!(LinConfig::from_connection(&json!({"port": "COM3", "mode": "slave"})).is_err());
+    }
+}
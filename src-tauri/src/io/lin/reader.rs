@@ -0,0 +1,357 @@
+// ui/src-tauri/src/io/lin/reader.rs
+//
+// Serial reader for UART LIN adapters. One blocking thread owns the port.
+// In monitor mode the bytes go through a LinDecoder, and the frame in
+// progress is closed when the line goes idle. In master mode each schedule
+// slot is a break, the header, the response when the master publishes the
+// frame, then reading until the slot's time is up. The UART hears its own
+// transmission, so a slot's bytes are parsed as a whole (parse_slot).
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::io::{Read, Write};
+use std::sync::mpsc as std_mpsc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+
+use super::ldf::description;
+use super::{encode_response, frame_message, parse_slot, protected_id, LinConfig, LinDecoder, LinMode, SYNC};
+use crate::io::error::IoError;
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::types::{SourceMessage, TransmitRequest};
+use crate::io::{now_us, FrameMessage};
+
+/// Serial read timeout; short, as slots are timed from the read loop.
+const READ_TIMEOUT: Duration = Duration::from_millis(2);
+
+/// Slot length for frames sent through the transmit path
+const SPORADIC_SLOT_MS: u64 = 10;
+
+/// How long a probe listens for traffic
+const PROBE_LISTEN: Duration = Duration::from_millis(500);
+
+type Port = Box<dyn serialport::SerialPort>;
+
+fn open_port(port: &str, baud_rate: u32) -> Result<Port, String> {
+    let device = format!("lin({})", port);
+    let serial_port = serialport::new(port, baud_rate)
+        .data_bits(serialport::DataBits::Eight)
+        .parity(serialport::Parity::None)
+        .stop_bits(serialport::StopBits::One)
+        .timeout(READ_TIMEOUT)
+        .open()
+        .map_err(|e| IoError::connection(&device, e.to_string()).to_string())?;
+    let _ = serial_port.clear(serialport::ClearBuffer::All);
+    Ok(serial_port)
+}
+
+/// Idle time that ends a frame: 30 bit times, at least 4 ms.
+fn idle_gap(baud_rate: u32) -> Duration {
+    Duration::from_micros((30_000_000 / baud_rate as u64).max(4_000))
+}
+
+/// Break (at least 13 dominant bits), then sync and the protected ID.
+fn send_header(port: &mut Port, id: u8, baud_rate: u32) -> Result<(), String> {
+    let break_time = Duration::from_micros(13_000_000 / baud_rate as u64 + 100);
+    port.set_break().map_err(|e| format!("Break failed: {}", e))?;
+    std::thread::sleep(break_time);
+    port.clear_break().map_err(|e| format!("Break failed: {}", e))?;
+    port.write_all(&[SYNC, protected_id(id)])
+        .map_err(|e| format!("Write error: {}", e))
+}
+
+/// Open the port and list the frame IDs seen in a short listen.
+pub fn probe_lin(port: &str, baud_rate: u32) -> Result<Vec<u8>, String> {
+    let mut serial_port = open_port(port, baud_rate)?;
+    let mut decoder = LinDecoder::default();
+    let mut ids = BTreeSet::new();
+    let mut buf = [0u8; 256];
+    let no_layout = |_: u8| -> Option<(usize, Option<super::ChecksumModel>)> { None };
+    let deadline = Instant::now() + PROBE_LISTEN;
+    while Instant::now() < deadline {
+        match serial_port.read(&mut buf) {
+            Ok(n) => ids.extend(decoder.push(&buf[..n], &no_layout, None).into_iter().map(|f| f.id)),
+            Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(e) => return Err(format!("Read error: {}", e)),
+        }
+    }
+    ids.extend(decoder.flush(None).map(|f| f.id));
+    Ok(ids.into_iter().collect())
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Map and forward a batch of frames; returns how many were bus-filtered.
+fn forward(
+    source_idx: usize,
+    frames: Vec<FrameMessage>,
+    bus_mappings: &[BusMapping],
+    tx: &mpsc::Sender<SourceMessage>,
+) -> u64 {
+    let total = frames.len();
+    let pending: Vec<FrameMessage> = frames
+        .into_iter()
+        .filter_map(|mut frame| apply_bus_mapping(&mut frame, bus_mappings).then_some(frame))
+        .collect();
+    let filtered = (total - pending.len()) as u64;
+    if !pending.is_empty() {
+        let _ = tx.blocking_send(SourceMessage::Frames(source_idx, pending));
+    }
+    filtered
+}
+
+#[derive(Default)]
+struct Counters {
+    rx_frames: u64,
+    tx_frames: u64,
+    checksum_errors: u64,
+    no_response: u64,
+    bus_filtered: u64,
+}
+
+impl Counters {
+    fn count(&mut self, frames: &[FrameMessage]) {
+        for frame in frames {
+            if frame.direction.as_deref() == Some("tx") {
+                self.tx_frames += 1;
+            } else {
+                self.rx_frames += 1;
+            }
+            if frame.is_error == Some(true) {
+                self.checksum_errors += 1;
+            }
+        }
+    }
+}
+
+/// Run a LIN source and send frames to the merge task
+pub async fn run_source(
+    session_id: String,
+    source_idx: usize,
+    config: LinConfig,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let blocking_handle = tokio::task::spawn_blocking(move || {
+        let mut port = match open_port(&config.port, config.baud_rate) {
+            Ok(p) => p,
+            Err(e) => {
+                let _ = tx.blocking_send(SourceMessage::Error(source_idx, e));
+                return;
+            }
+        };
+
+        tlog!(
+            "[lin] Source {} connected to {} (baud: {}, mode: {:?}, checksum: {:?})",
+            source_idx, config.port, config.baud_rate, config.mode, config.checksum
+        );
+
+        let _ = tx.blocking_send(SourceMessage::Connected(
+            source_idx,
+            "lin".to_string(),
+            config.port.clone(),
+            None,
+        ));
+
+        let mut counters = Counters::default();
+        let result = match config.mode {
+            LinMode::Monitor => monitor(&session_id, source_idx, &mut port, &config, &bus_mappings, &stop_flag, &tx, &mut counters),
+            LinMode::Master => {
+                let (transmit_tx, transmit_rx) = std_mpsc::sync_channel::<TransmitRequest>(32);
+                let _ = tx.blocking_send(SourceMessage::TransmitReady(source_idx, transmit_tx));
+                master(
+                    &session_id, source_idx, &mut port, &config, &bus_mappings, &stop_flag, &tx, &transmit_rx,
+                    &mut counters,
+                )
+            }
+        };
+        if let Err(e) = result {
+            let _ = tx.blocking_send(SourceMessage::Error(source_idx, e));
+            return;
+        }
+
+        tlog!(
+            "[lin] Source {} ended: rx={}, tx={}, checksum_errors={}, no_response={}, bus_filtered={}",
+            source_idx, counters.rx_frames, counters.tx_frames, counters.checksum_errors, counters.no_response,
+            counters.bus_filtered
+        );
+
+        let _ = tx.blocking_send(SourceMessage::Ended(source_idx, "stopped".to_string()));
+    });
+
+    let _ = blocking_handle.await;
+}
+
+/// Cut frames from the bus traffic until stopped.
+#[allow(clippy::too_many_arguments)]
+fn monitor(
+    session_id: &str,
+    source_idx: usize,
+    port: &mut Port,
+    config: &LinConfig,
+    bus_mappings: &[BusMapping],
+    stop_flag: &AtomicBool,
+    tx: &mpsc::Sender<SourceMessage>,
+    counters: &mut Counters,
+) -> Result<(), String> {
+    let gap = idle_gap(config.baud_rate);
+    let mut decoder = LinDecoder::default();
+    let mut buf = [0u8; 512];
+    let mut last_rx = Instant::now();
+    let mut pending = false;
+
+    while !stop_flag.load(Ordering::SeqCst) {
+        // Looked up each pass so a catalogue attached mid-session applies
+        let ldf = description(session_id);
+        let layout = |id: u8| ldf.as_ref().and_then(|d| d.layout(id));
+
+        let mut found = match port.read(&mut buf) {
+            Ok(n) if n > 0 => {
+                last_rx = Instant::now();
+                pending = true;
+                decoder.push(&buf[..n], &layout, config.checksum)
+            }
+            Ok(_) => Vec::new(),
+            Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => Vec::new(),
+            Err(e) => return Err(format!("Read error: {}", e)),
+        };
+        if pending && last_rx.elapsed() >= gap {
+            pending = false;
+            found.extend(decoder.flush(config.checksum));
+        }
+        if found.is_empty() {
+            continue;
+        }
+
+        let ts = now_us();
+        let frames: Vec<FrameMessage> = found.iter().map(|f| frame_message(f, ts, false)).collect();
+        counters.count(&frames);
+        counters.bus_filtered += forward(source_idx, frames, bus_mappings, tx);
+    }
+    counters.no_response = decoder.no_response;
+    if decoder.parity_errors > 0 {
+        tlog!("[lin] Source {}: {} headers failed the parity check", source_idx, decoder.parity_errors);
+    }
+    Ok(())
+}
+
+/// Run the schedule table until stopped, publishing transmitted frames in
+/// the next slot.
+#[allow(clippy::too_many_arguments)]
+fn master(
+    session_id: &str,
+    source_idx: usize,
+    port: &mut Port,
+    config: &LinConfig,
+    bus_mappings: &[BusMapping],
+    stop_flag: &AtomicBool,
+    tx: &mpsc::Sender<SourceMessage>,
+    transmit_rx: &std_mpsc::Receiver<TransmitRequest>,
+    counters: &mut Counters,
+) -> Result<(), String> {
+    // Latest data per master-published ID, from transmits or the schedule
+    let mut published: HashMap<u8, Vec<u8>> = HashMap::new();
+    let mut sporadic: VecDeque<(u8, Vec<u8>)> = VecDeque::new();
+    let mut slot_idx = 0usize;
+    let mut running: Option<String> = None;
+    let mut buf = [0u8; 256];
+
+    while !stop_flag.load(Ordering::SeqCst) {
+        for req in transmit_rx.try_iter() {
+            match req.data.split_first() {
+                Some((&id, data)) if !data.is_empty() => {
+                    published.insert(id, data.to_vec());
+                    sporadic.push_back((id, data.to_vec()));
+                    let _ = req.result_tx.try_send(Ok(()));
+                }
+                _ => {
+                    let _ = req.result_tx.try_send(Err("Empty LIN frame".to_string()));
+                }
+            }
+        }
+
+        let ldf = description(session_id);
+        let schedule = ldf.as_ref().and_then(|d| d.schedule(config.schedule.as_deref()));
+        if running.as_deref() != schedule.map(|(name, _)| name) {
+            running = schedule.map(|(name, _)| name.to_string());
+            slot_idx = 0;
+            match &running {
+                Some(name) => tlog!("[lin] Source {} running schedule '{}'", source_idx, name),
+                None => tlog!(
+                    "[lin] Source {}: no schedule table{} - attach a catalogue with a [lin] schedule",
+                    source_idx,
+                    config.schedule.as_deref().map(|s| format!(" named '{}'", s)).unwrap_or_default()
+                ),
+            }
+        }
+
+        // Transmitted frames go out first, then the schedule's next slot
+        let (id, delay_ms, data) = if let Some((id, data)) = sporadic.pop_front() {
+            (id, SPORADIC_SLOT_MS, Some(data))
+        } else if let Some((_, slots)) = schedule {
+            let slot = &slots[slot_idx % slots.len()];
+            slot_idx = slot_idx.wrapping_add(1);
+            let def = ldf.as_ref().and_then(|d| d.frames.get(&slot.id));
+            let master_publishes = def.map_or(slot.data.is_some(), |f| f.master_publishes);
+            let data = master_publishes.then(|| {
+                published
+                    .get(&slot.id)
+                    .or(slot.data.as_ref())
+                    .cloned()
+                    .unwrap_or_else(|| vec![0; def.map_or(1, |f| f.length)])
+            });
+            (slot.id, slot.delay_ms, data)
+        } else {
+            // Nothing to run yet: idle without driving the bus
+            std::thread::sleep(Duration::from_millis(50));
+            continue;
+        };
+
+        let layout = ldf.as_ref().and_then(|d| d.layout(id));
+        let model = layout.and_then(|(_, m)| m).or(config.checksum);
+        let slot_start = Instant::now();
+        let ts = now_us();
+
+        send_header(port, id, config.baud_rate)?;
+        if let Some(data) = &data {
+            port.write_all(&encode_response(id, data, model))
+                .map_err(|e| format!("Write error: {}", e))?;
+        }
+        let _ = port.flush();
+
+        let mut slot_bytes = Vec::new();
+        let slot_time = Duration::from_millis(delay_ms);
+        while slot_start.elapsed() < slot_time {
+            match port.read(&mut buf) {
+                Ok(n) => slot_bytes.extend_from_slice(&buf[..n]),
+                Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) => return Err(format!("Read error: {}", e)),
+            }
+        }
+
+        match parse_slot(&slot_bytes, id, layout.map(|(len, _)| len), model) {
+            Some(frame) => {
+                let frames = vec![frame_message(&frame, ts, data.is_some())];
+                counters.count(&frames);
+                counters.bus_filtered += forward(source_idx, frames, bus_mappings, tx);
+            }
+            None => {
+                counters.no_response += 1;
+                if counters.no_response % 100 == 1 {
+                    tlog!(
+                        "[lin] Source {}: no response to 0x{:02X} (no responses: {})",
+                        source_idx, id, counters.no_response
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
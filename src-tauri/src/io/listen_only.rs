@@ -0,0 +1,151 @@
+// ui/src-tauri/src/io/listen_only.rs
+//
+// One listen-only switch for every CAN adapter that has a silent mode. The
+// drivers keep reading their own profile key (`silent_mode` for slcan,
+// `listen_only` elsewhere) with their own defaults; a session source can
+// override it through `SourceConfig::listen_only`, which is resolved and
+// checked when the session is created and written back into the profile the
+// reader is started with.
+//
+// Listen-only means the adapter never touches the bus: no transmit, no ACK,
+// no error frames. Where that can't be guaranteed, asking for it fails the
+// session instead of quietly capturing in active mode:
+// - SocketCAN needs a bitrate, so WireTAP configures the interface itself
+//   (`listen-only on`) rather than trusting however it was brought up.
+// - GVRET's bus setup command only reaches buses 0 and 1.
+// - J2534 adapters always acknowledge frames, and serial or network sources
+//   have no bus of their own to stay off.
+
+use crate::io::gvret::BusMapping;
+use crate::settings::IOProfile;
+
+/// GVRET's SETUP_CANBUS command configures this many buses.
+pub const GVRET_CONFIGURABLE_BUSES: u8 = 2;
+
+/// Profile key holding a kind's listen-only setting, for kinds with a
+/// hardware silent mode.
+fn profile_key(kind: &str) -> Option<&'static str> {
+    match kind {
+        "slcan" => Some("silent_mode"),
+        "gs_usb" | "socketcan" | "gvret_tcp" | "gvret-tcp" | "gvret_usb" | "gvret-usb" | "pcan" | "kvaser"
+        | "vector_xl" => Some("listen_only"),
+        _ => None,
+    }
+}
+
+/// What the driver does when the profile doesn't say.
+fn driver_default(kind: &str) -> bool {
+    matches!(kind, "slcan" | "gs_usb" | "pcan" | "kvaser" | "vector_xl")
+}
+
+/// The profile's own listen-only setting (None for kinds without one).
+pub fn from_profile(profile: &IOProfile) -> Option<bool> {
+    let key = profile_key(&profile.kind)?;
+    Some(
+        profile
+            .connection
+            .get(key)
+            .and_then(|v| v.as_bool())
+            .unwrap_or_else(|| driver_default(&profile.kind)),
+    )
+}
+
+/// Effective listen-only setting of a source: `requested` when given, the
+/// profile's otherwise. Errors when listen-only is asked for but the source
+/// can't guarantee it.
+pub fn resolve(profile: &IOProfile, requested: Option<bool>, bus_mappings: &[BusMapping]) -> Result<Option<bool>, String> {
+    let Some(listen_only) = requested.or_else(|| from_profile(profile)) else {
+        return Ok(None);
+    };
+    if !listen_only {
+        return Ok(Some(false));
+    }
+    if profile_key(&profile.kind).is_none() {
+        return Err(format!(
+            "Profile '{}' ({}) has no listen-only mode; a capture-only session can't guarantee it stays off the bus",
+            profile.name, profile.kind
+        ));
+    }
+    match profile.kind.as_str() {
+        "socketcan" => {
+            // Read as the SocketCAN reader reads it
+            let has_bitrate = profile
+                .connection
+                .get("bitrate")
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.parse::<u32>().ok())
+                .is_some();
+            if !has_bitrate {
+                return Err(format!(
+                    "Listen-only on SocketCAN profile '{}' needs a bitrate, so WireTAP can configure the interface in listen-only mode",
+                    profile.name
+                ));
+            }
+        }
+        "gvret_tcp" | "gvret-tcp" | "gvret_usb" | "gvret-usb" => {
+            if let Some(m) = bus_mappings.iter().find(|m| m.enabled && m.device_bus >= GVRET_CONFIGURABLE_BUSES) {
+                return Err(format!(
+                    "GVRET profile '{}' can only be made listen-only on buses 0 and 1; disable bus {}",
+                    profile.name, m.device_bus
+                ));
+            }
+        }
+        _ => {}
+    }
+    Ok(Some(true))
+}
+
+/// Write a resolved setting into the profile a reader is started with.
+pub fn apply(profile: &mut IOProfile, listen_only: bool) {
+    if let Some(key) = profile_key(&profile.kind) {
+        profile.connection.insert(key.to_string(), serde_json::Value::Bool(listen_only));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(kind: &str, connection: serde_json::Value) -> IOProfile {
+        serde_json::from_value(serde_json::json!({
+            "id": "p1",
+            "name": "Bench",
+            "kind": kind,
+            "connection": connection,
+        }))
+        .unwrap()
+    }
+
+    fn bus(device_bus: u8) -> BusMapping {
+        BusMapping { device_bus, output_bus: device_bus, ..BusMapping::default() }
+    }
+
+    #[test]
+    fn override_wins_over_the_profile_and_its_key() {
+        let slcan = profile("slcan", serde_json::json!({ "silent_mode": false }));
+        assert_eq!(resolve(&slcan, None, &[]), Ok(Some(false)));
+        assert_eq!(resolve(&slcan, Some(true), &[]), Ok(Some(true)));
+        // gs_usb opens listen-only unless told otherwise
+        assert_eq!(from_profile(&profile("gs_usb", serde_json::json!({}))), Some(true));
+
+        let mut applied = slcan.clone();
+        apply(&mut applied, true);
+        assert_eq!(applied.connection.get("silent_mode"), Some(&serde_json::Value::Bool(true)));
+    }
+
+    #[test]
+    fn listen_only_is_refused_where_it_cant_be_guaranteed() {
+        let socketcan = profile("socketcan", serde_json::json!({ "interface": "can0" }));
+        assert!(resolve(&socketcan, Some(true), &[]).is_err());
+        let configured = profile("socketcan", serde_json::json!({ "interface": "can0", "bitrate": "500000" }));
+        assert_eq!(resolve(&configured, Some(true), &[]), Ok(Some(true)));
+
+        let gvret = profile("gvret_tcp", serde_json::json!({}));
+        assert_eq!(resolve(&gvret, Some(true), &[bus(0), bus(1)]), Ok(Some(true)));
+        assert!(resolve(&gvret, Some(true), &[bus(0), bus(2)]).unwrap_err().contains("bus 2"));
+
+        let j2534 = profile("j2534", serde_json::json!({ "listen_only": true }));
+        assert_eq!(resolve(&j2534, None, &[]), Ok(None));
+        assert!(resolve(&j2534, Some(true), &[]).is_err());
+    }
+}
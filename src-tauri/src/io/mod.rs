@@ -9,6 +9,11 @@ pub mod codec; // Frame codec trait and implementations
 mod error;
 pub(crate) mod periodic; // Shared cadence primitive for interval-driven loops
 mod signal_throttle;
+pub mod batching; // Merge-task batch size and flush interval
+pub mod downsample; // Per-listener delivery rate limits
+pub(crate) mod hw_clock; // Device timestamp unwrapping and epoch alignment
+pub(crate) mod can_error; // CAN error frame classification
+pub(crate) mod listen_only; // Uniform listen-only mode across CAN adapters
 pub use signal_throttle::SignalThrottle;
 pub mod post_session;
 pub mod traits; // InterfaceTraits validation
@@ -19,24 +24,40 @@ mod recorded;
 
 // Real-time drivers
 pub mod gs_usb; // pub for Tauri command access
+pub mod pcan; // PCAN-USB (PEAK); pub for Tauri command access
+pub mod kvaser; // Kvaser CANlib devices; pub for Tauri command access
+pub mod vector_xl; // Vector XL Driver Library interfaces; pub for Tauri command access
+pub mod j2534; // SAE J2534 PassThru adapters; pub for Tauri command access
 pub mod gvret; // GVRET TCP/USB driver
+pub(crate) mod canserver; // CANserver / panda UDP streaming
+pub(crate) mod websocket; // WebSocket streaming (gateways, cloud loggers)
+pub(crate) mod udp_can; // UDP listener for WiFi CAN bridges
+pub(crate) mod doip; // DoIP (ISO 13400) diagnostic gateway client
+pub(crate) mod lin; // LIN over UART adapters, plus LDF-lite descriptions
+pub mod netbridge; // WireTAP-to-WireTAP network bridge; pub for Tauri command access
 pub mod modbus_tcp; // pub for scanner command access
 pub mod modbus_rtu; // Modbus RTU master over serial
-mod mqtt;
+pub mod mqtt; // pub for Tauri command access (embedded broker)
 mod broker;
 mod virtual_device;
 #[cfg(not(target_os = "ios"))]
 pub mod serial; // pub for Tauri command access (list_serial_ports)
 #[cfg(not(target_os = "ios"))]
 pub mod slcan; // pub for slcan transmit_frame access
+#[cfg(not(target_os = "ios"))]
+pub(crate) mod custom_serial; // User-described binary CAN-over-serial formats
+#[cfg(not(target_os = "ios"))]
+pub(crate) mod elm327; // ELM327 / OBD-II serial dongles
 pub mod framelink;
 mod socketcan;
 
 // Re-export recorded sources
-pub use recorded::{step_frame, CaptureSource, StepResult};
+pub use recorded::{
+    parse_blf_file, parse_candump_file, parse_pcap_file, parse_trc_file, step_frame, AscTimeBase, AscWriter, BlfWriter, CandumpWriter, ParquetWriter, PcapWriter, CaptureSource, Mdf4Reader, MultiCaptureSource, StepResult,
+};
 pub use recorded::{
     parse_csv_file, parse_csv_with_mapping, preview_csv_file, CsvColumnMapping, CsvPreview,
-    Delimiter, SequenceGap, TimestampUnit,
+    CsvWriter, Delimiter, SequenceGap, TimestampUnit,
 };
 pub use recorded::{PostgresConfig, PostgresSource, PostgresSourceOptions, PostgresSourceType};
 pub use recorded::{BackendApiConfig, BackendApiSource, BackendApiSourceOptions};
@@ -49,26 +70,34 @@ pub use gvret::GvretCodec;
 #[cfg(not(target_os = "ios"))]
 #[allow(unused_imports)]
 pub use codec::SlcanCodec;
+#[cfg(not(target_os = "ios"))]
+#[allow(unused_imports)]
+pub use codec::{CustomFrameFormat, CustomSerialCodec};
 #[cfg(any(target_os = "windows", target_os = "macos"))]
 #[allow(unused_imports)]
 pub use codec::GsUsbCodec;
 #[cfg(target_os = "linux")]
 #[allow(unused_imports)]
 pub use codec::{SocketCanCodec, SocketCanEncodedFrame};
+#[cfg(target_os = "linux")]
+pub use socketcan::bcm::BcmRepeat;
 
 // Re-export driver types
 #[cfg(any(target_os = "windows", target_os = "macos"))]
 #[allow(unused_imports)]
 pub use gs_usb::GsUsbConfig;
 pub use gvret::{BusMapping, GvretDeviceInfo, probe_gvret_tcp};
+pub use gvret::{configure_gvret_tcp, GvretBusChange, GvretBusParams, GVRET_MAX_BITRATE, GVRET_MIN_BITRATE};
+pub use gvret::{discover_gvret_tcp, GvretDiscoveryResult};
 pub use modbus_tcp::{
-    build_polls_from_catalog, ModbusTcpConfig, ModbusTcpSource, PollGroup,
+    build_polls_from_catalog, ModbusTcpConfig, ModbusTcpSource, ModbusWrite, PollGroup,
     ModbusScanConfig, ScanCompletePayload, UnitIdScanConfig,
 };
 #[cfg(not(target_os = "ios"))]
-pub use gvret::probe_gvret_usb;
-pub use broker::{ModbusRole, IOBroker, SourceConfig};
-pub use mqtt::{MqttConfig, MqttSource};
+pub use gvret::{configure_gvret_usb, probe_gvret_usb};
+pub use broker::{ModbusRole, IOBroker, ReconnectPolicy, SourceConfig};
+pub(crate) use broker::run_headless_source;
+pub use mqtt::{MqttConfig, MqttSource, MqttTlsConfig};
 pub use virtual_device::{VirtualDeviceConfig, VirtualSource, VirtualInterfaceConfig, VirtualTrafficType};
 #[cfg(not(target_os = "ios"))]
 #[allow(unused_imports)]
@@ -100,10 +129,11 @@ use crate::{capture_store, sessions};
 // ============================================================================
 
 /// Parsed frame message - the main data structure emitted by all readers
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 pub struct FrameMessage {
     pub protocol: String, // e.g., "can", "modbus", "serial"
-    /// Host UNIX timestamp in microseconds.
+    /// UNIX timestamp in microseconds (host clock unless `clock` says otherwise).
     pub timestamp_us: u64,
     pub frame_id: u32,
     pub bus: u8,
@@ -121,10 +151,199 @@ pub struct FrameMessage {
     /// Direction: "rx" for received, "tx" for transmitted
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub direction: Option<String>,
+    /// Relationship to other frames (reassembly segment, UDS request/response, transmit echo)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub link: Option<FrameLink>,
+    /// Clock `timestamp_us` was taken from, where the driver records it
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub clock: Option<FrameClock>,
+    /// CAN error frame. `frame_id` is then the SocketCAN error class bits with
+    /// CAN_ERR_FLAG set (outside any 29-bit ID) and `bytes` the error details.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub is_error: Option<bool>,
+    /// Main class of an error frame
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error_class: Option<CanErrorClass>,
+    /// CAN FD bit rate switch: the data phase ran at the data bitrate. Set
+    /// on FD frames by drivers that report it.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub brs: Option<bool>,
+}
+
+/// Classic CAN frame on bus 0 for unit tests. Set anything else with struct
+/// update syntax: `FrameMessage { bus: 1, ..test_frame(0x100, 0, &[]) }`.
+#[cfg(test)]
+pub(crate) fn test_frame(frame_id: u32, timestamp_us: u64, bytes: &[u8]) -> FrameMessage {
+    FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us,
+        frame_id,
+        dlc: bytes.len() as u8,
+        bytes: bytes.to_vec(),
+        ..Default::default()
+    }
+}
+
+/// Role a frame plays within a group of related frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum LinkRole {
+    /// Constituent frame of a multi-frame message
+    Segment,
+    /// Synthetic frame produced by reassembly
+    Message,
+    /// Diagnostic request (and the tester's own flow control/continuation)
+    Request,
+    /// Diagnostic response to a correlated request
+    Response,
+    /// Device echo of a frame WireTAP transmitted
+    Echo,
+}
+
+impl LinkRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkRole::Segment => "segment",
+            LinkRole::Message => "message",
+            LinkRole::Request => "request",
+            LinkRole::Response => "response",
+            LinkRole::Echo => "echo",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "segment" => Some(LinkRole::Segment),
+            "message" => Some(LinkRole::Message),
+            "request" => Some(LinkRole::Request),
+            "response" => Some(LinkRole::Response),
+            "echo" => Some(LinkRole::Echo),
+            _ => None,
+        }
+    }
+}
+
+/// Linkage metadata: frames sharing a `correlation_id` belong together.
+/// `parent_rowid` is filled in by the capture database on read — it is the
+/// rowid of the first frame in the capture carrying the same correlation ID
+/// (None for that first frame itself).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+pub struct FrameLink {
+    pub correlation_id: u64,
+    pub role: LinkRole,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub parent_rowid: Option<i64>,
+}
+
+/// Clock a frame's timestamp came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum FrameClock {
+    /// Host time when the frame was received (kernel or reader)
+    Host,
+    /// The device's own timestamp, aligned to the UNIX epoch
+    Hardware,
+}
+
+/// Main class of a CAN error frame. A frame reporting several conditions
+/// takes the most severe, in the order listed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum CanErrorClass {
+    /// Controller went bus-off
+    BusOff,
+    /// Transmitted frame was not acknowledged
+    Ack,
+    /// Bit stuffing violation
+    Stuff,
+    /// Fixed-form field violation
+    Form,
+    /// Bit error (sent one level, read back the other)
+    Bit,
+    /// CRC mismatch
+    Crc,
+    /// Controller entered error-passive
+    ErrorPassive,
+    /// Error counters passed the warning level
+    ErrorWarning,
+    /// Controller RX or TX buffer overflow
+    Overflow,
+    /// Arbitration lost
+    ArbitrationLost,
+    /// Transmit timed out
+    TxTimeout,
+    /// Transceiver fault
+    Transceiver,
+    /// Controller restarted after bus-off
+    Restarted,
+    Other,
+}
+
+impl CanErrorClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CanErrorClass::BusOff => "bus_off",
+            CanErrorClass::Ack => "ack",
+            CanErrorClass::Stuff => "stuff",
+            CanErrorClass::Form => "form",
+            CanErrorClass::Bit => "bit",
+            CanErrorClass::Crc => "crc",
+            CanErrorClass::ErrorPassive => "error_passive",
+            CanErrorClass::ErrorWarning => "error_warning",
+            CanErrorClass::Overflow => "overflow",
+            CanErrorClass::ArbitrationLost => "arbitration_lost",
+            CanErrorClass::TxTimeout => "tx_timeout",
+            CanErrorClass::Transceiver => "transceiver",
+            CanErrorClass::Restarted => "restarted",
+            CanErrorClass::Other => "other",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "bus_off" => Some(CanErrorClass::BusOff),
+            "ack" => Some(CanErrorClass::Ack),
+            "stuff" => Some(CanErrorClass::Stuff),
+            "form" => Some(CanErrorClass::Form),
+            "bit" => Some(CanErrorClass::Bit),
+            "crc" => Some(CanErrorClass::Crc),
+            "error_passive" => Some(CanErrorClass::ErrorPassive),
+            "error_warning" => Some(CanErrorClass::ErrorWarning),
+            "overflow" => Some(CanErrorClass::Overflow),
+            "arbitration_lost" => Some(CanErrorClass::ArbitrationLost),
+            "tx_timeout" => Some(CanErrorClass::TxTimeout),
+            "transceiver" => Some(CanErrorClass::Transceiver),
+            "restarted" => Some(CanErrorClass::Restarted),
+            "other" => Some(CanErrorClass::Other),
+            _ => None,
+        }
+    }
+}
+
+impl FrameClock {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FrameClock::Host => "host",
+            FrameClock::Hardware => "hardware",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "host" => Some(FrameClock::Host),
+            "hardware" => Some(FrameClock::Hardware),
+            _ => None,
+        }
+    }
 }
 
 /// Playback position - stored and signalled via playback-position events during capture streaming
 #[derive(Clone, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 pub struct PlaybackPosition {
     /// Current timestamp in microseconds
     pub timestamp_us: i64,
@@ -179,6 +398,10 @@ pub struct TransmitResult {
     pub timestamp_us: u64,
     /// Error message if transmission failed
     pub error: Option<String>,
+    /// Catalog validation issues (see transmit_validation.rs). In warn mode
+    /// the frame was still sent; in reject mode these are why it wasn't.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<crate::transmit_validation::ValidationIssue>,
 }
 
 impl TransmitResult {
@@ -188,6 +411,7 @@ impl TransmitResult {
             success: true,
             timestamp_us: now_us(),
             error: None,
+            issues: Vec::new(),
         }
     }
 
@@ -199,6 +423,7 @@ impl TransmitResult {
             success: true,
             timestamp_us: now_us(),
             error: None,
+            issues: Vec::new(),
         }
     }
 
@@ -208,6 +433,7 @@ impl TransmitResult {
             success: false,
             timestamp_us: now_us(),
             error: Some(message),
+            issues: Vec::new(),
         }
     }
 }
@@ -219,6 +445,10 @@ pub enum TransmitPayload {
     CanFrame(CanTransmitFrame),
     /// Transmit raw bytes (serial, SPI, etc.)
     RawBytes(Vec<u8>),
+    /// Write coils or registers on a Modbus device
+    ModbusWrite(modbus_tcp::ModbusWrite),
+    /// Send a UDS request through a DoIP gateway
+    Diagnostic(doip::DiagnosticRequest),
 }
 
 // ============================================================================
@@ -227,6 +457,7 @@ pub enum TransmitPayload {
 
 /// Temporal mode of an interface/session
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 #[serde(rename_all = "lowercase")]
 pub enum TemporalMode {
     /// Real-time streaming from live devices (GVRET, slcan, gs_usb, SocketCAN, MQTT)
@@ -239,6 +470,7 @@ pub enum TemporalMode {
 
 /// Protocol family for frame-based communication
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 #[serde(rename_all = "lowercase")]
 pub enum Protocol {
     /// CAN 2.0A/2.0B (standard/extended)
@@ -252,8 +484,38 @@ pub enum Protocol {
     Serial,
 }
 
+impl Protocol {
+    /// Protocol family of a frame, from its `protocol` tag. CAN frames with
+    /// `is_fd` count as CAN FD; anything not CAN or Modbus is framed serial.
+    pub fn of_frame(frame: &FrameMessage) -> Protocol {
+        Protocol::from_tag(&frame.protocol, frame.is_fd)
+    }
+
+    /// Protocol family from a stored `protocol` tag and FD flag.
+    pub fn from_tag(tag: &str, is_fd: bool) -> Protocol {
+        match tag {
+            "canfd" => Protocol::CanFd,
+            "can" if is_fd => Protocol::CanFd,
+            "can" => Protocol::Can,
+            "modbus" => Protocol::Modbus,
+            _ => Protocol::Serial,
+        }
+    }
+}
+
+/// One protocol's sub-stream within a mixed-protocol session: which output
+/// buses carry frames of that protocol.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+pub struct ProtocolStream {
+    pub protocol: Protocol,
+    /// Output bus numbers (sorted)
+    pub buses: Vec<u8>,
+}
+
 /// Combined interface traits for formal session/interface characterization
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 pub struct InterfaceTraits {
     /// Temporal mode of the interface
     pub temporal_mode: TemporalMode,
@@ -273,15 +535,22 @@ pub struct InterfaceTraits {
 /// declaration of what a session will emit. Used by the frontend to decide
 /// which event listeners and views to set up.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 pub struct SessionDataStreams {
     /// Whether this session emits framed messages (`frame-message` events)
     pub rx_frames: bool,
     /// Whether this session emits raw byte streams (`bytes-ready` signal)
     pub rx_bytes: bool,
+    /// Per-protocol frame sub-streams for multi-source sessions mixing
+    /// protocols (e.g. CAN plus RS-485 body modules). Empty when every frame
+    /// shares `traits.protocols`.
+    #[serde(default)]
+    pub sub_streams: Vec<ProtocolStream>,
 }
 
 /// IO device capabilities - what this device type supports
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 pub struct IOCapabilities {
     /// Supports pause/resume (PostgreSQL: true, GVRET: false)
     pub can_pause: bool,
@@ -339,6 +608,7 @@ impl IOCapabilities {
             data_streams: SessionDataStreams {
                 rx_frames: true,
                 rx_bytes: false,
+                sub_streams: Vec::new(),
             },
         }
     }
@@ -369,6 +639,7 @@ impl IOCapabilities {
             data_streams: SessionDataStreams {
                 rx_frames: true,
                 rx_bytes: false,
+                sub_streams: Vec::new(),
             },
         }
     }
@@ -421,6 +692,7 @@ impl IOCapabilities {
         self.data_streams = SessionDataStreams {
             rx_frames,
             rx_bytes,
+            sub_streams: Vec::new(),
         };
         self
     }
@@ -428,6 +700,7 @@ impl IOCapabilities {
 
 /// Current state of an IO session
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 #[serde(tag = "type", content = "message")]
 pub enum IOState {
     Stopped,
@@ -520,6 +793,13 @@ pub trait IOSource: Send + Sync {
         Err("This session does not support changing framing".to_string())
     }
 
+    /// SocketCAN interface that CAN frames for `bus` go out on, when the
+    /// kernel can schedule their repeats (broadcast manager).
+    /// Default implementation returns None.
+    fn socketcan_transmit_interface(&self, _bus: u8) -> Option<String> {
+        None
+    }
+
     /// Get current state
     fn state(&self) -> IOState;
 
@@ -666,6 +946,7 @@ fn state_to_string(state: &IOState) -> String {
 /// Emit a state change event for a session
 fn emit_state_change(session_id: &str, _previous: &IOState, current: &IOState) {
     crate::ws::dispatch::send_session_state(session_id, current);
+    crate::menu_state::session_changed(session_id);
 }
 
 /// Emit a joiner count change event for a session.
@@ -678,6 +959,7 @@ fn emit_joiner_count_change(
     _change: Option<&str>,
 ) {
     crate::ws::dispatch::send_session_info(session_id, -1.0, joiner_count as u16);
+    crate::menu_state::session_changed(session_id);
 }
 
 /// Emit a speed change event for a session.
@@ -726,6 +1008,8 @@ pub struct AppInstance {
     pub last_heartbeat: std::time::Instant,
     /// Whether actively receiving frames (false when detached / paused).
     pub is_active: bool,
+    /// Delivery limit per frame ID (None = every frame). See `downsample`.
+    pub max_frames_per_second: Option<u32>,
 }
 
 /// Serializable snapshot of an app instance for the frontend roster.
@@ -799,6 +1083,50 @@ pub fn current_session_of_app(instance_id: &str) -> Option<String> {
     reg.get(instance_id).and_then(|a| a.session_id.clone())
 }
 
+/// Delivery rate limit for a session's frame stream: the least restrictive
+/// limit among its attached listeners, or None if any listener has none.
+pub fn session_rate_limit(session_id: &str) -> Option<u32> {
+    let reg = APP_REGISTRY.lock().ok()?;
+    let mut limit: Option<u32> = None;
+    for a in reg.values().filter(|a| a.session_id.as_deref() == Some(session_id)) {
+        let fps = a.max_frames_per_second?;
+        limit = Some(limit.map_or(fps, |l| l.max(fps)));
+    }
+    limit
+}
+
+/// Set a listener's delivery rate limit. No-op if the instance is unknown.
+pub fn set_app_rate_limit(instance_id: &str, max_frames_per_second: Option<u32>) {
+    update_app(instance_id, |a| a.max_frames_per_second = max_frames_per_second.filter(|&fps| fps > 0));
+}
+
+/// Collect the distinct window labels of `apps`. None if any of them has no known
+/// window (attached without a prior register_app), so the caller must broadcast.
+fn window_labels<'a>(apps: impl Iterator<Item = &'a AppInstance>) -> Option<Vec<String>> {
+    let mut labels: Vec<String> = Vec::new();
+    for a in apps {
+        if a.window_label.is_empty() || a.window_label == "unknown" {
+            return None;
+        }
+        if !labels.contains(&a.window_label) {
+            labels.push(a.window_label.clone());
+        }
+    }
+    Some(labels)
+}
+
+/// Windows hosting the apps attached to `session_id` (see [`window_labels`]).
+pub fn windows_for_session(session_id: &str) -> Option<Vec<String>> {
+    let reg = APP_REGISTRY.lock().ok()?;
+    window_labels(reg.values().filter(|a| a.session_id.as_deref() == Some(session_id)))
+}
+
+/// Window hosting an app instance (None if unknown).
+pub fn window_of_app(instance_id: &str) -> Option<String> {
+    let reg = APP_REGISTRY.lock().ok()?;
+    window_labels(reg.get(instance_id).into_iter()).and_then(|l| l.into_iter().next())
+}
+
 /// Register an open app instance (panel mount). Idempotent: if it already exists,
 /// refresh app_name/window_label/heartbeat but preserve its session attachment and
 /// its display_id (the first one wins, so re-registers don't churn the label).
@@ -821,6 +1149,7 @@ pub fn register_app(instance_id: &str, display_id: &str, app_name: &str, window_
                 registered_at: now,
                 last_heartbeat: now,
                 is_active: false,
+                max_frames_per_second: None,
             });
     }
     emit_open_apps_changed();
@@ -853,6 +1182,7 @@ pub fn attach_app(instance_id: &str, app_name: &str, session_id: &str) {
                 registered_at: now,
                 last_heartbeat: now,
                 is_active: true,
+                max_frames_per_second: None,
             });
     }
     emit_open_apps_changed();
@@ -953,6 +1283,62 @@ pub fn emit_open_apps_changed() {
     crate::ws::dispatch::send_open_apps_changed(&roster);
 }
 
+/// Tell every window a ring capture dropped frames from its head, so views
+/// paginating it can shift their offsets.
+pub fn emit_capture_trimmed(payload: &crate::capture_store::CaptureTrimmedPayload) {
+    if let Some(app) = APP_HANDLE.get() {
+        let _ = app.emit("capture-trimmed", payload);
+    }
+}
+
+/// A full segment of an auto-split recording was orphaned (capture_rotation.rs).
+pub fn emit_buffer_orphaned(payload: &crate::capture_rotation::BufferOrphanedPayload) {
+    if let Some(app) = APP_HANDLE.get() {
+        let _ = app.emit("buffer-orphaned", payload);
+    }
+}
+
+/// An auto-split recording moved on to a fresh capture.
+pub fn emit_buffer_created(payload: &crate::capture_rotation::BufferCreatedPayload) {
+    if let Some(app) = APP_HANDLE.get() {
+        let _ = app.emit("buffer-created", payload);
+    }
+}
+
+/// Periodic throughput and bus-load figures for a multi-source session, so
+/// dashboards can show bus health without counting frames themselves.
+pub fn emit_session_metrics(payload: &broker::SessionMetricsPayload) {
+    if let Some(app) = APP_HANDLE.get() {
+        let _ = app.emit("session-metrics", payload);
+    }
+}
+
+/// Payload of the `device-disconnected` event.
+#[derive(Clone, Debug, Serialize)]
+pub struct DeviceDisconnectedPayload {
+    pub session_id: String,
+    pub profile_id: String,
+    pub reason: String,
+    /// Reconnect attempt about to be made (1-based)
+    pub attempt: u32,
+    pub retry_in_ms: u64,
+}
+
+/// A source dropped and its reconnect policy will restart it. A matching
+/// device-connected follows when it comes back.
+pub fn emit_device_disconnected(payload: &DeviceDisconnectedPayload) {
+    if let Some(app) = APP_HANDLE.get() {
+        emit_to_session(app, &payload.session_id, "device-disconnected", payload.clone());
+    }
+}
+
+/// A snapshot trigger fired and its window was copied into a new capture.
+pub fn emit_trigger_fired(payload: &crate::trigger_snapshot::TriggerFiredPayload) {
+    if let Some(app) = APP_HANDLE.get() {
+        emit_to_session(app, &payload.session_id, "trigger-fired", payload.clone());
+    }
+}
+
 /// Playback position cache — updated during capture/recorded streaming, polled by frontend
 static PLAYBACK_POSITIONS: Lazy<RwLock<HashMap<String, PlaybackPosition>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
@@ -1360,6 +1746,7 @@ fn clear_session_closing(session_id: &str) {
 
 /// Payload for global session lifecycle events (emitted to all windows)
 #[derive(Clone, Debug, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 pub struct SessionLifecyclePayload {
     /// The session ID
     pub session_id: String,
@@ -1381,6 +1768,29 @@ pub struct SessionLifecyclePayload {
     pub reset: bool,
 }
 
+/// Emit a Tauri event to the given windows only, or to every window when `labels`
+/// is None (listeners couldn't be resolved). MUST be called WITHOUT holding APP_REGISTRY.
+pub fn emit_to_windows<S: Serialize + Clone>(app: &AppHandle, labels: Option<Vec<String>>, event: &str, payload: S) {
+    match labels {
+        Some(labels) => {
+            for label in labels {
+                let _ = app.emit_to(label.as_str(), event, payload.clone());
+            }
+        }
+        None => {
+            let _ = app.emit(event, payload);
+        }
+    }
+}
+
+/// Emit a session-scoped Tauri event to the windows hosting that session's apps
+/// rather than broadcasting it, so windows running unrelated sessions don't wake
+/// up to filter it out. Streaming signals don't need this — they go over the WS
+/// session channel, which is already per-subscription.
+pub fn emit_to_session<S: Serialize + Clone>(app: &AppHandle, session_id: &str, event: &str, payload: S) {
+    emit_to_windows(app, windows_for_session(session_id), event, payload);
+}
+
 /// Emit a global session lifecycle event to all windows.
 /// This event is NOT scoped to a session ID - it broadcasts to all windows.
 pub fn emit_session_lifecycle(app: &AppHandle, payload: SessionLifecyclePayload) {
@@ -1390,6 +1800,23 @@ pub fn emit_session_lifecycle(app: &AppHandle, payload: SessionLifecyclePayload)
     );
     let _ = app.emit("session-lifecycle", &payload);
     crate::ws::dispatch::send_session_lifecycle(&payload);
+
+    let event = match payload.event_type.as_str() {
+        "created" => Some(crate::webhooks::WebhookEvent::SessionCreated),
+        "destroyed" => Some(crate::webhooks::WebhookEvent::SessionDestroyed),
+        _ => None,
+    };
+    if let Some(event) = event {
+        crate::webhooks::fire(
+            event,
+            Some(&payload.session_id),
+            serde_json::json!({
+                "source_type": payload.source_type,
+                "source_profile_ids": payload.source_profile_ids,
+                "reset": payload.reset,
+            }),
+        );
+    }
 }
 
 /// Emit a session error signal and store for later retrieval.
@@ -1472,6 +1899,17 @@ pub fn emit_stream_ended(
     post_session::store_stream_ended(session_id, stream_ended_info.clone());
 
     crate::ws::dispatch::send_stream_ended(session_id, &stream_ended_info);
+    crate::webhooks::fire(
+        crate::webhooks::WebhookEvent::StreamEnded,
+        Some(session_id),
+        serde_json::json!({
+            "reason": reason,
+            "capture_id": capture_id,
+            "capture_kind": capture_kind,
+            "count": count,
+            "time_range": time_range,
+        }),
+    );
     tlog!(
         "[{}:{}] Stream ended (reason: {}, count: {})",
         log_prefix, session_id, reason, count
@@ -1666,6 +2104,21 @@ pub async fn get_session_joiner_count(session_id: &str) -> usize {
     }
 }
 
+/// Display names of a session's sources (empty if the session doesn't exist
+/// or isn't multi-source).
+pub async fn get_session_source_names(session_id: &str) -> Vec<String> {
+    let sessions = IO_SESSIONS.lock().await;
+    sessions
+        .get(session_id)
+        .map(|s| s.source_names.clone())
+        .unwrap_or_default()
+}
+
+/// Number of live sessions.
+pub async fn session_count() -> usize {
+    IO_SESSIONS.lock().await.len()
+}
+
 /// Get the number of source configs in a multi-source session.
 /// Returns 0 if the session doesn't exist or isn't a multi-source session.
 pub async fn get_session_source_count(session_id: &str) -> usize {
@@ -2014,7 +2467,7 @@ pub async fn start_session(session_id: &str) -> Result<IOState, String> {
         .get_mut(session_id)
         .ok_or_else(|| {
             tlog!("[reader] start_session('{}') - session not found!", session_id);
-            format!("Session '{}' not found", session_id)
+            msg!(SESSION_NOT_FOUND, session_id = session_id)
         })?;
 
     let previous = session.source.state();
@@ -2044,7 +2497,7 @@ pub async fn stop_session(session_id: &str) -> Result<IOState, String> {
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     let previous = session.source.state();
 
@@ -2071,7 +2524,7 @@ pub async fn suspend_session(session_id: &str) -> Result<IOState, String> {
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     let previous = session.source.state();
 
@@ -2118,7 +2571,7 @@ pub async fn replace_session_source(
 ) -> Result<SourceReplacedPayload, String> {
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     // 1. Stop old device (idempotent)
     let previous_state = session.source.state();
@@ -2197,7 +2650,7 @@ pub async fn stop_and_switch_to_capture(app: &AppHandle, session_id: &str, speed
     {
         let session = sessions
             .get_mut(session_id)
-            .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+            .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
         if !matches!(session.source.state(), IOState::Stopped) {
             session.source.stop().await?;
         }
@@ -2269,7 +2722,7 @@ pub async fn resume_session_fresh(session_id: &str) -> Result<IOState, String> {
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     let previous = session.source.state();
 
@@ -2308,7 +2761,7 @@ pub async fn pause_session(session_id: &str) -> Result<IOState, String> {
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     let previous = session.source.state();
 
@@ -2333,7 +2786,7 @@ pub async fn resume_session(session_id: &str) -> Result<IOState, String> {
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     let previous = session.source.state();
 
@@ -2357,7 +2810,7 @@ pub async fn set_session_traffic_enabled(session_id: &str, enabled: bool) -> Res
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.set_traffic_enabled(enabled)
 }
@@ -2367,7 +2820,7 @@ pub async fn set_session_bus_traffic_enabled(session_id: &str, bus: u8, enabled:
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.set_bus_traffic_enabled(bus, enabled)
 }
@@ -2377,7 +2830,7 @@ pub async fn set_session_bus_cadence(session_id: &str, bus: u8, frame_rate_hz: f
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.set_bus_cadence(bus, frame_rate_hz)
 }
@@ -2387,7 +2840,7 @@ pub async fn get_session_virtual_bus_states(session_id: &str) -> Result<Vec<Virt
     let sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.virtual_bus_states()
 }
@@ -2397,7 +2850,7 @@ pub async fn add_session_virtual_bus(session_id: &str, bus: u8, traffic_type: St
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.add_virtual_bus(bus, traffic_type, frame_rate_hz)
 }
@@ -2407,7 +2860,7 @@ pub async fn remove_session_virtual_bus(session_id: &str, bus: u8) -> Result<(),
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.remove_virtual_bus(bus)
 }
@@ -2417,7 +2870,7 @@ pub async fn update_session_speed(session_id: &str, speed: f64) -> Result<(), St
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.set_speed(speed)?;
 
@@ -2442,7 +2895,7 @@ pub async fn update_session_time_range(
 
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions.get_mut(session_id).ok_or_else(|| {
-        let err = format!("Session '{}' not found", session_id);
+        let err = msg!(SESSION_NOT_FOUND, session_id = session_id);
         tlog!("[io] update_session_time_range: {}", err);
         err
     })?;
@@ -2470,7 +2923,7 @@ pub async fn reconfigure_session(
 
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions.get_mut(session_id).ok_or_else(|| {
-        let err = format!("Session '{}' not found", session_id);
+        let err = msg!(SESSION_NOT_FOUND, session_id = session_id);
         tlog!("[io] reconfigure_session: {}", err);
         err
     })?;
@@ -2505,7 +2958,7 @@ pub async fn seek_session(session_id: &str, timestamp_us: i64) -> Result<(), Str
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.seek(timestamp_us)
 }
@@ -2515,7 +2968,7 @@ pub async fn seek_session_by_frame(session_id: &str, frame_index: i64) -> Result
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.seek_by_frame(frame_index)
 }
@@ -2525,7 +2978,7 @@ pub async fn update_session_direction(session_id: &str, reverse: bool) -> Result
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.set_direction(reverse)
 }
@@ -2625,6 +3078,37 @@ pub async fn resume_to_live_session(
     Ok(result.capabilities)
 }
 
+/// Drop a destroyed session's state in every per-session module (triggers,
+/// responders, decoders, taps, ...). Shared by both teardown paths so a new
+/// module only has to be added here.
+fn clear_session_state(session_id: &str) {
+    crate::capture_trigger::clear_conditions(session_id);
+    crate::capture_rotation::clear_session(session_id);
+    crate::responder::clear_session(session_id);
+    crate::obd_poll::stop_obd_poll(session_id);
+    crate::j1939::clear_session(session_id);
+    crate::canopen::clear_session(session_id);
+    crate::io::lin::clear_session(session_id);
+    crate::reactive_transmit::clear_session(session_id);
+    crate::trigger_snapshot::clear_session(session_id);
+    downsample::clear_session(session_id);
+    batching::clear_session(session_id);
+    crate::recording_schedule::clear_session(session_id);
+    netbridge::clear_session(session_id);
+    crate::reassembly::clear_session(session_id);
+    crate::frame_link::clear_session(session_id);
+    crate::transmit_validation::clear_session(session_id);
+    crate::timebase::clear_session(session_id);
+    crate::latest_values::clear_session(session_id);
+    crate::frame_priority::clear_session(session_id);
+    crate::live_analysis::clear_session(session_id);
+    crate::black_box::clear_session(session_id);
+    mqtt::publisher::clear_session(session_id);
+    crate::signal_trends::clear_session(session_id);
+    crate::source_dedup::clear_session(session_id);
+    crate::source_clock::clear_session(session_id);
+}
+
 /// Destroy a reader session. `reset` marks a deliberate user destroy so the
 /// frontend resets to "No source" rather than the orphaned capture.
 pub async fn destroy_session(session_id: &str, reset: bool) -> Result<(), String> {
@@ -2658,6 +3142,7 @@ pub async fn destroy_session(session_id: &str, reset: bool) -> Result<(), String
     // Clear any stored startup error
     clear_startup_error(session_id);
     clear_playback_position(session_id);
+    clear_session_state(session_id);
     // Don't sweep_expired here — the orphaned capture IDs were just stored
     // and need to survive long enough for the frontend to fetch them.
     Ok(())
@@ -2671,6 +3156,7 @@ pub async fn session_exists(session_id: &str) -> bool {
 
 /// Info about an active session (for listing)
 #[derive(Clone, Debug, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 pub struct ActiveSessionInfo {
     /// Session ID
     pub session_id: String,
@@ -2685,6 +3171,7 @@ pub struct ActiveSessionInfo {
     /// Individual subscriber details
     pub subscribers: Vec<SubscriberInfo>,
     /// For multi-source sessions: the source configurations
+    #[cfg_attr(test, ts(type = "Array<Record<string, unknown>> | null"))]
     pub broker_configs: Option<Vec<broker::SourceConfig>>,
     /// Profile IDs feeding this session (populated from SESSION_PROFILES in sessions.rs)
     #[serde(default)]
@@ -2755,7 +3242,7 @@ pub async fn session_transmit(session_id: &str, payload: &TransmitPayload) -> Re
     let sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     let caps = session.source.capabilities();
 
@@ -2767,13 +3254,42 @@ pub async fn session_transmit(session_id: &str, payload: &TransmitPayload) -> Re
         TransmitPayload::RawBytes(_) if !caps.traits.tx_bytes => {
             return Err("This session does not support serial transmission".to_string());
         }
+        TransmitPayload::ModbusWrite(_) if !caps.traits.protocols.contains(&Protocol::Modbus) => {
+            return Err("This session does not support Modbus writes".to_string());
+        }
         _ => {}
     }
 
+    // Catalog validation (only when the session has a policy)
+    let mut issues = Vec::new();
+    if let TransmitPayload::CanFrame(frame) = payload {
+        if let Some(verdict) = crate::transmit_validation::validate(session_id, frame) {
+            if verdict.reject {
+                let summary: Vec<&str> = verdict.issues.iter().map(|i| i.message.as_str()).collect();
+                let mut result = TransmitResult::error(format!(
+                    "Rejected by transmit validation: {}",
+                    summary.join("; ")
+                ));
+                result.issues = verdict.issues;
+                return Ok(result);
+            }
+            issues = verdict.issues;
+        }
+    }
+
     // Call device transmit — fire-and-forget for most devices.
     // Queues the frame into the device's transmit channel and returns
     // immediately. The lock is held only briefly for the channel send.
-    session.source.transmit(payload)
+    let mut result = session.source.transmit(payload)?;
+    if let TransmitPayload::CanFrame(frame) = payload {
+        if result.success {
+            // Remember the frame so its device echo can be linked back to it
+            crate::frame_link::record_transmit(session_id, frame);
+            crate::transmit_validation::record_sent(session_id, frame);
+        }
+    }
+    result.issues = issues;
+    Ok(result)
 }
 
 /// Transmit a CAN frame through a session (convenience wrapper)
@@ -2781,11 +3297,28 @@ pub async fn transmit_frame(session_id: &str, frame: &CanTransmitFrame) -> Resul
     session_transmit(session_id, &TransmitPayload::CanFrame(frame.clone())).await
 }
 
+/// SocketCAN interface a session transmits CAN frames for `bus` on, if that
+/// bus is routed to a connected SocketCAN source.
+pub async fn socketcan_transmit_interface(session_id: &str, bus: u8) -> Option<String> {
+    let sessions = IO_SESSIONS.lock().await;
+    sessions.get(session_id)?.source.socketcan_transmit_interface(bus)
+}
+
 /// Transmit raw serial bytes through a session (convenience wrapper)
 pub async fn transmit_serial(session_id: &str, bytes: &[u8]) -> Result<TransmitResult, String> {
     session_transmit(session_id, &TransmitPayload::RawBytes(bytes.to_vec())).await
 }
 
+/// Write coils or registers through a Modbus session (convenience wrapper)
+pub async fn transmit_modbus_write(session_id: &str, write: &modbus_tcp::ModbusWrite) -> Result<TransmitResult, String> {
+    session_transmit(session_id, &TransmitPayload::ModbusWrite(write.clone())).await
+}
+
+/// Send a UDS request through a DoIP session (convenience wrapper)
+pub async fn transmit_diagnostic(session_id: &str, request: &doip::DiagnosticRequest) -> Result<TransmitResult, String> {
+    session_transmit(session_id, &TransmitPayload::Diagnostic(request.clone())).await
+}
+
 /// Change serial framing on a running session in place (no device reconnect),
 /// then broadcast the updated capabilities (rx_frames flips when framing turns
 /// a Raw byte stream into framed messages). Returns the new capabilities.
@@ -2796,7 +3329,7 @@ pub async fn set_framing(
     let sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
     session.source.set_framing(req)?;
     let capabilities = session.source.capabilities();
     let state = session.source.state();
@@ -2812,6 +3345,7 @@ pub async fn set_framing(
 
 /// Info about a registered subscriber (for TypeScript)
 #[derive(Clone, Debug, Serialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
 pub struct SubscriberInfo {
     pub subscriber_id: String,
     /// Human-readable app name (e.g., "discovery", "decoder")
@@ -2861,7 +3395,7 @@ pub async fn register_subscriber(session_id: &str, subscriber_id: &str, app_name
         let needs_resume = {
             let session = sessions
                 .get_mut(session_id)
-                .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+                .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
             if let Some(suspended_at) = session.suspended_at.take() {
                 let suspended_for = now.duration_since(suspended_at);
                 tlog!(
@@ -2895,7 +3429,7 @@ pub async fn register_subscriber(session_id: &str, subscriber_id: &str, app_name
 
         let session = sessions
             .get_mut(session_id)
-            .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+            .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
         // Resume from suspension if needed (the reader was paused when listeners went stale)
         if needs_resume {
@@ -2967,6 +3501,7 @@ async fn destroy_extracted_session(session_id: &str, mut session: IOSession) {
     // Clear any closing flag
     clear_session_closing(session_id);
     clear_playback_position(session_id);
+    clear_session_state(session_id);
     // Clean up profile tracking (release single-handle device locks)
     crate::sessions::cleanup_session_profiles(session_id);
     tlog!("[reader] Session '{}' destroyed", session_id);
@@ -3032,6 +3567,9 @@ async fn detach_subscriber_to_capture_copy(
         }
     }
 
+    // Resolve the subscriber's window while it's still known to the registry.
+    let window = window_of_app(subscriber_id);
+
     // Unregister the subscriber (this may destroy the session if it was the last one).
     let remaining = unregister_subscriber(session_id, subscriber_id).await?;
 
@@ -3042,7 +3580,8 @@ async fn detach_subscriber_to_capture_copy(
         subscriber_id: String,
         capture_ids: Vec<String>,
     }
-    let _ = app.emit("subscriber-evicted", SubscriberEvictedPayload {
+    // Only the detached app's window cares; other windows would just filter it out.
+    emit_to_windows(app, window.map(|w| vec![w]), "subscriber-evicted", SubscriberEvictedPayload {
         session_id: session_id.to_string(),
         subscriber_id: subscriber_id.to_string(),
         capture_ids: copied_capture_ids.clone(),
@@ -3079,7 +3618,7 @@ pub async fn add_source_to_session(
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     // Get current source configs — only multi-source sessions support this
     let existing_configs = session.source.broker_configs()
@@ -3140,7 +3679,7 @@ pub async fn remove_source_from_session(
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     // Get current source configs — only multi-source sessions support this
     let existing_configs = session.source.broker_configs()
@@ -3199,6 +3738,89 @@ pub async fn remove_source_from_session(
     Ok(capabilities)
 }
 
+/// Replace one source of a multi-source session with another (e.g. a flaky USB
+/// adapter with its TCP twin) without tearing the session down. With
+/// `keep_bus_mappings` the new source takes over the old one's bus mappings,
+/// so output bus numbers stay the same. Keeps the same session ID and listeners.
+pub async fn replace_source_in_session(
+    app: &AppHandle,
+    session_id: &str,
+    old_profile_id: &str,
+    mut new_source: SourceConfig,
+    keep_bus_mappings: bool,
+) -> Result<IOCapabilities, String> {
+    let mut sessions = IO_SESSIONS.lock().await;
+    let session = sessions
+        .get_mut(session_id)
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
+
+    // Get current source configs — only multi-source sessions support this
+    let existing_configs = session.source.broker_configs()
+        .ok_or_else(|| "Session does not support multi-source — cannot replace a source".to_string())?;
+
+    let old_source = existing_configs
+        .iter()
+        .find(|c| c.profile_id == old_profile_id)
+        .ok_or_else(|| format!("Profile '{}' is not a source in session '{}'", old_profile_id, session_id))?;
+    let same_profile = new_source.profile_id == old_profile_id;
+    if !same_profile && existing_configs.iter().any(|c| c.profile_id == new_source.profile_id) {
+        return Err(format!(
+            "Profile '{}' is already a source in session '{}'",
+            new_source.profile_id, session_id
+        ));
+    }
+    if keep_bus_mappings {
+        new_source.bus_mappings = old_source.bus_mappings.clone();
+    }
+    let new_profile_id = new_source.profile_id.clone();
+
+    // If the session is running, hot-swap the source without stopping. A
+    // different profile is added before the old one goes, so the session
+    // never runs out of sources in between.
+    if matches!(session.source.state(), IOState::Running) {
+        if same_profile {
+            session.source.remove_source_hot(old_profile_id)?;
+            session.source.add_source_hot(new_source)?;
+        } else {
+            session.source.add_source_hot(new_source)?;
+            session.source.remove_source_hot(old_profile_id)?;
+        }
+        if let Some(configs) = session.source.broker_configs() {
+            session.source_names = configs.iter().map(|c| c.display_name.clone()).collect();
+        }
+        let capabilities = session.source.capabilities();
+        tlog!(
+            "[reader] Hot-swapped source '{}' for '{}' in session '{}' (sources: {:?})",
+            old_profile_id, new_profile_id, session_id, session.source_names
+        );
+        return Ok(capabilities);
+    }
+
+    // Cold path: session not running — rebuild the IOBroker with the new
+    // source in the old one's place
+    let all_configs: Vec<SourceConfig> = existing_configs
+        .into_iter()
+        .map(|c| if c.profile_id == old_profile_id { new_source.clone() } else { c })
+        .collect();
+
+    let source_display_names: Vec<String> = all_configs.iter()
+        .map(|c| c.display_name.clone())
+        .collect();
+
+    let reader = IOBroker::new(app.clone(), session_id.to_string(), all_configs)?;
+    let capabilities = reader.capabilities();
+
+    session.source = Box::new(reader);
+    session.source_names = source_display_names;
+
+    tlog!(
+        "[reader] Replaced source '{}' with '{}' in session '{}' (sources: {:?})",
+        old_profile_id, new_profile_id, session_id, session.source_names
+    );
+
+    Ok(capabilities)
+}
+
 /// Pause polling for a specific source within a running session.
 /// The session stays active and other sources continue normally.
 pub async fn pause_source_in_session(
@@ -3208,7 +3830,7 @@ pub async fn pause_source_in_session(
     let sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.pause_source_polling(profile_id)
 }
@@ -3221,7 +3843,7 @@ pub async fn resume_source_in_session(
     let sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     session.source.resume_source_polling(profile_id)
 }
@@ -3237,7 +3859,7 @@ pub async fn update_source_bus_mappings(
     let mut sessions = IO_SESSIONS.lock().await;
     let session = sessions
         .get_mut(session_id)
-        .ok_or_else(|| format!("Session '{}' not found", session_id))?;
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
 
     // Only multi-source sessions support this
     session.source.broker_configs()
@@ -3265,7 +3887,7 @@ pub async fn update_source_bus_mappings(
 pub async fn get_session_subscribers(session_id: &str) -> Result<Vec<SubscriberInfo>, String> {
     let sessions = IO_SESSIONS.lock().await;
     if !sessions.contains_key(session_id) {
-        return Err(format!("Session '{}' not found", session_id));
+        return Err(msg!(SESSION_NOT_FOUND, session_id = session_id).into());
     }
     Ok(subscribers_for_session(session_id))
 }
@@ -3353,7 +3975,7 @@ pub async fn set_subscriber_active(session_id: &str, subscriber_id: &str, is_act
     {
         let sessions = IO_SESSIONS.lock().await;
         if !sessions.contains_key(session_id) {
-            return Err(format!("Session '{}' not found", session_id));
+            return Err(msg!(SESSION_NOT_FOUND, session_id = session_id).into());
         }
     }
     // The subscriber lives in the open-app registry; verify it's attached to this session.
@@ -3367,3 +3989,17 @@ pub async fn set_subscriber_active(session_id: &str, subscriber_id: &str, is_act
     set_app_active(subscriber_id, is_active);
     Ok(())
 }
+
+/// Limit how many frames per frame ID per second a subscriber is sent
+/// (None or 0 = no limit). See `downsample` for how limits combine.
+pub fn set_subscriber_rate_limit(session_id: &str, subscriber_id: &str, max_frames_per_second: Option<u32>) -> Result<(), String> {
+    if current_session_of_app(subscriber_id).as_deref() != Some(session_id) {
+        return Err(format!("Subscriber '{}' not found in session '{}'", subscriber_id, session_id));
+    }
+    tlog!(
+        "[reader] Session '{}' subscriber '{}' rate limit -> {:?}",
+        session_id, subscriber_id, max_frames_per_second
+    );
+    set_app_rate_limit(subscriber_id, max_frames_per_second);
+    Ok(())
+}
@@ -298,6 +298,11 @@ async fn run_poll_loop(
                     source_address: None,
                     incomplete: None,
                     direction: Some("rx".to_string()),
+                    link: None,
+                    clock: None,
+                    is_error: None,
+                    error_class: None,
+                    brs: None,
                 };
 
                 capture_store::append_frames_to_session(&session_id, vec![frame]);
@@ -3,11 +3,17 @@
 // Modbus TCP client driver for polling registers and scanning Modbus devices.
 // - Source: catalog-driven polling of known registers
 // - Scanner: one-shot discovery of registers and active unit IDs
+// - Write: coil and register writes sent from the Transmit app
+// - Simulator: register map served by the server's simulator role
 
 mod reader;
 pub mod scanner;
+mod simulator;
+mod write;
 
 pub use reader::{ModbusTcpConfig, ModbusTcpSource, PollGroup, RegisterType};
+pub use simulator::{MbapFrame, RegisterMap};
+pub use write::ModbusWrite;
 pub use scanner::{
     ModbusScanConfig, ScanCompletePayload, UnitIdScanConfig,
 };
@@ -36,7 +36,7 @@ use crate::io::{
 // ============================================================================
 
 /// Register type for Modbus polling
-#[derive(Clone, Debug, serde::Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, serde::Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum RegisterType {
     Holding,
@@ -363,6 +363,11 @@ fn spawn_poll_task(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     };
 
                     capture_store::append_frames_to_session(&session_id, vec![frame]);
@@ -239,6 +239,11 @@ pub async fn modbus_scan_registers(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     });
                 }
                 found_count += data.len() as u32;
@@ -259,6 +264,11 @@ pub async fn modbus_scan_registers(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     });
                 }
                 found_count += data.len() as u32;
@@ -488,6 +498,11 @@ pub async fn modbus_scan_unit_ids(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     };
                     found_count += 1;
                     scan_frames.push(frame);
@@ -573,6 +588,11 @@ pub async fn modbus_scan_unit_ids(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     });
                     found_count += 1;
                     tlog!(
@@ -596,6 +616,11 @@ pub async fn modbus_scan_unit_ids(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     });
                     found_count += 1;
                     tlog!(
@@ -619,6 +644,11 @@ pub async fn modbus_scan_unit_ids(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     });
                     found_count += 1;
                     tlog!(
@@ -0,0 +1,287 @@
+// io/modbus_tcp/simulator.rs
+//
+// Simulated Modbus device for the server's simulator role.
+//
+// The register map comes from the catalog's poll groups: every register a
+// client would poll is served, per unit ID, starting at zero. Clients read it
+// with function codes 01-04 and change coils and holding registers with
+// 05, 06, 15 and 16; the Transmit app can set any mapped value through the
+// same write requests. Anything outside the map gets a Modbus exception.
+
+use std::collections::{BTreeSet, HashMap};
+
+use super::reader::{coils_to_bytes, registers_to_bytes};
+use super::write::{WRITE_MULTIPLE_COILS, WRITE_MULTIPLE_REGISTERS, WRITE_SINGLE_COIL, WRITE_SINGLE_REGISTER};
+use super::{ModbusWrite, PollGroup, RegisterType};
+
+const READ_COILS: u8 = 0x01;
+const READ_DISCRETE_INPUTS: u8 = 0x02;
+const READ_HOLDING_REGISTERS: u8 = 0x03;
+const READ_INPUT_REGISTERS: u8 = 0x04;
+
+const ILLEGAL_FUNCTION: u8 = 0x01;
+const ILLEGAL_DATA_ADDRESS: u8 = 0x02;
+const ILLEGAL_DATA_VALUE: u8 = 0x03;
+const GATEWAY_TARGET_FAILED: u8 = 0x0B;
+
+/// Most bits one FC01/FC02 request may read
+const MAX_READ_BITS: u16 = 2000;
+/// Most registers one FC03/FC04 request may read
+const MAX_READ_REGISTERS: u16 = 125;
+
+/// MBAP header: transaction ID, protocol ID, length, unit ID
+const MBAP_HEADER_LEN: usize = 7;
+
+/// Register values served by a simulated device, keyed by unit ID, table and address
+#[derive(Clone, Debug, Default)]
+pub struct RegisterMap {
+    values: HashMap<(u8, RegisterType, u16), u16>,
+}
+
+impl RegisterMap {
+    /// Map every register covered by the poll groups, all starting at zero.
+    pub fn from_polls(polls: &[PollGroup]) -> Self {
+        let mut values = HashMap::new();
+        for poll in polls {
+            let end = (u32::from(poll.start_register) + u32::from(poll.count)).min(0x1_0000);
+            for address in u32::from(poll.start_register)..end {
+                values.insert((poll.device_address, poll.register_type.clone(), address as u16), 0);
+            }
+        }
+        Self { values }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Unit IDs with at least one mapped register
+    pub fn units(&self) -> BTreeSet<u8> {
+        self.values.keys().map(|(unit, _, _)| *unit).collect()
+    }
+
+    /// Read `count` values from one table; `None` if any address is unmapped.
+    fn read(&self, unit: u8, table: RegisterType, address: u16, count: u16) -> Option<Vec<u16>> {
+        (0..count)
+            .map(|i| {
+                let addr = address.checked_add(i)?;
+                self.values.get(&(unit, table.clone(), addr)).copied()
+            })
+            .collect()
+    }
+
+    /// Set the values a write covers. Fails without changing anything if an
+    /// address is outside the map.
+    pub fn apply_write(&mut self, write: &ModbusWrite) -> Result<(), String> {
+        write.validate()?;
+        let table = match write.function_code {
+            WRITE_SINGLE_COIL | WRITE_MULTIPLE_COILS => RegisterType::Coil,
+            _ => RegisterType::Holding,
+        };
+        let keys: Vec<_> = (0..write.values.len())
+            .map(|i| (write.unit_id, table.clone(), write.address + i as u16))
+            .collect();
+        if let Some((_, _, addr)) = keys.iter().find(|key| !self.values.contains_key(key)) {
+            return Err(format!("Unit {} has no {:?} register at address {}", write.unit_id, table, addr));
+        }
+        for (key, &value) in keys.into_iter().zip(&write.values) {
+            self.values.insert(key, value);
+        }
+        Ok(())
+    }
+
+    /// Answer a request PDU (function code onwards) for `unit`. Returns the
+    /// response PDU, which is an exception response when the request can't be
+    /// served; `None` for an empty PDU.
+    pub fn respond(&mut self, unit: u8, pdu: &[u8]) -> Option<Vec<u8>> {
+        let (&function_code, body) = pdu.split_first()?;
+        let exception = |code: u8| vec![function_code | 0x80, code];
+
+        if !self.values.keys().any(|(u, _, _)| *u == unit) {
+            return Some(exception(GATEWAY_TARGET_FAILED));
+        }
+
+        let response = match function_code {
+            READ_COILS | READ_DISCRETE_INPUTS | READ_HOLDING_REGISTERS | READ_INPUT_REGISTERS => {
+                let [a0, a1, q0, q1] = body else {
+                    return Some(exception(ILLEGAL_DATA_VALUE));
+                };
+                let address = u16::from_be_bytes([*a0, *a1]);
+                let count = u16::from_be_bytes([*q0, *q1]);
+                let (table, max) = match function_code {
+                    READ_COILS => (RegisterType::Coil, MAX_READ_BITS),
+                    READ_DISCRETE_INPUTS => (RegisterType::Discrete, MAX_READ_BITS),
+                    READ_HOLDING_REGISTERS => (RegisterType::Holding, MAX_READ_REGISTERS),
+                    _ => (RegisterType::Input, MAX_READ_REGISTERS),
+                };
+                if !(1..=max).contains(&count) {
+                    return Some(exception(ILLEGAL_DATA_VALUE));
+                }
+                let is_bits = matches!(table, RegisterType::Coil | RegisterType::Discrete);
+                let Some(values) = self.read(unit, table, address, count) else {
+                    return Some(exception(ILLEGAL_DATA_ADDRESS));
+                };
+                let data = if is_bits {
+                    coils_to_bytes(&values.iter().map(|&v| v != 0).collect::<Vec<_>>())
+                } else {
+                    registers_to_bytes(&values)
+                };
+                let mut response = vec![function_code, data.len() as u8];
+                response.extend_from_slice(&data);
+                response
+            }
+            WRITE_SINGLE_COIL | WRITE_SINGLE_REGISTER | WRITE_MULTIPLE_COILS | WRITE_MULTIPLE_REGISTERS => {
+                let mut request = vec![unit];
+                request.extend_from_slice(pdu);
+                let Ok(write) = ModbusWrite::decode(&request) else {
+                    return Some(exception(ILLEGAL_DATA_VALUE));
+                };
+                if self.apply_write(&write).is_err() {
+                    return Some(exception(ILLEGAL_DATA_ADDRESS));
+                }
+                match function_code {
+                    // Single writes echo the request
+                    WRITE_SINGLE_COIL | WRITE_SINGLE_REGISTER => pdu.to_vec(),
+                    _ => pdu[..5].to_vec(),
+                }
+            }
+            _ => exception(ILLEGAL_FUNCTION),
+        };
+        Some(response)
+    }
+}
+
+/// One Modbus TCP application data unit
+#[derive(Debug, PartialEq)]
+pub struct MbapFrame {
+    pub transaction_id: u16,
+    pub unit_id: u8,
+    /// Function code onwards
+    pub pdu: Vec<u8>,
+}
+
+impl MbapFrame {
+    /// Take the next complete frame off the front of `buf`. A header that
+    /// isn't Modbus (non-zero protocol ID or impossible length) discards the
+    /// buffered bytes so the stream can resynchronise.
+    pub fn take(buf: &mut Vec<u8>) -> Option<Self> {
+        if buf.len() < MBAP_HEADER_LEN {
+            return None;
+        }
+        let protocol_id = u16::from_be_bytes([buf[2], buf[3]]);
+        let length = usize::from(u16::from_be_bytes([buf[4], buf[5]]));
+        if protocol_id != 0 || !(2..=254).contains(&length) {
+            buf.clear();
+            return None;
+        }
+        let total = 6 + length;
+        if buf.len() < total {
+            return None;
+        }
+        let frame = MbapFrame {
+            transaction_id: u16::from_be_bytes([buf[0], buf[1]]),
+            unit_id: buf[6],
+            pdu: buf[MBAP_HEADER_LEN..total].to_vec(),
+        };
+        buf.drain(..total);
+        Some(frame)
+    }
+
+    /// Encode a frame with the given PDU, e.g. the response to this request.
+    pub fn encode(transaction_id: u16, unit_id: u8, pdu: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(MBAP_HEADER_LEN + pdu.len());
+        buf.extend_from_slice(&transaction_id.to_be_bytes());
+        buf.extend_from_slice(&[0, 0]);
+        buf.extend_from_slice(&(pdu.len() as u16 + 1).to_be_bytes());
+        buf.push(unit_id);
+        buf.extend_from_slice(pdu);
+        buf
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> RegisterMap {
+        let poll = |register_type, start_register, count| PollGroup {
+            register_type,
+            start_register,
+            count,
+            interval_ms: 1000,
+            frame_id: 0,
+            device_address: 1,
+        };
+        RegisterMap::from_polls(&[poll(RegisterType::Holding, 100, 4), poll(RegisterType::Coil, 0, 10)])
+    }
+
+    #[test]
+    fn test_write_then_read() {
+        let mut map = map();
+        assert_eq!(map.len(), 14);
+
+        // FC16: write 0x1234, 0xBEEF at 101; response echoes address and quantity
+        let write = [0x10, 0x00, 0x65, 0x00, 0x02, 4, 0x12, 0x34, 0xBE, 0xEF];
+        assert_eq!(map.respond(1, &write).unwrap(), vec![0x10, 0x00, 0x65, 0x00, 0x02]);
+        assert_eq!(
+            map.respond(1, &[0x03, 0x00, 0x64, 0x00, 0x04]).unwrap(),
+            vec![0x03, 8, 0, 0, 0x12, 0x34, 0xBE, 0xEF, 0, 0]
+        );
+
+        // FC05 echoes; coils read back packed LSB first
+        assert_eq!(map.respond(1, &[0x05, 0x00, 0x02, 0xFF, 0x00]).unwrap(), vec![0x05, 0x00, 0x02, 0xFF, 0x00]);
+        assert_eq!(map.respond(1, &[0x01, 0x00, 0x00, 0x00, 0x0A]).unwrap(), vec![0x01, 2, 0b100, 0]);
+
+        // The Transmit app's writes land in the same map
+        let set = ModbusWrite { function_code: WRITE_SINGLE_REGISTER, unit_id: 1, address: 103, values: vec![7] };
+        map.apply_write(&set).unwrap();
+        assert_eq!(map.respond(1, &[0x03, 0x00, 0x67, 0x00, 0x01]).unwrap(), vec![0x03, 2, 0, 7]);
+    }
+
+    #[test]
+    fn test_exceptions() {
+        let mut map = map();
+        // Past the mapped holding registers
+        assert_eq!(map.respond(1, &[0x03, 0x00, 0x66, 0x00, 0x03]).unwrap(), vec![0x83, ILLEGAL_DATA_ADDRESS]);
+        // Input registers aren't mapped at all
+        assert_eq!(map.respond(1, &[0x04, 0x00, 0x64, 0x00, 0x01]).unwrap(), vec![0x84, ILLEGAL_DATA_ADDRESS]);
+        // Zero quantity
+        assert_eq!(map.respond(1, &[0x03, 0x00, 0x64, 0x00, 0x00]).unwrap(), vec![0x83, ILLEGAL_DATA_VALUE]);
+        // Unsupported function code
+        assert_eq!(map.respond(1, &[0x2B, 0x0E, 0x01, 0x00]).unwrap(), vec![0xAB, ILLEGAL_FUNCTION]);
+        // Unknown unit
+        assert_eq!(map.respond(9, &[0x03, 0x00, 0x64, 0x00, 0x01]).unwrap(), vec![0x83, GATEWAY_TARGET_FAILED]);
+        // A rejected write changes nothing
+        let write = [0x10, 0x00, 0x63, 0x00, 0x02, 4, 0, 1, 0, 2];
+        assert_eq!(map.respond(1, &write).unwrap(), vec![0x90, ILLEGAL_DATA_ADDRESS]);
+        assert_eq!(map.respond(1, &[0x03, 0x00, 0x64, 0x00, 0x01]).unwrap(), vec![0x03, 2, 0, 0]);
+    }
+
+    #[test]
+    fn test_mbap_framing() {
+        let mut buf = MbapFrame::encode(7, 1, &[0x03, 0x00, 0x64, 0x00, 0x01]);
+        buf.extend_from_slice(&MbapFrame::encode(8, 2, &[0x01, 0x00, 0x00, 0x00, 0x08]));
+        let second_half = buf.split_off(15);
+
+        let first = MbapFrame::take(&mut buf).unwrap();
+        assert_eq!(first, MbapFrame { transaction_id: 7, unit_id: 1, pdu: vec![0x03, 0x00, 0x64, 0x00, 0x01] });
+        // Second frame is split across reads
+        assert_eq!(MbapFrame::take(&mut buf), None);
+        buf.extend_from_slice(&second_half);
+        assert_eq!(MbapFrame::take(&mut buf).unwrap().transaction_id, 8);
+        assert!(buf.is_empty());
+
+        // Not Modbus: dropped
+        let mut junk = b"GET / HTTP/1.1\r\n".to_vec();
+        assert_eq!(MbapFrame::take(&mut junk), None);
+        assert!(junk.is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+// io/modbus_tcp/write.rs
+//
+// Modbus writes from the Transmit app (function codes 05, 06, 15 and 16).
+//
+// A write travels through the session's transmit channel like an encoded CAN
+// frame: the broker encodes it as the unit ID followed by the standard request
+// PDU, and the Modbus TCP client source decodes it and runs it on its shared
+// connection between polls.
+
+use serde::{Deserialize, Serialize};
+use tokio_modbus::client::Context;
+use tokio_modbus::prelude::*;
+
+use super::reader::{coils_to_bytes, registers_to_bytes};
+
+pub const WRITE_SINGLE_COIL: u8 = 0x05;
+pub const WRITE_SINGLE_REGISTER: u8 = 0x06;
+pub const WRITE_MULTIPLE_COILS: u8 = 0x0F;
+pub const WRITE_MULTIPLE_REGISTERS: u8 = 0x10;
+
+/// Most coils one FC15 request may set
+const MAX_COILS: usize = 1968;
+/// Most registers one FC16 request may set
+const MAX_REGISTERS: usize = 123;
+
+/// A single Modbus write request
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModbusWrite {
+    /// 5 (single coil), 6 (single register), 15 (multiple coils) or 16 (multiple registers)
+    pub function_code: u8,
+    /// Device (slave) address
+    pub unit_id: u8,
+    /// Protocol-level start address (0-based)
+    pub address: u16,
+    /// Register values, or 0/1 per coil
+    pub values: Vec<u16>,
+}
+
+impl ModbusWrite {
+    fn is_coil(&self) -> bool {
+        matches!(self.function_code, WRITE_SINGLE_COIL | WRITE_MULTIPLE_COILS)
+    }
+
+    /// Check the value count against the function code and the protocol limits.
+    pub fn validate(&self) -> Result<(), String> {
+        let count = self.values.len();
+        let (min, max) = match self.function_code {
+            WRITE_SINGLE_COIL | WRITE_SINGLE_REGISTER => (1, 1),
+            WRITE_MULTIPLE_COILS => (1, MAX_COILS),
+            WRITE_MULTIPLE_REGISTERS => (1, MAX_REGISTERS),
+            fc => return Err(format!("Function code {} is not a Modbus write (use 5, 6, 15 or 16)", fc)),
+        };
+        if !(min..=max).contains(&count) {
+            return Err(if min == max {
+                format!("Function code {} writes exactly one value, not {}", self.function_code, count)
+            } else {
+                format!("Function code {} writes 1-{} values, not {}", self.function_code, max, count)
+            });
+        }
+        if self.is_coil() && self.values.iter().any(|&v| v > 1) {
+            return Err("Coil values must be 0 or 1".to_string());
+        }
+        if usize::from(self.address) + count > 0x1_0000 {
+            return Err(format!("{} values from address {} run past 65535", count, self.address));
+        }
+        Ok(())
+    }
+
+    /// The written values as they appear in polled frames: big-endian
+    /// registers, or coils packed LSB first.
+    pub fn data_bytes(&self) -> Vec<u8> {
+        if self.is_coil() {
+            coils_to_bytes(&self.coils())
+        } else {
+            registers_to_bytes(&self.values)
+        }
+    }
+
+    fn coils(&self) -> Vec<bool> {
+        self.values.iter().map(|&v| v != 0).collect()
+    }
+
+    /// Unit ID followed by the request PDU.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![self.unit_id, self.function_code];
+        buf.extend_from_slice(&self.address.to_be_bytes());
+        match self.function_code {
+            WRITE_SINGLE_COIL => {
+                buf.extend_from_slice(if self.values[0] != 0 { &[0xFF, 0x00] } else { &[0x00, 0x00] });
+            }
+            WRITE_SINGLE_REGISTER => buf.extend_from_slice(&self.values[0].to_be_bytes()),
+            _ => {
+                let data = self.data_bytes();
+                buf.extend_from_slice(&(self.values.len() as u16).to_be_bytes());
+                buf.push(data.len() as u8);
+                buf.extend_from_slice(&data);
+            }
+        }
+        buf
+    }
+
+    /// Reverse of [`ModbusWrite::encode`].
+    pub fn decode(buf: &[u8]) -> Result<Self, String> {
+        let short = || format!("Modbus write request too short ({} bytes)", buf.len());
+        let [unit_id, function_code, a0, a1, rest @ ..] = buf else {
+            return Err(short());
+        };
+        let address = u16::from_be_bytes([*a0, *a1]);
+        let values = match *function_code {
+            WRITE_SINGLE_COIL => match rest {
+                [0xFF, 0x00] => vec![1],
+                [0x00, 0x00] => vec![0],
+                _ => return Err("Invalid single coil value".to_string()),
+            },
+            WRITE_SINGLE_REGISTER => match rest {
+                [hi, lo] => vec![u16::from_be_bytes([*hi, *lo])],
+                _ => return Err(short()),
+            },
+            fc @ (WRITE_MULTIPLE_COILS | WRITE_MULTIPLE_REGISTERS) => {
+                let [q0, q1, _byte_count, data @ ..] = rest else {
+                    return Err(short());
+                };
+                let quantity = usize::from(u16::from_be_bytes([*q0, *q1]));
+                if fc == WRITE_MULTIPLE_COILS {
+                    if data.len() * 8 < quantity {
+                        return Err(short());
+                    }
+                    (0..quantity).map(|i| u16::from((data[i / 8] >> (i % 8)) & 1)).collect()
+                } else {
+                    if data.len() < quantity * 2 {
+                        return Err(short());
+                    }
+                    data.chunks_exact(2).take(quantity).map(|c| u16::from_be_bytes([c[0], c[1]])).collect()
+                }
+            }
+            fc => return Err(format!("Function code {} is not a Modbus write", fc)),
+        };
+        let write = ModbusWrite { function_code: *function_code, unit_id: *unit_id, address, values };
+        write.validate()?;
+        Ok(write)
+    }
+
+    /// Run the write on a client connection already pointed at `unit_id`.
+    pub async fn execute(&self, ctx: &mut Context) -> Result<(), String> {
+        let result = match self.function_code {
+            WRITE_SINGLE_COIL => ctx.write_single_coil(self.address, self.values[0] != 0).await,
+            WRITE_SINGLE_REGISTER => ctx.write_single_register(self.address, self.values[0]).await,
+            WRITE_MULTIPLE_COILS => ctx.write_multiple_coils(self.address, &self.coils()).await,
+            _ => ctx.write_multiple_registers(self.address, &self.values).await,
+        };
+        // Outer Result = IO error, Inner Result = Modbus exception
+        match result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(exc)) => Err(format!("Modbus exception: {}", exc)),
+            Err(e) => Err(format!("IO error: {}", e)),
+        }
+    }
+
+    /// Short description for logs, e.g. `FC16 unit 1 @ 100 ×3`.
+    pub fn describe(&self) -> String {
+        format!("FC{:02} unit {} @ {} ×{}", self.function_code, self.unit_id, self.address, self.values.len())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(function_code: u8, values: Vec<u16>) -> ModbusWrite {
+        ModbusWrite { function_code, unit_id: 7, address: 100, values }
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let single_coil = write(WRITE_SINGLE_COIL, vec![1]);
+        assert_eq!(single_coil.encode(), vec![7, 0x05, 0x00, 0x64, 0xFF, 0x00]);
+
+        let single_register = write(WRITE_SINGLE_REGISTER, vec![0x1234]);
+        assert_eq!(single_register.encode(), vec![7, 0x06, 0x00, 0x64, 0x12, 0x34]);
+
+        let coils = write(WRITE_MULTIPLE_COILS, vec![1, 0, 1, 1, 0, 0, 0, 0, 1, 1]);
+        assert_eq!(coils.encode(), vec![7, 0x0F, 0x00, 0x64, 0x00, 0x0A, 2, 0b0000_1101, 0b0000_0011]);
+
+        let registers = write(WRITE_MULTIPLE_REGISTERS, vec![1, 0xBEEF]);
+        assert_eq!(registers.encode(), vec![7, 0x10, 0x00, 0x64, 0x00, 0x02, 4, 0x00, 0x01, 0xBE, 0xEF]);
+
+        for w in [single_coil, single_register, coils, registers] {
+            assert_eq!(ModbusWrite::decode(&w.encode()).unwrap(), w);
+        }
+    }
+
+    #[test]
+    fn test_validate_limits() {
+        assert!(write(WRITE_SINGLE_REGISTER, vec![1, 2]).validate().is_err());
+        assert!(write(WRITE_MULTIPLE_REGISTERS, vec![]).validate().is_err());
+        assert!(write(WRITE_MULTIPLE_REGISTERS, vec![0; 124]).validate().is_err());
+        assert!(write(WRITE_MULTIPLE_REGISTERS, vec![0; 123]).validate().is_ok());
+        assert!(write(WRITE_MULTIPLE_COILS, vec![2]).validate().is_err());
+        assert!(write(0x03, vec![1]).validate().is_err());
+        let past_end = ModbusWrite { address: 65535, ..write(WRITE_MULTIPLE_REGISTERS, vec![1, 2]) };
+        assert!(past_end.validate().is_err());
+        assert!(ModbusWrite::decode(&[7, 0x05, 0x00, 0x64, 0x12, 0x34]).is_err());
+    }
+
+    #[test]
+    fn test_data_bytes_match_polled_layout() {
+        assert_eq!(write(WRITE_MULTIPLE_REGISTERS, vec![0x0102, 0x0304]).data_bytes(), vec![1, 2, 3, 4]);
+        assert_eq!(write(WRITE_MULTIPLE_COILS, vec![0, 1, 1]).data_bytes(), vec![0b110]);
+    }
+}
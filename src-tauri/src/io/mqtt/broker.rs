@@ -0,0 +1,533 @@
+// ui/src-tauri/src/io/mqtt/broker.rs
+//
+// Minimal embedded MQTT 3.1.1 broker for field use: WiCAN-style devices
+// publish straight to the analysis laptop, and an ordinary MQTT profile
+// pointed at localhost:<port> feeds the frames through MqttSource — no
+// Mosquitto install needed.
+//
+// Deliberately small: CONNECT (optional username/password), PUBLISH at
+// QoS 0/1/2 inbound (acknowledged, delivered onward at QoS 0), retained
+// messages, SUBSCRIBE/UNSUBSCRIBE with +/# wildcards, PINGREQ, DISCONNECT.
+// No persistent sessions, wills or MQTT 5. A slow subscriber drops
+// messages rather than stalling publishers.
+//
+// Built only with the `mqtt-broker` Cargo feature (on by default).
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::io::now_us;
+
+/// Largest packet accepted (remaining length). CAN JSON messages are tiny.
+const MAX_PACKET_BYTES: usize = 256 * 1024;
+
+/// Outbound queue per client; messages beyond this are dropped.
+const CLIENT_QUEUE: usize = 1024;
+
+/// Retained messages kept (one per topic).
+const MAX_RETAINED: usize = 1024;
+
+/// Distinct topics tracked in the status view.
+const MAX_TRACKED_TOPICS: usize = 512;
+
+/// How long a client has to send CONNECT.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Options for starting the broker.
+#[derive(Debug, Clone)]
+pub struct BrokerConfig {
+    pub bind: String,
+    pub port: u16,
+    /// When set, clients must present these credentials
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+/// Per-topic inbound counters.
+#[derive(Debug, Clone, Serialize)]
+pub struct BrokerTopicStats {
+    pub topic: String,
+    pub messages: u64,
+    pub last_us: u64,
+}
+
+/// Broker state reported to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct MqttBrokerStatus {
+    pub running: bool,
+    pub bind: Option<String>,
+    pub port: Option<u16>,
+    pub clients: usize,
+    pub messages_in: u64,
+    /// Most active topics first
+    pub topics: Vec<BrokerTopicStats>,
+}
+
+struct Client {
+    tx: mpsc::Sender<Vec<u8>>,
+    filters: Vec<String>,
+}
+
+#[derive(Default)]
+struct Shared {
+    clients: HashMap<u64, Client>,
+    retained: HashMap<String, Vec<u8>>,
+    topics: HashMap<String, BrokerTopicStats>,
+    messages_in: u64,
+}
+
+struct RunningBroker {
+    config: BrokerConfig,
+    shared: Arc<Mutex<Shared>>,
+    cancel: CancellationToken,
+}
+
+static BROKER: Lazy<Mutex<Option<RunningBroker>>> = Lazy::new(|| Mutex::new(None));
+
+static NEXT_CLIENT: AtomicU64 = AtomicU64::new(1);
+
+// ============================================================================
+// Wire helpers
+// ============================================================================
+
+/// MQTT topic filter match with `+` (one level) and `#` (rest) wildcards.
+/// Topics starting with `$` only match filters that name them explicitly.
+pub(crate) fn topic_matches(filter: &str, topic: &str) -> bool {
+    if topic.starts_with('$') && !filter.starts_with('$') {
+        return false;
+    }
+    let mut f = filter.split('/');
+    let mut t = topic.split('/');
+    loop {
+        match (f.next(), t.next()) {
+            (Some("#"), _) => return true,
+            (Some("+"), Some(_)) => {}
+            (Some(a), Some(b)) if a == b => {}
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+fn encode_remaining_length(mut len: usize, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+}
+
+fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(body.len() + 5);
+    out.push(header);
+    encode_remaining_length(body.len(), &mut out);
+    out.extend_from_slice(body);
+    out
+}
+
+/// QoS 0 PUBLISH as delivered to subscribers.
+fn publish_packet(topic: &str, payload: &[u8], retain: bool) -> Vec<u8> {
+    let mut body = Vec::with_capacity(2 + topic.len() + payload.len());
+    body.extend_from_slice(&(topic.len() as u16).to_be_bytes());
+    body.extend_from_slice(topic.as_bytes());
+    body.extend_from_slice(payload);
+    packet(0x30 | retain as u8, &body)
+}
+
+/// Reader over a packet body.
+struct Body<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Body<'a> {
+    fn u8(&mut self) -> Result<u8, String> {
+        let b = *self.buf.get(self.pos).ok_or("Truncated packet")?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_be_bytes([self.u8()?, self.u8()?]))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], String> {
+        let len = self.u16()? as usize;
+        let end = self.pos + len;
+        let out = self.buf.get(self.pos..end).ok_or("Truncated packet")?;
+        self.pos = end;
+        Ok(out)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        String::from_utf8(self.bytes()?.to_vec()).map_err(|_| "Invalid UTF-8 string".to_string())
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        &self.buf[self.pos.min(self.buf.len())..]
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.buf.len()
+    }
+}
+
+/// Read one packet: (first header byte, body).
+async fn read_packet<R: AsyncReadExt + Unpin>(reader: &mut R) -> Result<(u8, Vec<u8>), String> {
+    let header = reader.read_u8().await.map_err(|e| e.to_string())?;
+    let mut len = 0usize;
+    let mut shift = 0;
+    loop {
+        let byte = reader.read_u8().await.map_err(|e| e.to_string())?;
+        len |= ((byte & 0x7F) as usize) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+        if shift > 21 {
+            return Err("Malformed remaining length".to_string());
+        }
+    }
+    if len > MAX_PACKET_BYTES {
+        return Err(format!("Packet of {} bytes exceeds limit", len));
+    }
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body).await.map_err(|e| e.to_string())?;
+    Ok((header, body))
+}
+
+// ============================================================================
+// Routing
+// ============================================================================
+
+fn route_publish(shared: &Mutex<Shared>, topic: &str, payload: &[u8], retain: bool) {
+    let mut state = shared.lock().unwrap();
+    state.messages_in += 1;
+    let now = now_us();
+    if let Some(stats) = state.topics.get_mut(topic) {
+        stats.messages += 1;
+        stats.last_us = now;
+    } else if state.topics.len() < MAX_TRACKED_TOPICS {
+        state.topics.insert(
+            topic.to_string(),
+            BrokerTopicStats { topic: topic.to_string(), messages: 1, last_us: now },
+        );
+    }
+    if retain {
+        if payload.is_empty() {
+            state.retained.remove(topic);
+        } else if state.retained.len() < MAX_RETAINED || state.retained.contains_key(topic) {
+            state.retained.insert(topic.to_string(), payload.to_vec());
+        }
+    }
+    let outgoing = publish_packet(topic, payload, false);
+    for client in state.clients.values() {
+        if client.filters.iter().any(|f| topic_matches(f, topic)) {
+            // Full queue = slow subscriber; QoS 0 lets us drop
+            let _ = client.tx.try_send(outgoing.clone());
+        }
+    }
+}
+
+// ============================================================================
+// Connection handling
+// ============================================================================
+
+async fn handle_connection(
+    stream: TcpStream,
+    config: BrokerConfig,
+    shared: Arc<Mutex<Shared>>,
+    cancel: CancellationToken,
+) -> Result<(), String> {
+    let peer = stream.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+    let (mut reader, mut writer) = stream.into_split();
+
+    // CONNECT
+    let (header, body) = tokio::time::timeout(CONNECT_TIMEOUT, read_packet(&mut reader))
+        .await
+        .map_err(|_| "No CONNECT received".to_string())??;
+    if header >> 4 != 1 {
+        return Err("First packet was not CONNECT".to_string());
+    }
+    let mut b = Body { buf: &body, pos: 0 };
+    let _protocol = b.string()?;
+    let level = b.u8()?;
+    let flags = b.u8()?;
+    let keep_alive = b.u16()?;
+    let client_name = b.string()?;
+    if flags & 0x04 != 0 {
+        b.string()?; // will topic
+        b.bytes()?; // will message
+    }
+    let username = if flags & 0x80 != 0 { Some(b.string()?) } else { None };
+    let password = if flags & 0x40 != 0 {
+        Some(String::from_utf8_lossy(b.bytes()?).into_owned())
+    } else {
+        None
+    };
+
+    // 0x01 unacceptable protocol version, 0x04 bad credentials
+    let return_code = if level != 3 && level != 4 {
+        0x01
+    } else if config.username.is_some()
+        && (username != config.username || password != config.password)
+    {
+        0x04
+    } else {
+        0x00
+    };
+    writer
+        .write_all(&packet(0x20, &[0x00, return_code]))
+        .await
+        .map_err(|e| e.to_string())?;
+    if return_code != 0 {
+        return Err(format!("Refused CONNECT from {} (code {})", peer, return_code));
+    }
+    tlog!("[mqtt_broker] Client '{}' connected from {}", client_name, peer);
+
+    let client_id = NEXT_CLIENT.fetch_add(1, Ordering::Relaxed);
+    let (tx, mut rx) = mpsc::channel::<Vec<u8>>(CLIENT_QUEUE);
+    shared
+        .lock()
+        .unwrap()
+        .clients
+        .insert(client_id, Client { tx: tx.clone(), filters: Vec::new() });
+
+    let writer_task = tauri::async_runtime::spawn(async move {
+        while let Some(bytes) = rx.recv().await {
+            if writer.write_all(&bytes).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    // Spec: disconnect after 1.5× keep-alive without a packet (0 = never)
+    let idle = (keep_alive > 0).then(|| Duration::from_millis(keep_alive as u64 * 1500));
+
+    let result: Result<(), String> = async {
+        loop {
+            let (header, body) = tokio::select! {
+                _ = cancel.cancelled() => return Ok(()),
+                r = async {
+                    match idle {
+                        Some(idle) => tokio::time::timeout(idle, read_packet(&mut reader))
+                            .await
+                            .map_err(|_| "Keep-alive timeout".to_string())?,
+                        None => read_packet(&mut reader).await,
+                    }
+                } => r?,
+            };
+            let mut b = Body { buf: &body, pos: 0 };
+            match header >> 4 {
+                // PUBLISH
+                3 => {
+                    let qos = (header >> 1) & 0x03;
+                    let retain = header & 0x01 != 0;
+                    let topic = b.string()?;
+                    if topic.contains(['+', '#']) {
+                        return Err("Wildcard in PUBLISH topic".to_string());
+                    }
+                    let packet_id = if qos > 0 { Some(b.u16()?) } else { None };
+                    route_publish(&shared, &topic, b.rest(), retain);
+                    match (qos, packet_id) {
+                        (1, Some(id)) => { let _ = tx.send(packet(0x40, &id.to_be_bytes())).await; }
+                        (2, Some(id)) => { let _ = tx.send(packet(0x50, &id.to_be_bytes())).await; }
+                        _ => {}
+                    }
+                }
+                // PUBREL → PUBCOMP (QoS 2 was delivered on PUBLISH)
+                6 => {
+                    let id = b.u16()?;
+                    let _ = tx.send(packet(0x70, &id.to_be_bytes())).await;
+                }
+                // SUBSCRIBE
+                8 => {
+                    let id = b.u16()?;
+                    let mut granted = id.to_be_bytes().to_vec();
+                    let mut filters = Vec::new();
+                    while !b.is_empty() {
+                        let filter = b.string()?;
+                        let _requested_qos = b.u8()?;
+                        filters.push(filter);
+                        granted.push(0x00);
+                    }
+                    let retained: Vec<Vec<u8>> = {
+                        let mut state = shared.lock().unwrap();
+                        if let Some(client) = state.clients.get_mut(&client_id) {
+                            for f in &filters {
+                                if !client.filters.contains(f) {
+                                    client.filters.push(f.clone());
+                                }
+                            }
+                        }
+                        state
+                            .retained
+                            .iter()
+                            .filter(|(topic, _)| filters.iter().any(|f| topic_matches(f, topic)))
+                            .map(|(topic, payload)| publish_packet(topic, payload, true))
+                            .collect()
+                    };
+                    let _ = tx.send(packet(0x90, &granted)).await;
+                    for msg in retained {
+                        let _ = tx.try_send(msg);
+                    }
+                }
+                // UNSUBSCRIBE
+                10 => {
+                    let id = b.u16()?;
+                    let mut filters = Vec::new();
+                    while !b.is_empty() {
+                        filters.push(b.string()?);
+                    }
+                    if let Some(client) = shared.lock().unwrap().clients.get_mut(&client_id) {
+                        client.filters.retain(|f| !filters.contains(f));
+                    }
+                    let _ = tx.send(packet(0xB0, &id.to_be_bytes())).await;
+                }
+                // PINGREQ
+                12 => {
+                    let _ = tx.send(packet(0xD0, &[])).await;
+                }
+                // DISCONNECT
+                14 => return Ok(()),
+                // PUBACK/PUBREC/PUBCOMP from clients: we only send QoS 0
+                4 | 5 | 7 => {}
+                other => return Err(format!("Unexpected packet type {}", other)),
+            }
+        }
+    }
+    .await;
+
+    shared.lock().unwrap().clients.remove(&client_id);
+    drop(tx);
+    let _ = writer_task.await;
+    tlog!("[mqtt_broker] Client '{}' disconnected", client_name);
+    result
+}
+
+// ============================================================================
+// Lifecycle
+// ============================================================================
+
+/// Start the broker. Fails if it is already running or the port is taken.
+pub async fn start(config: BrokerConfig) -> Result<MqttBrokerStatus, String> {
+    if BROKER.lock().unwrap().is_some() {
+        return Err("MQTT broker is already running".to_string());
+    }
+    let listener = TcpListener::bind((config.bind.as_str(), config.port))
+        .await
+        .map_err(|e| format!("Failed to listen on {}:{}: {}", config.bind, config.port, e))?;
+    let shared = Arc::new(Mutex::new(Shared::default()));
+    let cancel = CancellationToken::new();
+    {
+        let mut slot = BROKER.lock().unwrap();
+        if slot.is_some() {
+            return Err("MQTT broker is already running".to_string());
+        }
+        *slot = Some(RunningBroker {
+            config: config.clone(),
+            shared: shared.clone(),
+            cancel: cancel.clone(),
+        });
+    }
+    tlog!("[mqtt_broker] Listening on {}:{}", config.bind, config.port);
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let accepted = tokio::select! {
+                _ = cancel.cancelled() => break,
+                a = listener.accept() => a,
+            };
+            match accepted {
+                Ok((stream, _)) => {
+                    let config = config.clone();
+                    let shared = shared.clone();
+                    let cancel = cancel.clone();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = handle_connection(stream, config, shared, cancel).await {
+                            tlog!("[mqtt_broker] Connection closed: {}", e);
+                        }
+                    });
+                }
+                Err(e) => tlog!("[mqtt_broker] Accept failed: {}", e),
+            }
+        }
+        tlog!("[mqtt_broker] Stopped");
+    });
+
+    Ok(status())
+}
+
+/// Stop the broker and disconnect every client. No-op when not running.
+pub fn stop() -> MqttBrokerStatus {
+    if let Some(running) = BROKER.lock().unwrap().take() {
+        running.cancel.cancel();
+    }
+    status()
+}
+
+pub fn status() -> MqttBrokerStatus {
+    let guard = BROKER.lock().unwrap();
+    match guard.as_ref() {
+        Some(running) => {
+            let state = running.shared.lock().unwrap();
+            let mut topics: Vec<BrokerTopicStats> = state.topics.values().cloned().collect();
+            topics.sort_by(|a, b| b.messages.cmp(&a.messages));
+            MqttBrokerStatus {
+                running: true,
+                bind: Some(running.config.bind.clone()),
+                port: Some(running.config.port),
+                clients: state.clients.len(),
+                messages_in: state.messages_in,
+                topics,
+            }
+        }
+        None => MqttBrokerStatus {
+            running: false,
+            bind: None,
+            port: None,
+            clients: 0,
+            messages_in: 0,
+            topics: Vec::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topic_filters_match_wildcards() {
+        assert!(topic_matches("wican/#", "wican/can0/rx"));
+        assert!(topic_matches("wican/+/rx", "wican/can0/rx"));
+        assert!(!topic_matches("wican/+/rx", "wican/can0/tx"));
+        assert!(topic_matches("#", "a/b"));
+        assert!(!topic_matches("#", "$SYS/uptime"));
+        assert!(!topic_matches("wican/+", "wican/can0/rx"));
+        assert!(topic_matches("wican/can0", "wican/can0"));
+    }
+
+    #[test]
+    fn remaining_length_uses_varint_encoding() {
+        let mut out = Vec::new();
+        encode_remaining_length(321, &mut out);
+        assert_eq!(out, vec![0xC1, 0x02]);
+        let p = publish_packet("a", b"xy", false);
+        assert_eq!(p, vec![0x30, 5, 0, 1, b'a', b'x', b'y']);
+    }
+}
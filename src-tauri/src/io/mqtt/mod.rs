@@ -2,8 +2,116 @@
 //
 // MQTT source for streaming CAN frames from an MQTT broker.
 // Supports SavvyCAN JSON format with optional CAN FD.
+// Any session can also be mirrored to a broker (publisher.rs).
+// With the `mqtt-broker` feature, WireTAP can also host the broker itself.
 
+#[cfg(feature = "mqtt-broker")]
+mod broker;
+pub mod publisher;
 mod reader;
+mod tls;
 
 // Re-export public items
 pub use reader::{MqttConfig, MqttSource};
+pub use publisher::{MqttPublishFormat, MqttPublishStatus};
+pub use tls::MqttTlsConfig;
+
+#[cfg(feature = "mqtt-broker")]
+pub use broker::MqttBrokerStatus;
+
+/// Stand-in status type so the commands below keep one signature when the
+/// broker is compiled out.
+#[cfg(not(feature = "mqtt-broker"))]
+pub type MqttBrokerStatus = serde_json::Value;
+
+#[cfg(not(feature = "mqtt-broker"))]
+const BROKER_DISABLED: &str = "This build of WireTAP does not include the embedded MQTT broker";
+
+/// Start the embedded MQTT broker. Defaults to 0.0.0.0:1883 so devices on
+/// the LAN can publish; point an MQTT profile at localhost:<port> to ingest.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn mqtt_broker_start(
+    port: Option<u16>,
+    bind: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+) -> Result<MqttBrokerStatus, String> {
+    #[cfg(feature = "mqtt-broker")]
+    {
+        broker::start(broker::BrokerConfig {
+            bind: bind.unwrap_or_else(|| "0.0.0.0".to_string()),
+            port: port.unwrap_or(1883),
+            username: username.filter(|u| !u.is_empty()),
+            password,
+        })
+        .await
+    }
+    #[cfg(not(feature = "mqtt-broker"))]
+    {
+        let _ = (port, bind, username, password);
+        Err(BROKER_DISABLED.to_string())
+    }
+}
+
+/// Stop the embedded MQTT broker, disconnecting its clients.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn mqtt_broker_stop() -> Result<MqttBrokerStatus, String> {
+    #[cfg(feature = "mqtt-broker")]
+    {
+        Ok(broker::stop())
+    }
+    #[cfg(not(feature = "mqtt-broker"))]
+    {
+        Err(BROKER_DISABLED.to_string())
+    }
+}
+
+/// Embedded broker state: clients, message count and per-topic activity.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_mqtt_broker_status() -> Result<MqttBrokerStatus, String> {
+    #[cfg(feature = "mqtt-broker")]
+    {
+        Ok(broker::status())
+    }
+    #[cfg(not(feature = "mqtt-broker"))]
+    {
+        Err(BROKER_DISABLED.to_string())
+    }
+}
+
+/// Mirror a session's frames to a broker topic. The broker connection comes
+/// from the MQTT profile `profile_id`; its subscription topic is ignored.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn mqtt_publish_start(
+    app: tauri::AppHandle,
+    session_id: String,
+    profile_id: String,
+    topic: String,
+    format: MqttPublishFormat,
+) -> Result<MqttPublishStatus, String> {
+    if crate::io::get_session_state(&session_id).await.is_none() {
+        return Err(format!("Session '{}' not found", session_id));
+    }
+    let settings = crate::settings::load_settings_sync(&app)?;
+    let profile = settings
+        .io_profiles
+        .iter()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| format!("Profile '{}' not found", profile_id))?;
+    if profile.kind != "mqtt" {
+        return Err(format!("Profile '{}' is not an MQTT profile", profile.name));
+    }
+    publisher::start(&session_id, crate::sessions::mqtt_config(profile), topic, format)
+}
+
+/// Stop mirroring a session. Returns whether it was publishing.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn mqtt_publish_stop(session_id: String) -> bool {
+    publisher::stop(&session_id)
+}
+
+/// Publisher state of a session (null when it isn't publishing).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_mqtt_publish_status(session_id: String) -> Option<MqttPublishStatus> {
+    publisher::status(&session_id)
+}
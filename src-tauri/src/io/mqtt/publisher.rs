@@ -0,0 +1,402 @@
+// ui/src-tauri/src/io/mqtt/publisher.rs
+//
+// MQTT publisher: mirrors a session's merged frame stream to a broker topic,
+// so remote colleagues or cloud pipelines can subscribe to a live capture.
+// Any session can publish, whatever its source; the broker connection
+// (host, credentials, TLS) comes from an MQTT profile.
+//
+// Frames are taken in capture_store::append_frames_to_session, after gating
+// and reassembly, so subscribers see what the session's capture sees. Only
+// CAN frames are published. Two formats:
+//
+// - `savvycan`: one JSON message per frame, in the format the MQTT source
+//   reads (plus a `timestamp` in µs). `{bus}` and `{id}` in the topic are
+//   replaced per frame, e.g. `wiretap/{bus}/{id}`.
+// - `binary`: each batch packed into as few messages as fit the outgoing
+//   packet limit. A message is a version byte (1) followed by records of
+//   timestamp_us (u64 LE), frame_id (u32 LE), bus (u8), flags (u8: 0x01
+//   extended, 0x02 FD, 0x04 BRS, 0x08 error, 0x10 transmitted), length (u8)
+//   and the data bytes.
+//
+// Publishing never blocks the frame path: messages go into the client's
+// request queue with try_publish, and whatever doesn't fit while the broker
+// is slow or unreachable is counted as dropped.
+
+use once_cell::sync::Lazy;
+use rumqttc::{AsyncClient, Event, MqttOptions, Outgoing, Packet, QoS, TlsConfiguration, Transport};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::time::Duration;
+
+use super::MqttConfig;
+use crate::io::FrameMessage;
+
+/// Messages queued for the event loop before further frames are dropped
+const REQUEST_QUEUE: usize = 1000;
+/// Largest binary message (rumqttc's default outgoing packet limit is 10 KiB)
+const MAX_BINARY_PAYLOAD: usize = 8 * 1024;
+/// Binary format version byte
+const BINARY_VERSION: u8 = 1;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Wire format of published frames
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MqttPublishFormat {
+    Savvycan,
+    Binary,
+}
+
+/// State of a session's publisher.
+#[derive(Clone, Debug, Serialize)]
+pub struct MqttPublishStatus {
+    pub session_id: String,
+    pub host: String,
+    pub port: u16,
+    pub topic: String,
+    pub format: MqttPublishFormat,
+    /// Connected to the broker
+    pub connected: bool,
+    /// Messages sent to the broker
+    pub published: u64,
+    /// Messages dropped because the request queue was full
+    pub dropped: u64,
+    /// Most recent connection error
+    pub last_error: Option<String>,
+}
+
+/// Counters shared with the event loop task
+#[derive(Default)]
+struct PublishStats {
+    connected: AtomicBool,
+    published: AtomicU64,
+    dropped: AtomicU64,
+    last_error: Mutex<Option<String>>,
+}
+
+struct Publisher {
+    client: AsyncClient,
+    host: String,
+    port: u16,
+    topic: String,
+    format: MqttPublishFormat,
+    stats: Arc<PublishStats>,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+impl Publisher {
+    fn status(&self, session_id: &str) -> MqttPublishStatus {
+        MqttPublishStatus {
+            session_id: session_id.to_string(),
+            host: self.host.clone(),
+            port: self.port,
+            topic: self.topic.clone(),
+            format: self.format,
+            connected: self.stats.connected.load(Ordering::Relaxed),
+            published: self.stats.published.load(Ordering::Relaxed),
+            dropped: self.stats.dropped.load(Ordering::Relaxed),
+            last_error: self.stats.last_error.lock().unwrap().clone(),
+        }
+    }
+
+    fn shutdown(self) {
+        let _ = self.client.try_disconnect();
+        self.task.abort();
+    }
+}
+
+/// Publishers by session ID
+static PUBLISHERS: Lazy<RwLock<HashMap<String, Publisher>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+/// SavvyCAN JSON message, as the MQTT source reads it
+#[derive(Serialize)]
+struct SavvycanFrame<'a> {
+    bus: u8,
+    id: u32,
+    dlc: u8,
+    data: &'a [u8],
+    extended: bool,
+    fd: bool,
+    timestamp: u64,
+}
+
+/// Frames a publisher sends: CAN only. SavvyCAN JSON has no way to mark an
+/// error frame, so those only go out in the binary format.
+fn publishable(frame: &FrameMessage, format: MqttPublishFormat) -> bool {
+    frame.protocol == "can" && (format == MqttPublishFormat::Binary || frame.is_error != Some(true))
+}
+
+/// One (topic, payload) per frame, `{bus}` and `{id}` filled in.
+fn encode_savvycan(topic: &str, frames: &[FrameMessage]) -> Vec<(String, Vec<u8>)> {
+    let per_frame_topic = topic.contains('{');
+    frames
+        .iter()
+        .filter(|f| publishable(f, MqttPublishFormat::Savvycan))
+        .filter_map(|f| {
+            let payload = serde_json::to_vec(&SavvycanFrame {
+                bus: f.bus,
+                id: f.frame_id,
+                dlc: f.dlc,
+                data: &f.bytes,
+                extended: f.is_extended,
+                fd: f.is_fd,
+                timestamp: f.timestamp_us,
+            })
+            .ok()?;
+            let topic = if per_frame_topic {
+                topic.replace("{bus}", &f.bus.to_string()).replace("{id}", &f.frame_id.to_string())
+            } else {
+                topic.to_string()
+            };
+            Some((topic, payload))
+        })
+        .collect()
+}
+
+/// The batch as binary messages of at most MAX_BINARY_PAYLOAD bytes.
+fn encode_binary(frames: &[FrameMessage]) -> Vec<Vec<u8>> {
+    let mut messages = Vec::new();
+    let mut current = vec![BINARY_VERSION];
+
+    for f in frames.iter().filter(|f| publishable(f, MqttPublishFormat::Binary)) {
+        let data = &f.bytes[..f.bytes.len().min(u8::MAX as usize)];
+        let record_len = 15 + data.len();
+        if current.len() > 1 && current.len() + record_len > MAX_BINARY_PAYLOAD {
+            messages.push(std::mem::replace(&mut current, vec![BINARY_VERSION]));
+        }
+
+        let mut flags = 0u8;
+        if f.is_extended {
+            flags |= 0x01;
+        }
+        if f.is_fd {
+            flags |= 0x02;
+        }
+        if f.brs == Some(true) {
+            flags |= 0x04;
+        }
+        if f.is_error == Some(true) {
+            flags |= 0x08;
+        }
+        if f.direction.as_deref() == Some("tx") {
+            flags |= 0x10;
+        }
+
+        current.extend_from_slice(&f.timestamp_us.to_le_bytes());
+        current.extend_from_slice(&f.frame_id.to_le_bytes());
+        current.extend_from_slice(&[f.bus, flags, data.len() as u8]);
+        current.extend_from_slice(data);
+    }
+
+    if current.len() > 1 {
+        messages.push(current);
+    }
+    messages
+}
+
+/// Placeholders only make sense when each frame is its own message.
+fn check_topic(topic: &str, format: MqttPublishFormat) -> Result<(), String> {
+    if topic.trim().is_empty() {
+        return Err("Publish topic is empty".to_string());
+    }
+    if topic.contains('+') || topic.contains('#') {
+        return Err(format!("Publish topic '{}' can't contain MQTT wildcards", topic));
+    }
+    if format == MqttPublishFormat::Binary && topic.contains('{') {
+        return Err("Topic placeholders are only available with the SavvyCAN format".to_string());
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Publisher lifecycle
+// ============================================================================
+
+/// Start mirroring a session to `topic` on the broker in `config`. Replaces
+/// the session's current publisher, if any.
+pub fn start(
+    session_id: &str,
+    config: MqttConfig,
+    topic: String,
+    format: MqttPublishFormat,
+) -> Result<MqttPublishStatus, String> {
+    check_topic(&topic, format)?;
+
+    let client_id = config
+        .client_id
+        .clone()
+        .unwrap_or_else(|| format!("wiretap-pub-{:x}", crate::io::now_us()));
+    let mut options = MqttOptions::new(client_id, &config.host, config.port);
+    options.set_keep_alive(Duration::from_secs(30));
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        options.set_credentials(username, password);
+    }
+    if let Some(tls) = &config.tls {
+        let tls_config = tls.client_config().map_err(|e| format!("MQTT TLS setup failed: {}", e))?;
+        options.set_transport(Transport::tls_with_config(TlsConfiguration::Rustls(Arc::new(tls_config))));
+    }
+
+    let (client, mut eventloop) = AsyncClient::new(options, REQUEST_QUEUE);
+    let stats = Arc::new(PublishStats::default());
+
+    let task_stats = stats.clone();
+    let task_session = session_id.to_string();
+    let task = tauri::async_runtime::spawn(async move {
+        loop {
+            match eventloop.poll().await {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    task_stats.connected.store(true, Ordering::Relaxed);
+                    *task_stats.last_error.lock().unwrap() = None;
+                }
+                Ok(Event::Outgoing(Outgoing::Publish(_))) => {
+                    task_stats.published.fetch_add(1, Ordering::Relaxed);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    if task_stats.connected.swap(false, Ordering::Relaxed) {
+                        tlog!("[MQTT:{}] Publisher lost the broker: {}", task_session, e);
+                    }
+                    *task_stats.last_error.lock().unwrap() = Some(e.to_string());
+                    // rumqttc reconnects on the next poll
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+    });
+
+    tlog!(
+        "[MQTT:{}] Publishing to {}:{} '{}' ({:?})",
+        session_id, config.host, config.port, topic, format
+    );
+
+    let publisher = Publisher {
+        client,
+        host: config.host,
+        port: config.port,
+        topic,
+        format,
+        stats,
+        task,
+    };
+    let status = publisher.status(session_id);
+    if let Some(previous) = PUBLISHERS.write().unwrap().insert(session_id.to_string(), publisher) {
+        previous.shutdown();
+    }
+    Ok(status)
+}
+
+/// Stop a session's publisher. Returns whether one was running.
+pub fn stop(session_id: &str) -> bool {
+    let removed = PUBLISHERS.write().unwrap().remove(session_id);
+    match removed {
+        Some(publisher) => {
+            tlog!("[MQTT:{}] Publisher stopped", session_id);
+            publisher.shutdown();
+            true
+        }
+        None => false,
+    }
+}
+
+/// State of a session's publisher (None when it isn't publishing).
+pub fn status(session_id: &str) -> Option<MqttPublishStatus> {
+    PUBLISHERS.read().unwrap().get(session_id).map(|p| p.status(session_id))
+}
+
+/// Called when a session is destroyed.
+pub fn clear_session(session_id: &str) {
+    stop(session_id);
+}
+
+/// Publish a batch of the session's frames. One read lock per batch for
+/// sessions that aren't publishing.
+pub fn tap_frames(session_id: &str, frames: &[FrameMessage]) {
+    let publishers = PUBLISHERS.read().unwrap();
+    let Some(publisher) = publishers.get(session_id) else {
+        return;
+    };
+
+    let messages = match publisher.format {
+        MqttPublishFormat::Savvycan => encode_savvycan(&publisher.topic, frames),
+        MqttPublishFormat::Binary => encode_binary(frames)
+            .into_iter()
+            .map(|payload| (publisher.topic.clone(), payload))
+            .collect(),
+    };
+
+    for (topic, payload) in messages {
+        if publisher.client.try_publish(topic, QoS::AtMostOnce, false, payload).is_err() {
+            publisher.stats.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(frame_id: u32, bus: u8, bytes: Vec<u8>) -> FrameMessage {
+        FrameMessage {
+            bus,
+            direction: Some("rx".to_string()),
+            ..test_frame(frame_id, 1_000, &bytes)
+        }
+    }
+
+    #[test]
+    fn test_savvycan_messages_fill_topic_placeholders() {
+        let mut error = frame(0x2000_0040, 0, vec![0; 8]);
+        error.is_error = Some(true);
+        let frames = vec![frame(0x123, 1, vec![1, 2]), error];
+
+        let messages = encode_savvycan("car/{bus}/{id}", &frames);
+        assert_eq!(messages.len(), 1, "error frames aren't published as SavvyCAN JSON");
+        assert_eq!(messages[0].0, "car/1/291");
+        let json: serde_json::Value = serde_json::from_slice(&messages[0].1).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"bus": 1, "id": 291, "dlc": 2, "data": [1, 2], "extended": false, "fd": false, "timestamp": 1000})
+        );
+    }
+
+    #[test]
+    fn test_binary_records_are_packed_and_split() {
+        let mut fd = frame(0x18DA_F110, 2, vec![0xAA; 12]);
+        fd.is_extended = true;
+        fd.is_fd = true;
+        fd.brs = Some(true);
+        let messages = encode_binary(&[fd]);
+        assert_eq!(messages.len(), 1);
+        let msg = &messages[0];
+        assert_eq!(msg[0], BINARY_VERSION);
+        assert_eq!(&msg[1..9], &1_000u64.to_le_bytes());
+        assert_eq!(&msg[9..13], &0x18DA_F110u32.to_le_bytes());
+        assert_eq!(&msg[13..16], &[2, 0x07, 12]);
+        assert_eq!(msg.len(), 1 + 15 + 12);
+
+        let many: Vec<_> = (0..1000).map(|i| frame(i, 0, vec![0; 8])).collect();
+        let messages = encode_binary(&many);
+        assert!(messages.len() > 1);
+        assert!(messages.iter().all(|m| m.len() <= MAX_BINARY_PAYLOAD && m[0] == BINARY_VERSION));
+        let records: usize = messages.iter().map(|m| (m.len() - 1) / 23).sum();
+        assert_eq!(records, 1000);
+    }
+
+    #[test]
+    fn test_topics_are_checked_against_the_format() {
+        assert!(check_topic("wiretap/{bus}/{id}", MqttPublishFormat::Savvycan).is_ok());
+        assert!(check_topic("wiretap/{bus}", MqttPublishFormat::Binary).is_err());
+        assert!(check_topic("wiretap/#", MqttPublishFormat::Savvycan).is_err());
+        assert!(check_topic(" ", MqttPublishFormat::Binary).is_err());
+    }
+}
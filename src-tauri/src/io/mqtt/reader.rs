@@ -14,7 +14,7 @@
 // }
 
 use async_trait::async_trait;
-use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS, TlsConfiguration, Transport};
 use serde::Deserialize;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
@@ -26,6 +26,8 @@ use tokio::time::Duration;
 use crate::io::{emit_device_connected, emit_session_error, emit_stream_ended, now_us, signal_frames_ready, FrameMessage, IOCapabilities, IOSource, IOState, Protocol, SignalThrottle};
 use crate::capture_store::{self, CaptureKind};
 
+use super::tls::MqttTlsConfig;
+
 // ============================================================================
 // Configuration
 // ============================================================================
@@ -45,6 +47,8 @@ pub struct MqttConfig {
     pub topic: String,
     /// Client ID (auto-generated if None)
     pub client_id: Option<String>,
+    /// TLS settings (None = plain TCP)
+    pub tls: Option<MqttTlsConfig>,
 }
 
 impl Default for MqttConfig {
@@ -56,6 +60,7 @@ impl Default for MqttConfig {
             password: None,
             topic: "wiretap/#".to_string(),
             client_id: None,
+            tls: None,
         }
     }
 }
@@ -254,6 +259,19 @@ fn spawn_mqtt_stream(
             mqttoptions.set_credentials(username, password);
         }
 
+        if let Some(tls) = &config.tls {
+            match tls.client_config() {
+                Ok(tls_config) => {
+                    mqttoptions.set_transport(Transport::tls_with_config(TlsConfiguration::Rustls(Arc::new(tls_config))));
+                }
+                Err(e) => {
+                    emit_session_error(&session_id, format!("MQTT TLS setup failed: {}", e));
+                    emit_stream_ended(&session_id, "error", "MQTT");
+                    return;
+                }
+            }
+        }
+
         // Create async client
         let (client, mut eventloop) = AsyncClient::new(mqttoptions, 100);
 
@@ -307,6 +325,11 @@ fn spawn_mqtt_stream(
                                     source_address: None,
                                     incomplete: None,
                                     direction: Some("rx".to_string()),
+                                    link: None,
+                                    clock: None,
+                                    is_error: None,
+                                    error_class: None,
+                                    brs: None,
                                 };
 
                                 // Buffer frame for replay
@@ -0,0 +1,169 @@
+// ui/src-tauri/src/io/mqtt/tls.rs
+//
+// TLS for MQTT connections. Builds the rustls client config handed to
+// rumqttc: the profile's CA certificate or the system roots, an optional
+// client certificate and key for brokers that require mutual TLS, ALPN
+// protocols (AWS IoT on port 443 wants "mqtt"), and a skip-verify switch
+// for test brokers with self-signed certificates.
+//
+// Certificates and keys are PEM text. The client certificate and key live
+// in the OS keyring (credential fields `tls_client_cert` / `tls_client_key`);
+// the CA certificate is public and stays in the profile.
+
+use std::sync::Arc;
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+
+/// TLS settings for an MQTT connection
+#[derive(Clone, Debug, Default)]
+pub struct MqttTlsConfig {
+    /// PEM CA certificate(s) to trust instead of the system roots
+    pub ca_cert: Option<String>,
+    /// PEM client certificate chain (mutual TLS)
+    pub client_cert: Option<String>,
+    /// PEM private key for the client certificate
+    pub client_key: Option<String>,
+    /// ALPN protocols to offer
+    pub alpn: Vec<String>,
+    /// Accept any server certificate. Testing only: the connection is
+    /// encrypted but the broker isn't authenticated.
+    pub insecure_skip_verify: bool,
+}
+
+impl MqttTlsConfig {
+    /// rustls client config for these settings.
+    pub fn client_config(&self) -> Result<ClientConfig, String> {
+        let builder = if self.insecure_skip_verify {
+            ClientConfig::builder()
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(AcceptAnyServerCert::new()))
+        } else {
+            ClientConfig::builder().with_root_certificates(self.root_store()?)
+        };
+
+        let mut config = match (&self.client_cert, &self.client_key) {
+            (Some(cert), Some(key)) => {
+                let chain = CertificateDer::pem_slice_iter(cert.as_bytes())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| format!("Invalid client certificate: {}", e))?;
+                if chain.is_empty() {
+                    return Err("Client certificate contains no PEM certificates".to_string());
+                }
+                let key = PrivateKeyDer::from_pem_slice(key.as_bytes())
+                    .map_err(|e| format!("Invalid client key: {}", e))?;
+                builder
+                    .with_client_auth_cert(chain, key)
+                    .map_err(|e| format!("Client certificate and key don't match: {}", e))?
+            }
+            (None, None) => builder.with_no_client_auth(),
+            _ => return Err("Mutual TLS needs both a client certificate and its key".to_string()),
+        };
+
+        config.alpn_protocols = self.alpn.iter().map(|p| p.as_bytes().to_vec()).collect();
+        Ok(config)
+    }
+
+    /// The profile's CA certificates, or the system's trusted roots.
+    fn root_store(&self) -> Result<RootCertStore, String> {
+        let mut roots = RootCertStore::empty();
+        match &self.ca_cert {
+            Some(pem) => {
+                for cert in CertificateDer::pem_slice_iter(pem.as_bytes()) {
+                    let cert = cert.map_err(|e| format!("Invalid CA certificate: {}", e))?;
+                    roots.add(cert).map_err(|e| format!("Invalid CA certificate: {}", e))?;
+                }
+                if roots.is_empty() {
+                    return Err("CA certificate contains no PEM certificates".to_string());
+                }
+            }
+            None => {
+                let native = rustls_native_certs::load_native_certs()
+                    .map_err(|e| format!("Failed to load system certificates: {}", e))?;
+                roots.add_parsable_certificates(native);
+            }
+        }
+        Ok(roots)
+    }
+}
+
+/// Certificate verifier for `insecure_skip_verify`: any certificate is
+/// accepted, but handshake signatures are still checked.
+#[derive(Debug)]
+struct AcceptAnyServerCert(CryptoProvider);
+
+impl AcceptAnyServerCert {
+    fn new() -> Self {
+        Self(rustls::crypto::ring::default_provider())
+    }
+}
+
+impl ServerCertVerifier for AcceptAnyServerCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insecure_config_offers_alpn() {
+        let tls = MqttTlsConfig {
+            alpn: vec!["mqtt".to_string()],
+            insecure_skip_verify: true,
+            ..Default::default()
+        };
+        let config = tls.client_config().unwrap();
+        assert_eq!(config.alpn_protocols, vec![b"mqtt".to_vec()]);
+    }
+
+    #[test]
+    fn test_bad_pem_and_half_a_client_identity_are_refused() {
+        let no_certs = MqttTlsConfig {
+            ca_cert: Some("not a certificate".to_string()),
+            ..Default::default()
+        };
+        assert!(no_certs.client_config().unwrap_err().contains("CA certificate"));
+
+        let cert_only = MqttTlsConfig {
+            client_cert: Some("-----BEGIN CERTIFICATE-----".to_string()),
+            insecure_skip_verify: true,
+            ..Default::default()
+        };
+        assert!(cert_only.client_config().unwrap_err().contains("both"));
+    }
+}
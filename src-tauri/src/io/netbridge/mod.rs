@@ -0,0 +1,290 @@
+// ui/src-tauri/src/io/netbridge/mod.rs
+//
+// WireTAP-to-WireTAP network bridge. A publisher serves one session's merged
+// frame stream over TCP (publisher.rs); a `wiretap_net` profile on another
+// machine connects to it and streams those frames into its own session
+// (reader.rs). A laptop in the garage captures, a desktop in the office views.
+//
+// Protocol: on connect the publisher sends the 8-byte greeting `WTAPNET` + a
+// version byte, then a sequence of messages, each a u32 LE payload length
+// followed by the payload. A payload is a u16 LE record count and that many
+// records (all integers little-endian):
+//
+//   u64 timestamp_us, u32 frame_id, u8 bus, u8 dlc, u8 flags, u16 source_address,
+//   u8 protocol length + protocol, u16 data length + data
+//
+// flags: bit 0 extended, bit 1 FD, bit 2 transmitted by the publisher, bit 3
+// incomplete, bit 4 source_address present. Frame links are not carried; the
+// receiving session derives its own. The stream is one-way and unauthenticated,
+// so only publish on networks you trust. Receive only.
+
+pub mod publisher;
+pub mod reader;
+
+use serde_json::Value;
+
+use crate::io::FrameMessage;
+
+pub(crate) use publisher::{clear_session, publish};
+pub(crate) use reader::run_source as run_netbridge_source;
+pub use reader::probe_publisher;
+
+/// Default TCP port a publisher listens on
+pub const DEFAULT_PORT: u16 = 1339;
+
+/// Sent by the publisher when a client connects
+pub const GREETING: &[u8; 8] = b"WTAPNET\x01";
+
+/// Largest payload accepted from a publisher
+pub const MAX_PAYLOAD: usize = 16 * 1024 * 1024;
+
+/// Most records in one message
+const MAX_RECORDS: usize = u16::MAX as usize;
+
+const FLAG_EXTENDED: u8 = 0x01;
+const FLAG_FD: u8 = 0x02;
+const FLAG_TX: u8 = 0x04;
+const FLAG_INCOMPLETE: u8 = 0x08;
+const FLAG_SOURCE_ADDRESS: u8 = 0x10;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Bridge client settings, parsed from a `wiretap_net` profile's connection
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetBridgeConfig {
+    pub host: String,
+    pub port: u16,
+    /// Connect timeout, and the silence after which the source logs that no
+    /// data is arriving
+    pub timeout_sec: f64,
+}
+
+fn number(connection: &Value, key: &str) -> Option<f64> {
+    connection
+        .get(key)
+        .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+}
+
+impl NetBridgeConfig {
+    /// Read the settings from a profile's connection map.
+    pub fn from_connection(connection: &Value) -> Result<Self, String> {
+        let host = connection
+            .get("host")
+            .and_then(|v| v.as_str())
+            .map(|h| h.trim())
+            .filter(|h| !h.is_empty())
+            .ok_or("Host is required")?
+            .to_string();
+        let port = number(connection, "port").unwrap_or(DEFAULT_PORT as f64);
+        if !(1.0..=65535.0).contains(&port) {
+            return Err(format!("Invalid TCP port {}", port));
+        }
+        Ok(NetBridgeConfig {
+            host,
+            port: port as u16,
+            timeout_sec: number(connection, "timeout").unwrap_or(5.0).max(0.5),
+        })
+    }
+}
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+/// Encode frames as length-prefixed messages (more than one if there are
+/// more frames than a message can count).
+pub fn encode_frames(frames: &[FrameMessage]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for chunk in frames.chunks(MAX_RECORDS) {
+        let start = out.len();
+        out.extend_from_slice(&[0u8; 4]);
+        out.extend_from_slice(&(chunk.len() as u16).to_le_bytes());
+        for frame in chunk {
+            encode_record(&mut out, frame);
+        }
+        let len = (out.len() - start - 4) as u32;
+        out[start..start + 4].copy_from_slice(&len.to_le_bytes());
+    }
+    out
+}
+
+fn encode_record(out: &mut Vec<u8>, frame: &FrameMessage) {
+    let mut flags = 0u8;
+    if frame.is_extended {
+        flags |= FLAG_EXTENDED;
+    }
+    if frame.is_fd {
+        flags |= FLAG_FD;
+    }
+    if frame.direction.as_deref() == Some("tx") {
+        flags |= FLAG_TX;
+    }
+    if frame.incomplete == Some(true) {
+        flags |= FLAG_INCOMPLETE;
+    }
+    if frame.source_address.is_some() {
+        flags |= FLAG_SOURCE_ADDRESS;
+    }
+    let protocol = &frame.protocol.as_bytes()[..frame.protocol.len().min(u8::MAX as usize)];
+    let data = &frame.bytes[..frame.bytes.len().min(u16::MAX as usize)];
+
+    out.extend_from_slice(&frame.timestamp_us.to_le_bytes());
+    out.extend_from_slice(&frame.frame_id.to_le_bytes());
+    out.push(frame.bus);
+    out.push(frame.dlc);
+    out.push(flags);
+    out.extend_from_slice(&frame.source_address.unwrap_or(0).to_le_bytes());
+    out.push(protocol.len() as u8);
+    out.extend_from_slice(protocol);
+    out.extend_from_slice(&(data.len() as u16).to_le_bytes());
+    out.extend_from_slice(data);
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+/// Take the next whole message payload off the front of `buf`, if one has
+/// arrived.
+pub fn take_message(buf: &mut Vec<u8>) -> Result<Option<Vec<u8>>, String> {
+    if buf.len() < 4 {
+        return Ok(None);
+    }
+    let len = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+    if len > MAX_PAYLOAD {
+        return Err(format!("Message of {} bytes exceeds the {} byte limit", len, MAX_PAYLOAD));
+    }
+    if buf.len() < 4 + len {
+        return Ok(None);
+    }
+    let payload = buf[4..4 + len].to_vec();
+    buf.drain(..4 + len);
+    Ok(Some(payload))
+}
+
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos + n;
+        let bytes = self.data.get(self.pos..end).ok_or("Truncated record")?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+/// Decode one message payload into frames.
+pub fn decode_payload(payload: &[u8]) -> Result<Vec<FrameMessage>, String> {
+    let mut cursor = Cursor { data: payload, pos: 0 };
+    let count = cursor.u16()? as usize;
+    let mut frames = Vec::with_capacity(count);
+    for _ in 0..count {
+        let timestamp_us = cursor.u64()?;
+        let frame_id = cursor.u32()?;
+        let bus = cursor.u8()?;
+        let dlc = cursor.u8()?;
+        let flags = cursor.u8()?;
+        let source_address = cursor.u16()?;
+        let protocol_len = cursor.u8()? as usize;
+        let protocol = String::from_utf8_lossy(cursor.take(protocol_len)?).to_string();
+        let data_len = cursor.u16()? as usize;
+        let bytes = cursor.take(data_len)?.to_vec();
+        frames.push(FrameMessage {
+            protocol,
+            timestamp_us,
+            frame_id,
+            bus,
+            dlc,
+            bytes,
+            is_extended: flags & FLAG_EXTENDED != 0,
+            is_fd: flags & FLAG_FD != 0,
+            source_address: (flags & FLAG_SOURCE_ADDRESS != 0).then_some(source_address),
+            incomplete: (flags & FLAG_INCOMPLETE != 0).then_some(true),
+            direction: Some(if flags & FLAG_TX != 0 { "tx" } else { "rx" }.to_string()),
+            link: None,
+            clock: None,
+            is_error: None,
+            error_class: None,
+            brs: None,
+        });
+    }
+    Ok(frames)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+    use serde_json::json;
+
+    fn frame(frame_id: u32, bytes: &[u8]) -> FrameMessage {
+        FrameMessage {
+            bus: 2,
+            direction: Some("rx".to_string()),
+            ..test_frame(frame_id, 1_700_000_000_123_456, bytes)
+        }
+    }
+
+    #[test]
+    fn test_frames_round_trip() {
+        let mut fd = frame(0x18DA_F110, &[0xAA; 12]);
+        fd.is_extended = true;
+        fd.is_fd = true;
+        fd.direction = Some("tx".to_string());
+        fd.source_address = Some(0x10);
+        let frames = vec![frame(0x123, &[1, 2, 3]), fd];
+
+        let mut buf = encode_frames(&frames);
+        buf.extend_from_slice(&[7, 0]); // start of the next message
+        let payload = take_message(&mut buf).unwrap().unwrap();
+        assert_eq!(buf, vec![7, 0]);
+        let decoded = decode_payload(&payload).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&frames).unwrap());
+        assert_eq!(take_message(&mut buf).unwrap(), None);
+    }
+
+    #[test]
+    fn test_bad_messages_are_rejected() {
+        let mut oversized = ((MAX_PAYLOAD + 1) as u32).to_le_bytes().to_vec();
+        assert!(take_message(&mut oversized).is_err());
+
+        let mut payload = encode_frames(&[frame(0x1, &[1, 2, 3, 4])]).split_off(4);
+        payload.truncate(payload.len() - 2);
+        assert!(decode_payload(&payload).is_err());
+    }
+
+    #[test]
+    fn test_config_from_connection() {
+        let config = NetBridgeConfig::from_connection(&json!({"host": " garage-pi.local "})).unwrap();
+        assert_eq!(config.host, "garage-pi.local");
+        assert_eq!(config.port, DEFAULT_PORT);
+        assert_eq!(NetBridgeConfig::from_connection(&json!({"host": "x", "port": "1400"})).unwrap().port, 1400);
+        assert!(NetBridgeConfig::from_connection(&json!({})).is_err());
+        assert!(NetBridgeConfig::from_connection(&json!({"host": "x", "port": 0})).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+// ui/src-tauri/src/io/netbridge/publisher.rs
+//
+// Network publisher: serves a session's merged frame stream to bridge clients
+// over TCP. The merge task hands every batch to `publish`, which encodes it
+// once and broadcasts it to the session's connected clients. A client that
+// falls too far behind skips the batches it missed rather than holding the
+// session up. Publishing stops with the session.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+
+use super::{encode_frames, DEFAULT_PORT, GREETING};
+use crate::io::FrameMessage;
+
+/// Encoded batches buffered per client before it starts skipping
+const CLIENT_BACKLOG: usize = 256;
+
+struct Publisher {
+    bind_address: String,
+    port: u16,
+    batches: broadcast::Sender<Arc<Vec<u8>>>,
+    clients: Arc<AtomicUsize>,
+    cancel: CancellationToken,
+}
+
+static PUBLISHERS: Lazy<Mutex<HashMap<String, Publisher>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Number of sessions publishing, so `publish` costs nothing otherwise
+static ACTIVE_PUBLISHERS: AtomicUsize = AtomicUsize::new(0);
+
+/// A session being published
+#[derive(Clone, Debug, Serialize)]
+pub struct NetworkPublisherInfo {
+    pub session_id: String,
+    pub bind_address: String,
+    pub port: u16,
+    /// Bridge clients currently connected
+    pub clients: usize,
+}
+
+impl Publisher {
+    fn info(&self, session_id: &str) -> NetworkPublisherInfo {
+        NetworkPublisherInfo {
+            session_id: session_id.to_string(),
+            bind_address: self.bind_address.clone(),
+            port: self.port,
+            clients: self.clients.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Send a batch of the session's frames to its bridge clients, if it is
+/// being published.
+pub(crate) fn publish(session_id: &str, frames: &[FrameMessage]) {
+    if ACTIVE_PUBLISHERS.load(Ordering::Relaxed) == 0 || frames.is_empty() {
+        return;
+    }
+    let Ok(publishers) = PUBLISHERS.lock() else { return };
+    if let Some(publisher) = publishers.get(session_id) {
+        if publisher.batches.receiver_count() > 0 {
+            let _ = publisher.batches.send(Arc::new(encode_frames(frames)));
+        }
+    }
+}
+
+fn remove(session_id: &str) -> Option<Publisher> {
+    let removed = PUBLISHERS.lock().ok()?.remove(session_id);
+    if let Some(publisher) = &removed {
+        ACTIVE_PUBLISHERS.fetch_sub(1, Ordering::Relaxed);
+        publisher.cancel.cancel();
+        tlog!("[netbridge] Stopped publishing session '{}' on port {}", session_id, publisher.port);
+    }
+    removed
+}
+
+/// Stop publishing a session that is being destroyed
+pub(crate) fn clear_session(session_id: &str) {
+    remove(session_id);
+}
+
+async fn serve_client(
+    mut stream: TcpStream,
+    mut batches: broadcast::Receiver<Arc<Vec<u8>>>,
+    cancel: CancellationToken,
+    session_id: String,
+    peer: String,
+) {
+    let _ = stream.set_nodelay(true);
+    if stream.write_all(GREETING).await.is_err() {
+        return;
+    }
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            batch = batches.recv() => match batch {
+                Ok(bytes) => {
+                    if let Err(e) = stream.write_all(&bytes).await {
+                        tlog!("[netbridge] Client {} of '{}' dropped: {}", peer, session_id, e);
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tlog!("[netbridge] Client {} of '{}' fell behind, skipped {} batches", peer, session_id, skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+    let _ = stream.shutdown().await;
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    batches: broadcast::Sender<Arc<Vec<u8>>>,
+    clients: Arc<AtomicUsize>,
+    cancel: CancellationToken,
+    session_id: String,
+) {
+    loop {
+        let (stream, peer) = tokio::select! {
+            _ = cancel.cancelled() => break,
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    tlog!("[netbridge] Accept failed for '{}': {}", session_id, e);
+                    continue;
+                }
+            },
+        };
+        tlog!("[netbridge] Client {} connected to '{}'", peer, session_id);
+        let receiver = batches.subscribe();
+        let clients = clients.clone();
+        let cancel = cancel.clone();
+        let session_id = session_id.clone();
+        clients.fetch_add(1, Ordering::Relaxed);
+        tauri::async_runtime::spawn(async move {
+            serve_client(stream, receiver, cancel, session_id, peer.to_string()).await;
+            clients.fetch_sub(1, Ordering::Relaxed);
+        });
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Start serving a session's frames to bridge clients. Listens on all
+/// interfaces unless `bind_address` says otherwise; port 0 picks a free port.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn start_network_publisher(
+    session_id: String,
+    port: Option<u16>,
+    bind_address: Option<String>,
+) -> Result<NetworkPublisherInfo, String> {
+    if !crate::io::session_exists(&session_id).await {
+        return Err(format!("Session '{}' not found", session_id));
+    }
+    let bind_address = bind_address.filter(|a| !a.trim().is_empty()).unwrap_or_else(|| "0.0.0.0".to_string());
+    let port = port.unwrap_or(DEFAULT_PORT);
+    remove(&session_id);
+
+    let listener = TcpListener::bind((bind_address.as_str(), port))
+        .await
+        .map_err(|e| format!("Failed to listen on {}:{}: {}", bind_address, port, e))?;
+    let port = listener.local_addr().map(|a| a.port()).unwrap_or(port);
+
+    let (batches, _) = broadcast::channel(CLIENT_BACKLOG);
+    let clients = Arc::new(AtomicUsize::new(0));
+    let cancel = CancellationToken::new();
+    tauri::async_runtime::spawn(accept_loop(listener, batches.clone(), clients.clone(), cancel.clone(), session_id.clone()));
+
+    let publisher = Publisher { bind_address, port, batches, clients, cancel };
+    let info = publisher.info(&session_id);
+    let replaced = PUBLISHERS
+        .lock()
+        .map_err(|e| format!("Failed to lock publishers: {}", e))?
+        .insert(session_id.clone(), publisher);
+    match replaced {
+        // Another start for this session won the race; keep the newer one
+        Some(old) => old.cancel.cancel(),
+        None => {
+            ACTIVE_PUBLISHERS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    tlog!("[netbridge] Publishing session '{}' on {}:{}", session_id, info.bind_address, port);
+    Ok(info)
+}
+
+/// Stop serving a session's frames. Connected clients are disconnected.
+#[tauri::command(rename_all = "snake_case")]
+pub fn stop_network_publisher(session_id: String) -> bool {
+    remove(&session_id).is_some()
+}
+
+/// Sessions currently being published
+#[tauri::command(rename_all = "snake_case")]
+pub fn list_network_publishers() -> Vec<NetworkPublisherInfo> {
+    let mut list: Vec<NetworkPublisherInfo> = PUBLISHERS
+        .lock()
+        .map(|p| p.iter().map(|(id, publisher)| publisher.info(id)).collect())
+        .unwrap_or_default();
+    list.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+    list
+}
@@ -0,0 +1,159 @@
+// ui/src-tauri/src/io/netbridge/reader.rs
+//
+// TCP reader for a WireTAP network publisher. Checks the greeting, then
+// decodes length-prefixed messages as they arrive. The publisher's bus
+// numbers come through as they are, subject to the profile's bus mapping.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+
+use super::{decode_payload, take_message, NetBridgeConfig, GREETING};
+use crate::io::error::IoError;
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::FrameMessage;
+use crate::io::types::SourceMessage;
+
+/// Bytes read from the socket at a time
+const READ_CHUNK: usize = 64 * 1024;
+
+/// Connect to a publisher and check its greeting.
+async fn connect(host: &str, port: u16, timeout_sec: f64) -> Result<TcpStream, IoError> {
+    let device = format!("wiretap_net({}:{})", host, port);
+    let timeout = Duration::from_secs_f64(timeout_sec);
+    let mut stream = tokio::time::timeout(timeout, TcpStream::connect((host, port)))
+        .await
+        .map_err(|_| IoError::timeout(&device, "connect"))?
+        .map_err(|e| IoError::connection(&device, e.to_string()))?;
+    let _ = stream.set_nodelay(true);
+
+    let mut greeting = [0u8; GREETING.len()];
+    tokio::time::timeout(timeout, stream.read_exact(&mut greeting))
+        .await
+        .map_err(|_| IoError::timeout(&device, "greeting"))?
+        .map_err(|e| IoError::read(&device, e.to_string()))?;
+    if greeting[..7] != GREETING[..7] {
+        return Err(IoError::protocol(&device, "not a WireTAP network publisher"));
+    }
+    if greeting[7] != GREETING[7] {
+        return Err(IoError::protocol(
+            &device,
+            format!("unsupported bridge protocol version {} (expected {})", greeting[7], GREETING[7]),
+        ));
+    }
+    Ok(stream)
+}
+
+// ============================================================================
+// Device Probing
+// ============================================================================
+
+/// Connect and check that a publisher answers.
+pub async fn probe_publisher(host: &str, port: u16, timeout_sec: f64) -> Result<(), IoError> {
+    connect(host, port, timeout_sec).await.map(|_| ())
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Run a bridge client source and send frames to the merge task
+pub async fn run_source(
+    source_idx: usize,
+    config: NetBridgeConfig,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let mut stream = match connect(&config.host, config.port, config.timeout_sec).await {
+        Ok(s) => s,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e.to_string())).await;
+            return;
+        }
+    };
+
+    let address = format!("{}:{}", config.host, config.port);
+    tlog!("[netbridge] Source {} streaming from {}", source_idx, address);
+    let _ = tx
+        .send(SourceMessage::Connected(source_idx, "wiretap_net".to_string(), address.clone(), None))
+        .await;
+
+    let silence_limit = Duration::from_secs_f64(config.timeout_sec);
+    let mut last_data = Instant::now();
+    let mut silent = false;
+    let mut rx_frames: u64 = 0;
+    let mut buf: Vec<u8> = Vec::with_capacity(READ_CHUNK);
+    let mut chunk = vec![0u8; READ_CHUNK];
+    let mut end_reason = "stopped".to_string();
+
+    'read: while !stop_flag.load(Ordering::SeqCst) {
+        match tokio::time::timeout(Duration::from_millis(50), stream.read(&mut chunk)).await {
+            Ok(Ok(0)) => {
+                end_reason = "publisher closed the connection".to_string();
+                break;
+            }
+            Ok(Ok(n)) => {
+                last_data = Instant::now();
+                if silent {
+                    tlog!("[netbridge] Source {}: data resumed from {}", source_idx, address);
+                    silent = false;
+                }
+                buf.extend_from_slice(&chunk[..n]);
+                loop {
+                    let payload = match take_message(&mut buf) {
+                        Ok(Some(payload)) => payload,
+                        Ok(None) => break,
+                        Err(e) => {
+                            let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+                            return;
+                        }
+                    };
+                    let frames = match decode_payload(&payload) {
+                        Ok(frames) => frames,
+                        Err(e) => {
+                            let _ = tx.send(SourceMessage::Error(source_idx, format!("Bad message: {}", e))).await;
+                            return;
+                        }
+                    };
+                    rx_frames += frames.len() as u64;
+                    let mapped: Vec<FrameMessage> = frames
+                        .into_iter()
+                        .filter_map(|mut frame| apply_bus_mapping(&mut frame, &bus_mappings).then_some(frame))
+                        .collect();
+                    if !mapped.is_empty() && tx.send(SourceMessage::Frames(source_idx, mapped)).await.is_err() {
+                        break 'read;
+                    }
+                }
+            }
+            Ok(Err(e)) => {
+                let _ = tx
+                    .send(SourceMessage::Error(source_idx, format!("Read error: {}", e)))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                // Timeout - continue
+            }
+        }
+
+        // A quiet bus is normal; note the silence once
+        if !silent && last_data.elapsed() >= silence_limit {
+            tlog!(
+                "[netbridge] Source {}: no data from {} for {:.1}s",
+                source_idx, address, silence_limit.as_secs_f64()
+            );
+            silent = true;
+        }
+    }
+
+    tlog!("[netbridge] Source {} ended: rx={} ({})", source_idx, rx_frames, end_reason);
+
+    let _ = tx.send(SourceMessage::Ended(source_idx, end_reason)).await;
+}
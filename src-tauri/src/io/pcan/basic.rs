@@ -0,0 +1,357 @@
+// src-tauri/src/io/pcan/basic.rs
+//
+// PCAN-USB reader using PEAK's PCAN-Basic library (Windows and macOS).
+//
+// The library is loaded at runtime with libloading so a missing install only
+// fails the PCAN source, not the app. PCAN-Basic is a polling API: CAN_Read
+// returns QRCVEMPTY when the receive queue is drained, so the read loop drains
+// the queue into one batch and sleeps briefly when it's empty.
+
+use once_cell::sync::OnceCell;
+use std::os::raw::{c_char, c_void};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::{
+    baud_code, channel_handle, decode_msg, param, status, PcanDeviceInfo, PcanMsg, PcanTimestamp,
+    ERROR_TEXT_LANGUAGE, USB_CHANNEL_HANDLES,
+};
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::types::{SourceMessage, TransmitRequest};
+
+/// Library file name per platform
+#[cfg(target_os = "windows")]
+const LIBRARY_NAME: &str = "PCANBasic.dll";
+#[cfg(target_os = "macos")]
+const LIBRARY_NAME: &str = "libPCBUSB.dylib";
+
+/// Sleep between polls when the receive queue is empty.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Upper bound on frames drained per batch, so a flooded queue still yields.
+const MAX_BATCH: usize = 512;
+
+type InitializeFn = unsafe extern "system" fn(u16, u16, u8, u32, u16) -> u32;
+type UninitializeFn = unsafe extern "system" fn(u16) -> u32;
+type ReadFn = unsafe extern "system" fn(u16, *mut PcanMsg, *mut PcanTimestamp) -> u32;
+type WriteFn = unsafe extern "system" fn(u16, *mut PcanMsg) -> u32;
+type GetValueFn = unsafe extern "system" fn(u16, u8, *mut c_void, u32) -> u32;
+type SetValueFn = unsafe extern "system" fn(u16, u8, *mut c_void, u32) -> u32;
+type GetErrorTextFn = unsafe extern "system" fn(u32, u16, *mut c_char) -> u32;
+
+/// Entry points resolved from the PCAN-Basic library.
+struct PcanBasic {
+    // Keeps the library mapped for as long as the function pointers live
+    _lib: libloading::Library,
+    initialize: InitializeFn,
+    uninitialize: UninitializeFn,
+    read: ReadFn,
+    write: WriteFn,
+    get_value: GetValueFn,
+    set_value: SetValueFn,
+    get_error_text: GetErrorTextFn,
+}
+
+static PCAN_BASIC: OnceCell<Result<PcanBasic, String>> = OnceCell::new();
+
+impl PcanBasic {
+    /// Load PCAN-Basic once per process.
+    fn get() -> Result<&'static PcanBasic, String> {
+        PCAN_BASIC
+            .get_or_init(|| unsafe { Self::load() })
+            .as_ref()
+            .map_err(|e| e.clone())
+    }
+
+    unsafe fn load() -> Result<PcanBasic, String> {
+        let lib = libloading::Library::new(LIBRARY_NAME).map_err(|e| {
+            format!(
+                "PCAN-Basic library ({}) not found — install the PEAK driver package: {}",
+                LIBRARY_NAME, e
+            )
+        })?;
+        macro_rules! sym {
+            ($name:literal) => {
+                *lib.get($name).map_err(|e| format!("PCAN-Basic is missing {}: {}", String::from_utf8_lossy($name), e))?
+            };
+        }
+        Ok(PcanBasic {
+            initialize: sym!(b"CAN_Initialize"),
+            uninitialize: sym!(b"CAN_Uninitialize"),
+            read: sym!(b"CAN_Read"),
+            write: sym!(b"CAN_Write"),
+            get_value: sym!(b"CAN_GetValue"),
+            set_value: sym!(b"CAN_SetValue"),
+            get_error_text: sym!(b"CAN_GetErrorText"),
+            _lib: lib,
+        })
+    }
+
+    fn error_text(&self, code: u32) -> String {
+        let mut buf = [0 as c_char; 256];
+        let rc = unsafe { (self.get_error_text)(code, ERROR_TEXT_LANGUAGE, buf.as_mut_ptr()) };
+        if rc != status::OK {
+            return format!("PCAN error 0x{:05X}", code);
+        }
+        let bytes: Vec<u8> = buf.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    fn check(&self, code: u32) -> Result<(), String> {
+        if code == status::OK {
+            Ok(())
+        } else {
+            Err(self.error_text(code))
+        }
+    }
+
+    fn get_u32(&self, handle: u16, parameter: u8) -> Result<u32, String> {
+        let mut value: u32 = 0;
+        let rc = unsafe {
+            (self.get_value)(handle, parameter, &mut value as *mut u32 as *mut c_void, 4)
+        };
+        self.check(rc).map(|_| value)
+    }
+
+    fn get_string(&self, handle: u16, parameter: u8) -> Result<String, String> {
+        let mut buf = [0u8; 256];
+        let rc = unsafe {
+            (self.get_value)(handle, parameter, buf.as_mut_ptr() as *mut c_void, buf.len() as u32)
+        };
+        self.check(rc)?;
+        let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+        Ok(String::from_utf8_lossy(&buf[..end]).into_owned())
+    }
+
+    fn set_u32(&self, handle: u16, parameter: u8, value: u32) -> Result<(), String> {
+        let mut value = value;
+        let rc = unsafe {
+            (self.set_value)(handle, parameter, &mut value as *mut u32 as *mut c_void, 4)
+        };
+        self.check(rc)
+    }
+}
+
+// ============================================================================
+// Device Enumeration
+// ============================================================================
+
+/// List PCAN-USB channels that are plugged in (available or occupied).
+pub fn list_devices() -> Result<Vec<PcanDeviceInfo>, String> {
+    let api = PcanBasic::get()?;
+    let mut devices = Vec::new();
+    for (idx, &handle) in USB_CHANNEL_HANDLES.iter().enumerate() {
+        let condition = match api.get_u32(handle, param::CHANNEL_CONDITION) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        if condition & (param::CHANNEL_AVAILABLE | param::CHANNEL_OCCUPIED) == 0 {
+            continue;
+        }
+        devices.push(PcanDeviceInfo {
+            channel: idx as u8 + 1,
+            hardware_name: api
+                .get_string(handle, param::HARDWARE_NAME)
+                .unwrap_or_else(|_| "PCAN-USB".to_string()),
+            device_id: api.get_u32(handle, param::DEVICE_ID).ok(),
+            occupied: condition & param::CHANNEL_OCCUPIED != 0,
+            interface_name: None,
+            interface_up: None,
+        });
+    }
+    Ok(devices)
+}
+
+/// Resolve the channel handle for a profile: by device ID when one is set
+/// (stable across replugging), otherwise by channel number.
+fn resolve_handle(api: &PcanBasic, channel: u8, device_id: Option<u32>) -> Result<u16, String> {
+    if let Some(id) = device_id {
+        return USB_CHANNEL_HANDLES
+            .iter()
+            .copied()
+            .find(|&h| api.get_u32(h, param::DEVICE_ID).ok() == Some(id))
+            .ok_or_else(|| format!("No PCAN-USB channel with device ID {} is connected", id));
+    }
+    channel_handle(channel).ok_or_else(|| format!("Invalid PCAN-USB channel {} (expected 1-16)", channel))
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Run a PCAN-USB source and feed frames to the merge task.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_source(
+    source_idx: usize,
+    channel: u8,
+    device_id: Option<u32>,
+    bitrate: u32,
+    listen_only: bool,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let api = match PcanBasic::get() {
+        Ok(api) => api,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+            return;
+        }
+    };
+
+    let handle = match resolve_handle(api, channel, device_id) {
+        Ok(h) => h,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+            return;
+        }
+    };
+
+    let Some(baud) = baud_code(bitrate) else {
+        let _ = tx
+            .send(SourceMessage::Error(
+                source_idx,
+                format!("Unsupported PCAN bitrate {} bit/s", bitrate),
+            ))
+            .await;
+        return;
+    };
+
+    // Listen-only must be set before the channel is initialised
+    if let Err(e) = api.set_u32(handle, param::LISTEN_ONLY, if listen_only { param::ON } else { param::OFF }) {
+        tlog!("[pcan] Source {} warning: could not set listen-only: {}", source_idx, e);
+    }
+
+    let rc = unsafe { (api.initialize)(handle, baud, 0, 0, 0) };
+    if let Err(e) = api.check(rc) {
+        let _ = tx
+            .send(SourceMessage::Error(
+                source_idx,
+                format!("Failed to initialize PCAN channel: {}", e),
+            ))
+            .await;
+        return;
+    }
+    let _ = api.set_u32(handle, param::BUSOFF_AUTORESET, param::ON);
+
+    tlog!(
+        "[pcan] Source {} connected to handle 0x{:03X} (bitrate: {}, listen_only: {})",
+        source_idx, handle, bitrate, listen_only
+    );
+
+    let _ = tx
+        .send(SourceMessage::Connected(
+            source_idx,
+            "pcan".to_string(),
+            format!("PCAN_USBBUS{}", channel),
+            Some(0),
+        ))
+        .await;
+
+    // Setup transmit channel if not in listen-only mode
+    let transmit_task = if !listen_only {
+        let (transmit_tx, transmit_rx) = std_mpsc::sync_channel::<TransmitRequest>(32);
+        let _ = tx
+            .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
+            .await;
+
+        let stop_flag_for_transmit = stop_flag.clone();
+        Some(tokio::task::spawn_blocking(move || {
+            while !stop_flag_for_transmit.load(Ordering::Relaxed) {
+                match transmit_rx.recv_timeout(Duration::from_millis(10)) {
+                    Ok(req) => {
+                        let result = match PcanMsg::from_bytes(&req.data) {
+                            Some(mut msg) => {
+                                let rc = unsafe { (api.write)(handle, &mut msg) };
+                                // Bus state bits alone don't mean the write failed
+                                api.check(rc & !status::BUS_STATE_MASK)
+                                    .map_err(|e| format!("Write failed: {}", e))
+                            }
+                            None => Err("Malformed PCAN transmit request".to_string()),
+                        };
+                        let _ = req.result_tx.try_send(result);
+                    }
+                    Err(std_mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(std_mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        }))
+    } else {
+        None
+    };
+
+    // Read loop
+    let mut rx_frames: u64 = 0;
+    let mut bus_filtered: u64 = 0;
+    let mut last_bus_state = status::OK;
+
+    'read: while !stop_flag.load(Ordering::Relaxed) {
+        let mut batch = Vec::new();
+        while batch.len() < MAX_BATCH {
+            let mut msg = PcanMsg::default();
+            let mut timestamp = PcanTimestamp::default();
+            let rc = unsafe { (api.read)(handle, &mut msg, &mut timestamp) };
+
+            if rc == status::QRCVEMPTY {
+                break;
+            }
+
+            // Report bus state transitions (bus-heavy, bus-off) once each
+            let bus_state = rc & status::BUS_STATE_MASK;
+            if bus_state != last_bus_state {
+                if bus_state != status::OK {
+                    tlog!("[pcan] Source {} bus state: {}", source_idx, api.error_text(bus_state));
+                }
+                last_bus_state = bus_state;
+            }
+
+            let rc = rc & !status::BUS_STATE_MASK;
+            if rc != status::OK && rc != status::QRCVEMPTY {
+                let _ = tx
+                    .send(SourceMessage::Error(
+                        source_idx,
+                        format!("PCAN read failed: {}", api.error_text(rc)),
+                    ))
+                    .await;
+                break 'read;
+            }
+            if rc == status::QRCVEMPTY {
+                break;
+            }
+
+            if let Some(mut frame) = decode_msg(&msg) {
+                rx_frames += 1;
+                if apply_bus_mapping(&mut frame, &bus_mappings) {
+                    batch.push(frame);
+                } else {
+                    bus_filtered += 1;
+                }
+            }
+        }
+
+        if batch.is_empty() {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        } else {
+            let _ = tx.send(SourceMessage::Frames(source_idx, batch)).await;
+        }
+    }
+
+    tlog!(
+        "[pcan] Source {} ended: rx={}, bus_filtered={}",
+        source_idx, rx_frames, bus_filtered
+    );
+
+    // Cleanup
+    if let Some(task) = transmit_task {
+        task.abort();
+    }
+
+    let _ = unsafe { (api.uninitialize)(handle) };
+
+    let _ = tx
+        .send(SourceMessage::Ended(source_idx, "stopped".to_string()))
+        .await;
+}
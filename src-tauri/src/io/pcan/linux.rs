@@ -0,0 +1,81 @@
+// src-tauri/src/io/pcan/linux.rs
+//
+// PCAN-USB device enumeration for Linux using sysfs.
+//
+// On Linux, PEAK adapters are handled by the kernel peak_usb driver, which
+// exposes each channel as a SocketCAN interface. This module finds those
+// interfaces; reading/writing is done by the existing SocketCAN reader.
+
+use std::fs;
+use std::path::Path;
+
+use super::PcanDeviceInfo;
+
+/// Kernel driver bound to PEAK USB adapters
+const PEAK_USB_DRIVER: &str = "peak_usb";
+
+/// List PCAN-USB channels by scanning sysfs for CAN interfaces bound to peak_usb.
+pub fn list_devices() -> Result<Vec<PcanDeviceInfo>, String> {
+    let mut devices = Vec::new();
+
+    let net_path = Path::new("/sys/class/net");
+    if !net_path.exists() {
+        return Ok(devices);
+    }
+
+    let entries = fs::read_dir(net_path).map_err(|e| format!("Failed to read /sys/class/net: {}", e))?;
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("can"))
+        .collect();
+    names.sort();
+
+    for iface_name in names {
+        if let Some(mut info) = get_pcan_info_for_interface(&iface_name) {
+            info.channel = devices.len() as u8 + 1;
+            devices.push(info);
+        }
+    }
+
+    Ok(devices)
+}
+
+/// PCAN device info for a CAN interface, if it's bound to peak_usb
+fn get_pcan_info_for_interface(iface_name: &str) -> Option<PcanDeviceInfo> {
+    let device_path = format!("/sys/class/net/{}/device", iface_name);
+    let driver = fs::read_link(format!("{}/driver", device_path)).ok()?;
+    if driver.file_name()?.to_str()? != PEAK_USB_DRIVER {
+        return None;
+    }
+
+    // The device link points at the USB interface; the product string lives on
+    // the USB device one level up.
+    let device_full_path = fs::canonicalize(&device_path).ok()?;
+    let hardware_name = device_full_path
+        .ancestors()
+        .find_map(|p| fs::read_to_string(p.join("product")).ok())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| "PCAN-USB".to_string());
+
+    // Newer kernels expose the user-assigned channel ID
+    let device_id = fs::read_to_string(format!("/sys/class/net/{}/peak_usb/can_channel_id", iface_name))
+        .ok()
+        .and_then(|s| {
+            let s = s.trim();
+            u32::from_str_radix(s.trim_start_matches("0x").trim_start_matches("0X"), 16).ok()
+        });
+
+    let interface_up = fs::read_to_string(format!("/sys/class/net/{}/operstate", iface_name))
+        .ok()
+        .map(|s| s.trim() == "up");
+
+    Some(PcanDeviceInfo {
+        channel: 0,
+        hardware_name,
+        device_id,
+        occupied: false,
+        interface_name: Some(iface_name.to_string()),
+        interface_up,
+    })
+}
@@ -0,0 +1,325 @@
+// src-tauri/src/io/pcan/mod.rs
+//
+// PCAN-USB (PEAK-System) support for WireTAP.
+//
+// Platform strategy (mirrors gs_usb):
+// - Linux: The kernel peak_usb driver exposes adapters as SocketCAN interfaces.
+//          We enumerate them so users can configure a socketcan profile.
+// - Windows/macOS: PEAK's PCAN-Basic library (PCANBasic.dll on Windows,
+//          libPCBUSB.dylib on macOS), loaded at runtime so WireTAP still starts
+//          when the library isn't installed.
+//
+// Supported devices: PCAN-USB, PCAN-USB FD, PCAN-USB Pro (FD), PCAN-USB X6
+// channels — anything PCAN-Basic exposes as a USB channel. Classic CAN only
+// for now (CAN_Initialize); FD channels run in classic mode.
+
+// Allow dead_code for protocol constants that are only used on specific platforms
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+
+use crate::io::{now_us, CanTransmitFrame, FrameMessage};
+
+#[cfg(target_os = "linux")]
+pub mod linux;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+pub mod basic;
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+pub use basic::run_source;
+
+// ============================================================================
+// PCAN-Basic Constants
+// ============================================================================
+
+/// Channel handles for PCAN_USBBUS1..16 (PCAN-Basic `TPCANHandle`)
+pub const USB_CHANNEL_HANDLES: [u16; 16] = [
+    0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, // PCAN_USBBUS1-8
+    0x509, 0x50A, 0x50B, 0x50C, 0x50D, 0x50E, 0x50F, 0x510, // PCAN_USBBUS9-16
+];
+
+/// Status codes (`TPCANStatus`)
+pub mod status {
+    pub const OK: u32 = 0x00000;
+    pub const XMTFULL: u32 = 0x00001;
+    pub const OVERRUN: u32 = 0x00002;
+    pub const BUSLIGHT: u32 = 0x00004;
+    pub const BUSHEAVY: u32 = 0x00008;
+    pub const BUSPASSIVE: u32 = 0x40000;
+    pub const BUSOFF: u32 = 0x00010;
+    pub const QRCVEMPTY: u32 = 0x00020;
+    pub const QOVERRUN: u32 = 0x00040;
+    pub const QXMTFULL: u32 = 0x00080;
+    pub const NODRIVER: u32 = 0x01000;
+    pub const INITIALIZE: u32 = 0x04000;
+    pub const ILLOPERATION: u32 = 0x08000;
+
+    /// Bus state bits — reported by CAN_Read/CAN_Write but not fatal
+    pub const BUS_STATE_MASK: u32 = BUSLIGHT | BUSHEAVY | BUSPASSIVE | BUSOFF;
+}
+
+/// Parameters for CAN_GetValue / CAN_SetValue (`TPCANParameter`)
+pub mod param {
+    pub const DEVICE_ID: u8 = 0x01;
+    pub const BUSOFF_AUTORESET: u8 = 0x07;
+    pub const LISTEN_ONLY: u8 = 0x08;
+    pub const CHANNEL_CONDITION: u8 = 0x0D;
+    pub const HARDWARE_NAME: u8 = 0x0E;
+
+    pub const ON: u32 = 0x01;
+    pub const OFF: u32 = 0x00;
+
+    /// CHANNEL_CONDITION values
+    pub const CHANNEL_UNAVAILABLE: u32 = 0x00;
+    pub const CHANNEL_AVAILABLE: u32 = 0x01;
+    pub const CHANNEL_OCCUPIED: u32 = 0x02;
+}
+
+/// Message type flags (`TPCANMessageType`)
+pub mod msg_type {
+    pub const STANDARD: u8 = 0x00;
+    pub const RTR: u8 = 0x01;
+    pub const EXTENDED: u8 = 0x02;
+    pub const FD: u8 = 0x04;
+    pub const BRS: u8 = 0x08;
+    pub const ESI: u8 = 0x10;
+    pub const ECHO: u8 = 0x20;
+    pub const ERRFRAME: u8 = 0x40;
+    pub const STATUS: u8 = 0x80;
+}
+
+/// English, for CAN_GetErrorText
+pub const ERROR_TEXT_LANGUAGE: u16 = 0x09;
+
+/// Classic CAN bitrates and their BTR0/BTR1 codes (`TPCANBaudrate`)
+pub const BAUD_RATES: &[(u32, u16)] = &[
+    (1_000_000, 0x0014),
+    (800_000, 0x0016),
+    (500_000, 0x001C),
+    (250_000, 0x011C),
+    (125_000, 0x031C),
+    (100_000, 0x432F),
+    (95_000, 0xC34E),
+    (83_000, 0x852B),
+    (50_000, 0x472F),
+    (47_000, 0x1414),
+    (33_000, 0x8B2F),
+    (20_000, 0x532F),
+    (10_000, 0x672F),
+    (5_000, 0x7F7F),
+];
+
+/// BTR0/BTR1 code for a bitrate, or None if PCAN-Basic has no preset for it.
+pub fn baud_code(bitrate: u32) -> Option<u16> {
+    BAUD_RATES.iter().find(|(b, _)| *b == bitrate).map(|(_, code)| *code)
+}
+
+/// PCAN-Basic handle for a 1-based USB channel number.
+pub fn channel_handle(channel: u8) -> Option<u16> {
+    USB_CHANNEL_HANDLES.get((channel as usize).checked_sub(1)?).copied()
+}
+
+// ============================================================================
+// Message Structures
+// ============================================================================
+
+/// PCAN-Basic `TPCANMsg` (classic CAN)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PcanMsg {
+    pub id: u32,
+    pub msg_type: u8,
+    pub len: u8,
+    pub data: [u8; 8],
+}
+
+impl PcanMsg {
+    /// Wire size used for transmit requests (ID + type + length + data)
+    pub const SIZE: usize = 14;
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(Self::SIZE);
+        buf.extend_from_slice(&self.id.to_le_bytes());
+        buf.push(self.msg_type);
+        buf.push(self.len);
+        buf.extend_from_slice(&self.data);
+        buf
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() < Self::SIZE {
+            return None;
+        }
+        let mut msg = PcanMsg {
+            id: u32::from_le_bytes(data[0..4].try_into().ok()?),
+            msg_type: data[4],
+            len: data[5],
+            data: [0; 8],
+        };
+        msg.data.copy_from_slice(&data[6..14]);
+        Some(msg)
+    }
+}
+
+/// PCAN-Basic `TPCANTimestamp`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PcanTimestamp {
+    pub millis: u32,
+    pub millis_overflow: u16,
+    pub micros: u16,
+}
+
+/// Encode a CAN frame as a PCAN-Basic message (for transmit routing).
+pub fn encode_frame(frame: &CanTransmitFrame) -> Vec<u8> {
+    let mut msg = PcanMsg {
+        id: frame.frame_id,
+        msg_type: msg_type::STANDARD,
+        len: frame.data.len().min(8) as u8,
+        data: [0; 8],
+    };
+    if frame.is_extended {
+        msg.msg_type |= msg_type::EXTENDED;
+    }
+    if frame.is_rtr {
+        msg.msg_type |= msg_type::RTR;
+    }
+    msg.data[..msg.len as usize].copy_from_slice(&frame.data[..msg.len as usize]);
+    msg.to_bytes()
+}
+
+/// Convert a received PCAN-Basic message into a frame. Status and error
+/// frames return None.
+pub fn decode_msg(msg: &PcanMsg) -> Option<FrameMessage> {
+    if msg.msg_type & (msg_type::STATUS | msg_type::ERRFRAME) != 0 {
+        return None;
+    }
+    let len = (msg.len as usize).min(8);
+    let is_echo = msg.msg_type & msg_type::ECHO != 0;
+    Some(FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us: now_us(),
+        frame_id: msg.id & 0x1FFF_FFFF,
+        bus: 0,
+        dlc: len as u8,
+        bytes: msg.data[..len].to_vec(),
+        is_extended: msg.msg_type & msg_type::EXTENDED != 0,
+        is_fd: false,
+        source_address: None,
+        incomplete: None,
+        direction: Some(if is_echo { "tx" } else { "rx" }.to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    })
+}
+
+// ============================================================================
+// Device Info
+// ============================================================================
+
+/// Information about a detected PCAN-USB channel
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PcanDeviceInfo {
+    /// 1-based USB channel number (PCAN_USBBUS<n>)
+    pub channel: u8,
+    /// Hardware name reported by the driver (e.g. "PCAN-USB FD")
+    pub hardware_name: String,
+    /// User-assigned device ID (stable across replugging, set with PCAN-View)
+    pub device_id: Option<u32>,
+    /// Whether another application already holds the channel
+    pub occupied: bool,
+    /// SocketCAN interface name (Linux only, e.g., "can0")
+    pub interface_name: Option<String>,
+    /// Whether the interface is currently up (Linux only)
+    pub interface_up: Option<bool>,
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// List PCAN-USB channels connected to the system.
+/// On Linux, lists the SocketCAN interfaces bound to the peak_usb driver.
+#[tauri::command]
+pub fn list_pcan_devices() -> Result<Vec<PcanDeviceInfo>, String> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::list_devices()
+    }
+
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    {
+        basic::list_devices()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// Classic CAN bitrates PCAN-Basic supports, for the profile editor.
+#[tauri::command]
+pub fn get_pcan_bitrates() -> Vec<u32> {
+    BAUD_RATES.iter().map(|(b, _)| *b).collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_handles() {
+        assert_eq!(channel_handle(1), Some(0x51));
+        assert_eq!(channel_handle(8), Some(0x58));
+        assert_eq!(channel_handle(9), Some(0x509));
+        assert_eq!(channel_handle(16), Some(0x510));
+        assert_eq!(channel_handle(0), None);
+        assert_eq!(channel_handle(17), None);
+    }
+
+    #[test]
+    fn test_baud_codes() {
+        assert_eq!(baud_code(500_000), Some(0x001C));
+        assert_eq!(baud_code(1_000_000), Some(0x0014));
+        assert_eq!(baud_code(123_456), None);
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let frame = CanTransmitFrame {
+            frame_id: 0x18DAF110,
+            data: vec![0x02, 0x10, 0x03],
+            bus: 0,
+            is_extended: true,
+            is_fd: false,
+            is_brs: false,
+            is_rtr: false,
+        };
+        let bytes = encode_frame(&frame);
+        assert_eq!(bytes.len(), PcanMsg::SIZE);
+        let msg = PcanMsg::from_bytes(&bytes).unwrap();
+        assert_eq!(msg.msg_type, msg_type::EXTENDED);
+        assert_eq!(msg.len, 3);
+
+        let decoded = decode_msg(&msg).unwrap();
+        assert_eq!(decoded.frame_id, 0x18DAF110);
+        assert!(decoded.is_extended);
+        assert_eq!(decoded.bytes, vec![0x02, 0x10, 0x03]);
+        assert_eq!(decoded.direction.as_deref(), Some("rx"));
+    }
+
+    #[test]
+    fn test_status_messages_are_skipped() {
+        let msg = PcanMsg { msg_type: msg_type::STATUS, ..Default::default() };
+        assert!(decode_msg(&msg).is_none());
+    }
+}
@@ -0,0 +1,239 @@
+// ui/src-tauri/src/io/recorded/asc.rs
+//
+// Vector ASC (ASCII log) writing, so captures can be opened in CANalyzer and
+// CANoe by colleagues who don't read BLF.
+//
+// An ASC file is a short header (measurement date, "base hex  timestamps
+// absolute|relative", "internal events logged") followed by one trigger block
+// of events, one per line. Times are seconds with 6 decimals. With absolute
+// timestamps each event carries its offset from the start of measurement;
+// with relative timestamps it carries the gap since the previous event.
+//
+// Classic frames use the CAN line:
+//   <time> <ch>  <id>[x]          Rx|Tx   d <dlc> <bytes...>
+// CAN FD frames use the CANFD line (BRS set, ESI clear, hex DLC code, then
+// the payload length and data; duration, CRC and bit timing are written as
+// 0 since captures don't record them):
+//   <time> CANFD <ch> Rx|Tx <id>[x] <name> <brs> <esi> <dlc> <len> <bytes...> ...
+// Channels are 1-based (bus + 1). Non-CAN frames are skipped.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use super::blf::len_to_dlc;
+use crate::io::FrameMessage;
+
+/// ASC flags for CANFD lines
+const FLAG_EDL: u32 = 1 << 12;
+const FLAG_BRS: u32 = 1 << 13;
+
+/// How event times are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AscTimeBase {
+    /// Seconds since the start of measurement
+    #[default]
+    Absolute,
+    /// Seconds since the previous event
+    Relative,
+}
+
+/// Vector's header date form, e.g. "Tue Oct 17 10:30:45.123 am 2023" (local time).
+fn asc_date(us: u64) -> String {
+    let dt = Local
+        .timestamp_opt((us / 1_000_000) as i64, ((us % 1_000_000) * 1000) as u32)
+        .single()
+        .unwrap_or_else(|| Local.timestamp_opt(0, 0).unwrap());
+    format!(
+        "{}.{:03} {} {}",
+        dt.format("%a %b %d %I:%M:%S"),
+        dt.timestamp_subsec_millis(),
+        dt.format("%P"),
+        dt.format("%Y")
+    )
+}
+
+fn format_id(frame: &FrameMessage) -> String {
+    if frame.is_extended {
+        format!("{:X}x", frame.frame_id)
+    } else {
+        format!("{:X}", frame.frame_id)
+    }
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
+
+/// Streams frames into an ASC file. Call `finish` to close the trigger block.
+pub struct AscWriter<W: Write> {
+    out: W,
+    time_base: AscTimeBase,
+    start_us: u64,
+    last_us: u64,
+    written: u32,
+}
+
+impl AscWriter<BufWriter<File>> {
+    /// Create an ASC file whose measurement starts at `start_us`.
+    pub fn create(file_path: &str, start_us: u64, time_base: AscTimeBase) -> Result<Self, String> {
+        let file = File::create(file_path)
+            .map_err(|e| format!("Failed to create ASC file '{}': {}", file_path, e))?;
+        AscWriter::new(BufWriter::new(file), start_us, time_base)
+    }
+}
+
+impl<W: Write> AscWriter<W> {
+    pub fn new(mut out: W, start_us: u64, time_base: AscTimeBase) -> Result<Self, String> {
+        let date = asc_date(start_us);
+        let timestamps = match time_base {
+            AscTimeBase::Absolute => "absolute",
+            AscTimeBase::Relative => "relative",
+        };
+        write!(
+            out,
+            "date {date}\r\nbase hex  timestamps {timestamps}\r\ninternal events logged\r\n\
+             // version 13.0.0\r\nBegin Triggerblock {date}\r\n   0.000000 Start of measurement\r\n"
+        )
+        .map_err(|e| format!("Failed to write ASC header: {}", e))?;
+        Ok(AscWriter { out, time_base, start_us, last_us: start_us, written: 0 })
+    }
+
+    /// Event time column for a frame, per the time base.
+    fn event_time(&mut self, timestamp_us: u64) -> String {
+        let from = match self.time_base {
+            AscTimeBase::Absolute => self.start_us,
+            AscTimeBase::Relative => self.last_us,
+        };
+        self.last_us = timestamp_us.max(self.last_us);
+        let us = timestamp_us.saturating_sub(from);
+        format!("{:>11}", format!("{}.{:06}", us / 1_000_000, us % 1_000_000))
+    }
+
+    /// Append a CAN or CAN FD frame; other protocols are skipped. Returns
+    /// whether the frame was written.
+    pub fn write_frame(&mut self, frame: &FrameMessage) -> Result<bool, String> {
+        if frame.protocol != "can" {
+            return Ok(false);
+        }
+        let time = self.event_time(frame.timestamp_us);
+        let channel = frame.bus as u16 + 1;
+        let dir = if frame.direction.as_deref() == Some("tx") { "Tx" } else { "Rx" };
+        let id = format_id(frame);
+        let line = if frame.is_fd || frame.bytes.len() > 8 {
+            let len = frame.bytes.len().min(64);
+            format!(
+                "{} CANFD {:>3} {:<4} {:>8}  {:>32} {} {} {:x} {:>2} {} {:>8} {:>4} {:>8X} {:>8} {:>8} {:>8} {:>8} {:>8}",
+                time,
+                channel,
+                dir,
+                id,
+                "",
+                1,
+                0,
+                len_to_dlc(len),
+                len,
+                hex_bytes(&frame.bytes[..len]),
+                0,
+                0,
+                FLAG_EDL | FLAG_BRS,
+                0,
+                0,
+                0,
+                0,
+                0
+            )
+        } else {
+            format!(
+                "{} {}  {:<15} {:<4} d {} {}",
+                time,
+                channel,
+                id,
+                dir,
+                frame.bytes.len(),
+                hex_bytes(&frame.bytes)
+            )
+        };
+        self.out
+            .write_all(line.trim_end().as_bytes())
+            .and_then(|_| self.out.write_all(b"\r\n"))
+            .map_err(|e| format!("Failed to write ASC event: {}", e))?;
+        self.written += 1;
+        Ok(true)
+    }
+
+    /// Close the trigger block and flush. Returns the number of frames written.
+    pub fn finish(mut self) -> Result<u32, String> {
+        self.out
+            .write_all(b"End TriggerBlock\r\n")
+            .and_then(|_| self.out.flush())
+            .map_err(|e| format!("Failed to finish ASC file: {}", e))?;
+        Ok(self.written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(ts: u64, id: u32, data: &[u8]) -> FrameMessage {
+        FrameMessage {
+            is_extended: id > 0x7FF,
+            is_fd: data.len() > 8,
+            direction: Some("rx".to_string()),
+            ..test_frame(id, ts, data)
+        }
+    }
+
+    fn write(time_base: AscTimeBase, frames: &[FrameMessage]) -> Vec<String> {
+        let start = 1_700_000_000_000_000;
+        let mut out = Vec::new();
+        let mut writer = AscWriter::new(&mut out, start, time_base).unwrap();
+        for f in frames {
+            writer.write_frame(&FrameMessage { timestamp_us: start + f.timestamp_us, ..f.clone() }).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap() as usize, frames.len());
+        String::from_utf8(out).unwrap().split("\r\n").map(String::from).collect()
+    }
+
+    #[test]
+    fn writes_header_and_classic_frames() {
+        let lines = write(
+            AscTimeBase::Absolute,
+            &[frame(1_234, 0x123, &[0x01, 0xAB]), frame(2_500_000, 0x18FEF100, &[])],
+        );
+        assert!(lines[0].starts_with("date "));
+        assert_eq!(lines[1], "base hex  timestamps absolute");
+        assert!(lines[4].starts_with("Begin Triggerblock "));
+        assert_eq!(lines[6], "   0.001234 1  123             Rx   d 2 01 AB");
+        assert_eq!(lines[7], "   2.500000 1  18FEF100x       Rx   d 0");
+        assert_eq!(lines[8], "End TriggerBlock");
+    }
+
+    #[test]
+    fn relative_time_base_writes_gaps() {
+        let lines = write(
+            AscTimeBase::Relative,
+            &[frame(1_000, 0x100, &[1]), frame(11_000, 0x100, &[2])],
+        );
+        assert_eq!(lines[1], "base hex  timestamps relative");
+        assert!(lines[6].starts_with("   0.001000 1  100"));
+        assert!(lines[7].starts_with("   0.010000 1  100"));
+    }
+
+    #[test]
+    fn formats_can_fd_record() {
+        let data: Vec<u8> = (0..12).collect();
+        let lines = write(AscTimeBase::Absolute, &[frame(0, 0x7E0, &data)]);
+        let fields: Vec<&str> = lines[6].split_whitespace().collect();
+        // time, CANFD, channel, dir, id, brs, esi, dlc code, length, 12 data bytes
+        assert_eq!(&fields[..9], &["0.000000", "CANFD", "1", "Rx", "7E0", "1", "0", "9", "12"]);
+        assert_eq!(fields[9], "00");
+        assert_eq!(fields[20], "0B");
+        assert_eq!(fields[23], "3000");
+    }
+}
@@ -248,6 +248,11 @@ impl CursorFetcher {
                 source_address: None,
                 incomplete: None,
                 direction: None,
+                link: None,
+                clock: None,
+                is_error: None,
+                error_class: None,
+                brs: None,
             });
         }
         if let Some(rem) = self.remaining.as_mut() {
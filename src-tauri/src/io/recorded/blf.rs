@@ -0,0 +1,620 @@
+// ui/src-tauri/src/io/recorded/blf.rs
+//
+// Vector BLF (binary logging format) reading and writing, so captures
+// interoperate with CANalyzer/CANoe and the rest of the Vector toolchain.
+//
+// A BLF file is a 144-byte "LOGG" header (application, object count, start
+// and stop time as Windows SYSTEMTIME) followed by "LOBJ" objects. Nearly all
+// objects live inside LOG_CONTAINER objects whose payload is a zlib-deflated
+// (or stored) run of further objects; an inner object may straddle two
+// containers. Every object has a 16-byte base header (signature, header size,
+// header version, object size, type) and a v1 or v2 header carrying flags and
+// a timestamp in 10 µs or 1 ns units from the file's start time. Objects are
+// padded to 4 bytes.
+//
+// Frames read: CAN_MESSAGE / CAN_MESSAGE2, CAN_FD_MESSAGE and
+// CAN_FD_MESSAGE_64, plus CAN_ERROR / CAN_ERROR_EXT as unclassified error
+// frames. A message whose ID carries CAN_ERR_FLAG (how WireTAP writes error
+// frames) is read back as an error frame through can_error. Other objects
+// (statistics, LIN, ...) are skipped. Channels are 1-based in BLF and map to bus = channel - 1. Frames
+// are written as CAN_MESSAGE2 / CAN_FD_MESSAGE with nanosecond timestamps,
+// in zlib-compressed containers.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+
+use chrono::{Datelike, Local, NaiveDate, TimeZone, Timelike};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+use crate::io::can_error::{self, CAN_ERR_FLAG};
+use crate::io::FrameMessage;
+
+const FILE_SIGNATURE: &[u8; 4] = b"LOGG";
+const OBJ_SIGNATURE: &[u8; 4] = b"LOBJ";
+const FILE_HEADER_SIZE: usize = 144;
+const OBJ_HEADER_BASE_SIZE: usize = 16;
+const OBJ_HEADER_V1_SIZE: usize = 16;
+const CONTAINER_HEADER_SIZE: usize = 16;
+
+// Object types
+const CAN_MESSAGE: u32 = 1;
+const CAN_ERROR: u32 = 2;
+const LOG_CONTAINER: u32 = 10;
+const CAN_ERROR_EXT: u32 = 73;
+const CAN_MESSAGE2: u32 = 86;
+const CAN_FD_MESSAGE: u32 = 100;
+const CAN_FD_MESSAGE_64: u32 = 101;
+
+// Container compression methods
+const NO_COMPRESSION: u16 = 0;
+const ZLIB_DEFLATE: u16 = 2;
+
+// Object header timestamp units
+const TIME_TEN_MICS: u32 = 1;
+const TIME_ONE_NANS: u32 = 2;
+
+// Message flags
+const CAN_MSG_EXT: u32 = 0x8000_0000;
+const DIR_TX: u8 = 0x01;
+const REMOTE_FLAG: u8 = 0x80;
+const FD_EDL: u8 = 0x01;
+const FD_BRS: u8 = 0x02;
+const FD64_EDL: u32 = 0x1000;
+
+/// Uncompressed bytes gathered before a container is written
+const MAX_CONTAINER_SIZE: usize = 128 * 1024;
+
+fn u16_at(b: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([b[at], b[at + 1]])
+}
+
+fn u32_at(b: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([b[at], b[at + 1], b[at + 2], b[at + 3]])
+}
+
+fn u64_at(b: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(b[at..at + 8].try_into().unwrap())
+}
+
+/// CAN FD DLC code for a payload length.
+pub(super) fn len_to_dlc(len: usize) -> u8 {
+    match len {
+        0..=8 => len as u8,
+        9..=12 => 9,
+        13..=16 => 10,
+        17..=20 => 11,
+        21..=24 => 12,
+        25..=32 => 13,
+        33..=48 => 14,
+        _ => 15,
+    }
+}
+
+// ============================================================================
+// SYSTEMTIME
+// ============================================================================
+
+/// Decode a Windows SYSTEMTIME (local time, as Vector tools write it) to µs
+/// since the Unix epoch. Returns None for an unset time.
+fn systemtime_to_us(b: &[u8]) -> Option<u64> {
+    let field = |i: usize| u16_at(b, i * 2) as u32;
+    let date = NaiveDate::from_ymd_opt(field(0) as i32, field(1), field(3))?;
+    let naive = date.and_hms_milli_opt(field(4), field(5), field(6), field(7))?;
+    let local = Local.from_local_datetime(&naive).earliest()?;
+    u64::try_from(local.timestamp_micros()).ok()
+}
+
+fn us_to_systemtime(us: u64) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    let Some(t) = Local
+        .timestamp_opt((us / 1_000_000) as i64, (us % 1_000_000) as u32 * 1000)
+        .single()
+    else {
+        return out;
+    };
+    let fields = [
+        t.year() as u16,
+        t.month() as u16,
+        t.weekday().num_days_from_sunday() as u16,
+        t.day() as u16,
+        t.hour() as u16,
+        t.minute() as u16,
+        t.second() as u16,
+        (t.nanosecond() / 1_000_000).min(999) as u16,
+    ];
+    for (i, f) in fields.iter().enumerate() {
+        out[i * 2..i * 2 + 2].copy_from_slice(&f.to_le_bytes());
+    }
+    out
+}
+
+// ============================================================================
+// Reading
+// ============================================================================
+
+/// Parse one non-container object (starting at its base header). Returns
+/// None for object types that aren't CAN frames or error frames.
+fn parse_object(obj: &[u8], start_us: u64) -> Option<FrameMessage> {
+    let header_size = u16_at(obj, 4) as usize;
+    let header_version = u16_at(obj, 6);
+    let obj_type = u32_at(obj, 12);
+    if !matches!(
+        obj_type,
+        CAN_MESSAGE | CAN_MESSAGE2 | CAN_FD_MESSAGE | CAN_FD_MESSAGE_64 | CAN_ERROR | CAN_ERROR_EXT
+    ) {
+        return None;
+    }
+    let (flags, timestamp) = match header_version {
+        1 if obj.len() >= OBJ_HEADER_BASE_SIZE + 16 => (u32_at(obj, 16), u64_at(obj, 24)),
+        2 if obj.len() >= OBJ_HEADER_BASE_SIZE + 24 => (u32_at(obj, 16), u64_at(obj, 24)),
+        _ => return None,
+    };
+    let offset_us = match flags {
+        TIME_TEN_MICS => timestamp * 10,
+        _ => timestamp / 1000,
+    };
+    let body = obj.get(header_size..)?;
+    let timestamp_us = start_us + offset_us;
+    let bus_of = |channel: u16| channel.saturating_sub(1).min(u8::MAX as u16) as u8;
+
+    let (channel, frame_id, bytes, is_fd, is_tx) = match obj_type {
+        CAN_ERROR | CAN_ERROR_EXT => {
+            // Vector's own error objects don't carry the SocketCAN classes
+            let channel = u16_at(body.get(..2)?, 0);
+            return Some(can_error::error_frame(CAN_ERR_FLAG, &[], bus_of(channel), timestamp_us));
+        }
+        CAN_MESSAGE | CAN_MESSAGE2 => {
+            let body = body.get(..16)?;
+            let msg_flags = body[2];
+            let dlc = (body[3] as usize).min(8);
+            let data = if msg_flags & REMOTE_FLAG != 0 {
+                Vec::new()
+            } else {
+                body[8..8 + dlc].to_vec()
+            };
+            (u16_at(body, 0), u32_at(body, 4), data, false, msg_flags & DIR_TX != 0)
+        }
+        CAN_FD_MESSAGE => {
+            let body = body.get(..84)?;
+            let valid = (body[14] as usize).min(64);
+            (u16_at(body, 0), u32_at(body, 4), body[20..20 + valid].to_vec(), body[13] & FD_EDL != 0, body[2] & DIR_TX != 0)
+        }
+        _ => {
+            // CAN_FD_MESSAGE_64: 40-byte fixed part, then the payload
+            let fixed = body.get(..40)?;
+            let valid = fixed[2] as usize;
+            let data = body.get(40..40 + valid)?.to_vec();
+            (fixed[0] as u16, u32_at(fixed, 4), data, u32_at(fixed, 12) & FD64_EDL != 0, fixed[34] != 0)
+        }
+    };
+    let direction = if is_tx { "tx" } else { "rx" };
+
+    if frame_id & CAN_MSG_EXT != 0 && can_error::is_error_id(frame_id) {
+        let mut frame = can_error::error_frame(frame_id, &bytes, bus_of(channel), timestamp_us);
+        frame.direction = Some(direction.to_string());
+        return Some(frame);
+    }
+
+    Some(FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us,
+        frame_id: frame_id & 0x1FFF_FFFF,
+        bus: bus_of(channel),
+        dlc: bytes.len() as u8,
+        bytes,
+        is_extended: frame_id & CAN_MSG_EXT != 0,
+        is_fd,
+        source_address: None,
+        incomplete: None,
+        direction: Some(direction.to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    })
+}
+
+/// Parse the objects in decompressed container data. Returns the offset of
+/// the first object that doesn't fit (the tail carried into the next
+/// container).
+fn parse_container_data(data: &[u8], start_us: u64, frames: &mut Vec<FrameMessage>) -> usize {
+    let mut pos = 0;
+    while pos + OBJ_HEADER_BASE_SIZE <= data.len() {
+        // Skip the padding in front of the next object
+        let window_end = (pos + 8).min(data.len());
+        let Some(skip) = data[pos..window_end].windows(4).position(|w| w == OBJ_SIGNATURE) else {
+            if data.len() - pos < 8 {
+                return pos;
+            }
+            // Corrupt data: resync on the next signature
+            match data[pos..].windows(4).position(|w| w == OBJ_SIGNATURE) {
+                Some(skip) => {
+                    pos += skip;
+                    continue;
+                }
+                None => return data.len(),
+            }
+        };
+        pos += skip;
+        if pos + OBJ_HEADER_BASE_SIZE > data.len() {
+            return pos;
+        }
+        let obj_size = u32_at(data, pos + 8) as usize;
+        if obj_size < OBJ_HEADER_BASE_SIZE {
+            pos += 4;
+            continue;
+        }
+        if pos + obj_size > data.len() {
+            return pos;
+        }
+        if let Some(frame) = parse_object(&data[pos..pos + obj_size], start_us) {
+            frames.push(frame);
+        }
+        pos += obj_size;
+    }
+    pos
+}
+
+/// Parse an entire BLF file and return its CAN and CAN FD frames
+pub fn parse_blf_file(file_path: &str) -> Result<Vec<FrameMessage>, String> {
+    let file = File::open(file_path)
+        .map_err(|e| format!("Failed to open BLF file '{}': {}", file_path, e))?;
+    let mut reader = BufReader::new(file);
+
+    let mut header = [0u8; FILE_HEADER_SIZE];
+    reader
+        .read_exact(&mut header[..72])
+        .map_err(|_| "File is too short to be a BLF log".to_string())?;
+    if &header[..4] != FILE_SIGNATURE {
+        return Err("Not a BLF file (missing LOGG signature)".to_string());
+    }
+    let header_size = u32_at(&header, 4) as usize;
+    if header_size > 72 {
+        std::io::copy(&mut (&mut reader).take((header_size - 72) as u64), &mut std::io::sink())
+            .map_err(|e| format!("Failed to read BLF header: {}", e))?;
+    }
+    let start_us = systemtime_to_us(&header[40..56]).unwrap_or(0);
+
+    let mut frames = Vec::new();
+    let mut tail: Vec<u8> = Vec::new();
+    let mut base = [0u8; OBJ_HEADER_BASE_SIZE];
+    loop {
+        match reader.read_exact(&mut base) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(format!("Failed to read BLF object: {}", e)),
+        }
+        if &base[..4] != OBJ_SIGNATURE {
+            if base.iter().all(|&b| b == 0) {
+                break; // zero padding at the end of the file
+            }
+            return Err(format!("Corrupt BLF object after {} frames", frames.len()));
+        }
+        let obj_size = u32_at(&base, 8) as usize;
+        let obj_type = u32_at(&base, 12);
+        let mut obj = vec![0u8; obj_size.max(OBJ_HEADER_BASE_SIZE)];
+        obj[..OBJ_HEADER_BASE_SIZE].copy_from_slice(&base);
+        reader
+            .read_exact(&mut obj[OBJ_HEADER_BASE_SIZE..])
+            .map_err(|e| format!("Truncated BLF object: {}", e))?;
+        // Padding after the object
+        let mut pad = [0u8; 3];
+        let _ = reader.read_exact(&mut pad[..obj_size % 4]);
+
+        if obj_type != LOG_CONTAINER {
+            if let Some(frame) = parse_object(&obj, start_us) {
+                frames.push(frame);
+            }
+            continue;
+        }
+
+        let header_size = u16_at(&obj, 4) as usize;
+        let container = obj.get(header_size..).unwrap_or_default();
+        if container.len() < CONTAINER_HEADER_SIZE {
+            continue;
+        }
+        let method = u16_at(container, 0);
+        let uncompressed_size = u32_at(container, 8) as usize;
+        let payload = &container[CONTAINER_HEADER_SIZE..];
+        let data = match method {
+            NO_COMPRESSION => payload.to_vec(),
+            ZLIB_DEFLATE => {
+                let mut out = Vec::with_capacity(uncompressed_size);
+                ZlibDecoder::new(payload)
+                    .read_to_end(&mut out)
+                    .map_err(|e| format!("Failed to decompress BLF container: {}", e))?;
+                out
+            }
+            other => return Err(format!("Unsupported BLF compression method {}", other)),
+        };
+        tail.extend_from_slice(&data);
+        let consumed = parse_container_data(&tail, start_us, &mut frames);
+        tail.drain(..consumed);
+    }
+
+    Ok(frames)
+}
+
+// ============================================================================
+// Writing
+// ============================================================================
+
+/// Streams frames into a BLF file. Call `finish` to write the final container
+/// and the file header. The header goes at the start but is only known at the
+/// end, so the output must be seekable.
+pub struct BlfWriter<W: Write + Seek = BufWriter<File>> {
+    file: W,
+    buffer: Vec<u8>,
+    /// File start time (ms resolution, as SYSTEMTIME holds it)
+    start_us: Option<u64>,
+    last_us: u64,
+    object_count: u32,
+    uncompressed_size: u64,
+}
+
+impl BlfWriter<BufWriter<File>> {
+    pub fn create(file_path: &str) -> Result<Self, String> {
+        let file = File::create(file_path)
+            .map_err(|e| format!("Failed to create BLF file '{}': {}", file_path, e))?;
+        BlfWriter::new(BufWriter::new(file))
+    }
+}
+
+impl<W: Write + Seek> BlfWriter<W> {
+    pub fn new(mut file: W) -> Result<Self, String> {
+        file.write_all(&[0u8; FILE_HEADER_SIZE])
+            .map_err(|e| format!("Failed to write BLF header: {}", e))?;
+        Ok(BlfWriter {
+            file,
+            buffer: Vec::with_capacity(MAX_CONTAINER_SIZE + 256),
+            start_us: None,
+            last_us: 0,
+            object_count: 0,
+            uncompressed_size: FILE_HEADER_SIZE as u64,
+        })
+    }
+
+    /// Append a CAN or CAN FD frame; other protocols are skipped. Returns
+    /// whether the frame was written.
+    pub fn write_frame(&mut self, frame: &FrameMessage) -> Result<bool, String> {
+        if frame.protocol != "can" {
+            return Ok(false);
+        }
+        let start_us = *self.start_us.get_or_insert(frame.timestamp_us / 1000 * 1000);
+        self.last_us = self.last_us.max(frame.timestamp_us);
+        let timestamp_ns = frame.timestamp_us.saturating_sub(start_us) * 1000;
+
+        let channel = frame.bus as u16 + 1;
+        let frame_id = frame.frame_id | if frame.is_extended { CAN_MSG_EXT } else { 0 };
+        let dir = if frame.direction.as_deref() == Some("tx") { DIR_TX } else { 0 };
+        let mut body = Vec::with_capacity(84);
+        let obj_type = if frame.is_fd || frame.bytes.len() > 8 {
+            let len = frame.bytes.len().min(64);
+            body.extend_from_slice(&channel.to_le_bytes());
+            body.push(dir);
+            body.push(len_to_dlc(len));
+            body.extend_from_slice(&frame_id.to_le_bytes());
+            body.extend_from_slice(&0u32.to_le_bytes()); // frame length (ns)
+            body.push(0); // arbitration bit count
+            body.push(FD_EDL | FD_BRS);
+            body.push(len as u8);
+            body.extend_from_slice(&[0u8; 5]);
+            body.extend_from_slice(&frame.bytes[..len]);
+            body.resize(84, 0);
+            CAN_FD_MESSAGE
+        } else {
+            body.extend_from_slice(&channel.to_le_bytes());
+            body.push(dir);
+            body.push(frame.bytes.len() as u8);
+            body.extend_from_slice(&frame_id.to_le_bytes());
+            body.extend_from_slice(&frame.bytes);
+            body.resize(16, 0);
+            body.extend_from_slice(&0u32.to_le_bytes()); // frame length (ns)
+            body.extend_from_slice(&0u32.to_le_bytes()); // bit count + reserved
+            CAN_MESSAGE2
+        };
+
+        let header_size = (OBJ_HEADER_BASE_SIZE + OBJ_HEADER_V1_SIZE) as u16;
+        let obj_size = header_size as u32 + body.len() as u32;
+        self.buffer.extend_from_slice(OBJ_SIGNATURE);
+        self.buffer.extend_from_slice(&header_size.to_le_bytes());
+        self.buffer.extend_from_slice(&1u16.to_le_bytes());
+        self.buffer.extend_from_slice(&obj_size.to_le_bytes());
+        self.buffer.extend_from_slice(&obj_type.to_le_bytes());
+        self.buffer.extend_from_slice(&TIME_ONE_NANS.to_le_bytes());
+        self.buffer.extend_from_slice(&0u16.to_le_bytes()); // client index
+        self.buffer.extend_from_slice(&0u16.to_le_bytes()); // object version
+        self.buffer.extend_from_slice(&timestamp_ns.to_le_bytes());
+        self.buffer.extend_from_slice(&body);
+        self.buffer.resize(self.buffer.len() + obj_size as usize % 4, 0);
+        self.object_count += 1;
+
+        if self.buffer.len() >= MAX_CONTAINER_SIZE {
+            self.flush_container()?;
+        }
+        Ok(true)
+    }
+
+    fn flush_container(&mut self) -> Result<(), String> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&self.buffer)
+            .and_then(|_| encoder.finish())
+            .map_err(|e| format!("Failed to compress BLF container: {}", e))
+            .and_then(|compressed| {
+                let obj_size = (OBJ_HEADER_BASE_SIZE + CONTAINER_HEADER_SIZE + compressed.len()) as u32;
+                let mut out = Vec::with_capacity(obj_size as usize + 3);
+                out.extend_from_slice(OBJ_SIGNATURE);
+                out.extend_from_slice(&(OBJ_HEADER_BASE_SIZE as u16).to_le_bytes());
+                out.extend_from_slice(&1u16.to_le_bytes());
+                out.extend_from_slice(&obj_size.to_le_bytes());
+                out.extend_from_slice(&LOG_CONTAINER.to_le_bytes());
+                out.extend_from_slice(&ZLIB_DEFLATE.to_le_bytes());
+                out.extend_from_slice(&[0u8; 6]);
+                out.extend_from_slice(&(self.buffer.len() as u32).to_le_bytes());
+                out.extend_from_slice(&[0u8; 4]);
+                out.extend_from_slice(&compressed);
+                out.resize(out.len() + obj_size as usize % 4, 0);
+                self.file
+                    .write_all(&out)
+                    .map_err(|e| format!("Failed to write BLF container: {}", e))
+            })?;
+        self.uncompressed_size += (OBJ_HEADER_BASE_SIZE + CONTAINER_HEADER_SIZE + self.buffer.len()) as u64;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Write the last container and the file header. Returns the number of
+    /// frames written.
+    pub fn finish(self) -> Result<u32, String> {
+        self.finish_into_inner().map(|(count, _)| count)
+    }
+
+    /// `finish`, also handing back the output.
+    pub fn finish_into_inner(mut self) -> Result<(u32, W), String> {
+        self.flush_container()?;
+        let file_size = self
+            .file
+            .stream_position()
+            .map_err(|e| format!("Failed to finish BLF file: {}", e))?;
+
+        let mut header = Vec::with_capacity(FILE_HEADER_SIZE);
+        header.extend_from_slice(FILE_SIGNATURE);
+        header.extend_from_slice(&(FILE_HEADER_SIZE as u32).to_le_bytes());
+        header.extend_from_slice(&[5, 0, 0, 0]); // application ID + version
+        header.extend_from_slice(&[2, 6, 8, 1]); // binary log format version
+        header.extend_from_slice(&file_size.to_le_bytes());
+        header.extend_from_slice(&self.uncompressed_size.to_le_bytes());
+        header.extend_from_slice(&self.object_count.to_le_bytes());
+        header.extend_from_slice(&0u32.to_le_bytes()); // objects read
+        let start_us = self.start_us.unwrap_or(0);
+        header.extend_from_slice(&us_to_systemtime(start_us));
+        header.extend_from_slice(&us_to_systemtime(self.last_us.max(start_us)));
+        header.resize(FILE_HEADER_SIZE, 0);
+
+        self.file
+            .seek(SeekFrom::Start(0))
+            .and_then(|_| self.file.write_all(&header))
+            .and_then(|_| self.file.flush())
+            .map_err(|e| format!("Failed to write BLF header: {}", e))?;
+        Ok((self.object_count, self.file))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(ts: u64, bus: u8, id: u32, data: &[u8]) -> FrameMessage {
+        FrameMessage {
+            bus,
+            is_extended: id > 0x7FF,
+            is_fd: data.len() > 8,
+            direction: Some("rx".to_string()),
+            ..test_frame(id, ts, data)
+        }
+    }
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("wiretap-blf-{}-{}.blf", name, std::process::id()))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn round_trips_can_and_fd_frames() {
+        let path = temp_path("roundtrip");
+        let start = 1_700_000_000_123_000;
+        let mut frames = vec![
+            frame(start + 250, 0, 0x123, &[1, 2, 3]),
+            frame(start + 1_500, 1, 0x18DA_F110, &[0xAA; 8]),
+            frame(start + 2_000, 0, 0x7E8, &(0..24).collect::<Vec<u8>>()),
+        ];
+        frames[1].direction = Some("tx".to_string());
+
+        let mut writer = BlfWriter::create(&path).unwrap();
+        for f in &frames {
+            assert!(writer.write_frame(f).unwrap());
+        }
+        assert_eq!(writer.finish().unwrap(), 3);
+
+        let read = parse_blf_file(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(read.len(), 3);
+        for (a, b) in frames.iter().zip(&read) {
+            assert_eq!((a.timestamp_us, a.frame_id, a.bus), (b.timestamp_us, b.frame_id, b.bus));
+            assert_eq!((a.is_extended, a.is_fd), (b.is_extended, b.is_fd));
+            assert_eq!(a.bytes, b.bytes);
+            assert_eq!(a.direction, b.direction);
+        }
+    }
+
+    #[test]
+    fn error_frames_round_trip_with_their_class() {
+        let path = temp_path("errors");
+        let bus_off = can_error::error_frame(CAN_ERR_FLAG | 0x40, &[0; 8], 1, 1_700_000_000_000_000);
+        let mut writer = BlfWriter::create(&path).unwrap();
+        writer.write_frame(&bus_off).unwrap();
+        writer.finish().unwrap();
+
+        let read = parse_blf_file(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!((read[0].frame_id, read[0].bus), (bus_off.frame_id, 1));
+        assert_eq!((read[0].is_error, read[0].error_class), (Some(true), Some(crate::io::CanErrorClass::BusOff)));
+    }
+
+    #[test]
+    fn objects_straddling_containers_are_reassembled() {
+        let mut data = Vec::new();
+        let mut object = |id: u32| {
+            let mut obj = Vec::new();
+            obj.extend_from_slice(OBJ_SIGNATURE);
+            obj.extend_from_slice(&32u16.to_le_bytes());
+            obj.extend_from_slice(&1u16.to_le_bytes());
+            obj.extend_from_slice(&48u32.to_le_bytes());
+            obj.extend_from_slice(&CAN_MESSAGE.to_le_bytes());
+            obj.extend_from_slice(&TIME_TEN_MICS.to_le_bytes());
+            obj.extend_from_slice(&[0u8; 4]);
+            obj.extend_from_slice(&100u64.to_le_bytes()); // 1 ms
+            obj.extend_from_slice(&1u16.to_le_bytes()); // channel 1
+            obj.extend_from_slice(&[0, 2]);
+            obj.extend_from_slice(&id.to_le_bytes());
+            obj.extend_from_slice(&[9, 8, 0, 0, 0, 0, 0, 0]);
+            data.extend(obj);
+        };
+        object(0x100);
+        object(0x200);
+
+        // Split the second object across two containers
+        let mut frames = Vec::new();
+        let mut tail = data[..60].to_vec();
+        let consumed = parse_container_data(&tail, 5_000_000, &mut frames);
+        assert_eq!((frames.len(), consumed), (1, 48));
+        tail.drain(..consumed);
+        tail.extend_from_slice(&data[60..]);
+        parse_container_data(&tail, 5_000_000, &mut frames);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].frame_id, 0x200);
+        assert_eq!(frames[1].timestamp_us, 5_001_000);
+        assert_eq!(frames[1].bytes, vec![9, 8]);
+    }
+
+    #[test]
+    fn rejects_non_blf_files() {
+        let path = temp_path("bad");
+        std::fs::write(&path, b"(1.0) can0 123#00\n").unwrap();
+        let result = parse_blf_file(&path);
+        let _ = std::fs::remove_file(&path);
+        assert!(result.is_err());
+        assert_eq!(len_to_dlc(12), 9);
+        assert_eq!(len_to_dlc(64), 15);
+    }
+}
@@ -0,0 +1,290 @@
+// ui/src-tauri/src/io/recorded/candump.rs
+//
+// candump log file parsing (`candump -l` / `candump -L` output), so Linux
+// captures can be imported and replayed without converting them to CSV first.
+//
+//   (1436509052.249713) can0 123#DEADBEEF           classic frame
+//   (1436509052.249713) can0 12345678#0011          29-bit ID (8 hex digits)
+//   (1436509052.249713) can0 123#R                  remote request
+//   (1436509052.249713) can1 123##1001122334455     CAN FD (flags nibble, data)
+//   (1436509052.249713) can0 123#DEADBEEF R         rx/tx marker (candump -x)
+//
+// The bus number comes from the interface name's trailing digits (can1 → 1,
+// vcan0 → 0); interfaces without one are numbered after the highest seen.
+// Error frames (CAN_ERR_FLAG set in a 29-bit ID) are classified through
+// can_error like those from live drivers. `CandumpWriter` writes the same
+// format back out, bus N as interface canN.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+use crate::io::can_error;
+use crate::io::FrameMessage;
+
+/// Interface name → bus number, in order of appearance. Shared with the pcapng
+/// reader, whose interface names are the same SocketCAN names.
+#[derive(Default)]
+pub(super) struct BusNumbers {
+    buses: HashMap<String, u8>,
+}
+
+impl BusNumbers {
+    pub(super) fn bus_for(&mut self, interface: &str) -> u8 {
+        if let Some(&bus) = self.buses.get(interface) {
+            return bus;
+        }
+        let digits = interface.len() - interface.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        let bus = interface[interface.len() - digits..]
+            .parse::<u8>()
+            .ok()
+            .unwrap_or_else(|| self.buses.values().max().map_or(0, |b| b.saturating_add(1)));
+        self.buses.insert(interface.to_string(), bus);
+        bus
+    }
+}
+
+/// Parse "(seconds.fraction)" into microseconds without going through f64.
+fn parse_timestamp(s: &str) -> Option<u64> {
+    let s = s.strip_prefix('(')?.strip_suffix(')')?;
+    let (secs, frac) = s.split_once('.').unwrap_or((s, ""));
+    let secs: u64 = secs.parse().ok()?;
+    if !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut micros = frac.chars().take(6).collect::<String>();
+    while micros.len() < 6 {
+        micros.push('0');
+    }
+    Some(secs * 1_000_000 + micros.parse::<u64>().ok()?)
+}
+
+fn parse_hex_bytes(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Parse one candump log line. Returns None for blank lines, comments and
+/// anything that isn't a frame record.
+fn parse_candump_line(line: &str, buses: &mut BusNumbers) -> Option<FrameMessage> {
+    let mut parts = line.split_whitespace();
+    let timestamp_us = parse_timestamp(parts.next()?)?;
+    let interface = parts.next()?;
+    let mut frame = parse_candump_record(parts.next()?)?;
+    frame.timestamp_us = timestamp_us;
+    frame.bus = buses.bus_for(interface);
+    if parts.next() == Some("T") {
+        frame.direction = Some("tx".to_string());
+    }
+    Some(frame)
+}
+
+/// Parse a bare `ID#DATA` record (bus 0, timestamp 0, received). Also used
+/// by the UDP listener for bridges that send candump text. Returns None for
+/// malformed records.
+pub(crate) fn parse_candump_record(record: &str) -> Option<FrameMessage> {
+    let (id_part, data_part) = record.split_once('#')?;
+    let frame_id = u32::from_str_radix(id_part, 16).ok()?;
+    let is_extended = id_part.len() > 3;
+    if is_extended && can_error::is_error_id(frame_id) {
+        return Some(can_error::error_frame(frame_id, &parse_hex_bytes(data_part)?, 0, 0));
+    }
+
+    let (bytes, dlc, is_fd) = if let Some(fd) = data_part.strip_prefix('#') {
+        // CAN FD: one hex digit of flags (BRS/ESI) before the data
+        let bytes = parse_hex_bytes(fd.get(1..)?)?;
+        let len = bytes.len() as u8;
+        (bytes, len, true)
+    } else if let Some(rtr) = data_part.strip_prefix(['R', 'r']) {
+        // Remote request: optional requested length digit
+        (Vec::new(), rtr.parse::<u8>().unwrap_or(0).min(8), false)
+    } else {
+        // Classic frame, possibly with a "_<dlc>" suffix for DLCs 9-15
+        let (data, _) = data_part.split_once('_').unwrap_or((data_part, ""));
+        let bytes = parse_hex_bytes(data)?;
+        let len = bytes.len() as u8;
+        (bytes, len, false)
+    };
+
+    Some(FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us: 0,
+        frame_id: frame_id & 0x1FFF_FFFF,
+        bus: 0,
+        dlc,
+        bytes,
+        is_extended,
+        is_fd,
+        source_address: None,
+        incomplete: None,
+        direction: Some("rx".to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    })
+}
+
+/// Parse an entire candump log file and return all frames
+pub fn parse_candump_file(file_path: &str) -> Result<Vec<FrameMessage>, String> {
+    let file = File::open(file_path)
+        .map_err(|e| format!("Failed to open candump log '{}': {}", file_path, e))?;
+    let reader = BufReader::new(file);
+
+    let mut frames: Vec<FrameMessage> = Vec::new();
+    let mut buses = BusNumbers::default();
+    let mut skipped = 0usize;
+
+    for (index, line_result) in reader.lines().enumerate() {
+        let line = line_result.map_err(|e| format!("Failed to read line {}: {}", index + 1, e))?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        match parse_candump_line(trimmed, &mut buses) {
+            Some(frame) => frames.push(frame),
+            None => skipped += 1,
+        }
+    }
+
+    if skipped > 0 {
+        tlog!("[candump] {}: skipped {} unparseable lines", file_path, skipped);
+    }
+    Ok(frames)
+}
+
+/// Format one frame as a candump log line (no trailing newline).
+fn format_candump_line(frame: &FrameMessage) -> String {
+    let hex: String = frame.bytes.iter().map(|b| format!("{:02X}", b)).collect();
+    let id = if frame.is_extended {
+        format!("{:08X}", frame.frame_id)
+    } else {
+        format!("{:03X}", frame.frame_id)
+    };
+    let data = if frame.is_fd { format!("#0{}", hex) } else { hex };
+    let dir = match frame.direction.as_deref() {
+        Some("tx") => " T",
+        Some(_) => " R",
+        None => "",
+    };
+    format!(
+        "({}.{:06}) can{} {}#{}{}",
+        frame.timestamp_us / 1_000_000,
+        frame.timestamp_us % 1_000_000,
+        frame.bus,
+        id,
+        data,
+        dir
+    )
+}
+
+/// Streams frames into a candump log. Call `finish` to flush.
+pub struct CandumpWriter<W: Write> {
+    out: W,
+    written: u32,
+}
+
+impl CandumpWriter<BufWriter<File>> {
+    pub fn create(file_path: &str) -> Result<Self, String> {
+        let file = File::create(file_path)
+            .map_err(|e| format!("Failed to create candump log '{}': {}", file_path, e))?;
+        Ok(CandumpWriter::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write> CandumpWriter<W> {
+    pub fn new(out: W) -> Self {
+        CandumpWriter { out, written: 0 }
+    }
+
+    /// Append a CAN or CAN FD frame; other protocols are skipped. Returns
+    /// whether the frame was written.
+    pub fn write_frame(&mut self, frame: &FrameMessage) -> Result<bool, String> {
+        if frame.protocol != "can" {
+            return Ok(false);
+        }
+        writeln!(self.out, "{}", format_candump_line(frame))
+            .map_err(|e| format!("Failed to write candump line: {}", e))?;
+        self.written += 1;
+        Ok(true)
+    }
+
+    /// Flush. Returns the number of frames written.
+    pub fn finish(mut self) -> Result<u32, String> {
+        self.out
+            .flush()
+            .map_err(|e| format!("Failed to finish candump log: {}", e))?;
+        Ok(self.written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Option<FrameMessage> {
+        parse_candump_line(line, &mut BusNumbers::default())
+    }
+
+    #[test]
+    fn parses_classic_and_extended_frames() {
+        let f = parse("(1436509052.249713) can0 123#DEADBEEF").unwrap();
+        assert_eq!(f.timestamp_us, 1_436_509_052_249_713);
+        assert_eq!((f.frame_id, f.bus, f.dlc), (0x123, 0, 4));
+        assert_eq!(f.bytes, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+        assert!(!f.is_extended);
+
+        let f = parse("(12.5) vcan1 18DAF110#0011 T").unwrap();
+        assert_eq!(f.timestamp_us, 12_500_000);
+        assert_eq!((f.frame_id, f.bus), (0x18DA_F110, 1));
+        assert!(f.is_extended);
+        assert_eq!(f.direction.as_deref(), Some("tx"));
+    }
+
+    #[test]
+    fn parses_fd_and_remote_frames() {
+        let f = parse("(1.000000) can0 7E8##1").unwrap();
+        assert!(f.is_fd && f.bytes.is_empty());
+        let f = parse("(1.000000) can0 7E8##30011223344556677AA").unwrap();
+        assert!(f.is_fd);
+        assert_eq!(f.dlc, 9);
+        let f = parse("(1.000000) can0 123#R4").unwrap();
+        assert_eq!((f.dlc, f.bytes.len()), (4, 0));
+    }
+
+    #[test]
+    fn written_lines_parse_back() {
+        let lines = [
+            "(1436509052.249713) can0 123#DEADBEEF R",
+            "(12.500000) can1 18DAF110#0011 T",
+            "(1.000000) can0 7E8##0112233445566778899 R",
+        ];
+        let mut out = Vec::new();
+        let mut writer = CandumpWriter::new(&mut out);
+        for line in lines {
+            writer.write_frame(&parse(line).unwrap()).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), 3);
+        assert_eq!(String::from_utf8(out).unwrap().lines().collect::<Vec<_>>(), lines);
+    }
+
+    #[test]
+    fn classifies_error_frames_and_numbers_unnamed_buses() {
+        let f = parse("(1.0) can1 20000004#0030000000000000").unwrap();
+        assert_eq!((f.frame_id, f.bus, f.is_error), (0x2000_0004, 1, Some(true)));
+        assert_eq!(f.error_class, Some(crate::io::CanErrorClass::ErrorPassive));
+        assert!(parse("not a candump line").is_none());
+        assert!(parse("(1.0) can0 123#ABC").is_none());
+
+        let mut buses = BusNumbers::default();
+        assert_eq!(buses.bus_for("can2"), 2);
+        assert_eq!(buses.bus_for("slcan"), 3);
+        assert_eq!(buses.bus_for("can2"), 2);
+    }
+}
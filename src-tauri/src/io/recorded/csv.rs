@@ -4,8 +4,9 @@
 // Used by the capture import system to parse CSV/CAN dump files into FrameMessages.
 
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 
+use crate::io::can_error;
 use crate::io::FrameMessage;
 
 // ============================================================================
@@ -236,9 +237,38 @@ fn parse_csv_header(header: &str) -> CsvColumnIndices {
     indices
 }
 
-/// Parse a GVRET CSV line into a FrameMessage using detected column indices
-fn parse_csv_line_with_indices(line: &str, indices: &CsvColumnIndices) -> Option<FrameMessage> {
-    let parts: Vec<&str> = line.split(',').collect();
+/// Parse a hex byte, with or without a 0x prefix (0 when invalid)
+fn parse_hex_byte(s: &str) -> u8 {
+    let digits = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+    u8::from_str_radix(digits, 16).unwrap_or(0)
+}
+
+/// Rows whose extended ID carries CAN_ERR_FLAG (how error frames are
+/// exported) become error frames, keeping the row's direction.
+fn error_frame_row(frame: FrameMessage) -> FrameMessage {
+    if !frame.is_extended || !can_error::is_error_id(frame.frame_id) {
+        return frame;
+    }
+    let error = can_error::error_frame(frame.frame_id, &frame.bytes, frame.bus, frame.timestamp_us);
+    FrameMessage { direction: frame.direction.or(error.direction.clone()), ..error }
+}
+
+/// Parse a GVRET CSV line into a FrameMessage using detected column indices.
+/// `parts` is scratch space for the split fields, reused across lines so the
+/// hot loop doesn't allocate per line.
+fn parse_csv_line_with_indices<'a>(
+    line: &'a str,
+    indices: &CsvColumnIndices,
+    parts: &mut Vec<&'a str>,
+) -> Option<FrameMessage> {
+    parts.clear();
+    let bytes = line.as_bytes();
+    let mut start = 0;
+    for comma in memchr::memchr_iter(b',', bytes) {
+        parts.push(&line[start..comma]);
+        start = comma + 1;
+    }
+    parts.push(&line[start..]);
 
     // Need at least enough columns for data_start
     if parts.len() <= indices.data_start {
@@ -251,7 +281,7 @@ fn parse_csv_line_with_indices(line: &str, indices: &CsvColumnIndices) -> Option
     let id_str = parts.get(indices.id)?.trim();
     let frame_id: u32 = if id_str.starts_with("0x") || id_str.starts_with("0X") {
         u32::from_str_radix(&id_str[2..], 16).ok()?
-    } else if id_str.chars().all(|c| c.is_ascii_hexdigit()) && id_str.len() == 8 {
+    } else if id_str.len() == 8 && id_str.bytes().all(|c| c.is_ascii_hexdigit()) {
         // 8-char hex without prefix (GVRET format)
         u32::from_str_radix(id_str, 16).ok()?
     } else {
@@ -274,8 +304,7 @@ fn parse_csv_line_with_indices(line: &str, indices: &CsvColumnIndices) -> Option
     // Parse direction if present
     let direction = indices.dir.and_then(|dir_idx| {
         parts.get(dir_idx).map(|s| {
-            let dir = s.trim().to_lowercase();
-            if dir == "tx" { "tx".to_string() } else { "rx".to_string() }
+            if s.trim().eq_ignore_ascii_case("tx") { "tx".to_string() } else { "rx".to_string() }
         })
     });
 
@@ -287,17 +316,11 @@ fn parse_csv_line_with_indices(line: &str, indices: &CsvColumnIndices) -> Option
             if byte_str.is_empty() {
                 break;
             }
-            // Parse hex byte (with or without 0x)
-            let byte_val = if byte_str.starts_with("0x") || byte_str.starts_with("0X") {
-                u8::from_str_radix(&byte_str[2..], 16).unwrap_or(0)
-            } else {
-                u8::from_str_radix(byte_str, 16).unwrap_or(0)
-            };
-            bytes.push(byte_val);
+            bytes.push(parse_hex_byte(byte_str));
         }
     }
 
-    Some(FrameMessage {
+    Some(error_frame_row(FrameMessage {
         protocol: "can".to_string(),
         timestamp_us,
         frame_id,
@@ -309,42 +332,155 @@ fn parse_csv_line_with_indices(line: &str, indices: &CsvColumnIndices) -> Option
         source_address: None,
         incomplete: None,
         direction,
-    })
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    }))
+}
+
+/// Smallest slice of the file worth handing to its own parser thread
+const CSV_PARALLEL_CHUNK: usize = 4 * 1024 * 1024;
+
+/// Parse the lines of one chunk (whole lines only) into a preallocated arena.
+fn parse_csv_chunk(chunk: &str, indices: &CsvColumnIndices) -> Vec<FrameMessage> {
+    let line_count = memchr::memchr_iter(b'\n', chunk.as_bytes()).count() + 1;
+    let mut frames = Vec::with_capacity(line_count);
+    let mut parts = Vec::with_capacity(indices.data_start + 16);
+    for line in chunk.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(frame) = parse_csv_line_with_indices(line, indices, &mut parts) {
+            frames.push(frame);
+        }
+    }
+    frames
 }
 
+/// Split `body` into up to `threads` chunks ending on line boundaries, parse
+/// them in parallel and concatenate the results in file order.
+fn parse_csv_body(body: &str, indices: &CsvColumnIndices, threads: usize, min_chunk: usize) -> Vec<FrameMessage> {
+    let threads = threads.min(body.len() / min_chunk.max(1)).max(1);
+    if threads == 1 {
+        return parse_csv_chunk(body, indices);
+    }
+
+    let bytes = body.as_bytes();
+    let target = body.len() / threads;
+    let mut chunks = Vec::with_capacity(threads);
+    let mut start = 0;
+    while start < body.len() {
+        let end = if chunks.len() + 1 == threads || start + target >= body.len() {
+            body.len()
+        } else {
+            memchr::memchr(b'\n', &bytes[start + target..])
+                .map_or(body.len(), |i| start + target + i + 1)
+        };
+        chunks.push(&body[start..end]);
+        start = end;
+    }
+
+    let parsed: Vec<Vec<FrameMessage>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .iter()
+            .map(|chunk| scope.spawn(move || parse_csv_chunk(chunk, indices)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap_or_default())
+            .collect()
+    });
 
-/// Parse an entire CSV file and return all frames
+    let mut frames = Vec::with_capacity(parsed.iter().map(Vec::len).sum());
+    for chunk in parsed {
+        frames.extend(chunk);
+    }
+    frames
+}
+
+/// Parse an entire CSV file and return all frames.
+///
+/// The file is read in one go and split into line-aligned chunks (memchr, so
+/// SIMD where available) that are parsed on all cores and merged in order.
 pub fn parse_csv_file(file_path: &str) -> Result<Vec<FrameMessage>, String> {
-    let file = File::open(file_path)
+    let data = std::fs::read(file_path)
         .map_err(|e| format!("Failed to open CSV file '{}': {}", file_path, e))?;
-    let reader = BufReader::new(file);
+    let text = std::str::from_utf8(&data).map_err(|e| {
+        let line = memchr::memchr_iter(b'\n', &data[..e.valid_up_to()]).count() + 1;
+        format!("Failed to read line {}: stream did not contain valid UTF-8", line)
+    })?;
+
+    // Detect header and parse column indices
+    let (first, rest) = text.split_once('\n').unwrap_or((text, ""));
+    let first_lower = first.to_lowercase();
+    let (indices, body) = if first_lower.contains("time") || first_lower.contains("id,") {
+        (parse_csv_header(first.trim_end_matches('\r')), rest)
+    } else {
+        (CsvColumnIndices::default(), text)
+    };
 
-    let mut frames: Vec<FrameMessage> = Vec::new();
-    let mut line_number = 0;
-    let mut indices: Option<CsvColumnIndices> = None;
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    Ok(parse_csv_body(body, &indices, threads, CSV_PARALLEL_CHUNK))
+}
 
-    for line_result in reader.lines() {
-        line_number += 1;
-        let line = line_result.map_err(|e| format!("Failed to read line {}: {}", line_number, e))?;
 
-        // Skip empty lines
-        if line.trim().is_empty() {
-            continue;
-        }
+// ============================================================================
+// CSV export (SavvyCAN/GVRET layout, readable by parse_csv_file)
+// ============================================================================
 
-        // Detect header and parse column indices
-        if line_number == 1 && (line.to_lowercase().contains("time") || line.to_lowercase().contains("id,")) {
-            indices = Some(parse_csv_header(&line));
-            continue;
-        }
+const CSV_EXPORT_HEADER: &str = "Time Stamp,ID,Extended,Dir,Bus,LEN,D1,D2,D3,D4,D5,D6,D7,D8";
 
-        let col_indices = indices.as_ref().cloned().unwrap_or_default();
-        if let Some(frame) = parse_csv_line_with_indices(&line, &col_indices) {
-            frames.push(frame);
+/// Streams frames into a SavvyCAN-style CSV file. CAN FD rows carry more data
+/// columns than the header names. Call `finish` to flush.
+pub struct CsvWriter<W: Write> {
+    out: W,
+    written: u32,
+}
+
+impl CsvWriter<BufWriter<File>> {
+    pub fn create(file_path: &str) -> Result<Self, String> {
+        let file = File::create(file_path)
+            .map_err(|e| format!("Failed to create CSV file '{}': {}", file_path, e))?;
+        CsvWriter::new(BufWriter::new(file))
+    }
+}
+
+impl<W: Write> CsvWriter<W> {
+    pub fn new(mut out: W) -> Result<Self, String> {
+        writeln!(out, "{}", CSV_EXPORT_HEADER).map_err(|e| format!("Failed to write CSV header: {}", e))?;
+        Ok(CsvWriter { out, written: 0 })
+    }
+
+    /// Append a frame row. Every protocol is kept.
+    pub fn write_frame(&mut self, frame: &FrameMessage) -> Result<(), String> {
+        let dir = if frame.direction.as_deref() == Some("tx") { "Tx" } else { "Rx" };
+        let mut line = format!(
+            "{},{:08X},{},{},{},{}",
+            frame.timestamp_us,
+            frame.frame_id,
+            frame.is_extended,
+            dir,
+            frame.bus,
+            frame.bytes.len()
+        );
+        for b in &frame.bytes {
+            line.push_str(&format!(",{:02X}", b));
         }
+        writeln!(self.out, "{}", line).map_err(|e| format!("Failed to write CSV row: {}", e))?;
+        self.written += 1;
+        Ok(())
     }
 
-    Ok(frames)
+    /// Flush. Returns the number of frames written.
+    pub fn finish(mut self) -> Result<u32, String> {
+        self.out
+            .flush()
+            .map_err(|e| format!("Failed to finish CSV file: {}", e))?;
+        Ok(self.written)
+    }
 }
 
 // ============================================================================
@@ -664,7 +800,7 @@ pub fn parse_csv_with_mapping(
         });
 
         frame_line_numbers.push(line_number);
-        frames.push(FrameMessage {
+        frames.push(error_frame_row(FrameMessage {
             protocol: "can".to_string(),
             timestamp_us,
             frame_id,
@@ -676,7 +812,12 @@ pub fn parse_csv_with_mapping(
             source_address: None,
             incomplete: None,
             direction,
-        });
+            link: None,
+            clock: None,
+            is_error: None,
+            error_class: None,
+            brs: None,
+        }));
     }
 
     // Normalise timestamps, then convert to microseconds.
@@ -1344,3 +1485,72 @@ fn suggest_timestamp_unit(
 
     TimestampUnit::Microseconds
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn savvycan_rows(n: usize) -> String {
+        let mut s = String::new();
+        for i in 0..n {
+            s.push_str(&format!("{},{:08X},true,Rx,{},3,0x{:02X},AB,0c\r\n", i * 10, 0x100 + i, i % 3, i % 256));
+        }
+        s
+    }
+
+    #[test]
+    fn parses_savvycan_lines() {
+        let indices = CsvColumnIndices::default();
+        let mut parts = Vec::new();
+        let f = parse_csv_line_with_indices("1000,000007E8,false,Tx,1,2,0x10,ff", &indices, &mut parts).unwrap();
+        assert_eq!((f.timestamp_us, f.frame_id, f.bus, f.dlc), (1000, 0x7E8, 1, 2));
+        assert_eq!(f.bytes, vec![0x10, 0xFF]);
+        assert_eq!(f.direction.as_deref(), Some("tx"));
+        assert!(!f.is_extended);
+        assert!(parse_csv_line_with_indices("1000,12,false", &indices, &mut parts).is_none());
+
+        let f = parse_csv_line_with_indices("1000,20000040,true,Rx,0,8,00,00,00,00,00,00,00,00", &indices, &mut parts).unwrap();
+        assert_eq!((f.frame_id, f.is_error), (0x2000_0040, Some(true)));
+        assert_eq!(f.error_class, Some(crate::io::CanErrorClass::BusOff));
+    }
+
+    #[test]
+    fn written_rows_parse_back() {
+        let indices = CsvColumnIndices::default();
+        let mut parts = Vec::new();
+        let source = [
+            "1000,000007E8,false,Tx,1,2,10,FF",
+            "2500,18DAF110,true,Rx,0,12,00,01,02,03,04,05,06,07,08,09,0A,0B",
+        ];
+        let frames: Vec<_> = source
+            .iter()
+            .map(|line| parse_csv_line_with_indices(line, &indices, &mut parts).unwrap())
+            .collect();
+
+        let mut out = Vec::new();
+        let mut writer = CsvWriter::new(&mut out).unwrap();
+        for f in &frames {
+            writer.write_frame(f).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), 2);
+        let text = String::from_utf8(out).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(lines.next(), Some(CSV_EXPORT_HEADER));
+        assert_eq!(lines.collect::<Vec<_>>(), source);
+        assert!(frames[1].is_fd);
+    }
+
+    #[test]
+    fn parallel_chunks_match_sequential_parse() {
+        let body = savvycan_rows(1000);
+        let indices = CsvColumnIndices::default();
+        let sequential = parse_csv_chunk(&body, &indices);
+        let parallel = parse_csv_body(&body, &indices, 7, 64);
+        assert_eq!(sequential.len(), 1000);
+        assert_eq!(parallel.len(), sequential.len());
+        for (a, b) in parallel.iter().zip(&sequential) {
+            assert_eq!((a.timestamp_us, a.frame_id, &a.bytes), (b.timestamp_us, b.frame_id, &b.bytes));
+        }
+        assert_eq!(parallel[999].frame_id, 0x100 + 999);
+    }
+}
@@ -0,0 +1,815 @@
+// ui/src-tauri/src/io/recorded/mdf4.rs
+//
+// ASAM MDF4 (.mf4) reading for CAN bus logging files, in the layout CSS
+// Electronics CANedge loggers write: a channel group per frame type
+// (CAN_DataFrame, CAN_RemoteFrame, ...) whose structure channel composes
+// BusChannel / ID / IDE / DLC / DataLength / Dir / EDL / BRS / DataBytes, plus a
+// Timestamp master channel in seconds from the header's start time.
+// CAN_ErrorFrame groups become error frames through can_error, classified by
+// their ErrorType channel (1 bit, 2 form, 3 stuff, 4 CRC, 5 ACK). CANedge
+// puts all groups in one unsorted data group (records prefixed by a record
+// ID); sorted files with one group per data group work too.
+//
+// Only the metadata blocks (HD/DG/CG/CN/TX/CC and the DL/HL data lists) are
+// read when a file is opened. Record data is read lazily, block by block —
+// DT blocks in 1 MiB chunks, DZ blocks inflated (and de-transposed) one at a
+// time — with a carry buffer for records straddling blocks, so memory stays
+// bounded however large the file is. Each data group streams its records in
+// time order and the groups are merged, so frames come out time-ordered.
+// A start time skips records without building frames; an end time stops each
+// group once it passes.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+
+use flate2::read::ZlibDecoder;
+
+use crate::io::can_error::{self, Violation};
+use crate::io::FrameMessage;
+
+const BLOCK_HEADER_SIZE: u64 = 24;
+
+/// Largest piece of a DT block read at once
+const DT_CHUNK: usize = 1024 * 1024;
+
+/// Upper bound on a metadata block (guards against corrupt lengths)
+const MAX_META_BLOCK: u64 = 16 * 1024 * 1024;
+
+/// Upper bound on a DZ block's inflated size
+const MAX_DZ_BLOCK: u64 = 256 * 1024 * 1024;
+
+// Channel data types
+const DT_UINT_LE: u8 = 0;
+const DT_FLOAT_LE: u8 = 4;
+const DT_BYTE_ARRAY: u8 = 10;
+
+// cg_flags: variable-length signal data group
+const CG_VLSD: u16 = 0x0001;
+
+// ============================================================================
+// Blocks
+// ============================================================================
+
+struct Block {
+    /// Block type ("HD", "DG", "DT", ...) from the "##XX" identifier
+    kind: [u8; 2],
+    links: Vec<u64>,
+    data: Vec<u8>,
+}
+
+impl Block {
+    fn link(&self, i: usize) -> u64 {
+        self.links.get(i).copied().unwrap_or(0)
+    }
+}
+
+fn le_u16(b: &[u8], at: usize) -> u16 {
+    b.get(at..at + 2).map_or(0, |s| u16::from_le_bytes([s[0], s[1]]))
+}
+
+fn le_u32(b: &[u8], at: usize) -> u32 {
+    b.get(at..at + 4).map_or(0, |s| u32::from_le_bytes(s.try_into().unwrap()))
+}
+
+fn le_u64(b: &[u8], at: usize) -> u64 {
+    b.get(at..at + 8).map_or(0, |s| u64::from_le_bytes(s.try_into().unwrap()))
+}
+
+fn le_f64(b: &[u8], at: usize) -> f64 {
+    f64::from_bits(le_u64(b, at))
+}
+
+/// Read a block's header and links, and its data when `with_data` is set
+/// (data blocks are read separately, in pieces).
+fn read_block<R: Read + Seek>(r: &mut R, offset: u64, with_data: bool) -> Result<(Block, u64), String> {
+    let mut header = [0u8; BLOCK_HEADER_SIZE as usize];
+    r.seek(SeekFrom::Start(offset))
+        .and_then(|_| r.read_exact(&mut header))
+        .map_err(|e| format!("Failed to read MDF block at {:#x}: {}", offset, e))?;
+    if &header[..2] != b"##" {
+        return Err(format!("Invalid MDF block at {:#x}", offset));
+    }
+    let length = le_u64(&header, 8);
+    let link_count = le_u64(&header, 16);
+    let links_len = link_count.saturating_mul(8);
+    if length < BLOCK_HEADER_SIZE + links_len {
+        return Err(format!("Corrupt MDF block at {:#x}", offset));
+    }
+    let mut links_raw = vec![0u8; links_len as usize];
+    r.read_exact(&mut links_raw)
+        .map_err(|e| format!("Failed to read MDF block links: {}", e))?;
+    let links = links_raw.chunks_exact(8).map(|c| u64::from_le_bytes(c.try_into().unwrap())).collect();
+    let data_len = length - BLOCK_HEADER_SIZE - links_len;
+    let mut data = Vec::new();
+    if with_data {
+        if data_len > MAX_META_BLOCK {
+            return Err(format!("MDF block at {:#x} is too large", offset));
+        }
+        data = vec![0u8; data_len as usize];
+        r.read_exact(&mut data)
+            .map_err(|e| format!("Failed to read MDF block data: {}", e))?;
+    }
+    Ok((Block { kind: [header[2], header[3]], links, data }, data_len))
+}
+
+fn read_text<R: Read + Seek>(r: &mut R, offset: u64) -> Option<String> {
+    if offset == 0 {
+        return None;
+    }
+    let (block, _) = read_block(r, offset, true).ok()?;
+    if &block.kind != b"TX" && &block.kind != b"MD" {
+        return None;
+    }
+    let end = block.data.iter().position(|&b| b == 0).unwrap_or(block.data.len());
+    Some(String::from_utf8_lossy(&block.data[..end]).trim().to_string())
+}
+
+// ============================================================================
+// Channel Layout
+// ============================================================================
+
+/// Where a channel's value sits in a record (after the record ID).
+#[derive(Clone, Debug, Default)]
+struct Field {
+    byte_offset: usize,
+    bit_offset: u32,
+    bit_count: u32,
+    data_type: u8,
+    /// Linear conversion (offset, factor), when the channel has one
+    linear: Option<(f64, f64)>,
+}
+
+impl Field {
+    fn uint(&self, record: &[u8]) -> Option<u64> {
+        let nbytes = (self.bit_offset + self.bit_count).div_ceil(8) as usize;
+        let bytes = record.get(self.byte_offset..self.byte_offset + nbytes)?;
+        let mut raw: u128 = 0;
+        for (i, &b) in bytes.iter().enumerate().take(16) {
+            raw |= (b as u128) << (8 * i);
+        }
+        raw >>= self.bit_offset;
+        if self.bit_count < 64 {
+            raw &= (1u128 << self.bit_count) - 1;
+        }
+        Some(raw as u64)
+    }
+
+    fn number(&self, record: &[u8]) -> Option<f64> {
+        let raw = match (self.data_type, self.bit_count) {
+            (DT_FLOAT_LE, 64) => le_f64(record.get(self.byte_offset..)?, 0),
+            (DT_FLOAT_LE, 32) => f32::from_bits(le_u32(record.get(self.byte_offset..)?, 0)) as f64,
+            _ => self.uint(record)? as f64,
+        };
+        Some(match self.linear {
+            Some((a, b)) => a + b * raw,
+            None => raw,
+        })
+    }
+
+    fn bytes<'a>(&self, record: &'a [u8]) -> Option<&'a [u8]> {
+        record.get(self.byte_offset..self.byte_offset + (self.bit_count / 8) as usize)
+    }
+}
+
+/// Channels of a CAN frame group, by member name.
+#[derive(Clone, Debug, Default)]
+struct CanLayout {
+    remote: bool,
+    /// CAN_ErrorFrame group
+    error: bool,
+    timestamp: Field,
+    bus: Option<Field>,
+    /// Required for data and remote frames
+    id: Option<Field>,
+    ide: Option<Field>,
+    dlc: Option<Field>,
+    data_length: Option<Field>,
+    dir: Option<Field>,
+    edl: Option<Field>,
+    data_bytes: Option<Field>,
+    error_type: Option<Field>,
+}
+
+impl CanLayout {
+    fn timestamp_us(&self, record: &[u8], start_us: u64) -> Option<u64> {
+        let secs = self.timestamp.number(record)?;
+        Some(start_us.saturating_add((secs.max(0.0) * 1_000_000.0).round() as u64))
+    }
+
+    fn frame(&self, record: &[u8], timestamp_us: u64) -> Option<FrameMessage> {
+        let uint = |f: &Option<Field>| f.as_ref().and_then(|f| f.uint(record));
+        let bus = uint(&self.bus).map_or(0, |b| b.saturating_sub(1).min(255) as u8);
+        if self.error {
+            let violation = match uint(&self.error_type) {
+                Some(1) => Violation::Bit,
+                Some(2) => Violation::Form,
+                Some(3) => Violation::Stuff,
+                Some(4) => Violation::Crc,
+                Some(5) => Violation::Ack,
+                _ => Violation::Other,
+            };
+            return Some(can_error::violation_frame(violation, bus, timestamp_us));
+        }
+        let raw_id = uint(&self.id)? as u32;
+        // Some writers fold the IDE flag into bit 31 of the ID
+        let is_extended = uint(&self.ide).map_or(raw_id & 0x8000_0000 != 0, |v| v != 0);
+        let dlc = uint(&self.dlc).unwrap_or(0) as u8;
+        let bytes = match (&self.data_bytes, self.remote) {
+            (Some(field), false) => {
+                let all = field.bytes(record)?;
+                let len = uint(&self.data_length).map_or(all.len(), |l| l as usize).min(all.len());
+                all[..len].to_vec()
+            }
+            _ => Vec::new(),
+        };
+        Some(FrameMessage {
+            protocol: "can".to_string(),
+            timestamp_us,
+            frame_id: raw_id & 0x1FFF_FFFF,
+            bus,
+            dlc: if self.remote { dlc } else { bytes.len() as u8 },
+            is_fd: uint(&self.edl).is_some_and(|v| v != 0),
+            bytes,
+            is_extended,
+            source_address: None,
+            incomplete: None,
+            direction: Some(if uint(&self.dir).is_some_and(|v| v != 0) { "tx" } else { "rx" }.to_string()),
+            link: None,
+            clock: None,
+            is_error: None,
+            error_class: None,
+            brs: None,
+        })
+    }
+}
+
+/// How to step over (and possibly decode) one record ID's records.
+#[derive(Clone, Debug)]
+enum RecordLayout {
+    /// Fixed-size record (data + invalidation bytes)
+    Fixed { size: usize, can: Option<CanLayout> },
+    /// Variable-length signal data: u32 length, then that many bytes
+    Vlsd,
+}
+
+struct Channel {
+    name: String,
+    cn_type: u8,
+    sync_type: u8,
+    field: Field,
+    composition: u64,
+}
+
+fn read_channel<R: Read + Seek>(r: &mut R, offset: u64) -> Result<(Channel, u64), String> {
+    let (block, _) = read_block(r, offset, true)?;
+    if &block.kind != b"CN" {
+        return Err(format!("Expected CN block at {:#x}", offset));
+    }
+    let d = &block.data;
+    let mut field = Field {
+        byte_offset: le_u32(d, 4) as usize,
+        bit_offset: *d.get(3).unwrap_or(&0) as u32,
+        bit_count: le_u32(d, 8),
+        data_type: *d.get(2).unwrap_or(&0),
+        linear: None,
+    };
+    // Linear conversion (cc_type 1: phys = a + b * raw)
+    let cc = block.link(4);
+    if cc != 0 {
+        if let Ok((cc_block, _)) = read_block(r, cc, true) {
+            if &cc_block.kind == b"CC" && cc_block.data.first() == Some(&1) {
+                field.linear = Some((le_f64(&cc_block.data, 24), le_f64(&cc_block.data, 32)));
+            }
+        }
+    }
+    let name = read_text(r, block.link(2)).unwrap_or_default();
+    Ok((
+        Channel {
+            name,
+            cn_type: *d.first().unwrap_or(&0),
+            sync_type: *d.get(1).unwrap_or(&0),
+            field,
+            composition: block.link(1),
+        },
+        block.link(0),
+    ))
+}
+
+/// Walk a CN chain, descending into compositions, collecting every channel.
+fn read_channels<R: Read + Seek>(r: &mut R, first: u64, out: &mut Vec<Channel>, depth: usize) -> Result<(), String> {
+    let mut next = first;
+    let mut guard = 0;
+    while next != 0 && guard < 4096 {
+        let (channel, following) = read_channel(r, next)?;
+        let composition = channel.composition;
+        out.push(channel);
+        if composition != 0 && depth < 4 {
+            // A composition may also point at a CA (array) block; only CN chains are followed
+            let (peek, _) = read_block(r, composition, false)?;
+            if &peek.kind == b"CN" {
+                read_channels(r, composition, out, depth + 1)?;
+            }
+        }
+        next = following;
+        guard += 1;
+    }
+    Ok(())
+}
+
+/// Member name without any "CAN_DataFrame." style prefix.
+fn member(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Build the CAN layout of a channel group, if it is a CAN frame group.
+fn can_layout(group_name: &str, channels: &[Channel]) -> Option<CanLayout> {
+    let frame_type = channels
+        .iter()
+        .map(|c| c.name.as_str())
+        .chain(std::iter::once(group_name))
+        .find(|n| ["CAN_DataFrame", "CAN_RemoteFrame", "CAN_ErrorFrame"].iter().any(|t| n.starts_with(t)))?;
+    let find = |name: &str| {
+        channels
+            .iter()
+            .find(|c| member(&c.name).eq_ignore_ascii_case(name))
+            .map(|c| c.field.clone())
+    };
+    // Master time channel (cn_type 2 master / 3 virtual master, sync type time)
+    let timestamp = channels
+        .iter()
+        .find(|c| matches!(c.cn_type, 2 | 3) && c.sync_type == 1)
+        .map(|c| c.field.clone())
+        .or_else(|| find("Timestamp"))?;
+    let error = frame_type.starts_with("CAN_ErrorFrame");
+    let id = find("ID");
+    if !error && id.is_none() {
+        return None;
+    }
+    Some(CanLayout {
+        remote: frame_type.starts_with("CAN_RemoteFrame"),
+        error,
+        timestamp,
+        bus: find("BusChannel"),
+        id,
+        ide: find("IDE"),
+        dlc: find("DLC"),
+        data_length: find("DataLength"),
+        dir: find("Dir"),
+        edl: find("EDL"),
+        data_bytes: find("DataBytes").filter(|f| f.data_type == DT_BYTE_ARRAY || f.data_type == DT_UINT_LE),
+        error_type: find("ErrorType"),
+    })
+}
+
+// ============================================================================
+// Data Streams
+// ============================================================================
+
+#[derive(Clone, Debug)]
+enum DataBlock {
+    /// Raw records: file offset and length of the block's data
+    Dt { offset: u64, len: u64 },
+    /// Compressed records: offset of the DZ block
+    Dz { offset: u64 },
+}
+
+/// Resolve a data link (DT, DZ, DL chain or HL) to its data blocks.
+fn collect_data_blocks<R: Read + Seek>(r: &mut R, link: u64, out: &mut Vec<DataBlock>) -> Result<(), String> {
+    if link == 0 {
+        return Ok(());
+    }
+    let (block, data_len) = read_block(r, link, false)?;
+    match &block.kind {
+        b"DT" => out.push(DataBlock::Dt {
+            offset: link + BLOCK_HEADER_SIZE + block.links.len() as u64 * 8,
+            len: data_len,
+        }),
+        b"DZ" => out.push(DataBlock::Dz { offset: link }),
+        b"HL" => collect_data_blocks(r, block.link(0), out)?,
+        b"DL" => {
+            let mut next = Some(block);
+            let mut guard = 0;
+            while let Some(list) = next.take() {
+                for &data in list.links.iter().skip(1) {
+                    collect_data_blocks(r, data, out)?;
+                }
+                let following = list.link(0);
+                guard += 1;
+                if following != 0 && guard < 1_000_000 {
+                    next = Some(read_block(r, following, false)?.0);
+                }
+            }
+        }
+        // SD/RD/empty blocks carry no records for us
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Inflate a DZ block, undoing byte transposition when used.
+fn read_dz<R: Read + Seek>(r: &mut R, offset: u64) -> Result<Vec<u8>, String> {
+    let (_, data_len) = read_block(r, offset, false)?;
+    let mut header = [0u8; 24];
+    r.read_exact(&mut header)
+        .map_err(|e| format!("Failed to read DZ header: {}", e))?;
+    let zip_type = header[2];
+    let zip_parameter = le_u32(&header, 4) as usize;
+    let org_len = le_u64(&header, 8);
+    let zipped_len = le_u64(&header, 16).min(data_len.saturating_sub(24));
+    if org_len > MAX_DZ_BLOCK {
+        return Err(format!("DZ block at {:#x} is too large", offset));
+    }
+    let mut zipped = vec![0u8; zipped_len as usize];
+    r.read_exact(&mut zipped)
+        .map_err(|e| format!("Failed to read DZ data: {}", e))?;
+    let mut data = Vec::with_capacity(org_len as usize);
+    ZlibDecoder::new(&zipped[..])
+        .read_to_end(&mut data)
+        .map_err(|e| format!("Failed to inflate DZ block: {}", e))?;
+    if zip_type == 1 && zip_parameter > 0 {
+        data = detranspose(&data, zip_parameter);
+    }
+    Ok(data)
+}
+
+/// Undo DZ transposition: `columns`-byte records were stored column by column.
+fn detranspose(data: &[u8], columns: usize) -> Vec<u8> {
+    let rows = data.len() / columns;
+    let n = rows * columns;
+    let mut out = vec![0u8; data.len()];
+    for c in 0..columns {
+        for row in 0..rows {
+            out[row * columns + c] = data[c * rows + row];
+        }
+    }
+    out[n..].copy_from_slice(&data[n..]);
+    out
+}
+
+/// Records of one data group, read lazily.
+struct GroupStream {
+    rec_id_size: usize,
+    records: HashMap<u64, RecordLayout>,
+    blocks: Vec<DataBlock>,
+    next_block: usize,
+    /// Unread remainder of the current DT block (offset, len)
+    dt_remaining: Option<(u64, u64)>,
+    carry: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl GroupStream {
+    /// Append the next piece of record data to the carry buffer.
+    fn refill<R: Read + Seek>(&mut self, r: &mut R) -> Result<bool, String> {
+        self.carry.drain(..self.pos);
+        self.pos = 0;
+        loop {
+            if let Some((offset, len)) = self.dt_remaining {
+                let take = len.min(DT_CHUNK as u64);
+                let start = self.carry.len();
+                self.carry.resize(start + take as usize, 0);
+                r.seek(SeekFrom::Start(offset))
+                    .and_then(|_| r.read_exact(&mut self.carry[start..]))
+                    .map_err(|e| format!("Failed to read DT data: {}", e))?;
+                self.dt_remaining = (len > take).then_some((offset + take, len - take));
+                return Ok(true);
+            }
+            let Some(block) = self.blocks.get(self.next_block).cloned() else {
+                return Ok(false);
+            };
+            self.next_block += 1;
+            match block {
+                DataBlock::Dt { offset, len } => self.dt_remaining = (len > 0).then_some((offset, len)),
+                DataBlock::Dz { offset } => {
+                    let data = read_dz(r, offset)?;
+                    self.carry.extend_from_slice(&data);
+                    return Ok(true);
+                }
+            }
+        }
+    }
+
+    /// Next CAN frame at or after `start_us` (None when the group is exhausted).
+    fn next_frame<R: Read + Seek>(&mut self, r: &mut R, start_us: u64, from_us: u64) -> Result<Option<FrameMessage>, String> {
+        while !self.done {
+            let avail = &self.carry[self.pos..];
+            let parsed = self.parse_record(avail);
+            match parsed {
+                Ok(Some((consumed, frame))) => {
+                    let record_start = self.pos + self.rec_id_size;
+                    self.pos += consumed;
+                    let Some(layout) = frame else { continue };
+                    let record = &self.carry[record_start..self.pos];
+                    let Some(ts) = layout.timestamp_us(record, start_us) else { continue };
+                    if ts < from_us {
+                        continue;
+                    }
+                    if let Some(frame) = layout.frame(record, ts) {
+                        return Ok(Some(frame));
+                    }
+                }
+                Ok(None) => {
+                    if !self.refill(r)? {
+                        self.done = true;
+                    }
+                }
+                Err(e) => {
+                    tlog!("[MDF4] Stopping data group: {}", e);
+                    self.done = true;
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Size of the record at the front of `avail` and its CAN layout, or
+    /// None when more data is needed.
+    fn parse_record(&self, avail: &[u8]) -> Result<Option<(usize, Option<CanLayout>)>, String> {
+        if avail.len() < self.rec_id_size.max(1) {
+            return Ok(None);
+        }
+        let rec_id = match self.rec_id_size {
+            0 => 0,
+            1 => avail[0] as u64,
+            2 => le_u16(avail, 0) as u64,
+            4 => le_u32(avail, 0) as u64,
+            _ => le_u64(avail, 0),
+        };
+        let layout = match self.rec_id_size {
+            0 => self.records.values().next(),
+            _ => self.records.get(&rec_id),
+        }
+        .ok_or_else(|| format!("unknown record ID {}", rec_id))?;
+        let body = &avail[self.rec_id_size..];
+        match layout {
+            RecordLayout::Fixed { size, can } => {
+                if body.len() < *size {
+                    return Ok(None);
+                }
+                Ok(Some((self.rec_id_size + size, can.clone())))
+            }
+            RecordLayout::Vlsd => {
+                if body.len() < 4 {
+                    return Ok(None);
+                }
+                let len = le_u32(body, 0) as usize;
+                if body.len() < 4 + len {
+                    return Ok(None);
+                }
+                Ok(Some((self.rec_id_size + 4 + len, None)))
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Reader
+// ============================================================================
+
+/// An opened MDF4 file: structure parsed, record data read on demand.
+pub struct Mdf4Reader {
+    file: BufReader<File>,
+    /// Measurement start (µs since the Unix epoch)
+    start_us: u64,
+    groups: Vec<GroupStream>,
+}
+
+impl Mdf4Reader {
+    pub fn open(file_path: &str) -> Result<Self, String> {
+        let file = File::open(file_path)
+            .map_err(|e| format!("Failed to open MDF file '{}': {}", file_path, e))?;
+        let mut r = BufReader::new(file);
+
+        let mut id_block = [0u8; 64];
+        r.read_exact(&mut id_block)
+            .map_err(|_| "File is too short to be an MDF log".to_string())?;
+        if !id_block.starts_with(b"MDF") && !id_block.starts_with(b"UnFinMF") {
+            return Err("Not an MDF file (missing MDF identification block)".to_string());
+        }
+        let version = le_u16(&id_block, 28);
+        if version != 0 && version < 400 {
+            return Err(format!("MDF version {} is not supported (MDF4 only)", version));
+        }
+
+        let (hd, _) = read_block(&mut r, 64, true)?;
+        if &hd.kind != b"HD" {
+            return Err("MDF header block not found".to_string());
+        }
+        let start_us = le_u64(&hd.data, 0) / 1000;
+
+        let mut groups = Vec::new();
+        let mut dg_link = hd.link(0);
+        while dg_link != 0 {
+            let (dg, _) = read_block(&mut r, dg_link, true)?;
+            let rec_id_size = *dg.data.first().unwrap_or(&0) as usize;
+            let mut records = HashMap::new();
+            let mut has_can = false;
+            let mut cg_link = dg.link(1);
+            while cg_link != 0 {
+                let (cg, _) = read_block(&mut r, cg_link, true)?;
+                let record_id = le_u64(&cg.data, 0);
+                let flags = le_u16(&cg.data, 16);
+                let layout = if flags & CG_VLSD != 0 {
+                    RecordLayout::Vlsd
+                } else {
+                    let size = le_u32(&cg.data, 24) as usize + le_u32(&cg.data, 28) as usize;
+                    let mut channels = Vec::new();
+                    read_channels(&mut r, cg.link(1), &mut channels, 0)?;
+                    let group_name = read_text(&mut r, cg.link(2)).unwrap_or_default();
+                    let can = can_layout(&group_name, &channels);
+                    has_can |= can.is_some();
+                    RecordLayout::Fixed { size, can }
+                };
+                records.insert(record_id, layout);
+                cg_link = cg.link(0);
+            }
+
+            if has_can {
+                let mut blocks = Vec::new();
+                collect_data_blocks(&mut r, dg.link(2), &mut blocks)?;
+                groups.push(GroupStream {
+                    rec_id_size,
+                    records,
+                    blocks,
+                    next_block: 0,
+                    dt_remaining: None,
+                    carry: Vec::new(),
+                    pos: 0,
+                    done: false,
+                });
+            }
+            dg_link = dg.link(0);
+        }
+
+        if groups.is_empty() {
+            return Err("MDF file has no CAN_DataFrame channel groups".to_string());
+        }
+        Ok(Mdf4Reader { file: r, start_us, groups })
+    }
+
+    /// Measurement start time (µs since the Unix epoch).
+    pub fn start_time_us(&self) -> u64 {
+        self.start_us
+    }
+
+    /// Stream frames in time order, from `start_us` up to `end_us` (either
+    /// open), in batches of up to `batch_size`. Returns the frame count.
+    pub fn read_frames(
+        &mut self,
+        start_us: Option<u64>,
+        end_us: Option<u64>,
+        batch_size: usize,
+        mut on_batch: impl FnMut(Vec<FrameMessage>) -> Result<(), String>,
+    ) -> Result<usize, String> {
+        let from_us = start_us.unwrap_or(0);
+        let until_us = end_us.unwrap_or(u64::MAX);
+        let mut heads: Vec<Option<FrameMessage>> = Vec::with_capacity(self.groups.len());
+        for group in &mut self.groups {
+            heads.push(group.next_frame(&mut self.file, self.start_us, from_us)?);
+        }
+
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut total = 0;
+        loop {
+            // Earliest head across the data groups
+            let Some(i) = heads
+                .iter()
+                .enumerate()
+                .filter_map(|(i, h)| h.as_ref().map(|f| (i, f.timestamp_us)))
+                .min_by_key(|&(_, ts)| ts)
+                .map(|(i, _)| i)
+            else {
+                break;
+            };
+            let frame = heads[i].take().unwrap();
+            if frame.timestamp_us > until_us {
+                // Groups are time-ordered: nothing later from this one is wanted
+                continue;
+            }
+            heads[i] = self.groups[i].next_frame(&mut self.file, self.start_us, from_us)?;
+            batch.push(frame);
+            total += 1;
+            if batch.len() >= batch_size {
+                on_batch(std::mem::replace(&mut batch, Vec::with_capacity(batch_size)))?;
+            }
+        }
+        if !batch.is_empty() {
+            on_batch(batch)?;
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(byte_offset: usize, bit_count: u32) -> Field {
+        Field { byte_offset, bit_offset: 0, bit_count, data_type: DT_UINT_LE, linear: None }
+    }
+
+    /// CANedge-style CAN_DataFrame record: u64 timestamp (µs via linear
+    /// conversion), bus, ID (bit 31 = IDE), DLC, DataLength, Dir, 64 data bytes.
+    fn canedge_layout() -> CanLayout {
+        CanLayout {
+            remote: false,
+            error: false,
+            timestamp: Field { linear: Some((0.0, 1e-6)), ..field(0, 64) },
+            bus: Some(field(8, 8)),
+            id: Some(field(9, 32)),
+            ide: None,
+            dlc: Some(field(13, 8)),
+            data_length: Some(field(14, 8)),
+            dir: Some(field(15, 8)),
+            edl: None,
+            data_bytes: Some(Field { data_type: DT_BYTE_ARRAY, ..field(16, 64 * 8) }),
+            error_type: None,
+        }
+    }
+
+    fn record(rec_id: u8, ts_us: u64, bus: u8, id: u32, data: &[u8]) -> Vec<u8> {
+        let mut r = vec![rec_id];
+        r.extend_from_slice(&ts_us.to_le_bytes());
+        r.push(bus);
+        r.extend_from_slice(&id.to_le_bytes());
+        r.push(data.len() as u8);
+        r.push(data.len() as u8);
+        r.push(0);
+        let mut bytes = data.to_vec();
+        bytes.resize(64, 0);
+        r.extend(bytes);
+        r
+    }
+
+    fn stream(data: Vec<u8>) -> GroupStream {
+        GroupStream {
+            rec_id_size: 1,
+            records: HashMap::from([
+                (1, RecordLayout::Fixed { size: 80, can: Some(canedge_layout()) }),
+                (2, RecordLayout::Vlsd),
+            ]),
+            blocks: Vec::new(),
+            next_block: 0,
+            dt_remaining: None,
+            carry: data,
+            pos: 0,
+            done: false,
+        }
+    }
+
+    #[test]
+    fn decodes_unsorted_canedge_records() {
+        let mut data = record(1, 1_000, 1, 0x123, &[1, 2, 3]);
+        // A VLSD record in between is stepped over
+        data.extend([2, 3, 0, 0, 0, 0xAA, 0xBB, 0xCC]);
+        data.extend(record(1, 2_500, 2, 0x8000_0000 | 0x18DA_F110, &[9; 12]));
+
+        let mut s = stream(data);
+        let mut nothing = std::io::Cursor::new(Vec::new());
+        let a = s.next_frame(&mut nothing, 1_000_000, 0).unwrap().unwrap();
+        assert_eq!((a.timestamp_us, a.bus, a.frame_id), (1_001_000, 0, 0x123));
+        assert_eq!(a.bytes, vec![1, 2, 3]);
+        assert!(!a.is_extended);
+        let b = s.next_frame(&mut nothing, 1_000_000, 0).unwrap().unwrap();
+        assert_eq!((b.bus, b.frame_id, b.bytes.len()), (1, 0x18DA_F110, 12));
+        assert!(b.is_extended);
+        assert!(s.next_frame(&mut nothing, 1_000_000, 0).unwrap().is_none());
+    }
+
+    #[test]
+    fn error_frame_groups_are_classified() {
+        // Timestamp, BusChannel, ErrorType
+        let layout = CanLayout {
+            error: true,
+            id: None,
+            data_bytes: None,
+            error_type: Some(field(9, 8)),
+            ..canedge_layout()
+        };
+        let mut record = 2_000u64.to_le_bytes().to_vec();
+        record.extend([2, 3]);
+        let f = layout.frame(&record, layout.timestamp_us(&record, 0).unwrap()).unwrap();
+        assert_eq!((f.timestamp_us, f.bus, f.is_error), (2_000, 1, Some(true)));
+        assert_eq!(f.error_class, Some(crate::io::CanErrorClass::Stuff));
+    }
+
+    #[test]
+    fn skips_records_before_start_time() {
+        let mut data = record(1, 1_000, 1, 0x100, &[1]);
+        data.extend(record(1, 5_000, 1, 0x200, &[2]));
+        let mut s = stream(data);
+        let mut nothing = std::io::Cursor::new(Vec::new());
+        let f = s.next_frame(&mut nothing, 0, 2_000).unwrap().unwrap();
+        assert_eq!(f.frame_id, 0x200);
+    }
+
+    #[test]
+    fn detransposes_dz_data() {
+        // Three 2-byte records [a0 a1][b0 b1][c0 c1] stored column-wise, plus a tail byte
+        let transposed = [b'a', b'b', b'c', b'0', b'1', b'2', b'T'];
+        assert_eq!(detranspose(&transposed, 2), b"a0b1c2T".to_vec());
+        assert_eq!(member("CAN_DataFrame.DataBytes"), "DataBytes");
+    }
+}
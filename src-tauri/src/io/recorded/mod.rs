@@ -3,18 +3,35 @@
 // Recorded sources for playback from captured/imported data.
 // These sources share common control patterns via RecordedSourceState.
 
+mod asc;
 mod backend_api;
 mod base;
+mod blf;
+mod candump;
 mod capture;
 mod csv;
+mod mdf4;
+mod multi_capture;
 mod pacing;
+mod parquet_writer;
+mod pcap;
 mod postgres;
+mod trc;
 
 // Re-export public items
+pub use asc::{AscTimeBase, AscWriter};
 pub use backend_api::{BackendApiConfig, BackendApiSource, BackendApiSourceOptions};
+pub use blf::{parse_blf_file, BlfWriter};
+pub use candump::{parse_candump_file, CandumpWriter};
+pub(crate) use candump::parse_candump_record;
 pub use capture::{step_frame, CaptureSource, StepResult};
 pub use csv::{
     parse_csv_file, parse_csv_with_mapping, preview_csv_file, CsvColumnMapping, CsvPreview,
-    Delimiter, SequenceGap, TimestampUnit,
+    CsvWriter, Delimiter, SequenceGap, TimestampUnit,
 };
+pub use mdf4::Mdf4Reader;
+pub use multi_capture::MultiCaptureSource;
+pub use parquet_writer::ParquetWriter;
+pub use pcap::{parse_pcap_file, PcapWriter};
 pub use postgres::{PostgresConfig, PostgresSource, PostgresSourceOptions, PostgresSourceType};
+pub use trc::parse_trc_file;
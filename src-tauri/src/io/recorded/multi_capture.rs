@@ -0,0 +1,429 @@
+// ui/src-tauri/src/io/recorded/multi_capture.rs
+//
+// Multi-Capture Source - replays several frame captures as one timeline.
+// Each source capture is read lazily in rowid chunks and the chunk heads are
+// merged by timestamp, so nothing is copied up front. Merged frames are
+// written to the session's own capture as they are emitted, like PostgreSQL.
+
+use async_trait::async_trait;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use super::base::{PlaybackControl, RecordedSourceState};
+use super::pacing::{HIGH_SPEED_BATCH_SIZE, MIN_DELAY_MS, NO_LIMIT_BATCH_SIZE, NO_LIMIT_YIELD_MS, PACING_INTERVAL_MS};
+use crate::capture_db;
+use crate::capture_store::{self, CaptureKind, MergeSource};
+use crate::io::{
+    emit_capture_changed, emit_session_error, emit_stream_ended, signal_frames_ready,
+    signal_playback_position, FrameMessage, IOCapabilities, IOSource, IOState, PlaybackPosition,
+    SignalThrottle,
+};
+
+/// Frames read from one source capture at a time
+const CHUNK_SIZE: usize = 2000;
+
+/// Where a [`MultiSourceReader`] reads its sources' frames from.
+pub trait FrameStore: Send {
+    /// Up to `limit` frames of `capture_id` after `after_rowid`, in rowid order.
+    fn read_after(
+        &self,
+        capture_id: &str,
+        after_rowid: i64,
+        limit: usize,
+    ) -> Result<Vec<(i64, FrameMessage)>, String>;
+}
+
+/// The capture database.
+pub struct CaptureDb;
+
+impl FrameStore for CaptureDb {
+    fn read_after(
+        &self,
+        capture_id: &str,
+        after_rowid: i64,
+        limit: usize,
+    ) -> Result<Vec<(i64, FrameMessage)>, String> {
+        capture_db::read_frame_chunk(capture_id, after_rowid, limit)
+    }
+}
+
+/// Read position within one source capture.
+struct SourceCursor {
+    capture_id: String,
+    bus_map: HashMap<u8, u8>,
+    pending: VecDeque<(i64, FrameMessage)>,
+    last_rowid: i64,
+    exhausted: bool,
+}
+
+/// Merges several frame captures by timestamp without materialising them.
+/// Only one chunk per source is held in memory. Frames with equal timestamps
+/// come out in source order, and each frame's bus is renumbered through its
+/// source's `bus_map` (unmapped buses pass through).
+pub struct MultiSourceReader<S: FrameStore = CaptureDb> {
+    store: S,
+    cursors: Vec<SourceCursor>,
+    chunk_size: usize,
+}
+
+impl MultiSourceReader {
+    /// Open a reader over the capture database. Sources are not validated
+    /// here; see [`validate_sources`].
+    pub fn open(sources: &[MergeSource]) -> Self {
+        Self::with_store(CaptureDb, sources, CHUNK_SIZE)
+    }
+}
+
+impl<S: FrameStore> MultiSourceReader<S> {
+    pub fn with_store(store: S, sources: &[MergeSource], chunk_size: usize) -> Self {
+        let cursors = sources
+            .iter()
+            .map(|src| SourceCursor {
+                capture_id: src.capture_id.clone(),
+                bus_map: src.bus_map.clone(),
+                pending: VecDeque::new(),
+                last_rowid: 0,
+                exhausted: false,
+            })
+            .collect();
+        Self { store, cursors, chunk_size: chunk_size.max(1) }
+    }
+
+    /// Read the next chunk of a source whose buffered frames have run out.
+    fn fill(&mut self, index: usize) -> Result<(), String> {
+        let cursor = &mut self.cursors[index];
+        if cursor.exhausted || !cursor.pending.is_empty() {
+            return Ok(());
+        }
+        let chunk = self.store.read_after(&cursor.capture_id, cursor.last_rowid, self.chunk_size)?;
+        cursor.exhausted = chunk.len() < self.chunk_size;
+        if let Some((rowid, _)) = chunk.last() {
+            cursor.last_rowid = *rowid;
+        }
+        cursor.pending.extend(chunk);
+        Ok(())
+    }
+
+    /// The earliest remaining frame across all sources, or `None` once every
+    /// source is exhausted.
+    pub fn next_frame(&mut self) -> Result<Option<FrameMessage>, String> {
+        let mut earliest: Option<(u64, usize)> = None;
+        for index in 0..self.cursors.len() {
+            self.fill(index)?;
+            if let Some((_, frame)) = self.cursors[index].pending.front() {
+                let earlier = match earliest {
+                    Some((ts, _)) => frame.timestamp_us < ts,
+                    None => true,
+                };
+                if earlier {
+                    earliest = Some((frame.timestamp_us, index));
+                }
+            }
+        }
+
+        let Some((_, index)) = earliest else {
+            return Ok(None);
+        };
+        let cursor = &mut self.cursors[index];
+        let Some((_, mut frame)) = cursor.pending.pop_front() else {
+            return Ok(None);
+        };
+        if let Some(bus) = cursor.bus_map.get(&frame.bus) {
+            frame.bus = *bus;
+        }
+        Ok(Some(frame))
+    }
+}
+
+/// Check that there are at least two sources and that each is a frame capture
+/// with data. Returns the merged bus list (after renumbering, sorted).
+pub fn validate_sources(sources: &[MergeSource]) -> Result<Vec<u8>, String> {
+    if sources.len() < 2 {
+        return Err("Select at least two captures to replay together".to_string());
+    }
+
+    let mut buses = BTreeSet::new();
+    for src in sources {
+        let metadata = capture_store::get_capture_metadata(&src.capture_id)
+            .filter(|m| m.kind == CaptureKind::Frames)
+            .ok_or_else(|| msg!(CAPTURE_NOT_FRAMES, capture_id = src.capture_id.as_str()))?;
+        if metadata.count == 0 {
+            return Err(msg!(CAPTURE_EMPTY, capture_id = src.capture_id.as_str()).into());
+        }
+        buses.extend(metadata.buses.iter().map(|b| *src.bus_map.get(b).unwrap_or(b)));
+    }
+    Ok(buses.into_iter().collect())
+}
+
+/// Multi-Capture Source - replays several captures merged by timestamp
+pub struct MultiCaptureSource {
+    /// Common recorded source state (control, state, session_id, task_handle)
+    reader_state: RecordedSourceState,
+    sources: Vec<MergeSource>,
+    /// Buses after renumbering, across all sources
+    buses: Vec<u8>,
+}
+
+impl MultiCaptureSource {
+    /// Validates the sources up front so a bad selection fails before the
+    /// session is created.
+    pub fn new(session_id: String, sources: Vec<MergeSource>, speed: f64) -> Result<Self, String> {
+        let buses = validate_sources(&sources)?;
+        Ok(Self {
+            reader_state: RecordedSourceState::new(session_id, speed),
+            sources,
+            buses,
+        })
+    }
+}
+
+#[async_trait]
+impl IOSource for MultiCaptureSource {
+    fn capabilities(&self) -> IOCapabilities {
+        IOCapabilities::recorded_can().with_buses(self.buses.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), String> {
+        self.reader_state.check_can_start()?;
+        // Sources may have been deleted or cleared since the session was created
+        validate_sources(&self.sources)?;
+        self.reader_state.prepare_start();
+
+        let session_id = self.reader_state.session_id.clone();
+
+        // Create the session capture before spawning (see PostgresSource::start)
+        let _orphaned = capture_store::orphan_captures_for_session(&session_id);
+        let capture_id = capture_store::create_capture(CaptureKind::Frames, session_id.clone());
+        let _ = capture_store::set_capture_owner(&capture_id, &session_id);
+        emit_capture_changed(&session_id);
+
+        let reader = MultiSourceReader::open(&self.sources);
+        let control = self.reader_state.control.clone();
+
+        let handle = tauri::async_runtime::spawn(async move {
+            if let Err(e) = run_multi_capture_stream(&session_id, reader, control).await {
+                emit_stream_ended(&session_id, "error", "MultiCapture");
+                emit_session_error(&session_id, format!("Multi-capture replay error: {}", e));
+            }
+        });
+        self.reader_state.mark_running(handle);
+
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<(), String> {
+        self.reader_state.stop().await;
+        Ok(())
+    }
+
+    async fn pause(&mut self) -> Result<(), String> {
+        self.reader_state.pause()
+    }
+
+    async fn resume(&mut self) -> Result<(), String> {
+        self.reader_state.resume()
+    }
+
+    fn set_speed(&mut self, speed: f64) -> Result<(), String> {
+        self.reader_state.set_speed(speed, "MultiCapture")
+    }
+
+    fn state(&self) -> IOState {
+        self.reader_state.state()
+    }
+
+    fn session_id(&self) -> &str {
+        self.reader_state.session_id()
+    }
+
+    fn source_type(&self) -> &'static str {
+        "multi_capture"
+    }
+}
+
+/// Store a batch in the session capture and report the playback position.
+fn store_batch(
+    session_id: &str,
+    batch: &mut Vec<FrameMessage>,
+    total_emitted: usize,
+    throttle: &mut SignalThrottle,
+) {
+    let Some(last_ts) = batch.last().map(|f| f.timestamp_us) else {
+        return;
+    };
+    capture_store::append_frames_to_session(session_id, std::mem::take(batch));
+    if throttle.should_signal("frames-ready") {
+        signal_frames_ready(session_id);
+    }
+
+    crate::io::store_playback_position(session_id, PlaybackPosition {
+        timestamp_us: last_ts as i64,
+        frame_index: total_emitted.saturating_sub(1),
+        frame_count: Some(total_emitted),
+    });
+    if throttle.should_signal("playback-position") {
+        signal_playback_position(session_id);
+    }
+}
+
+async fn run_multi_capture_stream(
+    session_id: &str,
+    mut reader: MultiSourceReader,
+    control: PlaybackControl,
+) -> Result<(), String> {
+    let mut batch: Vec<FrameMessage> = Vec::new();
+    let mut throttle = SignalThrottle::new();
+    let mut total_emitted = 0usize;
+
+    // Pacing compares playback time against wall-clock time since a baseline,
+    // which is reset on speed changes and while paused to avoid a flood
+    let mut wall_clock_baseline = Instant::now();
+    let mut playback_baseline_us: Option<u64> = None;
+    let mut last_frame_time_us: Option<u64> = None;
+    let mut last_speed = control.read_speed();
+    let mut last_store = Instant::now();
+    // Frame read but not yet due, emitted on a later pass
+    let mut held: Option<FrameMessage> = None;
+
+    tlog!("[MultiCapture:{}] Streaming (speed: {}x)", session_id, last_speed);
+
+    loop {
+        // Break immediately on cancel; don't drain (see PostgreSQL source)
+        if control.is_cancelled() {
+            break;
+        }
+
+        if control.is_paused() {
+            store_batch(session_id, &mut batch, total_emitted, &mut throttle);
+            playback_baseline_us = last_frame_time_us;
+            wall_clock_baseline = Instant::now();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            continue;
+        }
+
+        let is_pacing = control.is_pacing_enabled();
+        let speed = control.read_speed();
+        if is_pacing && (speed - last_speed).abs() > 0.001 {
+            playback_baseline_us = last_frame_time_us;
+            wall_clock_baseline = Instant::now();
+            last_speed = speed;
+        }
+
+        let frame = match held.take() {
+            Some(frame) => frame,
+            None => match reader.next_frame()? {
+                Some(frame) => frame,
+                None => break,
+            },
+        };
+
+        if is_pacing {
+            let baseline_us = *playback_baseline_us.get_or_insert(frame.timestamp_us);
+            let playback_ms = frame.timestamp_us.saturating_sub(baseline_us) as f64 / 1000.0;
+            let wait_ms = playback_ms / speed - wall_clock_baseline.elapsed().as_secs_f64() * 1000.0;
+            if wait_ms >= MIN_DELAY_MS {
+                // Not due yet: store what is pending, sleep, and re-check
+                // pause/cancel/speed before emitting it
+                store_batch(session_id, &mut batch, total_emitted, &mut throttle);
+                last_store = Instant::now();
+                tokio::time::sleep(Duration::from_millis(wait_ms.min(500.0) as u64)).await;
+                held = Some(frame);
+                continue;
+            }
+        }
+
+        last_frame_time_us = Some(frame.timestamp_us);
+        batch.push(frame);
+        total_emitted += 1;
+
+        let batch_limit = if is_pacing { HIGH_SPEED_BATCH_SIZE } else { NO_LIMIT_BATCH_SIZE };
+        if batch.len() >= batch_limit || last_store.elapsed().as_millis() as u64 >= PACING_INTERVAL_MS {
+            store_batch(session_id, &mut batch, total_emitted, &mut throttle);
+            last_store = Instant::now();
+            if is_pacing {
+                tokio::task::yield_now().await;
+            } else {
+                tokio::time::sleep(Duration::from_millis(NO_LIMIT_YIELD_MS)).await;
+            }
+        }
+    }
+
+    throttle.flush();
+    store_batch(session_id, &mut batch, total_emitted, &mut throttle);
+
+    // Cancellation is reported by suspend_session(), not as stream-ended
+    if control.is_cancelled() {
+        tlog!("[MultiCapture:{}] Stream cancelled by user (emitted: {})", session_id, total_emitted);
+    } else {
+        tlog!("[MultiCapture:{}] Stream ended (emitted: {})", session_id, total_emitted);
+        emit_stream_ended(session_id, "complete", "MultiCapture");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    /// In-memory sources; rowids are 1-based positions.
+    struct MemoryStore(HashMap<String, Vec<FrameMessage>>);
+
+    impl FrameStore for MemoryStore {
+        fn read_after(
+            &self,
+            capture_id: &str,
+            after_rowid: i64,
+            limit: usize,
+        ) -> Result<Vec<(i64, FrameMessage)>, String> {
+            let frames = self.0.get(capture_id).ok_or("no such capture")?;
+            Ok(frames
+                .iter()
+                .enumerate()
+                .map(|(i, f)| (i as i64 + 1, f.clone()))
+                .skip(after_rowid as usize)
+                .take(limit)
+                .collect())
+        }
+    }
+
+    fn frame(id: u32, ts: u64, bus: u8) -> FrameMessage {
+        FrameMessage { bus, ..test_frame(id, ts, &[]) }
+    }
+
+    fn source(capture_id: &str, bus_map: &[(u8, u8)]) -> MergeSource {
+        MergeSource { capture_id: capture_id.to_string(), bus_map: bus_map.iter().copied().collect() }
+    }
+
+    #[test]
+    fn interleaves_by_timestamp_and_renumbers_buses() {
+        let store = MemoryStore(HashMap::from([
+            ("a".to_string(), vec![frame(0x100, 10, 0), frame(0x101, 30, 0), frame(0x102, 50, 1), frame(0x103, 70, 0)]),
+            ("b".to_string(), vec![frame(0x200, 20, 0), frame(0x201, 30, 0), frame(0x202, 40, 0)]),
+        ]));
+        // Chunks smaller than either source so refills happen mid-merge
+        let mut reader = MultiSourceReader::with_store(store, &[source("a", &[]), source("b", &[(0, 2)])], 2);
+
+        let mut merged = Vec::new();
+        while let Some(f) = reader.next_frame().unwrap() {
+            merged.push((f.timestamp_us, f.frame_id, f.bus));
+        }
+
+        assert_eq!(
+            merged,
+            vec![
+                (10, 0x100, 0),
+                (20, 0x200, 2),
+                // Equal timestamps keep source order
+                (30, 0x101, 0),
+                (30, 0x201, 2),
+                (40, 0x202, 2),
+                // Unmapped buses pass through
+                (50, 0x102, 1),
+                (70, 0x103, 0),
+            ]
+        );
+        assert!(reader.next_frame().unwrap().is_none());
+    }
+}
@@ -0,0 +1,253 @@
+// ui/src-tauri/src/io/recorded/parquet_writer.rs
+//
+// Apache Parquet export, so captures load straight into pandas / polars /
+// DuckDB without a CSV round trip. One row per frame:
+//
+//   timestamp_us  int64   TIMESTAMP(MICROS, UTC)
+//   frame_id      int32   UINT32
+//   bus           int32   UINT8
+//   dlc           int32   UINT8
+//   bytes         binary  payload
+//   is_extended   boolean
+//   is_fd         boolean
+//   direction     binary  STRING, optional ("rx" / "tx")
+//   protocol      binary  STRING ("can", "modbus", "serial", ...)
+//
+// Frames are buffered column-wise and flushed as Snappy-compressed row groups
+// of ROW_GROUP_ROWS, so memory stays bounded however large the capture is.
+
+use std::fs::File;
+use std::io::Write;
+use std::sync::Arc;
+
+use parquet::basic::Compression;
+use parquet::data_type::{BoolType, ByteArray, ByteArrayType, Int32Type, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+
+use crate::io::FrameMessage;
+
+/// Rows per row group (~100k frames ≈ a few MB per group)
+const ROW_GROUP_ROWS: usize = 100_000;
+
+const SCHEMA: &str = "
+message frame {
+    required int64 timestamp_us (TIMESTAMP(MICROS,true));
+    required int32 frame_id (INTEGER(32,false));
+    required int32 bus (INTEGER(8,false));
+    required int32 dlc (INTEGER(8,false));
+    required binary bytes;
+    required boolean is_extended;
+    required boolean is_fd;
+    optional binary direction (STRING);
+    required binary protocol (STRING);
+}
+";
+
+/// One row group's worth of frames, column by column.
+#[derive(Default)]
+struct Columns {
+    timestamp_us: Vec<i64>,
+    frame_id: Vec<i32>,
+    bus: Vec<i32>,
+    dlc: Vec<i32>,
+    bytes: Vec<ByteArray>,
+    is_extended: Vec<bool>,
+    is_fd: Vec<bool>,
+    direction: Vec<ByteArray>,
+    /// Definition levels for the optional direction column
+    direction_def: Vec<i16>,
+    protocol: Vec<ByteArray>,
+}
+
+impl Columns {
+    fn push(&mut self, frame: &FrameMessage) {
+        self.timestamp_us.push(frame.timestamp_us as i64);
+        // UINT32 annotation: readers reinterpret the bits
+        self.frame_id.push(frame.frame_id as i32);
+        self.bus.push(frame.bus as i32);
+        self.dlc.push(frame.dlc as i32);
+        self.bytes.push(ByteArray::from(frame.bytes.clone()));
+        self.is_extended.push(frame.is_extended);
+        self.is_fd.push(frame.is_fd);
+        match &frame.direction {
+            Some(dir) => {
+                self.direction.push(ByteArray::from(dir.as_str()));
+                self.direction_def.push(1);
+            }
+            None => self.direction_def.push(0),
+        }
+        self.protocol.push(ByteArray::from(frame.protocol.as_str()));
+    }
+
+    fn len(&self) -> usize {
+        self.timestamp_us.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.timestamp_us.is_empty()
+    }
+}
+
+/// Streams frames into a Parquet file. Call `finish` to write the last row
+/// group and the footer.
+pub struct ParquetWriter<W: Write + Send = File> {
+    writer: SerializedFileWriter<W>,
+    columns: Columns,
+    written: u32,
+}
+
+fn pq_err(e: parquet::errors::ParquetError) -> String {
+    format!("Failed to write Parquet file: {}", e)
+}
+
+impl ParquetWriter<File> {
+    pub fn create(file_path: &str) -> Result<Self, String> {
+        let file = File::create(file_path)
+            .map_err(|e| format!("Failed to create Parquet file '{}': {}", file_path, e))?;
+        ParquetWriter::new(file)
+    }
+}
+
+impl<W: Write + Send> ParquetWriter<W> {
+    /// The footer is written last, so any byte stream will do (no seeking).
+    pub fn new(out: W) -> Result<Self, String> {
+        let schema = Arc::new(parse_message_type(SCHEMA).map_err(pq_err)?);
+        let props = Arc::new(
+            WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .set_created_by(format!("WireTAP {}", env!("CARGO_PKG_VERSION")))
+                .build(),
+        );
+        let writer = SerializedFileWriter::new(out, schema, props).map_err(pq_err)?;
+        Ok(ParquetWriter { writer, columns: Columns::default(), written: 0 })
+    }
+
+    /// Append a frame (every protocol is kept; filter on `protocol` when
+    /// loading).
+    pub fn write_frame(&mut self, frame: &FrameMessage) -> Result<(), String> {
+        self.columns.push(frame);
+        self.written += 1;
+        if self.columns.len() >= ROW_GROUP_ROWS {
+            self.flush_row_group()?;
+        }
+        Ok(())
+    }
+
+    fn flush_row_group(&mut self) -> Result<(), String> {
+        if self.columns.is_empty() {
+            return Ok(());
+        }
+        let c = std::mem::take(&mut self.columns);
+        let mut group = self.writer.next_row_group().map_err(pq_err)?;
+        let mut index = 0;
+        while let Some(mut column) = group.next_column().map_err(pq_err)? {
+            let result = match index {
+                0 => column.typed::<Int64Type>().write_batch(&c.timestamp_us, None, None),
+                1 => column.typed::<Int32Type>().write_batch(&c.frame_id, None, None),
+                2 => column.typed::<Int32Type>().write_batch(&c.bus, None, None),
+                3 => column.typed::<Int32Type>().write_batch(&c.dlc, None, None),
+                4 => column.typed::<ByteArrayType>().write_batch(&c.bytes, None, None),
+                5 => column.typed::<BoolType>().write_batch(&c.is_extended, None, None),
+                6 => column.typed::<BoolType>().write_batch(&c.is_fd, None, None),
+                7 => column
+                    .typed::<ByteArrayType>()
+                    .write_batch(&c.direction, Some(&c.direction_def), None),
+                _ => column.typed::<ByteArrayType>().write_batch(&c.protocol, None, None),
+            };
+            result.map_err(pq_err)?;
+            column.close().map_err(pq_err)?;
+            index += 1;
+        }
+        group.close().map_err(pq_err)?;
+        Ok(())
+    }
+
+    /// Write the final row group and the footer. Returns the number of frames
+    /// written.
+    pub fn finish(mut self) -> Result<u32, String> {
+        self.flush_row_group()?;
+        self.writer.close().map_err(pq_err)?;
+        Ok(self.written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+
+    fn frame(ts: u64, id: u32, data: &[u8], direction: Option<&str>) -> FrameMessage {
+        FrameMessage {
+            bus: 1,
+            is_extended: id > 0x7FF,
+            is_fd: data.len() > 8,
+            direction: direction.map(String::from),
+            ..test_frame(id, ts, data)
+        }
+    }
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("wiretap-parquet-{}-{}.parquet", name, std::process::id()))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn read_back(path: &str) -> SerializedFileReader<File> {
+        SerializedFileReader::new(File::open(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn round_trips_frame_columns() {
+        let path = temp_path("roundtrip");
+        let mut w = ParquetWriter::create(&path).unwrap();
+        w.write_frame(&frame(1_700_000_000_000_000, 0x123, &[1, 2, 3], Some("rx"))).unwrap();
+        w.write_frame(&frame(1_700_000_000_010_000, 0x18FEF100, &[0xFF; 12], None)).unwrap();
+        assert_eq!(w.finish().unwrap(), 2);
+
+        let reader = read_back(&path);
+        let rows: Vec<_> = reader.get_row_iter(None).unwrap().map(|r| r.unwrap()).collect();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get_uint(1).unwrap(), 0x123);
+        assert_eq!(rows[0].get_bytes(4).unwrap().data(), &[1, 2, 3]);
+        assert_eq!(rows[0].get_string(7).unwrap(), "rx");
+        assert_eq!(rows[1].get_uint(1).unwrap(), 0x18FEF100);
+        assert!(rows[1].get_bool(5).unwrap() && rows[1].get_bool(6).unwrap());
+        assert!(rows[1].get_string(7).is_err());
+    }
+
+    #[test]
+    fn splits_large_captures_into_row_groups() {
+        let path = temp_path("groups");
+        let mut w = ParquetWriter::create(&path).unwrap();
+        for i in 0..(ROW_GROUP_ROWS as u64 + 10) {
+            w.write_frame(&frame(i, 0x100, &[i as u8], Some("rx"))).unwrap();
+        }
+        w.finish().unwrap();
+
+        let reader = read_back(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(reader.num_row_groups(), 2);
+        assert_eq!(reader.metadata().file_metadata().num_rows(), ROW_GROUP_ROWS as i64 + 10);
+    }
+
+    #[test]
+    fn schema_annotates_timestamp_and_unsigned_ids() {
+        let path = temp_path("schema");
+        ParquetWriter::create(&path).unwrap().finish().unwrap();
+        let reader = read_back(&path);
+        let _ = std::fs::remove_file(&path);
+        let schema = reader.metadata().file_metadata().schema_descr();
+        let names: Vec<&str> = schema.columns().iter().map(|c| c.name()).collect();
+        assert_eq!(
+            names,
+            ["timestamp_us", "frame_id", "bus", "dlc", "bytes", "is_extended", "is_fd", "direction", "protocol"]
+        );
+        assert!(format!("{:?}", schema.column(0).logical_type()).contains("Timestamp"));
+    }
+}
@@ -0,0 +1,622 @@
+// ui/src-tauri/src/io/recorded/pcap.rs
+//
+// pcap / pcapng parsing for SocketCAN captures (Wireshark, `tcpdump -i can0`,
+// dumpcap), link type LINKTYPE_CAN_SOCKETCAN (227). Each packet is a SocketCAN
+// frame with the CAN ID word in network byte order:
+//
+//   [0..4]  can_id  (EFF 0x80000000, RTR 0x40000000, ERR 0x20000000 flags)
+//   [4]     payload length
+//   [5]     FD flags (CANFD_FDF 0x04 marks CAN FD; CANXL_XLF 0x80 is CAN XL)
+//   [6..8]  reserved / len8_dlc
+//   [8..]   data (8 bytes classic, 64 bytes FD)
+//
+// Classic pcap files (µs or ns timestamps, either byte order) are a single bus
+// 0. In pcapng each interface gets a bus from its if_name's trailing digits
+// (can1 → 1), honouring if_tsresol / if_tsoffset, and the EPB direction flag
+// sets rx/tx. Error frames are kept and built by can_error::error_frame, so
+// they carry `is_error` and `error_class` like those from live drivers. CAN XL
+// packets and other link types are skipped.
+//
+// `PcapWriter` writes little-endian pcapng with one interface per bus (named
+// canN, microsecond timestamps) and the direction in epb_flags, so everything
+// the reader recovers survives a round trip.
+
+use std::fs::File;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+
+use super::candump::BusNumbers;
+use crate::io::can_error::{self, CAN_ERR_FLAG};
+use crate::io::FrameMessage;
+
+const CAN_EFF_FLAG: u32 = 0x8000_0000;
+const CAN_RTR_FLAG: u32 = 0x4000_0000;
+const CANFD_FDF: u8 = 0x04;
+const CANXL_XLF: u8 = 0x80;
+/// Size of a struct canfd_frame (packets without the FDF flag but this long are FD)
+const CANFD_MTU: usize = 72;
+
+const LINKTYPE_CAN_SOCKETCAN: u32 = 227;
+
+const PCAPNG_SHB: u32 = 0x0A0D_0D0A;
+const PCAPNG_IDB: u32 = 1;
+const PCAPNG_PACKET: u32 = 2;
+const PCAPNG_EPB: u32 = 6;
+
+/// Upper bound on a packet / block (guards against corrupt lengths)
+const MAX_RECORD: usize = 16 * 1024 * 1024;
+
+/// Decode one LINKTYPE_CAN_SOCKETCAN packet.
+fn decode_socketcan(packet: &[u8], timestamp_us: u64, bus: u8, direction: &str) -> Option<FrameMessage> {
+    let header = packet.get(..8)?;
+    let flags = header[5];
+    if flags & CANXL_XLF != 0 {
+        return None;
+    }
+    let can_id = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+    let len = header[4] as usize;
+    let data = &packet[8..];
+
+    if can_error::is_error_id(can_id) {
+        let mut frame = can_error::error_frame(can_id, &data[..len.min(data.len())], bus, timestamp_us);
+        frame.direction = Some(direction.to_string());
+        return Some(frame);
+    }
+    let is_fd = flags & CANFD_FDF != 0 || packet.len() == CANFD_MTU;
+    let is_remote = !is_fd && can_id & CAN_RTR_FLAG != 0;
+    let is_extended = can_id & CAN_EFF_FLAG != 0;
+
+    let frame_id = if is_extended {
+        can_id & 0x1FFF_FFFF
+    } else {
+        can_id & 0x7FF
+    };
+    let (bytes, dlc) = if is_remote {
+        // Remote request: length is the requested DLC, no data
+        (Vec::new(), len.min(8) as u8)
+    } else {
+        let n = len.min(if is_fd { 64 } else { 8 }).min(data.len());
+        (data[..n].to_vec(), n as u8)
+    };
+
+    Some(FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us,
+        frame_id,
+        bus,
+        dlc,
+        bytes,
+        is_extended,
+        is_fd,
+        source_address: None,
+        incomplete: None,
+        direction: Some(direction.to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    })
+}
+
+// ============================================================================
+// Byte order
+// ============================================================================
+
+#[derive(Clone, Copy)]
+struct Order {
+    big: bool,
+}
+
+impl Order {
+    fn u16(self, b: &[u8], at: usize) -> u16 {
+        let v = b.get(at..at + 2).map_or([0; 2], |s| [s[0], s[1]]);
+        if self.big { u16::from_be_bytes(v) } else { u16::from_le_bytes(v) }
+    }
+
+    fn u32(self, b: &[u8], at: usize) -> u32 {
+        let v = b.get(at..at + 4).map_or([0; 4], |s| [s[0], s[1], s[2], s[3]]);
+        if self.big { u32::from_be_bytes(v) } else { u32::from_le_bytes(v) }
+    }
+
+    fn u64(self, b: &[u8], at: usize) -> u64 {
+        let v: [u8; 8] = b.get(at..at + 8).and_then(|s| s.try_into().ok()).unwrap_or([0; 8]);
+        if self.big { u64::from_be_bytes(v) } else { u64::from_le_bytes(v) }
+    }
+}
+
+/// True at a clean end of file (no more bytes).
+fn at_eof<R: BufRead>(r: &mut R) -> Result<bool, String> {
+    r.fill_buf()
+        .map(|b| b.is_empty())
+        .map_err(|e| format!("Failed to read capture: {}", e))
+}
+
+fn read_vec<R: Read>(r: &mut R, len: usize) -> Option<Vec<u8>> {
+    if len > MAX_RECORD {
+        return None;
+    }
+    let mut buf = vec![0u8; len];
+    r.read_exact(&mut buf).ok()?;
+    Some(buf)
+}
+
+// ============================================================================
+// Classic pcap
+// ============================================================================
+
+fn read_pcap<R: BufRead>(r: &mut R, magic: [u8; 4], frames: &mut Vec<FrameMessage>) -> Result<usize, String> {
+    let (order, nanos) = match magic {
+        [0xD4, 0xC3, 0xB2, 0xA1] => (Order { big: false }, false),
+        [0xA1, 0xB2, 0xC3, 0xD4] => (Order { big: true }, false),
+        [0x4D, 0x3C, 0xB2, 0xA1] => (Order { big: false }, true),
+        [0xA1, 0xB2, 0x3C, 0x4D] => (Order { big: true }, true),
+        _ => return Err("Not a pcap or pcapng file".to_string()),
+    };
+    let mut header = [0u8; 20];
+    r.read_exact(&mut header)
+        .map_err(|_| "Truncated pcap file header".to_string())?;
+    let linktype = order.u32(&header, 16) & 0xFFFF;
+    if linktype != LINKTYPE_CAN_SOCKETCAN {
+        return Err(format!(
+            "Unsupported pcap link type {} (expected LINKTYPE_CAN_SOCKETCAN, 227)",
+            linktype
+        ));
+    }
+
+    let mut skipped = 0;
+    while !at_eof(r)? {
+        let mut record = [0u8; 16];
+        if r.read_exact(&mut record).is_err() {
+            tlog!("[pcap] Truncated record header at end of file");
+            break;
+        }
+        let secs = order.u32(&record, 0) as u64;
+        let frac = order.u32(&record, 4) as u64;
+        let incl_len = order.u32(&record, 8) as usize;
+        let Some(packet) = read_vec(r, incl_len) else {
+            tlog!("[pcap] Truncated or oversized record at end of file");
+            break;
+        };
+        let timestamp_us = secs * 1_000_000 + if nanos { frac / 1000 } else { frac };
+        match decode_socketcan(&packet, timestamp_us, 0, "rx") {
+            Some(frame) => frames.push(frame),
+            None => skipped += 1,
+        }
+    }
+    Ok(skipped)
+}
+
+// ============================================================================
+// pcapng
+// ============================================================================
+
+struct Interface {
+    linktype: u32,
+    bus: u8,
+    /// Timestamp units per second (if_tsresol)
+    units_per_sec: u64,
+    /// Seconds added to every timestamp (if_tsoffset)
+    offset_secs: i64,
+}
+
+impl Interface {
+    fn timestamp_us(&self, ts: u64) -> u64 {
+        let us = (ts as u128 * 1_000_000 / self.units_per_sec.max(1) as u128) as u64;
+        us.saturating_add_signed(self.offset_secs.saturating_mul(1_000_000))
+    }
+}
+
+/// Walk a pcapng options list, calling `f(code, value)` for each option.
+fn for_each_option(order: Order, mut opts: &[u8], mut f: impl FnMut(u16, &[u8])) {
+    while opts.len() >= 4 {
+        let code = order.u16(opts, 0);
+        let len = order.u16(opts, 2) as usize;
+        if code == 0 || opts.len() < 4 + len {
+            break;
+        }
+        f(code, &opts[4..4 + len]);
+        let padded = 4 + len.div_ceil(4) * 4;
+        opts = &opts[padded.min(opts.len())..];
+    }
+}
+
+fn parse_idb(order: Order, body: &[u8], index: usize, buses: &mut BusNumbers) -> Interface {
+    let mut name = None;
+    let mut units_per_sec = 1_000_000u64;
+    let mut offset_secs = 0i64;
+    for_each_option(order, body.get(8..).unwrap_or(&[]), |code, value| match code {
+        2 => name = Some(String::from_utf8_lossy(value).trim_end_matches('\0').to_string()),
+        9 => {
+            if let Some(&resol) = value.first() {
+                let exp = (resol & 0x7F) as u32;
+                units_per_sec = if resol & 0x80 != 0 {
+                    1u64.checked_shl(exp).unwrap_or(1_000_000)
+                } else {
+                    10u64.checked_pow(exp).unwrap_or(1_000_000)
+                };
+            }
+        }
+        14 => offset_secs = order.u64(value, 0) as i64,
+        _ => {}
+    });
+    let name = name.unwrap_or_else(|| format!("interface{}", index));
+    Interface {
+        linktype: order.u16(body, 0) as u32,
+        bus: buses.bus_for(&name),
+        units_per_sec,
+        offset_secs,
+    }
+}
+
+fn read_pcapng<R: BufRead>(r: &mut R, frames: &mut Vec<FrameMessage>) -> Result<usize, String> {
+    let mut order = Order { big: false };
+    let mut interfaces: Vec<Interface> = Vec::new();
+    let mut buses = BusNumbers::default();
+    let mut skipped = 0;
+    // The first block's type (SHB) was consumed as the file magic
+    let mut block_type = Some(PCAPNG_SHB);
+
+    loop {
+        let kind = match block_type.take() {
+            Some(kind) => kind,
+            None => {
+                if at_eof(r)? {
+                    break;
+                }
+                let mut raw = [0u8; 4];
+                if r.read_exact(&mut raw).is_err() {
+                    break;
+                }
+                order.u32(&raw, 0)
+            }
+        };
+
+        if kind == PCAPNG_SHB {
+            // Section header: byte-order magic decides the section's endianness
+            let mut head = [0u8; 8];
+            r.read_exact(&mut head)
+                .map_err(|_| "Truncated pcapng section header".to_string())?;
+            order = match head[4..8] {
+                [0x1A, 0x2B, 0x3C, 0x4D] => Order { big: true },
+                [0x4D, 0x3C, 0x2B, 0x1A] => Order { big: false },
+                _ => return Err("Invalid pcapng byte-order magic".to_string()),
+            };
+            let total = order.u32(&head, 0) as usize;
+            if total < 12 || read_vec(r, total - 12).is_none() {
+                return Err("Corrupt pcapng section header".to_string());
+            }
+            interfaces.clear();
+            continue;
+        }
+
+        let mut len_raw = [0u8; 4];
+        if r.read_exact(&mut len_raw).is_err() {
+            tlog!("[pcap] Truncated block at end of file");
+            break;
+        }
+        let total = order.u32(&len_raw, 0) as usize;
+        if total < 12 {
+            return Err("Corrupt pcapng block length".to_string());
+        }
+        let Some(rest) = read_vec(r, total - 8) else {
+            tlog!("[pcap] Truncated or oversized block at end of file");
+            break;
+        };
+        let body = &rest[..total - 12];
+
+        match kind {
+            PCAPNG_IDB => {
+                let interface = parse_idb(order, body, interfaces.len(), &mut buses);
+                interfaces.push(interface);
+            }
+            PCAPNG_EPB | PCAPNG_PACKET => {
+                let interface_id = if kind == PCAPNG_EPB {
+                    order.u32(body, 0) as usize
+                } else {
+                    order.u16(body, 0) as usize
+                };
+                let Some(interface) = interfaces.get(interface_id) else {
+                    skipped += 1;
+                    continue;
+                };
+                if interface.linktype != LINKTYPE_CAN_SOCKETCAN {
+                    skipped += 1;
+                    continue;
+                }
+                let ts = ((order.u32(body, 4) as u64) << 32) | order.u32(body, 8) as u64;
+                let cap_len = order.u32(body, 12) as usize;
+                let Some(packet) = body.get(20..20 + cap_len) else {
+                    skipped += 1;
+                    continue;
+                };
+                // epb_flags / pack_flags: inbound = 1, outbound = 2
+                let mut direction = "rx";
+                let opts = body.get(20 + cap_len.div_ceil(4) * 4..).unwrap_or(&[]);
+                for_each_option(order, opts, |code, value| {
+                    if code == 2 && order.u32(value, 0) & 0x3 == 2 {
+                        direction = "tx";
+                    }
+                });
+                match decode_socketcan(packet, interface.timestamp_us(ts), interface.bus, direction) {
+                    Some(frame) => frames.push(frame),
+                    None => skipped += 1,
+                }
+            }
+            // Simple packet, name resolution, statistics, ... blocks
+            _ => {}
+        }
+    }
+    Ok(skipped)
+}
+
+fn parse_pcap_reader<R: BufRead>(r: &mut R) -> Result<Vec<FrameMessage>, String> {
+    let mut magic = [0u8; 4];
+    r.read_exact(&mut magic)
+        .map_err(|_| "File is too short to be a pcap capture".to_string())?;
+    let mut frames = Vec::new();
+    let skipped = if u32::from_le_bytes(magic) == PCAPNG_SHB {
+        read_pcapng(r, &mut frames)?
+    } else {
+        read_pcap(r, magic, &mut frames)?
+    };
+    if skipped > 0 {
+        tlog!("[pcap] Skipped {} packets that are not SocketCAN CAN/CAN FD frames", skipped);
+    }
+    Ok(frames)
+}
+
+/// Parse an entire pcap or pcapng SocketCAN capture and return all frames
+pub fn parse_pcap_file(file_path: &str) -> Result<Vec<FrameMessage>, String> {
+    let file = File::open(file_path)
+        .map_err(|e| format!("Failed to open capture '{}': {}", file_path, e))?;
+    parse_pcap_reader(&mut BufReader::new(file))
+}
+
+// ============================================================================
+// Writing
+// ============================================================================
+
+/// Encode a frame as a LINKTYPE_CAN_SOCKETCAN packet (inverse of decode_socketcan).
+fn encode_socketcan(frame: &FrameMessage) -> Vec<u8> {
+    let is_error = frame.is_error == Some(true) || (can_error::is_error_id(frame.frame_id) && frame.is_extended);
+    let is_remote = !frame.is_fd && frame.bytes.is_empty() && frame.dlc > 0;
+    let mut can_id = frame.frame_id & 0x1FFF_FFFF;
+    if is_error {
+        can_id |= CAN_ERR_FLAG;
+    } else if frame.is_extended {
+        can_id |= CAN_EFF_FLAG;
+    }
+    if is_remote {
+        can_id |= CAN_RTR_FLAG;
+    }
+    let (mtu, max, flags) = if frame.is_fd { (CANFD_MTU, 64, CANFD_FDF) } else { (16, 8, 0) };
+    let data = &frame.bytes[..frame.bytes.len().min(max)];
+    let len = if is_remote { frame.dlc.min(8) } else { data.len() as u8 };
+
+    let mut packet = Vec::with_capacity(mtu);
+    packet.extend(can_id.to_be_bytes());
+    packet.extend([len, flags, 0, 0]);
+    packet.extend_from_slice(data);
+    packet.resize(mtu, 0);
+    packet
+}
+
+/// Append one pcapng block (little-endian), padding the body to 4 bytes.
+fn push_block(out: &mut Vec<u8>, kind: u32, body: &[u8]) {
+    let padded = body.len().div_ceil(4) * 4;
+    let total = (12 + padded) as u32;
+    out.extend(kind.to_le_bytes());
+    out.extend(total.to_le_bytes());
+    out.extend_from_slice(body);
+    out.resize(out.len() + padded - body.len(), 0);
+    out.extend(total.to_le_bytes());
+}
+
+/// Streams frames into a pcapng SocketCAN capture. Call `finish` to flush.
+pub struct PcapWriter<W: Write> {
+    out: W,
+    /// Bus → pcapng interface index, assigned as buses first appear
+    interfaces: HashMap<u8, u32>,
+    block: Vec<u8>,
+    written: u32,
+}
+
+impl PcapWriter<BufWriter<File>> {
+    pub fn create(file_path: &str) -> Result<Self, String> {
+        let file = File::create(file_path)
+            .map_err(|e| format!("Failed to create capture '{}': {}", file_path, e))?;
+        PcapWriter::new(BufWriter::new(file))
+    }
+}
+
+impl<W: Write> PcapWriter<W> {
+    pub fn new(mut out: W) -> Result<Self, String> {
+        let mut shb = 0x1A2B_3C4Du32.to_le_bytes().to_vec();
+        shb.extend([1, 0, 0, 0]); // version 1.0
+        shb.extend(u64::MAX.to_le_bytes()); // section length unknown
+        let mut block = Vec::new();
+        push_block(&mut block, PCAPNG_SHB, &shb);
+        out.write_all(&block)
+            .map_err(|e| format!("Failed to write pcapng header: {}", e))?;
+        Ok(PcapWriter { out, interfaces: HashMap::new(), block, written: 0 })
+    }
+
+    /// Append a CAN or CAN FD frame; other protocols are skipped. Returns
+    /// whether the frame was written.
+    pub fn write_frame(&mut self, frame: &FrameMessage) -> Result<bool, String> {
+        if frame.protocol != "can" {
+            return Ok(false);
+        }
+        self.block.clear();
+
+        let next = self.interfaces.len() as u32;
+        let interface = *self.interfaces.entry(frame.bus).or_insert_with(|| {
+            let name = format!("can{}", frame.bus);
+            let mut idb = (LINKTYPE_CAN_SOCKETCAN as u16).to_le_bytes().to_vec();
+            idb.extend([0, 0]);
+            idb.extend((CANFD_MTU as u32).to_le_bytes());
+            idb.extend(2u16.to_le_bytes()); // if_name
+            idb.extend((name.len() as u16).to_le_bytes());
+            idb.extend(name.as_bytes());
+            idb.resize(idb.len().div_ceil(4) * 4, 0);
+            idb.extend([0, 0, 0, 0]); // opt_endofopt
+            push_block(&mut self.block, PCAPNG_IDB, &idb);
+            next
+        });
+
+        let packet = encode_socketcan(frame);
+        let mut epb = interface.to_le_bytes().to_vec();
+        epb.extend(((frame.timestamp_us >> 32) as u32).to_le_bytes());
+        epb.extend((frame.timestamp_us as u32).to_le_bytes());
+        epb.extend((packet.len() as u32).to_le_bytes());
+        epb.extend((packet.len() as u32).to_le_bytes());
+        epb.extend(&packet);
+        let direction: u32 = if frame.direction.as_deref() == Some("tx") { 2 } else { 1 };
+        epb.extend([2, 0, 4, 0]); // epb_flags
+        epb.extend(direction.to_le_bytes());
+        epb.extend([0, 0, 0, 0]);
+        push_block(&mut self.block, PCAPNG_EPB, &epb);
+
+        self.out
+            .write_all(&self.block)
+            .map_err(|e| format!("Failed to write pcapng packet: {}", e))?;
+        self.written += 1;
+        Ok(true)
+    }
+
+    /// Flush. Returns the number of frames written.
+    pub fn finish(mut self) -> Result<u32, String> {
+        self.out
+            .flush()
+            .map_err(|e| format!("Failed to finish capture: {}", e))?;
+        Ok(self.written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::CanErrorClass;
+
+    fn socketcan(can_id: u32, flags: u8, data: &[u8], mtu: usize) -> Vec<u8> {
+        let mut p = can_id.to_be_bytes().to_vec();
+        p.extend([data.len() as u8, flags, 0, 0]);
+        p.extend_from_slice(data);
+        p.resize(mtu, 0);
+        p
+    }
+
+    #[test]
+    fn decodes_classic_fd_remote_and_error_frames() {
+        let f = decode_socketcan(&socketcan(0x123, 0, &[1, 2, 3], 16), 5, 0, "rx").unwrap();
+        assert_eq!((f.frame_id, f.dlc, f.is_extended, f.is_fd), (0x123, 3, false, false));
+        assert_eq!(f.bytes, vec![1, 2, 3]);
+
+        let f = decode_socketcan(&socketcan(CAN_EFF_FLAG | 0x18DA_F110, CANFD_FDF, &[7; 12], 72), 5, 1, "tx").unwrap();
+        assert_eq!((f.frame_id, f.dlc, f.is_extended, f.is_fd), (0x18DA_F110, 12, true, true));
+
+        let mut rtr = socketcan(CAN_RTR_FLAG | 0x7DF, 0, &[], 16);
+        rtr[4] = 4;
+        let f = decode_socketcan(&rtr, 5, 0, "rx").unwrap();
+        assert_eq!((f.dlc, f.bytes.len()), (4, 0));
+
+        // CAN_ERR_BUSOFF (0x40) error frame
+        let f = decode_socketcan(&socketcan(CAN_ERR_FLAG | 0x40, 0, &[0; 8], 16), 5, 0, "rx").unwrap();
+        assert_eq!(f.frame_id, CAN_ERR_FLAG | 0x40);
+        assert!(f.is_extended && !f.is_fd);
+        assert_eq!((f.is_error, f.error_class), (Some(true), Some(CanErrorClass::BusOff)));
+
+        // CAN_ERR_PROT (0x08) with a stuff violation in data[2]
+        let f = decode_socketcan(&socketcan(CAN_ERR_FLAG | 0x08, 0, &[0, 0, 0x04, 0, 0, 0, 0, 0], 16), 5, 0, "tx").unwrap();
+        assert_eq!(f.error_class, Some(CanErrorClass::Stuff));
+        assert_eq!(f.direction.as_deref(), Some("tx"));
+
+        assert!(decode_socketcan(&socketcan(0x1, CANXL_XLF, &[0; 8], 16), 5, 0, "rx").is_none());
+    }
+
+    #[test]
+    fn parses_classic_pcap_with_nanosecond_timestamps() {
+        let mut file = vec![0x4D, 0x3C, 0xB2, 0xA1, 2, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        file.extend(65535u32.to_le_bytes());
+        file.extend(LINKTYPE_CAN_SOCKETCAN.to_le_bytes());
+        let packet = socketcan(0x321, 0, &[0xAA, 0xBB], 16);
+        file.extend(10u32.to_le_bytes());
+        file.extend(1_500_000u32.to_le_bytes());
+        file.extend((packet.len() as u32).to_le_bytes());
+        file.extend((packet.len() as u32).to_le_bytes());
+        file.extend(&packet);
+
+        let frames = parse_pcap_reader(&mut &file[..]).unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!((frames[0].timestamp_us, frames[0].frame_id), (10_001_500, 0x321));
+    }
+
+    #[test]
+    fn parses_pcapng_interfaces_and_direction() {
+        fn block(kind: u32, body: &[u8]) -> Vec<u8> {
+            let total = (12 + body.len()) as u32;
+            let mut b = kind.to_le_bytes().to_vec();
+            b.extend(total.to_le_bytes());
+            b.extend_from_slice(body);
+            b.extend(total.to_le_bytes());
+            b
+        }
+        let mut shb = 0x1A2B_3C4Du32.to_le_bytes().to_vec();
+        shb.extend([1, 0, 0, 0]);
+        shb.extend(u64::MAX.to_le_bytes());
+        let mut idb = (LINKTYPE_CAN_SOCKETCAN as u16).to_le_bytes().to_vec();
+        idb.extend([0, 0, 0, 0, 0, 0]);
+        idb.extend([2, 0, 4, 0]);
+        idb.extend(b"can1");
+        idb.extend([9, 0, 1, 0, 9, 0, 0, 0]); // if_tsresol = 10^-9
+        idb.extend([0, 0, 0, 0]);
+        let packet = socketcan(0x100, 0, &[1], 16);
+        let ts: u64 = 2_000_000_123_000;
+        let mut epb = 0u32.to_le_bytes().to_vec();
+        epb.extend(((ts >> 32) as u32).to_le_bytes());
+        epb.extend((ts as u32).to_le_bytes());
+        epb.extend((packet.len() as u32).to_le_bytes());
+        epb.extend((packet.len() as u32).to_le_bytes());
+        epb.extend(&packet);
+        epb.extend([2, 0, 4, 0, 2, 0, 0, 0, 0, 0, 0, 0]); // epb_flags: outbound
+
+        let mut file = block(PCAPNG_SHB, &shb);
+        file.extend(block(PCAPNG_IDB, &idb));
+        file.extend(block(PCAPNG_EPB, &epb));
+
+        let frames = parse_pcap_reader(&mut &file[..]).unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!((frames[0].bus, frames[0].timestamp_us), (1, 2_000_000_123));
+        assert_eq!(frames[0].direction.as_deref(), Some("tx"));
+    }
+
+    #[test]
+    fn written_pcapng_reads_back() {
+        let frames = vec![
+            decode_socketcan(&socketcan(0x123, 0, &[1, 2, 3], 16), 1_000_001, 0, "rx").unwrap(),
+            decode_socketcan(&socketcan(CAN_EFF_FLAG | 0x18DA_F110, CANFD_FDF, &[7; 12], 72), 1_000_002, 2, "tx").unwrap(),
+            decode_socketcan(&socketcan(CAN_ERR_FLAG | 0x40, 0, &[0; 8], 16), 1_000_003, 0, "rx").unwrap(),
+        ];
+        let mut rtr = socketcan(CAN_RTR_FLAG | 0x7DF, 0, &[], 16);
+        rtr[4] = 4;
+        let remote = decode_socketcan(&rtr, 1_000_004, 0, "rx").unwrap();
+
+        let mut out = Vec::new();
+        let mut writer = PcapWriter::new(&mut out).unwrap();
+        for f in frames.iter().chain([&remote]) {
+            writer.write_frame(f).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), 4);
+
+        let read = parse_pcap_reader(&mut &out[..]).unwrap();
+        assert_eq!(read.len(), 4);
+        for (a, b) in read.iter().zip(frames.iter().chain([&remote])) {
+            assert_eq!(
+                (a.timestamp_us, a.frame_id, a.bus, a.dlc, &a.bytes, a.is_extended, a.is_fd, &a.direction),
+                (b.timestamp_us, b.frame_id, b.bus, b.dlc, &b.bytes, b.is_extended, b.is_fd, &b.direction)
+            );
+            assert_eq!(a.error_class, b.error_class);
+        }
+    }
+}
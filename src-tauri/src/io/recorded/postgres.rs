@@ -750,6 +750,11 @@ fn parse_can_frame_row(row: &Row) -> Result<FrameMessage, Box<dyn std::error::Er
         source_address: None,
         incomplete: None,
         direction: None,
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
     })
 }
 
@@ -787,6 +792,11 @@ fn parse_modbus_frame_row(row: &Row) -> Result<FrameMessage, Box<dyn std::error:
         source_address: None,
         incomplete: None,
         direction: None,
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
     })
 }
 
@@ -821,6 +831,11 @@ fn parse_serial_frame_row(row: &Row) -> Result<FrameMessage, Box<dyn std::error:
         source_address: None, // Not extracted from PostgreSQL serial_frame table
         incomplete: None,
         direction: None,
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
     })
 }
 
@@ -867,6 +882,11 @@ fn parse_row_for_source_type(
                 source_address: None,
                 incomplete: None,
                 direction: None,
+                link: None,
+                clock: None,
+                is_error: None,
+                error_class: None,
+                brs: None,
             })
         }
     }
@@ -0,0 +1,348 @@
+// ui/src-tauri/src/io/recorded/trc.rs
+//
+// PEAK PCAN-View trace (.trc) parsing, versions 1.1 to 2.1, so logs from PCAN
+// tooling can be imported and replayed. The header's `;$FILEVERSION` picks the
+// line layout and `;$STARTTIME` (days since 1899-12-30, local time) anchors
+// the per-message offsets, which are in milliseconds:
+//
+//   1.1  `    1)      1059.9  Rx        0300  8  00 00 ...`          no bus
+//   1.2  `    1)      1059.9 1  Rx        0300  8  00 00 ...`        + bus
+//   1.3  `    1)      1059.900 1  Rx        0300 -  8  00 00 ...`    + reserved
+//   2.x  `    1      1059.900 DT 1     0300 Rx -  8    00 00 ...`   `;$COLUMNS`
+//
+// 2.x lines follow the `;$COLUMNS` list (default `N,O,T,I,d,l,D` in 2.0):
+// N number, O offset, T type, B bus, I ID, d direction, R reserved,
+// L data length, l DLC, D data. 2.x types DT/FD/FB/FE/BI (data, FD variants)
+// and RR (remote) are imported, and error frames (2.x ER, 1.x Error) become
+// error frames through can_error with the PEAK error type from the ID column;
+// status and event records are skipped, as are 1.x Warng/Info lines. Buses are 1-based in TRC (bus 1 → 0);
+// IDs written with more than 4 hex digits are extended. Without a start time
+// the offsets are used as-is.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use chrono::{Local, NaiveDate, TimeZone};
+
+use crate::io::can_error::{self, Violation};
+use crate::io::FrameMessage;
+
+/// CAN FD DLC → payload length
+const FD_LENGTHS: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+const DEFAULT_V2_COLUMNS: &str = "N,O,T,I,d,l,D";
+
+/// Layout information gathered from the header comments.
+struct TrcHeader {
+    /// Version as major * 10 + minor (1.1 → 11, 2.1 → 21)
+    version: u32,
+    /// Absolute time of offset 0 (µs since the Unix epoch)
+    start_us: u64,
+    columns: Vec<char>,
+}
+
+impl Default for TrcHeader {
+    fn default() -> Self {
+        Self {
+            version: 11,
+            start_us: 0,
+            columns: DEFAULT_V2_COLUMNS.split(',').filter_map(|c| c.chars().next()).collect(),
+        }
+    }
+}
+
+impl TrcHeader {
+    /// Take in one `;$KEY=value` header line.
+    fn apply(&mut self, line: &str) {
+        let Some((key, value)) = line.trim_start_matches(';').trim().split_once('=') else {
+            return;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "$FILEVERSION" => {
+                let (major, minor) = value.split_once('.').unwrap_or((value, "0"));
+                if let (Ok(major), Ok(minor)) = (major.parse::<u32>(), minor.parse::<u32>()) {
+                    self.version = major * 10 + minor;
+                }
+            }
+            "$STARTTIME" => {
+                if let Some(us) = value.parse::<f64>().ok().and_then(ole_days_to_us) {
+                    self.start_us = us;
+                }
+            }
+            "$COLUMNS" => {
+                self.columns = value.split(',').filter_map(|c| c.trim().chars().next()).collect();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Convert an OLE automation date (fractional days since 1899-12-30, local
+/// time) to µs since the Unix epoch.
+fn ole_days_to_us(days: f64) -> Option<u64> {
+    if !days.is_finite() || days <= 0.0 {
+        return None;
+    }
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)?.and_hms_opt(0, 0, 0)?;
+    let naive = epoch + chrono::Duration::microseconds((days * 86_400_000_000.0).round() as i64);
+    let local = Local.from_local_datetime(&naive).earliest()?;
+    u64::try_from(local.timestamp_micros()).ok()
+}
+
+/// Parse a millisecond offset ("1059.9", "1059.900") into µs without going
+/// through f64.
+fn parse_offset_us(s: &str) -> Option<u64> {
+    let (ms, frac) = s.split_once('.').unwrap_or((s, ""));
+    let ms: u64 = ms.parse().ok()?;
+    if !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let mut micros = frac.chars().take(3).collect::<String>();
+    while micros.len() < 3 {
+        micros.push('0');
+    }
+    Some(ms * 1000 + micros.parse::<u64>().ok()?)
+}
+
+fn parse_id(s: &str) -> Option<(u32, bool)> {
+    let id = u32::from_str_radix(s, 16).ok()?;
+    Some((id & 0x1FFF_FFFF, s.len() > 4))
+}
+
+fn parse_data(tokens: &[&str]) -> Option<Vec<u8>> {
+    tokens.iter().map(|t| u8::from_str_radix(t, 16).ok()).collect()
+}
+
+fn bus_from(s: &str) -> u8 {
+    s.parse::<u16>().map_or(0, |b| b.saturating_sub(1).min(255) as u8)
+}
+
+struct Record {
+    offset_us: u64,
+    frame_id: u32,
+    is_extended: bool,
+    bus: u8,
+    direction: &'static str,
+    dlc: u8,
+    bytes: Vec<u8>,
+    is_fd: bool,
+    /// Error frame; `frame_id` is then the PEAK error type
+    is_error: bool,
+}
+
+fn direction_of(s: &str) -> &'static str {
+    if s.eq_ignore_ascii_case("tx") { "tx" } else { "rx" }
+}
+
+/// 1.1 - 1.3: `n) offset [bus] type id [-] dlc data...`
+fn parse_v1_line(line: &str, version: u32) -> Option<Record> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if !tokens.first()?.ends_with(')') {
+        return None;
+    }
+    let offset_us = parse_offset_us(tokens.get(1)?)?;
+    let mut i = 2;
+    let mut bus = 0;
+    if version >= 12 {
+        bus = bus_from(tokens.get(i)?);
+        i += 1;
+    }
+    let kind = *tokens.get(i)?;
+    let is_error = kind.eq_ignore_ascii_case("error");
+    if !is_error && !kind.eq_ignore_ascii_case("rx") && !kind.eq_ignore_ascii_case("tx") {
+        // Warng, Info, ...
+        return None;
+    }
+    i += 1;
+    let (frame_id, is_extended) = parse_id(tokens.get(i)?)?;
+    i += 1;
+    if version >= 13 && tokens.get(i) == Some(&"-") {
+        i += 1;
+    }
+    let dlc: u8 = tokens.get(i)?.parse().ok()?;
+    i += 1;
+    let rest = tokens.get(i..).unwrap_or(&[]);
+    let bytes = if rest.first().is_some_and(|t| t.eq_ignore_ascii_case("RTR")) {
+        Vec::new()
+    } else {
+        parse_data(&rest[..rest.len().min(dlc as usize)])?
+    };
+    Some(Record { offset_us, frame_id, is_extended, bus, direction: direction_of(kind), dlc, bytes, is_fd: false, is_error })
+}
+
+/// 2.0 / 2.1: tokens follow the `;$COLUMNS` list.
+fn parse_v2_line(line: &str, columns: &[char]) -> Option<Record> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let mut offset_us = None;
+    let mut kind = "DT";
+    let mut bus = 0;
+    let mut id = None;
+    let mut direction = "rx";
+    let mut data_len = None;
+    let mut dlc = None;
+    let mut data: &[&str] = &[];
+
+    for (i, column) in columns.iter().enumerate() {
+        if *column == 'D' {
+            // Data runs to the end of the line (absent for remote/empty frames)
+            data = tokens.get(i..).unwrap_or(&[]);
+            break;
+        }
+        let token = *tokens.get(i)?;
+        match column {
+            'O' => offset_us = Some(parse_offset_us(token)?),
+            'T' => kind = token,
+            'B' => bus = bus_from(token),
+            'I' => id = Some(token),
+            'd' => direction = direction_of(token),
+            'L' => data_len = token.parse::<usize>().ok(),
+            'l' => dlc = token.parse::<usize>().ok(),
+            // N, R and anything unknown
+            _ => {}
+        }
+    }
+
+    let (is_fd, is_remote) = match kind {
+        "DT" | "ER" => (false, false),
+        "FD" | "FB" | "FE" | "BI" => (true, false),
+        "RR" => (false, true),
+        // ST status, EC error counter, EV event
+        _ => return None,
+    };
+    let (frame_id, is_extended) = parse_id(id?)?;
+    let len = match (data_len, dlc) {
+        (Some(len), _) => len,
+        (None, Some(dlc)) if is_fd => *FD_LENGTHS.get(dlc)?,
+        (None, Some(dlc)) => dlc.min(8),
+        (None, None) => data.len(),
+    };
+    let bytes = if is_remote { Vec::new() } else { parse_data(data.get(..len)?)? };
+    let dlc = if is_remote { len.min(8) as u8 } else { bytes.len() as u8 };
+    Some(Record { offset_us: offset_us?, frame_id, is_extended, bus, direction, dlc, bytes, is_fd, is_error: kind == "ER" })
+}
+
+/// Parse an entire PCAN-View trace file and return all frames
+pub fn parse_trc_file(file_path: &str) -> Result<Vec<FrameMessage>, String> {
+    let file = File::open(file_path)
+        .map_err(|e| format!("Failed to open TRC file '{}': {}", file_path, e))?;
+    parse_trc_lines(BufReader::new(file).lines().map_while(Result::ok))
+}
+
+fn parse_trc_lines(lines: impl Iterator<Item = String>) -> Result<Vec<FrameMessage>, String> {
+    let mut header = TrcHeader::default();
+    let mut frames = Vec::new();
+    let mut skipped = 0usize;
+
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed.starts_with(';') {
+            header.apply(trimmed);
+            continue;
+        }
+        if header.version < 11 || header.version > 21 {
+            return Err(format!(
+                "TRC version {}.{} is not supported (1.1 to 2.1)",
+                header.version / 10,
+                header.version % 10
+            ));
+        }
+        let record = if header.version >= 20 {
+            parse_v2_line(trimmed, &header.columns)
+        } else {
+            parse_v1_line(trimmed, header.version)
+        };
+        let Some(r) = record else {
+            skipped += 1;
+            continue;
+        };
+        if r.is_error {
+            // PEAK error type codes
+            let violation = match r.frame_id {
+                1 => Violation::Bit,
+                2 => Violation::Form,
+                4 => Violation::Stuff,
+                _ => Violation::Other,
+            };
+            let mut frame = can_error::violation_frame(violation, r.bus, header.start_us + r.offset_us);
+            frame.direction = Some(r.direction.to_string());
+            frames.push(frame);
+            continue;
+        }
+        frames.push(FrameMessage {
+            protocol: "can".to_string(),
+            timestamp_us: header.start_us + r.offset_us,
+            frame_id: r.frame_id,
+            bus: r.bus,
+            dlc: r.dlc,
+            bytes: r.bytes,
+            is_extended: r.is_extended,
+            is_fd: r.is_fd,
+            source_address: None,
+            incomplete: None,
+            direction: Some(r.direction.to_string()),
+            link: None,
+            clock: None,
+            is_error: None,
+            error_class: None,
+            brs: None,
+        });
+    }
+
+    if skipped > 0 {
+        tlog!("[trc] Skipped {} status, event or unparseable lines", skipped);
+    }
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Vec<FrameMessage> {
+        parse_trc_lines(text.lines().map(String::from)).unwrap()
+    }
+
+    #[test]
+    fn parses_version_1_layouts() {
+        let v11 = parse(";$FILEVERSION=1.1\n     1)      1059.9  Rx        0300  8  00 01 02 03 04 05 06 07\n     2)      1060.0  Error     0000  4  00 00 00 00\n");
+        assert_eq!(v11.len(), 2);
+        assert_eq!((v11[0].timestamp_us, v11[0].frame_id, v11[0].dlc), (1_059_900, 0x300, 8));
+        assert_eq!((v11[1].timestamp_us, v11[1].is_error), (1_060_000, Some(true)));
+
+        let v12 = parse(";$FILEVERSION=1.2\n     1)      2.5 2  Tx    18FF1021  2  AA BB\n");
+        assert_eq!((v12[0].bus, v12[0].frame_id, v12[0].is_extended), (1, 0x18FF_1021, true));
+        assert_eq!(v12[0].direction.as_deref(), Some("tx"));
+
+        let v13 = parse(";$FILEVERSION=1.3\n     1)      3.000 1  Rx        0123 -  4  RTR\n");
+        assert_eq!((v13[0].dlc, v13[0].bytes.len()), (4, 0));
+    }
+
+    #[test]
+    fn parses_version_2_columns_and_fd() {
+        let v20 = parse(";$FILEVERSION=2.0\n     1      1.250 DT     0300 Rx 3    01 02 03\n     2      1.300 FD     0301 Rx 9    00 01 02 03 04 05 06 07 08 09 0A 0B\n     3      1.400 ER     0004 Rx 0\n");
+        assert_eq!(v20.len(), 3);
+        assert_eq!(v20[2].error_class, Some(crate::io::CanErrorClass::Stuff));
+        assert_eq!((v20[0].timestamp_us, v20[0].bytes.len()), (1_250, 3));
+        assert!(v20[1].is_fd);
+        assert_eq!(v20[1].bytes.len(), 12);
+
+        let v21 = parse(";$FILEVERSION=2.1\n;$COLUMNS=N,O,T,B,I,d,R,L,D\n     1      5.000 FB 2  18DAF110 Tx -  6    10 20 30 40 50 60\n");
+        assert_eq!((v21[0].bus, v21[0].frame_id, v21[0].dlc), (1, 0x18DA_F110, 6));
+        assert!(v21[0].is_fd && v21[0].is_extended);
+    }
+
+    #[test]
+    fn anchors_offsets_to_start_time_and_rejects_unknown_versions() {
+        // 1970-01-02 00:00 local time
+        let start = ole_days_to_us(25570.0).unwrap();
+        let frames = parse(";$FILEVERSION=2.0\n;$STARTTIME=25570.0\n     1      1.500 DT     0100 Rx 1    FF\n");
+        assert_eq!(frames[0].timestamp_us, start + 1_500);
+        assert_eq!(parse_offset_us("12.3456"), Some(12_345));
+
+        assert!(parse_trc_lines(";$FILEVERSION=3.0\n     1      1.0 DT 0100 Rx 0\n".lines().map(String::from)).is_err());
+    }
+}
@@ -247,6 +247,11 @@ pub async fn run_source(
                                 source_address,
                                 incomplete: None,
                                 direction: None,
+                                link: None,
+                                clock: None,
+                                is_error: None,
+                                error_class: None,
+                                brs: None,
                             };
 
                             // Apply bus mapping
@@ -309,6 +314,11 @@ pub async fn run_source(
                         source_address,
                         incomplete: None,
                         direction: None,
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     };
 
                     if apply_bus_mapping(&mut msg, &bus_mappings) {
@@ -45,7 +45,7 @@ impl FrameCodec for SlcanCodec {
     ///   `r1230` -> Standard RTR, ID=0x123, DLC=0
     ///   `d7E09112233445566778899AABBCC` -> FD frame, ID=0x7E0, DLC=9 (12 bytes)
     ///   `b7E0F...64 hex bytes...` -> FD+BRS frame, ID=0x7E0, DLC=F (64 bytes)
-    fn decode(line: &str) -> Result<FrameMessage, IoError> {
+    fn decode(&self, line: &str) -> Result<FrameMessage, IoError> {
         let bytes = line.as_bytes();
         if bytes.is_empty() {
             return Err(IoError::protocol("slcan", "empty frame"));
@@ -68,7 +68,6 @@ impl FrameCodec for SlcanCodec {
                 ))
             }
         };
-        let _ = is_brs; // BRS is encoded in the prefix; no separate field in FrameMessage
 
         let id_len = if is_extended { 8 } else { 3 };
         let min_len = 1 + id_len + 1; // prefix + ID + DLC
@@ -150,13 +149,18 @@ impl FrameCodec for SlcanCodec {
             source_address: None,
             incomplete: None,
             direction: None,
+            link: None,
+            clock: None,
+            is_error: None,
+            error_class: None,
+            brs: is_fd.then_some(is_brs),
         })
     }
 
     /// Encode a CAN frame to slcan ASCII format.
     ///
     /// Returns ASCII bytes including trailing `\r`.
-    fn encode(frame: &CanTransmitFrame) -> Result<Vec<u8>, IoError> {
+    fn encode(&self, frame: &CanTransmitFrame) -> Result<Vec<u8>, IoError> {
         let max_len = if frame.is_fd { 64 } else { 8 };
         if frame.data.len() > max_len {
             return Err(IoError::protocol(
@@ -197,9 +201,10 @@ impl FrameCodec for SlcanCodec {
         };
         cmd.push_str(&format!("{:X}", dlc_code));
 
-        // Data bytes
-        for byte in &frame.data {
-            cmd.push_str(&format!("{:02X}", byte));
+        // Data bytes, zero-padded up to the FD length the DLC code stands for
+        let data_len = if frame.is_fd { DLC_LEN[dlc_code as usize] } else { frame.data.len() };
+        for i in 0..data_len {
+            cmd.push_str(&format!("{:02X}", frame.data.get(i).copied().unwrap_or(0)));
         }
 
         cmd.push('\r');
@@ -209,7 +214,7 @@ impl FrameCodec for SlcanCodec {
 
 /// Convert a data length to the CAN FD DLC code.
 /// Finds the smallest DLC code whose length >= the given length.
-fn len_to_fd_dlc(len: usize) -> u8 {
+pub(super) fn len_to_fd_dlc(len: usize) -> u8 {
     DLC_LEN.iter().position(|&l| l >= len).unwrap_or(15) as u8
 }
 
@@ -219,7 +224,7 @@ mod tests {
 
     #[test]
     fn test_slcan_decode_standard_frame() {
-        let frame = SlcanCodec::decode("t1234AABBCCDD").unwrap();
+        let frame = SlcanCodec.decode("t1234AABBCCDD").unwrap();
         assert_eq!(frame.frame_id, 0x123);
         assert_eq!(frame.dlc, 4);
         assert_eq!(frame.bytes, vec![0xAA, 0xBB, 0xCC, 0xDD]);
@@ -228,7 +233,7 @@ mod tests {
 
     #[test]
     fn test_slcan_decode_extended_frame() {
-        let frame = SlcanCodec::decode("T123456782AABB").unwrap();
+        let frame = SlcanCodec.decode("T123456782AABB").unwrap();
         assert_eq!(frame.frame_id, 0x12345678);
         assert_eq!(frame.dlc, 2);
         assert_eq!(frame.bytes, vec![0xAA, 0xBB]);
@@ -237,7 +242,7 @@ mod tests {
 
     #[test]
     fn test_slcan_decode_zero_dlc() {
-        let frame = SlcanCodec::decode("t1230").unwrap();
+        let frame = SlcanCodec.decode("t1230").unwrap();
         assert_eq!(frame.frame_id, 0x123);
         assert_eq!(frame.dlc, 0);
         assert!(frame.bytes.is_empty());
@@ -245,7 +250,7 @@ mod tests {
 
     #[test]
     fn test_slcan_decode_rtr() {
-        let frame = SlcanCodec::decode("r1234").unwrap();
+        let frame = SlcanCodec.decode("r1234").unwrap();
         assert_eq!(frame.frame_id, 0x123);
         assert_eq!(frame.dlc, 4);
         assert!(frame.bytes.is_empty()); // RTR has no data
@@ -253,8 +258,8 @@ mod tests {
 
     #[test]
     fn test_slcan_decode_invalid_prefix() {
-        assert!(SlcanCodec::decode("x1234AABB").is_err());
-        assert!(SlcanCodec::decode("").is_err());
+        assert!(SlcanCodec.decode("x1234AABB").is_err());
+        assert!(SlcanCodec.decode("").is_err());
     }
 
     #[test]
@@ -269,7 +274,7 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = SlcanCodec::encode(&frame).unwrap();
+        let encoded = SlcanCodec.encode(&frame).unwrap();
         assert_eq!(encoded, b"t1233010203\r");
     }
 
@@ -285,7 +290,7 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = SlcanCodec::encode(&frame).unwrap();
+        let encoded = SlcanCodec.encode(&frame).unwrap();
         assert_eq!(encoded, b"T123456782AABB\r");
     }
 
@@ -301,10 +306,10 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = SlcanCodec::encode(&original).unwrap();
+        let encoded = SlcanCodec.encode(&original).unwrap();
         // Remove trailing \r for parsing
         let encoded_str = std::str::from_utf8(&encoded[..encoded.len() - 1]).unwrap();
-        let decoded = SlcanCodec::decode(encoded_str).unwrap();
+        let decoded = SlcanCodec.decode(encoded_str).unwrap();
 
         assert_eq!(decoded.frame_id, original.frame_id);
         assert_eq!(decoded.bytes, original.data);
@@ -317,7 +322,7 @@ mod tests {
     #[test]
     fn test_slcan_decode_fd_standard_12bytes() {
         // d prefix = FD 11-bit, no BRS. DLC '9' = 12 bytes.
-        let frame = SlcanCodec::decode("d7E09112233445566778899AABBCC").unwrap();
+        let frame = SlcanCodec.decode("d7E09112233445566778899AABBCC").unwrap();
         assert_eq!(frame.frame_id, 0x7E0);
         assert_eq!(frame.dlc, 12);
         assert_eq!(frame.bytes.len(), 12);
@@ -332,7 +337,7 @@ mod tests {
         // D prefix = FD 29-bit, no BRS. DLC 'A' = 16 bytes.
         let data_hex = "00112233445566778899AABBCCDDEEFF";
         let line = format!("D12345678A{}", data_hex);
-        let frame = SlcanCodec::decode(&line).unwrap();
+        let frame = SlcanCodec.decode(&line).unwrap();
         assert_eq!(frame.frame_id, 0x12345678);
         assert_eq!(frame.dlc, 16);
         assert_eq!(frame.bytes.len(), 16);
@@ -345,12 +350,13 @@ mod tests {
         // b prefix = FD 11-bit, with BRS. DLC 'F' = 64 bytes.
         let data_hex = "42".repeat(64);
         let line = format!("b100F{}", data_hex);
-        let frame = SlcanCodec::decode(&line).unwrap();
+        let frame = SlcanCodec.decode(&line).unwrap();
         assert_eq!(frame.frame_id, 0x100);
         assert_eq!(frame.dlc, 64);
         assert_eq!(frame.bytes.len(), 64);
         assert!(frame.bytes.iter().all(|&b| b == 0x42));
         assert!(frame.is_fd);
+        assert_eq!(frame.brs, Some(true));
         assert!(!frame.is_extended);
     }
 
@@ -359,7 +365,7 @@ mod tests {
         // B prefix = FD 29-bit, with BRS
         let data_hex = "C0FFEE42".repeat(6); // 24 bytes, DLC 'C'
         let line = format!("B00000456C{}", data_hex);
-        let frame = SlcanCodec::decode(&line).unwrap();
+        let frame = SlcanCodec.decode(&line).unwrap();
         assert_eq!(frame.frame_id, 0x456);
         assert_eq!(frame.dlc, 24);
         assert_eq!(frame.bytes.len(), 24);
@@ -370,7 +376,7 @@ mod tests {
     #[test]
     fn test_slcan_decode_fd_classic_dlc_8() {
         // FD frame with DLC 8 = 8 bytes (same as classic)
-        let frame = SlcanCodec::decode("d1008AABBCCDDEEFF0011").unwrap();
+        let frame = SlcanCodec.decode("d1008AABBCCDDEEFF0011").unwrap();
         assert_eq!(frame.dlc, 8);
         assert_eq!(frame.bytes.len(), 8);
         assert!(frame.is_fd);
@@ -388,7 +394,7 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = SlcanCodec::encode(&frame).unwrap();
+        let encoded = SlcanCodec.encode(&frame).unwrap();
         assert_eq!(encoded, b"d7E09112233445566778899AABBCC\r");
     }
 
@@ -404,7 +410,7 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = SlcanCodec::encode(&frame).unwrap();
+        let encoded = SlcanCodec.encode(&frame).unwrap();
         let encoded_str = std::str::from_utf8(&encoded).unwrap();
         assert!(encoded_str.starts_with("B00000456F"));
         assert!(encoded_str.ends_with("\r"));
@@ -425,9 +431,9 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = SlcanCodec::encode(&original).unwrap();
+        let encoded = SlcanCodec.encode(&original).unwrap();
         let encoded_str = std::str::from_utf8(&encoded[..encoded.len() - 1]).unwrap();
-        let decoded = SlcanCodec::decode(encoded_str).unwrap();
+        let decoded = SlcanCodec.decode(encoded_str).unwrap();
 
         assert_eq!(decoded.frame_id, original.frame_id);
         assert_eq!(decoded.bytes, original.data);
@@ -24,6 +24,7 @@ use crate::io::error::IoError;
 use crate::io::gvret::{apply_bus_mapping, BusMapping};
 use crate::io::serial::utils as serial_utils;
 use crate::io::types::{SourceMessage, TransmitRequest};
+use super::codec::len_to_fd_dlc;
 use crate::io::{now_us, CanTransmitFrame, FrameMessage};
 
 // ============================================================================
@@ -226,6 +227,11 @@ pub fn parse_slcan_frame(line: &str) -> Option<FrameMessage> {
         source_address: None,
         incomplete: None,
         direction: None,
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: is_fd.then_some(matches!(bytes[0], b'b' | b'B')),
     })
 }
 
@@ -562,24 +568,43 @@ fn send_and_read(port: &mut Box<dyn serialport::SerialPort>, cmd: &[u8]) -> Opti
 // ============================================================================
 
 /// Encode a CAN transmit frame to slcan format for transmission
+///
+/// FD frames use the ELMUE extension: d/D, or b/B when the data phase
+/// switches bitrate. Their payload is zero-padded to the next FD length so
+/// the hex digits always match the DLC code.
 pub fn encode_transmit_frame(frame: &CanTransmitFrame) -> Vec<u8> {
-    let mut cmd = String::with_capacity(32);
+    let mut cmd = String::with_capacity(if frame.is_fd { 140 } else { 32 });
 
     // Frame type prefix
+    let prefix = match (frame.is_fd, frame.is_brs, frame.is_rtr) {
+        (true, true, _) => 'b',
+        (true, false, _) => 'd',
+        (false, _, true) => 'r',
+        (false, _, false) => 't',
+    };
     if frame.is_extended {
-        cmd.push('T');
+        cmd.push(prefix.to_ascii_uppercase());
         cmd.push_str(&format!("{:08X}", frame.frame_id));
     } else {
-        cmd.push('t');
+        cmd.push(prefix);
         cmd.push_str(&format!("{:03X}", frame.frame_id & 0x7FF));
     }
 
-    // DLC
-    cmd.push_str(&format!("{:X}", frame.data.len().min(8)));
+    // DLC: FD frames carry a DLC code, classic frames the byte count
+    let (dlc_code, data_len) = if frame.is_fd {
+        let code = len_to_fd_dlc(frame.data.len().min(64));
+        (code, DLC_LEN[code as usize])
+    } else {
+        let len = frame.data.len().min(8);
+        (len as u8, len)
+    };
+    cmd.push_str(&format!("{:X}", dlc_code));
 
-    // Data bytes
-    for byte in &frame.data {
-        cmd.push_str(&format!("{:02X}", byte));
+    // Data bytes (remote frames carry none)
+    if !(frame.is_rtr && !frame.is_fd) {
+        for i in 0..data_len {
+            cmd.push_str(&format!("{:02X}", frame.data.get(i).copied().unwrap_or(0)));
+        }
     }
 
     cmd.push('\r');
@@ -805,6 +830,7 @@ pub async fn run_source(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::io::test_frame;
 
     #[test]
     fn test_parse_standard_frame() {
@@ -878,55 +904,19 @@ mod tests {
 
     #[test]
     fn test_encode_standard_frame() {
-        let frame = FrameMessage {
-            protocol: "can".to_string(),
-            timestamp_us: 0,
-            frame_id: 0x123,
-            bus: 0,
-            dlc: 3,
-            bytes: vec![0x01, 0x02, 0x03],
-            is_extended: false,
-            is_fd: false,
-            source_address: None,
-            incomplete: None,
-            direction: None,
-        };
+        let frame = test_frame(0x123, 0, &[0x01, 0x02, 0x03]);
         assert_eq!(encode_slcan_frame(&frame), "t1233010203\r");
     }
 
     #[test]
     fn test_encode_extended_frame() {
-        let frame = FrameMessage {
-            protocol: "can".to_string(),
-            timestamp_us: 0,
-            frame_id: 0x12345678,
-            bus: 0,
-            dlc: 2,
-            bytes: vec![0xAA, 0xBB],
-            is_extended: true,
-            is_fd: false,
-            source_address: None,
-            incomplete: None,
-            direction: None,
-        };
+        let frame = FrameMessage { is_extended: true, ..test_frame(0x12345678, 0, &[0xAA, 0xBB]) };
         assert_eq!(encode_slcan_frame(&frame), "T123456782AABB\r");
     }
 
     #[test]
     fn test_encode_decode_roundtrip() {
-        let original = FrameMessage {
-            protocol: "can".to_string(),
-            timestamp_us: 0,
-            frame_id: 0x7FF,
-            bus: 0,
-            dlc: 4,
-            bytes: vec![0xDE, 0xAD, 0xBE, 0xEF],
-            is_extended: false,
-            is_fd: false,
-            source_address: None,
-            incomplete: None,
-            direction: None,
-        };
+        let original = test_frame(0x7FF, 0, &[0xDE, 0xAD, 0xBE, 0xEF]);
 
         let encoded = encode_slcan_frame(&original);
         // Remove trailing \r for parsing
@@ -938,6 +928,42 @@ mod tests {
         assert_eq!(decoded.is_extended, original.is_extended);
     }
 
+    #[test]
+    fn test_transmit_fd_frames_use_the_fd_commands() {
+        let mut frame = CanTransmitFrame {
+            frame_id: 0x7E0,
+            data: vec![0x11; 10],
+            bus: 0,
+            is_extended: false,
+            is_fd: true,
+            is_brs: true,
+            is_rtr: false,
+        };
+        // 10 bytes go out as DLC 9 (12 bytes), padded with zeros
+        let encoded = encode_transmit_frame(&frame);
+        assert_eq!(encoded, format!("b7E09{}0000\r", "11".repeat(10)).into_bytes());
+        let decoded = parse_slcan_frame(std::str::from_utf8(&encoded[..encoded.len() - 1]).unwrap()).unwrap();
+        assert_eq!((decoded.dlc, decoded.is_fd, decoded.brs), (12, true, Some(true)));
+
+        frame.is_brs = false;
+        frame.is_extended = true;
+        assert!(encode_transmit_frame(&frame).starts_with(b"D000007E09"));
+    }
+
+    #[test]
+    fn test_transmit_remote_frame_has_no_data() {
+        let frame = CanTransmitFrame {
+            frame_id: 0x123,
+            data: vec![0; 4],
+            bus: 0,
+            is_extended: false,
+            is_fd: false,
+            is_brs: false,
+            is_rtr: true,
+        };
+        assert_eq!(encode_transmit_frame(&frame), b"r1234\r");
+    }
+
     #[test]
     fn test_bitrate_mapping() {
         assert_eq!(find_bitrate_command(500_000).unwrap(), "S6");
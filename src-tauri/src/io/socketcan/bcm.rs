@@ -0,0 +1,165 @@
+// ui/src-tauri/src/io/socketcan/bcm.rs
+//
+// Periodic transmit through SocketCAN's broadcast manager (CAN_BCM). A
+// TX_SETUP hands the frame and its interval to the kernel, which sends it
+// from an hrtimer instead of a userspace loop that jitters under load.
+//
+// Each repeat owns its BCM socket. Closing the socket deletes the kernel's
+// transmit job, so dropping a `BcmRepeat` stops the repeat.
+
+use std::ffi::CString;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+use super::reader::{encode_frame, EncodedFrame};
+use crate::io::CanTransmitFrame;
+
+// Opcode and flags from linux/can/bcm.h
+const TX_SETUP: u32 = 1;
+const SETTIMER: u32 = 0x0001;
+const STARTTIMER: u32 = 0x0002;
+const TX_ANNOUNCE: u32 = 0x0008;
+const CAN_FD_FRAME: u32 = 0x0800;
+
+/// struct bcm_timeval
+#[repr(C)]
+struct BcmTimeval {
+    tv_sec: libc::c_long,
+    tv_usec: libc::c_long,
+}
+
+/// struct bcm_msg_head
+#[repr(C)]
+struct BcmMsgHead {
+    opcode: u32,
+    flags: u32,
+    count: u32,
+    ival1: BcmTimeval,
+    ival2: BcmTimeval,
+    can_id: u32,
+    nframes: u32,
+}
+
+/// `can_frame` / `canfd_frame` bytes, 8-byte aligned as in the kernel
+#[repr(C, align(8))]
+struct FrameBytes<const N: usize>([u8; N]);
+
+/// A BCM message carrying a single frame
+#[repr(C)]
+struct TxSetup<const N: usize> {
+    head: BcmMsgHead,
+    frame: FrameBytes<N>,
+}
+
+/// A frame the kernel is sending on a timer.
+pub struct BcmRepeat {
+    _socket: OwnedFd,
+}
+
+impl BcmRepeat {
+    /// Start sending `frame` on `interface` every `interval_ms`, the first
+    /// copy straight away.
+    pub fn start(interface: &str, frame: &CanTransmitFrame, interval_ms: u64) -> Result<Self, String> {
+        let socket = open(interface)?;
+        let flags = SETTIMER | STARTTIMER | TX_ANNOUNCE;
+        let sent = match encode_frame(frame) {
+            EncodedFrame::Classic(buf) => send(&socket, &tx_setup(buf, flags, interval_ms)),
+            EncodedFrame::Fd(buf) => send(&socket, &tx_setup(buf, flags | CAN_FD_FRAME, interval_ms)),
+        };
+        sent.map_err(|e| format!("BCM transmit setup on {} failed: {}", interface, e))?;
+        Ok(Self { _socket: socket })
+    }
+}
+
+fn tx_setup<const N: usize>(frame: [u8; N], flags: u32, interval_ms: u64) -> TxSetup<N> {
+    TxSetup {
+        head: BcmMsgHead {
+            opcode: TX_SETUP,
+            flags,
+            count: 0,
+            ival1: BcmTimeval { tv_sec: 0, tv_usec: 0 },
+            ival2: BcmTimeval {
+                tv_sec: (interval_ms / 1000) as libc::c_long,
+                tv_usec: ((interval_ms % 1000) * 1000) as libc::c_long,
+            },
+            can_id: u32::from_ne_bytes([frame[0], frame[1], frame[2], frame[3]]),
+            nframes: 1,
+        },
+        frame: FrameBytes(frame),
+    }
+}
+
+/// Open a BCM socket connected to `interface`.
+fn open(interface: &str) -> Result<OwnedFd, String> {
+    let name = CString::new(interface).map_err(|_| format!("Invalid interface name '{}'", interface))?;
+    let ifindex = unsafe { libc::if_nametoindex(name.as_ptr()) };
+    if ifindex == 0 {
+        return Err(format!("SocketCAN interface '{}' not found", interface));
+    }
+
+    let fd = unsafe { libc::socket(libc::PF_CAN, libc::SOCK_DGRAM, libc::CAN_BCM) };
+    if fd < 0 {
+        return Err(format!("Failed to open BCM socket: {}", std::io::Error::last_os_error()));
+    }
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    let mut addr: libc::sockaddr_can = unsafe { std::mem::zeroed() };
+    addr.can_family = libc::AF_CAN as libc::sa_family_t;
+    addr.can_ifindex = ifindex as libc::c_int;
+    let rc = unsafe {
+        libc::connect(
+            socket.as_raw_fd(),
+            &addr as *const libc::sockaddr_can as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_can>() as libc::socklen_t,
+        )
+    };
+    if rc < 0 {
+        return Err(format!(
+            "Failed to connect BCM socket to {}: {}",
+            interface,
+            std::io::Error::last_os_error()
+        ));
+    }
+    Ok(socket)
+}
+
+fn send<T>(socket: &OwnedFd, msg: &T) -> std::io::Result<()> {
+    let len = std::mem::size_of::<T>();
+    let written = unsafe { libc::write(socket.as_raw_fd(), msg as *const T as *const libc::c_void, len) };
+    if written < 0 {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_messages_match_the_kernel_layout() {
+        // bcm_msg_head is 56 bytes on 64-bit, frames follow directly
+        assert_eq!(std::mem::size_of::<BcmMsgHead>(), 56);
+        assert_eq!(std::mem::size_of::<TxSetup<16>>(), 56 + 16);
+        assert_eq!(std::mem::size_of::<TxSetup<72>>(), 56 + 72);
+    }
+
+    #[test]
+    fn test_setup_carries_the_interval_and_frame_id() {
+        let frame = CanTransmitFrame {
+            frame_id: 0x123,
+            data: vec![1, 2, 3],
+            bus: 0,
+            is_extended: true,
+            is_fd: false,
+            is_brs: false,
+            is_rtr: false,
+        };
+        let EncodedFrame::Classic(buf) = encode_frame(&frame) else { panic!("classic frame expected") };
+        let msg = tx_setup(buf, SETTIMER | STARTTIMER, 1250);
+        assert_eq!((msg.head.ival2.tv_sec, msg.head.ival2.tv_usec), (1, 250_000));
+        assert_eq!(msg.head.can_id, 0x8000_0123);
+        assert_eq!((msg.head.count, msg.head.nframes), (0, 1));
+    }
+}
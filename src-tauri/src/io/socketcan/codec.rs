@@ -52,7 +52,7 @@ impl FrameCodec for SocketCanCodec {
     ///
     /// Classic layout: can_id(4) + dlc(1) + pad(3) + data(8)
     /// FD layout: can_id(4) + len(1) + flags(1) + pad(2) + data(64)
-    fn decode(raw: &[u8]) -> Result<FrameMessage, IoError> {
+    fn decode(&self, raw: &[u8]) -> Result<FrameMessage, IoError> {
         let is_fd = raw.len() >= consts::FD_FRAME_SIZE;
 
         if !is_fd && raw.len() < consts::CLASSIC_FRAME_SIZE {
@@ -91,13 +91,18 @@ impl FrameCodec for SocketCanCodec {
             source_address: None,
             incomplete: None,
             direction: None,
+            link: None,
+            clock: None,
+            is_error: None,
+            error_class: None,
+            brs: None,
         })
     }
 
     /// Encode a CAN frame for SocketCAN.
     ///
     /// Returns Classic (16 bytes) for standard CAN or Fd (72 bytes) for CAN FD.
-    fn encode(frame: &CanTransmitFrame) -> Result<SocketCanEncodedFrame, IoError> {
+    fn encode(&self, frame: &CanTransmitFrame) -> Result<SocketCanEncodedFrame, IoError> {
         if frame.is_fd {
             // Validate FD data length
             if frame.data.len() > 64 {
@@ -186,7 +191,7 @@ mod tests {
         // data
         raw[8..12].copy_from_slice(&[0xAA, 0xBB, 0xCC, 0xDD]);
 
-        let frame = SocketCanCodec::decode(&raw).unwrap();
+        let frame = SocketCanCodec.decode(&raw).unwrap();
         assert_eq!(frame.frame_id, 0x123);
         assert_eq!(frame.dlc, 4);
         assert_eq!(frame.bytes, vec![0xAA, 0xBB, 0xCC, 0xDD]);
@@ -205,7 +210,7 @@ mod tests {
             raw[8 + i] = i as u8;
         }
 
-        let frame = SocketCanCodec::decode(&raw).unwrap();
+        let frame = SocketCanCodec.decode(&raw).unwrap();
         assert_eq!(frame.frame_id, 0x456);
         assert_eq!(frame.dlc, 12);
         assert!(frame.is_extended);
@@ -224,7 +229,7 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = SocketCanCodec::encode(&frame).unwrap();
+        let encoded = SocketCanCodec.encode(&frame).unwrap();
         match encoded {
             SocketCanEncodedFrame::Classic(buf) => {
                 assert_eq!(
@@ -250,7 +255,7 @@ mod tests {
             is_rtr: false,
         };
 
-        let encoded = SocketCanCodec::encode(&frame).unwrap();
+        let encoded = SocketCanCodec.encode(&frame).unwrap();
         match encoded {
             SocketCanEncodedFrame::Fd(buf) => {
                 let can_id = u32::from_ne_bytes(buf[0..4].try_into().unwrap());
@@ -274,6 +279,6 @@ mod tests {
             is_rtr: false,
         };
 
-        assert!(SocketCanCodec::encode(&frame).is_err());
+        assert!(SocketCanCodec.encode(&frame).is_err());
     }
 }
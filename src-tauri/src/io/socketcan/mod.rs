@@ -11,8 +11,11 @@
 // Allow dead code on non-Linux platforms where this module is not functional
 #![allow(dead_code)]
 
+#[cfg(target_os = "linux")]
+pub mod bcm;
 pub mod codec;
 mod reader;
+pub mod setup;
 
 // Re-export reader types (platform-specific)
 #[cfg(target_os = "linux")]
@@ -15,6 +15,7 @@ mod linux_impl {
         CanAnyFrame, CanDataFrame, CanFdFrame, CanFdSocket, EmbeddedFrame, ExtendedId, Frame, Id,
         Socket, StandardId,
     };
+    use std::os::unix::io::{AsRawFd, RawFd};
     use std::sync::{
         atomic::{AtomicBool, Ordering},
         mpsc as std_mpsc,
@@ -23,10 +24,12 @@ mod linux_impl {
     use std::time::Duration;
     use tokio::sync::mpsc;
 
+    use crate::io::can_error;
     use crate::io::error::IoError;
     use crate::io::gvret::{apply_bus_mapping, BusMapping};
+    use crate::io::hw_clock::HardwareClock;
     use crate::io::types::{SourceMessage, TransmitRequest};
-    use crate::io::{now_us, CanTransmitFrame, FrameMessage};
+    use crate::io::{now_us, CanTransmitFrame, FrameClock, FrameMessage};
 
     // ============================================================================
     // Types and Configuration
@@ -61,88 +64,6 @@ mod linux_impl {
         pub data_bitrate: Option<u32>,
     }
 
-    // ============================================================================
-    // Interface Configuration
-    // ============================================================================
-
-    /// Configure a SocketCAN interface using pkexec for privilege escalation.
-    /// This brings down the interface, sets the bitrate, and brings it back up.
-    /// If enable_fd is true, the interface is configured for CAN FD mode.
-    ///
-    /// Returns Ok(()) on success, or an error message on failure.
-    pub fn configure_interface(
-        interface: &str,
-        bitrate: u32,
-        enable_fd: bool,
-        data_bitrate: Option<u32>,
-    ) -> Result<(), String> {
-        use std::process::Command;
-
-        tlog!(
-            "[socketcan] Configuring interface {} with bitrate {}{} using pkexec",
-            interface,
-            bitrate,
-            if enable_fd {
-                format!(" (FD mode, dbitrate: {:?})", data_bitrate)
-            } else {
-                String::new()
-            }
-        );
-
-        // Build the shell command to configure the interface
-        // We use a single pkexec call with sh -c to run all commands in sequence
-        let mut script = format!(
-            "ip link set {iface} down && ip link set {iface} type can bitrate {bitrate}",
-            iface = interface,
-            bitrate = bitrate
-        );
-
-        // Add FD configuration if enabled
-        if enable_fd {
-            script.push_str(" fd on");
-            if let Some(dbitrate) = data_bitrate {
-                script.push_str(&format!(" dbitrate {}", dbitrate));
-            }
-        }
-
-        script.push_str(&format!(" && ip link set {} up", interface));
-
-        let output = Command::new("pkexec")
-            .args(["sh", "-c", &script])
-            .output()
-            .map_err(|e| {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    "pkexec not found. Install polkit or configure the interface manually.".to_string()
-                } else {
-                    format!("Failed to run pkexec: {}", e)
-                }
-            })?;
-
-        if output.status.success() {
-            tlog!("[socketcan] Interface {} configured successfully", interface);
-            Ok(())
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-
-            // Check for common error cases
-            if stderr.contains("dismissed") || stderr.contains("cancelled") {
-                Err("Authentication cancelled by user".to_string())
-            } else if stderr.contains("Not authorized") {
-                Err("Not authorised to configure network interfaces".to_string())
-            } else {
-                let error_detail = if !stderr.is_empty() {
-                    stderr.trim().to_string()
-                } else if !stdout.is_empty() {
-                    stdout.trim().to_string()
-                } else {
-                    format!("Exit code: {:?}", output.status.code())
-                };
-                Err(format!("Failed to configure interface: {}", error_detail))
-            }
-        }
-    }
-
     // ============================================================================
     // Utility Functions
     // ============================================================================
@@ -162,6 +83,11 @@ mod linux_impl {
                 source_address: None,
                 incomplete: None,
                 direction: None,
+                link: None,
+                clock: Some(FrameClock::Host),
+                is_error: None,
+                error_class: None,
+                brs: None,
             }),
             CanAnyFrame::Fd(f) => Some(FrameMessage {
                 protocol: "can".to_string(),
@@ -175,9 +101,124 @@ mod linux_impl {
                 source_address: None,
                 incomplete: None,
                 direction: None,
+                link: None,
+                clock: Some(FrameClock::Host),
+                is_error: None,
+                error_class: None,
+                brs: None,
             }),
             CanAnyFrame::Remote(_) => None, // Skip remote frames
-            CanAnyFrame::Error(_) => None,  // Skip error frames
+            // Only delivered when the error filter is set (`error_frames`)
+            CanAnyFrame::Error(f) => {
+                let mut msg = can_error::error_frame(f.id_word(), f.data(), bus_override.unwrap_or(0), now_us());
+                msg.clock = Some(FrameClock::Host);
+                Some(msg)
+            }
+        }
+    }
+
+    /// Ask the kernel to deliver error frames of every class.
+    fn enable_error_frames(fd: RawFd) -> std::io::Result<()> {
+        let mask: libc::can_err_mask_t = can_error::CAN_ERR_MASK;
+        let ret = unsafe {
+            libc::setsockopt(
+                fd,
+                libc::SOL_CAN_RAW,
+                libc::CAN_RAW_ERR_FILTER,
+                &mask as *const libc::can_err_mask_t as *const libc::c_void,
+                std::mem::size_of::<libc::can_err_mask_t>() as libc::socklen_t,
+            )
+        };
+        if ret < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    // ============================================================================
+    // Kernel Timestamps
+    // ============================================================================
+
+    /// Timestamps the kernel attached to a received frame (SO_TIMESTAMPING)
+    #[derive(Clone, Copy, Debug, Default)]
+    struct RxTimestamps {
+        /// Kernel receive time (host clock)
+        software_us: Option<u64>,
+        /// Raw hardware timestamp, where the driver provides one
+        hardware_us: Option<u64>,
+    }
+
+    /// Ask the kernel to timestamp received frames: in hardware where the
+    /// driver supports it, and in software at receive either way.
+    fn enable_timestamping(fd: RawFd) -> std::io::Result<()> {
+        let flags = (libc::SOF_TIMESTAMPING_RX_HARDWARE
+            | libc::SOF_TIMESTAMPING_RAW_HARDWARE
+            | libc::SOF_TIMESTAMPING_RX_SOFTWARE
+            | libc::SOF_TIMESTAMPING_SOFTWARE) as libc::c_int;
+        let ret = unsafe {
+            libc::setsockopt(
+                fd,
+                libc::SOL_SOCKET,
+                libc::SO_TIMESTAMPING,
+                &flags as *const libc::c_int as *const libc::c_void,
+                std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+        if ret < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    fn timespec_us(ts: &libc::timespec) -> Option<u64> {
+        let us = ts.tv_sec as i64 * 1_000_000 + ts.tv_nsec as i64 / 1_000;
+        (us > 0).then_some(us as u64)
+    }
+
+    /// Timestamps of the next frame, peeked without consuming it so the frame
+    /// itself is then read as usual. Waits up to the socket's read timeout.
+    fn peek_timestamps(fd: RawFd) -> std::io::Result<RxTimestamps> {
+        let mut frame = [0u8; 72];
+        let mut iov = libc::iovec {
+            iov_base: frame.as_mut_ptr() as *mut libc::c_void,
+            iov_len: frame.len(),
+        };
+        // u64 elements keep the control buffer aligned for cmsghdr
+        let mut control = [0u64; 32];
+        let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = std::mem::size_of_val(&control) as _;
+
+        if unsafe { libc::recvmsg(fd, &mut msg, libc::MSG_PEEK) } < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        let mut stamps = RxTimestamps::default();
+        unsafe {
+            let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+            while !cmsg.is_null() {
+                if (*cmsg).cmsg_level == libc::SOL_SOCKET && (*cmsg).cmsg_type == libc::SCM_TIMESTAMPING {
+                    // struct scm_timestamping: [software, legacy, raw hardware]
+                    let ts = std::ptr::read_unaligned(libc::CMSG_DATA(cmsg) as *const [libc::timespec; 3]);
+                    stamps.software_us = timespec_us(&ts[0]);
+                    stamps.hardware_us = timespec_us(&ts[2]);
+                }
+                cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+            }
+        }
+        Ok(stamps)
+    }
+
+    /// Stamp a frame from the kernel's timestamps: the hardware clock aligned
+    /// to the epoch when there is one, else the kernel's receive time.
+    fn apply_timestamps(frame: &mut FrameMessage, stamps: RxTimestamps, clock: &mut HardwareClock) {
+        if let Some(hardware_us) = stamps.hardware_us {
+            frame.timestamp_us = clock.epoch_us(hardware_us, frame.timestamp_us);
+            frame.clock = Some(FrameClock::Hardware);
+        } else if let Some(software_us) = stamps.software_us {
+            frame.timestamp_us = software_us;
         }
     }
 
@@ -244,12 +285,12 @@ mod linux_impl {
             // Build the frame
             let frame = if is_extended {
                 let id = ExtendedId::new(raw_id)
-                    .ok_or_else(|| format!("Invalid extended ID: 0x{:08X}", raw_id))?;
+                    .ok_or_else(|| msg!(INVALID_EXTENDED_ID, id = format!("0x{:08X}", raw_id)))?;
                 CanDataFrame::new(Id::Extended(id), frame_data)
                     .ok_or_else(|| "Failed to create extended frame".to_string())?
             } else {
                 let id = StandardId::new(raw_id as u16)
-                    .ok_or_else(|| format!("Invalid standard ID: 0x{:03X}", raw_id))?;
+                    .ok_or_else(|| msg!(INVALID_STANDARD_ID, id = format!("0x{:03X}", raw_id)))?;
                 CanDataFrame::new(Id::Standard(id), frame_data)
                     .ok_or_else(|| "Failed to create standard frame".to_string())?
             };
@@ -279,12 +320,12 @@ mod linux_impl {
             // Build the FD frame
             let frame = if is_extended {
                 let id = ExtendedId::new(raw_id)
-                    .ok_or_else(|| format!("Invalid extended ID: 0x{:08X}", raw_id))?;
+                    .ok_or_else(|| msg!(INVALID_EXTENDED_ID, id = format!("0x{:08X}", raw_id)))?;
                 CanFdFrame::new(Id::Extended(id), frame_data)
                     .ok_or_else(|| "Failed to create extended FD frame".to_string())?
             } else {
                 let id = StandardId::new(raw_id as u16)
-                    .ok_or_else(|| format!("Invalid standard ID: 0x{:03X}", raw_id))?;
+                    .ok_or_else(|| msg!(INVALID_STANDARD_ID, id = format!("0x{:03X}", raw_id)))?;
                 CanFdFrame::new(Id::Standard(id), frame_data)
                     .ok_or_else(|| "Failed to create standard FD frame".to_string())?
             };
@@ -369,22 +410,41 @@ mod linux_impl {
     /// Run SocketCAN source and send frames to merge task (supports CAN FD)
     ///
     /// If `bitrate` is provided, the interface will be configured automatically
-    /// using pkexec before opening the socket.
+    /// using pkexec before opening the socket. With `hardware_timestamps`,
+    /// frames carry the kernel's timestamps (hardware where the driver has
+    /// them) instead of the reader's receive time. With `error_frames`, the
+    /// controller's error frames are delivered too. A `listen_only` source
+    /// needs `bitrate`: the interface is configured listen-only and no
+    /// transmit channel is offered.
     pub async fn run_source(
         source_idx: usize,
         interface: String,
         bitrate: Option<u32>,
         enable_fd: bool,
         data_bitrate: Option<u32>,
+        hardware_timestamps: bool,
+        error_frames: bool,
+        listen_only: bool,
         bus_mappings: Vec<BusMapping>,
         stop_flag: Arc<AtomicBool>,
         tx: mpsc::Sender<SourceMessage>,
     ) {
         let device = format!("socketcan({})", interface);
 
+        // Listen-only is only trusted when the interface is configured here
+        if listen_only && bitrate.is_none() {
+            let _ = tx
+                .send(SourceMessage::Error(
+                    source_idx,
+                    "Listen-only needs a bitrate so the interface can be configured".to_string(),
+                ))
+                .await;
+            return;
+        }
+
         // Configure interface if bitrate is specified
         if let Some(br) = bitrate {
-            if let Err(e) = configure_interface(&interface, br, enable_fd, data_bitrate) {
+            if let Err(e) = crate::io::socketcan::setup::configure_interface(&interface, br, enable_fd, data_bitrate, listen_only) {
                 let _ = tx
                     .send(SourceMessage::Error(source_idx, e))
                     .await;
@@ -411,15 +471,31 @@ mod linux_impl {
             tlog!("[socketcan] Warning: could not set read timeout: {}", e);
         }
 
-        // Create transmit channel
+        if error_frames {
+            if let Err(e) = enable_error_frames(socket.as_raw_fd()) {
+                tlog!("[socketcan] Warning: error frames unavailable on {}: {}", interface, e);
+            }
+        }
+
+        let mut hw_clock = None;
+        if hardware_timestamps {
+            match enable_timestamping(socket.as_raw_fd()) {
+                Ok(()) => hw_clock = Some(HardwareClock::new(64)),
+                Err(e) => tlog!("[socketcan] Warning: kernel timestamps unavailable on {}: {}", interface, e),
+            }
+        }
+
+        // Create transmit channel (not offered in listen-only mode)
         let (transmit_tx, transmit_rx) = std_mpsc::sync_channel::<TransmitRequest>(32);
-        let _ = tx
-            .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
-            .await;
+        if !listen_only {
+            let _ = tx
+                .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
+                .await;
+        }
 
         tlog!(
-            "[socketcan] Source {} connected to {} (FD capable)",
-            source_idx, interface
+            "[socketcan] Source {} connected to {} (FD capable, listen_only: {})",
+            source_idx, interface, listen_only
         );
 
         // Emit device-connected event
@@ -439,10 +515,35 @@ mod linux_impl {
                     let _ = req.result_tx.send(result);
                 }
 
+                // Timestamps first, so waiting for a frame happens here
+                let stamps = if hw_clock.is_some() {
+                    match peek_timestamps(socket.as_raw_fd()) {
+                        Ok(stamps) => Some(stamps),
+                        Err(ref e)
+                            if e.kind() == std::io::ErrorKind::WouldBlock
+                                || e.kind() == std::io::ErrorKind::TimedOut =>
+                        {
+                            continue;
+                        }
+                        Err(e) => {
+                            let _ = tx_clone.blocking_send(SourceMessage::Error(
+                                source_idx,
+                                format!("Read error: {}", e),
+                            ));
+                            return;
+                        }
+                    }
+                } else {
+                    None
+                };
+
                 // Read frame (CanAnyFrame supports both classic and FD)
                 match socket.read_frame() {
                     Ok(frame) => {
                         if let Some(mut frame_msg) = convert_any_frame(frame, None) {
+                            if let (Some(clock), Some(stamps)) = (hw_clock.as_mut(), stamps) {
+                                apply_timestamps(&mut frame_msg, stamps, clock);
+                            }
                             if apply_bus_mapping(&mut frame_msg, &bus_mappings) {
                                 let _ = tx_clone
                                     .blocking_send(SourceMessage::Frames(source_idx, vec![frame_msg]));
@@ -486,12 +587,12 @@ mod linux_impl {
 
             let frame = if is_extended {
                 let id = ExtendedId::new(raw_id)
-                    .ok_or_else(|| format!("Invalid extended ID: 0x{:08X}", raw_id))?;
+                    .ok_or_else(|| msg!(INVALID_EXTENDED_ID, id = format!("0x{:08X}", raw_id)))?;
                 CanFdFrame::new(Id::Extended(id), frame_data)
                     .ok_or_else(|| "Failed to create extended FD frame".to_string())?
             } else {
                 let id = StandardId::new(raw_id as u16)
-                    .ok_or_else(|| format!("Invalid standard ID: 0x{:03X}", raw_id))?;
+                    .ok_or_else(|| msg!(INVALID_STANDARD_ID, id = format!("0x{:03X}", raw_id)))?;
                 CanFdFrame::new(Id::Standard(id), frame_data)
                     .ok_or_else(|| "Failed to create standard FD frame".to_string())?
             };
@@ -510,12 +611,12 @@ mod linux_impl {
 
             let frame = if is_extended {
                 let id = ExtendedId::new(raw_id)
-                    .ok_or_else(|| format!("Invalid extended ID: 0x{:08X}", raw_id))?;
+                    .ok_or_else(|| msg!(INVALID_EXTENDED_ID, id = format!("0x{:08X}", raw_id)))?;
                 CanDataFrame::new(Id::Extended(id), frame_data)
                     .ok_or_else(|| "Failed to create extended frame".to_string())?
             } else {
                 let id = StandardId::new(raw_id as u16)
-                    .ok_or_else(|| format!("Invalid standard ID: 0x{:03X}", raw_id))?;
+                    .ok_or_else(|| msg!(INVALID_STANDARD_ID, id = format!("0x{:03X}", raw_id)))?;
                 CanDataFrame::new(Id::Standard(id), frame_data)
                     .ok_or_else(|| "Failed to create standard frame".to_string())?
             };
@@ -573,16 +674,6 @@ mod stub {
         Fd([u8; 72]),
     }
 
-    /// Stub configure_interface for non-Linux
-    pub fn configure_interface(
-        _interface: &str,
-        _bitrate: u32,
-        _enable_fd: bool,
-        _data_bitrate: Option<u32>,
-    ) -> Result<(), String> {
-        Err("SocketCAN is only available on Linux".to_string())
-    }
-
     /// Stub encode_frame for non-Linux (not actually usable)
     pub fn encode_frame(frame: &CanTransmitFrame) -> EncodedFrame {
         if frame.is_fd {
@@ -599,6 +690,9 @@ mod stub {
         _bitrate: Option<u32>,
         _enable_fd: bool,
         _data_bitrate: Option<u32>,
+        _hardware_timestamps: bool,
+        _error_frames: bool,
+        _listen_only: bool,
         _bus_mappings: Vec<BusMapping>,
         _stop_flag: Arc<AtomicBool>,
         tx: mpsc::Sender<SourceMessage>,
@@ -615,5 +709,5 @@ mod stub {
 #[cfg(not(target_os = "linux"))]
 #[allow(unused_imports)]
 pub use stub::{
-    configure_interface, encode_frame, run_source, EncodedFrame, SocketCanConfig,
+    encode_frame, run_source, EncodedFrame, SocketCanConfig,
 };
@@ -0,0 +1,264 @@
+// ui/src-tauri/src/io/socketcan/setup.rs
+//
+// SocketCAN interface setup from inside WireTAP: set bitrate, FD data
+// bitrate and listen-only mode, and bring an interface up or down. `ip`
+// needs CAP_NET_ADMIN, so every change runs as one pkexec call and polkit
+// asks for the user's password. Reading the current setup needs no
+// privileges.
+//
+// Interface names end up in a shell script, so they're checked against
+// what the kernel accepts in a name before anything runs.
+
+use serde::Serialize;
+
+/// An interface's current setup, as `ip -details link show` reports it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct CanInterfaceStatus {
+    pub interface: String,
+    /// Administratively up
+    pub up: bool,
+    /// Nominal bitrate (None for virtual interfaces)
+    pub bitrate: Option<u32>,
+    /// CAN FD data phase bitrate
+    pub data_bitrate: Option<u32>,
+    pub fd: bool,
+    pub listen_only: bool,
+    /// Controller state (ERROR-ACTIVE, ERROR-PASSIVE, BUS-OFF, ...)
+    pub state: Option<String>,
+}
+
+/// Reject anything that isn't a plain interface name (at most 15 chars of
+/// letters, digits, `-`, `_` and `.`).
+fn check_interface_name(interface: &str) -> Result<(), String> {
+    let valid = !interface.is_empty()
+        && interface.len() < 16
+        && interface
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid CAN interface name '{}'", interface))
+    }
+}
+
+/// `ip` commands that take an interface down, set its bit timing and bring
+/// it back up.
+fn configure_script(
+    interface: &str,
+    bitrate: u32,
+    enable_fd: bool,
+    data_bitrate: Option<u32>,
+    listen_only: bool,
+) -> String {
+    let mut script = format!(
+        "ip link set {iface} down && ip link set {iface} type can bitrate {bitrate}",
+        iface = interface,
+        bitrate = bitrate
+    );
+
+    if enable_fd {
+        script.push_str(" fd on");
+        if let Some(dbitrate) = data_bitrate {
+            script.push_str(&format!(" dbitrate {}", dbitrate));
+        }
+    }
+
+    // Always set explicitly, so an earlier listen-only setup doesn't linger
+    script.push_str(if listen_only { " listen-only on" } else { " listen-only off" });
+
+    script.push_str(&format!(" && ip link set {} up", interface));
+    script
+}
+
+/// Read `ip -details -json link show` output for one interface.
+fn parse_link_details(interface: &str, json: &str) -> Result<CanInterfaceStatus, String> {
+    let links: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("Unexpected output from ip: {}", e))?;
+    let link = links
+        .get(0)
+        .ok_or_else(|| format!("SocketCAN interface '{}' not found", interface))?;
+
+    let flags = link.get("flags").and_then(|f| f.as_array());
+    let up = flags.is_some_and(|f| f.iter().any(|v| v == "UP"));
+
+    let info = link.pointer("/linkinfo/info_data");
+    let bitrate_at = |path: &str| {
+        info.and_then(|i| i.pointer(path))
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32)
+    };
+    let ctrlmode = info
+        .and_then(|i| i.get("ctrlmode"))
+        .and_then(|m| m.as_array());
+    let has_mode = |mode: &str| ctrlmode.is_some_and(|m| m.iter().any(|v| v == mode));
+
+    Ok(CanInterfaceStatus {
+        interface: interface.to_string(),
+        up,
+        bitrate: bitrate_at("/bittiming/bitrate"),
+        data_bitrate: bitrate_at("/data_bittiming/bitrate"),
+        fd: has_mode("FD"),
+        listen_only: has_mode("LISTEN-ONLY"),
+        state: info
+            .and_then(|i| i.get("state"))
+            .and_then(|s| s.as_str())
+            .map(str::to_string),
+    })
+}
+
+/// Run a script as root through pkexec. `action` names it in errors.
+#[cfg(target_os = "linux")]
+fn run_privileged(script: &str, action: &str) -> Result<(), String> {
+    use std::process::Command;
+
+    let output = Command::new("pkexec")
+        .args(["sh", "-c", script])
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                "pkexec not found. Install polkit or configure the interface manually.".to_string()
+            } else {
+                format!("Failed to run pkexec: {}", e)
+            }
+        })?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Check for common error cases
+    if stderr.contains("dismissed") || stderr.contains("cancelled") {
+        Err("Authentication cancelled by user".to_string())
+    } else if stderr.contains("Not authorized") {
+        Err("Not authorised to configure network interfaces".to_string())
+    } else {
+        let error_detail = if !stderr.is_empty() {
+            stderr.trim().to_string()
+        } else if !stdout.is_empty() {
+            stdout.trim().to_string()
+        } else {
+            format!("Exit code: {:?}", output.status.code())
+        };
+        Err(format!("Failed to {}: {}", action, error_detail))
+    }
+}
+
+/// Configure a SocketCAN interface using pkexec for privilege escalation.
+/// This brings down the interface, sets the bitrate, and brings it back up.
+/// If enable_fd is true, the interface is configured for CAN FD mode.
+#[cfg(target_os = "linux")]
+pub fn configure_interface(
+    interface: &str,
+    bitrate: u32,
+    enable_fd: bool,
+    data_bitrate: Option<u32>,
+    listen_only: bool,
+) -> Result<(), String> {
+    check_interface_name(interface)?;
+
+    tlog!(
+        "[socketcan] Configuring interface {} with bitrate {}{}{} using pkexec",
+        interface,
+        bitrate,
+        if enable_fd {
+            format!(" (FD mode, dbitrate: {:?})", data_bitrate)
+        } else {
+            String::new()
+        },
+        if listen_only { " (listen-only)" } else { "" }
+    );
+
+    let script = configure_script(interface, bitrate, enable_fd, data_bitrate, listen_only);
+    run_privileged(&script, "configure interface")?;
+    tlog!("[socketcan] Interface {} configured successfully", interface);
+    Ok(())
+}
+
+/// Bring an interface up or down, keeping its current bit timing.
+#[cfg(target_os = "linux")]
+pub fn set_interface_up(interface: &str, up: bool) -> Result<(), String> {
+    check_interface_name(interface)?;
+    let state = if up { "up" } else { "down" };
+    tlog!("[socketcan] Setting interface {} {} using pkexec", interface, state);
+    run_privileged(
+        &format!("ip link set {} {}", interface, state),
+        &format!("set interface {}", state),
+    )
+}
+
+/// Current setup of an interface.
+#[cfg(target_os = "linux")]
+pub fn interface_status(interface: &str) -> Result<CanInterfaceStatus, String> {
+    check_interface_name(interface)?;
+    let output = std::process::Command::new("ip")
+        .args(["-details", "-json", "link", "show", "dev", interface])
+        .output()
+        .map_err(|e| format!("Failed to run ip: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("SocketCAN interface '{}' not found", interface));
+    }
+    parse_link_details(interface, &String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn configure_interface(
+    _interface: &str,
+    _bitrate: u32,
+    _enable_fd: bool,
+    _data_bitrate: Option<u32>,
+    _listen_only: bool,
+) -> Result<(), String> {
+    Err("SocketCAN is only available on Linux".to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_interface_up(_interface: &str, _up: bool) -> Result<(), String> {
+    Err("SocketCAN is only available on Linux".to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn interface_status(_interface: &str) -> Result<CanInterfaceStatus, String> {
+    Err("SocketCAN is only available on Linux".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_that_a_shell_would_interpret_are_refused() {
+        assert!(check_interface_name("can0").is_ok());
+        assert!(check_interface_name("vcan-bench.1").is_ok());
+        assert!(check_interface_name("can0; reboot").is_err());
+        assert!(check_interface_name("$(id)").is_err());
+        assert!(check_interface_name("").is_err());
+        assert!(check_interface_name("a_very_long_name0").is_err());
+    }
+
+    #[test]
+    fn test_configure_script_sets_fd_and_listen_only() {
+        assert_eq!(
+            configure_script("can1", 500_000, true, Some(2_000_000), false),
+            "ip link set can1 down && ip link set can1 type can bitrate 500000 fd on dbitrate 2000000 listen-only off && ip link set can1 up"
+        );
+        assert!(configure_script("can0", 250_000, false, Some(2_000_000), true).contains("bitrate 250000 listen-only on"));
+    }
+
+    #[test]
+    fn test_link_details_are_read_from_ip_json() {
+        let json = r#"[{"ifname":"can0","flags":["NOARP","UP","LOWER_UP","ECHO"],
+            "linkinfo":{"info_kind":"can","info_data":{"ctrlmode":["FD"],"state":"ERROR-ACTIVE",
+            "bittiming":{"bitrate":500000,"sample_point":"0.875"},"data_bittiming":{"bitrate":2000000}}}}]"#;
+        let status = parse_link_details("can0", json).unwrap();
+        assert!(status.up && status.fd && !status.listen_only);
+        assert_eq!((status.bitrate, status.data_bitrate), (Some(500_000), Some(2_000_000)));
+        assert_eq!(status.state.as_deref(), Some("ERROR-ACTIVE"));
+
+        let down = parse_link_details("vcan0", r#"[{"ifname":"vcan0","flags":["NOARP"]}]"#).unwrap();
+        assert_eq!(down, CanInterfaceStatus { interface: "vcan0".to_string(), ..Default::default() });
+    }
+}
@@ -2,7 +2,7 @@
 //
 // Interface trait validation and session trait inheritance.
 
-use super::{InterfaceTraits, Protocol, TemporalMode};
+use super::{InterfaceTraits, Protocol, ProtocolStream, TemporalMode};
 
 /// Result of validating multiple interface traits for a session
 #[derive(Clone, Debug)]
@@ -109,6 +109,34 @@ pub fn validate_session_traits(interface_traits: &[InterfaceTraits]) -> SessionT
     }
 }
 
+/// Group a session's output buses by the protocol their frames carry, for
+/// `SessionDataStreams::sub_streams`. Takes (protocols, output bus) per bus
+/// route; a bus whose interface declares several protocols (CAN + CAN FD)
+/// appears under each. Returns nothing when only one protocol is present —
+/// a single-protocol session is one stream.
+pub fn protocol_streams(routes: impl IntoIterator<Item = (Vec<Protocol>, u8)>) -> Vec<ProtocolStream> {
+    let mut streams: Vec<ProtocolStream> = Vec::new();
+    for (protocols, bus) in routes {
+        for protocol in protocols {
+            match streams.iter_mut().find(|s| s.protocol == protocol) {
+                Some(stream) => {
+                    if !stream.buses.contains(&bus) {
+                        stream.buses.push(bus);
+                    }
+                }
+                None => streams.push(ProtocolStream { protocol, buses: vec![bus] }),
+            }
+        }
+    }
+    if streams.len() < 2 {
+        return Vec::new();
+    }
+    for stream in &mut streams {
+        stream.buses.sort_unstable();
+    }
+    streams
+}
+
 /// Get interface traits for a profile kind.
 /// Used when explicit traits are not available in IOCapabilities.
 pub fn get_traits_for_profile_kind(kind: &str) -> InterfaceTraits {
@@ -127,6 +155,64 @@ pub fn get_traits_for_profile_kind(kind: &str) -> InterfaceTraits {
             tx_bytes: false,
             multi_source: true,
         },
+        "canserver" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Can],
+            tx_frames: false,
+            tx_bytes: false,
+            multi_source: true,
+        },
+        "websocket" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Can, Protocol::CanFd],
+            tx_frames: false,
+            tx_bytes: false,
+            multi_source: true,
+        },
+        "udp_can" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Can, Protocol::CanFd],
+            tx_frames: false,
+            tx_bytes: false,
+            multi_source: true,
+        },
+        // UDS payloads carried as "doip" frames; sent via TransmitPayload::Diagnostic
+        "doip" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Serial],
+            tx_frames: false,
+            tx_bytes: false,
+            multi_source: true,
+        },
+        "wiretap_net" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Can, Protocol::CanFd],
+            tx_frames: false,
+            tx_bytes: false,
+            multi_source: true,
+        },
+        // Master-mode profiles get tx_frames on their bus mapping
+        "lin" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Serial],
+            tx_frames: false,
+            tx_bytes: false,
+            multi_source: true,
+        },
+        "elm327" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Can],
+            tx_frames: false, // Poll requests are generated by the reader
+            tx_bytes: false,
+            multi_source: true,
+        },
+        "custom_serial" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Can],
+            tx_frames: true, // Note: listen_only overrides this at runtime
+            tx_bytes: false,
+            multi_source: true,
+        },
         "gs_usb" => InterfaceTraits {
             temporal_mode: TemporalMode::Realtime,
             protocols: vec![Protocol::Can],
@@ -134,6 +220,34 @@ pub fn get_traits_for_profile_kind(kind: &str) -> InterfaceTraits {
             tx_bytes: false,
             multi_source: true,
         },
+        "pcan" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Can],
+            tx_frames: true, // Note: listen_only overrides this at runtime
+            tx_bytes: false,
+            multi_source: true,
+        },
+        "kvaser" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Can],
+            tx_frames: true, // Note: listen_only overrides this at runtime
+            tx_bytes: false,
+            multi_source: true,
+        },
+        "vector_xl" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Can, Protocol::CanFd],
+            tx_frames: true, // Note: listen_only overrides this at runtime
+            tx_bytes: false,
+            multi_source: true,
+        },
+        "j2534" => InterfaceTraits {
+            temporal_mode: TemporalMode::Realtime,
+            protocols: vec![Protocol::Can],
+            tx_frames: true, // Note: listen_only overrides this at runtime
+            tx_bytes: false,
+            multi_source: true,
+        },
         "socketcan" => InterfaceTraits {
             temporal_mode: TemporalMode::Realtime,
             protocols: vec![Protocol::Can],
@@ -369,4 +483,27 @@ mod tests {
         ));
         assert!(protocols_compatible(&[], &[Protocol::Can]));
     }
+
+    #[test]
+    fn protocol_streams_group_buses_by_protocol() {
+        // Single protocol: one stream, nothing to declare
+        assert!(protocol_streams(vec![(vec![Protocol::Can], 0), (vec![Protocol::Can], 1)]).is_empty());
+
+        let streams = protocol_streams(vec![
+            (vec![Protocol::Can, Protocol::CanFd], 1),
+            (vec![Protocol::Serial], 4),
+            (vec![Protocol::Can], 0),
+            (vec![Protocol::Modbus], 5),
+        ]);
+        let summary: Vec<_> = streams.iter().map(|s| (s.protocol.clone(), s.buses.clone())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Protocol::Can, vec![0, 1]),
+                (Protocol::CanFd, vec![1]),
+                (Protocol::Serial, vec![4]),
+                (Protocol::Modbus, vec![5]),
+            ]
+        );
+    }
 }
@@ -40,6 +40,9 @@ pub enum SourceMessage {
     ControlReady(usize, ControlSender),
     /// Source connected successfully (source_index, device_type, address, bus_number)
     Connected(usize, String, String, Option<u8>),
+    /// Source dropped and will be restarted by its reconnect policy
+    /// (source_index, reason, attempt, retry_in_ms)
+    Disconnected(usize, String, u32, u64),
 }
 
 // ============================================================================
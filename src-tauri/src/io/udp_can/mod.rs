@@ -0,0 +1,348 @@
+// ui/src-tauri/src/io/udp_can/mod.rs
+//
+// UDP listener source for WiFi CAN bridges (typically an ESP32) that just
+// blast datagrams at a port, with no handshake or heartbeat. WireTAP binds
+// the port, optionally joins a multicast group or accepts only one sender,
+// and reads each datagram in one of two layouts:
+//
+// - binary: records with the ID, length, bus and data at configurable
+//   offsets. The defaults read `u32 LE ID, u8 length, data`. Bit 31 of a
+//   4-byte ID marks a 29-bit ID (SocketCAN's EFF flag); otherwise IDs above
+//   0x7FF are taken as extended. With a record size a datagram can pack
+//   several records; without one it holds a single frame.
+// - candump: text lines, `can0 123#DEADBEEF` (timestamp and interface
+//   optional) or candump's default `can0 123 [4] DE AD BE EF`.
+//
+// Frames get the receive time. Bus numbers (binary bus byte, interface digits
+// in text) map onto session buses through the profile's interfaces, as with
+// CANserver. Receive only.
+
+pub mod reader;
+
+use serde_json::Value;
+
+use crate::io::recorded::parse_candump_record;
+use crate::io::{now_us, FrameMessage};
+
+// Internal items used by multi_source
+pub(crate) use reader::run_source as run_udp_can_source;
+pub use reader::probe_udp_can;
+
+/// SocketCAN's extended-frame flag in a 32-bit ID word
+const EFF_FLAG: u32 = 0x8000_0000;
+/// Remote and error flags, dropped from the ID
+const RTR_ERR_FLAGS: u32 = 0x6000_0000;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// How datagrams are laid out
+#[derive(Clone, Debug, PartialEq)]
+pub enum UdpCanFormat {
+    Binary(BinaryLayout),
+    Candump,
+}
+
+/// Field positions within a binary record
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinaryLayout {
+    pub id_offset: usize,
+    /// Width of the ID field (1-4 bytes)
+    pub id_bytes: usize,
+    /// Byte holding the number of data bytes (data runs to the end of the
+    /// record if unset)
+    pub dlc_offset: Option<usize>,
+    pub data_offset: usize,
+    pub bus_offset: Option<usize>,
+    /// Size of each record when a datagram packs several
+    pub record_size: Option<usize>,
+    pub big_endian: bool,
+}
+
+impl Default for BinaryLayout {
+    fn default() -> Self {
+        Self {
+            id_offset: 0,
+            id_bytes: 4,
+            dlc_offset: Some(4),
+            data_offset: 5,
+            bus_offset: None,
+            record_size: None,
+            big_endian: false,
+        }
+    }
+}
+
+/// UDP listener settings, parsed from a profile's connection
+#[derive(Clone, Debug, PartialEq)]
+pub struct UdpCanConfig {
+    /// Local address to bind (default all interfaces)
+    pub bind: String,
+    pub port: u16,
+    /// IPv4 multicast group to join
+    pub multicast_group: Option<std::net::Ipv4Addr>,
+    /// Only accept datagrams from this host
+    pub sender: Option<String>,
+    pub format: UdpCanFormat,
+    /// Silence after which the source reports that no data is arriving
+    pub timeout_sec: f64,
+}
+
+fn number(connection: &Value, key: &str) -> Option<f64> {
+    connection
+        .get(key)
+        .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.trim().parse().ok())))
+}
+
+fn offset(connection: &Value, key: &str) -> Result<Option<usize>, String> {
+    match number(connection, key) {
+        Some(v) if !(0.0..=1500.0).contains(&v) => Err(format!("Invalid {} {}", key, v)),
+        Some(v) => Ok(Some(v as usize)),
+        None => Ok(None),
+    }
+}
+
+fn text(connection: &Value, key: &str) -> Option<String> {
+    connection
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+}
+
+impl UdpCanConfig {
+    /// Read the settings from a profile's connection map.
+    pub fn from_connection(connection: &Value) -> Result<Self, String> {
+        let port = number(connection, "port").ok_or("UDP port is required")?;
+        if !(1.0..=65535.0).contains(&port) {
+            return Err(format!("Invalid UDP port {}", port));
+        }
+
+        let format = match text(connection, "format").as_deref() {
+            None | Some("binary") => {
+                let defaults = BinaryLayout::default();
+                let id_bytes = offset(connection, "id_bytes")?.unwrap_or(defaults.id_bytes);
+                if !(1..=4).contains(&id_bytes) {
+                    return Err(format!("ID width must be 1-4 bytes, not {}", id_bytes));
+                }
+                // An explicit empty value clears the length byte
+                let dlc_offset = match connection.get("dlc_offset") {
+                    Some(Value::Null) => None,
+                    Some(Value::String(s)) if s.trim().is_empty() => None,
+                    Some(_) => offset(connection, "dlc_offset")?,
+                    None => defaults.dlc_offset,
+                };
+                UdpCanFormat::Binary(BinaryLayout {
+                    id_offset: offset(connection, "id_offset")?.unwrap_or(defaults.id_offset),
+                    id_bytes,
+                    dlc_offset,
+                    data_offset: offset(connection, "data_offset")?.unwrap_or(defaults.data_offset),
+                    bus_offset: offset(connection, "bus_offset")?,
+                    record_size: offset(connection, "record_size")?.filter(|&s| s > 0),
+                    big_endian: connection.get("big_endian").and_then(|v| v.as_bool()).unwrap_or(false),
+                })
+            }
+            Some("candump") => UdpCanFormat::Candump,
+            Some(other) => return Err(format!("Unknown datagram format '{}'", other)),
+        };
+
+        let multicast_group = match text(connection, "multicast_group") {
+            Some(group) => {
+                let addr: std::net::Ipv4Addr = group
+                    .parse()
+                    .map_err(|_| format!("Invalid multicast group '{}'", group))?;
+                if !addr.is_multicast() {
+                    return Err(format!("{} is not a multicast address", addr));
+                }
+                Some(addr)
+            }
+            None => None,
+        };
+
+        Ok(UdpCanConfig {
+            bind: text(connection, "bind").unwrap_or_else(|| "0.0.0.0".to_string()),
+            port: port as u16,
+            multicast_group,
+            sender: text(connection, "sender"),
+            format,
+            timeout_sec: number(connection, "timeout").unwrap_or(5.0).max(0.5),
+        })
+    }
+}
+
+// ============================================================================
+// Datagram Parsing
+// ============================================================================
+
+fn frame(frame_id: u32, is_extended: bool, bus: u8, mut bytes: Vec<u8>) -> FrameMessage {
+    bytes.truncate(64);
+    FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us: now_us(),
+        frame_id,
+        bus,
+        dlc: bytes.len() as u8,
+        is_fd: bytes.len() > 8,
+        bytes,
+        is_extended,
+        source_address: None,
+        incomplete: None,
+        direction: Some("rx".to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    }
+}
+
+fn parse_record(layout: &BinaryLayout, record: &[u8]) -> Option<FrameMessage> {
+    let id_field = record.get(layout.id_offset..layout.id_offset + layout.id_bytes)?;
+    let fold = |acc: u32, b: &u8| (acc << 8) | *b as u32;
+    let raw_id = if layout.big_endian {
+        id_field.iter().fold(0, fold)
+    } else {
+        id_field.iter().rev().fold(0, fold)
+    };
+    let (frame_id, is_extended) = if raw_id & EFF_FLAG != 0 {
+        (raw_id & !(EFF_FLAG | RTR_ERR_FLAGS), true)
+    } else {
+        let id = raw_id & !RTR_ERR_FLAGS;
+        (id, id > 0x7FF)
+    };
+
+    let data = record.get(layout.data_offset..).unwrap_or_default();
+    let len = match layout.dlc_offset {
+        Some(at) => (*record.get(at)? as usize).min(data.len()),
+        None => data.len(),
+    };
+    let bus = match layout.bus_offset {
+        Some(at) => *record.get(at)?,
+        None => 0,
+    };
+    Some(frame(frame_id, is_extended, bus, data[..len].to_vec()))
+}
+
+/// Bus number from an interface name's trailing digits (can1 → 1).
+fn bus_from_interface(interface: &str) -> u8 {
+    let digits = interface.trim_start_matches(|c: char| !c.is_ascii_digit());
+    digits.parse().unwrap_or(0)
+}
+
+/// One line of candump text, with or without timestamp and interface.
+fn parse_candump_text_line(line: &str) -> Option<FrameMessage> {
+    let tokens: Vec<&str> = line.split_whitespace().filter(|t| !t.starts_with('(')).collect();
+
+    // `[iface] ID#DATA`
+    if let Some(pos) = tokens.iter().position(|t| t.contains('#')) {
+        let mut frame = parse_candump_record(tokens[pos])?;
+        frame.timestamp_us = now_us();
+        frame.bus = pos.checked_sub(1).map_or(0, |i| bus_from_interface(tokens[i]));
+        return Some(frame);
+    }
+
+    // `iface ID [n] b0 b1 ...`
+    let [interface, id, len, data @ ..] = tokens.as_slice() else {
+        return None;
+    };
+    let len: usize = len.strip_prefix('[')?.strip_suffix(']')?.parse().ok()?;
+    let frame_id = u32::from_str_radix(id, 16).ok()?;
+    let bytes = data
+        .get(..len)?
+        .iter()
+        .map(|b| u8::from_str_radix(b, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    Some(frame(frame_id & 0x1FFF_FFFF, id.len() > 3, bus_from_interface(interface), bytes))
+}
+
+/// Frames in one datagram. Malformed records and lines are skipped.
+pub fn parse_datagram(format: &UdpCanFormat, datagram: &[u8]) -> Vec<FrameMessage> {
+    match format {
+        UdpCanFormat::Binary(layout) => match layout.record_size {
+            Some(size) => datagram
+                .chunks_exact(size)
+                .filter_map(|record| parse_record(layout, record))
+                .collect(),
+            None => parse_record(layout, datagram).into_iter().collect(),
+        },
+        UdpCanFormat::Candump => String::from_utf8_lossy(datagram)
+            .lines()
+            .filter_map(parse_candump_text_line)
+            .collect(),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_binary_records_with_default_and_custom_layouts() {
+        let config = UdpCanConfig::from_connection(&json!({"port": 3333})).unwrap();
+        let mut datagram = (EFF_FLAG | 0x18DA_F110).to_le_bytes().to_vec();
+        datagram.extend_from_slice(&[3, 0xAA, 0xBB, 0xCC, 0, 0, 0, 0, 0]);
+        let frames = parse_datagram(&config.format, &datagram);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].frame_id, 0x18DA_F110);
+        assert!(frames[0].is_extended);
+        assert_eq!(frames[0].bytes, vec![0xAA, 0xBB, 0xCC]);
+
+        // Big-endian 2-byte ID, bus byte, fixed 8 data bytes, two records per datagram
+        let config = UdpCanConfig::from_connection(&json!({
+            "port": "3333", "id_bytes": 2, "big_endian": true, "bus_offset": 2,
+            "dlc_offset": "", "data_offset": 3, "record_size": 11
+        }))
+        .unwrap();
+        let datagram = [
+            0x01, 0x23, 1, 1, 2, 3, 4, 5, 6, 7, 8, //
+            0x07, 0xE8, 0, 9, 9, 9, 9, 9, 9, 9, 9, //
+            0x00, // partial record
+        ];
+        let frames = parse_datagram(&config.format, &datagram);
+        assert_eq!(frames.len(), 2);
+        assert_eq!((frames[0].frame_id, frames[0].bus, frames[0].bytes.len()), (0x123, 1, 8));
+        assert_eq!((frames[1].frame_id, frames[1].bus), (0x7E8, 0));
+        assert!(!frames[1].is_extended);
+    }
+
+    #[test]
+    fn test_candump_text_lines() {
+        let config = UdpCanConfig::from_connection(&json!({"port": 3333, "format": "candump"})).unwrap();
+        let text = "(1436509052.249713) can1 123#DEADBEEF\n\
+                    7E8#0441\n\
+                    can2  18DAF110   [3]  01 02 03\n\
+                    garbage\n";
+        let frames = parse_datagram(&config.format, text.as_bytes());
+        assert_eq!(frames.len(), 3);
+        assert_eq!((frames[0].frame_id, frames[0].bus), (0x123, 1));
+        assert_eq!(frames[0].bytes, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!((frames[1].frame_id, frames[1].bus), (0x7E8, 0));
+        assert_eq!((frames[2].frame_id, frames[2].bus), (0x18DA_F110, 2));
+        assert!(frames[2].is_extended);
+        assert_eq!(frames[2].bytes, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_config_from_connection() {
+        assert!(UdpCanConfig::from_connection(&json!({})).is_err());
+        assert!(UdpCanConfig::from_connection(&json!({"port": 70000})).is_err());
+        assert!(UdpCanConfig::from_connection(&json!({"port": 3333, "id_bytes": 5})).is_err());
+        assert!(UdpCanConfig::from_connection(&json!({"port": 3333, "multicast_group": "192.168.1.5"})).is_err());
+
+        let config = UdpCanConfig::from_connection(&json!({
+            "port": 3333, "multicast_group": "239.0.0.42", "sender": " 192.168.4.1 "
+        }))
+        .unwrap();
+        assert_eq!(config.bind, "0.0.0.0");
+        assert_eq!(config.multicast_group, Some(std::net::Ipv4Addr::new(239, 0, 0, 42)));
+        assert_eq!(config.sender.as_deref(), Some("192.168.4.1"));
+        assert_eq!(config.format, UdpCanFormat::Binary(BinaryLayout::default()));
+    }
+}
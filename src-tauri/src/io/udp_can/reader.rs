@@ -0,0 +1,213 @@
+// ui/src-tauri/src/io/udp_can/reader.rs
+//
+// UDP listener for the raw CAN source. The socket is bound to the profile's
+// port (joining the multicast group if one is set); datagrams from other
+// hosts are dropped when a sender is configured.
+
+use std::collections::BTreeSet;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+
+use super::{parse_datagram, UdpCanConfig};
+use crate::io::error::IoError;
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::types::SourceMessage;
+use crate::io::FrameMessage;
+
+/// Largest datagram read (anything bigger is truncated by the OS)
+const MAX_DATAGRAM: usize = 65536;
+
+/// How long a probe listens once the first frames have arrived
+const PROBE_LISTEN: Duration = Duration::from_millis(1000);
+
+fn device(config: &UdpCanConfig) -> String {
+    format!("udp_can({}:{})", config.bind, config.port)
+}
+
+/// A bound socket plus the sender filter, resolved once up front
+struct Listener {
+    socket: UdpSocket,
+    sender: Option<IpAddr>,
+}
+
+impl Listener {
+    async fn bind(config: &UdpCanConfig) -> Result<Self, IoError> {
+        let device = device(config);
+        let socket = UdpSocket::bind((config.bind.as_str(), config.port))
+            .await
+            .map_err(|e| IoError::connection(&device, e.to_string()))?;
+        if let Some(group) = config.multicast_group {
+            socket
+                .join_multicast_v4(group, Ipv4Addr::UNSPECIFIED)
+                .map_err(|e| IoError::connection(&device, format!("join {}: {}", group, e)))?;
+        }
+        let sender = match &config.sender {
+            Some(host) => Some(
+                tokio::net::lookup_host((host.as_str(), 0))
+                    .await
+                    .ok()
+                    .and_then(|mut addrs| addrs.next())
+                    .map(|addr| addr.ip())
+                    .ok_or_else(|| IoError::connection(&device, format!("cannot resolve sender '{}'", host)))?,
+            ),
+            None => None,
+        };
+        Ok(Self { socket, sender })
+    }
+
+    /// Length and origin of the next datagram from an accepted sender
+    async fn recv(&self, buf: &mut [u8]) -> std::io::Result<(usize, SocketAddr)> {
+        loop {
+            let (n, from) = self.socket.recv_from(buf).await?;
+            if self.sender.is_none_or(|ip| ip == from.ip()) {
+                return Ok((n, from));
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Port Probing
+// ============================================================================
+
+/// Listen briefly; returns the buses seen, in order, and the sending host.
+pub async fn probe_udp_can(config: &UdpCanConfig) -> Result<(Vec<u8>, String), IoError> {
+    let device = device(config);
+    let listener = Listener::bind(config).await?;
+
+    let mut buf = vec![0u8; MAX_DATAGRAM];
+    let mut buses = BTreeSet::new();
+    let mut senders = BTreeSet::new();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs_f64(config.timeout_sec);
+    let mut listen_until: Option<tokio::time::Instant> = None;
+
+    loop {
+        let until = listen_until.unwrap_or(deadline);
+        let remaining = until.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        match tokio::time::timeout(remaining, listener.recv(&mut buf)).await {
+            Ok(Ok((n, from))) => {
+                let frames = parse_datagram(&config.format, &buf[..n]);
+                if !frames.is_empty() {
+                    buses.extend(frames.iter().map(|f| f.bus));
+                    senders.insert(from.ip());
+                    listen_until.get_or_insert_with(|| tokio::time::Instant::now() + PROBE_LISTEN);
+                }
+            }
+            Ok(Err(e)) => return Err(IoError::read(&device, e.to_string())),
+            Err(_) => break,
+        }
+    }
+
+    if listen_until.is_none() {
+        return Err(IoError::timeout(&device, "frames"));
+    }
+    let senders = senders.iter().map(|ip| ip.to_string()).collect::<Vec<_>>().join(", ");
+    tlog!("[udp_can] Probe of port {} saw buses {:?} from {}", config.port, buses, senders);
+    Ok((buses.into_iter().collect(), senders))
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Run a UDP listener source and send frames to the merge task
+pub async fn run_source(
+    source_idx: usize,
+    config: UdpCanConfig,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let listener = match Listener::bind(&config).await {
+        Ok(l) => l,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e.to_string())).await;
+            return;
+        }
+    };
+
+    let address = format!("{}:{}", config.bind, config.port);
+    tlog!("[udp_can] Source {} listening on {}", source_idx, address);
+    let _ = tx
+        .send(SourceMessage::Connected(source_idx, "udp_can".to_string(), address.clone(), None))
+        .await;
+
+    let silence_limit = Duration::from_secs_f64(config.timeout_sec);
+    let mut last_data = Instant::now();
+    let mut silent = false;
+    let mut rx_frames: u64 = 0;
+    let mut bus_filtered: u64 = 0;
+    let mut unreadable: u64 = 0;
+    let mut buf = vec![0u8; MAX_DATAGRAM];
+
+    while !stop_flag.load(Ordering::SeqCst) {
+        match tokio::time::timeout(Duration::from_millis(50), listener.recv(&mut buf)).await {
+            Ok(Ok((n, from))) => {
+                let frames = parse_datagram(&config.format, &buf[..n]);
+                if frames.is_empty() {
+                    // Log the first few, then every thousandth
+                    unreadable += 1;
+                    if unreadable <= 3 || unreadable % 1000 == 0 {
+                        tlog!(
+                            "[udp_can] Source {}: no frames in {}-byte datagram from {} ({} so far)",
+                            source_idx, n, from, unreadable
+                        );
+                    }
+                    continue;
+                }
+                last_data = Instant::now();
+                if silent {
+                    tlog!("[udp_can] Source {}: data resumed on {}", source_idx, address);
+                    silent = false;
+                }
+                let total = frames.len();
+                rx_frames += total as u64;
+                let mapped: Vec<FrameMessage> = frames
+                    .into_iter()
+                    .filter_map(|mut frame| apply_bus_mapping(&mut frame, &bus_mappings).then_some(frame))
+                    .collect();
+                bus_filtered += (total - mapped.len()) as u64;
+                if !mapped.is_empty() {
+                    let _ = tx.send(SourceMessage::Frames(source_idx, mapped)).await;
+                }
+            }
+            Ok(Err(e)) => {
+                let _ = tx
+                    .send(SourceMessage::Error(source_idx, format!("Read error: {}", e)))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                // Timeout - continue
+            }
+        }
+
+        // A bridge that stops sending looks the same as one that's off; note it once
+        if !silent && last_data.elapsed() >= silence_limit {
+            tlog!(
+                "[udp_can] Source {}: no frames on {} for {:.1}s",
+                source_idx, address, silence_limit.as_secs_f64()
+            );
+            silent = true;
+        }
+    }
+
+    tlog!(
+        "[udp_can] Source {} ended: rx={}, bus_filtered={}, unreadable={}",
+        source_idx, rx_frames, bus_filtered, unreadable
+    );
+
+    let _ = tx
+        .send(SourceMessage::Ended(source_idx, "stopped".to_string()))
+        .await;
+}
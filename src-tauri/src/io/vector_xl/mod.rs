@@ -0,0 +1,310 @@
+// src-tauri/src/io/vector_xl/mod.rs
+//
+// Vector XL support for WireTAP (VN1610, VN1630, VN1640 and other XL Driver
+// Library interfaces). Windows only: Vector's XL Driver Library
+// (vxlapi64.dll) is loaded at runtime so WireTAP still starts without it.
+//
+// A profile selects one interface (by serial number, or by the global XL
+// channel index of its first channel) and opens every CAN channel enabled in
+// its interface list on a single XL port, so a VN1630 maps onto buses the same
+// way a multi-bus GVRET does: the device bus is the channel number on the
+// interface (hwChannel). With `enable_fd` the port is opened with the CAN FD
+// API (XL interface version 4); otherwise the classic CAN API is used.
+
+// Allow dead_code for constants and helpers that are only used on Windows
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+
+use crate::io::{now_us, CanTransmitFrame, FrameMessage};
+
+#[cfg(target_os = "windows")]
+pub mod xlapi;
+
+#[cfg(target_os = "windows")]
+pub use xlapi::{run_source, VectorBusConfig};
+
+// ============================================================================
+// XL API Constants
+// ============================================================================
+
+/// Status codes (`XLstatus`)
+pub mod status {
+    pub const SUCCESS: i16 = 0;
+    pub const ERR_QUEUE_IS_EMPTY: i16 = 10;
+    pub const ERR_QUEUE_IS_FULL: i16 = 11;
+    pub const ERR_NO_LICENSE: i16 = 14;
+    pub const ERR_INVALID_ACCESS: i16 = 112;
+    pub const ERR_CANNOT_OPEN_DRIVER: i16 = 201;
+}
+
+pub const BUS_TYPE_CAN: u32 = 0x0000_0001;
+pub const INTERFACE_VERSION: u32 = 3;
+pub const INTERFACE_VERSION_V4: u32 = 4;
+pub const ACTIVATE_RESET_CLOCK: u32 = 8;
+pub const OUTPUT_MODE_SILENT: i32 = 0;
+pub const OUTPUT_MODE_NORMAL: i32 = 1;
+pub const HWTYPE_VIRTUAL: u8 = 1;
+/// channelBusCapabilities bit: channel can be activated as CAN
+pub const BUS_ACTIVE_CAP_CAN: u32 = BUS_TYPE_CAN << 16;
+/// channelCapabilities bits: ISO / Bosch CAN FD support
+pub const CHANNEL_FLAG_CANFD_ISO_SUPPORT: u32 = 0x8000_0000;
+pub const CHANNEL_FLAG_CANFD_BOSCH_SUPPORT: u32 = 0x2000_0000;
+
+/// Classic API event tags and message flags (`XLevent`, `s_xl_can_msg`)
+pub mod classic {
+    pub const RECEIVE_MSG: u8 = 1;
+    pub const TRANSMIT_MSG: u8 = 10;
+    pub const EXT_MSG_ID: u32 = 0x8000_0000;
+    pub const FLAG_ERROR_FRAME: u16 = 0x01;
+    pub const FLAG_REMOTE_FRAME: u16 = 0x10;
+    pub const FLAG_TX_COMPLETED: u16 = 0x40;
+}
+
+/// CAN FD API event tags and message flags (`XLcanRxEvent`, `XLcanTxEvent`)
+pub mod fd {
+    pub const EV_TAG_RX_OK: u16 = 0x0400;
+    pub const EV_TAG_TX_OK: u16 = 0x0404;
+    pub const EV_TAG_TX_MSG: u16 = 0x0440;
+    pub const RXMSG_FLAG_EDL: u32 = 0x0001;
+    pub const RXMSG_FLAG_BRS: u32 = 0x0002;
+    pub const RXMSG_FLAG_RTR: u32 = 0x0010;
+    pub const RXMSG_FLAG_EF: u32 = 0x0200;
+    pub const TXMSG_FLAG_EDL: u32 = 0x0001;
+    pub const TXMSG_FLAG_BRS: u32 = 0x0002;
+    pub const TXMSG_FLAG_RTR: u32 = 0x0010;
+}
+
+/// Nominal (arbitration) bitrates offered in the profile editor
+pub const BITRATES: &[u32] = &[10_000, 20_000, 50_000, 83_333, 100_000, 125_000, 250_000, 500_000, 800_000, 1_000_000];
+
+/// CAN FD data-phase bitrates offered in the profile editor
+pub const DATA_BITRATES: &[u32] = &[1_000_000, 2_000_000, 4_000_000, 5_000_000, 8_000_000];
+
+/// CAN FD DLC code to payload length
+pub const DLC_LEN: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+/// CAN FD DLC code for a payload length (smallest code that fits).
+pub fn len_to_dlc(len: usize) -> u8 {
+    DLC_LEN.iter().position(|&l| l >= len).unwrap_or(15) as u8
+}
+
+// ============================================================================
+// Transmit Encoding
+// ============================================================================
+
+/// Flags carried in a transmit request (independent of XL API version)
+pub mod tx_flags {
+    pub const EXTENDED: u8 = 0x01;
+    pub const RTR: u8 = 0x02;
+    pub const FD: u8 = 0x04;
+    pub const BRS: u8 = 0x08;
+}
+
+/// A frame queued for one channel of a Vector interface.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VectorTx {
+    /// Channel number on the interface (the device bus)
+    pub channel: u8,
+    pub id: u32,
+    pub flags: u8,
+    pub data: Vec<u8>,
+}
+
+impl VectorTx {
+    /// channel(1) + id(4 LE) + flags(1) + len(1) + data
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(7 + self.data.len());
+        buf.push(self.channel);
+        buf.extend_from_slice(&self.id.to_le_bytes());
+        buf.push(self.flags);
+        buf.push(self.data.len() as u8);
+        buf.extend_from_slice(&self.data);
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 7 {
+            return None;
+        }
+        let len = bytes[6] as usize;
+        Some(VectorTx {
+            channel: bytes[0],
+            id: u32::from_le_bytes(bytes[1..5].try_into().ok()?),
+            flags: bytes[5],
+            data: bytes.get(7..7 + len)?.to_vec(),
+        })
+    }
+
+    pub fn is_fd(&self) -> bool {
+        self.flags & tx_flags::FD != 0
+    }
+}
+
+/// Encode a CAN or CAN FD frame for transmit routing. `frame.bus` is the
+/// device bus (channel on the interface) after the broker reverses the mapping.
+pub fn encode_frame(frame: &CanTransmitFrame) -> Vec<u8> {
+    let mut flags = 0;
+    if frame.is_extended {
+        flags |= tx_flags::EXTENDED;
+    }
+    if frame.is_rtr && !frame.is_fd {
+        flags |= tx_flags::RTR;
+    }
+    if frame.is_fd {
+        flags |= tx_flags::FD;
+        if frame.is_brs {
+            flags |= tx_flags::BRS;
+        }
+    }
+    let max_len = if frame.is_fd { 64 } else { 8 };
+    VectorTx {
+        channel: frame.bus,
+        id: frame.frame_id,
+        flags,
+        data: frame.data.iter().take(max_len).copied().collect(),
+    }
+    .to_bytes()
+}
+
+/// Build a frame message from a received frame. `data` is the payload,
+/// already cut to the length its DLC decodes to.
+pub fn frame_message(channel: u8, id: u32, data: &[u8], is_extended: bool, is_fd: bool, is_tx: bool) -> FrameMessage {
+    FrameMessage {
+        protocol: "can".to_string(),
+        timestamp_us: now_us(),
+        frame_id: id & 0x1FFF_FFFF,
+        bus: channel,
+        dlc: data.len() as u8,
+        bytes: data.to_vec(),
+        is_extended,
+        is_fd,
+        source_address: None,
+        incomplete: None,
+        direction: Some(if is_tx { "tx" } else { "rx" }.to_string()),
+        link: None,
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    }
+}
+
+// ============================================================================
+// Device Info
+// ============================================================================
+
+/// A CAN channel on a Vector interface
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VectorChannelInfo {
+    /// Channel number on the interface (0-based; the device bus)
+    pub hw_channel: u8,
+    /// Global XL channel index
+    pub channel_index: u8,
+    /// Channel name from the driver (e.g. "VN1630A Channel 1")
+    pub name: String,
+    /// Transceiver name (e.g. "On board CAN 1051cap(Highspeed)")
+    pub transceiver: String,
+    pub supports_fd: bool,
+}
+
+/// A Vector interface (one hardware device) with its CAN channels
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VectorDeviceInfo {
+    /// Hardware name (e.g. "VN1630A")
+    pub name: String,
+    /// Serial number (stable across replugging)
+    pub serial: u32,
+    /// XL hardware type and index, as shown in Vector Hardware Config
+    pub hw_type: u8,
+    pub hw_index: u8,
+    pub channels: Vec<VectorChannelInfo>,
+}
+
+/// Hardware name from a channel name: "VN1630A Channel 1" -> "VN1630A".
+pub fn device_name(channel_name: &str) -> String {
+    channel_name
+        .rsplit_once(" Channel ")
+        .map(|(name, _)| name)
+        .unwrap_or(channel_name)
+        .to_string()
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// List Vector interfaces with CAN channels (virtual channels excluded).
+#[tauri::command]
+pub fn list_vector_devices() -> Result<Vec<VectorDeviceInfo>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        xlapi::list_devices()
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Ok(vec![])
+    }
+}
+
+/// Nominal and data-phase bitrates for the profile editor.
+#[tauri::command]
+pub fn get_vector_bitrates() -> (Vec<u32>, Vec<u32>) {
+    (BITRATES.to_vec(), DATA_BITRATES.to_vec())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(is_fd: bool, len: usize) -> CanTransmitFrame {
+        CanTransmitFrame {
+            frame_id: 0x18FF_1234,
+            data: (0..len as u8).collect(),
+            bus: 2,
+            is_extended: true,
+            is_fd,
+            is_brs: is_fd,
+            is_rtr: false,
+        }
+    }
+
+    #[test]
+    fn test_encode_classic_roundtrip() {
+        let tx = VectorTx::from_bytes(&encode_frame(&frame(false, 12))).unwrap();
+        assert_eq!(tx.channel, 2);
+        assert_eq!(tx.id, 0x18FF_1234);
+        assert_eq!(tx.flags, tx_flags::EXTENDED);
+        assert_eq!(tx.data.len(), 8);
+        assert!(!tx.is_fd());
+    }
+
+    #[test]
+    fn test_encode_fd_roundtrip() {
+        let tx = VectorTx::from_bytes(&encode_frame(&frame(true, 48))).unwrap();
+        assert!(tx.is_fd());
+        assert_eq!(tx.flags & tx_flags::BRS, tx_flags::BRS);
+        assert_eq!(tx.data.len(), 48);
+        assert!(VectorTx::from_bytes(&[0; 3]).is_none());
+    }
+
+    #[test]
+    fn test_len_to_dlc() {
+        assert_eq!(len_to_dlc(8), 8);
+        assert_eq!(len_to_dlc(9), 9);
+        assert_eq!(len_to_dlc(48), 14);
+        assert_eq!(len_to_dlc(64), 15);
+    }
+
+    #[test]
+    fn test_device_name() {
+        assert_eq!(device_name("VN1630A Channel 3"), "VN1630A");
+        assert_eq!(device_name("Virtual Channel 1"), "Virtual");
+        assert_eq!(device_name("VN1610"), "VN1610");
+    }
+}
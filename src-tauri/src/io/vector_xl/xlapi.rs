@@ -0,0 +1,714 @@
+// src-tauri/src/io/vector_xl/xlapi.rs
+//
+// Vector reader using the XL Driver Library (Windows).
+//
+// vxlapi64.dll is loaded at runtime with libloading so a missing install only
+// fails the Vector source, not the app. All enabled channels of the interface
+// share one XL port (access mask = OR of their channel masks); received events
+// carry the global channel index, which is mapped back to the channel number
+// on the interface before bus mapping. Like the other polling drivers, the
+// read loop drains the queue until XL_ERR_QUEUE_IS_EMPTY, then sleeps briefly.
+
+use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc as std_mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::{
+    classic, device_name, fd, frame_message, len_to_dlc, status, tx_flags, VectorChannelInfo,
+    VectorDeviceInfo, VectorTx, ACTIVATE_RESET_CLOCK, BUS_ACTIVE_CAP_CAN, BUS_TYPE_CAN,
+    CHANNEL_FLAG_CANFD_BOSCH_SUPPORT, CHANNEL_FLAG_CANFD_ISO_SUPPORT, DLC_LEN, HWTYPE_VIRTUAL,
+    INTERFACE_VERSION, INTERFACE_VERSION_V4, OUTPUT_MODE_NORMAL, OUTPUT_MODE_SILENT,
+};
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::types::{SourceMessage, TransmitRequest};
+
+const LIBRARY_NAME: &str = "vxlapi64.dll";
+
+/// Application name registered with the XL driver (shown in Vector Hardware Config)
+const APP_NAME: &[u8] = b"WireTAP\0";
+
+/// Sleep between polls when the receive queue is empty.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Upper bound on frames drained per batch, so a flooded queue still yields.
+const MAX_BATCH: usize = 512;
+
+/// Events fetched per xlReceive call (classic API)
+const RECEIVE_CHUNK: usize = 64;
+
+/// Receive queue size in events (power of two for the classic API)
+const RX_QUEUE_SIZE_CLASSIC: u32 = 8192;
+/// Receive queue size in bytes for the CAN FD API
+const RX_QUEUE_SIZE_FD: u32 = 524_288;
+
+// ============================================================================
+// XL API Structures
+// ============================================================================
+
+/// `XLchannelConfig` (packed to 1 in vxlapi.h)
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+struct XlChannelConfig {
+    name: [u8; 32],
+    hw_type: u8,
+    hw_index: u8,
+    hw_channel: u8,
+    transceiver_type: u16,
+    transceiver_state: u16,
+    config_error: u16,
+    channel_index: u8,
+    channel_mask: u64,
+    channel_capabilities: u32,
+    channel_bus_capabilities: u32,
+    is_on_bus: u8,
+    connected_bus_type: u32,
+    bus_params: [u8; 32],
+    _do_not_use: u32,
+    driver_version: u32,
+    interface_version: u32,
+    raw_data: [u32; 10],
+    serial_number: u32,
+    article_number: u32,
+    transceiver_name: [u8; 32],
+    special_cab_flags: u32,
+    dominant_timeout: u32,
+    dominant_recessive_delay: u8,
+    recessive_dominant_delay: u8,
+    connection_info: u8,
+    currently_available_timestamps: u8,
+    minimal_supply_voltage: u16,
+    maximal_supply_voltage: u16,
+    maximal_baudrate: u32,
+    fpga_core_capabilities: u8,
+    special_device_status: u8,
+    channel_bus_active_capabilities: u16,
+    break_offset: u16,
+    delimiter_offset: u16,
+    reserved: [u32; 3],
+}
+
+/// `XLdriverConfig` (packed to 1 in vxlapi.h)
+#[repr(C, packed)]
+struct XlDriverConfig {
+    dll_version: u32,
+    channel_count: u32,
+    reserved: [u32; 10],
+    channel: [XlChannelConfig; 64],
+}
+
+/// `s_xl_can_msg`, the classic CAN payload of an `XLevent`
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct XlCanMsg {
+    id: u32,
+    flags: u16,
+    dlc: u16,
+    res1: u64,
+    data: [u8; 8],
+    res2: u64,
+}
+
+/// `XLevent` (classic API, 48 bytes)
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct XlEvent {
+    tag: u8,
+    chan_index: u8,
+    trans_id: u16,
+    port_handle: u16,
+    flags: u8,
+    reserved: u8,
+    time_stamp: u64,
+    msg: XlCanMsg,
+}
+
+/// `XL_CAN_EV_RX_MSG`, the payload of an RX_OK / TX_OK `XLcanRxEvent`
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct XlCanRxMsg {
+    can_id: u32,
+    msg_flags: u32,
+    crc: u32,
+    reserved1: [u8; 12],
+    total_bit_cnt: u16,
+    dlc: u8,
+    reserved: [u8; 5],
+    data: [u8; 64],
+}
+
+/// `XLcanRxEvent` (CAN FD API, 128 bytes)
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct XlCanRxEvent {
+    size: u32,
+    tag: u16,
+    channel_index: u16,
+    user_handle: u32,
+    flags_chip: u16,
+    reserved0: u16,
+    reserved1: u64,
+    time_stamp_sync: u64,
+    msg: XlCanRxMsg,
+}
+
+/// `XLcanTxEvent` (CAN FD API)
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct XlCanTxEvent {
+    tag: u16,
+    trans_id: u16,
+    channel_index: u8,
+    reserved: [u8; 3],
+    can_id: u32,
+    msg_flags: u32,
+    dlc: u8,
+    reserved2: [u8; 7],
+    data: [u8; 64],
+}
+
+/// `XLcanFdConf`
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct XlCanFdConf {
+    arbitration_bit_rate: u32,
+    sjw_abr: u32,
+    tseg1_abr: u32,
+    tseg2_abr: u32,
+    data_bit_rate: u32,
+    sjw_dbr: u32,
+    tseg1_dbr: u32,
+    tseg2_dbr: u32,
+    reserved: u8,
+    options: u8,
+    reserved1: [u8; 2],
+    reserved2: u32,
+}
+
+type OpenDriverFn = unsafe extern "system" fn() -> i16;
+type GetDriverConfigFn = unsafe extern "system" fn(*mut XlDriverConfig) -> i16;
+type GetErrorStringFn = unsafe extern "system" fn(i16) -> *const c_char;
+type OpenPortFn = unsafe extern "system" fn(*mut c_int, *const c_char, u64, *mut u64, u32, u32, u32) -> i16;
+type ClosePortFn = unsafe extern "system" fn(c_int) -> i16;
+type SetChannelBitrateFn = unsafe extern "system" fn(c_int, u64, u32) -> i16;
+type FdSetConfigurationFn = unsafe extern "system" fn(c_int, u64, *mut XlCanFdConf) -> i16;
+type SetChannelOutputFn = unsafe extern "system" fn(c_int, u64, c_int) -> i16;
+type ActivateChannelFn = unsafe extern "system" fn(c_int, u64, u32, u32) -> i16;
+type DeactivateChannelFn = unsafe extern "system" fn(c_int, u64) -> i16;
+type ReceiveFn = unsafe extern "system" fn(c_int, *mut u32, *mut XlEvent) -> i16;
+type CanReceiveFn = unsafe extern "system" fn(c_int, *mut XlCanRxEvent) -> i16;
+type CanTransmitFn = unsafe extern "system" fn(c_int, u64, *mut u32, *mut c_void) -> i16;
+type CanTransmitExFn = unsafe extern "system" fn(c_int, u64, u32, *mut u32, *mut XlCanTxEvent) -> i16;
+
+/// Entry points resolved from the XL Driver Library.
+struct XlApi {
+    // Keeps the library mapped for as long as the function pointers live
+    _lib: libloading::Library,
+    get_driver_config: GetDriverConfigFn,
+    get_error_string: GetErrorStringFn,
+    open_port: OpenPortFn,
+    close_port: ClosePortFn,
+    set_channel_bitrate: SetChannelBitrateFn,
+    fd_set_configuration: FdSetConfigurationFn,
+    set_channel_output: SetChannelOutputFn,
+    activate_channel: ActivateChannelFn,
+    deactivate_channel: DeactivateChannelFn,
+    receive: ReceiveFn,
+    can_receive: CanReceiveFn,
+    can_transmit: CanTransmitFn,
+    can_transmit_ex: CanTransmitExFn,
+}
+
+static XL_API: OnceCell<Result<XlApi, String>> = OnceCell::new();
+
+impl XlApi {
+    /// Load the library and open the driver once per process.
+    fn get() -> Result<&'static XlApi, String> {
+        XL_API
+            .get_or_init(|| unsafe { Self::load() })
+            .as_ref()
+            .map_err(|e| e.clone())
+    }
+
+    unsafe fn load() -> Result<XlApi, String> {
+        let lib = libloading::Library::new(LIBRARY_NAME).map_err(|e| {
+            format!(
+                "Vector XL Driver Library ({}) not found — install the Vector driver setup: {}",
+                LIBRARY_NAME, e
+            )
+        })?;
+        macro_rules! sym {
+            ($name:literal) => {
+                *lib.get($name).map_err(|e| format!("XL Driver Library is missing {}: {}", String::from_utf8_lossy($name), e))?
+            };
+        }
+        let api = XlApi {
+            get_driver_config: sym!(b"xlGetDriverConfig"),
+            get_error_string: sym!(b"xlGetErrorString"),
+            open_port: sym!(b"xlOpenPort"),
+            close_port: sym!(b"xlClosePort"),
+            set_channel_bitrate: sym!(b"xlCanSetChannelBitrate"),
+            fd_set_configuration: sym!(b"xlCanFdSetConfiguration"),
+            set_channel_output: sym!(b"xlCanSetChannelOutput"),
+            activate_channel: sym!(b"xlActivateChannel"),
+            deactivate_channel: sym!(b"xlDeactivateChannel"),
+            receive: sym!(b"xlReceive"),
+            can_receive: sym!(b"xlCanReceive"),
+            can_transmit: sym!(b"xlCanTransmit"),
+            can_transmit_ex: sym!(b"xlCanTransmitEx"),
+            _lib: lib,
+        };
+        let open_driver: OpenDriverFn = *api._lib.get(b"xlOpenDriver").map_err(|e| format!("XL Driver Library is missing xlOpenDriver: {}", e))?;
+        api.check(open_driver()).map_err(|e| format!("Failed to open the Vector XL driver: {}", e))?;
+        Ok(api)
+    }
+
+    fn error_text(&self, code: i16) -> String {
+        let text = unsafe { (self.get_error_string)(code) };
+        if text.is_null() {
+            return format!("XL error {}", code);
+        }
+        unsafe { CStr::from_ptr(text) }.to_string_lossy().into_owned()
+    }
+
+    fn check(&self, code: i16) -> Result<(), String> {
+        if code == status::SUCCESS {
+            Ok(())
+        } else {
+            Err(self.error_text(code))
+        }
+    }
+
+    /// Read the driver's channel table.
+    fn channels(&self) -> Result<Vec<XlChannelConfig>, String> {
+        // ~27 KB; boxed to keep it off the async task's stack
+        let mut config: Box<XlDriverConfig> = Box::new(unsafe { std::mem::zeroed() });
+        self.check(unsafe { (self.get_driver_config)(&mut *config) })?;
+        let count = (config.channel_count as usize).min(64);
+        let channels = config.channel;
+        Ok(channels[..count].to_vec())
+    }
+}
+
+fn c_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+// ============================================================================
+// Device Enumeration
+// ============================================================================
+
+/// List Vector interfaces, grouping CAN channels by hardware type and index.
+pub fn list_devices() -> Result<Vec<VectorDeviceInfo>, String> {
+    let api = XlApi::get()?;
+    let mut devices: Vec<VectorDeviceInfo> = Vec::new();
+    for ch in api.channels()? {
+        let (hw_type, hw_index, bus_caps, caps) =
+            (ch.hw_type, ch.hw_index, ch.channel_bus_capabilities, ch.channel_capabilities);
+        if hw_type == HWTYPE_VIRTUAL || bus_caps & BUS_ACTIVE_CAP_CAN == 0 {
+            continue;
+        }
+        let name = c_string(&ch.name);
+        let channel = VectorChannelInfo {
+            hw_channel: ch.hw_channel,
+            channel_index: ch.channel_index,
+            transceiver: c_string(&ch.transceiver_name),
+            supports_fd: caps & (CHANNEL_FLAG_CANFD_ISO_SUPPORT | CHANNEL_FLAG_CANFD_BOSCH_SUPPORT) != 0,
+            name: name.clone(),
+        };
+        match devices.iter_mut().find(|d| d.hw_type == hw_type && d.hw_index == hw_index) {
+            Some(device) => device.channels.push(channel),
+            None => devices.push(VectorDeviceInfo {
+                name: device_name(&name),
+                serial: ch.serial_number,
+                hw_type,
+                hw_index,
+                channels: vec![channel],
+            }),
+        }
+    }
+    for device in &mut devices {
+        device.channels.sort_by_key(|c| c.hw_channel);
+    }
+    Ok(devices)
+}
+
+/// Resolve the interface for a profile: by serial when one is set (stable
+/// across replugging), otherwise the interface owning the given XL channel.
+fn resolve_device(serial: Option<u32>, channel_index: u8) -> Result<VectorDeviceInfo, String> {
+    let devices = list_devices()?;
+    match serial {
+        Some(serial) => devices
+            .into_iter()
+            .find(|d| d.serial == serial)
+            .ok_or_else(|| format!("No Vector interface with serial {} is connected", serial)),
+        None => devices
+            .into_iter()
+            .find(|d| d.channels.iter().any(|c| c.channel_index == channel_index))
+            .ok_or_else(|| format!("Vector XL channel {} is not connected", channel_index)),
+    }
+}
+
+/// Channel mask for a global channel index (`xlGetChannelMask` equivalent).
+fn channel_mask(channel_index: u8) -> u64 {
+    1u64 << channel_index
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Bus settings for a Vector source.
+pub struct VectorBusConfig {
+    pub bitrate: u32,
+    pub enable_fd: bool,
+    pub data_bitrate: u32,
+    pub listen_only: bool,
+}
+
+/// Configure and activate the channels in `access_mask` on an open port.
+fn setup_port(api: &XlApi, port: c_int, access_mask: u64, permission_mask: u64, config: &VectorBusConfig) -> Result<(), String> {
+    // Bus parameters need init access; without it (another application owns
+    // the channel) we join at whatever rate it was configured with
+    if permission_mask != 0 {
+        if config.enable_fd {
+            let mut conf = XlCanFdConf {
+                arbitration_bit_rate: config.bitrate,
+                sjw_abr: 2,
+                tseg1_abr: 6,
+                tseg2_abr: 3,
+                data_bit_rate: config.data_bitrate,
+                sjw_dbr: 2,
+                tseg1_dbr: 6,
+                tseg2_dbr: 3,
+                ..Default::default()
+            };
+            api.check(unsafe { (api.fd_set_configuration)(port, permission_mask, &mut conf) })
+                .map_err(|e| format!("Failed to configure CAN FD: {}", e))?;
+        } else {
+            api.check(unsafe { (api.set_channel_bitrate)(port, permission_mask, config.bitrate) })
+                .map_err(|e| format!("Failed to set bitrate: {}", e))?;
+        }
+        let mode = if config.listen_only { OUTPUT_MODE_SILENT } else { OUTPUT_MODE_NORMAL };
+        api.check(unsafe { (api.set_channel_output)(port, permission_mask, mode) })
+            .map_err(|e| format!("Failed to set output mode: {}", e))?;
+    }
+    api.check(unsafe { (api.activate_channel)(port, access_mask, BUS_TYPE_CAN, ACTIVATE_RESET_CLOCK) })
+        .map_err(|e| format!("Failed to activate channels: {}", e))
+}
+
+/// Send one frame through the port.
+fn transmit(api: &XlApi, port: c_int, enable_fd: bool, mask: u64, msg: &VectorTx) -> Result<(), String> {
+    let extended = msg.flags & tx_flags::EXTENDED != 0;
+    if enable_fd {
+        let mut flags = 0;
+        if msg.is_fd() {
+            flags |= fd::TXMSG_FLAG_EDL;
+            if msg.flags & tx_flags::BRS != 0 {
+                flags |= fd::TXMSG_FLAG_BRS;
+            }
+        } else if msg.flags & tx_flags::RTR != 0 {
+            flags |= fd::TXMSG_FLAG_RTR;
+        }
+        let mut event = XlCanTxEvent {
+            tag: fd::EV_TAG_TX_MSG,
+            trans_id: 0xFFFF,
+            channel_index: 0,
+            reserved: [0; 3],
+            can_id: msg.id | if extended { classic::EXT_MSG_ID } else { 0 },
+            msg_flags: flags,
+            dlc: len_to_dlc(msg.data.len()),
+            reserved2: [0; 7],
+            data: [0; 64],
+        };
+        event.data[..msg.data.len().min(64)].copy_from_slice(&msg.data[..msg.data.len().min(64)]);
+        let mut sent: u32 = 0;
+        api.check(unsafe { (api.can_transmit_ex)(port, mask, 1, &mut sent, &mut event) })
+    } else {
+        if msg.is_fd() {
+            return Err("Vector source is not in CAN FD mode; enable CAN FD in the profile to send FD frames".to_string());
+        }
+        let mut event = XlEvent {
+            tag: classic::TRANSMIT_MSG,
+            ..Default::default()
+        };
+        event.msg.id = msg.id | if extended { classic::EXT_MSG_ID } else { 0 };
+        event.msg.flags = if msg.flags & tx_flags::RTR != 0 { classic::FLAG_REMOTE_FRAME } else { 0 };
+        event.msg.dlc = msg.data.len().min(8) as u16;
+        event.msg.data[..msg.data.len().min(8)].copy_from_slice(&msg.data[..msg.data.len().min(8)]);
+        let mut count: u32 = 1;
+        api.check(unsafe { (api.can_transmit)(port, mask, &mut count, &mut event as *mut XlEvent as *mut c_void) })
+    }
+}
+
+/// Run a Vector source and feed frames to the merge task.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_source(
+    source_idx: usize,
+    serial: Option<u32>,
+    channel_index: u8,
+    config: VectorBusConfig,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let api = match XlApi::get() {
+        Ok(api) => api,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+            return;
+        }
+    };
+
+    let device = match resolve_device(serial, channel_index) {
+        Ok(d) => d,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+            return;
+        }
+    };
+
+    // Enabled channels: device bus (hwChannel) <-> global channel index
+    let mut bus_for_index: HashMap<u8, u8> = HashMap::new();
+    let mut mask_for_bus: HashMap<u8, u64> = HashMap::new();
+    for mapping in bus_mappings.iter().filter(|m| m.enabled) {
+        match device.channels.iter().find(|c| c.hw_channel == mapping.device_bus) {
+            Some(ch) => {
+                if config.enable_fd && !ch.supports_fd {
+                    let _ = tx
+                        .send(SourceMessage::Error(
+                            source_idx,
+                            format!("{} does not support CAN FD", ch.name),
+                        ))
+                        .await;
+                    return;
+                }
+                bus_for_index.insert(ch.channel_index, ch.hw_channel);
+                mask_for_bus.insert(ch.hw_channel, channel_mask(ch.channel_index));
+            }
+            None => tlog!(
+                "[vector_xl] Source {} skipping bus {}: {} has no such channel",
+                source_idx, mapping.device_bus, device.name
+            ),
+        }
+    }
+    let access_mask = mask_for_bus.values().fold(0u64, |acc, m| acc | m);
+    if access_mask == 0 {
+        let _ = tx
+            .send(SourceMessage::Error(
+                source_idx,
+                format!("No enabled channels to open on {}", device.name),
+            ))
+            .await;
+        return;
+    }
+
+    let (version, queue_size) = if config.enable_fd {
+        (INTERFACE_VERSION_V4, RX_QUEUE_SIZE_FD)
+    } else {
+        (INTERFACE_VERSION, RX_QUEUE_SIZE_CLASSIC)
+    };
+    let mut port: c_int = -1;
+    let mut permission_mask: u64 = access_mask;
+    let rc = unsafe {
+        (api.open_port)(
+            &mut port,
+            APP_NAME.as_ptr() as *const c_char,
+            access_mask,
+            &mut permission_mask,
+            queue_size,
+            version,
+            BUS_TYPE_CAN,
+        )
+    };
+    if let Err(e) = api.check(rc) {
+        let _ = tx
+            .send(SourceMessage::Error(
+                source_idx,
+                format!("Failed to open Vector port: {}", e),
+            ))
+            .await;
+        return;
+    }
+    if permission_mask != access_mask {
+        tlog!(
+            "[vector_xl] Source {} has init access to 0x{:X} of 0x{:X}; other channels keep their bus settings",
+            source_idx, permission_mask, access_mask
+        );
+    }
+
+    if let Err(e) = setup_port(api, port, access_mask, permission_mask, &config) {
+        unsafe { (api.close_port)(port) };
+        let _ = tx.send(SourceMessage::Error(source_idx, e)).await;
+        return;
+    }
+
+    tlog!(
+        "[vector_xl] Source {} connected to {} (serial: {}, channels: {}, bitrate: {}, fd: {}, listen_only: {})",
+        source_idx, device.name, device.serial, mask_for_bus.len(), config.bitrate, config.enable_fd, config.listen_only
+    );
+
+    let _ = tx
+        .send(SourceMessage::Connected(
+            source_idx,
+            "vector_xl".to_string(),
+            format!("{} #{}", device.name, device.serial),
+            None,
+        ))
+        .await;
+
+    // Setup transmit channel if not in listen-only mode
+    let transmit_task = if !config.listen_only {
+        let (transmit_tx, transmit_rx) = std_mpsc::sync_channel::<TransmitRequest>(32);
+        let _ = tx
+            .send(SourceMessage::TransmitReady(source_idx, transmit_tx))
+            .await;
+
+        let stop_flag_for_transmit = stop_flag.clone();
+        let enable_fd = config.enable_fd;
+        let tx_masks = mask_for_bus.clone();
+        Some(tokio::task::spawn_blocking(move || {
+            while !stop_flag_for_transmit.load(Ordering::Relaxed) {
+                match transmit_rx.recv_timeout(Duration::from_millis(10)) {
+                    Ok(req) => {
+                        let result = match VectorTx::from_bytes(&req.data) {
+                            Some(msg) => match tx_masks.get(&msg.channel) {
+                                Some(&mask) => transmit(api, port, enable_fd, mask, &msg)
+                                    .map_err(|e| format!("Write failed: {}", e)),
+                                None => Err(format!("Vector channel {} is not open", msg.channel)),
+                            },
+                            None => Err("Malformed Vector transmit request".to_string()),
+                        };
+                        let _ = req.result_tx.try_send(result);
+                    }
+                    Err(std_mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(std_mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        }))
+    } else {
+        None
+    };
+
+    // Read loop
+    let mut rx_frames: u64 = 0;
+    let mut bus_filtered: u64 = 0;
+
+    'read: while !stop_flag.load(Ordering::Relaxed) {
+        let mut batch = Vec::new();
+        let mut drained = false;
+        while batch.len() < MAX_BATCH && !drained {
+            let mut received = Vec::new();
+            let rc = if config.enable_fd {
+                let mut event: XlCanRxEvent = unsafe { std::mem::zeroed() };
+                let rc = unsafe { (api.can_receive)(port, &mut event) };
+                if rc == status::SUCCESS
+                    && (event.tag == fd::EV_TAG_RX_OK || event.tag == fd::EV_TAG_TX_OK)
+                    && event.msg.msg_flags & fd::RXMSG_FLAG_EF == 0
+                {
+                    let msg = event.msg;
+                    let is_fd = msg.msg_flags & fd::RXMSG_FLAG_EDL != 0;
+                    let len = if is_fd {
+                        DLC_LEN[(msg.dlc as usize).min(15)]
+                    } else if msg.msg_flags & fd::RXMSG_FLAG_RTR != 0 {
+                        0
+                    } else {
+                        (msg.dlc as usize).min(8)
+                    };
+                    received.push((
+                        event.channel_index as u8,
+                        msg.can_id,
+                        msg.data[..len].to_vec(),
+                        is_fd,
+                        event.tag == fd::EV_TAG_TX_OK,
+                    ));
+                }
+                rc
+            } else {
+                let mut events = [XlEvent::default(); RECEIVE_CHUNK];
+                let mut count = RECEIVE_CHUNK as u32;
+                let rc = unsafe { (api.receive)(port, &mut count, events.as_mut_ptr()) };
+                if rc == status::SUCCESS {
+                    for event in events.iter().take(count as usize) {
+                        let msg = event.msg;
+                        if event.tag != classic::RECEIVE_MSG || msg.flags & classic::FLAG_ERROR_FRAME != 0 {
+                            continue;
+                        }
+                        let len = if msg.flags & classic::FLAG_REMOTE_FRAME != 0 {
+                            0
+                        } else {
+                            (msg.dlc as usize).min(8)
+                        };
+                        received.push((
+                            event.chan_index,
+                            msg.id,
+                            msg.data[..len].to_vec(),
+                            false,
+                            msg.flags & classic::FLAG_TX_COMPLETED != 0,
+                        ));
+                    }
+                }
+                rc
+            };
+
+            match rc {
+                status::SUCCESS => {}
+                status::ERR_QUEUE_IS_EMPTY => drained = true,
+                _ => {
+                    let _ = tx
+                        .send(SourceMessage::Error(
+                            source_idx,
+                            format!("Vector read failed: {}", api.error_text(rc)),
+                        ))
+                        .await;
+                    break 'read;
+                }
+            }
+
+            for (index, id, data, is_fd, is_tx) in received {
+                let Some(&bus) = bus_for_index.get(&index) else {
+                    continue;
+                };
+                let is_extended = id & classic::EXT_MSG_ID != 0;
+                let mut frame = frame_message(bus, id, &data, is_extended, is_fd, is_tx);
+                rx_frames += 1;
+                if apply_bus_mapping(&mut frame, &bus_mappings) {
+                    batch.push(frame);
+                } else {
+                    bus_filtered += 1;
+                }
+            }
+        }
+
+        if batch.is_empty() {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        } else {
+            let _ = tx.send(SourceMessage::Frames(source_idx, batch)).await;
+        }
+    }
+
+    tlog!(
+        "[vector_xl] Source {} ended: rx={}, bus_filtered={}",
+        source_idx, rx_frames, bus_filtered
+    );
+
+    // Cleanup
+    if let Some(task) = transmit_task {
+        task.abort();
+    }
+
+    unsafe {
+        (api.deactivate_channel)(port, access_mask);
+        (api.close_port)(port);
+    }
+
+    let _ = tx
+        .send(SourceMessage::Ended(source_idx, "stopped".to_string()))
+        .await;
+}
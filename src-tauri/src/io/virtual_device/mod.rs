@@ -355,6 +355,12 @@ impl IOSource for VirtualSource {
             (VirtualTrafficType::Serial, TransmitPayload::CanFrame(_)) => {
                 Err("Virtual Serial device does not support CAN frame transmission.".to_string())
             }
+            (_, TransmitPayload::ModbusWrite(_)) => {
+                Err("Virtual device does not support Modbus writes.".to_string())
+            }
+            (_, TransmitPayload::Diagnostic(_)) => {
+                Err("Virtual device does not support DoIP diagnostic requests.".to_string())
+            }
         }
     }
 
@@ -515,6 +521,11 @@ fn spawn_bus_generator(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     };
 
                     capture_store::append_frames_to_session(&session_id, vec![frame]);
@@ -546,6 +557,11 @@ fn spawn_bus_generator(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     };
 
                     capture_store::append_frames_to_session(&session_id, vec![frame]);
@@ -571,6 +587,11 @@ fn spawn_bus_generator(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     };
 
                     capture_store::append_frames_to_session(&session_id, vec![frame]);
@@ -635,6 +656,11 @@ fn spawn_loopback_handler(
                         source_address: None,
                         incomplete: None,
                         direction: Some("rx".to_string()),
+                        link: None,
+                        clock: None,
+                        is_error: None,
+                        error_class: None,
+                        brs: None,
                     };
                     capture_store::append_frames_to_session(&session_id, vec![frame]);
                     if throttle.should_signal("frames-ready") {
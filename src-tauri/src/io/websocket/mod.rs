@@ -0,0 +1,487 @@
+// ui/src-tauri/src/io/websocket/mod.rs
+//
+// WebSocket streaming source. Connects to a ws:// or wss:// endpoint (a
+// browser-based gateway, a cloud logger's live feed) and turns the messages
+// it pushes into frames. An optional subscribe message is sent once the
+// connection is up, for servers that wait to be asked.
+//
+// Messages are mapped onto frame fields, like the CSV column mapping:
+//
+// - JSON: each mapping names a key path (`id`, `frame.data`, `msgs.0.id`).
+//   A message may hold one frame object or an array of them, optionally
+//   under `frames_path`. Without a mapping the SavvyCAN keys are used (`id`,
+//   `data`, `bus`, `dlc`, `extended`, `fd`, `timestamp`), so a WireTAP MQTT
+//   publisher's output relayed over WebSocket reads as-is. String IDs are
+//   read as hex; data may be a byte array or a hex string.
+// - Binary: each mapping gives a byte offset and width within a record. A
+//   message is one record, or several of `record_size` bytes each.
+//
+// A mask on a mapping picks bits out of the value, so an extended flag that
+// shares a word with the ID (bit 31 in SocketCAN's layout) can be mapped
+// twice. Frames without a mapped timestamp get the receive time.
+//
+// Bus numbers in the messages map onto session buses through the profile's
+// interfaces, as with CANserver. Receive only.
+
+pub mod reader;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::io::{now_us, FrameMessage, TimestampUnit};
+
+// Internal items used by multi_source
+pub(crate) use reader::run_source as run_websocket_source;
+pub use reader::probe_websocket;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// How messages are encoded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WsMessageFormat {
+    #[default]
+    Json,
+    Binary,
+}
+
+/// Frame field a mapping fills
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WsFieldRole {
+    FrameId,
+    Data,
+    Bus,
+    /// Number of data bytes
+    Dlc,
+    Extended,
+    Fd,
+    Timestamp,
+}
+
+/// Where one frame field comes from
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WsFieldMapping {
+    pub role: WsFieldRole,
+    /// JSON: key path, dot separated
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Binary: byte offset within the record
+    #[serde(default)]
+    pub offset: Option<usize>,
+    /// Binary: width in bytes (data runs to the end of the record if unset)
+    #[serde(default)]
+    pub length: Option<usize>,
+    /// Bits of the value to keep (flags: any kept bit set means true)
+    #[serde(default)]
+    pub mask: Option<u64>,
+}
+
+impl WsFieldMapping {
+    fn json(role: WsFieldRole, path: &str) -> Self {
+        Self { role, path: Some(path.to_string()), offset: None, length: None, mask: None }
+    }
+}
+
+/// WebSocket source settings, parsed from a profile's connection
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebSocketConfig {
+    pub url: String,
+    pub format: WsMessageFormat,
+    pub mapping: Vec<WsFieldMapping>,
+    /// JSON: key path of the frame array within a message
+    pub frames_path: Option<String>,
+    /// Binary: size of each record when a message packs several
+    pub record_size: Option<usize>,
+    /// Binary: multi-byte fields are big-endian
+    pub big_endian: bool,
+    /// Unit of mapped timestamps (default µs, as the MQTT publisher writes them)
+    pub timestamp_unit: TimestampUnit,
+    /// Text message sent after connecting
+    pub subscribe: Option<String>,
+    /// Silence after which the source reports that no data is arriving
+    pub timeout_sec: f64,
+}
+
+/// SavvyCAN keys, used when a JSON profile has no mapping
+fn default_json_mapping() -> Vec<WsFieldMapping> {
+    vec![
+        WsFieldMapping::json(WsFieldRole::FrameId, "id"),
+        WsFieldMapping::json(WsFieldRole::Data, "data"),
+        WsFieldMapping::json(WsFieldRole::Bus, "bus"),
+        WsFieldMapping::json(WsFieldRole::Dlc, "dlc"),
+        WsFieldMapping::json(WsFieldRole::Extended, "extended"),
+        WsFieldMapping::json(WsFieldRole::Fd, "fd"),
+        WsFieldMapping::json(WsFieldRole::Timestamp, "timestamp"),
+    ]
+}
+
+fn number(connection: &Value, key: &str) -> Option<f64> {
+    connection
+        .get(key)
+        .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+}
+
+fn text(connection: &Value, key: &str) -> Option<String> {
+    connection
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+}
+
+impl WebSocketConfig {
+    /// Read the settings from a profile's connection map.
+    pub fn from_connection(connection: &Value) -> Result<Self, String> {
+        let url = text(connection, "url").ok_or("URL is required")?;
+        if !(url.starts_with("ws://") || url.starts_with("wss://")) {
+            return Err(format!("'{}' is not a ws:// or wss:// URL", url));
+        }
+
+        let format: WsMessageFormat = match connection.get("format") {
+            Some(v) if !v.is_null() => {
+                serde_json::from_value(v.clone()).map_err(|_| format!("Unknown message format {}", v))?
+            }
+            _ => WsMessageFormat::Json,
+        };
+
+        let mut mapping: Vec<WsFieldMapping> = match connection.get("mapping") {
+            Some(v) if !v.is_null() => {
+                serde_json::from_value(v.clone()).map_err(|e| format!("Invalid field mapping: {}", e))?
+            }
+            _ => Vec::new(),
+        };
+        mapping.retain(|m| match format {
+            WsMessageFormat::Json => m.path.as_deref().is_some_and(|p| !p.trim().is_empty()),
+            WsMessageFormat::Binary => m.offset.is_some(),
+        });
+        if mapping.is_empty() && format == WsMessageFormat::Json {
+            mapping = default_json_mapping();
+        }
+        for role in [WsFieldRole::FrameId, WsFieldRole::Data] {
+            if !mapping.iter().any(|m| m.role == role) {
+                return Err(format!("The field mapping needs a {:?} field", role));
+            }
+        }
+
+        let timestamp_unit = match connection.get("timestamp_unit") {
+            Some(v) if !v.is_null() => {
+                serde_json::from_value(v.clone()).map_err(|_| format!("Unknown timestamp unit {}", v))?
+            }
+            _ => TimestampUnit::Microseconds,
+        };
+
+        Ok(WebSocketConfig {
+            url,
+            format,
+            mapping,
+            frames_path: text(connection, "frames_path"),
+            record_size: number(connection, "record_size").map(|v| v as usize).filter(|&v| v > 0),
+            big_endian: connection.get("big_endian").and_then(|v| v.as_bool()).unwrap_or(false),
+            timestamp_unit,
+            subscribe: text(connection, "subscribe"),
+            timeout_sec: number(connection, "timeout").unwrap_or(5.0).max(0.5),
+        })
+    }
+}
+
+// ============================================================================
+// Message Parsing
+// ============================================================================
+
+/// Frame fields as read from one message or record
+#[derive(Default)]
+struct Fields {
+    frame_id: Option<u32>,
+    data: Option<Vec<u8>>,
+    bus: Option<u8>,
+    dlc: Option<usize>,
+    extended: Option<bool>,
+    fd: Option<bool>,
+    timestamp: Option<f64>,
+}
+
+impl Fields {
+    fn into_frame(self, unit: TimestampUnit) -> Option<FrameMessage> {
+        let frame_id = self.frame_id?;
+        let mut bytes = self.data?;
+        if let Some(dlc) = self.dlc {
+            bytes.truncate(dlc);
+        }
+        bytes.truncate(64);
+        let is_fd = self.fd.unwrap_or(bytes.len() > 8);
+        if !is_fd {
+            bytes.truncate(8);
+        }
+        let is_extended = self.extended.unwrap_or(frame_id > 0x7FF);
+        Some(FrameMessage {
+            protocol: "can".to_string(),
+            timestamp_us: self.timestamp.and_then(|t| timestamp_us(t, unit)).unwrap_or_else(now_us),
+            frame_id: if is_extended { frame_id & 0x1FFF_FFFF } else { frame_id & 0x7FF },
+            bus: self.bus.unwrap_or(0),
+            dlc: bytes.len() as u8,
+            bytes,
+            is_extended,
+            is_fd,
+            source_address: None,
+            incomplete: None,
+            direction: Some("rx".to_string()),
+            link: None,
+            clock: None,
+            is_error: None,
+            error_class: None,
+            brs: None,
+        })
+    }
+}
+
+fn timestamp_us(value: f64, unit: TimestampUnit) -> Option<u64> {
+    let us = match unit {
+        TimestampUnit::Seconds => value * 1e6,
+        TimestampUnit::Milliseconds => value * 1e3,
+        TimestampUnit::Microseconds => value,
+        TimestampUnit::Nanoseconds => value / 1e3,
+    };
+    (us.is_finite() && us >= 0.0).then_some(us as u64)
+}
+
+fn masked(value: u64, mask: Option<u64>) -> u64 {
+    mask.map_or(value, |m| value & m)
+}
+
+/// Follow a dotted key path; numeric segments index arrays.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |v, key| match v {
+        Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => v.get(key),
+    })
+}
+
+fn json_integer(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64().or_else(|| n.as_f64().filter(|f| *f >= 0.0).map(|f| f as u64)),
+        Value::String(s) => s.trim().parse().ok(),
+        Value::Bool(b) => Some(*b as u64),
+        _ => None,
+    }
+}
+
+/// IDs in strings are hex, with or without 0x
+fn json_frame_id(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(s) => {
+            let s = s.trim();
+            let hex = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+            u64::from_str_radix(hex, 16).ok()
+        }
+        other => json_integer(other),
+    }
+}
+
+fn json_data(value: &Value) -> Option<Vec<u8>> {
+    match value {
+        Value::Array(items) => items.iter().map(|b| json_integer(b).map(|b| b as u8)).collect(),
+        Value::String(s) => {
+            let hex: String = s.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+            if hex.len() % 2 != 0 {
+                return None;
+            }
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+                .collect()
+        }
+        _ => None,
+    }
+}
+
+fn json_frame(config: &WebSocketConfig, item: &Value) -> Option<FrameMessage> {
+    let mut fields = Fields::default();
+    for m in &config.mapping {
+        let Some(value) = m.path.as_deref().and_then(|p| lookup(item, p)) else {
+            continue;
+        };
+        match m.role {
+            WsFieldRole::FrameId => fields.frame_id = json_frame_id(value).map(|v| masked(v, m.mask) as u32),
+            WsFieldRole::Data => fields.data = json_data(value),
+            WsFieldRole::Bus => fields.bus = json_integer(value).map(|v| masked(v, m.mask) as u8),
+            WsFieldRole::Dlc => fields.dlc = json_integer(value).map(|v| masked(v, m.mask) as usize),
+            WsFieldRole::Extended => fields.extended = json_integer(value).map(|v| masked(v, m.mask) != 0),
+            WsFieldRole::Fd => fields.fd = json_integer(value).map(|v| masked(v, m.mask) != 0),
+            WsFieldRole::Timestamp => {
+                fields.timestamp = match value {
+                    Value::String(s) => s.trim().parse().ok(),
+                    other => other.as_f64(),
+                }
+            }
+        }
+    }
+    fields.into_frame(config.timestamp_unit)
+}
+
+/// Frames in a JSON message. Items that don't map to a frame are skipped.
+pub fn parse_json_message(config: &WebSocketConfig, text: &str) -> Result<Vec<FrameMessage>, String> {
+    let message: Value = serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let body = match &config.frames_path {
+        Some(path) => lookup(&message, path).ok_or_else(|| format!("No '{}' in message", path))?,
+        None => &message,
+    };
+    Ok(match body {
+        Value::Array(items) => items.iter().filter_map(|item| json_frame(config, item)).collect(),
+        item => json_frame(config, item).into_iter().collect(),
+    })
+}
+
+fn binary_integer(bytes: &[u8], big_endian: bool) -> u64 {
+    let fold = |acc: u64, b: &u8| (acc << 8) | *b as u64;
+    if big_endian {
+        bytes.iter().fold(0, fold)
+    } else {
+        bytes.iter().rev().fold(0, fold)
+    }
+}
+
+fn binary_frame(config: &WebSocketConfig, record: &[u8]) -> Option<FrameMessage> {
+    let mut fields = Fields::default();
+    for m in &config.mapping {
+        let Some(offset) = m.offset.filter(|&o| o < record.len()) else {
+            continue;
+        };
+        if m.role == WsFieldRole::Data {
+            let end = m.length.map_or(record.len(), |l| (offset + l).min(record.len()));
+            fields.data = Some(record[offset..end].to_vec());
+            continue;
+        }
+        let width = m.length.unwrap_or(1).clamp(1, 8);
+        let Some(bytes) = record.get(offset..offset + width) else {
+            continue;
+        };
+        let value = masked(binary_integer(bytes, config.big_endian), m.mask);
+        match m.role {
+            WsFieldRole::FrameId => fields.frame_id = Some(value as u32),
+            WsFieldRole::Bus => fields.bus = Some(value as u8),
+            WsFieldRole::Dlc => fields.dlc = Some(value as usize),
+            WsFieldRole::Extended => fields.extended = Some(value != 0),
+            WsFieldRole::Fd => fields.fd = Some(value != 0),
+            WsFieldRole::Timestamp => fields.timestamp = Some(value as f64),
+            WsFieldRole::Data => unreachable!(),
+        }
+    }
+    fields.into_frame(config.timestamp_unit)
+}
+
+/// Frames in a binary message (a trailing partial record is ignored).
+pub fn parse_binary_message(config: &WebSocketConfig, message: &[u8]) -> Vec<FrameMessage> {
+    match config.record_size {
+        Some(size) => message
+            .chunks_exact(size)
+            .filter_map(|record| binary_frame(config, record))
+            .collect(),
+        None => binary_frame(config, message).into_iter().collect(),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_messages_with_default_and_custom_mapping() {
+        let config = WebSocketConfig::from_connection(&json!({"url": "ws://gateway.local/can"})).unwrap();
+        let frames = parse_json_message(
+            &config,
+            r#"[{"id": 291, "data": [1, 2, 3], "bus": 1, "timestamp": 1500},
+                {"id": "18DAF110", "data": "AA BB", "extended": true},
+                {"note": "not a frame"}]"#,
+        )
+        .unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!((frames[0].frame_id, frames[0].bus, frames[0].timestamp_us), (0x123, 1, 1_500));
+        assert_eq!(frames[0].bytes, vec![1, 2, 3]);
+        assert!(!frames[0].is_extended);
+        assert_eq!(frames[1].frame_id, 0x18DA_F110);
+        assert_eq!(frames[1].bytes, vec![0xAA, 0xBB]);
+        assert!(frames[1].is_extended);
+
+        let config = WebSocketConfig::from_connection(&json!({
+            "url": "wss://logger.example.com/live",
+            "frames_path": "payload.frames",
+            "timestamp_unit": "seconds",
+            "mapping": [
+                {"role": "frame_id", "path": "arb"},
+                {"role": "data", "path": "raw"},
+                {"role": "timestamp", "path": "t"}
+            ]
+        }))
+        .unwrap();
+        let frames = parse_json_message(
+            &config,
+            r#"{"payload": {"frames": [{"arb": "0x7E8", "raw": "0441", "t": 2.5}]}}"#,
+        )
+        .unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!((frames[0].frame_id, frames[0].timestamp_us), (0x7E8, 2_500_000));
+        assert_eq!(frames[0].bytes, vec![0x04, 0x41]);
+    }
+
+    #[test]
+    fn test_binary_records_with_masked_flags() {
+        // SocketCAN-like records: ID word with EFF flag, length byte, 8 data bytes
+        let config = WebSocketConfig::from_connection(&json!({
+            "url": "ws://10.0.0.2:8080",
+            "format": "binary",
+            "record_size": 13,
+            "mapping": [
+                {"role": "frame_id", "offset": 0, "length": 4, "mask": 0x1FFF_FFFFu32},
+                {"role": "extended", "offset": 0, "length": 4, "mask": 0x8000_0000u32},
+                {"role": "dlc", "offset": 4},
+                {"role": "data", "offset": 5, "length": 8}
+            ]
+        }))
+        .unwrap();
+
+        let mut message = Vec::new();
+        message.extend_from_slice(&(0x8000_0000u32 | 0x18FE_F100).to_le_bytes());
+        message.extend_from_slice(&[3, 0xDE, 0xAD, 0xBE, 0, 0, 0, 0, 0]);
+        message.extend_from_slice(&0x100u32.to_le_bytes());
+        message.extend_from_slice(&[2, 9, 8, 0, 0, 0, 0, 0, 0]);
+        message.extend_from_slice(&[0; 4]); // partial record
+
+        let frames = parse_binary_message(&config, &message);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].frame_id, 0x18FE_F100);
+        assert!(frames[0].is_extended);
+        assert_eq!(frames[0].bytes, vec![0xDE, 0xAD, 0xBE]);
+        assert_eq!(frames[1].frame_id, 0x100);
+        assert!(!frames[1].is_extended);
+        assert_eq!(frames[1].bytes, vec![9, 8]);
+    }
+
+    #[test]
+    fn test_config_from_connection() {
+        assert!(WebSocketConfig::from_connection(&json!({})).is_err());
+        assert!(WebSocketConfig::from_connection(&json!({"url": "http://example.com"})).is_err());
+        // Binary messages have no default layout
+        assert!(WebSocketConfig::from_connection(&json!({"url": "ws://x", "format": "binary"})).is_err());
+        let err = WebSocketConfig::from_connection(&json!({
+            "url": "ws://x",
+            "mapping": [{"role": "frame_id", "path": "id"}]
+        }))
+        .unwrap_err();
+        assert!(err.contains("Data"));
+
+        let config = WebSocketConfig::from_connection(&json!({"url": " ws://x ", "subscribe": "{\"op\":\"sub\"}"})).unwrap();
+        assert_eq!(config.url, "ws://x");
+        assert_eq!(config.format, WsMessageFormat::Json);
+        assert_eq!(config.subscribe.as_deref(), Some("{\"op\":\"sub\"}"));
+        assert_eq!(config.timeout_sec, 5.0);
+    }
+}
@@ -0,0 +1,217 @@
+// ui/src-tauri/src/io/websocket/reader.rs
+//
+// WebSocket client for the streaming source. tokio-tungstenite handles the
+// handshake (TLS for wss://) and answers the server's pings while the read
+// loop polls the stream.
+
+use std::collections::BTreeSet;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+use super::{parse_binary_message, parse_json_message, WebSocketConfig, WsMessageFormat};
+use crate::io::error::IoError;
+use crate::io::gvret::{apply_bus_mapping, BusMapping};
+use crate::io::types::SourceMessage;
+use crate::io::FrameMessage;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Time allowed for the TCP, TLS and WebSocket handshakes
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a probe listens once the first frames have arrived
+const PROBE_LISTEN: Duration = Duration::from_millis(1000);
+
+async fn connect(config: &WebSocketConfig) -> Result<Socket, IoError> {
+    let device = format!("websocket({})", config.url);
+    let (mut socket, _response) = tokio::time::timeout(CONNECT_TIMEOUT, connect_async(config.url.as_str()))
+        .await
+        .map_err(|_| IoError::timeout(&device, "WebSocket handshake"))?
+        .map_err(|e| IoError::connection(&device, e.to_string()))?;
+    if let Some(subscribe) = &config.subscribe {
+        socket
+            .send(Message::Text(subscribe.clone().into()))
+            .await
+            .map_err(|e| IoError::connection(&device, e.to_string()))?;
+    }
+    Ok(socket)
+}
+
+/// Frames in one message. Text messages are read as JSON, and so are
+/// binary ones when the profile expects JSON (some servers send JSON as
+/// binary); text messages to a binary profile are ignored.
+fn parse_message(config: &WebSocketConfig, message: &Message) -> Result<Vec<FrameMessage>, String> {
+    match (message, config.format) {
+        (Message::Text(text), WsMessageFormat::Json) => parse_json_message(config, text.as_str()),
+        (Message::Binary(bytes), WsMessageFormat::Json) => {
+            let text = std::str::from_utf8(bytes).map_err(|_| "Binary message is not UTF-8 JSON".to_string())?;
+            parse_json_message(config, text)
+        }
+        (Message::Binary(bytes), WsMessageFormat::Binary) => Ok(parse_binary_message(config, bytes)),
+        _ => Ok(Vec::new()),
+    }
+}
+
+// ============================================================================
+// Endpoint Probing
+// ============================================================================
+
+/// Connect and listen briefly; returns the buses seen, in order.
+pub async fn probe_websocket(config: &WebSocketConfig) -> Result<Vec<u8>, IoError> {
+    let device = format!("websocket({})", config.url);
+    let mut socket = connect(config).await?;
+
+    let mut buses = BTreeSet::new();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs_f64(config.timeout_sec);
+    let mut listen_until: Option<tokio::time::Instant> = None;
+
+    loop {
+        let until = listen_until.unwrap_or(deadline);
+        let remaining = until.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        match tokio::time::timeout(remaining, socket.next()).await {
+            Ok(Some(Ok(message))) => {
+                let frames = parse_message(config, &message).unwrap_or_default();
+                if !frames.is_empty() {
+                    buses.extend(frames.iter().map(|f| f.bus));
+                    listen_until.get_or_insert_with(|| tokio::time::Instant::now() + PROBE_LISTEN);
+                }
+            }
+            Ok(Some(Err(e))) => return Err(IoError::read(&device, e.to_string())),
+            Ok(None) => break,
+            Err(_) => break,
+        }
+    }
+    let _ = socket.close(None).await;
+
+    if listen_until.is_none() {
+        return Err(IoError::timeout(&device, "frames"));
+    }
+    tlog!("[websocket] Probe of {} saw buses {:?}", config.url, buses);
+    Ok(buses.into_iter().collect())
+}
+
+// ============================================================================
+// Multi-Source Streaming
+// ============================================================================
+
+/// Run a WebSocket source and send frames to the merge task
+pub async fn run_source(
+    source_idx: usize,
+    config: WebSocketConfig,
+    bus_mappings: Vec<BusMapping>,
+    stop_flag: Arc<AtomicBool>,
+    tx: mpsc::Sender<SourceMessage>,
+) {
+    let mut socket = match connect(&config).await {
+        Ok(s) => s,
+        Err(e) => {
+            let _ = tx.send(SourceMessage::Error(source_idx, e.to_string())).await;
+            return;
+        }
+    };
+
+    tlog!("[websocket] Source {} streaming from {}", source_idx, config.url);
+    let _ = tx
+        .send(SourceMessage::Connected(source_idx, "websocket".to_string(), config.url.clone(), None))
+        .await;
+
+    let silence_limit = Duration::from_secs_f64(config.timeout_sec);
+    let mut last_data = Instant::now();
+    let mut silent = false;
+    let mut rx_frames: u64 = 0;
+    let mut bus_filtered: u64 = 0;
+    let mut parse_errors: u64 = 0;
+
+    while !stop_flag.load(Ordering::SeqCst) {
+        match tokio::time::timeout(Duration::from_millis(50), socket.next()).await {
+            Ok(Some(Ok(Message::Close(frame)))) => {
+                let reason = frame.map(|f| f.reason.to_string()).filter(|r| !r.is_empty());
+                let _ = tx
+                    .send(SourceMessage::Error(
+                        source_idx,
+                        format!("Server closed the connection{}", reason.map(|r| format!(": {}", r)).unwrap_or_default()),
+                    ))
+                    .await;
+                return;
+            }
+            Ok(Some(Ok(message))) => {
+                let frames = match parse_message(&config, &message) {
+                    Ok(frames) => frames,
+                    Err(e) => {
+                        // Log the first few, then every thousandth
+                        parse_errors += 1;
+                        if parse_errors <= 3 || parse_errors % 1000 == 0 {
+                            tlog!("[websocket] Source {}: unreadable message ({} so far): {}", source_idx, parse_errors, e);
+                        }
+                        continue;
+                    }
+                };
+                if frames.is_empty() {
+                    continue;
+                }
+                last_data = Instant::now();
+                if silent {
+                    tlog!("[websocket] Source {}: data resumed from {}", source_idx, config.url);
+                    silent = false;
+                }
+                let total = frames.len();
+                rx_frames += total as u64;
+                let mapped: Vec<FrameMessage> = frames
+                    .into_iter()
+                    .filter_map(|mut frame| apply_bus_mapping(&mut frame, &bus_mappings).then_some(frame))
+                    .collect();
+                bus_filtered += (total - mapped.len()) as u64;
+                if !mapped.is_empty() {
+                    let _ = tx.send(SourceMessage::Frames(source_idx, mapped)).await;
+                }
+            }
+            Ok(Some(Err(e))) => {
+                let _ = tx
+                    .send(SourceMessage::Error(source_idx, format!("Read error: {}", e)))
+                    .await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx
+                    .send(SourceMessage::Error(source_idx, "Connection closed".to_string()))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                // Timeout - continue
+            }
+        }
+
+        // A quiet feed isn't an error; note the silence once
+        if !silent && last_data.elapsed() >= silence_limit {
+            tlog!(
+                "[websocket] Source {}: no frames from {} for {:.1}s",
+                source_idx, config.url, silence_limit.as_secs_f64()
+            );
+            silent = true;
+        }
+    }
+
+    let _ = socket.close(None).await;
+
+    tlog!(
+        "[websocket] Source {} ended: rx={}, bus_filtered={}, parse_errors={}",
+        source_idx, rx_frames, bus_filtered, parse_errors
+    );
+
+    let _ = tx
+        .send(SourceMessage::Ended(source_idx, "stopped".to_string()))
+        .await;
+}
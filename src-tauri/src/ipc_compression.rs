@@ -0,0 +1,236 @@
+// ui/src-tauri/src/ipc_compression.rs
+//
+// Compression for large IPC responses. A page of a few thousand frames
+// serialises to megabytes of JSON; packed commands return the JSON as a raw
+// binary body instead, LZ4-compressed once it passes THRESHOLD_BYTES, and the
+// frontend inflates it before parsing (src/utils/lz4.ts). Body layout:
+//
+//   byte 0     codec tag (CODEC_NONE, CODEC_LZ4, CODEC_FRAMES)
+//   bytes 1..  JSON; for LZ4, a u32 LE uncompressed length then one LZ4 block
+//
+// Pages of frames can instead be sent as CODEC_FRAMES, a fixed binary layout
+// the frontend reads with a DataView (src/utils/frameCodec.ts), skipping JSON
+// on both sides. All integers little-endian:
+//
+//   u8 version, u32 total_count, u32 offset, u32 limit, u32 frame count,
+//   u8 protocol count, then per protocol [u8 len][UTF-8 name]
+//   per frame: u64 timestamp_us, u64 capture_index, u32 frame_id, u8 bus,
+//     u8 dlc, u8 flags (FRAME_FLAG_*), u8 protocol table index,
+//     u16 source_address, u16 payload length, [link], payload
+//   link (FRAME_FLAG_LINK): u64 correlation_id, u8 role (LinkRole order),
+//     i64 parent_rowid (FRAME_FLAG_PARENT; else 0)
+//
+// LZ4 rather than zstd: its block decoder is a few dozen lines of TypeScript
+// with no npm dependency, and frame JSON (repeated keys, small integers)
+// shrinks 4-6x, which is most of what zstd would save.
+
+use serde::{Deserialize, Serialize};
+
+use crate::io::{FrameMessage, LinkRole};
+
+/// JSON bodies smaller than this go uncompressed.
+pub const THRESHOLD_BYTES: usize = 64 * 1024;
+
+pub const CODEC_NONE: u8 = 0;
+pub const CODEC_LZ4: u8 = 1;
+pub const CODEC_FRAMES: u8 = 2;
+
+/// Version byte of the CODEC_FRAMES layout.
+pub const FRAME_PAGE_VERSION: u8 = 1;
+
+pub const FRAME_FLAG_EXTENDED: u8 = 1 << 0;
+pub const FRAME_FLAG_FD: u8 = 1 << 1;
+pub const FRAME_FLAG_INCOMPLETE: u8 = 1 << 2;
+pub const FRAME_FLAG_SOURCE_ADDRESS: u8 = 1 << 3;
+pub const FRAME_FLAG_RX: u8 = 1 << 4;
+pub const FRAME_FLAG_TX: u8 = 1 << 5;
+pub const FRAME_FLAG_LINK: u8 = 1 << 6;
+pub const FRAME_FLAG_PARENT: u8 = 1 << 7;
+
+/// How a command returns a page of frames.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameEncoding {
+    /// Packed JSON (LZ4 when large)
+    #[default]
+    Json,
+    /// CODEC_FRAMES binary layout
+    Binary,
+}
+
+/// Serialise `value` and pack it, compressing when it's worth it.
+pub fn pack_json<T: Serialize>(value: &T) -> Result<Vec<u8>, String> {
+    let json = serde_json::to_vec(value).map_err(|e| format!("Failed to serialise response: {}", e))?;
+    Ok(pack(&json, THRESHOLD_BYTES))
+}
+
+/// Pack a body: tagged LZ4 when at least `threshold` bytes and the block is
+/// smaller, tagged raw otherwise.
+pub fn pack(body: &[u8], threshold: usize) -> Vec<u8> {
+    if body.len() >= threshold {
+        let block = lz4_flex::block::compress_prepend_size(body);
+        if block.len() < body.len() {
+            let mut out = Vec::with_capacity(block.len() + 1);
+            out.push(CODEC_LZ4);
+            out.extend_from_slice(&block);
+            return out;
+        }
+    }
+    let mut out = Vec::with_capacity(body.len() + 1);
+    out.push(CODEC_NONE);
+    out.extend_from_slice(body);
+    out
+}
+
+fn link_role_code(role: LinkRole) -> u8 {
+    match role {
+        LinkRole::Segment => 0,
+        LinkRole::Message => 1,
+        LinkRole::Request => 2,
+        LinkRole::Response => 3,
+        LinkRole::Echo => 4,
+    }
+}
+
+/// Encode a page of frames in the CODEC_FRAMES layout. `capture_indices`
+/// runs parallel to `frames`.
+pub fn pack_frame_page(
+    frames: &[FrameMessage],
+    capture_indices: &[usize],
+    total_count: usize,
+    offset: usize,
+    limit: usize,
+) -> Vec<u8> {
+    let mut protocols: Vec<&str> = Vec::new();
+    for f in frames {
+        if !protocols.contains(&f.protocol.as_str()) {
+            protocols.push(&f.protocol);
+        }
+    }
+
+    let mut out = Vec::with_capacity(18 + frames.iter().map(|f| 45 + f.bytes.len()).sum::<usize>());
+    out.push(CODEC_FRAMES);
+    out.push(FRAME_PAGE_VERSION);
+    for n in [total_count, offset, limit, frames.len()] {
+        out.extend_from_slice(&(n.min(u32::MAX as usize) as u32).to_le_bytes());
+    }
+    out.push(protocols.len() as u8);
+    for p in &protocols {
+        let name = &p.as_bytes()[..p.len().min(255)];
+        out.push(name.len() as u8);
+        out.extend_from_slice(name);
+    }
+
+    for (i, f) in frames.iter().enumerate() {
+        let mut flags = 0;
+        if f.is_extended { flags |= FRAME_FLAG_EXTENDED; }
+        if f.is_fd { flags |= FRAME_FLAG_FD; }
+        if f.incomplete == Some(true) { flags |= FRAME_FLAG_INCOMPLETE; }
+        if f.source_address.is_some() { flags |= FRAME_FLAG_SOURCE_ADDRESS; }
+        match f.direction.as_deref() {
+            Some("rx") => flags |= FRAME_FLAG_RX,
+            Some("tx") => flags |= FRAME_FLAG_TX,
+            _ => {}
+        }
+        if let Some(link) = &f.link {
+            flags |= FRAME_FLAG_LINK;
+            if link.parent_rowid.is_some() { flags |= FRAME_FLAG_PARENT; }
+        }
+
+        out.extend_from_slice(&f.timestamp_us.to_le_bytes());
+        out.extend_from_slice(&(capture_indices.get(i).copied().unwrap_or(0) as u64).to_le_bytes());
+        out.extend_from_slice(&f.frame_id.to_le_bytes());
+        out.push(f.bus);
+        out.push(f.dlc);
+        out.push(flags);
+        out.push(protocols.iter().position(|p| *p == f.protocol).unwrap_or(0) as u8);
+        out.extend_from_slice(&f.source_address.unwrap_or(0).to_le_bytes());
+        out.extend_from_slice(&(f.bytes.len() as u16).to_le_bytes());
+        if let Some(link) = &f.link {
+            out.extend_from_slice(&link.correlation_id.to_le_bytes());
+            out.push(link_role_code(link.role));
+            out.extend_from_slice(&link.parent_rowid.unwrap_or(0).to_le_bytes());
+        }
+        out.extend_from_slice(&f.bytes);
+    }
+    out
+}
+
+/// Inverse of `pack` (the frontend has its own; this one serves tests and
+/// Rust-side consumers).
+pub fn unpack(packed: &[u8]) -> Result<Vec<u8>, String> {
+    match packed.split_first() {
+        Some((&CODEC_NONE, body)) => Ok(body.to_vec()),
+        Some((&CODEC_LZ4, block)) => lz4_flex::block::decompress_size_prepended(block)
+            .map_err(|e| format!("Failed to decompress response: {}", e)),
+        Some((&CODEC_FRAMES, _)) => Err("Binary frame pages aren't JSON".to_string()),
+        Some((tag, _)) => Err(format!("Unknown response codec {}", tag)),
+        None => Err("Empty response body".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    #[test]
+    fn small_bodies_stay_raw() {
+        let packed = pack(b"{\"frames\":[]}", THRESHOLD_BYTES);
+        assert_eq!(packed[0], CODEC_NONE);
+        assert_eq!(&packed[1..], b"{\"frames\":[]}");
+    }
+
+    #[test]
+    fn large_bodies_round_trip_through_lz4() {
+        let body = "{\"frame_id\":291,\"bus\":0,\"bytes\":[1,2,3,4,5,6,7,8]},".repeat(5000);
+        let packed = pack(body.as_bytes(), THRESHOLD_BYTES);
+        assert_eq!(packed[0], CODEC_LZ4);
+        assert!(packed.len() < body.len() / 4);
+        assert_eq!(unpack(&packed).unwrap(), body.as_bytes());
+    }
+
+    #[test]
+    fn frame_pages_use_the_fixed_binary_layout() {
+        use crate::io::FrameLink;
+        let frame = FrameMessage {
+            bus: 2,
+            direction: Some("rx".to_string()),
+            link: Some(FrameLink { correlation_id: 9, role: LinkRole::Response, parent_rowid: Some(41) }),
+            ..test_frame(0x7E8, 1_000_000, &[0x03, 0x7F, 0x22])
+        };
+        let page = pack_frame_page(&[frame], &[42], 100, 10, 50);
+
+        assert_eq!(&page[..2], &[CODEC_FRAMES, FRAME_PAGE_VERSION]);
+        assert_eq!(&page[2..18], &[100, 0, 0, 0, 10, 0, 0, 0, 50, 0, 0, 0, 1, 0, 0, 0]);
+        assert_eq!(&page[18..22], &[1, 3, b'c', b'a']);
+        let rec = &page[23..];
+        assert_eq!(u64::from_le_bytes(rec[0..8].try_into().unwrap()), 1_000_000);
+        assert_eq!(u64::from_le_bytes(rec[8..16].try_into().unwrap()), 42);
+        assert_eq!(u32::from_le_bytes(rec[16..20].try_into().unwrap()), 0x7E8);
+        assert_eq!(&rec[20..24], &[2, 3, FRAME_FLAG_RX | FRAME_FLAG_LINK | FRAME_FLAG_PARENT, 0]);
+        assert_eq!(&rec[26..28], &[3, 0]);
+        assert_eq!(rec[36], 3);
+        assert_eq!(i64::from_le_bytes(rec[37..45].try_into().unwrap()), 41);
+        assert_eq!(&rec[45..], &[0x03, 0x7F, 0x22]);
+        assert!(unpack(&page).is_err());
+    }
+
+    #[test]
+    fn incompressible_bodies_are_sent_raw() {
+        // xorshift noise doesn't compress; the block would only add overhead
+        let mut x: u32 = 0x9E37_79B9;
+        let noise: Vec<u8> = (0..4096)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                x as u8
+            })
+            .collect();
+        let packed = pack(&noise, 1024);
+        assert_eq!(packed[0], CODEC_NONE);
+        assert_eq!(unpack(&packed).unwrap(), noise);
+        assert!(unpack(&[7, 1, 2]).is_err());
+    }
+}
@@ -0,0 +1,747 @@
+// ui/src-tauri/src/j1939.rs
+//
+// J1939 addressing and SPN decoding in the frame pipeline.
+//
+// A 29-bit J1939 ID packs priority, PGN and source address; PDU1 PGNs
+// (PF < 240) carry the destination address in the PS byte instead of part of
+// the PGN. With J1939 enabled on a session, extended frames passing through
+// capture_store::append_frames_to_session get `source_address` filled in
+// before capture, and after reassembly (so TP-transported PGNs are included)
+// every frame whose PGN carries a configured SPN is decoded into the
+// latest-value cache, like catalogue signals.
+//
+// SPNs are written the way SAE J1939-71 lists them — PGN, start position
+// ("4-5" for bytes 4 to 5, "1.3" for byte 1 bit 3), length, resolution and
+// offset — so nobody has to convert to little-endian start bits. They come
+// from a `[j1939]` table in the attached catalogue (read here; the catalogue
+// crate ignores it), from set_j1939_config, and optionally from a built-in
+// table of common engine and vehicle SPNs.
+//
+// Address Claimed messages (PGN 60928) are tracked on every session into a
+// network map — NAME → claimed address, Cannot Claim senders and arbitration
+// conflicts — with changes pushed as AddressClaimChanged WS messages.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+
+use crate::io::FrameMessage;
+use crate::latest_values::LatestValue;
+
+// ============================================================================
+// Identifier
+// ============================================================================
+
+/// Fields of a 29-bit J1939 identifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct J1939Id {
+    pub priority: u8,
+    /// Parameter group number (PS zeroed for PDU1 groups)
+    pub pgn: u32,
+    pub source_address: u8,
+    /// Destination for PDU1 groups (None = PDU2 broadcast)
+    pub destination_address: Option<u8>,
+}
+
+impl J1939Id {
+    pub fn parse(frame_id: u32) -> Self {
+        let pf = (frame_id >> 16) & 0xFF;
+        let ps = ((frame_id >> 8) & 0xFF) as u8;
+        let pdu1 = pf < 240;
+        let pgn = (frame_id >> 8) & 0x3_FFFF;
+        Self {
+            priority: ((frame_id >> 26) & 0x7) as u8,
+            pgn: if pdu1 { pgn & 0x3_FF00 } else { pgn },
+            source_address: frame_id as u8,
+            destination_address: pdu1.then_some(ps),
+        }
+    }
+
+    pub fn to_frame_id(self) -> u32 {
+        let ps = self.destination_address.map_or(0, u32::from);
+        (u32::from(self.priority) << 26) | ((self.pgn | ps) << 8) | u32::from(self.source_address)
+    }
+}
+
+// ============================================================================
+// SPNs
+// ============================================================================
+
+/// One SPN as listed in J1939-71.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpnDef {
+    pub pgn: u32,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Start position: "4" or "4-5" (whole bytes, 1-based) or "1.3" (byte.bit,
+    /// bit 1 = least significant)
+    pub position: String,
+    /// Length in bits (default: the bytes `position` spans)
+    #[serde(default)]
+    pub length: Option<u8>,
+    #[serde(default = "default_resolution")]
+    pub resolution: f64,
+    #[serde(default)]
+    pub offset: f64,
+    #[serde(default)]
+    pub unit: Option<String>,
+}
+
+fn default_resolution() -> f64 {
+    1.0
+}
+
+/// An SPN resolved to a little-endian bit range over the PGN's data.
+#[derive(Clone, Debug, PartialEq)]
+struct ResolvedSpn {
+    spn: u32,
+    pgn: u32,
+    name: String,
+    start_bit: usize,
+    bit_length: usize,
+    resolution: f64,
+    offset: f64,
+    unit: Option<String>,
+}
+
+impl ResolvedSpn {
+    fn new(spn: u32, def: &SpnDef) -> Result<Self, String> {
+        let bad = || format!("SPN {}: invalid position '{}'", spn, def.position);
+        let parse = |s: &str| s.trim().parse::<usize>().ok().filter(|&n| n >= 1);
+        let (start_bit, span_bits) = match def.position.split_once(['.', '-']) {
+            Some((byte, bit)) if def.position.contains('.') => {
+                let (byte, bit) = (parse(byte).ok_or_else(bad)?, parse(bit).filter(|&b| b <= 8).ok_or_else(bad)?);
+                ((byte - 1) * 8 + bit - 1, 9 - bit)
+            }
+            Some((first, last)) => {
+                let (first, last) = (parse(first).ok_or_else(bad)?, parse(last).ok_or_else(bad)?);
+                if last < first {
+                    return Err(bad());
+                }
+                ((first - 1) * 8, (last - first + 1) * 8)
+            }
+            None => ((parse(&def.position).ok_or_else(bad)? - 1) * 8, 8),
+        };
+        let bit_length = def.length.map_or(span_bits, usize::from);
+        if !(1..=32).contains(&bit_length) {
+            return Err(format!("SPN {}: length must be 1-32 bits", spn));
+        }
+        Ok(Self {
+            spn,
+            pgn: def.pgn,
+            name: def.name.clone().unwrap_or_else(|| format!("SPN {}", spn)),
+            start_bit,
+            bit_length,
+            resolution: def.resolution,
+            offset: def.offset,
+            unit: def.unit.clone(),
+        })
+    }
+
+    /// Scaled value, or None if the data is short or the parameter reports
+    /// "error" / "not available" (J1939-71 reserves the top of each range).
+    fn decode(&self, data: &[u8]) -> Option<(u64, f64)> {
+        let end = self.start_bit + self.bit_length;
+        if end > data.len() * 8 {
+            return None;
+        }
+        let raw = (self.start_bit..end).rev().fold(0u64, |acc, bit| {
+            (acc << 1) | u64::from((data[bit / 8] >> (bit % 8)) & 1)
+        });
+        let max_valid = match self.bit_length {
+            8 => 0xFA,
+            16 => 0xFAFF,
+            32 => 0xFAFF_FFFF,
+            n => (1u64 << n) - 2,
+        };
+        (raw <= max_valid).then(|| (raw, raw as f64 * self.resolution + self.offset))
+    }
+}
+
+/// Common engine and vehicle SPNs: (spn, pgn, name, position, resolution, offset, unit)
+const BUILTIN_SPNS: &[(u32, u32, &str, &str, f64, f64, &str)] = &[
+    (190, 61444, "Engine Speed", "4-5", 0.125, 0.0, "rpm"),
+    (513, 61444, "Actual Engine Percent Torque", "3", 1.0, -125.0, "%"),
+    (91, 61443, "Accelerator Pedal Position 1", "2", 0.4, 0.0, "%"),
+    (92, 61443, "Engine Percent Load At Current Speed", "3", 1.0, 0.0, "%"),
+    (84, 65265, "Wheel-Based Vehicle Speed", "2-3", 1.0 / 256.0, 0.0, "km/h"),
+    (110, 65262, "Engine Coolant Temperature", "1", 1.0, -40.0, "°C"),
+    (175, 65262, "Engine Oil Temperature 1", "3-4", 0.03125, -273.0, "°C"),
+    (100, 65263, "Engine Oil Pressure", "4", 4.0, 0.0, "kPa"),
+    (102, 65270, "Engine Intake Manifold 1 Pressure", "2", 2.0, 0.0, "kPa"),
+    (105, 65270, "Engine Intake Manifold 1 Temperature", "3", 1.0, -40.0, "°C"),
+    (171, 65269, "Ambient Air Temperature", "4-5", 0.03125, -273.0, "°C"),
+    (96, 65276, "Fuel Level 1", "2", 0.4, 0.0, "%"),
+    (183, 65266, "Engine Fuel Rate", "1-2", 0.05, 0.0, "L/h"),
+    (168, 65271, "Battery Potential / Power Input 1", "7-8", 0.05, 0.0, "V"),
+    (247, 65253, "Engine Total Hours of Operation", "1-4", 0.05, 0.0, "h"),
+    (245, 65248, "Total Vehicle Distance", "5-8", 0.125, 0.0, "km"),
+];
+
+fn builtin_spns() -> Vec<ResolvedSpn> {
+    BUILTIN_SPNS
+        .iter()
+        .filter_map(|&(spn, pgn, name, position, resolution, offset, unit)| {
+            let def = SpnDef {
+                pgn,
+                name: Some(name.to_string()),
+                position: position.to_string(),
+                length: None,
+                resolution,
+                offset,
+                unit: Some(unit.to_string()),
+            };
+            ResolvedSpn::new(spn, &def).ok()
+        })
+        .collect()
+}
+
+// ============================================================================
+// Per-session configuration and pipeline hooks
+// ============================================================================
+
+/// J1939 handling for a session; also the shape of a catalogue's `[j1939]` table.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct J1939Config {
+    /// Fill `source_address` on extended frames (default true)
+    #[serde(default = "default_source_addresses")]
+    pub source_addresses: bool,
+    /// Also decode the built-in table of common SPNs
+    #[serde(default)]
+    pub builtin_spns: bool,
+    /// SPN definitions keyed by SPN number (`[j1939.spn.190]`)
+    #[serde(default)]
+    pub spn: BTreeMap<String, SpnDef>,
+}
+
+fn default_source_addresses() -> bool {
+    true
+}
+
+struct SessionJ1939 {
+    config: J1939Config,
+    /// Set from the attached catalogue (replaced or dropped with it)
+    from_catalog: bool,
+    /// Resolved SPNs by PGN
+    spns: HashMap<u32, Vec<ResolvedSpn>>,
+}
+
+impl SessionJ1939 {
+    fn new(config: J1939Config, from_catalog: bool) -> Result<Self, String> {
+        let mut resolved = if config.builtin_spns { builtin_spns() } else { Vec::new() };
+        for (key, def) in &config.spn {
+            let spn = key.parse::<u32>().map_err(|_| format!("SPN key '{}' is not a number", key))?;
+            // A user definition replaces a built-in one with the same number
+            resolved.retain(|r| r.spn != spn);
+            resolved.push(ResolvedSpn::new(spn, def)?);
+        }
+        let mut spns: HashMap<u32, Vec<ResolvedSpn>> = HashMap::new();
+        for r in resolved {
+            spns.entry(r.pgn).or_default().push(r);
+        }
+        Ok(Self { config, from_catalog, spns })
+    }
+}
+
+static SESSION_J1939: Lazy<RwLock<HashMap<String, SessionJ1939>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+fn is_j1939_frame(frame: &FrameMessage) -> bool {
+    frame.is_extended && (frame.protocol == "can" || frame.protocol == "j1939")
+}
+
+/// Fill in source addresses on extended CAN frames. Called from
+/// capture_store::append_frames_to_session before frames are captured.
+pub fn tag_frames(session_id: &str, frames: &mut [FrameMessage]) {
+    let Ok(sessions) = SESSION_J1939.read() else {
+        return;
+    };
+    if !sessions.get(session_id).is_some_and(|s| s.config.source_addresses) {
+        return;
+    }
+    for frame in frames.iter_mut().filter(|f| is_j1939_frame(f) && f.source_address.is_none()) {
+        frame.source_address = Some(u16::from(J1939Id::parse(frame.frame_id).source_address));
+    }
+}
+
+/// Track address claims and decode configured SPNs into the latest-value
+/// cache. Called from capture_store::append_frames_to_session after reassembly.
+pub fn tap_frames(session_id: &str, frames: &[FrameMessage]) {
+    track_claims(session_id, frames);
+    let mut samples = Vec::new();
+    {
+        let Ok(sessions) = SESSION_J1939.read() else {
+            return;
+        };
+        let Some(session) = sessions.get(session_id).filter(|s| !s.spns.is_empty()) else {
+            return;
+        };
+        for frame in frames.iter().filter(|f| is_j1939_frame(f)) {
+            let Some(spns) = session.spns.get(&J1939Id::parse(frame.frame_id).pgn) else {
+                continue;
+            };
+            for spn in spns {
+                let Some((raw, value)) = spn.decode(&frame.bytes) else {
+                    continue;
+                };
+                let rounded = (value * 1000.0).round() / 1000.0;
+                samples.push(LatestValue {
+                    frame_id: frame.frame_id,
+                    bus: frame.bus,
+                    name: spn.name.clone(),
+                    mux_value: None,
+                    raw: raw as f64,
+                    value,
+                    display: match &spn.unit {
+                        Some(unit) => format!("{} {}", rounded, unit),
+                        None => rounded.to_string(),
+                    },
+                    unit: spn.unit.clone(),
+                    timestamp_us: frame.timestamp_us,
+                    updates: 0,
+                });
+            }
+        }
+    }
+    crate::latest_values::record_values(session_id, samples);
+}
+
+/// Pick up the `[j1939]` table of a catalogue being attached to a session.
+/// A catalogue without one drops any J1939 config an earlier catalogue set;
+/// config from set_j1939_config is left alone.
+pub fn attach_catalog(session_id: &str, content: &str) -> Result<(), String> {
+    let table = content
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|mut t| t.remove("j1939"));
+    let Ok(mut sessions) = SESSION_J1939.write() else {
+        return Ok(());
+    };
+    match table {
+        Some(value) => {
+            let config: J1939Config = value
+                .try_into()
+                .map_err(|e| format!("Invalid [j1939] table: {}", e))?;
+            let session = SessionJ1939::new(config, true)?;
+            tlog!(
+                "[j1939:{}] Catalogue config: {} SPN PGNs, source addresses {}",
+                session_id, session.spns.len(), session.config.source_addresses
+            );
+            sessions.insert(session_id.to_string(), session);
+        }
+        None => {
+            if sessions.get(session_id).is_some_and(|s| s.from_catalog) {
+                sessions.remove(session_id);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Drop catalogue-derived config when the session's catalogue is detached.
+pub fn detach_catalog(session_id: &str) {
+    if let Ok(mut sessions) = SESSION_J1939.write() {
+        if sessions.get(session_id).is_some_and(|s| s.from_catalog) {
+            sessions.remove(session_id);
+        }
+    }
+}
+
+/// Remove a session's J1939 state. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut sessions) = SESSION_J1939.write() {
+        sessions.remove(session_id);
+    }
+    if let Ok(mut claims) = SESSION_CLAIMS.write() {
+        claims.remove(session_id);
+    }
+}
+
+// ============================================================================
+// Address claim monitoring (SAE J1939-81)
+// ============================================================================
+
+/// PGN of Address Claimed / Cannot Claim Address.
+const PGN_ADDRESS_CLAIMED: u32 = 60928;
+/// Source address of a Cannot Claim Address message.
+const NULL_ADDRESS: u8 = 0xFE;
+/// Conflicts kept per session (oldest dropped first).
+const MAX_CONFLICTS: usize = 100;
+
+/// Fields of a 64-bit J1939 NAME.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct J1939Name {
+    pub identity_number: u32,
+    pub manufacturer_code: u16,
+    pub ecu_instance: u8,
+    pub function_instance: u8,
+    pub function: u8,
+    pub vehicle_system: u8,
+    pub vehicle_system_instance: u8,
+    pub industry_group: u8,
+    pub arbitrary_address_capable: bool,
+}
+
+impl J1939Name {
+    pub fn parse(name: u64) -> Self {
+        let bits = |shift: u32, width: u32| (name >> shift) & ((1u64 << width) - 1);
+        Self {
+            identity_number: bits(0, 21) as u32,
+            manufacturer_code: bits(21, 11) as u16,
+            ecu_instance: bits(32, 3) as u8,
+            function_instance: bits(35, 5) as u8,
+            function: bits(40, 8) as u8,
+            vehicle_system: bits(49, 7) as u8,
+            vehicle_system_instance: bits(56, 4) as u8,
+            industry_group: bits(60, 3) as u8,
+            arbitrary_address_capable: bits(63, 1) == 1,
+        }
+    }
+}
+
+/// A controller application on the network map.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ClaimedAddress {
+    pub address: u8,
+    /// NAME as 16 hex digits (lower value wins arbitration)
+    pub name: String,
+    pub fields: J1939Name,
+    pub first_seen_us: u64,
+    pub last_claim_us: u64,
+    /// Address claims seen from this NAME at this address
+    pub claims: u64,
+}
+
+/// Two NAMEs claiming the same address.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AddressConflict {
+    pub address: u8,
+    /// NAME that keeps the address (the lower one)
+    pub winner: String,
+    pub loser: String,
+    pub timestamp_us: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct J1939NetworkMap {
+    /// Current claims, by address
+    pub addresses: Vec<ClaimedAddress>,
+    /// NAMEs that sent Cannot Claim Address (address 254)
+    pub cannot_claim: Vec<ClaimedAddress>,
+    pub conflicts: Vec<AddressConflict>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressClaimChange {
+    /// A NAME claimed a free address
+    Claimed,
+    /// A NAME claimed a new address (`previous_address` released)
+    Moved,
+    /// A NAME claimed an address already held by another; `displaced` lost it
+    /// if the newcomer won, otherwise the newcomer is `name` and lost
+    Conflict,
+    /// A NAME gave up (Cannot Claim Address)
+    CannotClaim,
+}
+
+/// Pushed as an AddressClaimChanged WS message (JSON array).
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AddressClaimEvent {
+    pub change: AddressClaimChange,
+    pub address: u8,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_address: Option<u8>,
+    /// Other NAME involved in a conflict
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub other_name: Option<String>,
+    /// Whether `name` holds the address after the event
+    pub holds_address: bool,
+    pub timestamp_us: u64,
+}
+
+impl J1939NetworkMap {
+    /// Apply one Address Claimed message, returning the change it made (None
+    /// for a repeat claim).
+    fn claim(&mut self, address: u8, name: u64, timestamp_us: u64) -> Option<AddressClaimEvent> {
+        let name_hex = format!("{:016X}", name);
+        let entry = |address| ClaimedAddress {
+            address,
+            name: name_hex.clone(),
+            fields: J1939Name::parse(name),
+            first_seen_us: timestamp_us,
+            last_claim_us: timestamp_us,
+            claims: 1,
+        };
+        let event = |change, holds_address, previous_address, other_name| AddressClaimEvent {
+            change,
+            address,
+            name: name_hex.clone(),
+            previous_address,
+            other_name,
+            holds_address,
+            timestamp_us,
+        };
+
+        if let Some(current) = self.addresses.iter_mut().find(|a| a.address == address && a.name == name_hex) {
+            current.last_claim_us = timestamp_us;
+            current.claims += 1;
+            return None;
+        }
+
+        // The NAME gives up any address it held before
+        let previous = self.addresses.iter().position(|a| a.name == name_hex).map(|i| self.addresses.remove(i));
+        let previous_address = previous.map(|p| p.address);
+
+        if address == NULL_ADDRESS {
+            let seen = self.cannot_claim.iter().any(|c| c.name == name_hex);
+            if !seen {
+                self.cannot_claim.push(entry(address));
+            }
+            return (!seen || previous.is_some())
+                .then(|| event(AddressClaimChange::CannotClaim, false, previous_address, None));
+        }
+        self.cannot_claim.retain(|c| c.name != name_hex);
+
+        let holder = self.addresses.iter().position(|a| a.address == address);
+        let Some(holder) = holder else {
+            self.insert(entry(address));
+            let change = if previous.is_some() { AddressClaimChange::Moved } else { AddressClaimChange::Claimed };
+            return Some(event(change, true, previous_address, None));
+        };
+
+        // Arbitration: the lower NAME keeps the address
+        let holder_name = self.addresses[holder].name.clone();
+        let holder_value = u64::from_str_radix(&holder_name, 16).unwrap_or(u64::MAX);
+        let won = name < holder_value;
+        let (winner, loser) = if won { (name_hex.clone(), holder_name.clone()) } else { (holder_name.clone(), name_hex.clone()) };
+        if self.conflicts.len() >= MAX_CONFLICTS {
+            self.conflicts.remove(0);
+        }
+        self.conflicts.push(AddressConflict { address, winner, loser, timestamp_us });
+        if won {
+            self.addresses.remove(holder);
+            self.insert(entry(address));
+        }
+        Some(event(AddressClaimChange::Conflict, won, previous_address, Some(holder_name)))
+    }
+
+    fn insert(&mut self, claimed: ClaimedAddress) {
+        let at = self.addresses.partition_point(|a| a.address < claimed.address);
+        self.addresses.insert(at, claimed);
+    }
+}
+
+static SESSION_CLAIMS: Lazy<RwLock<HashMap<String, J1939NetworkMap>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Track Address Claimed messages on every session (no config needed) and
+/// push the changes as an AddressClaimChanged message.
+fn track_claims(session_id: &str, frames: &[FrameMessage]) {
+    let mut claims = frames.iter().filter_map(|f| {
+        let id = J1939Id::parse(f.frame_id);
+        let name: [u8; 8] = f.bytes.get(..8)?.try_into().ok()?;
+        (is_j1939_frame(f) && id.pgn == PGN_ADDRESS_CLAIMED)
+            .then(|| (id.source_address, u64::from_le_bytes(name), f.timestamp_us))
+    })
+    .peekable();
+    if claims.peek().is_none() {
+        return;
+    }
+    let events: Vec<AddressClaimEvent> = {
+        let Ok(mut sessions) = SESSION_CLAIMS.write() else {
+            return;
+        };
+        let map = sessions.entry(session_id.to_string()).or_default();
+        claims.filter_map(|(address, name, ts)| map.claim(address, name, ts)).collect()
+    };
+    if events.is_empty() {
+        return;
+    }
+    for e in &events {
+        tlog!("[j1939:{}] {:?} 0x{:02X} by {}", session_id, e.change, e.address, e.name);
+    }
+    crate::ws::dispatch::send_address_claims_changed(session_id, &events);
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Enable (or reconfigure) J1939 handling on a session. Replaces config from
+/// an attached catalogue until the next attach.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_j1939_config(session_id: String, config: J1939Config) -> Result<(), String> {
+    let session = SessionJ1939::new(config, false)?;
+    tlog!(
+        "[j1939:{}] {} SPN PGNs, source addresses {}",
+        session_id, session.spns.len(), session.config.source_addresses
+    );
+    SESSION_J1939
+        .write()
+        .map_err(|e| format!("J1939 lock poisoned: {e}"))?
+        .insert(session_id, session);
+    Ok(())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_j1939_config(session_id: String) {
+    if let Ok(mut sessions) = SESSION_J1939.write() {
+        sessions.remove(&session_id);
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_j1939_config(session_id: String) -> Option<J1939Config> {
+    SESSION_J1939
+        .read()
+        .ok()
+        .and_then(|s| s.get(&session_id).map(|j| j.config.clone()))
+}
+
+/// Current address claims, Cannot Claim senders and recent conflicts.
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_j1939_network_map(session_id: String) -> J1939NetworkMap {
+    SESSION_CLAIMS
+        .read()
+        .ok()
+        .and_then(|s| s.get(&session_id).cloned())
+        .unwrap_or_default()
+}
+
+/// Forget a session's claims (e.g. before sending a Request for Address Claim).
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_j1939_network_map(session_id: String) {
+    if let Ok(mut claims) = SESSION_CLAIMS.write() {
+        claims.remove(&session_id);
+    }
+}
+
+/// Split a 29-bit ID into priority, PGN, source and destination address.
+#[tauri::command(rename_all = "snake_case")]
+pub fn parse_j1939_id(frame_id: u32) -> J1939Id {
+    J1939Id::parse(frame_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    #[test]
+    fn parses_pdu1_and_pdu2_ids() {
+        // EEC1 (PGN 61444, PDU2) from the engine at priority 3
+        let eec1 = J1939Id::parse(0x0CF0_0400);
+        assert_eq!(
+            eec1,
+            J1939Id { priority: 3, pgn: 61444, source_address: 0x00, destination_address: None }
+        );
+        // Request (PGN 59904, PDU1) from 0xF9 to 0x00
+        let request = J1939Id::parse(0x18EA_00F9);
+        assert_eq!((request.pgn, request.destination_address, request.source_address), (59904, Some(0x00), 0xF9));
+        assert_eq!(request.to_frame_id(), 0x18EA_00F9);
+        assert_eq!(eec1.to_frame_id(), 0x0CF0_0400);
+    }
+
+    #[test]
+    fn decodes_spn_positions() {
+        let spns = builtin_spns();
+        let engine_speed = spns.iter().find(|s| s.spn == 190).unwrap();
+        assert_eq!((engine_speed.start_bit, engine_speed.bit_length), (24, 16));
+        // 0x3E80 × 0.125 = 2000 rpm
+        let eec1 = [0xF0, 0x7D, 0x7D, 0x80, 0x3E, 0x00, 0xF0, 0x7D];
+        assert_eq!(engine_speed.decode(&eec1), Some((0x3E80, 2000.0)));
+        // Not available
+        assert_eq!(engine_speed.decode(&[0xFF; 8]), None);
+
+        // Bit field: byte 1 bits 3-4
+        let def = SpnDef {
+            pgn: 65265,
+            name: None,
+            position: "1.3".to_string(),
+            length: Some(2),
+            resolution: 1.0,
+            offset: 0.0,
+            unit: None,
+        };
+        let switch = ResolvedSpn::new(70, &def).unwrap();
+        assert_eq!((switch.start_bit, switch.name.as_str()), (2, "SPN 70"));
+        assert_eq!(switch.decode(&[0b0000_0100]), Some((1, 1.0)));
+        assert!(ResolvedSpn::new(70, &SpnDef { position: "5-4".to_string(), ..def }).is_err());
+    }
+
+    #[test]
+    fn reads_catalog_table() {
+        let toml = r#"
+            [meta]
+            name = "Truck"
+
+            [j1939]
+            builtin_spns = true
+
+            [j1939.spn.190]
+            pgn = 61444
+            name = "RPM"
+            position = "4-5"
+            resolution = 0.125
+            unit = "rpm"
+        "#;
+        attach_catalog("j1939-test", toml).unwrap();
+        {
+            let sessions = SESSION_J1939.read().unwrap();
+            let session = sessions.get("j1939-test").unwrap();
+            assert!(session.config.source_addresses && session.from_catalog);
+            let eec1 = &session.spns[&61444];
+            // The catalogue's SPN 190 replaces the built-in one
+            assert_eq!(eec1.iter().filter(|s| s.spn == 190).count(), 1);
+            assert!(eec1.iter().any(|s| s.name == "RPM"));
+        }
+
+        let mut frames = vec![FrameMessage { is_extended: true, ..test_frame(0x0CF0_0417, 0, &[0; 8]) }];
+        tag_frames("j1939-test", &mut frames);
+        assert_eq!(frames[0].source_address, Some(0x17));
+
+        // A catalogue without [j1939] drops the catalogue's config
+        attach_catalog("j1939-test", "[meta]\nname = \"Car\"\n").unwrap();
+        assert!(SESSION_J1939.read().unwrap().get("j1939-test").is_none());
+    }
+
+    #[test]
+    fn tracks_address_claims() {
+        // Engine #1: industry group 0, function 0 (engine), manufacturer 0x145
+        let engine: u64 = (0x145 << 21) | 0x1234 | (1 << 63);
+        let fields = J1939Name::parse(engine);
+        assert_eq!((fields.manufacturer_code, fields.identity_number, fields.function), (0x145, 0x1234, 0));
+        assert!(fields.arbitrary_address_capable);
+
+        let mut map = J1939NetworkMap::default();
+        let claimed = map.claim(0x00, engine, 10).unwrap();
+        assert_eq!((claimed.change, claimed.holds_address), (AddressClaimChange::Claimed, true));
+        // Repeat claims only bump the counters
+        assert!(map.claim(0x00, engine, 20).is_none());
+        assert_eq!(map.addresses[0].claims, 2);
+
+        // A lower NAME wins address 0x00; the engine moves to 0x01
+        let lower: u64 = 0x42;
+        let conflict = map.claim(0x00, lower, 30).unwrap();
+        assert_eq!(conflict.change, AddressClaimChange::Conflict);
+        assert!(conflict.holds_address);
+        assert_eq!(map.conflicts[0].winner, format!("{:016X}", lower));
+        let moved = map.claim(0x01, engine, 40).unwrap();
+        assert_eq!((moved.change, moved.previous_address), (AddressClaimChange::Claimed, None));
+        assert_eq!(map.addresses.iter().map(|a| a.address).collect::<Vec<_>>(), vec![0x00, 0x01]);
+
+        // A higher NAME loses, then gives up
+        let higher: u64 = u64::MAX - 1;
+        assert!(!map.claim(0x01, higher, 50).unwrap().holds_address);
+        let gave_up = map.claim(NULL_ADDRESS, higher, 60).unwrap();
+        assert_eq!(gave_up.change, AddressClaimChange::CannotClaim);
+        assert_eq!((map.addresses.len(), map.cannot_claim.len(), map.conflicts.len()), (2, 1, 2));
+
+        // The engine moving from 0x01 to 0x03
+        let moved = map.claim(0x03, engine, 70).unwrap();
+        assert_eq!((moved.change, moved.previous_address), (AddressClaimChange::Moved, Some(0x01)));
+    }
+}
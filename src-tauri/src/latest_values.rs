@@ -0,0 +1,350 @@
+// ui/src-tauri/src/latest_values.rs
+//
+// Latest-value cache — the most recent decoded value of every signal on a
+// session, so gauge-style dashboards can read values instead of processing
+// the frame (or DecodedSignals) stream themselves.
+//
+// Frames are decoded against the session's attached catalogue in
+// capture_store::append_frames_to_session, after gating and reassembly, so
+// the cache sees exactly what the DecodedSignals stream sees (synthetic
+// reassembled messages included). Sessions without a catalogue cost one read
+// lock per batch. Values decoded outside the catalogue (OBD-II PID polling)
+// come in through record_values. The decoded values also feed signal_trends
+// when the session records any.
+//
+// Optional change thresholds turn the cache into an event source: a signal is
+// reported once its scaled value has moved at least `delta` from the value
+// last reported (its first value is always reported), no more often than
+// `min_interval_ms` of frame time. Reports for a batch are pushed as one
+// LatestValuesChanged message on the session's WS channel.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::io::FrameMessage;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// The most recent value of one signal.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LatestValue {
+    pub frame_id: u32,
+    pub bus: u8,
+    pub name: String,
+    /// Selector value of the mux case the signal came from, if any
+    pub mux_value: Option<i64>,
+    /// Raw (pre-scale) value
+    pub raw: f64,
+    /// Scaled value (raw × factor + offset)
+    pub value: f64,
+    /// Display string (enum label, hex, formatted number, ...)
+    pub display: String,
+    pub unit: Option<String>,
+    /// Timestamp of the frame that carried the value (µs)
+    pub timestamp_us: u64,
+    /// Number of times the signal has been decoded since the cache was reset
+    pub updates: u64,
+}
+
+/// Change thresholds for LatestValuesChanged reports.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ChangeThresholds {
+    /// Absolute change that reports any signal without its own threshold
+    /// (None = only signals listed in `signals` are reported)
+    #[serde(default)]
+    pub default_delta: Option<f64>,
+    /// Per-signal absolute change, keyed by signal name
+    #[serde(default)]
+    pub signals: HashMap<String, f64>,
+    /// Minimum frame time between two reports of the same signal
+    #[serde(default)]
+    pub min_interval_ms: u64,
+}
+
+impl ChangeThresholds {
+    fn delta_for(&self, name: &str) -> Option<f64> {
+        self.signals.get(name).copied().or(self.default_delta)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for delta in self.default_delta.iter().chain(self.signals.values()) {
+            if !delta.is_finite() || *delta < 0.0 {
+                return Err("Change thresholds must be non-negative numbers".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// (bus, frame_id, mux_value, signal name)
+type SignalKey = (u8, u32, Option<i64>, String);
+
+struct Entry {
+    value: LatestValue,
+    /// Scaled value and frame time of the last report
+    reported: Option<(f64, u64)>,
+}
+
+#[derive(Default)]
+struct SessionValues {
+    values: HashMap<SignalKey, Entry>,
+    thresholds: Option<ChangeThresholds>,
+}
+
+impl SessionValues {
+    /// Store a decoded value; returns it when it crosses the change threshold.
+    fn record(&mut self, mut sample: LatestValue) -> Option<LatestValue> {
+        let key = (sample.bus, sample.frame_id, sample.mux_value, sample.name.clone());
+        let entry = self.values.entry(key).or_insert_with(|| Entry {
+            value: sample.clone(),
+            reported: None,
+        });
+        sample.updates = entry.value.updates + 1;
+        entry.value = sample;
+
+        let thresholds = self.thresholds.as_ref()?;
+        let delta = thresholds.delta_for(&entry.value.name)?;
+        let (value, ts) = (entry.value.value, entry.value.timestamp_us);
+        let due = match entry.reported {
+            None => true,
+            Some((last, last_ts)) => {
+                (value - last).abs() >= delta
+                    && (value - last).abs() > 0.0
+                    && ts.saturating_sub(last_ts) >= thresholds.min_interval_ms * 1000
+            }
+        };
+        if !due {
+            return None;
+        }
+        entry.reported = Some((value, ts));
+        Some(entry.value.clone())
+    }
+
+    fn reset_reports(&mut self) {
+        for entry in self.values.values_mut() {
+            entry.reported = None;
+        }
+    }
+}
+
+/// Map of session_id -> cached values and thresholds.
+static LATEST_VALUES: Lazy<RwLock<HashMap<String, SessionValues>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+// ============================================================================
+// Capture Tap
+// ============================================================================
+
+/// Decode a batch against the session's attached catalogue and update the
+/// cache. Called from capture_store::append_frames_to_session; signals
+/// LatestValuesChanged with the values that crossed a change threshold.
+pub fn tap_frames(session_id: &str, frames: &[FrameMessage]) {
+    let Some(catalog) = crate::ws::dispatch::attached_catalog(session_id) else {
+        return;
+    };
+
+    let mut samples = Vec::new();
+    for f in frames {
+        let Some(decoded) = wiretap_catalog::decode::decode_by_id(&catalog, f.frame_id, &f.bytes)
+        else {
+            continue;
+        };
+        for s in &decoded.signals {
+            samples.push(LatestValue {
+                frame_id: f.frame_id,
+                bus: f.bus,
+                name: s.name.clone(),
+                mux_value: s.mux_value.map(|m| m as i64),
+                raw: s.value as f64,
+                value: s.scaled as f64,
+                display: s.display.clone(),
+                unit: s.unit.clone(),
+                timestamp_us: f.timestamp_us,
+                updates: 0,
+            });
+        }
+    }
+    record_values(session_id, samples);
+}
+
+/// Update the cache with decoded values. Used by the capture tap and by
+/// decoders outside the catalogue (OBD-II PID polling), so their values get
+/// the same change reports and trends.
+pub fn record_values(session_id: &str, samples: Vec<LatestValue>) {
+    if samples.is_empty() {
+        return;
+    }
+    let tracking = crate::signal_trends::is_tracking(session_id);
+    let mut trend_samples = Vec::new();
+    let mut changed = Vec::new();
+    {
+        let Ok(mut sessions) = LATEST_VALUES.write() else {
+            return;
+        };
+        let session = sessions.entry(session_id.to_string()).or_default();
+        for sample in samples {
+            if tracking {
+                trend_samples.push(sample.clone());
+            }
+            if let Some(report) = session.record(sample) {
+                changed.push(report);
+            }
+        }
+    }
+
+    if !trend_samples.is_empty() {
+        crate::signal_trends::record_samples(session_id, &trend_samples);
+    }
+    if !changed.is_empty() {
+        crate::ws::dispatch::send_latest_values_changed(session_id, &changed);
+    }
+}
+
+/// Drop a session's cached values, keeping its thresholds. Called when a new
+/// catalogue is attached, since signal names may have changed.
+pub fn reset_values(session_id: &str) {
+    if let Ok(mut sessions) = LATEST_VALUES.write() {
+        if let Some(session) = sessions.get_mut(session_id) {
+            session.values.clear();
+        }
+    }
+}
+
+/// Remove a session's cache and thresholds. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut sessions) = LATEST_VALUES.write() {
+        sessions.remove(session_id);
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Latest value of every decoded signal on a session, sorted by bus, frame
+/// ID and name. `names` restricts the result to those signal names.
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_latest_values(session_id: String, names: Option<Vec<String>>) -> Vec<LatestValue> {
+    let Ok(sessions) = LATEST_VALUES.read() else {
+        return Vec::new();
+    };
+    let Some(session) = sessions.get(&session_id) else {
+        return Vec::new();
+    };
+    let mut values: Vec<LatestValue> = session
+        .values
+        .values()
+        .filter(|e| names.as_ref().is_none_or(|n| n.contains(&e.value.name)))
+        .map(|e| e.value.clone())
+        .collect();
+    values.sort_by(|a, b| (a.bus, a.frame_id, &a.name, a.mux_value).cmp(&(b.bus, b.frame_id, &b.name, b.mux_value)));
+    values
+}
+
+/// Set the change thresholds for a session's LatestValuesChanged reports.
+/// Every matching signal is reported again on its next update.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_latest_value_thresholds(session_id: String, thresholds: ChangeThresholds) -> Result<(), String> {
+    thresholds.validate()?;
+    let mut sessions = LATEST_VALUES
+        .write()
+        .map_err(|e| format!("Latest-value lock poisoned: {e}"))?;
+    let session = sessions.entry(session_id.clone()).or_default();
+    session.reset_reports();
+    session.thresholds = Some(thresholds);
+    tlog!("[LatestValues:{}] Change thresholds set", session_id);
+    Ok(())
+}
+
+/// Stop LatestValuesChanged reports for a session; the cache keeps updating.
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_latest_value_thresholds(session_id: String) {
+    if let Ok(mut sessions) = LATEST_VALUES.write() {
+        if let Some(session) = sessions.get_mut(&session_id) {
+            session.thresholds = None;
+        }
+    }
+}
+
+/// The session's change thresholds (None = no reports).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_latest_value_thresholds(session_id: String) -> Option<ChangeThresholds> {
+    LATEST_VALUES
+        .read()
+        .ok()
+        .and_then(|s| s.get(&session_id).and_then(|v| v.thresholds.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(name: &str, value: f64, ts_ms: u64) -> LatestValue {
+        LatestValue {
+            frame_id: 0x123,
+            bus: 0,
+            name: name.to_string(),
+            mux_value: None,
+            raw: value,
+            value,
+            display: value.to_string(),
+            unit: None,
+            timestamp_us: ts_ms * 1000,
+            updates: 0,
+        }
+    }
+
+    #[test]
+    fn caches_latest_value_without_thresholds() {
+        let mut session = SessionValues::default();
+        assert!(session.record(sample("Speed", 10.0, 0)).is_none());
+        assert!(session.record(sample("Speed", 12.0, 10)).is_none());
+        assert_eq!(session.values.len(), 1);
+        let entry = session.values.values().next().unwrap();
+        assert_eq!(entry.value.value, 12.0);
+        assert_eq!(entry.value.updates, 2);
+    }
+
+    #[test]
+    fn reports_on_threshold_crossing() {
+        let mut session = SessionValues {
+            thresholds: Some(ChangeThresholds {
+                default_delta: None,
+                signals: HashMap::from([("Speed".to_string(), 5.0)]),
+                min_interval_ms: 0,
+            }),
+            ..Default::default()
+        };
+        // First value is always reported; unlisted signals never are
+        assert!(session.record(sample("Speed", 10.0, 0)).is_some());
+        assert!(session.record(sample("Rpm", 800.0, 0)).is_none());
+        // Change is measured from the last report, not the last update
+        assert!(session.record(sample("Speed", 13.0, 10)).is_none());
+        assert!(session.record(sample("Speed", 14.0, 20)).is_none());
+        let report = session.record(sample("Speed", 15.0, 30)).unwrap();
+        assert_eq!(report.value, 15.0);
+        assert_eq!(report.updates, 4);
+    }
+
+    #[test]
+    fn rate_limits_reports() {
+        let mut session = SessionValues {
+            thresholds: Some(ChangeThresholds {
+                default_delta: Some(0.0),
+                signals: HashMap::new(),
+                min_interval_ms: 100,
+            }),
+            ..Default::default()
+        };
+        assert!(session.record(sample("Temp", 1.0, 0)).is_some());
+        assert!(session.record(sample("Temp", 2.0, 50)).is_none());
+        assert!(session.record(sample("Temp", 3.0, 100)).is_some());
+        // No change, no report
+        assert!(session.record(sample("Temp", 3.0, 300)).is_none());
+        assert!(ChangeThresholds { default_delta: Some(-1.0), ..Default::default() }.validate().is_err());
+    }
+}
@@ -1,9 +1,21 @@
 #[macro_use]
 pub(crate) mod logging;
+#[macro_use]
+mod messages;
 mod analysis;
 mod app_registry;
+mod black_box;
 mod ble_provision;
+mod canopen;
+mod capture_chunks;
 mod capture_db;
+mod capture_diff;
+mod capture_integrity;
+mod capture_journal;
+mod capture_preview;
+mod capture_rotation;
+mod capture_share;
+mod capture_trigger;
 mod capturequery;
 mod capture_store;
 mod captures;
@@ -11,25 +23,59 @@ mod catalog;
 mod apiclient;
 mod dashboard;
 mod checksums;
+mod convert;
 mod credentials;
 mod dbquery;
+mod export_sidecar;
+mod headless;
 mod device_scan;
 #[cfg(not(target_os = "ios"))]
 mod flashers;
+mod frame_link;
+mod frame_priority;
 mod framing;
 pub mod io;
+mod profile_schema;
 mod profile_tracker;
+mod session_templates;
 mod sessions;
+mod session_quota;
 mod settings;
+mod signal_bits;
+mod signal_trends;
+mod source_clock;
+mod source_dedup;
 mod telemetry;
+mod timebase;
 #[cfg(not(target_os = "ios"))]
 mod serial_terminal;
 mod store_manager;
 mod transmit;
 mod transmit_history;
+mod transmit_validation;
+mod trigger_snapshot;
+#[cfg(test)]
+mod ts_bindings;
+#[cfg(not(target_os = "ios"))]
+mod usb_hotplug;
+mod reactive_transmit;
+mod reassembly;
+mod recording_schedule;
+mod remote_api;
 mod replay;
+mod report;
+mod responder;
+mod retention;
+mod row_model;
 mod io_test;
+mod ipc_compression;
+mod j1939;
+mod latest_values;
+mod live_analysis;
 mod mcp;
+mod menu_state;
+mod obd_poll;
+mod webhooks;
 pub mod ws;
 
 use std::sync::Mutex;
@@ -323,34 +369,25 @@ fn open_settings_panel(app: AppHandle, state: State<SettingsWindowState>) {
     open_settings_singleton(&app, &state);
 }
 
-/// Update the Session menu state based on the focused app's session.
+/// Apply Session menu state to the native menu items.
 /// When `has_session` is false, disables all session and bookmark menu items.
 /// When `has_session` is true, updates items based on session state.
 #[cfg(not(target_os = "ios"))]
-#[tauri::command]
-fn update_menu_state(
-    state: State<SessionMenuState>,
-    bookmark_item_state: State<BookmarkMenuItemState>,
-    bookmarks_menu_state: State<BookmarksMenuState>,
-    has_session: bool,
-    profile_name: Option<String>,
-    is_streaming: bool,
-    is_paused: bool,
-    can_pause: bool,
-    joiner_count: u32,
-    has_bookmarks: bool,
-) {
-    if let Some(items) = state.0.lock().unwrap().as_ref() {
-        if has_session {
-            let text = profile_name
+pub(crate) fn apply_menu_state(app: &AppHandle, menu: &menu_state::MenuSessionState, has_bookmarks: bool) {
+    if let Some(items) = app.state::<SessionMenuState>().0.lock().unwrap().as_ref() {
+        let is_streaming = menu.is_streaming;
+        let is_paused = menu.is_paused;
+        if menu.has_session {
+            let text = menu
+                .profile_name
                 .as_ref()
                 .map(|n| format!("Source: {}", n))
                 .unwrap_or_else(|| "No source selected".to_string());
             let _ = items.source.set_text(&text);
             let _ = items.play.set_enabled(!is_streaming || is_paused);
-            let _ = items.pause.set_enabled(is_streaming && !is_paused && can_pause);
-            let _ = items.stop.set_enabled(is_streaming && !is_paused && can_pause);
-            let _ = items.detach.set_enabled(is_streaming && joiner_count > 1);
+            let _ = items.pause.set_enabled(is_streaming && !is_paused && menu.can_pause);
+            let _ = items.stop.set_enabled(is_streaming && !is_paused && menu.can_pause);
+            let _ = items.detach.set_enabled(is_streaming && menu.joiner_count > 1);
             let _ = items.stop_all.set_enabled(is_streaming);
             let _ = items.picker.set_enabled(true);
             let _ = items.clear.set_enabled(true);
@@ -367,14 +404,43 @@ fn update_menu_state(
     }
 
     // Enable/disable bookmark items
-    if let Some(items) = bookmark_item_state.0.lock().unwrap().as_ref() {
+    let has_bookmarks = menu.has_session && has_bookmarks;
+    if let Some(items) = app.state::<BookmarkMenuItemState>().0.lock().unwrap().as_ref() {
         let _ = items.save.set_enabled(has_bookmarks);
     }
-    if let Some(submenu) = bookmarks_menu_state.0.lock().unwrap().as_ref() {
+    if let Some(submenu) = app.state::<BookmarksMenuState>().0.lock().unwrap().as_ref() {
         let _ = submenu.set_enabled(has_bookmarks);
     }
 }
 
+/// Update the Session menu from state reported by the frontend. Prefer
+/// `sync_menu_state`, which computes the state from the session backend-side
+/// and keeps it current; calling this stops the menu following a session.
+#[cfg(not(target_os = "ios"))]
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+fn update_menu_state(
+    app: AppHandle,
+    has_session: bool,
+    profile_name: Option<String>,
+    is_streaming: bool,
+    is_paused: bool,
+    can_pause: bool,
+    joiner_count: u32,
+    has_bookmarks: bool,
+) {
+    menu_state::clear_focus();
+    let menu = menu_state::MenuSessionState {
+        has_session,
+        profile_name,
+        is_streaming,
+        is_paused,
+        can_pause,
+        joiner_count,
+    };
+    apply_menu_state(&app, &menu, has_bookmarks);
+}
+
 /// Update the Bookmarks > Jump to Bookmark submenu with bookmarks for the current profile.
 /// Called by the frontend when panel focus or IO profile changes.
 #[cfg(not(target_os = "ios"))]
@@ -515,12 +581,16 @@ async fn create_main_window(_app: AppHandle, _label: String) -> Result<(), Strin
     Ok(()) // No-op on iOS
 }
 
+#[cfg(target_os = "ios")]
+pub(crate) fn apply_menu_state(_app: &AppHandle, _menu: &menu_state::MenuSessionState, _has_bookmarks: bool) {
+    // No-op on iOS
+}
+
 #[cfg(target_os = "ios")]
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 fn update_menu_state(
-    _state: State<SessionMenuState>,
-    _bookmark_item_state: State<BookmarkMenuItemState>,
-    _bookmarks_menu_state: State<BookmarksMenuState>,
+    _app: AppHandle,
     _has_session: bool,
     _profile_name: Option<String>,
     _is_streaming: bool,
@@ -934,6 +1004,15 @@ fn toggle_mcp_server(app: AppHandle, enabled: bool) -> Result<McpStatus, String>
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // `WireTAP convert ...` converts log files and exits without starting the app
+    if let Some(code) = convert::run_from_args() {
+        std::process::exit(code);
+    }
+    // `WireTAP capture ...` records from an IO profile without the GUI
+    if let Some(code) = headless::run_from_args() {
+        std::process::exit(code);
+    }
+
     // Pipe `tracing` events from framelink (and any other crate that
     // uses the `tracing` macros) to stderr, gated by RUST_LOG. With
     // RUST_LOG unset, only WARN and above are shown.
@@ -1005,10 +1084,10 @@ pub fn run() {
 
             // Initialise the SQLite-backed capture database
             if let Ok(data_dir) = app.path().app_data_dir() {
-                let clear_on_start = settings::load_settings_sync(app.handle())
-                    .map(|s| s.clear_captures_on_start)
-                    .unwrap_or(true);
-                if let Err(e) = capture_db::initialise(&data_dir, clear_on_start) {
+                let (clear_on_start, db_cache_mb) = settings::load_settings_sync(app.handle())
+                    .map(|s| (s.clear_captures_on_start, s.capture_db_cache_mb))
+                    .unwrap_or((true, 64));
+                if let Err(e) = capture_db::initialise(&data_dir, clear_on_start, db_cache_mb) {
                     tlog!("[setup] Failed to initialise capture database: {}", e);
                     record_startup_error(format!(
                         "Capture database failed to initialise: {e}. Captures, Discovery \
@@ -1026,6 +1105,12 @@ pub fn run() {
                 // Hydrate the capture registry from SQLite.
                 // Always called — persistent (pinned) captures may survive clear_on_start.
                 capture_store::hydrate_from_db();
+
+                // Recover black-box rings left by sessions that didn't end cleanly
+                black_box::initialise(app.handle());
+
+                // Recover captures that were still recording when the app died
+                capture_journal::initialise(app.handle());
             }
 
             // Restore dashboard window geometry from persisted state (desktop only).
@@ -1064,6 +1149,16 @@ pub fn run() {
             // and probe WebView health (detects content process jettison on macOS)
             io::start_heartbeat_watchdog(app.handle().clone());
 
+            // Report USB adapters arriving and leaving (desktop only)
+            #[cfg(not(target_os = "ios"))]
+            usb_hotplug::start_usb_hotplug_watcher(app.handle().clone());
+
+            // Apply the file retention policy periodically (no-op unless enabled)
+            retention::start_retention_task(app.handle().clone());
+
+            // Session lifecycle webhooks (no-op until some are configured)
+            webhooks::load_config(app.handle());
+
             // Start WebSocket binary transport server
             match ws::server::WsServer::start() {
                 Ok((port, _token)) => {
@@ -1096,6 +1191,16 @@ pub fn run() {
                 Err(e) => tlog!("[mcp] Could not load settings to start server: {}", e),
             }
 
+            // Start the remote control API if enabled (same rules as MCP above)
+            if let Ok(s) = settings::load_settings_sync(app.handle()) {
+                if s.remote_api_enabled {
+                    let token = remote_api::load_token(&s);
+                    if let Err(e) = remote_api::start(app.handle().clone(), s.remote_api_port, token) {
+                        tlog!("[remote_api] Failed to start: {}", e);
+                    }
+                }
+            }
+
             // Seed bundled example decoders synchronously, before the webview loads.
             // The frontend's first list_catalogs() must read a populated decoder
             // directory, otherwise it silently shows an empty catalog list (the copy
@@ -1146,6 +1251,8 @@ pub fn run() {
             settings_panel_closed,
             open_settings_panel,
             update_menu_state,
+            menu_state::get_menu_state,
+            menu_state::sync_menu_state,
             update_bookmarks_menu,
             catalog::open_catalog,
             catalog::save_catalog,
@@ -1160,6 +1267,9 @@ pub fn run() {
             dashboard::save_dashboard,
             settings::load_settings,
             settings::save_settings,
+            // Profile connection schemas / validation
+            profile_schema::get_profile_schema,
+            profile_schema::validate_profile,
             settings::validate_directory,
             settings::create_directory,
             settings::get_app_version,
@@ -1167,6 +1277,9 @@ pub fn run() {
             settings::check_candor_migration,
             settings::run_candor_migration,
             settings::delete_candor_data,
+            // Message catalogue (localisable backend text)
+            messages::get_message_catalogue,
+            messages::describe_message,
             // Session-based reader API
             sessions::create_reader_session,
             sessions::get_reader_session_state,
@@ -1195,6 +1308,7 @@ pub fn run() {
             sessions::update_reader_direction,
             sessions::destroy_reader_session,
             sessions::create_capture_source_session,
+            sessions::create_multi_capture_source_session,
             sessions::transition_to_capture_source,
             sessions::switch_session_to_capture_replay,
             sessions::resume_session_to_live,
@@ -1207,6 +1321,7 @@ pub fn run() {
             sessions::session_leave_to_capture,
             sessions::add_source_to_session_cmd,
             sessions::remove_source_from_session_cmd,
+            sessions::replace_source_in_session_cmd,
             sessions::pause_source_polling,
             sessions::resume_source_polling,
             sessions::update_source_bus_mappings_cmd,
@@ -1217,9 +1332,18 @@ pub fn run() {
             sessions::prune_window_apps,
             sessions::reinitialize_session_if_safe_cmd,
             sessions::set_session_subscriber_active,
+            sessions::set_session_subscriber_rate_limit,
+            sessions::set_session_batching,
+            sessions::get_session_batching,
             sessions::probe_gvret_device,
+            sessions::configure_gvret_bus,
+            sessions::discover_gvret_devices,
             sessions::probe_device,
             sessions::create_multi_source_session,
+            session_templates::list_session_templates,
+            session_templates::save_session_template,
+            session_templates::delete_session_template,
+            session_templates::create_session_from_template,
             sessions::list_active_sessions,
             sessions::generate_session_id,
             // Profile-to-session mapping API
@@ -1235,6 +1359,9 @@ pub fn run() {
             sessions::get_modbus_scan_state_cmd,
             // Power management API
             sessions::set_wake_settings,
+            // Session quotas / admission control
+            session_quota::get_session_quotas,
+            session_quota::set_session_quotas,
             // Modbus scanning API
             sessions::modbus_scan_registers,
             sessions::modbus_scan_unit_ids,
@@ -1243,6 +1370,14 @@ pub fn run() {
             io::check_recovery_occurred,
             // Capture / CSV Import API
             captures::import_csv_to_capture,
+            captures::import_candump_to_capture,
+            captures::import_blf_to_capture,
+            captures::export_capture_blf,
+            captures::export_capture_asc,
+            captures::export_capture_parquet,
+            captures::import_mdf4_to_capture,
+            captures::import_pcap_to_capture,
+            captures::import_trc_to_capture,
             captures::preview_csv,
             captures::import_csv_with_mapping,
             captures::import_csv_batch_with_mapping,
@@ -1250,16 +1385,22 @@ pub fn run() {
             captures::get_capture_frames,
             captures::get_capture_frames_paginated,
             captures::get_capture_frames_paginated_filtered,
+            captures::get_capture_frames_paginated_packed,
+            captures::get_capture_frames_by_protocol,
+            captures::get_capture_protocol_counts,
             captures::get_capture_frames_paginated_by_id,
             captures::get_capture_frames_tail,
             captures::get_capture_frame_info,
             captures::find_capture_offset_for_timestamp,
             captures::search_capture_frames,
+            captures::search_capture_pattern,
             // Multi-capture registry API
             captures::list_captures,
             captures::list_capture_ids,
             captures::delete_capture,
             captures::clear_capture,
+            captures::trim_capture,
+            captures::split_capture,
             captures::get_capture_metadata_by_id,
             captures::get_capture_frames_by_id,
             captures::get_capture_bytes_by_id,
@@ -1273,18 +1414,180 @@ pub fn run() {
             captures::find_capture_bytes_offset_for_timestamp,
             captures::rename_capture,
             captures::set_capture_persistent,
+            captures::add_capture_annotation,
+            captures::list_capture_annotations,
+            captures::update_capture_annotation,
+            captures::delete_capture_annotation,
+            captures::set_capture_ring_limit,
+            captures::get_capture_ring_limit,
+            captures::persist_capture,
+            captures::open_persisted_capture,
+            captures::merge_captures,
             // Session-aware capture API
             captures::list_orphaned_captures,
+            // Conditional capture (start/stop triggers)
+            capture_trigger::set_capture_conditions,
+            capture_trigger::clear_capture_conditions,
+            capture_trigger::rearm_capture_trigger,
+            capture_trigger::get_capture_trigger_state,
+            // Auto-split of session recordings
+            capture_rotation::set_capture_rotation,
+            capture_rotation::clear_capture_rotation,
+            capture_rotation::get_capture_rotation,
+            // Scheduled and timed recordings
+            recording_schedule::schedule_recording,
+            recording_schedule::cancel_recording,
+            recording_schedule::get_recording_state,
+            // Simulated ECU responder (rule + UDS replies on a session)
+            responder::responder_start,
+            responder::responder_stop,
+            responder::get_responder_state,
+            // OBD-II PID polling (decoded values into the latest-value cache)
+            obd_poll::obd_poll_start,
+            obd_poll::obd_poll_stop,
+            obd_poll::get_obd_poll_state,
+            // J1939 source addresses and SPN decoding
+            j1939::set_j1939_config,
+            j1939::clear_j1939_config,
+            j1939::get_j1939_config,
+            j1939::parse_j1939_id,
+            j1939::get_j1939_network_map,
+            j1939::clear_j1939_network_map,
+            // CANopen frame decoding and SDO client
+            canopen::decode_canopen_frame,
+            canopen::canopen_sdo_read,
+            canopen::canopen_sdo_write,
+            // LIN LDF-lite description
+            io::lin::ldf::get_lin_description,
+            retention::get_retention_policy,
+            retention::set_retention_policy,
+            retention::run_retention,
+            retention::get_last_retention_report,
+            // Black box mode
+            black_box::get_black_box_config,
+            black_box::set_black_box_config,
+            capture_journal::get_capture_journal_config,
+            capture_journal::set_capture_journal_config,
+            // Session lifecycle webhooks
+            webhooks::get_webhooks,
+            webhooks::set_webhooks,
+            webhooks::test_webhook,
+            webhooks::get_webhook_deliveries,
+            webhooks::notify_export_completed,
+            // Reactive transmit (frame-triggered injection in the merge task)
+            reactive_transmit::set_reactive_transmit_rules,
+            reactive_transmit::clear_reactive_transmit_rules,
+            reactive_transmit::get_reactive_transmit_state,
+            trigger_snapshot::set_session_triggers,
+            trigger_snapshot::clear_session_triggers,
+            trigger_snapshot::get_session_triggers,
+            // Multi-frame reassembly (ISO-TP, J1939 TP, custom schemes)
+            reassembly::set_reassembly_config,
+            reassembly::clear_reassembly_config,
+            reassembly::get_reassembly_config,
+            reassembly::get_reassembly_links,
+            // Latest decoded value per signal (gauge dashboards)
+            latest_values::get_latest_values,
+            latest_values::set_latest_value_thresholds,
+            latest_values::clear_latest_value_thresholds,
+            latest_values::get_latest_value_thresholds,
+            // Pinned frame IDs delivered ahead of background traffic
+            frame_priority::set_frame_priority,
+            frame_priority::clear_frame_priority,
+            frame_priority::get_frame_priority,
+            // Windowed statistics, cadence, entropy and change heatmaps on live sessions
+            live_analysis::start_live_analysis,
+            live_analysis::stop_live_analysis,
+            live_analysis::get_live_analysis,
+            live_analysis::get_live_change_heatmap,
+            // In-memory trend storage for selected signals (strip charts)
+            signal_trends::set_signal_trends,
+            signal_trends::clear_signal_trends,
+            signal_trends::get_signal_trends,
+            signal_trends::query_signal_trend,
+            // Redundant-capture dedup with per-source fidelity counts
+            source_dedup::set_source_dedup,
+            source_dedup::clear_source_dedup,
+            source_dedup::get_source_dedup_stats,
+            source_clock::set_source_clock,
+            source_clock::clear_source_clock,
+            source_clock::get_source_clock_stats,
+            // Catalog-driven transmit validation
+            transmit_validation::set_transmit_validation,
+            transmit_validation::clear_transmit_validation,
+            transmit_validation::get_transmit_validation,
+            transmit_validation::validate_transmit_frame,
+            // WireTAP-to-WireTAP capture sharing and network import
+            capture_share::start_capture_share,
+            capture_share::stop_capture_share,
+            capture_share::get_capture_share_status,
+            capture_share::list_remote_captures,
+            capture_share::import_remote_capture,
+            capture_share::resume_remote_import,
+            capture_share::cancel_remote_import,
+            capture_share::list_remote_imports,
+            capture_share::forget_remote_import,
+            // Capture integrity seals and signed export manifests
+            capture_integrity::seal_capture_integrity,
+            capture_integrity::verify_capture_integrity,
+            capture_integrity::build_export_manifest,
+            capture_integrity::verify_export_manifest,
+            // Export metadata sidecars
+            export_sidecar::build_export_sidecar,
+            capture_integrity::get_capture_signing_key,
+            capture_integrity::generate_capture_signing_key,
+            capture_integrity::delete_capture_signing_key,
+            // Capture preview series for picker sparklines
+            capture_preview::get_capture_previews,
+            // Per-ID comparison of two captures
+            capture_diff::diff_captures,
+            // Request → response latency analysis
+            analysis::analyze_response_latency,
+            // Timestamp sources and host clock sync status
+            timebase::get_clock_sync_status,
+            timebase::set_session_timestamp_source,
+            timebase::get_session_timestamp_source,
+            // Backend row models for virtualised frame tables
+            row_model::row_model_open,
+            row_model::row_model_update,
+            row_model::row_model_get_rows,
+            row_model::row_model_find_row,
+            row_model::row_model_close,
             // Backend framing
             framing::apply_framing_to_capture,
             // Serial port API (platform-aware: real on desktop, stub on iOS)
             platform_list_serial_ports,
             // slcan device probing (platform-aware: real on desktop, stub on iOS)
             platform_probe_slcan_device,
+            // Embedded MQTT broker (mqtt-broker feature)
+            io::mqtt::mqtt_broker_start,
+            io::mqtt::mqtt_broker_stop,
+            io::mqtt::get_mqtt_broker_status,
+            // Session mirroring to an MQTT broker
+            io::mqtt::mqtt_publish_start,
+            io::mqtt::mqtt_publish_stop,
+            io::mqtt::get_mqtt_publish_status,
+            // WireTAP-to-WireTAP network bridge publisher
+            io::netbridge::publisher::start_network_publisher,
+            io::netbridge::publisher::stop_network_publisher,
+            io::netbridge::publisher::list_network_publishers,
             // gs_usb device enumeration and setup commands
             io::gs_usb::list_gs_usb_devices,
             io::gs_usb::get_can_setup_command,
+            io::gs_usb::configure_can_interface,
+            io::gs_usb::set_can_interface_up,
+            io::gs_usb::get_can_interface_status,
             io::gs_usb::probe_gs_usb_device,
+            // PCAN-USB device enumeration
+            io::pcan::list_pcan_devices,
+            io::pcan::get_pcan_bitrates,
+            // Kvaser device enumeration
+            io::kvaser::list_kvaser_devices,
+            io::kvaser::get_kvaser_bitrates,
+            // Vector XL device enumeration
+            io::vector_xl::list_vector_devices,
+            io::vector_xl::get_vector_bitrates,
+            io::j2534::list_j2534_devices,
             // Credential storage API
             credentials::store_credential,
             credentials::get_credential,
@@ -1303,6 +1606,8 @@ pub fn run() {
             // IO session-based transmit
             transmit::io_transmit_can_frame,
             transmit::io_transmit_serial,
+            transmit::io_transmit_modbus_write,
+            transmit::io_transmit_diagnostic,
             transmit::io_set_framing,
             transmit::get_io_session_capabilities,
             transmit::io_start_repeat_transmit,
@@ -1342,6 +1647,8 @@ pub fn run() {
             #[cfg(not(target_os = "ios"))]
             flashers::flasher_dfu_list_devices,
             #[cfg(not(target_os = "ios"))]
+            usb_hotplug::list_usb_devices,
+            #[cfg(not(target_os = "ios"))]
             flashers::flasher_dfu_flash,
             #[cfg(not(target_os = "ios"))]
             flashers::flasher_dfu_cancel,
@@ -1401,6 +1708,8 @@ pub fn run() {
             capturequery::capture_query_distribution,
             capturequery::capture_query_gap_analysis,
             capturequery::capture_query_pattern_search,
+            // Capture reports (HTML/Markdown with inline SVG plots)
+            report::generate_report,
                         // Unified Device Scan API
                         device_scan::device_scan_start,
                         device_scan::device_scan_stop,
@@ -1425,6 +1734,9 @@ pub fn run() {
                         // MCP server control
                         get_mcp_status,
                         toggle_mcp_server,
+                        // Remote control API
+                        remote_api::get_remote_api_status,
+                        remote_api::toggle_remote_api,
         ]);
 
     // Handle window close events to prevent crashes on macOS 26.2+ (Tahoe)
@@ -1509,6 +1821,14 @@ pub fn run() {
     });
 
     builder
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            // A normal quit isn't a crash: drop the black-box rings and
+            // capture journals so they aren't "recovered" on the next launch
+            if let tauri::RunEvent::Exit = event {
+                black_box::discard_all();
+                capture_journal::discard_all();
+            }
+        });
 }
@@ -0,0 +1,527 @@
+// ui/src-tauri/src/live_analysis.rs
+//
+// Windowed analysis of a live session — the Discovery analytics (per-ID
+// statistics, cadence, per-byte entropy and change heatmaps) over the last
+// `window_ms` of traffic, so they can be read while the capture is running
+// instead of after it has stopped.
+//
+// Frames are fed in capture_store::append_frames_to_session (after gating and
+// reassembly), and each (bus, frame_id) keeps its frames inside the window
+// together with running totals: a value histogram and sum per byte position,
+// per-byte change counts and the interval sum / sum of squares. Adding a frame
+// and evicting one that has left the window both update the totals, so a
+// read is O(IDs × bytes) whatever the window holds. The window follows frame
+// time (the newest timestamp seen on the session), so paused or replayed
+// sources behave the same as live ones.
+//
+// The SQLite capture commands (capturequery.rs) still answer questions about
+// the full capture; this is the cheap rolling view.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use crate::io::FrameMessage;
+
+/// Window used when the config doesn't set one.
+const DEFAULT_WINDOW_MS: u64 = 10_000;
+
+/// Longest accepted window.
+const MAX_WINDOW_MS: u64 = 600_000;
+
+/// Heatmap bucket width used when the config doesn't set one.
+const DEFAULT_BUCKET_MS: u64 = 500;
+
+/// Frames kept per ID; the oldest are evicted early past this.
+const MAX_FRAMES_PER_ID: usize = 100_000;
+
+/// Byte positions tracked per frame (CAN FD payload size).
+const MAX_BYTES: usize = 64;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Live analysis settings for a session.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LiveAnalysisConfig {
+    /// Sliding window length in frame time
+    #[serde(default = "default_window_ms")]
+    pub window_ms: u64,
+    /// Change heatmap bucket width
+    #[serde(default = "default_bucket_ms")]
+    pub bucket_ms: u64,
+    /// Buses to analyse (None = every bus)
+    #[serde(default)]
+    pub buses: Option<Vec<u8>>,
+}
+
+fn default_window_ms() -> u64 {
+    DEFAULT_WINDOW_MS
+}
+
+fn default_bucket_ms() -> u64 {
+    DEFAULT_BUCKET_MS
+}
+
+impl LiveAnalysisConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.window_ms == 0 || self.window_ms > MAX_WINDOW_MS {
+            return Err(format!("Analysis window must be 1-{} ms", MAX_WINDOW_MS));
+        }
+        if self.bucket_ms == 0 || self.bucket_ms > self.window_ms {
+            return Err("Heatmap bucket must be between 1 ms and the window length".to_string());
+        }
+        Ok(())
+    }
+
+    fn covers(&self, bus: u8) -> bool {
+        self.buses.as_ref().is_none_or(|b| b.contains(&bus))
+    }
+}
+
+/// Statistics for one byte position within the window.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LiveByteStats {
+    pub index: usize,
+    /// Frames in the window that carried this byte
+    pub samples: u32,
+    pub min: u8,
+    pub max: u8,
+    pub mean: f64,
+    pub distinct: u16,
+    /// Shannon entropy of the byte's values, in bits (0-8)
+    pub entropy_bits: f64,
+    /// Frames where the byte differed from the previous frame
+    pub changes: u32,
+}
+
+/// Window statistics for one frame ID.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LiveFrameStats {
+    pub bus: u8,
+    pub frame_id: u32,
+    pub is_extended: bool,
+    pub count: usize,
+    pub first_us: u64,
+    pub last_us: u64,
+    /// Frames per second across the window
+    pub rate_hz: f64,
+    /// Cadence: interval between consecutive frames (ms)
+    pub interval_mean_ms: Option<f64>,
+    pub interval_min_ms: Option<f64>,
+    pub interval_max_ms: Option<f64>,
+    pub interval_stddev_ms: Option<f64>,
+    pub bytes: Vec<LiveByteStats>,
+    /// Oldest frames were evicted early (MAX_FRAMES_PER_ID)
+    pub truncated: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LiveAnalysis {
+    pub window_ms: u64,
+    /// Frame-time span of the window
+    pub window_start_us: u64,
+    pub window_end_us: u64,
+    pub frames: Vec<LiveFrameStats>,
+}
+
+/// Per-byte change counts in time buckets across the window.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LiveChangeHeatmap {
+    pub bus: u8,
+    pub frame_id: u32,
+    pub bucket_ms: u64,
+    pub bucket_starts_us: Vec<u64>,
+    /// One row per byte position, parallel to `bucket_starts_us`
+    pub rows: Vec<Vec<u32>>,
+}
+
+struct WindowFrame {
+    timestamp_us: u64,
+    bytes: Vec<u8>,
+    /// Interval since the previous frame of this ID (None for the first)
+    interval_us: Option<u64>,
+    /// Bit n set when byte n changed from the previous frame
+    changed: u64,
+}
+
+/// Frames of one ID inside the window, with running totals.
+struct IdWindow {
+    is_extended: bool,
+    frames: VecDeque<WindowFrame>,
+    /// Value histogram per byte position
+    histograms: Vec<Box<[u32; 256]>>,
+    sums: Vec<u64>,
+    samples: Vec<u32>,
+    changes: Vec<u32>,
+    interval_count: u32,
+    /// Integer totals (µs, µs²) so add/evict never drifts
+    interval_sum: u128,
+    interval_sq_sum: u128,
+    /// Last frame seen (kept across eviction so the next frame diffs against it)
+    last: Option<(u64, Vec<u8>)>,
+    truncated: bool,
+}
+
+impl IdWindow {
+    fn new(is_extended: bool) -> Self {
+        IdWindow {
+            is_extended,
+            frames: VecDeque::new(),
+            histograms: Vec::new(),
+            sums: Vec::new(),
+            samples: Vec::new(),
+            changes: Vec::new(),
+            interval_count: 0,
+            interval_sum: 0,
+            interval_sq_sum: 0,
+            last: None,
+            truncated: false,
+        }
+    }
+
+    fn push(&mut self, frame: &FrameMessage) {
+        let bytes = &frame.bytes[..frame.bytes.len().min(MAX_BYTES)];
+        while self.histograms.len() < bytes.len() {
+            self.histograms.push(Box::new([0; 256]));
+            self.sums.push(0);
+            self.samples.push(0);
+            self.changes.push(0);
+        }
+
+        let (interval_us, changed) = match &self.last {
+            Some((ts, prev)) => {
+                let changed = bytes
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, b)| prev.get(*i) != Some(*b))
+                    .fold(0u64, |mask, (i, _)| mask | (1 << i));
+                (Some(frame.timestamp_us.saturating_sub(*ts)), changed)
+            }
+            None => (None, 0),
+        };
+
+        for (i, &b) in bytes.iter().enumerate() {
+            self.histograms[i][b as usize] += 1;
+            self.sums[i] += b as u64;
+            self.samples[i] += 1;
+            if changed & (1 << i) != 0 {
+                self.changes[i] += 1;
+            }
+        }
+        if let Some(iv) = interval_us {
+            let iv = iv as u128;
+            self.interval_count += 1;
+            self.interval_sum += iv;
+            self.interval_sq_sum += iv * iv;
+        }
+
+        self.last = Some((frame.timestamp_us, bytes.to_vec()));
+        self.frames.push_back(WindowFrame {
+            timestamp_us: frame.timestamp_us,
+            bytes: bytes.to_vec(),
+            interval_us,
+            changed,
+        });
+        if self.frames.len() > MAX_FRAMES_PER_ID {
+            self.pop_front();
+            self.truncated = true;
+        }
+    }
+
+    fn pop_front(&mut self) {
+        let Some(old) = self.frames.pop_front() else { return };
+        for (i, &b) in old.bytes.iter().enumerate() {
+            self.histograms[i][b as usize] -= 1;
+            self.sums[i] -= b as u64;
+            self.samples[i] -= 1;
+            if old.changed & (1 << i) != 0 {
+                self.changes[i] -= 1;
+            }
+        }
+        if let Some(iv) = old.interval_us {
+            let iv = iv as u128;
+            self.interval_count -= 1;
+            self.interval_sum -= iv;
+            self.interval_sq_sum -= iv * iv;
+        }
+    }
+
+    fn evict_before(&mut self, cutoff_us: u64) {
+        while self.frames.front().is_some_and(|f| f.timestamp_us < cutoff_us) {
+            self.pop_front();
+        }
+    }
+
+    fn byte_stats(&self, i: usize) -> LiveByteStats {
+        let hist = &self.histograms[i];
+        let n = self.samples[i];
+        let mut distinct = 0u16;
+        let mut entropy = 0.0;
+        for &c in hist.iter().filter(|c| **c > 0) {
+            distinct += 1;
+            let p = c as f64 / n as f64;
+            entropy -= p * p.log2();
+        }
+        LiveByteStats {
+            index: i,
+            samples: n,
+            min: hist.iter().position(|c| *c > 0).unwrap_or(0) as u8,
+            max: hist.iter().rposition(|c| *c > 0).unwrap_or(0) as u8,
+            mean: if n > 0 { self.sums[i] as f64 / n as f64 } else { 0.0 },
+            distinct,
+            entropy_bits: entropy.max(0.0),
+            changes: self.changes[i],
+        }
+    }
+
+    fn stats(&self, bus: u8, frame_id: u32) -> Option<LiveFrameStats> {
+        let first_us = self.frames.front()?.timestamp_us;
+        let last_us = self.frames.back()?.timestamp_us;
+        let count = self.frames.len();
+        let span_s = (last_us - first_us) as f64 / 1e6;
+
+        // The first frame's interval reaches back outside the window
+        let (n, sum, sq_sum) = match self.frames.front().and_then(|f| f.interval_us) {
+            Some(iv) => {
+                let iv = iv as u128;
+                (self.interval_count - 1, self.interval_sum - iv, self.interval_sq_sum - iv * iv)
+            }
+            None => (self.interval_count, self.interval_sum, self.interval_sq_sum),
+        };
+        let (mean, stddev) = if n > 0 {
+            let mean = sum as f64 / n as f64;
+            let var = (sq_sum as f64 / n as f64 - mean * mean).max(0.0);
+            (Some(mean / 1000.0), Some(var.sqrt() / 1000.0))
+        } else {
+            (None, None)
+        };
+        let intervals = self.frames.iter().skip(1).filter_map(|f| f.interval_us);
+        let (min, max) = intervals.fold((None, None), |(lo, hi): (Option<u64>, Option<u64>), iv| {
+            (Some(lo.map_or(iv, |l| l.min(iv))), Some(hi.map_or(iv, |h| h.max(iv))))
+        });
+
+        Some(LiveFrameStats {
+            bus,
+            frame_id,
+            is_extended: self.is_extended,
+            count,
+            first_us,
+            last_us,
+            rate_hz: if span_s > 0.0 { (count - 1) as f64 / span_s } else { 0.0 },
+            interval_mean_ms: mean,
+            interval_min_ms: min.map(|v| v as f64 / 1000.0),
+            interval_max_ms: max.map(|v| v as f64 / 1000.0),
+            interval_stddev_ms: stddev,
+            bytes: (0..self.histograms.len())
+                .filter(|i| self.samples[*i] > 0)
+                .map(|i| self.byte_stats(i))
+                .collect(),
+            truncated: self.truncated,
+        })
+    }
+}
+
+struct SessionAnalysis {
+    config: LiveAnalysisConfig,
+    ids: HashMap<(u8, u32), IdWindow>,
+    /// Newest frame time seen on the session
+    latest_us: u64,
+}
+
+impl SessionAnalysis {
+    fn new(config: LiveAnalysisConfig) -> Self {
+        SessionAnalysis { config, ids: HashMap::new(), latest_us: 0 }
+    }
+
+    fn window_start_us(&self) -> u64 {
+        self.latest_us.saturating_sub(self.config.window_ms * 1000)
+    }
+
+    fn ingest(&mut self, frames: &[FrameMessage]) {
+        for frame in frames.iter().filter(|f| self.config.covers(f.bus)) {
+            self.latest_us = self.latest_us.max(frame.timestamp_us);
+            self.ids
+                .entry((frame.bus, frame.frame_id))
+                .or_insert_with(|| IdWindow::new(frame.is_extended))
+                .push(frame);
+        }
+        let cutoff = self.window_start_us();
+        for window in self.ids.values_mut() {
+            window.evict_before(cutoff);
+        }
+        self.ids.retain(|_, w| !w.frames.is_empty());
+    }
+
+    fn analysis(&self) -> LiveAnalysis {
+        let mut frames: Vec<LiveFrameStats> = self
+            .ids
+            .iter()
+            .filter_map(|(&(bus, id), w)| w.stats(bus, id))
+            .collect();
+        frames.sort_by_key(|f| (f.bus, f.frame_id));
+        LiveAnalysis {
+            window_ms: self.config.window_ms,
+            window_start_us: self.window_start_us(),
+            window_end_us: self.latest_us,
+            frames,
+        }
+    }
+
+    fn heatmap(&self, bus: u8, frame_id: u32) -> Option<LiveChangeHeatmap> {
+        let window = self.ids.get(&(bus, frame_id))?;
+        let bucket_us = self.config.bucket_ms * 1000;
+        let start = self.window_start_us() / bucket_us * bucket_us;
+        let buckets = ((self.latest_us - start) / bucket_us + 1) as usize;
+        let mut rows = vec![vec![0u32; buckets]; window.histograms.len()];
+        for f in &window.frames {
+            let b = ((f.timestamp_us.saturating_sub(start)) / bucket_us) as usize;
+            for (i, row) in rows.iter_mut().enumerate() {
+                if f.changed & (1 << i) != 0 {
+                    row[b.min(buckets - 1)] += 1;
+                }
+            }
+        }
+        Some(LiveChangeHeatmap {
+            bus,
+            frame_id,
+            bucket_ms: self.config.bucket_ms,
+            bucket_starts_us: (0..buckets as u64).map(|i| start + i * bucket_us).collect(),
+            rows,
+        })
+    }
+}
+
+static LIVE_ANALYSIS: Lazy<Mutex<HashMap<String, SessionAnalysis>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Capture Tap
+// ============================================================================
+
+/// Called from capture_store::append_frames_to_session. Sessions without live
+/// analysis cost one lock per batch.
+pub fn tap_frames(session_id: &str, frames: &[FrameMessage]) {
+    let Ok(mut sessions) = LIVE_ANALYSIS.lock() else { return };
+    if let Some(analysis) = sessions.get_mut(session_id) {
+        analysis.ingest(frames);
+    }
+}
+
+/// Drop a session's window. Called on session teardown.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut sessions) = LIVE_ANALYSIS.lock() {
+        sessions.remove(session_id);
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Start (or restart with new settings) windowed analysis on a session. The
+/// window starts empty and fills as frames arrive.
+#[tauri::command(rename_all = "snake_case")]
+pub fn start_live_analysis(session_id: String, config: LiveAnalysisConfig) -> Result<(), String> {
+    config.validate()?;
+    let mut sessions = LIVE_ANALYSIS.lock().map_err(|e| e.to_string())?;
+    sessions.insert(session_id, SessionAnalysis::new(config));
+    Ok(())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn stop_live_analysis(session_id: String) {
+    clear_session(&session_id);
+}
+
+/// Per-ID statistics, cadence and byte entropy over the current window.
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_live_analysis(session_id: String) -> Result<LiveAnalysis, String> {
+    let sessions = LIVE_ANALYSIS.lock().map_err(|e| e.to_string())?;
+    sessions
+        .get(&session_id)
+        .map(|a| a.analysis())
+        .ok_or_else(|| format!("Live analysis is not running on session '{}'", session_id))
+}
+
+/// Per-byte change counts over time for one frame ID in the current window
+/// (None when the ID has no frames in it).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_live_change_heatmap(
+    session_id: String,
+    bus: u8,
+    frame_id: u32,
+) -> Result<Option<LiveChangeHeatmap>, String> {
+    let sessions = LIVE_ANALYSIS.lock().map_err(|e| e.to_string())?;
+    let analysis = sessions
+        .get(&session_id)
+        .ok_or_else(|| format!("Live analysis is not running on session '{}'", session_id))?;
+    Ok(analysis.heatmap(bus, frame_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(frame_id: u32, timestamp_us: u64, bytes: Vec<u8>) -> FrameMessage {
+        test_frame(frame_id, timestamp_us, &bytes)
+    }
+
+    fn session(window_ms: u64) -> SessionAnalysis {
+        SessionAnalysis::new(LiveAnalysisConfig { window_ms, bucket_ms: 100, buses: None })
+    }
+
+    #[test]
+    fn cadence_and_byte_stats_over_window() {
+        let mut s = session(1000);
+        let frames: Vec<_> = (0..5).map(|i| frame(0x100, i * 10_000, vec![i as u8, 0x55])).collect();
+        s.ingest(&frames);
+
+        let a = s.analysis();
+        let stats = &a.frames[0];
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.interval_mean_ms, Some(10.0));
+        assert_eq!(stats.interval_stddev_ms, Some(0.0));
+        assert!((stats.rate_hz - 100.0).abs() < 1e-9);
+        // Byte 0 counts up (every frame changes), byte 1 is static
+        assert_eq!((stats.bytes[0].min, stats.bytes[0].max, stats.bytes[0].changes), (0, 4, 4));
+        assert!((stats.bytes[0].entropy_bits - 5f64.log2()).abs() < 1e-9);
+        assert_eq!((stats.bytes[1].distinct, stats.bytes[1].changes), (1, 0));
+        assert_eq!(stats.bytes[1].entropy_bits, 0.0);
+    }
+
+    #[test]
+    fn eviction_keeps_totals_in_step_with_window() {
+        let mut s = session(100);
+        s.ingest(&[frame(0x100, 0, vec![1]), frame(0x100, 50_000, vec![2])]);
+        s.ingest(&[frame(0x100, 200_000, vec![2]), frame(0x200, 200_000, vec![9])]);
+
+        let a = s.analysis();
+        assert_eq!(a.window_start_us, 100_000);
+        let stats = a.frames.iter().find(|f| f.frame_id == 0x100).unwrap();
+        // Only the 200 ms frame remains; its interval reached outside the window
+        assert_eq!(stats.count, 1);
+        assert_eq!(stats.interval_mean_ms, None);
+        assert_eq!((stats.bytes[0].samples, stats.bytes[0].changes), (1, 0));
+        assert_eq!(a.frames.len(), 2);
+    }
+
+    #[test]
+    fn heatmap_buckets_changes_per_byte() {
+        let mut s = session(1000);
+        s.ingest(&[
+            frame(0x100, 1_000_000, vec![0, 0]),
+            frame(0x100, 1_050_000, vec![1, 0]),
+            frame(0x100, 1_150_000, vec![2, 7]),
+        ]);
+        let h = s.heatmap(0, 0x100).unwrap();
+        let first = h.bucket_starts_us.iter().position(|t| *t == 1_000_000).unwrap();
+        assert_eq!(h.rows[0][first], 1);
+        assert_eq!(h.rows[0][first + 1], 1);
+        assert_eq!(h.rows[1][first + 1], 1);
+        assert!(s.heatmap(0, 0x999).is_none());
+    }
+}
@@ -7,7 +7,7 @@
 //! [`bridge`].
 
 pub mod bridge;
-mod session;
+pub(crate) mod session;
 mod tools;
 mod types;
 
@@ -1,9 +1,10 @@
 // Copyright 2026 Wired Square Pty Ltd
 
-//! Rust-native session open for the MCP server. Mirrors the frontend's open
-//! flow (build Modbus poll groups from the profile's catalog, then create the
-//! reader session) without needing an app window. A keep-alive task touches the
-//! MCP subscriber so the session isn't reaped by the heartbeat watchdog.
+//! Rust-native session open for the MCP server (also used by the remote API).
+//! Mirrors the frontend's open flow (build Modbus poll groups from the
+//! profile's catalog, then create the reader session) without needing an app
+//! window. A keep-alive task touches the client's subscriber so the session
+//! isn't reaped by the heartbeat watchdog.
 
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
@@ -12,7 +13,7 @@ use serde_json::{json, Value};
 
 static SID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
-fn generate_session_id(kind: &str) -> String {
+fn generate_session_id(kind: &str, client: &str) -> String {
     let prefix = if kind.starts_with("modbus") {
         "m"
     } else if kind == "serial" {
@@ -20,10 +21,10 @@ fn generate_session_id(kind: &str) -> String {
     } else {
         "f"
     };
-    format!("{prefix}_mcp{}", SID_COUNTER.fetch_add(1, Ordering::Relaxed))
+    format!("{prefix}_{client}{}", SID_COUNTER.fetch_add(1, Ordering::Relaxed))
 }
 
-/// Touch the MCP subscriber every 10s so the heartbeat watchdog doesn't reap a
+/// Touch the client's subscriber every 10s so the heartbeat watchdog doesn't reap a
 /// headless session. Self-terminates once the session is gone.
 fn spawn_keepalive(session_id: String) {
     tauri::async_runtime::spawn(async move {
@@ -43,6 +44,16 @@ pub async fn open(
     app: tauri::AppHandle,
     profile_id: String,
     session_id: Option<String>,
+) -> Result<Value, String> {
+    open_for(app, profile_id, session_id, "mcp").await
+}
+
+/// `open` on behalf of `client`, which names the session's subscriber and app.
+pub async fn open_for(
+    app: tauri::AppHandle,
+    profile_id: String,
+    session_id: Option<String>,
+    client: &str,
 ) -> Result<Value, String> {
     let settings = crate::settings::load_settings_sync(&app)?;
     let profile = settings
@@ -67,7 +78,7 @@ pub async fn open(
         None
     };
 
-    let sid = session_id.unwrap_or_else(|| generate_session_id(&profile.kind));
+    let sid = session_id.unwrap_or_else(|| generate_session_id(&profile.kind, client));
     let capabilities = crate::sessions::create_reader_session(
         app.clone(),
         sid.clone(),
@@ -78,8 +89,8 @@ pub async fn open(
         None,
         None,
         None,
-        Some("mcp".to_string()),
-        Some("mcp".to_string()),
+        Some(client.to_string()),
+        Some(client.to_string()),
         modbus_polls,
     )
     .await?;
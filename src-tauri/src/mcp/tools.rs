@@ -220,7 +220,7 @@ impl WireTapTools {
             .await
             .into_iter()
             .find(|s| s.session_id == p.session_id)
-            .ok_or_else(|| err(format!("Session '{}' not found", p.session_id)))?;
+            .ok_or_else(|| err(msg!(SESSION_NOT_FOUND, session_id = p.session_id)))?;
         ok_json(info)
     }
 
@@ -448,6 +448,19 @@ impl WireTapTools {
         ok_json(report)
     }
 
+    #[tool(description = "Request → response latency for request/response id pairs (e.g. OBD 0x7DF → 0x7E8): min/mean/p50/p90/p95/p99/max, timeouts, and outliers above Q3 + 1.5·IQR. Source is capture_id, session_id (live session's capture) or profile_id. Set isotp=true on a pair to skip responsePending and consecutive frames.")]
+    async fn response_latency(
+        &self,
+        Parameters(p): Parameters<ResponseLatencyParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let src = crate::analysis::resolve_with_session(p.capture_id, p.session_id, p.profile_id)
+            .map_err(err)?;
+        let stats = crate::analysis::response_latency(&self.app, &src, &p.pairs, p.start_time, p.end_time)
+            .await
+            .map_err(err)?;
+        ok_json(json!({ "pairs": stats }))
+    }
+
     // ── Exposed analytical engines (dispatch capture vs postgres) ────────────
 
     #[tool(description = "Find timestamps where one payload byte of a frame changed value. Source: capture_id or profile_id.")]
@@ -811,7 +824,7 @@ impl WireTapTools {
         Parameters(p): Parameters<AttachSourceParams>,
     ) -> Result<CallToolResult, McpError> {
         if !crate::io::session_exists(&p.session_id).await {
-            return Err(err(format!("Session '{}' not found", p.session_id)));
+            return Err(err(msg!(SESSION_NOT_FOUND, session_id = p.session_id)));
         }
         if !crate::app_registry::is_session_aware_panel(&p.panel) {
             return Err(err(format!(
@@ -334,6 +334,26 @@ pub struct CatalogCoverageParams {
     pub end_time: Option<String>,
 }
 
+/// Request → response latency over a capture, a live session's capture or a
+/// postgres profile.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ResponseLatencyParams {
+    #[serde(default)]
+    pub capture_id: Option<String>,
+    /// Live session; resolves to the capture it is recording into.
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub profile_id: Option<String>,
+    /// Request/response id pairs to time, e.g. `{"request_id": 2015,
+    /// "response_ids": [2024], "isotp": true}` for OBD 0x7DF → 0x7E8.
+    pub pairs: Vec<crate::analysis::LatencyPair>,
+    #[serde(default)]
+    pub start_time: Option<String>,
+    #[serde(default)]
+    pub end_time: Option<String>,
+}
+
 // ── Exposed analytical engines (capture OR postgres) ─────────────────────────
 
 /// Base params for a per-frame analytical query (frame_changes, first_last).
@@ -0,0 +1,131 @@
+// ui/src-tauri/src/menu_state.rs
+//
+// Backend-computed Session menu state. The native Session menu used to be
+// driven entirely by the focused panel calling `update_menu_state` with its
+// own view of the session, which desyncs whenever that window is busy (or
+// throttled in the background) when the session changes underneath it.
+//
+// Instead the focused panel names its session once with `sync_menu_state`;
+// the menu is computed here from the session itself and refreshed whenever
+// that session's state or joiner count changes (io::emit_state_change /
+// emit_joiner_count_change call `session_changed`). `get_menu_state` exposes
+// the same booleans for callers that only want to read them.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::io::{self, IOState, TemporalMode};
+
+/// Exactly what the Session menu needs to enable/disable its items.
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+pub struct MenuSessionState {
+    /// False when the session doesn't exist (all items disabled)
+    pub has_session: bool,
+    /// Source name shown in the menu ("Capture" when replaying a capture)
+    pub profile_name: Option<String>,
+    /// Running or paused
+    pub is_streaming: bool,
+    pub is_paused: bool,
+    pub can_pause: bool,
+    pub joiner_count: u32,
+}
+
+/// The session the menu currently reflects, and whether the focused panel
+/// supports bookmarks.
+struct MenuFocus {
+    app: AppHandle,
+    session_id: String,
+    has_bookmarks: bool,
+}
+
+static MENU_FOCUS: Lazy<Mutex<Option<MenuFocus>>> = Lazy::new(|| Mutex::new(None));
+
+/// Compute the menu state for a session.
+pub async fn menu_state_for_session(session_id: &str) -> MenuSessionState {
+    let (Some(state), Some(capabilities)) = (
+        io::get_session_state(session_id).await,
+        io::get_session_capabilities(session_id).await,
+    ) else {
+        return MenuSessionState::default();
+    };
+
+    let profile_name = if capabilities.traits.temporal_mode == TemporalMode::Capture {
+        Some("Capture".to_string())
+    } else {
+        let names = io::get_session_source_names(session_id).await;
+        if names.is_empty() {
+            crate::sessions::get_session_profile_ids(session_id).into_iter().next()
+        } else {
+            Some(names.join(" + "))
+        }
+    };
+
+    MenuSessionState {
+        has_session: true,
+        profile_name,
+        is_streaming: matches!(state, IOState::Running | IOState::Paused),
+        is_paused: state == IOState::Paused,
+        can_pause: capabilities.can_pause,
+        joiner_count: io::get_session_joiner_count(session_id).await as u32,
+    }
+}
+
+async fn refresh(app: &AppHandle, session_id: &str, has_bookmarks: bool) {
+    let state = menu_state_for_session(session_id).await;
+    crate::apply_menu_state(app, &state, has_bookmarks);
+}
+
+/// Called when a session's state or joiner count changes; refreshes the menu
+/// if it reflects that session.
+pub fn session_changed(session_id: &str) {
+    let Some((app, has_bookmarks)) = MENU_FOCUS.lock().ok().and_then(|focus| {
+        focus
+            .as_ref()
+            .filter(|f| f.session_id == session_id)
+            .map(|f| (f.app.clone(), f.has_bookmarks))
+    }) else {
+        return;
+    };
+    let session_id = session_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        refresh(&app, &session_id, has_bookmarks).await;
+    });
+}
+
+/// Stop following a session (a non-session panel took focus).
+pub fn clear_focus() {
+    if let Ok(mut focus) = MENU_FOCUS.lock() {
+        *focus = None;
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Menu booleans for a session, computed from the session itself.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_menu_state(session_id: String) -> MenuSessionState {
+    menu_state_for_session(&session_id).await
+}
+
+/// Make the Session menu follow a session (None = no session focused). The
+/// menu is updated now and again whenever the session changes.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn sync_menu_state(app: AppHandle, session_id: Option<String>, has_bookmarks: bool) {
+    let Some(session_id) = session_id else {
+        clear_focus();
+        crate::apply_menu_state(&app, &MenuSessionState::default(), false);
+        return;
+    };
+    if let Ok(mut focus) = MENU_FOCUS.lock() {
+        *focus = Some(MenuFocus {
+            app: app.clone(),
+            session_id: session_id.clone(),
+            has_bookmarks,
+        });
+    }
+    refresh(&app, &session_id, has_bookmarks).await;
+}
@@ -0,0 +1,195 @@
+// ui/src-tauri/src/messages.rs
+//
+// Catalogue of user-facing backend messages. Each message has a stable key,
+// named parameters and an English template; `msg!` builds one and it converts
+// into the plain English `String` the command error paths already return, so
+// callers and the frontend see the same text as before. The frontend can turn
+// any such text back into key + parameters (`describe_message`) to localise it
+// or match on the key instead of the wording, and fetches the templates with
+// `get_message_catalogue`.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// One catalogue entry.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct MessageDef {
+    pub key: &'static str,
+    /// English text with `{name}` placeholders
+    pub template: &'static str,
+}
+
+macro_rules! catalogue {
+    ($($name:ident = $key:literal => $template:literal;)*) => {
+        $(pub const $name: MessageDef = MessageDef { key: $key, template: $template };)*
+        /// Every message, in declaration order.
+        pub const CATALOGUE: &[MessageDef] = &[$($name),*];
+    };
+}
+
+catalogue! {
+    SESSION_NOT_FOUND = "session.not_found" => "Session '{session_id}' not found";
+    CAPTURE_NOT_FOUND = "capture.not_found" => "Capture '{capture_id}' not found";
+    CAPTURE_NOT_FRAMES = "capture.not_frame_capture" => "Capture '{capture_id}' not found or is not a frame capture";
+    CAPTURE_EMPTY = "capture.empty" => "Capture '{capture_id}' has no frames";
+    CAPTURE_NOT_BYTES = "capture.not_byte_capture" => "Capture '{capture_id}' not found or is not a byte capture";
+    PROFILE_NOT_FOUND = "profile.not_found" => "Profile not found: {profile_id}";
+    SETTINGS_LOAD_FAILED = "settings.load_failed" => "Failed to load settings: {error}";
+    INVALID_STANDARD_ID = "frame.invalid_standard_id" => "Invalid standard ID: {id}";
+    INVALID_EXTENDED_ID = "frame.invalid_extended_id" => "Invalid extended ID: {id}";
+    QUOTA_EXCEEDED = "session.quota_exceeded" => "Session quota exceeded: {quota} limit is {limit} (in use: {in_use})";
+    PROFILE_INVALID = "profile.invalid" => "Profile '{profile}' has invalid settings: {errors}";
+}
+
+/// A rendered message: key and parameters alongside the English text.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Message {
+    pub key: &'static str,
+    pub params: BTreeMap<String, String>,
+    pub text: String,
+}
+
+/// Build a [`Message`] from a catalogue entry and named parameters:
+/// `msg!(SESSION_NOT_FOUND, session_id = id)`.
+macro_rules! msg {
+    ($def:ident $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::messages::$def.with(&[$((stringify!($name), $value.to_string())),*])
+    };
+}
+
+impl MessageDef {
+    /// Render with the given parameters. Placeholders without a value are left as-is.
+    pub fn with(&self, params: &[(&str, String)]) -> Message {
+        let params: BTreeMap<String, String> =
+            params.iter().map(|(k, v)| (k.to_string(), v.clone())).collect();
+        let mut text = String::with_capacity(self.template.len());
+        for segment in segments(self.template) {
+            match segment {
+                Segment::Literal(s) => text.push_str(s),
+                Segment::Param(name) => match params.get(name) {
+                    Some(v) => text.push_str(v),
+                    None => {
+                        text.push('{');
+                        text.push_str(name);
+                        text.push('}');
+                    }
+                },
+            }
+        }
+        Message { key: self.key, params, text }
+    }
+
+    /// Recover the parameters from text rendered from this entry.
+    fn parse(&self, text: &str) -> Option<Message> {
+        let segments = segments(self.template);
+        let mut params = BTreeMap::new();
+        let mut rest = text;
+        let mut pending: Option<&str> = None;
+        for segment in &segments {
+            match *segment {
+                Segment::Param(name) => pending = Some(name),
+                Segment::Literal(lit) => match pending.take() {
+                    Some(name) => {
+                        let at = rest.find(lit)?;
+                        params.insert(name.to_string(), rest[..at].to_string());
+                        rest = &rest[at + lit.len()..];
+                    }
+                    None => rest = rest.strip_prefix(lit)?,
+                },
+            }
+        }
+        match pending {
+            Some(name) => {
+                params.insert(name.to_string(), rest.to_string());
+            }
+            None if !rest.is_empty() => return None,
+            None => {}
+        }
+        Some(Message { key: self.key, params, text: text.to_string() })
+    }
+}
+
+enum Segment<'a> {
+    Literal(&'a str),
+    Param(&'a str),
+}
+
+fn segments(template: &str) -> Vec<Segment<'_>> {
+    let mut out = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}') else { break };
+        if open > 0 {
+            out.push(Segment::Literal(&rest[..open]));
+        }
+        out.push(Segment::Param(&rest[open + 1..open + close]));
+        rest = &rest[open + close + 1..];
+    }
+    if !rest.is_empty() {
+        out.push(Segment::Literal(rest));
+    }
+    out
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl From<Message> for String {
+    fn from(m: Message) -> String {
+        m.text
+    }
+}
+
+/// Match English text (e.g. a command error) against the catalogue. The
+/// longest matching template wins, so a more specific message beats a prefix.
+pub fn describe(text: &str) -> Option<Message> {
+    let mut defs: Vec<&MessageDef> = CATALOGUE.iter().collect();
+    defs.sort_by_key(|d| std::cmp::Reverse(d.template.len()));
+    defs.into_iter().find_map(|d| d.parse(text))
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// All catalogue entries, for building frontend translations.
+#[tauri::command]
+pub fn get_message_catalogue() -> Vec<MessageDef> {
+    CATALOGUE.to_vec()
+}
+
+/// Key and parameters for backend English text, or None if it isn't a catalogued message.
+#[tauri::command(rename_all = "snake_case")]
+pub fn describe_message(text: String) -> Option<Message> {
+    describe(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_and_parses_back() {
+        let m = msg!(SESSION_NOT_FOUND, session_id = "abc");
+        assert_eq!(m.text, "Session 'abc' not found");
+        assert_eq!(describe(&m.text), Some(m.clone()));
+        let s: String = m.into();
+        assert_eq!(s, "Session 'abc' not found");
+
+        let p = describe("Profile not found: can0 bench").unwrap();
+        assert_eq!(p.key, "profile.not_found");
+        assert_eq!(p.params["profile_id"], "can0 bench");
+    }
+
+    #[test]
+    fn prefers_the_most_specific_template() {
+        let m = describe("Capture 'x' not found or is not a byte capture").unwrap();
+        assert_eq!(m.key, "capture.not_byte_capture");
+        assert_eq!(m.params["capture_id"], "x");
+        assert!(describe("Something unrelated").is_none());
+    }
+}
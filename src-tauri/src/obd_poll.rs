@@ -0,0 +1,483 @@
+// ui/src-tauri/src/obd_poll.rs
+//
+// OBD-II PID polling — turns a transmit-capable CAN session into a quick
+// vehicle health dashboard. Standard mode 01 requests for the configured
+// PIDs go out on the functional address (0x7DF, or 0x18DB33F1 with 29-bit
+// IDs) every interval, plus a mode 09 VIN request until an ECU answers it.
+// Replies from 0x7E8-0x7EF (0x18DAF1xx) are decoded here and recorded in the
+// latest-value cache like catalogue signals, so they get the same
+// LatestValuesChanged reports and trends without a catalogue.
+//
+// Requests go through io::session_transmit; replies reach the poller through
+// a tap in capture_store::append_frames_to_session (same shape as the
+// responder). The multi-frame VIN reply is reassembled here: the poller
+// answers the ECU's first frame with a flow control on its physical request ID.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::io::{self, CanTransmitFrame, FrameMessage, TransmitPayload};
+use crate::latest_values::LatestValue;
+
+/// Functional (broadcast) request IDs
+const FUNCTIONAL_ID: u32 = 0x7DF;
+const FUNCTIONAL_ID_EXT: u32 = 0x18DB_33F1;
+/// First ECU response ID (11-bit); physical request IDs sit 8 below
+const RESPONSE_ID_BASE: u32 = 0x7E8;
+const PHYSICAL_ID_BASE: u32 = 0x7E0;
+/// 29-bit response IDs are 0x18DAF1xx, physical requests 0x18DAxxF1
+const RESPONSE_ID_EXT_BASE: u32 = 0x18DA_F100;
+const PHYSICAL_ID_EXT_BASE: u32 = 0x18DA_00F1;
+
+const MODE_CURRENT_DATA: u8 = 0x01;
+const MODE_VEHICLE_INFO: u8 = 0x09;
+const PID_VIN: u8 = 0x02;
+/// Positive responses carry the mode + 0x40
+const POSITIVE_OFFSET: u8 = 0x40;
+
+const PADDING: u8 = 0x00;
+/// Gap between requests within a cycle, so slow ECUs keep up
+const REQUEST_GAP: Duration = Duration::from_millis(25);
+const MIN_INTERVAL_MS: u64 = 100;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObdPollConfig {
+    /// Mode 01 PIDs requested every cycle
+    #[serde(default = "default_pids")]
+    pub pids: Vec<u8>,
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+    #[serde(default)]
+    pub bus: u8,
+    /// Use 29-bit request/response IDs
+    #[serde(default)]
+    pub is_extended: bool,
+    /// Request the VIN (mode 09 PID 02) until an ECU answers
+    #[serde(default = "default_read_vin")]
+    pub read_vin: bool,
+}
+
+fn default_pids() -> Vec<u8> {
+    vec![0x0C, 0x0D, 0x05, 0x04, 0x11, 0x42]
+}
+
+fn default_interval_ms() -> u64 {
+    1000
+}
+
+fn default_read_vin() -> bool {
+    true
+}
+
+/// Snapshot of a running poller, fetched by the frontend.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ObdPollState {
+    pub session_id: String,
+    pub running: bool,
+    pub cycles: u64,
+    pub requests_sent: u64,
+    /// Decoded PID and VIN replies
+    pub responses: u64,
+    pub transmit_errors: u64,
+    pub last_error: Option<String>,
+    pub vin: Option<String>,
+}
+
+/// A mode 01 PID this poller decodes.
+struct PidInfo {
+    pid: u8,
+    name: &'static str,
+    unit: &'static str,
+    /// Data bytes (A, B, ...) the formula uses
+    len: usize,
+    decode: fn(&[u8]) -> f64,
+}
+
+fn word(d: &[u8]) -> f64 {
+    f64::from(u16::from_be_bytes([d[0], d[1]]))
+}
+
+/// The well-known mode 01 PIDs (SAE J1979 formulas).
+const PIDS: &[PidInfo] = &[
+    PidInfo { pid: 0x04, name: "Engine load", unit: "%", len: 1, decode: |d| f64::from(d[0]) * 100.0 / 255.0 },
+    PidInfo { pid: 0x05, name: "Coolant temperature", unit: "°C", len: 1, decode: |d| f64::from(d[0]) - 40.0 },
+    PidInfo { pid: 0x0B, name: "Intake manifold pressure", unit: "kPa", len: 1, decode: |d| f64::from(d[0]) },
+    PidInfo { pid: 0x0C, name: "Engine speed", unit: "rpm", len: 2, decode: |d| word(d) / 4.0 },
+    PidInfo { pid: 0x0D, name: "Vehicle speed", unit: "km/h", len: 1, decode: |d| f64::from(d[0]) },
+    PidInfo { pid: 0x0F, name: "Intake air temperature", unit: "°C", len: 1, decode: |d| f64::from(d[0]) - 40.0 },
+    PidInfo { pid: 0x10, name: "MAF air flow rate", unit: "g/s", len: 2, decode: |d| word(d) / 100.0 },
+    PidInfo { pid: 0x11, name: "Throttle position", unit: "%", len: 1, decode: |d| f64::from(d[0]) * 100.0 / 255.0 },
+    PidInfo { pid: 0x1F, name: "Run time since engine start", unit: "s", len: 2, decode: word },
+    PidInfo { pid: 0x2F, name: "Fuel tank level", unit: "%", len: 1, decode: |d| f64::from(d[0]) * 100.0 / 255.0 },
+    PidInfo { pid: 0x42, name: "Control module voltage", unit: "V", len: 2, decode: |d| word(d) / 1000.0 },
+    PidInfo { pid: 0x46, name: "Ambient air temperature", unit: "°C", len: 1, decode: |d| f64::from(d[0]) - 40.0 },
+    PidInfo { pid: 0x5C, name: "Engine oil temperature", unit: "°C", len: 1, decode: |d| f64::from(d[0]) - 40.0 },
+];
+
+fn pid_info(pid: u8) -> Option<&'static PidInfo> {
+    PIDS.iter().find(|p| p.pid == pid)
+}
+
+// ============================================================================
+// State management
+// ============================================================================
+
+/// Frame tap senders: session_id -> poller task input.
+static OBD_TAPS: Lazy<StdMutex<HashMap<String, mpsc::UnboundedSender<Vec<FrameMessage>>>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+static OBD_STATES: Lazy<StdMutex<HashMap<String, ObdPollState>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+fn update_state(session_id: &str, f: impl FnOnce(&mut ObdPollState)) {
+    if let Ok(mut states) = OBD_STATES.lock() {
+        if let Some(state) = states.get_mut(session_id) {
+            f(state);
+        }
+    }
+}
+
+/// Called from capture_store::append_frames_to_session to forward received
+/// frames to the session's poller (if one is running).
+pub fn tap_frames(session_id: &str, frames: &[FrameMessage]) {
+    let taps = match OBD_TAPS.lock() {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+    if let Some(sender) = taps.get(session_id) {
+        let rx: Vec<FrameMessage> = frames
+            .iter()
+            .filter(|f| f.direction.as_deref() != Some("tx"))
+            .cloned()
+            .collect();
+        if !rx.is_empty() {
+            let _ = sender.send(rx);
+        }
+    }
+}
+
+/// Stop a session's poller. Dropping the tap sender ends the task.
+/// Called on explicit stop and when the session is destroyed.
+pub fn stop_obd_poll(session_id: &str) {
+    let removed = OBD_TAPS.lock().ok().and_then(|mut t| t.remove(session_id));
+    if removed.is_some() {
+        update_state(session_id, |s| s.running = false);
+        tlog!("[obd_poll:{}] Stopped", session_id);
+    }
+}
+
+// ============================================================================
+// Requests and replies
+// ============================================================================
+
+/// ECU index (0-7 for 11-bit, the source address for 29-bit) of a reply ID.
+fn response_ecu(frame_id: u32, is_extended: bool) -> Option<u8> {
+    if is_extended {
+        (frame_id & 0xFFFF_FF00 == RESPONSE_ID_EXT_BASE).then_some(frame_id as u8)
+    } else {
+        (RESPONSE_ID_BASE..RESPONSE_ID_BASE + 8)
+            .contains(&frame_id)
+            .then(|| (frame_id - RESPONSE_ID_BASE) as u8)
+    }
+}
+
+/// Single-frame request for one PID on the functional address.
+fn request_frame(config: &ObdPollConfig, mode: u8, pid: u8) -> CanTransmitFrame {
+    CanTransmitFrame {
+        frame_id: if config.is_extended { FUNCTIONAL_ID_EXT } else { FUNCTIONAL_ID },
+        data: vec![0x02, mode, pid, PADDING, PADDING, PADDING, PADDING, PADDING],
+        bus: config.bus,
+        is_extended: config.is_extended,
+        is_fd: false,
+        is_brs: false,
+        is_rtr: false,
+    }
+}
+
+/// Flow control (continue, no block limit, no separation time) sent to the
+/// ECU that started a multi-frame reply.
+fn flow_control_frame(config: &ObdPollConfig, ecu: u8) -> CanTransmitFrame {
+    let frame_id = if config.is_extended {
+        PHYSICAL_ID_EXT_BASE | (u32::from(ecu) << 8)
+    } else {
+        PHYSICAL_ID_BASE + u32::from(ecu)
+    };
+    CanTransmitFrame {
+        frame_id,
+        data: vec![0x30, 0x00, 0x00, PADDING, PADDING, PADDING, PADDING, PADDING],
+        ..request_frame(config, 0, 0)
+    }
+}
+
+/// ISO-TP receive state for one ECU.
+#[derive(Default)]
+struct IsoTpRx {
+    expected: usize,
+    payload: Vec<u8>,
+}
+
+/// What one reply frame amounted to.
+#[derive(Debug, PartialEq)]
+enum RxEvent {
+    /// A complete response payload (service ID onwards)
+    Complete(Vec<u8>),
+    /// First frame of a multi-frame reply; the ECU waits for flow control
+    FirstFrame,
+    Pending,
+}
+
+impl IsoTpRx {
+    fn feed(&mut self, bytes: &[u8]) -> RxEvent {
+        let Some(&pci) = bytes.first() else {
+            return RxEvent::Pending;
+        };
+        match pci >> 4 {
+            0x0 => {
+                let len = usize::from(pci & 0x0F);
+                match bytes.get(1..1 + len) {
+                    Some(payload) if len > 0 => RxEvent::Complete(payload.to_vec()),
+                    _ => RxEvent::Pending,
+                }
+            }
+            0x1 if bytes.len() >= 2 => {
+                self.expected = (usize::from(pci & 0x0F) << 8) | usize::from(bytes[1]);
+                self.payload = bytes[2..].to_vec();
+                RxEvent::FirstFrame
+            }
+            0x2 if self.expected > 0 => {
+                self.payload.extend_from_slice(&bytes[1..]);
+                if self.payload.len() < self.expected {
+                    return RxEvent::Pending;
+                }
+                self.payload.truncate(self.expected);
+                self.expected = 0;
+                RxEvent::Complete(std::mem::take(&mut self.payload))
+            }
+            _ => RxEvent::Pending,
+        }
+    }
+}
+
+/// Decoded value(s) of one response payload: mode 01 PID replies and the
+/// mode 09 VIN. Returns (name, value, display, unit).
+fn decode_response(payload: &[u8]) -> Option<(String, f64, String, Option<String>)> {
+    match payload {
+        [sid, pid, data @ ..] if *sid == MODE_CURRENT_DATA + POSITIVE_OFFSET => {
+            let info = pid_info(*pid)?;
+            let data = data.get(..info.len)?;
+            let value = (info.decode)(data);
+            let display = format!("{} {}", (value * 100.0).round() / 100.0, info.unit);
+            Some((info.name.to_string(), value, display, Some(info.unit.to_string())))
+        }
+        // 49 02 <item count> then 17 ASCII characters
+        [sid, pid, _count, vin @ ..] if *sid == MODE_VEHICLE_INFO + POSITIVE_OFFSET && *pid == PID_VIN => {
+            let vin: String = vin
+                .iter()
+                .filter(|b| b.is_ascii_alphanumeric())
+                .map(|&b| b as char)
+                .collect();
+            (!vin.is_empty()).then(|| ("VIN".to_string(), 0.0, vin, None))
+        }
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Poller task
+// ============================================================================
+
+async fn send(session_id: &str, frame: CanTransmitFrame) {
+    match io::session_transmit(session_id, &TransmitPayload::CanFrame(frame)).await {
+        Ok(r) if r.success => update_state(session_id, |s| s.requests_sent += 1),
+        Ok(r) => update_state(session_id, |s| {
+            s.transmit_errors += 1;
+            s.last_error = r.error;
+        }),
+        Err(e) => update_state(session_id, |s| {
+            s.transmit_errors += 1;
+            s.last_error = Some(e);
+        }),
+    }
+}
+
+async fn run_poller(
+    session_id: String,
+    config: ObdPollConfig,
+    mut frame_rx: mpsc::UnboundedReceiver<Vec<FrameMessage>>,
+) {
+    let mut ticker = tokio::time::interval(Duration::from_millis(config.interval_ms));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    let mut receivers: HashMap<u8, IsoTpRx> = HashMap::new();
+    let mut vin_read = !config.read_vin;
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                for &pid in &config.pids {
+                    send(&session_id, request_frame(&config, MODE_CURRENT_DATA, pid)).await;
+                    tokio::time::sleep(REQUEST_GAP).await;
+                }
+                if !vin_read {
+                    send(&session_id, request_frame(&config, MODE_VEHICLE_INFO, PID_VIN)).await;
+                }
+                update_state(&session_id, |s| s.cycles += 1);
+            }
+            batch = frame_rx.recv() => {
+                let Some(batch) = batch else { break };
+                let mut samples = Vec::new();
+                for frame in &batch {
+                    if frame.bus != config.bus {
+                        continue;
+                    }
+                    let Some(ecu) = response_ecu(frame.frame_id, frame.is_extended) else {
+                        continue;
+                    };
+                    let payload = match receivers.entry(ecu).or_default().feed(&frame.bytes) {
+                        RxEvent::Complete(payload) => payload,
+                        RxEvent::FirstFrame => {
+                            send(&session_id, flow_control_frame(&config, ecu)).await;
+                            continue;
+                        }
+                        RxEvent::Pending => continue,
+                    };
+                    let Some((name, value, display, unit)) = decode_response(&payload) else {
+                        continue;
+                    };
+                    if name == "VIN" {
+                        vin_read = true;
+                        tlog!("[obd_poll:{}] VIN {} from 0x{:X}", session_id, display, frame.frame_id);
+                        let vin = display.clone();
+                        update_state(&session_id, |s| s.vin = Some(vin));
+                    }
+                    update_state(&session_id, |s| s.responses += 1);
+                    samples.push(LatestValue {
+                        frame_id: frame.frame_id,
+                        bus: frame.bus,
+                        name,
+                        mux_value: None,
+                        raw: value,
+                        value,
+                        display,
+                        unit,
+                        timestamp_us: frame.timestamp_us,
+                        updates: 0,
+                    });
+                }
+                crate::latest_values::record_values(&session_id, samples);
+            }
+        }
+    }
+    tlog!("[obd_poll:{}] Task ended", session_id);
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Start (or restart) OBD-II polling on a session.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn obd_poll_start(session_id: String, config: ObdPollConfig) -> Result<ObdPollState, String> {
+    let caps = io::get_session_capabilities(&session_id)
+        .await
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
+    if !caps.traits.tx_frames {
+        return Err("This session does not support CAN transmission".to_string());
+    }
+    if config.interval_ms < MIN_INTERVAL_MS {
+        return Err(format!("Poll interval must be at least {} ms", MIN_INTERVAL_MS));
+    }
+    if config.pids.is_empty() && !config.read_vin {
+        return Err("Nothing to poll: give at least one PID or read the VIN".to_string());
+    }
+    if let Some(pid) = config.pids.iter().find(|&&p| pid_info(p).is_none()) {
+        let known: Vec<String> = PIDS.iter().map(|p| format!("{:02X}", p.pid)).collect();
+        return Err(format!("PID {:02X} is not decoded (supported: {})", pid, known.join(", ")));
+    }
+
+    stop_obd_poll(&session_id);
+
+    let state = ObdPollState {
+        session_id: session_id.clone(),
+        running: true,
+        ..Default::default()
+    };
+    if let Ok(mut states) = OBD_STATES.lock() {
+        states.insert(session_id.clone(), state.clone());
+    }
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    if let Ok(mut taps) = OBD_TAPS.lock() {
+        taps.insert(session_id.clone(), tx);
+    }
+    tlog!(
+        "[obd_poll:{}] Started ({} PIDs every {} ms on bus {}, VIN {})",
+        session_id, config.pids.len(), config.interval_ms, config.bus, config.read_vin
+    );
+    tauri::async_runtime::spawn(run_poller(session_id, config, rx));
+    Ok(state)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn obd_poll_stop(session_id: String) {
+    stop_obd_poll(&session_id);
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_obd_poll_state(session_id: String) -> Option<ObdPollState> {
+    OBD_STATES.lock().ok().and_then(|s| s.get(&session_id).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_well_known_pids() {
+        let (name, value, display, unit) = decode_response(&[0x41, 0x0C, 0x1A, 0xF8]).unwrap();
+        assert_eq!((name.as_str(), value, unit.as_deref()), ("Engine speed", 1726.0, Some("rpm")));
+        assert_eq!(display, "1726 rpm");
+        assert_eq!(decode_response(&[0x41, 0x05, 0x7B]).unwrap().1, 83.0);
+        assert_eq!(decode_response(&[0x41, 0x0D, 0x32]).unwrap().1, 50.0);
+        // Truncated reply, unknown PID, negative response
+        assert!(decode_response(&[0x41, 0x0C, 0x1A]).is_none());
+        assert!(decode_response(&[0x41, 0xA6, 0, 0, 0, 0]).is_none());
+        assert!(decode_response(&[0x7F, 0x01, 0x12]).is_none());
+    }
+
+    #[test]
+    fn reassembles_vin() {
+        let mut rx = IsoTpRx::default();
+        assert_eq!(rx.feed(&[0x10, 0x14, 0x49, 0x02, 0x01, b'1', b'G', b'1']), RxEvent::FirstFrame);
+        assert_eq!(rx.feed(&[0x21, b'J', b'C', b'5', b'4', b'4', b'4', b'R']), RxEvent::Pending);
+        let RxEvent::Complete(payload) = rx.feed(&[0x22, b'7', b'2', b'5', b'2', b'3', b'6', b'7'])
+        else {
+            panic!("VIN not complete");
+        };
+        let (name, _, vin, _) = decode_response(&payload).unwrap();
+        assert_eq!((name.as_str(), vin.as_str()), ("VIN", "1G1JC5444R7252367"));
+    }
+
+    #[test]
+    fn request_and_response_ids() {
+        let mut config: ObdPollConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.pids, default_pids());
+        let req = request_frame(&config, MODE_CURRENT_DATA, 0x0C);
+        assert_eq!((req.frame_id, req.data[..3].to_vec()), (0x7DF, vec![0x02, 0x01, 0x0C]));
+        assert_eq!(response_ecu(0x7E9, false), Some(1));
+        assert_eq!(response_ecu(0x7DF, false), None);
+        assert_eq!(flow_control_frame(&config, 1).frame_id, 0x7E1);
+
+        config.is_extended = true;
+        assert_eq!(request_frame(&config, MODE_CURRENT_DATA, 0x0C).frame_id, 0x18DB_33F1);
+        assert_eq!(response_ecu(0x18DA_F110, true), Some(0x10));
+        assert_eq!(flow_control_frame(&config, 0x10).frame_id, 0x18DA_10F1);
+    }
+}
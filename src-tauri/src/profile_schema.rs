@@ -0,0 +1,757 @@
+// ui/src-tauri/src/profile_schema.rs
+//
+// Per-kind schemas for IO profile connection settings. A profile's
+// `connection` is a free-form JSON map and every driver reads it with
+// `.get("...").unwrap_or(default)`, so a typo like "buad_rate" used to fall
+// back to the default without a word. Each profile kind now declares its
+// fields here; the table is exported as JSON Schema for the frontend
+// (`get_profile_schema`) and checked on save and on session creation.
+//
+// Numeric fields accept numeric strings (decimal, or hex with a `0x` prefix),
+// because the profile editor stores most numbers as strings and the drivers
+// parse both. Keys starting with `_`
+// are internal bookkeeping (e.g. `_probed_bus_count`) and are never checked.
+// An unknown key is an error when it's a near miss of a known field, and a
+// warning otherwise, so settings written by newer builds still load.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::settings::IOProfile;
+
+// ============================================================================
+// Field specs
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FieldType {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Array,
+    Object,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct FieldSpec {
+    name: &'static str,
+    ty: FieldType,
+    description: &'static str,
+    required: bool,
+    /// Allowed values (string fields only; empty = any)
+    values: &'static [&'static str],
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl FieldSpec {
+    const fn new(name: &'static str, ty: FieldType, description: &'static str) -> Self {
+        Self { name, ty, description, required: false, values: &[], min: None, max: None }
+    }
+
+    const fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    const fn one_of(mut self, values: &'static [&'static str]) -> Self {
+        self.values = values;
+        self
+    }
+
+    const fn range(mut self, min: f64, max: f64) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+}
+
+use FieldType::{Array, Boolean, Integer, Number, Object, String as Str};
+
+const fn f(name: &'static str, ty: FieldType, description: &'static str) -> FieldSpec {
+    FieldSpec::new(name, ty, description)
+}
+
+const HOST: FieldSpec = f("host", Str, "Host name or IP address");
+const TCP_PORT: FieldSpec = f("port", Integer, "TCP port").range(1.0, 65535.0);
+const SERIAL_PORT: FieldSpec = f("port", Str, "Serial port path (e.g. /dev/ttyACM0, COM3)").required();
+const BAUD_RATE: FieldSpec = f("baud_rate", Integer, "Serial baud rate").range(300.0, 12_000_000.0);
+const DATA_BITS: FieldSpec = f("data_bits", Integer, "Serial data bits").range(5.0, 8.0);
+const STOP_BITS: FieldSpec = f("stop_bits", Integer, "Serial stop bits").range(1.0, 2.0);
+const PARITY: FieldSpec = f("parity", Str, "Serial parity").one_of(&["none", "odd", "even"]);
+const TIMEOUT: FieldSpec = f("timeout", Number, "Connection timeout in seconds").range(0.0, 3600.0);
+const BITRATE: FieldSpec = f("bitrate", Integer, "CAN bitrate in bit/s").range(1_000.0, 1_000_000.0);
+const DATA_BITRATE: FieldSpec = f("data_bitrate", Integer, "CAN FD data-phase bitrate in bit/s").range(1_000.0, 16_000_000.0);
+const ENABLE_FD: FieldSpec = f("enable_fd", Boolean, "Enable CAN FD");
+const LISTEN_ONLY: FieldSpec = f("listen_only", Boolean, "Listen-only mode (no ACK, no transmit)");
+const HARDWARE_TIMESTAMPS: FieldSpec = f("hardware_timestamps", Boolean, "Prefer the device's hardware timestamps over host receive time");
+const ERROR_FRAMES: FieldSpec = f("error_frames", Boolean, "Capture the controller's CAN error frames");
+const INTERFACES: FieldSpec = f("interfaces", Array, "Per-interface configuration");
+const USERNAME: FieldSpec = f("username", Str, "User name");
+const PASSWORD: FieldSpec = f("password", Str, "Password (stored in the keyring when saved from the editor)");
+const START: FieldSpec = f("start", Str, "Default replay start time");
+const END: FieldSpec = f("end", Str, "Default replay end time");
+const LIMIT: FieldSpec = f("limit", Integer, "Default maximum number of frames").range(0.0, f64::MAX);
+const SPEED: FieldSpec = f("speed", Number, "Default replay speed (0 = unpaced)").range(0.0, f64::MAX);
+const DEFAULT_SPEED: FieldSpec = f("default_speed", Number, "Replay speed preselected in the UI").range(0.0, f64::MAX);
+const BATCH_SIZE: FieldSpec = f("batch_size", Integer, "Rows fetched per query").range(1.0, 1_000_000.0);
+const FRAMING_MODE: FieldSpec = f("framing_mode", Str, "Framing mode preselected in the UI");
+
+/// Fields every kind may carry.
+const COMMON: &[FieldSpec] = &[
+    f("bus_override", Integer, "Output bus for single-bus sources").range(0.0, 255.0),
+];
+
+const MQTT: &[FieldSpec] = &[
+    HOST,
+    TCP_PORT,
+    USERNAME,
+    PASSWORD,
+    f("formats", Object, "Topic formats to subscribe to (json, savvycan, decode)"),
+    f("tls", Boolean, "Connect over TLS"),
+    f("tls_ca_cert", Str, "PEM CA certificate(s) to trust (system roots when unset)"),
+    f("tls_client_cert", Str, "PEM client certificate for mutual TLS (stored in the keyring)"),
+    f("tls_client_key", Str, "PEM client private key for mutual TLS (stored in the keyring)"),
+    f("tls_alpn", Str, "Comma-separated ALPN protocols to offer"),
+    f("tls_insecure_skip_verify", Boolean, "Accept any server certificate (testing only)"),
+];
+
+const POSTGRES: &[FieldSpec] = &[
+    HOST,
+    TCP_PORT,
+    f("database", Str, "Database name"),
+    f("db", Str, "Database name (legacy alias of database)"),
+    USERNAME,
+    f("user", Str, "User name (legacy alias of username)"),
+    PASSWORD,
+    f("sslmode", Str, "TLS mode").one_of(&["disable", "allow", "prefer", "require", "verify-ca", "verify-full"]),
+    f("source_type", Str, "Table layout to replay").one_of(&["can_frame", "modbus_frame", "serial_frame", "serial_raw"]),
+    START,
+    END,
+    LIMIT,
+    SPEED,
+    DEFAULT_SPEED,
+    BATCH_SIZE,
+    FRAMING_MODE,
+];
+
+const WIRETAP: &[FieldSpec] = &[
+    f("url", Str, "Backend gateway base URL").required(),
+    f("database", Str, "Backend database name"),
+    f("api_key", Str, "API key (stored in the keyring when saved from the editor)"),
+    START,
+    END,
+    LIMIT,
+    SPEED,
+    DEFAULT_SPEED,
+    BATCH_SIZE,
+];
+
+const GVRET_TCP: &[FieldSpec] = &[
+    HOST.required(),
+    TCP_PORT,
+    TIMEOUT,
+    f("tcp_keepalive", Boolean, "Enable TCP keepalive"),
+    LISTEN_ONLY,
+    INTERFACES,
+];
+
+const GVRET_USB: &[FieldSpec] = &[SERIAL_PORT, BAUD_RATE, LISTEN_ONLY, INTERFACES];
+
+const CANSERVER: &[FieldSpec] = &[
+    HOST.required(),
+    f("port", Integer, "UDP port (default 1338)").range(1.0, 65535.0),
+    TIMEOUT,
+    f("heartbeat_ms", Integer, "Time between hello heartbeats in milliseconds").range(100.0, 4_000.0),
+    INTERFACES,
+];
+
+const WEBSOCKET: &[FieldSpec] = &[
+    f("url", Str, "ws:// or wss:// endpoint URL").required(),
+    f("format", Str, "Message encoding").one_of(&["json", "binary"]),
+    f("mapping", Array, "Frame field mappings: role plus JSON key path or binary offset/length/mask"),
+    f("frames_path", Str, "JSON key path of the frame array within a message"),
+    f("record_size", Integer, "Binary record size when a message packs several frames").range(1.0, 4_096.0),
+    f("big_endian", Boolean, "Binary fields are big-endian"),
+    f("timestamp_unit", Str, "Unit of mapped timestamps").one_of(&["seconds", "milliseconds", "microseconds", "nanoseconds"]),
+    f("subscribe", Str, "Text message sent after connecting"),
+    f("timeout", Number, "Seconds of silence before the source logs that no data is arriving").range(0.0, 3600.0),
+    INTERFACES,
+];
+
+const UDP_CAN: &[FieldSpec] = &[
+    f("port", Integer, "UDP port to listen on").range(1.0, 65535.0).required(),
+    f("bind", Str, "Local address to bind (default 0.0.0.0)"),
+    f("multicast_group", Str, "IPv4 multicast group to join"),
+    f("sender", Str, "Only accept datagrams from this host"),
+    f("format", Str, "Datagram layout").one_of(&["binary", "candump"]),
+    f("id_offset", Integer, "Byte offset of the frame ID").range(0.0, 1_500.0),
+    f("id_bytes", Integer, "Width of the frame ID in bytes (default 4)").range(1.0, 4.0),
+    f("dlc_offset", Integer, "Byte offset of the data length (default 4; empty = rest of record)").range(0.0, 1_500.0),
+    f("data_offset", Integer, "Byte offset of the data (default 5)").range(0.0, 1_500.0),
+    f("bus_offset", Integer, "Byte offset of the bus number").range(0.0, 1_500.0),
+    f("record_size", Integer, "Record size when a datagram packs several frames").range(1.0, 1_500.0),
+    f("big_endian", Boolean, "Frame ID is big-endian"),
+    f("timeout", Number, "Seconds of silence before the source logs that no data is arriving").range(0.0, 3600.0),
+    INTERFACES,
+];
+
+const DOIP: &[FieldSpec] = &[
+    HOST.required(),
+    f("port", Integer, "TCP port (default 13400)").range(1.0, 65535.0),
+    f("tester_address", Integer, "Logical address of this tester (default 0x0E00)").range(0.0, 65535.0),
+    f("target_address", Integer, "Default ECU logical address (empty = the gateway itself)").range(0.0, 65535.0),
+    f("activation_type", Integer, "Routing activation type (0 = default, 1 = WWH-OBD)").range(0.0, 255.0),
+    f("protocol_version", Integer, "DoIP protocol version byte (default 2)").range(1.0, 254.0),
+    TIMEOUT,
+    INTERFACES,
+];
+
+const WIRETAP_NET: &[FieldSpec] = &[
+    HOST.required(),
+    f("port", Integer, "Publisher TCP port (default 1339)").range(1.0, 65535.0),
+    TIMEOUT,
+];
+
+const SERIAL: &[FieldSpec] = &[
+    SERIAL_PORT,
+    BAUD_RATE,
+    DATA_BITS,
+    STOP_BITS,
+    PARITY,
+    FRAMING_MODE,
+    f("framing_encoding", Str, "Frame boundary encoding").one_of(&["raw", "slip", "modbus_rtu", "delimiter"]),
+    f("delimiter", Array, "Delimiter bytes for delimiter framing"),
+    f("include_delimiter", Boolean, "Keep the delimiter in each frame"),
+    f("max_frame_length", Integer, "Maximum frame length in bytes").range(1.0, 1_048_576.0),
+    f("min_frame_length", Integer, "Minimum frame length in bytes").range(0.0, 1_048_576.0),
+    f("emit_raw_bytes", Boolean, "Also emit raw bytes alongside frames"),
+    f("modbus_device_address", Integer, "Only accept Modbus RTU frames for this address").range(0.0, 255.0),
+    f("modbus_validate_crc", Boolean, "Drop Modbus RTU frames with bad CRC"),
+    f("frame_id_config", Object, "Frame ID extraction (UI form)"),
+    f("source_address_config", Object, "Source address extraction (UI form)"),
+    f("frame_id_start_byte", Integer, "Frame ID start byte").range(-64.0, 1_048_576.0),
+    f("frame_id_bytes", Integer, "Frame ID length in bytes").range(1.0, 4.0),
+    f("frame_id_big_endian", Boolean, "Frame ID is big-endian"),
+    f("source_address_start_byte", Integer, "Source address start byte").range(-64.0, 1_048_576.0),
+    f("source_address_bytes", Integer, "Source address length in bytes").range(1.0, 4.0),
+    f("source_address_big_endian", Boolean, "Source address is big-endian"),
+];
+
+const SLCAN: &[FieldSpec] = &[
+    SERIAL_PORT,
+    BAUD_RATE,
+    DATA_BITS,
+    STOP_BITS,
+    PARITY,
+    BITRATE,
+    f("silent_mode", Boolean, "Silent mode (no ACK, no transmit)"),
+    ENABLE_FD,
+    DATA_BITRATE,
+];
+
+const CUSTOM_SERIAL: &[FieldSpec] = &[
+    SERIAL_PORT,
+    BAUD_RATE,
+    DATA_BITS,
+    STOP_BITS,
+    PARITY,
+    f("header", Array, "Sync bytes that start every frame"),
+    f("id_bytes", Integer, "Frame ID width in bytes").range(1.0, 4.0),
+    f("id_big_endian", Boolean, "Frame ID is big-endian"),
+    f("length_byte", Boolean, "A length byte follows the frame ID"),
+    f("fixed_length", Integer, "Data length when there is no length byte").range(0.0, 8.0),
+    f("checksum", Str, "Checksum after the data").one_of(&["none", "sum8", "xor8", "crc8"]),
+    f("checksum_includes_header", Boolean, "Checksum also covers the header bytes"),
+    f("footer", Array, "Trailing bytes after the checksum"),
+    f("listen_only", Boolean, "Receive only (no transmit)"),
+];
+
+const ELM327: &[FieldSpec] = &[
+    SERIAL_PORT,
+    BAUD_RATE,
+    f("mode", Str, "Monitor all traffic (ATMA) or poll OBD requests").one_of(&["monitor", "poll"]),
+    f("protocol", Str, "ATSP protocol number (0 = automatic)").one_of(&["0", "6", "7", "8", "9"]),
+    f("pids", Array, "OBD requests to poll, as hex (e.g. 010C)"),
+    f("poll_interval_ms", Integer, "Time between poll rounds in milliseconds").range(10.0, 60_000.0),
+    f("request_header", Str, "ATSH request header for polling (e.g. 7E0)"),
+];
+
+const LIN: &[FieldSpec] = &[
+    SERIAL_PORT,
+    f("baud_rate", Integer, "LIN bus bit rate (default 19200)").range(1_000.0, 20_000.0),
+    f("mode", Str, "Monitor bus traffic, or run a schedule table as the master").one_of(&["monitor", "master"]),
+    f("schedule", Str, "Schedule table from the catalogue's [lin] description (default: the first)"),
+    f("checksum", Str, "Checksum model for frames the description doesn't cover").one_of(&["auto", "classic", "enhanced"]),
+];
+
+const SOCKETCAN: &[FieldSpec] = &[
+    f("interface", Str, "SocketCAN interface name (e.g. can0)").required(),
+    BITRATE,
+    ENABLE_FD,
+    DATA_BITRATE,
+    LISTEN_ONLY,
+    HARDWARE_TIMESTAMPS,
+    ERROR_FRAMES,
+];
+
+const GS_USB: &[FieldSpec] = &[
+    f("device_id", Str, "Device identifier from enumeration"),
+    f("bus", Integer, "USB bus number").range(0.0, 255.0),
+    f("address", Integer, "USB device address").range(0.0, 255.0),
+    f("serial", Str, "USB serial number"),
+    f("interface", Str, "SocketCAN interface name (Linux)"),
+    BITRATE,
+    f("sample_point", Number, "Nominal sample point (percent)").range(50.0, 95.0),
+    LISTEN_ONLY,
+    f("channel", Integer, "Channel on the device").range(0.0, 7.0),
+    ENABLE_FD,
+    DATA_BITRATE,
+    f("data_sample_point", Number, "Data-phase sample point (percent)").range(50.0, 95.0),
+    HARDWARE_TIMESTAMPS,
+    ERROR_FRAMES,
+    f("direct_usb", Boolean, "Linux: detach the kernel gs_usb driver and drive the device directly"),
+];
+
+const PCAN: &[FieldSpec] = &[
+    f("channel", Integer, "PCAN_USBBUS channel").range(1.0, 16.0),
+    f("device_id", Integer, "User-assigned device ID (takes precedence over channel)").range(0.0, 4_294_967_295.0),
+    BITRATE,
+    LISTEN_ONLY,
+];
+
+const KVASER: &[FieldSpec] = &[
+    f("serial", Integer, "Card serial number (takes precedence over channel)").range(0.0, f64::MAX),
+    f("channel", Integer, "CANlib channel of the card's first channel").range(0.0, 255.0),
+    BITRATE,
+    LISTEN_ONLY,
+    INTERFACES,
+];
+
+const VECTOR_XL: &[FieldSpec] = &[
+    f("serial", Integer, "Interface serial number (takes precedence over channel)").range(0.0, 4_294_967_295.0),
+    f("channel", Integer, "Global XL channel index of the interface's first channel").range(0.0, 63.0),
+    BITRATE,
+    ENABLE_FD,
+    DATA_BITRATE,
+    LISTEN_ONLY,
+    INTERFACES,
+];
+
+const J2534: &[FieldSpec] = &[
+    f("device", Str, "Registered PassThru device name").required(),
+    f("library_path", Str, "Vendor PassThru DLL (overrides the registry lookup)"),
+    BITRATE,
+    f("listen_only", Boolean, "Receive only (no transmit)"),
+];
+
+const MODBUS_TCP: &[FieldSpec] = &[
+    HOST,
+    TCP_PORT,
+    f("unit_id", Integer, "Modbus unit identifier").range(0.0, 255.0),
+];
+
+const VIRTUAL: &[FieldSpec] = &[
+    f("traffic_type", Str, "Generated traffic").one_of(&["can", "canfd", "modbus", "serial"]),
+    f("loopback", Boolean, "Echo transmitted frames back as received"),
+    INTERFACES,
+    f("bus_count", Integer, "Number of buses (legacy)").range(1.0, 8.0),
+    f("frame_rate_hz", Number, "Frames per second per bus (legacy)").range(0.0, 100_000.0),
+    f("signal_generator", Boolean, "Generate signal patterns (legacy)"),
+];
+
+const FRAMELINK: &[FieldSpec] = &[
+    HOST.required(),
+    TCP_PORT,
+    TIMEOUT,
+    f("device_id", Str, "FrameLink device identifier"),
+    f("board_name", Str, "Board name reported by the device"),
+    f("board_revision", Str, "Board revision reported by the device"),
+    INTERFACES,
+    f("interface_index", Integer, "Interface index (legacy)").range(0.0, 255.0),
+    f("interface_type", Integer, "Interface type (legacy)").range(0.0, 255.0),
+    f("interface_name", Str, "Interface name (legacy)"),
+];
+
+/// Field table for a profile kind (dash spellings are legacy aliases).
+fn fields_for_kind(kind: &str) -> Option<&'static [FieldSpec]> {
+    Some(match kind {
+        "mqtt" => MQTT,
+        "postgres" => POSTGRES,
+        "wiretap" => WIRETAP,
+        "gvret_tcp" | "gvret-tcp" => GVRET_TCP,
+        "gvret_usb" | "gvret-usb" => GVRET_USB,
+        "serial" => SERIAL,
+        "slcan" => SLCAN,
+        "custom_serial" => CUSTOM_SERIAL,
+        "elm327" => ELM327,
+        "lin" => LIN,
+        "canserver" => CANSERVER,
+        "websocket" => WEBSOCKET,
+        "udp_can" => UDP_CAN,
+        "doip" => DOIP,
+        "wiretap_net" => WIRETAP_NET,
+        "socketcan" => SOCKETCAN,
+        "gs_usb" => GS_USB,
+        "pcan" => PCAN,
+        "kvaser" => KVASER,
+        "vector_xl" => VECTOR_XL,
+        "j2534" => J2534,
+        "modbus_tcp" => MODBUS_TCP,
+        "virtual" => VIRTUAL,
+        "framelink" => FRAMELINK,
+        _ => return None,
+    })
+}
+
+const KINDS: &[&str] = &[
+    "mqtt", "postgres", "wiretap", "gvret_tcp", "gvret_usb", "serial", "slcan", "custom_serial", "elm327", "lin", "canserver", "websocket",
+    "udp_can", "doip", "wiretap_net", "socketcan", "gs_usb", "pcan", "kvaser", "vector_xl", "j2534", "modbus_tcp", "virtual", "framelink",
+];
+
+fn lookup(fields: &'static [FieldSpec], name: &str) -> Option<&'static FieldSpec> {
+    fields.iter().chain(COMMON).find(|f| f.name == name)
+}
+
+// ============================================================================
+// JSON Schema export
+// ============================================================================
+
+fn field_schema(field: &FieldSpec) -> Value {
+    let mut schema = match field.ty {
+        Str if !field.values.is_empty() => json!({ "type": "string", "enum": field.values }),
+        Str => json!({ "type": "string" }),
+        Integer | Number => {
+            let ty = if field.ty == Integer { "integer" } else { "number" };
+            let mut number = json!({ "type": ty });
+            if let Some(min) = field.min {
+                number["minimum"] = json!(min);
+            }
+            if let Some(max) = field.max.filter(|m| *m < f64::MAX) {
+                number["maximum"] = json!(max);
+            }
+            // The profile editor stores numbers as strings
+            json!({ "anyOf": [number, { "type": "string", "pattern": "^\\s*-?[0-9]+(\\.[0-9]+)?\\s*$" }] })
+        }
+        Boolean => json!({ "type": "boolean" }),
+        Array => json!({ "type": "array" }),
+        Object => json!({ "type": "object" }),
+    };
+    schema["description"] = json!(field.description);
+    schema
+}
+
+/// JSON Schema (draft 2020-12) for a kind's `connection` object.
+fn schema_for_kind(kind: &str) -> Option<Value> {
+    let fields = fields_for_kind(kind)?;
+    let properties: Map<String, Value> = fields
+        .iter()
+        .chain(COMMON)
+        .map(|f| (f.name.to_string(), field_schema(f)))
+        .collect();
+    let required: Vec<&str> = fields.iter().filter(|f| f.required).map(|f| f.name).collect();
+    Some(json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": format!("wiretap:profile/{}", kind),
+        "title": format!("{} connection", kind),
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "patternProperties": { "^_": {} },
+        "additionalProperties": false,
+    }))
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+/// A problem with one connection field.
+#[derive(Clone, Debug, Serialize)]
+pub struct FieldIssue {
+    /// Connection key the issue is about
+    pub field: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+    /// Known field the key was probably meant to be
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ProfileValidation {
+    pub profile_id: String,
+    pub kind: String,
+    pub valid: bool,
+    pub issues: Vec<FieldIssue>,
+}
+
+/// Levenshtein distance, for "did you mean" suggestions.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+/// Closest known field within two edits (one for very short names).
+fn suggest(fields: &'static [FieldSpec], key: &str) -> Option<&'static str> {
+    let limit = if key.len() <= 4 { 1 } else { 2 };
+    fields
+        .iter()
+        .chain(COMMON)
+        .map(|f| (edit_distance(key, f.name), f.name))
+        .filter(|(d, _)| *d <= limit)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, name)| name)
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    value.as_f64().or_else(|| {
+        let s = value.as_str()?.trim();
+        match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => u64::from_str_radix(hex, 16).ok().map(|v| v as f64),
+            None => s.parse().ok(),
+        }
+    })
+}
+
+/// Check one value against its field spec.
+fn check_value(field: &FieldSpec, value: &Value) -> Result<(), String> {
+    // Empty strings are how the editor clears a field; drivers treat them as unset
+    if value.is_null() || value.as_str() == Some("") {
+        return Ok(());
+    }
+    match field.ty {
+        Str => {
+            let s = value.as_str().ok_or("expected a string")?;
+            if !field.values.is_empty() && !field.values.contains(&s) {
+                return Err(format!("'{}' is not one of: {}", s, field.values.join(", ")));
+            }
+        }
+        Integer | Number => {
+            let n = as_number(value).ok_or("expected a number")?;
+            if field.ty == Integer && n.fract() != 0.0 {
+                return Err(format!("expected a whole number, got {}", n));
+            }
+            if let (Some(min), Some(max)) = (field.min, field.max) {
+                if n < min || n > max {
+                    return Err(if max < f64::MAX {
+                        format!("{} is out of range ({}-{})", n, min, max)
+                    } else {
+                        format!("{} must be at least {}", n, min)
+                    });
+                }
+            }
+        }
+        Boolean => {
+            value.as_bool().ok_or("expected true or false")?;
+        }
+        Array => {
+            value.as_array().ok_or("expected a list")?;
+        }
+        Object => {
+            value.as_object().ok_or("expected an object")?;
+        }
+    }
+    Ok(())
+}
+
+/// Validate a profile's connection settings against its kind's schema.
+pub fn validate(profile: &IOProfile) -> ProfileValidation {
+    let mut issues = Vec::new();
+    let issue = |field: &str, severity, message: String, suggestion: Option<&str>| FieldIssue {
+        field: field.to_string(),
+        severity,
+        message,
+        suggestion: suggestion.map(String::from),
+    };
+
+    match fields_for_kind(&profile.kind) {
+        None => issues.push(issue(
+            "kind",
+            IssueSeverity::Error,
+            format!("unknown profile kind '{}'", profile.kind),
+            None,
+        )),
+        Some(fields) => {
+            let mut keys: Vec<&String> = profile.connection.keys().collect();
+            keys.sort();
+            for key in keys {
+                if key.starts_with('_') {
+                    continue;
+                }
+                let value = &profile.connection[key];
+                match lookup(fields, key) {
+                    Some(field) => {
+                        if let Err(e) = check_value(field, value) {
+                            issues.push(issue(key, IssueSeverity::Error, e, None));
+                        }
+                    }
+                    None => match suggest(fields, key) {
+                        // Near miss of a real field: the value would be silently ignored
+                        Some(name) if !profile.connection.contains_key(name) => issues.push(issue(
+                            key,
+                            IssueSeverity::Error,
+                            format!("unknown field (did you mean '{}'?)", name),
+                            Some(name),
+                        )),
+                        _ => issues.push(issue(
+                            key,
+                            IssueSeverity::Warning,
+                            format!("unknown field for {} profiles; it is ignored", profile.kind),
+                            None,
+                        )),
+                    },
+                }
+            }
+            for field in fields.iter().filter(|f| f.required) {
+                let missing = match profile.connection.get(field.name) {
+                    None | Some(Value::Null) => true,
+                    Some(v) => v.as_str().is_some_and(|s| s.trim().is_empty()),
+                };
+                if missing {
+                    issues.push(issue(field.name, IssueSeverity::Error, "required".to_string(), None));
+                }
+            }
+        }
+    }
+
+    ProfileValidation {
+        profile_id: profile.id.clone(),
+        kind: profile.kind.clone(),
+        valid: !issues.iter().any(|i| i.severity == IssueSeverity::Error),
+        issues,
+    }
+}
+
+/// Validate a profile, returning a catalogued "invalid settings" error
+/// listing each field error. Warnings are logged but don't fail.
+pub fn ensure_valid(profile: &IOProfile) -> Result<(), String> {
+    let result = validate(profile);
+    for warning in result.issues.iter().filter(|i| i.severity == IssueSeverity::Warning) {
+        tlog!("[profile_schema] Profile '{}' {}: {}", profile.id, warning.field, warning.message);
+    }
+    if result.valid {
+        return Ok(());
+    }
+    let errors: Vec<String> = result
+        .issues
+        .iter()
+        .filter(|i| i.severity == IssueSeverity::Error)
+        .map(|i| format!("{}: {}", i.field, i.message))
+        .collect();
+    Err(msg!(PROFILE_INVALID, profile = profile.name, errors = errors.join("; ")).into())
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// JSON Schema for one profile kind's connection settings, or for every
+/// kind (keyed by kind) when `kind` is omitted.
+#[tauri::command]
+pub fn get_profile_schema(kind: Option<String>) -> Result<Value, String> {
+    match kind {
+        Some(kind) => schema_for_kind(&kind).ok_or_else(|| format!("Unknown profile kind '{}'", kind)),
+        None => Ok(Value::Object(
+            KINDS
+                .iter()
+                .filter_map(|k| Some((k.to_string(), schema_for_kind(k)?)))
+                .collect(),
+        )),
+    }
+}
+
+/// Validate a profile (e.g. the one open in the editor) without saving it.
+#[tauri::command]
+pub fn validate_profile(profile: IOProfile) -> ProfileValidation {
+    validate(&profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn profile(kind: &str, connection: Value) -> IOProfile {
+        IOProfile {
+            id: "p1".to_string(),
+            name: "Test".to_string(),
+            kind: kind.to_string(),
+            connection: serde_json::from_value::<HashMap<String, Value>>(connection).unwrap(),
+            preferred_catalog: None,
+        }
+    }
+
+    #[test]
+    fn flags_typo_with_suggestion() {
+        let result = validate(&profile("slcan", json!({ "port": "/dev/ttyACM0", "buad_rate": "115200" })));
+        assert!(!result.valid);
+        assert_eq!(result.issues[0].field, "buad_rate");
+        assert_eq!(result.issues[0].suggestion.as_deref(), Some("baud_rate"));
+    }
+
+    #[test]
+    fn accepts_numeric_strings_and_internal_keys() {
+        let result = validate(&profile(
+            "gvret_tcp",
+            json!({ "host": "10.0.0.5", "port": "23", "timeout": 5, "_probed_bus_count": 3 }),
+        ));
+        assert!(result.valid, "{:?}", result.issues);
+        assert!(result.issues.is_empty());
+    }
+
+    #[test]
+    fn accepts_hex_address_strings() {
+        let result = validate(&profile(
+            "doip",
+            json!({ "host": "192.168.0.10", "tester_address": "0x0E80", "target_address": "0x10001" }),
+        ));
+        let fields: Vec<&str> = result.issues.iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(fields, vec!["target_address"]);
+    }
+
+    #[test]
+    fn reports_type_range_and_required_errors() {
+        let result = validate(&profile(
+            "socketcan",
+            json!({ "bitrate": "fast", "enable_fd": "yes", "bus_override": 300 }),
+        ));
+        let fields: Vec<&str> = result.issues.iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(fields, vec!["bitrate", "bus_override", "enable_fd", "interface"]);
+        assert!(result.issues.iter().all(|i| i.severity == IssueSeverity::Error));
+    }
+
+    #[test]
+    fn unrelated_unknown_key_is_a_warning() {
+        let result = validate(&profile("pcan", json!({ "channel": 1, "colour": "blue" })));
+        assert!(result.valid);
+        assert_eq!(result.issues[0].severity, IssueSeverity::Warning);
+    }
+
+    #[test]
+    fn exports_json_schema_for_every_kind() {
+        let all = get_profile_schema(None).unwrap();
+        assert_eq!(all.as_object().unwrap().len(), KINDS.len());
+        let schema = get_profile_schema(Some("socketcan".to_string())).unwrap();
+        assert_eq!(schema["required"], json!(["interface"]));
+        assert!(schema["properties"]["bitrate"]["anyOf"].is_array());
+        assert!(get_profile_schema(Some("nope".to_string())).is_err());
+    }
+}
@@ -23,9 +23,16 @@ pub struct ProfileUsage {
 static PROFILE_USAGE: Lazy<Mutex<HashMap<String, HashSet<String>>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
+/// Map of profile_id -> profile kind, for profiles in PROFILE_USAGE
+static PROFILE_KINDS: Lazy<Mutex<HashMap<String, String>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
 /// Register a profile as being used by a session.
 /// For multi-handle devices, multiple sessions can use the same profile.
-pub fn register_usage(profile_id: &str, session_id: &str) {
+pub fn register_usage(profile_id: &str, profile_kind: &str, session_id: &str) {
+    if let Ok(mut kinds) = PROFILE_KINDS.lock() {
+        kinds.insert(profile_id.to_string(), profile_kind.to_string());
+    }
     if let Ok(mut map) = PROFILE_USAGE.lock() {
         let sessions = map.entry(profile_id.to_string()).or_insert_with(HashSet::new);
         let is_new = sessions.insert(session_id.to_string());
@@ -51,6 +58,7 @@ pub fn unregister_usage_by_session(profile_id: &str, session_id: &str) {
                 // Remove the profile entry entirely if no sessions remain
                 if sessions.is_empty() {
                     map.remove(profile_id);
+                    forget_kind(profile_id);
                     tlog!(
                         "[profile_tracker] Profile '{}' has no more sessions, removed from tracker",
                         profile_id
@@ -77,6 +85,7 @@ pub fn unregister_usage(profile_id: &str) {
                 );
                 if sessions.is_empty() {
                     map.remove(profile_id);
+                    forget_kind(profile_id);
                     tlog!(
                         "[profile_tracker] Profile '{}' has no more sessions, removed from tracker",
                         profile_id
@@ -87,6 +96,12 @@ pub fn unregister_usage(profile_id: &str) {
     }
 }
 
+fn forget_kind(profile_id: &str) {
+    if let Ok(mut kinds) = PROFILE_KINDS.lock() {
+        kinds.remove(profile_id);
+    }
+}
+
 /// Check if a profile is in use, and by what sessions
 pub fn get_usage(profile_id: &str) -> Option<ProfileUsage> {
     let map = PROFILE_USAGE.lock().ok()?;
@@ -96,7 +111,9 @@ pub fn get_usage(profile_id: &str) -> Option<ProfileUsage> {
 }
 
 /// Profile kinds that require exclusive (single-handle) access
-const SINGLE_HANDLE_KINDS: &[&str] = &["slcan", "serial"];
+/// (PCAN-Basic lets only one client initialise a channel; Kvaser channels
+/// are opened exclusively.)
+const SINGLE_HANDLE_KINDS: &[&str] = &["slcan", "custom_serial", "serial", "pcan", "kvaser", "j2534", "elm327", "lin"];
 
 /// Check if a profile can be used (not already in use by another session)
 ///
@@ -122,6 +139,23 @@ pub fn can_use_profile(profile_id: &str, profile_kind: &str) -> Result<(), Strin
     Ok(())
 }
 
+/// Profile kinds that occupy a local USB device (USB-serial adapters included)
+const USB_KINDS: &[&str] = &["gvret_usb", "gvret-usb", "slcan", "custom_serial", "elm327", "lin", "gs_usb", "pcan", "kvaser", "vector_xl", "j2534", "serial"];
+
+/// Check if a profile kind occupies a local USB device
+pub fn is_usb_kind(profile_kind: &str) -> bool {
+    USB_KINDS.contains(&profile_kind)
+}
+
+/// Number of USB device profiles currently in use by any session
+pub fn usb_devices_in_use() -> usize {
+    let Ok(map) = PROFILE_USAGE.lock() else { return 0 };
+    let Ok(kinds) = PROFILE_KINDS.lock() else { return 0 };
+    map.keys()
+        .filter(|id| kinds.get(*id).is_some_and(|k| is_usb_kind(k)))
+        .count()
+}
+
 /// Check if a profile kind requires single-handle access
 #[allow(dead_code)]
 pub fn is_single_handle_kind(profile_kind: &str) -> bool {
@@ -0,0 +1,399 @@
+// ui/src-tauri/src/reactive_transmit.rs
+//
+// Reactive transmit — soft real-time "when frame X matching mask Y is
+// received, transmit frame Z within T ms" rules. Meant for gateway-spoofing
+// and response-injection experiments that don't justify a script or the
+// full responder.
+//
+// Rules are evaluated in the IO broker's merge task as each source batch
+// arrives, ahead of the merge emit batching (up to batch_max_latency_ms)
+// and the capture_store taps the responder uses. A match is dispatched on
+// its own task through io::session_transmit, so transmit validation and
+// frame linkage still apply. If the frame can't be queued before the rule's
+// deadline it is dropped and counted as missed — a late injection is worse
+// than none. Latency is measured on the host clock from merge receipt to
+// the frame being queued for the device.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+
+use crate::capture_trigger::FramePattern;
+use crate::io::{self, CanTransmitFrame, FrameMessage, TransmitPayload};
+use crate::responder::ResponseFrame;
+
+/// Deadline used when a rule doesn't set one.
+const DEFAULT_DEADLINE_MS: u64 = 10;
+
+/// Upper bound on in-flight reactions per session; matches beyond this are
+/// counted as missed rather than piling up transmit tasks.
+const MAX_IN_FLIGHT: usize = 64;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// One reactive rule. Every rule is checked against every received frame.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReactiveRule {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Received frame that fires the rule (masked ID and payload)
+    pub trigger: FramePattern,
+    /// Frame to transmit (bus None = the bus the trigger arrived on)
+    pub transmit: ResponseFrame,
+    /// Hold-off before transmitting
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// Drop the transmit if it can't be queued within this many ms of receipt
+    #[serde(default = "default_deadline_ms")]
+    pub deadline_ms: u64,
+    /// Minimum time between firings (0 = fire on every match)
+    #[serde(default)]
+    pub cooldown_ms: u64,
+    /// Stop firing after this many transmits (None = unlimited)
+    #[serde(default)]
+    pub max_fires: Option<u64>,
+}
+
+fn default_deadline_ms() -> u64 {
+    DEFAULT_DEADLINE_MS
+}
+
+/// Per-rule counters, fetched by the frontend.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ReactiveRuleStats {
+    pub name: Option<String>,
+    pub matched: u64,
+    pub sent: u64,
+    /// Matches skipped by the cooldown or `max_fires`
+    pub suppressed: u64,
+    /// Matches dropped because the deadline passed before transmit
+    pub missed_deadline: u64,
+    pub transmit_errors: u64,
+    pub last_latency_us: Option<u64>,
+    pub max_latency_us: Option<u64>,
+    pub mean_latency_us: Option<u64>,
+    #[serde(skip)]
+    total_latency_us: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ReactiveTransmitState {
+    pub session_id: String,
+    pub rules: Vec<ReactiveRuleStats>,
+    pub last_error: Option<String>,
+}
+
+struct ReactiveSession {
+    rules: Vec<ReactiveRule>,
+    last_fired: Vec<Option<Instant>>,
+    /// Transmits queued or sent per rule (for `max_fires`)
+    fired: Vec<u64>,
+    in_flight: usize,
+    state: ReactiveTransmitState,
+}
+
+/// A matched rule waiting to be transmitted.
+struct Reaction {
+    rule: usize,
+    frame: CanTransmitFrame,
+    received_at: Instant,
+    delay: Duration,
+    deadline: Duration,
+}
+
+// ============================================================================
+// State management
+// ============================================================================
+
+static SESSIONS: Lazy<StdMutex<HashMap<String, ReactiveSession>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// Sessions with rules installed. Lets the merge task skip the lock when no
+/// session uses reactive transmit.
+static ACTIVE_SESSIONS: AtomicUsize = AtomicUsize::new(0);
+
+impl ReactiveSession {
+    fn new(session_id: &str, rules: Vec<ReactiveRule>) -> Self {
+        let count = rules.len();
+        let state = ReactiveTransmitState {
+            session_id: session_id.to_string(),
+            rules: rules
+                .iter()
+                .map(|r| ReactiveRuleStats { name: r.name.clone(), ..Default::default() })
+                .collect(),
+            last_error: None,
+        };
+        Self { rules, last_fired: vec![None; count], fired: vec![0; count], in_flight: 0, state }
+    }
+
+    /// Match received frames against the rules. Our own transmits echo back
+    /// as tx frames and never fire a rule.
+    fn evaluate(&mut self, frames: &[FrameMessage], now: Instant) -> Vec<Reaction> {
+        let mut reactions = Vec::new();
+        for frame in frames.iter().filter(|f| f.direction.as_deref() != Some("tx")) {
+            for (idx, rule) in self.rules.iter().enumerate() {
+                if !rule.trigger.matches(frame) {
+                    continue;
+                }
+                let stats = &mut self.state.rules[idx];
+                stats.matched += 1;
+                let cooling = self.last_fired[idx]
+                    .is_some_and(|t| now.duration_since(t) < Duration::from_millis(rule.cooldown_ms));
+                let exhausted = rule.max_fires.is_some_and(|max| self.fired[idx] >= max);
+                if cooling || exhausted {
+                    stats.suppressed += 1;
+                    continue;
+                }
+                if self.in_flight >= MAX_IN_FLIGHT {
+                    stats.missed_deadline += 1;
+                    continue;
+                }
+                self.last_fired[idx] = Some(now);
+                self.fired[idx] += 1;
+                self.in_flight += 1;
+                reactions.push(Reaction {
+                    rule: idx,
+                    frame: CanTransmitFrame {
+                        frame_id: rule.transmit.frame_id,
+                        data: rule.transmit.data.clone(),
+                        bus: rule.transmit.bus.unwrap_or(frame.bus),
+                        is_extended: rule.transmit.is_extended,
+                        is_fd: rule.transmit.is_fd,
+                        is_brs: false,
+                        is_rtr: false,
+                    },
+                    received_at: now,
+                    delay: Duration::from_millis(rule.delay_ms),
+                    deadline: Duration::from_millis(rule.deadline_ms),
+                });
+            }
+        }
+        reactions
+    }
+}
+
+fn update_session(session_id: &str, f: impl FnOnce(&mut ReactiveSession)) {
+    if let Ok(mut sessions) = SESSIONS.lock() {
+        if let Some(session) = sessions.get_mut(session_id) {
+            f(session);
+        }
+    }
+}
+
+/// Called from the IO broker merge task for every source batch. Matching is
+/// done inline; transmits run on their own tasks so the merge loop never
+/// waits on a device.
+pub fn react(session_id: &str, frames: &[FrameMessage]) {
+    if ACTIVE_SESSIONS.load(Ordering::Relaxed) == 0 {
+        return;
+    }
+    let now = Instant::now();
+    let reactions = match SESSIONS.lock() {
+        Ok(mut sessions) => match sessions.get_mut(session_id) {
+            Some(session) => session.evaluate(frames, now),
+            None => return,
+        },
+        Err(_) => return,
+    };
+    for reaction in reactions {
+        let session_id = session_id.to_string();
+        tauri::async_runtime::spawn(async move { dispatch(session_id, reaction).await });
+    }
+}
+
+async fn dispatch(session_id: String, reaction: Reaction) {
+    if !reaction.delay.is_zero() {
+        tokio::time::sleep(reaction.delay.saturating_sub(reaction.received_at.elapsed())).await;
+    }
+    let result = if reaction.received_at.elapsed() > reaction.deadline {
+        None
+    } else {
+        Some(io::session_transmit(&session_id, &TransmitPayload::CanFrame(reaction.frame)).await)
+    };
+    let latency_us = reaction.received_at.elapsed().as_micros() as u64;
+
+    update_session(&session_id, |session| {
+        session.in_flight = session.in_flight.saturating_sub(1);
+        let Some(stats) = session.state.rules.get_mut(reaction.rule) else {
+            return;
+        };
+        let error = match result {
+            None => {
+                stats.missed_deadline += 1;
+                return;
+            }
+            Some(Ok(r)) if r.success => {
+                // Queueing can itself take us past the deadline; count it, but
+                // the frame is already on its way.
+                if latency_us > reaction.deadline.as_micros() as u64 {
+                    stats.missed_deadline += 1;
+                }
+                stats.sent += 1;
+                stats.total_latency_us += latency_us;
+                stats.last_latency_us = Some(latency_us);
+                stats.max_latency_us = Some(stats.max_latency_us.unwrap_or(0).max(latency_us));
+                stats.mean_latency_us = Some(stats.total_latency_us / stats.sent);
+                return;
+            }
+            Some(Ok(r)) => r.error,
+            Some(Err(e)) => Some(e),
+        };
+        stats.transmit_errors += 1;
+        session.state.last_error = error;
+    });
+}
+
+/// Remove a session's rules. Called on explicit clear and when the session
+/// is destroyed.
+pub fn clear_session(session_id: &str) {
+    let removed = SESSIONS.lock().ok().and_then(|mut s| s.remove(session_id));
+    if removed.is_some() {
+        ACTIVE_SESSIONS.fetch_sub(1, Ordering::Relaxed);
+        tlog!("[reactive_tx:{}] Rules cleared", session_id);
+    }
+}
+
+fn validate_rule(rule: &ReactiveRule) -> Result<(), String> {
+    let name = rule.name.as_deref().unwrap_or("unnamed");
+    let max_len = if rule.transmit.is_fd { 64 } else { 8 };
+    if rule.transmit.data.len() > max_len {
+        return Err(format!(
+            "Rule '{}' transmits {} bytes (max {})",
+            name,
+            rule.transmit.data.len(),
+            max_len
+        ));
+    }
+    if rule.deadline_ms == 0 {
+        return Err(format!("Rule '{}' needs a deadline above 0 ms", name));
+    }
+    if rule.delay_ms >= rule.deadline_ms {
+        return Err(format!(
+            "Rule '{}' delay ({} ms) must be shorter than its deadline ({} ms)",
+            name, rule.delay_ms, rule.deadline_ms
+        ));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Install (or replace) a session's reactive transmit rules. Counters reset.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_reactive_transmit_rules(
+    session_id: String,
+    rules: Vec<ReactiveRule>,
+) -> Result<ReactiveTransmitState, String> {
+    let caps = io::get_session_capabilities(&session_id)
+        .await
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
+    if !caps.traits.tx_frames {
+        return Err("This session does not support CAN transmission".to_string());
+    }
+    for rule in &rules {
+        validate_rule(rule)?;
+    }
+
+    clear_session(&session_id);
+    if rules.is_empty() {
+        return Ok(ReactiveTransmitState { session_id, ..Default::default() });
+    }
+
+    tlog!("[reactive_tx:{}] Installed {} rule(s)", session_id, rules.len());
+    let session = ReactiveSession::new(&session_id, rules);
+    let state = session.state.clone();
+    if let Ok(mut sessions) = SESSIONS.lock() {
+        sessions.insert(session_id, session);
+        ACTIVE_SESSIONS.fetch_add(1, Ordering::Relaxed);
+    }
+    Ok(state)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_reactive_transmit_rules(session_id: String) {
+    clear_session(&session_id);
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_reactive_transmit_state(session_id: String) -> Option<ReactiveTransmitState> {
+    SESSIONS
+        .lock()
+        .ok()
+        .and_then(|s| s.get(&session_id).map(|session| session.state.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(frame_id: u32, bytes: Vec<u8>, direction: &str) -> FrameMessage {
+        FrameMessage {
+            bus: 1,
+            direction: Some(direction.to_string()),
+            ..test_frame(frame_id, 0, &bytes)
+        }
+    }
+
+    fn rule(cooldown_ms: u64, max_fires: Option<u64>) -> ReactiveRule {
+        ReactiveRule {
+            name: Some("spoof".to_string()),
+            trigger: FramePattern {
+                frame_id: 0x100,
+                id_mask: None,
+                bus: None,
+                data: vec![0x01],
+                data_mask: vec![0x0F],
+            },
+            transmit: ResponseFrame {
+                frame_id: 0x200,
+                data: vec![0xAA],
+                bus: None,
+                is_extended: false,
+                is_fd: false,
+            },
+            delay_ms: 0,
+            deadline_ms: DEFAULT_DEADLINE_MS,
+            cooldown_ms,
+            max_fires,
+        }
+    }
+
+    #[test]
+    fn masked_match_fires_on_trigger_bus_and_ignores_tx_echo() {
+        let mut session = ReactiveSession::new("s", vec![rule(0, None)]);
+        let now = Instant::now();
+        let reactions = session.evaluate(
+            &[
+                frame(0x100, vec![0xF1], "rx"),
+                frame(0x100, vec![0x02], "rx"),
+                frame(0x100, vec![0x01], "tx"),
+            ],
+            now,
+        );
+        assert_eq!(reactions.len(), 1);
+        assert_eq!(reactions[0].frame.frame_id, 0x200);
+        assert_eq!(reactions[0].frame.bus, 1);
+        assert_eq!(session.state.rules[0].matched, 1);
+    }
+
+    #[test]
+    fn cooldown_and_max_fires_suppress() {
+        let mut session = ReactiveSession::new("s", vec![rule(100, Some(2))]);
+        let start = Instant::now();
+        let trigger = [frame(0x100, vec![0x01], "rx")];
+        assert_eq!(session.evaluate(&trigger, start).len(), 1);
+        assert_eq!(session.evaluate(&trigger, start + Duration::from_millis(50)).len(), 0);
+        assert_eq!(session.evaluate(&trigger, start + Duration::from_millis(150)).len(), 1);
+        assert_eq!(session.evaluate(&trigger, start + Duration::from_millis(300)).len(), 0);
+        let stats = &session.state.rules[0];
+        assert_eq!((stats.matched, stats.suppressed), (4, 2));
+    }
+}
@@ -0,0 +1,687 @@
+// ui/src-tauri/src/reassembly.rs
+//
+// Multi-frame payload reassembly stage in the frame pipeline.
+//
+// Transport protocols that split one message across several CAN frames
+// (ISO-TP, J1939 TP, proprietary sequence-counter schemes) implement the
+// `Reassembler` trait. Each session can be given a `ReassemblyConfig`; frames
+// passing through capture_store::append_frames_to_session are fed to the
+// session's reassemblers, and every completed message is inserted into the
+// capture as a synthetic frame directly after its final segment (protocol
+// "isotp" / "j1939" / the scheme name). A `ReassemblyLink` recording which
+// frames made up the message is kept per session and fetched by the frontend
+// via get_reassembly_links.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+
+use crate::frame_link::next_correlation_id;
+use crate::io::{FrameLink, FrameMessage, LinkRole};
+
+/// Links retained per session (oldest dropped first).
+const MAX_LINKS_PER_SESSION: usize = 10_000;
+
+/// Partial messages older than this are discarded (ISO-TP N_Cr is 1 s; J1939
+/// T1–T4 are at most 1.25 s — leave headroom for slow loggers).
+const STALE_AFTER_US: u64 = 5_000_000;
+
+// ============================================================================
+// Framework
+// ============================================================================
+
+/// A completed multi-frame message.
+#[derive(Clone, Debug)]
+pub struct ReassembledMessage {
+    /// Synthetic frame carrying the full payload
+    pub frame: FrameMessage,
+    /// Timestamp of the first segment
+    pub first_segment_us: u64,
+    /// Number of frames (including control frames) that made up the message
+    pub segment_count: usize,
+    /// Distinct CAN IDs of the constituent frames
+    pub segment_frame_ids: Vec<u32>,
+    /// Correlation ID shared by the segments and the synthetic frame
+    pub correlation_id: u64,
+}
+
+/// What a reassembler made of one frame.
+pub enum Outcome {
+    /// Not part of a transfer this reassembler tracks
+    Ignored,
+    /// Consumed as a segment of an in-flight transfer
+    Segment { correlation_id: u64 },
+    /// Final segment — the transfer is complete
+    Complete(ReassembledMessage),
+}
+
+/// A transport-protocol reassembler. Implementations keep their own
+/// per-channel partial state and return a message when one completes.
+pub trait Reassembler: Send + Sync {
+    fn process(&mut self, frame: &FrameMessage) -> Outcome;
+}
+
+/// Relationship between a synthetic frame and the frames it was built from.
+#[derive(Clone, Debug, Serialize)]
+pub struct ReassemblyLink {
+    pub protocol: String,
+    pub bus: u8,
+    pub frame_id: u32,
+    /// Timestamp of the synthetic frame (= the final segment)
+    pub timestamp_us: u64,
+    pub first_segment_us: u64,
+    pub segment_count: usize,
+    pub segment_frame_ids: Vec<u32>,
+    pub length: usize,
+    /// Shared with the segments' and synthetic frame's `FrameLink`
+    pub correlation_id: u64,
+}
+
+fn synthetic_frame(
+    protocol: &str,
+    template: &FrameMessage,
+    frame_id: u32,
+    payload: Vec<u8>,
+    correlation_id: u64,
+) -> FrameMessage {
+    FrameMessage {
+        protocol: protocol.to_string(),
+        timestamp_us: template.timestamp_us,
+        frame_id,
+        bus: template.bus,
+        dlc: payload.len().min(u8::MAX as usize) as u8,
+        bytes: payload,
+        is_extended: template.is_extended,
+        is_fd: false,
+        source_address: template.source_address,
+        incomplete: None,
+        direction: template.direction.clone(),
+        link: Some(FrameLink { correlation_id, role: LinkRole::Message, parent_rowid: None }),
+        clock: None,
+        is_error: None,
+        error_class: None,
+        brs: None,
+    }
+}
+
+/// Accumulates segments of one in-flight message.
+struct Partial {
+    data: Vec<u8>,
+    expected_len: usize,
+    next_seq: u8,
+    first_us: u64,
+    last_us: u64,
+    segments: usize,
+    frame_ids: Vec<u32>,
+    correlation_id: u64,
+}
+
+impl Partial {
+    /// Start a transfer. A first segment already linked (e.g. a correlated
+    /// UDS response) keeps its correlation ID so the whole exchange shares one.
+    fn new(first: &FrameMessage, expected_len: usize, next_seq: u8) -> Self {
+        Self {
+            data: Vec::with_capacity(expected_len.min(4096)),
+            expected_len,
+            next_seq,
+            first_us: first.timestamp_us,
+            last_us: first.timestamp_us,
+            segments: 1,
+            frame_ids: vec![first.frame_id],
+            correlation_id: first
+                .link
+                .as_ref()
+                .map(|l| l.correlation_id)
+                .unwrap_or_else(next_correlation_id),
+        }
+    }
+
+    fn segment(&self) -> Outcome {
+        Outcome::Segment { correlation_id: self.correlation_id }
+    }
+
+    fn push(&mut self, frame: &FrameMessage, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+        self.segments += 1;
+        self.last_us = frame.timestamp_us;
+        if !self.frame_ids.contains(&frame.frame_id) {
+            self.frame_ids.push(frame.frame_id);
+        }
+    }
+
+    fn is_stale(&self, now_us: u64) -> bool {
+        now_us.saturating_sub(self.last_us) > STALE_AFTER_US
+    }
+
+    fn finish(mut self, protocol: &str, last: &FrameMessage, frame_id: u32) -> ReassembledMessage {
+        self.data.truncate(self.expected_len);
+        ReassembledMessage {
+            frame: synthetic_frame(protocol, last, frame_id, self.data, self.correlation_id),
+            first_segment_us: self.first_us,
+            segment_count: self.segments,
+            segment_frame_ids: self.frame_ids,
+            correlation_id: self.correlation_id,
+        }
+    }
+}
+
+// ============================================================================
+// ISO-TP (ISO 15765-2)
+// ============================================================================
+
+/// One ISO-TP sender to reassemble (typically one per diagnostic ID).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IsoTpChannel {
+    pub frame_id: u32,
+    #[serde(default)]
+    pub bus: Option<u8>,
+    /// Extended/mixed addressing: first payload byte is an address
+    #[serde(default)]
+    pub extended_addressing: bool,
+}
+
+/// Reassembles ISO-TP multi-frame transfers (First Frame + Consecutive
+/// Frames). Single frames are already complete and are not duplicated.
+pub struct IsoTpReassembler {
+    channels: Vec<IsoTpChannel>,
+    partial: HashMap<(u8, u32), Partial>,
+}
+
+impl IsoTpReassembler {
+    pub fn new(channels: Vec<IsoTpChannel>) -> Self {
+        Self { channels, partial: HashMap::new() }
+    }
+}
+
+impl Reassembler for IsoTpReassembler {
+    fn process(&mut self, frame: &FrameMessage) -> Outcome {
+        let Some(channel) = self
+            .channels
+            .iter()
+            .find(|c| c.frame_id == frame.frame_id && c.bus.is_none_or(|b| b == frame.bus))
+        else {
+            return Outcome::Ignored;
+        };
+        let skip = channel.extended_addressing as usize;
+        let Some(bytes) = frame.bytes.get(skip..).filter(|b| !b.is_empty()) else {
+            return Outcome::Ignored;
+        };
+        let pci = bytes[0];
+        let key = (frame.bus, frame.frame_id);
+
+        match pci >> 4 {
+            // First Frame: 12-bit length, or 0 followed by a 32-bit length
+            0x1 => {
+                let Some(&len_low) = bytes.get(1) else {
+                    return Outcome::Ignored;
+                };
+                let short_len = (((pci & 0x0F) as usize) << 8) | len_low as usize;
+                let (len, data_start) = if short_len == 0 {
+                    let Some(b) = bytes.get(2..6) else {
+                        return Outcome::Ignored;
+                    };
+                    (u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize, 6)
+                } else {
+                    (short_len, 2)
+                };
+                let mut partial = Partial::new(frame, len, 1);
+                partial.data.extend_from_slice(bytes.get(data_start..).unwrap_or(&[]));
+                let outcome = partial.segment();
+                self.partial.insert(key, partial);
+                outcome
+            }
+            // Consecutive Frame: 4-bit wrapping sequence number
+            0x2 => {
+                let Some(partial) = self.partial.get_mut(&key) else {
+                    return Outcome::Ignored;
+                };
+                if (pci & 0x0F) != partial.next_seq || partial.is_stale(frame.timestamp_us) {
+                    // Lost or out-of-order segment — abandon the transfer
+                    self.partial.remove(&key);
+                    return Outcome::Ignored;
+                }
+                partial.next_seq = (partial.next_seq + 1) & 0x0F;
+                partial.push(frame, &bytes[1..]);
+                if partial.data.len() < partial.expected_len {
+                    return partial.segment();
+                }
+                match self.partial.remove(&key) {
+                    Some(done) => Outcome::Complete(done.finish("isotp", frame, frame.frame_id)),
+                    None => Outcome::Ignored,
+                }
+            }
+            _ => Outcome::Ignored,
+        }
+    }
+}
+
+// ============================================================================
+// J1939 Transport Protocol (SAE J1939-21: BAM and RTS/CTS)
+// ============================================================================
+
+const J1939_PF_TP_CM: u32 = 0xEC;
+const J1939_PF_TP_DT: u32 = 0xEB;
+const J1939_CM_RTS: u8 = 16;
+const J1939_CM_BAM: u8 = 32;
+const J1939_CM_ABORT: u8 = 255;
+
+/// Partial J1939 transfer plus the PGN it carries.
+struct J1939Partial {
+    partial: Partial,
+    pgn: u32,
+    priority: u32,
+    packets: u8,
+}
+
+/// Reassembles J1939 TP.CM/TP.DT transfers on 29-bit IDs. The synthetic
+/// frame carries the transported PGN's ID (priority from the TP.CM frame).
+#[derive(Default)]
+pub struct J1939TpReassembler {
+    // (bus, source address, destination address)
+    partial: HashMap<(u8, u8, u8), J1939Partial>,
+}
+
+impl Reassembler for J1939TpReassembler {
+    fn process(&mut self, frame: &FrameMessage) -> Outcome {
+        if !frame.is_extended || frame.bytes.len() < 8 {
+            return Outcome::Ignored;
+        }
+        let pf = (frame.frame_id >> 16) & 0xFF;
+        let da = ((frame.frame_id >> 8) & 0xFF) as u8;
+        let sa = (frame.frame_id & 0xFF) as u8;
+        let key = (frame.bus, sa, da);
+        let b = &frame.bytes;
+
+        if pf == J1939_PF_TP_CM {
+            match b[0] {
+                J1939_CM_BAM | J1939_CM_RTS => {
+                    let size = u16::from_le_bytes([b[1], b[2]]) as usize;
+                    let pgn = u32::from_le_bytes([b[5], b[6], b[7], 0]);
+                    let partial = Partial::new(frame, size, 1);
+                    let outcome = partial.segment();
+                    self.partial.insert(
+                        key,
+                        J1939Partial {
+                            partial,
+                            pgn,
+                            priority: (frame.frame_id >> 26) & 0x7,
+                            packets: b[3],
+                        },
+                    );
+                    return outcome;
+                }
+                J1939_CM_ABORT => {
+                    self.partial.remove(&key);
+                }
+                _ => {}
+            }
+            return Outcome::Ignored;
+        }
+
+        if pf != J1939_PF_TP_DT {
+            return Outcome::Ignored;
+        }
+        let Some(entry) = self.partial.get_mut(&key) else {
+            return Outcome::Ignored;
+        };
+        let seq = b[0];
+        if seq != entry.partial.next_seq || entry.partial.is_stale(frame.timestamp_us) {
+            self.partial.remove(&key);
+            return Outcome::Ignored;
+        }
+        entry.partial.next_seq = seq.wrapping_add(1);
+        entry.partial.push(frame, &b[1..]);
+        if seq < entry.packets && entry.partial.data.len() < entry.partial.expected_len {
+            return entry.partial.segment();
+        }
+
+        let Some(done) = self.partial.remove(&key) else {
+            return Outcome::Ignored;
+        };
+        // PDU1 PGNs (PF < 240) are destination-specific: the DA goes in PS.
+        let pgn_pf = (done.pgn >> 8) & 0xFF;
+        let pgn = if pgn_pf < 240 { (done.pgn & 0x3FF00) | da as u32 } else { done.pgn };
+        let frame_id = (done.priority << 26) | (pgn << 8) | sa as u32;
+        let mut msg = done.partial.finish("j1939", frame, frame_id);
+        msg.frame.source_address = Some(sa as u16);
+        Outcome::Complete(msg)
+    }
+}
+
+// ============================================================================
+// Proprietary sequence-counter schemes
+// ============================================================================
+
+/// A simple proprietary multi-frame scheme: one byte of each frame holds a
+/// sequence index (0 starts a message) and a flag bit marks the last segment.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SequencedScheme {
+    /// Protocol label given to the synthetic frames
+    pub name: String,
+    pub frame_id: u32,
+    #[serde(default)]
+    pub bus: Option<u8>,
+    /// Byte holding the sequence index
+    #[serde(default)]
+    pub index_byte: usize,
+    /// Bits of the index byte that form the index
+    #[serde(default = "default_index_mask")]
+    pub index_mask: u8,
+    /// Bit of the index byte set on the final segment
+    pub last_flag: u8,
+    /// First payload byte of each segment
+    #[serde(default = "default_payload_offset")]
+    pub payload_offset: usize,
+}
+
+fn default_index_mask() -> u8 {
+    0x7F
+}
+
+fn default_payload_offset() -> usize {
+    1
+}
+
+pub struct SequencedReassembler {
+    scheme: SequencedScheme,
+    partial: HashMap<u8, Partial>,
+}
+
+impl SequencedReassembler {
+    pub fn new(scheme: SequencedScheme) -> Self {
+        Self { scheme, partial: HashMap::new() }
+    }
+}
+
+impl Reassembler for SequencedReassembler {
+    fn process(&mut self, frame: &FrameMessage) -> Outcome {
+        let s = &self.scheme;
+        if frame.frame_id != s.frame_id || !s.bus.is_none_or(|b| b == frame.bus) {
+            return Outcome::Ignored;
+        }
+        let Some(&marker) = frame.bytes.get(s.index_byte) else {
+            return Outcome::Ignored;
+        };
+        let index = marker & s.index_mask;
+        let last = marker & s.last_flag != 0;
+        let payload = frame.bytes.get(s.payload_offset..).unwrap_or(&[]);
+
+        if index == 0 {
+            let mut partial = Partial::new(frame, usize::MAX, 1);
+            partial.data.extend_from_slice(payload);
+            self.partial.insert(frame.bus, partial);
+        } else {
+            let Some(partial) = self.partial.get_mut(&frame.bus) else {
+                return Outcome::Ignored;
+            };
+            if index != partial.next_seq || partial.is_stale(frame.timestamp_us) {
+                self.partial.remove(&frame.bus);
+                return Outcome::Ignored;
+            }
+            partial.next_seq = (partial.next_seq + 1) & s.index_mask;
+            partial.push(frame, payload);
+        }
+
+        if !last {
+            return match self.partial.get(&frame.bus) {
+                Some(partial) => partial.segment(),
+                None => Outcome::Ignored,
+            };
+        }
+        match self.partial.remove(&frame.bus) {
+            Some(done) => Outcome::Complete(done.finish(&self.scheme.name, frame, frame.frame_id)),
+            None => Outcome::Ignored,
+        }
+    }
+}
+
+// ============================================================================
+// Per-session configuration and pipeline hook
+// ============================================================================
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ReassemblyConfig {
+    #[serde(default)]
+    pub isotp: Vec<IsoTpChannel>,
+    #[serde(default)]
+    pub j1939_tp: bool,
+    #[serde(default)]
+    pub sequenced: Vec<SequencedScheme>,
+}
+
+struct SessionReassembly {
+    config: ReassemblyConfig,
+    stages: Vec<Box<dyn Reassembler>>,
+    links: VecDeque<ReassemblyLink>,
+}
+
+impl SessionReassembly {
+    fn new(config: ReassemblyConfig) -> Self {
+        let mut stages: Vec<Box<dyn Reassembler>> = Vec::new();
+        if !config.isotp.is_empty() {
+            stages.push(Box::new(IsoTpReassembler::new(config.isotp.clone())));
+        }
+        if config.j1939_tp {
+            stages.push(Box::new(J1939TpReassembler::default()));
+        }
+        for scheme in &config.sequenced {
+            stages.push(Box::new(SequencedReassembler::new(scheme.clone())));
+        }
+        Self { config, stages, links: VecDeque::new() }
+    }
+}
+
+/// Map of session_id -> reassemblers. Most sessions have none, so the
+/// append-path check is a read lock; only configured sessions take the write
+/// lock.
+static SESSION_REASSEMBLY: Lazy<RwLock<HashMap<String, SessionReassembly>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Run a batch through the session's reassemblers, inserting each completed
+/// message as a synthetic frame right after its final segment. Returns the
+/// batch unchanged when the session has no reassembly configured.
+pub fn process_frames(session_id: &str, frames: Vec<FrameMessage>) -> Vec<FrameMessage> {
+    if SESSION_REASSEMBLY.read().map(|s| !s.contains_key(session_id)).unwrap_or(true) {
+        return frames;
+    }
+
+    let mut sessions = match SESSION_REASSEMBLY.write() {
+        Ok(s) => s,
+        Err(_) => return frames,
+    };
+    let Some(session) = sessions.get_mut(session_id) else {
+        return frames;
+    };
+
+    let mut out = Vec::with_capacity(frames.len());
+    for mut frame in frames {
+        let mut completed = Vec::new();
+        for stage in session.stages.iter_mut() {
+            let correlation_id = match stage.process(&frame) {
+                Outcome::Ignored => continue,
+                Outcome::Segment { correlation_id } => correlation_id,
+                Outcome::Complete(msg) => {
+                    let id = msg.correlation_id;
+                    completed.push(msg);
+                    id
+                }
+            };
+            if frame.link.is_none() {
+                frame.link = Some(FrameLink { correlation_id, role: LinkRole::Segment, parent_rowid: None });
+            }
+        }
+        out.push(frame);
+        for msg in completed {
+            if session.links.len() >= MAX_LINKS_PER_SESSION {
+                session.links.pop_front();
+            }
+            session.links.push_back(ReassemblyLink {
+                protocol: msg.frame.protocol.clone(),
+                bus: msg.frame.bus,
+                frame_id: msg.frame.frame_id,
+                timestamp_us: msg.frame.timestamp_us,
+                first_segment_us: msg.first_segment_us,
+                segment_count: msg.segment_count,
+                segment_frame_ids: msg.segment_frame_ids,
+                length: msg.frame.bytes.len(),
+                correlation_id: msg.correlation_id,
+            });
+            out.push(msg.frame);
+        }
+    }
+    out
+}
+
+/// Remove a session's reassembly state. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut sessions) = SESSION_REASSEMBLY.write() {
+        sessions.remove(session_id);
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Configure (or reconfigure) reassembly for a session. In-flight partial
+/// messages are discarded.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_reassembly_config(session_id: String, config: ReassemblyConfig) -> Result<(), String> {
+    for scheme in &config.sequenced {
+        if scheme.last_flag == 0 || scheme.last_flag & scheme.index_mask != 0 {
+            return Err(format!(
+                "Scheme '{}': last_flag must be a bit outside index_mask",
+                scheme.name
+            ));
+        }
+    }
+    tlog!(
+        "[reassembly:{}] {} ISO-TP channels, J1939 TP {}, {} custom schemes",
+        session_id,
+        config.isotp.len(),
+        if config.j1939_tp { "on" } else { "off" },
+        config.sequenced.len()
+    );
+    SESSION_REASSEMBLY
+        .write()
+        .map_err(|e| format!("Reassembly lock poisoned: {e}"))?
+        .insert(session_id, SessionReassembly::new(config));
+    Ok(())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_reassembly_config(session_id: String) {
+    clear_session(&session_id);
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_reassembly_config(session_id: String) -> Option<ReassemblyConfig> {
+    SESSION_REASSEMBLY
+        .read()
+        .ok()
+        .and_then(|s| s.get(&session_id).map(|r| r.config.clone()))
+}
+
+/// Links for messages reassembled at or after `since_us` (all if None).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_reassembly_links(session_id: String, since_us: Option<u64>) -> Vec<ReassemblyLink> {
+    SESSION_REASSEMBLY
+        .read()
+        .ok()
+        .and_then(|s| {
+            s.get(&session_id).map(|r| {
+                r.links
+                    .iter()
+                    .filter(|l| since_us.is_none_or(|t| l.timestamp_us >= t))
+                    .cloned()
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(id: u32, ts: u64, bytes: &[u8], extended: bool) -> FrameMessage {
+        FrameMessage { is_extended: extended, ..test_frame(id, ts, bytes) }
+    }
+
+    fn done(outcome: Outcome) -> Option<ReassembledMessage> {
+        match outcome {
+            Outcome::Complete(msg) => Some(msg),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn isotp_multi_frame() {
+        let mut r = IsoTpReassembler::new(vec![IsoTpChannel {
+            frame_id: 0x7E8,
+            bus: None,
+            extended_addressing: false,
+        }]);
+        let Outcome::Segment { correlation_id } =
+            r.process(&frame(0x7E8, 1, &[0x10, 0x0A, 1, 2, 3, 4, 5, 6], false))
+        else {
+            panic!("first frame should start a transfer");
+        };
+        let msg = done(r.process(&frame(0x7E8, 2, &[0x21, 7, 8, 9, 10, 0xAA, 0xAA, 0xAA], false))).unwrap();
+        assert_eq!(msg.frame.bytes, (1..=10).collect::<Vec<u8>>());
+        assert_eq!(msg.frame.protocol, "isotp");
+        assert_eq!(msg.segment_count, 2);
+        assert_eq!(msg.first_segment_us, 1);
+        assert_eq!(msg.correlation_id, correlation_id);
+        assert_eq!(msg.frame.link.as_ref().map(|l| l.role), Some(LinkRole::Message));
+    }
+
+    #[test]
+    fn isotp_sequence_gap_abandons() {
+        let mut r = IsoTpReassembler::new(vec![IsoTpChannel {
+            frame_id: 0x7E8,
+            bus: None,
+            extended_addressing: false,
+        }]);
+        r.process(&frame(0x7E8, 1, &[0x10, 0x14, 1, 2, 3, 4, 5, 6], false));
+        assert!(done(r.process(&frame(0x7E8, 2, &[0x22, 0, 0, 0, 0, 0, 0, 0], false))).is_none());
+        assert!(r.partial.is_empty());
+    }
+
+    #[test]
+    fn j1939_bam() {
+        let mut r = J1939TpReassembler::default();
+        // BAM from SA 0x00 announcing 9 bytes of PGN 0xFEE3 in 2 packets
+        let cm = 0x18EC_FF00;
+        let dt = 0x18EB_FF00;
+        assert!(matches!(
+            r.process(&frame(cm, 1, &[32, 9, 0, 2, 0xFF, 0xE3, 0xFE, 0x00], true)),
+            Outcome::Segment { .. }
+        ));
+        assert!(done(r.process(&frame(dt, 2, &[1, 1, 2, 3, 4, 5, 6, 7], true))).is_none());
+        let msg = done(r.process(&frame(dt, 3, &[2, 8, 9, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF], true))).unwrap();
+        assert_eq!(msg.frame.bytes, (1..=9).collect::<Vec<u8>>());
+        assert_eq!(msg.frame.frame_id, 0x18FE_E300);
+        assert_eq!(msg.frame.source_address, Some(0));
+        assert_eq!(msg.segment_frame_ids, vec![cm, dt]);
+    }
+
+    #[test]
+    fn sequenced_scheme() {
+        let mut r = SequencedReassembler::new(SequencedScheme {
+            name: "bms".to_string(),
+            frame_id: 0x400,
+            bus: None,
+            index_byte: 0,
+            index_mask: 0x7F,
+            last_flag: 0x80,
+            payload_offset: 1,
+        });
+        assert!(done(r.process(&frame(0x400, 1, &[0x00, 1, 2], false))).is_none());
+        let msg = done(r.process(&frame(0x400, 2, &[0x81, 3, 4], false))).unwrap();
+        assert_eq!(msg.frame.bytes, vec![1, 2, 3, 4]);
+        assert_eq!(msg.frame.protocol, "bms");
+    }
+}
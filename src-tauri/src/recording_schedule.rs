@@ -0,0 +1,359 @@
+// ui/src-tauri/src/recording_schedule.rs
+//
+// Scheduled and timed recordings. A session can be armed to start recording
+// at a wall-clock time (or straight away) and to stop after a fixed duration
+// and/or frame count. When a limit is reached the session is suspended and its
+// capture orphaned, so it shows up as a standalone capture and the session can
+// record again later — the unattended overnight capture case.
+//
+// Starting goes through the usual start path, which gives the session a fresh
+// capture. If the session is already streaming it is suspended first so the
+// recording doesn't include what came before. Limits are checked every
+// POLL_INTERVAL, so a frame limit may overshoot by one poll's worth of frames.
+//
+// One schedule per session; arming again replaces it. The last state is kept
+// until the session is destroyed and is emitted as `recording-changed` on every
+// phase change.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::capture_store;
+use crate::io::{self, IOState};
+
+/// How often an active recording checks its limits.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Longest single sleep while armed, so wall-clock changes are followed.
+const ARMED_TICK: Duration = Duration::from_secs(1);
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordingSchedule {
+    /// Wall-clock start in microseconds since the epoch (None or past = now)
+    #[serde(default)]
+    pub start_at_us: Option<u64>,
+    /// Stop after recording for this long
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    /// Stop after this many frames
+    #[serde(default)]
+    pub max_frames: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingPhase {
+    Armed,
+    Recording,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+/// Why a recording stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingEnd {
+    Duration,
+    Frames,
+    /// The session was stopped by something else
+    Stopped,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RecordingState {
+    pub session_id: String,
+    pub schedule: RecordingSchedule,
+    pub phase: RecordingPhase,
+    pub started_at_us: Option<u64>,
+    pub frames: u64,
+    pub ended: Option<RecordingEnd>,
+    /// The orphaned capture holding the recording
+    pub capture_id: Option<String>,
+    pub error: Option<String>,
+}
+
+struct Scheduled {
+    state: RecordingState,
+    task: Option<tauri::async_runtime::JoinHandle<()>>,
+}
+
+static SCHEDULES: Lazy<Mutex<HashMap<String, Scheduled>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Schedule logic
+// ============================================================================
+
+fn now_us() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_micros() as u64).unwrap_or(0)
+}
+
+impl RecordingSchedule {
+    fn validate(&self) -> Result<(), String> {
+        if self.duration_ms.is_none() && self.max_frames.is_none() {
+            return Err("A recording needs a duration or a frame count".to_string());
+        }
+        if self.duration_ms == Some(0) || self.max_frames == Some(0) {
+            return Err("Recording limits must be greater than zero".to_string());
+        }
+        Ok(())
+    }
+
+    /// Time left before the recording should start.
+    fn start_delay(&self, now_us: u64) -> Duration {
+        let start = self.start_at_us.unwrap_or(now_us);
+        Duration::from_micros(start.saturating_sub(now_us))
+    }
+
+    /// Which limit, if any, has been reached.
+    fn limit_reached(&self, elapsed: Duration, frames: u64) -> Option<RecordingEnd> {
+        if self.max_frames.is_some_and(|max| frames >= max) {
+            return Some(RecordingEnd::Frames);
+        }
+        if self.duration_ms.is_some_and(|ms| elapsed >= Duration::from_millis(ms)) {
+            return Some(RecordingEnd::Duration);
+        }
+        None
+    }
+}
+
+/// Update a session's state and emit it. Returns false when the schedule was
+/// removed.
+fn update(app: &AppHandle, session_id: &str, f: impl FnOnce(&mut RecordingState)) -> bool {
+    let state = {
+        let Ok(mut schedules) = SCHEDULES.lock() else { return false };
+        let Some(scheduled) = schedules.get_mut(session_id) else { return false };
+        f(&mut scheduled.state);
+        scheduled.state.clone()
+    };
+    io::emit_to_session(app, session_id, "recording-changed", state);
+    true
+}
+
+async fn run(app: AppHandle, session_id: String, schedule: RecordingSchedule) {
+    // Armed: wait for the start time
+    loop {
+        let delay = schedule.start_delay(now_us());
+        if delay.is_zero() {
+            break;
+        }
+        tokio::time::sleep(delay.min(ARMED_TICK)).await;
+    }
+
+    if let Err(e) = begin(&session_id).await {
+        tlog!("[recording_schedule] Failed to start '{}': {}", session_id, e);
+        update(&app, &session_id, |s| {
+            s.phase = RecordingPhase::Failed;
+            s.error = Some(e);
+        });
+        return;
+    }
+    let started = tokio::time::Instant::now();
+    tlog!("[recording_schedule] Recording '{}' started", session_id);
+    if !update(&app, &session_id, |s| {
+        s.phase = RecordingPhase::Recording;
+        s.started_at_us = Some(now_us());
+    }) {
+        return;
+    }
+
+    let mut frames = 0u64;
+    let end = loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        frames = capture_store::get_session_frame_capture_id(&session_id)
+            .map(|id| capture_store::get_capture_count(&id) as u64)
+            .unwrap_or(frames);
+        if let Some(end) = schedule.limit_reached(started.elapsed(), frames) {
+            break end;
+        }
+        match io::get_session_state(&session_id).await {
+            Some(IOState::Running | IOState::Starting | IOState::Paused) => {}
+            Some(_) => break RecordingEnd::Stopped,
+            None => {
+                update(&app, &session_id, |s| {
+                    s.phase = RecordingPhase::Failed;
+                    s.error = Some("Session ended during recording".to_string());
+                });
+                return;
+            }
+        }
+    };
+
+    let result = finish(&session_id).await;
+    tlog!("[recording_schedule] Recording '{}' ended ({:?}, {} frames)", session_id, end, frames);
+    update(&app, &session_id, |s| {
+        s.frames = frames;
+        s.ended = Some(end);
+        match result {
+            Ok(capture_id) => {
+                s.phase = RecordingPhase::Completed;
+                s.capture_id = capture_id;
+            }
+            Err(e) => {
+                s.phase = RecordingPhase::Failed;
+                s.error = Some(e);
+            }
+        }
+    });
+}
+
+/// Start the session with a fresh capture.
+async fn begin(session_id: &str) -> Result<(), String> {
+    match io::get_session_state(session_id).await {
+        None => return Err(format!("Session '{}' not found", session_id)),
+        Some(IOState::Stopped) => {}
+        Some(_) => {
+            io::suspend_session(session_id).await?;
+        }
+    }
+    io::start_session(session_id).await?;
+    Ok(())
+}
+
+/// Suspend the session and orphan its capture. Returns the frame capture ID.
+async fn finish(session_id: &str) -> Result<Option<String>, String> {
+    io::suspend_session(session_id).await?;
+    let orphaned = capture_store::orphan_captures_for_session(session_id);
+    let capture_id = orphaned
+        .iter()
+        .find(|o| o.kind == capture_store::CaptureKind::Frames)
+        .map(|o| o.capture_id.clone());
+    io::emit_capture_orphaned_as_changed(session_id, orphaned);
+    Ok(capture_id)
+}
+
+/// Cancel and forget a session's schedule. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut schedules) = SCHEDULES.lock() {
+        if let Some(task) = schedules.remove(session_id).and_then(|s| s.task) {
+            task.abort();
+        }
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Arm a session to record, replacing any existing schedule.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn schedule_recording(
+    app: AppHandle,
+    session_id: String,
+    schedule: RecordingSchedule,
+) -> Result<RecordingState, String> {
+    schedule.validate()?;
+    if !io::session_exists(&session_id).await {
+        return Err(format!("Session '{}' not found", session_id));
+    }
+    clear_session(&session_id);
+
+    let state = RecordingState {
+        session_id: session_id.clone(),
+        schedule: schedule.clone(),
+        phase: RecordingPhase::Armed,
+        started_at_us: None,
+        frames: 0,
+        ended: None,
+        capture_id: None,
+        error: None,
+    };
+    {
+        let mut schedules = SCHEDULES.lock().map_err(|e| e.to_string())?;
+        schedules.insert(session_id.clone(), Scheduled { state: state.clone(), task: None });
+    }
+    let task = tauri::async_runtime::spawn(run(app.clone(), session_id.clone(), schedule));
+    if let Ok(mut schedules) = SCHEDULES.lock() {
+        if let Some(scheduled) = schedules.get_mut(&session_id) {
+            scheduled.task = Some(task);
+        }
+    }
+    io::emit_to_session(&app, &session_id, "recording-changed", state.clone());
+    Ok(state)
+}
+
+/// Cancel an armed or running recording. A running one is stopped where it
+/// is and its capture orphaned as if it had reached its limit.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cancel_recording(app: AppHandle, session_id: String) -> Result<Option<RecordingState>, String> {
+    let phase = {
+        let mut schedules = SCHEDULES.lock().map_err(|e| e.to_string())?;
+        let Some(scheduled) = schedules.get_mut(&session_id) else { return Ok(None) };
+        if let Some(task) = scheduled.task.take() {
+            task.abort();
+        }
+        scheduled.state.phase
+    };
+
+    match phase {
+        RecordingPhase::Armed => {
+            update(&app, &session_id, |s| s.phase = RecordingPhase::Cancelled);
+        }
+        RecordingPhase::Recording => {
+            let result = finish(&session_id).await;
+            update(&app, &session_id, |s| {
+                s.phase = RecordingPhase::Cancelled;
+                match result {
+                    Ok(capture_id) => s.capture_id = capture_id,
+                    Err(e) => s.error = Some(e),
+                }
+            });
+        }
+        _ => {}
+    }
+    Ok(get_recording_state(session_id))
+}
+
+/// A session's recording schedule and progress, if one was armed.
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_recording_state(session_id: String) -> Option<RecordingState> {
+    let schedules = SCHEDULES.lock().ok()?;
+    let scheduled = schedules.get(&session_id)?;
+    let mut state = scheduled.state.clone();
+    if state.phase == RecordingPhase::Recording {
+        state.frames = capture_store::get_session_frame_capture_id(&session_id)
+            .map(|id| capture_store::get_capture_count(&id) as u64)
+            .unwrap_or(state.frames);
+    }
+    Some(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(duration_ms: Option<u64>, max_frames: Option<u64>) -> RecordingSchedule {
+        RecordingSchedule { start_at_us: None, duration_ms, max_frames }
+    }
+
+    #[test]
+    fn a_schedule_needs_a_positive_limit() {
+        assert!(schedule(None, None).validate().is_err());
+        assert!(schedule(Some(0), None).validate().is_err());
+        assert!(schedule(None, Some(1_000)).validate().is_ok());
+    }
+
+    #[test]
+    fn start_delay_counts_down_to_the_start_time() {
+        let mut s = schedule(Some(1_000), None);
+        assert_eq!(s.start_delay(5_000_000), Duration::ZERO);
+        s.start_at_us = Some(8_000_000);
+        assert_eq!(s.start_delay(5_000_000), Duration::from_secs(3));
+        assert_eq!(s.start_delay(9_000_000), Duration::ZERO);
+    }
+
+    #[test]
+    fn the_first_limit_reached_ends_the_recording() {
+        let s = schedule(Some(60_000), Some(500));
+        assert_eq!(s.limit_reached(Duration::from_secs(10), 499), None);
+        assert_eq!(s.limit_reached(Duration::from_secs(10), 500), Some(RecordingEnd::Frames));
+        assert_eq!(s.limit_reached(Duration::from_secs(60), 10), Some(RecordingEnd::Duration));
+    }
+}
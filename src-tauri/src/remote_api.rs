@@ -0,0 +1,454 @@
+// ui/src-tauri/src/remote_api.rs
+//
+// Remote control API, so test automation and CI rigs can drive WireTAP
+// without the GUI. A small REST + WebSocket server on 127.0.0.1, off by
+// default (`remote_api_enabled`) and refusing to start without a bearer
+// token, kept in the system keyring like other secrets (see credentials.rs):
+//
+//   GET    /api/sessions                      list live sessions
+//   POST   /api/sessions                      open a session for a profile
+//                                             {profile_id, session_id?}
+//   POST   /api/sessions/{id}/start           start streaming
+//   POST   /api/sessions/{id}/stop            stop streaming
+//   DELETE /api/sessions/{id}                 destroy the session
+//   POST   /api/sessions/{id}/transmit        send a CAN frame (CanTransmitFrame)
+//   GET    /api/sessions/{id}/frames          WebSocket: new frames as JSON
+//   GET    /api/captures                      list captures
+//   GET    /api/captures/{id}/export?format=  download in an export format
+//
+// Handlers call the same functions as the Tauri commands, so a session opened
+// here shows up in the GUI like any other. Errors are JSON `{ "error": ... }`.
+// Exports are streamed from the capture database as they are written.
+
+use std::io::{BufWriter, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::capture_store::{self, CaptureKind};
+use crate::convert::{self, OutputFormat, Sink};
+use crate::io::{self, CanTransmitFrame};
+use crate::settings::AppSettings;
+use crate::{capture_db, credentials};
+
+/// How often the frames WebSocket checks the session capture for new frames.
+const FRAME_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Most frames sent in one WebSocket message.
+const FRAME_MESSAGE_LIMIT: usize = 1000;
+/// Frames read from the capture database per step of an export.
+const EXPORT_READ_FRAMES: usize = 5000;
+/// Export output is handed to the response body in chunks of this size.
+const EXPORT_CHUNK_BYTES: usize = 64 * 1024;
+/// Chunks queued for the response body before the export waits for the client.
+const EXPORT_QUEUE_CHUNKS: usize = 16;
+
+/// Keyring entry for the bearer token (profile, field; see credentials.rs).
+const TOKEN_PROFILE: &str = "remote-api";
+const TOKEN_FIELD: &str = "token";
+
+struct ServerHandle {
+    cancel: CancellationToken,
+    port: u16,
+}
+
+static HANDLE: Lazy<Mutex<Option<ServerHandle>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RemoteApiStatus {
+    pub running: bool,
+    pub port: Option<u16>,
+}
+
+fn status() -> RemoteApiStatus {
+    let port = HANDLE.lock().ok().and_then(|h| h.as_ref().map(|x| x.port));
+    RemoteApiStatus { running: port.is_some(), port }
+}
+
+/// The bearer token from the keyring. Settings files written before the token
+/// moved there still carry it in `remote_api_token`, which is used until the
+/// next save moves it (see `store_token`).
+pub fn load_token(settings: &AppSettings) -> String {
+    match credentials::get_credential(TOKEN_PROFILE, TOKEN_FIELD) {
+        Ok(Some(token)) => token,
+        Ok(None) => settings.remote_api_token.clone(),
+        Err(e) => {
+            tlog!("[remote_api] {e}");
+            settings.remote_api_token.clone()
+        }
+    }
+}
+
+/// Keep the bearer token in the keyring; an empty token removes it.
+pub fn store_token(token: &str) -> Result<(), String> {
+    if !token.is_empty() {
+        return credentials::store_credential(TOKEN_PROFILE, TOKEN_FIELD, token);
+    }
+    // Nothing secret to keep, so an unreachable keyring shouldn't fail the save
+    if let Err(e) = credentials::delete_credential(TOKEN_PROFILE, TOKEN_FIELD) {
+        tlog!("[remote_api] {e}");
+    }
+    Ok(())
+}
+
+/// Start the server on `127.0.0.1:port`. Binds synchronously so a port
+/// conflict comes back as an error.
+pub fn start(app: AppHandle, port: u16, token: String) -> Result<(), String> {
+    if token.is_empty() {
+        return Err("The remote API needs a token; set remote_api_token first".to_string());
+    }
+    if status().running {
+        return Err("Remote API already running".to_string());
+    }
+
+    let std_listener = std::net::TcpListener::bind(("127.0.0.1", port))
+        .map_err(|e| format!("Failed to bind remote API on 127.0.0.1:{port}: {e}"))?;
+    std_listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to set remote API listener non-blocking: {e}"))?;
+
+    let router = Router::new()
+        .route("/api/sessions", get(list_sessions).post(open_session))
+        .route("/api/sessions/{id}", delete(destroy_session))
+        .route("/api/sessions/{id}/start", post(start_session))
+        .route("/api/sessions/{id}/stop", post(stop_session))
+        .route("/api/sessions/{id}/transmit", post(transmit))
+        .route("/api/sessions/{id}/frames", get(frames_socket))
+        .route("/api/captures", get(list_captures))
+        .route("/api/captures/{id}/export", get(export_capture))
+        .layer(axum::middleware::from_fn_with_state(Arc::new(token), require_token))
+        .with_state(app);
+
+    let cancel = CancellationToken::new();
+    let shutdown = cancel.clone();
+    tauri::async_runtime::spawn(async move {
+        let listener = match tokio::net::TcpListener::from_std(std_listener) {
+            Ok(l) => l,
+            Err(e) => {
+                tlog!("[remote_api] Failed to adopt listener: {e}");
+                return;
+            }
+        };
+        let _ = axum::serve(listener, router)
+            .with_graceful_shutdown(async move { shutdown.cancelled().await })
+            .await;
+        tlog!("[remote_api] Server task exited");
+    });
+
+    if let Ok(mut guard) = HANDLE.lock() {
+        *guard = Some(ServerHandle { cancel, port });
+    }
+    tlog!("[remote_api] Listening on 127.0.0.1:{port}");
+    Ok(())
+}
+
+/// Stop the server if running.
+pub fn stop() {
+    if let Ok(mut guard) = HANDLE.lock() {
+        if let Some(handle) = guard.take() {
+            handle.cancel.cancel();
+            tlog!("[remote_api] Stopping on port {}", handle.port);
+        }
+    }
+}
+
+// ============================================================================
+// Auth and errors
+// ============================================================================
+
+fn authorized(header: Option<&str>, token: &str) -> bool {
+    match header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(given) => !token.is_empty() && tokens_match(given, token),
+        None => false,
+    }
+}
+
+/// Compare without an early exit, so response timing doesn't reveal how much
+/// of a guess was right. Both sides are hashed first so their lengths (and the
+/// token's) don't matter either.
+fn tokens_match(given: &str, token: &str) -> bool {
+    let (given, token) = (Sha256::digest(given.as_bytes()), Sha256::digest(token.as_bytes()));
+    let diff = given.iter().zip(token.iter()).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+    std::hint::black_box(diff) == 0
+}
+
+async fn require_token(State(token): State<Arc<String>>, req: Request, next: Next) -> Result<Response, StatusCode> {
+    let header = req.headers().get(header::AUTHORIZATION).and_then(|v| v.to_str().ok());
+    if !authorized(header, &token) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    Ok(next.run(req).await)
+}
+
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<String> for ApiError {
+    fn from(e: String) -> Self {
+        ApiError(StatusCode::BAD_REQUEST, e)
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+async fn require_session(session_id: &str) -> Result<(), ApiError> {
+    if io::session_exists(session_id).await {
+        Ok(())
+    } else {
+        Err(ApiError(StatusCode::NOT_FOUND, format!("Session '{}' not found", session_id)))
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+async fn list_sessions() -> Json<Vec<io::ActiveSessionInfo>> {
+    Json(io::list_sessions().await)
+}
+
+#[derive(Deserialize)]
+struct OpenSessionBody {
+    profile_id: String,
+    #[serde(default)]
+    session_id: Option<String>,
+}
+
+async fn open_session(State(app): State<AppHandle>, Json(body): Json<OpenSessionBody>) -> ApiResult<Value> {
+    Ok(Json(crate::mcp::session::open_for(app, body.profile_id, body.session_id, "remote").await?))
+}
+
+async fn start_session(Path(id): Path<String>) -> ApiResult<io::IOState> {
+    require_session(&id).await?;
+    Ok(Json(io::start_session(&id).await?))
+}
+
+async fn stop_session(Path(id): Path<String>) -> ApiResult<io::IOState> {
+    require_session(&id).await?;
+    Ok(Json(io::stop_session(&id).await?))
+}
+
+async fn destroy_session(Path(id): Path<String>) -> ApiResult<Value> {
+    require_session(&id).await?;
+    crate::sessions::destroy_reader_session(id.clone(), false).await?;
+    Ok(Json(json!({ "destroyed": id })))
+}
+
+async fn transmit(
+    State(app): State<AppHandle>,
+    Path(id): Path<String>,
+    Json(frame): Json<CanTransmitFrame>,
+) -> ApiResult<io::TransmitResult> {
+    require_session(&id).await?;
+    Ok(Json(crate::transmit::io_transmit_can_frame(app, id, frame).await?))
+}
+
+async fn list_captures() -> Json<Vec<capture_store::CaptureMetadata>> {
+    Json(capture_store::list_captures())
+}
+
+#[derive(Deserialize)]
+struct ExportQuery {
+    #[serde(default = "default_export_format")]
+    format: String,
+}
+
+fn default_export_format() -> String {
+    "csv".to_string()
+}
+
+/// Check the format and the capture before any of the response is sent.
+fn export_format(capture_id: &str, format: &str) -> Result<OutputFormat, ApiError> {
+    let output = convert::output_format(format)?;
+    match capture_store::get_capture_metadata(capture_id) {
+        Some(metadata) if metadata.kind == CaptureKind::Frames => Ok(output),
+        _ => Err(ApiError(StatusCode::NOT_FOUND, format!("Capture '{}' not found", capture_id))),
+    }
+}
+
+/// Hands export output to the response body. Blocks while the client is
+/// behind, and fails once it has gone away.
+struct BodyWriter(mpsc::Sender<std::io::Result<Vec<u8>>>);
+
+impl Write for BodyWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .blocking_send(Ok(buf.to_vec()))
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "Export client disconnected"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Write a capture to `out` in `format`, reading it from the database in
+/// chunks so it is never held in memory whole.
+fn write_export(capture_id: &str, format: OutputFormat, out: impl Write + Send) -> Result<u32, String> {
+    let mut chunk = capture_db::read_frame_chunk(capture_id, 0, EXPORT_READ_FRAMES)?;
+    let mut sink = Sink::new(format, out, chunk.first().map_or(0, |(_, f)| f.timestamp_us))?;
+    while let Some(&(last_rowid, _)) = chunk.last() {
+        for (_, frame) in &chunk {
+            sink.write_frame(frame)?;
+        }
+        chunk = capture_db::read_frame_chunk(capture_id, last_rowid, EXPORT_READ_FRAMES)?;
+    }
+    sink.finish()
+}
+
+async fn export_capture(Path(id): Path<String>, Query(query): Query<ExportQuery>) -> Result<Response, ApiError> {
+    let format = export_format(&id, &query.format)?;
+
+    let (tx, rx) = mpsc::channel(EXPORT_QUEUE_CHUNKS);
+    let capture_id = id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let out = BufWriter::with_capacity(EXPORT_CHUNK_BYTES, BodyWriter(tx.clone()));
+        if let Err(e) = write_export(&capture_id, format, out) {
+            tlog!("[remote_api] Export of {capture_id} failed: {e}");
+            // The status line has gone out; end the body with an error so the
+            // client sees a failed download rather than a short file
+            let _ = tx.blocking_send(Err(std::io::Error::other(e)));
+        }
+    });
+    let chunks = futures::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|chunk| (chunk, rx)) });
+    let body = Body::from_stream(chunks);
+    let filename = format!("{}.{}", id, query.format.trim_start_matches('.'));
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+async fn frames_socket(Path(id): Path<String>, ws: WebSocketUpgrade) -> Result<Response, ApiError> {
+    require_session(&id).await?;
+    Ok(ws.on_upgrade(move |socket| stream_frames(socket, id)))
+}
+
+/// Rowid of the newest frame in a capture, or 0 when it is empty.
+fn live_point(capture_id: &str) -> i64 {
+    capture_db::get_rowid_range(capture_id).ok().flatten().map_or(0, |(_, max)| max)
+}
+
+/// Send frames as they reach the session capture, starting from the live
+/// point. The position is the last rowid sent rather than a frame index, so
+/// ring trims dropping the oldest frames don't make it skip any. A new capture
+/// (session restarted) is followed from its start.
+async fn stream_frames(mut socket: WebSocket, session_id: String) {
+    let mut capture_id = capture_store::get_session_frame_capture_id(&session_id);
+    let mut last_rowid = capture_id.as_deref().map_or(0, live_point);
+    let mut tick = tokio::time::interval(FRAME_POLL_INTERVAL);
+
+    loop {
+        tokio::select! {
+            msg = socket.recv() => match msg {
+                Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                Some(Ok(_)) => {}
+            },
+            _ = tick.tick() => {
+                if !io::session_exists(&session_id).await {
+                    break;
+                }
+                let current = capture_store::get_session_frame_capture_id(&session_id);
+                if current != capture_id {
+                    capture_id = current;
+                    last_rowid = 0;
+                }
+                let Some(id) = capture_id.as_deref() else { continue };
+                let chunk = match capture_db::read_frame_chunk(id, last_rowid, FRAME_MESSAGE_LIMIT) {
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        tlog!("[remote_api] Failed to read frames for {session_id}: {e}");
+                        continue;
+                    }
+                };
+                let Some(&(rowid, _)) = chunk.last() else { continue };
+                last_rowid = rowid;
+                let frames: Vec<io::FrameMessage> = chunk.into_iter().map(|(_, frame)| frame).collect();
+                let text = json!({ "session_id": session_id, "frames": frames }).to_string();
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    let _ = socket.send(Message::Close(None)).await;
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Whether the remote API is listening, and on which port.
+#[tauri::command]
+pub fn get_remote_api_status() -> RemoteApiStatus {
+    status()
+}
+
+/// Start or stop the remote API with the port and token from settings.
+#[tauri::command]
+pub fn toggle_remote_api(app: AppHandle, enabled: bool) -> Result<RemoteApiStatus, String> {
+    stop();
+    if enabled {
+        let settings = crate::settings::load_settings_sync(&app)?;
+        let token = load_token(&settings);
+        start(app, settings.remote_api_port, token)?;
+    }
+    Ok(status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_need_the_exact_bearer_token() {
+        assert!(authorized(Some("Bearer s3cret"), "s3cret"));
+        assert!(!authorized(Some("Bearer wrong"), "s3cret"));
+        assert!(!authorized(Some("s3cret"), "s3cret"));
+        assert!(!authorized(None, "s3cret"));
+    }
+
+    #[test]
+    fn an_empty_token_never_authorizes() {
+        assert!(!authorized(Some("Bearer "), ""));
+        assert!(!authorized(None, ""));
+    }
+
+    #[test]
+    fn tokens_of_any_length_compare_safely() {
+        assert!(!authorized(Some("Bearer s3cre"), "s3cret"));
+        assert!(!authorized(Some("Bearer s3cret-and-more"), "s3cret"));
+    }
+
+    #[test]
+    fn export_rejects_unknown_formats_and_captures() {
+        let bad_format = export_format("missing", "xyz").err().map(|e| e.0);
+        assert_eq!(bad_format, Some(StatusCode::BAD_REQUEST));
+        let missing = export_format("no-such-capture", "csv").err().map(|e| e.0);
+        assert_eq!(missing, Some(StatusCode::NOT_FOUND));
+    }
+}
@@ -0,0 +1,698 @@
+// ui/src-tauri/src/report.rs
+//
+// Screenshot-free capture reports. `generate_report` walks a capture (or the
+// live capture of a session) once in chunks and renders a self-contained HTML
+// or Markdown document: summary, per-bus totals, top talkers, a per-frame
+// table, timing/DLC anomalies, the capture's annotations plus any extra notes,
+// and selected signals plotted as inline SVG. Intended for attaching to tickets without manual screenshots.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::capture_db;
+use crate::capture_store::{self, CaptureAnnotation, CaptureKind, CaptureMetadata};
+use crate::signal_bits::SignalLayout;
+
+/// Frames read per chunk while scanning the capture.
+const SCAN_CHUNK: usize = 10_000;
+/// Points kept per plot after downsampling.
+const MAX_PLOT_POINTS: usize = 1_000;
+/// Minimum frames for an ID before its timing is checked for gaps.
+const MIN_FRAMES_FOR_GAP_CHECK: u64 = 10;
+
+const PLOT_WIDTH: f64 = 720.0;
+const PLOT_HEIGHT: f64 = 200.0;
+const PLOT_MARGIN: f64 = 40.0;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Html,
+    Markdown,
+}
+
+/// A signal to plot, described by its raw bit layout.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReportPlot {
+    pub label: String,
+    #[serde(default)]
+    pub unit: Option<String>,
+    #[serde(flatten)]
+    pub layout: SignalLayout,
+}
+
+/// A free-text note pinned to a point in the capture.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReportNote {
+    #[serde(default)]
+    pub timestamp_us: Option<u64>,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ReportRequest {
+    /// Capture to report on (exactly one of capture_id / session_id)
+    #[serde(default)]
+    pub capture_id: Option<String>,
+    /// Session whose frame capture is reported on
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub format: ReportFormat,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Number of top talkers to list (default 10)
+    #[serde(default)]
+    pub top_talkers: Option<usize>,
+    /// A gap is an interval larger than this multiple of the ID's mean period (default 3)
+    #[serde(default)]
+    pub gap_factor: Option<f64>,
+    #[serde(default)]
+    pub plots: Vec<ReportPlot>,
+    /// Notes to add to the capture's stored annotations
+    #[serde(default)]
+    pub notes: Vec<ReportNote>,
+    /// Write the report to this path as well as returning it
+    #[serde(default)]
+    pub output_path: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct GeneratedReport {
+    pub format: ReportFormat,
+    pub content: String,
+    pub path: Option<String>,
+    pub frame_count: u64,
+    pub anomaly_count: usize,
+}
+
+/// Per (bus, frame_id, is_extended) statistics gathered during the scan.
+#[derive(Clone, Debug, Default)]
+struct FrameStats {
+    count: u64,
+    bytes: u64,
+    last_us: u64,
+    interval_sum_us: u64,
+    max_interval_us: u64,
+    max_interval_at_us: u64,
+    dlcs: BTreeMap<u8, u64>,
+}
+
+#[derive(Clone, Debug)]
+struct Anomaly {
+    kind: &'static str,
+    bus: u8,
+    frame_id: u32,
+    is_extended: bool,
+    timestamp_us: Option<u64>,
+    detail: String,
+}
+
+struct ScanResult {
+    frames: u64,
+    first_us: Option<u64>,
+    last_us: Option<u64>,
+    stats: BTreeMap<(u8, u32, bool), FrameStats>,
+    series: Vec<Vec<(u64, f64)>>,
+}
+
+// ============================================================================
+// Scan
+// ============================================================================
+
+fn scan_capture(capture_id: &str, plots: &[ReportPlot], expected: usize) -> Result<ScanResult, String> {
+    let mut result = ScanResult {
+        frames: 0,
+        first_us: None,
+        last_us: None,
+        stats: BTreeMap::new(),
+        series: vec![Vec::new(); plots.len()],
+    };
+    // Keep every Nth sample so each series stays near MAX_PLOT_POINTS without
+    // a second pass.
+    let stride = (expected / MAX_PLOT_POINTS).max(1) as u64;
+    let mut plot_hits = vec![0u64; plots.len()];
+
+    let mut after_rowid = 0i64;
+    loop {
+        let chunk = capture_db::read_frame_chunk(capture_id, after_rowid, SCAN_CHUNK)?;
+        let Some((last_rowid, _)) = chunk.last() else { break };
+        after_rowid = *last_rowid;
+
+        for (_, f) in &chunk {
+            result.frames += 1;
+            result.first_us = Some(result.first_us.map_or(f.timestamp_us, |t| t.min(f.timestamp_us)));
+            result.last_us = Some(result.last_us.map_or(f.timestamp_us, |t| t.max(f.timestamp_us)));
+
+            let s = result.stats.entry((f.bus, f.frame_id, f.is_extended)).or_default();
+            if s.count > 0 {
+                let interval = f.timestamp_us.saturating_sub(s.last_us);
+                s.interval_sum_us += interval;
+                if interval > s.max_interval_us {
+                    s.max_interval_us = interval;
+                    s.max_interval_at_us = s.last_us;
+                }
+            }
+            s.count += 1;
+            s.bytes += f.bytes.len() as u64;
+            s.last_us = f.timestamp_us;
+            *s.dlcs.entry(f.dlc).or_default() += 1;
+
+            for (i, plot) in plots.iter().enumerate() {
+                if let Some(v) = plot.layout.physical_value(f) {
+                    if plot_hits[i] % stride == 0 {
+                        result.series[i].push((f.timestamp_us, v));
+                    }
+                    plot_hits[i] += 1;
+                }
+            }
+        }
+
+        if chunk.len() < SCAN_CHUNK {
+            break;
+        }
+    }
+    Ok(result)
+}
+
+fn find_anomalies(stats: &BTreeMap<(u8, u32, bool), FrameStats>, gap_factor: f64) -> Vec<Anomaly> {
+    let mut out = Vec::new();
+    for (&(bus, frame_id, is_extended), s) in stats {
+        if s.count >= MIN_FRAMES_FOR_GAP_CHECK {
+            let mean = s.interval_sum_us as f64 / (s.count - 1) as f64;
+            if mean > 0.0 && s.max_interval_us as f64 > mean * gap_factor {
+                out.push(Anomaly {
+                    kind: "gap",
+                    bus,
+                    frame_id,
+                    is_extended,
+                    timestamp_us: Some(s.max_interval_at_us),
+                    detail: format!(
+                        "{} silence (mean period {})",
+                        fmt_duration(s.max_interval_us as f64),
+                        fmt_duration(mean)
+                    ),
+                });
+            }
+        }
+        if s.dlcs.len() > 1 {
+            let dlcs: Vec<String> = s.dlcs.iter().map(|(d, n)| format!("{d}×{n}")).collect();
+            out.push(Anomaly {
+                kind: "dlc_change",
+                bus,
+                frame_id,
+                is_extended,
+                timestamp_us: None,
+                detail: format!("multiple DLCs seen: {}", dlcs.join(", ")),
+            });
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Formatting helpers
+// ============================================================================
+
+/// Hex frame id with standard (3) / extended (8) nibble padding.
+fn hex_id(id: u32, is_extended: bool) -> String {
+    let width = if is_extended { 8 } else { 3 };
+    format!("0x{:0width$X}", id, width = width)
+}
+
+fn fmt_time(us: u64) -> String {
+    chrono::DateTime::from_timestamp_micros(us as i64)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S%.3f UTC").to_string())
+        .unwrap_or_else(|| us.to_string())
+}
+
+fn fmt_duration(us: f64) -> String {
+    if us >= 1_000_000.0 {
+        format!("{:.2} s", us / 1_000_000.0)
+    } else if us >= 1_000.0 {
+        format!("{:.1} ms", us / 1_000.0)
+    } else {
+        format!("{:.0} µs", us)
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Percent-encode an SVG document for a `data:` URI (Markdown can't embed SVG inline).
+fn svg_data_uri(svg: &str) -> String {
+    let mut out = String::from("data:image/svg+xml;utf8,");
+    for c in svg.chars() {
+        match c {
+            '%' | '#' | '<' | '>' | '"' | '\'' | '\n' | ' ' | '(' | ')' => {
+                let mut buf = [0u8; 4];
+                for b in c.encode_utf8(&mut buf).bytes() {
+                    let _ = write!(out, "%{:02X}", b);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Render a time series as a standalone SVG line chart.
+fn render_svg_plot(plot: &ReportPlot, points: &[(u64, f64)]) -> String {
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {w} {h}\" width=\"{w}\" height=\"{h}\" font-family=\"sans-serif\" font-size=\"11\">",
+        w = PLOT_WIDTH,
+        h = PLOT_HEIGHT
+    );
+    let _ = write!(svg, "<rect width=\"100%\" height=\"100%\" fill=\"#fff\"/>");
+    let unit = plot.unit.as_deref().map(|u| format!(" ({u})")).unwrap_or_default();
+    let _ = write!(
+        svg,
+        "<text x=\"{}\" y=\"14\" font-weight=\"bold\">{}{}</text>",
+        PLOT_MARGIN,
+        escape_html(&plot.label),
+        escape_html(&unit)
+    );
+
+    if points.is_empty() {
+        let _ = write!(
+            svg,
+            "<text x=\"{}\" y=\"{}\" fill=\"#888\">No samples</text></svg>",
+            PLOT_WIDTH / 2.0 - 30.0,
+            PLOT_HEIGHT / 2.0
+        );
+        return svg;
+    }
+
+    let t0 = points.first().map(|p| p.0).unwrap_or(0);
+    let t1 = points.last().map(|p| p.0).unwrap_or(t0).max(t0 + 1);
+    let (mut vmin, mut vmax) = points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| (lo.min(p.1), hi.max(p.1)));
+    if (vmax - vmin).abs() < f64::EPSILON {
+        vmin -= 1.0;
+        vmax += 1.0;
+    }
+    let plot_w = PLOT_WIDTH - 2.0 * PLOT_MARGIN;
+    let plot_h = PLOT_HEIGHT - 2.0 * PLOT_MARGIN;
+    let x = |t: u64| PLOT_MARGIN + (t - t0) as f64 / (t1 - t0) as f64 * plot_w;
+    let y = |v: f64| PLOT_MARGIN + (1.0 - (v - vmin) / (vmax - vmin)) * plot_h;
+
+    let _ = write!(
+        svg,
+        "<rect x=\"{m}\" y=\"{m}\" width=\"{pw}\" height=\"{ph}\" fill=\"none\" stroke=\"#ccc\"/>",
+        m = PLOT_MARGIN,
+        pw = plot_w,
+        ph = plot_h
+    );
+    let _ = write!(svg, "<text x=\"2\" y=\"{}\">{:.3}</text>", PLOT_MARGIN + 4.0, vmax);
+    let _ = write!(svg, "<text x=\"2\" y=\"{}\">{:.3}</text>", PLOT_MARGIN + plot_h, vmin);
+    let _ = write!(
+        svg,
+        "<text x=\"{}\" y=\"{}\">0 s</text><text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+        PLOT_MARGIN,
+        PLOT_HEIGHT - PLOT_MARGIN + 14.0,
+        PLOT_WIDTH - PLOT_MARGIN,
+        PLOT_HEIGHT - PLOT_MARGIN + 14.0,
+        fmt_duration((t1 - t0) as f64)
+    );
+
+    let mut path = String::new();
+    for (t, v) in points {
+        let _ = write!(path, "{:.1},{:.1} ", x(*t), y(*v));
+    }
+    let _ = write!(
+        svg,
+        "<polyline fill=\"none\" stroke=\"#2563eb\" stroke-width=\"1.2\" points=\"{}\"/></svg>",
+        path.trim_end()
+    );
+    svg
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+
+/// Report sections as (heading, rows) tables so HTML and Markdown share one
+/// source of truth.
+struct Table {
+    heading: String,
+    columns: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+fn build_tables(
+    meta: &CaptureMetadata,
+    scan: &ScanResult,
+    anomalies: &[Anomaly],
+    notes: &[ReportNote],
+    top_n: usize,
+) -> Vec<Table> {
+    let duration_us = match (scan.first_us, scan.last_us) {
+        (Some(a), Some(b)) => b.saturating_sub(a),
+        _ => 0,
+    };
+    let secs = (duration_us as f64 / 1_000_000.0).max(f64::EPSILON);
+    let mut tables = Vec::new();
+
+    tables.push(Table {
+        heading: "Summary".to_string(),
+        columns: vec!["Field", "Value"],
+        rows: vec![
+            vec!["Capture".into(), format!("{} ({})", meta.name, meta.id)],
+            vec!["Frames".into(), scan.frames.to_string()],
+            vec!["Unique IDs".into(), scan.stats.len().to_string()],
+            vec!["Start".into(), scan.first_us.map(fmt_time).unwrap_or_else(|| "—".into())],
+            vec!["End".into(), scan.last_us.map(fmt_time).unwrap_or_else(|| "—".into())],
+            vec!["Duration".into(), fmt_duration(duration_us as f64)],
+            vec!["Average rate".into(), format!("{:.1} frames/s", scan.frames as f64 / secs)],
+        ],
+    });
+
+    let mut per_bus: BTreeMap<u8, (u64, u64)> = BTreeMap::new();
+    for (&(bus, _, _), s) in &scan.stats {
+        let e = per_bus.entry(bus).or_default();
+        e.0 += s.count;
+        e.1 += s.bytes;
+    }
+    tables.push(Table {
+        heading: "Buses".to_string(),
+        columns: vec!["Bus", "Frames", "Payload bytes", "Frames/s"],
+        rows: per_bus
+            .iter()
+            .map(|(bus, (frames, bytes))| {
+                vec![
+                    bus.to_string(),
+                    frames.to_string(),
+                    bytes.to_string(),
+                    format!("{:.1}", *frames as f64 / secs),
+                ]
+            })
+            .collect(),
+    });
+
+    let mut talkers: Vec<(&(u8, u32, bool), &FrameStats)> = scan.stats.iter().collect();
+    talkers.sort_by(|a, b| b.1.count.cmp(&a.1.count));
+    tables.push(Table {
+        heading: format!("Top {} talkers", top_n.min(talkers.len())),
+        columns: vec!["Bus", "ID", "Frames", "Share", "Frames/s"],
+        rows: talkers
+            .iter()
+            .take(top_n)
+            .map(|((bus, id, ext), s)| {
+                vec![
+                    bus.to_string(),
+                    hex_id(*id, *ext),
+                    s.count.to_string(),
+                    format!("{:.1}%", s.count as f64 * 100.0 / scan.frames.max(1) as f64),
+                    format!("{:.1}", s.count as f64 / secs),
+                ]
+            })
+            .collect(),
+    });
+
+    tables.push(Table {
+        heading: "Frames".to_string(),
+        columns: vec!["Bus", "ID", "Frames", "Mean period", "Max interval", "DLC"],
+        rows: scan
+            .stats
+            .iter()
+            .map(|((bus, id, ext), s)| {
+                let mean = if s.count > 1 {
+                    fmt_duration(s.interval_sum_us as f64 / (s.count - 1) as f64)
+                } else {
+                    "—".into()
+                };
+                let dlcs: Vec<String> = s.dlcs.keys().map(|d| d.to_string()).collect();
+                vec![
+                    bus.to_string(),
+                    hex_id(*id, *ext),
+                    s.count.to_string(),
+                    mean,
+                    fmt_duration(s.max_interval_us as f64),
+                    dlcs.join("/"),
+                ]
+            })
+            .collect(),
+    });
+
+    tables.push(Table {
+        heading: "Anomalies".to_string(),
+        columns: vec!["Kind", "Bus", "ID", "At", "Detail"],
+        rows: anomalies
+            .iter()
+            .map(|a| {
+                vec![
+                    a.kind.to_string(),
+                    a.bus.to_string(),
+                    hex_id(a.frame_id, a.is_extended),
+                    a.timestamp_us.map(fmt_time).unwrap_or_else(|| "—".into()),
+                    a.detail.clone(),
+                ]
+            })
+            .collect(),
+    });
+
+    if !notes.is_empty() {
+        tables.push(Table {
+            heading: "Notes".to_string(),
+            columns: vec!["At", "Note"],
+            rows: notes
+                .iter()
+                .map(|n| {
+                    vec![
+                        n.timestamp_us.map(fmt_time).unwrap_or_else(|| "—".into()),
+                        n.text.clone(),
+                    ]
+                })
+                .collect(),
+        });
+    }
+
+    tables
+}
+
+/// The capture's annotations and the caller's extra notes, in time order
+/// (annotations first on a tie, untimed notes last).
+fn collect_notes(annotations: Vec<CaptureAnnotation>, extra: &[ReportNote]) -> Vec<ReportNote> {
+    let mut notes: Vec<ReportNote> = annotations
+        .into_iter()
+        .map(|a| ReportNote { timestamp_us: Some(a.timestamp_us), text: a.text })
+        .collect();
+    notes.extend(extra.iter().cloned());
+    notes.sort_by_key(|n| (n.timestamp_us.is_none(), n.timestamp_us));
+    notes
+}
+
+fn render_html(title: &str, tables: &[Table], plots: &[(ReportPlot, String)]) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{t}</title>\n<style>\
+         body{{font-family:sans-serif;margin:2em;color:#111}}\
+         table{{border-collapse:collapse;margin-bottom:1.5em}}\
+         th,td{{border:1px solid #ccc;padding:4px 8px;text-align:left;font-size:13px}}\
+         th{{background:#f3f4f6}}td{{font-family:monospace}}\
+         </style></head><body>\n<h1>{t}</h1>\n<p>Generated {g}</p>\n",
+        t = escape_html(title),
+        g = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    for table in tables {
+        let _ = writeln!(out, "<h2>{}</h2>", escape_html(&table.heading));
+        if table.rows.is_empty() {
+            out.push_str("<p>None.</p>\n");
+            continue;
+        }
+        out.push_str("<table><tr>");
+        for c in &table.columns {
+            let _ = write!(out, "<th>{}</th>", escape_html(c));
+        }
+        out.push_str("</tr>\n");
+        for row in &table.rows {
+            out.push_str("<tr>");
+            for cell in row {
+                let _ = write!(out, "<td>{}</td>", escape_html(cell));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+    }
+    if !plots.is_empty() {
+        out.push_str("<h2>Signals</h2>\n");
+        for (_, svg) in plots {
+            let _ = writeln!(out, "<div>{svg}</div>");
+        }
+    }
+    out.push_str("</body></html>\n");
+    out
+}
+
+fn render_markdown(title: &str, tables: &[Table], plots: &[(ReportPlot, String)]) -> String {
+    let md_cell = |s: &str| s.replace('|', "\\|");
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}\n", title);
+    let _ = writeln!(out, "Generated {}\n", chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"));
+    for table in tables {
+        let _ = writeln!(out, "## {}\n", table.heading);
+        if table.rows.is_empty() {
+            out.push_str("None.\n\n");
+            continue;
+        }
+        let _ = writeln!(out, "| {} |", table.columns.join(" | "));
+        let _ = writeln!(out, "|{}|", vec!["---"; table.columns.len()].join("|"));
+        for row in &table.rows {
+            let cells: Vec<String> = row.iter().map(|c| md_cell(c)).collect();
+            let _ = writeln!(out, "| {} |", cells.join(" | "));
+        }
+        out.push('\n');
+    }
+    if !plots.is_empty() {
+        out.push_str("## Signals\n\n");
+        for (plot, svg) in plots {
+            let _ = writeln!(out, "![{}]({})\n", plot.label.replace(']', ""), svg_data_uri(svg));
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Build a report. Shared by the Tauri command and any headless caller.
+pub fn build_report(req: ReportRequest) -> Result<GeneratedReport, String> {
+    let capture_id = match (&req.capture_id, &req.session_id) {
+        (Some(c), None) => c.clone(),
+        (None, Some(s)) => capture_store::get_session_frame_capture_id(s)
+            .ok_or_else(|| format!("Session '{}' has no frame capture", s))?,
+        (Some(_), Some(_)) => return Err("Provide exactly one of capture_id / session_id, not both".into()),
+        (None, None) => return Err("Provide one of capture_id or session_id".into()),
+    };
+    let meta = capture_store::get_capture_metadata(&capture_id)
+        .ok_or_else(|| msg!(CAPTURE_NOT_FOUND, capture_id = capture_id))?;
+    if meta.kind != CaptureKind::Frames {
+        return Err("Reports are only available for frame captures".to_string());
+    }
+    for plot in &req.plots {
+        plot.layout.validate()?;
+    }
+
+    let gap_factor = req.gap_factor.filter(|f| f.is_finite() && *f > 1.0).unwrap_or(3.0);
+    let top_n = req.top_talkers.unwrap_or(10).max(1);
+
+    let scan = scan_capture(&capture_id, &req.plots, meta.count)?;
+    let anomalies = find_anomalies(&scan.stats, gap_factor);
+    let notes = collect_notes(capture_db::list_annotations(&capture_id)?, &req.notes);
+    let tables = build_tables(&meta, &scan, &anomalies, &notes, top_n);
+    let plots: Vec<(ReportPlot, String)> = req
+        .plots
+        .iter()
+        .zip(scan.series.iter())
+        .map(|(p, series)| (p.clone(), render_svg_plot(p, series)))
+        .collect();
+
+    let title = req.title.clone().unwrap_or_else(|| format!("Capture report — {}", meta.name));
+    let content = match req.format {
+        ReportFormat::Html => render_html(&title, &tables, &plots),
+        ReportFormat::Markdown => render_markdown(&title, &tables, &plots),
+    };
+
+    if let Some(path) = &req.output_path {
+        std::fs::write(path, &content).map_err(|e| format!("Failed to write report: {}", e))?;
+        tlog!("[report] Wrote {:?} report for capture '{}' to {}", req.format, capture_id, path);
+    }
+
+    Ok(GeneratedReport {
+        format: req.format,
+        content,
+        path: req.output_path,
+        frame_count: scan.frames,
+        anomaly_count: anomalies.len(),
+    })
+}
+
+/// Generate an HTML or Markdown report for a capture or session.
+/// Runs on a blocking thread — large captures take a full scan.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn generate_report(request: ReportRequest) -> Result<GeneratedReport, String> {
+    tokio::task::spawn_blocking(move || build_report(request))
+        .await
+        .map_err(|e| format!("Report task failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(count: u64, interval: u64, max: u64) -> FrameStats {
+        FrameStats {
+            count,
+            bytes: count * 8,
+            last_us: interval * count,
+            interval_sum_us: interval * (count - 1),
+            max_interval_us: max,
+            max_interval_at_us: 1_000,
+            dlcs: BTreeMap::from([(8, count)]),
+        }
+    }
+
+    #[test]
+    fn gap_anomaly_only_above_factor() {
+        let mut map = BTreeMap::new();
+        map.insert((0, 0x100, false), stats(20, 10_000, 25_000));
+        map.insert((0, 0x200, false), stats(20, 10_000, 100_000));
+        let anomalies = find_anomalies(&map, 3.0);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].frame_id, 0x200);
+        assert_eq!(anomalies[0].kind, "gap");
+    }
+
+    #[test]
+    fn dlc_change_is_reported() {
+        let mut s = stats(2, 10_000, 10_000);
+        s.dlcs.insert(4, 1);
+        let map = BTreeMap::from([((1, 0x7E8, false), s)]);
+        let anomalies = find_anomalies(&map, 3.0);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].kind, "dlc_change");
+    }
+
+    #[test]
+    fn annotations_and_extra_notes_are_merged_by_time() {
+        let annotation = |ts: u64, text: &str| CaptureAnnotation {
+            id: 0,
+            capture_id: "c".to_string(),
+            timestamp_us: ts,
+            frame_index: None,
+            text: text.to_string(),
+            color: None,
+            created_at: 0,
+        };
+        let extra = [
+            ReportNote { timestamp_us: None, text: "overall".to_string() },
+            ReportNote { timestamp_us: Some(20), text: "extra at 20".to_string() },
+        ];
+        let notes = collect_notes(vec![annotation(20, "door"), annotation(5, "ignition")], &extra);
+        let texts: Vec<&str> = notes.iter().map(|n| n.text.as_str()).collect();
+        assert_eq!(texts, ["ignition", "door", "extra at 20", "overall"]);
+    }
+
+    #[test]
+    fn svg_data_uri_escapes_markup() {
+        let uri = svg_data_uri("<svg a=\"b\">#</svg>");
+        assert!(uri.starts_with("data:image/svg+xml;utf8,"));
+        assert!(!uri[24..].contains('<'));
+        assert!(uri.contains("%23"));
+    }
+}
@@ -0,0 +1,587 @@
+// ui/src-tauri/src/responder.rs
+//
+// Responder — turns a transmit-capable session into a simple bench ECU
+// simulator. Incoming frames are matched against user-defined rules and the
+// backend transmits the configured reply:
+//
+//   - Fixed rules: a byte pattern on the request (reusing the capture-trigger
+//     FramePattern) answered by a fixed frame, optionally after a delay.
+//   - UDS servers: a request/response CAN ID pair served over ISO-TP with a
+//     DID table. ReadDataByIdentifier, WriteDataByIdentifier (updates the
+//     table), DiagnosticSessionControl, ECUReset and TesterPresent are
+//     answered; anything else gets a serviceNotSupported negative response.
+//     Long responses are segmented and paced by the tester's flow control.
+//
+// Frames reach the responder through a tap in
+// capture_store::append_frames_to_session (same shape as the io_test tap), so
+// every reader is covered. State is fetched by the frontend via
+// get_responder_state.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use tokio::sync::mpsc;
+
+use crate::capture_trigger::FramePattern;
+use crate::io::{self, CanTransmitFrame, FrameMessage, TransmitPayload};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A frame to send in reply to a matched request.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResponseFrame {
+    pub frame_id: u32,
+    pub data: Vec<u8>,
+    /// Bus to reply on (None = the bus the request arrived on)
+    #[serde(default)]
+    pub bus: Option<u8>,
+    #[serde(default)]
+    pub is_extended: bool,
+    #[serde(default)]
+    pub is_fd: bool,
+}
+
+/// Fixed request → response rule. Rules are checked in order; the first
+/// match wins.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResponderRule {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub request: FramePattern,
+    /// One or more frames sent in order
+    pub responses: Vec<ResponseFrame>,
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+/// A data identifier served by a UDS server.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UdsDid {
+    pub did: u16,
+    pub data: Vec<u8>,
+    /// Accept WriteDataByIdentifier for this DID
+    #[serde(default)]
+    pub writable: bool,
+}
+
+/// A simulated UDS server on one physical request/response ID pair.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UdsServerConfig {
+    pub request_id: u32,
+    pub response_id: u32,
+    #[serde(default)]
+    pub bus: Option<u8>,
+    #[serde(default)]
+    pub is_extended: bool,
+    /// ISO-TP padding byte for short frames
+    #[serde(default = "default_padding")]
+    pub padding: u8,
+    #[serde(default)]
+    pub dids: Vec<UdsDid>,
+}
+
+fn default_padding() -> u8 {
+    0xAA
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ResponderConfig {
+    #[serde(default)]
+    pub rules: Vec<ResponderRule>,
+    #[serde(default)]
+    pub uds: Vec<UdsServerConfig>,
+}
+
+/// Snapshot of a running responder, fetched by the frontend.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ResponderState {
+    pub session_id: String,
+    pub running: bool,
+    pub requests_matched: u64,
+    pub frames_sent: u64,
+    pub transmit_errors: u64,
+    pub last_error: Option<String>,
+    /// Current DID tables (reflect writes), keyed by UDS request ID
+    pub did_values: HashMap<u32, Vec<UdsDid>>,
+}
+
+// ============================================================================
+// State management
+// ============================================================================
+
+/// Frame tap senders: session_id -> responder task input.
+static RESPONDER_TAPS: Lazy<StdMutex<HashMap<String, mpsc::UnboundedSender<Vec<FrameMessage>>>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+static RESPONDER_STATES: Lazy<StdMutex<HashMap<String, ResponderState>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+fn update_state(session_id: &str, f: impl FnOnce(&mut ResponderState)) {
+    if let Ok(mut states) = RESPONDER_STATES.lock() {
+        if let Some(state) = states.get_mut(session_id) {
+            f(state);
+        }
+    }
+}
+
+/// Called from capture_store::append_frames_to_session to forward received
+/// frames to the session's responder (if one is running).
+pub fn tap_frames(session_id: &str, frames: &[FrameMessage]) {
+    let taps = match RESPONDER_TAPS.lock() {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+    if let Some(sender) = taps.get(session_id) {
+        // Our own replies echo back as tx frames — never answer those.
+        let rx: Vec<FrameMessage> = frames
+            .iter()
+            .filter(|f| f.direction.as_deref() != Some("tx"))
+            .cloned()
+            .collect();
+        if !rx.is_empty() {
+            let _ = sender.send(rx);
+        }
+    }
+}
+
+/// Stop a session's responder. Dropping the tap sender ends the task. The
+/// last state stays readable until the session is destroyed.
+pub fn stop_responder(session_id: &str) {
+    let removed = RESPONDER_TAPS.lock().ok().and_then(|mut t| t.remove(session_id));
+    if removed.is_some() {
+        update_state(session_id, |s| s.running = false);
+        tlog!("[responder:{}] Stopped", session_id);
+    }
+}
+
+/// Stop the responder and drop its state. Called when the session is
+/// destroyed.
+pub fn clear_session(session_id: &str) {
+    stop_responder(session_id);
+    if let Ok(mut states) = RESPONDER_STATES.lock() {
+        states.remove(session_id);
+    }
+}
+
+// ============================================================================
+// UDS / ISO-TP
+// ============================================================================
+
+const SID_DIAGNOSTIC_SESSION_CONTROL: u8 = 0x10;
+const SID_ECU_RESET: u8 = 0x11;
+const SID_READ_DATA_BY_ID: u8 = 0x22;
+const SID_WRITE_DATA_BY_ID: u8 = 0x2E;
+const SID_TESTER_PRESENT: u8 = 0x3E;
+const SID_NEGATIVE_RESPONSE: u8 = 0x7F;
+
+const NRC_SERVICE_NOT_SUPPORTED: u8 = 0x11;
+const NRC_INCORRECT_LENGTH: u8 = 0x13;
+const NRC_REQUEST_OUT_OF_RANGE: u8 = 0x31;
+const NRC_SECURITY_ACCESS_DENIED: u8 = 0x33;
+
+fn negative(sid: u8, nrc: u8) -> Option<Vec<u8>> {
+    Some(vec![SID_NEGATIVE_RESPONSE, sid, nrc])
+}
+
+/// Answer one UDS request against a DID table. Returns None when the request
+/// suppresses its positive response (TesterPresent with bit 7 set).
+fn handle_uds_request(request: &[u8], dids: &mut [UdsDid]) -> Option<Vec<u8>> {
+    let (&sid, args) = request.split_first()?;
+    match sid {
+        SID_DIAGNOSTIC_SESSION_CONTROL => {
+            let Some(&sub) = args.first() else {
+                return negative(sid, NRC_INCORRECT_LENGTH);
+            };
+            // P2 = 50 ms, P2* = 5000 ms (in 10 ms units)
+            Some(vec![sid + 0x40, sub, 0x00, 0x32, 0x01, 0xF4])
+        }
+        SID_ECU_RESET => {
+            let Some(&sub) = args.first() else {
+                return negative(sid, NRC_INCORRECT_LENGTH);
+            };
+            Some(vec![sid + 0x40, sub])
+        }
+        SID_TESTER_PRESENT => {
+            let sub = args.first().copied().unwrap_or(0);
+            if sub & 0x80 != 0 {
+                None
+            } else {
+                Some(vec![sid + 0x40, sub & 0x7F])
+            }
+        }
+        SID_READ_DATA_BY_ID => {
+            if args.is_empty() || !args.len().is_multiple_of(2) {
+                return negative(sid, NRC_INCORRECT_LENGTH);
+            }
+            let mut resp = vec![sid + 0x40];
+            for pair in args.chunks(2) {
+                let did = u16::from_be_bytes([pair[0], pair[1]]);
+                let Some(entry) = dids.iter().find(|d| d.did == did) else {
+                    return negative(sid, NRC_REQUEST_OUT_OF_RANGE);
+                };
+                resp.extend_from_slice(&pair[..2]);
+                resp.extend_from_slice(&entry.data);
+            }
+            Some(resp)
+        }
+        SID_WRITE_DATA_BY_ID => {
+            if args.len() < 3 {
+                return negative(sid, NRC_INCORRECT_LENGTH);
+            }
+            let did = u16::from_be_bytes([args[0], args[1]]);
+            let Some(entry) = dids.iter_mut().find(|d| d.did == did) else {
+                return negative(sid, NRC_REQUEST_OUT_OF_RANGE);
+            };
+            if !entry.writable {
+                return negative(sid, NRC_SECURITY_ACCESS_DENIED);
+            }
+            entry.data = args[2..].to_vec();
+            Some(vec![sid + 0x40, args[0], args[1]])
+        }
+        _ => negative(sid, NRC_SERVICE_NOT_SUPPORTED),
+    }
+}
+
+/// Extract the payload of an ISO-TP single frame (classic CAN addressing).
+fn isotp_single_frame(bytes: &[u8]) -> Option<&[u8]> {
+    let pci = *bytes.first()?;
+    if pci >> 4 != 0 {
+        return None;
+    }
+    let len = (pci & 0x0F) as usize;
+    if len == 0 || bytes.len() < 1 + len {
+        return None;
+    }
+    Some(&bytes[1..1 + len])
+}
+
+fn pad(mut data: Vec<u8>, padding: u8) -> Vec<u8> {
+    data.resize(8, padding);
+    data
+}
+
+/// A segmented response waiting on (or being paced by) flow control.
+struct PendingTransfer {
+    data: Vec<u8>,
+    offset: usize,
+    seq: u8,
+}
+
+impl PendingTransfer {
+    /// First frame for a payload longer than a single frame can carry.
+    /// Lengths above 4095 use the ISO 15765-2 escape: a zero 12-bit length
+    /// followed by the length as 32 bits.
+    fn first_frame(data: Vec<u8>) -> (Vec<u8>, Self) {
+        let len = data.len();
+        let mut ff = if len <= 0xFFF {
+            vec![0x10 | (len >> 8) as u8, (len & 0xFF) as u8]
+        } else {
+            let mut ff = vec![0x10, 0x00];
+            ff.extend_from_slice(&(len as u32).to_be_bytes());
+            ff
+        };
+        let offset = 8 - ff.len();
+        ff.extend_from_slice(&data[..offset]);
+        (ff, Self { data, offset, seq: 1 })
+    }
+
+    /// Next consecutive frame, or None when the transfer is complete.
+    fn next_consecutive(&mut self, padding: u8) -> Option<Vec<u8>> {
+        if self.offset >= self.data.len() {
+            return None;
+        }
+        let end = (self.offset + 7).min(self.data.len());
+        let mut cf = vec![0x20 | (self.seq & 0x0F)];
+        cf.extend_from_slice(&self.data[self.offset..end]);
+        self.offset = end;
+        self.seq = self.seq.wrapping_add(1) & 0x0F;
+        Some(pad(cf, padding))
+    }
+}
+
+/// Decode an ISO-TP STmin byte into a delay.
+fn st_min_delay(st_min: u8) -> std::time::Duration {
+    match st_min {
+        0x00..=0x7F => std::time::Duration::from_millis(st_min as u64),
+        0xF1..=0xF9 => std::time::Duration::from_micros((st_min - 0xF0) as u64 * 100),
+        _ => std::time::Duration::from_millis(0x7F),
+    }
+}
+
+struct UdsServer {
+    config: UdsServerConfig,
+    pending: Option<PendingTransfer>,
+}
+
+impl UdsServer {
+    fn reply_frame(&self, data: Vec<u8>, bus: u8) -> CanTransmitFrame {
+        CanTransmitFrame {
+            frame_id: self.config.response_id,
+            data,
+            bus: self.config.bus.unwrap_or(bus),
+            is_extended: self.config.is_extended,
+            is_fd: false,
+            is_brs: false,
+            is_rtr: false,
+        }
+    }
+
+    fn accepts(&self, frame: &FrameMessage) -> bool {
+        frame.frame_id == self.config.request_id
+            && frame.is_extended == self.config.is_extended
+            && self.config.bus.is_none_or(|b| b == frame.bus)
+    }
+}
+
+// ============================================================================
+// Responder task
+// ============================================================================
+
+async fn send(session_id: &str, frame: CanTransmitFrame) {
+    match io::session_transmit(session_id, &TransmitPayload::CanFrame(frame)).await {
+        Ok(r) if r.success => update_state(session_id, |s| s.frames_sent += 1),
+        Ok(r) => update_state(session_id, |s| {
+            s.transmit_errors += 1;
+            s.last_error = r.error;
+        }),
+        Err(e) => update_state(session_id, |s| {
+            s.transmit_errors += 1;
+            s.last_error = Some(e);
+        }),
+    }
+}
+
+async fn handle_frame(session_id: &str, frame: &FrameMessage, rules: &[ResponderRule], uds: &mut [UdsServer]) {
+    for server in uds.iter_mut() {
+        if !server.accepts(frame) {
+            continue;
+        }
+        let padding = server.config.padding;
+
+        // Flow control from the tester paces a pending segmented response.
+        if frame.bytes.first().map(|b| b >> 4) == Some(0x3) {
+            let block_size = frame.bytes.get(1).copied().unwrap_or(0);
+            let delay = st_min_delay(frame.bytes.get(2).copied().unwrap_or(0));
+            let mut sent = 0u8;
+            while let Some(cf) = server.pending.as_mut().and_then(|p| p.next_consecutive(padding)) {
+                send(session_id, server.reply_frame(cf, frame.bus)).await;
+                sent = sent.wrapping_add(1);
+                if block_size != 0 && sent == block_size {
+                    return; // wait for the next flow control
+                }
+                tokio::time::sleep(delay).await;
+            }
+            server.pending = None;
+            return;
+        }
+
+        let Some(request) = isotp_single_frame(&frame.bytes) else {
+            return;
+        };
+        update_state(session_id, |s| s.requests_matched += 1);
+        let response = handle_uds_request(request, &mut server.config.dids);
+        let dids = server.config.dids.clone();
+        let request_id = server.config.request_id;
+        update_state(session_id, |s| {
+            s.did_values.insert(request_id, dids);
+        });
+        let Some(response) = response else { return };
+
+        if response.len() <= 7 {
+            let mut sf = vec![response.len() as u8];
+            sf.extend_from_slice(&response);
+            send(session_id, server.reply_frame(pad(sf, padding), frame.bus)).await;
+        } else {
+            let (ff, pending) = PendingTransfer::first_frame(response);
+            server.pending = Some(pending);
+            send(session_id, server.reply_frame(ff, frame.bus)).await;
+        }
+        return;
+    }
+
+    let Some(rule) = rules.iter().find(|r| r.request.matches(frame)) else {
+        return;
+    };
+    update_state(session_id, |s| s.requests_matched += 1);
+    if rule.delay_ms > 0 {
+        tokio::time::sleep(std::time::Duration::from_millis(rule.delay_ms)).await;
+    }
+    for resp in &rule.responses {
+        let out = CanTransmitFrame {
+            frame_id: resp.frame_id,
+            data: resp.data.clone(),
+            bus: resp.bus.unwrap_or(frame.bus),
+            is_extended: resp.is_extended,
+            is_fd: resp.is_fd,
+            is_brs: false,
+            is_rtr: false,
+        };
+        send(session_id, out).await;
+    }
+}
+
+async fn run_responder(
+    session_id: String,
+    config: ResponderConfig,
+    mut frame_rx: mpsc::UnboundedReceiver<Vec<FrameMessage>>,
+) {
+    let mut uds: Vec<UdsServer> = config
+        .uds
+        .into_iter()
+        .map(|c| UdsServer { config: c, pending: None })
+        .collect();
+    while let Some(batch) = frame_rx.recv().await {
+        for frame in &batch {
+            handle_frame(&session_id, frame, &config.rules, &mut uds).await;
+        }
+    }
+    tlog!("[responder:{}] Task ended", session_id);
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Start (or restart) the responder on a session.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn responder_start(session_id: String, config: ResponderConfig) -> Result<ResponderState, String> {
+    let caps = io::get_session_capabilities(&session_id)
+        .await
+        .ok_or_else(|| msg!(SESSION_NOT_FOUND, session_id = session_id))?;
+    if !caps.traits.tx_frames {
+        return Err("This session does not support CAN transmission".to_string());
+    }
+    for rule in &config.rules {
+        if rule.responses.is_empty() {
+            return Err(format!(
+                "Rule '{}' has no response frames",
+                rule.name.as_deref().unwrap_or("unnamed")
+            ));
+        }
+    }
+    for server in &config.uds {
+        if server.request_id == server.response_id {
+            return Err(format!("UDS server 0x{:X} uses the same request and response ID", server.request_id));
+        }
+    }
+
+    stop_responder(&session_id);
+
+    let state = ResponderState {
+        session_id: session_id.clone(),
+        running: true,
+        did_values: config.uds.iter().map(|u| (u.request_id, u.dids.clone())).collect(),
+        ..Default::default()
+    };
+    if let Ok(mut states) = RESPONDER_STATES.lock() {
+        states.insert(session_id.clone(), state.clone());
+    }
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    if let Ok(mut taps) = RESPONDER_TAPS.lock() {
+        taps.insert(session_id.clone(), tx);
+    }
+    tlog!(
+        "[responder:{}] Started ({} rules, {} UDS servers)",
+        session_id, config.rules.len(), config.uds.len()
+    );
+    tauri::async_runtime::spawn(run_responder(session_id, config, rx));
+    Ok(state)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn responder_stop(session_id: String) {
+    stop_responder(&session_id);
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_responder_state(session_id: String) -> Option<ResponderState> {
+    RESPONDER_STATES.lock().ok().and_then(|s| s.get(&session_id).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dids() -> Vec<UdsDid> {
+        vec![
+            UdsDid { did: 0xF190, data: b"WIRETAP0000000001".to_vec(), writable: false },
+            UdsDid { did: 0x0100, data: vec![0x12], writable: true },
+        ]
+    }
+
+    #[test]
+    fn read_did_positive_and_unknown() {
+        let mut table = dids();
+        assert_eq!(
+            handle_uds_request(&[0x22, 0x01, 0x00], &mut table),
+            Some(vec![0x62, 0x01, 0x00, 0x12])
+        );
+        assert_eq!(
+            handle_uds_request(&[0x22, 0x12, 0x34], &mut table),
+            Some(vec![0x7F, 0x22, NRC_REQUEST_OUT_OF_RANGE])
+        );
+    }
+
+    #[test]
+    fn write_did_updates_table() {
+        let mut table = dids();
+        assert_eq!(
+            handle_uds_request(&[0x2E, 0x01, 0x00, 0x55], &mut table),
+            Some(vec![0x6E, 0x01, 0x00])
+        );
+        assert_eq!(table[1].data, vec![0x55]);
+        assert_eq!(
+            handle_uds_request(&[0x2E, 0xF1, 0x90, 0x00], &mut table),
+            Some(vec![0x7F, 0x2E, NRC_SECURITY_ACCESS_DENIED])
+        );
+    }
+
+    #[test]
+    fn tester_present_suppression() {
+        let mut table = dids();
+        assert_eq!(handle_uds_request(&[0x3E, 0x00], &mut table), Some(vec![0x7E, 0x00]));
+        assert_eq!(handle_uds_request(&[0x3E, 0x80], &mut table), None);
+        assert_eq!(
+            handle_uds_request(&[0x31, 0x01], &mut table),
+            Some(vec![0x7F, 0x31, NRC_SERVICE_NOT_SUPPORTED])
+        );
+    }
+
+    #[test]
+    fn isotp_segmentation() {
+        let payload: Vec<u8> = (0..20).collect();
+        let (ff, mut pending) = PendingTransfer::first_frame(payload);
+        assert_eq!(ff, vec![0x10, 20, 0, 1, 2, 3, 4, 5]);
+        assert_eq!(pending.next_consecutive(0xAA), Some(vec![0x21, 6, 7, 8, 9, 10, 11, 12]));
+        assert_eq!(
+            pending.next_consecutive(0xAA),
+            Some(vec![0x22, 13, 14, 15, 16, 17, 18, 19])
+        );
+        assert_eq!(pending.next_consecutive(0xAA), None);
+    }
+
+    #[test]
+    fn isotp_escaped_length_above_4095() {
+        let payload: Vec<u8> = (0..5000u32).map(|i| i as u8).collect();
+        let (ff, mut pending) = PendingTransfer::first_frame(payload);
+        assert_eq!(ff, vec![0x10, 0x00, 0x00, 0x00, 0x13, 0x88, 0, 1]);
+        assert_eq!(pending.next_consecutive(0xAA), Some(vec![0x21, 2, 3, 4, 5, 6, 7, 8]));
+        let mut frames = 1;
+        while pending.next_consecutive(0xAA).is_some() {
+            frames += 1;
+        }
+        // 4998 bytes after the first frame, 7 per consecutive frame
+        assert_eq!(frames, 714);
+    }
+
+    #[test]
+    fn single_frame_parse() {
+        assert_eq!(isotp_single_frame(&[0x03, 0x22, 0xF1, 0x90, 0, 0, 0, 0]), Some(&[0x22, 0xF1, 0x90][..]));
+        assert_eq!(isotp_single_frame(&[0x10, 0x14, 0x22]), None);
+        assert_eq!(isotp_single_frame(&[0x05, 0x22]), None);
+    }
+}
@@ -0,0 +1,589 @@
+// ui/src-tauri/src/retention.rs
+//
+// Retention policy for files WireTAP leaves on disk (exports, dumps, logger
+// spill directories). A policy is a list of rules; each rule covers one
+// directory, optionally only files whose name carries a tag, and deletes or
+// archives files older than its age limit:
+//
+//   - delete:  the file is removed
+//   - archive: the file is moved into `archive_dir` (default `<directory>/Archive`)
+//
+// An export's `<file>.integrity.json` manifest follows its file. Every run
+// produces a report; a dry run builds the same report without touching
+// anything. With `enabled` set the policy is applied every `interval_hours`
+// by a background task started at launch. The policy is persisted in
+// retention-policy.json in the app data dir.
+//
+// Because a rule deletes whatever it matches, its directory is canonicalised
+// and refused if it is a filesystem or system root, or the home directory, a
+// top-level user folder (Documents, Desktop, ...) or one of their ancestors.
+// Age limits must be positive. Files are only deleted or archived once the
+// user has confirmed a destructive run of the current rules; changing the
+// rules withdraws the confirmation.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+/// Sidecar written beside exports by capture_integrity.
+const MANIFEST_SUFFIX: &str = ".integrity.json";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    #[default]
+    Delete,
+    Archive,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RetentionRule {
+    pub directory: String,
+    /// Only files whose name contains this tag (case-insensitive), e.g. a
+    /// vehicle ID or an extension like ".csv". None covers every file.
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Files last modified longer ago than this are acted on (> 0)
+    pub max_age_days: f64,
+    #[serde(default)]
+    pub action: RetentionAction,
+    /// Archive destination (default `<directory>/Archive`)
+    #[serde(default)]
+    pub archive_dir: Option<String>,
+    /// Also walk subdirectories (never the archive directory itself)
+    #[serde(default)]
+    pub recursive: bool,
+}
+
+fn default_interval_hours() -> u32 {
+    24
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// Apply the policy automatically
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_interval_hours")]
+    pub interval_hours: u32,
+    #[serde(default)]
+    pub rules: Vec<RetentionRule>,
+    /// Set when the user confirms the first destructive run of these rules
+    /// (`run_retention` with `confirm`); cleared whenever the rules change.
+    /// Neither manual nor automatic runs touch files until it is set.
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self { enabled: false, interval_hours: default_interval_hours(), rules: Vec::new(), confirmed: false }
+    }
+}
+
+/// One file the policy acted on (or would act on, in a dry run).
+#[derive(Clone, Debug, Serialize)]
+pub struct RetentionEntry {
+    pub path: String,
+    pub rule_index: usize,
+    pub action: RetentionAction,
+    pub age_days: f64,
+    pub size_bytes: u64,
+    /// Destination for archived files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    /// Unix seconds when the run started
+    pub ran_at: u64,
+    pub entries: Vec<RetentionEntry>,
+    pub deleted: usize,
+    pub archived: usize,
+    pub failed: usize,
+    /// Bytes freed from (or moved out of) the covered directories
+    pub bytes_reclaimed: u64,
+    /// Rules whose directory couldn't be read
+    pub rule_errors: Vec<String>,
+}
+
+// ============================================================================
+// State
+// ============================================================================
+
+/// Loaded lazily from retention-policy.json on first use.
+static POLICY: Lazy<Mutex<Option<RetentionPolicy>>> = Lazy::new(|| Mutex::new(None));
+
+static LAST_REPORT: Lazy<Mutex<Option<RetentionReport>>> = Lazy::new(|| Mutex::new(None));
+
+fn policy_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    Ok(dir.join("retention-policy.json"))
+}
+
+fn load_policy(app: &AppHandle) -> Result<RetentionPolicy, String> {
+    let path = policy_path(app)?;
+    let mut guard = POLICY.lock().map_err(|e| e.to_string())?;
+    let policy = guard.get_or_insert_with(|| {
+        std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    });
+    Ok(policy.clone())
+}
+
+fn save_policy(app: &AppHandle, policy: RetentionPolicy) -> Result<(), String> {
+    let path = policy_path(app)?;
+    let content = serde_json::to_string_pretty(&policy).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write retention policy: {}", e))?;
+    *POLICY.lock().map_err(|e| e.to_string())? = Some(policy);
+    Ok(())
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// System directories a rule may not cover or sit inside.
+#[cfg(windows)]
+const SYSTEM_ROOTS: &[&str] = &[r"C:\Windows", r"C:\Program Files", r"C:\Program Files (x86)", r"C:\ProgramData"];
+#[cfg(not(windows))]
+const SYSTEM_ROOTS: &[&str] = &[
+    "/bin", "/boot", "/dev", "/etc", "/lib", "/lib32", "/lib64", "/proc", "/run", "/sbin", "/sys", "/usr",
+    "/var/lib", "/Applications", "/Library", "/System", "/private/etc", "/private/var/db",
+];
+
+/// The home directory and the top-level user folders in it. A rule may sit
+/// inside one of these but never be one, or an ancestor of one.
+fn protected_dirs() -> Vec<PathBuf> {
+    [
+        dirs::home_dir(),
+        dirs::config_dir(),
+        dirs::data_dir(),
+        dirs::data_local_dir(),
+        dirs::cache_dir(),
+        dirs::desktop_dir(),
+        dirs::document_dir(),
+        dirs::download_dir(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|dir| std::fs::canonicalize(dir).ok())
+    .collect()
+}
+
+/// Canonical directory of a rule, or why the rule may not run.
+fn check_rule(rule: &RetentionRule, protected: &[PathBuf]) -> Result<PathBuf, String> {
+    if rule.directory.trim().is_empty() {
+        return Err("no directory".to_string());
+    }
+    if rule.max_age_days.is_nan() || rule.max_age_days <= 0.0 {
+        return Err("the age limit must be more than 0 days".to_string());
+    }
+    let dir = std::fs::canonicalize(rule.directory.trim())
+        .map_err(|e| format!("'{}' can't be resolved: {}", rule.directory, e))?;
+    if dir.parent().is_none() {
+        return Err(format!("'{}' is a filesystem root", dir.display()));
+    }
+    let mut system = SYSTEM_ROOTS.iter().filter_map(|root| std::fs::canonicalize(root).ok());
+    if let Some(root) = system.find(|root| dir.starts_with(root)) {
+        return Err(format!("'{}' is inside the system directory '{}'", dir.display(), root.display()));
+    }
+    if let Some(covered) = protected.iter().find(|p| p.starts_with(&dir)) {
+        return Err(format!("'{}' would cover '{}'", dir.display(), covered.display()));
+    }
+    Ok(dir)
+}
+
+// ============================================================================
+// Selection
+// ============================================================================
+
+fn tag_matches(tag: Option<&str>, file_name: &str) -> bool {
+    match tag {
+        Some(tag) if !tag.is_empty() => file_name.to_lowercase().contains(&tag.to_lowercase()),
+        _ => true,
+    }
+}
+
+/// Archive destination of a rule whose canonical directory is `directory`.
+fn archive_dir(rule: &RetentionRule, directory: &Path) -> PathBuf {
+    match &rule.archive_dir {
+        Some(dir) if !dir.is_empty() => std::fs::canonicalize(dir).unwrap_or_else(|_| PathBuf::from(dir)),
+        _ => directory.join("Archive"),
+    }
+}
+
+/// A file found under a rule's directory.
+struct Candidate {
+    path: PathBuf,
+    modified: SystemTime,
+    size: u64,
+}
+
+/// Regular files under `dir` (symlinks are not followed), skipping `exclude`
+/// and integrity manifests, which travel with their export instead.
+fn collect(dir: &Path, recursive: bool, exclude: &Path, out: &mut Vec<Candidate>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let Ok(entry) = entry else { continue };
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else { continue };
+        if file_type.is_dir() {
+            if recursive && path != exclude {
+                let _ = collect(&path, recursive, exclude, out);
+            }
+            continue;
+        }
+        if !file_type.is_file() || path.to_string_lossy().ends_with(MANIFEST_SUFFIX) {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else { continue };
+        out.push(Candidate {
+            path,
+            modified: meta.modified().unwrap_or(SystemTime::now()),
+            size: meta.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Age in days of a file modified at `modified`, if older than the rule allows.
+fn expired_age(rule: &RetentionRule, now: SystemTime, modified: SystemTime) -> Option<f64> {
+    let age = now.duration_since(modified).ok()?.as_secs_f64() / 86_400.0;
+    (age > rule.max_age_days).then_some(age)
+}
+
+// ============================================================================
+// Actions
+// ============================================================================
+
+fn manifest_for(path: &Path) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(MANIFEST_SUFFIX);
+    PathBuf::from(s)
+}
+
+/// Move a file, copying when the destination is on another volume.
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)?;
+    std::fs::remove_file(from)
+}
+
+/// Archive destination that doesn't overwrite an earlier archived file.
+fn unique_destination(dir: &Path, file_name: &std::ffi::OsStr) -> PathBuf {
+    let dest = dir.join(file_name);
+    if !dest.exists() {
+        return dest;
+    }
+    let name = Path::new(file_name);
+    let stem = name.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = name.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (1..)
+        .map(|n| dir.join(format!("{}_{}{}", stem, n, ext)))
+        .find(|p| !p.exists())
+        .unwrap_or(dest)
+}
+
+fn apply(rule: &RetentionRule, archive: &Path, path: &Path) -> Result<Option<PathBuf>, String> {
+    let manifest = manifest_for(path);
+    match rule.action {
+        RetentionAction::Delete => {
+            std::fs::remove_file(path).map_err(|e| e.to_string())?;
+            if manifest.exists() {
+                let _ = std::fs::remove_file(&manifest);
+            }
+            Ok(None)
+        }
+        RetentionAction::Archive => {
+            std::fs::create_dir_all(archive).map_err(|e| format!("Failed to create archive dir: {}", e))?;
+            let file_name = path.file_name().ok_or("File has no name")?;
+            let dest = unique_destination(archive, file_name);
+            move_file(path, &dest).map_err(|e| e.to_string())?;
+            if manifest.exists() {
+                let _ = move_file(&manifest, &manifest_for(&dest));
+            }
+            Ok(Some(dest))
+        }
+    }
+}
+
+/// Apply (or, with `dry_run`, only report) every rule of `policy`. Rules
+/// failing `check_rule` are skipped and listed in `rule_errors`; the caller
+/// checks the policy is confirmed before a destructive run.
+pub fn run_policy(policy: &RetentionPolicy, dry_run: bool) -> RetentionReport {
+    let now = SystemTime::now();
+    let mut report = RetentionReport {
+        dry_run,
+        ran_at: now.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        ..Default::default()
+    };
+    // A file matched by an earlier rule is not considered again
+    let mut handled = std::collections::HashSet::new();
+    let protected = protected_dirs();
+
+    for (rule_index, rule) in policy.rules.iter().enumerate() {
+        let directory = match check_rule(rule, &protected) {
+            Ok(dir) => dir,
+            Err(e) => {
+                report.rule_errors.push(format!("Rule {} skipped: {}", rule_index, e));
+                continue;
+            }
+        };
+        let archive = archive_dir(rule, &directory);
+        let mut candidates = Vec::new();
+        if let Err(e) = collect(&directory, rule.recursive, &archive, &mut candidates) {
+            report.rule_errors.push(format!("Rule {} ({}): {}", rule_index, rule.directory, e));
+            continue;
+        }
+        for candidate in candidates {
+            let name = candidate.path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+            if !tag_matches(rule.tag.as_deref(), &name) || handled.contains(&candidate.path) {
+                continue;
+            }
+            let Some(age_days) = expired_age(rule, now, candidate.modified) else { continue };
+            handled.insert(candidate.path.clone());
+
+            let mut entry = RetentionEntry {
+                path: candidate.path.to_string_lossy().to_string(),
+                rule_index,
+                action: rule.action,
+                age_days,
+                size_bytes: candidate.size,
+                archived_to: None,
+                error: None,
+            };
+            if dry_run {
+                if rule.action == RetentionAction::Archive {
+                    entry.archived_to = candidate
+                        .path
+                        .file_name()
+                        .map(|n| unique_destination(&archive, n).to_string_lossy().to_string());
+                }
+            } else {
+                match apply(rule, &archive, &candidate.path) {
+                    Ok(dest) => entry.archived_to = dest.map(|d| d.to_string_lossy().to_string()),
+                    Err(e) => entry.error = Some(e),
+                }
+            }
+
+            if entry.error.is_some() {
+                report.failed += 1;
+            } else {
+                match rule.action {
+                    RetentionAction::Delete => report.deleted += 1,
+                    RetentionAction::Archive => report.archived += 1,
+                }
+                report.bytes_reclaimed += candidate.size;
+            }
+            report.entries.push(entry);
+        }
+    }
+    report
+}
+
+fn run_and_record(policy: &RetentionPolicy, dry_run: bool) -> RetentionReport {
+    let report = run_policy(policy, dry_run);
+    tlog!(
+        "[retention] {} run: {} deleted, {} archived, {} failed, {} bytes",
+        if dry_run { "Dry" } else { "Policy" },
+        report.deleted, report.archived, report.failed, report.bytes_reclaimed
+    );
+    if let Ok(mut last) = LAST_REPORT.lock() {
+        *last = Some(report.clone());
+    }
+    report
+}
+
+/// Background task applying the policy every `interval_hours` while it is
+/// enabled. The policy is re-read each cycle so edits take effect.
+pub fn start_retention_task(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let interval_hours = match load_policy(&app) {
+                Ok(policy) => {
+                    let interval_hours = policy.interval_hours.max(1);
+                    if policy.enabled && !policy.rules.is_empty() {
+                        if policy.confirmed {
+                            let _ = tokio::task::spawn_blocking(move || run_and_record(&policy, false)).await;
+                        } else {
+                            tlog!("[retention] Policy enabled but not yet confirmed; skipping automatic run");
+                        }
+                    }
+                    interval_hours
+                }
+                Err(e) => {
+                    tlog!("[retention] Failed to load policy: {}", e);
+                    default_interval_hours()
+                }
+            };
+            tokio::time::sleep(Duration::from_secs(interval_hours as u64 * 3600)).await;
+        }
+    });
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+#[tauri::command]
+pub fn get_retention_policy(app: AppHandle) -> Result<RetentionPolicy, String> {
+    load_policy(&app)
+}
+
+/// Save the policy. Every rule must pass `check_rule`. The confirmation
+/// carries over only while the rules are unchanged.
+#[tauri::command]
+pub fn set_retention_policy(app: AppHandle, mut policy: RetentionPolicy) -> Result<(), String> {
+    let protected = protected_dirs();
+    for (i, rule) in policy.rules.iter().enumerate() {
+        check_rule(rule, &protected).map_err(|e| format!("Retention rule {}: {}", i, e))?;
+    }
+    let previous = load_policy(&app)?;
+    policy.confirmed = previous.confirmed && previous.rules == policy.rules;
+    save_policy(&app, policy)
+}
+
+/// Apply the saved policy now. With `dry_run` nothing is changed and the
+/// report lists what would be deleted or archived. The first destructive
+/// run of a set of rules must pass `confirm` (after reviewing a dry run);
+/// later runs, manual or automatic, don't need it.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn run_retention(app: AppHandle, dry_run: bool, confirm: Option<bool>) -> Result<RetentionReport, String> {
+    let mut policy = load_policy(&app)?;
+    if !dry_run && !policy.confirmed {
+        if confirm != Some(true) {
+            return Err("Review a dry run and confirm before the retention policy deletes or archives files".to_string());
+        }
+        policy.confirmed = true;
+        save_policy(&app, policy.clone())?;
+    }
+    tokio::task::spawn_blocking(move || run_and_record(&policy, dry_run))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Report from the most recent run (manual, dry or automatic).
+#[tauri::command]
+pub fn get_last_retention_report() -> Option<RetentionReport> {
+    LAST_REPORT.lock().ok()?.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(tag: Option<&str>, max_age_days: f64) -> RetentionRule {
+        RetentionRule {
+            directory: "/tmp".into(),
+            tag: tag.map(String::from),
+            max_age_days,
+            action: RetentionAction::Delete,
+            archive_dir: None,
+            recursive: false,
+        }
+    }
+
+    #[test]
+    fn tags_match_case_insensitively() {
+        assert!(tag_matches(Some("FLEET-07"), "dump_fleet-07_2026.csv"));
+        assert!(tag_matches(Some(".csv"), "export.CSV"));
+        assert!(!tag_matches(Some("fleet-08"), "dump_fleet-07.csv"));
+        assert!(tag_matches(None, "anything"));
+    }
+
+    #[test]
+    fn only_files_older_than_the_limit_expire() {
+        let now = SystemTime::now();
+        let day = Duration::from_secs(86_400);
+        let r = rule(None, 7.0);
+        assert!(expired_age(&r, now, now - day * 3).is_none());
+        let age = expired_age(&r, now, now - day * 10).unwrap();
+        assert!((age - 10.0).abs() < 0.01);
+        // Clock skew: a file from the future never expires
+        assert!(expired_age(&r, now, now + day).is_none());
+    }
+
+    #[test]
+    fn rules_must_be_safe_to_run() {
+        let protected = protected_dirs();
+        let check = |directory: &str, max_age_days: f64| {
+            check_rule(&RetentionRule { directory: directory.into(), ..rule(None, max_age_days) }, &protected)
+        };
+        let temp = std::env::temp_dir();
+        assert!(check(&temp.to_string_lossy(), 7.0).is_ok());
+        assert!(check(&temp.to_string_lossy(), 0.0).is_err());
+        assert!(check(&temp.to_string_lossy(), f64::NAN).is_err());
+        assert!(check("", 7.0).is_err());
+
+        let root = temp.ancestors().last().unwrap();
+        assert!(check(&root.to_string_lossy(), 7.0).is_err());
+        if let Some(home) = dirs::home_dir() {
+            assert!(check(&home.to_string_lossy(), 7.0).is_err());
+            assert!(check(&home.parent().unwrap().to_string_lossy(), 7.0).is_err());
+        }
+        #[cfg(not(windows))]
+        assert!(check("/usr/share", 7.0).is_err());
+    }
+
+    #[test]
+    fn dry_run_reports_without_touching_files() {
+        let dir = std::env::temp_dir().join(format!("wiretap_retention_{}", std::process::id()));
+        let archive = dir.join("Archive");
+        std::fs::create_dir_all(&archive).unwrap();
+        let file = dir.join("old_fleet-07.csv");
+        std::fs::write(&file, b"0123456789").unwrap();
+        std::fs::write(manifest_for(&file), b"{}").unwrap();
+        let week_ago = SystemTime::now() - Duration::from_secs(7 * 86_400);
+        std::fs::File::options().write(true).open(&file).unwrap().set_modified(week_ago).unwrap();
+        // An earlier archive of the same name is kept
+        let earlier = archive.join("old_fleet-07.csv");
+        std::fs::write(&earlier, b"earlier").unwrap();
+
+        let mut r = rule(Some("fleet-07"), 1.0);
+        r.directory = dir.to_string_lossy().to_string();
+        r.action = RetentionAction::Archive;
+        let policy = RetentionPolicy { rules: vec![r], ..Default::default() };
+
+        let report = run_policy(&policy, true);
+        assert_eq!(report.archived, 1);
+        assert_eq!(report.bytes_reclaimed, 10);
+        assert!(file.exists());
+        let planned = report.entries[0].archived_to.clone().unwrap();
+        assert!(planned.ends_with("old_fleet-07_1.csv"));
+
+        let report = run_policy(&policy, false);
+        assert_eq!(report.archived, 1);
+        assert_eq!(report.entries[0].archived_to.as_deref(), Some(planned.as_str()));
+        assert!(!file.exists());
+        let archived = PathBuf::from(&planned);
+        assert!(archived.exists());
+        assert!(manifest_for(&archived).exists());
+        assert_eq!(std::fs::read(&earlier).unwrap(), b"earlier");
+
+        // Remove only what the test created
+        for path in [manifest_for(&archived), archived, earlier] {
+            let _ = std::fs::remove_file(path);
+        }
+        let _ = std::fs::remove_dir(&archive);
+        let _ = std::fs::remove_dir(&dir);
+    }
+}
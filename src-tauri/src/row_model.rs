@@ -0,0 +1,283 @@
+// ui/src-tauri/src/row_model.rs
+//
+// Backend-maintained row models for virtualised frame tables. A view holds
+// a capture's filter, sort key and grouping; the frontend asks only for the
+// row range it is rendering and is told when the view changed, instead of
+// shipping the whole filtered result set over IPC.
+//
+// Rows are read straight from the capture database (capture_db::row_model_*)
+// so a view costs no memory beyond its spec. capture_store marks views dirty
+// as frames are appended; a notifier task coalesces those marks into
+// `row-model-changed` events at most every NOTIFY_INTERVAL_MS per view.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use tauri::{AppHandle, Emitter};
+
+use crate::capture_db;
+use crate::io::FrameMessage;
+
+/// Coalescing window for change notifications.
+const NOTIFY_INTERVAL_MS: u64 = 250;
+
+/// Upper bound on rows per range request.
+const MAX_RANGE_ROWS: usize = 5000;
+
+/// Column the view is ordered by. Ties break on capture order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RowSortKey {
+    /// Capture order (grouped: latest frame of each ID)
+    #[default]
+    Time,
+    FrameId,
+    Bus,
+    Dlc,
+    /// Frames per ID (grouped views only; capture order otherwise)
+    Count,
+}
+
+/// Filter, sort and grouping for a view.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RowModelSpec {
+    /// Frame IDs to include (empty = all)
+    #[serde(default)]
+    pub frame_ids: Vec<u32>,
+    /// Buses to include (empty = all)
+    #[serde(default)]
+    pub buses: Vec<u8>,
+    /// "rx" or "tx" (None = both)
+    #[serde(default)]
+    pub direction: Option<String>,
+    #[serde(default)]
+    pub start_us: Option<u64>,
+    #[serde(default)]
+    pub end_us: Option<u64>,
+    #[serde(default)]
+    pub sort: RowSortKey,
+    #[serde(default)]
+    pub descending: bool,
+    /// One row per (frame_id, bus, is_extended), showing the latest frame
+    #[serde(default)]
+    pub group_by_id: bool,
+}
+
+impl RowModelSpec {
+    fn validate(&self) -> Result<(), String> {
+        match self.direction.as_deref() {
+            None | Some("rx") | Some("tx") => {}
+            Some(other) => return Err(format!("Unknown direction filter '{}'", other)),
+        }
+        if let (Some(s), Some(e)) = (self.start_us, self.end_us) {
+            if e < s {
+                return Err("Row model end time is before its start".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One table row. Grouped views fill `count` and `first_us`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RowModelRow {
+    /// 1-based capture position (rowid) of `frame`
+    pub capture_index: i64,
+    pub frame: FrameMessage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_us: Option<u64>,
+}
+
+/// A range of rows plus the view's current size.
+#[derive(Debug, Clone, Serialize)]
+pub struct RowModelPage {
+    pub view_id: String,
+    pub revision: u64,
+    pub offset: usize,
+    pub total_rows: usize,
+    pub rows: Vec<RowModelRow>,
+}
+
+/// Payload of `row-model-changed`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RowModelChanged {
+    pub view_id: String,
+    pub revision: u64,
+    pub total_rows: usize,
+}
+
+struct RowModelView {
+    capture_id: String,
+    spec: RowModelSpec,
+    /// Bumped on spec updates and capture changes
+    revision: u64,
+    /// Last revision announced to the frontend
+    notified: u64,
+}
+
+static VIEWS: Lazy<RwLock<HashMap<String, RowModelView>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+static NOTIFIER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+fn new_view_id() -> String {
+    format!("rm_{:x}", crate::frame_link::next_correlation_id())
+}
+
+/// Called by capture_store whenever a capture's frames change.
+pub fn mark_capture_changed(capture_id: &str) {
+    if !NOTIFIER_RUNNING.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut views = VIEWS.write().unwrap();
+    for view in views.values_mut().filter(|v| v.capture_id == capture_id) {
+        view.revision += 1;
+    }
+}
+
+/// Drop every view on a capture (capture deleted).
+pub fn clear_capture(capture_id: &str) {
+    VIEWS.write().unwrap().retain(|_, v| v.capture_id != capture_id);
+}
+
+/// Emit `row-model-changed` for dirty views until no views remain.
+fn ensure_notifier(app: AppHandle) {
+    if NOTIFIER_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(NOTIFY_INTERVAL_MS)).await;
+            let dirty: Vec<(String, String, RowModelSpec, u64)> = {
+                let views = VIEWS.read().unwrap();
+                if views.is_empty() {
+                    NOTIFIER_RUNNING.store(false, Ordering::SeqCst);
+                    break;
+                }
+                views
+                    .iter()
+                    .filter(|(_, v)| v.revision != v.notified)
+                    .map(|(id, v)| (id.clone(), v.capture_id.clone(), v.spec.clone(), v.revision))
+                    .collect()
+            };
+            for (view_id, capture_id, spec, revision) in dirty {
+                let total_rows = match capture_db::row_model_count(&capture_id, &spec) {
+                    Ok(n) => n,
+                    Err(e) => {
+                        tlog!("[row_model] Count failed for view '{}': {}", view_id, e);
+                        continue;
+                    }
+                };
+                if let Some(v) = VIEWS.write().unwrap().get_mut(&view_id) {
+                    v.notified = revision;
+                }
+                let _ = app.emit(
+                    "row-model-changed",
+                    RowModelChanged { view_id, revision, total_rows },
+                );
+            }
+        }
+    });
+}
+
+fn require_frame_capture(capture_id: &str) -> Result<(), String> {
+    match crate::capture_store::get_capture_kind(capture_id) {
+        Some(crate::capture_store::CaptureKind::Frames) => Ok(()),
+        Some(_) => Err(format!("Capture '{}' is not a frame capture", capture_id)),
+        None => Err(msg!(CAPTURE_NOT_FOUND, capture_id = capture_id).into()),
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Open a row model over a frame capture. Returns the first page (empty when
+/// `limit` is 0) so the table can size itself.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn row_model_open(
+    app: AppHandle,
+    capture_id: String,
+    spec: RowModelSpec,
+    limit: Option<usize>,
+) -> Result<RowModelPage, String> {
+    require_frame_capture(&capture_id)?;
+    spec.validate()?;
+    let view_id = new_view_id();
+    VIEWS.write().unwrap().insert(
+        view_id.clone(),
+        RowModelView { capture_id, spec, revision: 0, notified: 0 },
+    );
+    ensure_notifier(app);
+    tlog!("[row_model] Opened view '{}'", view_id);
+    row_model_get_rows(view_id, 0, limit.unwrap_or(0)).await
+}
+
+/// Replace a view's filter/sort/grouping. Returns the first page.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn row_model_update(
+    view_id: String,
+    spec: RowModelSpec,
+    limit: Option<usize>,
+) -> Result<RowModelPage, String> {
+    spec.validate()?;
+    {
+        let mut views = VIEWS.write().unwrap();
+        let view = views
+            .get_mut(&view_id)
+            .ok_or_else(|| format!("Row model '{}' not found", view_id))?;
+        view.spec = spec;
+        view.revision += 1;
+        // The caller gets the new state in the response; no event needed
+        view.notified = view.revision;
+    }
+    row_model_get_rows(view_id, 0, limit.unwrap_or(0)).await
+}
+
+/// Fetch the rows in `[offset, offset + limit)` of a view.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn row_model_get_rows(
+    view_id: String,
+    offset: usize,
+    limit: usize,
+) -> Result<RowModelPage, String> {
+    let (capture_id, spec, revision) = {
+        let views = VIEWS.read().unwrap();
+        let view = views
+            .get(&view_id)
+            .ok_or_else(|| format!("Row model '{}' not found", view_id))?;
+        (view.capture_id.clone(), view.spec.clone(), view.revision)
+    };
+    let total_rows = capture_db::row_model_count(&capture_id, &spec)?;
+    let rows = if limit == 0 || offset >= total_rows {
+        Vec::new()
+    } else {
+        capture_db::row_model_rows(&capture_id, &spec, offset, limit.min(MAX_RANGE_ROWS))?
+    };
+    Ok(RowModelPage { view_id, revision, offset, total_rows, rows })
+}
+
+/// Position of a capture rowid within a view (for scroll-to-frame), or None
+/// when the frame is filtered out.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn row_model_find_row(view_id: String, capture_index: i64) -> Result<Option<usize>, String> {
+    let (capture_id, spec) = {
+        let views = VIEWS.read().unwrap();
+        let view = views
+            .get(&view_id)
+            .ok_or_else(|| format!("Row model '{}' not found", view_id))?;
+        (view.capture_id.clone(), view.spec.clone())
+    };
+    capture_db::row_model_position(&capture_id, &spec, capture_index)
+}
+
+/// Close a view. Unknown IDs are ignored.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn row_model_close(view_id: String) -> Result<(), String> {
+    VIEWS.write().unwrap().remove(&view_id);
+    Ok(())
+}
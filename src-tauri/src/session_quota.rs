@@ -0,0 +1,222 @@
+// ui/src-tauri/src/session_quota.rs
+//
+// Global session quotas and admission control. Before a new session opens its
+// devices, the session commands ask `admit` whether the machine has room for
+// it:
+//
+//   - sessions:      live sessions (joining an existing one doesn't count)
+//   - buffer memory: estimated size of the captures owned by live sessions
+//   - usb devices:   USB device profiles in use (a profile shared by two
+//                    sessions counts once)
+//
+// A refusal is a catalogued `session.quota_exceeded` message, so the frontend
+// can tell which limit was hit via `describe_message`. A limit of 0 disables
+// that check. Limits are held in memory; the frontend pushes the user's values
+// with `set_session_quotas`, as it does for the wake settings.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+use crate::messages::Message;
+use crate::{capture_store, io, profile_tracker};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionQuotas {
+    /// Maximum number of live sessions (0 = unlimited)
+    #[serde(default = "default_max_sessions")]
+    pub max_sessions: usize,
+    /// Maximum estimated size of session-owned captures, in bytes (0 = unlimited)
+    #[serde(default = "default_max_buffer_bytes")]
+    pub max_buffer_bytes: u64,
+    /// Maximum number of USB device profiles in use (0 = unlimited)
+    #[serde(default = "default_max_usb_devices")]
+    pub max_usb_devices: usize,
+}
+
+fn default_max_sessions() -> usize {
+    8
+}
+
+fn default_max_buffer_bytes() -> u64 {
+    4 * 1024 * 1024 * 1024
+}
+
+fn default_max_usb_devices() -> usize {
+    8
+}
+
+impl Default for SessionQuotas {
+    fn default() -> Self {
+        Self {
+            max_sessions: default_max_sessions(),
+            max_buffer_bytes: default_max_buffer_bytes(),
+            max_usb_devices: default_max_usb_devices(),
+        }
+    }
+}
+
+/// Current consumption of each quota.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct QuotaUsage {
+    pub sessions: usize,
+    pub buffer_bytes: u64,
+    pub usb_devices: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct QuotaStatus {
+    pub limits: SessionQuotas,
+    pub usage: QuotaUsage,
+}
+
+/// What a session operation is about to add.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AdmissionRequest {
+    /// A new session will be created (as opposed to changing an existing one)
+    pub new_session: bool,
+    /// USB device profiles that will come into use
+    pub usb_devices: usize,
+}
+
+static QUOTAS: Lazy<RwLock<SessionQuotas>> = Lazy::new(|| RwLock::new(SessionQuotas::default()));
+
+// ============================================================================
+// Admission
+// ============================================================================
+
+fn mib(bytes: u64) -> String {
+    format!("{} MiB", bytes.div_ceil(1024 * 1024))
+}
+
+/// Check a request against the limits, given current usage.
+fn check(limits: &SessionQuotas, usage: &QuotaUsage, request: AdmissionRequest) -> Result<(), Message> {
+    if request.new_session {
+        if limits.max_sessions > 0 && usage.sessions >= limits.max_sessions {
+            return Err(msg!(
+                QUOTA_EXCEEDED,
+                quota = "sessions",
+                limit = limits.max_sessions,
+                in_use = usage.sessions
+            ));
+        }
+        if limits.max_buffer_bytes > 0 && usage.buffer_bytes >= limits.max_buffer_bytes {
+            return Err(msg!(
+                QUOTA_EXCEEDED,
+                quota = "buffer_memory",
+                limit = mib(limits.max_buffer_bytes),
+                in_use = mib(usage.buffer_bytes)
+            ));
+        }
+    }
+    if limits.max_usb_devices > 0
+        && request.usb_devices > 0
+        && usage.usb_devices + request.usb_devices > limits.max_usb_devices
+    {
+        return Err(msg!(
+            QUOTA_EXCEEDED,
+            quota = "usb_devices",
+            limit = limits.max_usb_devices,
+            in_use = usage.usb_devices
+        ));
+    }
+    Ok(())
+}
+
+async fn current_usage() -> QuotaUsage {
+    QuotaUsage {
+        sessions: io::session_count().await,
+        buffer_bytes: capture_store::session_buffer_bytes(),
+        usb_devices: profile_tracker::usb_devices_in_use(),
+    }
+}
+
+/// USB devices that `(profile_id, profile_kind)` pairs would newly bring into
+/// use — profiles already held by another session are not counted again.
+pub fn new_usb_devices<'a>(profiles: impl IntoIterator<Item = (&'a str, &'a str)>) -> usize {
+    let mut seen = std::collections::HashSet::new();
+    profiles
+        .into_iter()
+        .filter(|(id, kind)| {
+            profile_tracker::is_usb_kind(kind)
+                && profile_tracker::get_usage(id).is_none()
+                && seen.insert(*id)
+        })
+        .count()
+}
+
+/// Admit a session operation, or return a "quota exceeded" error.
+pub async fn admit(request: AdmissionRequest) -> Result<(), String> {
+    let limits = QUOTAS.read().unwrap().clone();
+    let usage = current_usage().await;
+    check(&limits, &usage, request).map_err(|m| {
+        tlog!("[session_quota] Refused {:?}: {}", request, m);
+        m.into()
+    })
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Current limits and how much of each is in use.
+#[tauri::command]
+pub async fn get_session_quotas() -> QuotaStatus {
+    QuotaStatus {
+        limits: QUOTAS.read().unwrap().clone(),
+        usage: current_usage().await,
+    }
+}
+
+/// Replace the quota limits. Existing sessions are never closed; the new
+/// limits apply to the next admission check.
+#[tauri::command]
+pub async fn set_session_quotas(quotas: SessionQuotas) -> QuotaStatus {
+    tlog!(
+        "[session_quota] Limits set: sessions={}, buffer={}, usb_devices={}",
+        quotas.max_sessions,
+        mib(quotas.max_buffer_bytes),
+        quotas.max_usb_devices
+    );
+    *QUOTAS.write().unwrap() = quotas;
+    get_session_quotas().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> SessionQuotas {
+        SessionQuotas { max_sessions: 2, max_buffer_bytes: 1024, max_usb_devices: 2 }
+    }
+
+    #[test]
+    fn refuses_new_session_at_limit() {
+        let usage = QuotaUsage { sessions: 2, ..Default::default() };
+        let err = check(&limits(), &usage, AdmissionRequest { new_session: true, usb_devices: 0 }).unwrap_err();
+        assert_eq!(err.key, "session.quota_exceeded");
+        assert_eq!(err.params["quota"], "sessions");
+        assert_eq!(err.params["limit"], "2");
+        // Changing an existing session isn't a new session
+        assert!(check(&limits(), &usage, AdmissionRequest::default()).is_ok());
+    }
+
+    #[test]
+    fn counts_requested_usb_devices() {
+        let usage = QuotaUsage { usb_devices: 1, ..Default::default() };
+        assert!(check(&limits(), &usage, AdmissionRequest { new_session: true, usb_devices: 1 }).is_ok());
+        let err = check(&limits(), &usage, AdmissionRequest { new_session: false, usb_devices: 2 }).unwrap_err();
+        assert_eq!(err.params["quota"], "usb_devices");
+    }
+
+    #[test]
+    fn zero_disables_a_limit() {
+        let limits = SessionQuotas { max_sessions: 0, max_buffer_bytes: 0, max_usb_devices: 0 };
+        let usage = QuotaUsage { sessions: 100, buffer_bytes: u64::MAX, usb_devices: 100 };
+        assert!(check(&limits, &usage, AdmissionRequest { new_session: true, usb_devices: 5 }).is_ok());
+    }
+}
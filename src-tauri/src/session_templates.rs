@@ -0,0 +1,249 @@
+// ui/src-tauri/src/session_templates.rs
+//
+// Session templates: a complete session configuration saved under a name, so
+// a recurring test setup is one click. A template holds the multi-source set
+// (profiles, bus mappings and framing options, as `create_multi_source_session`
+// takes them), shared Modbus polls, and the per-session options applied once
+// the session exists: playback speed, merge batching and source dedup. The
+// frontend's view filters are stored with it as given and handed back when
+// the session is created.
+//
+// Templates live in settings.json (`session_templates`) but are written only
+// through these commands; `save_settings` keeps the copy on disk.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::io::batching::BatchingOptions;
+use crate::io::IOCapabilities;
+use crate::sessions::MultiSourceInput;
+use crate::settings::{self, AppSettings};
+use crate::source_dedup::DedupConfig;
+
+/// A saved session configuration
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionTemplate {
+    /// Assigned on first save when empty
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Sources with their bus mappings and framing options
+    pub sources: Vec<MultiSourceInput>,
+    /// Shared Modbus poll groups (JSON, as `create_multi_source_session` takes them)
+    #[serde(default)]
+    pub modbus_polls: Option<String>,
+    /// Playback speed (ignored by realtime sources)
+    #[serde(default)]
+    pub speed: Option<f64>,
+    #[serde(default)]
+    pub batching: Option<BatchingOptions>,
+    #[serde(default)]
+    pub dedup: Option<DedupConfig>,
+    /// Frontend view filters, stored as given
+    #[serde(default)]
+    pub filters: Option<serde_json::Value>,
+}
+
+/// A session created from a template
+#[derive(Clone, Debug, Serialize)]
+pub struct TemplateSession {
+    pub session_id: String,
+    pub capabilities: IOCapabilities,
+    /// The template's view filters, for the frontend to apply
+    pub filters: Option<serde_json::Value>,
+}
+
+/// Serialises read-modify-write of the settings file
+static TEMPLATES_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+fn check_template(template: &SessionTemplate, settings: &AppSettings) -> Result<(), String> {
+    if template.name.trim().is_empty() {
+        return Err("Template name is required".to_string());
+    }
+    if template.sources.is_empty() {
+        return Err("A template needs at least one source".to_string());
+    }
+    for source in &template.sources {
+        if !settings.io_profiles.iter().any(|p| p.id == source.profile_id) {
+            return Err(format!("Profile '{}' not found", source.profile_id));
+        }
+    }
+    if let Some(speed) = template.speed {
+        if !(speed > 0.0) {
+            return Err(format!("Invalid speed {}", speed));
+        }
+    }
+    Ok(())
+}
+
+/// An id derived from the name, made unique among `existing`.
+fn new_template_id(name: &str, existing: &[SessionTemplate]) -> String {
+    let slug: String = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let base = if slug.is_empty() { "template".to_string() } else { slug };
+    let mut id = base.clone();
+    let mut n = 2;
+    while existing.iter().any(|t| t.id == id) {
+        id = format!("{}-{}", base, n);
+        n += 1;
+    }
+    id
+}
+
+/// Insert or replace `template` by id, assigning one if it has none.
+fn upsert(templates: &mut Vec<SessionTemplate>, mut template: SessionTemplate) -> SessionTemplate {
+    template.name = template.name.trim().to_string();
+    if template.id.is_empty() {
+        template.id = new_template_id(&template.name, templates);
+    }
+    match templates.iter_mut().find(|t| t.id == template.id) {
+        Some(existing) => *existing = template.clone(),
+        None => templates.push(template.clone()),
+    }
+    template
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Saved session templates
+#[tauri::command(rename_all = "snake_case")]
+pub fn list_session_templates(app: AppHandle) -> Result<Vec<SessionTemplate>, String> {
+    Ok(settings::load_settings_sync(&app)?.session_templates)
+}
+
+/// Save a template, replacing any with the same id. Returns it with its id.
+#[tauri::command(rename_all = "snake_case")]
+pub fn save_session_template(app: AppHandle, template: SessionTemplate) -> Result<SessionTemplate, String> {
+    let _guard = TEMPLATES_LOCK.lock().map_err(|e| format!("Template lock poisoned: {e}"))?;
+    let mut settings = settings::load_settings_sync(&app)?;
+    check_template(&template, &settings)?;
+    let saved = upsert(&mut settings.session_templates, template);
+    settings::write_settings(&app, &settings)?;
+    tlog!("[session_templates] Saved '{}' ({})", saved.name, saved.id);
+    Ok(saved)
+}
+
+/// Delete a template. Returns false if there was none with that id.
+#[tauri::command(rename_all = "snake_case")]
+pub fn delete_session_template(app: AppHandle, template_id: String) -> Result<bool, String> {
+    let _guard = TEMPLATES_LOCK.lock().map_err(|e| format!("Template lock poisoned: {e}"))?;
+    let mut settings = settings::load_settings_sync(&app)?;
+    let before = settings.session_templates.len();
+    settings.session_templates.retain(|t| t.id != template_id);
+    if settings.session_templates.len() == before {
+        return Ok(false);
+    }
+    settings::write_settings(&app, &settings)?;
+    Ok(true)
+}
+
+/// Create (and start) a session from a template, then apply its per-session
+/// options. `session_id` defaults to a fresh id.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_session_from_template(
+    app: AppHandle,
+    template_id: String,
+    session_id: Option<String>,
+    subscriber_id: Option<String>,
+    app_name: Option<String>,
+) -> Result<TemplateSession, String> {
+    let template = settings::load_settings_sync(&app)?
+        .session_templates
+        .into_iter()
+        .find(|t| t.id == template_id)
+        .ok_or_else(|| format!("Session template '{}' not found", template_id))?;
+
+    let session_id = match session_id {
+        Some(id) => id,
+        None => {
+            let profile_ids = template.sources.iter().map(|s| s.profile_id.clone()).collect();
+            let raw_bytes = template.sources.iter().any(|s| s.emit_raw_bytes == Some(true));
+            crate::sessions::generate_session_id(app.clone(), profile_ids, Some(raw_bytes)).await?
+        }
+    };
+
+    let capabilities = crate::sessions::create_multi_source_session(
+        app,
+        session_id.clone(),
+        template.sources,
+        subscriber_id,
+        app_name,
+        template.modbus_polls,
+    )
+    .await?;
+
+    if let Some(batching) = template.batching {
+        crate::io::batching::set_session_options(&session_id, batching);
+    }
+    if let Some(dedup) = template.dedup {
+        crate::source_dedup::set_source_dedup(session_id.clone(), dedup)?;
+    }
+    if let Some(speed) = template.speed {
+        if let Err(e) = crate::io::update_session_speed(&session_id, speed).await {
+            tlog!("[session_templates] Speed not applied to '{}': {}", session_id, e);
+        }
+    }
+    tlog!("[session_templates] Created '{}' from template '{}'", session_id, template.name);
+
+    Ok(TemplateSession { session_id, capabilities, filters: template.filters })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(id: &str, name: &str) -> SessionTemplate {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "sources": [{ "profile_id": "bench", "bus_mappings": [] }],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn ids_come_from_the_name_and_stay_unique() {
+        let mut templates = Vec::new();
+        let first = upsert(&mut templates, template("", " Bench: HV bus "));
+        assert_eq!(first.id, "bench-hv-bus");
+        assert_eq!(first.name, "Bench: HV bus");
+        let second = upsert(&mut templates, template("", "bench hv bus"));
+        assert_eq!(second.id, "bench-hv-bus-2");
+        assert_eq!(new_template_id("***", &templates), "template");
+    }
+
+    #[test]
+    fn saving_an_existing_id_replaces_it() {
+        let mut templates = vec![template("nightly", "Nightly")];
+        let mut renamed = template("nightly", "Nightly soak");
+        renamed.speed = Some(2.0);
+        upsert(&mut templates, renamed);
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].name, "Nightly soak");
+        assert_eq!(templates[0].speed, Some(2.0));
+    }
+
+    #[test]
+    fn templates_must_name_known_profiles() {
+        let settings = AppSettings::default();
+        let err = check_template(&template("", "Bench"), &settings).unwrap_err();
+        assert!(err.contains("bench"));
+        let mut unnamed = template("", " ");
+        unnamed.sources.clear();
+        assert!(check_template(&unnamed, &settings).is_err());
+    }
+}
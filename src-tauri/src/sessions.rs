@@ -11,29 +11,32 @@ use crate::{
         create_session, destroy_session, get_session_capabilities, get_session_joiner_count, get_session_state,
         get_session_subscribers, get_session_source_configs, list_sessions, pause_session,
         reconfigure_session, register_subscriber, reinitialize_session_if_safe, resume_session,
-        resume_session_fresh, seek_session, seek_session_by_frame, set_subscriber_active, start_session, stop_session,
+        resume_session_fresh, seek_session, seek_session_by_frame, set_subscriber_active, set_subscriber_rate_limit, start_session, stop_session,
         stop_and_switch_to_capture, suspend_session, switch_to_capture_replay, resume_to_live_session, transmit_frame, unregister_subscriber,
-        evict_session_subscriber, leave_session_to_capture, add_source_to_session, remove_source_from_session, update_source_bus_mappings, pause_source_in_session, resume_source_in_session, get_session_source_count,
+        evict_session_subscriber, leave_session_to_capture, add_source_to_session, remove_source_from_session, replace_source_in_session, update_source_bus_mappings, pause_source_in_session, resume_source_in_session, get_session_source_count,
         update_session_direction, update_session_speed, update_session_time_range, ActiveSessionInfo, IOCapabilities, IOSource, IOState,
-        SubscriberInfo, RegisterSubscriberResult, ReinitializeResult, CaptureSource, step_frame, StepResult,
+        SubscriberInfo, RegisterSubscriberResult, ReinitializeResult, CaptureSource, MultiCaptureSource, step_frame, StepResult,
         BusMapping, InterfaceTraits, Protocol, TemporalMode,
-        GvretDeviceInfo, probe_gvret_tcp,
+        GvretDeviceInfo, GvretDiscoveryResult, discover_gvret_tcp, probe_gvret_tcp,
+        configure_gvret_tcp, GvretBusChange, GvretBusParams, GVRET_MAX_BITRATE, GVRET_MIN_BITRATE,
         ModbusTcpConfig, ModbusTcpSource,
         ModbusScanConfig, ScanCompletePayload, UnitIdScanConfig,
-        MqttConfig, MqttSource,
+        MqttConfig, MqttSource, MqttTlsConfig,
         VirtualDeviceConfig, VirtualSource, VirtualInterfaceConfig, VirtualTrafficType,
-        ModbusRole, IOBroker, SourceConfig,
+        ModbusRole, IOBroker, SourceConfig, listen_only,
         BackendApiConfig, BackendApiSource, BackendApiSourceOptions, PostgresConfig,
         PostgresSource, PostgresSourceOptions, PostgresSourceType,
         CanTransmitFrame, TransmitResult,
         emit_device_probe, DeviceProbePayload,
         set_wake_settings as io_set_wake_settings,
     },
+    profile_schema,
     profile_tracker,
+    session_quota::{self, AdmissionRequest},
     settings::{self, AppSettings, IOProfile},
 };
 #[cfg(not(target_os = "ios"))]
-use crate::io::probe_gvret_usb;
+use crate::io::{configure_gvret_usb, probe_gvret_usb};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::{
@@ -261,6 +264,94 @@ fn get_secure_credential(profile: &IOProfile, field: &str) -> Option<String> {
     }
 }
 
+/// TLS settings of an MQTT profile (None unless `tls` is on). The client
+/// certificate and key come from the keyring.
+fn mqtt_tls_config(profile: &IOProfile) -> Option<MqttTlsConfig> {
+    let conn = &profile.connection;
+    if !conn.get("tls").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return None;
+    }
+    let text = |field: &str| {
+        conn.get(field)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty())
+            .map(String::from)
+    };
+    Some(MqttTlsConfig {
+        ca_cert: text("tls_ca_cert"),
+        client_cert: get_secure_credential(profile, "tls_client_cert").filter(|s| !s.trim().is_empty()),
+        client_key: get_secure_credential(profile, "tls_client_key").filter(|s| !s.trim().is_empty()),
+        alpn: text("tls_alpn")
+            .map(|s| s.split(',').map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect())
+            .unwrap_or_default(),
+        insecure_skip_verify: conn.get("tls_insecure_skip_verify").and_then(|v| v.as_bool()).unwrap_or(false),
+    })
+}
+
+/// Broker connection and subscription of an MQTT profile. The password
+/// comes from the keyring when it was stored there.
+pub(crate) fn mqtt_config(profile: &IOProfile) -> MqttConfig {
+    let host = profile
+        .connection
+        .get("host")
+        .and_then(|v| v.as_str())
+        .unwrap_or("localhost")
+        .to_string();
+
+    let port = profile
+        .connection
+        .get("port")
+        .and_then(|v| {
+            v.as_str()
+                .and_then(|s| s.parse().ok())
+                .or_else(|| v.as_i64().map(|n| n as u16))
+        })
+        .unwrap_or(1883);
+
+    let username = profile
+        .connection
+        .get("username")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+
+    // Get password from keyring if stored, otherwise from profile
+    let password_stored = profile
+        .connection
+        .get("_password_stored")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let password = if password_stored {
+        credentials::get_credential(&profile.id, "password").ok().flatten()
+    } else {
+        profile
+            .connection
+            .get("password")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    };
+
+    // Get subscription topic from savvycan format config
+    let topic = profile
+        .connection
+        .get("formats")
+        .and_then(|f| f.get("savvycan"))
+        .and_then(|s| s.get("topic"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("wiretap/#")
+        .to_string();
+
+    MqttConfig {
+        host,
+        port,
+        username,
+        password,
+        topic,
+        client_id: None,
+        tls: mqtt_tls_config(profile),
+    }
+}
+
 fn choose_profile_by_id(settings: &AppSettings, profile_id: Option<&str>) -> Option<IOProfile> {
     if let Some(id) = profile_id {
         settings.io_profiles.iter().find(|p| p.id == id).cloned()
@@ -276,9 +367,9 @@ fn choose_profile_by_id(settings: &AppSettings, profile_id: Option<&str>) -> Opt
 /// | "unknown"). Used to pick a session-id prefix.
 fn protocol_for_kind(kind: &str) -> &'static str {
     match kind {
-        "gvret_tcp" | "gvret-tcp" | "gvret_usb" | "gvret-usb" | "slcan" | "gs_usb"
-        | "socketcan" | "mqtt" | "framelink" | "virtual" => "can",
-        "serial" => "serial",
+        "gvret_tcp" | "gvret-tcp" | "gvret_usb" | "gvret-usb" | "slcan" | "custom_serial" | "elm327" | "canserver" | "websocket" | "udp_can"
+        | "wiretap_net" | "gs_usb" | "pcan" | "kvaser" | "vector_xl" | "j2534" | "socketcan" | "mqtt" | "framelink" | "virtual" => "can",
+        "serial" | "doip" | "lin" => "serial",
         "modbus_tcp" | "modbus_rtu" => "modbus",
         _ => "unknown",
     }
@@ -310,7 +401,7 @@ pub async fn generate_session_id(
 ) -> Result<String, String> {
     let settings = settings::load_settings(app.clone())
         .await
-        .map_err(|e| format!("Failed to load settings: {}", e))?;
+        .map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
     let mut protocol: Option<&str> = None;
     for id in &profile_ids {
         if let Some(p) = settings.io_profiles.iter().find(|p| &p.id == id) {
@@ -338,7 +429,7 @@ pub async fn generate_session_id(
 fn is_realtime_device(kind: &str) -> bool {
     matches!(
         kind,
-        "gvret_tcp" | "gvret-tcp" | "gvret_usb" | "gvret-usb" | "slcan" | "gs_usb" | "socketcan" | "serial" | "modbus_tcp" | "virtual" | "framelink"
+        "gvret_tcp" | "gvret-tcp" | "gvret_usb" | "gvret-usb" | "slcan" | "custom_serial" | "elm327" | "lin" | "canserver" | "websocket" | "udp_can" | "doip" | "wiretap_net" | "gs_usb" | "pcan" | "kvaser" | "vector_xl" | "j2534" | "socketcan" | "serial" | "modbus_tcp" | "virtual" | "framelink"
     )
 }
 
@@ -388,6 +479,8 @@ fn create_source_config_from_profile(
         modbus_polls: None,
         modbus_role: None,
         max_register_errors: None,
+        reconnect: None,
+        listen_only: listen_only::from_profile(profile),
     })
 }
 
@@ -397,10 +490,10 @@ fn parse_interfaces_from_profile(
     profile: &IOProfile,
     bus_override: Option<u8>,
 ) -> Option<Vec<BusMapping>> {
-    // Only GVRET profiles have multi-bus interface configuration
+    // Only GVRET, Kvaser, Vector, CANserver, WebSocket and UDP listener profiles have multi-bus interface configuration
     if !matches!(
         profile.kind.as_str(),
-        "gvret_tcp" | "gvret-tcp" | "gvret_usb" | "gvret-usb"
+        "gvret_tcp" | "gvret-tcp" | "gvret_usb" | "gvret-usb" | "kvaser" | "vector_xl" | "canserver" | "websocket" | "udp_can"
     ) {
         return None;
     }
@@ -425,8 +518,9 @@ fn parse_interfaces_from_profile(
             let output_bus = bus_override.unwrap_or(device_bus);
 
             // Determine protocols based on interface protocol setting
+            // (Kvaser channels run in classic CAN mode)
             let protocols = match protocol {
-                "canfd" => vec![Protocol::Can, Protocol::CanFd],
+                "canfd" if profile.kind != "kvaser" => vec![Protocol::Can, Protocol::CanFd],
                 _ => vec![Protocol::Can],
             };
 
@@ -438,7 +532,8 @@ fn parse_interfaces_from_profile(
                 traits: Some(InterfaceTraits {
                     temporal_mode: TemporalMode::Realtime,
                     protocols,
-                    tx_frames: true,
+                    // CANserver, WebSocket and UDP listener streams are receive only
+                    tx_frames: !matches!(profile.kind.as_str(), "canserver" | "websocket" | "udp_can"),
                     tx_bytes: false,
                     multi_source: true,
                 }),
@@ -453,6 +548,24 @@ fn parse_interfaces_from_profile(
     }
 }
 
+/// Device bus a single-bus source reads: the configured channel of a gs_usb
+/// profile, 0 for everything else.
+/// LIN profiles transmit only when they run the bus as master.
+fn is_lin_master(profile: &IOProfile) -> bool {
+    profile.connection.get("mode").and_then(|v| v.as_str()) == Some("master")
+}
+
+fn default_device_bus(profile: &IOProfile) -> u8 {
+    match profile.kind.as_str() {
+        "gs_usb" => profile
+            .connection
+            .get("channel")
+            .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+            .unwrap_or(0) as u8,
+        _ => 0,
+    }
+}
+
 /// Create default single-bus mapping for devices without interface configuration.
 fn create_default_bus_mapping(profile: &IOProfile, bus_override: Option<u8>) -> Vec<BusMapping> {
     let output_bus = bus_override.unwrap_or_else(|| {
@@ -470,7 +583,22 @@ fn create_default_bus_mapping(profile: &IOProfile, bus_override: Option<u8>) ->
             (0, "can0".to_string(), vec![Protocol::Can, Protocol::CanFd], true, false)
         }
         "slcan" => (0, "can0".to_string(), vec![Protocol::Can], true, false),
-        "gs_usb" => (0, "can0".to_string(), vec![Protocol::Can, Protocol::CanFd], true, false),
+        "custom_serial" => (0, "can0".to_string(), vec![Protocol::Can], true, false),
+        "elm327" => (0, "can0".to_string(), vec![Protocol::Can], false, false),
+        "lin" => (0, "lin0".to_string(), vec![Protocol::Serial], is_lin_master(profile), false),
+        "canserver" => (0, "can0".to_string(), vec![Protocol::Can], false, false),
+        "websocket" => (0, "can0".to_string(), vec![Protocol::Can, Protocol::CanFd], false, false),
+        "udp_can" => (0, "can0".to_string(), vec![Protocol::Can, Protocol::CanFd], false, false),
+        "doip" => (0, "doip0".to_string(), vec![Protocol::Serial], false, false),
+        "wiretap_net" => (0, "can0".to_string(), vec![Protocol::Can, Protocol::CanFd], false, false),
+        "gs_usb" => {
+            let channel = default_device_bus(profile);
+            (channel, format!("can{}", channel), vec![Protocol::Can, Protocol::CanFd], true, false)
+        }
+        "pcan" => (0, "can0".to_string(), vec![Protocol::Can], true, false),
+        "kvaser" => (0, "can0".to_string(), vec![Protocol::Can], true, false),
+        "vector_xl" => (0, "can0".to_string(), vec![Protocol::Can, Protocol::CanFd], true, false),
+        "j2534" => (0, "can0".to_string(), vec![Protocol::Can], true, false),
         "socketcan" => (0, "can0".to_string(), vec![Protocol::Can, Protocol::CanFd], true, false),
         "modbus_tcp" => (0, "modbus0".to_string(), vec![Protocol::Modbus], false, false),
         "framelink" => {
@@ -564,14 +692,25 @@ pub async fn create_reader_session(
 ) -> Result<IOCapabilities, String> {
     let settings = settings::load_settings(app.clone())
         .await
-        .map_err(|e| format!("Failed to load settings: {}", e))?;
+        .map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
     let profile = choose_profile_by_id(&settings, profile_id.as_deref())
         .ok_or_else(|| "No IO profile configured".to_string())?;
 
+    profile_schema::ensure_valid(&profile)?;
+
     // Check if this profile is already in use (for single-handle devices)
     profile_tracker::can_use_profile(&profile.id, &profile.kind)?;
 
+    // Admission control (joining an existing session opens nothing new)
+    if get_session_state(&session_id).await.is_none() {
+        session_quota::admit(AdmissionRequest {
+            new_session: true,
+            usb_devices: session_quota::new_usb_devices([(profile.id.as_str(), profile.kind.as_str())]),
+        })
+        .await?;
+    }
+
     // Anonymous usage telemetry: which source kind gets started (postgres,
     // wiretap, and any MCP-driven kind all land here).
     crate::telemetry::emit_feature_usage("io_source_start", &profile.kind);
@@ -785,65 +924,7 @@ pub async fn create_reader_session(
             Box::new(ModbusTcpSource::new(app.clone(), session_id.clone(), config))
         }
         "mqtt" => {
-            let host = profile
-                .connection
-                .get("host")
-                .and_then(|v| v.as_str())
-                .unwrap_or("localhost")
-                .to_string();
-
-            let port = profile
-                .connection
-                .get("port")
-                .and_then(|v| {
-                    v.as_str()
-                        .and_then(|s| s.parse().ok())
-                        .or_else(|| v.as_i64().map(|n| n as u16))
-                })
-                .unwrap_or(1883);
-
-            let username = profile
-                .connection
-                .get("username")
-                .and_then(|v| v.as_str())
-                .map(String::from);
-
-            // Get password from keyring if stored, otherwise from profile
-            let password_stored = profile
-                .connection
-                .get("_password_stored")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-
-            let password = if password_stored {
-                credentials::get_credential(&profile.id, "password").ok().flatten()
-            } else {
-                profile
-                    .connection
-                    .get("password")
-                    .and_then(|v| v.as_str())
-                    .map(String::from)
-            };
-
-            // Get subscription topic from savvycan format config
-            let topic = profile
-                .connection
-                .get("formats")
-                .and_then(|f| f.get("savvycan"))
-                .and_then(|s| s.get("topic"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("wiretap/#")
-                .to_string();
-
-            let config = MqttConfig {
-                host,
-                port,
-                username,
-                password,
-                topic,
-                client_id: None,
-            };
-
+            let config = mqtt_config(&profile);
             Box::new(MqttSource::new(app.clone(), session_id.clone(), config))
         }
         "virtual" => {
@@ -969,7 +1050,7 @@ pub async fn create_reader_session(
     };
 
     // Register profile usage BEFORE create_session so lifecycle event has profile IDs
-    profile_tracker::register_usage(&profile_id_for_tracking, &session_id);
+    profile_tracker::register_usage(&profile_id_for_tracking, &profile.kind, &session_id);
     register_session_profile(&session_id, &profile_id_for_tracking);
 
     let result = create_session(app, session_id.clone(), reader, subscriber_id, app_name, None, vec![]).await;
@@ -1254,6 +1335,10 @@ pub async fn create_capture_source_session(
         return Err("No data in capture. Please import a CSV file first.".to_string());
     }
 
+    if get_session_state(&session_id).await.is_none() {
+        session_quota::admit(AdmissionRequest { new_session: true, usb_devices: 0 }).await?;
+    }
+
     register_session_profile(&session_id, &capture_id);
 
     let reader = CaptureSource::new(
@@ -1270,6 +1355,40 @@ pub async fn create_capture_source_session(
     Ok(result.capabilities)
 }
 
+/// Create one replay session over several timeline sources. The sources are
+/// read lazily and interleaved by timestamp with buses renumbered per source
+/// (see `MultiCaptureSource`), so speed and pause apply to all of them
+/// together. Every source must be an existing frame capture with data. Each
+/// source capture is registered as a source profile; the replayed frames land
+/// in a session-owned capture that is orphaned when the session ends.
+/// Sources streamed from elsewhere (e.g. PostgreSQL) can take part once their
+/// frames are in a capture.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_multi_capture_source_session(
+    app: tauri::AppHandle,
+    session_id: String,
+    sources: Vec<capture_store::MergeSource>,
+    speed: Option<f64>,
+) -> Result<IOCapabilities, String> {
+    if get_session_state(&session_id).await.is_none() {
+        session_quota::admit(AdmissionRequest { new_session: true, usb_devices: 0 }).await?;
+    }
+
+    let source_ids: Vec<String> = sources.iter().map(|s| s.capture_id.clone()).collect();
+    let reader = MultiCaptureSource::new(
+        session_id.clone(),
+        sources,
+        speed.unwrap_or(0.0), // 0 = no limit by default
+    )?;
+
+    register_session_profiles(&session_id, &source_ids);
+
+    crate::telemetry::emit_feature_usage("io_source_start", "multi_capture");
+
+    let result = create_session(app, session_id, Box::new(reader), None, None, None, vec![]).await;
+    Ok(result.capabilities)
+}
+
 /// Transition an existing session to use a capture for replay.
 /// This is used when a streaming source (GVRET, PostgreSQL) ends and
 /// the user wants to replay the captured frames.
@@ -1288,6 +1407,8 @@ pub async fn transition_to_capture_source(
         return Err("No data in capture for replay".to_string());
     }
 
+    session_quota::admit(AdmissionRequest { new_session: true, usb_devices: 0 }).await?;
+
     register_session_profile(&session_id, &capture_id);
 
     let reader = CaptureSource::new(
@@ -1340,7 +1461,7 @@ pub async fn resume_session_to_live(
 
         let settings = settings::load_settings(app.clone())
             .await
-            .map_err(|e| format!("Failed to load settings: {}", e))?;
+            .map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
         let profile_id = &profile_ids[0];
         let profile = settings
@@ -1366,10 +1487,17 @@ pub async fn resume_session_to_live(
     for config in &configs {
         crate::profile_tracker::can_use_profile(&config.profile_id, &config.profile_kind)?;
     }
+    session_quota::admit(AdmissionRequest {
+        new_session: false,
+        usb_devices: session_quota::new_usb_devices(
+            configs.iter().map(|c| (c.profile_id.as_str(), c.profile_kind.as_str())),
+        ),
+    })
+    .await?;
 
     // Re-register profiles with the tracker
     for config in &configs {
-        crate::profile_tracker::register_usage(&config.profile_id, &session_id);
+        crate::profile_tracker::register_usage(&config.profile_id, &config.profile_kind, &session_id);
     }
 
     // Restore original profile IDs to SESSION_PROFILES (replacing the capture ID)
@@ -1522,7 +1650,7 @@ pub async fn add_source_to_session_cmd(
 ) -> Result<IOCapabilities, String> {
     let settings = settings::load_settings(app.clone())
         .await
-        .map_err(|e| format!("Failed to load settings: {}", e))?;
+        .map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
     // Determine next source index from existing configs (for auto-assigning output bus)
     let existing_count = get_session_source_count(&session_id).await;
@@ -1539,10 +1667,18 @@ pub async fn add_source_to_session_cmd(
 
     // Check if profile is already in use by another session
     profile_tracker::can_use_profile(&source_config.profile_id, &source_config.profile_kind)?;
+    session_quota::admit(AdmissionRequest {
+        new_session: false,
+        usb_devices: session_quota::new_usb_devices([(
+            source_config.profile_id.as_str(),
+            source_config.profile_kind.as_str(),
+        )]),
+    })
+    .await?;
 
     // Register profile usage
     let profile_id = source_config.profile_id.clone();
-    profile_tracker::register_usage(&profile_id, &session_id);
+    profile_tracker::register_usage(&profile_id, &source_config.profile_kind, &session_id);
     register_session_profile(&session_id, &profile_id);
 
     let capabilities = add_source_to_session(&app, &session_id, source_config).await?;
@@ -1560,24 +1696,79 @@ pub async fn remove_source_from_session_cmd(
     profile_id: String,
 ) -> Result<IOCapabilities, String> {
     let capabilities = remove_source_from_session(&app, &session_id, &profile_id).await?;
+    forget_session_profile(&session_id, &profile_id);
+    Ok(capabilities)
+}
 
+/// Drop the tracking of a profile that is no longer a source of a session.
+fn forget_session_profile(session_id: &str, profile_id: &str) {
     // Unregister profile tracking for the removed source
-    profile_tracker::unregister_usage_by_session(&profile_id, &session_id);
+    profile_tracker::unregister_usage_by_session(profile_id, session_id);
 
     // Remove from session→profile mapping
     if let Ok(mut map) = SESSION_PROFILES.lock() {
-        if let Some(profiles) = map.get_mut(&session_id) {
-            profiles.retain(|id| id != &profile_id);
+        if let Some(profiles) = map.get_mut(session_id) {
+            profiles.retain(|id| id != profile_id);
         }
     }
     if let Ok(mut map) = PROFILE_SESSIONS.lock() {
-        if let Some(sessions) = map.get_mut(&profile_id) {
-            sessions.remove(&session_id);
+        if let Some(sessions) = map.get_mut(profile_id) {
+            sessions.remove(session_id);
             if sessions.is_empty() {
-                map.remove(&profile_id);
+                map.remove(profile_id);
             }
         }
     }
+}
+
+/// Swap one source of a multi-source session for another, e.g. a flaky USB
+/// adapter for its TCP twin, without tearing the session down. When the new
+/// source has no bus mappings it takes over the old one's, so output bus
+/// numbers stay the same. Keeps the same session ID and listeners.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn replace_source_in_session_cmd(
+    app: tauri::AppHandle,
+    session_id: String,
+    old_profile_id: String,
+    new_source_config: MultiSourceInput,
+) -> Result<IOCapabilities, String> {
+    let settings = settings::load_settings(app.clone())
+        .await
+        .map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
+
+    let keep_bus_mappings = new_source_config.bus_mappings.is_empty();
+    let existing_count = get_session_source_count(&session_id).await;
+    let source_config = resolve_source_config(new_source_config, existing_count, &settings)?;
+
+    // Validate it's a real-time device
+    if !is_realtime_device(&source_config.profile_kind) {
+        return Err(format!(
+            "Profile '{}' has unsupported type '{}' for multi-source mode",
+            source_config.profile_id, source_config.profile_kind
+        ));
+    }
+
+    let new_profile_id = source_config.profile_id.clone();
+    let new_profile_kind = source_config.profile_kind.clone();
+    let same_profile = new_profile_id == old_profile_id;
+    if !same_profile {
+        // Check if profile is already in use by another session
+        profile_tracker::can_use_profile(&new_profile_id, &new_profile_kind)?;
+        session_quota::admit(AdmissionRequest {
+            new_session: false,
+            usb_devices: session_quota::new_usb_devices([(new_profile_id.as_str(), new_profile_kind.as_str())]),
+        })
+        .await?;
+    }
+
+    let capabilities =
+        replace_source_in_session(&app, &session_id, &old_profile_id, source_config, keep_bus_mappings).await?;
+
+    if !same_profile {
+        forget_session_profile(&session_id, &old_profile_id);
+        profile_tracker::register_usage(&new_profile_id, &new_profile_kind, &session_id);
+        register_session_profile(&session_id, &new_profile_id);
+    }
 
     Ok(capabilities)
 }
@@ -1640,6 +1831,39 @@ pub async fn set_session_subscriber_active(
     set_subscriber_active(&session_id, &subscriber_id, is_active).await
 }
 
+/// Limit a listener to at most `max_frames_per_second` frames per frame ID
+/// (None or 0 = every frame). Only the newest frame of each ID in each
+/// interval is delivered; drops are counted in `session-metrics`.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_session_subscriber_rate_limit(
+    session_id: String,
+    subscriber_id: String,
+    max_frames_per_second: Option<u32>,
+) -> Result<(), String> {
+    set_subscriber_rate_limit(&session_id, &subscriber_id, max_frames_per_second)
+}
+
+/// Override a session's merge batching: flush after `batch_max_frames`
+/// frames or `batch_max_latency_ms`, whichever comes first. None falls back
+/// to the settings default. Applies from the running session's next flush.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_session_batching(session_id: String, options: crate::io::batching::BatchingOptions) {
+    crate::io::batching::set_session_options(&session_id, options);
+}
+
+/// A session's batching overrides (unset fields use the settings defaults).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_session_batching(session_id: String) -> crate::io::batching::BatchingOptions {
+    crate::io::batching::session_options(&session_id)
+}
+
+/// Scan the local network for GVRET TCP devices (UDP beacon + mDNS) and probe
+/// each one, so a new profile can be prefilled with the device's address.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn discover_gvret_devices(scan_ms: Option<u64>) -> Result<GvretDiscoveryResult, String> {
+    Ok(discover_gvret_tcp(scan_ms).await)
+}
+
 /// Probe a GVRET device to discover its capabilities (number of buses, etc.)
 ///
 /// This loads the profile from settings, connects to the device, queries it,
@@ -1651,7 +1875,7 @@ pub async fn probe_gvret_device(
 ) -> Result<GvretDeviceInfo, String> {
     let settings = settings::load_settings(app.clone())
         .await
-        .map_err(|e| format!("Failed to load settings: {}", e))?;
+        .map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
     let profile = settings
         .io_profiles
@@ -1661,43 +1885,18 @@ pub async fn probe_gvret_device(
 
     match profile.kind.as_str() {
         "gvret_tcp" | "gvret-tcp" => {
-            let host = profile
-                .connection
-                .get("host")
-                .and_then(|v| v.as_str())
-                .unwrap_or("127.0.0.1");
-            let port = profile
-                .connection
-                .get("port")
-                .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
-                .unwrap_or(23) as u16;
-            let timeout_sec = profile
-                .connection
-                .get("timeout")
-                .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
-                .unwrap_or(5.0);
-
+            let (host, port, timeout_sec) = gvret_tcp_endpoint(profile);
             probe_gvret_tcp(host, port, timeout_sec)
                 .await
                 .map_err(String::from)
         }
         #[cfg(not(target_os = "ios"))]
         "gvret_usb" | "gvret-usb" => {
-            let port = profile
-                .connection
-                .get("port")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| "Serial port is required for GVRET USB".to_string())?;
-            let baud_rate = profile
-                .connection
-                .get("baud_rate")
-                .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
-                .unwrap_or(115200) as u32;
+            let (port, baud_rate) = gvret_usb_port(profile)?;
 
             // Run blocking serial probe in a dedicated thread
-            let port_owned = port.to_string();
             tokio::task::spawn_blocking(move || {
-                probe_gvret_usb(&port_owned, baud_rate).map_err(String::from)
+                probe_gvret_usb(&port, baud_rate).map_err(String::from)
             })
                 .await
                 .map_err(|e| format!("Probe task failed: {}", e))?
@@ -1713,6 +1912,115 @@ pub async fn probe_gvret_device(
     }
 }
 
+/// Host, port and connect timeout of a GVRET TCP profile.
+fn gvret_tcp_endpoint(profile: &IOProfile) -> (&str, u16, f64) {
+    let host = profile
+        .connection
+        .get("host")
+        .and_then(|v| v.as_str())
+        .unwrap_or("127.0.0.1");
+    let port = profile
+        .connection
+        .get("port")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(23) as u16;
+    let timeout_sec = profile
+        .connection
+        .get("timeout")
+        .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(5.0);
+    (host, port, timeout_sec)
+}
+
+/// Serial port and baud rate of a GVRET USB profile.
+#[cfg(not(target_os = "ios"))]
+fn gvret_usb_port(profile: &IOProfile) -> Result<(String, u32), String> {
+    let port = profile
+        .connection
+        .get("port")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Serial port is required for GVRET USB".to_string())?;
+    let baud_rate = profile
+        .connection
+        .get("baud_rate")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+        .unwrap_or(115200) as u32;
+    Ok((port.to_string(), baud_rate))
+}
+
+/// Change a GVRET device's bus setup: bitrate, enabled, listen-only. Unset
+/// fields keep the device's current value. Only buses 0 and 1 can be set up,
+/// and not while a session is using the profile (it owns the connection and
+/// restores the bus mode when it stops). Returns both buses as the device
+/// reports them afterwards.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn configure_gvret_bus(
+    app: tauri::AppHandle,
+    profile_id: String,
+    bus: u8,
+    bitrate: Option<u32>,
+    enabled: Option<bool>,
+    listen_only: Option<bool>,
+) -> Result<Vec<GvretBusParams>, String> {
+    let change = GvretBusChange { bitrate, enabled, listen_only };
+    if change.is_empty() {
+        return Err("Nothing to change: give a bitrate, enabled or listen_only".to_string());
+    }
+    if bus >= listen_only::GVRET_CONFIGURABLE_BUSES {
+        return Err(format!("GVRET bus setup only reaches buses 0 and 1 (got bus {})", bus));
+    }
+    if let Some(bitrate) = bitrate {
+        if !(GVRET_MIN_BITRATE..=GVRET_MAX_BITRATE).contains(&bitrate) {
+            return Err(format!(
+                "Bitrate {} is outside {}–{} bit/s",
+                bitrate, GVRET_MIN_BITRATE, GVRET_MAX_BITRATE
+            ));
+        }
+    }
+    let sessions = get_sessions_for_profile(&profile_id);
+    if !sessions.is_empty() {
+        return Err(format!(
+            "Profile '{}' is in use by session(s) {}; stop them before changing the bus setup",
+            profile_id,
+            sessions.join(", ")
+        ));
+    }
+
+    let settings = settings::load_settings(app.clone())
+        .await
+        .map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
+    let profile = settings
+        .io_profiles
+        .iter()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| format!("Profile '{}' not found", profile_id))?;
+
+    let params = match profile.kind.as_str() {
+        "gvret_tcp" | "gvret-tcp" => {
+            let (host, port, timeout_sec) = gvret_tcp_endpoint(profile);
+            configure_gvret_tcp(host, port, timeout_sec, bus, change).await?
+        }
+        #[cfg(not(target_os = "ios"))]
+        "gvret_usb" | "gvret-usb" => {
+            let (port, baud_rate) = gvret_usb_port(profile)?;
+            tokio::task::spawn_blocking(move || configure_gvret_usb(&port, baud_rate, bus, change))
+                .await
+                .map_err(|e| format!("Bus setup task failed: {}", e))??
+        }
+        #[cfg(target_os = "ios")]
+        "gvret_usb" | "gvret-usb" => {
+            return Err("GVRET USB is not available on iOS".to_string());
+        }
+        _ => {
+            return Err(format!(
+                "Profile '{}' is not a GVRET device (kind: {})",
+                profile_id, profile.kind
+            ))
+        }
+    };
+    Ok(params.to_vec())
+}
+
 // ============================================================================
 // Unified Device Probe API
 // ============================================================================
@@ -1751,7 +2059,19 @@ pub struct DeviceProbeResult {
 /// Supported device types:
 /// - gvret_tcp, gvret_usb: Multi-bus GVRET devices
 /// - slcan: Single-bus slcan/CANable devices
-/// - gs_usb: Single-bus gs_usb/candleLight devices (Windows/macOS)
+/// - custom_serial: User-described binary CAN-over-serial adapters (port must exist)
+/// - elm327: ELM327 / OBD-II serial dongles (reset and identified)
+/// - lin: UART LIN adapters (frame IDs seen in a short listen)
+/// - canserver: CANserver / panda UDP streams (buses seen in a short listen)
+/// - websocket: WebSocket streaming endpoints (buses seen in a short listen)
+/// - udp_can: UDP listener for WiFi CAN bridges (buses and sender seen in a short listen)
+/// - doip: DoIP vehicle gateways (routing activation checked)
+/// - wiretap_net: WireTAP network publishers (greeting checked)
+/// - gs_usb: gs_usb/candleLight devices, one bus per channel (Windows/macOS, or Linux with direct USB access)
+/// - pcan: Single-channel PCAN-USB adapters (Windows/macOS)
+/// - kvaser: Single- or multi-channel Kvaser CANlib devices (Windows/Linux)
+/// - vector_xl: Single- or multi-channel Vector XL interfaces (Windows)
+/// - j2534: Single-channel SAE J2534 PassThru adapters (Windows)
 /// - socketcan: Single-bus SocketCAN interfaces (Linux)
 /// - serial: Raw serial ports (always "online" if port exists)
 #[tauri::command(rename_all = "snake_case")]
@@ -1789,7 +2109,7 @@ pub async fn probe_device(
             // Don't cache capture probes — metadata may change as data streams in
             return Ok(result);
         } else {
-            return Err(format!("Capture '{}' not found", profile_id));
+            return Err(msg!(CAPTURE_NOT_FOUND, capture_id = profile_id).into());
         }
     }
 
@@ -1810,7 +2130,7 @@ pub async fn probe_device(
 
     let settings = settings::load_settings(app.clone())
         .await
-        .map_err(|e| format!("Failed to load settings: {}", e))?;
+        .map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
     let profile = settings
         .io_profiles
@@ -1961,10 +2281,11 @@ pub async fn probe_device(
             })
         }
 
-        // gs_usb devices - single-bus (Windows/macOS via nusb)
-        #[cfg(any(target_os = "windows", target_os = "macos"))]
-        "gs_usb" => {
-            use crate::io::gs_usb::probe_gs_usb_device;
+        // gs_usb devices - one bus per channel (nusb; on Linux only for
+        // direct-access profiles, SocketCAN ones are probed as interfaces)
+        #[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+        "gs_usb" if crate::io::gs_usb::uses_direct_usb(&profile) => {
+            use crate::io::gs_usb::nusb_driver::probe_device as probe_gs_usb_device;
 
             let bus = profile.connection.get("bus")
                 .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
@@ -1977,12 +2298,13 @@ pub async fn probe_device(
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string());
 
-            match probe_gs_usb_device(bus, address, serial) {
+            match probe_gs_usb_device(bus, address, serial.as_deref()).map_err(String::from) {
                 Ok(info) => Ok(DeviceProbeResult {
                     success: true,
                     source_type: "gs_usb".to_string(),
-                    is_multi_bus: false,
-                    bus_count: info.channel_count.unwrap_or(1) as u8,
+                    // Dual-channel devices get a bus mapping per channel
+                    is_multi_bus: info.channel_count.unwrap_or(1) > 1,
+                    bus_count: info.channel_count.unwrap_or(1),
                     primary_info: info.channel_count.map(|c| format!("{} channel(s)", c)),
                     secondary_info: if info.supports_fd.unwrap_or(false) {
                         Some("CAN FD supported".to_string())
@@ -2005,6 +2327,165 @@ pub async fn probe_device(
             }
         }
 
+        // PCAN-USB - single channel (Windows/macOS via PCAN-Basic)
+        #[cfg(any(target_os = "windows", target_os = "macos"))]
+        "pcan" => {
+            let channel = profile.connection.get("channel")
+                .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+                .unwrap_or(1) as u8;
+            let device_id = profile.connection.get("device_id")
+                .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+                .map(|v| v as u32);
+
+            let found = crate::io::pcan::list_pcan_devices().map(|devices| {
+                devices.into_iter().find(|d| match device_id {
+                    Some(id) => d.device_id == Some(id),
+                    None => d.channel == channel,
+                })
+            });
+            let result = |success: bool, primary_info: Option<String>, error: Option<String>| DeviceProbeResult {
+                success,
+                source_type: "pcan".to_string(),
+                is_multi_bus: false,
+                bus_count: if success { 1 } else { 0 },
+                primary_info,
+                secondary_info: None,
+                supports_fd: Some(false),
+                error,
+            };
+            match found {
+                Ok(Some(d)) if d.occupied => Ok(result(
+                    false,
+                    Some(d.hardware_name),
+                    Some(format!("PCAN_USBBUS{} is in use by another application", d.channel)),
+                )),
+                Ok(Some(d)) => Ok(result(true, Some(d.hardware_name), None)),
+                Ok(None) => Ok(result(false, None, Some("PCAN-USB channel not connected".to_string()))),
+                Err(e) => Ok(result(false, None, Some(e))),
+            }
+        }
+
+        // Kvaser - one device, one bus per channel (Windows/Linux via CANlib)
+        #[cfg(any(target_os = "windows", target_os = "linux"))]
+        "kvaser" => {
+            let serial = profile.connection.get("serial")
+                .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())));
+            let channel = profile.connection.get("channel")
+                .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+                .unwrap_or(0) as u32;
+
+            let found = crate::io::kvaser::list_kvaser_devices().map(|devices| {
+                devices.into_iter().find(|d| match serial {
+                    Some(serial) => d.serial == serial,
+                    None => d.channels.contains(&channel),
+                })
+            });
+            match found {
+                Ok(Some(d)) => Ok(DeviceProbeResult {
+                    success: true,
+                    source_type: "kvaser".to_string(),
+                    is_multi_bus: d.channels.len() > 1,
+                    bus_count: d.channels.len() as u8,
+                    primary_info: Some(d.name),
+                    secondary_info: Some(format!("S/N {} ({})", d.serial, d.ean)),
+                    supports_fd: Some(false),
+                    error: None,
+                }),
+                Ok(None) | Err(_) => Ok(DeviceProbeResult {
+                    success: false,
+                    source_type: "kvaser".to_string(),
+                    is_multi_bus: false,
+                    bus_count: 0,
+                    primary_info: None,
+                    secondary_info: None,
+                    supports_fd: Some(false),
+                    error: Some(found.err().unwrap_or_else(|| "Kvaser device not connected".to_string())),
+                }),
+            }
+        }
+
+        // Vector - one interface, one bus per CAN channel (Windows via XL Driver Library)
+        #[cfg(target_os = "windows")]
+        "vector_xl" => {
+            let serial = profile.connection.get("serial")
+                .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+                .map(|s| s as u32);
+            let channel = profile.connection.get("channel")
+                .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+                .unwrap_or(0) as u8;
+
+            let found = crate::io::vector_xl::list_vector_devices().map(|devices| {
+                devices.into_iter().find(|d| match serial {
+                    Some(serial) => d.serial == serial,
+                    None => d.channels.iter().any(|c| c.channel_index == channel),
+                })
+            });
+            match found {
+                Ok(Some(d)) => Ok(DeviceProbeResult {
+                    success: true,
+                    source_type: "vector_xl".to_string(),
+                    is_multi_bus: d.channels.len() > 1,
+                    bus_count: d.channels.len() as u8,
+                    supports_fd: Some(d.channels.iter().all(|c| c.supports_fd)),
+                    primary_info: Some(d.name),
+                    secondary_info: Some(format!("S/N {}", d.serial)),
+                    error: None,
+                }),
+                Ok(None) | Err(_) => Ok(DeviceProbeResult {
+                    success: false,
+                    source_type: "vector_xl".to_string(),
+                    is_multi_bus: false,
+                    bus_count: 0,
+                    primary_info: None,
+                    secondary_info: None,
+                    supports_fd: None,
+                    error: Some(found.err().unwrap_or_else(|| "Vector interface not connected".to_string())),
+                }),
+            }
+        }
+
+        // J2534 - open the PassThru device briefly and read its versions (Windows)
+        #[cfg(target_os = "windows")]
+        "j2534" => {
+            let device = profile.connection.get("device")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string();
+            let library_path = profile.connection.get("library_path")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+
+            let name = device.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                crate::io::j2534::probe(&name, library_path.as_deref())
+            })
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| r);
+            match result {
+                Ok(version) => Ok(DeviceProbeResult {
+                    success: true,
+                    source_type: "j2534".to_string(),
+                    is_multi_bus: false,
+                    bus_count: 1,
+                    primary_info: Some(device),
+                    secondary_info: Some(format!("Firmware {} (API {})", version.firmware, version.api)),
+                    supports_fd: Some(false),
+                    error: None,
+                }),
+                Err(e) => Ok(DeviceProbeResult {
+                    success: false,
+                    source_type: "j2534".to_string(),
+                    is_multi_bus: false,
+                    bus_count: 0,
+                    primary_info: None,
+                    secondary_info: None,
+                    supports_fd: Some(false),
+                    error: Some(e),
+                }),
+            }
+        }
+
         // SocketCAN - Linux only, check if interface exists
         #[cfg(target_os = "linux")]
         "socketcan" => {
@@ -2074,6 +2555,234 @@ pub async fn probe_device(
                 })
             }
         }
+        // Custom CAN-over-serial - check the frame format and that the port exists
+        #[cfg(not(target_os = "ios"))]
+        "custom_serial" => {
+            let port = profile.connection.get("port")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| "Serial port is required".to_string())?;
+            let format = crate::io::custom_serial::CustomFrameFormat::from_connection(&profile.connection);
+            let port_exists = serialport::available_ports()
+                .unwrap_or_default()
+                .iter()
+                .any(|p| p.port_name == port);
+
+            let error = match (&format, port_exists) {
+                (Err(e), _) => Some(e.clone()),
+                (Ok(_), false) => Some(format!("Port '{}' not found", port)),
+                (Ok(_), true) => None,
+            };
+            Ok(DeviceProbeResult {
+                success: error.is_none(),
+                source_type: "custom_serial".to_string(),
+                is_multi_bus: false,
+                bus_count: if error.is_none() { 1 } else { 0 },
+                primary_info: error.is_none().then(|| port.to_string()),
+                secondary_info: format.ok().map(|f| format!(
+                    "Header {}, {}-byte ID",
+                    f.header.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" "),
+                    f.id_bytes
+                )),
+                supports_fd: Some(false),
+                error,
+            })
+        }
+
+        // ELM327 - reset the adapter and read its identification
+        #[cfg(not(target_os = "ios"))]
+        "elm327" => {
+            let config = crate::io::elm327::Elm327Config::from_connection(&profile.connection)?;
+            let (port, baud_rate) = (config.port.clone(), config.baud_rate);
+            let result = tokio::task::spawn_blocking(move || {
+                crate::io::elm327::probe_elm327(&port, baud_rate)
+            }).await.map_err(|e| format!("Probe task failed: {}", e))?;
+
+            Ok(DeviceProbeResult {
+                success: result.is_ok(),
+                source_type: "elm327".to_string(),
+                is_multi_bus: false,
+                bus_count: if result.is_ok() { 1 } else { 0 },
+                primary_info: result.as_ref().ok().cloned(),
+                secondary_info: Some(match config.mode {
+                    crate::io::elm327::Elm327Mode::Monitor => "Monitor all".to_string(),
+                    crate::io::elm327::Elm327Mode::Poll => format!("Polling {} requests", config.pids.len()),
+                }),
+                supports_fd: Some(false),
+                error: result.err(),
+            })
+        }
+
+        // LIN - open the port and list the frame IDs heard
+        #[cfg(not(target_os = "ios"))]
+        "lin" => {
+            let config = crate::io::lin::LinConfig::from_connection(&profile.connection)?;
+            let (port, baud_rate) = (config.port.clone(), config.baud_rate);
+            let result = tokio::task::spawn_blocking(move || {
+                crate::io::lin::probe_lin(&port, baud_rate)
+            }).await.map_err(|e| format!("Probe task failed: {}", e))?;
+
+            Ok(DeviceProbeResult {
+                success: result.is_ok(),
+                source_type: "lin".to_string(),
+                is_multi_bus: false,
+                bus_count: if result.is_ok() { 1 } else { 0 },
+                primary_info: result.as_ref().ok().map(|ids| {
+                    if ids.is_empty() {
+                        "Port open, no LIN traffic heard".to_string()
+                    } else {
+                        let seen: Vec<String> = ids.iter().map(|id| format!("0x{:02X}", id)).collect();
+                        format!("Frames seen: {}", seen.join(", "))
+                    }
+                }),
+                secondary_info: Some(match config.mode {
+                    crate::io::lin::LinMode::Monitor => "Monitor".to_string(),
+                    crate::io::lin::LinMode::Master => "Master (schedule table)".to_string(),
+                }),
+                supports_fd: Some(false),
+                error: result.err(),
+            })
+        }
+
+        // CANserver - say hello and see which buses carry traffic
+        "canserver" => {
+            let config = crate::io::canserver::CanServerConfig::from_connection(&profile.connection)?;
+            let address = format!("{}:{}", config.host, config.port);
+            match crate::io::canserver::probe_canserver(&config.host, config.port, config.timeout_sec).await {
+                Ok(buses) => {
+                    let bus_count = buses.iter().max().map_or(1, |b| b + 1);
+                    let seen: Vec<String> = buses.iter().map(|b| b.to_string()).collect();
+                    Ok(DeviceProbeResult {
+                        success: true,
+                        source_type: "canserver".to_string(),
+                        is_multi_bus: bus_count > 1,
+                        bus_count,
+                        primary_info: Some(format!("Streaming on bus {}", seen.join(", "))),
+                        secondary_info: Some(address),
+                        supports_fd: Some(false),
+                        error: None,
+                    })
+                }
+                Err(e) => Ok(DeviceProbeResult {
+                    success: false,
+                    source_type: "canserver".to_string(),
+                    is_multi_bus: false,
+                    bus_count: 0,
+                    primary_info: None,
+                    secondary_info: Some(address),
+                    supports_fd: Some(false),
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+
+        // WebSocket - connect and see which buses the mapped frames carry
+        "websocket" => {
+            let config = crate::io::websocket::WebSocketConfig::from_connection(&profile.connection)?;
+            match crate::io::websocket::probe_websocket(&config).await {
+                Ok(buses) => {
+                    let bus_count = buses.iter().max().map_or(1, |b| b + 1);
+                    let seen: Vec<String> = buses.iter().map(|b| b.to_string()).collect();
+                    Ok(DeviceProbeResult {
+                        success: true,
+                        source_type: "websocket".to_string(),
+                        is_multi_bus: bus_count > 1,
+                        bus_count,
+                        primary_info: Some(format!("Streaming on bus {}", seen.join(", "))),
+                        secondary_info: Some(config.url),
+                        supports_fd: Some(true),
+                        error: None,
+                    })
+                }
+                Err(e) => Ok(DeviceProbeResult {
+                    success: false,
+                    source_type: "websocket".to_string(),
+                    is_multi_bus: false,
+                    bus_count: 0,
+                    primary_info: None,
+                    secondary_info: Some(config.url),
+                    supports_fd: Some(true),
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+
+        // UDP listener - bind the port and see which buses and sender turn up
+        "udp_can" => {
+            let config = crate::io::udp_can::UdpCanConfig::from_connection(&profile.connection)?;
+            let address = format!("{}:{}", config.bind, config.port);
+            match crate::io::udp_can::probe_udp_can(&config).await {
+                Ok((buses, sender)) => {
+                    let bus_count = buses.iter().max().map_or(1, |b| b + 1);
+                    let seen: Vec<String> = buses.iter().map(|b| b.to_string()).collect();
+                    Ok(DeviceProbeResult {
+                        success: true,
+                        source_type: "udp_can".to_string(),
+                        is_multi_bus: bus_count > 1,
+                        bus_count,
+                        primary_info: Some(format!("Receiving bus {} from {}", seen.join(", "), sender)),
+                        secondary_info: Some(address),
+                        supports_fd: Some(true),
+                        error: None,
+                    })
+                }
+                Err(e) => Ok(DeviceProbeResult {
+                    success: false,
+                    source_type: "udp_can".to_string(),
+                    is_multi_bus: false,
+                    bus_count: 0,
+                    primary_info: None,
+                    secondary_info: Some(address),
+                    supports_fd: Some(true),
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+
+        // DoIP gateway - connect and activate routing
+        "doip" => {
+            let config = crate::io::doip::DoipConfig::from_connection(&profile.connection)?;
+            let address = format!("{}:{}", config.host, config.port);
+            match crate::io::doip::probe_doip(&config).await {
+                Ok(entity) => Ok(DeviceProbeResult {
+                    success: true,
+                    source_type: "doip".to_string(),
+                    is_multi_bus: false,
+                    bus_count: 1,
+                    primary_info: Some(format!("Routing active on gateway 0x{:04X}", entity)),
+                    secondary_info: Some(address),
+                    supports_fd: None,
+                    error: None,
+                }),
+                Err(e) => Ok(DeviceProbeResult {
+                    success: false,
+                    source_type: "doip".to_string(),
+                    is_multi_bus: false,
+                    bus_count: 0,
+                    primary_info: None,
+                    secondary_info: Some(address),
+                    supports_fd: None,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+
+        // WireTAP network publisher - connect and check the greeting
+        "wiretap_net" => {
+            let config = crate::io::netbridge::NetBridgeConfig::from_connection(&profile.connection)?;
+            let address = format!("{}:{}", config.host, config.port);
+            let result = crate::io::netbridge::probe_publisher(&config.host, config.port, config.timeout_sec).await;
+            Ok(DeviceProbeResult {
+                success: result.is_ok(),
+                source_type: "wiretap_net".to_string(),
+                is_multi_bus: false,
+                bus_count: if result.is_ok() { 1 } else { 0 },
+                primary_info: result.is_ok().then(|| "WireTAP publisher".to_string()),
+                secondary_info: Some(address),
+                supports_fd: Some(true),
+                error: result.err().map(|e| e.to_string()),
+            })
+        }
+
         #[cfg(target_os = "ios")]
         "serial" => {
             Ok(DeviceProbeResult {
@@ -2297,9 +3006,16 @@ pub struct MultiSourceInput {
     /// Source address extraction: byte order (true = big endian)
     #[serde(default)]
     pub source_address_big_endian: Option<bool>,
-    /// Modbus interface role (client or server)
+    /// Modbus interface role (None = the profile's role, else client)
     #[serde(default)]
     pub modbus_role: Option<ModbusRole>,
+    /// Restart the source with backoff when it drops (None = the source ends)
+    #[serde(default)]
+    pub reconnect: Option<crate::io::ReconnectPolicy>,
+    /// Listen-only override (None = the profile's own setting). Refused for
+    /// sources that can't guarantee staying off the bus.
+    #[serde(default)]
+    pub listen_only: Option<bool>,
 }
 
 /// Convert a MultiSourceInput to a SourceConfig, resolving profile name and kind from settings.
@@ -2314,6 +3030,7 @@ fn resolve_source_config(
         .iter()
         .find(|p| p.id == input.profile_id)
         .ok_or_else(|| format!("Profile '{}' not found", input.profile_id))?;
+    profile_schema::ensure_valid(profile)?;
 
     let display_name = input.display_name.unwrap_or_else(|| profile.name.clone());
     let profile_kind = profile.kind.clone();
@@ -2324,7 +3041,19 @@ fn resolve_source_config(
             ("can0".to_string(), vec![Protocol::Can, Protocol::CanFd], true)
         }
         "slcan" => ("can0".to_string(), vec![Protocol::Can], true),
+        "custom_serial" => ("can0".to_string(), vec![Protocol::Can], true),
+        "elm327" => ("can0".to_string(), vec![Protocol::Can], false),
+        "lin" => ("lin0".to_string(), vec![Protocol::Serial], is_lin_master(profile)),
+        "canserver" => ("can0".to_string(), vec![Protocol::Can], false),
+        "websocket" => ("can0".to_string(), vec![Protocol::Can, Protocol::CanFd], false),
+        "udp_can" => ("can0".to_string(), vec![Protocol::Can, Protocol::CanFd], false),
+        "doip" => ("doip0".to_string(), vec![Protocol::Serial], false),
+        "wiretap_net" => ("can0".to_string(), vec![Protocol::Can, Protocol::CanFd], false),
         "gs_usb" => ("can0".to_string(), vec![Protocol::Can, Protocol::CanFd], true),
+        "pcan" => ("can0".to_string(), vec![Protocol::Can], true),
+        "kvaser" => ("can0".to_string(), vec![Protocol::Can], true),
+        "vector_xl" => ("can0".to_string(), vec![Protocol::Can, Protocol::CanFd], true),
+        "j2534" => ("can0".to_string(), vec![Protocol::Can], true),
         "socketcan" => ("can0".to_string(), vec![Protocol::Can, Protocol::CanFd], true),
         "modbus_tcp" => ("modbus0".to_string(), vec![Protocol::Modbus], false),
         _ => ("can0".to_string(), vec![Protocol::Can], true),
@@ -2338,7 +3067,7 @@ fn resolve_source_config(
             source_idx, display_name, output_bus
         );
         vec![BusMapping {
-            device_bus: 0,
+            device_bus: default_device_bus(profile),
             enabled: true,
             output_bus,
             interface_id: default_interface_id,
@@ -2353,6 +3082,7 @@ fn resolve_source_config(
     } else {
         input.bus_mappings
     };
+    let listen_only = listen_only::resolve(profile, input.listen_only, &bus_mappings)?;
 
     Ok(SourceConfig {
         profile_id: input.profile_id,
@@ -2373,6 +3103,8 @@ fn resolve_source_config(
         modbus_polls: None,    // Injected by create_multi_source_session
         modbus_role: input.modbus_role,
         max_register_errors: None, // Injected by create_multi_source_session
+        reconnect: input.reconnect,
+        listen_only,
     })
 }
 
@@ -2399,7 +3131,7 @@ pub async fn create_multi_source_session(
 
     let settings = settings::load_settings(app.clone())
         .await
-        .map_err(|e| format!("Failed to load settings: {}", e))?;
+        .map_err(|e| msg!(SETTINGS_LOAD_FAILED, error = e))?;
 
     // Parse shared Modbus poll groups (if any)
     let parsed_polls: Option<Vec<crate::io::PollGroup>> = match &modbus_polls {
@@ -2431,17 +3163,55 @@ pub async fn create_multi_source_session(
         if !is_realtime_device(&config.profile_kind) {
             return Err(format!(
                 "Profile '{}' has unsupported type '{}' for multi-source mode. \
-                Currently supported: gvret_tcp, gvret_usb, slcan, gs_usb, socketcan, serial, modbus_tcp, virtual",
+                Currently supported: gvret_tcp, gvret_usb, slcan, custom_serial, elm327, lin, canserver, websocket, udp_can, doip, wiretap_net, gs_usb, pcan, kvaser, vector_xl, j2534, socketcan, serial, modbus_tcp, virtual",
                 config.profile_id, config.profile_kind
             ));
         }
 
         // Platform-specific validation
         #[cfg(target_os = "linux")]
-        if config.profile_kind == "gs_usb" {
+        if config.profile_kind == "gs_usb"
+            && !settings.io_profiles.iter().any(|p| p.id == config.profile_id && crate::io::gs_usb::uses_direct_usb(p))
+        {
             return Err(format!(
                 "Profile '{}' uses gs_usb which on Linux should use SocketCAN interface. \
-                Configure a socketcan profile instead.",
+                Configure a socketcan profile instead, or enable direct USB access on the profile.",
+                config.profile_id
+            ));
+        }
+
+        #[cfg(target_os = "linux")]
+        if config.profile_kind == "pcan" {
+            return Err(format!(
+                "Profile '{}' uses PCAN-USB which on Linux appears as a SocketCAN interface \
+                (peak_usb driver). Configure a socketcan profile instead.",
+                config.profile_id
+            ));
+        }
+
+        #[cfg(target_os = "macos")]
+        if config.profile_kind == "kvaser" {
+            return Err(format!(
+                "Profile '{}' uses a Kvaser device, but Kvaser CANlib is only available \
+                on Windows and Linux.",
+                config.profile_id
+            ));
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        if config.profile_kind == "vector_xl" {
+            return Err(format!(
+                "Profile '{}' uses a Vector interface, but the Vector XL Driver Library \
+                is only available on Windows.",
+                config.profile_id
+            ));
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        if config.profile_kind == "j2534" {
+            return Err(format!(
+                "Profile '{}' uses a J2534 PassThru adapter, but J2534 drivers \
+                are only available on Windows.",
                 config.profile_id
             ));
         }
@@ -2468,6 +3238,14 @@ pub async fn create_multi_source_session(
         let _ = destroy_session(&session_id, false).await;
     }
 
+    session_quota::admit(AdmissionRequest {
+        new_session: true,
+        usb_devices: session_quota::new_usb_devices(
+            source_configs.iter().map(|c| (c.profile_id.as_str(), c.profile_kind.as_str())),
+        ),
+    })
+    .await?;
+
     // Create the multi-source reader (validates interface trait compatibility)
     // Extract display names for logging before moving source_configs
     let source_display_names: Vec<String> = source_configs.iter()
@@ -2477,8 +3255,8 @@ pub async fn create_multi_source_session(
     let reader = IOBroker::new(app.clone(), session_id.clone(), source_configs)?;
 
     // Register profile usage BEFORE create_session so lifecycle event has profile IDs
-    for profile_id in &profile_ids {
-        profile_tracker::register_usage(profile_id, &session_id);
+    for config in &stored_configs {
+        profile_tracker::register_usage(&config.profile_id, &config.profile_kind, &session_id);
     }
     // Store all profiles for this session (needed for cleanup on destroy)
     register_session_profiles(&session_id, &profile_ids);
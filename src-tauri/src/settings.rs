@@ -134,6 +134,13 @@ pub struct AppSettings {
     #[serde(default = "default_capture_storage", alias = "buffer_storage")]
     pub capture_storage: String,
 
+    /// SQLite page cache (and memory-mapped read window) for the whole capture
+    /// database, in MB. Shared by every capture and not a limit on any one of
+    /// them: capture data always lives on disk, and this only sets how much of
+    /// it stays in memory for faster reads. Applied at launch.
+    #[serde(default = "default_capture_db_cache_mb", alias = "capture_cache_mb")]
+    pub capture_db_cache_mb: u32,
+
     // Decoder buffer limits
     #[serde(default = "default_decoder_max_unmatched_frames")]
     pub decoder_max_unmatched_frames: u32,
@@ -148,6 +155,17 @@ pub struct AppSettings {
     #[serde(default = "default_transmit_max_history")]
     pub transmit_max_history: u32,
 
+    // Frame batching (merge task). Per-session overrides via set_session_batching
+    /// Frames held before the merge task flushes a batch to the capture
+    #[serde(default = "default_batch_max_frames")]
+    pub batch_max_frames: u32,
+    /// Longest a frame waits in the merge task before its batch is flushed
+    #[serde(default = "default_batch_max_latency_ms")]
+    pub batch_max_latency_ms: u32,
+    /// Raise `bus-silent` when an enabled bus carries no frames for this long (0 = off)
+    #[serde(default = "default_bus_silence_timeout_ms")]
+    pub bus_silence_timeout_ms: u32,
+
     // Modbus settings
     /// Stop polling a register group after this many consecutive errors (0 = never stop)
     #[serde(default = "default_modbus_max_register_errors")]
@@ -195,6 +213,25 @@ pub struct AppSettings {
     /// static client config survives restarts.
     #[serde(default)]
     pub mcp_server_token: String,
+
+    // Remote control API — REST/WebSocket access to sessions for test rigs
+    // and CI. Off by default and never starts without a token.
+    /// When true the remote API server binds and listens.
+    #[serde(default)]
+    pub remote_api_enabled: bool,
+    /// Fixed localhost port the remote API listens on.
+    #[serde(default = "default_remote_api_port")]
+    pub remote_api_port: u16,
+    /// Bearer token required by every request. Lives in the system keyring;
+    /// `load_settings` fills it in and `save_settings` moves it back out, so
+    /// it is only on disk in settings files from before that.
+    #[serde(default)]
+    pub remote_api_token: String,
+
+    /// Saved session configurations (see session_templates.rs). Managed by the
+    /// template commands; `save_settings` keeps whatever is on disk.
+    #[serde(default)]
+    pub session_templates: Vec<crate::session_templates::SessionTemplate>,
 }
 
 fn default_display_frame_id_format() -> String {
@@ -345,6 +382,9 @@ fn default_clear_captures_on_start() -> bool {
 fn default_capture_storage() -> String {
     "sqlite".to_string()
 }
+fn default_capture_db_cache_mb() -> u32 {
+    64
+}
 fn default_smp_port() -> u16 {
     1337
 }
@@ -362,6 +402,9 @@ fn default_mcp_allow_control() -> bool {
 fn default_mcp_server_port() -> u16 {
     8787
 }
+fn default_remote_api_port() -> u16 {
+    8788
+}
 
 // Decoder buffer limit defaults
 fn default_decoder_max_unmatched_frames() -> u32 {
@@ -382,6 +425,17 @@ fn default_transmit_max_history() -> u32 {
     1000
 }
 
+// Frame batching defaults
+fn default_batch_max_frames() -> u32 {
+    100
+}
+fn default_batch_max_latency_ms() -> u32 {
+    50
+}
+fn default_bus_silence_timeout_ms() -> u32 {
+    2000
+}
+
 // Modbus defaults
 fn default_modbus_max_register_errors() -> u32 {
     3
@@ -454,6 +508,7 @@ impl Default for AppSettings {
             // Capture persistence
             clear_captures_on_start: default_clear_captures_on_start(),
             capture_storage: default_capture_storage(),
+            capture_db_cache_mb: default_capture_db_cache_mb(),
             // Decoder buffer limits
             decoder_max_unmatched_frames: default_decoder_max_unmatched_frames(),
             decoder_max_filtered_frames: default_decoder_max_filtered_frames(),
@@ -461,6 +516,10 @@ impl Default for AppSettings {
             decoder_max_decoded_per_source: default_decoder_max_decoded_per_source(),
             // Transmit limits
             transmit_max_history: default_transmit_max_history(),
+            // Frame batching
+            batch_max_frames: default_batch_max_frames(),
+            batch_max_latency_ms: default_batch_max_latency_ms(),
+            bus_silence_timeout_ms: default_bus_silence_timeout_ms(),
             // Modbus
             modbus_max_register_errors: default_modbus_max_register_errors(),
             smp_port: default_smp_port(),
@@ -475,6 +534,10 @@ impl Default for AppSettings {
             mcp_allow_ui_control: default_mcp_allow_control(),
             mcp_server_port: default_mcp_server_port(),
             mcp_server_token: String::new(),
+            remote_api_enabled: false,
+            remote_api_port: default_remote_api_port(),
+            remote_api_token: String::new(),
+            session_templates: Vec::new(),
         }
     }
 }
@@ -549,6 +612,7 @@ impl AppSettings {
             // Capture persistence
             clear_captures_on_start: default_clear_captures_on_start(),
             capture_storage: default_capture_storage(),
+            capture_db_cache_mb: default_capture_db_cache_mb(),
             // Decoder buffer limits
             decoder_max_unmatched_frames: default_decoder_max_unmatched_frames(),
             decoder_max_filtered_frames: default_decoder_max_filtered_frames(),
@@ -556,6 +620,10 @@ impl AppSettings {
             decoder_max_decoded_per_source: default_decoder_max_decoded_per_source(),
             // Transmit limits
             transmit_max_history: default_transmit_max_history(),
+            // Frame batching
+            batch_max_frames: default_batch_max_frames(),
+            batch_max_latency_ms: default_batch_max_latency_ms(),
+            bus_silence_timeout_ms: default_bus_silence_timeout_ms(),
             // Modbus
             modbus_max_register_errors: default_modbus_max_register_errors(),
             smp_port: default_smp_port(),
@@ -570,6 +638,10 @@ impl AppSettings {
             mcp_allow_ui_control: default_mcp_allow_control(),
             mcp_server_port: default_mcp_server_port(),
             mcp_server_token: String::new(),
+            remote_api_enabled: false,
+            remote_api_port: default_remote_api_port(),
+            remote_api_token: String::new(),
+            session_templates: Vec::new(),
         })
     }
 }
@@ -626,6 +698,16 @@ pub fn load_settings_sync(app: &AppHandle) -> Result<AppSettings, String> {
     }
 }
 
+/// Write settings to disk as they are, without the checks and side effects of
+/// `save_settings`. For backend-managed parts of the settings file.
+pub(crate) fn write_settings(app: &AppHandle, settings: &AppSettings) -> Result<(), String> {
+    let settings_path = get_settings_path(app)?;
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    std::fs::write(&settings_path, content)
+        .map_err(|e| format!("Failed to write settings: {}", e))
+}
+
 #[tauri::command]
 pub async fn load_settings(app: AppHandle) -> Result<AppSettings, String> {
     let settings_path = get_settings_path(&app)?;
@@ -646,6 +728,9 @@ pub async fn load_settings(app: AppHandle) -> Result<AppSettings, String> {
         // Migrate persisted paths from CANdor → WireTAP (rebrand)
         migrate_persisted_paths(&mut settings);
 
+        // The remote API token is kept in the keyring
+        settings.remote_api_token = crate::remote_api::load_token(&settings);
+
         // Check for stale paths (e.g., old iOS container UUIDs after reinstall)
         if paths_are_stale(&settings, &app) {
             tlog!("[settings] Regenerating stale directory paths");
@@ -807,6 +892,8 @@ pub async fn run_candor_migration(app: AppHandle) -> Result<String, String> {
                 current.decoder_max_decoded_frames = old.decoder_max_decoded_frames;
                 current.decoder_max_decoded_per_source = old.decoder_max_decoded_per_source;
                 current.transmit_max_history = old.transmit_max_history;
+                current.batch_max_frames = old.batch_max_frames;
+                current.batch_max_latency_ms = old.batch_max_latency_ms;
                 current.modbus_max_register_errors = old.modbus_max_register_errors;
 
                 // Apply path migration (CANdor → WireTAP) but keep fresh defaults for paths
@@ -974,17 +1061,26 @@ pub fn install_example_decoders(app: &AppHandle, decoder_dir: &str) -> Result<u3
 }
 
 #[tauri::command]
-pub async fn save_settings(app: AppHandle, settings: AppSettings) -> Result<(), String> {
-    let settings_path = get_settings_path(&app)?;
+pub async fn save_settings(app: AppHandle, mut settings: AppSettings) -> Result<(), String> {
+    // Session templates are saved through their own commands; the frontend's
+    // copy may be stale (or absent), so keep the ones on disk.
+    if let Ok(existing) = load_settings_sync(&app) {
+        settings.session_templates = existing.session_templates;
+    }
+
+    // Reject profiles whose connection settings don't match their kind's schema
+    for profile in &settings.io_profiles {
+        crate::profile_schema::ensure_valid(profile)?;
+    }
 
     // Ensure directories exist when saving
     initialize_directories(&settings)?;
 
-    let content = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    // Secrets go to the keyring, not the settings file
+    crate::remote_api::store_token(&settings.remote_api_token)?;
+    settings.remote_api_token.clear();
 
-    std::fs::write(&settings_path, content)
-        .map_err(|e| format!("Failed to write settings: {}", e))?;
+    write_settings(&app, &settings)?;
 
     // Rebuild the catalogue cache + re-point the watcher if the decoder dir moved.
     crate::catalog::handle_decoder_dir_change(&app, &settings.decoder_dir);
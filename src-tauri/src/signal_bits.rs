@@ -0,0 +1,129 @@
+// ui/src-tauri/src/signal_bits.rs
+//
+// Raw bit-field signal extraction, for backend features that need a signal's
+// physical value without a full catalogue (capture triggers, report plots).
+// Layout follows DBC conventions: `start_bit` is the LSB for little-endian
+// (Intel) signals and the MSB for big-endian (Motorola) signals.
+
+use serde::{Deserialize, Serialize};
+
+use crate::io::FrameMessage;
+
+/// Where a signal lives in a frame and how to scale it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignalLayout {
+    pub frame_id: u32,
+    /// Restrict to one bus (None = any bus)
+    #[serde(default)]
+    pub bus: Option<u8>,
+    pub start_bit: u16,
+    pub bit_length: u8,
+    #[serde(default)]
+    pub big_endian: bool,
+    #[serde(default)]
+    pub signed: bool,
+    #[serde(default = "default_factor")]
+    pub factor: f64,
+    #[serde(default)]
+    pub offset: f64,
+}
+
+fn default_factor() -> f64 {
+    1.0
+}
+
+impl SignalLayout {
+    /// Decode the physical value of this signal from a frame, if the frame
+    /// carries it.
+    pub fn physical_value(&self, frame: &FrameMessage) -> Option<f64> {
+        if frame.frame_id != self.frame_id {
+            return None;
+        }
+        if let Some(bus) = self.bus {
+            if frame.bus != bus {
+                return None;
+            }
+        }
+        let raw = extract_bits(&frame.bytes, self.start_bit, self.bit_length, self.big_endian)?;
+        Some(self.scale(raw))
+    }
+
+    /// Apply sign extension, factor and offset to a raw field value.
+    pub fn scale(&self, raw: u64) -> f64 {
+        let sign_bit = (raw >> (self.bit_length.max(1) - 1)) & 1 == 1;
+        let raw = if self.signed && sign_bit && self.bit_length < 64 {
+            (raw | (u64::MAX << self.bit_length)) as i64 as f64
+        } else if self.signed {
+            raw as i64 as f64
+        } else {
+            raw as f64
+        };
+        raw * self.factor + self.offset
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.bit_length == 0 || self.bit_length > 64 {
+            return Err(format!("bit_length must be 1-64, got {}", self.bit_length));
+        }
+        if !self.factor.is_finite() || !self.offset.is_finite() {
+            return Err("factor and offset must be finite".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Extract a raw bit field from a payload. Returns None if the field runs
+/// past the end of the payload or is wider than 64 bits.
+pub fn extract_bits(data: &[u8], start_bit: u16, bit_length: u8, big_endian: bool) -> Option<u64> {
+    if bit_length == 0 || bit_length > 64 {
+        return None;
+    }
+    let mut value: u64 = 0;
+    if big_endian {
+        // Motorola: walk from the MSB, moving to the next byte's bit 7
+        // whenever we fall off bit 0 of the current byte.
+        let mut bit = start_bit as usize;
+        for _ in 0..bit_length {
+            let byte = *data.get(bit / 8)?;
+            value = (value << 1) | ((byte >> (bit % 8)) & 1) as u64;
+            bit = if bit % 8 == 0 { bit + 15 } else { bit - 1 };
+        }
+    } else {
+        for i in 0..bit_length as usize {
+            let bit = start_bit as usize + i;
+            let byte = *data.get(bit / 8)?;
+            value |= (((byte >> (bit % 8)) & 1) as u64) << i;
+        }
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_little_and_big_endian() {
+        let data = [0x34, 0x12];
+        assert_eq!(extract_bits(&data, 0, 16, false), Some(0x1234));
+        assert_eq!(extract_bits(&data, 7, 16, true), Some(0x3412));
+        assert_eq!(extract_bits(&data, 4, 4, false), Some(0x3));
+        assert_eq!(extract_bits(&data, 8, 16, false), None);
+    }
+
+    #[test]
+    fn scale_sign_extends() {
+        let layout = SignalLayout {
+            frame_id: 0x200,
+            bus: None,
+            start_bit: 0,
+            bit_length: 8,
+            big_endian: false,
+            signed: true,
+            factor: 0.5,
+            offset: 1.0,
+        };
+        assert_eq!(layout.scale(0xFE), 0.0);
+        assert_eq!(layout.scale(0x02), 2.0);
+    }
+}
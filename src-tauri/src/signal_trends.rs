@@ -0,0 +1,375 @@
+// ui/src-tauri/src/signal_trends.rs
+//
+// Historical trend storage for selected decoded signals — a small in-memory
+// time-series store that outlives the frame buffer, for long-duration strip
+// charts.
+//
+// A session is given a list of signals to track. Their decoded values come
+// from the latest-value tap (latest_values::tap_frames), so only sessions
+// with an attached catalogue record anything. Each signal is a series of
+// points holding min/max/mean/count: at full resolution one point per
+// sample, otherwise one per `resolution_ms` of frame time. A series is capped
+// at `max_points`; when it overflows, the oldest half is merged pairwise, so
+// recent data stays detailed while older data gets progressively coarser and
+// memory stays bounded however long the session runs.
+//
+// query_signal_trend returns the points in a time range, merged into at most
+// `max_points` equal-width buckets so a chart gets one point per pixel column
+// without losing spikes (min/max survive merging).
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::latest_values::LatestValue;
+
+/// Default series cap (points per signal).
+const DEFAULT_MAX_POINTS: usize = 100_000;
+
+/// Smallest allowed series cap; compaction needs room to halve.
+const MIN_MAX_POINTS: usize = 64;
+
+/// Default point budget for a query.
+const DEFAULT_QUERY_POINTS: usize = 1_000;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A signal to record. `frame_id` disambiguates signals that share a name.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrendSignal {
+    pub name: String,
+    #[serde(default)]
+    pub frame_id: Option<u32>,
+}
+
+impl TrendSignal {
+    fn matches(&self, value: &LatestValue) -> bool {
+        self.name == value.name && self.frame_id.is_none_or(|id| id == value.frame_id)
+    }
+}
+
+fn default_max_points() -> usize {
+    DEFAULT_MAX_POINTS
+}
+
+/// Signals to record for a session and how finely.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrendConfig {
+    pub signals: Vec<TrendSignal>,
+    /// Point width in frame time (0 = every sample)
+    #[serde(default)]
+    pub resolution_ms: u64,
+    /// Points kept per signal before old data is compacted
+    #[serde(default = "default_max_points")]
+    pub max_points: usize,
+}
+
+/// One point of a series: the samples between `t_us` and the next point.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrendPoint {
+    /// Timestamp of the first sample (µs)
+    pub t_us: u64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub count: u64,
+}
+
+impl TrendPoint {
+    fn sample(t_us: u64, value: f64) -> Self {
+        TrendPoint { t_us, min: value, max: value, mean: value, count: 1 }
+    }
+
+    fn merge(&mut self, other: &TrendPoint) {
+        let count = self.count + other.count;
+        self.mean = (self.mean * self.count as f64 + other.mean * other.count as f64) / count as f64;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.t_us = self.t_us.min(other.t_us);
+        self.count = count;
+    }
+}
+
+/// Per-signal summary returned by get_signal_trends.
+#[derive(Clone, Debug, Serialize)]
+pub struct TrendSeriesInfo {
+    pub name: String,
+    pub frame_id: Option<u32>,
+    pub points: usize,
+    pub samples: u64,
+    pub first_us: Option<u64>,
+    pub last_us: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TrendState {
+    pub config: TrendConfig,
+    pub series: Vec<TrendSeriesInfo>,
+}
+
+#[derive(Default)]
+struct Series {
+    points: Vec<TrendPoint>,
+    samples: u64,
+    last_us: Option<u64>,
+}
+
+impl Series {
+    fn push(&mut self, t_us: u64, value: f64, resolution_us: u64, max_points: usize) {
+        self.samples += 1;
+        self.last_us = Some(self.last_us.map_or(t_us, |l| l.max(t_us)));
+        match self.points.last_mut() {
+            // Same bucket, or a sample older than the newest point (sources
+            // on a multi-source session don't interleave perfectly)
+            Some(last) if t_us < last.t_us || (resolution_us > 0 && t_us - last.t_us < resolution_us) => {
+                last.merge(&TrendPoint::sample(t_us.max(last.t_us), value));
+            }
+            _ => self.points.push(TrendPoint::sample(t_us, value)),
+        }
+        if self.points.len() > max_points {
+            self.compact();
+        }
+    }
+
+    /// Merge the oldest half of the series pairwise.
+    fn compact(&mut self) {
+        let half = (self.points.len() / 2) & !1;
+        let mut merged: Vec<TrendPoint> = self.points[..half]
+            .chunks(2)
+            .map(|pair| {
+                let mut p = pair[0];
+                p.merge(&pair[1]);
+                p
+            })
+            .collect();
+        merged.extend_from_slice(&self.points[half..]);
+        self.points = merged;
+    }
+}
+
+struct SessionTrends {
+    config: TrendConfig,
+    /// Parallel to config.signals
+    series: Vec<Series>,
+}
+
+/// Map of session_id -> tracked signals. Empty for most sessions, so the
+/// tap's check is a single read lock.
+static SIGNAL_TRENDS: Lazy<RwLock<HashMap<String, SessionTrends>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+/// Whether a session records any trends (lets the latest-value tap skip
+/// collecting samples).
+pub fn is_tracking(session_id: &str) -> bool {
+    SIGNAL_TRENDS.read().map(|t| t.contains_key(session_id)).unwrap_or(false)
+}
+
+/// Append decoded values to the session's tracked series.
+pub fn record_samples(session_id: &str, samples: &[LatestValue]) {
+    let Ok(mut trends) = SIGNAL_TRENDS.write() else {
+        return;
+    };
+    let Some(session) = trends.get_mut(session_id) else {
+        return;
+    };
+    let resolution_us = session.config.resolution_ms * 1000;
+    let max_points = session.config.max_points;
+    for sample in samples {
+        for (signal, series) in session.config.signals.iter().zip(session.series.iter_mut()) {
+            if signal.matches(sample) {
+                series.push(sample.timestamp_us, sample.value, resolution_us, max_points);
+            }
+        }
+    }
+}
+
+/// Merge points into at most `max_points` equal-width time buckets.
+fn downsample(points: &[TrendPoint], max_points: usize) -> Vec<TrendPoint> {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    let max_points = max_points.max(1);
+    if points.len() <= max_points {
+        return points.to_vec();
+    }
+    let width = ((last.t_us - first.t_us) / max_points as u64).max(1);
+    let mut out: Vec<TrendPoint> = Vec::with_capacity(max_points + 1);
+    let mut current_bucket = u64::MAX;
+    for p in points {
+        let bucket = (p.t_us - first.t_us) / width;
+        match out.last_mut() {
+            Some(last) if bucket == current_bucket => last.merge(p),
+            _ => {
+                out.push(*p);
+                current_bucket = bucket;
+            }
+        }
+    }
+    out
+}
+
+/// Remove a session's trends. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut trends) = SIGNAL_TRENDS.write() {
+        trends.remove(session_id);
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Set the signals recorded for a session. Series for signals that stay in
+/// the list keep their data; a changed resolution applies to new samples.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_signal_trends(session_id: String, mut config: TrendConfig) -> Result<(), String> {
+    if config.signals.iter().any(|s| s.name.is_empty()) {
+        return Err("Trend signals need a name".to_string());
+    }
+    config.max_points = config.max_points.max(MIN_MAX_POINTS);
+
+    let mut trends = SIGNAL_TRENDS
+        .write()
+        .map_err(|e| format!("Trend lock poisoned: {e}"))?;
+    let mut previous = trends.remove(&session_id);
+    let series = config
+        .signals
+        .iter()
+        .map(|signal| {
+            previous
+                .as_mut()
+                .and_then(|p| {
+                    let idx = p.config.signals.iter().position(|s| s == signal)?;
+                    Some(std::mem::take(&mut p.series[idx]))
+                })
+                .unwrap_or_default()
+        })
+        .collect();
+    tlog!(
+        "[SignalTrends:{}] Tracking {} signals (resolution {} ms, max {} points)",
+        session_id, config.signals.len(), config.resolution_ms, config.max_points
+    );
+    trends.insert(session_id, SessionTrends { config, series });
+    Ok(())
+}
+
+/// Stop recording trends for a session and drop the stored series.
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_signal_trends(session_id: String) {
+    clear_session(&session_id);
+}
+
+/// The session's trend config and per-signal point counts (None = no trends).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_signal_trends(session_id: String) -> Option<TrendState> {
+    let trends = SIGNAL_TRENDS.read().ok()?;
+    let session = trends.get(&session_id)?;
+    let series = session
+        .config
+        .signals
+        .iter()
+        .zip(&session.series)
+        .map(|(signal, series)| TrendSeriesInfo {
+            name: signal.name.clone(),
+            frame_id: signal.frame_id,
+            points: series.points.len(),
+            samples: series.samples,
+            first_us: series.points.first().map(|p| p.t_us),
+            last_us: series.last_us,
+        })
+        .collect();
+    Some(TrendState { config: session.config.clone(), series })
+}
+
+/// Points of one tracked signal between `start_us` and `end_us` (inclusive,
+/// open-ended when None), downsampled to at most `max_points` (default 1000).
+#[tauri::command(rename_all = "snake_case")]
+pub fn query_signal_trend(
+    session_id: String,
+    name: String,
+    frame_id: Option<u32>,
+    start_us: Option<u64>,
+    end_us: Option<u64>,
+    max_points: Option<usize>,
+) -> Result<Vec<TrendPoint>, String> {
+    let trends = SIGNAL_TRENDS
+        .read()
+        .map_err(|e| format!("Trend lock poisoned: {e}"))?;
+    let session = trends
+        .get(&session_id)
+        .ok_or_else(|| format!("No trends recorded on session '{}'", session_id))?;
+    let idx = session
+        .config
+        .signals
+        .iter()
+        .position(|s| s.name == name && (frame_id.is_none() || s.frame_id == frame_id))
+        .ok_or_else(|| format!("Signal '{}' is not tracked on session '{}'", name, session_id))?;
+
+    let points = &session.series[idx].points;
+    let from = start_us.map_or(0, |t| points.partition_point(|p| p.t_us < t));
+    let to = end_us.map_or(points.len(), |t| points.partition_point(|p| p.t_us <= t));
+    if from >= to {
+        return Ok(Vec::new());
+    }
+    Ok(downsample(&points[from..to], max_points.unwrap_or(DEFAULT_QUERY_POINTS)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(values: &[(u64, f64)], resolution_us: u64, max_points: usize) -> Series {
+        let mut s = Series::default();
+        for &(t, v) in values {
+            s.push(t, v, resolution_us, max_points);
+        }
+        s
+    }
+
+    #[test]
+    fn buckets_by_resolution() {
+        let s = series(&[(0, 1.0), (400, 3.0), (1_000, 10.0), (1_500, 20.0), (2_000, 5.0)], 1_000, 100);
+        assert_eq!(s.points.len(), 3);
+        assert_eq!(s.points[0].mean, 2.0);
+        assert_eq!((s.points[1].min, s.points[1].max, s.points[1].count), (10.0, 20.0, 2));
+        assert_eq!(s.samples, 5);
+
+        // Full resolution keeps every sample; a late sample folds into the newest point
+        let s = series(&[(0, 1.0), (10, 2.0), (5, 4.0)], 0, 100);
+        assert_eq!(s.points.len(), 2);
+        assert_eq!(s.points[1].max, 4.0);
+        assert_eq!(s.last_us, Some(10));
+    }
+
+    #[test]
+    fn compaction_keeps_recent_detail() {
+        let values: Vec<(u64, f64)> = (0..1_000).map(|i| (i * 10, i as f64)).collect();
+        let s = series(&values, 0, 100);
+        assert!(s.points.len() <= 100);
+        assert_eq!(s.points.iter().map(|p| p.count).sum::<u64>(), 1_000);
+        // The newest samples are still individual points, the oldest are merged
+        let last = s.points.last().unwrap();
+        assert_eq!((last.t_us, last.count), (9_990, 1));
+        assert!(s.points[0].count > 1);
+        assert_eq!(s.points[0].min, 0.0);
+    }
+
+    #[test]
+    fn downsample_preserves_extremes() {
+        let mut points: Vec<TrendPoint> = (0..1_000).map(|i| TrendPoint::sample(i * 1_000, 0.0)).collect();
+        points[500].max = 99.0;
+        points[500].min = 99.0;
+        let out = downsample(&points, 100);
+        assert!(out.len() <= 101);
+        assert_eq!(out.iter().map(|p| p.count).sum::<u64>(), 1_000);
+        assert_eq!(out.iter().map(|p| p.max).fold(f64::MIN, f64::max), 99.0);
+        assert_eq!(downsample(&points[..10], 100).len(), 10);
+    }
+}
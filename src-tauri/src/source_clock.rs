@@ -0,0 +1,462 @@
+// ui/src-tauri/src/source_clock.rs
+//
+// Clock offset correction for multi-source sessions. Each adapter stamps
+// frames with its own clock, so a GVRET TCP device and a local USB adapter
+// in one session disagree by an offset that also drifts. This shifts every
+// source's timestamps onto a common timeline before the frames are merged.
+//
+// Two corrections, applied in order:
+//   - manual: a fixed offset per profile, added to every timestamp.
+//   - auto-align: when both a reference source and another source see the
+//     same frame (ID and payload) on the wire, the difference between their
+//     timestamps is one sample of the other source's offset. Copies are
+//     paired on host arrival time within `pair_window_ms`, as in source
+//     dedup, so the window should be shorter than the frame's period. A
+//     least-squares line through the recent samples gives the offset and
+//     its drift, and each frame is corrected by that line at its own
+//     timestamp.
+//
+// The reference is the configured profile, or else the first source to
+// deliver the align frame. Runs in the IO broker's merge task ahead of
+// source dedup. Only meaningful with the `device` timestamp source; the
+// host and monotonic sources restamp frames on arrival anyway.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use crate::io::{now_us, FrameMessage};
+
+/// Pairing window used when the config doesn't set one.
+const DEFAULT_PAIR_WINDOW_MS: u64 = 20;
+
+/// Longest accepted pairing window.
+const MAX_PAIR_WINDOW_MS: u64 = 1000;
+
+/// Offset samples kept per source for the fit.
+const MAX_SAMPLES: usize = 256;
+
+/// Samples must span at least this long (source clock) before drift is
+/// estimated; over shorter spans jitter swamps the slope.
+const MIN_DRIFT_SPAN_US: f64 = 2_000_000.0;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Clock correction settings for a session.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClockAlignConfig {
+    /// Fixed offset (µs) added to each source's timestamps, by profile ID
+    #[serde(default)]
+    pub offsets_us: HashMap<String, i64>,
+    /// Frame ID every source sees, used to estimate offset and drift (None = manual offsets only)
+    #[serde(default)]
+    pub align_frame_id: Option<u32>,
+    /// Profile whose clock the others are aligned to (None = first to deliver the align frame)
+    #[serde(default)]
+    pub reference_profile_id: Option<String>,
+    /// How far apart (host arrival time) two copies of the align frame may be
+    #[serde(default = "default_pair_window_ms")]
+    pub pair_window_ms: u64,
+}
+
+fn default_pair_window_ms() -> u64 {
+    DEFAULT_PAIR_WINDOW_MS
+}
+
+impl Default for ClockAlignConfig {
+    fn default() -> Self {
+        ClockAlignConfig {
+            offsets_us: HashMap::new(),
+            align_frame_id: None,
+            reference_profile_id: None,
+            pair_window_ms: DEFAULT_PAIR_WINDOW_MS,
+        }
+    }
+}
+
+impl ClockAlignConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.pair_window_ms == 0 || self.pair_window_ms > MAX_PAIR_WINDOW_MS {
+            return Err(format!("Pairing window must be 1-{} ms", MAX_PAIR_WINDOW_MS));
+        }
+        Ok(())
+    }
+}
+
+/// Correction currently applied to one source.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SourceClockStats {
+    pub source_idx: usize,
+    pub profile_id: String,
+    /// Auto-align reference (its clock is the common timeline)
+    pub reference: bool,
+    pub manual_offset_us: i64,
+    /// Estimated offset to the reference at the latest sample (None before any pairs)
+    pub auto_offset_us: Option<f64>,
+    /// Estimated drift against the reference, parts per million
+    pub drift_ppm: Option<f64>,
+    /// Offset samples in the fit
+    pub samples: usize,
+}
+
+/// Clock correction settings and per-source estimates.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClockAlignStats {
+    pub config: ClockAlignConfig,
+    pub sources: Vec<SourceClockStats>,
+}
+
+/// A source's offset to the reference: `offset_us + drift * (t - at_us)`
+/// for a timestamp `t` on the source's clock.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Fit {
+    at_us: f64,
+    offset_us: f64,
+    drift: f64,
+}
+
+impl Fit {
+    fn offset_at(&self, timestamp_us: u64) -> f64 {
+        self.offset_us + self.drift * (timestamp_us as f64 - self.at_us)
+    }
+
+    /// Least-squares line through `(source timestamp, offset)` samples,
+    /// evaluated from the latest sample.
+    fn from_samples(samples: &VecDeque<(u64, i64)>) -> Option<Fit> {
+        let n = samples.len() as f64;
+        let &(last_t, _) = samples.back()?;
+        let mean_t = samples.iter().map(|&(t, _)| t as f64).sum::<f64>() / n;
+        let mean_o = samples.iter().map(|&(_, o)| o as f64).sum::<f64>() / n;
+        let span = last_t as f64 - samples.front().map(|&(t, _)| t as f64).unwrap_or(0.0);
+        let drift = if span >= MIN_DRIFT_SPAN_US {
+            let (mut sxy, mut sxx) = (0.0, 0.0);
+            for &(t, o) in samples {
+                let dx = t as f64 - mean_t;
+                sxy += dx * (o as f64 - mean_o);
+                sxx += dx * dx;
+            }
+            if sxx > 0.0 { sxy / sxx } else { 0.0 }
+        } else {
+            0.0
+        };
+        Some(Fit {
+            at_us: last_t as f64,
+            offset_us: mean_o + drift * (last_t as f64 - mean_t),
+            drift,
+        })
+    }
+}
+
+/// A copy of the align frame, waiting to be paired.
+struct Sighting {
+    source_idx: usize,
+    bytes: Vec<u8>,
+    timestamp_us: u64,
+    arrived_us: u64,
+    /// Sources already paired with this (reference) sighting, as a bitmask
+    paired: u64,
+}
+
+#[derive(Default)]
+struct SourceClock {
+    profile_id: String,
+    samples: VecDeque<(u64, i64)>,
+    fit: Option<Fit>,
+}
+
+struct SessionClock {
+    config: ClockAlignConfig,
+    reference: Option<usize>,
+    sightings: VecDeque<Sighting>,
+    sources: HashMap<usize, SourceClock>,
+}
+
+impl SessionClock {
+    fn new(config: ClockAlignConfig) -> Self {
+        SessionClock {
+            config,
+            reference: None,
+            sightings: VecDeque::new(),
+            sources: HashMap::new(),
+        }
+    }
+
+    /// Pair a copy of the align frame with the other side's copies.
+    fn sight(&mut self, source_idx: usize, frame: &FrameMessage, now_us: u64) {
+        let window_us = self.config.pair_window_ms * 1000;
+        while self
+            .sightings
+            .front()
+            .is_some_and(|s| now_us.saturating_sub(s.arrived_us) > window_us)
+        {
+            self.sightings.pop_front();
+        }
+
+        let reference = match self.reference {
+            Some(r) => r,
+            None => {
+                let is_configured = self.config.reference_profile_id.as_deref().is_none_or(|id| {
+                    self.sources.get(&source_idx).is_some_and(|s| s.profile_id == id)
+                });
+                if !is_configured {
+                    return;
+                }
+                tlog!("[SourceClock] Aligning to source {}", source_idx);
+                self.reference = Some(source_idx);
+                source_idx
+            }
+        };
+
+        let same_payload = |s: &Sighting| s.bytes == frame.bytes;
+        if source_idx == reference {
+            // Pair with the oldest copy each other source delivered first
+            let mut paired = 0u64;
+            let mut samples = Vec::new();
+            self.sightings.retain(|s| {
+                if s.source_idx == reference || !same_payload(s) || paired & bit(s.source_idx) != 0 {
+                    return true;
+                }
+                paired |= bit(s.source_idx);
+                samples.push((s.source_idx, s.timestamp_us));
+                false
+            });
+            for (other, timestamp_us) in samples {
+                self.add_sample(other, timestamp_us, frame.timestamp_us);
+            }
+            self.sightings.push_back(Sighting {
+                source_idx,
+                bytes: frame.bytes.clone(),
+                timestamp_us: frame.timestamp_us,
+                arrived_us: now_us,
+                paired,
+            });
+        } else {
+            let copy = self.sightings.iter_mut().find(|s| {
+                s.source_idx == reference && s.paired & bit(source_idx) == 0 && s.bytes == frame.bytes
+            });
+            match copy {
+                Some(s) => {
+                    s.paired |= bit(source_idx);
+                    let reference_us = s.timestamp_us;
+                    self.add_sample(source_idx, frame.timestamp_us, reference_us);
+                }
+                None => self.sightings.push_back(Sighting {
+                    source_idx,
+                    bytes: frame.bytes.clone(),
+                    timestamp_us: frame.timestamp_us,
+                    arrived_us: now_us,
+                    paired: 0,
+                }),
+            }
+        }
+    }
+
+    fn add_sample(&mut self, source_idx: usize, source_us: u64, reference_us: u64) {
+        let clock = self.sources.entry(source_idx).or_default();
+        if clock.samples.len() == MAX_SAMPLES {
+            clock.samples.pop_front();
+        }
+        clock.samples.push_back((source_us, reference_us as i64 - source_us as i64));
+        clock.fit = Fit::from_samples(&clock.samples);
+    }
+
+    /// Learn from and correct one source batch.
+    fn process(&mut self, source_idx: usize, profile_id: &str, frames: &mut [FrameMessage], now_us: u64) {
+        self.sources.entry(source_idx).or_default().profile_id = profile_id.to_string();
+        if let Some(align_id) = self.config.align_frame_id {
+            for frame in frames.iter().filter(|f| f.frame_id == align_id) {
+                self.sight(source_idx, frame, now_us);
+            }
+        }
+
+        let manual = self.config.offsets_us.get(profile_id).copied().unwrap_or(0);
+        let fit = self.sources.get(&source_idx).and_then(|c| c.fit);
+        if manual == 0 && fit.is_none() {
+            return;
+        }
+        for frame in frames.iter_mut() {
+            let auto = fit.map(|f| f.offset_at(frame.timestamp_us).round() as i64).unwrap_or(0);
+            frame.timestamp_us = (frame.timestamp_us as i64).saturating_add(manual + auto).max(0) as u64;
+        }
+    }
+
+    /// Forget a source that has ended; a new reference is picked if it was one.
+    fn remove_source(&mut self, source_idx: usize) {
+        self.sources.remove(&source_idx);
+        self.sightings.retain(|s| s.source_idx != source_idx);
+        if self.reference == Some(source_idx) {
+            self.reference = None;
+            for clock in self.sources.values_mut() {
+                clock.samples.clear();
+                clock.fit = None;
+            }
+        }
+    }
+
+    fn snapshot(&self) -> ClockAlignStats {
+        let mut sources: Vec<SourceClockStats> = self
+            .sources
+            .iter()
+            .map(|(&source_idx, clock)| SourceClockStats {
+                source_idx,
+                profile_id: clock.profile_id.clone(),
+                reference: self.reference == Some(source_idx),
+                manual_offset_us: self.config.offsets_us.get(&clock.profile_id).copied().unwrap_or(0),
+                auto_offset_us: clock.fit.map(|f| f.offset_us),
+                drift_ppm: clock.fit.map(|f| f.drift * 1_000_000.0),
+                samples: clock.samples.len(),
+            })
+            .collect();
+        sources.sort_by_key(|s| s.source_idx);
+        ClockAlignStats { config: self.config.clone(), sources }
+    }
+}
+
+/// Bit for a source in a `paired` mask; later sources share the top bit.
+fn bit(source_idx: usize) -> u64 {
+    1u64 << source_idx.min(63)
+}
+
+/// Map of session_id -> clock correction. Sessions without an entry pass through.
+static SOURCE_CLOCKS: Lazy<Mutex<HashMap<String, SessionClock>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Merge Task Hooks
+// ============================================================================
+
+/// Correct a source batch's timestamps. Called from the IO broker merge task.
+pub fn correct_frames(session_id: &str, source_idx: usize, profile_id: &str, frames: &mut [FrameMessage]) {
+    let Ok(mut sessions) = SOURCE_CLOCKS.lock() else { return };
+    if let Some(clock) = sessions.get_mut(session_id) {
+        clock.process(source_idx, profile_id, frames, now_us());
+    }
+}
+
+/// Called when a source ends so a replacement starts from a fresh estimate.
+pub fn source_ended(session_id: &str, source_idx: usize) {
+    if let Ok(mut sessions) = SOURCE_CLOCKS.lock() {
+        if let Some(clock) = sessions.get_mut(session_id) {
+            clock.remove_source(source_idx);
+        }
+    }
+}
+
+/// Remove a session's clock correction. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut sessions) = SOURCE_CLOCKS.lock() {
+        sessions.remove(session_id);
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Correct source timestamps in a multi-source session. Replaces any
+/// previous config and starts the estimates afresh.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_source_clock(session_id: String, config: ClockAlignConfig) -> Result<(), String> {
+    config.validate()?;
+    let mut sessions = SOURCE_CLOCKS
+        .lock()
+        .map_err(|e| format!("Source clock lock poisoned: {e}"))?;
+    tlog!(
+        "[SourceClock:{}] Enabled (offsets {:?}, align frame {:?})",
+        session_id, config.offsets_us, config.align_frame_id
+    );
+    sessions.insert(session_id, SessionClock::new(config));
+    Ok(())
+}
+
+/// Stop correcting; sources keep their own clocks again.
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_source_clock(session_id: String) {
+    clear_session(&session_id);
+}
+
+/// Clock settings and per-source offset/drift estimates (None = correction off).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_source_clock_stats(session_id: String) -> Option<ClockAlignStats> {
+    let sessions = SOURCE_CLOCKS.lock().ok()?;
+    Some(sessions.get(&session_id)?.snapshot())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(id: u32, data: &[u8], timestamp_us: u64) -> FrameMessage {
+        FrameMessage {
+            direction: Some("rx".to_string()),
+            ..test_frame(id, timestamp_us, data)
+        }
+    }
+
+    fn aligned(reference: Option<&str>) -> SessionClock {
+        SessionClock::new(ClockAlignConfig {
+            align_frame_id: Some(0x100),
+            reference_profile_id: reference.map(str::to_string),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn manual_offsets_shift_each_source() {
+        let mut offsets_us = HashMap::new();
+        offsets_us.insert("usb".to_string(), -1_500);
+        let mut clock = SessionClock::new(ClockAlignConfig { offsets_us, ..Default::default() });
+        let mut usb = vec![frame(0x200, &[1], 10_000), frame(0x200, &[2], 1_000)];
+        clock.process(1, "usb", &mut usb, 0);
+        assert_eq!((usb[0].timestamp_us, usb[1].timestamp_us), (8_500, 0));
+        let mut tcp = vec![frame(0x200, &[1], 10_000)];
+        clock.process(0, "tcp", &mut tcp, 0);
+        assert_eq!(tcp[0].timestamp_us, 10_000);
+    }
+
+    #[test]
+    fn auto_align_pairs_copies_and_tracks_drift() {
+        let mut clock = aligned(Some("tcp"));
+        // The USB adapter's clock runs 5 s behind and gains 100 µs per second
+        for i in 0..5u64 {
+            let ref_us = 1_000_000_000 + i * 1_000_000;
+            let usb_us = ref_us - 5_000_000 + i * 100;
+            let arrived = i * 1_000_000;
+            let payload = [i as u8];
+            // Either side may arrive first
+            if i % 2 == 0 {
+                clock.process(0, "tcp", &mut [frame(0x100, &payload, ref_us)], arrived);
+                clock.process(1, "usb", &mut [frame(0x100, &payload, usb_us)], arrived + 3_000);
+            } else {
+                clock.process(1, "usb", &mut [frame(0x100, &payload, usb_us)], arrived);
+                clock.process(0, "tcp", &mut [frame(0x100, &payload, ref_us)], arrived + 3_000);
+            }
+        }
+        let stats = clock.snapshot();
+        let usb = &stats.sources[1];
+        assert!(stats.sources[0].reference && !usb.reference);
+        assert_eq!(usb.samples, 5);
+        assert!((usb.drift_ppm.unwrap() + 100.0).abs() < 0.1);
+
+        // A later USB frame lands on the reference timeline
+        let mut later = [frame(0x200, &[9], 1_005_000_000 - 5_000_000 + 500)];
+        clock.process(1, "usb", &mut later, 5_000_000);
+        assert!((later[0].timestamp_us as i64 - 1_005_000_000).abs() <= 1);
+    }
+
+    #[test]
+    fn copies_outside_the_window_or_with_other_payloads_do_not_pair() {
+        let mut clock = aligned(None);
+        clock.process(0, "tcp", &mut [frame(0x100, &[1], 50_000)], 0);
+        clock.process(1, "usb", &mut [frame(0x100, &[2], 10_000)], 1_000);
+        clock.process(1, "usb", &mut [frame(0x100, &[1], 10_000)], 30_000);
+        assert_eq!(clock.snapshot().sources[1].samples, 0);
+        clock.remove_source(0);
+        assert!(clock.reference.is_none());
+        assert!(ClockAlignConfig { pair_window_ms: 0, ..Default::default() }.validate().is_err());
+    }
+}
@@ -0,0 +1,362 @@
+// ui/src-tauri/src/source_dedup.rs
+//
+// Redundant-capture deduplication — when two or more sources in a session
+// are mapped onto the same output bus (two adapters on one physical bus, for
+// redundancy), keep one copy of each frame and count, per source, the frames
+// the other adapters saw but it didn't. The missed counts measure each
+// adapter's capture fidelity.
+//
+// Runs in the IO broker's merge task as each source batch arrives, before
+// reactive transmit and the capture_store taps, so everything downstream sees
+// the merged stream. Adapters stamp frames with their own clocks, so copies
+// are paired on host arrival time: a frame from one source matches the oldest
+// pending frame on the same bus with the same ID and payload that arrived
+// within `window_ms` and hasn't already been matched by that source. The
+// first copy is forwarded straight away (no added latency); later copies are
+// dropped. When a pending frame ages out of the window, every other source
+// that has delivered traffic on that bus and didn't report it is charged a
+// miss. Periodic frames with an unchanging payload pair up in arrival order,
+// so the window should be shorter than the fastest such period.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use crate::io::{now_us, FrameMessage};
+
+/// Pairing window used when the config doesn't set one.
+const DEFAULT_WINDOW_MS: u64 = 20;
+
+/// Longest accepted pairing window.
+const MAX_WINDOW_MS: u64 = 1000;
+
+/// Sources are tracked in a u64 bitmask; later hot-added sources pass
+/// through undeduplicated.
+const MAX_SOURCES: usize = 64;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Deduplication settings for a session.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DedupConfig {
+    /// How far apart (host arrival time) two copies of a frame may be
+    #[serde(default = "default_window_ms")]
+    pub window_ms: u64,
+    /// Output buses to deduplicate (None = every bus)
+    #[serde(default)]
+    pub buses: Option<Vec<u8>>,
+}
+
+fn default_window_ms() -> u64 {
+    DEFAULT_WINDOW_MS
+}
+
+impl DedupConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.window_ms == 0 || self.window_ms > MAX_WINDOW_MS {
+            return Err(format!("Dedup window must be 1-{} ms", MAX_WINDOW_MS));
+        }
+        Ok(())
+    }
+
+    fn covers(&self, bus: u8) -> bool {
+        self.buses.as_ref().is_none_or(|b| b.contains(&bus))
+    }
+}
+
+/// Capture counts for one source.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SourceFidelity {
+    pub source_idx: usize,
+    pub profile_id: String,
+    /// Frames received on deduplicated buses
+    pub received: u64,
+    /// Frames this source delivered first (forwarded)
+    pub forwarded: u64,
+    /// Frames dropped as copies of another source's frame
+    pub duplicates: u64,
+    /// Frames other sources saw on a shared bus that this source didn't
+    pub missed: u64,
+    /// received / (received + missed), as a percentage (None before any traffic)
+    pub fidelity_pct: Option<f64>,
+}
+
+/// Deduplication settings and per-source counts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DedupStats {
+    pub config: DedupConfig,
+    /// Distinct frames forwarded on deduplicated buses
+    pub unique_frames: u64,
+    /// Frames still inside the pairing window
+    pub pending: usize,
+    pub sources: Vec<SourceFidelity>,
+}
+
+struct Pending {
+    frame_id: u32,
+    is_extended: bool,
+    bytes: Vec<u8>,
+    arrived_us: u64,
+    /// Sources that have delivered this frame
+    seen: u64,
+}
+
+#[derive(Default)]
+struct BusState {
+    pending: VecDeque<Pending>,
+    /// Sources that have delivered traffic on this bus
+    sources: u64,
+}
+
+struct SessionDedup {
+    config: DedupConfig,
+    buses: HashMap<u8, BusState>,
+    stats: HashMap<usize, SourceFidelity>,
+    unique_frames: u64,
+}
+
+impl SessionDedup {
+    fn new(config: DedupConfig) -> Self {
+        SessionDedup {
+            config,
+            buses: HashMap::new(),
+            stats: HashMap::new(),
+            unique_frames: 0,
+        }
+    }
+
+    /// Charge misses for pending frames that have left the window.
+    fn expire(&mut self, now_us: u64) {
+        let window_us = self.config.window_ms * 1000;
+        for bus in self.buses.values_mut() {
+            while bus
+                .pending
+                .front()
+                .is_some_and(|p| now_us.saturating_sub(p.arrived_us) > window_us)
+            {
+                let Some(p) = bus.pending.pop_front() else { break };
+                let missing = bus.sources & !p.seen;
+                for (idx, stats) in self.stats.iter_mut() {
+                    if missing & (1 << idx) != 0 {
+                        stats.missed += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Filter one source batch, returning the frames to forward.
+    fn process(&mut self, source_idx: usize, profile_id: &str, frames: Vec<FrameMessage>, now_us: u64) -> Vec<FrameMessage> {
+        if source_idx >= MAX_SOURCES {
+            return frames;
+        }
+        self.expire(now_us);
+
+        let bit = 1u64 << source_idx;
+        let stats = self.stats.entry(source_idx).or_insert_with(|| SourceFidelity {
+            source_idx,
+            profile_id: profile_id.to_string(),
+            ..Default::default()
+        });
+        let mut out = Vec::with_capacity(frames.len());
+        for frame in frames {
+            if !self.config.covers(frame.bus) {
+                out.push(frame);
+                continue;
+            }
+            stats.received += 1;
+            let bus = self.buses.entry(frame.bus).or_default();
+            bus.sources |= bit;
+            let copy = bus.pending.iter_mut().find(|p| {
+                p.seen & bit == 0
+                    && p.frame_id == frame.frame_id
+                    && p.is_extended == frame.is_extended
+                    && p.bytes == frame.bytes
+            });
+            match copy {
+                Some(p) => {
+                    p.seen |= bit;
+                    stats.duplicates += 1;
+                }
+                None => {
+                    bus.pending.push_back(Pending {
+                        frame_id: frame.frame_id,
+                        is_extended: frame.is_extended,
+                        bytes: frame.bytes.clone(),
+                        arrived_us: now_us,
+                        seen: bit,
+                    });
+                    stats.forwarded += 1;
+                    self.unique_frames += 1;
+                    out.push(frame);
+                }
+            }
+        }
+        out
+    }
+
+    /// Stop expecting frames from a source that has ended.
+    fn remove_source(&mut self, source_idx: usize) {
+        if source_idx >= MAX_SOURCES {
+            return;
+        }
+        for bus in self.buses.values_mut() {
+            bus.sources &= !(1u64 << source_idx);
+        }
+    }
+
+    fn snapshot(&self) -> DedupStats {
+        let mut sources: Vec<SourceFidelity> = self
+            .stats
+            .values()
+            .map(|s| {
+                let expected = s.received + s.missed;
+                SourceFidelity {
+                    fidelity_pct: (expected > 0).then(|| s.received as f64 * 100.0 / expected as f64),
+                    ..s.clone()
+                }
+            })
+            .collect();
+        sources.sort_by_key(|s| s.source_idx);
+        DedupStats {
+            config: self.config.clone(),
+            unique_frames: self.unique_frames,
+            pending: self.buses.values().map(|b| b.pending.len()).sum(),
+            sources,
+        }
+    }
+}
+
+/// Map of session_id -> dedup state. Sessions without an entry pass through.
+static SOURCE_DEDUP: Lazy<Mutex<HashMap<String, SessionDedup>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// ============================================================================
+// Merge Task Hooks
+// ============================================================================
+
+/// Deduplicate a source batch. Called from the IO broker merge task.
+pub fn filter_frames(session_id: &str, source_idx: usize, profile_id: &str, frames: Vec<FrameMessage>) -> Vec<FrameMessage> {
+    let Ok(mut sessions) = SOURCE_DEDUP.lock() else {
+        return frames;
+    };
+    match sessions.get_mut(session_id) {
+        Some(dedup) => dedup.process(source_idx, profile_id, frames, now_us()),
+        None => frames,
+    }
+}
+
+/// Called when a source ends so its absence isn't counted as misses.
+pub fn source_ended(session_id: &str, source_idx: usize) {
+    if let Ok(mut sessions) = SOURCE_DEDUP.lock() {
+        if let Some(dedup) = sessions.get_mut(session_id) {
+            dedup.remove_source(source_idx);
+        }
+    }
+}
+
+/// Remove a session's dedup state. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut sessions) = SOURCE_DEDUP.lock() {
+        sessions.remove(session_id);
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Deduplicate frames from sources sharing an output bus. Replaces any
+/// previous config and resets the counts.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_source_dedup(session_id: String, config: DedupConfig) -> Result<(), String> {
+    config.validate()?;
+    let mut sessions = SOURCE_DEDUP
+        .lock()
+        .map_err(|e| format!("Source dedup lock poisoned: {e}"))?;
+    tlog!(
+        "[SourceDedup:{}] Enabled (window {} ms, buses {:?})",
+        session_id, config.window_ms, config.buses
+    );
+    sessions.insert(session_id, SessionDedup::new(config));
+    Ok(())
+}
+
+/// Stop deduplicating; every source's frames are forwarded again.
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_source_dedup(session_id: String) {
+    clear_session(&session_id);
+}
+
+/// Dedup settings and per-source fidelity (None = dedup off).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_source_dedup_stats(session_id: String) -> Option<DedupStats> {
+    let mut sessions = SOURCE_DEDUP.lock().ok()?;
+    let dedup = sessions.get_mut(&session_id)?;
+    dedup.expire(now_us());
+    Some(dedup.snapshot())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(bus: u8, id: u32, data: &[u8]) -> FrameMessage {
+        FrameMessage { bus, direction: Some("rx".to_string()), ..test_frame(id, 0, data) }
+    }
+
+    fn dedup() -> SessionDedup {
+        SessionDedup::new(DedupConfig { window_ms: 20, buses: None })
+    }
+
+    #[test]
+    fn drops_copies_within_window() {
+        let mut d = dedup();
+        assert_eq!(d.process(0, "a", vec![frame(0, 0x100, &[1]), frame(0, 0x200, &[2])], 0).len(), 2);
+        // Second adapter delivers the same frames 5 ms later, plus one the first missed
+        let out = d.process(1, "b", vec![frame(0, 0x100, &[1]), frame(0, 0x200, &[2]), frame(0, 0x300, &[3])], 5_000);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].frame_id, 0x300);
+        // A changed payload is a new frame
+        assert_eq!(d.process(1, "b", vec![frame(0, 0x100, &[9])], 6_000).len(), 1);
+        assert_eq!(d.unique_frames, 4);
+    }
+
+    #[test]
+    fn counts_missed_frames_per_source() {
+        let mut d = dedup();
+        d.process(0, "a", vec![frame(0, 0x100, &[1])], 0);
+        d.process(1, "b", vec![frame(0, 0x100, &[1])], 1_000);
+        // Same periodic frame again: pairs in arrival order, b misses this one
+        d.process(0, "a", vec![frame(0, 0x100, &[1])], 10_000);
+        d.process(1, "b", vec![frame(0, 0x200, &[2])], 12_000);
+        d.expire(100_000);
+        let stats = d.snapshot();
+        let (a, b) = (&stats.sources[0], &stats.sources[1]);
+        assert_eq!((a.received, a.forwarded, a.missed), (2, 2, 1));
+        assert_eq!((b.received, b.duplicates, b.missed), (2, 1, 1));
+        assert_eq!(a.fidelity_pct.map(|p| p.round()), Some(67.0));
+        assert_eq!(stats.pending, 0);
+    }
+
+    #[test]
+    fn ended_sources_and_other_buses_are_not_charged() {
+        let mut d = SessionDedup::new(DedupConfig { window_ms: 20, buses: Some(vec![0]) });
+        d.process(0, "a", vec![frame(0, 0x100, &[1])], 0);
+        d.process(1, "b", vec![frame(0, 0x100, &[1])], 1_000);
+        // Bus 1 isn't deduplicated: both copies pass and aren't counted
+        assert_eq!(d.process(1, "b", vec![frame(1, 0x100, &[1])], 2_000).len(), 1);
+        assert_eq!(d.process(0, "a", vec![frame(1, 0x100, &[1])], 2_000).len(), 1);
+        d.remove_source(1);
+        d.process(0, "a", vec![frame(0, 0x200, &[2])], 30_000);
+        d.expire(100_000);
+        let stats = d.snapshot();
+        assert!(stats.sources.iter().all(|s| s.missed == 0));
+        assert_eq!(stats.sources[1].received, 1);
+        assert!(DedupConfig { window_ms: 0, buses: None }.validate().is_err());
+    }
+}
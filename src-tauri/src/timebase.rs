@@ -0,0 +1,394 @@
+// ui/src-tauri/src/timebase.rs
+//
+// Time subsystem: which clock a session's frame timestamps come from, and
+// how well the host clock is disciplined.
+//
+// Timestamp sources (per session, default `device`):
+//   - device:    keep what the driver produced — the hardware clock where
+//                the device reports one, otherwise the driver's receive time.
+//   - host:      host wall clock at arrival.
+//   - monotonic: wall clock at session start advanced by the monotonic
+//                clock, so NTP steps mid-capture can't reorder frames.
+// For host/monotonic a batch is rebased so its last frame lands on "now"
+// while the spacing the driver measured inside the batch is kept.
+//
+// Clock sync status comes from whatever the platform offers: PTP (pmc),
+// chrony or timedatectl on Linux, sntp on macOS, w32tm on Windows. The
+// source choice and the latest sync snapshot are recorded on a fresh
+// capture's metadata (`timing`) when a session takes ownership of it, so a
+// capture can later be judged for forensic timing confidence.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::io::{now_us, FrameClock, FrameMessage};
+
+/// Offsets under this are "good" (µs).
+const GOOD_OFFSET_US: f64 = 1_000.0;
+
+/// Offsets under this are "fair" (µs).
+const FAIR_OFFSET_US: f64 = 50_000.0;
+
+/// Largest intra-batch spacing kept when rebasing; anything wider is a
+/// device clock jump, not real spacing.
+const MAX_BATCH_SPAN_US: u64 = 1_000_000;
+
+/// How long a sync probe command may take.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A cached sync status older than this is refreshed before being recorded.
+const SYNC_STALE_US: u64 = 10 * 60 * 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampSource {
+    #[default]
+    Device,
+    Host,
+    Monotonic,
+}
+
+/// Host clock discipline as last measured.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+pub struct ClockSyncStatus {
+    /// "ptp" | "chrony" | "timedatectl" | "sntp" | "w32tm" | "unavailable"
+    pub method: String,
+    /// None when the method can't tell
+    pub synchronized: Option<bool>,
+    /// Host clock offset from the reference (µs), when measured
+    pub offset_us: Option<f64>,
+    /// Reference server / grandmaster, when reported
+    pub source: Option<String>,
+    /// "good" | "fair" | "poor" | "unknown"
+    pub quality: String,
+    pub checked_at_us: u64,
+}
+
+/// Timing provenance recorded on capture metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS))]
+pub struct CaptureTiming {
+    pub timestamp_source: TimestampSource,
+    pub clock_sync: Option<ClockSyncStatus>,
+}
+
+struct SessionClock {
+    source: TimestampSource,
+    anchor_wall_us: u64,
+    anchor: Instant,
+    last_us: u64,
+}
+
+static SESSION_CLOCKS: Lazy<Mutex<HashMap<String, SessionClock>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static LAST_SYNC: Lazy<Mutex<Option<ClockSyncStatus>>> = Lazy::new(|| Mutex::new(None));
+
+// ============================================================================
+// Timestamping
+// ============================================================================
+
+/// Source a session stamps with (device when never set).
+pub fn session_source(session_id: &str) -> TimestampSource {
+    SESSION_CLOCKS
+        .lock()
+        .unwrap()
+        .get(session_id)
+        .map(|c| c.source)
+        .unwrap_or_default()
+}
+
+/// Shift a batch so its latest frame sits at `now_us`, keeping the
+/// driver's intra-batch spacing (capped at MAX_BATCH_SPAN_US).
+fn rebase(frames: &mut [FrameMessage], now_us: u64) {
+    let Some(last) = frames.iter().map(|f| f.timestamp_us).max() else {
+        return;
+    };
+    for f in frames.iter_mut() {
+        let behind = last.saturating_sub(f.timestamp_us).min(MAX_BATCH_SPAN_US);
+        f.timestamp_us = now_us.saturating_sub(behind);
+    }
+}
+
+/// Apply the session's timestamp source to an incoming batch. Called by
+/// capture_store::append_frames_to_session before anything else sees it.
+pub fn stamp_frames(session_id: &str, frames: &mut [FrameMessage]) {
+    let mut clocks = SESSION_CLOCKS.lock().unwrap();
+    let Some(clock) = clocks.get_mut(session_id) else {
+        return;
+    };
+    let now = match clock.source {
+        TimestampSource::Device => return,
+        TimestampSource::Host => now_us(),
+        TimestampSource::Monotonic => clock.anchor_wall_us + clock.anchor.elapsed().as_micros() as u64,
+    };
+    rebase(frames, now);
+    // Whatever the driver measured, the timestamps are host time now
+    for f in frames.iter_mut() {
+        f.clock = Some(FrameClock::Host);
+    }
+    if clock.source == TimestampSource::Monotonic {
+        // Never step backwards across batches
+        for f in frames.iter_mut() {
+            f.timestamp_us = f.timestamp_us.max(clock.last_us);
+            clock.last_us = f.timestamp_us;
+        }
+    }
+}
+
+/// Forget a destroyed session's clock.
+pub fn clear_session(session_id: &str) {
+    SESSION_CLOCKS.lock().unwrap().remove(session_id);
+}
+
+/// Timing to record on a capture a session is about to fill.
+pub fn capture_timing(session_id: &str) -> CaptureTiming {
+    CaptureTiming {
+        timestamp_source: session_source(session_id),
+        clock_sync: LAST_SYNC.lock().unwrap().clone(),
+    }
+}
+
+// ============================================================================
+// Clock sync probing
+// ============================================================================
+
+fn quality(synchronized: Option<bool>, offset_us: Option<f64>) -> String {
+    match (synchronized, offset_us) {
+        (Some(false), _) => "poor",
+        (_, Some(o)) if o.abs() < GOOD_OFFSET_US => "good",
+        (_, Some(o)) if o.abs() < FAIR_OFFSET_US => "fair",
+        (_, Some(_)) => "poor",
+        _ => "unknown",
+    }
+    .to_string()
+}
+
+fn status(method: &str, synchronized: Option<bool>, offset_us: Option<f64>, source: Option<String>) -> ClockSyncStatus {
+    ClockSyncStatus {
+        method: method.to_string(),
+        synchronized,
+        offset_us,
+        source,
+        quality: quality(synchronized, offset_us),
+        checked_at_us: now_us(),
+    }
+}
+
+/// `chronyc -c tracking`: CSV with the reference name in field 1, the
+/// current system time offset (s) in field 4 and leap status last.
+#[cfg_attr(not(any(target_os = "linux", test)), allow(dead_code))]
+fn parse_chrony_tracking(out: &str) -> Option<ClockSyncStatus> {
+    let fields: Vec<&str> = out.trim().split(',').collect();
+    if fields.len() < 14 {
+        return None;
+    }
+    let offset_s: f64 = fields[4].parse().ok()?;
+    let synced = fields[13].trim() != "Not synchronised";
+    let source = Some(fields[1].to_string()).filter(|s| !s.is_empty());
+    Some(status("chrony", Some(synced), Some(offset_s * 1e6), source))
+}
+
+/// `pmc -u -b 0 'GET TIME_STATUS_NP'`: `master_offset <ns>` and `gmPresent true|false`.
+#[cfg_attr(not(any(target_os = "linux", test)), allow(dead_code))]
+fn parse_pmc_time_status(out: &str) -> Option<ClockSyncStatus> {
+    let mut offset_ns: Option<f64> = None;
+    let mut gm_present: Option<bool> = None;
+    let mut gm_identity: Option<String> = None;
+    for line in out.lines() {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next()) {
+            (Some("master_offset"), Some(v)) => offset_ns = v.parse().ok(),
+            (Some("gmPresent"), Some(v)) => gm_present = Some(v == "true"),
+            (Some("gmIdentity"), Some(v)) => gm_identity = Some(v.to_string()),
+            _ => {}
+        }
+    }
+    offset_ns?;
+    Some(status("ptp", gm_present, offset_ns.map(|ns| ns / 1000.0), gm_identity))
+}
+
+/// `sntp -t 2 <server>`: `+0.001234 +/- 0.012345 <server> <ip>`.
+#[cfg_attr(not(any(target_os = "macos", test)), allow(dead_code))]
+fn parse_sntp(out: &str) -> Option<ClockSyncStatus> {
+    let line = out.lines().rev().find(|l| l.contains("+/-"))?;
+    let mut parts = line.split_whitespace();
+    let offset_s: f64 = parts.next()?.parse().ok()?;
+    let source = parts.nth(2).map(str::to_string);
+    Some(status("sntp", None, Some(offset_s * 1e6), source))
+}
+
+/// `w32tm /query /status /verbose`: `Source:` and `Phase Offset: 0.0001234s`.
+#[cfg_attr(not(any(target_os = "windows", test)), allow(dead_code))]
+fn parse_w32tm(out: &str) -> Option<ClockSyncStatus> {
+    let field = |name: &str| {
+        out.lines()
+            .find_map(|l| l.trim().strip_prefix(name).map(|v| v.trim().to_string()))
+    };
+    let source = field("Source:")?;
+    let free_running = source.contains("Local CMOS Clock") || source.contains("Free-running");
+    let offset_us = field("Phase Offset:")
+        .and_then(|v| v.trim_end_matches('s').parse::<f64>().ok())
+        .map(|s| s * 1e6);
+    Some(status("w32tm", Some(!free_running), offset_us, Some(source)))
+}
+
+#[cfg_attr(target_os = "ios", allow(dead_code))]
+async fn run(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = tokio::time::timeout(
+        PROBE_TIMEOUT,
+        tokio::process::Command::new(cmd).args(args).output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+async fn probe_clock_sync() -> ClockSyncStatus {
+    #[cfg(target_os = "linux")]
+    {
+        let has_ptp = std::fs::read_dir("/sys/class/ptp")
+            .map(|mut d| d.next().is_some())
+            .unwrap_or(false);
+        if has_ptp {
+            if let Some(s) = run("pmc", &["-u", "-b", "0", "GET TIME_STATUS_NP"]).await.and_then(|o| parse_pmc_time_status(&o)) {
+                return s;
+            }
+        }
+        if let Some(s) = run("chronyc", &["-c", "tracking"]).await.and_then(|o| parse_chrony_tracking(&o)) {
+            return s;
+        }
+        if let Some(out) = run("timedatectl", &["show", "-p", "NTPSynchronized", "--value"]).await {
+            return status("timedatectl", Some(out.trim() == "yes"), None, None);
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(s) = run("sntp", &["-t", "2", "time.apple.com"]).await.and_then(|o| parse_sntp(&o)) {
+            return s;
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(s) = run("w32tm", &["/query", "/status", "/verbose"]).await.and_then(|o| parse_w32tm(&o)) {
+            return s;
+        }
+    }
+    status("unavailable", None, None, None)
+}
+
+/// Measure (or reuse a recent measurement of) the host clock sync status.
+pub async fn clock_sync_status(refresh: bool) -> ClockSyncStatus {
+    if !refresh {
+        if let Some(s) = LAST_SYNC.lock().unwrap().clone() {
+            if now_us().saturating_sub(s.checked_at_us) < SYNC_STALE_US {
+                return s;
+            }
+        }
+    }
+    let s = probe_clock_sync().await;
+    tlog!("[timebase] Clock sync via {}: quality {} (offset {:?} µs)", s.method, s.quality, s.offset_us);
+    *LAST_SYNC.lock().unwrap() = Some(s.clone());
+    s
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Host clock sync status (NTP/PTP offset where the platform exposes it).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_clock_sync_status(refresh: Option<bool>) -> Result<ClockSyncStatus, String> {
+    Ok(clock_sync_status(refresh.unwrap_or(false)).await)
+}
+
+/// Choose where a session's frame timestamps come from. Captures the
+/// session is currently filling get the new choice recorded.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_session_timestamp_source(
+    session_id: String,
+    source: TimestampSource,
+) -> Result<CaptureTiming, String> {
+    let clock_sync = Some(clock_sync_status(false).await);
+    {
+        let mut clocks = SESSION_CLOCKS.lock().unwrap();
+        let wall = now_us();
+        let clock = clocks.entry(session_id.clone()).or_insert_with(|| SessionClock {
+            source,
+            anchor_wall_us: wall,
+            anchor: Instant::now(),
+            last_us: 0,
+        });
+        if clock.source != source {
+            *clock = SessionClock { source, anchor_wall_us: wall, anchor: Instant::now(), last_us: 0 };
+        }
+    }
+    let timing = CaptureTiming { timestamp_source: source, clock_sync };
+    for capture_id in crate::capture_store::get_session_capture_ids(&session_id) {
+        crate::capture_store::set_capture_timing(&capture_id, timing.clone());
+    }
+    tlog!("[timebase] Session '{}' timestamps from {:?}", session_id, source);
+    Ok(timing)
+}
+
+/// Timestamp source a session currently uses.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_session_timestamp_source(session_id: String) -> Result<TimestampSource, String> {
+    Ok(session_source(&session_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(ts: u64) -> FrameMessage {
+        test_frame(0x100, ts, &[])
+    }
+
+    #[test]
+    fn rebase_keeps_spacing_and_caps_jumps() {
+        let mut frames = vec![frame(10), frame(510), frame(5_000_000_000)];
+        rebase(&mut frames, 2_000_000);
+        let ts: Vec<u64> = frames.iter().map(|f| f.timestamp_us).collect();
+        // The first two are > 1 s behind the last, so they clamp to now - 1 s
+        assert_eq!(ts, vec![1_000_000, 1_000_000, 2_000_000]);
+
+        let mut frames = vec![frame(1_000), frame(1_250)];
+        rebase(&mut frames, 9_000);
+        assert_eq!(frames[0].timestamp_us, 8_750);
+        assert_eq!(frames[1].timestamp_us, 9_000);
+    }
+
+    #[test]
+    fn sync_outputs_parse() {
+        let chrony = "C0A80101,192.168.1.1,3,1700000000.1,-0.000012345,0.000001,0.00002,-1.2,0.0,0.01,0.02,0.001,64.5,Normal\n";
+        let s = parse_chrony_tracking(chrony).unwrap();
+        assert_eq!(s.synchronized, Some(true));
+        assert!((s.offset_us.unwrap() + 12.345).abs() < 1e-6);
+        assert_eq!(s.quality, "good");
+
+        let pmc = "sending: GET TIME_STATUS_NP\n\tmaster_offset              -250\n\tgmPresent                  true\n\tgmIdentity                 001122.fffe.334455\n";
+        let s = parse_pmc_time_status(pmc).unwrap();
+        assert_eq!(s.offset_us, Some(-0.25));
+        assert_eq!(s.source.as_deref(), Some("001122.fffe.334455"));
+
+        let s = parse_sntp("+0.021500 +/- 0.004000 time.apple.com 17.253.66.45\n").unwrap();
+        assert_eq!(s.quality, "fair");
+        assert_eq!(s.source.as_deref(), Some("time.apple.com"));
+
+        let w32 = "Leap Indicator: 0(no warning)\nSource: Local CMOS Clock\nPhase Offset: 0.0000000s\n";
+        let s = parse_w32tm(w32).unwrap();
+        assert_eq!(s.synchronized, Some(false));
+        assert_eq!(s.quality, "poor");
+    }
+}
@@ -76,7 +76,10 @@ pub struct RepeatStartedEvent {
 
 /// Kinds that support CAN transmit (platform-dependent)
 #[cfg(not(target_os = "ios"))]
-const CAN_TRANSMIT_KINDS: [&str; 6] = ["slcan", "gvret_tcp", "gvret_usb", "socketcan", "gs_usb", "virtual"];
+const CAN_TRANSMIT_KINDS: [&str; 11] = [
+    "slcan", "custom_serial", "gvret_tcp", "gvret_usb", "socketcan", "gs_usb", "pcan", "kvaser", "vector_xl",
+    "j2534", "virtual",
+];
 #[cfg(target_os = "ios")]
 const CAN_TRANSMIT_KINDS: [&str; 2] = ["gvret_tcp", "virtual"];
 
@@ -137,8 +140,120 @@ fn get_capabilities_for_kind(kind: &str, profile: &IOProfile) -> WriterCapabilit
                 }
             }
         }
+        "pcan" => {
+            // Listen-only (the default) means no ACK and no transmit
+            let listen_only = profile
+                .connection
+                .get("listen_only")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            WriterCapabilities {
+                can_transmit_can: !listen_only && cfg!(any(target_os = "windows", target_os = "macos")),
+                can_transmit_serial: false,
+                supports_canfd: false,
+                supports_extended_id: true,
+                supports_rtr: true,
+                available_buses: vec![], // Single channel
+            }
+        }
+        "kvaser" => {
+            let listen_only = profile
+                .connection
+                .get("listen_only")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            // Buses are the channels enabled in the profile's interface list
+            let available_buses = profile
+                .connection
+                .get("interfaces")
+                .and_then(|v| v.as_array())
+                .map(|interfaces| {
+                    interfaces
+                        .iter()
+                        .filter(|i| i.get("enabled").and_then(|v| v.as_bool()).unwrap_or(true))
+                        .filter_map(|i| i.get("device_bus").and_then(|v| v.as_u64()).map(|b| b as u8))
+                        .collect()
+                })
+                .unwrap_or_default();
+            WriterCapabilities {
+                can_transmit_can: !listen_only && cfg!(any(target_os = "windows", target_os = "linux")),
+                can_transmit_serial: false,
+                supports_canfd: false,
+                supports_extended_id: true,
+                supports_rtr: true,
+                available_buses,
+            }
+        }
+        "vector_xl" => {
+            let listen_only = profile
+                .connection
+                .get("listen_only")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let enable_fd = profile
+                .connection
+                .get("enable_fd")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            // Buses are the channels enabled in the profile's interface list
+            let available_buses = profile
+                .connection
+                .get("interfaces")
+                .and_then(|v| v.as_array())
+                .map(|interfaces| {
+                    interfaces
+                        .iter()
+                        .filter(|i| i.get("enabled").and_then(|v| v.as_bool()).unwrap_or(true))
+                        .filter_map(|i| i.get("device_bus").and_then(|v| v.as_u64()).map(|b| b as u8))
+                        .collect()
+                })
+                .unwrap_or_default();
+            WriterCapabilities {
+                can_transmit_can: !listen_only && cfg!(target_os = "windows"),
+                can_transmit_serial: false,
+                supports_canfd: enable_fd,
+                supports_extended_id: true,
+                supports_rtr: true,
+                available_buses,
+            }
+        }
+        "custom_serial" => {
+            let listen_only = profile
+                .connection
+                .get("listen_only")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            WriterCapabilities {
+                can_transmit_can: !listen_only && cfg!(not(target_os = "ios")),
+                can_transmit_serial: false,
+                supports_canfd: false,
+                supports_extended_id: profile
+                    .connection
+                    .get("id_bytes")
+                    .and_then(|v| v.as_i64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+                    .unwrap_or(2)
+                    >= 4,
+                supports_rtr: false,
+                available_buses: vec![], // Single channel
+            }
+        }
+        "j2534" => {
+            let listen_only = profile
+                .connection
+                .get("listen_only")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            WriterCapabilities {
+                can_transmit_can: !listen_only && cfg!(target_os = "windows"),
+                can_transmit_serial: false,
+                supports_canfd: false,
+                supports_extended_id: true,
+                supports_rtr: false,
+                available_buses: vec![], // Single channel
+            }
+        }
         "gvret_tcp" | "gvret_usb" => WriterCapabilities {
-            can_transmit_can: true,
+            can_transmit_can: !crate::io::listen_only::from_profile(profile).unwrap_or(false),
             can_transmit_serial: false,
             supports_canfd: true,
             supports_extended_id: true,
@@ -146,7 +261,8 @@ fn get_capabilities_for_kind(kind: &str, profile: &IOProfile) -> WriterCapabilit
             available_buses: vec![0, 1, 2, 3, 4], // Bus 0-4 (device-dependent)
         },
         "socketcan" => WriterCapabilities {
-            can_transmit_can: cfg!(target_os = "linux"),
+            can_transmit_can: !crate::io::listen_only::from_profile(profile).unwrap_or(false)
+                && cfg!(target_os = "linux"),
             can_transmit_serial: false,
             supports_canfd: true,
             supports_extended_id: true,
@@ -271,6 +387,52 @@ pub async fn io_transmit_serial(
     Ok(result)
 }
 
+/// Write coils or registers on a Modbus device through an IO session.
+/// The source records each write it runs in the transmit history; writes
+/// refused before reaching it are recorded here.
+#[tauri::command]
+pub async fn io_transmit_modbus_write(
+    session_id: String,
+    write: crate::io::ModbusWrite,
+) -> Result<crate::io::TransmitResult, String> {
+    let result = io::transmit_modbus_write(&session_id, &write).await?;
+    if !result.success {
+        crate::transmit_history::write_entry(
+            &session_id, "modbus",
+            Some(write.address as i64),
+            Some(write.values.len() as i64),
+            &write.data_bytes(),
+            write.unit_id as i64,
+            false, false,
+            false,
+            result.error.as_deref(),
+        );
+        crate::ws::dispatch::send_transmit_updated(crate::transmit_history::count());
+    }
+    Ok(result)
+}
+
+/// Send a UDS request through a DoIP gateway session. The response arrives
+/// as a "doip" frame linked to the request's echo.
+#[tauri::command]
+pub async fn io_transmit_diagnostic(
+    session_id: String,
+    request: crate::io::doip::DiagnosticRequest,
+) -> Result<crate::io::TransmitResult, String> {
+    let result = io::transmit_diagnostic(&session_id, &request).await?;
+    crate::transmit_history::write_entry(
+        &session_id, "doip",
+        request.target_address.map(i64::from),
+        Some(request.data.len() as i64),
+        &request.data,
+        0, false, false,
+        result.success,
+        result.error.as_deref(),
+    );
+    crate::ws::dispatch::send_transmit_updated(crate::transmit_history::count());
+    Ok(result)
+}
+
 /// Get IO session capabilities (includes transmit capabilities)
 #[tauri::command]
 pub async fn get_io_session_capabilities(session_id: String) -> Result<Option<IOCapabilities>, String> {
@@ -392,7 +554,128 @@ struct IoRepeatTask {
 static IO_REPEAT_TASKS: Lazy<tokio::sync::Mutex<HashMap<String, IoRepeatTask>>> =
     Lazy::new(|| tokio::sync::Mutex::new(HashMap::new()));
 
+/// Write a repeated CAN frame's transmit result to SQLite and throttle the
+/// UI notification. Returns the error, if any.
+fn record_can_transmit(
+    session_id: &str,
+    frame: &CanTransmitFrame,
+    result: &Result<crate::io::TransmitResult, String>,
+    throttle: &mut SignalThrottle,
+) -> Option<String> {
+    let (success, error) = match result {
+        Ok(r) => (r.success, r.error.clone()),
+        Err(e) => (false, Some(e.clone())),
+    };
+    crate::transmit_history::write_entry(
+        session_id, "can",
+        Some(frame.frame_id as i64),
+        Some(frame.data.len() as i64),
+        &frame.data,
+        frame.bus as i64,
+        frame.is_extended,
+        frame.is_fd,
+        success,
+        error.as_deref(),
+    );
+    if throttle.should_signal("transmit-updated") {
+        crate::ws::dispatch::send_transmit_updated(crate::transmit_history::count());
+    }
+    error
+}
+
+/// Tell the UI a repeat ended on its own.
+fn notify_repeat_stopped(queue_id: &str, reason: String) {
+    tlog!(
+        "[io_transmit] Stopping repeat for '{}': {}",
+        queue_id, reason
+    );
+    crate::ws::dispatch::send_repeat_stopped(&RepeatStoppedEvent {
+        queue_id: queue_id.to_string(),
+        reason,
+    });
+    crate::ws::dispatch::send_transmit_updated(crate::transmit_history::count());
+}
+
+/// Repeat a frame from a timer loop here, one session transmit per tick.
+fn spawn_timed_repeat(
+    session_id: String,
+    queue_id: String,
+    frame: CanTransmitFrame,
+    interval_ms: u64,
+    cancel_flag: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let mut throttle = SignalThrottle::new();
+
+        // Fire immediately, then once per interval. Cadence handles the cancel
+        // check; subsequent ticks aren't skewed by the first transmit's latency.
+        let mut cadence = Cadence::new(interval_ms, cancel_flag, None);
+        while cadence.next().await.is_some() {
+            let (result, should_stop) = do_transmit(&session_id, &frame).await;
+            let error = record_can_transmit(&session_id, &frame, &result, &mut throttle);
+
+            // Stop on permanent errors (device gone, session invalid)
+            if should_stop {
+                notify_repeat_stopped(&queue_id, error.unwrap_or_else(|| "Permanent error".to_string()));
+                break;
+            }
+        }
+    })
+}
+
+/// Hand a repeat to SocketCAN's broadcast manager when the frame's bus is
+/// routed to a SocketCAN source and nothing needs to see each copy first
+/// (transmit validation rejecting it). The kernel then does the timing; the
+/// returned task only keeps the job alive, records history at the same
+/// cadence and ends the job when the session stops.
+#[cfg(target_os = "linux")]
+async fn spawn_kernel_repeat(
+    session_id: &str,
+    queue_id: &str,
+    frame: &CanTransmitFrame,
+    interval_ms: u64,
+    cancel_flag: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) -> Option<tauri::async_runtime::JoinHandle<()>> {
+    let interface = io::socketcan_transmit_interface(session_id, frame.bus).await?;
+    if crate::transmit_validation::validate(session_id, frame).is_some_and(|v| v.reject) {
+        return None;
+    }
+    let repeat = match io::BcmRepeat::start(&interface, frame, interval_ms) {
+        Ok(repeat) => repeat,
+        Err(e) => {
+            tlog!("[io_transmit] {}; falling back to timed repeat", e);
+            return None;
+        }
+    };
+    tlog!(
+        "[io_transmit] Repeat '{}' scheduled by the kernel on {} every {}ms",
+        queue_id, interface, interval_ms
+    );
+
+    let session_id = session_id.to_string();
+    let queue_id = queue_id.to_string();
+    let frame = frame.clone();
+    Some(tauri::async_runtime::spawn(async move {
+        // Dropping the repeat deletes the kernel's transmit job
+        let _repeat = repeat;
+        let mut throttle = SignalThrottle::new();
+        let mut cadence = Cadence::new(interval_ms, cancel_flag, None);
+        while cadence.next().await.is_some() {
+            if !matches!(io::get_session_state(&session_id).await, Some(io::IOState::Running)) {
+                notify_repeat_stopped(&queue_id, "Session is no longer running".to_string());
+                break;
+            }
+            let sent = Ok(crate::io::TransmitResult::success());
+            record_can_transmit(&session_id, &frame, &sent, &mut throttle);
+        }
+    }))
+}
+
 /// Start repeat transmission for a CAN frame through an IO session
+///
+/// On Linux, frames for a SocketCAN bus are repeated by the kernel's
+/// broadcast manager (see `spawn_kernel_repeat`); everything else runs on a
+/// timer loop here.
 #[tauri::command]
 pub async fn io_start_repeat_transmit(
     session_id: String,
@@ -408,59 +691,16 @@ pub async fn io_start_repeat_transmit(
     io_stop_repeat_transmit(queue_id.clone()).await?;
 
     let cancel_flag = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
-    let cancel_flag_clone = cancel_flag.clone();
-    let session_id_clone = session_id.clone();
-    let queue_id_for_task = queue_id.clone();
 
-    let handle = tauri::async_runtime::spawn(async move {
-        let mut throttle = SignalThrottle::new();
+    #[cfg(target_os = "linux")]
+    let kernel_handle = spawn_kernel_repeat(&session_id, &queue_id, &frame, interval_ms, cancel_flag.clone()).await;
+    #[cfg(not(target_os = "linux"))]
+    let kernel_handle = None;
 
-        // Write this frame's transmit result to SQLite and throttle the UI notification.
-        let write_and_notify = |result: &Result<crate::io::TransmitResult, String>, throttle: &mut SignalThrottle| -> (bool, Option<String>) {
-            let (success, error) = match result {
-                Ok(r) => (r.success, r.error.clone()),
-                Err(e) => (false, Some(e.clone())),
-            };
-            crate::transmit_history::write_entry(
-                &session_id_clone, "can",
-                Some(frame.frame_id as i64),
-                Some(frame.data.len() as i64),
-                &frame.data,
-                frame.bus as i64,
-                frame.is_extended,
-                frame.is_fd,
-                success,
-                error.as_deref(),
-            );
-            if throttle.should_signal("transmit-updated") {
-                crate::ws::dispatch::send_transmit_updated(crate::transmit_history::count());
-            }
-            (success, error)
-        };
-
-        // Fire immediately, then once per interval. Cadence handles the cancel
-        // check; subsequent ticks aren't skewed by the first transmit's latency.
-        let mut cadence = Cadence::new(interval_ms, cancel_flag_clone, None);
-        while cadence.next().await.is_some() {
-            let (result, should_stop) = do_transmit(&session_id_clone, &frame).await;
-            let (_, error) = write_and_notify(&result, &mut throttle);
-
-            // Stop on permanent errors (device gone, session invalid)
-            if should_stop {
-                let reason = error.unwrap_or_else(|| "Permanent error".to_string());
-                tlog!(
-                    "[io_transmit] Stopping repeat for '{}' due to permanent error: {}",
-                    queue_id_for_task, reason
-                );
-                crate::ws::dispatch::send_repeat_stopped(&RepeatStoppedEvent {
-                    queue_id: queue_id_for_task.clone(),
-                    reason,
-                });
-                crate::ws::dispatch::send_transmit_updated(crate::transmit_history::count());
-                break;
-            }
-        }
-    });
+    let handle = match kernel_handle {
+        Some(handle) => handle,
+        None => spawn_timed_repeat(session_id, queue_id.clone(), frame, interval_ms, cancel_flag.clone()),
+    };
 
     // Store the task
     let mut tasks = IO_REPEAT_TASKS.lock().await;
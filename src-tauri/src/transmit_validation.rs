@@ -0,0 +1,479 @@
+// ui/src-tauri/src/transmit_validation.rs
+//
+// Catalog-driven transmit validation. A session can be given a validation
+// policy; every CAN frame sent through io::session_transmit is then checked
+// against the catalog definition for its ID before it reaches the bus:
+//
+//   - length:        payload length matches the catalog frame `length`
+//   - ranges:        each decoded signal's scaled value is within `min`/`max`
+//   - reserved bits: no bit is set that no signal (or mux selector) covers
+//   - counters:      configured counter signals advance by `step` per send
+//
+// In `warn` mode the frame is sent and the issues ride along on the
+// TransmitResult; in `reject` mode the frame is dropped and the result
+// carries the issues as a structured error.
+//
+// Reserved bits are found by decoding: a set bit is "used" when clearing it
+// changes a decoded signal or selector value. That follows the frame's byte
+// order and active mux case without re-implementing the bit layout here.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use wiretap_catalog::model::{Frame, Mux, Signal};
+use wiretap_catalog::Catalog;
+
+use crate::io::CanTransmitFrame;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationMode {
+    /// Send the frame, report issues alongside the result
+    #[default]
+    Warn,
+    /// Drop frames that have issues
+    Reject,
+}
+
+/// A counter signal that must advance on every transmit of its frame.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CounterRule {
+    pub frame_id: u32,
+    /// Signal name in the catalog frame
+    pub signal: String,
+    /// Expected increment per transmit (default 1)
+    #[serde(default = "default_counter_step")]
+    pub step: u64,
+    /// Counter wraps at this value (e.g. 16 for a 4-bit rolling counter)
+    #[serde(default)]
+    pub modulo: Option<u64>,
+}
+
+fn default_counter_step() -> u64 {
+    1
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransmitValidationConfig {
+    #[serde(default)]
+    pub mode: ValidationMode,
+    /// Catalog to validate against. When unset, the catalog attached to the
+    /// session for live decode is used.
+    #[serde(default)]
+    pub catalog_path: Option<String>,
+    #[serde(default = "default_true")]
+    pub check_length: bool,
+    #[serde(default = "default_true")]
+    pub check_ranges: bool,
+    #[serde(default = "default_true")]
+    pub check_reserved_bits: bool,
+    /// Treat frame IDs missing from the catalog as an issue
+    #[serde(default)]
+    pub require_catalogued: bool,
+    #[serde(default)]
+    pub counters: Vec<CounterRule>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    NoCatalog,
+    UnknownFrame,
+    Length,
+    Range,
+    ReservedBits,
+    Counter,
+}
+
+/// One problem found with a frame about to be transmitted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub kind: IssueKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal: Option<String>,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn new(kind: IssueKind, signal: Option<&str>, message: String) -> Self {
+        Self { kind, signal: signal.map(str::to_string), message }
+    }
+}
+
+/// Outcome of validating one frame.
+pub struct Verdict {
+    pub issues: Vec<ValidationIssue>,
+    /// True when the policy is `reject` and there are issues
+    pub reject: bool,
+}
+
+// ============================================================================
+// State
+// ============================================================================
+
+struct SessionValidation {
+    config: TransmitValidationConfig,
+    /// Catalog loaded from `catalog_path`; None falls back to the attached one
+    catalog: Option<Arc<Catalog>>,
+    /// (frame_id, signal) -> last transmitted raw counter value
+    last_counters: HashMap<(u32, String), u64>,
+}
+
+static VALIDATIONS: Lazy<RwLock<HashMap<String, SessionValidation>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+// ============================================================================
+// Checks
+// ============================================================================
+
+fn find_frame<'a>(catalog: &'a Catalog, frame: &CanTransmitFrame) -> Option<&'a Frame> {
+    catalog.frames.iter().find(|f| {
+        f.frame_id == frame.frame_id && f.is_extended.is_none_or(|ext| ext == frame.is_extended)
+    })
+}
+
+fn find_signal<'a>(signals: &'a [Signal], mux: Option<&'a Mux>, name: &str) -> Option<&'a Signal> {
+    if let Some(s) = signals.iter().find(|s| s.name.as_deref() == Some(name)) {
+        return Some(s);
+    }
+    mux?.cases
+        .values()
+        .find_map(|case| find_signal(&case.signals, case.mux.as_ref(), name))
+}
+
+/// Decoded raw values (signals then selectors) — the fingerprint used to tell
+/// whether a bit is covered by the frame layout.
+fn decode_fingerprint(catalog: &Catalog, frame_id: u32, data: &[u8]) -> Vec<f64> {
+    let Some(decoded) = wiretap_catalog::decode::decode_by_id(catalog, frame_id, data) else {
+        return Vec::new();
+    };
+    decoded
+        .signals
+        .iter()
+        .map(|s| s.value as f64)
+        .chain(decoded.selectors.iter().map(|s| s.value as f64))
+        .collect()
+}
+
+/// Bits set in `data` that no signal or selector decodes, as (byte, bit).
+fn reserved_bits_set(catalog: &Catalog, frame_id: u32, data: &[u8]) -> Vec<(usize, u8)> {
+    let baseline = decode_fingerprint(catalog, frame_id, data);
+    let mut probe = data.to_vec();
+    let mut out = Vec::new();
+    for byte in 0..data.len() {
+        for bit in 0..8u8 {
+            let mask = 1u8 << bit;
+            if data[byte] & mask == 0 {
+                continue;
+            }
+            probe[byte] &= !mask;
+            if decode_fingerprint(catalog, frame_id, &probe) == baseline {
+                out.push((byte, bit));
+            }
+            probe[byte] |= mask;
+        }
+    }
+    out
+}
+
+fn counter_value(catalog: &Catalog, frame_id: u32, data: &[u8], signal: &str) -> Option<u64> {
+    let decoded = wiretap_catalog::decode::decode_by_id(catalog, frame_id, data)?;
+    decoded
+        .signals
+        .iter()
+        .find(|s| s.name == signal)
+        .map(|s| s.value as u64)
+}
+
+/// Run the configured checks for one frame. `last_counters` is read, not
+/// updated — counters advance only once the frame is actually sent.
+fn check_frame(
+    config: &TransmitValidationConfig,
+    catalog: &Catalog,
+    frame: &CanTransmitFrame,
+    last_counters: &HashMap<(u32, String), u64>,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let Some(def) = find_frame(catalog, frame) else {
+        if config.require_catalogued {
+            issues.push(ValidationIssue::new(
+                IssueKind::UnknownFrame,
+                None,
+                format!("Frame 0x{:X} is not defined in the catalog", frame.frame_id),
+            ));
+        }
+        return issues;
+    };
+
+    if config.check_length {
+        if let Some(expected) = def.length.map(|l| l as usize) {
+            if frame.data.len() != expected {
+                issues.push(ValidationIssue::new(
+                    IssueKind::Length,
+                    None,
+                    format!("Payload is {} bytes, catalog expects {}", frame.data.len(), expected),
+                ));
+            }
+        }
+    }
+
+    if config.check_ranges {
+        if let Some(decoded) = wiretap_catalog::decode::decode_by_id(catalog, frame.frame_id, &frame.data) {
+            for s in &decoded.signals {
+                let Some(sig) = find_signal(&def.signals, def.mux.as_ref(), &s.name) else {
+                    continue;
+                };
+                let scaled = s.scaled as f64;
+                let below = sig.min.is_some_and(|min| scaled < min);
+                let above = sig.max.is_some_and(|max| scaled > max);
+                if below || above {
+                    issues.push(ValidationIssue::new(
+                        IssueKind::Range,
+                        Some(&s.name),
+                        format!(
+                            "{} = {} is outside [{}, {}]",
+                            s.name,
+                            scaled,
+                            sig.min.map(|v| v.to_string()).unwrap_or_else(|| "-inf".into()),
+                            sig.max.map(|v| v.to_string()).unwrap_or_else(|| "inf".into()),
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    if config.check_reserved_bits {
+        let bits = reserved_bits_set(catalog, frame.frame_id, &frame.data);
+        if !bits.is_empty() {
+            let list: Vec<String> = bits.iter().map(|(byte, bit)| format!("{}.{}", byte, bit)).collect();
+            issues.push(ValidationIssue::new(
+                IssueKind::ReservedBits,
+                None,
+                format!("Reserved bits set (byte.bit): {}", list.join(", ")),
+            ));
+        }
+    }
+
+    for rule in config.counters.iter().filter(|r| r.frame_id == frame.frame_id) {
+        let Some(value) = counter_value(catalog, frame.frame_id, &frame.data, &rule.signal) else {
+            issues.push(ValidationIssue::new(
+                IssueKind::Counter,
+                Some(&rule.signal),
+                format!("Counter signal '{}' not found in frame", rule.signal),
+            ));
+            continue;
+        };
+        let Some(&last) = last_counters.get(&(rule.frame_id, rule.signal.clone())) else {
+            continue;
+        };
+        let expected = match rule.modulo {
+            Some(m) if m > 0 => (last + rule.step) % m,
+            _ => last.wrapping_add(rule.step),
+        };
+        if value != expected {
+            issues.push(ValidationIssue::new(
+                IssueKind::Counter,
+                Some(&rule.signal),
+                format!("{} = {}, expected {} (previous {})", rule.signal, value, expected, last),
+            ));
+        }
+    }
+
+    issues
+}
+
+// ============================================================================
+// Transmit hook
+// ============================================================================
+
+/// Validate a frame about to be sent on `session_id`. Returns None when the
+/// session has no validation policy.
+pub fn validate(session_id: &str, frame: &CanTransmitFrame) -> Option<Verdict> {
+    let validations = VALIDATIONS.read().ok()?;
+    let v = validations.get(session_id)?;
+    let catalog = v
+        .catalog
+        .clone()
+        .or_else(|| crate::ws::dispatch::attached_catalog(session_id));
+    let issues = match catalog {
+        Some(catalog) => check_frame(&v.config, &catalog, frame, &v.last_counters),
+        None => vec![ValidationIssue::new(
+            IssueKind::NoCatalog,
+            None,
+            "No catalog to validate against (set catalog_path or attach one)".to_string(),
+        )],
+    };
+    let reject = v.config.mode == ValidationMode::Reject && !issues.is_empty();
+    Some(Verdict { issues, reject })
+}
+
+/// Remember counter values of a frame that was sent, so the next transmit is
+/// checked against them.
+pub fn record_sent(session_id: &str, frame: &CanTransmitFrame) {
+    let Ok(mut validations) = VALIDATIONS.write() else {
+        return;
+    };
+    let Some(v) = validations.get_mut(session_id) else {
+        return;
+    };
+    if !v.config.counters.iter().any(|r| r.frame_id == frame.frame_id) {
+        return;
+    }
+    let Some(catalog) = v
+        .catalog
+        .clone()
+        .or_else(|| crate::ws::dispatch::attached_catalog(session_id))
+    else {
+        return;
+    };
+    for rule in v.config.counters.iter().filter(|r| r.frame_id == frame.frame_id) {
+        if let Some(value) = counter_value(&catalog, frame.frame_id, &frame.data, &rule.signal) {
+            v.last_counters.insert((rule.frame_id, rule.signal.clone()), value);
+        }
+    }
+}
+
+/// Drop a session's validation policy. Called when the session is destroyed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut validations) = VALIDATIONS.write() {
+        validations.remove(session_id);
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+/// Enable transmit validation on a session (replaces any existing policy).
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_transmit_validation(
+    session_id: String,
+    config: TransmitValidationConfig,
+) -> Result<(), String> {
+    let catalog = match &config.catalog_path {
+        Some(path) => {
+            let toml = crate::catalog::open_catalog(path.clone()).await?;
+            Some(Arc::new(Catalog::parse(&toml).map_err(|e| e.to_string())?))
+        }
+        None => None,
+    };
+    tlog!(
+        "[transmit_validation] Session '{}': {:?} mode, {} counter rule(s)",
+        session_id,
+        config.mode,
+        config.counters.len()
+    );
+    let mut validations = VALIDATIONS.write().map_err(|e| e.to_string())?;
+    validations.insert(
+        session_id,
+        SessionValidation { config, catalog, last_counters: HashMap::new() },
+    );
+    Ok(())
+}
+
+/// Disable transmit validation on a session.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn clear_transmit_validation(session_id: String) -> Result<(), String> {
+    clear_session(&session_id);
+    Ok(())
+}
+
+/// The session's validation policy, if any.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_transmit_validation(session_id: String) -> Result<Option<TransmitValidationConfig>, String> {
+    let validations = VALIDATIONS.read().map_err(|e| e.to_string())?;
+    Ok(validations.get(&session_id).map(|v| v.config.clone()))
+}
+
+/// Check a frame against the session's policy without sending it.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn validate_transmit_frame(
+    session_id: String,
+    frame: CanTransmitFrame,
+) -> Result<Vec<ValidationIssue>, String> {
+    Ok(validate(&session_id, &frame).map(|v| v.issues).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"
+[meta]
+name = "Test"
+version = 4
+
+[meta.can]
+default_byte_order = "little"
+
+[frame.can."0x123"]
+length = 4
+
+[[frame.can."0x123".signals]]
+name = "Speed"
+start_bit = 0
+bit_length = 16
+factor = 0.1
+min = 0
+max = 250
+
+[[frame.can."0x123".signals]]
+name = "Counter"
+start_bit = 24
+bit_length = 4
+"#;
+
+    fn frame(data: &[u8]) -> CanTransmitFrame {
+        CanTransmitFrame {
+            frame_id: 0x123,
+            data: data.to_vec(),
+            bus: 0,
+            is_extended: false,
+            is_fd: false,
+            is_brs: false,
+            is_rtr: false,
+        }
+    }
+
+    fn config() -> TransmitValidationConfig {
+        serde_json::from_value(serde_json::json!({
+            "mode": "reject",
+            "counters": [{ "frame_id": 0x123, "signal": "Counter", "modulo": 16 }]
+        }))
+        .unwrap()
+    }
+
+    fn kinds(issues: &[ValidationIssue]) -> Vec<IssueKind> {
+        issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn valid_frame_has_no_issues() {
+        let catalog = Catalog::parse(CATALOG).unwrap();
+        let issues = check_frame(&config(), &catalog, &frame(&[0xE8, 0x03, 0x00, 0x01]), &HashMap::new());
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn flags_length_range_reserved_and_counter() {
+        let catalog = Catalog::parse(CATALOG).unwrap();
+        // 3000 * 0.1 = 300 > 250; byte 2 is unused; counter repeats
+        let mut last = HashMap::new();
+        last.insert((0x123, "Counter".to_string()), 1);
+        let issues = check_frame(&config(), &catalog, &frame(&[0xB8, 0x0B, 0x01, 0x01]), &last);
+        assert_eq!(kinds(&issues), vec![IssueKind::Range, IssueKind::ReservedBits, IssueKind::Counter]);
+
+        let issues = check_frame(&config(), &catalog, &frame(&[0x00, 0x00, 0x00, 0x00, 0x00]), &HashMap::new());
+        assert_eq!(kinds(&issues), vec![IssueKind::Length]);
+    }
+}
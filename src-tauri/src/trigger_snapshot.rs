@@ -0,0 +1,336 @@
+// ui/src-tauri/src/trigger_snapshot.rs
+//
+// Trigger snapshots — "when frame X is seen, keep the N seconds around it".
+// Unlike conditional capture (capture_trigger.rs), which decides what the
+// session capture stores, snapshot triggers leave the session capture alone
+// and copy a window of it into a new orphaned capture each time a rule
+// fires. Paired with a ring-limited session capture this gives a
+// long-running monitor that only keeps the moments that matter.
+//
+// Rules are matched in capture_store::append_frames_to_session against the
+// frames written to the session capture. A match opens a pending snapshot;
+// once a frame at or past trigger + post-trigger window has been written,
+// the range [trigger - pre, trigger + post] is copied off-thread with
+// capture_store::split_capture and a `trigger-fired` event is emitted.
+// Windows are measured in frame time, so rules behave the same on replayed
+// captures. Pre-trigger frames already dropped by the session capture's
+// ring limit are simply missing from the snapshot.
+
+use chrono::{TimeZone, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex as StdMutex;
+
+use crate::capture_store::{self, CaptureMetadata};
+use crate::capture_trigger::FramePattern;
+use crate::io::FrameMessage;
+
+/// Window kept before and after the trigger frame when a rule doesn't say.
+const DEFAULT_WINDOW_MS: u64 = 5_000;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// One snapshot rule. A rule doesn't fire again while its own snapshot is
+/// still waiting for the post-trigger window.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SnapshotTrigger {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Frame that fires the rule (masked ID and payload)
+    pub trigger: FramePattern,
+    /// Frame time kept before the trigger frame
+    #[serde(default = "default_window_ms")]
+    pub pre_trigger_ms: u64,
+    /// Frame time kept after the trigger frame
+    #[serde(default = "default_window_ms")]
+    pub post_trigger_ms: u64,
+}
+
+fn default_window_ms() -> u64 {
+    DEFAULT_WINDOW_MS
+}
+
+/// Per-rule counters, fetched by the frontend.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SnapshotTriggerStats {
+    pub name: Option<String>,
+    /// Times the rule matched
+    pub fired: u64,
+    /// Snapshots written
+    pub snapshots: u64,
+    /// ID of the most recent snapshot capture
+    pub last_capture_id: Option<String>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SnapshotTriggerState {
+    pub session_id: String,
+    pub rules: Vec<SnapshotTriggerStats>,
+    /// Snapshots waiting for their post-trigger window
+    pub pending: usize,
+}
+
+/// Payload of the `trigger-fired` event, emitted once a snapshot is written.
+#[derive(Clone, Debug, Serialize)]
+pub struct TriggerFiredPayload {
+    pub session_id: String,
+    /// Index of the rule in the list given to `set_session_triggers`
+    pub rule: usize,
+    pub name: Option<String>,
+    /// Timestamp of the frame that fired the rule
+    pub trigger_us: u64,
+    /// The new (orphaned) capture holding the snapshot
+    pub capture: CaptureMetadata,
+}
+
+/// A snapshot waiting for frames past its window, or ready to be copied.
+#[derive(Clone, Debug)]
+pub struct PendingSnapshot {
+    session_id: String,
+    rule: usize,
+    name: Option<String>,
+    capture_id: String,
+    trigger_us: u64,
+    start_us: u64,
+    end_us: u64,
+}
+
+struct SnapshotSession {
+    rules: Vec<SnapshotTrigger>,
+    pending: Vec<PendingSnapshot>,
+    state: SnapshotTriggerState,
+}
+
+// ============================================================================
+// State management
+// ============================================================================
+
+static SESSIONS: Lazy<StdMutex<HashMap<String, SnapshotSession>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// Sessions with rules installed. Lets the append path skip the lock when no
+/// session uses snapshot triggers.
+static ACTIVE_SESSIONS: AtomicUsize = AtomicUsize::new(0);
+
+impl SnapshotSession {
+    fn new(session_id: &str, rules: Vec<SnapshotTrigger>) -> Self {
+        let state = SnapshotTriggerState {
+            session_id: session_id.to_string(),
+            rules: rules
+                .iter()
+                .map(|r| SnapshotTriggerStats { name: r.name.clone(), ..Default::default() })
+                .collect(),
+            pending: 0,
+        };
+        Self { rules, pending: Vec::new(), state }
+    }
+
+    /// Match frames written to `capture_id` and return the snapshots whose
+    /// post-trigger window those frames complete.
+    fn evaluate(&mut self, capture_id: &str, frames: &[FrameMessage]) -> Vec<PendingSnapshot> {
+        let session_id = self.state.session_id.clone();
+        for frame in frames {
+            for (idx, rule) in self.rules.iter().enumerate() {
+                if self.pending.iter().any(|p| p.rule == idx) || !rule.trigger.matches(frame) {
+                    continue;
+                }
+                self.state.rules[idx].fired += 1;
+                self.pending.push(PendingSnapshot {
+                    session_id: session_id.clone(),
+                    rule: idx,
+                    name: rule.name.clone(),
+                    capture_id: capture_id.to_string(),
+                    trigger_us: frame.timestamp_us,
+                    start_us: frame.timestamp_us.saturating_sub(rule.pre_trigger_ms.saturating_mul(1000)),
+                    end_us: frame.timestamp_us.saturating_add(rule.post_trigger_ms.saturating_mul(1000)),
+                });
+            }
+        }
+
+        let newest = frames.iter().map(|f| f.timestamp_us).max().unwrap_or(0);
+        let (due, waiting): (Vec<_>, Vec<_>) = self.pending.drain(..).partition(|p| newest >= p.end_us);
+        self.pending = waiting;
+        self.state.pending = self.pending.len();
+        due
+    }
+}
+
+/// Match a batch of frames about to be appended to the session's capture.
+/// Called from capture_store::append_frames_to_session; the returned
+/// snapshots are handed to `take_snapshots` once the batch is written.
+pub fn tap_frames(session_id: &str, capture_id: &str, frames: &[FrameMessage]) -> Vec<PendingSnapshot> {
+    if ACTIVE_SESSIONS.load(Ordering::Relaxed) == 0 {
+        return Vec::new();
+    }
+    let mut sessions = SESSIONS.lock().unwrap();
+    match sessions.get_mut(session_id) {
+        Some(session) => session.evaluate(capture_id, frames),
+        None => Vec::new(),
+    }
+}
+
+fn snapshot_name(snapshot: &PendingSnapshot) -> String {
+    let label = snapshot.name.clone().unwrap_or_else(|| format!("Trigger {}", snapshot.rule + 1));
+    match Utc.timestamp_micros(snapshot.trigger_us as i64).single() {
+        Some(t) => format!("{} {}", label, t.format("%H:%M:%S%.3f")),
+        None => label,
+    }
+}
+
+/// Copy each snapshot's window into a new capture off-thread and emit
+/// `trigger-fired` for each one written.
+pub fn take_snapshots(snapshots: Vec<PendingSnapshot>) {
+    if snapshots.is_empty() {
+        return;
+    }
+    std::thread::spawn(move || {
+        for snapshot in snapshots {
+            let result = capture_store::split_capture(
+                &snapshot.capture_id,
+                Some(snapshot.start_us),
+                Some(snapshot.end_us),
+                snapshot_name(&snapshot),
+            );
+            if let Some(session) = SESSIONS.lock().unwrap().get_mut(&snapshot.session_id) {
+                if let Some(stats) = session.state.rules.get_mut(snapshot.rule) {
+                    match &result {
+                        Ok(meta) => {
+                            stats.snapshots += 1;
+                            stats.last_capture_id = Some(meta.id.clone());
+                        }
+                        Err(e) => stats.last_error = Some(e.clone()),
+                    }
+                }
+            }
+            match result {
+                Ok(capture) => {
+                    tlog!(
+                        "[TriggerSnapshot:{}] Rule {} fired at {} us, {} frames into '{}'",
+                        snapshot.session_id, snapshot.rule, snapshot.trigger_us, capture.count, capture.id
+                    );
+                    crate::io::emit_trigger_fired(&TriggerFiredPayload {
+                        session_id: snapshot.session_id,
+                        rule: snapshot.rule,
+                        name: snapshot.name,
+                        trigger_us: snapshot.trigger_us,
+                        capture,
+                    });
+                }
+                Err(e) => tlog!("[TriggerSnapshot:{}] Snapshot failed: {}", snapshot.session_id, e),
+            }
+        }
+    });
+}
+
+/// Remove a session's rules, writing out any snapshots still waiting with
+/// whatever frames their window holds so far. Called when the session is
+/// destroyed.
+pub fn clear_session(session_id: &str) {
+    let removed = SESSIONS.lock().unwrap().remove(session_id);
+    if let Some(session) = removed {
+        ACTIVE_SESSIONS.fetch_sub(1, Ordering::Relaxed);
+        take_snapshots(session.pending);
+    }
+}
+
+fn validate(rules: &[SnapshotTrigger]) -> Result<(), String> {
+    for rule in rules {
+        if rule.trigger.data_mask.len() > rule.trigger.data.len() {
+            return Err("data_mask is longer than data".to_string());
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+/// Install snapshot trigger rules on a session, replacing any existing ones.
+/// Snapshots still waiting under the old rules are dropped.
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_session_triggers(session_id: String, rules: Vec<SnapshotTrigger>) -> Result<SnapshotTriggerState, String> {
+    validate(&rules)?;
+    let session = SnapshotSession::new(&session_id, rules);
+    let state = session.state.clone();
+    if SESSIONS.lock().unwrap().insert(session_id.clone(), session).is_none() {
+        ACTIVE_SESSIONS.fetch_add(1, Ordering::Relaxed);
+    }
+    tlog!("[TriggerSnapshot:{}] {} rule(s) set", session_id, state.rules.len());
+    Ok(state)
+}
+
+/// Remove a session's snapshot triggers. Snapshots still waiting for their
+/// post-trigger window are written with the frames captured so far.
+#[tauri::command(rename_all = "snake_case")]
+pub fn clear_session_triggers(session_id: String) {
+    clear_session(&session_id);
+}
+
+/// Per-rule counters for a session (None = no rules installed).
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_session_triggers(session_id: String) -> Option<SnapshotTriggerState> {
+    SESSIONS.lock().unwrap().get(&session_id).map(|s| s.state.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::test_frame;
+
+    fn frame(id: u32, ts_ms: u64, bytes: &[u8]) -> FrameMessage {
+        test_frame(id, ts_ms * 1000, bytes)
+    }
+
+    fn rule(id: u32, data: &[u8], pre_ms: u64, post_ms: u64) -> SnapshotTrigger {
+        SnapshotTrigger {
+            name: Some("door".to_string()),
+            trigger: FramePattern { frame_id: id, id_mask: None, bus: None, data: data.to_vec(), data_mask: vec![] },
+            pre_trigger_ms: pre_ms,
+            post_trigger_ms: post_ms,
+        }
+    }
+
+    #[test]
+    fn snapshot_is_due_once_the_post_window_is_written() {
+        let mut session = SnapshotSession::new("s1", vec![rule(0x100, &[0x01], 1000, 500)]);
+        assert!(session.evaluate("cap", &[frame(0x100, 2000, &[0x00]), frame(0x100, 3000, &[0x01])]).is_empty());
+        assert_eq!(session.state.pending, 1);
+
+        assert!(session.evaluate("cap", &[frame(0x200, 3400, &[])]).is_empty());
+        let due = session.evaluate("cap", &[frame(0x200, 3500, &[])]);
+        assert_eq!(due.len(), 1);
+        assert_eq!((due[0].start_us, due[0].trigger_us, due[0].end_us), (2_000_000, 3_000_000, 3_500_000));
+        assert_eq!(due[0].capture_id, "cap");
+        assert_eq!(session.state.pending, 0);
+    }
+
+    #[test]
+    fn rule_does_not_refire_while_its_snapshot_is_pending() {
+        let mut session = SnapshotSession::new("s1", vec![rule(0x100, &[], 0, 1000)]);
+        session.evaluate("cap", &[frame(0x100, 0, &[]), frame(0x100, 200, &[]), frame(0x100, 400, &[])]);
+        assert_eq!(session.state.rules[0].fired, 1);
+
+        // The frame closing the window belongs to it; the next one fires again
+        assert_eq!(session.evaluate("cap", &[frame(0x100, 1000, &[])]).len(), 1);
+        assert_eq!((session.state.rules[0].fired, session.state.pending), (1, 0));
+        session.evaluate("cap", &[frame(0x100, 1200, &[])]);
+        assert_eq!((session.state.rules[0].fired, session.state.pending), (2, 1));
+    }
+
+    #[test]
+    fn pre_window_saturates_at_zero_and_names_include_time() {
+        let mut session = SnapshotSession::new("s1", vec![rule(0x100, &[], 5000, 0)]);
+        let due = session.evaluate("cap", &[frame(0x100, 1_700_000_000_250, &[])]);
+        assert_eq!(due.len(), 1);
+        assert_eq!(snapshot_name(&due[0]), "door 22:13:20.250");
+
+        let mut early = SnapshotSession::new("s1", vec![rule(0x100, &[], 5000, 0)]);
+        assert_eq!(early.evaluate("cap", &[frame(0x100, 1000, &[])])[0].start_us, 0);
+    }
+}
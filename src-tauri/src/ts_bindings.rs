@@ -0,0 +1,93 @@
+// ui/src-tauri/src/ts_bindings.rs
+//
+// TypeScript declarations for the event payloads and command results the
+// frontend consumes, generated from the Rust types so the two can't drift.
+//
+// Types opt in with `#[cfg_attr(test, derive(ts_rs::TS))]` (ts-rs is a
+// dev-dependency, so release builds don't carry it) and are listed in
+// `declarations` below. The output is src/types/backend.d.ts, which is
+// committed so the frontend builds without a Rust toolchain. The test fails
+// (after rewriting the file) whenever the committed copy is stale, so a struct
+// change that isn't regenerated shows up in `cargo test`.
+//
+// serde_json writes 64-bit integers as plain JSON numbers, so ts-rs's
+// `bigint` is rendered as `number`.
+
+use ts_rs::TS;
+
+use crate::capture_integrity::{CaptureIntegrity, IntegritySignature};
+use crate::capture_store::{CaptureKind, CaptureMetadata};
+use crate::io::post_session::{SourceInfo, StreamEndedInfo};
+use crate::io::{
+    ActiveSessionInfo, CanErrorClass, FrameClock, FrameLink, FrameMessage, IOCapabilities, IOState, InterfaceTraits,
+    LinkRole, PlaybackPosition, Protocol, ProtocolStream, SessionDataStreams, SessionLifecyclePayload,
+    SubscriberInfo, TemporalMode,
+};
+use crate::timebase::{CaptureTiming, ClockSyncStatus, TimestampSource};
+
+const BINDINGS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../src/types/backend.d.ts");
+
+const HEADER: &str = "\
+// src/types/backend.d.ts
+//
+// GENERATED by src-tauri/src/ts_bindings.rs from the Rust types — do not edit.
+// Regenerate with `cargo test ts_bindings` in src-tauri.
+";
+
+macro_rules! declare {
+    ($out:ident, $($ty:ty),+ $(,)?) => {$(
+        $out.push('\n');
+        if let Some(docs) = <$ty as TS>::docs() {
+            $out.push_str(&docs);
+        }
+        $out.push_str("export ");
+        $out.push_str(&<$ty as TS>::decl().replace("bigint", "number"));
+        $out.push('\n');
+    )+};
+}
+
+fn declarations() -> String {
+    let mut out = HEADER.to_string();
+    declare!(
+        out,
+        // Frames
+        FrameMessage,
+        FrameLink,
+        LinkRole,
+        FrameClock,
+        CanErrorClass,
+        // Sessions
+        IOState,
+        TemporalMode,
+        Protocol,
+        ProtocolStream,
+        InterfaceTraits,
+        SessionDataStreams,
+        IOCapabilities,
+        SubscriberInfo,
+        ActiveSessionInfo,
+        SessionLifecyclePayload,
+        PlaybackPosition,
+        StreamEndedInfo,
+        SourceInfo,
+        // Captures
+        CaptureKind,
+        CaptureMetadata,
+        CaptureTiming,
+        TimestampSource,
+        ClockSyncStatus,
+        CaptureIntegrity,
+        IntegritySignature,
+    );
+    out
+}
+
+#[test]
+fn bindings_are_current() {
+    let generated = declarations();
+    let committed = std::fs::read_to_string(BINDINGS_PATH).unwrap_or_default();
+    if committed != generated {
+        std::fs::write(BINDINGS_PATH, &generated).expect("write src/types/backend.d.ts");
+        panic!("src/types/backend.d.ts was stale and has been regenerated; commit the update");
+    }
+}
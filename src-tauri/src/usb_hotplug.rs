@@ -0,0 +1,179 @@
+// USB hotplug watcher
+//
+// Watches for USB devices arriving and leaving (nusb's hotplug stream: udev
+// netlink on Linux, IOKit on macOS, CM notifications on Windows) and emits
+// global `device-attached` / `device-removed` events with VID/PID/serial, so
+// the IO picker can refresh live. USB serial adapters show up here too; the
+// frontend re-lists serial ports when one attaches.
+//
+// Removal events only carry nusb's opaque device id, so the details of every
+// attached device are kept here to report what went away. Each attach also
+// bumps a generation counter that source reconnect (io/broker/reconnect.rs)
+// watches to retry straight away when an adapter comes back, rather than
+// waiting out its backoff.
+
+use futures::StreamExt;
+use nusb::hotplug::HotplugEvent;
+use nusb::{DeviceId, DeviceInfo};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// A USB device, as reported in `device-attached` / `device-removed`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct UsbDevicePayload {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub serial_number: Option<String>,
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+    /// Platform bus identifier
+    pub bus_id: String,
+    pub device_address: u8,
+}
+
+impl UsbDevicePayload {
+    fn from_info(info: &DeviceInfo) -> Self {
+        Self {
+            vendor_id: info.vendor_id(),
+            product_id: info.product_id(),
+            serial_number: info.serial_number().map(str::to_string),
+            manufacturer: info.manufacturer_string().map(str::to_string),
+            product: info.product_string().map(str::to_string),
+            bus_id: info.bus_id().to_string(),
+            device_address: info.device_address(),
+        }
+    }
+}
+
+/// Attached devices by hotplug id.
+struct DeviceTable<K> {
+    devices: HashMap<K, UsbDevicePayload>,
+}
+
+impl<K: Eq + Hash> DeviceTable<K> {
+    fn new() -> Self {
+        Self { devices: HashMap::new() }
+    }
+
+    /// Record an attach. Returns false if the device was already known (the
+    /// initial listing and the watch can both report it).
+    fn attached(&mut self, id: K, device: UsbDevicePayload) -> bool {
+        self.devices.insert(id, device).is_none()
+    }
+
+    /// Record a removal, returning the device's details if it was known.
+    fn removed(&mut self, id: &K) -> Option<UsbDevicePayload> {
+        self.devices.remove(id)
+    }
+
+    fn list(&self) -> Vec<UsbDevicePayload> {
+        let mut devices: Vec<_> = self.devices.values().cloned().collect();
+        devices.sort_by(|a, b| (&a.bus_id, a.device_address).cmp(&(&b.bus_id, b.device_address)));
+        devices
+    }
+}
+
+static DEVICES: Lazy<Mutex<DeviceTable<DeviceId>>> = Lazy::new(|| Mutex::new(DeviceTable::new()));
+static ATTACH_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Incremented each time a new USB device attaches.
+pub fn attach_generation() -> u64 {
+    ATTACH_GENERATION.load(Ordering::Relaxed)
+}
+
+/// Start the hotplug watcher. Runs for the life of the app.
+pub fn start_usb_hotplug_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Create the watch before listing so nothing slips between the two
+        let mut watch = match nusb::watch_devices() {
+            Ok(watch) => watch,
+            Err(e) => {
+                tlog!("[usb_hotplug] Hotplug watch unavailable: {}", e);
+                return;
+            }
+        };
+        match nusb::list_devices().await {
+            Ok(devices) => {
+                if let Ok(mut table) = DEVICES.lock() {
+                    for info in devices {
+                        table.attached(info.id(), UsbDevicePayload::from_info(&info));
+                    }
+                }
+            }
+            Err(e) => tlog!("[usb_hotplug] Failed to list USB devices: {}", e),
+        }
+
+        while let Some(event) = watch.next().await {
+            match event {
+                HotplugEvent::Connected(info) => {
+                    let device = UsbDevicePayload::from_info(&info);
+                    let is_new = DEVICES.lock().map(|mut t| t.attached(info.id(), device.clone())).unwrap_or(false);
+                    if is_new {
+                        ATTACH_GENERATION.fetch_add(1, Ordering::Relaxed);
+                        tlog!(
+                            "[usb_hotplug] Attached {:04x}:{:04x} {}",
+                            device.vendor_id,
+                            device.product_id,
+                            device.serial_number.as_deref().unwrap_or("")
+                        );
+                        let _ = app.emit("device-attached", &device);
+                    }
+                }
+                HotplugEvent::Disconnected(id) => {
+                    let removed = DEVICES.lock().ok().and_then(|mut t| t.removed(&id));
+                    if let Some(device) = removed {
+                        tlog!("[usb_hotplug] Removed {:04x}:{:04x}", device.vendor_id, device.product_id);
+                        let _ = app.emit("device-removed", &device);
+                    }
+                }
+            }
+        }
+        tlog!("[usb_hotplug] Hotplug watch ended");
+    });
+}
+
+/// USB devices currently attached, as last reported by the watcher.
+#[tauri::command]
+pub fn list_usb_devices() -> Vec<UsbDevicePayload> {
+    DEVICES.lock().map(|t| t.list()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(address: u8) -> UsbDevicePayload {
+        UsbDevicePayload {
+            vendor_id: 0x1d50,
+            product_id: 0x606f,
+            serial_number: Some(format!("SN{address}")),
+            manufacturer: None,
+            product: Some("candleLight".to_string()),
+            bus_id: "1".to_string(),
+            device_address: address,
+        }
+    }
+
+    #[test]
+    fn removal_reports_the_attached_details() {
+        let mut table = DeviceTable::new();
+        assert!(table.attached(7, device(3)));
+        assert_eq!(table.removed(&7), Some(device(3)));
+        assert_eq!(table.removed(&7), None);
+    }
+
+    #[test]
+    fn a_device_reported_twice_is_only_new_once() {
+        let mut table = DeviceTable::new();
+        assert!(table.attached(1, device(2)));
+        assert!(!table.attached(1, device(2)));
+        table.attached(2, device(1));
+        let addresses: Vec<u8> = table.list().iter().map(|d| d.device_address).collect();
+        assert_eq!(addresses, vec![1, 2]);
+    }
+}
@@ -0,0 +1,365 @@
+// ui/src-tauri/src/webhooks.rs
+//
+// Session lifecycle webhooks. Each configured webhook is an HTTP endpoint that
+// receives a JSON POST when one of its events happens, so lab automation (a
+// Jenkins job, a Slack notifier) can react to WireTAP without polling:
+//
+//   session_created    a session was created (io::emit_session_lifecycle)
+//   session_destroyed  a session was destroyed
+//   stream_ended       a session's stream ended (io::emit_stream_ended)
+//   alert_triggered    a capture trigger's start condition fired
+//   export_completed   a capture was exported to a file
+//
+// Body: { "event", "timestamp" (RFC 3339), "session_id"?, "data": {...} }.
+// Deliveries are fire-and-forget on the async runtime with a short timeout;
+// failures are logged and recorded per webhook. The configuration is loaded
+// at launch and persisted in webhooks.json in the app data dir. Header values
+// (often tokens) are kept in the system keyring, one entry per webhook and
+// header; webhooks.json only lists the header names.
+
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::credentials;
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    SessionCreated,
+    SessionDestroyed,
+    StreamEnded,
+    AlertTriggered,
+    ExportCompleted,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Webhook {
+    /// Display name (also used in logs)
+    #[serde(default)]
+    pub name: String,
+    /// http:// or https:// endpoint receiving the POST
+    pub url: String,
+    /// Events delivered to this webhook; empty means all of them
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    /// Extra request headers, e.g. an Authorization token for a CI server.
+    /// Values are stored in the keyring, not in webhooks.json.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl Webhook {
+    fn wants(&self, event: WebhookEvent) -> bool {
+        self.enabled && (self.events.is_empty() || self.events.contains(&event))
+    }
+
+    fn label(&self) -> &str {
+        if self.name.is_empty() { &self.url } else { &self.name }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    #[serde(default)]
+    pub webhooks: Vec<Webhook>,
+}
+
+/// Outcome of the most recent delivery to a webhook.
+#[derive(Clone, Debug, Serialize)]
+pub struct WebhookDelivery {
+    pub event: WebhookEvent,
+    /// Unix seconds
+    pub delivered_at: i64,
+    /// HTTP status, when the endpoint answered
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+static CONFIG: Lazy<Mutex<WebhookConfig>> = Lazy::new(|| Mutex::new(WebhookConfig::default()));
+
+/// Last delivery per webhook, by its index in the configuration. Cleared
+/// when the configuration is replaced.
+static LAST_DELIVERY: Lazy<Mutex<HashMap<usize, WebhookDelivery>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static HTTP: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+});
+
+fn config_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    Ok(dir.join("webhooks.json"))
+}
+
+/// Keyring profile holding the header values of the webhook at `index`.
+fn secret_profile(index: usize) -> String {
+    format!("webhook-{}", index)
+}
+
+/// The configuration as written to webhooks.json: header names only.
+fn without_header_values(config: &WebhookConfig) -> WebhookConfig {
+    let mut stripped = config.clone();
+    for hook in &mut stripped.webhooks {
+        hook.headers.values_mut().for_each(String::clear);
+    }
+    stripped
+}
+
+fn write_config(path: &PathBuf, config: &WebhookConfig) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(&without_header_values(config)).map_err(|e| e.to_string())?;
+    std::fs::write(path, content).map_err(|e| format!("Failed to write webhooks: {}", e))
+}
+
+/// Move header values into the keyring, dropping entries for headers (and
+/// webhooks) that `previous` had and `config` no longer does.
+fn store_header_values(previous: &WebhookConfig, config: &WebhookConfig) -> Result<(), String> {
+    for (i, hook) in previous.webhooks.iter().enumerate() {
+        let kept = config.webhooks.get(i);
+        for name in hook.headers.keys().filter(|n| kept.is_none_or(|h| !h.headers.contains_key(*n))) {
+            if let Err(e) = credentials::delete_credential(&secret_profile(i), name) {
+                tlog!("[webhooks] {e}");
+            }
+        }
+    }
+    for (i, hook) in config.webhooks.iter().enumerate() {
+        for (name, value) in &hook.headers {
+            credentials::store_credential(&secret_profile(i), name, value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Load the saved configuration and its header values. Called once at launch.
+/// Files written before header values moved to the keyring still hold them;
+/// they are moved there and the file is rewritten without them.
+pub fn load_config(app: &AppHandle) {
+    let path = config_path(app).ok();
+    let mut config: WebhookConfig = path
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    let mut legacy = false;
+    for (i, hook) in config.webhooks.iter_mut().enumerate() {
+        for (name, value) in hook.headers.iter_mut() {
+            if !value.is_empty() {
+                legacy = true;
+                continue;
+            }
+            match credentials::get_credential(&secret_profile(i), name) {
+                Ok(Some(secret)) => *value = secret,
+                Ok(None) => {}
+                Err(e) => tlog!("[webhooks] {e}"),
+            }
+        }
+    }
+    if legacy {
+        let moved = store_header_values(&WebhookConfig::default(), &config)
+            .and_then(|_| path.as_ref().map_or(Ok(()), |path| write_config(path, &config)));
+        if let Err(e) = moved {
+            tlog!("[webhooks] Header values left in webhooks.json: {e}");
+        }
+    }
+    if !config.webhooks.is_empty() {
+        tlog!("[webhooks] Loaded {} webhook(s)", config.webhooks.len());
+    }
+    if let Ok(mut current) = CONFIG.lock() {
+        *current = config;
+    }
+}
+
+fn validate(config: &WebhookConfig) -> Result<(), String> {
+    for (i, hook) in config.webhooks.iter().enumerate() {
+        let url = hook.url.trim();
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(format!("Webhook {} needs an http:// or https:// URL", i));
+        }
+        if reqwest::Url::parse(url).is_err() {
+            return Err(format!("Webhook {} has an invalid URL", i));
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Delivery
+// ============================================================================
+
+fn payload(event: WebhookEvent, session_id: Option<&str>, data: Value) -> Value {
+    json!({
+        "event": event,
+        "timestamp": Utc::now().to_rfc3339(),
+        "session_id": session_id,
+        "data": data,
+    })
+}
+
+/// POST to one webhook. `index` is its place in the configuration, under
+/// which the outcome is recorded (None for ad-hoc test deliveries).
+async fn deliver(hook: Webhook, index: Option<usize>, event: WebhookEvent, body: Value) -> WebhookDelivery {
+    let mut request = HTTP.post(hook.url.trim()).json(&body);
+    for (name, value) in &hook.headers {
+        request = request.header(name.as_str(), value.as_str());
+    }
+    let (status, error) = match request.send().await {
+        Ok(resp) if resp.status().is_success() => (Some(resp.status().as_u16()), None),
+        Ok(resp) => (Some(resp.status().as_u16()), Some(format!("HTTP {}", resp.status()))),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    if let Some(e) = &error {
+        tlog!("[webhooks] Delivery of {:?} to '{}' failed: {}", event, hook.label(), e);
+    }
+    let delivery = WebhookDelivery { event, delivered_at: Utc::now().timestamp(), status, error };
+    if let (Some(index), Ok(mut last)) = (index, LAST_DELIVERY.lock()) {
+        last.insert(index, delivery.clone());
+    }
+    delivery
+}
+
+/// POST an event to every enabled webhook subscribed to it. Returns
+/// immediately; deliveries run in the background.
+pub fn fire(event: WebhookEvent, session_id: Option<&str>, data: Value) {
+    let hooks: Vec<(usize, Webhook)> = match CONFIG.lock() {
+        Ok(config) => config
+            .webhooks
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| h.wants(event))
+            .map(|(i, h)| (i, h.clone()))
+            .collect(),
+        Err(_) => return,
+    };
+    if hooks.is_empty() {
+        return;
+    }
+    let body = payload(event, session_id, data);
+    for (index, hook) in hooks {
+        let body = body.clone();
+        tauri::async_runtime::spawn(async move {
+            deliver(hook, Some(index), event, body).await;
+        });
+    }
+}
+
+// ============================================================================
+// Tauri commands
+// ============================================================================
+
+#[tauri::command]
+pub fn get_webhooks() -> WebhookConfig {
+    CONFIG.lock().map(|c| c.clone()).unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn set_webhooks(app: AppHandle, config: WebhookConfig) -> Result<(), String> {
+    validate(&config)?;
+    let path = config_path(&app)?;
+    let previous = CONFIG.lock().map(|c| c.clone()).unwrap_or_default();
+    store_header_values(&previous, &config)?;
+    write_config(&path, &config)?;
+    *CONFIG.lock().map_err(|e| e.to_string())? = config;
+    // Indices may now refer to different webhooks
+    if let Ok(mut last) = LAST_DELIVERY.lock() {
+        last.clear();
+    }
+    Ok(())
+}
+
+/// Send a test event to one webhook (enabled or not) and wait for the result.
+#[tauri::command]
+pub async fn test_webhook(webhook: Webhook) -> Result<WebhookDelivery, String> {
+    validate(&WebhookConfig { webhooks: vec![webhook.clone()] })?;
+    let body = payload(WebhookEvent::SessionCreated, None, json!({ "test": true }));
+    Ok(deliver(webhook, None, WebhookEvent::SessionCreated, body).await)
+}
+
+/// Most recent delivery per webhook, by index in the configuration.
+#[tauri::command]
+pub fn get_webhook_deliveries() -> HashMap<usize, WebhookDelivery> {
+    LAST_DELIVERY.lock().map(|l| l.clone()).unwrap_or_default()
+}
+
+/// Report an export written by the frontend (the backend only sees exports it
+/// writes itself).
+#[tauri::command(rename_all = "snake_case")]
+pub fn notify_export_completed(capture_id: Option<String>, format: String, file_path: String) {
+    fire(
+        WebhookEvent::ExportCompleted,
+        None,
+        json!({ "capture_id": capture_id, "format": format, "file_path": file_path }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(url: &str, events: Vec<WebhookEvent>) -> Webhook {
+        Webhook { name: String::new(), url: url.into(), events, headers: HashMap::new(), enabled: true }
+    }
+
+    #[test]
+    fn empty_event_list_subscribes_to_everything() {
+        let all = hook("http://ci.local/hook", vec![]);
+        assert!(all.wants(WebhookEvent::StreamEnded));
+        let some = hook("http://ci.local/hook", vec![WebhookEvent::ExportCompleted]);
+        assert!(some.wants(WebhookEvent::ExportCompleted));
+        assert!(!some.wants(WebhookEvent::SessionCreated));
+        let disabled = Webhook { enabled: false, ..all };
+        assert!(!disabled.wants(WebhookEvent::StreamEnded));
+    }
+
+    #[test]
+    fn payload_names_event_in_snake_case() {
+        let body = payload(WebhookEvent::AlertTriggered, Some("s1"), json!({ "trigger_count": 2 }));
+        assert_eq!(body["event"], "alert_triggered");
+        assert_eq!(body["session_id"], "s1");
+        assert_eq!(body["data"]["trigger_count"], 2);
+        assert!(body["timestamp"].as_str().is_some());
+    }
+
+    #[test]
+    fn saved_config_keeps_header_names_only() {
+        let mut hook = hook("https://ci.local/hook", vec![]);
+        hook.headers.insert("Authorization".into(), "Bearer s3cret".into());
+        let config = WebhookConfig { webhooks: vec![hook] };
+        let saved = serde_json::to_string(&without_header_values(&config)).unwrap();
+        assert!(saved.contains("Authorization"));
+        assert!(!saved.contains("s3cret"));
+        assert_eq!(config.webhooks[0].headers["Authorization"], "Bearer s3cret");
+    }
+
+    #[test]
+    fn rejects_non_http_urls() {
+        let ok = WebhookConfig { webhooks: vec![hook("https://hooks.slack.com/services/x", vec![])] };
+        assert!(validate(&ok).is_ok());
+        let bad = WebhookConfig { webhooks: vec![hook("ftp://example.com", vec![])] };
+        assert!(validate(&bad).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2026 Wired Square Pty Ltd
+//
+// src-tauri/src/ws/backpressure.rs
+//
+// Frame delivery flow control. The frontend sends a FrameAck for each
+// FrameData batch once its handlers have run. While MAX_IN_FLIGHT batches are
+// unacknowledged, send_new_frames holds back: the send offset stays put, so
+// the frames pile up in the capture and go out as one coalesced batch when
+// the next ack arrives. If the held-back backlog grows past
+// MAX_BACKLOG_FRAMES, the oldest frames are skipped for delivery (they stay in
+// the capture). Coalesced and dropped counts are reported in
+// `session-metrics`.
+//
+// Flow control only starts once a session has seen its first ack, so
+// listeners that never ack are served as before. Listeners on one session
+// share its channel and each acks every batch, so extra acks just clear the
+// in-flight count early. An ack that never comes (dropped socket, crashed
+// view) stops holding frames back after ACK_TIMEOUT.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Unacknowledged batches allowed before sends are held back
+const MAX_IN_FLIGHT: u32 = 2;
+/// Frames a held-back session may accumulate before the oldest are skipped
+const MAX_BACKLOG_FRAMES: usize = 20_000;
+/// How long to wait for an ack before assuming it was lost
+const ACK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Default)]
+struct Flow {
+    acks_seen: bool,
+    in_flight: u32,
+    last_sent: Option<Instant>,
+    /// A send was held back since the last batch went out
+    deferred: bool,
+    coalesced: u64,
+    dropped: u64,
+}
+
+impl Flow {
+    fn may_send(&mut self, now: Instant) -> bool {
+        if !self.acks_seen || self.in_flight < MAX_IN_FLIGHT {
+            return true;
+        }
+        if self.last_sent.is_some_and(|t| now.duration_since(t) >= ACK_TIMEOUT) {
+            self.in_flight = 0;
+            return true;
+        }
+        self.deferred = true;
+        self.coalesced += 1;
+        false
+    }
+
+    /// Frames to skip from the head of a backlog of `pending` frames.
+    fn skip_count(&mut self, pending: usize) -> usize {
+        if !self.deferred || pending <= MAX_BACKLOG_FRAMES {
+            return 0;
+        }
+        let skip = pending - MAX_BACKLOG_FRAMES;
+        self.dropped += skip as u64;
+        skip
+    }
+
+    fn sent(&mut self, now: Instant) {
+        self.in_flight += 1;
+        self.last_sent = Some(now);
+        self.deferred = false;
+    }
+
+    /// Returns true when a send was held back and should be retried.
+    fn acked(&mut self) -> bool {
+        self.acks_seen = true;
+        self.in_flight = self.in_flight.saturating_sub(1);
+        self.deferred && self.in_flight < MAX_IN_FLIGHT
+    }
+}
+
+static FLOWS: Lazy<Mutex<HashMap<String, Flow>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn with_flow<T>(session_id: &str, default: T, f: impl FnOnce(&mut Flow) -> T) -> T {
+    match FLOWS.lock() {
+        Ok(mut flows) => f(flows.entry(session_id.to_string()).or_default()),
+        Err(_) => default,
+    }
+}
+
+/// Whether a frame batch may go out now. A held-back send counts as coalesced.
+pub fn may_send(session_id: &str) -> bool {
+    with_flow(session_id, true, |flow| flow.may_send(Instant::now()))
+}
+
+/// Frames to skip from the head of `pending` new frames, counted as dropped.
+pub fn skip_count(session_id: &str, pending: usize) -> usize {
+    with_flow(session_id, 0, |flow| flow.skip_count(pending))
+}
+
+/// Record a FrameData batch sent on the session's channel.
+pub fn record_sent(session_id: &str) {
+    with_flow(session_id, (), |flow| flow.sent(Instant::now()));
+}
+
+/// Record a FrameAck. Returns true when a held-back send should be retried.
+pub fn acknowledge(session_id: &str) -> bool {
+    with_flow(session_id, false, |flow| flow.acked())
+}
+
+/// (coalesced, dropped) since the last call.
+pub fn take_counts(session_id: &str) -> (u64, u64) {
+    let Ok(mut flows) = FLOWS.lock() else { return (0, 0) };
+    match flows.get_mut(session_id) {
+        Some(flow) => (std::mem::take(&mut flow.coalesced), std::mem::take(&mut flow.dropped)),
+        None => (0, 0),
+    }
+}
+
+/// Forget a session's flow state. Called when its channel is released or
+/// re-subscribed.
+pub fn clear_session(session_id: &str) {
+    if let Ok(mut flows) = FLOWS.lock() {
+        flows.remove(session_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listeners_that_never_ack_are_not_held_back() {
+        let mut flow = Flow::default();
+        let now = Instant::now();
+        for _ in 0..10 {
+            assert!(flow.may_send(now));
+            flow.sent(now);
+        }
+        assert_eq!(flow.coalesced, 0);
+    }
+
+    #[test]
+    fn sends_are_held_back_until_an_ack_arrives() {
+        let mut flow = Flow::default();
+        let now = Instant::now();
+        flow.acked();
+        flow.sent(now);
+        flow.sent(now);
+        assert!(!flow.may_send(now));
+        assert!(!flow.may_send(now));
+        assert_eq!(flow.coalesced, 2);
+        assert!(flow.acked());
+        assert!(flow.may_send(now));
+        assert_eq!(flow.skip_count(MAX_BACKLOG_FRAMES + 5), 5);
+        flow.sent(now);
+        assert_eq!(flow.skip_count(MAX_BACKLOG_FRAMES + 5), 0);
+        assert_eq!(flow.dropped, 5);
+    }
+
+    #[test]
+    fn a_lost_ack_stops_holding_back_after_the_timeout() {
+        let mut flow = Flow::default();
+        let start = Instant::now();
+        flow.acked();
+        flow.sent(start);
+        flow.sent(start);
+        assert!(!flow.may_send(start + Duration::from_millis(500)));
+        assert!(flow.may_send(start + ACK_TIMEOUT));
+        assert_eq!(flow.in_flight, 0);
+    }
+}
@@ -8,6 +8,7 @@ use once_cell::sync::Lazy;
 use crate::io::post_session::StreamEndedInfo;
 use crate::io::{FrameMessage, IOState, PlaybackPosition};
 use crate::transmit::{RepeatStartedEvent, RepeatStoppedEvent};
+use crate::ws::backpressure;
 use crate::ws::protocol::{self, MsgType};
 use crate::ws::server::ws_server;
 
@@ -35,6 +36,7 @@ pub fn attach_catalog(session_id: &str, path: Option<String>, catalog: wiretap_c
     if let Ok(mut m) = ATTACHED_CATALOGS.write() {
         m.insert(session_id.to_string(), (path, Arc::new(catalog)));
     }
+    crate::latest_values::reset_values(session_id);
 }
 
 /// Detach a session's catalogue (decoded stream stops). Called explicitly and
@@ -45,7 +47,9 @@ pub fn detach_catalog(session_id: &str) {
     }
 }
 
-fn attached_catalog(session_id: &str) -> Option<Arc<wiretap_catalog::Catalog>> {
+/// The catalogue attached to `session_id`, if any. Also used by transmit
+/// validation when no explicit catalogue is configured.
+pub fn attached_catalog(session_id: &str) -> Option<Arc<wiretap_catalog::Catalog>> {
     ATTACHED_CATALOGS
         .read()
         .ok()
@@ -165,6 +169,14 @@ pub fn send_new_frames(session_id: &str) {
         return;
     }
 
+    // Frontend still working through earlier batches: leave the offset where
+    // it is so these frames coalesce into the next batch (backpressure).
+    if !backpressure::may_send(session_id) {
+        return;
+    }
+    let skip = backpressure::skip_count(session_id, new_count);
+    let (offset, new_count) = (offset + skip, new_count - skip);
+
     let (frames, _indices, _total) =
         crate::capture_store::get_capture_frames_paginated(&capture_id, offset, new_count);
 
@@ -174,18 +186,15 @@ pub fn send_new_frames(session_id: &str) {
 
     let new_offset = offset + frames.len();
 
-    let payload = protocol::encode_frame_batch(&frames);
-    let msg = protocol::encode_message(MsgType::FrameData, channel, &payload);
-    server.send_to_channel(channel, msg);
-
-    // If a catalogue is attached, decode the same batch once (in Rust) and push
-    // it as a parallel DecodedSignals message — the frontend stops re-decoding.
-    if let Some(catalog) = attached_catalog(session_id) {
-        let decoded = encode_decoded_batch(&frames, &catalog);
-        if !decoded.is_empty() {
-            let dmsg = protocol::encode_message(MsgType::DecodedSignals, channel, &decoded);
-            server.send_to_channel(channel, dmsg);
-        }
+    // Pinned frames still buffered go out ahead of the background batch,
+    // which leaves out the pinned frames already pushed (frame_priority).
+    if let Some(pending) = crate::frame_priority::take_pending(session_id) {
+        send_frame_batch(session_id, channel, &pending);
+    }
+    let frames = crate::frame_priority::without_pinned(session_id, frames);
+    let frames = crate::io::downsample::for_delivery(session_id, frames);
+    if !frames.is_empty() {
+        send_frame_batch(session_id, channel, &frames);
     }
 
     // Push live counts so the frontend renders Frames/Unique straight from the
@@ -202,6 +211,41 @@ pub fn send_new_frames(session_id: &str) {
     }
 }
 
+/// Send a FrameData batch on a session's channel. If a catalogue is attached,
+/// decode the same batch once (in Rust) and push it as a parallel
+/// DecodedSignals message — the frontend stops re-decoding.
+fn send_frame_batch(session_id: &str, channel: u8, frames: &[FrameMessage]) {
+    let Some(server) = ws_server() else { return };
+    let payload = protocol::encode_frame_batch(frames);
+    let msg = protocol::encode_message(MsgType::FrameData, channel, &payload);
+    server.send_to_channel(channel, msg);
+    backpressure::record_sent(session_id);
+
+    if let Some(catalog) = attached_catalog(session_id) {
+        let decoded = encode_decoded_batch(frames, &catalog);
+        if !decoded.is_empty() {
+            let dmsg = protocol::encode_message(MsgType::DecodedSignals, channel, &decoded);
+            server.send_to_channel(channel, dmsg);
+        }
+    }
+}
+
+/// Push a priority batch of pinned frames (frame_priority) straight away,
+/// outside the frames-ready cadence.
+pub fn send_priority_frames(session_id: &str, frames: &[FrameMessage]) {
+    let Some(server) = ws_server() else { return };
+    let Some(channel) = server.channel_for_session(session_id) else { return };
+    send_frame_batch(session_id, channel, frames);
+}
+
+/// Handle a FrameAck from the frontend, sending any batch that was held back
+/// while it was behind.
+pub fn frame_acked(session_id: &str) {
+    if backpressure::acknowledge(session_id) {
+        send_new_frames(session_id);
+    }
+}
+
 /// Reset frame offset for a session to the current capture length.
 /// Called on subscribe so that only frames arriving after subscription are sent.
 pub fn reset_frame_offset(session_id: &str) {
@@ -212,6 +256,18 @@ pub fn reset_frame_offset(session_id: &str) {
     if let Ok(mut offsets) = FRAME_OFFSETS.write() {
         offsets.insert(session_id.to_string(), count);
     }
+    backpressure::clear_session(session_id);
+}
+
+/// Move a session's send offset back by `removed` after its ring capture
+/// dropped that many frames from the head, so delivery resumes at the same
+/// frame rather than skipping the next `removed` new ones.
+pub fn shift_frame_offset(session_id: &str, removed: usize) {
+    if let Ok(mut offsets) = FRAME_OFFSETS.write() {
+        if let Some(offset) = offsets.get_mut(session_id) {
+            *offset = offset.saturating_sub(removed);
+        }
+    }
 }
 
 /// Clear frame offset for a session.
@@ -220,6 +276,7 @@ pub fn clear_frame_offset(session_id: &str) {
     if let Ok(mut offsets) = FRAME_OFFSETS.write() {
         offsets.remove(session_id);
     }
+    backpressure::clear_session(session_id);
 }
 
 /// Decode the frames already delivered to this session's client (everything up to the
@@ -266,6 +323,7 @@ pub fn send_frames(session_id: &str, frames: &[FrameMessage]) {
     let payload = protocol::encode_frame_batch(frames);
     let msg = protocol::encode_message(MsgType::FrameData, channel, &payload);
     server.send_to_channel(channel, msg);
+    backpressure::record_sent(session_id);
 }
 
 /// Send session state change.
@@ -393,6 +451,42 @@ pub fn send_capture_changed(session_id: &str) {
     server.send_to_channel(channel, msg);
 }
 
+/// Send the signals that crossed a session's latest-value change thresholds.
+pub fn send_latest_values_changed(session_id: &str, values: &[crate::latest_values::LatestValue]) {
+    let server = match ws_server() {
+        Some(s) => s,
+        None => return,
+    };
+    let channel = match server.channel_for_session(session_id) {
+        Some(c) => c,
+        None => return,
+    };
+    let payload = match serde_json::to_vec(values) {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+    let msg = protocol::encode_message(MsgType::LatestValuesChanged, channel, &payload);
+    server.send_to_channel(channel, msg);
+}
+
+/// Send J1939 address claim changes seen on a session.
+pub fn send_address_claims_changed(session_id: &str, events: &[crate::j1939::AddressClaimEvent]) {
+    let server = match ws_server() {
+        Some(s) => s,
+        None => return,
+    };
+    let channel = match server.channel_for_session(session_id) {
+        Some(c) => c,
+        None => return,
+    };
+    let payload = match serde_json::to_vec(events) {
+        Ok(p) => p,
+        Err(_) => return,
+    };
+    let msg = protocol::encode_message(MsgType::AddressClaimChanged, channel, &payload);
+    server.send_to_channel(channel, msg);
+}
+
 /// Send session info (speed + subscriber count).
 pub fn send_session_info(session_id: &str, speed: f64, subscriber_count: u16) {
     let server = match ws_server() {
@@ -1,5 +1,6 @@
 // Copyright 2026 Wired Square Pty Ltd
 
+pub mod backpressure;
 pub mod dispatch;
 pub mod protocol;
 pub mod server;
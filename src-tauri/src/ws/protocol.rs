@@ -39,6 +39,16 @@ pub enum MsgType {
     // Global signal: the decoder-catalogue list changed (mutation, decoder-dir
     // change, or filesystem watcher). The frontend reconciles via list_catalogs.
     CatalogListChanged = 0x18,
+    // Signals whose latest decoded value crossed the session's change
+    // threshold (JSON array of LatestValue). Gauge dashboards read values from
+    // this and get_latest_values instead of the frame stream.
+    LatestValuesChanged = 0x19,
+    // Frontend → server: the FrameData batch on this channel has been handled.
+    // Drives delivery backpressure (ws::backpressure).
+    FrameAck         = 0x1A,
+    // J1939 address claim changes on a session (JSON array of
+    // j1939::AddressClaimEvent); get_j1939_network_map returns the full map.
+    AddressClaimChanged = 0x1B,
     Command          = 0x20,
     CommandResponse  = 0x21,
     // Reverse RPC: server (Rust/MCP) → frontend request, frontend → server reply.
@@ -77,6 +87,9 @@ impl TryFrom<u8> for MsgType {
             0x16 => Ok(MsgType::FrameCounts),
             0x17 => Ok(MsgType::OpenAppsChanged),
             0x18 => Ok(MsgType::CatalogListChanged),
+            0x19 => Ok(MsgType::LatestValuesChanged),
+            0x1A => Ok(MsgType::FrameAck),
+            0x1B => Ok(MsgType::AddressClaimChanged),
             0x20 => Ok(MsgType::Command),
             0x21 => Ok(MsgType::CommandResponse),
             0x30 => Ok(MsgType::BridgeRequest),
@@ -611,6 +624,8 @@ pub fn encode_subscribe_nack(error: &str) -> Vec<u8> {
 // ============================================================================
 
 pub fn encode_frame_batch(frames: &[crate::io::FrameMessage]) -> Vec<u8> {
+    use crate::io::Protocol;
+
     // Encode straight into one pre-sized buffer — this runs per send cycle at
     // full frame rate, so avoid the per-frame Vec/clone churn of building
     // CanFrame/FrameEnvelope intermediates. Worst case per frame: envelope
@@ -628,14 +643,11 @@ pub fn encode_frame_batch(frames: &[crate::io::FrameMessage]) -> Vec<u8> {
 
         // 4-byte LE prefix preceding the payload inside the envelope data:
         // CAN id_flags, Modbus register number, none for serial/raw.
-        let (frame_type, prefix) = if frame.is_fd || frame.protocol == "canfd" {
-            (FrameType::CanFd, Some(id_flags))
-        } else if frame.protocol == "can" {
-            (FrameType::Can, Some(id_flags))
-        } else if frame.protocol == "modbus" {
-            (FrameType::Modbus, Some(frame.frame_id))
-        } else {
-            (FrameType::Serial, None)
+        let (frame_type, prefix) = match Protocol::of_frame(frame) {
+            Protocol::CanFd => (FrameType::CanFd, Some(id_flags)),
+            Protocol::Can => (FrameType::Can, Some(id_flags)),
+            Protocol::Modbus => (FrameType::Modbus, Some(frame.frame_id)),
+            Protocol::Serial => (FrameType::Serial, None),
         };
 
         let data_len = prefix.map_or(0, |_| 4) + frame.bytes.len();
@@ -745,6 +757,7 @@ pub fn decode_bridge_response(payload: &[u8]) -> Result<BridgeResponseMsg, Proto
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::io::test_frame;
 
     #[test]
     fn header_round_trip() {
@@ -794,6 +807,7 @@ mod tests {
             MsgType::Unsubscribe,
             MsgType::SubscribeAck,
             MsgType::SubscribeNack,
+            MsgType::FrameAck,
             MsgType::Heartbeat,
             MsgType::Auth,
         ];
@@ -992,16 +1006,10 @@ mod tests {
     fn make_frame_message(protocol: &str, is_fd: bool, frame_id: u32, bus: u8, bytes: Vec<u8>, direction: Option<&str>) -> crate::io::FrameMessage {
         crate::io::FrameMessage {
             protocol: protocol.to_string(),
-            timestamp_us: 999,
-            frame_id,
             bus,
-            dlc: bytes.len() as u8,
-            bytes,
-            is_extended: false,
             is_fd,
-            source_address: None,
-            incomplete: None,
             direction: direction.map(|s| s.to_string()),
+            ..test_frame(frame_id, 999, &bytes)
         }
     }
 
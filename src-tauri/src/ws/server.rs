@@ -44,6 +44,12 @@ pub fn authenticated_connection_count() -> usize {
 static CHANNEL_MAP: once_cell::sync::Lazy<std::sync::RwLock<HashMap<String, u8>>> =
     once_cell::sync::Lazy::new(|| std::sync::RwLock::new(HashMap::new()));
 
+/// Session ID subscribed on `channel`, if any.
+fn session_for_channel(channel: u8) -> Option<String> {
+    let map = CHANNEL_MAP.read().ok()?;
+    map.iter().find(|(_, ch)| **ch == channel).map(|(sid, _)| sid.clone())
+}
+
 pub struct WsServer {
     port: u16,
     token: String,
@@ -642,6 +648,19 @@ async fn connection_read_task(
                         }
                     }
 
+                    MsgType::FrameAck => {
+                        if !authenticated {
+                            continue;
+                        }
+                        // A held-back batch may go out now; reading it touches
+                        // the capture DB, so keep it off the socket task.
+                        if let Some(session_id) = session_for_channel(header.channel) {
+                            tauri::async_runtime::spawn_blocking(move || {
+                                crate::ws::dispatch::frame_acked(&session_id);
+                            });
+                        }
+                    }
+
                     _ => {
                         // Frontend shouldn't send data messages; ignore silently
                     }
@@ -4,7 +4,9 @@
 // Supports multiple named captures with typed storage (frames or bytes).
 
 import { invoke } from "@tauri-apps/api/core";
-import type { IOCapabilities } from "./io";
+import type { IOCapabilities, Protocol } from "./io";
+import { unpackIpcJson } from "../utils/lz4";
+import { decodeFramePage, isFramePage } from "../utils/frameCodec";
 
 /**
  * Capture kind - determines what kind of data is stored
@@ -41,6 +43,34 @@ export interface CaptureMetadata {
   persistent: boolean;
   /** Distinct bus numbers present in this capture's data (sorted) */
   buses: number[];
+  /** Timestamp source and host clock sync when recorded (null for imports/older captures) */
+  timing?: CaptureTiming | null;
+  /** Content seal computed at finalisation (null until sealed; cleared on change) */
+  integrity?: CaptureIntegrity | null;
+  /** Payload bytes held in the capture database (compressed cold chunks plus the inline hot tail) */
+  stored_bytes?: number;
+}
+
+/** Where a session's frame timestamps come from */
+export type TimestampSource = "device" | "host" | "monotonic";
+
+/** Host clock discipline as measured by the backend */
+export interface ClockSyncStatus {
+  /** "ptp" | "chrony" | "timedatectl" | "sntp" | "w32tm" | "unavailable" */
+  method: string;
+  synchronized: boolean | null;
+  /** Host clock offset from the reference (microseconds) */
+  offset_us: number | null;
+  /** Reference server / PTP grandmaster */
+  source: string | null;
+  quality: "good" | "fair" | "poor" | "unknown";
+  checked_at_us: number;
+}
+
+/** Timing provenance recorded on a capture */
+export interface CaptureTiming {
+  timestamp_source: TimestampSource;
+  clock_sync: ClockSyncStatus | null;
 }
 
 /**
@@ -54,6 +84,124 @@ export async function importCsvToCapture(sessionId: string, filePath: string): P
   return invoke("import_csv_to_capture", { session_id: sessionId, file_path: filePath });
 }
 
+/**
+ * Import a candump log (`candump -l` output) into the shared capture.
+ * Interface names map to buses by their trailing digits (can1 → bus 1).
+ *
+ * @param filePath - Full path to the log file
+ * @returns Metadata about the imported data
+ */
+export async function importCandumpToCapture(sessionId: string, filePath: string): Promise<CaptureMetadata> {
+  return invoke("import_candump_to_capture", { session_id: sessionId, file_path: filePath });
+}
+
+/**
+ * Import a Vector BLF log into the shared capture. CAN and CAN FD messages
+ * are imported; BLF channels map to buses as channel - 1.
+ */
+export async function importBlfToCapture(sessionId: string, filePath: string): Promise<CaptureMetadata> {
+  return invoke("import_blf_to_capture", { session_id: sessionId, file_path: filePath });
+}
+
+/**
+ * Import an ASAM MDF4 (.mf4) log, e.g. from a CANedge logger, into the shared
+ * capture. The file is streamed, so multi-GB logs are fine; pass a time range
+ * (µs since the Unix epoch) to import only part of it.
+ */
+export async function importMdf4ToCapture(
+  sessionId: string,
+  filePath: string,
+  startUs?: number,
+  endUs?: number,
+): Promise<CaptureMetadata> {
+  return invoke("import_mdf4_to_capture", {
+    session_id: sessionId,
+    file_path: filePath,
+    start_us: startUs ?? null,
+    end_us: endUs ?? null,
+  });
+}
+
+/**
+ * Import a pcap/pcapng SocketCAN capture (Wireshark, tcpdump -i can0) into the
+ * shared capture. CAN FD frames are kept; error frames keep SocketCAN's
+ * CAN_ERR_FLAG (0x20000000) in their frame ID. pcapng interfaces map to buses
+ * by their name's trailing digits.
+ */
+export async function importPcapToCapture(sessionId: string, filePath: string): Promise<CaptureMetadata> {
+  return invoke("import_pcap_to_capture", { session_id: sessionId, file_path: filePath });
+}
+
+/**
+ * Import a PEAK PCAN-View trace (.trc, versions 1.1 to 2.1) into the shared
+ * capture. TRC buses are 1-based and map to bus - 1.
+ */
+export async function importTrcToCapture(sessionId: string, filePath: string): Promise<CaptureMetadata> {
+  return invoke("import_trc_to_capture", { session_id: sessionId, file_path: filePath });
+}
+
+/**
+ * Export a frame capture as a Vector BLF log for CANalyzer/CANoe.
+ *
+ * @param writeSidecar - Also write `<file>.metadata.json` beside the log
+ * @returns Number of frames written (non-CAN frames are skipped)
+ */
+export async function exportCaptureBlf(
+  captureId: string,
+  filePath: string,
+  writeSidecar = false,
+): Promise<number> {
+  return invoke("export_capture_blf", {
+    capture_id: captureId,
+    file_path: filePath,
+    write_sidecar: writeSidecar,
+  });
+}
+
+/**
+ * Export a frame capture as Apache Parquet for pandas/polars. Columns:
+ * timestamp_us, frame_id, bus, dlc, bytes, is_extended, is_fd, direction,
+ * protocol.
+ *
+ * @param writeSidecar - Also write `<file>.metadata.json` beside the file
+ * @returns Number of frames written
+ */
+export async function exportCaptureParquet(
+  captureId: string,
+  filePath: string,
+  writeSidecar = false,
+): Promise<number> {
+  return invoke("export_capture_parquet", {
+    capture_id: captureId,
+    file_path: filePath,
+    write_sidecar: writeSidecar,
+  });
+}
+
+/** ASC event times: since the capture start, or since the previous frame. */
+export type AscTimeBase = "absolute" | "relative";
+
+/**
+ * Export a frame capture as a Vector ASC log for CANalyzer/CANoe. CAN FD
+ * frames are written as CANFD records.
+ *
+ * @param writeSidecar - Also write `<file>.metadata.json` beside the log
+ * @returns Number of frames written (non-CAN frames are skipped)
+ */
+export async function exportCaptureAsc(
+  captureId: string,
+  filePath: string,
+  timeBase: AscTimeBase = "absolute",
+  writeSidecar = false,
+): Promise<number> {
+  return invoke("export_capture_asc", {
+    capture_id: captureId,
+    file_path: filePath,
+    time_base: timeBase,
+    write_sidecar: writeSidecar,
+  });
+}
+
 // ============================================================================
 // Flexible CSV Import API (column mapping)
 // ============================================================================
@@ -282,7 +430,7 @@ export async function getCaptureFramesPaginated(
   offset: number,
   limit: number
 ): Promise<PaginatedFramesResponse> {
-  return invoke("get_capture_frames_paginated", { capture_id: captureId, offset, limit });
+  return getCaptureFramesPaginatedFiltered(captureId, offset, limit, []);
 }
 
 /**
@@ -299,14 +447,49 @@ export async function getCaptureFramesPaginatedFiltered(
   limit: number,
   selectedIds: number[]
 ): Promise<PaginatedFramesResponse> {
-  return invoke("get_capture_frames_paginated_filtered", {
+  // Packed path: pages arrive in the fixed binary layout (see utils/frameCodec.ts),
+  // falling back to LZ4-packed JSON from backends without it
+  const body = await invoke<ArrayBuffer>("get_capture_frames_paginated_packed", {
     capture_id: captureId,
     offset,
     limit,
     selected_ids: selectedIds,
+    encoding: "binary",
+  });
+  if (isFramePage(body)) return decodeFramePage(body);
+  return unpackIpcJson<PaginatedFramesResponse>(body);
+}
+
+/**
+ * Get a page of frames from one or more protocol sub-streams of a capture
+ * (e.g. only the Modbus frames of a CAN + Modbus session). An empty
+ * `protocols` list returns every frame.
+ */
+export async function getCaptureFramesByProtocol(
+  captureId: string,
+  protocols: Protocol[],
+  offset: number,
+  limit: number
+): Promise<PaginatedFramesResponse> {
+  return invoke("get_capture_frames_by_protocol", {
+    capture_id: captureId,
+    protocols,
+    offset,
+    limit,
   });
 }
 
+/** Frame count for one protocol sub-stream of a capture */
+export interface ProtocolCount {
+  protocol: Protocol;
+  count: number;
+}
+
+/** Count a capture's frames per protocol. Protocols with no frames are omitted. */
+export async function getCaptureProtocolCounts(captureId: string): Promise<ProtocolCount[]> {
+  return invoke("get_capture_protocol_counts", { capture_id: captureId });
+}
+
 /**
  * Response for tail capture frames
  */
@@ -416,6 +599,27 @@ export async function createCaptureSourceSession(
   });
 }
 
+/**
+ * Create one replay session over several timeline sources (e.g. two imported
+ * CSV files). The sources are read lazily and interleaved by timestamp into a
+ * capture owned by the session, so speed and pause apply to all sources
+ * together. Fails if a source is missing, not a frame capture, or empty.
+ *
+ * @param sources - At least two frame captures with data, each with an optional bus renumbering
+ * @param speed - Playback speed (0 = no limit, 1 = realtime)
+ */
+export async function createMultiCaptureSourceSession(
+  sessionId: string,
+  sources: MergeSource[],
+  speed?: number
+): Promise<IOCapabilities> {
+  return invoke("create_multi_capture_source_session", {
+    session_id: sessionId,
+    sources,
+    speed,
+  });
+}
+
 // ============================================================================
 // Multi-Capture Registry API
 // ============================================================================
@@ -467,6 +671,50 @@ export async function clearCaptureData(captureId: string): Promise<void> {
   return invoke("clear_capture", { capture_id: captureId });
 }
 
+export interface TrimResult {
+  /** Number of frames (or bytes) deleted */
+  removed: number;
+  metadata: CaptureMetadata;
+}
+
+/**
+ * Delete a capture's frames outside (`keep_range`) or inside (`remove_range`)
+ * a time range, in place. A missing bound extends to the start or end of the
+ * capture. Not allowed while the capture is streaming.
+ */
+export async function trimCapture(
+  captureId: string,
+  range: { startUs?: number | null; endUs?: number | null },
+  mode: "keep_range" | "remove_range"
+): Promise<TrimResult> {
+  return invoke("trim_capture", {
+    capture_id: captureId,
+    start_us: range.startUs ?? null,
+    end_us: range.endUs ?? null,
+    mode,
+  });
+}
+
+/**
+ * Copy the part of a capture within a time range into a new capture, leaving
+ * the source untouched. A missing bound extends to the start or end of the
+ * capture.
+ *
+ * @returns Metadata of the new capture
+ */
+export async function splitCapture(
+  captureId: string,
+  range: { startUs?: number | null; endUs?: number | null },
+  newName: string
+): Promise<CaptureMetadata> {
+  return invoke("split_capture", {
+    capture_id: captureId,
+    start_us: range.startUs ?? null,
+    end_us: range.endUs ?? null,
+    new_name: newName,
+  });
+}
+
 /**
  * Rename a capture.
  *
@@ -490,6 +738,81 @@ export async function setCapturePersistent(captureId: string, persistent: boolea
   return invoke("set_capture_persistent", { capture_id: captureId, persistent });
 }
 
+/** Ring-buffer retention for a streaming frame capture. */
+export interface RingLimit {
+  /** Keep at most this many frames */
+  max_frames?: number | null;
+  /** Keep only frames within this many milliseconds of the newest */
+  max_duration_ms?: number | null;
+}
+
+/** Payload of the `capture-trimmed` event emitted after a ring trim. */
+export interface CaptureTrimmedPayload {
+  capture_id: string;
+  /** Frames dropped from the head — shift pagination offsets down by this */
+  removed: number;
+  count: number;
+  start_time_us: number | null;
+}
+
+/**
+ * Set or clear a frame capture's ring-buffer limit. Oldest frames are dropped
+ * as new ones stream in; each trim emits `capture-trimmed`.
+ *
+ * @param captureId - The capture ID
+ * @param limit - Max frames and/or max duration, or null for unbounded
+ */
+export async function setCaptureRingLimit(captureId: string, limit: RingLimit | null): Promise<void> {
+  return invoke("set_capture_ring_limit", { capture_id: captureId, limit });
+}
+
+/** Get a capture's ring-buffer limit (null = unbounded). */
+export async function getCaptureRingLimit(captureId: string): Promise<RingLimit | null> {
+  return invoke("get_capture_ring_limit", { capture_id: captureId });
+}
+
+/**
+ * Save a capture to a standalone SQLite capture file, kept outside the app
+ * database so it survives "clear captures on start" and can be reopened.
+ *
+ * @param captureId - The capture to save
+ * @param path - Destination file (replaced if it exists)
+ * @returns Number of frames/bytes written
+ */
+export async function persistCapture(captureId: string, path: string): Promise<number> {
+  return invoke("persist_capture", { capture_id: captureId, path });
+}
+
+/**
+ * Open a capture file saved by persistCapture as a new persistent capture.
+ * Pages are served from disk through the usual paginated APIs.
+ *
+ * @param path - Capture file to open
+ * @returns Metadata of the new capture
+ */
+export async function openPersistedCapture(path: string): Promise<CaptureMetadata> {
+  return invoke("open_persisted_capture", { path });
+}
+
+/** One input to mergeCaptures */
+export interface MergeSource {
+  capture_id: string;
+  /** Bus renumbering for this source (source bus → merged bus); unmapped buses keep their number */
+  bus_map?: Record<number, number>;
+}
+
+/**
+ * Merge frame captures taken separately into a new capture, frames
+ * interleaved by timestamp, so multi-device recordings can be analysed together.
+ *
+ * @param sources - At least two frame captures, each with an optional bus renumbering
+ * @param newName - Name for the merged capture
+ * @returns Metadata of the new capture
+ */
+export async function mergeCaptures(sources: MergeSource[], newName: string): Promise<CaptureMetadata> {
+  return invoke("merge_captures", { sources, new_name: newName });
+}
+
 /**
  * Get metadata for a specific capture by ID.
  *
@@ -736,3 +1059,517 @@ export async function searchCaptureFrames(
     selected_ids: selectedIds,
   });
 }
+
+/** Structured frame search; every non-empty part must match */
+export interface FramePattern {
+  /** Inclusive frame ID ranges; a frame matches any one of them */
+  id_ranges?: { from: number; to: number }[];
+  /** payload[index] & mask == value & mask (shorter frames never match) */
+  byte_masks?: { index: number; mask: number; value: number }[];
+  /** Byte sequence to find anywhere in the payload, as hex ("DE AD" or "dead") */
+  payload_hex?: string | null;
+}
+
+/**
+ * Search a frame capture with a structured pattern (e.g. byte 3 & 0xF0 == 0x20).
+ * Returns 0-based offsets of the hits in the selected-ID-filtered result set.
+ *
+ * @param selectedIds - Frame IDs to include (empty = all)
+ * @param limit - Maximum hits returned (backend default 100,000)
+ */
+export async function searchCapturePattern(
+  captureId: string,
+  pattern: FramePattern,
+  selectedIds: number[],
+  limit?: number
+): Promise<number[]> {
+  return invoke("search_capture_pattern", {
+    capture_id: captureId,
+    pattern,
+    selected_ids: selectedIds,
+    limit: limit ?? null,
+  });
+}
+
+// ============================================================================
+// Annotations
+// ============================================================================
+
+/** A note or marker pinned to a frame or timestamp of a capture */
+export interface CaptureAnnotation {
+  id: number;
+  capture_id: string;
+  /** Time the annotation marks (the pinned frame's timestamp, if any) */
+  timestamp_us: number;
+  /** Current 0-based index of the pinned frame (null when pinned to a time) */
+  frame_index: number | null;
+  text: string;
+  /** CSS colour for the marker (null = default) */
+  color: string | null;
+  created_at: number;
+}
+
+/** Where to pin an annotation: a frame (0-based capture order) or a time */
+export type AnnotationAnchor = { frameIndex: number } | { timestampUs: number };
+
+/**
+ * Pin a note or marker to a capture.
+ * @param captureId - The capture ID
+ * @param anchor - Frame index or timestamp to pin it to
+ * @param text - Note text
+ * @param color - Marker colour (CSS), or null for the default
+ */
+export async function addCaptureAnnotation(
+  captureId: string,
+  anchor: AnnotationAnchor,
+  text: string,
+  color: string | null = null
+): Promise<CaptureAnnotation> {
+  return invoke("add_capture_annotation", {
+    capture_id: captureId,
+    frame_index: "frameIndex" in anchor ? anchor.frameIndex : null,
+    timestamp_us: "timestampUs" in anchor ? anchor.timestampUs : null,
+    text,
+    color,
+  });
+}
+
+/** List a capture's annotations in time order. */
+export async function listCaptureAnnotations(captureId: string): Promise<CaptureAnnotation[]> {
+  return invoke("list_capture_annotations", { capture_id: captureId });
+}
+
+/** Change an annotation's text and colour. */
+export async function updateCaptureAnnotation(id: number, text: string, color: string | null): Promise<void> {
+  return invoke("update_capture_annotation", { id, text, color });
+}
+
+/** Delete an annotation. */
+export async function deleteCaptureAnnotation(id: number): Promise<void> {
+  return invoke("delete_capture_annotation", { id });
+}
+
+// ============================================================================
+// Network capture sharing (WireTAP-to-WireTAP import)
+// ============================================================================
+
+/** State of this instance's capture share listener */
+export interface CaptureShareStatus {
+  running: boolean;
+  port: number | null;
+  /** Token importing instances must present (only while running) */
+  token: string | null;
+}
+
+export type RemoteImportStatus = "running" | "interrupted" | "cancelled" | "completed";
+
+/** A pull of a capture from another WireTAP instance (emitted as `capture-import-progress`) */
+export interface RemoteImport {
+  import_id: string;
+  /** e.g. "http://192.168.1.20:8788" */
+  base_url: string;
+  remote_capture_id: string;
+  local_capture_id: string;
+  kind: CaptureKind;
+  /** Position in the remote capture (rowid for frames, offset for bytes) */
+  cursor: number;
+  imported: number;
+  /** Remote item count when the import started */
+  total: number;
+  status: RemoteImportStatus;
+  error: string | null;
+  /** Unix seconds when the import was started */
+  started_at: number;
+}
+
+/**
+ * Share this instance's captures with other WireTAP instances on the LAN.
+ * @param port - Listen port (default 8788)
+ * @param token - Bearer token to require (generated when omitted)
+ */
+export async function startCaptureShare(port?: number, token?: string): Promise<CaptureShareStatus> {
+  return invoke("start_capture_share", { port, token });
+}
+
+/** Stop sharing captures. */
+export async function stopCaptureShare(): Promise<CaptureShareStatus> {
+  return invoke("stop_capture_share");
+}
+
+export async function getCaptureShareStatus(): Promise<CaptureShareStatus> {
+  return invoke("get_capture_share_status");
+}
+
+/** List the captures another WireTAP instance is sharing. */
+export async function listRemoteCaptures(
+  host: string,
+  port: number | undefined,
+  token: string
+): Promise<CaptureMetadata[]> {
+  return invoke("list_remote_captures", { host, port, token });
+}
+
+/**
+ * Pull a capture from another WireTAP instance into a new local capture.
+ * Progress arrives as `capture-import-progress` events.
+ */
+export async function importRemoteCapture(
+  host: string,
+  port: number | undefined,
+  token: string,
+  captureId: string,
+  name?: string
+): Promise<RemoteImport> {
+  return invoke("import_remote_capture", { host, port, token, capture_id: captureId, name });
+}
+
+/** Resume an interrupted or cancelled import from its last chunk. */
+export async function resumeRemoteImport(importId: string, token: string): Promise<RemoteImport> {
+  return invoke("resume_remote_import", { import_id: importId, token });
+}
+
+/** Stop a running import after its current chunk (resumable). */
+export async function cancelRemoteImport(importId: string): Promise<void> {
+  return invoke("cancel_remote_import", { import_id: importId });
+}
+
+/** All known imports, newest first. */
+export async function listRemoteImports(): Promise<RemoteImport[]> {
+  return invoke("list_remote_imports");
+}
+
+/** Forget a finished or interrupted import (the local capture is kept). */
+export async function forgetRemoteImport(importId: string): Promise<void> {
+  return invoke("forget_remote_import", { import_id: importId });
+}
+
+// ============================================================================
+// Row models (backend-maintained views for virtualised frame tables)
+// ============================================================================
+
+export type RowSortKey = "time" | "frame_id" | "bus" | "dlc" | "count";
+
+/** Filter, sort and grouping for a row model view */
+export interface RowModelSpec {
+  /** Frame IDs to include (empty = all) */
+  frame_ids?: number[];
+  /** Buses to include (empty = all) */
+  buses?: number[];
+  direction?: "rx" | "tx" | null;
+  start_us?: number | null;
+  end_us?: number | null;
+  sort?: RowSortKey;
+  descending?: boolean;
+  /** One row per (frame_id, bus, is_extended), showing the latest frame */
+  group_by_id?: boolean;
+}
+
+export interface RowModelRow {
+  /** 1-based capture position (rowid) of `frame` */
+  capture_index: number;
+  frame: CaptureFrame;
+  /** Frames in the group (grouped views only) */
+  count?: number;
+  /** First timestamp of the group (grouped views only) */
+  first_us?: number;
+}
+
+export interface RowModelPage {
+  view_id: string;
+  revision: number;
+  offset: number;
+  total_rows: number;
+  rows: RowModelRow[];
+}
+
+/** Payload of the `row-model-changed` event */
+export interface RowModelChanged {
+  view_id: string;
+  revision: number;
+  total_rows: number;
+}
+
+/**
+ * Open a row model over a frame capture. Returns the first `limit` rows
+ * (none by default) and the view ID for later range requests.
+ */
+export async function openRowModel(
+  captureId: string,
+  spec: RowModelSpec,
+  limit?: number
+): Promise<RowModelPage> {
+  return invoke("row_model_open", { capture_id: captureId, spec, limit: limit ?? null });
+}
+
+/** Replace a view's filter/sort/grouping. Returns the first `limit` rows. */
+export async function updateRowModel(
+  viewId: string,
+  spec: RowModelSpec,
+  limit?: number
+): Promise<RowModelPage> {
+  return invoke("row_model_update", { view_id: viewId, spec, limit: limit ?? null });
+}
+
+/** Fetch rows [offset, offset + limit) of a view. */
+export async function getRowModelRows(
+  viewId: string,
+  offset: number,
+  limit: number
+): Promise<RowModelPage> {
+  return invoke("row_model_get_rows", { view_id: viewId, offset, limit });
+}
+
+/** Position of a capture rowid within a view, or null when filtered out. */
+export async function findRowModelRow(
+  viewId: string,
+  captureIndex: number
+): Promise<number | null> {
+  return invoke("row_model_find_row", { view_id: viewId, capture_index: captureIndex });
+}
+
+/** Close a view (call when the table unmounts). */
+export async function closeRowModel(viewId: string): Promise<void> {
+  return invoke("row_model_close", { view_id: viewId });
+}
+
+// ============================================================================
+// Capture previews (sparkline series for capture pickers)
+// ============================================================================
+
+export interface BusPreview {
+  bus: number;
+  /** Items per bucket */
+  counts: number[];
+}
+
+/** Sparkline series for one capture; every series has `points` entries */
+export interface CapturePreview {
+  capture_id: string;
+  points: number;
+  /** Item count the preview was computed from */
+  count: number;
+  /** Timestamp of the first bucket (null for an empty capture) */
+  start_us: number | null;
+  bucket_us: number;
+  /** Frames (or bytes) per second in each bucket */
+  rate: number[];
+  buses: BusPreview[];
+  /** Distinct (bus, frame ID) pairs per bucket; empty for byte captures */
+  unique_ids: number[];
+}
+
+/**
+ * Preview series for a list of captures (default 64 points). Served from the
+ * backend cache unless the capture changed since it was computed.
+ */
+export async function getCapturePreviews(
+  captureIds: string[],
+  points?: number
+): Promise<CapturePreview[]> {
+  return invoke("get_capture_previews", { capture_ids: captureIds, points: points ?? null });
+}
+
+// ============================================================================
+// Capture diff (per-ID comparison of two captures)
+// ============================================================================
+
+/** An ID seen in only one of the compared captures */
+export interface IdSummary {
+  frame_id: number;
+  is_extended: boolean;
+  count: number;
+  rate_hz: number | null;
+}
+
+/** A byte position whose observed values differ (no values = beyond that side's payload) */
+export interface ByteDiff {
+  index: number;
+  distinct_a: number;
+  distinct_b: number;
+  /** Values seen only in capture A (first 16) */
+  only_in_a: number[];
+  /** Values seen only in capture B (first 16) */
+  only_in_b: number[];
+}
+
+/** An ID present in both captures whose bytes or rate differ */
+export interface IdDiff {
+  frame_id: number;
+  is_extended: boolean;
+  count_a: number;
+  count_b: number;
+  rate_a_hz: number | null;
+  rate_b_hz: number | null;
+  /** Rates differ by more than 10% */
+  rate_changed: boolean;
+  bytes: ByteDiff[];
+}
+
+export interface CaptureDiff {
+  capture_a: string;
+  capture_b: string;
+  only_in_a: IdSummary[];
+  only_in_b: IdSummary[];
+  changed: IdDiff[];
+  /** IDs present in both with identical values and rate */
+  unchanged: number;
+}
+
+/**
+ * Compare two frame captures ID by ID (e.g. "feature off" vs "feature on").
+ * IDs are matched across buses.
+ */
+export async function diffCaptures(captureA: string, captureB: string): Promise<CaptureDiff> {
+  return invoke("diff_captures", { capture_a: captureA, capture_b: captureB });
+}
+
+// ============================================================================
+// Capture integrity (content seals and signed export manifests)
+// ============================================================================
+
+/** Ed25519 signature over a seal or export manifest */
+export interface IntegritySignature {
+  algorithm: "ed25519";
+  /** Hex public key of the signer */
+  public_key: string;
+  signature: string;
+}
+
+/** SHA-256 seal over a capture's contents */
+export interface CaptureIntegrity {
+  algorithm: "sha256";
+  digest: string;
+  /** Frames/bytes covered by the digest */
+  count: number;
+  /** Unix seconds */
+  sealed_at: number;
+  signature: IntegritySignature | null;
+}
+
+/** Manifest written beside an export (`<file>.integrity.json`) */
+export interface ExportManifest {
+  capture_id: string;
+  capture_name: string;
+  format: string;
+  /** Hex SHA-256 of the exported file contents */
+  export_sha256: string;
+  capture: CaptureIntegrity | null;
+  created_at: number;
+  signature: IntegritySignature | null;
+}
+
+export interface IntegrityVerification {
+  capture_id: string;
+  /** null when the capture was never sealed */
+  sealed_digest: string | null;
+  current_digest: string;
+  current_count: number;
+  digest_matches: boolean;
+  /** null when the seal is unsigned */
+  signature_valid: boolean | null;
+}
+
+/** Seal a capture now (imports, or captures changed since finalisation). */
+export async function sealCaptureIntegrity(captureId: string): Promise<CaptureIntegrity> {
+  return invoke("seal_capture_integrity", { capture_id: captureId });
+}
+
+/** Re-hash a capture and check it against its seal and signature. */
+export async function verifyCaptureIntegrity(captureId: string): Promise<IntegrityVerification> {
+  return invoke("verify_capture_integrity", { capture_id: captureId });
+}
+
+/** Build (and sign, when a key exists) the manifest for an export. */
+export async function buildExportManifest(
+  captureId: string,
+  format: string,
+  exportSha256: string
+): Promise<ExportManifest> {
+  return invoke("build_export_manifest", {
+    capture_id: captureId,
+    format,
+    export_sha256: exportSha256,
+  });
+}
+
+/** Suffix of the metadata sidecar written beside an export. */
+export const EXPORT_SIDECAR_SUFFIX = ".metadata.json";
+
+/** A note pinned to a point or span of an exported capture. */
+export interface SidecarAnnotation {
+  label: string;
+  start_time_us?: number | null;
+  end_time_us?: number | null;
+  note?: string | null;
+  color?: string | null;
+}
+
+export interface SidecarRequest {
+  capture_id: string;
+  format: string;
+  /** Defaults to the capture's owning session */
+  session_id?: string | null;
+  export_sha256?: string | null;
+  bus_names?: Record<number, string>;
+  annotations?: SidecarAnnotation[];
+}
+
+/**
+ * Companion metadata for an export: capture time range, sources, buses,
+ * attached catalogue versions, annotations and integrity hashes.
+ */
+export interface ExportSidecar {
+  schema: string;
+  generated_at: string;
+  format: string;
+  capture: {
+    id: string;
+    name: string;
+    kind: "frames" | "bytes";
+    count: number;
+    start_time_us: number | null;
+    end_time_us: number | null;
+    start_time: string | null;
+    end_time: string | null;
+    timing: unknown | null;
+  };
+  session_id: string | null;
+  sources: { profile_id: string }[];
+  source_names: string[];
+  buses: { bus: number; name: string | null }[];
+  catalogs: { path: string; name: string | null; version: unknown | null; sha256: string | null }[];
+  annotations: SidecarAnnotation[];
+  integrity: { export_sha256: string | null; capture: CaptureIntegrity | null };
+}
+
+/** Build the metadata sidecar for an export (written by the caller). */
+export async function buildExportSidecar(request: SidecarRequest): Promise<ExportSidecar> {
+  return invoke("build_export_sidecar", { request });
+}
+
+/** Check a manifest's signature. null when unsigned. */
+export async function verifyExportManifest(manifest: ExportManifest): Promise<boolean | null> {
+  return invoke("verify_export_manifest", { manifest });
+}
+
+/** Public key (hex) of the capture signing key, or null if none. */
+export async function getCaptureSigningKey(): Promise<string | null> {
+  return invoke("get_capture_signing_key");
+}
+
+/** Create (or replace) the capture signing key. Returns the public key. */
+export async function generateCaptureSigningKey(): Promise<string> {
+  return invoke("generate_capture_signing_key");
+}
+
+export async function deleteCaptureSigningKey(): Promise<void> {
+  return invoke("delete_capture_signing_key");
+}
+
+/** Hex SHA-256 of export contents (for `buildExportManifest`). */
+export async function sha256Hex(content: string | Uint8Array): Promise<string> {
+  const data = typeof content === "string" ? new TextEncoder().encode(content) : content;
+  const digest = await crypto.subtle.digest("SHA-256", data as BufferSource);
+  return Array.from(new Uint8Array(digest), (b) => b.toString(16).padStart(2, "0")).join("");
+}
+
@@ -199,6 +199,14 @@ export async function importDbcWs(content: string): Promise<string> {
   return await wsTransport.command<string>("catalog.import_dbc", { content });
 }
 
+/**
+ * Import a CANopen EDS/DCF to catalogue TOML: one frame per default PDO
+ * mapping plus heartbeat and EMCY. `nodeId` defaults to the DCF's NodeID, else 1.
+ */
+export async function importEdsWs(content: string, nodeId?: number): Promise<string> {
+  return await wsTransport.command<string>("catalog.import_eds", { content, node_id: nodeId });
+}
+
 /** Export catalogue TOML to DBC text via the crate (over the WebSocket). */
 export async function exportDbcWs(
   content: string,
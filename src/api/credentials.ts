@@ -55,7 +55,7 @@ export async function deleteAllCredentials(profileId: string): Promise<void> {
  * Fields that should be stored securely in the keyring.
  * When saving a profile, these fields are extracted and stored separately.
  */
-export const SECURE_FIELDS = ["password", "token", "api_key", "secret"] as const;
+export const SECURE_FIELDS = ["password", "token", "api_key", "secret", "tls_client_cert", "tls_client_key"] as const;
 
 export type SecureField = (typeof SECURE_FIELDS)[number];
 
@@ -448,18 +448,73 @@ export interface DatabaseActivityResult {
   queries: DatabaseActivity[];
   /** Active sessions connected to the database */
   sessions: DatabaseActivity[];
+  /** Frame traffic breakdown (only when includeTraffic is set) */
+  traffic?: TrafficActivity;
+}
+
+/** Frame activity of one bus or source over the traffic window */
+export interface ActivityGroup {
+  /** Bus number or source value */
+  key: string;
+  frame_count: number;
+  first_us: number | null;
+  last_us: number | null;
+  frames_per_sec: number;
+  /** Buckets with no frames — a bus that went quiet shows up here */
+  empty_buckets: number;
+}
+
+/** One heatmap row: frame count per bucket, parallel to bucket_starts_us */
+export interface ActivitySeries {
+  key: string;
+  counts: number[];
+}
+
+/** Time-bucketed frame traffic broken down by bus and source */
+export interface TrafficActivity {
+  start_us: number;
+  end_us: number;
+  /** Effective bucket width (widened to cap the bucket count) */
+  bucket_secs: number;
+  bucket_starts_us: number[];
+  by_bus: ActivityGroup[];
+  bus_series: ActivitySeries[];
+  /** Column the source breakdown groups by, or null when the table has none */
+  source_column: string | null;
+  by_source: ActivityGroup[];
+  source_series: ActivitySeries[];
+  /** True when read from the hourly rollup */
+  from_rollup: boolean;
+}
+
+/** Options for the frame traffic part of queryActivity */
+export interface ActivityTrafficOptions {
+  /** ISO start of the window (default: one hour before end) */
+  startTime?: string;
+  /** ISO end of the window (default: now) */
+  endTime?: string;
+  /** Bucket width in seconds (default 60) */
+  bucketSecs?: number;
 }
 
 /**
  * Query pg_stat_activity for running queries and active sessions.
  *
  * Returns information about queries currently running on the database
- * and all active sessions (connections).
+ * and all active sessions (connections). Pass `traffic` to also get
+ * per-bus/per-source frame counts and heatmap series for a time window.
  */
 export async function queryActivity(
-  profileId: string
+  profileId: string,
+  traffic?: ActivityTrafficOptions
 ): Promise<DatabaseActivityResult> {
-  return invoke("db_query_activity", { profileId });
+  return invoke("db_query_activity", {
+    profileId,
+    includeTraffic: traffic !== undefined,
+    startTime: traffic?.startTime ?? null,
+    endTime: traffic?.endTime ?? null,
+    bucketSecs: traffic?.bucketSecs ?? null,
+  });
 }
 
 /**
@@ -20,6 +20,10 @@ export interface GsUsbDeviceInfo {
   interface_name: string | null;
   /** Whether the interface is currently up (Linux only) */
   interface_up: boolean | null;
+  /** CAN channel this entry stands for (null when it couldn't be read) */
+  channel: number | null;
+  /** Number of CAN channels on the device (Windows/macOS, when queried) */
+  channel_count: number | null;
 }
 
 /**
@@ -42,7 +46,7 @@ export interface GsUsbProbeResult {
 }
 
 /**
- * List all gs_usb devices connected to the system.
+ * List all gs_usb devices connected to the system, one entry per CAN channel.
  * On Linux, includes the SocketCAN interface name if available.
  */
 export async function listGsUsbDevices(): Promise<GsUsbDeviceInfo[]> {
@@ -63,35 +67,91 @@ export async function getCanSetupCommand(
   });
 }
 
+/**
+ * Current setup of a SocketCAN interface (Linux only).
+ */
+export interface CanInterfaceStatus {
+  interface: string;
+  /** Administratively up */
+  up: boolean;
+  /** Nominal bitrate (null for virtual interfaces) */
+  bitrate: number | null;
+  /** CAN FD data phase bitrate */
+  data_bitrate: number | null;
+  fd: boolean;
+  listen_only: boolean;
+  /** Controller state (ERROR-ACTIVE, ERROR-PASSIVE, BUS-OFF, ...) */
+  state: string | null;
+}
+
+/**
+ * Configure a CAN interface (bitrate, FD, listen-only) and bring it up.
+ * Linux only; polkit asks for the user's password.
+ */
+export async function configureCanInterface(
+  interfaceName: string,
+  bitrate: number,
+  enableFd: boolean,
+  dataBitrate: number | null,
+  listenOnly: boolean
+): Promise<void> {
+  return invoke("configure_can_interface", {
+    interface: interfaceName,
+    bitrate,
+    enable_fd: enableFd,
+    data_bitrate: dataBitrate,
+    listen_only: listenOnly,
+  });
+}
+
+/**
+ * Bring a CAN interface up or down. Linux only; polkit asks for the user's password.
+ */
+export async function setCanInterfaceUp(interfaceName: string, up: boolean): Promise<void> {
+  return invoke("set_can_interface_up", { interface: interfaceName, up });
+}
+
+/**
+ * Read a CAN interface's current setup (Linux only).
+ */
+export async function getCanInterfaceStatus(interfaceName: string): Promise<CanInterfaceStatus> {
+  return invoke("get_can_interface_status", { interface: interfaceName });
+}
+
 /**
  * Probe a gs_usb device to get its capabilities.
- * Only available on Windows (Linux uses SocketCAN).
+ * Available on Windows and macOS, and on Linux for profiles in direct USB mode
+ * (otherwise the kernel driver owns the device and SocketCAN is used).
  * Pass serial number for stable device matching across USB re-enumeration.
  */
 export async function probeGsUsbDevice(
   bus: number,
   address: number,
-  serial?: string | null
+  serial?: string | null,
+  directUsb?: boolean
 ): Promise<GsUsbProbeResult> {
-  return invoke("probe_gs_usb_device", { bus, address, serial: serial ?? null });
+  return invoke("probe_gs_usb_device", { bus, address, serial: serial ?? null, direct_usb: directUsb ?? null });
 }
 
 /**
  * Create a unique device ID for display and selection purposes.
  * Preference order: interface_name (Linux) > serial number > bus:address
  * Serial numbers are stable across USB reconnects, unlike bus:address.
+ * Channels after the first get a `#<channel>` suffix, so channel 0 keeps the
+ * ID single-channel devices always had.
  */
 export function createDeviceId(device: GsUsbDeviceInfo): string {
   // Use interface name on Linux if available (most specific)
   if (device.interface_name) {
     return device.interface_name;
   }
+  const channelSuffix = device.channel ? `#${device.channel}` : "";
   // Prefer serial number when available (stable across reconnects)
   if (device.serial) {
-    return device.serial;
+    return `${device.serial}${channelSuffix}`;
   }
   // Fall back to bus:address (may change on reconnect)
-  return `${device.bus}:${device.address}`;
+  return `${device.bus}:${device.address}${channelSuffix}`;
 }
 
 /**
@@ -111,5 +171,9 @@ export function formatDeviceDisplay(device: GsUsbDeviceInfo): string {
     parts.push(`- USB ${device.bus}:${device.address}`);
   }
 
+  if (device.channel !== null && (device.channel_count ?? 1) > 1) {
+    parts.push(`ch${device.channel}`);
+  }
+
   return parts.join(" ");
 }
@@ -15,6 +15,15 @@ export {
   tlog,
 } from "./settings";
 
+// Message catalogue API
+export {
+  getMessageCatalogue,
+  describeMessage,
+  formatMessage,
+  type MessageDef,
+  type BackendMessage,
+} from "./messages";
+
 // Catalog API
 export {
   openCatalog,
@@ -46,7 +55,10 @@ export {
 export {
   updateMenuState,
   updateBookmarksMenu,
+  getMenuState,
+  syncMenuState,
   type MenuState,
+  type MenuSessionState,
   type BookmarkMenuInfo,
 } from "./menu";
 
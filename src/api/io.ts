@@ -4,8 +4,11 @@
 // Provides a unified interface for reading and writing CAN data.
 
 import { invoke } from "@tauri-apps/api/core";
+import type { TransmitValidationIssue } from "./transmit";
 import type { FrameMessage } from "../types/frame";
+import type { CaptureMetadata, CaptureTiming, ClockSyncStatus, TimestampSource } from "./capture";
 import type { SerialFrameConfig } from "../utils/frameExport";
+import type { PlaybackPosition, ProtocolStream, StreamEndedInfo } from "../types/backend";
 
 // ============================================================================
 // Interface Traits
@@ -51,8 +54,15 @@ export interface SessionDataStreams {
   rx_frames: boolean;
   /** Whether this session emits raw byte streams (serial-raw-bytes events) */
   rx_bytes: boolean;
+  /**
+   * Per-protocol frame sub-streams (protocol → output buses) for multi-source
+   * sessions mixing protocols. Empty when all frames share traits.protocols.
+   */
+  sub_streams?: ProtocolStream[];
 }
 
+export type { ProtocolStream };
+
 /** A single raw byte with timestamp, as emitted by serial/byte-stream sessions */
 export interface RawByteEntry {
   byte: number;
@@ -578,15 +588,9 @@ export interface StepResult {
 
 /**
  * Playback position - emitted with playback-time events during capture streaming.
+ * Generated from the Rust struct (src/types/backend.d.ts).
  */
-export interface PlaybackPosition {
-  /** Current timestamp in microseconds */
-  timestamp_us: number;
-  /** Current frame index (0-based) */
-  frame_index: number;
-  /** Total frame count in capture (optional, for recorded sources) */
-  frame_count?: number;
-}
+export type { PlaybackPosition };
 
 /**
  * Step one frame forward or backward in the capture.
@@ -616,6 +620,266 @@ export async function stepCaptureFrame(
   });
 }
 
+/** Live metrics for one output bus over the last interval */
+export interface BusMetrics {
+  bus: number;
+  frames_per_sec: number;
+  bytes_per_sec: number;
+  /** Estimated bus load 0-100, stuff bits excluded (null when the bitrate isn't known) */
+  load_percent: number | null;
+  /** Incomplete frames and CAN error frames in the interval */
+  errors: number;
+}
+
+/** CAN error frames by class since the session started */
+export interface CanErrorCounts {
+  bus_off: number;
+  ack: number;
+  stuff: number;
+  /** Every other class (form, bit, CRC, controller state, ...) */
+  other: number;
+}
+
+/**
+ * Payload of the `session-metrics` event, emitted about once a second by
+ * multi-source sessions while they run.
+ */
+export interface SessionMetricsPayload {
+  session_id: string;
+  /** Length of the window the rates cover */
+  interval_ms: number;
+  frames_per_sec: number;
+  bytes_per_sec: number;
+  buses: BusMetrics[];
+  /** Source errors, incomplete frames and CAN error frames in the interval */
+  errors: number;
+  /** CAN error frames by class, cumulative for the session */
+  can_errors: CanErrorCounts;
+  /** Frames left out of delivery by listener rate limits in the interval */
+  rate_limited: number;
+  /** Delivery sends held back while the frontend was behind (merged into a later batch) */
+  coalesced: number;
+  /** Frames skipped for delivery because the held-back backlog overflowed */
+  dropped: number;
+}
+
+// ============================================================================
+// Snapshot triggers
+// ============================================================================
+
+/**
+ * Masked frame match. `data` is compared against the payload under
+ * `data_mask` (missing mask bytes default to 0xFF).
+ */
+export interface TriggerFramePattern {
+  frame_id: number;
+  /** Mask applied to both IDs (default: exact match) */
+  id_mask?: number | null;
+  /** Restrict to one bus (default: any bus) */
+  bus?: number | null;
+  data?: number[];
+  data_mask?: number[];
+}
+
+/** A rule that copies the frames around each match into a new capture */
+export interface SnapshotTrigger {
+  name?: string | null;
+  trigger: TriggerFramePattern;
+  /** Frame time kept before the trigger frame (default 5000) */
+  pre_trigger_ms?: number;
+  /** Frame time kept after the trigger frame (default 5000) */
+  post_trigger_ms?: number;
+}
+
+export interface SnapshotTriggerStats {
+  name: string | null;
+  fired: number;
+  snapshots: number;
+  last_capture_id: string | null;
+  last_error: string | null;
+}
+
+export interface SnapshotTriggerState {
+  session_id: string;
+  rules: SnapshotTriggerStats[];
+  /** Snapshots waiting for their post-trigger window */
+  pending: number;
+}
+
+/** Payload of the `trigger-fired` event, emitted once a snapshot is written */
+export interface TriggerFiredPayload {
+  session_id: string;
+  /** Index of the rule in the list given to setSessionTriggers */
+  rule: number;
+  name: string | null;
+  trigger_us: number;
+  /** The new orphaned capture holding the snapshot */
+  capture: CaptureMetadata;
+}
+
+/**
+ * Install snapshot trigger rules on a session, replacing any existing ones.
+ * Each match copies the pre/post-trigger window of the session capture into
+ * a new orphaned capture and emits `trigger-fired`.
+ */
+export async function setSessionTriggers(sessionId: string, rules: SnapshotTrigger[]): Promise<SnapshotTriggerState> {
+  return invoke("set_session_triggers", { session_id: sessionId, rules });
+}
+
+/** Remove a session's snapshot triggers (pending snapshots are written with what they have). */
+export async function clearSessionTriggers(sessionId: string): Promise<void> {
+  return invoke("clear_session_triggers", { session_id: sessionId });
+}
+
+/** Per-rule counters for a session, or null when no rules are installed. */
+export async function getSessionTriggers(sessionId: string): Promise<SnapshotTriggerState | null> {
+  return invoke("get_session_triggers", { session_id: sessionId });
+}
+
+/** When a scheduled recording starts and what ends it. */
+export interface RecordingSchedule {
+  /** Wall-clock start in microseconds since the epoch (omit or past = now) */
+  start_at_us?: number;
+  /** Stop after recording for this long */
+  duration_ms?: number;
+  /** Stop after this many frames (checked every 250 ms, so may overshoot slightly) */
+  max_frames?: number;
+}
+
+export type RecordingPhase = "armed" | "recording" | "completed" | "cancelled" | "failed";
+
+/** A session's recording schedule and progress, also the `recording-changed` payload */
+export interface RecordingState {
+  session_id: string;
+  schedule: RecordingSchedule;
+  phase: RecordingPhase;
+  started_at_us: number | null;
+  frames: number;
+  /** Why the recording stopped ("stopped" = the session was stopped by something else) */
+  ended: "duration" | "frames" | "stopped" | null;
+  /** The orphaned capture holding the recording */
+  capture_id: string | null;
+  error: string | null;
+}
+
+/**
+ * Arm a session to record, replacing any existing schedule. At the start time
+ * the session starts with a fresh capture; when the duration or frame limit is
+ * reached it is suspended and the capture orphaned. Needs at least one limit.
+ */
+export async function scheduleRecording(sessionId: string, schedule: RecordingSchedule): Promise<RecordingState> {
+  return invoke("schedule_recording", { session_id: sessionId, schedule });
+}
+
+/** Cancel an armed or running recording. A running one keeps what it has recorded so far. */
+export async function cancelRecording(sessionId: string): Promise<RecordingState | null> {
+  return invoke("cancel_recording", { session_id: sessionId });
+}
+
+/** A session's recording schedule and progress, or null if none was armed. */
+export async function getRecordingState(sessionId: string): Promise<RecordingState | null> {
+  return invoke("get_recording_state", { session_id: sessionId });
+}
+
+/** When a session's recording is split into a new capture. Any limit reached starts a new segment. */
+export interface RotationPolicy {
+  /** Split after this many frames */
+  max_frames?: number;
+  /** Split after this many payload bytes */
+  max_bytes?: number;
+  /** Split once a segment spans this long (frame timestamps) */
+  max_duration_ms?: number;
+}
+
+export type RotationReason = "frames" | "bytes" | "duration";
+
+/** A session's rotation policy and its current segment */
+export interface RotationState {
+  session_id: string;
+  policy: RotationPolicy;
+  /** Segments closed so far */
+  segments: number;
+  frames: number;
+  bytes: number;
+  span_ms: number;
+}
+
+/** `buffer-orphaned` payload: a full segment, now a standalone capture */
+export interface BufferOrphanedPayload {
+  session_id: string;
+  capture_id: string;
+  name: string;
+  kind: "frames" | "bytes";
+  count: number;
+  reason: RotationReason;
+}
+
+/** `buffer-created` payload: the capture the session moved on to */
+export interface BufferCreatedPayload {
+  session_id: string;
+  capture_id: string;
+  name: string;
+  kind: "frames" | "bytes";
+}
+
+/**
+ * Split a session's recording whenever a limit is reached, replacing any
+ * existing policy. Each full segment is finalised and orphaned, and the
+ * session continues in a fresh capture. A segment may overshoot by one batch.
+ */
+export async function setCaptureRotation(sessionId: string, policy: RotationPolicy): Promise<RotationState> {
+  return invoke("set_capture_rotation", { session_id: sessionId, policy });
+}
+
+/** Stop splitting a session's recording. */
+export async function clearCaptureRotation(sessionId: string): Promise<void> {
+  return invoke("clear_capture_rotation", { session_id: sessionId });
+}
+
+/** A session's rotation policy and current segment, or null if none is set. */
+export async function getCaptureRotation(sessionId: string): Promise<RotationState | null> {
+  return invoke("get_capture_rotation", { session_id: sessionId });
+}
+
+// ============================================================================
+// Network bridge publishing
+// ============================================================================
+
+/** A session being served to WireTAP network bridge clients. */
+export interface NetworkPublisherInfo {
+  session_id: string;
+  bind_address: string;
+  port: number;
+  /** Bridge clients currently connected */
+  clients: number;
+}
+
+/**
+ * Serve a session's live frames over TCP so a `wiretap_net` profile on
+ * another machine can stream them. Listens on all interfaces on port 1339
+ * unless told otherwise; port 0 picks a free port.
+ */
+export async function startNetworkPublisher(
+  sessionId: string,
+  options: { port?: number; bindAddress?: string } = {}
+): Promise<NetworkPublisherInfo> {
+  return invoke("start_network_publisher", {
+    session_id: sessionId,
+    port: options.port ?? null,
+    bind_address: options.bindAddress ?? null,
+  });
+}
+
+/** Stop publishing a session. Returns false if it wasn't being published. */
+export async function stopNetworkPublisher(sessionId: string): Promise<boolean> {
+  return invoke("stop_network_publisher", { session_id: sessionId });
+}
+
+/** Sessions currently being published. */
+export async function listNetworkPublishers(): Promise<NetworkPublisherInfo[]> {
+  return invoke("list_network_publishers");
+}
+
 /**
  * Payload sent when a session is suspended (stopped with capture available).
  */
@@ -807,6 +1071,8 @@ export interface TransmitResult {
   timestamp_us: number;
   /** Error message if transmission failed */
   error?: string;
+  /** Catalog validation issues (warn mode: sent anyway; reject mode: why it wasn't) */
+  issues?: TransmitValidationIssue[];
 }
 
 /**
@@ -992,6 +1258,28 @@ export async function removeSourceFromSession(
   });
 }
 
+/**
+ * Replace one source of a multi-source session with another (e.g. a flaky USB
+ * adapter with its TCP twin) without tearing the session down. Leave
+ * `busMappings` empty to keep the old source's mappings and output buses.
+ * Keeps the same session ID and listeners.
+ * @param sessionId The session ID
+ * @param oldProfileId The profile ID of the source to replace
+ * @param newSource The source configuration to use instead
+ * @returns Updated IOCapabilities for the session
+ */
+export async function replaceSourceInSession(
+  sessionId: string,
+  oldProfileId: string,
+  newSource: MultiSourceInput
+): Promise<IOCapabilities> {
+  return invoke("replace_source_in_session_cmd", {
+    session_id: sessionId,
+    old_profile_id: oldProfileId,
+    new_source_config: toRustSource(newSource),
+  });
+}
+
 /**
  * Update bus mappings for a source in a multi-source session.
  * Hot-swaps the source by removing and re-adding it with updated mappings.
@@ -1084,6 +1372,44 @@ export async function setSessionSubscriberActive(
   });
 }
 
+/**
+ * Limit a subscriber to at most `maxFramesPerSecond` frames per frame ID
+ * (the newest in each interval). Subscribers sharing a session get the least
+ * restrictive limit among them. Pass null to receive every frame.
+ */
+export async function setSessionSubscriberRateLimit(
+  sessionId: string,
+  subscriberId: string,
+  maxFramesPerSecond: number | null
+): Promise<void> {
+  return invoke("set_session_subscriber_rate_limit", {
+    session_id: sessionId,
+    subscriber_id: subscriberId,
+    max_frames_per_second: maxFramesPerSecond,
+  });
+}
+
+/**
+ * Per-session overrides of the merge task's batching. Unset fields use the
+ * `batch_max_frames` / `batch_max_latency_ms` settings defaults.
+ */
+export interface BatchingOptions {
+  /** Frames held before a batch is flushed (small = low latency, large = throughput) */
+  batch_max_frames?: number | null;
+  /** Longest a frame waits before its batch is flushed */
+  batch_max_latency_ms?: number | null;
+}
+
+/** Override a session's batching. Applies from its next flush. */
+export async function setSessionBatching(sessionId: string, options: BatchingOptions): Promise<void> {
+  return invoke("set_session_batching", { session_id: sessionId, options });
+}
+
+/** Get a session's batching overrides. */
+export async function getSessionBatching(sessionId: string): Promise<BatchingOptions> {
+  return invoke("get_session_batching", { session_id: sessionId });
+}
+
 // ============================================================================
 // GVRET Device Probing
 // ============================================================================
@@ -1124,6 +1450,155 @@ export async function probeGvretDevice(profileId: string): Promise<GvretDeviceIn
   return invoke("probe_gvret_device", { profile_id: profileId });
 }
 
+/** One GVRET bus's setup as the device reports it */
+export interface GvretBusParams {
+  enabled: boolean;
+  listen_only: boolean;
+  bitrate: number;
+}
+
+/** Changes to one GVRET bus; omitted fields keep the device's value */
+export interface GvretBusChange {
+  /** 10000–1000000 bit/s */
+  bitrate?: number;
+  enabled?: boolean;
+  listen_only?: boolean;
+}
+
+/**
+ * Change a GVRET device's bus setup (buses 0 and 1 only). Refused while a
+ * session is using the profile. Returns both buses as the device reports them
+ * afterwards.
+ */
+export async function configureGvretBus(
+  profileId: string,
+  bus: number,
+  change: GvretBusChange
+): Promise<GvretBusParams[]> {
+  return invoke("configure_gvret_bus", { profile_id: profileId, bus, ...change });
+}
+
+/** A GVRET TCP device found on the local network */
+export interface GvretCandidate {
+  host: string;
+  port: number;
+  /** Beacon payload or mDNS instance name, when available */
+  name: string | null;
+  source: "beacon" | "mdns";
+  /** Set when the device answered a GVRET probe */
+  bus_count: number | null;
+}
+
+export interface GvretDiscoveryResult {
+  /** Responding devices first */
+  candidates: GvretCandidate[];
+  /** Non-fatal problems (e.g. beacon port already in use) */
+  errors: string[];
+}
+
+/**
+ * Scan the LAN for GVRET TCP devices (ESP32RET/WiCAN UDP beacon on port 17222
+ * and mDNS), probing each candidate. Takes roughly `scanMs` (default 3000) plus
+ * a short probe.
+ */
+export async function discoverGvretDevices(scanMs?: number): Promise<GvretDiscoveryResult> {
+  return invoke("discover_gvret_devices", { scan_ms: scanMs ?? null });
+}
+
+/** Per-topic counters of the embedded MQTT broker */
+export interface MqttBrokerTopicStats {
+  topic: string;
+  messages: number;
+  last_us: number;
+}
+
+/** Embedded MQTT broker state */
+export interface MqttBrokerStatus {
+  running: boolean;
+  bind: string | null;
+  port: number | null;
+  clients: number;
+  messages_in: number;
+  /** Most active topics first */
+  topics: MqttBrokerTopicStats[];
+}
+
+/**
+ * Start WireTAP's embedded MQTT broker (default 0.0.0.0:1883) so devices can
+ * publish to this machine directly. Ingest by pointing an MQTT profile at
+ * localhost on the same port. Errors when the build omits the broker.
+ */
+export async function startMqttBroker(options?: {
+  port?: number;
+  bind?: string;
+  username?: string;
+  password?: string;
+}): Promise<MqttBrokerStatus> {
+  return invoke("mqtt_broker_start", {
+    port: options?.port ?? null,
+    bind: options?.bind ?? null,
+    username: options?.username ?? null,
+    password: options?.password ?? null,
+  });
+}
+
+/** Stop the embedded MQTT broker. */
+export async function stopMqttBroker(): Promise<MqttBrokerStatus> {
+  return invoke("mqtt_broker_stop");
+}
+
+/** Embedded MQTT broker state (clients, messages, topics). */
+export async function getMqttBrokerStatus(): Promise<MqttBrokerStatus> {
+  return invoke("get_mqtt_broker_status");
+}
+
+/** Wire format of a session mirrored to MQTT */
+export type MqttPublishFormat = "savvycan" | "binary";
+
+/** State of a session's MQTT publisher */
+export interface MqttPublishStatus {
+  session_id: string;
+  host: string;
+  port: number;
+  topic: string;
+  format: MqttPublishFormat;
+  connected: boolean;
+  /** Messages sent to the broker */
+  published: number;
+  /** Messages dropped while the broker was slow or unreachable */
+  dropped: number;
+  last_error: string | null;
+}
+
+/**
+ * Mirror a session's frames to a broker topic, using the broker connection
+ * of an MQTT profile. With the SavvyCAN format, `{bus}` and `{id}` in the
+ * topic are filled in per frame.
+ */
+export async function startMqttPublish(
+  sessionId: string,
+  profileId: string,
+  topic: string,
+  format: MqttPublishFormat
+): Promise<MqttPublishStatus> {
+  return invoke("mqtt_publish_start", {
+    session_id: sessionId,
+    profile_id: profileId,
+    topic,
+    format,
+  });
+}
+
+/** Stop mirroring a session to MQTT. Resolves to whether it was publishing. */
+export async function stopMqttPublish(sessionId: string): Promise<boolean> {
+  return invoke("mqtt_publish_stop", { session_id: sessionId });
+}
+
+/** MQTT publisher state of a session (null when it isn't publishing). */
+export async function getMqttPublishStatus(sessionId: string): Promise<MqttPublishStatus | null> {
+  return invoke("get_mqtt_publish_status", { session_id: sessionId });
+}
+
 /**
  * Result of probing any real-time device.
  * Provides a unified structure for all device types.
@@ -1252,8 +1727,43 @@ export interface MultiSourceInput {
   sourceAddressBytes?: number;
   /** Source address extraction: byte order (true = big endian) */
   sourceAddressBigEndian?: boolean;
-  /** Modbus interface role (client or server) */
-  modbusRole?: "client" | "server";
+  /** Modbus interface role (omit = the profile's role, else client) */
+  modbusRole?: "client" | "server" | "simulator";
+  /** Restart the source with backoff when it drops (omit = the source ends) */
+  reconnect?: ReconnectPolicy;
+  /** Keep the adapter off the bus (overrides the profile; refused where it can't be guaranteed) */
+  listenOnly?: boolean;
+}
+
+/** Per-source reconnect policy. Omitted fields take the backend defaults. */
+export interface ReconnectPolicy {
+  /** Attempts before giving up (0 = keep trying, default 10) */
+  max_retries?: number;
+  /** Wait before the first attempt, doubling each attempt (default 500) */
+  initial_backoff_ms?: number;
+  /** Cap on the wait between attempts (default 30000) */
+  max_backoff_ms?: number;
+}
+
+/** Payload of the `device-disconnected` event, emitted before each reconnect attempt */
+export interface DeviceDisconnectedPayload {
+  session_id: string;
+  profile_id: string;
+  reason: string;
+  /** Reconnect attempt about to be made (1-based) */
+  attempt: number;
+  retry_in_ms: number;
+}
+
+/**
+ * Payload of the `bus-silent` and `bus-active` events. `silent_ms` is the time
+ * since the bus's last frame (bus-silent) or how long it was silent in all
+ * (bus-active). The limit is the `bus_silence_timeout_ms` setting.
+ */
+export interface BusSilencePayload {
+  session_id: string;
+  bus: number;
+  silent_ms: number;
 }
 
 /**
@@ -1289,8 +1799,18 @@ export interface CreateMultiSourceSessionOptions {
 export async function createMultiSourceSession(
   options: CreateMultiSourceSessionOptions
 ): Promise<IOCapabilities> {
-  // Convert TypeScript camelCase to Rust snake_case for the sources
-  const rustSources = options.sources.map((source) => ({
+  return invoke("create_multi_source_session", {
+    session_id: options.sessionId,
+    sources: options.sources.map(toRustSource),
+    subscriber_id: options.subscriberId,
+    app_name: options.appName,
+    modbus_polls: options.modbusPollsJson,
+  });
+}
+
+/** Convert a source from TypeScript camelCase to the backend's snake_case */
+function toRustSource(source: MultiSourceInput) {
+  return {
     profile_id: source.profileId,
     display_name: source.displayName,
     bus_mappings: source.busMappings.map((m) => ({
@@ -1313,14 +1833,72 @@ export async function createMultiSourceSession(
     source_address_bytes: source.sourceAddressBytes,
     source_address_big_endian: source.sourceAddressBigEndian,
     modbus_role: source.modbusRole,
-  }));
+    reconnect: source.reconnect,
+    listen_only: source.listenOnly,
+  };
+}
 
-  return invoke("create_multi_source_session", {
+// ============================================================================
+// Session Templates
+// ============================================================================
+
+/** A source as stored in a template (backend snake_case) */
+export type SessionTemplateSource = ReturnType<typeof toRustSource>;
+
+/** A saved session configuration, as returned by the backend */
+export interface SessionTemplate {
+  /** Empty on first save; the backend assigns one */
+  id: string;
+  name: string;
+  description?: string | null;
+  sources: SessionTemplateSource[];
+  modbus_polls?: string | null;
+  speed?: number | null;
+  batching?: BatchingOptions | null;
+  dedup?: DedupConfig | null;
+  /** Frontend view filters, stored as given */
+  filters?: unknown;
+}
+
+/** Template fields as the frontend holds them (sources in camelCase) */
+export interface SessionTemplateInput extends Omit<SessionTemplate, "id" | "sources"> {
+  id?: string;
+  sources: MultiSourceInput[];
+}
+
+export interface TemplateSession {
+  session_id: string;
+  capabilities: IOCapabilities;
+  /** The template's view filters, for the view to apply */
+  filters?: unknown;
+}
+
+export async function listSessionTemplates(): Promise<SessionTemplate[]> {
+  return invoke("list_session_templates");
+}
+
+/** Save a template, replacing any with the same id. Resolves to it with its id. */
+export async function saveSessionTemplate(template: SessionTemplateInput): Promise<SessionTemplate> {
+  return invoke("save_session_template", {
+    template: { ...template, id: template.id ?? "", sources: template.sources.map(toRustSource) },
+  });
+}
+
+/** Resolves to false if there was no template with that id. */
+export async function deleteSessionTemplate(templateId: string): Promise<boolean> {
+  return invoke("delete_session_template", { template_id: templateId });
+}
+
+/** Create and start a session from a template. The session id is generated unless given. */
+export async function createSessionFromTemplate(
+  templateId: string,
+  options: { sessionId?: string; subscriberId?: string; appName?: string } = {}
+): Promise<TemplateSession> {
+  return invoke("create_session_from_template", {
+    template_id: templateId,
     session_id: options.sessionId,
-    sources: rustSources,
     subscriber_id: options.subscriberId,
     app_name: options.appName,
-    modbus_polls: options.modbusPollsJson,
   });
 }
 
@@ -1651,14 +2229,8 @@ export async function getStreamEndedInfo(
   return invoke("get_stream_ended_info", { session_id: sessionId });
 }
 
-export interface StreamEndedInfo {
-  reason: string;
-  capture_available: boolean;
-  capture_id: string | null;
-  capture_kind: string | null;
-  count: number;
-  time_range: [number, number] | null;
-}
+/** Generated from the Rust struct (src/types/backend.d.ts). */
+export type { StreamEndedInfo };
 
 /** Fetch the last session error (from post-session cache or startup errors). */
 export async function getSessionError(
@@ -1740,3 +2312,674 @@ export interface BytesTailResponse {
   bytes: RawByteEntry[];
   total_count: number;
 }
+
+// ============================================================================
+// Timestamp sources and clock sync
+// ============================================================================
+
+/**
+ * Host clock sync status (PTP/NTP offset where the platform exposes it).
+ * Cached for ten minutes unless `refresh` is set.
+ */
+export async function getClockSyncStatus(refresh = false): Promise<ClockSyncStatus> {
+  return invoke("get_clock_sync_status", { refresh });
+}
+
+/**
+ * Choose where a session's frame timestamps come from: the device/driver
+ * ("device", default), the host wall clock at arrival ("host"), or a monotonic
+ * clock anchored at session start ("monotonic"). Recorded on the session's captures.
+ */
+export async function setSessionTimestampSource(
+  sessionId: string,
+  source: TimestampSource
+): Promise<CaptureTiming> {
+  return invoke("set_session_timestamp_source", { session_id: sessionId, source });
+}
+
+/** Timestamp source a session currently uses. */
+export async function getSessionTimestampSource(sessionId: string): Promise<TimestampSource> {
+  return invoke("get_session_timestamp_source", { session_id: sessionId });
+}
+
+// ============================================================================
+// Session quotas
+// ============================================================================
+
+/** Global session limits. 0 disables a limit. */
+export interface SessionQuotas {
+  max_sessions: number;
+  /** Estimated size of session-owned captures, in bytes */
+  max_buffer_bytes: number;
+  max_usb_devices: number;
+}
+
+export interface SessionQuotaStatus {
+  limits: SessionQuotas;
+  usage: {
+    sessions: number;
+    buffer_bytes: number;
+    usb_devices: number;
+  };
+}
+
+/** Current session limits and how much of each is in use. */
+export async function getSessionQuotas(): Promise<SessionQuotaStatus> {
+  return invoke("get_session_quotas");
+}
+
+/**
+ * Replace the session limits. Sessions that are already open stay open;
+ * creating a session beyond a limit fails with a `session.quota_exceeded` message.
+ */
+export async function setSessionQuotas(quotas: SessionQuotas): Promise<SessionQuotaStatus> {
+  return invoke("set_session_quotas", { quotas });
+}
+
+// ============================================================================
+// Latest decoded values
+// ============================================================================
+
+/** Most recent decoded value of one signal (sessions with an attached catalogue). */
+export interface LatestValue {
+  frame_id: number;
+  bus: number;
+  name: string;
+  mux_value: number | null;
+  /** Raw (pre-scale) value */
+  raw: number;
+  /** Scaled value */
+  value: number;
+  display: string;
+  unit: string | null;
+  /** Timestamp of the frame that carried the value (µs) */
+  timestamp_us: number;
+  updates: number;
+}
+
+/** When to push a signal in a `LatestValuesChanged` message. */
+export interface ChangeThresholds {
+  /** Absolute change for signals without their own threshold (null = listed signals only) */
+  default_delta?: number | null;
+  /** Absolute change per signal name */
+  signals?: Record<string, number>;
+  /** Minimum frame time between two reports of one signal */
+  min_interval_ms?: number;
+}
+
+/** Latest value of every decoded signal on a session, optionally only `names`. */
+export async function getLatestValues(sessionId: string, names?: string[]): Promise<LatestValue[]> {
+  return invoke("get_latest_values", { session_id: sessionId, names: names ?? null });
+}
+
+/**
+ * Report signals whose value moves past a threshold. Reports arrive as
+ * `LatestValuesChanged` WS messages carrying `LatestValue[]`.
+ */
+export async function setLatestValueThresholds(sessionId: string, thresholds: ChangeThresholds): Promise<void> {
+  return invoke("set_latest_value_thresholds", { session_id: sessionId, thresholds });
+}
+
+/** Stop threshold reports for a session (the cache keeps updating). */
+export async function clearLatestValueThresholds(sessionId: string): Promise<void> {
+  return invoke("clear_latest_value_thresholds", { session_id: sessionId });
+}
+
+// ============================================================================
+// OBD-II PID polling
+// ============================================================================
+
+/** Omitted fields take the backend defaults. */
+export interface ObdPollConfig {
+  /** Mode 01 PIDs requested every cycle (default RPM, speed, coolant, load, throttle, voltage) */
+  pids?: number[];
+  /** Time between polling cycles (default 1000, minimum 100) */
+  interval_ms?: number;
+  bus?: number;
+  /** Use 29-bit request/response IDs (0x18DB33F1 / 0x18DAF1xx) */
+  is_extended?: boolean;
+  /** Request the VIN until an ECU answers (default true) */
+  read_vin?: boolean;
+}
+
+export interface ObdPollState {
+  session_id: string;
+  running: boolean;
+  cycles: number;
+  requests_sent: number;
+  /** Decoded PID and VIN replies */
+  responses: number;
+  transmit_errors: number;
+  last_error: string | null;
+  vin: string | null;
+}
+
+/**
+ * Poll OBD-II PIDs on a transmit-capable CAN session. Decoded values land in
+ * the latest-value cache (see `getLatestValues`), named e.g. "Engine speed".
+ */
+export async function obdPollStart(sessionId: string, config: ObdPollConfig): Promise<ObdPollState> {
+  return invoke("obd_poll_start", { session_id: sessionId, config });
+}
+
+export async function obdPollStop(sessionId: string): Promise<void> {
+  return invoke("obd_poll_stop", { session_id: sessionId });
+}
+
+export async function getObdPollState(sessionId: string): Promise<ObdPollState | null> {
+  return invoke("get_obd_poll_state", { session_id: sessionId });
+}
+
+// ============================================================================
+// J1939
+// ============================================================================
+
+export interface J1939Id {
+  priority: number;
+  /** Parameter group number (PS zeroed for PDU1 groups) */
+  pgn: number;
+  source_address: number;
+  /** Destination of PDU1 groups (null = PDU2 broadcast) */
+  destination_address: number | null;
+}
+
+/** One SPN as listed in J1939-71. */
+export interface SpnDef {
+  pgn: number;
+  name?: string | null;
+  /** "4" or "4-5" (1-based bytes) or "1.3" (byte.bit, bit 1 = least significant) */
+  position: string;
+  /** Length in bits (default: the bytes `position` spans) */
+  length?: number | null;
+  /** Default 1 */
+  resolution?: number;
+  offset?: number;
+  unit?: string | null;
+}
+
+/** Same shape as a catalogue's `[j1939]` table. */
+export interface J1939Config {
+  /** Fill `source_address` on extended frames (default true) */
+  source_addresses?: boolean;
+  /** Also decode common engine and vehicle SPNs (EEC1, CCVS, ET1, ...) */
+  builtin_spns?: boolean;
+  /** SPN definitions keyed by SPN number */
+  spn?: Record<string, SpnDef>;
+}
+
+/**
+ * Enable J1939 handling on a session. Decoded SPNs appear in the latest-value
+ * cache alongside catalogue signals.
+ */
+export async function setJ1939Config(sessionId: string, config: J1939Config): Promise<void> {
+  return invoke("set_j1939_config", { session_id: sessionId, config });
+}
+
+export async function clearJ1939Config(sessionId: string): Promise<void> {
+  return invoke("clear_j1939_config", { session_id: sessionId });
+}
+
+export async function getJ1939Config(sessionId: string): Promise<J1939Config | null> {
+  return invoke("get_j1939_config", { session_id: sessionId });
+}
+
+export async function parseJ1939Id(frameId: number): Promise<J1939Id> {
+  return invoke("parse_j1939_id", { frame_id: frameId });
+}
+
+/** Fields of a 64-bit J1939 NAME. */
+export interface J1939Name {
+  identity_number: number;
+  manufacturer_code: number;
+  ecu_instance: number;
+  function_instance: number;
+  function: number;
+  vehicle_system: number;
+  vehicle_system_instance: number;
+  industry_group: number;
+  arbitrary_address_capable: boolean;
+}
+
+export interface ClaimedAddress {
+  address: number;
+  /** NAME as 16 hex digits (lower value wins arbitration) */
+  name: string;
+  fields: J1939Name;
+  first_seen_us: number;
+  last_claim_us: number;
+  claims: number;
+}
+
+export interface AddressConflict {
+  address: number;
+  winner: string;
+  loser: string;
+  timestamp_us: number;
+}
+
+export interface J1939NetworkMap {
+  addresses: ClaimedAddress[];
+  /** NAMEs that sent Cannot Claim Address */
+  cannot_claim: ClaimedAddress[];
+  conflicts: AddressConflict[];
+}
+
+/** Payload item of an `AddressClaimChanged` WS message. */
+export interface AddressClaimEvent {
+  change: "claimed" | "moved" | "conflict" | "cannot_claim";
+  address: number;
+  name: string;
+  previous_address?: number;
+  /** Other NAME involved in a conflict */
+  other_name?: string;
+  /** Whether `name` holds the address after the event */
+  holds_address: boolean;
+  timestamp_us: number;
+}
+
+/** Address claims (PGN 60928) seen on a session; tracked without any config. */
+export async function getJ1939NetworkMap(sessionId: string): Promise<J1939NetworkMap> {
+  return invoke("get_j1939_network_map", { session_id: sessionId });
+}
+
+export async function clearJ1939NetworkMap(sessionId: string): Promise<void> {
+  return invoke("clear_j1939_network_map", { session_id: sessionId });
+}
+
+// ============================================================================
+// CANopen
+// ============================================================================
+
+/** A frame decoded under the CANopen predefined connection set. */
+export type CanopenMessage =
+  | { type: "nmt"; command: string; target_node: number }
+  | { type: "sync"; counter: number | null }
+  | { type: "time"; bytes: number[] }
+  | {
+      type: "emcy";
+      node_id: number;
+      error_code: number;
+      error_class: string;
+      error_register: number;
+      manufacturer: number[];
+    }
+  | { type: "pdo"; node_id: number; number: number; transmit: boolean; bytes: number[] }
+  | {
+      type: "sdo";
+      node_id: number;
+      /** true = client request (0x600), false = server response (0x580) */
+      request: boolean;
+      command: string;
+      index?: number;
+      subindex?: number;
+      bytes: number[];
+      abort_code?: number;
+      abort_reason?: string;
+    }
+  | { type: "heartbeat"; node_id: number; state: string };
+
+export interface SdoTarget {
+  node_id: number;
+  index: number;
+  subindex?: number;
+  bus?: number;
+  /** Per-response timeout (default 1000) */
+  timeout_ms?: number;
+}
+
+export interface SdoReadResult {
+  bytes: number[];
+  /** Little-endian unsigned value for entries up to 8 bytes */
+  value: number | null;
+  /** The bytes as text, when printable */
+  text: string | null;
+  segmented: boolean;
+}
+
+export async function decodeCanopenFrame(
+  frameId: number,
+  isExtended: boolean,
+  bytes: number[]
+): Promise<CanopenMessage | null> {
+  return invoke("decode_canopen_frame", { frame_id: frameId, is_extended: isExtended, bytes });
+}
+
+/** Read an object dictionary entry (SDO upload) through a transmit-capable session. */
+export async function canopenSdoRead(sessionId: string, target: SdoTarget): Promise<SdoReadResult> {
+  return invoke("canopen_sdo_read", { session_id: sessionId, target });
+}
+
+/** Write an object dictionary entry (SDO download; segmented above 4 bytes). */
+export async function canopenSdoWrite(sessionId: string, target: SdoTarget, bytes: number[]): Promise<void> {
+  return invoke("canopen_sdo_write", { session_id: sessionId, target, bytes });
+}
+
+// ============================================================================
+// LIN
+// ============================================================================
+
+export type LinChecksumModel = "classic" | "enhanced";
+
+export interface LinSignal {
+  name: string;
+  start_bit: number;
+  bit_length: number;
+  signed: boolean;
+  factor: number;
+  offset: number;
+  unit: string | null;
+}
+
+export interface LinFrameDef {
+  id: number;
+  name: string | null;
+  /** Data bytes (1-8) */
+  length: number;
+  checksum: LinChecksumModel | null;
+  publisher: string | null;
+  master_publishes: boolean;
+  signals: LinSignal[];
+}
+
+export interface LinScheduleSlot {
+  id: number;
+  delay_ms: number;
+  data: number[] | null;
+}
+
+/** A session's LDF-lite description, from the catalogue's `[lin]` table. */
+export interface LinDescription {
+  baud_rate: number | null;
+  checksum: LinChecksumModel | null;
+  master: string | null;
+  /** Keyed by frame ID */
+  frames: Record<string, LinFrameDef>;
+  schedules: Record<string, LinScheduleSlot[]>;
+}
+
+export async function getLinDescription(sessionId: string): Promise<LinDescription | null> {
+  return invoke("get_lin_description", { session_id: sessionId });
+}
+
+// ============================================================================
+// Signal trends
+// ============================================================================
+
+export interface TrendSignal {
+  name: string;
+  /** Restrict to one frame when several frames carry a signal of this name */
+  frame_id?: number | null;
+}
+
+export interface TrendConfig {
+  signals: TrendSignal[];
+  /** Point width in frame time (0 = every sample) */
+  resolution_ms?: number;
+  /** Points kept per signal before older data is merged (default 100000) */
+  max_points?: number;
+}
+
+/** Samples from `t_us` up to the next point, aggregated. */
+export interface TrendPoint {
+  t_us: number;
+  min: number;
+  max: number;
+  mean: number;
+  count: number;
+}
+
+export interface TrendState {
+  config: Required<TrendConfig>;
+  series: {
+    name: string;
+    frame_id: number | null;
+    points: number;
+    samples: number;
+    first_us: number | null;
+    last_us: number | null;
+  }[];
+}
+
+/** Record trends for these signals (needs a catalogue attached to the session). */
+export async function setSignalTrends(sessionId: string, config: TrendConfig): Promise<void> {
+  return invoke("set_signal_trends", { session_id: sessionId, config });
+}
+
+/** Stop recording trends and drop the stored series. */
+export async function clearSignalTrends(sessionId: string): Promise<void> {
+  return invoke("clear_signal_trends", { session_id: sessionId });
+}
+
+export async function getSignalTrends(sessionId: string): Promise<TrendState | null> {
+  return invoke("get_signal_trends", { session_id: sessionId });
+}
+
+/** Points of a tracked signal in a time range, merged down to at most `maxPoints`. */
+export async function querySignalTrend(
+  sessionId: string,
+  name: string,
+  options: { frameId?: number; startUs?: number; endUs?: number; maxPoints?: number } = {}
+): Promise<TrendPoint[]> {
+  return invoke("query_signal_trend", {
+    session_id: sessionId,
+    name,
+    frame_id: options.frameId ?? null,
+    start_us: options.startUs ?? null,
+    end_us: options.endUs ?? null,
+    max_points: options.maxPoints ?? null,
+  });
+}
+
+// ============================================================================
+// Redundant-capture dedup
+// ============================================================================
+
+export interface DedupConfig {
+  /** Host arrival-time window within which two copies of a frame pair up (default 20, max 1000) */
+  window_ms?: number;
+  /** Output buses to deduplicate (null = every bus) */
+  buses?: number[] | null;
+}
+
+/** Capture counts for one source on deduplicated buses. */
+export interface SourceFidelity {
+  source_idx: number;
+  profile_id: string;
+  received: number;
+  /** Frames this source delivered first */
+  forwarded: number;
+  /** Frames dropped as copies of another source's frame */
+  duplicates: number;
+  /** Frames other sources saw on a shared bus that this source didn't */
+  missed: number;
+  /** received / (received + missed) as a percentage */
+  fidelity_pct: number | null;
+}
+
+export interface DedupStats {
+  config: Required<DedupConfig>;
+  unique_frames: number;
+  pending: number;
+  sources: SourceFidelity[];
+}
+
+/** Merge sources that capture the same bus, keeping one copy of each frame. Resets the counts. */
+export async function setSourceDedup(sessionId: string, config: DedupConfig): Promise<void> {
+  return invoke("set_source_dedup", { session_id: sessionId, config });
+}
+
+/** Stop deduplicating; every source's frames are forwarded again. */
+export async function clearSourceDedup(sessionId: string): Promise<void> {
+  return invoke("clear_source_dedup", { session_id: sessionId });
+}
+
+export async function getSourceDedupStats(sessionId: string): Promise<DedupStats | null> {
+  return invoke("get_source_dedup_stats", { session_id: sessionId });
+}
+
+// ============================================================================
+// Source clock alignment
+// ============================================================================
+
+export interface ClockAlignConfig {
+  /** Fixed offset (µs) added to each source's timestamps, by profile ID */
+  offsets_us?: Record<string, number>;
+  /** Frame ID every source sees, used to estimate offset and drift (null = manual offsets only) */
+  align_frame_id?: number | null;
+  /** Profile whose clock the others follow (null = first to deliver the align frame) */
+  reference_profile_id?: string | null;
+  /** Host arrival-time window within which two copies of the align frame pair up (default 20, max 1000) */
+  pair_window_ms?: number;
+}
+
+export interface SourceClockStats {
+  source_idx: number;
+  profile_id: string;
+  /** Auto-align reference: its clock is the common timeline */
+  reference: boolean;
+  manual_offset_us: number;
+  /** Estimated offset to the reference (null before any copies have paired) */
+  auto_offset_us: number | null;
+  drift_ppm: number | null;
+  samples: number;
+}
+
+export interface ClockAlignStats {
+  config: Required<ClockAlignConfig>;
+  sources: SourceClockStats[];
+}
+
+/** Shift source timestamps onto a common timeline. Restarts the estimates. */
+export async function setSourceClock(sessionId: string, config: ClockAlignConfig): Promise<void> {
+  return invoke("set_source_clock", { session_id: sessionId, config });
+}
+
+/** Stop correcting; each source keeps its own clock again. */
+export async function clearSourceClock(sessionId: string): Promise<void> {
+  return invoke("clear_source_clock", { session_id: sessionId });
+}
+
+export async function getSourceClockStats(sessionId: string): Promise<ClockAlignStats | null> {
+  return invoke("get_source_clock_stats", { session_id: sessionId });
+}
+
+// ============================================================================
+// Priority frame emission
+// ============================================================================
+
+export interface PinnedId {
+  frame_id: number;
+  /** Bus to match (null = any bus) */
+  bus?: number | null;
+}
+
+export interface PriorityConfig {
+  ids: PinnedId[];
+  /** Shortest gap between priority batches in ms (default 20, max 500) */
+  interval_ms?: number;
+}
+
+export interface PriorityStats {
+  config: Required<PriorityConfig>;
+  /** Pinned frames pushed ahead of the background batch */
+  priority_frames: number;
+  priority_batches: number;
+  /** Pinned frames waiting for the next batch */
+  pending: number;
+}
+
+/** Deliver frames for pinned IDs in small, frequent batches ahead of background traffic. */
+export async function setFramePriority(sessionId: string, config: PriorityConfig): Promise<void> {
+  return invoke("set_frame_priority", { session_id: sessionId, config });
+}
+
+/** Unpin every frame ID; all frames go out with the regular batches again. */
+export async function clearFramePriority(sessionId: string): Promise<void> {
+  return invoke("clear_frame_priority", { session_id: sessionId });
+}
+
+export async function getFramePriority(sessionId: string): Promise<PriorityStats | null> {
+  return invoke("get_frame_priority", { session_id: sessionId });
+}
+
+// ============================================================================
+// Live windowed analysis
+// ============================================================================
+
+export interface LiveAnalysisConfig {
+  /** Sliding window in frame time, ms (default 10000, max 600000) */
+  window_ms?: number;
+  /** Change heatmap bucket width, ms (default 500) */
+  bucket_ms?: number;
+  /** Buses to analyse (null = every bus) */
+  buses?: number[] | null;
+}
+
+export interface LiveByteStats {
+  index: number;
+  samples: number;
+  min: number;
+  max: number;
+  mean: number;
+  distinct: number;
+  /** Shannon entropy of the byte's values, 0-8 bits */
+  entropy_bits: number;
+  /** Frames where the byte differed from the previous frame */
+  changes: number;
+}
+
+export interface LiveFrameStats {
+  bus: number;
+  frame_id: number;
+  is_extended: boolean;
+  count: number;
+  first_us: number;
+  last_us: number;
+  rate_hz: number;
+  interval_mean_ms: number | null;
+  interval_min_ms: number | null;
+  interval_max_ms: number | null;
+  interval_stddev_ms: number | null;
+  bytes: LiveByteStats[];
+  /** Oldest frames were evicted before leaving the window (per-ID cap) */
+  truncated: boolean;
+}
+
+export interface LiveAnalysis {
+  window_ms: number;
+  window_start_us: number;
+  window_end_us: number;
+  frames: LiveFrameStats[];
+}
+
+export interface LiveChangeHeatmap {
+  bus: number;
+  frame_id: number;
+  bucket_ms: number;
+  bucket_starts_us: number[];
+  /** One row per byte position, parallel to bucket_starts_us */
+  rows: number[][];
+}
+
+/** Start windowed analysis on a live session (restarts with an empty window if already running). */
+export async function startLiveAnalysis(sessionId: string, config: LiveAnalysisConfig = {}): Promise<void> {
+  return invoke("start_live_analysis", { session_id: sessionId, config });
+}
+
+export async function stopLiveAnalysis(sessionId: string): Promise<void> {
+  return invoke("stop_live_analysis", { session_id: sessionId });
+}
+
+/** Per-ID statistics, cadence and byte entropy over the current window. */
+export async function getLiveAnalysis(sessionId: string): Promise<LiveAnalysis> {
+  return invoke("get_live_analysis", { session_id: sessionId });
+}
+
+/** Per-byte change counts over time for one frame ID (null when it has no frames in the window). */
+export async function getLiveChangeHeatmap(
+  sessionId: string,
+  bus: number,
+  frameId: number
+): Promise<LiveChangeHeatmap | null> {
+  return invoke("get_live_change_heatmap", { session_id: sessionId, bus, frame_id: frameId });
+}
@@ -0,0 +1,32 @@
+// src/api/j2534.ts
+//
+// API wrapper for SAE J2534 (PassThru) commands.
+
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * A PassThru device registered with Windows.
+ */
+export interface J2534DeviceInfo {
+  /** Device name from the registry (e.g. "Mongoose Pro GM II") */
+  name: string;
+  vendor: string;
+  /** Vendor PassThru DLL */
+  library_path: string;
+  supports_can: boolean;
+  supports_iso15765: boolean;
+}
+
+/**
+ * List registered PassThru devices that support raw CAN (Windows; empty elsewhere).
+ */
+export async function listJ2534Devices(): Promise<J2534DeviceInfo[]> {
+  return invoke("list_j2534_devices");
+}
+
+/**
+ * Format a device for display in a dropdown.
+ */
+export function formatJ2534DeviceDisplay(device: J2534DeviceInfo): string {
+  return device.vendor ? `${device.name} (${device.vendor})` : device.name;
+}
@@ -0,0 +1,41 @@
+// src/api/kvaser.ts
+//
+// API wrapper for Kvaser (CANlib) device commands.
+
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * Information about a detected Kvaser device (one card with its channels).
+ */
+export interface KvaserDeviceInfo {
+  /** Product name (e.g. "Kvaser Leaf Light v2") */
+  name: string;
+  /** Card serial number (stable across replugging) */
+  serial: number;
+  /** Product EAN (e.g. "73-30130-00685-0") */
+  ean: string;
+  /** CANlib channel number of each channel on the card */
+  channels: number[];
+}
+
+/**
+ * List Kvaser devices known to CANlib (Windows/Linux; empty on macOS).
+ */
+export async function listKvaserDevices(): Promise<KvaserDeviceInfo[]> {
+  return invoke("list_kvaser_devices");
+}
+
+/**
+ * Classic CAN bitrates supported by CANlib's presets.
+ */
+export async function getKvaserBitrates(): Promise<number[]> {
+  return invoke("get_kvaser_bitrates");
+}
+
+/**
+ * Format a device for display in a dropdown.
+ */
+export function formatKvaserDeviceDisplay(device: KvaserDeviceInfo): string {
+  const channels = device.channels.length === 1 ? "1 channel" : `${device.channels.length} channels`;
+  return `${device.name} - S/N ${device.serial} (${channels})`;
+}
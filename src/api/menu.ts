@@ -31,6 +31,32 @@ export async function updateMenuState(state: MenuState): Promise<void> {
   });
 }
 
+/** Session menu state computed backend-side from the session itself. */
+export interface MenuSessionState {
+  has_session: boolean;
+  /** "Capture" when replaying a capture */
+  profile_name: string | null;
+  /** Running or paused */
+  is_streaming: boolean;
+  is_paused: boolean;
+  can_pause: boolean;
+  joiner_count: number;
+}
+
+/** The menu booleans for a session, without touching the menu. */
+export async function getMenuState(sessionId: string): Promise<MenuSessionState> {
+  return invoke("get_menu_state", { session_id: sessionId });
+}
+
+/**
+ * Make the Session menu follow a session (null = no session focused). The
+ * backend computes the menu state and refreshes it whenever the session's
+ * state or joiner count changes, so a busy window can't leave it stale.
+ */
+export async function syncMenuState(sessionId: string | null, hasBookmarks: boolean): Promise<void> {
+  return invoke("sync_menu_state", { session_id: sessionId, has_bookmarks: hasBookmarks });
+}
+
 /** Update the Bookmarks > Jump to Bookmark submenu with bookmarks for the current profile. */
 export async function updateBookmarksMenu(bookmarks: BookmarkMenuInfo[]): Promise<void> {
   return invoke("update_bookmarks_menu", { bookmarks });
@@ -0,0 +1,35 @@
+// src/api/messages.ts
+//
+// Backend message catalogue. Catalogued backend text (command errors, session
+// errors) is plain English; these wrappers recover its stable key and
+// parameters so the UI can match on the key or render a translation.
+
+import { invoke } from "@tauri-apps/api/core";
+
+/** A catalogue entry: stable key and English template with `{name}` placeholders. */
+export interface MessageDef {
+  key: string;
+  template: string;
+}
+
+/** Backend text described by key and parameters. */
+export interface BackendMessage {
+  key: string;
+  params: Record<string, string>;
+  text: string;
+}
+
+/** Every catalogued backend message. */
+export async function getMessageCatalogue(): Promise<MessageDef[]> {
+  return invoke("get_message_catalogue");
+}
+
+/** Key and parameters for backend text, or null when it isn't catalogued. */
+export async function describeMessage(text: string): Promise<BackendMessage | null> {
+  return invoke("describe_message", { text });
+}
+
+/** Fill a (translated) template's `{name}` placeholders from a message's params. */
+export function formatMessage(template: string, params: Record<string, string>): string {
+  return template.replace(/\{(\w+)\}/g, (whole, name: string) => params[name] ?? whole);
+}
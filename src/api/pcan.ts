@@ -0,0 +1,58 @@
+// src/api/pcan.ts
+//
+// API wrapper for PCAN-USB (PEAK) device commands.
+
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * Information about a detected PCAN-USB channel.
+ */
+export interface PcanDeviceInfo {
+  /** 1-based USB channel number (PCAN_USBBUS<n>) */
+  channel: number;
+  /** Hardware name reported by the driver (e.g. "PCAN-USB FD") */
+  hardware_name: string;
+  /** User-assigned device ID (stable across replugging) */
+  device_id: number | null;
+  /** Whether another application already holds the channel */
+  occupied: boolean;
+  /** SocketCAN interface name (Linux only, e.g., "can0") */
+  interface_name: string | null;
+  /** Whether the interface is currently up (Linux only) */
+  interface_up: boolean | null;
+}
+
+/**
+ * List PCAN-USB channels connected to the system.
+ * On Linux, lists SocketCAN interfaces bound to the peak_usb driver.
+ */
+export async function listPcanDevices(): Promise<PcanDeviceInfo[]> {
+  return invoke("list_pcan_devices");
+}
+
+/**
+ * Classic CAN bitrates supported by PCAN-Basic.
+ */
+export async function getPcanBitrates(): Promise<number[]> {
+  return invoke("get_pcan_bitrates");
+}
+
+/**
+ * Format a channel for display in a dropdown.
+ */
+export function formatPcanDeviceDisplay(device: PcanDeviceInfo): string {
+  const parts = [device.hardware_name];
+  if (device.interface_name) {
+    const upStatus = device.interface_up ? "up" : "down";
+    parts.push(`- ${device.interface_name} [${upStatus}]`);
+  } else {
+    parts.push(`- PCAN_USBBUS${device.channel}`);
+  }
+  if (device.device_id !== null) {
+    parts.push(`(ID ${device.device_id})`);
+  }
+  if (device.occupied) {
+    parts.push("[in use]");
+  }
+  return parts.join(" ");
+}
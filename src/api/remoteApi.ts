@@ -0,0 +1,25 @@
+// src/api/remoteApi.ts
+//
+// Remote control API (desktop only): a localhost REST/WebSocket server that
+// lets test automation drive sessions. Port and token come from settings;
+// these commands start/stop it and report whether it is listening.
+
+import { invoke } from "@tauri-apps/api/core";
+
+export interface RemoteApiStatus {
+  running: boolean;
+  port: number | null;
+}
+
+/** Whether the remote API is listening, and on which port. */
+export async function getRemoteApiStatus(): Promise<RemoteApiStatus> {
+  return invoke("get_remote_api_status");
+}
+
+/**
+ * Start or stop the remote API using the saved port and token.
+ * Save settings first so edits take effect.
+ */
+export async function toggleRemoteApi(enabled: boolean): Promise<RemoteApiStatus> {
+  return invoke("toggle_remote_api", { enabled });
+}
@@ -2,7 +2,7 @@
 // Settings-related Tauri commands
 
 import { invoke } from "@tauri-apps/api/core";
-import type { AppSettings } from "../hooks/useSettings";
+import type { AppSettings, IOProfile } from "../hooks/useSettings";
 
 /**
  * Load application settings from the backend
@@ -134,3 +134,205 @@ export const tlog = {
     invoke("log_from_frontend", { level: "verbose", message }).catch(() => {});
   },
 };
+
+// ── File retention ──
+
+export type RetentionAction = "delete" | "archive";
+
+/** Deletes or archives files in one directory once they are older than `max_age_days`. */
+export interface RetentionRule {
+  directory: string;
+  /** Only files whose name contains this (case-insensitive), e.g. a vehicle ID or ".csv" */
+  tag?: string | null;
+  max_age_days: number;
+  action?: RetentionAction;
+  /** Archive destination (default `<directory>/Archive`) */
+  archive_dir?: string | null;
+  recursive?: boolean;
+}
+
+export interface RetentionPolicy {
+  /** Apply automatically every `interval_hours` */
+  enabled: boolean;
+  interval_hours: number;
+  rules: RetentionRule[];
+  /** Set by the backend once a destructive run of these rules was confirmed; cleared when they change */
+  confirmed?: boolean;
+}
+
+export interface RetentionEntry {
+  path: string;
+  rule_index: number;
+  action: RetentionAction;
+  age_days: number;
+  size_bytes: number;
+  archived_to?: string;
+  error?: string;
+}
+
+export interface RetentionReport {
+  dry_run: boolean;
+  ran_at: number;
+  entries: RetentionEntry[];
+  deleted: number;
+  archived: number;
+  failed: number;
+  bytes_reclaimed: number;
+  rule_errors: string[];
+}
+
+export async function getRetentionPolicy(): Promise<RetentionPolicy> {
+  return invoke("get_retention_policy");
+}
+
+export async function setRetentionPolicy(policy: RetentionPolicy): Promise<void> {
+  await invoke("set_retention_policy", { policy });
+}
+
+/**
+ * Apply the saved retention policy now. A dry run changes nothing and
+ * reports what would be deleted or archived. The first real run of a set of
+ * rules must pass `confirm` (after the user has reviewed a dry run).
+ */
+export async function runRetention(dryRun: boolean, confirm?: boolean): Promise<RetentionReport> {
+  return invoke("run_retention", { dry_run: dryRun, confirm: confirm ?? null });
+}
+
+/** Report from the most recent retention run, if any. */
+export async function getLastRetentionReport(): Promise<RetentionReport | null> {
+  return invoke("get_last_retention_report");
+}
+
+// ── Black box ──
+
+/**
+ * Black box mode: the last `window_secs` of every active session are kept in
+ * an on-disk ring and recovered as a capture on the next launch after a crash.
+ */
+export interface BlackBoxConfig {
+  enabled: boolean;
+  /** Seconds kept per session (1–600) */
+  window_secs: number;
+}
+
+export async function getBlackBoxConfig(): Promise<BlackBoxConfig> {
+  return invoke("get_black_box_config");
+}
+
+export async function setBlackBoxConfig(config: BlackBoxConfig): Promise<BlackBoxConfig> {
+  return invoke("set_black_box_config", { config });
+}
+
+// ── Capture journal ──
+
+/**
+ * Crash-safe journaling: every capture a session is recording is also written
+ * to an append-only journal, and captures still recording when the app died
+ * come back as "<name> (recovered)" on the next launch.
+ */
+export interface CaptureJournalConfig {
+  enabled: boolean;
+}
+
+export async function getCaptureJournalConfig(): Promise<CaptureJournalConfig> {
+  return invoke("get_capture_journal_config");
+}
+
+export async function setCaptureJournalConfig(config: CaptureJournalConfig): Promise<CaptureJournalConfig> {
+  return invoke("set_capture_journal_config", { config });
+}
+
+// ── Webhooks ──
+
+export type WebhookEvent =
+  | "session_created"
+  | "session_destroyed"
+  | "stream_ended"
+  | "alert_triggered"
+  | "export_completed";
+
+/** HTTP endpoint that receives a JSON POST for each subscribed event. */
+export interface Webhook {
+  name?: string;
+  url: string;
+  /** Subscribed events; empty means all */
+  events?: WebhookEvent[];
+  /** Extra request headers, e.g. an Authorization token. Values are kept in the system keyring */
+  headers?: Record<string, string>;
+  enabled?: boolean;
+}
+
+export interface WebhookConfig {
+  webhooks: Webhook[];
+}
+
+export interface WebhookDelivery {
+  event: WebhookEvent;
+  /** Unix seconds */
+  delivered_at: number;
+  status: number | null;
+  error: string | null;
+}
+
+export async function getWebhooks(): Promise<WebhookConfig> {
+  return invoke("get_webhooks");
+}
+
+export async function setWebhooks(config: WebhookConfig): Promise<void> {
+  await invoke("set_webhooks", { config });
+}
+
+/** Send a test POST to a webhook and report how it went. */
+export async function testWebhook(webhook: Webhook): Promise<WebhookDelivery> {
+  return invoke("test_webhook", { webhook });
+}
+
+/** Most recent delivery per webhook, keyed by its index in the configuration. */
+export async function getWebhookDeliveries(): Promise<Record<number, WebhookDelivery>> {
+  return invoke("get_webhook_deliveries");
+}
+
+/** Fire the export_completed webhook for a file the frontend wrote. */
+export async function notifyExportCompleted(
+  captureId: string | null,
+  format: string,
+  filePath: string,
+): Promise<void> {
+  await invoke("notify_export_completed", { capture_id: captureId, format, file_path: filePath });
+}
+
+// ============================================================================
+// Profile connection schemas
+// ============================================================================
+
+export interface ProfileFieldIssue {
+  /** Connection key the issue is about */
+  field: string;
+  severity: "error" | "warning";
+  message: string;
+  /** Known field the key was probably meant to be (typos) */
+  suggestion?: string;
+}
+
+export interface ProfileValidation {
+  profile_id: string;
+  kind: string;
+  valid: boolean;
+  issues: ProfileFieldIssue[];
+}
+
+/**
+ * JSON Schema for a profile kind's connection settings, or a map of every
+ * kind's schema when `kind` is omitted.
+ */
+export async function getProfileSchema(kind?: string): Promise<Record<string, unknown>> {
+  return invoke("get_profile_schema", { kind: kind ?? null });
+}
+
+/**
+ * Check a profile's connection settings without saving. Saving settings and
+ * starting a session reject profiles with errors; warnings don't block.
+ */
+export async function validateProfile(profile: IOProfile): Promise<ProfileValidation> {
+  return invoke("validate_profile", { profile });
+}
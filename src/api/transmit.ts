@@ -27,6 +27,29 @@ export interface CanTransmitFrame {
   is_rtr: boolean;
 }
 
+/** Modbus write function codes */
+export type ModbusWriteFunction = 5 | 6 | 15 | 16;
+
+/** Coil or register write on a Modbus device */
+export interface ModbusWrite {
+  /** 5 (single coil), 6 (single register), 15 (multiple coils) or 16 (multiple registers) */
+  function_code: ModbusWriteFunction;
+  /** Device (slave) address */
+  unit_id: number;
+  /** Protocol-level start address (0-based) */
+  address: number;
+  /** Register values, or 0/1 per coil */
+  values: number[];
+}
+
+/** UDS request sent through a DoIP gateway */
+export interface DiagnosticRequest {
+  /** ECU logical address; omit to use the profile's default target */
+  target_address?: number | null;
+  /** UDS payload (service ID first) */
+  data: number[];
+}
+
 /** Result of a transmit operation */
 export interface TransmitResult {
   /** Whether the transmission was successful */
@@ -35,6 +58,49 @@ export interface TransmitResult {
   timestamp_us: number;
   /** Error message if transmission failed */
   error?: string;
+  /** Catalog validation issues (warn mode: sent anyway; reject mode: why it wasn't) */
+  issues?: TransmitValidationIssue[];
+}
+
+/** Kind of problem found by catalog transmit validation */
+export type TransmitValidationIssueKind =
+  | "no_catalog"
+  | "unknown_frame"
+  | "length"
+  | "range"
+  | "reserved_bits"
+  | "counter";
+
+/** One problem found with a frame about to be transmitted */
+export interface TransmitValidationIssue {
+  kind: TransmitValidationIssueKind;
+  /** Signal the issue concerns (range and counter issues) */
+  signal?: string;
+  message: string;
+}
+
+/** A counter signal that must advance on every transmit of its frame */
+export interface TransmitCounterRule {
+  frame_id: number;
+  signal: string;
+  /** Expected increment per transmit (default 1) */
+  step?: number;
+  /** Counter wraps at this value (e.g. 16 for a 4-bit rolling counter) */
+  modulo?: number;
+}
+
+/** Per-session catalog validation policy for transmitted frames */
+export interface TransmitValidationConfig {
+  /** "warn" sends and reports issues; "reject" drops frames with issues */
+  mode?: "warn" | "reject";
+  /** Catalog to validate against (defaults to the session's attached catalog) */
+  catalog_path?: string;
+  check_length?: boolean;
+  check_ranges?: boolean;
+  check_reserved_bits?: boolean;
+  /** Treat frame IDs missing from the catalog as an issue */
+  require_catalogued?: boolean;
+  counters?: TransmitCounterRule[];
 }
 
 /** Writer capabilities - what a transmit-capable profile supports */
@@ -200,6 +266,35 @@ export async function ioTransmitSerial(
   return invoke("io_transmit_serial", { sessionId, bytes });
 }
 
+/**
+ * Write coils or registers through an existing Modbus TCP session.
+ * The write is queued on the session's connection; its outcome is recorded
+ * in the transmit history.
+ * @param sessionId - IO session to use for the write
+ * @param write - Function code, unit, address and values
+ * @returns Transmit result with success/error info
+ */
+export async function ioTransmitModbusWrite(
+  sessionId: string,
+  write: ModbusWrite
+): Promise<TransmitResult> {
+  return invoke("io_transmit_modbus_write", { sessionId, write });
+}
+
+/**
+ * Send a UDS request through an existing DoIP gateway session.
+ * The response arrives as a "doip" frame linked to the request.
+ * @param sessionId - IO session to use
+ * @param request - Target ECU address and UDS payload
+ * @returns Transmit result with success/error info
+ */
+export async function ioTransmitDiagnostic(
+  sessionId: string,
+  request: DiagnosticRequest
+): Promise<TransmitResult> {
+  return invoke("io_transmit_diagnostic", { sessionId, request });
+}
+
 /**
  * Start repeat transmission through an IO session.
  * @param sessionId - IO session to use
@@ -350,3 +445,42 @@ export async function ioStopReplay(replayId: string): Promise<void> {
 export async function ioStopAllReplays(): Promise<void> {
   return invoke("io_stop_all_replays");
 }
+
+/**
+ * Enable catalog-driven validation of frames transmitted on a session.
+ * @param sessionId - IO session to validate transmits on
+ * @param config - Validation policy (replaces any existing one)
+ */
+export async function setTransmitValidation(
+  sessionId: string,
+  config: TransmitValidationConfig
+): Promise<void> {
+  return invoke("set_transmit_validation", { session_id: sessionId, config });
+}
+
+/**
+ * Disable transmit validation on a session.
+ */
+export async function clearTransmitValidation(sessionId: string): Promise<void> {
+  return invoke("clear_transmit_validation", { session_id: sessionId });
+}
+
+/**
+ * Get a session's transmit validation policy, if any.
+ */
+export async function getTransmitValidation(
+  sessionId: string
+): Promise<TransmitValidationConfig | null> {
+  return invoke("get_transmit_validation", { session_id: sessionId });
+}
+
+/**
+ * Check a frame against the session's validation policy without sending it.
+ * @returns Issues found (empty when valid or no policy is set)
+ */
+export async function validateTransmitFrame(
+  sessionId: string,
+  frame: CanTransmitFrame
+): Promise<TransmitValidationIssue[]> {
+  return invoke("validate_transmit_frame", { session_id: sessionId, frame });
+}
@@ -8,7 +8,7 @@ export interface TransmitHistoryRow {
   id: number;
   session_id: string;
   timestamp_us: number;
-  kind: "can" | "serial";
+  kind: "can" | "serial" | "modbus" | "doip";
   frame_id: number | null;
   dlc: number | null;
   bytes: number[];
@@ -0,0 +1,45 @@
+// src/api/usbHotplug.ts
+//
+// USB hotplug events (desktop only). The backend watches for USB devices
+// arriving and leaving and emits global `device-attached` / `device-removed`.
+
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+/** A USB device, as reported in `device-attached` / `device-removed`. */
+export interface UsbDevice {
+  vendor_id: number;
+  product_id: number;
+  serial_number: string | null;
+  manufacturer: string | null;
+  product: string | null;
+  /** Platform bus identifier */
+  bus_id: string;
+  device_address: number;
+}
+
+/** USB devices currently attached, as last reported by the watcher. */
+export async function listUsbDevices(): Promise<UsbDevice[]> {
+  return invoke("list_usb_devices");
+}
+
+/**
+ * Subscribe to USB devices attaching and detaching.
+ * Returns an unlisten function that removes both listeners.
+ *
+ * @param callback Called with the change and the device it concerns
+ */
+export async function onUsbHotplug(
+  callback: (change: "attached" | "removed", device: UsbDevice) => void
+): Promise<UnlistenFn> {
+  const unlistenAttached = await listen<UsbDevice>("device-attached", (event) => {
+    callback("attached", event.payload);
+  });
+  const unlistenRemoved = await listen<UsbDevice>("device-removed", (event) => {
+    callback("removed", event.payload);
+  });
+  return () => {
+    unlistenAttached();
+    unlistenRemoved();
+  };
+}
@@ -0,0 +1,56 @@
+// src/api/vector_xl.ts
+//
+// API wrapper for Vector XL Driver Library commands.
+
+import { invoke } from "@tauri-apps/api/core";
+
+/**
+ * A CAN channel on a Vector interface.
+ */
+export interface VectorChannelInfo {
+  /** Channel number on the interface (0-based; the device bus) */
+  hw_channel: number;
+  /** Global XL channel index */
+  channel_index: number;
+  /** Channel name from the driver (e.g. "VN1630A Channel 1") */
+  name: string;
+  /** Transceiver name */
+  transceiver: string;
+  supports_fd: boolean;
+}
+
+/**
+ * Information about a detected Vector interface (one device with its CAN channels).
+ */
+export interface VectorDeviceInfo {
+  /** Hardware name (e.g. "VN1630A") */
+  name: string;
+  /** Serial number (stable across replugging) */
+  serial: number;
+  hw_type: number;
+  hw_index: number;
+  channels: VectorChannelInfo[];
+}
+
+/**
+ * List Vector interfaces with CAN channels (Windows; empty elsewhere).
+ */
+export async function listVectorDevices(): Promise<VectorDeviceInfo[]> {
+  return invoke("list_vector_devices");
+}
+
+/**
+ * Nominal and CAN FD data-phase bitrates offered for Vector interfaces.
+ */
+export async function getVectorBitrates(): Promise<[number[], number[]]> {
+  return invoke("get_vector_bitrates");
+}
+
+/**
+ * Format a device for display in a dropdown.
+ */
+export function formatVectorDeviceDisplay(device: VectorDeviceInfo): string {
+  const channels = device.channels.length === 1 ? "1 channel" : `${device.channels.length} channels`;
+  const fd = device.channels.every((c) => c.supports_fd) ? ", CAN FD" : "";
+  return `${device.name} - S/N ${device.serial} (${channels}${fd})`;
+}
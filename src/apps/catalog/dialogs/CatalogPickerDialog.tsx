@@ -7,7 +7,7 @@ import { borderDivider, hoverLight, bgSurface, dialogOptionButton } from "../../
 import Dialog from "../../../components/Dialog";
 import type { CatalogMetadata } from "../../../api/catalog";
 import { pickFileToOpen, pickCatalogToSave } from "../../../api/dialogs";
-import { openCatalog, importDbcWs, saveCatalog } from "../../../api/catalog";
+import { openCatalog, importDbcWs, importEdsWs, saveCatalog } from "../../../api/catalog";
 
 type Props = {
   isOpen: boolean;
@@ -41,21 +41,24 @@ export default function CatalogPickerDialog({
     try {
       const selected = await pickFileToOpen({
         filters: [
-          { name: "Catalog Files", extensions: ["toml", "dbc"] },
+          { name: "Catalog Files", extensions: ["toml", "dbc", "eds", "dcf"] },
           { name: "TOML Files", extensions: ["toml"] },
           { name: "DBC Files", extensions: ["dbc"] },
+          { name: "CANopen EDS/DCF Files", extensions: ["eds", "dcf"] },
         ],
       });
 
       if (selected) {
-        if (selected.endsWith(".dbc")) {
-          // Read DBC file and convert to TOML
-          const dbcContent = await openCatalog(selected);
-          const tomlContent = await importDbcWs(dbcContent);
+        if (/\.(dbc|eds|dcf)$/i.test(selected)) {
+          // Read DBC / EDS file and convert to TOML
+          const sourceContent = await openCatalog(selected);
+          const tomlContent = /\.dbc$/i.test(selected)
+            ? await importDbcWs(sourceContent)
+            : await importEdsWs(sourceContent);
 
-          // Derive default save path from DBC filename
-          const dbcFilename = selected.split(/[/\\]/).pop() || "imported";
-          const baseName = dbcFilename.replace(/\.dbc$/i, "");
+          // Derive default save path from the source filename
+          const sourceFilename = selected.split(/[/\\]/).pop() || "imported";
+          const baseName = sourceFilename.replace(/\.(dbc|eds|dcf)$/i, "");
           const defaultPath = decoderDir ? `${decoderDir}/${baseName}.toml` : undefined;
 
           // Prompt user to save the converted TOML
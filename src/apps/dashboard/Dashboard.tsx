@@ -402,6 +402,7 @@ function DashboardInner() {
   useMenuSessionControl({
     panelId: "dashboard",
     sessionState: {
+      sessionId,
       profileName: ioProfileName ?? null,
       isStreaming,
       isPaused,
@@ -800,6 +800,7 @@ function DecoderInner() {
   useMenuSessionControl({
     panelId: "decoder",
     sessionState: {
+      sessionId,
       profileName: ioProfileName ?? null,
       isStreaming,
       isPaused,
@@ -959,6 +959,7 @@ function DiscoveryInner() {
     getCaptureFramesPaginated: (offset, limit) => getCaptureFramesPaginated((captureMetadata?.id ?? sessionCaptureId)!, offset, limit),
     getCaptureFramesPaginatedById,
     captureMetadata,
+    exportCaptureId: captureMetadata?.id ?? sessionCaptureId ?? null,
     pickFileToSave,
     saveCatalog,
 
@@ -997,6 +998,7 @@ function DiscoveryInner() {
   useMenuSessionControl({
     panelId: "discovery",
     sessionState: {
+      sessionId,
       profileName: ioProfileName ?? null,
       isStreaming,
       isPaused,
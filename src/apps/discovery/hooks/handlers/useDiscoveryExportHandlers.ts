@@ -5,7 +5,14 @@
 import { useCallback } from "react";
 import type { FrameMessage } from "../../../../stores/discoveryStore";
 import type { ExportFormat, ExportDataMode } from "../../../../dialogs/ExportFramesDialog";
-import type { TimestampedByte } from "../../../../api/capture";
+import {
+  buildExportManifest,
+  buildExportSidecar,
+  EXPORT_SIDECAR_SUFFIX,
+  sha256Hex,
+  type TimestampedByte,
+} from "../../../../api/capture";
+import { notifyExportCompleted } from "../../../../api/settings";
 import { useSessionStore } from "../../../../stores/sessionStore";
 import { withAppError } from "../../../../utils/appError";
 
@@ -33,6 +40,8 @@ export interface UseDiscoveryExportHandlersParams {
   getCaptureBytesPaginated: (offset: number, limit: number) => Promise<{ bytes: TimestampedByte[] }>;
   getCaptureFramesPaginated: (offset: number, limit: number) => Promise<{ frames: any[] }>;
   getCaptureFramesPaginatedById: (id: string, offset: number, limit: number) => Promise<{ frames: any[] }>;
+  /** Backend capture behind the session/capture view (for the integrity manifest) */
+  exportCaptureId: string | null;
   pickFileToSave: (options: any) => Promise<string | null>;
   saveCatalog: (path: string, content: string) => Promise<void>;
 
@@ -59,6 +68,7 @@ export function useDiscoveryExportHandlers({
   getCaptureBytesPaginated,
   getCaptureFramesPaginated,
   getCaptureFramesPaginatedById,
+  exportCaptureId,
   pickFileToSave,
   saveCatalog,
   closeExportDialog,
@@ -69,7 +79,7 @@ export function useDiscoveryExportHandlers({
   }, [saveFrames, decoderDir, saveFrameIdFormat]);
 
   // Handle export dialog confirm
-  const handleExport = useCallback(async (format: ExportFormat, filename: string) => {
+  const handleExport = useCallback(async (format: ExportFormat, filename: string, writeSidecar: boolean) => {
     if (!dumpDir) {
       useSessionStore.getState().showAppError("Export Error", "Dump directory not configured", "Please set a dump directory in Settings.");
       return;
@@ -78,6 +88,8 @@ export function useDiscoveryExportHandlers({
     await withAppError("Export Error", "Failed to export", async () => {
       let content: string | Uint8Array;
       let extension: string;
+      // Backend capture the data came from; in-memory buffers have no seal
+      let sourceCaptureId: string | null = null;
 
       if (exportDataMode === "bytes") {
         // Export bytes
@@ -85,6 +97,7 @@ export function useDiscoveryExportHandlers({
         let bytesToExport: { byte: number; timestampUs: number }[];
 
         if (backendByteCount > 0) {
+          sourceCaptureId = exportCaptureId;
           const response = await getCaptureBytesPaginated(0, backendByteCount);
           bytesToExport = response.bytes.map((b: TimestampedByte) => ({
             byte: b.byte,
@@ -102,9 +115,11 @@ export function useDiscoveryExportHandlers({
         let framesToExport: FrameMessage[];
 
         if (captureModeEnabled) {
+          sourceCaptureId = exportCaptureId;
           const response = await getCaptureFramesPaginated(0, captureModeTotalFrames);
           framesToExport = response.frames as FrameMessage[];
         } else if (isSerialMode && framedCaptureId && backendFrameCount > 0) {
+          sourceCaptureId = framedCaptureId;
           const response = await getCaptureFramesPaginatedById(framedCaptureId, 0, backendFrameCount);
           framesToExport = response.frames as FrameMessage[];
         } else if (isSerialMode && framedData.length > 0) {
@@ -128,13 +143,28 @@ export function useDiscoveryExportHandlers({
       });
 
       if (selectedPath) {
-        if (content instanceof Uint8Array) {
-          // Binary data - convert to string for saving via Tauri command
-          const binaryString = Array.from(content).map(b => String.fromCharCode(b)).join('');
-          await saveCatalog(selectedPath, binaryString);
-        } else {
-          await saveCatalog(selectedPath, content);
+        // Binary data - convert to string for saving via Tauri command
+        const fileContent = content instanceof Uint8Array
+          ? Array.from(content).map(b => String.fromCharCode(b)).join('')
+          : content;
+        await saveCatalog(selectedPath, fileContent);
+        // Integrity manifest beside the export: the file's hash plus the
+        // capture's seal, signed when a signing key exists
+        if (sourceCaptureId) {
+          const exportSha256 = await sha256Hex(fileContent);
+          const manifest = await buildExportManifest(sourceCaptureId, format, exportSha256);
+          await saveCatalog(`${selectedPath}.integrity.json`, JSON.stringify(manifest, null, 2));
+          // Context the data format can't carry: time range, sources, buses, catalogue
+          if (writeSidecar) {
+            const sidecar = await buildExportSidecar({
+              capture_id: sourceCaptureId,
+              format,
+              export_sha256: exportSha256,
+            });
+            await saveCatalog(`${selectedPath}${EXPORT_SIDECAR_SUFFIX}`, JSON.stringify(sidecar, null, 2));
+          }
         }
+        void notifyExportCompleted(sourceCaptureId, format, selectedPath);
         closeExportDialog();
       }
     });
@@ -152,6 +182,7 @@ export function useDiscoveryExportHandlers({
     getCaptureBytesPaginated,
     getCaptureFramesPaginated,
     getCaptureFramesPaginatedById,
+    exportCaptureId,
     pickFileToSave,
     saveCatalog,
     closeExportDialog,
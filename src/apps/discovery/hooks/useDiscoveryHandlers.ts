@@ -132,6 +132,8 @@ export interface UseDiscoveryHandlersParams {
   getCaptureFramesPaginated: (offset: number, limit: number) => Promise<{ frames: any[] }>;
   getCaptureFramesPaginatedById: (id: string, offset: number, limit: number) => Promise<{ frames: any[] }>;
   captureMetadata: CaptureMetadata | null;
+  /** Backend capture the export reads from (for its integrity manifest) */
+  exportCaptureId: string | null;
   pickFileToSave: (options: any) => Promise<string | null>;
   saveCatalog: (path: string, content: string) => Promise<void>;
 
@@ -237,6 +239,7 @@ export function useDiscoveryHandlers(params: UseDiscoveryHandlersParams): Discov
     getCaptureBytesPaginated: params.getCaptureBytesPaginated,
     getCaptureFramesPaginated: params.getCaptureFramesPaginated,
     getCaptureFramesPaginatedById: params.getCaptureFramesPaginatedById,
+    exportCaptureId: params.exportCaptureId,
     pickFileToSave: params.pickFileToSave,
     saveCatalog: params.saveCatalog,
     closeExportDialog: params.closeExportDialog,
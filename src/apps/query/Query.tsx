@@ -246,6 +246,7 @@ function QueryInner() {
   useMenuSessionControl({
     panelId: "query",
     sessionState: {
+      sessionId: session.sessionId,
       profileName: ioProfileName ?? null,
       isStreaming,
       isPaused,
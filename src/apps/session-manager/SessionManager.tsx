@@ -252,7 +252,7 @@ export default function SessionManager() {
   const addSourceSession = addSourceSessionId
     ? sessions.find((s) => s.sessionId === addSourceSessionId)
     : null;
-  const realtimeKinds = new Set(["gvret_tcp", "gvret_usb", "slcan", "gs_usb", "socketcan", "serial", "mqtt", "modbus_tcp", "framelink", "virtual"]);
+  const realtimeKinds = new Set(["gvret_tcp", "gvret_usb", "slcan", "custom_serial", "elm327", "lin", "canserver", "websocket", "udp_can", "doip", "wiretap_net", "gs_usb", "pcan", "kvaser", "vector_xl", "j2534", "socketcan", "serial", "mqtt", "modbus_tcp", "framelink", "virtual"]);
   const availableProfiles = addSourceSession
     ? profiles.filter(
         (p) =>
@@ -13,6 +13,10 @@ import {
   getIOSessionState,
   getStateType,
   getReaderSessionJoinerCount,
+  type DeviceDisconnectedPayload,
+  type BusSilencePayload,
+  type BufferCreatedPayload,
+  type BufferOrphanedPayload,
 } from "../../../api/io";
 
 /** Payload for session-lifecycle event from Rust */
@@ -109,6 +113,7 @@ export function useSessionLogSubscription(): void {
   const globalUnlistenRef = useRef<UnlistenFn | null>(null);
   const deviceProbeUnlistenRef = useRef<UnlistenFn | null>(null);
   const mcpConnUnlistenRef = useRef<UnlistenFn | null>(null);
+  const bufferUnlistenRefs = useRef<UnlistenFn[]>([]);
   // Track effect instance to handle React StrictMode double-mount
   const effectInstanceRef = useRef(0);
 
@@ -137,7 +142,7 @@ export function useSessionLogSubscription(): void {
         // Determine mode based on source type (realtime = Live, recorded = Playback)
         let modeLabel = "";
         if (p.source_type) {
-          const realtimeDevices = ["gvret_tcp", "gvret_usb", "slcan", "socketcan", "gs_usb", "mqtt", "modbus_tcp", "serial", "framelink", "virtual"];
+          const realtimeDevices = ["gvret_tcp", "gvret_usb", "slcan", "custom_serial", "elm327", "lin", "canserver", "websocket", "udp_can", "doip", "wiretap_net", "socketcan", "gs_usb", "pcan", "kvaser", "vector_xl", "j2534", "mqtt", "modbus_tcp", "serial", "framelink", "virtual"];
           const isRealtime = realtimeDevices.some((d) => p.source_type?.includes(d));
           modeLabel = isRealtime ? " (Live)" : " (Playback)";
         }
@@ -239,6 +244,44 @@ export function useSessionLogSubscription(): void {
       }
     });
 
+    // Listen to auto-split segments (global; payloads carry the session)
+    listen<BufferOrphanedPayload>("buffer-orphaned", (event) => {
+      if (!isCurrentInstance()) return;
+      const p = event.payload;
+      useSessionLogStore.getState().addEntry({
+        eventType: "buffer-orphaned",
+        sessionId: p.session_id,
+        profileId: null,
+        profileName: null,
+        appName: null,
+        details: `Segment ${p.name} closed (${p.reason}): ${p.count} ${p.kind === "frames" ? "frames" : "bytes"}`,
+      });
+    }).then((unlisten) => {
+      if (isCurrentInstance()) {
+        bufferUnlistenRefs.current.push(unlisten);
+      } else {
+        unlisten();
+      }
+    });
+    listen<BufferCreatedPayload>("buffer-created", (event) => {
+      if (!isCurrentInstance()) return;
+      const p = event.payload;
+      useSessionLogStore.getState().addEntry({
+        eventType: "buffer-created",
+        sessionId: p.session_id,
+        profileId: null,
+        profileName: null,
+        appName: null,
+        details: `Recording continues in ${p.name}`,
+      });
+    }).then((unlisten) => {
+      if (isCurrentInstance()) {
+        bufferUnlistenRefs.current.push(unlisten);
+      } else {
+        unlisten();
+      }
+    });
+
     // Set up listeners for any sessions that already exist
     listActiveSessions().then((sessions) => {
       if (!isCurrentInstance()) return;
@@ -257,6 +300,8 @@ export function useSessionLogSubscription(): void {
       deviceProbeUnlistenRef.current = null;
       mcpConnUnlistenRef.current?.();
       mcpConnUnlistenRef.current = null;
+      for (const unlisten of bufferUnlistenRefs.current) unlisten();
+      bufferUnlistenRefs.current = [];
       for (const sessionId of perSessionListeners.keys()) {
         cleanupPerSessionListeners(sessionId, perSessionListeners);
       }
@@ -437,6 +482,32 @@ async function setupPerSessionListeners(
       })
     )) return;
 
+    // Source dropped, reconnect pending
+    if (pushOrAbort(
+      await listen<DeviceDisconnectedPayload>("device-disconnected", async (event) => {
+        const p = event.payload;
+        if (p.session_id !== sessionId) return;
+        const { profileId, profileName } = await getProfileInfo();
+        addEntry({ eventType: "device-disconnected", sessionId, profileId, profileName, appName: null, details: `${p.profile_id} dropped (${p.reason}), retry ${p.attempt} in ${p.retry_in_ms}ms` });
+      })
+    )) return;
+
+    // Bus silence watchdog
+    for (const eventType of ["bus-silent", "bus-active"] as const) {
+      if (pushOrAbort(
+        await listen<BusSilencePayload>(eventType, async (event) => {
+          const p = event.payload;
+          if (p.session_id !== sessionId) return;
+          const { profileId, profileName } = await getProfileInfo();
+          const seconds = (p.silent_ms / 1000).toFixed(1);
+          const details = eventType === "bus-silent"
+            ? `Bus ${p.bus}: no frames for ${seconds}s`
+            : `Bus ${p.bus}: frames again after ${seconds}s of silence`;
+          addEntry({ eventType, sessionId, profileId, profileName, appName: null, details });
+        })
+      )) return;
+    }
+
     // Track state changes (play/stop/pause) — fetch current state on signal
     if (pushOrAbort(
       await listen<void>(`session-changed:${sessionId}`, async () => {
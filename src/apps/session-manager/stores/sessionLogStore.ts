@@ -36,6 +36,9 @@ export type SessionLogEventType =
   | "buffer-created"
   | "buffer-changed"
   | "device-connected"
+  | "device-disconnected"
+  | "bus-silent"
+  | "bus-active"
   | "device-probe"
   | "mcp-connected"
   | "mcp-disconnected";
@@ -226,6 +229,9 @@ export const EVENT_TYPE_LABELS: Record<SessionLogEventType, string> = {
   "buffer-created": "Buffer",
   "buffer-changed": "Buffer",
   "device-connected": "Connected",
+  "device-disconnected": "Disconnected",
+  "bus-silent": "Bus Silent",
+  "bus-active": "Bus Active",
   "device-probe": "Probe",
   "mcp-connected": "MCP Connect",
   "mcp-disconnected": "MCP Disconnect",
@@ -249,6 +255,9 @@ export const EVENT_TYPE_COLOURS: Record<SessionLogEventType, string> = {
   "buffer-created": badgeSmallSuccess,
   "buffer-changed": badgeSmallInfo,
   "device-connected": badgeSmallSuccess,
+  "device-disconnected": badgeSmallWarning,
+  "bus-silent": badgeSmallWarning,
+  "bus-active": badgeSmallSuccess,
   "device-probe": badgeSmallInfo,
   "mcp-connected": badgeSmallPurple,
   "mcp-disconnected": badgeSmallNeutral,
@@ -272,6 +281,9 @@ export const ALL_EVENT_TYPES: SessionLogEventType[] = [
   "buffer-created",
   "buffer-changed",
   "device-connected",
+  "device-disconnected",
+  "bus-silent",
+  "bus-active",
   "device-probe",
   "mcp-connected",
   "mcp-disconnected",
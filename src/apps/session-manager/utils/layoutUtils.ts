@@ -94,7 +94,7 @@ export function buildSessionGraph(
     .filter((p) => activeProfileIds.has(p.id))
     .sort((a, b) => (profileOutputBus.get(a.id) ?? 0) - (profileOutputBus.get(b.id) ?? 0));
   activeProfiles.forEach((profile, index) => {
-    const isRealtime = ["gvret_tcp", "gvret_usb", "slcan", "socketcan", "gs_usb", "mqtt", "modbus_tcp", "serial", "framelink", "virtual"].includes(
+    const isRealtime = ["gvret_tcp", "gvret_usb", "slcan", "custom_serial", "elm327", "lin", "canserver", "websocket", "udp_can", "doip", "wiretap_net", "socketcan", "gs_usb", "pcan", "kvaser", "vector_xl", "j2534", "mqtt", "modbus_tcp", "serial", "framelink", "virtual"].includes(
       profile.kind
     );
     const deviceBusSet = profileDeviceBuses.get(profile.id);
@@ -19,6 +19,7 @@ import {
   Shield,
   Cpu,
   Bot,
+  Radio,
 } from "lucide-react";
 import { bgDataView, borderDataView } from "../../styles/colourTokens";
 import LocationsView from "./views/LocationsView";
@@ -32,6 +33,7 @@ import SelectionSetsView from "./views/SelectionSetsView";
 import DashboardLayoutsView from "./views/DashboardLayoutsView";
 import PrivacyView from "./views/PrivacyView";
 import McpServerView from "./views/McpServerView";
+import RemoteApiView from "./views/RemoteApiView";
 import Devices from "../devices/Devices";
 import IOProfileDialog from "./dialogs/IOProfileDialog";
 import EditCatalogDialog from "./dialogs/EditCatalogDialog";
@@ -149,6 +151,8 @@ export default function Settings() {
   const setDecoderMaxDecodedFrames = useSettingsStore((s) => s.setDecoderMaxDecodedFrames);
   const decoderMaxDecodedPerSource = useSettingsStore((s) => s.buffers.decoderMaxDecodedPerSource);
   const setDecoderMaxDecodedPerSource = useSettingsStore((s) => s.setDecoderMaxDecodedPerSource);
+  const captureDbCacheMb = useSettingsStore((s) => s.buffers.captureDbCacheMb);
+  const setCaptureDbCacheMb = useSettingsStore((s) => s.setCaptureDbCacheMb);
   const transmitMaxHistory = useSettingsStore((s) => s.buffers.transmitMaxHistory);
   const setTransmitMaxHistory = useSettingsStore((s) => s.setTransmitMaxHistory);
 
@@ -263,6 +267,7 @@ export default function Settings() {
     { id: "dashboard-layouts", label: t("sidebar.dashboardLayouts"), icon: LayoutGrid },
     { id: "mcp", label: "MCP Server", icon: Bot },
     { id: "privacy", label: t("sidebar.privacy"), icon: Shield },
+    { id: "remote-api", label: "Remote API", icon: Radio },
     { id: "selection-sets", label: t("sidebar.selectionSets"), icon: Star },
     // Hide Storage on iOS - custom directory paths aren't supported
     ...(!isIOSPlatform
@@ -358,6 +363,8 @@ export default function Settings() {
               onChangeClearCapturesOnStart={setClearCapturesOnStart}
               captureStorage={captureStorage}
               onChangeCaptureStorage={setCaptureStorage}
+              captureDbCacheMb={captureDbCacheMb}
+              onChangeCaptureDbCacheMb={setCaptureDbCacheMb}
               discoveryHistorySize={discoveryHistorySize}
               onChangeDiscoveryHistorySize={setDiscoveryHistorySize}
               queryResultLimit={queryResultLimit}
@@ -388,6 +395,8 @@ export default function Settings() {
 
           {currentSection === "mcp" && <McpServerView />}
 
+          {currentSection === "remote-api" && <RemoteApiView />}
+
           {/* Data IO Section */}
           {currentSection === "data-io" && (
             <DataIOView
@@ -108,6 +108,12 @@ export default function GsUsbDevicePicker({ value, onChange }: Props) {
             t("gsUsbDevicePicker.usbBusAddress", { bus: selectedDevice.bus, address: selectedDevice.address }),
             selectedDevice.interface_name &&
               t("gsUsbDevicePicker.interfaceLabel", { name: selectedDevice.interface_name }),
+            selectedDevice.channel !== null &&
+              (selectedDevice.channel_count ?? 1) > 1 &&
+              t("gsUsbDevicePicker.channelLabel", {
+                channel: selectedDevice.channel,
+                count: selectedDevice.channel_count,
+              }),
             selectedDevice.interface_up !== null &&
               (selectedDevice.interface_up ? t("gsUsbDevicePicker.interfaceUp") : t("gsUsbDevicePicker.interfaceDown")),
           ]
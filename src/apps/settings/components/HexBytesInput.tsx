@@ -0,0 +1,54 @@
+// src/apps/settings/components/HexBytesInput.tsx
+//
+// Text input for a short byte sequence typed as hex ("AA 55").
+
+import { useState, useEffect } from "react";
+import { Input } from "../../../components/forms";
+
+interface Props {
+  /** Current bytes */
+  value: number[];
+  /** Called with the parsed bytes when the field loses focus */
+  onChange: (bytes: number[]) => void;
+  placeholder?: string;
+}
+
+/** Format bytes as space-separated hex pairs. */
+export function formatHexBytes(bytes: number[]): string {
+  return bytes.map((b) => b.toString(16).toUpperCase().padStart(2, "0")).join(" ");
+}
+
+/** Parse space- or comma-separated hex pairs ("AA 55", "0xAA,0x55"); invalid tokens are dropped. */
+export function parseHexBytes(text: string): number[] {
+  return text
+    .split(/[\s,]+/)
+    .map((token) => token.replace(/^0x/i, ""))
+    .filter((token) => /^[0-9a-f]{1,2}$/i.test(token))
+    .map((token) => parseInt(token, 16));
+}
+
+export default function HexBytesInput({ value, onChange, placeholder }: Props) {
+  const [text, setText] = useState(formatHexBytes(value));
+
+  // Follow external changes (dialog reopened, profile switched)
+  useEffect(() => {
+    setText(formatHexBytes(value));
+  }, [value]);
+
+  const commit = () => {
+    const bytes = parseHexBytes(text);
+    setText(formatHexBytes(bytes));
+    onChange(bytes);
+  };
+
+  return (
+    <Input
+      variant="default"
+      className="font-mono"
+      value={text}
+      onChange={(e) => setText(e.target.value)}
+      onBlur={commit}
+      placeholder={placeholder}
+    />
+  );
+}
@@ -0,0 +1,95 @@
+// src/apps/settings/components/J2534DevicePicker.tsx
+//
+// Component for selecting a J2534 PassThru device.
+
+import { useState, useEffect } from "react";
+import { useTranslation } from "react-i18next";
+import { RefreshCw } from "lucide-react";
+import { iconLg } from "../../../styles/spacing";
+import {
+  listJ2534Devices,
+  formatJ2534DeviceDisplay,
+  type J2534DeviceInfo,
+} from "../../../api/j2534";
+import { Select } from "../../../components/forms";
+import { iconButtonBase } from "../../../styles/buttonStyles";
+import { textDanger, spaceYSmall, helpText } from "../../../styles";
+
+interface Props {
+  /** Currently selected device name */
+  value: string;
+  /** Called when device selection changes */
+  onChange: (name: string, device: J2534DeviceInfo | null) => void;
+}
+
+export default function J2534DevicePicker({ value, onChange }: Props) {
+  const { t } = useTranslation("settings");
+  const [devices, setDevices] = useState<J2534DeviceInfo[]>([]);
+  const [isRefreshing, setIsRefreshing] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refreshDevices = async () => {
+    setIsRefreshing(true);
+    setError(null);
+    try {
+      const available = await listJ2534Devices();
+      setDevices(available);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsRefreshing(false);
+    }
+  };
+
+  useEffect(() => {
+    refreshDevices();
+  }, []);
+
+  const handleChange = (selected: string) => {
+    if (!selected) {
+      onChange("", null);
+      return;
+    }
+    const device = devices.find((d) => d.name === selected);
+    onChange(selected, device || null);
+  };
+
+  return (
+    <div className={spaceYSmall}>
+      <div className="flex gap-2">
+        <Select
+          variant="default"
+          value={value}
+          onChange={(e) => handleChange(e.target.value)}
+          className="flex-1"
+        >
+          <option value="">{t("j2534DevicePicker.selectDevice")}</option>
+          {devices.map((device) => (
+            <option key={device.name} value={device.name}>
+              {formatJ2534DeviceDisplay(device)}
+            </option>
+          ))}
+        </Select>
+        <button
+          type="button"
+          onClick={refreshDevices}
+          disabled={isRefreshing}
+          className={`${iconButtonBase} disabled:opacity-50`}
+          title={t("j2534DevicePicker.refreshDeviceList")}
+        >
+          <RefreshCw
+            className={`${iconLg} ${isRefreshing ? "animate-spin" : ""}`}
+          />
+        </button>
+      </div>
+
+      {error && <p className={`text-sm ${textDanger}`}>{error}</p>}
+
+      {devices.length === 0 && !isRefreshing && !error && (
+        <p className={helpText}>
+          {t("j2534DevicePicker.noDevicesFound")}
+        </p>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,95 @@
+// src/apps/settings/components/KvaserDevicePicker.tsx
+//
+// Component for selecting a Kvaser device.
+
+import { useState, useEffect } from "react";
+import { useTranslation } from "react-i18next";
+import { RefreshCw } from "lucide-react";
+import { iconLg } from "../../../styles/spacing";
+import {
+  listKvaserDevices,
+  formatKvaserDeviceDisplay,
+  type KvaserDeviceInfo,
+} from "../../../api/kvaser";
+import { Select } from "../../../components/forms";
+import { iconButtonBase } from "../../../styles/buttonStyles";
+import { textDanger, spaceYSmall, helpText } from "../../../styles";
+
+interface Props {
+  /** Currently selected card serial number */
+  value: string;
+  /** Called when device selection changes */
+  onChange: (serial: string, device: KvaserDeviceInfo | null) => void;
+}
+
+export default function KvaserDevicePicker({ value, onChange }: Props) {
+  const { t } = useTranslation("settings");
+  const [devices, setDevices] = useState<KvaserDeviceInfo[]>([]);
+  const [isRefreshing, setIsRefreshing] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refreshDevices = async () => {
+    setIsRefreshing(true);
+    setError(null);
+    try {
+      const available = await listKvaserDevices();
+      setDevices(available);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsRefreshing(false);
+    }
+  };
+
+  useEffect(() => {
+    refreshDevices();
+  }, []);
+
+  const handleChange = (selected: string) => {
+    if (!selected) {
+      onChange("", null);
+      return;
+    }
+    const device = devices.find((d) => String(d.serial) === selected);
+    onChange(selected, device || null);
+  };
+
+  return (
+    <div className={spaceYSmall}>
+      <div className="flex gap-2">
+        <Select
+          variant="default"
+          value={value}
+          onChange={(e) => handleChange(e.target.value)}
+          className="flex-1"
+        >
+          <option value="">{t("kvaserDevicePicker.selectDevice")}</option>
+          {devices.map((device) => (
+            <option key={device.serial} value={String(device.serial)}>
+              {formatKvaserDeviceDisplay(device)}
+            </option>
+          ))}
+        </Select>
+        <button
+          type="button"
+          onClick={refreshDevices}
+          disabled={isRefreshing}
+          className={`${iconButtonBase} disabled:opacity-50`}
+          title={t("kvaserDevicePicker.refreshDeviceList")}
+        >
+          <RefreshCw
+            className={`${iconLg} ${isRefreshing ? "animate-spin" : ""}`}
+          />
+        </button>
+      </div>
+
+      {error && <p className={`text-sm ${textDanger}`}>{error}</p>}
+
+      {devices.length === 0 && !isRefreshing && !error && (
+        <p className={helpText}>
+          {t("kvaserDevicePicker.noDevicesFound")}
+        </p>
+      )}
+    </div>
+  );
+}
@@ -1,13 +1,23 @@
 // src/apps/settings/components/LinuxCanSetupHelper.tsx
 //
-// Shows the setup command required to configure a CAN interface on Linux.
-// Provides a copy button for easy use.
+// Helps configure a CAN interface on Linux: shows its current state, can
+// apply the profile's bitrate/FD settings or bring it up and down (polkit
+// asks for the password), and still offers the equivalent terminal command
+// with a copy button.
 
-import { useState, useEffect } from "react";
-import { Copy, Check, Terminal } from "lucide-react";
+import { useState, useEffect, useCallback } from "react";
+import { useTranslation } from "react-i18next";
+import { Copy, Check, Terminal, RefreshCw } from "lucide-react";
 import { iconMd, flexRowGap2 } from "../../../styles/spacing";
-import { getCanSetupCommand } from "../../../api/gs_usb";
-import { alertWarning, helpText } from "../../../styles";
+import {
+  getCanSetupCommand,
+  getCanInterfaceStatus,
+  configureCanInterface,
+  setCanInterfaceUp,
+  type CanInterfaceStatus,
+} from "../../../api/gs_usb";
+import { buttonBase, iconButtonBase } from "../../../styles/buttonStyles";
+import { alertWarning, helpText, textDanger } from "../../../styles";
 import { COPY_FEEDBACK_TIMEOUT_MS } from "../../../constants";
 
 interface Props {
@@ -15,11 +25,27 @@ interface Props {
   interfaceName: string;
   /** CAN bitrate in bits/second */
   bitrate: number;
+  /** Configure the interface for CAN FD */
+  enableFd?: boolean;
+  /** CAN FD data phase bitrate in bits/second */
+  dataBitrate?: number | null;
+  /** Configure the interface listen-only */
+  listenOnly?: boolean;
 }
 
-export default function LinuxCanSetupHelper({ interfaceName, bitrate }: Props) {
+export default function LinuxCanSetupHelper({
+  interfaceName,
+  bitrate,
+  enableFd = false,
+  dataBitrate = null,
+  listenOnly = false,
+}: Props) {
+  const { t } = useTranslation("settings");
   const [setupCommand, setSetupCommand] = useState("");
   const [copied, setCopied] = useState(false);
+  const [status, setStatus] = useState<CanInterfaceStatus | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
 
   useEffect(() => {
     if (interfaceName && bitrate) {
@@ -31,6 +57,40 @@ export default function LinuxCanSetupHelper({ interfaceName, bitrate }: Props) {
     }
   }, [interfaceName, bitrate]);
 
+  const refreshStatus = useCallback(() => {
+    if (!interfaceName) {
+      setStatus(null);
+      return;
+    }
+    getCanInterfaceStatus(interfaceName)
+      .then(setStatus)
+      .catch(() => setStatus(null));
+  }, [interfaceName]);
+
+  useEffect(() => {
+    refreshStatus();
+  }, [refreshStatus]);
+
+  const runPrivileged = async (action: () => Promise<void>) => {
+    setBusy(true);
+    setError(null);
+    try {
+      await action();
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setBusy(false);
+      refreshStatus();
+    }
+  };
+
+  const handleConfigure = () =>
+    runPrivileged(() =>
+      configureCanInterface(interfaceName, bitrate, enableFd, enableFd ? dataBitrate : null, listenOnly)
+    );
+
+  const handleToggleUp = () => runPrivileged(() => setCanInterfaceUp(interfaceName, !status?.up));
+
   const handleCopy = async () => {
     if (!setupCommand) return;
 
@@ -61,10 +121,43 @@ export default function LinuxCanSetupHelper({ interfaceName, bitrate }: Props) {
         <Terminal className={`${iconMd} mt-0.5 flex-shrink-0`} />
         <div className="flex-1">
           <p className="text-sm font-medium mb-2">
-            Linux setup required
+            {t("linuxCanSetup.title")}
           </p>
+
+          <div className={`${flexRowGap2} mb-2`}>
+            <p className={`${helpText} flex-1`}>
+              {status
+                ? t(status.up ? "linuxCanSetup.statusUp" : "linuxCanSetup.statusDown", {
+                    name: interfaceName,
+                    bitrate: status.bitrate ?? "—",
+                    state: status.state ?? "—",
+                  })
+                : t("linuxCanSetup.statusUnknown", { name: interfaceName })}
+            </p>
+            <button
+              type="button"
+              onClick={refreshStatus}
+              className={iconButtonBase}
+              title={t("linuxCanSetup.refreshStatus")}
+            >
+              <RefreshCw className={iconMd} />
+            </button>
+          </div>
+
+          <div className={`${flexRowGap2} mb-2`}>
+            <button type="button" onClick={handleConfigure} disabled={busy} className={buttonBase}>
+              {t("linuxCanSetup.configure", { bitrate })}
+            </button>
+            {status && (
+              <button type="button" onClick={handleToggleUp} disabled={busy} className={buttonBase}>
+                {t(status.up ? "linuxCanSetup.bringDown" : "linuxCanSetup.bringUp")}
+              </button>
+            )}
+          </div>
+          {error && <p className={`${textDanger} text-xs mb-2`}>{error}</p>}
+
           <p className={`${helpText} mb-2`}>
-            Run this command in your terminal to configure the CAN interface:
+            {t("linuxCanSetup.manualHint")}
           </p>
           <div className={flexRowGap2}>
             <code className="flex-1 p-2 bg-[var(--bg-warning)] rounded text-xs font-mono break-all">
@@ -74,7 +167,7 @@ export default function LinuxCanSetupHelper({ interfaceName, bitrate }: Props) {
               type="button"
               onClick={handleCopy}
               className="p-1.5 hover:bg-[var(--hover-bg-warning)] rounded transition-colors flex-shrink-0"
-              title={copied ? "Copied!" : "Copy to clipboard"}
+              title={copied ? t("linuxCanSetup.copied") : t("linuxCanSetup.copy")}
             >
               {copied ? (
                 <Check className={`${iconMd} text-[color:var(--accent-success)]`} />
@@ -84,7 +177,7 @@ export default function LinuxCanSetupHelper({ interfaceName, bitrate }: Props) {
             </button>
           </div>
           <p className={`${helpText} mt-2 text-xs`}>
-            Note: You may need to adjust udev rules for non-root access, or run WireTAP with elevated privileges.
+            {t("linuxCanSetup.polkitNote")}
           </p>
         </div>
       </div>
@@ -0,0 +1,95 @@
+// src/apps/settings/components/PcanDevicePicker.tsx
+//
+// Component for selecting a PCAN-USB (PEAK) channel.
+
+import { useState, useEffect } from "react";
+import { useTranslation } from "react-i18next";
+import { RefreshCw } from "lucide-react";
+import { iconLg } from "../../../styles/spacing";
+import {
+  listPcanDevices,
+  formatPcanDeviceDisplay,
+  type PcanDeviceInfo,
+} from "../../../api/pcan";
+import { Select } from "../../../components/forms";
+import { iconButtonBase } from "../../../styles/buttonStyles";
+import { textDanger, spaceYSmall, helpText } from "../../../styles";
+
+interface Props {
+  /** Currently selected channel number ("1".."16") */
+  value: string;
+  /** Called when channel selection changes */
+  onChange: (channel: string, device: PcanDeviceInfo | null) => void;
+}
+
+export default function PcanDevicePicker({ value, onChange }: Props) {
+  const { t } = useTranslation("settings");
+  const [devices, setDevices] = useState<PcanDeviceInfo[]>([]);
+  const [isRefreshing, setIsRefreshing] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refreshDevices = async () => {
+    setIsRefreshing(true);
+    setError(null);
+    try {
+      const available = await listPcanDevices();
+      setDevices(available);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsRefreshing(false);
+    }
+  };
+
+  useEffect(() => {
+    refreshDevices();
+  }, []);
+
+  const handleChange = (selected: string) => {
+    if (!selected) {
+      onChange("", null);
+      return;
+    }
+    const device = devices.find((d) => String(d.channel) === selected);
+    onChange(selected, device || null);
+  };
+
+  return (
+    <div className={spaceYSmall}>
+      <div className="flex gap-2">
+        <Select
+          variant="default"
+          value={value}
+          onChange={(e) => handleChange(e.target.value)}
+          className="flex-1"
+        >
+          <option value="">{t("pcanDevicePicker.selectDevice")}</option>
+          {devices.map((device) => (
+            <option key={device.channel} value={String(device.channel)}>
+              {formatPcanDeviceDisplay(device)}
+            </option>
+          ))}
+        </Select>
+        <button
+          type="button"
+          onClick={refreshDevices}
+          disabled={isRefreshing}
+          className={`${iconButtonBase} disabled:opacity-50`}
+          title={t("pcanDevicePicker.refreshDeviceList")}
+        >
+          <RefreshCw
+            className={`${iconLg} ${isRefreshing ? "animate-spin" : ""}`}
+          />
+        </button>
+      </div>
+
+      {error && <p className={`text-sm ${textDanger}`}>{error}</p>}
+
+      {devices.length === 0 && !isRefreshing && !error && (
+        <p className={helpText}>
+          {t("pcanDevicePicker.noDevicesFound")}
+        </p>
+      )}
+    </div>
+  );
+}
@@ -7,6 +7,7 @@ import { useTranslation } from "react-i18next";
 import { RefreshCw } from "lucide-react";
 import { iconLg } from "../../../styles/spacing";
 import { listSerialPorts, SerialPortInfo } from "../../../api/serial";
+import { onUsbHotplug } from "../../../api/usbHotplug";
 import { Input, Select } from "../../../components/forms";
 import { iconButtonBase } from "../../../styles/buttonStyles";
 import { helpText, textDanger, spaceYSmall } from "../../../styles";
@@ -37,6 +38,11 @@ export default function SerialPortPicker({ value, onChange }: Props) {
 
   useEffect(() => {
     refreshPorts();
+    // USB serial adapters plugged in or pulled out while the picker is open
+    const unlisten = onUsbHotplug(() => refreshPorts());
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
   // Format port display text
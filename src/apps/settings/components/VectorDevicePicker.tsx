@@ -0,0 +1,95 @@
+// src/apps/settings/components/VectorDevicePicker.tsx
+//
+// Component for selecting a Vector interface.
+
+import { useState, useEffect } from "react";
+import { useTranslation } from "react-i18next";
+import { RefreshCw } from "lucide-react";
+import { iconLg } from "../../../styles/spacing";
+import {
+  listVectorDevices,
+  formatVectorDeviceDisplay,
+  type VectorDeviceInfo,
+} from "../../../api/vector_xl";
+import { Select } from "../../../components/forms";
+import { iconButtonBase } from "../../../styles/buttonStyles";
+import { textDanger, spaceYSmall, helpText } from "../../../styles";
+
+interface Props {
+  /** Currently selected interface serial number */
+  value: string;
+  /** Called when device selection changes */
+  onChange: (serial: string, device: VectorDeviceInfo | null) => void;
+}
+
+export default function VectorDevicePicker({ value, onChange }: Props) {
+  const { t } = useTranslation("settings");
+  const [devices, setDevices] = useState<VectorDeviceInfo[]>([]);
+  const [isRefreshing, setIsRefreshing] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refreshDevices = async () => {
+    setIsRefreshing(true);
+    setError(null);
+    try {
+      const available = await listVectorDevices();
+      setDevices(available);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setIsRefreshing(false);
+    }
+  };
+
+  useEffect(() => {
+    refreshDevices();
+  }, []);
+
+  const handleChange = (selected: string) => {
+    if (!selected) {
+      onChange("", null);
+      return;
+    }
+    const device = devices.find((d) => String(d.serial) === selected);
+    onChange(selected, device || null);
+  };
+
+  return (
+    <div className={spaceYSmall}>
+      <div className="flex gap-2">
+        <Select
+          variant="default"
+          value={value}
+          onChange={(e) => handleChange(e.target.value)}
+          className="flex-1"
+        >
+          <option value="">{t("vectorDevicePicker.selectDevice")}</option>
+          {devices.map((device) => (
+            <option key={device.serial} value={String(device.serial)}>
+              {formatVectorDeviceDisplay(device)}
+            </option>
+          ))}
+        </Select>
+        <button
+          type="button"
+          onClick={refreshDevices}
+          disabled={isRefreshing}
+          className={`${iconButtonBase} disabled:opacity-50`}
+          title={t("vectorDevicePicker.refreshDeviceList")}
+        >
+          <RefreshCw
+            className={`${iconLg} ${isRefreshing ? "animate-spin" : ""}`}
+          />
+        </button>
+      </div>
+
+      {error && <p className={`text-sm ${textDanger}`}>{error}</p>}
+
+      {devices.length === 0 && !isRefreshing && !error && (
+        <p className={helpText}>
+          {t("vectorDevicePicker.noDevicesFound")}
+        </p>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,101 @@
+// src/apps/settings/components/WebSocketFieldMapping.tsx
+//
+// Maps WebSocket message fields onto frame fields, one row per field: a key
+// path for JSON messages, or byte offset, width and bit mask for binary
+// records. Rows left empty are dropped from the mapping.
+
+import { useTranslation } from "react-i18next";
+import { Input } from "../../../components/forms";
+import type { WebSocketFieldMapping as FieldMapping, WebSocketFieldRole } from "../../../hooks/useSettings";
+import { helpText } from "../../../styles";
+
+const ROLES: WebSocketFieldRole[] = ["frame_id", "data", "bus", "dlc", "extended", "fd", "timestamp"];
+
+/** SavvyCAN key for each role, used when a JSON mapping is empty */
+const DEFAULT_PATHS: Record<WebSocketFieldRole, string> = {
+  frame_id: "id",
+  data: "data",
+  bus: "bus",
+  dlc: "dlc",
+  extended: "extended",
+  fd: "fd",
+  timestamp: "timestamp",
+};
+
+interface Props {
+  format: "json" | "binary";
+  value: FieldMapping[];
+  onChange: (mapping: FieldMapping[]) => void;
+}
+
+function parseNumber(text: string, radix = 10): number | undefined {
+  const trimmed = radix === 16 ? text.trim().replace(/^0x/i, "") : text.trim();
+  if (!trimmed) return undefined;
+  const value = parseInt(trimmed, radix);
+  return Number.isNaN(value) ? undefined : value;
+}
+
+export default function WebSocketFieldMapping({ format, value, onChange }: Props) {
+  const { t } = useTranslation("settings");
+
+  const entryFor = (role: WebSocketFieldRole): FieldMapping =>
+    value.find((m) => m.role === role) ?? { role };
+
+  const update = (role: WebSocketFieldRole, patch: Partial<FieldMapping>) => {
+    const entry = { ...entryFor(role), ...patch };
+    const isSet = format === "json" ? !!entry.path : entry.offset !== undefined;
+    const rest = value.filter((m) => m.role !== role);
+    const next = isSet ? [...rest, entry] : rest;
+    onChange(ROLES.flatMap((r) => next.filter((m) => m.role === r)));
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="grid grid-cols-[8rem_1fr] gap-2 items-center">
+        {ROLES.map((role) => {
+          const entry = entryFor(role);
+          return (
+            <div key={role} className="contents">
+              <span className="text-sm">{t(`ioProfileDialog.websocket.roles.${role}`)}</span>
+              {format === "json" ? (
+                <Input
+                  variant="default"
+                  value={entry.path ?? ""}
+                  onChange={(e) => update(role, { path: e.target.value.trim() || undefined })}
+                  placeholder={DEFAULT_PATHS[role]}
+                />
+              ) : (
+                <div className="grid grid-cols-3 gap-2">
+                  <Input
+                    variant="default"
+                    type="number"
+                    value={entry.offset ?? ""}
+                    onChange={(e) => update(role, { offset: parseNumber(e.target.value) })}
+                    placeholder={t("ioProfileDialog.websocket.offset")}
+                  />
+                  <Input
+                    variant="default"
+                    type="number"
+                    value={entry.length ?? ""}
+                    onChange={(e) => update(role, { length: parseNumber(e.target.value) })}
+                    placeholder={t("ioProfileDialog.websocket.length")}
+                  />
+                  <Input
+                    variant="default"
+                    value={entry.mask !== undefined ? `0x${entry.mask.toString(16).toUpperCase()}` : ""}
+                    onChange={(e) => update(role, { mask: parseNumber(e.target.value, 16) })}
+                    placeholder={t("ioProfileDialog.websocket.mask")}
+                    disabled={role === "data"}
+                  />
+                </div>
+              )}
+            </div>
+          );
+        })}
+      </div>
+      <p className={helpText}>
+        {t(format === "json" ? "ioProfileDialog.websocket.jsonMappingHint" : "ioProfileDialog.websocket.binaryMappingHint")}
+      </p>
+    </div>
+  );
+}
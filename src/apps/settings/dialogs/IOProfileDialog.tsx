@@ -11,6 +11,12 @@ import { isProfileKind } from "../../../hooks/useSettings";
 import { useSettingsStore } from "../stores/settingsStore";
 import SerialPortPicker from "../components/SerialPortPicker";
 import GsUsbDevicePicker from "../components/GsUsbDevicePicker";
+import PcanDevicePicker from "../components/PcanDevicePicker";
+import KvaserDevicePicker from "../components/KvaserDevicePicker";
+import VectorDevicePicker from "../components/VectorDevicePicker";
+import J2534DevicePicker from "../components/J2534DevicePicker";
+import HexBytesInput from "../components/HexBytesInput";
+import WebSocketFieldMapping from "../components/WebSocketFieldMapping";
 import LinuxCanSetupHelper from "../components/LinuxCanSetupHelper";
 import SecurePasswordField from "../components/SecurePasswordField";
 import IODeviceStatus, { type DeviceProbeState, type DeviceProbeResult } from "../components/IODeviceStatus";
@@ -22,7 +28,7 @@ import {
   type SignalDescriptor,
 } from "../../../api/framelink";
 import { DeviceBusConfig, type BusMappingWithProtocol } from "../../../dialogs/io-source-picker";
-import { Input, Select, FormField, PrimaryButton, SecondaryButton } from "../../../components/forms";
+import { Input, Select, Textarea, FormField, PrimaryButton, SecondaryButton } from "../../../components/forms";
 import BaudRateSelect from "../../../components/forms/BaudRateSelect";
 import {
   h2,
@@ -42,8 +48,17 @@ import {
 } from "../../../styles";
 import { probeSlcanDevice } from "../../../api/serial";
 import { probeGsUsbDevice } from "../../../api/gs_usb";
-import { probeDevice, type GvretDeviceInfo } from "../../../api/io";
-import { tlog } from "../../../api/settings";
+import {
+  discoverGvretDevices,
+  getMqttBrokerStatus,
+  probeDevice,
+  startMqttBroker,
+  stopMqttBroker,
+  type GvretCandidate,
+  type GvretDeviceInfo,
+  type MqttBrokerStatus,
+} from "../../../api/io";
+import { tlog, validateProfile, type ProfileFieldIssue } from "../../../api/settings";
 import { getPlatform, isWindows, isLinux, isMacOS } from "../../../utils/platform";
 import { getAvailableProfileKinds, type Platform, type ProfileKind } from "../../../utils/profileTraits";
 import type { GvretInterfaceConfig } from "../../../hooks/useSettings";
@@ -112,6 +127,21 @@ export default function IOProfileDialog({
   const [gvretDeviceInfo, setGvretDeviceInfo] = useState<GvretDeviceInfo | null>(null);
   const [gvretProbeError, setGvretProbeError] = useState<string | null>(null);
 
+  // Schema issues from the last save attempt
+  const [fieldIssues, setFieldIssues] = useState<ProfileFieldIssue[]>([]);
+  useEffect(() => {
+    if (isOpen) setFieldIssues([]);
+  }, [isOpen]);
+
+  // Embedded MQTT broker state (MQTT profiles)
+  const [mqttBroker, setMqttBroker] = useState<MqttBrokerStatus | null>(null);
+  const [mqttBrokerError, setMqttBrokerError] = useState<string | null>(null);
+
+  // GVRET TCP network scan state
+  const [gvretScanning, setGvretScanning] = useState(false);
+  const [gvretCandidates, setGvretCandidates] = useState<GvretCandidate[] | null>(null);
+  const [gvretScanError, setGvretScanError] = useState<string | null>(null);
+
   // FrameLink interface configuration state — per-interface signal map keyed by iface_index
   const [flSignalsByIface, setFlSignalsByIface] = useState<Record<number, SignalDescriptor[]>>({});
   const [flLoading, setFlLoading] = useState(false);
@@ -226,7 +256,7 @@ export default function IOProfileDialog({
 
   // Reset GVRET probe state when dialog closes or profile type changes
   useEffect(() => {
-    if (!isOpen || (profileForm.kind !== "gvret_tcp" && profileForm.kind !== "gvret_usb")) {
+    if (!isOpen || (profileForm.kind !== "gvret_tcp" && profileForm.kind !== "gvret_usb" && profileForm.kind !== "canserver" && profileForm.kind !== "websocket" && profileForm.kind !== "udp_can")) {
       setGvretProbeState("idle");
       setGvretDeviceInfo(null);
       setGvretProbeError(null);
@@ -235,7 +265,7 @@ export default function IOProfileDialog({
 
   // Initialize GVRET device info from profile connection if available
   useEffect(() => {
-    if (isOpen && (isProfileKind(profileForm, "gvret_tcp") || isProfileKind(profileForm, "gvret_usb"))) {
+    if (isOpen && (isProfileKind(profileForm, "gvret_tcp") || isProfileKind(profileForm, "gvret_usb") || isProfileKind(profileForm, "canserver") || isProfileKind(profileForm, "websocket") || isProfileKind(profileForm, "udp_can"))) {
       const busCount = profileForm.connection._probed_bus_count;
       if (typeof busCount === "number" && busCount > 0) {
         setGvretDeviceInfo({ bus_count: busCount });
@@ -246,7 +276,7 @@ export default function IOProfileDialog({
 
   // Probe GVRET device
   const probeGvret = useCallback(async () => {
-    if (profileForm.kind !== "gvret_tcp" && profileForm.kind !== "gvret_usb") return;
+    if (profileForm.kind !== "gvret_tcp" && profileForm.kind !== "gvret_usb" && profileForm.kind !== "canserver" && profileForm.kind !== "websocket" && profileForm.kind !== "udp_can") return;
     if (!editingProfileId) {
       setGvretProbeError("Save profile first to probe device");
       setGvretProbeState("error");
@@ -295,9 +325,63 @@ export default function IOProfileDialog({
     }
   }, [editingProfileId, profileForm, onUpdateConnectionField]);
 
+  // Embedded broker status while editing an MQTT profile
+  useEffect(() => {
+    if (!isOpen || profileForm.kind !== "mqtt") return;
+    getMqttBrokerStatus()
+      .then(setMqttBroker)
+      .catch(() => setMqttBroker(null));
+  }, [isOpen, profileForm.kind]);
+
+  // Start/stop the embedded broker; starting points this profile at it
+  const toggleMqttBroker = useCallback(async () => {
+    setMqttBrokerError(null);
+    try {
+      if (mqttBroker?.running) {
+        setMqttBroker(await stopMqttBroker());
+      } else {
+        const port = Number(profileForm.connection.port) || 1883;
+        const status = await startMqttBroker({ port });
+        setMqttBroker(status);
+        onUpdateConnectionField("host", "localhost");
+        onUpdateConnectionField("port", String(status.port ?? port));
+      }
+    } catch (e) {
+      setMqttBrokerError(e instanceof Error ? e.message : String(e));
+    }
+  }, [mqttBroker, profileForm.connection.port, onUpdateConnectionField]);
+
+  // Scan the LAN for GVRET TCP devices to prefill host/port
+  const scanGvretNetwork = useCallback(async () => {
+    setGvretScanning(true);
+    setGvretScanError(null);
+    try {
+      const result = await discoverGvretDevices();
+      setGvretCandidates(result.candidates);
+      if (result.candidates.length === 0) {
+        setGvretScanError([t("ioProfileDialog.gvret.scanNoneFound"), ...result.errors].join(" "));
+      } else if (result.candidates.length === 1) {
+        onUpdateConnectionField("host", result.candidates[0].host);
+        onUpdateConnectionField("port", String(result.candidates[0].port));
+      }
+    } catch (e) {
+      setGvretScanError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setGvretScanning(false);
+    }
+  }, [onUpdateConnectionField, t]);
+
   // Convert GvretInterfaceConfig[] to BusMappingWithProtocol[] for the component
   const getDeviceBusConfig = useCallback((): BusMappingWithProtocol[] => {
-    if (profileForm.kind !== "gvret_tcp" && profileForm.kind !== "gvret_usb") return [];
+    if (
+      profileForm.kind !== "gvret_tcp" &&
+      profileForm.kind !== "gvret_usb" &&
+      profileForm.kind !== "kvaser" &&
+      profileForm.kind !== "vector_xl" &&
+      profileForm.kind !== "canserver" &&
+      profileForm.kind !== "websocket" &&
+      profileForm.kind !== "udp_can"
+    ) return [];
     const interfaces = profileForm.connection.interfaces;
     if (!interfaces || interfaces.length === 0) {
       // No interfaces configured - show empty state
@@ -324,6 +408,19 @@ export default function IOProfileDialog({
     [onUpdateConnectionField]
   );
 
+  // Check connection settings against the kind's schema before saving;
+  // errors block the save, warnings (unknown fields) are shown but allowed
+  const handleSave = useCallback(async () => {
+    try {
+      const result = await validateProfile(profileForm);
+      setFieldIssues(result.issues);
+      if (!result.valid) return;
+    } catch (e) {
+      tlog.info(`[IOProfileDialog] Profile validation unavailable: ${e}`);
+    }
+    onSave();
+  }, [profileForm, onSave]);
+
   // Probe slcan device
   const probeSlcan = useCallback(async () => {
     if (!isProfileKind(profileForm, "slcan")) return;
@@ -405,8 +502,12 @@ export default function IOProfileDialog({
   const [gsUsbProbeResult, setGsUsbProbeResult] = useState<DeviceProbeResult | null>(null);
 
   // Probe gs_usb device (Windows/macOS - uses nusb userspace driver)
+  // gs_usb probing goes over USB: always on Windows/macOS, on Linux only in direct USB mode
+  const canProbeGsUsb =
+    platformIsWindows || platformIsMacos || (platformIsLinux && isProfileKind(profileForm, "gs_usb") && profileForm.connection.direct_usb === true);
+
   const probeGsUsb = useCallback(async () => {
-    if (!platformIsWindows && !platformIsMacos) return;
+    if (!canProbeGsUsb) return;
     if (!isProfileKind(profileForm, "gs_usb")) return;
 
     const bus = parseInt(profileForm.connection.bus || "0", 10);
@@ -422,7 +523,7 @@ export default function IOProfileDialog({
     setGsUsbProbeState("probing");
     try {
       // Pass serial for stable device matching across USB re-enumeration
-      const result = await probeGsUsbDevice(bus, address, serial);
+      const result = await probeGsUsbDevice(bus, address, serial, profileForm.connection.direct_usb === true);
       setGsUsbProbeResult({
         success: result.success,
         primaryInfo: result.channel_count ? `${result.channel_count} channel(s)` : undefined,
@@ -437,11 +538,11 @@ export default function IOProfileDialog({
       });
       setGsUsbProbeState("error");
     }
-  }, [platformIsWindows, platformIsMacos, profileForm]);
+  }, [canProbeGsUsb, profileForm]);
 
-  // Auto-probe gs_usb device when bus/address changes (Windows/macOS)
+  // Auto-probe gs_usb device when bus/address changes (Windows/macOS, Linux direct USB)
   useEffect(() => {
-    if (isProfileKind(profileForm, "gs_usb") && (platformIsWindows || platformIsMacos) && (profileForm.connection.bus || profileForm.connection.address)) {
+    if (isProfileKind(profileForm, "gs_usb") && canProbeGsUsb && (profileForm.connection.bus || profileForm.connection.address)) {
       const timer = setTimeout(() => {
         probeGsUsb();
       }, PROBE_DEBOUNCE_MS);
@@ -450,7 +551,7 @@ export default function IOProfileDialog({
       setGsUsbProbeState("idle");
       setGsUsbProbeResult(null);
     }
-  }, [profileForm, platformIsWindows, platformIsMacos, probeGsUsb]);
+  }, [profileForm, canProbeGsUsb, probeGsUsb]);
 
   // Reset gs_usb probe state when dialog closes or profile type changes
   useEffect(() => {
@@ -490,12 +591,24 @@ export default function IOProfileDialog({
               {availableKinds.includes("gs_usb") && <option value="gs_usb">{t("ioProfileDialog.kinds.gs_usb")}</option>}
               {availableKinds.includes("gvret_tcp") && <option value="gvret_tcp">{t("ioProfileDialog.kinds.gvret_tcp")}</option>}
               {availableKinds.includes("gvret_usb") && <option value="gvret_usb">{t("ioProfileDialog.kinds.gvret_usb")}</option>}
+              {availableKinds.includes("pcan") && <option value="pcan">{t("ioProfileDialog.kinds.pcan")}</option>}
+              {availableKinds.includes("kvaser") && <option value="kvaser">{t("ioProfileDialog.kinds.kvaser")}</option>}
+              {availableKinds.includes("vector_xl") && <option value="vector_xl">{t("ioProfileDialog.kinds.vector_xl")}</option>}
+              {availableKinds.includes("j2534") && <option value="j2534">{t("ioProfileDialog.kinds.j2534")}</option>}
               {availableKinds.includes("modbus_tcp") && <option value="modbus_tcp">{t("ioProfileDialog.kinds.modbus_tcp")}</option>}
               {availableKinds.includes("mqtt") && <option value="mqtt">{t("ioProfileDialog.kinds.mqtt")}</option>}
               {availableKinds.includes("postgres") && <option value="postgres">{t("ioProfileDialog.kinds.postgres")}</option>}
               {availableKinds.includes("wiretap") && <option value="wiretap">{t("ioProfileDialog.kinds.wiretap")}</option>}
               {availableKinds.includes("serial") && <option value="serial">{t("ioProfileDialog.kinds.serial")}</option>}
               {availableKinds.includes("slcan") && <option value="slcan">{t("ioProfileDialog.kinds.slcan")}</option>}
+              {availableKinds.includes("custom_serial") && <option value="custom_serial">{t("ioProfileDialog.kinds.custom_serial")}</option>}
+              {availableKinds.includes("elm327") && <option value="elm327">{t("ioProfileDialog.kinds.elm327")}</option>}
+              {availableKinds.includes("lin") && <option value="lin">{t("ioProfileDialog.kinds.lin")}</option>}
+              {availableKinds.includes("canserver") && <option value="canserver">{t("ioProfileDialog.kinds.canserver")}</option>}
+              {availableKinds.includes("websocket") && <option value="websocket">{t("ioProfileDialog.kinds.websocket")}</option>}
+              {availableKinds.includes("udp_can") && <option value="udp_can">{t("ioProfileDialog.kinds.udp_can")}</option>}
+              {availableKinds.includes("doip") && <option value="doip">{t("ioProfileDialog.kinds.doip")}</option>}
+              {availableKinds.includes("wiretap_net") && <option value="wiretap_net">{t("ioProfileDialog.kinds.wiretap_net")}</option>}
               {availableKinds.includes("socketcan") && <option value="socketcan">{t("ioProfileDialog.kinds.socketcan")}</option>}
               {availableKinds.includes("virtual") && <option value="virtual">{t("ioProfileDialog.kinds.virtual")}</option>}
             </Select>
@@ -552,6 +665,27 @@ export default function IOProfileDialog({
                 </FormField>
               </div>
 
+              <div className={flexRowGap2}>
+                <SecondaryButton onClick={toggleMqttBroker} className="text-xs py-1 px-2">
+                  {mqttBroker?.running ? t("ioProfileDialog.mqtt.brokerStop") : t("ioProfileDialog.mqtt.brokerStart")}
+                </SecondaryButton>
+                <span className={textMedium}>
+                  {mqttBroker?.running
+                    ? t("ioProfileDialog.mqtt.brokerRunning", {
+                        port: mqttBroker.port,
+                        clients: mqttBroker.clients,
+                        messages: mqttBroker.messages_in,
+                      })
+                    : t("ioProfileDialog.mqtt.brokerHint")}
+                </span>
+              </div>
+
+              {mqttBrokerError && (
+                <div className={alertWarning}>
+                  <p className="text-sm text-[color:var(--text-amber)]">{mqttBrokerError}</p>
+                </div>
+              )}
+
               <FormField label={t("ioProfileDialog.common.usernameOptional")} variant="default">
                 <Input
                   variant="default"
@@ -569,6 +703,88 @@ export default function IOProfileDialog({
                 optional
               />
 
+              {/* TLS */}
+              <div>
+                <div className={flexRowGap2}>
+                  <input
+                    type="checkbox"
+                    id="mqtt_tls"
+                    checked={profileForm.connection.tls === true}
+                    onChange={(e) => onUpdateConnectionField("tls", e.target.checked)}
+                    className={checkboxDefault}
+                  />
+                  <label htmlFor="mqtt_tls" className="text-sm text-[color:var(--text-secondary)]">
+                    {t("ioProfileDialog.mqtt.tls")}
+                  </label>
+                </div>
+                <p className="text-xs text-[color:var(--text-muted)] mt-1 ml-6">
+                  {t("ioProfileDialog.mqtt.tlsHint")}
+                </p>
+              </div>
+
+              {profileForm.connection.tls === true && (
+                <div className={`${spaceYDefault} pl-6`}>
+                  <FormField label={t("ioProfileDialog.mqtt.tlsCaCert")} variant="default">
+                    <Textarea
+                      variant="default"
+                      rows={3}
+                      className="font-mono text-xs"
+                      value={profileForm.connection.tls_ca_cert || ""}
+                      onChange={(e) => onUpdateConnectionField("tls_ca_cert", e.target.value)}
+                      placeholder={t("ioProfileDialog.mqtt.tlsCaCertPlaceholder")}
+                    />
+                  </FormField>
+                  <FormField label={t("ioProfileDialog.mqtt.tlsClientCert")} variant="default">
+                    <Textarea
+                      variant="default"
+                      rows={3}
+                      className="font-mono text-xs"
+                      value={profileForm.connection.tls_client_cert || ""}
+                      onChange={(e) => onUpdateConnectionField("tls_client_cert", e.target.value)}
+                      placeholder={t("ioProfileDialog.mqtt.pemPlaceholder")}
+                    />
+                  </FormField>
+                  <FormField label={t("ioProfileDialog.mqtt.tlsClientKey")} variant="default">
+                    <Textarea
+                      variant="default"
+                      rows={3}
+                      className="font-mono text-xs"
+                      value={profileForm.connection.tls_client_key || ""}
+                      onChange={(e) => onUpdateConnectionField("tls_client_key", e.target.value)}
+                      placeholder={t("ioProfileDialog.mqtt.pemPlaceholder")}
+                    />
+                  </FormField>
+                  <p className="text-xs text-[color:var(--text-muted)]">
+                    {t("ioProfileDialog.mqtt.tlsClientHint")}
+                  </p>
+                  <FormField label={t("ioProfileDialog.mqtt.tlsAlpn")} variant="default">
+                    <Input
+                      variant="default"
+                      value={profileForm.connection.tls_alpn || ""}
+                      onChange={(e) => onUpdateConnectionField("tls_alpn", e.target.value)}
+                      placeholder={t("ioProfileDialog.mqtt.tlsAlpnPlaceholder")}
+                    />
+                  </FormField>
+                  <div>
+                    <div className={flexRowGap2}>
+                      <input
+                        type="checkbox"
+                        id="mqtt_tls_insecure"
+                        checked={profileForm.connection.tls_insecure_skip_verify === true}
+                        onChange={(e) => onUpdateConnectionField("tls_insecure_skip_verify", e.target.checked)}
+                        className={checkboxDefault}
+                      />
+                      <label htmlFor="mqtt_tls_insecure" className="text-sm text-[color:var(--text-secondary)]">
+                        {t("ioProfileDialog.mqtt.tlsInsecure")}
+                      </label>
+                    </div>
+                    <p className="text-xs text-[color:var(--text-muted)] mt-1 ml-6">
+                      {t("ioProfileDialog.mqtt.tlsInsecureHint")}
+                    </p>
+                  </div>
+                </div>
+              )}
+
               {/* MQTT Formats */}
               <div className={`border-t ${borderDefault} pt-4 mt-6`}>
                 <h4 className="text-md font-semibold text-[color:var(--text-primary)] mb-4">
@@ -672,6 +888,24 @@ export default function IOProfileDialog({
             <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
               <h3 className={h3}>{t("ioProfileDialog.modbus.title")}</h3>
 
+              <FormField label={t("ioProfileDialog.modbus.role")} variant="default">
+                <Select
+                  variant="default"
+                  value={profileForm.connection.role || "client"}
+                  onChange={(e) => onUpdateConnectionField("role", e.target.value)}
+                >
+                  <option value="client">{t("ioProfileDialog.modbus.roles.client")}</option>
+                  <option value="simulator">{t("ioProfileDialog.modbus.roles.simulator")}</option>
+                  <option value="server">{t("ioProfileDialog.modbus.roles.server")}</option>
+                </Select>
+              </FormField>
+              {profileForm.connection.role === "simulator" && (
+                <p className={caption}>{t("ioProfileDialog.modbus.simulatorHint")}</p>
+              )}
+              {profileForm.connection.role === "server" && (
+                <p className={caption}>{t("ioProfileDialog.modbus.serverHint")}</p>
+              )}
+
               <div className="grid grid-cols-2 gap-4">
                 <FormField label={t("ioProfileDialog.common.host")} variant="default">
                   <Input
@@ -973,6 +1207,43 @@ export default function IOProfileDialog({
                 </FormField>
               </div>
 
+              <div className={flexRowGap2}>
+                <SecondaryButton
+                  onClick={scanGvretNetwork}
+                  disabled={gvretScanning}
+                  className="text-xs py-1 px-2"
+                >
+                  <RefreshCw className={`${iconXs} mr-1 ${gvretScanning ? "animate-spin" : ""}`} />
+                  {gvretScanning ? t("ioProfileDialog.gvret.scanning") : t("ioProfileDialog.gvret.scanNetwork")}
+                </SecondaryButton>
+                {gvretCandidates && gvretCandidates.length > 0 && (
+                  <Select
+                    variant="default"
+                    value=""
+                    onChange={(e) => {
+                      const candidate = gvretCandidates[Number(e.target.value)];
+                      if (!candidate) return;
+                      onUpdateConnectionField("host", candidate.host);
+                      onUpdateConnectionField("port", String(candidate.port));
+                    }}
+                  >
+                    <option value="">{t("ioProfileDialog.gvret.scanPick", { count: gvretCandidates.length })}</option>
+                    {gvretCandidates.map((c, i) => (
+                      <option key={`${c.host}:${c.port}`} value={i}>
+                        {c.name ? `${c.name} — ` : ""}{c.host}:{c.port}
+                        {c.bus_count != null ? ` (${t("ioProfileDialog.gvret.scanBuses", { count: c.bus_count })})` : ""}
+                      </option>
+                    ))}
+                  </Select>
+                )}
+              </div>
+
+              {gvretScanError && (
+                <div className={alertWarning}>
+                  <p className="text-sm text-[color:var(--text-amber)]">{gvretScanError}</p>
+                </div>
+              )}
+
               <FormField label={t("ioProfileDialog.common.connectionTimeout")} variant="default">
                 <Input
                   variant="default"
@@ -1001,6 +1272,24 @@ export default function IOProfileDialog({
                 </label>
               </div>
 
+              <div>
+                <div className={flexRowGap2}>
+                  <input
+                    type="checkbox"
+                    id="gvret_tcp_listen_only"
+                    checked={profileForm.connection.listen_only === true}
+                    onChange={(e) => onUpdateConnectionField("listen_only", e.target.checked)}
+                    className={checkboxDefault}
+                  />
+                  <label htmlFor="gvret_tcp_listen_only" className="text-sm text-[color:var(--text-secondary)]">
+                    {t("ioProfileDialog.gvret.listenOnly")}
+                  </label>
+                </div>
+                <p className="text-xs text-[color:var(--text-muted)] mt-1 ml-6">
+                  {t("ioProfileDialog.gvret.listenOnlyHint")}
+                </p>
+              </div>
+
               {/* Interface Configuration */}
               <div className={`border-t ${borderDefault} pt-4 mt-4`}>
                 <div className="flex items-center justify-between mb-3">
@@ -1059,34 +1348,41 @@ export default function IOProfileDialog({
             </div>
           )}
 
-          {/* GVRET USB */}
-          {profileForm.kind === "gvret_usb" && (
+          {/* CANserver / panda UDP */}
+          {profileForm.kind === "canserver" && (
             <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
-              <h3 className={h3}>{t("ioProfileDialog.gvret.usbTitle")}</h3>
+              <h3 className={h3}>{t("ioProfileDialog.canserver.title")}</h3>
 
-              {/* Port Selection */}
-              <FormField label={t("ioProfileDialog.gvret.serialPort")} variant="default">
-                <SerialPortPicker
-                  value={profileForm.connection.port || ""}
-                  onChange={(port) => onUpdateConnectionField("port", port)}
-                />
-              </FormField>
+              <div className="grid grid-cols-2 gap-4">
+                <FormField label={t("ioProfileDialog.common.host")} variant="default">
+                  <Input
+                    variant="default"
+                    value={profileForm.connection.host || ""}
+                    onChange={(e) => onUpdateConnectionField("host", e.target.value)}
+                    placeholder={t("ioProfileDialog.canserver.hostPlaceholder")}
+                  />
+                </FormField>
+                <FormField label={t("ioProfileDialog.canserver.udpPort")} variant="default">
+                  <Input
+                    variant="default"
+                    type="number"
+                    value={profileForm.connection.port || ""}
+                    onChange={(e) => onUpdateConnectionField("port", e.target.value)}
+                    placeholder="1338"
+                  />
+                </FormField>
+              </div>
 
-              {/* Serial Baud Rate */}
-              <FormField label={t("ioProfileDialog.gvret.serialBaudRate")} variant="default">
-                <BaudRateSelect
-                  value={profileForm.connection.baud_rate || "115200"}
-                  onChange={(v) => onUpdateConnectionField("baud_rate", v)}
-                  defaultLabel={t("ioProfileDialog.gvret.baudDefault")}
+              <FormField label={t("ioProfileDialog.canserver.silenceTimeout")} variant="default">
+                <Input
+                  variant="default"
+                  type="number"
+                  value={profileForm.connection.timeout || "5"}
+                  onChange={(e) => onUpdateConnectionField("timeout", e.target.value)}
+                  placeholder="5"
                 />
               </FormField>
 
-              <div className={alertInfo}>
-                <p className="text-sm text-[color:var(--text-info)]">
-                  {t("ioProfileDialog.gvret.usbHint")}
-                </p>
-              </div>
-
               {/* Interface Configuration */}
               <div className={`border-t ${borderDefault} pt-4 mt-4`}>
                 <div className="flex items-center justify-between mb-3">
@@ -1132,45 +1428,116 @@ export default function IOProfileDialog({
                     busConfig={getDeviceBusConfig()}
                     onBusConfigChange={handleDeviceBusConfigChange}
                     showOutputBus={false}
-                    showProtocol={true}
+                    showProtocol={false}
                   />
                 )}
+              </div>
 
-                {editingProfileId && getDeviceBusConfig().length === 0 && gvretProbeState !== "probing" && gvretProbeState !== "error" && (
-                  <p className="text-sm text-[color:var(--text-muted)]">
-                    {t("ioProfileDialog.common.clickProbeHint")}
-                  </p>
-                )}
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.canserver.supportHint")}
+                </p>
               </div>
             </div>
           )}
 
-          {/* FrameLink (WiredFlexLink) */}
-          {profileForm.kind === "framelink" && (
+          {/* WebSocket streaming */}
+          {profileForm.kind === "websocket" && (
             <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
-              <h3 className={h3}>{t("ioProfileDialog.framelink.title")}</h3>
+              <h3 className={h3}>{t("ioProfileDialog.websocket.title")}</h3>
+
+              <FormField label={t("ioProfileDialog.websocket.url")} variant="default">
+                <Input
+                  variant="default"
+                  value={profileForm.connection.url || ""}
+                  onChange={(e) => onUpdateConnectionField("url", e.target.value)}
+                  placeholder="wss://gateway.example.com/can"
+                />
+              </FormField>
+
+              <FormField label={t("ioProfileDialog.websocket.subscribe")} variant="default">
+                <Input
+                  variant="default"
+                  value={profileForm.connection.subscribe || ""}
+                  onChange={(e) => onUpdateConnectionField("subscribe", e.target.value)}
+                  placeholder={t("ioProfileDialog.websocket.subscribePlaceholder")}
+                />
+              </FormField>
 
               <div className="grid grid-cols-2 gap-4">
-                <FormField label={t("ioProfileDialog.common.host")} required variant="default">
-                  <Input
+                <FormField label={t("ioProfileDialog.websocket.format")} variant="default">
+                  <Select
                     variant="default"
-                    value={profileForm.connection.host || ""}
-                    onChange={(e) => onUpdateConnectionField("host", e.target.value)}
-                    placeholder={t("ioProfileDialog.framelink.hostPlaceholder")}
-                  />
+                    value={profileForm.connection.format || "json"}
+                    onChange={(e) => {
+                      onUpdateConnectionField("format", e.target.value);
+                      // Key paths and byte offsets don't carry over
+                      onUpdateConnectionField("mapping", []);
+                    }}
+                  >
+                    <option value="json">{t("ioProfileDialog.websocket.formats.json")}</option>
+                    <option value="binary">{t("ioProfileDialog.websocket.formats.binary")}</option>
+                  </Select>
                 </FormField>
-                <FormField label={t("ioProfileDialog.common.port")} variant="default">
+                <FormField label={t("ioProfileDialog.websocket.timestampUnit")} variant="default">
+                  <Select
+                    variant="default"
+                    value={profileForm.connection.timestamp_unit || "microseconds"}
+                    onChange={(e) => onUpdateConnectionField("timestamp_unit", e.target.value)}
+                  >
+                    <option value="seconds">{t("ioProfileDialog.websocket.units.seconds")}</option>
+                    <option value="milliseconds">{t("ioProfileDialog.websocket.units.milliseconds")}</option>
+                    <option value="microseconds">{t("ioProfileDialog.websocket.units.microseconds")}</option>
+                    <option value="nanoseconds">{t("ioProfileDialog.websocket.units.nanoseconds")}</option>
+                  </Select>
+                </FormField>
+              </div>
+
+              {(profileForm.connection.format || "json") === "json" ? (
+                <FormField label={t("ioProfileDialog.websocket.framesPath")} variant="default">
                   <Input
                     variant="default"
-                    type="number"
-                    value={profileForm.connection.port || ""}
-                    onChange={(e) => onUpdateConnectionField("port", e.target.value)}
-                    placeholder={t("ioProfileDialog.framelink.portPlaceholder")}
+                    value={profileForm.connection.frames_path || ""}
+                    onChange={(e) => onUpdateConnectionField("frames_path", e.target.value)}
+                    placeholder={t("ioProfileDialog.websocket.framesPathPlaceholder")}
                   />
                 </FormField>
+              ) : (
+                <div className="grid grid-cols-2 gap-4 items-end">
+                  <FormField label={t("ioProfileDialog.websocket.recordSize")} variant="default">
+                    <Input
+                      variant="default"
+                      type="number"
+                      value={profileForm.connection.record_size || ""}
+                      onChange={(e) => onUpdateConnectionField("record_size", e.target.value)}
+                      placeholder={t("ioProfileDialog.websocket.recordSizePlaceholder")}
+                    />
+                  </FormField>
+                  <div className={`${flexRowGap2} pb-2`}>
+                    <input
+                      type="checkbox"
+                      id="websocket_big_endian"
+                      checked={profileForm.connection.big_endian === true}
+                      onChange={(e) => onUpdateConnectionField("big_endian", e.target.checked)}
+                      className={checkboxDefault}
+                    />
+                    <label htmlFor="websocket_big_endian" className="text-sm text-[color:var(--text-secondary)]">
+                      {t("ioProfileDialog.websocket.bigEndian")}
+                    </label>
+                  </div>
+                </div>
+              )}
+
+              <div className={`border-t ${borderDefault} pt-4 mt-4`}>
+                <h4 className={`${textMedium} mb-3`}>{t("ioProfileDialog.websocket.mapping")}</h4>
+                <WebSocketFieldMapping
+                  format={profileForm.connection.format || "json"}
+                  value={profileForm.connection.mapping || []}
+                  onChange={(mapping) => onUpdateConnectionField("mapping", mapping)}
+                />
               </div>
 
-              <FormField label={t("ioProfileDialog.common.connectionTimeout")} variant="default">
+              <FormField label={t("ioProfileDialog.canserver.silenceTimeout")} variant="default">
                 <Input
                   variant="default"
                   type="number"
@@ -1180,58 +1547,572 @@ export default function IOProfileDialog({
                 />
               </FormField>
 
-              {/* Interfaces — each row is collapsible and contains its own device configuration */}
-              {Array.isArray(profileForm.connection.interfaces) && profileForm.connection.interfaces.length > 0 && (() => {
-                const interfaces = profileForm.connection.interfaces as Array<{
-                  index: number;
-                  iface_type: number;
-                  name: string;
-                  type_name?: string;
-                }>;
-                const totalSignalsLoaded = Object.values(flSignalsByIface).reduce((n, sigs) => n + sigs.length, 0);
-                const anyPersistable = Object.values(flSignalsByIface).some((sigs) => sigs.some((s) => s.persistable));
-                return (
-                  <div className={`border-t ${borderDefault} pt-4 mt-4`}>
-                    <div className="flex items-center justify-between mb-3">
-                      <h3 className={h3}>{t("ioProfileDialog.framelink.interfacesTitle", { count: interfaces.length })}</h3>
-                      <div className="flex items-center gap-2">
-                        <SecondaryButton onClick={loadFlSignals} disabled={flLoading}>
-                          {flLoading ? (
-                            <>
-                              <RefreshCw className={`${iconXs} animate-spin`} />
-                              {t("ioProfileDialog.framelink.reading")}
-                            </>
-                          ) : (
-                            <>
-                              <RefreshCw className={iconXs} />
-                              {t("ioProfileDialog.framelink.refresh")}
-                            </>
-                          )}
-                        </SecondaryButton>
-                        <SecondaryButton onClick={handleFlReprobe} disabled={flReprobing}>
-                          {flReprobing ? (
-                            <>
-                              <RefreshCw className={`${iconXs} animate-spin`} />
-                              {t("ioProfileDialog.framelink.reprobing")}
-                            </>
-                          ) : (
-                            <>
-                              <RefreshCw className={iconXs} />
-                              {t("ioProfileDialog.framelink.reprobe")}
-                            </>
-                          )}
-                        </SecondaryButton>
-                      </div>
-                    </div>
-
-                    {flError && (
-                      <div className={`${alertWarning} mb-3`}>
-                        <p className="text-sm text-[color:var(--text-warning)]">{flError}</p>
-                      </div>
-                    )}
-
-                    <div className="flex flex-col gap-1.5">
-                      {interfaces.map((iface) => {
+              {/* Interface Configuration */}
+              <div className={`border-t ${borderDefault} pt-4 mt-4`}>
+                <div className="flex items-center justify-between mb-3">
+                  <h4 className={textMedium}>
+                    {t("ioProfileDialog.common.canInterfaces")}
+                    {gvretProbeState === "success" && (
+                      <span className="ml-2 text-xs text-[color:var(--text-green)]">
+                        {t("ioProfileDialog.common.deviceOnline")}
+                      </span>
+                    )}
+                  </h4>
+                  <SecondaryButton
+                    onClick={probeGvret}
+                    disabled={gvretProbeState === "probing"}
+                    className="text-xs py-1 px-2"
+                  >
+                    <RefreshCw className={`${iconXs} mr-1 ${gvretProbeState === "probing" ? "animate-spin" : ""}`} />
+                    {gvretProbeState === "probing" ? t("ioProfileDialog.common.probing") : t("ioProfileDialog.common.probeDevice")}
+                  </SecondaryButton>
+                </div>
+
+                {!editingProfileId && (
+                  <div className={alertInfo}>
+                    <p className="text-sm text-[color:var(--text-info)]">
+                      {t("ioProfileDialog.common.saveFirstHint")}
+                    </p>
+                  </div>
+                )}
+
+                {gvretProbeError && (
+                  <div className={alertWarning}>
+                    <p className="text-sm text-[color:var(--text-amber)]">
+                      {gvretProbeError}
+                    </p>
+                  </div>
+                )}
+
+                {getDeviceBusConfig().length > 0 && (
+                  <DeviceBusConfig
+                    deviceInfo={gvretDeviceInfo}
+                    isLoading={gvretProbeState === "probing"}
+                    error={gvretProbeState === "error" ? gvretProbeError : null}
+                    busConfig={getDeviceBusConfig()}
+                    onBusConfigChange={handleDeviceBusConfigChange}
+                    showOutputBus={false}
+                    showProtocol={false}
+                  />
+                )}
+              </div>
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.websocket.supportHint")}
+                </p>
+              </div>
+            </div>
+          )}
+
+          {/* UDP listener */}
+          {profileForm.kind === "udp_can" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.udpCan.title")}</h3>
+
+              <div className="grid grid-cols-2 gap-4">
+                <FormField label={t("ioProfileDialog.udpCan.bind")} variant="default">
+                  <Input
+                    variant="default"
+                    value={profileForm.connection.bind || ""}
+                    onChange={(e) => onUpdateConnectionField("bind", e.target.value)}
+                    placeholder="0.0.0.0"
+                  />
+                </FormField>
+                <FormField label={t("ioProfileDialog.udpCan.port")} variant="default">
+                  <Input
+                    variant="default"
+                    type="number"
+                    value={profileForm.connection.port || ""}
+                    onChange={(e) => onUpdateConnectionField("port", e.target.value)}
+                    placeholder="3333"
+                  />
+                </FormField>
+              </div>
+
+              <div className="grid grid-cols-2 gap-4">
+                <FormField label={t("ioProfileDialog.udpCan.sender")} variant="default">
+                  <Input
+                    variant="default"
+                    value={profileForm.connection.sender || ""}
+                    onChange={(e) => onUpdateConnectionField("sender", e.target.value)}
+                    placeholder={t("ioProfileDialog.udpCan.anySender")}
+                  />
+                </FormField>
+                <FormField label={t("ioProfileDialog.udpCan.multicastGroup")} variant="default">
+                  <Input
+                    variant="default"
+                    value={profileForm.connection.multicast_group || ""}
+                    onChange={(e) => onUpdateConnectionField("multicast_group", e.target.value)}
+                    placeholder="239.0.0.1"
+                  />
+                </FormField>
+              </div>
+
+              <FormField label={t("ioProfileDialog.udpCan.format")} variant="default">
+                <Select
+                  variant="default"
+                  value={profileForm.connection.format || "binary"}
+                  onChange={(e) => onUpdateConnectionField("format", e.target.value)}
+                >
+                  <option value="binary">{t("ioProfileDialog.udpCan.formats.binary")}</option>
+                  <option value="candump">{t("ioProfileDialog.udpCan.formats.candump")}</option>
+                </Select>
+              </FormField>
+
+              {(profileForm.connection.format || "binary") === "binary" && (
+                <div className={`border-t ${borderDefault} pt-4 mt-4 space-y-3`}>
+                  <h4 className={textMedium}>{t("ioProfileDialog.udpCan.layout")}</h4>
+                  <div className="grid grid-cols-3 gap-4">
+                    <FormField label={t("ioProfileDialog.udpCan.idOffset")} variant="default">
+                      <Input
+                        variant="default"
+                        type="number"
+                        value={profileForm.connection.id_offset || ""}
+                        onChange={(e) => onUpdateConnectionField("id_offset", e.target.value)}
+                        placeholder="0"
+                      />
+                    </FormField>
+                    <FormField label={t("ioProfileDialog.udpCan.idBytes")} variant="default">
+                      <Select
+                        variant="default"
+                        value={profileForm.connection.id_bytes || "4"}
+                        onChange={(e) => onUpdateConnectionField("id_bytes", e.target.value)}
+                      >
+                        <option value="1">1</option>
+                        <option value="2">2</option>
+                        <option value="3">3</option>
+                        <option value="4">4</option>
+                      </Select>
+                    </FormField>
+                    <FormField label={t("ioProfileDialog.udpCan.busOffset")} variant="default">
+                      <Input
+                        variant="default"
+                        type="number"
+                        value={profileForm.connection.bus_offset || ""}
+                        onChange={(e) => onUpdateConnectionField("bus_offset", e.target.value)}
+                        placeholder={t("ioProfileDialog.udpCan.none")}
+                      />
+                    </FormField>
+                    <FormField label={t("ioProfileDialog.udpCan.dlcOffset")} variant="default">
+                      <Input
+                        variant="default"
+                        type="number"
+                        value={profileForm.connection.dlc_offset ?? "4"}
+                        onChange={(e) => onUpdateConnectionField("dlc_offset", e.target.value)}
+                        placeholder={t("ioProfileDialog.udpCan.restOfRecord")}
+                      />
+                    </FormField>
+                    <FormField label={t("ioProfileDialog.udpCan.dataOffset")} variant="default">
+                      <Input
+                        variant="default"
+                        type="number"
+                        value={profileForm.connection.data_offset || ""}
+                        onChange={(e) => onUpdateConnectionField("data_offset", e.target.value)}
+                        placeholder="5"
+                      />
+                    </FormField>
+                    <FormField label={t("ioProfileDialog.udpCan.recordSize")} variant="default">
+                      <Input
+                        variant="default"
+                        type="number"
+                        value={profileForm.connection.record_size || ""}
+                        onChange={(e) => onUpdateConnectionField("record_size", e.target.value)}
+                        placeholder={t("ioProfileDialog.udpCan.wholeDatagram")}
+                      />
+                    </FormField>
+                  </div>
+                  <div className={flexRowGap2}>
+                    <input
+                      type="checkbox"
+                      id="udp_can_big_endian"
+                      checked={profileForm.connection.big_endian === true}
+                      onChange={(e) => onUpdateConnectionField("big_endian", e.target.checked)}
+                      className={checkboxDefault}
+                    />
+                    <label htmlFor="udp_can_big_endian" className="text-sm text-[color:var(--text-secondary)]">
+                      {t("ioProfileDialog.udpCan.bigEndian")}
+                    </label>
+                  </div>
+                  <p className={caption}>{t("ioProfileDialog.udpCan.layoutHint")}</p>
+                </div>
+              )}
+
+              <FormField label={t("ioProfileDialog.canserver.silenceTimeout")} variant="default">
+                <Input
+                  variant="default"
+                  type="number"
+                  value={profileForm.connection.timeout || "5"}
+                  onChange={(e) => onUpdateConnectionField("timeout", e.target.value)}
+                  placeholder="5"
+                />
+              </FormField>
+
+              {/* Interface Configuration */}
+              <div className={`border-t ${borderDefault} pt-4 mt-4`}>
+                <div className="flex items-center justify-between mb-3">
+                  <h4 className={textMedium}>
+                    {t("ioProfileDialog.common.canInterfaces")}
+                    {gvretProbeState === "success" && (
+                      <span className="ml-2 text-xs text-[color:var(--text-green)]">
+                        {t("ioProfileDialog.common.deviceOnline")}
+                      </span>
+                    )}
+                  </h4>
+                  <SecondaryButton
+                    onClick={probeGvret}
+                    disabled={gvretProbeState === "probing"}
+                    className="text-xs py-1 px-2"
+                  >
+                    <RefreshCw className={`${iconXs} mr-1 ${gvretProbeState === "probing" ? "animate-spin" : ""}`} />
+                    {gvretProbeState === "probing" ? t("ioProfileDialog.common.probing") : t("ioProfileDialog.common.probeDevice")}
+                  </SecondaryButton>
+                </div>
+
+                {!editingProfileId && (
+                  <div className={alertInfo}>
+                    <p className="text-sm text-[color:var(--text-info)]">
+                      {t("ioProfileDialog.common.saveFirstHint")}
+                    </p>
+                  </div>
+                )}
+
+                {gvretProbeError && (
+                  <div className={alertWarning}>
+                    <p className="text-sm text-[color:var(--text-amber)]">
+                      {gvretProbeError}
+                    </p>
+                  </div>
+                )}
+
+                {getDeviceBusConfig().length > 0 && (
+                  <DeviceBusConfig
+                    deviceInfo={gvretDeviceInfo}
+                    isLoading={gvretProbeState === "probing"}
+                    error={gvretProbeState === "error" ? gvretProbeError : null}
+                    busConfig={getDeviceBusConfig()}
+                    onBusConfigChange={handleDeviceBusConfigChange}
+                    showOutputBus={false}
+                    showProtocol={false}
+                  />
+                )}
+              </div>
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.udpCan.supportHint")}
+                </p>
+              </div>
+            </div>
+          )}
+
+          {/* WireTAP network bridge client */}
+          {profileForm.kind === "doip" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.doip.title")}</h3>
+
+              <div className="grid grid-cols-2 gap-4">
+                <FormField label={t("ioProfileDialog.common.host")} variant="default">
+                  <Input
+                    variant="default"
+                    value={profileForm.connection.host || ""}
+                    onChange={(e) => onUpdateConnectionField("host", e.target.value)}
+                    placeholder={t("ioProfileDialog.doip.hostPlaceholder")}
+                  />
+                </FormField>
+                <FormField label={t("ioProfileDialog.doip.tcpPort")} variant="default">
+                  <Input
+                    variant="default"
+                    type="number"
+                    value={profileForm.connection.port || ""}
+                    onChange={(e) => onUpdateConnectionField("port", e.target.value)}
+                    placeholder="13400"
+                  />
+                </FormField>
+              </div>
+
+              <div className="grid grid-cols-2 gap-4">
+                <FormField label={t("ioProfileDialog.doip.testerAddress")} variant="default">
+                  <Input
+                    variant="default"
+                    value={profileForm.connection.tester_address || ""}
+                    onChange={(e) => onUpdateConnectionField("tester_address", e.target.value)}
+                    placeholder="0x0E00"
+                  />
+                </FormField>
+                <FormField label={t("ioProfileDialog.doip.targetAddress")} variant="default">
+                  <Input
+                    variant="default"
+                    value={profileForm.connection.target_address || ""}
+                    onChange={(e) => onUpdateConnectionField("target_address", e.target.value)}
+                    placeholder={t("ioProfileDialog.doip.targetPlaceholder")}
+                  />
+                </FormField>
+              </div>
+
+              <div className="grid grid-cols-2 gap-4">
+                <FormField label={t("ioProfileDialog.doip.activationType")} variant="default">
+                  <Select
+                    variant="default"
+                    value={profileForm.connection.activation_type || "0"}
+                    onChange={(e) => onUpdateConnectionField("activation_type", e.target.value)}
+                  >
+                    <option value="0">{t("ioProfileDialog.doip.activationTypes.default")}</option>
+                    <option value="1">{t("ioProfileDialog.doip.activationTypes.wwhObd")}</option>
+                  </Select>
+                </FormField>
+                <FormField label={t("ioProfileDialog.doip.timeout")} variant="default">
+                  <Input
+                    variant="default"
+                    type="number"
+                    value={profileForm.connection.timeout || "5"}
+                    onChange={(e) => onUpdateConnectionField("timeout", e.target.value)}
+                    placeholder="5"
+                  />
+                </FormField>
+              </div>
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.doip.supportHint")}
+                </p>
+              </div>
+            </div>
+          )}
+
+          {profileForm.kind === "wiretap_net" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.wiretapNet.title")}</h3>
+
+              <div className="grid grid-cols-2 gap-4">
+                <FormField label={t("ioProfileDialog.common.host")} variant="default">
+                  <Input
+                    variant="default"
+                    value={profileForm.connection.host || ""}
+                    onChange={(e) => onUpdateConnectionField("host", e.target.value)}
+                    placeholder={t("ioProfileDialog.wiretapNet.hostPlaceholder")}
+                  />
+                </FormField>
+                <FormField label={t("ioProfileDialog.wiretapNet.tcpPort")} variant="default">
+                  <Input
+                    variant="default"
+                    type="number"
+                    value={profileForm.connection.port || ""}
+                    onChange={(e) => onUpdateConnectionField("port", e.target.value)}
+                    placeholder="1339"
+                  />
+                </FormField>
+              </div>
+
+              <FormField label={t("ioProfileDialog.wiretapNet.timeout")} variant="default">
+                <Input
+                  variant="default"
+                  type="number"
+                  value={profileForm.connection.timeout || "5"}
+                  onChange={(e) => onUpdateConnectionField("timeout", e.target.value)}
+                  placeholder="5"
+                />
+              </FormField>
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.wiretapNet.supportHint")}
+                </p>
+              </div>
+            </div>
+          )}
+
+          {/* GVRET USB */}
+          {profileForm.kind === "gvret_usb" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.gvret.usbTitle")}</h3>
+
+              {/* Port Selection */}
+              <FormField label={t("ioProfileDialog.gvret.serialPort")} variant="default">
+                <SerialPortPicker
+                  value={profileForm.connection.port || ""}
+                  onChange={(port) => onUpdateConnectionField("port", port)}
+                />
+              </FormField>
+
+              {/* Serial Baud Rate */}
+              <FormField label={t("ioProfileDialog.gvret.serialBaudRate")} variant="default">
+                <BaudRateSelect
+                  value={profileForm.connection.baud_rate || "115200"}
+                  onChange={(v) => onUpdateConnectionField("baud_rate", v)}
+                  defaultLabel={t("ioProfileDialog.gvret.baudDefault")}
+                />
+              </FormField>
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.gvret.usbHint")}
+                </p>
+              </div>
+
+              <div>
+                <div className={flexRowGap2}>
+                  <input
+                    type="checkbox"
+                    id="gvret_usb_listen_only"
+                    checked={profileForm.connection.listen_only === true}
+                    onChange={(e) => onUpdateConnectionField("listen_only", e.target.checked)}
+                    className={checkboxDefault}
+                  />
+                  <label htmlFor="gvret_usb_listen_only" className="text-sm text-[color:var(--text-secondary)]">
+                    {t("ioProfileDialog.gvret.listenOnly")}
+                  </label>
+                </div>
+                <p className="text-xs text-[color:var(--text-muted)] mt-1 ml-6">
+                  {t("ioProfileDialog.gvret.listenOnlyHint")}
+                </p>
+              </div>
+
+              {/* Interface Configuration */}
+              <div className={`border-t ${borderDefault} pt-4 mt-4`}>
+                <div className="flex items-center justify-between mb-3">
+                  <h4 className={textMedium}>
+                    {t("ioProfileDialog.common.canInterfaces")}
+                    {gvretProbeState === "success" && (
+                      <span className="ml-2 text-xs text-[color:var(--text-green)]">
+                        {t("ioProfileDialog.common.deviceOnline")}
+                      </span>
+                    )}
+                  </h4>
+                  <SecondaryButton
+                    onClick={probeGvret}
+                    disabled={gvretProbeState === "probing"}
+                    className="text-xs py-1 px-2"
+                  >
+                    <RefreshCw className={`${iconXs} mr-1 ${gvretProbeState === "probing" ? "animate-spin" : ""}`} />
+                    {gvretProbeState === "probing" ? t("ioProfileDialog.common.probing") : t("ioProfileDialog.common.probeDevice")}
+                  </SecondaryButton>
+                </div>
+
+                {!editingProfileId && (
+                  <div className={alertInfo}>
+                    <p className="text-sm text-[color:var(--text-info)]">
+                      {t("ioProfileDialog.common.saveFirstHint")}
+                    </p>
+                  </div>
+                )}
+
+                {gvretProbeError && (
+                  <div className={alertWarning}>
+                    <p className="text-sm text-[color:var(--text-amber)]">
+                      {gvretProbeError}
+                    </p>
+                  </div>
+                )}
+
+                {getDeviceBusConfig().length > 0 && (
+                  <DeviceBusConfig
+                    deviceInfo={gvretDeviceInfo}
+                    isLoading={gvretProbeState === "probing"}
+                    error={gvretProbeState === "error" ? gvretProbeError : null}
+                    busConfig={getDeviceBusConfig()}
+                    onBusConfigChange={handleDeviceBusConfigChange}
+                    showOutputBus={false}
+                    showProtocol={true}
+                  />
+                )}
+
+                {editingProfileId && getDeviceBusConfig().length === 0 && gvretProbeState !== "probing" && gvretProbeState !== "error" && (
+                  <p className="text-sm text-[color:var(--text-muted)]">
+                    {t("ioProfileDialog.common.clickProbeHint")}
+                  </p>
+                )}
+              </div>
+            </div>
+          )}
+
+          {/* FrameLink (WiredFlexLink) */}
+          {profileForm.kind === "framelink" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.framelink.title")}</h3>
+
+              <div className="grid grid-cols-2 gap-4">
+                <FormField label={t("ioProfileDialog.common.host")} required variant="default">
+                  <Input
+                    variant="default"
+                    value={profileForm.connection.host || ""}
+                    onChange={(e) => onUpdateConnectionField("host", e.target.value)}
+                    placeholder={t("ioProfileDialog.framelink.hostPlaceholder")}
+                  />
+                </FormField>
+                <FormField label={t("ioProfileDialog.common.port")} variant="default">
+                  <Input
+                    variant="default"
+                    type="number"
+                    value={profileForm.connection.port || ""}
+                    onChange={(e) => onUpdateConnectionField("port", e.target.value)}
+                    placeholder={t("ioProfileDialog.framelink.portPlaceholder")}
+                  />
+                </FormField>
+              </div>
+
+              <FormField label={t("ioProfileDialog.common.connectionTimeout")} variant="default">
+                <Input
+                  variant="default"
+                  type="number"
+                  value={profileForm.connection.timeout || "5"}
+                  onChange={(e) => onUpdateConnectionField("timeout", e.target.value)}
+                  placeholder="5"
+                />
+              </FormField>
+
+              {/* Interfaces — each row is collapsible and contains its own device configuration */}
+              {Array.isArray(profileForm.connection.interfaces) && profileForm.connection.interfaces.length > 0 && (() => {
+                const interfaces = profileForm.connection.interfaces as Array<{
+                  index: number;
+                  iface_type: number;
+                  name: string;
+                  type_name?: string;
+                }>;
+                const totalSignalsLoaded = Object.values(flSignalsByIface).reduce((n, sigs) => n + sigs.length, 0);
+                const anyPersistable = Object.values(flSignalsByIface).some((sigs) => sigs.some((s) => s.persistable));
+                return (
+                  <div className={`border-t ${borderDefault} pt-4 mt-4`}>
+                    <div className="flex items-center justify-between mb-3">
+                      <h3 className={h3}>{t("ioProfileDialog.framelink.interfacesTitle", { count: interfaces.length })}</h3>
+                      <div className="flex items-center gap-2">
+                        <SecondaryButton onClick={loadFlSignals} disabled={flLoading}>
+                          {flLoading ? (
+                            <>
+                              <RefreshCw className={`${iconXs} animate-spin`} />
+                              {t("ioProfileDialog.framelink.reading")}
+                            </>
+                          ) : (
+                            <>
+                              <RefreshCw className={iconXs} />
+                              {t("ioProfileDialog.framelink.refresh")}
+                            </>
+                          )}
+                        </SecondaryButton>
+                        <SecondaryButton onClick={handleFlReprobe} disabled={flReprobing}>
+                          {flReprobing ? (
+                            <>
+                              <RefreshCw className={`${iconXs} animate-spin`} />
+                              {t("ioProfileDialog.framelink.reprobing")}
+                            </>
+                          ) : (
+                            <>
+                              <RefreshCw className={iconXs} />
+                              {t("ioProfileDialog.framelink.reprobe")}
+                            </>
+                          )}
+                        </SecondaryButton>
+                      </div>
+                    </div>
+
+                    {flError && (
+                      <div className={`${alertWarning} mb-3`}>
+                        <p className="text-sm text-[color:var(--text-warning)]">{flError}</p>
+                      </div>
+                    )}
+
+                    <div className="flex flex-col gap-1.5">
+                      {interfaces.map((iface) => {
                         const ifaceSignals = flSignalsByIface[iface.index] ?? [];
                         const expanded = !!flExpandedIface[iface.index];
                         const bitrateSig = ifaceSignals.find((s) => s.unit === "bps");
@@ -1569,6 +2450,343 @@ export default function IOProfileDialog({
             </div>
           )}
 
+          {/* ELM327 / OBD-II */}
+          {profileForm.kind === "elm327" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.elm327.title")}</h3>
+
+              {/* Port Selection */}
+              <FormField label={t("ioProfileDialog.elm327.serialPort")} variant="default">
+                <SerialPortPicker
+                  value={profileForm.connection.port || ""}
+                  onChange={(port) => onUpdateConnectionField("port", port)}
+                />
+              </FormField>
+
+              <FormField label={t("ioProfileDialog.elm327.serialBaudRate")} variant="default">
+                <BaudRateSelect
+                  value={profileForm.connection.baud_rate || "38400"}
+                  onChange={(v) => onUpdateConnectionField("baud_rate", v)}
+                />
+              </FormField>
+
+              <div className="grid grid-cols-2 gap-4">
+                <FormField label={t("ioProfileDialog.elm327.mode")} variant="default">
+                  <Select
+                    variant="default"
+                    value={profileForm.connection.mode || "monitor"}
+                    onChange={(e) => onUpdateConnectionField("mode", e.target.value)}
+                  >
+                    <option value="monitor">{t("ioProfileDialog.elm327.modes.monitor")}</option>
+                    <option value="poll">{t("ioProfileDialog.elm327.modes.poll")}</option>
+                  </Select>
+                </FormField>
+                <FormField label={t("ioProfileDialog.elm327.protocol")} variant="default">
+                  <Select
+                    variant="default"
+                    value={profileForm.connection.protocol || "6"}
+                    onChange={(e) => onUpdateConnectionField("protocol", e.target.value)}
+                  >
+                    {["6", "7", "8", "9", "0"].map((p) => (
+                      <option key={p} value={p}>{t(`ioProfileDialog.elm327.protocols.${p}`)}</option>
+                    ))}
+                  </Select>
+                </FormField>
+              </div>
+
+              {/* Poll settings */}
+              {profileForm.connection.mode === "poll" && (
+                <div className={`border-t ${borderDefault} pt-4 mt-2 space-y-3`}>
+                  <FormField label={t("ioProfileDialog.elm327.pids")} variant="default">
+                    <Textarea
+                      variant="default"
+                      rows={4}
+                      value={(profileForm.connection.pids ?? []).join("\n")}
+                      onChange={(e) => onUpdateConnectionField("pids", e.target.value.split("\n"))}
+                      placeholder={"010C\n010D"}
+                      className="font-mono"
+                    />
+                  </FormField>
+                  <p className={caption}>{t("ioProfileDialog.elm327.pidsHint")}</p>
+
+                  <div className="grid grid-cols-2 gap-4">
+                    <FormField label={t("ioProfileDialog.elm327.pollInterval")} variant="default">
+                      <Input
+                        variant="default"
+                        type="number"
+                        min={10}
+                        value={profileForm.connection.poll_interval_ms || "250"}
+                        onChange={(e) => onUpdateConnectionField("poll_interval_ms", e.target.value)}
+                      />
+                    </FormField>
+                    <FormField label={t("ioProfileDialog.elm327.requestHeader")} variant="default">
+                      <Input
+                        variant="default"
+                        value={profileForm.connection.request_header || ""}
+                        onChange={(e) => onUpdateConnectionField("request_header", e.target.value)}
+                        placeholder={t("ioProfileDialog.elm327.requestHeaderPlaceholder")}
+                        className="font-mono"
+                      />
+                    </FormField>
+                  </div>
+                </div>
+              )}
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.elm327.supportHint")}
+                </p>
+              </div>
+            </div>
+          )}
+
+          {/* LIN over a UART adapter */}
+          {profileForm.kind === "lin" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.lin.title")}</h3>
+
+              {/* Port Selection */}
+              <FormField label={t("ioProfileDialog.lin.serialPort")} variant="default">
+                <SerialPortPicker
+                  value={profileForm.connection.port || ""}
+                  onChange={(port) => onUpdateConnectionField("port", port)}
+                />
+              </FormField>
+
+              <div className="grid grid-cols-2 gap-4">
+                <FormField label={t("ioProfileDialog.lin.baudRate")} variant="default">
+                  <Input
+                    variant="default"
+                    type="number"
+                    min={1000}
+                    max={20000}
+                    value={profileForm.connection.baud_rate || "19200"}
+                    onChange={(e) => onUpdateConnectionField("baud_rate", e.target.value)}
+                  />
+                </FormField>
+                <FormField label={t("ioProfileDialog.lin.checksum")} variant="default">
+                  <Select
+                    variant="default"
+                    value={profileForm.connection.checksum || "auto"}
+                    onChange={(e) => onUpdateConnectionField("checksum", e.target.value)}
+                  >
+                    <option value="auto">{t("ioProfileDialog.lin.checksums.auto")}</option>
+                    <option value="classic">{t("ioProfileDialog.lin.checksums.classic")}</option>
+                    <option value="enhanced">{t("ioProfileDialog.lin.checksums.enhanced")}</option>
+                  </Select>
+                </FormField>
+              </div>
+
+              <div className="grid grid-cols-2 gap-4">
+                <FormField label={t("ioProfileDialog.lin.mode")} variant="default">
+                  <Select
+                    variant="default"
+                    value={profileForm.connection.mode || "monitor"}
+                    onChange={(e) => onUpdateConnectionField("mode", e.target.value)}
+                  >
+                    <option value="monitor">{t("ioProfileDialog.lin.modes.monitor")}</option>
+                    <option value="master">{t("ioProfileDialog.lin.modes.master")}</option>
+                  </Select>
+                </FormField>
+                {profileForm.connection.mode === "master" && (
+                  <FormField label={t("ioProfileDialog.lin.schedule")} variant="default">
+                    <Input
+                      variant="default"
+                      value={profileForm.connection.schedule || ""}
+                      onChange={(e) => onUpdateConnectionField("schedule", e.target.value)}
+                      placeholder={t("ioProfileDialog.lin.schedulePlaceholder")}
+                    />
+                  </FormField>
+                )}
+              </div>
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.lin.supportHint")}
+                </p>
+              </div>
+            </div>
+          )}
+
+          {/* Custom CAN-over-serial */}
+          {profileForm.kind === "custom_serial" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.customSerial.title")}</h3>
+
+              {/* Port Selection */}
+              <FormField label={t("ioProfileDialog.customSerial.serialPort")} variant="default">
+                <SerialPortPicker
+                  value={profileForm.connection.port || ""}
+                  onChange={(port) => onUpdateConnectionField("port", port)}
+                />
+              </FormField>
+
+              {/* Serial Settings */}
+              <FormField label={t("ioProfileDialog.customSerial.serialBaudRate")} variant="default">
+                <BaudRateSelect
+                  value={profileForm.connection.baud_rate || "115200"}
+                  onChange={(v) => onUpdateConnectionField("baud_rate", v)}
+                />
+              </FormField>
+              <div className="grid grid-cols-3 gap-4">
+                <FormField label={t("ioProfileDialog.serial.dataBits")} variant="default">
+                  <Select
+                    variant="default"
+                    value={profileForm.connection.data_bits || "8"}
+                    onChange={(e) => onUpdateConnectionField("data_bits", e.target.value)}
+                  >
+                    <option value="8">8</option>
+                    <option value="7">7</option>
+                    <option value="6">6</option>
+                    <option value="5">5</option>
+                  </Select>
+                </FormField>
+                <FormField label={t("ioProfileDialog.serial.stopBits")} variant="default">
+                  <Select
+                    variant="default"
+                    value={profileForm.connection.stop_bits || "1"}
+                    onChange={(e) => onUpdateConnectionField("stop_bits", e.target.value)}
+                  >
+                    <option value="1">1</option>
+                    <option value="2">2</option>
+                  </Select>
+                </FormField>
+                <FormField label={t("ioProfileDialog.serial.parity")} variant="default">
+                  <Select
+                    variant="default"
+                    value={profileForm.connection.parity || "none"}
+                    onChange={(e) => onUpdateConnectionField("parity", e.target.value)}
+                  >
+                    <option value="none">{t("ioProfileDialog.serial.parityOptions.none")}</option>
+                    <option value="odd">{t("ioProfileDialog.serial.parityOptions.odd")}</option>
+                    <option value="even">{t("ioProfileDialog.serial.parityOptions.even")}</option>
+                  </Select>
+                </FormField>
+              </div>
+
+              {/* Frame Format */}
+              <div className={`border-t ${borderDefault} pt-4 mt-2 space-y-3`}>
+                <p className={textMedium}>{t("ioProfileDialog.customSerial.frameFormat")}</p>
+                <p className={caption}>{t("ioProfileDialog.customSerial.frameFormatHint")}</p>
+
+                <div className="grid grid-cols-2 gap-4">
+                  <FormField label={t("ioProfileDialog.customSerial.header")} variant="default">
+                    <HexBytesInput
+                      value={profileForm.connection.header ?? [0xaa]}
+                      onChange={(bytes) => onUpdateConnectionField("header", bytes)}
+                      placeholder="AA 55"
+                    />
+                  </FormField>
+                  <FormField label={t("ioProfileDialog.customSerial.footer")} variant="default">
+                    <HexBytesInput
+                      value={profileForm.connection.footer ?? []}
+                      onChange={(bytes) => onUpdateConnectionField("footer", bytes)}
+                      placeholder={t("ioProfileDialog.customSerial.footerPlaceholder")}
+                    />
+                  </FormField>
+                </div>
+
+                <div className="grid grid-cols-2 gap-4">
+                  <FormField label={t("ioProfileDialog.customSerial.idBytes")} variant="default">
+                    <Select
+                      variant="default"
+                      value={profileForm.connection.id_bytes || "2"}
+                      onChange={(e) => onUpdateConnectionField("id_bytes", e.target.value)}
+                    >
+                      <option value="1">1</option>
+                      <option value="2">2</option>
+                      <option value="3">3</option>
+                      <option value="4">4</option>
+                    </Select>
+                  </FormField>
+                  <FormField label={t("ioProfileDialog.customSerial.idByteOrder")} variant="default">
+                    <Select
+                      variant="default"
+                      value={profileForm.connection.id_big_endian === false ? "little" : "big"}
+                      onChange={(e) => onUpdateConnectionField("id_big_endian", e.target.value === "big")}
+                    >
+                      <option value="big">{t("ioProfileDialog.customSerial.bigEndian")}</option>
+                      <option value="little">{t("ioProfileDialog.customSerial.littleEndian")}</option>
+                    </Select>
+                  </FormField>
+                </div>
+
+                <div className={flexRowGap2}>
+                  <input
+                    type="checkbox"
+                    id="custom_serial_length_byte"
+                    checked={profileForm.connection.length_byte !== false}
+                    onChange={(e) => onUpdateConnectionField("length_byte", e.target.checked)}
+                    className={checkboxDefault}
+                  />
+                  <label htmlFor="custom_serial_length_byte" className={textMedium}>
+                    {t("ioProfileDialog.customSerial.lengthByte")}
+                  </label>
+                </div>
+                {profileForm.connection.length_byte === false && (
+                  <FormField label={t("ioProfileDialog.customSerial.fixedLength")} variant="default">
+                    <Select
+                      variant="default"
+                      value={profileForm.connection.fixed_length || "8"}
+                      onChange={(e) => onUpdateConnectionField("fixed_length", e.target.value)}
+                    >
+                      {[0, 1, 2, 3, 4, 5, 6, 7, 8].map((n) => (
+                        <option key={n} value={String(n)}>{n}</option>
+                      ))}
+                    </Select>
+                  </FormField>
+                )}
+
+                <FormField label={t("ioProfileDialog.customSerial.checksum")} variant="default">
+                  <Select
+                    variant="default"
+                    value={profileForm.connection.checksum || "none"}
+                    onChange={(e) => onUpdateConnectionField("checksum", e.target.value)}
+                  >
+                    <option value="none">{t("ioProfileDialog.customSerial.checksumOptions.none")}</option>
+                    <option value="sum8">{t("ioProfileDialog.customSerial.checksumOptions.sum8")}</option>
+                    <option value="xor8">{t("ioProfileDialog.customSerial.checksumOptions.xor8")}</option>
+                    <option value="crc8">{t("ioProfileDialog.customSerial.checksumOptions.crc8")}</option>
+                  </Select>
+                </FormField>
+                {profileForm.connection.checksum && profileForm.connection.checksum !== "none" && (
+                  <div className={flexRowGap2}>
+                    <input
+                      type="checkbox"
+                      id="custom_serial_checksum_header"
+                      checked={profileForm.connection.checksum_includes_header === true}
+                      onChange={(e) => onUpdateConnectionField("checksum_includes_header", e.target.checked)}
+                      className={checkboxDefault}
+                    />
+                    <label htmlFor="custom_serial_checksum_header" className={textMedium}>
+                      {t("ioProfileDialog.customSerial.checksumIncludesHeader")}
+                    </label>
+                  </div>
+                )}
+              </div>
+
+              {/* Listen-only mode */}
+              <div className={flexRowGap2}>
+                <input
+                  type="checkbox"
+                  id="custom_serial_listen_only"
+                  checked={profileForm.connection.listen_only === true}
+                  onChange={(e) => onUpdateConnectionField("listen_only", e.target.checked)}
+                  className={checkboxDefault}
+                />
+                <label htmlFor="custom_serial_listen_only" className={textMedium}>
+                  {t("ioProfileDialog.customSerial.listenOnly")}
+                </label>
+              </div>
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.customSerial.supportHint")}
+                </p>
+              </div>
+            </div>
+          )}
+
           {/* SocketCAN (Linux) */}
           {profileForm.kind === "socketcan" && (
             <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
@@ -1640,7 +2858,61 @@ export default function IOProfileDialog({
                     </div>
                   )}
                 </div>
-              )}
+              )}
+
+              <div>
+                <div className={flexRowGap2}>
+                  <input
+                    type="checkbox"
+                    id="socketcan_hardware_timestamps"
+                    checked={profileForm.connection.hardware_timestamps === true}
+                    onChange={(e) => onUpdateConnectionField("hardware_timestamps", e.target.checked)}
+                    className={checkboxDefault}
+                  />
+                  <label htmlFor="socketcan_hardware_timestamps" className="text-sm text-[color:var(--text-secondary)]">
+                    {t("ioProfileDialog.socketcan.hardwareTimestamps")}
+                  </label>
+                </div>
+                <p className="text-xs text-[color:var(--text-muted)] mt-1 ml-6">
+                  {t("ioProfileDialog.socketcan.hardwareTimestampsHint")}
+                </p>
+              </div>
+
+              <div>
+                <div className={flexRowGap2}>
+                  <input
+                    type="checkbox"
+                    id="socketcan_error_frames"
+                    checked={profileForm.connection.error_frames === true}
+                    onChange={(e) => onUpdateConnectionField("error_frames", e.target.checked)}
+                    className={checkboxDefault}
+                  />
+                  <label htmlFor="socketcan_error_frames" className="text-sm text-[color:var(--text-secondary)]">
+                    {t("ioProfileDialog.socketcan.errorFrames")}
+                  </label>
+                </div>
+                <p className="text-xs text-[color:var(--text-muted)] mt-1 ml-6">
+                  {t("ioProfileDialog.socketcan.errorFramesHint")}
+                </p>
+              </div>
+
+              <div>
+                <div className={flexRowGap2}>
+                  <input
+                    type="checkbox"
+                    id="socketcan_listen_only"
+                    checked={profileForm.connection.listen_only === true}
+                    onChange={(e) => onUpdateConnectionField("listen_only", e.target.checked)}
+                    className={checkboxDefault}
+                  />
+                  <label htmlFor="socketcan_listen_only" className="text-sm text-[color:var(--text-secondary)]">
+                    {t("ioProfileDialog.socketcan.listenOnly")}
+                  </label>
+                </div>
+                <p className="text-xs text-[color:var(--text-muted)] mt-1 ml-6">
+                  {t("ioProfileDialog.socketcan.listenOnlyHint")}
+                </p>
+              </div>
 
               <div className={alertInfo}>
                 <p className="text-sm text-[color:var(--text-info)]">
@@ -1652,6 +2924,17 @@ export default function IOProfileDialog({
                     : t("ioProfileDialog.socketcan.configureManual")}
                 </p>
               </div>
+
+              {/* Configure or bring the interface up/down from here */}
+              {platformIsLinux && profileForm.connection.interface && (
+                <LinuxCanSetupHelper
+                  interfaceName={profileForm.connection.interface}
+                  bitrate={parseInt(profileForm.connection.bitrate || "500000", 10)}
+                  enableFd={profileForm.connection.enable_fd === true}
+                  dataBitrate={parseInt(profileForm.connection.data_bitrate || "2000000", 10)}
+                  listenOnly={profileForm.connection.listen_only === true}
+                />
+              )}
             </div>
           )}
 
@@ -1676,6 +2959,10 @@ export default function IOProfileDialog({
                       if (device.interface_name) {
                         onUpdateConnectionField("interface", device.interface_name);
                       }
+                      // Linux reads the SocketCAN interface, which is already one channel
+                      if (device.channel !== null && !device.interface_name) {
+                        onUpdateConnectionField("channel", String(device.channel));
+                      }
                     }
                   }}
                 />
@@ -1783,16 +3070,78 @@ export default function IOProfileDialog({
                 )}
               </div>
 
+              {/* Hardware timestamps */}
+              <div>
+                <div className={flexRowGap2}>
+                  <input
+                    type="checkbox"
+                    id="gs_usb_hardware_timestamps"
+                    checked={profileForm.connection.hardware_timestamps === true}
+                    onChange={(e) => onUpdateConnectionField("hardware_timestamps", e.target.checked)}
+                    className={checkboxDefault}
+                  />
+                  <label htmlFor="gs_usb_hardware_timestamps" className="text-sm text-[color:var(--text-secondary)]">
+                    {t("ioProfileDialog.gsUsb.hardwareTimestamps")}
+                  </label>
+                </div>
+                <p className="text-xs text-[color:var(--text-muted)] mt-1 ml-6">
+                  {t("ioProfileDialog.gsUsb.hardwareTimestampsHint")}
+                </p>
+              </div>
+
+              {/* Error frames */}
+              <div>
+                <div className={flexRowGap2}>
+                  <input
+                    type="checkbox"
+                    id="gs_usb_error_frames"
+                    checked={profileForm.connection.error_frames === true}
+                    onChange={(e) => onUpdateConnectionField("error_frames", e.target.checked)}
+                    className={checkboxDefault}
+                  />
+                  <label htmlFor="gs_usb_error_frames" className="text-sm text-[color:var(--text-secondary)]">
+                    {t("ioProfileDialog.gsUsb.errorFrames")}
+                  </label>
+                </div>
+                <p className="text-xs text-[color:var(--text-muted)] mt-1 ml-6">
+                  {t("ioProfileDialog.gsUsb.errorFramesHint")}
+                </p>
+              </div>
+
+              {/* Linux: Direct USB access instead of SocketCAN */}
+              {platformIsLinux && (
+                <div>
+                  <div className={flexRowGap2}>
+                    <input
+                      type="checkbox"
+                      id="gs_usb_direct_usb"
+                      checked={profileForm.connection.direct_usb === true}
+                      onChange={(e) => onUpdateConnectionField("direct_usb", e.target.checked)}
+                      className={checkboxDefault}
+                    />
+                    <label htmlFor="gs_usb_direct_usb" className="text-sm text-[color:var(--text-secondary)]">
+                      {t("ioProfileDialog.gsUsb.directUsb")}
+                    </label>
+                  </div>
+                  <p className="text-xs text-[color:var(--text-muted)] mt-1 ml-6">
+                    {t("ioProfileDialog.gsUsb.directUsbHint")}
+                  </p>
+                </div>
+              )}
+
               {/* Linux: Setup command helper */}
-              {platformIsLinux && profileForm.connection.interface && (
+              {platformIsLinux && profileForm.connection.interface && profileForm.connection.direct_usb !== true && (
                 <LinuxCanSetupHelper
                   interfaceName={profileForm.connection.interface}
                   bitrate={parseInt(profileForm.connection.bitrate || "500000", 10)}
+                  enableFd={profileForm.connection.enable_fd === true}
+                  dataBitrate={parseInt(profileForm.connection.data_bitrate || "2000000", 10)}
+                  listenOnly={profileForm.connection.listen_only === true}
                 />
               )}
 
-              {/* Windows/macOS: Device status indicator */}
-              {(platformIsWindows || platformIsMacos) && profileForm.connection.device_id && (
+              {/* Windows/macOS, Linux direct USB: Device status indicator */}
+              {canProbeGsUsb && profileForm.connection.device_id && (
                 <IODeviceStatus
                   state={gsUsbProbeState}
                   result={gsUsbProbeResult}
@@ -1815,12 +3164,357 @@ export default function IOProfileDialog({
               </div>
             </div>
           )}
+
+          {/* PCAN-USB (PEAK) */}
+          {profileForm.kind === "pcan" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.pcan.title")}</h3>
+
+              {/* Channel Selection */}
+              <FormField label={t("ioProfileDialog.pcan.channel")} variant="default">
+                <PcanDevicePicker
+                  value={profileForm.connection.channel || ""}
+                  onChange={(channel, device) => {
+                    onUpdateConnectionField("channel", channel);
+                    // Pin to the adapter's device ID when it has one, so replugging
+                    // into a different port still finds it
+                    onUpdateConnectionField(
+                      "device_id",
+                      device?.device_id ? String(device.device_id) : "",
+                    );
+                  }}
+                />
+              </FormField>
+
+              {/* CAN Bitrate */}
+              <FormField label={t("ioProfileDialog.pcan.canBitrate")} variant="default">
+                <Select
+                  variant="default"
+                  value={profileForm.connection.bitrate || "500000"}
+                  onChange={(e) => onUpdateConnectionField("bitrate", e.target.value)}
+                >
+                  <option value="10000">10 Kbit/s</option>
+                  <option value="20000">20 Kbit/s</option>
+                  <option value="33000">33.3 Kbit/s</option>
+                  <option value="50000">50 Kbit/s</option>
+                  <option value="83000">83.3 Kbit/s</option>
+                  <option value="100000">100 Kbit/s</option>
+                  <option value="125000">125 Kbit/s</option>
+                  <option value="250000">250 Kbit/s</option>
+                  <option value="500000">500 Kbit/s</option>
+                  <option value="800000">800 Kbit/s</option>
+                  <option value="1000000">1 Mbit/s</option>
+                </Select>
+              </FormField>
+
+              {/* Listen-only mode */}
+              <div className={flexRowGap2}>
+                <input
+                  type="checkbox"
+                  id="pcan_listen_only"
+                  checked={profileForm.connection.listen_only !== false}
+                  onChange={(e) => onUpdateConnectionField("listen_only", e.target.checked)}
+                  className={checkboxDefault}
+                />
+                <label htmlFor="pcan_listen_only" className="text-sm text-[color:var(--text-secondary)]">
+                  {t("ioProfileDialog.pcan.listenOnly")}
+                </label>
+              </div>
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.pcan.supportHint")}
+                  {platformIsLinux && t("ioProfileDialog.pcan.linuxNote")}
+                </p>
+              </div>
+            </div>
+          )}
+
+          {/* Kvaser (CANlib) */}
+          {profileForm.kind === "kvaser" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.kvaser.title")}</h3>
+
+              {/* Device Selection */}
+              <FormField label={t("ioProfileDialog.kvaser.device")} variant="default">
+                <KvaserDevicePicker
+                  value={profileForm.connection.serial || ""}
+                  onChange={(serial, device) => {
+                    onUpdateConnectionField("serial", serial);
+                    onUpdateConnectionField("channel", device ? String(device.channels[0]) : "");
+                    // One interface per channel on the card, all enabled by default
+                    if (device && profileForm.connection.interfaces?.length !== device.channels.length) {
+                      onUpdateConnectionField(
+                        "interfaces",
+                        device.channels.map((_, i) => ({ device_bus: i, enabled: true, protocol: "can" as const })),
+                      );
+                    }
+                  }}
+                />
+              </FormField>
+
+              {/* CAN Bitrate */}
+              <FormField label={t("ioProfileDialog.kvaser.canBitrate")} variant="default">
+                <Select
+                  variant="default"
+                  value={profileForm.connection.bitrate || "500000"}
+                  onChange={(e) => onUpdateConnectionField("bitrate", e.target.value)}
+                >
+                  <option value="10000">10 Kbit/s</option>
+                  <option value="50000">50 Kbit/s</option>
+                  <option value="62000">62.5 Kbit/s</option>
+                  <option value="83000">83.3 Kbit/s</option>
+                  <option value="100000">100 Kbit/s</option>
+                  <option value="125000">125 Kbit/s</option>
+                  <option value="250000">250 Kbit/s</option>
+                  <option value="500000">500 Kbit/s</option>
+                  <option value="1000000">1 Mbit/s</option>
+                </Select>
+              </FormField>
+
+              {/* Listen-only mode */}
+              <div className={flexRowGap2}>
+                <input
+                  type="checkbox"
+                  id="kvaser_listen_only"
+                  checked={profileForm.connection.listen_only !== false}
+                  onChange={(e) => onUpdateConnectionField("listen_only", e.target.checked)}
+                  className={checkboxDefault}
+                />
+                <label htmlFor="kvaser_listen_only" className="text-sm text-[color:var(--text-secondary)]">
+                  {t("ioProfileDialog.kvaser.listenOnly")}
+                </label>
+              </div>
+
+              {/* Per-channel interfaces */}
+              {getDeviceBusConfig().length > 0 && (
+                <DeviceBusConfig
+                  deviceInfo={{ bus_count: getDeviceBusConfig().length }}
+                  isLoading={false}
+                  error={null}
+                  busConfig={getDeviceBusConfig()}
+                  onBusConfigChange={handleDeviceBusConfigChange}
+                  showOutputBus={false}
+                  showProtocol={false}
+                />
+              )}
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.kvaser.supportHint")}
+                  {platformIsLinux && t("ioProfileDialog.kvaser.linuxNote")}
+                </p>
+              </div>
+            </div>
+          )}
+
+          {/* Vector (XL Driver Library) */}
+          {profileForm.kind === "vector_xl" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.vectorXl.title")}</h3>
+
+              {/* Device Selection */}
+              <FormField label={t("ioProfileDialog.vectorXl.device")} variant="default">
+                <VectorDevicePicker
+                  value={profileForm.connection.serial || ""}
+                  onChange={(serial, device) => {
+                    onUpdateConnectionField("serial", serial);
+                    onUpdateConnectionField("channel", device ? String(device.channels[0]?.channel_index ?? 0) : "");
+                    // One interface per CAN channel on the device, all enabled by default
+                    if (device && profileForm.connection.interfaces?.length !== device.channels.length) {
+                      const protocol = profileForm.connection.enable_fd ? "canfd" as const : "can" as const;
+                      onUpdateConnectionField(
+                        "interfaces",
+                        device.channels.map((c) => ({ device_bus: c.hw_channel, enabled: true, protocol })),
+                      );
+                    }
+                  }}
+                />
+              </FormField>
+
+              {/* CAN Bitrate */}
+              <FormField label={t("ioProfileDialog.vectorXl.canBitrate")} variant="default">
+                <Select
+                  variant="default"
+                  value={profileForm.connection.bitrate || "500000"}
+                  onChange={(e) => onUpdateConnectionField("bitrate", e.target.value)}
+                >
+                  <option value="10000">10 Kbit/s</option>
+                  <option value="20000">20 Kbit/s</option>
+                  <option value="50000">50 Kbit/s</option>
+                  <option value="83333">83.3 Kbit/s</option>
+                  <option value="100000">100 Kbit/s</option>
+                  <option value="125000">125 Kbit/s</option>
+                  <option value="250000">250 Kbit/s</option>
+                  <option value="500000">500 Kbit/s</option>
+                  <option value="800000">800 Kbit/s</option>
+                  <option value="1000000">1 Mbit/s</option>
+                </Select>
+              </FormField>
+
+              {/* Listen-only mode */}
+              <div className={flexRowGap2}>
+                <input
+                  type="checkbox"
+                  id="vector_xl_listen_only"
+                  checked={profileForm.connection.listen_only !== false}
+                  onChange={(e) => onUpdateConnectionField("listen_only", e.target.checked)}
+                  className={checkboxDefault}
+                />
+                <label htmlFor="vector_xl_listen_only" className="text-sm text-[color:var(--text-secondary)]">
+                  {t("ioProfileDialog.vectorXl.listenOnly")}
+                </label>
+              </div>
+
+              {/* CAN FD Options */}
+              <div className={`border-t ${borderDefault} pt-4 mt-2`}>
+                <div className={flexRowGap2}>
+                  <input
+                    type="checkbox"
+                    id="vector_xl_enable_fd"
+                    checked={profileForm.connection.enable_fd === true}
+                    onChange={(e) => {
+                      onUpdateConnectionField("enable_fd", e.target.checked);
+                      // The whole port runs in one mode, so every channel follows it
+                      const interfaces = profileForm.connection.interfaces;
+                      if (interfaces?.length) {
+                        const protocol = e.target.checked ? "canfd" as const : "can" as const;
+                        onUpdateConnectionField("interfaces", interfaces.map((iface) => ({ ...iface, protocol })));
+                      }
+                    }}
+                    className={checkboxDefault}
+                  />
+                  <label htmlFor="vector_xl_enable_fd" className="text-sm text-[color:var(--text-secondary)]">
+                    {t("ioProfileDialog.vectorXl.enableFd")}
+                  </label>
+                </div>
+                <p className="text-xs text-[color:var(--text-muted)] mt-1 ml-6">
+                  {t("ioProfileDialog.vectorXl.fdHint")}
+                </p>
+
+                {profileForm.connection.enable_fd && (
+                  <div className="mt-3 space-y-3 pl-6">
+                    <FormField label={t("ioProfileDialog.vectorXl.dataPhaseBitrate")} variant="default">
+                      <Select
+                        variant="default"
+                        value={profileForm.connection.data_bitrate || "2000000"}
+                        onChange={(e) => onUpdateConnectionField("data_bitrate", e.target.value)}
+                      >
+                        <option value="1000000">1 Mbit/s</option>
+                        <option value="2000000">2 Mbit/s</option>
+                        <option value="4000000">4 Mbit/s</option>
+                        <option value="5000000">5 Mbit/s</option>
+                        <option value="8000000">8 Mbit/s</option>
+                      </Select>
+                    </FormField>
+                  </div>
+                )}
+              </div>
+
+              {/* Per-channel interfaces */}
+              {getDeviceBusConfig().length > 0 && (
+                <DeviceBusConfig
+                  deviceInfo={{ bus_count: getDeviceBusConfig().length }}
+                  isLoading={false}
+                  error={null}
+                  busConfig={getDeviceBusConfig()}
+                  onBusConfigChange={handleDeviceBusConfigChange}
+                  showOutputBus={false}
+                  showProtocol={false}
+                />
+              )}
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.vectorXl.supportHint")}
+                </p>
+              </div>
+            </div>
+          )}
+
+          {/* J2534 PassThru */}
+          {profileForm.kind === "j2534" && (
+            <div className={`${spaceYDefault} border-t ${borderDefault} pt-6`}>
+              <h3 className={h3}>{t("ioProfileDialog.j2534.title")}</h3>
+
+              {/* Device Selection */}
+              <FormField label={t("ioProfileDialog.j2534.device")} variant="default">
+                <J2534DevicePicker
+                  value={profileForm.connection.device || ""}
+                  onChange={(name, device) => {
+                    onUpdateConnectionField("device", name);
+                    onUpdateConnectionField("library_path", device?.library_path ?? "");
+                  }}
+                />
+              </FormField>
+
+              {/* CAN Bitrate */}
+              <FormField label={t("ioProfileDialog.j2534.canBitrate")} variant="default">
+                <Select
+                  variant="default"
+                  value={profileForm.connection.bitrate || "500000"}
+                  onChange={(e) => onUpdateConnectionField("bitrate", e.target.value)}
+                >
+                  <option value="125000">125 Kbit/s</option>
+                  <option value="250000">250 Kbit/s</option>
+                  <option value="500000">500 Kbit/s</option>
+                  <option value="1000000">1 Mbit/s</option>
+                </Select>
+              </FormField>
+
+              {/* DLL override */}
+              <FormField label={t("ioProfileDialog.j2534.libraryPath")} variant="default">
+                <Input
+                  variant="default"
+                  value={profileForm.connection.library_path || ""}
+                  onChange={(e) => onUpdateConnectionField("library_path", e.target.value)}
+                  placeholder="C:\Program Files\...\PassThru.dll"
+                />
+              </FormField>
+              <p className="text-xs text-[color:var(--text-muted)] -mt-2">
+                {t("ioProfileDialog.j2534.libraryPathHint")}
+              </p>
+
+              {/* Receive only */}
+              <div className={flexRowGap2}>
+                <input
+                  type="checkbox"
+                  id="j2534_listen_only"
+                  checked={profileForm.connection.listen_only === true}
+                  onChange={(e) => onUpdateConnectionField("listen_only", e.target.checked)}
+                  className={checkboxDefault}
+                />
+                <label htmlFor="j2534_listen_only" className="text-sm text-[color:var(--text-secondary)]">
+                  {t("ioProfileDialog.j2534.listenOnly")}
+                </label>
+              </div>
+
+              <div className={alertInfo}>
+                <p className="text-sm text-[color:var(--text-info)]">
+                  {t("ioProfileDialog.j2534.supportHint")}
+                </p>
+              </div>
+            </div>
+          )}
         </div>
 
+        {/* Schema issues */}
+        {fieldIssues.length > 0 && (
+          <div className={`${alertWarning} mx-6`}>
+            <ul className="text-sm text-[color:var(--text-amber)] list-disc pl-4">
+              {fieldIssues.map((issue) => (
+                <li key={issue.field}>
+                  <code>{issue.field}</code>: {issue.message}
+                </li>
+              ))}
+            </ul>
+          </div>
+        )}
+
         {/* Actions */}
         <div className={`p-6 border-t ${borderDefault} flex justify-end gap-3`}>
           <SecondaryButton onClick={onCancel}>{t("ioProfileDialog.cancel")}</SecondaryButton>
-          <PrimaryButton onClick={onSave}>
+          <PrimaryButton onClick={handleSave}>
             {editingProfileId ? t("ioProfileDialog.update") : t("ioProfileDialog.addBtn")}
           </PrimaryButton>
         </div>
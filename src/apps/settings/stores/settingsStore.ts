@@ -26,12 +26,13 @@ import {
 } from '../../../utils/dashboardLayouts';
 import { setIOSScreenWake } from '../../../utils/platform';
 // Types
-export type SettingsSection = "general" | "privacy" | "locations" | "data-io" | "devices" | "captures" | "catalogs" | "bookmarks" | "selection-sets" | "dashboard-layouts" | "display" | "mcp";
+export type SettingsSection = "general" | "privacy" | "locations" | "data-io" | "devices" | "captures" | "catalogs" | "bookmarks" | "selection-sets" | "dashboard-layouts" | "display" | "mcp" | "remote-api";
 export type DefaultFrameType = 'can' | 'modbus' | 'serial';
 
 // Buffer setting defaults — single source of truth, referenced by settingsStore and useSettings
 export const DEFAULT_BUFFER_STORAGE = "sqlite";
 export const DEFAULT_CLEAR_BUFFERS_ON_START = true;
+export const DEFAULT_CAPTURE_DB_CACHE_MB = 64;
 export const DEFAULT_DISCOVERY_HISTORY_BUFFER = 100_000;
 export const DEFAULT_QUERY_RESULT_LIMIT = 10_000;
 export const DEFAULT_GRAPH_BUFFER_SIZE = 10_000;
@@ -134,6 +135,9 @@ interface AppSettings {
   mcp_allow_ui_control?: boolean;
   mcp_server_port?: number;
   mcp_server_token?: string;
+  remote_api_enabled?: boolean;
+  remote_api_port?: number;
+  remote_api_token?: string;
   // Theme settings
   theme_mode?: ThemeMode;
   theme_bg_primary_light?: string;
@@ -168,6 +172,7 @@ interface AppSettings {
   // Buffer persistence
   clear_captures_on_start?: boolean;
   buffer_storage?: string;
+  capture_db_cache_mb?: number;
   // Modbus settings
   modbus_max_register_errors?: number;
 }
@@ -329,6 +334,7 @@ interface SettingsState {
   buffers: {
     clearCapturesOnStart: boolean;
     captureStorage: string;
+    captureDbCacheMb: number;
     discoveryHistorySize: number;
     queryResultLimit: number;
     graphBufferSize: number;
@@ -369,6 +375,13 @@ interface SettingsState {
     serverToken: string;
   };
 
+  // Remote control API (REST/WebSocket for test automation)
+  remoteApi: {
+    enabled: boolean;
+    port: number;
+    token: string;
+  };
+
   // UI state
   ui: {
     currentSection: SettingsSection;
@@ -439,6 +452,7 @@ interface SettingsState {
   // Actions - Buffers
   setClearCapturesOnStart: (value: boolean) => void;
   setCaptureStorage: (value: string) => void;
+  setCaptureDbCacheMb: (value: number) => void;
   setDiscoveryHistorySize: (buffer: number) => void;
   setQueryResultLimit: (limit: number) => void;
   setGraphBufferSize: (size: number) => void;
@@ -471,6 +485,11 @@ interface SettingsState {
   setMcpAllowUiControl: (value: boolean) => void;
   setMcpServerPort: (port: number) => void;
   setMcpServerToken: (token: string) => void;
+
+  // Actions - Remote API
+  setRemoteApiEnabled: (value: boolean) => void;
+  setRemoteApiPort: (port: number) => void;
+  setRemoteApiToken: (token: string) => void;
 }
 
 // Auto-save debounce
@@ -599,6 +618,7 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
   buffers: {
     clearCapturesOnStart: DEFAULT_CLEAR_BUFFERS_ON_START,
     captureStorage: DEFAULT_BUFFER_STORAGE,
+    captureDbCacheMb: DEFAULT_CAPTURE_DB_CACHE_MB,
     discoveryHistorySize: DEFAULT_DISCOVERY_HISTORY_BUFFER,
     queryResultLimit: DEFAULT_QUERY_RESULT_LIMIT,
     graphBufferSize: DEFAULT_GRAPH_BUFFER_SIZE,
@@ -637,6 +657,12 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
     serverToken: "",
   },
 
+  remoteApi: {
+    enabled: false,
+    port: 8788,
+    token: "",
+  },
+
   ui: {
     currentSection: 'general',
     dialogs: { ...initialDialogs },
@@ -755,6 +781,7 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
         // Buffer persistence
         clear_captures_on_start: settings.clear_captures_on_start ?? DEFAULT_CLEAR_BUFFERS_ON_START,
         buffer_storage: settings.buffer_storage ?? DEFAULT_BUFFER_STORAGE,
+        capture_db_cache_mb: settings.capture_db_cache_mb ?? DEFAULT_CAPTURE_DB_CACHE_MB,
         // Modbus
         modbus_max_register_errors: settings.modbus_max_register_errors ?? DEFAULT_MODBUS_MAX_REGISTER_ERRORS,
         // Networking
@@ -771,6 +798,10 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
         mcp_allow_ui_control: settings.mcp_allow_ui_control ?? false,
         mcp_server_port: settings.mcp_server_port ?? 8787,
         mcp_server_token: settings.mcp_server_token ?? "",
+        // Remote API
+        remote_api_enabled: settings.remote_api_enabled ?? false,
+        remote_api_port: settings.remote_api_port ?? 8788,
+        remote_api_token: settings.remote_api_token ?? "",
       };
 
       set({
@@ -835,6 +866,7 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
         buffers: {
           clearCapturesOnStart: normalized.clear_captures_on_start ?? DEFAULT_CLEAR_BUFFERS_ON_START,
           captureStorage: normalized.buffer_storage ?? DEFAULT_BUFFER_STORAGE,
+          captureDbCacheMb: normalized.capture_db_cache_mb ?? DEFAULT_CAPTURE_DB_CACHE_MB,
           discoveryHistorySize: normalized.discovery_history_buffer ?? DEFAULT_DISCOVERY_HISTORY_BUFFER,
           queryResultLimit: normalized.query_result_limit ?? DEFAULT_QUERY_RESULT_LIMIT,
           graphBufferSize: normalized.graph_buffer_size ?? DEFAULT_GRAPH_BUFFER_SIZE,
@@ -870,6 +902,11 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
           serverPort: normalized.mcp_server_port ?? 8787,
           serverToken: normalized.mcp_server_token ?? "",
         },
+        remoteApi: {
+          enabled: normalized.remote_api_enabled ?? false,
+          port: normalized.remote_api_port ?? 8788,
+          token: normalized.remote_api_token ?? "",
+        },
         // When migration occurred, use pre-migration profiles as original so hasUnsavedChanges() detects the diff
         originalSettings: migration.removedIds.size > 0
           ? { ...normalized, io_profiles: settings.io_profiles || [] }
@@ -943,7 +980,7 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
     if (!get().hasUnsavedChanges()) return;
 
     try {
-      const { locations, ioProfiles, display, buffers, general, mcp } = get();
+      const { locations, ioProfiles, display, buffers, general, mcp, remoteApi } = get();
 
       const settings = {
         config_path: locations.configPath,
@@ -969,6 +1006,7 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
         // Buffers
         clear_captures_on_start: buffers.clearCapturesOnStart,
         buffer_storage: buffers.captureStorage,
+        capture_db_cache_mb: buffers.captureDbCacheMb,
         discovery_history_buffer: buffers.discoveryHistorySize,
         query_result_limit: buffers.queryResultLimit,
         graph_buffer_size: buffers.graphBufferSize,
@@ -1025,6 +1063,10 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
         mcp_allow_ui_control: mcp.allowUiControl,
         mcp_server_port: mcp.serverPort,
         mcp_server_token: mcp.serverToken,
+        // Remote API
+        remote_api_enabled: remoteApi.enabled,
+        remote_api_port: remoteApi.port,
+        remote_api_token: remoteApi.token,
       };
 
       await saveSettingsApi(settings);
@@ -1041,7 +1083,7 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
   },
 
   hasUnsavedChanges: () => {
-    const { locations, ioProfiles, display, buffers, general, mcp, originalSettings } = get();
+    const { locations, ioProfiles, display, buffers, general, mcp, remoteApi, originalSettings } = get();
     if (!originalSettings) return false;
 
     const currentSettings = {
@@ -1067,6 +1109,7 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
       frame_editor_colours: display.frameEditorColours,
       // Buffers
       clear_captures_on_start: buffers.clearCapturesOnStart,
+      capture_db_cache_mb: buffers.captureDbCacheMb,
       discovery_history_buffer: buffers.discoveryHistorySize,
       query_result_limit: buffers.queryResultLimit,
       graph_buffer_size: buffers.graphBufferSize,
@@ -1119,6 +1162,10 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
       mcp_allow_session_control: mcp.allowSessionControl,
       mcp_server_port: mcp.serverPort,
       mcp_server_token: mcp.serverToken,
+      // Remote API
+      remote_api_enabled: remoteApi.enabled,
+      remote_api_port: remoteApi.port,
+      remote_api_token: remoteApi.token,
     };
 
     return stableStringify(currentSettings) !== stableStringify(originalSettings);
@@ -1423,6 +1470,13 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
     scheduleSave(get().saveSettings);
   },
 
+  setCaptureDbCacheMb: (value) => {
+    set((state) => ({
+      buffers: { ...state.buffers, captureDbCacheMb: value },
+    }));
+    scheduleSave(get().saveSettings);
+  },
+
   setDiscoveryHistorySize: (buffer) => {
     set((state) => ({
       buffers: { ...state.buffers, discoveryHistorySize: buffer },
@@ -1617,4 +1671,17 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
     set((state) => ({ mcp: { ...state.mcp, serverToken: token } }));
     scheduleSave(get().saveSettings);
   },
+
+  setRemoteApiEnabled: (value) => {
+    set((state) => ({ remoteApi: { ...state.remoteApi, enabled: value } }));
+    scheduleSave(get().saveSettings);
+  },
+  setRemoteApiPort: (port) => {
+    set((state) => ({ remoteApi: { ...state.remoteApi, port } }));
+    scheduleSave(get().saveSettings);
+  },
+  setRemoteApiToken: (token) => {
+    set((state) => ({ remoteApi: { ...state.remoteApi, token } }));
+    scheduleSave(get().saveSettings);
+  },
 }));
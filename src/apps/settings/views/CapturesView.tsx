@@ -11,6 +11,8 @@ type CapturesViewProps = {
   onChangeClearCapturesOnStart: (value: boolean) => void;
   captureStorage: string;
   onChangeCaptureStorage: (value: string) => void;
+  captureDbCacheMb: number;
+  onChangeCaptureDbCacheMb: (value: number) => void;
   discoveryHistorySize: number;
   onChangeDiscoveryHistorySize: (value: number) => void;
   queryResultLimit: number;
@@ -34,6 +36,8 @@ export default function CapturesView({
   onChangeClearCapturesOnStart,
   captureStorage,
   onChangeCaptureStorage,
+  captureDbCacheMb,
+  onChangeCaptureDbCacheMb,
   discoveryHistorySize,
   onChangeDiscoveryHistorySize,
   queryResultLimit,
@@ -74,6 +78,24 @@ export default function CapturesView({
           </Select>
         </div>
 
+        <div className="space-y-2">
+          <label className={labelDefault}>{t("captures.storage.dbCacheMb.label")}</label>
+          <p className={helpText}>{t("captures.storage.dbCacheMb.help")}</p>
+          <Input
+            type="number"
+            min={16}
+            max={4096}
+            step={16}
+            value={captureDbCacheMb}
+            onChange={(e) => {
+              const value = Number(e.target.value);
+              if (value >= 16 && value <= 4096) {
+                onChangeCaptureDbCacheMb(value);
+              }
+            }}
+          />
+        </div>
+
         <label className="flex items-start gap-3 cursor-pointer">
           <input
             type="checkbox"
@@ -0,0 +1,193 @@
+// ui/src/apps/settings/views/RemoteApiView.tsx
+//
+// Settings view for the remote control API — a localhost REST/WebSocket server
+// that lets test automation and CI rigs open sessions, transmit and export
+// captures. Off by default, and it won't start without a bearer token.
+
+import { useCallback, useEffect, useState } from "react";
+import { Copy, RefreshCw, AlertTriangle, Check } from "lucide-react";
+import { useSettingsStore } from "../stores/settingsStore";
+import { getRemoteApiStatus, toggleRemoteApi, type RemoteApiStatus } from "../../../api/remoteApi";
+import { labelDefault, helpText, inputSimple, buttonBase } from "../../../styles";
+
+function generateToken(): string {
+  const bytes = new Uint8Array(24);
+  crypto.getRandomValues(bytes);
+  return Array.from(bytes, (b) => b.toString(16).padStart(2, "0")).join("");
+}
+
+export default function RemoteApiView() {
+  const enabled = useSettingsStore((s) => s.remoteApi.enabled);
+  const port = useSettingsStore((s) => s.remoteApi.port);
+  const token = useSettingsStore((s) => s.remoteApi.token);
+  const setEnabled = useSettingsStore((s) => s.setRemoteApiEnabled);
+  const setPort = useSettingsStore((s) => s.setRemoteApiPort);
+  const setToken = useSettingsStore((s) => s.setRemoteApiToken);
+
+  const [status, setStatus] = useState<RemoteApiStatus>({ running: false, port: null });
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [copied, setCopied] = useState<string | null>(null);
+
+  const refreshStatus = useCallback(async () => {
+    try {
+      setStatus(await getRemoteApiStatus());
+    } catch {
+      /* command unavailable — leave status as-is */
+    }
+  }, []);
+
+  useEffect(() => {
+    refreshStatus();
+  }, [refreshStatus]);
+
+  // Persist current settings then (re)start or stop the server so changes apply
+  // without an app restart.
+  const apply = useCallback(
+    async (on: boolean) => {
+      setBusy(true);
+      setError(null);
+      try {
+        await useSettingsStore.getState().saveSettings();
+        setStatus(await toggleRemoteApi(on));
+      } catch (e) {
+        setError(String(e));
+        await refreshStatus();
+      } finally {
+        setBusy(false);
+      }
+    },
+    [refreshStatus],
+  );
+
+  const copy = useCallback((key: string, text: string) => {
+    navigator.clipboard.writeText(text).then(() => {
+      setCopied(key);
+      setTimeout(() => setCopied((c) => (c === key ? null : c)), 1500);
+    });
+  }, []);
+
+  const example = `curl -H "Authorization: Bearer ${token || "<token>"}" http://127.0.0.1:${port}/api/sessions`;
+
+  return (
+    <div className="space-y-6">
+      <h2 className="text-xl font-semibold text-[color:var(--text-primary)]">Remote API</h2>
+      <p className={helpText}>
+        A REST and WebSocket API for test automation: open, start, stop and destroy
+        sessions, transmit CAN frames, stream frames and export captures. Off by
+        default. The server binds to 127.0.0.1 only.
+      </p>
+
+      <label className="flex items-start gap-3 cursor-pointer">
+        <input
+          type="checkbox"
+          checked={enabled}
+          disabled={busy || (!enabled && !token)}
+          onChange={(e) => {
+            setEnabled(e.target.checked);
+            apply(e.target.checked);
+          }}
+          className="mt-1"
+        />
+        <div>
+          <span className={labelDefault}>Enable remote API</span>
+          <p className={`${helpText} flex items-start gap-1`}>
+            <AlertTriangle size={14} className="text-amber-500 mt-0.5 shrink-0" />
+            <span>
+              Anything holding the token can <strong>drive</strong> the app, including
+              transmitting on the bus. A token is required.{" "}
+              <span className={status.running ? "text-green-500" : "text-[color:var(--text-secondary)]"}>
+                {status.running ? `Running on 127.0.0.1:${status.port}` : "Stopped"}
+              </span>
+            </span>
+          </p>
+        </div>
+      </label>
+
+      {/* Port */}
+      <div className="space-y-2 max-w-xs">
+        <label className={labelDefault} htmlFor="remote-api-port">
+          Port
+        </label>
+        <input
+          id="remote-api-port"
+          type="number"
+          min={1024}
+          max={65535}
+          value={port}
+          onChange={(e) => setPort(Number(e.target.value) || 8788)}
+          className={inputSimple}
+        />
+      </div>
+
+      {/* Token */}
+      <div className="space-y-2 max-w-xl">
+        <label className={labelDefault} htmlFor="remote-api-token">
+          Bearer token
+        </label>
+        <div className="flex items-center gap-2">
+          <input
+            id="remote-api-token"
+            type="text"
+            value={token}
+            placeholder="(required)"
+            onChange={(e) => setToken(e.target.value)}
+            className={`${inputSimple} font-mono text-xs`}
+          />
+          <button
+            type="button"
+            className={buttonBase}
+            title="Generate a new token"
+            onClick={() => setToken(generateToken())}
+          >
+            <RefreshCw size={14} /> Generate
+          </button>
+          <button
+            type="button"
+            className={buttonBase}
+            disabled={!token}
+            title="Copy token"
+            onClick={() => copy("token", token)}
+          >
+            {copied === "token" ? <Check size={14} /> : <Copy size={14} />}
+          </button>
+        </div>
+      </div>
+
+      {/* Apply */}
+      <div>
+        <button
+          type="button"
+          className={buttonBase}
+          disabled={busy || (enabled && !token)}
+          onClick={() => apply(enabled)}
+        >
+          {enabled ? "Apply & restart server" : "Apply"}
+        </button>
+        {error && <p className={`${helpText} mt-1 text-red-500`}>{error}</p>}
+      </div>
+
+      {/* Example */}
+      <div className="space-y-2 max-w-2xl">
+        <label className={labelDefault}>Try it</label>
+        <div className="flex items-start gap-2">
+          <pre className="flex-1 text-xs font-mono whitespace-pre-wrap break-all bg-[var(--bg-primary)] border border-[color:var(--border-default)] rounded p-3 text-[color:var(--text-primary)]">
+            {example}
+          </pre>
+          <button
+            type="button"
+            className={buttonBase}
+            title="Copy command"
+            onClick={() => copy("cmd", example)}
+          >
+            {copied === "cmd" ? <Check size={14} /> : <Copy size={14} />}
+          </button>
+        </div>
+        <p className={helpText}>
+          Endpoints live under <code>/api/sessions</code> and <code>/api/captures</code>;
+          <code> /api/sessions/&#123;id&#125;/frames</code> is a WebSocket streaming new frames.
+        </p>
+      </div>
+    </div>
+  );
+}
@@ -60,7 +60,7 @@ export default function TestPattern() {
     () =>
       ioProfiles.filter((p) => {
         const k = p.kind;
-        return ["slcan", "gvret_tcp", "gvret_usb", "gs_usb", "socketcan",
+        return ["slcan", "custom_serial", "gvret_tcp", "gvret_usb", "gs_usb", "pcan", "kvaser", "vector_xl", "j2534", "socketcan",
                 "serial", "virtual", "framelink"].includes(k);
       }),
     [ioProfiles],
@@ -73,11 +73,14 @@ export default function TestPattern() {
       if (k === "slcan" && p.connection?.silent_mode) {
         return [p.id, { canTransmit: false, reason: "Silent mode — cannot transmit" }];
       }
-      if (k === "gs_usb" && p.connection?.listen_only !== false) {
+      if ((k === "custom_serial" || k === "j2534") && p.connection?.listen_only === true) {
+        return [p.id, { canTransmit: false, reason: "Receive only — cannot transmit" }];
+      }
+      if ((k === "gs_usb" || k === "pcan" || k === "kvaser" || k === "vector_xl") && p.connection?.listen_only !== false) {
         return [p.id, { canTransmit: false, reason: "Listen-only mode — cannot transmit" }];
       }
       // Read-only sources
-      if (!["slcan", "gvret_tcp", "gvret_usb", "gs_usb", "socketcan",
+      if (!["slcan", "custom_serial", "gvret_tcp", "gvret_usb", "gs_usb", "pcan", "kvaser", "vector_xl", "j2534", "socketcan",
             "serial", "virtual", "framelink"].includes(k)) {
         return [p.id, { canTransmit: false, reason: "Not a transmit interface" }];
       }
@@ -1,6 +1,7 @@
 // ui/src/apps/transmit/Transmit.tsx
 //
-// Main Transmit app component with tabbed interface for CAN/Serial transmission.
+// Main Transmit app component with tabbed interface for CAN/Serial transmission
+// and Modbus writes.
 // Uses useIOSessionManager for session management and useTransmitHandlers for business logic.
 
 import { useEffect, useCallback, useMemo } from "react";
@@ -29,6 +30,7 @@ import AppLayout from "../../components/AppLayout";
 import TransmitTopBar from "./views/TransmitTopBar";
 import CanTransmitView from "./views/CanTransmitView";
 import SerialTransmitView from "./views/SerialTransmitView";
+import ModbusTransmitView from "./views/ModbusTransmitView";
 import TransmitQueueView from "./views/TransmitQueueView";
 import TransmitHistoryView from "./views/TransmitHistoryView";
 import TransmitReplayView from "./views/TransmitReplayView";
@@ -46,12 +48,19 @@ function getTransmitStatus(p: IOProfile): { canTransmit: boolean; reason?: strin
     }
     return { canTransmit: true };
   }
+  // Custom CAN-over-serial and J2534 can transmit unless set to receive only
+  if (p.kind === "custom_serial" || p.kind === "j2534") {
+    if (p.connection?.listen_only === true) {
+      return { canTransmit: false, reason: "Receive only" };
+    }
+    return { canTransmit: true };
+  }
   // gvret_tcp and gvret_usb can transmit
   if (p.kind === "gvret_tcp" || p.kind === "gvret_usb") {
     return { canTransmit: true };
   }
-  // gs_usb can transmit if not in listen-only mode
-  if (p.kind === "gs_usb") {
+  // gs_usb, PCAN-USB, Kvaser and Vector can transmit if not in listen-only mode
+  if (p.kind === "gs_usb" || p.kind === "pcan" || p.kind === "kvaser" || p.kind === "vector_xl") {
     if (p.connection?.listen_only !== false) {
       return { canTransmit: false, reason: "Listen-only mode" };
     }
@@ -73,6 +82,13 @@ function getTransmitStatus(p: IOProfile): { canTransmit: boolean; reason?: strin
   if (p.kind === "framelink") {
     return { canTransmit: true };
   }
+  // Modbus TCP clients write coils and registers; a simulator sets its own
+  if (p.kind === "modbus_tcp") {
+    if (p.connection?.role === "server") {
+      return { canTransmit: false, reason: "Listen-only server" };
+    }
+    return { canTransmit: true };
+  }
   return { canTransmit: false, reason: "Not a transmit interface" }; // surfaced via translation in views
 }
 
@@ -91,12 +107,18 @@ function TransmitInner() {
     () =>
       ioProfiles.filter((p) => {
         if (p.kind === "slcan") return true;
+        if (p.kind === "custom_serial") return true;
         if (p.kind === "gvret_tcp" || p.kind === "gvret_usb") return true;
         if (p.kind === "gs_usb") return true;
+        if (p.kind === "pcan") return true;
+        if (p.kind === "kvaser") return true;
+        if (p.kind === "vector_xl") return true;
+        if (p.kind === "j2534") return true;
         if (p.kind === "socketcan") return true;
         if (p.kind === "serial") return true;
         if (p.kind === "virtual") return true;
         if (p.kind === "framelink") return true;
+        if (p.kind === "modbus_tcp") return true;
         return false;
       }),
     [ioProfiles]
@@ -208,6 +230,7 @@ function TransmitInner() {
   useMenuSessionControl({
     panelId: "transmit",
     sessionState: {
+      sessionId: session.sessionId,
       profileName: ioProfileName ?? null,
       isStreaming,
       isPaused,
@@ -255,12 +278,14 @@ function TransmitInner() {
   const protocols = capabilities?.traits?.protocols ?? [];
   const isSerialProtocol = protocols.includes("serial");
   const isCanProtocol = protocols.some(p => p === "can" || p === "canfd");
+  const isModbusProtocol = protocols.includes("modbus");
 
   // Render active tab content
   const renderTabContent = () => {
     switch (activeTab) {
       case "frame":
-        // Show Serial view for serial protocol, CAN view for CAN protocol
+        // Show Modbus writes for Modbus, Serial view for serial protocol, CAN view for CAN protocol
+        if (isModbusProtocol) return <ModbusTransmitView />;
         return isSerialProtocol && !isCanProtocol ? (
           <SerialTransmitView />
         ) : (
@@ -349,7 +374,13 @@ function TransmitInner() {
             {/* Protocol badge with status light */}
             <div className="ml-1">
               <ProtocolBadge
-                label={isSerialProtocol && !isCanProtocol ? t("main.protocolSerial") : t("main.protocolCAN")}
+                label={
+                  isModbusProtocol
+                    ? t("main.protocolModbus")
+                    : isSerialProtocol && !isCanProtocol
+                      ? t("main.protocolSerial")
+                      : t("main.protocolCAN")
+                }
                 isStreaming={isStreaming}
               />
             </div>
@@ -359,8 +390,12 @@ function TransmitInner() {
               onClick={() => handlers.handleTabClick("frame")}
               className={dataViewTabClass(activeTab === "frame")}
             >
-              {/* Show "Bytes" for serial protocol, "Frame" for CAN */}
-              {isSerialProtocol && !isCanProtocol ? t("tabs.bytes") : t("tabs.frame")}
+              {/* Show "Write" for Modbus, "Bytes" for serial protocol, "Frame" for CAN */}
+              {isModbusProtocol
+                ? t("tabs.write")
+                : isSerialProtocol && !isCanProtocol
+                  ? t("tabs.bytes")
+                  : t("tabs.frame")}
             </button>
             <button
               onClick={() => handlers.handleTabClick("queue")}
@@ -0,0 +1,57 @@
+// src/apps/transmit/utils/modbusWrite.ts
+//
+// Builds a Modbus write request from the editor's text inputs.
+
+import type { ModbusWrite, ModbusWriteFunction } from "../../../api/transmit";
+
+/** Whether a function code writes coils (05, 15) rather than registers (06, 16) */
+export function isCoilWrite(functionCode: ModbusWriteFunction): boolean {
+  return functionCode === 5 || functionCode === 15;
+}
+
+/** Whether a function code writes a single value (05, 06) */
+export function isSingleWrite(functionCode: ModbusWriteFunction): boolean {
+  return functionCode === 5 || functionCode === 6;
+}
+
+/** Parse a decimal or 0x-prefixed hex integer; null if invalid */
+function parseInteger(text: string): number | null {
+  const trimmed = text.trim();
+  if (/^0x[0-9a-f]+$/i.test(trimmed)) return parseInt(trimmed.slice(2), 16);
+  if (/^\d+$/.test(trimmed)) return parseInt(trimmed, 10);
+  return null;
+}
+
+/** Parse a coil value: 0/1, on/off, true/false */
+function parseCoil(text: string): number | null {
+  const value = text.trim().toLowerCase();
+  if (value === "1" || value === "on" || value === "true") return 1;
+  if (value === "0" || value === "off" || value === "false") return 0;
+  return null;
+}
+
+/**
+ * Build a write from the editor inputs. Values are separated by commas or
+ * whitespace. Returns an error message key suffix when an input is invalid.
+ */
+export function buildModbusWrite(
+  functionCode: ModbusWriteFunction,
+  unitIdInput: string,
+  addressInput: string,
+  valuesInput: string
+): { write: ModbusWrite } | { error: "unitId" | "address" | "values" | "count" } {
+  const unitId = parseInteger(unitIdInput);
+  if (unitId === null || unitId > 255) return { error: "unitId" };
+  const address = parseInteger(addressInput);
+  if (address === null || address > 0xffff) return { error: "address" };
+
+  const tokens = valuesInput.split(/[\s,]+/).filter(Boolean);
+  const parse = isCoilWrite(functionCode) ? parseCoil : parseInteger;
+  const values = tokens.map(parse);
+  if (values.length === 0 || values.some((v) => v === null || v > 0xffff)) return { error: "values" };
+
+  const max = isSingleWrite(functionCode) ? 1 : isCoilWrite(functionCode) ? 1968 : 123;
+  if (values.length > max || address + values.length > 0x10000) return { error: "count" };
+
+  return { write: { function_code: functionCode, unit_id: unitId, address, values: values as number[] } };
+}
@@ -0,0 +1,165 @@
+// ui/src/apps/transmit/views/ModbusTransmitView.tsx
+//
+// Modbus coil/register write editor (function codes 05, 06, 15, 16).
+
+import { useCallback, useMemo, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { RotateCcw, Send } from "lucide-react";
+import { useTransmitStore } from "../../../stores/transmitStore";
+import { useActiveSession } from "../../../stores/sessionStore";
+import { ioTransmitModbusWrite, type ModbusWriteFunction } from "../../../api/transmit";
+import { buildModbusWrite, isCoilWrite } from "../utils/modbusWrite";
+import {
+  bgDataToolbar,
+  borderDataView,
+  textDataPrimary,
+  bgDataInput,
+  textDataSecondary,
+  focusBorder,
+} from "../../../styles/colourTokens";
+import { buttonBase, toggleChipClass } from "../../../styles/buttonStyles";
+import { emptyStateContainer, emptyStateText, emptyStateHeading, emptyStateDescription } from "../../../styles/typography";
+
+const FUNCTIONS: ModbusWriteFunction[] = [5, 6, 15, 16];
+
+export default function ModbusTransmitView() {
+  const { t } = useTranslation("transmit");
+  const activeSession = useActiveSession();
+  const modbusEditor = useTransmitStore((s) => s.modbusEditor);
+  const updateModbusEditor = useTransmitStore((s) => s.updateModbusEditor);
+  const resetModbusEditor = useTransmitStore((s) => s.resetModbusEditor);
+
+  const [isSending, setIsSending] = useState(false);
+  const [sendError, setSendError] = useState<string | null>(null);
+
+  const isConnected = activeSession?.lifecycleState === "connected";
+
+  const built = useMemo(
+    () =>
+      buildModbusWrite(
+        modbusEditor.functionCode,
+        modbusEditor.unitId,
+        modbusEditor.address,
+        modbusEditor.valuesInput
+      ),
+    [modbusEditor]
+  );
+  const write = "write" in built ? built.write : null;
+  const hasValues = modbusEditor.valuesInput.trim().length > 0;
+
+  const handleSend = useCallback(async () => {
+    if (!activeSession?.id || !write) return;
+    setIsSending(true);
+    setSendError(null);
+    try {
+      const result = await ioTransmitModbusWrite(activeSession.id, write);
+      if (!result.success) setSendError(result.error ?? null);
+    } catch (e) {
+      setSendError(String(e));
+    } finally {
+      setIsSending(false);
+    }
+  }, [activeSession, write]);
+
+  if (!isConnected) {
+    return (
+      <div className={emptyStateContainer}>
+        <div className={emptyStateText}>
+          <p className={emptyStateHeading}>{t("modbusView.notConnectedHeading")}</p>
+          <p className={emptyStateDescription}>{t("modbusView.notConnectedDescription")}</p>
+        </div>
+      </div>
+    );
+  }
+
+  const inputClass = `${bgDataInput} ${textDataPrimary} font-mono text-sm rounded px-2 py-1.5 border ${borderDataView} ${focusBorder}`;
+
+  return (
+    <div className="flex flex-col h-full overflow-auto">
+      <div className={`p-4 border-b ${borderDataView} space-y-4`}>
+        {/* Function code */}
+        <div className="space-y-2">
+          <label className={`${textDataSecondary} text-xs`}>{t("modbusView.function")}</label>
+          <div className="flex items-center gap-2 flex-wrap">
+            {FUNCTIONS.map((fc) => (
+              <button
+                key={fc}
+                onClick={() => updateModbusEditor({ functionCode: fc })}
+                className={toggleChipClass(modbusEditor.functionCode === fc)}
+              >
+                {t(`modbusView.functions.fc${fc}`)}
+              </button>
+            ))}
+          </div>
+        </div>
+
+        <div className="flex items-end gap-4">
+          <div>
+            <label className={`${textDataSecondary} text-xs mb-1 block`}>{t("modbusView.unitId")}</label>
+            <input
+              type="text"
+              value={modbusEditor.unitId}
+              onChange={(e) => updateModbusEditor({ unitId: e.target.value })}
+              className={`w-20 ${inputClass}`}
+            />
+          </div>
+          <div>
+            <label className={`${textDataSecondary} text-xs mb-1 block`}>{t("modbusView.address")}</label>
+            <input
+              type="text"
+              value={modbusEditor.address}
+              onChange={(e) => updateModbusEditor({ address: e.target.value })}
+              placeholder="0"
+              className={`w-28 ${inputClass}`}
+            />
+          </div>
+        </div>
+
+        <div>
+          <label className={`${textDataSecondary} text-xs mb-1 block`}>
+            {isCoilWrite(modbusEditor.functionCode) ? t("modbusView.coilValues") : t("modbusView.registerValues")}
+          </label>
+          <textarea
+            value={modbusEditor.valuesInput}
+            onChange={(e) => updateModbusEditor({ valuesInput: e.target.value })}
+            placeholder={
+              isCoilWrite(modbusEditor.functionCode)
+                ? t("modbusView.coilPlaceholder")
+                : t("modbusView.registerPlaceholder")
+            }
+            rows={3}
+            className={`w-full ${inputClass} px-3 py-2 resize-none`}
+          />
+          <p className={`${textDataSecondary} text-xs mt-1`}>{t("modbusView.addressHint")}</p>
+        </div>
+      </div>
+
+      {/* Validation / result */}
+      {(hasValues && "error" in built) || sendError ? (
+        <div className={`px-4 py-2 ${bgDataToolbar} border-b ${borderDataView} text-xs text-red-400`}>
+          {sendError ?? ("error" in built ? t(`modbusView.errors.${built.error}`) : null)}
+        </div>
+      ) : null}
+
+      {/* Actions */}
+      <div className={`flex items-center gap-3 px-4 py-3 ${bgDataToolbar}`}>
+        <button
+          onClick={handleSend}
+          disabled={!write || isSending}
+          className={`${buttonBase} ${write && !isSending ? "bg-blue-600 hover:bg-blue-500" : ""}`}
+          title={t("modbusView.writeTooltip")}
+        >
+          <Send size={16} />
+          <span>{isSending ? t("modbusView.writing") : t("modbusView.write")}</span>
+        </button>
+
+        <div className="flex-1" />
+
+        <button onClick={resetModbusEditor} className={buttonBase} title={t("modbusView.resetTooltip")}>
+          <RotateCcw size={14} />
+          <span>{t("modbusView.reset")}</span>
+        </button>
+      </div>
+    </div>
+  );
+}
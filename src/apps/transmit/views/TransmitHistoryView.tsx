@@ -37,7 +37,7 @@ function historyRowToFrameRow(row: TransmitHistoryRow): FrameRow {
   return {
     timestamp_us: row.timestamp_us,
     frame_id: row.frame_id ?? 0,
-    protocol: 'can',
+    protocol: row.kind === "modbus" ? 'modbus' : 'can',
     is_extended: row.is_extended,
     dlc: row.dlc ?? 0,
     bytes: row.bytes,
@@ -131,9 +131,16 @@ export default function TransmitHistoryView({ sessionId }: TransmitHistoryViewPr
       const allRows = await transmitHistoryQuery(0, totalCount + 1);
       const headers = ["Timestamp", "Session", "Kind", "Frame ID", "DLC", "Data", "Bus", "Flags", "Success", "Error"];
       const csvRows: (string | number)[][] = allRows.map((row) => {
-        const frameIdStr = row.kind === "can" && row.frame_id != null
-          ? formatFrameId(row.frame_id, "hex", row.is_extended)
-          : "";
+        // Modbus writes record the start address in frame_id, DoIP requests the target ECU
+        const frameIdStr = row.frame_id == null
+          ? ""
+          : row.kind === "can"
+            ? formatFrameId(row.frame_id, "hex", row.is_extended)
+            : row.kind === "modbus"
+              ? String(row.frame_id)
+              : row.kind === "doip"
+                ? `0x${row.frame_id.toString(16).toUpperCase().padStart(4, "0")}`
+                : "";
         const dataStr = row.bytes.map(byteToHex).join("");
         const flags = [
           row.is_extended && "EXT",
@@ -40,7 +40,7 @@ import {
   removeOpenMainWindow,
   getNextMainWindowNumber,
 } from "../utils/persistence";
-import { getAppVersion, settingsPanelClosed, openSettingsPanel, updateMenuState } from "../api";
+import { getAppVersion, settingsPanelClosed, openSettingsPanel, syncMenuState } from "../api";
 import { registerOpenApp, unregisterOpenApp } from "../api/io";
 import { trackFeatureUsage } from "../api/telemetry";
 import { formatWindowName } from "../utils/windowName";
@@ -577,15 +577,7 @@ export default function MainLayout() {
   useEffect(() => {
     const hasSession = focusedPanelId !== null && sessionAwarePanelIds.has(focusedPanelId);
     if (!hasSession) {
-      updateMenuState({
-        hasSession: false,
-        profileName: null,
-        isStreaming: false,
-        isPaused: false,
-        canPause: false,
-        joinerCount: 0,
-        hasBookmarks: false,
-      });
+      syncMenuState(null, false);
     }
   }, [focusedPanelId]);
 
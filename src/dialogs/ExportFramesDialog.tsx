@@ -20,7 +20,8 @@ export type ExportFramesDialogProps = {
   /** Default filename (without extension) - passed to OS file picker */
   defaultFilename?: string;
   onCancel: () => void;
-  onExport: (format: ExportFormat, filename: string) => void;
+  /** `writeSidecar`: also write a `<file>.metadata.json` companion */
+  onExport: (format: ExportFormat, filename: string, writeSidecar: boolean) => void;
 };
 
 const FRAME_FORMAT_EXTENSIONS: Record<string, string> = {
@@ -45,6 +46,7 @@ export default function ExportFramesDialog({
 }: ExportFramesDialogProps) {
   const { t } = useTranslation("dialogs");
   const [format, setFormat] = useState<ExportFormat>(dataMode === "bytes" ? "hex" : "csv");
+  const [writeSidecar, setWriteSidecar] = useState(false);
 
   // Update format when dataMode changes
   useEffect(() => {
@@ -76,7 +78,7 @@ export default function ExportFramesDialog({
     const ext = formatExtensions[format] || ".txt";
     const baseName = defaultFilename || (dataMode === "bytes" ? "serial-bytes" : "can-frames");
     const fullFilename = `${baseName}${ext}`;
-    onExport(format, fullFilename);
+    onExport(format, fullFilename, writeSidecar);
   };
 
   const title = dataMode === "bytes" ? t("exportFrames.titleBytes") : t("exportFrames.titleFrames");
@@ -102,6 +104,16 @@ export default function ExportFramesDialog({
 
         <div className={caption}>{formatDescription}</div>
 
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={writeSidecar}
+            onChange={(e) => setWriteSidecar(e.target.checked)}
+            className="rounded"
+          />
+          <span className="text-sm text-[color:var(--text-secondary)]">{t("exportFrames.writeSidecar")}</span>
+        </label>
+
         <div className="flex justify-end gap-3 pt-4">
           <SecondaryButton onClick={onCancel}>{t("common:actions.cancel")}</SecondaryButton>
           <PrimaryButton onClick={handleExport}>{t("common:actions.export")}</PrimaryButton>
@@ -42,6 +42,7 @@ const reinitializingSessions = new Map<string, number | true>();
 const REINITIALIZE_GRACE_PERIOD_MS = 300;
 import {
   setSessionSubscriberActive,
+  setSessionSubscriberRateLimit,
   getIOSessionState,
   getIOSessionCapabilities,
   getReaderSessionJoinerCount,
@@ -58,8 +59,10 @@ import {
   type TransmitResult,
   type PlaybackPosition,
   type RawBytesPayload,
+  type Protocol,
 } from "../api/io";
 import type { FrameMessage } from "../types/frame";
+import { filterFramesByProtocol } from "../utils/frameProtocol";
 import type { DecodedFrameMsg } from "../services/wsProtocol";
 
 // ============================================================================
@@ -180,6 +183,18 @@ export interface UseIOSessionOptions {
   requireFrames?: boolean;
   /** Callback when frames are received */
   onFrames?: (frames: FrameMessage[]) => void;
+  /**
+   * Only deliver frames of these protocols to onFrames (e.g. just "modbus" on
+   * a mixed CAN + Modbus session). Omit to receive every frame.
+   */
+  protocols?: Protocol[];
+  /**
+   * Ask the backend to send at most this many frames per frame ID per second
+   * (newest frame per interval), for high-rate buses on low-power devices.
+   * Listeners sharing a session get the least restrictive limit among them.
+   * Omit (or 0) to receive every frame.
+   */
+  maxFramesPerSecond?: number;
   /** Callback when decoded signals arrive (Rust decoder; catalogue attached) */
   onDecoded?: (decoded: DecodedFrameMsg[]) => void;
   /** Callback when raw bytes are received (serial byte streams) */
@@ -343,6 +358,8 @@ export function useIOSession(
     profileId: profileIdOption,
     requireFrames,
     onFrames,
+    protocols,
+    maxFramesPerSecond,
     onDecoded,
     onBytes,
     onError,
@@ -409,6 +426,7 @@ export function useIOSession(
   // Store callbacks in refs to keep them current
   const callbacksRef = useRef({
     onFrames,
+    protocols,
     onDecoded,
     onBytes,
     onError,
@@ -426,6 +444,7 @@ export function useIOSession(
   useEffect(() => {
     callbacksRef.current = {
       onFrames,
+      protocols,
       onDecoded,
       onBytes,
       onError,
@@ -440,7 +459,31 @@ export function useIOSession(
       onSourceReplaced,
       onDestroyed,
     };
-  }, [onFrames, onDecoded, onBytes, onError, onTimeUpdate, onStreamEnded, onStreamComplete, onSpeedChange, onReconfigure, onSuspended, onSwitchedToCapture, onResuming, onSourceReplaced, onDestroyed]);
+  }, [onFrames, protocols, onDecoded, onBytes, onError, onTimeUpdate, onStreamEnded, onStreamComplete, onSpeedChange, onReconfigure, onSuspended, onSwitchedToCapture, onResuming, onSourceReplaced, onDestroyed]);
+
+  // Backend delivery rate limit for this subscriber, re-sent after every
+  // (re)registration and whenever the option changes
+  const rateLimitRef = useRef(maxFramesPerSecond);
+  const applyRateLimit = useCallback((sessionId: string) => {
+    const limit = rateLimitRef.current;
+    if (limit === undefined) return;
+    setSessionSubscriberRateLimit(sessionId, subscriberIdRef.current, limit > 0 ? limit : null).catch((e) =>
+      tlog.info(`[useIOSession:${appName}] failed to set rate limit: ${e}`)
+    );
+  }, [appName]);
+  useEffect(() => {
+    if (rateLimitRef.current === maxFramesPerSecond) return;
+    rateLimitRef.current = maxFramesPerSecond ?? 0;
+    if (currentSessionIdRef.current) applyRateLimit(currentSessionIdRef.current);
+  }, [maxFramesPerSecond, applyRateLimit]);
+
+  // Frames for this subscriber, narrowed to its protocols on mixed sessions
+  const deliverFrames = useCallback((frames: FrameMessage[]) => {
+    const { onFrames: cb, protocols: wanted } = callbacksRef.current;
+    if (!cb) return;
+    const matching = filterFramesByProtocol(frames, wanted);
+    if (matching.length > 0) cb(matching);
+  }, []);
 
   // ---- Sync session store → localState ----
   // The session store receives WS push messages (SessionState, SessionLifecycle,
@@ -718,7 +761,7 @@ export function useIOSession(
         // Register callbacks with the frontend store for event routing
         tlog.debug(`[useIOSession:${appName}] calling registerCallbacks...`);
         registerCallbacks(effectiveSessionId, subscriberIdRef.current, {
-          onFrames: (frames) => callbacksRef.current.onFrames?.(frames),
+          onFrames: deliverFrames,
           onDecoded: (decoded) => callbacksRef.current.onDecoded?.(decoded),
           onBytes: (payload) => callbacksRef.current.onBytes?.(payload),
           onError: (error) => callbacksRef.current.onError?.(error),
@@ -732,6 +775,7 @@ export function useIOSession(
           onResuming: (payload) => callbacksRef.current.onResuming?.(payload),
           onSourceReplaced: (payload) => callbacksRef.current.onSourceReplaced?.(payload),
         });
+        applyRateLimit(effectiveSessionId);
         tlog.debug(`[useIOSession:${appName}] registerCallbacks completed`);
 
         // Initialize local state after session is created/joined
@@ -845,6 +889,7 @@ export function useIOSession(
     registerCallbacks,
     clearCallbacks,
     leaveSession,
+    deliverFrames,
   ]);
 
   // Action wrappers - all use effectiveSessionId directly
@@ -1112,7 +1157,7 @@ export function useIOSession(
 
         // Re-register callbacks after reinitialize
         registerCallbacks(targetSessionId, subscriberIdRef.current, {
-          onFrames: (frames) => callbacksRef.current.onFrames?.(frames),
+          onFrames: deliverFrames,
           onDecoded: (decoded) => callbacksRef.current.onDecoded?.(decoded),
           onBytes: (payload) => callbacksRef.current.onBytes?.(payload),
           onError: (error) => callbacksRef.current.onError?.(error),
@@ -1126,6 +1171,7 @@ export function useIOSession(
           onResuming: (payload) => callbacksRef.current.onResuming?.(payload),
           onSourceReplaced: (payload) => callbacksRef.current.onSourceReplaced?.(payload),
         });
+        applyRateLimit(targetSessionId);
 
         // Update local state after reinitialize
         try {
@@ -1178,7 +1224,7 @@ export function useIOSession(
         }, REINITIALIZE_GRACE_PERIOD_MS + 50);
       }
     },
-    [appName, effectiveSessionId, effectiveProfileName, reinitializeSession, registerCallbacks, clearCallbacks, leaveSession]
+    [appName, effectiveSessionId, effectiveProfileName, reinitializeSession, registerCallbacks, clearCallbacks, leaveSession, deliverFrames, applyRateLimit]
   );
 
   const switchToCaptureReplay = useCallback(
@@ -1217,7 +1263,7 @@ export function useIOSession(
 
       // Re-register callbacks
       registerCallbacks(targetSessionId, subscriberIdRef.current, {
-        onFrames: (frames) => callbacksRef.current.onFrames?.(frames),
+        onFrames: deliverFrames,
         onDecoded: (decoded) => callbacksRef.current.onDecoded?.(decoded),
         onBytes: (payload) => callbacksRef.current.onBytes?.(payload),
         onError: (error) => callbacksRef.current.onError?.(error),
@@ -1231,11 +1277,12 @@ export function useIOSession(
         onResuming: (payload) => callbacksRef.current.onResuming?.(payload),
         onSourceReplaced: (payload) => callbacksRef.current.onSourceReplaced?.(payload),
       });
+      applyRateLimit(targetSessionId);
     } catch (e) {
       const msg = e instanceof Error ? e.message : String(e);
       callbacksRef.current.onError?.(msg);
     }
-  }, [effectiveSessionId, effectiveProfileName, openSession, registerCallbacks]);
+  }, [effectiveSessionId, effectiveProfileName, openSession, registerCallbacks, deliverFrames, applyRateLimit]);
 
   const transmitFrame = useCallback(
     async (frame: CanTransmitFrame): Promise<TransmitResult> => {
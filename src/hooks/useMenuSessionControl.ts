@@ -10,6 +10,7 @@ import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { useFocusStore } from "../stores/focusStore";
 import {
   updateMenuState,
+  syncMenuState,
   updateBookmarksMenu,
   type BookmarkMenuInfo,
 } from "../api/menu";
@@ -34,6 +35,12 @@ export interface SessionControlCallbacks {
 
 /** Session state values reported to the native menu for enable/disable logic. */
 export interface MenuReportState {
+  /**
+   * Session this panel is attached to. When set, the menu state is computed
+   * backend-side from the session and kept current there; the fields below
+   * are only reported when there is no session.
+   */
+  sessionId?: string | null;
   profileName: string | null;
   isStreaming: boolean;
   isPaused: boolean;
@@ -94,6 +101,11 @@ export function useMenuSessionControl({
       (capabilities?.traits.temporal_mode === "recorded" ||
         capabilities?.traits.temporal_mode === "capture");
 
+    if (sessionState.sessionId) {
+      syncMenuState(sessionState.sessionId, bookmarksEnabled);
+      return;
+    }
+
     updateMenuState({
       hasSession: true,
       profileName: effectiveProfileName,
@@ -105,6 +117,7 @@ export function useMenuSessionControl({
     });
   }, [
     isFocused,
+    sessionState.sessionId,
     sessionState.profileName,
     sessionState.isStreaming,
     sessionState.isPaused,
@@ -9,6 +9,7 @@ import { getTraitsForKind, getProfileTraits, type Protocol } from '../utils/prof
 import {
   DEFAULT_BUFFER_STORAGE,
   DEFAULT_CLEAR_BUFFERS_ON_START,
+  DEFAULT_CAPTURE_DB_CACHE_MB,
   DEFAULT_DISCOVERY_HISTORY_BUFFER,
   DEFAULT_QUERY_RESULT_LIMIT,
   DEFAULT_DECODER_MAX_UNMATCHED_FRAMES,
@@ -23,7 +24,7 @@ import {
 // Profile Kind Type
 // ============================================================================
 
-export type ProfileKindId = 'mqtt' | 'postgres' | 'wiretap' | 'gvret_tcp' | 'gvret_usb' | 'serial' | 'slcan' | 'socketcan' | 'gs_usb' | 'modbus_tcp' | 'virtual' | 'framelink';
+export type ProfileKindId = 'mqtt' | 'postgres' | 'wiretap' | 'gvret_tcp' | 'gvret_usb' | 'serial' | 'slcan' | 'custom_serial' | 'elm327' | 'lin' | 'canserver' | 'websocket' | 'udp_can' | 'doip' | 'wiretap_net' | 'socketcan' | 'gs_usb' | 'pcan' | 'kvaser' | 'vector_xl' | 'j2534' | 'modbus_tcp' | 'virtual' | 'framelink';
 
 // ============================================================================
 // Connection Interfaces (per profile kind)
@@ -35,6 +36,18 @@ export interface MqttConnection {
   username?: string;
   password?: string;
   _password_stored?: boolean;
+  /** Connect over TLS */
+  tls?: boolean;
+  /** PEM CA certificate(s); system roots when empty */
+  tls_ca_cert?: string;
+  /** PEM client certificate and key for mutual TLS (kept in the keyring) */
+  tls_client_cert?: string;
+  _tls_client_cert_stored?: boolean;
+  tls_client_key?: string;
+  _tls_client_key_stored?: boolean;
+  /** Comma-separated ALPN protocols */
+  tls_alpn?: string;
+  tls_insecure_skip_verify?: boolean;
   formats?: {
     json?: { enabled: boolean; topic: string };
     savvycan?: { enabled: boolean; topic: string };
@@ -80,6 +93,105 @@ export interface GvretTcpConnection {
   _probed_bus_count?: number;
 }
 
+export interface CanServerConnection {
+  host?: string;
+  /** UDP port (default 1338) */
+  port?: string;
+  /** Seconds of silence before the source logs that no data is arriving */
+  timeout?: string;
+  heartbeat_ms?: string;
+  interfaces?: GvretInterfaceConfig[];
+  _probed_bus_count?: number;
+}
+
+/** Frame field a WebSocket mapping fills */
+export type WebSocketFieldRole = 'frame_id' | 'data' | 'bus' | 'dlc' | 'extended' | 'fd' | 'timestamp';
+
+/** Where one frame field comes from in a WebSocket message */
+export interface WebSocketFieldMapping {
+  role: WebSocketFieldRole;
+  /** JSON messages: dotted key path ("id", "frame.data") */
+  path?: string;
+  /** Binary messages: byte offset within the record */
+  offset?: number;
+  /** Binary messages: width in bytes */
+  length?: number;
+  /** Bits of the value to keep */
+  mask?: number;
+}
+
+export interface WebSocketConnection {
+  /** ws:// or wss:// endpoint */
+  url?: string;
+  format?: 'json' | 'binary';
+  /** Empty for JSON means the SavvyCAN keys */
+  mapping?: WebSocketFieldMapping[];
+  /** JSON key path of the frame array within a message */
+  frames_path?: string;
+  /** Binary record size when a message packs several frames */
+  record_size?: string;
+  big_endian?: boolean;
+  timestamp_unit?: 'seconds' | 'milliseconds' | 'microseconds' | 'nanoseconds';
+  /** Text message sent after connecting */
+  subscribe?: string;
+  /** Seconds of silence before the source logs that no data is arriving */
+  timeout?: string;
+  interfaces?: GvretInterfaceConfig[];
+  _probed_bus_count?: number;
+}
+
+export interface UdpCanConnection {
+  /** UDP port to listen on */
+  port?: string;
+  /** Local address to bind (default 0.0.0.0) */
+  bind?: string;
+  /** IPv4 multicast group to join */
+  multicast_group?: string;
+  /** Only accept datagrams from this host */
+  sender?: string;
+  format?: 'binary' | 'candump';
+  /** Binary layout byte offsets; an empty dlc_offset means the data runs to the end of the record */
+  id_offset?: string;
+  /** Width of the frame ID in bytes (default 4) */
+  id_bytes?: string;
+  dlc_offset?: string;
+  data_offset?: string;
+  bus_offset?: string;
+  /** Record size when a datagram packs several frames */
+  record_size?: string;
+  big_endian?: boolean;
+  /** Seconds of silence before the source logs that no data is arriving */
+  timeout?: string;
+  interfaces?: GvretInterfaceConfig[];
+  _probed_bus_count?: number;
+}
+
+export interface DoipConnection {
+  /** Vehicle gateway address */
+  host?: string;
+  /** Gateway TCP port (default 13400) */
+  port?: string;
+  /** Logical address of this tester, decimal or 0x hex (default 0x0E00) */
+  tester_address?: string;
+  /** Default ECU logical address; empty sends to the gateway itself */
+  target_address?: string;
+  /** Routing activation type (0 = default, 1 = WWH-OBD) */
+  activation_type?: string;
+  /** DoIP protocol version byte (default 2) */
+  protocol_version?: string;
+  /** Connect and routing activation timeout in seconds */
+  timeout?: string;
+}
+
+export interface WiretapNetConnection {
+  /** Machine running the WireTAP network publisher */
+  host?: string;
+  /** Publisher TCP port (default 1339) */
+  port?: string;
+  /** Connect timeout, and seconds of silence before a no-data warning */
+  timeout?: string;
+}
+
 export interface GvretUsbConnection {
   port?: string;
   baud_rate?: string;
@@ -115,6 +227,54 @@ export interface SlcanConnection {
   data_bitrate?: string;
 }
 
+export interface Elm327Connection {
+  port?: string;
+  baud_rate?: string;
+  /** monitor = ATMA (all traffic); poll = send the OBD requests below */
+  mode?: "monitor" | "poll";
+  /** ATSP protocol number ("0" = automatic) */
+  protocol?: string;
+  /** OBD requests as hex, e.g. "010C" */
+  pids?: string[];
+  poll_interval_ms?: string;
+  /** ATSH request header for polling (default 7DF) */
+  request_header?: string;
+}
+
+export interface LinConnection {
+  port?: string;
+  baud_rate?: string;
+  /** monitor = listen only; master = send headers from a schedule table */
+  mode?: "monitor" | "master";
+  /** Schedule table name from the attached catalogue's [lin] section */
+  schedule?: string;
+  /** auto follows the catalogue (enhanced for IDs below 0x3C) */
+  checksum?: "auto" | "classic" | "enhanced";
+}
+
+export interface CustomSerialConnection {
+  port?: string;
+  baud_rate?: string;
+  data_bits?: string;
+  stop_bits?: string;
+  parity?: string;
+  /** Sync bytes that start every frame */
+  header?: number[];
+  /** Frame ID width in bytes (1-4) */
+  id_bytes?: string;
+  id_big_endian?: boolean;
+  /** A length byte follows the frame ID */
+  length_byte?: boolean;
+  /** Data length when there is no length byte */
+  fixed_length?: string;
+  checksum?: "none" | "sum8" | "xor8" | "crc8";
+  checksum_includes_header?: boolean;
+  /** Trailing bytes after the checksum */
+  footer?: number[];
+  /** Receive only (no transmit) */
+  listen_only?: boolean;
+}
+
 export interface SocketcanConnection {
   interface?: string;
   bitrate?: string;
@@ -135,12 +295,58 @@ export interface GsUsbConnection {
   enable_fd?: boolean;
   data_bitrate?: string;
   data_sample_point?: string;
+  /** Linux: detach the kernel driver and drive the device directly */
+  direct_usb?: boolean;
+}
+
+export interface PcanConnection {
+  /** 1-based PCAN_USBBUS channel */
+  channel?: string;
+  /** User-assigned device ID; when set, takes precedence over channel */
+  device_id?: string;
+  bitrate?: string;
+  listen_only?: boolean;
+}
+
+export interface KvaserConnection {
+  /** Card serial number; when set, takes precedence over channel */
+  serial?: string;
+  /** CANlib channel number of the card's first channel */
+  channel?: string;
+  bitrate?: string;
+  listen_only?: boolean;
+  /** One entry per channel on the card (device_bus = channel on card) */
+  interfaces?: GvretInterfaceConfig[];
+}
+
+export interface VectorXlConnection {
+  /** Interface serial number; when set, takes precedence over channel */
+  serial?: string;
+  /** Global XL channel index of the interface's first channel */
+  channel?: string;
+  bitrate?: string;
+  enable_fd?: boolean;
+  data_bitrate?: string;
+  listen_only?: boolean;
+  /** One entry per CAN channel (device_bus = channel on the interface) */
+  interfaces?: GvretInterfaceConfig[];
+}
+
+export interface J2534Connection {
+  /** Registered PassThru device name */
+  device?: string;
+  /** Vendor PassThru DLL; looked up from the registry when empty */
+  library_path?: string;
+  bitrate?: string;
+  listen_only?: boolean;
 }
 
 export interface ModbusTcpConnection {
   host?: string;
   port?: string;
   unit_id?: string;
+  /** client (default) polls a device; simulator and server listen on host:port */
+  role?: "client" | "simulator" | "server";
 }
 
 export interface FrameLinkInterfaceConfig {
@@ -192,8 +398,20 @@ export interface ConnectionTypeMap {
   gvret_usb: GvretUsbConnection;
   serial: SerialConnection;
   slcan: SlcanConnection;
+  custom_serial: CustomSerialConnection;
+  elm327: Elm327Connection;
+  lin: LinConnection;
+  canserver: CanServerConnection;
+  websocket: WebSocketConnection;
+  udp_can: UdpCanConnection;
+  doip: DoipConnection;
+  wiretap_net: WiretapNetConnection;
   socketcan: SocketcanConnection;
   gs_usb: GsUsbConnection;
+  pcan: PcanConnection;
+  kvaser: KvaserConnection;
+  vector_xl: VectorXlConnection;
+  j2534: J2534Connection;
   modbus_tcp: ModbusTcpConnection;
   virtual: VirtualConnection;
   framelink: FrameLinkConnection;
@@ -237,7 +455,9 @@ export type ConnectionFieldValue =
   | GvretInterfaceConfig[]
   | FrameLinkInterfaceConfig[]
   | VirtualInterfaceConfig[]
+  | WebSocketFieldMapping[]
   | string[]
+  | number[]
   | { start_byte: number; num_bytes: number; big_endian: boolean }
   | MqttConnection['formats'];
 
@@ -337,6 +557,8 @@ export interface AppSettings {
   clear_captures_on_start?: boolean;
   /** Buffer storage backend ("sqlite" is the only option for now) */
   buffer_storage?: string;
+  /** SQLite page cache / mmap size for the whole capture database in MB, shared by all captures (applied at launch) */
+  capture_db_cache_mb?: number;
   // Decoder buffer limits
   decoder_max_unmatched_frames?: number;
   decoder_max_filtered_frames?: number;
@@ -344,6 +566,11 @@ export interface AppSettings {
   decoder_max_decoded_per_source?: number;
   // Transmit limits
   transmit_max_history?: number;
+  // Frame batching (merge task); per-session overrides via setSessionBatching
+  batch_max_frames?: number;
+  batch_max_latency_ms?: number;
+  /** Emit bus-silent after an enabled bus is quiet this long (ms, 0 = off, default 2000) */
+  bus_silence_timeout_ms?: number;
   // Modbus settings
   modbus_max_register_errors?: number;
   /** SMP UDP port for firmware upgrades (default 1337) */
@@ -369,6 +596,12 @@ export interface AppSettings {
   mcp_server_port?: number;
   /** Bearer token required by clients (empty = no auth). */
   mcp_server_token?: string;
+  /** Remote control REST/WebSocket API for test automation (off by default). */
+  remote_api_enabled?: boolean;
+  /** Localhost port the remote API listens on (default 8788). */
+  remote_api_port?: number;
+  /** Bearer token required by the remote API (it won't start without one). */
+  remote_api_token?: string;
 }
 
 /**
@@ -425,6 +658,7 @@ function normalizeSettings(
     // Buffer persistence
     clear_captures_on_start: settings.clear_captures_on_start ?? DEFAULT_CLEAR_BUFFERS_ON_START,
     buffer_storage: settings.buffer_storage ?? DEFAULT_BUFFER_STORAGE,
+    capture_db_cache_mb: settings.capture_db_cache_mb ?? DEFAULT_CAPTURE_DB_CACHE_MB,
     // Decoder buffer limits
     decoder_max_unmatched_frames: settings.decoder_max_unmatched_frames ?? DEFAULT_DECODER_MAX_UNMATCHED_FRAMES,
     decoder_max_filtered_frames: settings.decoder_max_filtered_frames ?? DEFAULT_DECODER_MAX_FILTERED_FRAMES,
@@ -446,6 +680,9 @@ function normalizeSettings(
     mcp_allow_ui_control: settings.mcp_allow_ui_control ?? false,
     mcp_server_port: settings.mcp_server_port ?? 8787,
     mcp_server_token: settings.mcp_server_token ?? "",
+    remote_api_enabled: settings.remote_api_enabled ?? false,
+    remote_api_port: settings.remote_api_port ?? 8788,
+    remote_api_token: settings.remote_api_token ?? "",
   };
 }
 
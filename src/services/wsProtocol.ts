@@ -39,6 +39,9 @@ export const MsgType = {
   FrameCounts: 0x16,
   OpenAppsChanged: 0x17,
   CatalogListChanged: 0x18,
+  LatestValuesChanged: 0x19,
+  FrameAck: 0x1a,
+  AddressClaimChanged: 0x1b,
   Command: 0x20,
   CommandResponse: 0x21,
   BridgeRequest: 0x30,
@@ -124,6 +127,11 @@ export function encodeHeartbeat(): ArrayBuffer {
   return buildHeader(MsgType.Heartbeat, 0).buffer;
 }
 
+/** Acknowledge a handled FrameData batch so the backend can apply backpressure. */
+export function encodeFrameAck(channel: number): ArrayBuffer {
+  return buildHeader(MsgType.FrameAck, channel).buffer;
+}
+
 // ============================================================================
 // Frame batch decoder
 // ============================================================================
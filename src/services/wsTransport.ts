@@ -17,6 +17,7 @@ import {
   encodeSubscribe,
   encodeUnsubscribe,
   encodeHeartbeat,
+  encodeFrameAck,
   encodeCommand,
   encodeBridgeResponse,
   MsgType,
@@ -348,6 +349,10 @@ class WsTransport {
           for (const h of handlers) h(payload, buf);
         }
       }
+      // Ack once handled: the backend coalesces batches while acks lag
+      if (header.msgType === MsgType.FrameData) {
+        this.ws?.send(encodeFrameAck(header.channel));
+      }
     }
   }
 
@@ -7,6 +7,7 @@
 import { create } from "zustand";
 import {
   type CanTransmitFrame,
+  type ModbusWriteFunction,
   type TransmitProfile,
   type TransmitResult,
   type ReplayFrame,
@@ -153,6 +154,18 @@ export interface SerialEditorState {
   delimiter: number[];
 }
 
+/** Modbus write editor state */
+export interface ModbusEditorState {
+  /** Write function code */
+  functionCode: ModbusWriteFunction;
+  /** Device (slave) address input */
+  unitId: string;
+  /** Start address input (decimal or 0x hex) */
+  address: string;
+  /** Values input: registers as decimal or 0x hex, coils as 0/1 */
+  valuesInput: string;
+}
+
 
 // ============================================================================
 // Store
@@ -184,6 +197,9 @@ export interface TransmitState {
   // ---- Serial Editor ----
   serialEditor: SerialEditorState;
 
+  // ---- Modbus Editor ----
+  modbusEditor: ModbusEditorState;
+
   // ---- Queue Editor ----
   /** Repeat interval for new queue items */
   queueRepeatIntervalMs: number;
@@ -216,6 +232,12 @@ export interface TransmitState {
   /** Parse hex input to bytes */
   parseSerialBytes: () => number[];
 
+  // Modbus Editor Actions
+  /** Update Modbus editor field */
+  updateModbusEditor: (updates: Partial<ModbusEditorState>) => void;
+  /** Reset Modbus editor to defaults */
+  resetModbusEditor: () => void;
+
   // Queue Actions
   /** Add current CAN frame to queue */
   addCanToQueue: () => void;
@@ -308,6 +330,13 @@ const DEFAULT_SERIAL_EDITOR: SerialEditorState = {
   delimiter: [0x0d, 0x0a], // CRLF default
 };
 
+const DEFAULT_MODBUS_EDITOR: ModbusEditorState = {
+  functionCode: 6,
+  unitId: "1",
+  address: "0",
+  valuesInput: "",
+};
+
 /** Helper to get the active session from sessionStore */
 const getActiveSession = () => {
   const { activeSessionId, sessions } = useSessionStore.getState();
@@ -329,6 +358,7 @@ export const useTransmitStore = create<TransmitState>((set, get) => ({
   error: null,
   canEditor: { ...DEFAULT_CAN_EDITOR },
   serialEditor: { ...DEFAULT_SERIAL_EDITOR },
+  modbusEditor: { ...DEFAULT_MODBUS_EDITOR },
   queueRepeatIntervalMs: 1000,
 
   // ---- Actions ----
@@ -464,6 +494,15 @@ export const useTransmitStore = create<TransmitState>((set, get) => ({
 
   resetSerialEditor: () => set({ serialEditor: { ...DEFAULT_SERIAL_EDITOR } }),
 
+  // Modbus Editor Actions
+  updateModbusEditor: (updates) => {
+    set((state) => ({
+      modbusEditor: { ...state.modbusEditor, ...updates },
+    }));
+  },
+
+  resetModbusEditor: () => set({ modbusEditor: { ...DEFAULT_MODBUS_EDITOR } }),
+
   parseSerialBytes: () => {
     const state = get();
     const hex = state.serialEditor.hexInput.replace(/\s/g, "");
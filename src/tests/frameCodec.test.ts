@@ -0,0 +1,57 @@
+import { describe, it, expect } from "vitest";
+import { decodeFramePage, isFramePage } from "../utils/frameCodec";
+
+// Little-endian builders for the CODEC_FRAMES layout
+const u16 = (n: number) => [n & 0xff, (n >> 8) & 0xff];
+const u32 = (n: number) => [...u16(n & 0xffff), ...u16(Math.floor(n / 0x10000))];
+const u64 = (n: number) => [...u32(n % 0x100000000), ...u32(Math.floor(n / 0x100000000))];
+
+function page(protocols: string[], records: number[][]): Uint8Array {
+  const table = protocols.flatMap((p) => [p.length, ...new TextEncoder().encode(p)]);
+  return Uint8Array.from([
+    2, 1, ...u32(500), ...u32(20), ...u32(50), ...u32(records.length),
+    protocols.length, ...table, ...records.flat(),
+  ]);
+}
+
+describe("binary frame pages", () => {
+  it("decodes header fields and a plain CAN frame", () => {
+    const body = page(["can"], [
+      [...u64(1_700_000_000_000_000), ...u64(21), ...u32(0x123), 1, 2, 0b0000_0001, 0, ...u16(0), ...u16(2), 0xde, 0xad],
+    ]);
+    expect(isFramePage(body)).toBe(true);
+    const decoded = decodeFramePage(body);
+    expect([decoded.total_count, decoded.offset, decoded.limit]).toEqual([500, 20, 50]);
+    expect(decoded.capture_indices).toEqual([21]);
+    expect(decoded.frames[0]).toEqual({
+      protocol: "can",
+      timestamp_us: 1_700_000_000_000_000,
+      frame_id: 0x123,
+      bus: 1,
+      dlc: 2,
+      bytes: [0xde, 0xad],
+      is_extended: true,
+      is_fd: false,
+    });
+  });
+
+  it("reads optional fields and link blocks", () => {
+    const flags = (1 << 3) | (1 << 5) | (1 << 6) | (1 << 7);
+    const body = page(["can", "isotp"], [
+      [...u64(5), ...u64(7), ...u32(0x7e0), 0, 8, flags, 1, ...u16(0xf1), ...u16(1), ...u64(9), 2, ...u64(3), 0x22],
+    ]);
+    const frame = decodeFramePage(body).frames[0];
+    expect(frame.protocol).toBe("isotp");
+    expect(frame.source_address).toBe(0xf1);
+    expect(frame.direction).toBe("tx");
+    expect(frame.link).toEqual({ correlation_id: 9, role: "request", parent_rowid: 3 });
+    expect(frame.bytes).toEqual([0x22]);
+    expect("incomplete" in frame).toBe(false);
+  });
+
+  it("rejects truncated pages and other codecs", () => {
+    const body = page(["can"], [[...u64(0), ...u64(1), ...u32(1), 0, 8, 0, 0, ...u16(0), ...u16(8), 1, 2]]);
+    expect(() => decodeFramePage(body)).toThrow(/Truncated/);
+    expect(isFramePage(Uint8Array.from([0, 123]))).toBe(false);
+  });
+});
@@ -0,0 +1,28 @@
+import { describe, it, expect } from "vitest";
+import { frameProtocol, filterFramesByProtocol } from "../utils/frameProtocol";
+
+describe("frameProtocol", () => {
+  it("maps wire tags to protocol families", () => {
+    expect(frameProtocol({ protocol: "can" })).toBe("can");
+    expect(frameProtocol({ protocol: "can", is_fd: true })).toBe("canfd");
+    expect(frameProtocol({ protocol: "canfd" })).toBe("canfd");
+    expect(frameProtocol({ protocol: "modbus" })).toBe("modbus");
+    expect(frameProtocol({ protocol: "rs485" })).toBe("serial");
+  });
+
+  it("filters a mixed batch", () => {
+    const frames = [
+      { protocol: "can", id: 1 },
+      { protocol: "modbus", id: 2 },
+      { protocol: "can", is_fd: true, id: 3 },
+    ];
+    expect(filterFramesByProtocol(frames, ["modbus"]).map((f) => f.id)).toEqual([2]);
+    expect(filterFramesByProtocol(frames, ["can", "canfd"]).map((f) => f.id)).toEqual([1, 3]);
+  });
+
+  it("passes everything through without a filter", () => {
+    const frames = [{ protocol: "can" }, { protocol: "serial" }];
+    expect(filterFramesByProtocol(frames)).toBe(frames);
+    expect(filterFramesByProtocol(frames, [])).toBe(frames);
+  });
+});
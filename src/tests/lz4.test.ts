@@ -0,0 +1,45 @@
+import { describe, it, expect } from "vitest";
+import { lz4DecompressBlock, unpackIpcBody, unpackIpcJson } from "../utils/lz4";
+
+const ascii = (s: string) => new TextEncoder().encode(s);
+const text = (b: Uint8Array) => new TextDecoder().decode(b);
+
+describe("lz4 block decoder", () => {
+  it("copies a literal-only block", () => {
+    const block = Uint8Array.from([0x50, ...ascii("hello")]);
+    expect(text(lz4DecompressBlock(block, 5))).toBe("hello");
+  });
+
+  it("expands overlapping matches", () => {
+    // "abc" literals, then a 9-byte match at offset 3, then an empty last sequence
+    const block = Uint8Array.from([0x35, ...ascii("abc"), 0x03, 0x00, 0x00]);
+    expect(text(lz4DecompressBlock(block, 12))).toBe("abcabcabcabc");
+  });
+
+  it("reads extended literal lengths", () => {
+    const literals = "x".repeat(20);
+    const block = Uint8Array.from([0xf0, 20 - 15, ...ascii(literals)]);
+    expect(text(lz4DecompressBlock(block, 20))).toBe(literals);
+  });
+
+  it("rejects offsets before the start of output", () => {
+    const block = Uint8Array.from([0x10, 0x61, 0x05, 0x00, 0x00]);
+    expect(() => lz4DecompressBlock(block, 6)).toThrow(/offset/);
+  });
+});
+
+describe("packed IPC bodies", () => {
+  it("passes raw bodies through", () => {
+    const body = Uint8Array.from([0, ...ascii('{"total_count":3}')]);
+    expect(unpackIpcJson<{ total_count: number }>(body).total_count).toBe(3);
+  });
+
+  it("inflates LZ4 bodies using the length prefix", () => {
+    const body = Uint8Array.from([1, 12, 0, 0, 0, 0x35, ...ascii("abc"), 0x03, 0x00, 0x00]);
+    expect(text(unpackIpcBody(body.buffer))).toBe("abcabcabcabc");
+  });
+
+  it("rejects unknown codecs", () => {
+    expect(() => unpackIpcBody(Uint8Array.from([9, 1, 2]))).toThrow(/codec/);
+  });
+});
@@ -0,0 +1,481 @@
+// src/types/backend.d.ts
+//
+// GENERATED by src-tauri/src/ts_bindings.rs from the Rust types — do not edit.
+// Regenerate with `cargo test ts_bindings` in src-tauri.
+
+export type FrameMessage = { protocol: string,
+/**
+ * UNIX timestamp in microseconds (host clock unless `clock` says otherwise).
+ */
+timestamp_us: number, frame_id: number, bus: number, dlc: number, bytes: Array<number>, is_extended: boolean, is_fd: boolean,
+/**
+ * Source address (for protocols like J1939, TWC that embed sender ID in frame)
+ */
+source_address?: number,
+/**
+ * Indicates incomplete frame (e.g., no delimiter found at end of stream)
+ */
+incomplete?: boolean,
+/**
+ * Direction: "rx" for received, "tx" for transmitted
+ */
+direction?: string,
+/**
+ * Relationship to other frames (reassembly segment, UDS request/response, transmit echo)
+ */
+link?: FrameLink,
+/**
+ * Clock `timestamp_us` was taken from, where the driver records it
+ */
+clock?: FrameClock,
+/**
+ * CAN error frame. `frame_id` is then the SocketCAN error class bits with
+ * CAN_ERR_FLAG set (outside any 29-bit ID) and `bytes` the error details.
+ */
+is_error?: boolean,
+/**
+ * Main class of an error frame
+ */
+error_class?: CanErrorClass,
+/**
+ * CAN FD bit rate switch: the data phase ran at the data bitrate. Set
+ * on FD frames by drivers that report it.
+ */
+brs?: boolean, };
+
+export type FrameLink = { correlation_id: number, role: LinkRole, parent_rowid?: number, };
+
+/**
+ * Role a frame plays within a group of related frames.
+ */
+export type LinkRole = "segment" | "message" | "request" | "response" | "echo";
+
+/**
+ * Clock a frame's timestamp came from.
+ */
+export type FrameClock = "host" | "hardware";
+
+/**
+ * Main class of a CAN error frame. A frame reporting several conditions
+ * takes the most severe, in the order listed.
+ */
+export type CanErrorClass = "bus_off" | "ack" | "stuff" | "form" | "bit" | "crc" | "error_passive" | "error_warning" | "overflow" | "arbitration_lost" | "tx_timeout" | "transceiver" | "restarted" | "other";
+
+/**
+ * Current state of an IO session
+ */
+export type IOState = { "type": "Stopped" } | { "type": "Starting" } | { "type": "Running" } | { "type": "Paused" } | { "type": "Error", "message": string };
+
+/**
+ * Temporal mode of an interface/session
+ */
+export type TemporalMode = "realtime" | "recorded" | "capture";
+
+/**
+ * Protocol family for frame-based communication
+ */
+export type Protocol = "can" | "canfd" | "modbus" | "serial";
+
+/**
+ * One protocol's sub-stream within a mixed-protocol session: which output
+ * buses carry frames of that protocol.
+ */
+export type ProtocolStream = { protocol: Protocol,
+/**
+ * Output bus numbers (sorted)
+ */
+buses: Array<number>, };
+
+/**
+ * Combined interface traits for formal session/interface characterization
+ */
+export type InterfaceTraits = {
+/**
+ * Temporal mode of the interface
+ */
+temporal_mode: TemporalMode,
+/**
+ * Protocols supported by the interface
+ */
+protocols: Array<Protocol>,
+/**
+ * Whether the interface can transmit frames (CAN, Modbus, framed serial)
+ */
+tx_frames: boolean,
+/**
+ * Whether the interface can transmit raw bytes (serial)
+ */
+tx_bytes: boolean,
+/**
+ * Whether this source can be combined with others in a multi-source session
+ */
+multi_source: boolean, };
+
+export type SessionDataStreams = {
+/**
+ * Whether this session emits framed messages (`frame-message` events)
+ */
+rx_frames: boolean,
+/**
+ * Whether this session emits raw byte streams (`bytes-ready` signal)
+ */
+rx_bytes: boolean,
+/**
+ * Per-protocol frame sub-streams for multi-source sessions mixing
+ * protocols (e.g. CAN plus RS-485 body modules). Empty when every frame
+ * shares `traits.protocols`.
+ */
+sub_streams: Array<ProtocolStream>, };
+
+/**
+ * IO device capabilities - what this device type supports
+ */
+export type IOCapabilities = {
+/**
+ * Supports pause/resume (PostgreSQL: true, GVRET: false)
+ */
+can_pause: boolean,
+/**
+ * Supports time range filtering (PostgreSQL: true, GVRET: false)
+ */
+supports_time_range: boolean,
+/**
+ * Supports speed control (PostgreSQL: true, GVRET: false)
+ */
+supports_speed_control: boolean,
+/**
+ * Supports seeking to a specific timestamp (Buffer: true, others: false)
+ */
+supports_seek: boolean,
+/**
+ * Supports reverse playback (Buffer: true, others: false)
+ */
+supports_reverse: boolean,
+/**
+ * Supports extended (29-bit) CAN IDs
+ */
+supports_extended_id: boolean,
+/**
+ * Supports Remote Transmission Request frames
+ */
+supports_rtr: boolean,
+/**
+ * Available bus numbers (empty = single bus, [0,1,2] = multi-bus like GVRET)
+ */
+available_buses: Array<number>,
+/**
+ * Interface traits (temporal mode, protocols, transmit capability)
+ */
+traits: InterfaceTraits,
+/**
+ * Declares which data streams this session produces (frames, bytes, or both)
+ */
+data_streams: SessionDataStreams, };
+
+/**
+ * Info about a registered subscriber (for TypeScript)
+ */
+export type SubscriberInfo = { subscriber_id: string,
+/**
+ * Human-readable app name (e.g., "discovery", "decoder")
+ */
+app_name: string,
+/**
+ * Seconds since registration
+ */
+registered_seconds_ago: number,
+/**
+ * Whether this subscriber is actively receiving frames
+ */
+is_active: boolean, };
+
+/**
+ * Info about an active session (for listing)
+ */
+export type ActiveSessionInfo = {
+/**
+ * Session ID
+ */
+session_id: string,
+/**
+ * Device type (e.g., "gvret_tcp", "realtime")
+ */
+source_type: string,
+/**
+ * Current state
+ */
+state: IOState,
+/**
+ * Session capabilities
+ */
+capabilities: IOCapabilities,
+/**
+ * Number of subscribers
+ */
+subscriber_count: number,
+/**
+ * Individual subscriber details
+ */
+subscribers: Array<SubscriberInfo>,
+/**
+ * For multi-source sessions: the source configurations
+ */
+broker_configs: Array<Record<string, unknown>> | null,
+/**
+ * Profile IDs feeding this session (populated from SESSION_PROFILES in sessions.rs)
+ */
+source_profile_ids: Array<string>,
+/**
+ * Capture ID owned by this session (if any)
+ */
+capture_id: string | null,
+/**
+ * Frame count in the owned capture
+ */
+capture_frame_count: number | null,
+/**
+ * Distinct (bus, frame_id) count in the owned capture (live streaming only)
+ */
+capture_unique_frame_count: number | null,
+/**
+ * Whether the session is actively streaming data
+ */
+is_streaming: boolean,
+/**
+ * Source file path of the catalogue attached for live decode (None when no
+ * decoder is bound). Authoritative — the frontend mirrors this one-way.
+ */
+catalog_path: string | null, };
+
+/**
+ * Payload for global session lifecycle events (emitted to all windows)
+ */
+export type SessionLifecyclePayload = {
+/**
+ * The session ID
+ */
+session_id: string,
+/**
+ * Event type: "created" or "destroyed"
+ */
+event_type: string,
+/**
+ * Device type (e.g., "gvret_tcp", "realtime") - only for "created"
+ */
+source_type: string | null,
+/**
+ * Current state - only for "created"
+ */
+state: string | null,
+/**
+ * Number of listeners
+ */
+subscriber_count: number,
+/**
+ * Source profile IDs
+ */
+source_profile_ids: Array<string>,
+/**
+ * The subscriber ID that created the session (only for "created")
+ */
+creator_subscriber_id: string | null,
+/**
+ * True when a "destroyed" event was a deliberate user destroy (the app should
+ * reset to "No source" rather than fall back to the orphaned capture).
+ */
+reset: boolean, };
+
+/**
+ * Playback position - stored and signalled via playback-position events during capture streaming
+ */
+export type PlaybackPosition = {
+/**
+ * Current timestamp in microseconds
+ */
+timestamp_us: number,
+/**
+ * Current frame index (0-based)
+ */
+frame_index: number,
+/**
+ * Total frame count in capture (optional, for recorded sources)
+ */
+frame_count?: number, };
+
+/**
+ * Stream-ended info, persisted after session destruction for late-arriving fetches.
+ */
+export type StreamEndedInfo = { reason: string, capture_available: boolean, capture_id: string | null, capture_kind: string | null, count: number, time_range: [number, number] | null, };
+
+/**
+ * Connected source info for a session.
+ */
+export type SourceInfo = { source_type: string, address: string, bus: number | null, };
+
+/**
+ * A note or marker pinned inside a capture ("door opened here").
+ */
+export type CaptureAnnotation = {
+id: number,
+capture_id: string,
+/**
+ * Time the annotation marks (the pinned frame's timestamp, if any)
+ */
+timestamp_us: number,
+/**
+ * Current 0-based index of the pinned frame (None when pinned to a
+ * time, or the frame has been trimmed away or the capture copied)
+ */
+frame_index: number | null,
+text: string,
+/**
+ * CSS colour for the marker (None = default)
+ */
+color: string | null,
+/**
+ * When the annotation was made (Unix timestamp in seconds)
+ */
+created_at: number, };
+
+/**
+ * Capture kind - determines what kind of data the capture contains
+ */
+export type CaptureKind = "frames" | "bytes";
+
+/**
+ * Metadata about a capture
+ */
+export type CaptureMetadata = {
+/**
+ * Unique capture ID (e.g., "xk9m2p", "r7f3kw")
+ */
+id: string,
+/**
+ * Capture kind (frames or bytes)
+ */
+kind: CaptureKind,
+/**
+ * Display name (e.g., "GVRET 10:30am", "Serial dump")
+ */
+name: string,
+/**
+ * Number of items (frames or bytes depending on type)
+ */
+count: number,
+/**
+ * Timestamp of first item (microseconds)
+ */
+start_time_us: number | null,
+/**
+ * Timestamp of last item (microseconds)
+ */
+end_time_us: number | null,
+/**
+ * When the capture was created (Unix timestamp in seconds)
+ */
+created_at: number,
+/**
+ * Whether this capture is actively receiving data (is the streaming target)
+ */
+is_streaming: boolean,
+/**
+ * Session ID that owns this capture (None = orphaned, available for standalone use)
+ * Captures with an owning session are only accessible through that session.
+ * When a session is destroyed, the capture is orphaned (owning_session_id = None).
+ */
+owning_session_id: string | null,
+/**
+ * Whether this capture survives app restart when 'clear captures on start' is enabled.
+ */
+persistent: boolean,
+/**
+ * Distinct bus numbers seen in this capture's data (sorted).
+ * Enables bus mapping/wiring when a capture is used as a source.
+ */
+buses: Array<number>,
+/**
+ * Timestamp source and host clock sync when the capture was recorded
+ * (None for imports and captures recorded before this was tracked).
+ */
+timing: CaptureTiming | null,
+/**
+ * Content hash (and optional signature) sealed at finalisation. Cleared
+ * when the capture's contents change.
+ */
+integrity: CaptureIntegrity | null,
+/**
+ * Payload bytes the capture holds in the capture database (compressed
+ * cold chunks plus inline payloads); kept current on append. 0 for byte
+ * captures.
+ */
+stored_bytes: number, };
+
+/**
+ * Timing provenance recorded on capture metadata.
+ */
+export type CaptureTiming = { timestamp_source: TimestampSource, clock_sync: ClockSyncStatus | null, };
+
+export type TimestampSource = "device" | "host" | "monotonic";
+
+/**
+ * Host clock discipline as last measured.
+ */
+export type ClockSyncStatus = {
+/**
+ * "ptp" | "chrony" | "timedatectl" | "sntp" | "w32tm" | "unavailable"
+ */
+method: string,
+/**
+ * None when the method can't tell
+ */
+synchronized: boolean | null,
+/**
+ * Host clock offset from the reference (µs), when measured
+ */
+offset_us: number | null,
+/**
+ * Reference server / grandmaster, when reported
+ */
+source: string | null,
+/**
+ * "good" | "fair" | "poor" | "unknown"
+ */
+quality: string, checked_at_us: number, };
+
+/**
+ * Content seal stored on a capture's metadata.
+ */
+export type CaptureIntegrity = {
+/**
+ * "sha256"
+ */
+algorithm: string,
+/**
+ * Hex digest of the capture contents
+ */
+digest: string,
+/**
+ * Frames/bytes covered by the digest
+ */
+count: number,
+/**
+ * When the seal was computed (Unix seconds)
+ */
+sealed_at: number, signature: IntegritySignature | null, };
+
+/**
+ * Ed25519 signature over a seal or manifest.
+ */
+export type IntegritySignature = {
+/**
+ * "ed25519"
+ */
+algorithm: string,
+/**
+ * Hex public key of the signer
+ */
+public_key: string,
+/**
+ * Hex signature
+ */
+signature: string, };
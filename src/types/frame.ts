@@ -34,4 +34,42 @@ export type FrameMessage = {
   incomplete?: boolean;
   /** Direction: "rx" for received, "tx" for transmitted */
   direction?: "rx" | "tx";
+  /** Relationship to other frames (reassembly, UDS request/response, transmit echo) */
+  link?: FrameLink;
+  /** Clock the timestamp came from: device hardware clock or host receive time */
+  clock?: "host" | "hardware";
+  /** CAN error frame: `frame_id` carries CAN_ERR_FLAG and the error class bits */
+  is_error?: boolean;
+  /** Main class of an error frame */
+  error_class?: CanErrorClass;
+  /** CAN FD bit rate switch: the data phase ran at the data bitrate */
+  brs?: boolean;
+};
+
+/** Main class of a CAN error frame (most severe condition reported) */
+export type CanErrorClass =
+  | "bus_off"
+  | "ack"
+  | "stuff"
+  | "form"
+  | "bit"
+  | "crc"
+  | "error_passive"
+  | "error_warning"
+  | "overflow"
+  | "arbitration_lost"
+  | "tx_timeout"
+  | "transceiver"
+  | "restarted"
+  | "other";
+
+/**
+ * Linkage metadata. Frames sharing a `correlation_id` belong together;
+ * `parent_rowid` (from the capture pagination APIs) is the rowid of the
+ * group's first frame in the same capture, absent on that frame itself.
+ */
+export type FrameLink = {
+  correlation_id: number;
+  role: "segment" | "message" | "request" | "response" | "echo";
+  parent_rowid?: number;
 };
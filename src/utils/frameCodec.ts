@@ -0,0 +1,97 @@
+// Decoder for binary frame pages (CODEC_FRAMES in src-tauri/src/ipc_compression.rs).
+// Reads the fixed little-endian layout with a DataView, so large pages skip
+// JSON on both sides. The result matches the JSON response field for field,
+// including leaving absent optional fields undefined.
+
+import type { FrameLink, FrameMessage } from "../types/frame";
+
+const CODEC_FRAMES = 2;
+const FRAME_PAGE_VERSION = 1;
+
+const FLAG_EXTENDED = 1 << 0;
+const FLAG_FD = 1 << 1;
+const FLAG_INCOMPLETE = 1 << 2;
+const FLAG_SOURCE_ADDRESS = 1 << 3;
+const FLAG_RX = 1 << 4;
+const FLAG_TX = 1 << 5;
+const FLAG_LINK = 1 << 6;
+const FLAG_PARENT = 1 << 7;
+
+const LINK_ROLES: FrameLink["role"][] = ["segment", "message", "request", "response", "echo"];
+
+export interface FramePage {
+  frames: FrameMessage[];
+  total_count: number;
+  offset: number;
+  limit: number;
+  capture_indices: number[];
+}
+
+/** Whether a packed IPC body holds a binary frame page. */
+export function isFramePage(body: ArrayBuffer | Uint8Array): boolean {
+  const bytes = body instanceof Uint8Array ? body : new Uint8Array(body);
+  return bytes.length > 0 && bytes[0] === CODEC_FRAMES;
+}
+
+/** Decode a binary frame page. */
+export function decodeFramePage(body: ArrayBuffer | Uint8Array): FramePage {
+  const bytes = body instanceof Uint8Array ? body : new Uint8Array(body);
+  const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+  if (bytes[0] !== CODEC_FRAMES) throw new Error(`Not a frame page (codec ${bytes[0]})`);
+  if (bytes[1] !== FRAME_PAGE_VERSION) throw new Error(`Unsupported frame page version ${bytes[1]}`);
+
+  const total_count = view.getUint32(2, true);
+  const offset = view.getUint32(6, true);
+  const limit = view.getUint32(10, true);
+  const count = view.getUint32(14, true);
+
+  const decoder = new TextDecoder();
+  const protocols: string[] = [];
+  let p = 19;
+  for (let i = 0; i < bytes[18]; i++) {
+    const len = bytes[p];
+    protocols.push(decoder.decode(bytes.subarray(p + 1, p + 1 + len)));
+    p += 1 + len;
+  }
+
+  const frames: FrameMessage[] = new Array(count);
+  const capture_indices: number[] = new Array(count);
+  for (let i = 0; i < count; i++) {
+    if (p + 28 > bytes.length) throw new Error("Truncated frame page");
+    const flags = bytes[p + 22];
+    const payloadLength = view.getUint16(p + 26, true);
+    const frame: FrameMessage = {
+      protocol: protocols[bytes[p + 23]] ?? "can",
+      timestamp_us: Number(view.getBigUint64(p, true)),
+      frame_id: view.getUint32(p + 16, true),
+      bus: bytes[p + 20],
+      dlc: bytes[p + 21],
+      bytes: [],
+      is_extended: (flags & FLAG_EXTENDED) !== 0,
+      is_fd: (flags & FLAG_FD) !== 0,
+    };
+    capture_indices[i] = Number(view.getBigUint64(p + 8, true));
+    if (flags & FLAG_SOURCE_ADDRESS) frame.source_address = view.getUint16(p + 24, true);
+    if (flags & FLAG_INCOMPLETE) frame.incomplete = true;
+    if (flags & FLAG_RX) frame.direction = "rx";
+    if (flags & FLAG_TX) frame.direction = "tx";
+    p += 28;
+
+    if (flags & FLAG_LINK) {
+      const link: FrameLink = {
+        correlation_id: Number(view.getBigUint64(p, true)),
+        role: LINK_ROLES[bytes[p + 8]] ?? "segment",
+      };
+      if (flags & FLAG_PARENT) link.parent_rowid = Number(view.getBigInt64(p + 9, true));
+      frame.link = link;
+      p += 17;
+    }
+
+    if (p + payloadLength > bytes.length) throw new Error("Truncated frame page");
+    frame.bytes = Array.from(bytes.subarray(p, p + payloadLength));
+    p += payloadLength;
+    frames[i] = frame;
+  }
+
+  return { frames, total_count, offset, limit, capture_indices };
+}
@@ -0,0 +1,34 @@
+// ui/src/utils/frameProtocol.ts
+//
+// Protocol classification for streamed frames, mirroring Protocol::from_tag in
+// src-tauri/src/io/mod.rs so listeners on a mixed session (e.g. CAN plus
+// Modbus) can subscribe to just the frames they understand.
+
+import type { Protocol } from "../api/io";
+import type { FrameMessage } from "../types/frame";
+
+/** Protocol family of a frame, from its wire tag and FD flag. */
+export function frameProtocol(frame: Pick<FrameMessage, "protocol" | "is_fd">): Protocol {
+  switch (frame.protocol) {
+    case "canfd":
+      return "canfd";
+    case "can":
+      return frame.is_fd ? "canfd" : "can";
+    case "modbus":
+      return "modbus";
+    default:
+      return "serial";
+  }
+}
+
+/**
+ * Keep only frames whose protocol is in `protocols`. An undefined or empty
+ * list means "everything" and returns the input unchanged.
+ */
+export function filterFramesByProtocol<T extends Pick<FrameMessage, "protocol" | "is_fd">>(
+  frames: T[],
+  protocols?: readonly Protocol[]
+): T[] {
+  if (!protocols || protocols.length === 0) return frames;
+  return frames.filter((f) => protocols.includes(frameProtocol(f)));
+}
@@ -22,10 +22,34 @@ export function getIOKindLabel(kind: string | undefined): string {
       return "Serial";
     case "slcan":
       return "slcan";
+    case "custom_serial":
+      return "Custom serial";
+    case "elm327":
+      return "ELM327";
+    case "lin":
+      return "LIN";
+    case "canserver":
+      return "CANserver";
+    case "websocket":
+      return "WebSocket";
+    case "udp_can":
+      return "UDP listener";
+    case "doip":
+      return "DoIP gateway";
+    case "wiretap_net":
+      return "WireTAP network";
     case "socketcan":
       return "SocketCAN";
     case "gs_usb":
       return "gs_usb";
+    case "pcan":
+      return "PCAN-USB";
+    case "kvaser":
+      return "Kvaser";
+    case "vector_xl":
+      return "Vector XL";
+    case "j2534":
+      return "J2534";
     case "modbus_tcp":
       return "Modbus TCP";
     case "virtual":
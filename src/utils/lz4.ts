@@ -0,0 +1,87 @@
+// ui/src/utils/lz4.ts
+//
+// Decoder for packed IPC bodies (src-tauri/src/ipc_compression.rs): a codec
+// tag byte, then either raw JSON or a u32 LE length followed by one LZ4 block.
+
+const CODEC_NONE = 0;
+const CODEC_LZ4 = 1;
+
+/**
+ * Decompress a single LZ4 block into a buffer of `outputLength` bytes.
+ * Throws on a malformed block.
+ */
+export function lz4DecompressBlock(src: Uint8Array, outputLength: number): Uint8Array {
+  const out = new Uint8Array(outputLength);
+  let ip = 0;
+  let op = 0;
+
+  while (ip < src.length) {
+    const token = src[ip++];
+
+    // Literals
+    let literalLength = token >>> 4;
+    if (literalLength === 15) {
+      let b;
+      do {
+        b = src[ip++];
+        literalLength += b;
+      } while (b === 255);
+    }
+    if (ip + literalLength > src.length || op + literalLength > outputLength) {
+      throw new Error("Malformed LZ4 block: literal run overflows");
+    }
+    out.set(src.subarray(ip, ip + literalLength), op);
+    ip += literalLength;
+    op += literalLength;
+
+    // The last sequence carries literals only
+    if (ip >= src.length) break;
+
+    // Match
+    const offset = src[ip] | (src[ip + 1] << 8);
+    ip += 2;
+    if (offset === 0 || offset > op) {
+      throw new Error("Malformed LZ4 block: bad match offset");
+    }
+    let matchLength = (token & 0x0f) + 4;
+    if ((token & 0x0f) === 15) {
+      let b;
+      do {
+        b = src[ip++];
+        matchLength += b;
+      } while (b === 255);
+    }
+    if (op + matchLength > outputLength) {
+      throw new Error("Malformed LZ4 block: match overflows");
+    }
+    // Byte-wise: matches may overlap their own output
+    let mp = op - offset;
+    for (let i = 0; i < matchLength; i++) out[op++] = out[mp++];
+  }
+
+  if (op !== outputLength) {
+    throw new Error(`Malformed LZ4 block: expected ${outputLength} bytes, got ${op}`);
+  }
+  return out;
+}
+
+/** Unpack a packed IPC body to its JSON bytes. */
+export function unpackIpcBody(body: ArrayBuffer | Uint8Array): Uint8Array {
+  const bytes = body instanceof Uint8Array ? body : new Uint8Array(body);
+  if (bytes.length === 0) throw new Error("Empty response body");
+  switch (bytes[0]) {
+    case CODEC_NONE:
+      return bytes.subarray(1);
+    case CODEC_LZ4: {
+      const view = new DataView(bytes.buffer, bytes.byteOffset + 1, 4);
+      return lz4DecompressBlock(bytes.subarray(5), view.getUint32(0, true));
+    }
+    default:
+      throw new Error(`Unknown response codec ${bytes[0]}`);
+  }
+}
+
+/** Unpack and parse a packed IPC JSON body. */
+export function unpackIpcJson<T>(body: ArrayBuffer | Uint8Array): T {
+  return JSON.parse(new TextDecoder().decode(unpackIpcBody(body))) as T;
+}
@@ -85,6 +85,70 @@ const PROFILE_TRAIT_REGISTRY: Record<ProfileKind, ProfileTraits> = {
     multiSource: true,
     hasDeviceBuses: false,
   },
+  canserver: {
+    temporalMode: "realtime",
+    protocols: ["can"],
+    canTransmit: false, // The panda UDP stream is receive only
+    platforms: ["windows", "macos", "linux", "ios"],
+    multiSource: true,
+    hasDeviceBuses: true,
+  },
+  websocket: {
+    temporalMode: "realtime",
+    protocols: ["can", "canfd"],
+    canTransmit: false, // Frames only flow from the server
+    platforms: ["windows", "macos", "linux", "ios"],
+    multiSource: true,
+    hasDeviceBuses: true,
+  },
+  udp_can: {
+    temporalMode: "realtime",
+    protocols: ["can", "canfd"],
+    canTransmit: false, // The bridge only sends to us
+    platforms: ["windows", "macos", "linux", "ios"],
+    multiSource: true,
+    hasDeviceBuses: true,
+  },
+  doip: {
+    temporalMode: "realtime",
+    protocols: ["serial"],
+    canTransmit: false, // UDS requests go through ioTransmitDiagnostic
+    platforms: ["windows", "macos", "linux", "ios"],
+    multiSource: true,
+    hasDeviceBuses: false,
+  },
+  wiretap_net: {
+    temporalMode: "realtime",
+    protocols: ["can", "canfd"],
+    canTransmit: false, // The bridge stream is one-way
+    platforms: ["windows", "macos", "linux", "ios"],
+    multiSource: true,
+    hasDeviceBuses: false,
+  },
+  elm327: {
+    temporalMode: "realtime",
+    protocols: ["can"],
+    canTransmit: false, // Poll requests are sent by the reader itself
+    platforms: ["windows", "macos", "linux"], // No iOS (requires serial port)
+    multiSource: true,
+    hasDeviceBuses: false,
+  },
+  lin: {
+    temporalMode: "realtime",
+    protocols: ["serial"],
+    canTransmit: false, // Master-mode profiles enable transmit on their bus mapping
+    platforms: ["windows", "macos", "linux"], // No iOS (requires serial port)
+    multiSource: true,
+    hasDeviceBuses: false,
+  },
+  custom_serial: {
+    temporalMode: "realtime",
+    protocols: ["can"],
+    canTransmit: true,
+    platforms: ["windows", "macos", "linux"], // No iOS (requires serial port)
+    multiSource: true,
+    hasDeviceBuses: false,
+  },
   gs_usb: {
     temporalMode: "realtime",
     protocols: ["can"],
@@ -93,6 +157,38 @@ const PROFILE_TRAIT_REGISTRY: Record<ProfileKind, ProfileTraits> = {
     multiSource: true,
     hasDeviceBuses: false,
   },
+  pcan: {
+    temporalMode: "realtime",
+    protocols: ["can"],
+    canTransmit: true,
+    platforms: ["windows", "macos"], // Linux uses the peak_usb SocketCAN driver, no iOS
+    multiSource: true,
+    hasDeviceBuses: false,
+  },
+  kvaser: {
+    temporalMode: "realtime",
+    protocols: ["can"],
+    canTransmit: true,
+    platforms: ["windows", "linux"], // CANlib isn't available on macOS
+    multiSource: true,
+    hasDeviceBuses: true,
+  },
+  vector_xl: {
+    temporalMode: "realtime",
+    protocols: ["can", "canfd"],
+    canTransmit: true,
+    platforms: ["windows"], // XL Driver Library is Windows-only
+    multiSource: true,
+    hasDeviceBuses: true,
+  },
+  j2534: {
+    temporalMode: "realtime",
+    protocols: ["can"],
+    canTransmit: true,
+    platforms: ["windows"], // PassThru drivers are Windows-only
+    multiSource: true,
+    hasDeviceBuses: false,
+  },
   socketcan: {
     temporalMode: "realtime",
     protocols: ["can"],
@@ -334,7 +430,9 @@ export function isMultiBusProfile(profile: IOProfile): boolean {
       const interfaces = profile.connection?.interfaces;
       return Array.isArray(interfaces) && interfaces.length > 1;
     }
-    case "virtual": {
+    case "virtual":
+    case "kvaser":
+    case "vector_xl": {
       const interfaces = profile.connection?.interfaces;
       return Array.isArray(interfaces) && interfaces.length > 1;
     }
@@ -461,6 +559,45 @@ export function buildDefaultBusMappings(profile: IOProfile): BusMapping[] {
     });
   }
 
+  // Kvaser — one classic CAN mapping per enabled channel on the card
+  if (profile.kind === "kvaser" && Array.isArray(profile.connection?.interfaces)) {
+    return profile.connection.interfaces
+      .filter((iface) => iface.enabled)
+      .map((iface, idx) => ({
+        deviceBus: iface.device_bus,
+        enabled: true,
+        outputBus: idx,
+        interfaceId: `can${iface.device_bus}`,
+        traits: {
+          temporal_mode: "realtime" as TemporalMode,
+          protocols: ["can"] as Protocol[],
+          tx_frames: profile.connection?.listen_only === false,
+          tx_bytes: false,
+          multi_source: true,
+        },
+      }));
+  }
+
+  // Vector — one mapping per enabled channel; FD when the port runs in FD mode
+  if (profile.kind === "vector_xl" && Array.isArray(profile.connection?.interfaces)) {
+    const isFd = profile.connection?.enable_fd === true;
+    return profile.connection.interfaces
+      .filter((iface) => iface.enabled)
+      .map((iface, idx) => ({
+        deviceBus: iface.device_bus,
+        enabled: true,
+        outputBus: idx,
+        interfaceId: `can${iface.device_bus}`,
+        traits: {
+          temporal_mode: "realtime" as TemporalMode,
+          protocols: (isFd ? ["can", "canfd"] : ["can"]) as Protocol[],
+          tx_frames: profile.connection?.listen_only === false,
+          tx_bytes: false,
+          multi_source: true,
+        },
+      }));
+  }
+
   const traits = getProfileTraits(profile);
   const protocol = traits?.protocols[0] ?? "can";
   // Legacy single-interface FrameLink fallback